@@ -0,0 +1,35 @@
+-- reaction-context-table
+
+BEGIN;
+
+CREATE TABLE IF NOT EXISTS reaction_context
+(
+    uuid               UUID PRIMARY KEY,
+    person_uuid        UUID        NOT NULL,
+    system_prompt      TEXT        NOT NULL,
+    user_prompt        TEXT        NOT NULL,
+    assistant_response TEXT        NOT NULL,
+    is_good            BOOLEAN     NOT NULL DEFAULT FALSE,
+    created_at         TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+DO
+$$
+    BEGIN
+        IF NOT EXISTS (SELECT 1
+                       FROM pg_constraint
+                       WHERE conname = 'reaction_context_fk_person') THEN
+            ALTER TABLE reaction_context
+                ADD CONSTRAINT reaction_context_fk_person
+                    FOREIGN KEY (person_uuid)
+                        REFERENCES person (uuid)
+                        ON DELETE CASCADE;
+        END IF;
+    END
+$$;
+
+CREATE INDEX IF NOT EXISTS idx_reaction_context_person ON reaction_context (person_uuid);
+CREATE INDEX IF NOT EXISTS idx_reaction_context_created_at ON reaction_context (created_at);
+CREATE INDEX IF NOT EXISTS idx_reaction_context_is_good ON reaction_context (is_good);
+
+COMMIT;
@@ -0,0 +1,34 @@
+-- fine-tuned-model-table
+
+BEGIN;
+
+CREATE TABLE IF NOT EXISTS fine_tuned_model
+(
+    uuid        UUID PRIMARY KEY,
+    job_id      TEXT        NOT NULL UNIQUE,
+    base_model  TEXT        NOT NULL,
+    status      TEXT        NOT NULL,
+    model_id    TEXT,
+    person_uuid UUID,
+    created_at  TIMESTAMPTZ NOT NULL DEFAULT now(),
+    updated_at  TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+DO
+$$
+    BEGIN
+        IF NOT EXISTS (SELECT 1
+                       FROM pg_constraint
+                       WHERE conname = 'fine_tuned_model_fk_person') THEN
+            ALTER TABLE fine_tuned_model
+                ADD CONSTRAINT fine_tuned_model_fk_person
+                    FOREIGN KEY (person_uuid)
+                        REFERENCES person (uuid)
+                        ON DELETE CASCADE;
+        END IF;
+    END
+$$;
+
+CREATE INDEX IF NOT EXISTS idx_fine_tuned_model_person ON fine_tuned_model (person_uuid);
+
+COMMIT;
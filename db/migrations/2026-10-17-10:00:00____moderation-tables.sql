@@ -0,0 +1,41 @@
+-- moderation-tables
+
+BEGIN;
+
+CREATE TABLE IF NOT EXISTS moderation_setting
+(
+    id        BOOLEAN PRIMARY KEY DEFAULT TRUE,
+    threshold DOUBLE PRECISION NOT NULL DEFAULT 0.8 CHECK (threshold >= 0 AND threshold <= 1)
+);
+
+INSERT INTO moderation_setting (id, threshold)
+VALUES (TRUE, 0.8)
+ON CONFLICT (id) DO NOTHING;
+
+CREATE TABLE IF NOT EXISTS blocked_content
+(
+    uuid               UUID PRIMARY KEY,
+    sender_person_uuid UUID,
+    content            TEXT        NOT NULL,
+    categories         TEXT[]      NOT NULL,
+    created_at         TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+DO
+$$
+    BEGIN
+        IF NOT EXISTS (SELECT 1
+                       FROM pg_constraint
+                       WHERE conname = 'blocked_content_fk_person') THEN
+            ALTER TABLE blocked_content
+                ADD CONSTRAINT blocked_content_fk_person
+                    FOREIGN KEY (sender_person_uuid)
+                        REFERENCES person (uuid)
+                        ON DELETE CASCADE;
+        END IF;
+    END
+$$;
+
+CREATE INDEX IF NOT EXISTS idx_blocked_content_created_at ON blocked_content (created_at);
+
+COMMIT;
@@ -0,0 +1,16 @@
+-- content-scrub-setting
+
+BEGIN;
+
+CREATE TABLE IF NOT EXISTS content_scrub_setting
+(
+    id                  BOOLEAN PRIMARY KEY DEFAULT TRUE,
+    scrub_personal_data BOOLEAN NOT NULL DEFAULT TRUE,
+    scrub_profanity     BOOLEAN NOT NULL DEFAULT FALSE
+);
+
+INSERT INTO content_scrub_setting (id, scrub_personal_data, scrub_profanity)
+VALUES (TRUE, TRUE, FALSE)
+ON CONFLICT (id) DO NOTHING;
+
+COMMIT;
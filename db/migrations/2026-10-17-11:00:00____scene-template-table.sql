@@ -0,0 +1,16 @@
+-- scene-template-table
+
+BEGIN;
+
+CREATE TABLE IF NOT EXISTS scene_template
+(
+    uuid            UUID PRIMARY KEY,
+    name            TEXT        NOT NULL UNIQUE,
+    name_pattern    TEXT        NOT NULL,
+    description     TEXT        NOT NULL,
+    starting_cast   TEXT[]      NOT NULL DEFAULT '{}',
+    opening_message TEXT        NOT NULL DEFAULT '',
+    created_at      TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+COMMIT;
@@ -0,0 +1,42 @@
+-- person-relationship-table
+
+BEGIN;
+
+CREATE TABLE IF NOT EXISTS person_relationship
+(
+    uuid              UUID PRIMARY KEY,
+    person_uuid       UUID        NOT NULL,
+    other_person_uuid UUID        NOT NULL,
+    description       TEXT        NOT NULL,
+    created_at        TIMESTAMPTZ NOT NULL DEFAULT now(),
+    UNIQUE (person_uuid, other_person_uuid)
+);
+
+DO
+$$
+    BEGIN
+        IF NOT EXISTS (SELECT 1
+                       FROM pg_constraint
+                       WHERE conname = 'person_relationship_fk_person') THEN
+            ALTER TABLE person_relationship
+                ADD CONSTRAINT person_relationship_fk_person
+                    FOREIGN KEY (person_uuid)
+                        REFERENCES person (uuid)
+                        ON DELETE CASCADE;
+        END IF;
+
+        IF NOT EXISTS (SELECT 1
+                       FROM pg_constraint
+                       WHERE conname = 'person_relationship_fk_other_person') THEN
+            ALTER TABLE person_relationship
+                ADD CONSTRAINT person_relationship_fk_other_person
+                    FOREIGN KEY (other_person_uuid)
+                        REFERENCES person (uuid)
+                        ON DELETE CASCADE;
+        END IF;
+    END
+$$;
+
+CREATE INDEX IF NOT EXISTS idx_person_relationship_other_person ON person_relationship (other_person_uuid);
+
+COMMIT;
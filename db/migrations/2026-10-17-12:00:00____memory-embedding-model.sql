@@ -0,0 +1,22 @@
+-- memory-embedding-model
+
+BEGIN;
+
+-- Every existing memory was embedded with text-embedding-3-small.
+ALTER TABLE memory
+    ADD COLUMN IF NOT EXISTS embedding_model TEXT NOT NULL DEFAULT 'text-embedding-3-small',
+    ADD COLUMN IF NOT EXISTS embedding_dimension INT NOT NULL DEFAULT 1536;
+
+ALTER TABLE memory
+    ALTER COLUMN embedding_model DROP DEFAULT,
+    ALTER COLUMN embedding_dimension DROP DEFAULT;
+
+-- Let memories embedded by a different model be stored alongside the old ones.
+-- Searches only compare vectors with a matching model and dimension.
+ALTER TABLE memory
+    ALTER COLUMN embedding TYPE vector;
+
+CREATE INDEX IF NOT EXISTS idx_memory_person_embedding_model
+    ON memory (person_uuid, embedding_model, embedding_dimension);
+
+COMMIT;
@@ -0,0 +1,55 @@
+-- message-expected-reply-table
+
+BEGIN;
+
+CREATE TABLE IF NOT EXISTS message_expected_reply
+(
+    message_uuid          UUID PRIMARY KEY,
+    asker_person_uuid     UUID        NOT NULL,
+    recipient_person_uuid UUID        NOT NULL,
+    reply_window_ms       BIGINT      NOT NULL,
+    -- 'replied' or 'ignored' once the reply window has been checked
+    outcome               TEXT,
+    resolved_at           TIMESTAMPTZ,
+    created_at            TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+DO
+$$
+    BEGIN
+        IF NOT EXISTS (SELECT 1
+                       FROM pg_constraint
+                       WHERE conname = 'message_expected_reply_fk_message') THEN
+            ALTER TABLE message_expected_reply
+                ADD CONSTRAINT message_expected_reply_fk_message
+                    FOREIGN KEY (message_uuid)
+                        REFERENCES message (uuid)
+                        ON DELETE CASCADE;
+        END IF;
+
+        IF NOT EXISTS (SELECT 1
+                       FROM pg_constraint
+                       WHERE conname = 'message_expected_reply_fk_asker') THEN
+            ALTER TABLE message_expected_reply
+                ADD CONSTRAINT message_expected_reply_fk_asker
+                    FOREIGN KEY (asker_person_uuid)
+                        REFERENCES person (uuid)
+                        ON DELETE CASCADE;
+        END IF;
+
+        IF NOT EXISTS (SELECT 1
+                       FROM pg_constraint
+                       WHERE conname = 'message_expected_reply_fk_recipient') THEN
+            ALTER TABLE message_expected_reply
+                ADD CONSTRAINT message_expected_reply_fk_recipient
+                    FOREIGN KEY (recipient_person_uuid)
+                        REFERENCES person (uuid)
+                        ON DELETE CASCADE;
+        END IF;
+    END
+$$;
+
+CREATE INDEX IF NOT EXISTS idx_message_expected_reply_recipient
+    ON message_expected_reply (recipient_person_uuid);
+
+COMMIT;
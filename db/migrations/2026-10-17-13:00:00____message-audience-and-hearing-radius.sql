@@ -0,0 +1,19 @@
+-- message-audience-and-hearing-radius
+
+BEGIN;
+
+-- 'everyone', 'whisper', or 'side conversation'
+ALTER TABLE message
+    ADD COLUMN IF NOT EXISTS audience TEXT NOT NULL DEFAULT 'everyone';
+
+-- 'delivered' to the people a message was meant for, or 'overheard' by
+-- everyone else who was close enough to hear it
+ALTER TABLE scene_message_recipient
+    ADD COLUMN IF NOT EXISTS delivery TEXT NOT NULL DEFAULT 'delivered';
+
+-- 'room' when side conversations carry across the whole scene, or 'close'
+-- when only the people addressed can hear them
+ALTER TABLE scene
+    ADD COLUMN IF NOT EXISTS hearing_radius TEXT NOT NULL DEFAULT 'room';
+
+COMMIT;
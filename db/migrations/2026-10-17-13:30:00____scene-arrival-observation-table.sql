@@ -0,0 +1,42 @@
+-- scene-arrival-observation-table
+
+BEGIN;
+
+CREATE TABLE IF NOT EXISTS scene_arrival_observation
+(
+    uuid        UUID PRIMARY KEY,
+    person_uuid UUID        NOT NULL,
+    scene_uuid  UUID        NOT NULL,
+    observation TEXT        NOT NULL,
+    created_at  TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+DO
+$$
+    BEGIN
+        IF NOT EXISTS (SELECT 1
+                       FROM pg_constraint
+                       WHERE conname = 'scene_arrival_observation_fk_person') THEN
+            ALTER TABLE scene_arrival_observation
+                ADD CONSTRAINT scene_arrival_observation_fk_person
+                    FOREIGN KEY (person_uuid)
+                        REFERENCES person (uuid)
+                        ON DELETE CASCADE;
+        END IF;
+
+        IF NOT EXISTS (SELECT 1
+                       FROM pg_constraint
+                       WHERE conname = 'scene_arrival_observation_fk_scene') THEN
+            ALTER TABLE scene_arrival_observation
+                ADD CONSTRAINT scene_arrival_observation_fk_scene
+                    FOREIGN KEY (scene_uuid)
+                        REFERENCES scene (uuid)
+                        ON DELETE CASCADE;
+        END IF;
+    END
+$$;
+
+CREATE INDEX IF NOT EXISTS idx_scene_arrival_observation_person_scene
+    ON scene_arrival_observation (person_uuid, scene_uuid, created_at);
+
+COMMIT;
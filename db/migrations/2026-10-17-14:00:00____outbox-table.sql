@@ -0,0 +1,23 @@
+-- outbox-table
+
+BEGIN;
+
+CREATE TABLE IF NOT EXISTS outbox
+(
+    uuid            UUID PRIMARY KEY,
+    event_name      TEXT        NOT NULL,
+    payload         JSONB       NOT NULL,
+    attempts        INT         NOT NULL DEFAULT 0,
+    last_error      TEXT,
+    -- Claimed entries are pushed into the future so two dispatchers never
+    -- deliver the same entry at once
+    next_attempt_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    delivered_at    TIMESTAMPTZ,
+    created_at      TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+CREATE INDEX IF NOT EXISTS idx_outbox_undelivered
+    ON outbox (next_attempt_at)
+    WHERE delivered_at IS NULL;
+
+COMMIT;
@@ -0,0 +1,35 @@
+-- job-lock-key
+
+BEGIN;
+
+-- Jobs that share a lock key never run at the same time. Person jobs use
+-- 'person:<uuid>', so each person reacts to one thing at a time while
+-- different people still run in parallel.
+ALTER TABLE job
+    ADD COLUMN IF NOT EXISTS lock_key TEXT;
+
+UPDATE job
+SET lock_key = 'person:' || COALESCE(
+        data ->> 'recipient_person_uuid',
+        data ->> 'gazing_person_uuid',
+        data ->> 'asker_person_uuid',
+        data ->> 'person_uuid'
+    )
+WHERE lock_key IS NULL
+  AND finished_at IS NULL
+  AND deleted_at IS NULL
+  AND COALESCE(
+        data ->> 'recipient_person_uuid',
+        data ->> 'gazing_person_uuid',
+        data ->> 'asker_person_uuid',
+        data ->> 'person_uuid'
+    ) IS NOT NULL;
+
+CREATE INDEX IF NOT EXISTS idx_job_running_lock_key
+    ON job (lock_key)
+    WHERE started_at IS NOT NULL
+      AND finished_at IS NULL
+      AND error IS NULL
+      AND deleted_at IS NULL;
+
+COMMIT;
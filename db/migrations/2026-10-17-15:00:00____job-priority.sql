@@ -0,0 +1,10 @@
+-- job-priority
+
+BEGIN;
+
+-- Higher priority jobs are popped first, so a person answers being spoken to
+-- directly before reacting to background chatter.
+ALTER TABLE job
+    ADD COLUMN IF NOT EXISTS priority INT NOT NULL DEFAULT 0;
+
+COMMIT;
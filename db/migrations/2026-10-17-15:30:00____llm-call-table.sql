@@ -0,0 +1,24 @@
+-- llm-call-table
+
+BEGIN;
+
+-- One row per attempt at a completion, so it is clear which provider served
+-- each call and how often the primary one had to be given up on
+CREATE TABLE IF NOT EXISTS llm_call
+(
+    uuid              UUID PRIMARY KEY,
+    provider          TEXT        NOT NULL,
+    model             TEXT        NOT NULL,
+    attempt           INT         NOT NULL,
+    succeeded         BOOLEAN     NOT NULL,
+    error             TEXT,
+    duration_ms       BIGINT      NOT NULL,
+    prompt_tokens     BIGINT,
+    completion_tokens BIGINT,
+    created_at        TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+CREATE INDEX IF NOT EXISTS idx_llm_call_created_at
+    ON llm_call (created_at);
+
+COMMIT;
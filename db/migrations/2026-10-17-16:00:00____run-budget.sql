@@ -0,0 +1,32 @@
+-- run-budget
+
+BEGIN;
+
+-- The dollar budget for the current run. Spend is counted from set_at, so
+-- setting a new budget starts a new run
+CREATE TABLE IF NOT EXISTS run_budget
+(
+    id         BOOLEAN PRIMARY KEY DEFAULT TRUE,
+    budget_usd DOUBLE PRECISION,
+    set_at     TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+INSERT INTO run_budget (id, budget_usd)
+VALUES (TRUE, NULL)
+ON CONFLICT (id) DO NOTHING;
+
+-- What a million tokens cost per model. Calls to models without a price are
+-- counted separately instead of being guessed at
+CREATE TABLE IF NOT EXISTS llm_price
+(
+    model                  TEXT PRIMARY KEY,
+    input_usd_per_million  DOUBLE PRECISION NOT NULL,
+    output_usd_per_million DOUBLE PRECISION NOT NULL
+);
+
+INSERT INTO llm_price (model, input_usd_per_million, output_usd_per_million)
+VALUES ('gpt-4o-2024-08-06', 2.50, 10.00),
+       ('gpt-5-mini', 0.25, 2.00)
+ON CONFLICT (model) DO NOTHING;
+
+COMMIT;
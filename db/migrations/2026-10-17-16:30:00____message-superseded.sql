@@ -0,0 +1,11 @@
+-- message-superseded
+
+BEGIN;
+
+-- Set when the real world user retracts or edits a message before anyone
+-- reacted to it. An edit points at the message that replaced it
+ALTER TABLE message
+    ADD COLUMN IF NOT EXISTS superseded_at      TIMESTAMPTZ,
+    ADD COLUMN IF NOT EXISTS superseded_by_uuid UUID REFERENCES message (uuid);
+
+COMMIT;
@@ -0,0 +1,10 @@
+-- message-quote
+
+BEGIN;
+
+-- The earlier scene message a comment refers to, as a snapshot of who said
+-- what, so the recipient's reaction prompt can show it
+ALTER TABLE message
+    ADD COLUMN IF NOT EXISTS quote JSONB;
+
+COMMIT;
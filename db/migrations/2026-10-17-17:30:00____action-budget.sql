@@ -0,0 +1,26 @@
+-- action-budget
+
+BEGIN;
+
+-- The world-day on the active clock each reaction happened in, so actions
+-- can be counted per simulated day rather than per wall clock day
+ALTER TABLE reaction_history
+    ADD COLUMN IF NOT EXISTS world_day BIGINT;
+
+CREATE INDEX IF NOT EXISTS reaction_history_person_world_day_idx
+    ON reaction_history (person_uuid, world_day);
+
+-- How many times each person may take an action in one world-day. Actions
+-- without a row are unlimited
+CREATE TABLE IF NOT EXISTS action_budget
+(
+    action_name   TEXT PRIMARY KEY,
+    per_world_day BIGINT NOT NULL
+);
+
+INSERT INTO action_budget (action_name, per_world_day)
+VALUES ('say in scene', 40),
+       ('ask', 12)
+ON CONFLICT (action_name) DO NOTHING;
+
+COMMIT;
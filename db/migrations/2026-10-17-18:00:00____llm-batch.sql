@@ -0,0 +1,16 @@
+-- llm-batch
+
+BEGIN;
+
+-- Batch API batches that were submitted, so they can be followed up on after
+-- the poll job that watches them is gone
+CREATE TABLE IF NOT EXISTS llm_batch
+(
+    batch_id      TEXT PRIMARY KEY,
+    status        TEXT        NOT NULL,
+    request_count INTEGER     NOT NULL,
+    created_at    TIMESTAMPTZ NOT NULL DEFAULT now(),
+    finished_at   TIMESTAMPTZ
+);
+
+COMMIT;
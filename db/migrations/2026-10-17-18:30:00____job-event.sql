@@ -0,0 +1,23 @@
+-- job-event
+
+BEGIN;
+
+-- Everything that happened to a job, in order, for working out where the
+-- time went. The part of `duration_ms` not in `llm_ms` was not spent on OpenAI
+-- requests, which mostly means the database.
+CREATE TABLE IF NOT EXISTS job_event
+(
+    uuid        UUID PRIMARY KEY,
+    job_uuid    UUID        NOT NULL REFERENCES job (uuid),
+    kind        TEXT        NOT NULL,
+    runner      TEXT,
+    error_class TEXT,
+    duration_ms BIGINT,
+    llm_ms      BIGINT,
+    created_at  TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+CREATE INDEX IF NOT EXISTS job_event_job_uuid_created_at_idx
+    ON job_event (job_uuid, created_at);
+
+COMMIT;
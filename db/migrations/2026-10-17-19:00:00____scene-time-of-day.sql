@@ -0,0 +1,15 @@
+-- scene-time-of-day
+
+BEGIN;
+
+-- How far a scene's clock runs ahead of the active clock, so a scene can
+-- start at the time of day its template calls for
+ALTER TABLE scene
+    ADD COLUMN IF NOT EXISTS clock_offset_ms BIGINT NOT NULL DEFAULT 0;
+
+-- Minutes after midnight that scenes made from the template start at. Null
+-- leaves them on the active clock's time of day
+ALTER TABLE scene_template
+    ADD COLUMN IF NOT EXISTS time_of_day_minutes INTEGER;
+
+COMMIT;
@@ -0,0 +1,10 @@
+-- person-chattiness
+
+BEGIN;
+
+-- The chance, from 0 to 1, that a person who has gone quiet in a busy scene
+-- is nudged to react each time the job runner looks for idle persons
+ALTER TABLE person
+    ADD COLUMN IF NOT EXISTS chattiness DOUBLE PRECISION NOT NULL DEFAULT 0.3;
+
+COMMIT;
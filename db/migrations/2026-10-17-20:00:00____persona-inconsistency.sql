@@ -0,0 +1,20 @@
+-- persona-inconsistency
+
+BEGIN;
+
+-- Things a person said that an LLM judge found at odds with their identity,
+-- written by the `check persona consistency` job
+CREATE TABLE IF NOT EXISTS persona_inconsistency
+(
+    uuid           UUID PRIMARY KEY,
+    person_uuid    UUID        NOT NULL REFERENCES person (uuid),
+    utterance      TEXT        NOT NULL,
+    identity_claim TEXT        NOT NULL,
+    explanation    TEXT        NOT NULL,
+    created_at     TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS persona_inconsistency_person_time_idx
+    ON persona_inconsistency (person_uuid, created_at DESC);
+
+COMMIT;
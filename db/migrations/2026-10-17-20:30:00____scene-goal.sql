@@ -0,0 +1,19 @@
+-- scene-goal
+
+BEGIN;
+
+-- What ends a scene. The `check scene goals` job watches every goal that has
+-- not been met, and closes the scene once any of its conditions is met.
+CREATE TABLE IF NOT EXISTS scene_goal
+(
+    scene_uuid         UUID PRIMARY KEY REFERENCES scene (uuid),
+    -- The active clock time the scene has to wrap up by
+    deadline_active_ms BIGINT,
+    closing_phrase     TEXT,
+    consensus_on       TEXT,
+    set_at             TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    met_at             TIMESTAMPTZ,
+    met_reason         TEXT
+);
+
+COMMIT;
@@ -0,0 +1,11 @@
+-- scene-archive
+
+BEGIN;
+
+-- Set by the `archive scene` job once an ended scene's leftover jobs are
+-- cancelled and its transcript is written to disk
+ALTER TABLE scene
+    ADD COLUMN IF NOT EXISTS archived_at     TIMESTAMPTZ,
+    ADD COLUMN IF NOT EXISTS transcript_path TEXT;
+
+COMMIT;
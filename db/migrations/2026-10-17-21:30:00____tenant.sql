@@ -0,0 +1,35 @@
+-- tenant
+
+BEGIN;
+
+-- Someone whose world is hosted on this server. Api tokens are bound to a
+-- tenant, and the api only serves scenes that belong to it.
+CREATE TABLE IF NOT EXISTS tenant
+(
+    uuid       UUID PRIMARY KEY,
+    name       TEXT        NOT NULL UNIQUE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Only the sha256 of a token is kept, the token itself is shown once when
+-- it is issued
+CREATE TABLE IF NOT EXISTS api_token
+(
+    uuid        UUID PRIMARY KEY,
+    tenant_uuid UUID        NOT NULL REFERENCES tenant (uuid),
+    token_hash  TEXT        NOT NULL UNIQUE,
+    created_at  TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    revoked_at  TIMESTAMPTZ
+);
+
+-- Scenes without a tenant belong to whoever runs the server and are not
+-- served by the api. Scenes are the only thing a tenant owns: persons walk
+-- between scenes, so they, their memories, the messages they send and the
+-- jobs that run them stay shared by the world, and the api only reaches
+-- them through a tenant's scenes.
+ALTER TABLE scene
+    ADD COLUMN IF NOT EXISTS tenant_uuid UUID REFERENCES tenant (uuid);
+
+CREATE INDEX IF NOT EXISTS scene_tenant_uuid_idx ON scene (tenant_uuid);
+
+COMMIT;
@@ -0,0 +1,11 @@
+-- api-token-role
+
+BEGIN;
+
+-- What a token may do: viewers read, directors also steer scenes, admins
+-- also manage persons and the database
+ALTER TABLE api_token
+    ADD COLUMN IF NOT EXISTS role TEXT NOT NULL DEFAULT 'viewer'
+        CHECK (role IN ('viewer', 'director', 'admin'));
+
+COMMIT;
@@ -0,0 +1,19 @@
+-- delivery
+
+BEGIN;
+
+-- One row per attempt to hand an outbox entry to an integration
+CREATE TABLE IF NOT EXISTS delivery
+(
+    uuid        UUID PRIMARY KEY,
+    outbox_uuid UUID        NOT NULL REFERENCES outbox (uuid),
+    integration TEXT        NOT NULL,
+    status      TEXT        NOT NULL CHECK (status IN ('sent', 'failed', 'retried')),
+    details     TEXT,
+    created_at  TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+CREATE INDEX IF NOT EXISTS idx_delivery_created_at
+    ON delivery (created_at);
+
+COMMIT;
@@ -0,0 +1,18 @@
+-- guardrail-setting
+
+BEGIN;
+
+-- Each world has its own database, so one row is one world's guardrails
+CREATE TABLE IF NOT EXISTS guardrail_setting
+(
+    id             BOOLEAN PRIMARY KEY DEFAULT TRUE,
+    prompt         TEXT   NOT NULL DEFAULT '',
+    refused_topics TEXT[] NOT NULL DEFAULT '{}',
+    tone           TEXT   NOT NULL DEFAULT ''
+);
+
+INSERT INTO guardrail_setting (id)
+VALUES (TRUE)
+ON CONFLICT (id) DO NOTHING;
+
+COMMIT;
@@ -0,0 +1,24 @@
+-- cron-job
+
+BEGIN;
+
+-- Jobs the job runner enqueues on a schedule. The job is stored the same way
+-- as in the job table, as a job name and its data.
+CREATE TABLE IF NOT EXISTS cron_job
+(
+    uuid        UUID PRIMARY KEY,
+    name        TEXT        NOT NULL,
+    expression  TEXT        NOT NULL,
+    job_name    TEXT        NOT NULL,
+    job_data    JSONB,
+    enabled     BOOLEAN     NOT NULL DEFAULT TRUE,
+    last_run_at TIMESTAMPTZ,
+    next_run_at TIMESTAMPTZ,
+    created_at  TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+CREATE INDEX IF NOT EXISTS idx_cron_job_next_run_at
+    ON cron_job (next_run_at)
+    WHERE enabled;
+
+COMMIT;
@@ -0,0 +1,16 @@
+-- person-tag
+
+BEGIN;
+
+CREATE TABLE IF NOT EXISTS person_tag
+(
+    person_uuid UUID        NOT NULL REFERENCES person (uuid) ON DELETE CASCADE,
+    tag         TEXT        NOT NULL,
+    created_at  TIMESTAMPTZ NOT NULL DEFAULT now(),
+    PRIMARY KEY (person_uuid, tag)
+);
+
+CREATE INDEX IF NOT EXISTS idx_person_tag_tag
+    ON person_tag (tag);
+
+COMMIT;
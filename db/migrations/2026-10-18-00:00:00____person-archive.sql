@@ -0,0 +1,11 @@
+-- person-archive
+
+BEGIN;
+
+-- Archived persons are kept for their history but no longer take part. A
+-- person merged into another points at the person they were merged into.
+ALTER TABLE person
+    ADD COLUMN IF NOT EXISTS archived_at      TIMESTAMPTZ,
+    ADD COLUMN IF NOT EXISTS merged_into_uuid UUID REFERENCES person (uuid) ON DELETE SET NULL;
+
+COMMIT;
@@ -0,0 +1,20 @@
+-- annotation
+
+BEGIN;
+
+CREATE TABLE IF NOT EXISTS annotation
+(
+    uuid              UUID PRIMARY KEY,
+    scene_uuid        UUID        NOT NULL REFERENCES scene (uuid) ON DELETE CASCADE,
+    at                TIMESTAMPTZ NOT NULL,
+    item_summary      TEXT        NOT NULL,
+    note              TEXT        NOT NULL,
+    is_bookmark       BOOLEAN     NOT NULL DEFAULT FALSE,
+    include_in_export BOOLEAN     NOT NULL DEFAULT FALSE,
+    created_at        TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+CREATE INDEX IF NOT EXISTS idx_annotation_scene_uuid_at
+    ON annotation (scene_uuid, at);
+
+COMMIT;
@@ -0,0 +1,24 @@
+-- topic
+
+BEGIN;
+
+CREATE TABLE IF NOT EXISTS topic
+(
+    scene_uuid        UUID        NOT NULL REFERENCES scene (uuid) ON DELETE CASCADE,
+    topic             TEXT        NOT NULL,
+    mentions          INT         NOT NULL,
+    last_mentioned_at TIMESTAMPTZ NOT NULL,
+    PRIMARY KEY (scene_uuid, topic)
+);
+
+CREATE INDEX IF NOT EXISTS idx_topic_topic
+    ON topic (topic);
+
+CREATE INDEX IF NOT EXISTS idx_topic_last_mentioned_at
+    ON topic (last_mentioned_at);
+
+-- The newest message already tagged, so each scan only reads what is new
+ALTER TABLE scene
+    ADD COLUMN IF NOT EXISTS topics_tagged_through TIMESTAMPTZ;
+
+COMMIT;
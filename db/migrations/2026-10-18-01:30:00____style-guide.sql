@@ -0,0 +1,24 @@
+-- style-guide
+
+BEGIN;
+
+-- Each world has its own database, so one row is one world's style guide
+CREATE TABLE IF NOT EXISTS style_guide_setting
+(
+    id            BOOLEAN PRIMARY KEY DEFAULT TRUE,
+    max_sentences INT  NULL,
+    dialect       TEXT NOT NULL DEFAULT '',
+    formality     TEXT NULL
+);
+
+INSERT INTO style_guide_setting (id)
+VALUES (TRUE)
+ON CONFLICT (id) DO NOTHING;
+
+-- A person's own style, where set, wins over the world's
+ALTER TABLE person
+    ADD COLUMN IF NOT EXISTS style_max_sentences INT  NULL,
+    ADD COLUMN IF NOT EXISTS style_dialect       TEXT NOT NULL DEFAULT '',
+    ADD COLUMN IF NOT EXISTS style_formality     TEXT NULL;
+
+COMMIT;
@@ -0,0 +1,25 @@
+-- memory-person-mention
+
+BEGIN;
+
+-- The persons a memory mentions, so memory searches can favor the ones about
+-- whoever is in the scene
+CREATE TABLE IF NOT EXISTS memory_person_mention
+(
+    memory_uuid UUID NOT NULL REFERENCES memory (uuid) ON DELETE CASCADE,
+    person_uuid UUID NOT NULL REFERENCES person (uuid) ON DELETE CASCADE,
+    PRIMARY KEY (memory_uuid, person_uuid)
+);
+
+CREATE INDEX IF NOT EXISTS memory_person_mention_person_uuid_idx
+    ON memory_person_mention (person_uuid);
+
+-- Memories made before this table already list who they mention
+INSERT INTO memory_person_mention (memory_uuid, person_uuid)
+SELECT DISTINCT memory.uuid, mentioned.person_uuid
+FROM memory
+         CROSS JOIN LATERAL UNNEST(memory.people_uuids) AS mentioned(person_uuid)
+         JOIN person ON person.uuid = mentioned.person_uuid
+ON CONFLICT DO NOTHING;
+
+COMMIT;
@@ -0,0 +1,18 @@
+-- memory-retrieval-setting
+
+BEGIN;
+
+-- Each world has its own database, so one row is one world's settings
+CREATE TABLE IF NOT EXISTS memory_retrieval_setting
+(
+    id             BOOLEAN PRIMARY KEY DEFAULT TRUE,
+    top_k          INT              NOT NULL DEFAULT 5,
+    max_distance   DOUBLE PRECISION NOT NULL DEFAULT 0.45,
+    recency_weight DOUBLE PRECISION NOT NULL DEFAULT 0
+);
+
+INSERT INTO memory_retrieval_setting (id)
+VALUES (TRUE)
+ON CONFLICT (id) DO NOTHING;
+
+COMMIT;
@@ -0,0 +1,9 @@
+-- simulation-speed
+
+BEGIN;
+
+ALTER TABLE job_runner_setting
+    ADD COLUMN IF NOT EXISTS time_factor DOUBLE PRECISION NOT NULL DEFAULT 1.0,
+    ADD COLUMN IF NOT EXISTS skip_wait_requested BOOLEAN NOT NULL DEFAULT FALSE;
+
+COMMIT;
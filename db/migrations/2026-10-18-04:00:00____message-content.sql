@@ -0,0 +1,40 @@
+-- message-content
+BEGIN;
+
+CREATE TABLE IF NOT EXISTS message_content (
+    hash       TEXT PRIMARY KEY,
+    body       TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+ALTER TABLE message ADD COLUMN IF NOT EXISTS content_hash TEXT REFERENCES message_content (hash);
+
+-- Back-fill existing messages, sharing rows between identical bodies. Once
+-- message.content is dropped there is nothing left to back-fill from.
+DO $$
+BEGIN
+    IF EXISTS (
+        SELECT 1
+        FROM information_schema.columns
+        WHERE table_name = 'message'
+          AND column_name = 'content'
+    ) THEN
+        INSERT INTO message_content (hash, body)
+        SELECT DISTINCT encode(sha256(convert_to(content, 'UTF8')), 'hex'), content
+        FROM message
+        ON CONFLICT (hash) DO NOTHING;
+
+        UPDATE message
+        SET content_hash = encode(sha256(convert_to(content, 'UTF8')), 'hex')
+        WHERE content_hash IS NULL;
+    END IF;
+END
+$$;
+
+ALTER TABLE message ALTER COLUMN content_hash SET NOT NULL;
+
+ALTER TABLE message DROP COLUMN IF EXISTS content;
+
+CREATE INDEX IF NOT EXISTS idx_message_content_hash ON message (content_hash);
+
+COMMIT;
@@ -0,0 +1,25 @@
+-- item
+
+BEGIN;
+
+-- Objects in the world, like a letter or a set of keys. Each one is either
+-- carried by a person or lying in a scene, never both
+CREATE TABLE IF NOT EXISTS item
+(
+    uuid              UUID PRIMARY KEY,
+    name              TEXT        NOT NULL,
+    description       TEXT        NOT NULL DEFAULT '',
+    owner_person_uuid UUID REFERENCES person (uuid) ON DELETE CASCADE,
+    scene_uuid        UUID REFERENCES scene (uuid) ON DELETE CASCADE,
+    created_at        TIMESTAMPTZ NOT NULL DEFAULT now(),
+    updated_at        TIMESTAMPTZ NOT NULL DEFAULT now(),
+    CHECK ((owner_person_uuid IS NULL) <> (scene_uuid IS NULL))
+);
+
+CREATE INDEX IF NOT EXISTS idx_item_owner_person_uuid
+    ON item (owner_person_uuid);
+
+CREATE INDEX IF NOT EXISTS idx_item_scene_uuid
+    ON item (scene_uuid);
+
+COMMIT;
@@ -0,0 +1,20 @@
+-- scene-ambience
+
+BEGIN;
+
+-- Every change to a scene's weather, noise and lighting. The latest row is
+-- the scene's ambience, and the rest show up in its timeline
+CREATE TABLE IF NOT EXISTS scene_ambience_change
+(
+    uuid       UUID PRIMARY KEY,
+    scene_uuid UUID        NOT NULL REFERENCES scene (uuid) ON DELETE CASCADE,
+    weather    TEXT        NOT NULL,
+    noise      TEXT        NOT NULL,
+    lighting   TEXT        NOT NULL,
+    changed_at TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+CREATE INDEX IF NOT EXISTS idx_scene_ambience_change_scene_uuid_changed_at
+    ON scene_ambience_change (scene_uuid, changed_at);
+
+COMMIT;
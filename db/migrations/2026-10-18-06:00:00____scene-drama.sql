@@ -0,0 +1,22 @@
+-- scene-drama
+
+BEGIN;
+
+-- The chance, from 0 to 1, that something unexpected happens in a scene
+-- each time the job runner looks for scenes to complicate
+ALTER TABLE scene
+    ADD COLUMN IF NOT EXISTS drama_intensity DOUBLE PRECISION NOT NULL DEFAULT 0;
+
+-- Things that happened in a scene that nobody said, like a phone ringing
+CREATE TABLE IF NOT EXISTS scene_event
+(
+    uuid        UUID PRIMARY KEY,
+    scene_uuid  UUID        NOT NULL REFERENCES scene (uuid) ON DELETE CASCADE,
+    description TEXT        NOT NULL,
+    created_at  TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+CREATE INDEX IF NOT EXISTS idx_scene_event_scene_uuid_created_at
+    ON scene_event (scene_uuid, created_at);
+
+COMMIT;
@@ -0,0 +1,19 @@
+-- chat-latency
+
+BEGIN;
+
+-- How long the real world user waited for a reply to each message they sent
+-- in a scene, measured to the first message a person sent back there
+CREATE TABLE IF NOT EXISTS chat_latency
+(
+    message_uuid       UUID PRIMARY KEY REFERENCES message (uuid) ON DELETE CASCADE,
+    reply_message_uuid UUID        NOT NULL REFERENCES message (uuid) ON DELETE CASCADE,
+    scene_uuid         UUID        NOT NULL REFERENCES scene (uuid) ON DELETE CASCADE,
+    latency_ms         BIGINT      NOT NULL,
+    replied_at         TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+CREATE INDEX IF NOT EXISTS idx_chat_latency_replied_at
+    ON chat_latency (replied_at);
+
+COMMIT;
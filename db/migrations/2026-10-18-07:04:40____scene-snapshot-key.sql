@@ -0,0 +1,33 @@
+-- scene-snapshot-key
+
+BEGIN;
+
+-- A scene keeps every snapshot, newest being its description. The original
+-- key on scene_uuid allowed only one, so refreshing or closing a scene
+-- failed. Each snapshot gets its own uuid to be keyed by instead.
+DO $$
+BEGIN
+    IF NOT EXISTS (
+        SELECT 1
+        FROM information_schema.columns
+        WHERE table_name = 'scene_snapshot'
+          AND column_name = 'uuid'
+    ) THEN
+        ALTER TABLE scene_snapshot
+            DROP CONSTRAINT IF EXISTS scene_snapshot_pkey;
+
+        -- The default only fills in snapshots taken before now. New ones get
+        -- their uuid from the app.
+        ALTER TABLE scene_snapshot
+            ADD COLUMN uuid UUID PRIMARY KEY DEFAULT gen_random_uuid();
+
+        ALTER TABLE scene_snapshot
+            ALTER COLUMN uuid DROP DEFAULT;
+    END IF;
+END
+$$;
+
+CREATE INDEX IF NOT EXISTS idx_scene_snapshot_scene_uuid_created_at
+    ON scene_snapshot (scene_uuid, created_at DESC);
+
+COMMIT;
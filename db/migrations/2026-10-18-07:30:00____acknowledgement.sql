@@ -0,0 +1,19 @@
+-- acknowledgement
+
+BEGIN;
+
+-- Short replies, like "give me a second", a person has ready to send the
+-- moment the real world user talks to them, while their full reaction is
+-- still being worked out
+CREATE TABLE IF NOT EXISTS acknowledgement
+(
+    uuid        UUID PRIMARY KEY,
+    person_uuid UUID        NOT NULL REFERENCES person (uuid) ON DELETE CASCADE,
+    content     TEXT        NOT NULL,
+    created_at  TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+CREATE INDEX IF NOT EXISTS idx_acknowledgement_person_uuid_created_at
+    ON acknowledgement (person_uuid, created_at);
+
+COMMIT;
@@ -0,0 +1,24 @@
+-- operator-token
+
+BEGIN;
+
+-- Operator tokens run the whole world, like queueing jobs, so they belong
+-- to no tenant. Every other token still belongs to exactly one.
+ALTER TABLE api_token
+    ALTER COLUMN tenant_uuid DROP NOT NULL;
+
+ALTER TABLE api_token
+    DROP CONSTRAINT IF EXISTS api_token_role_check;
+
+ALTER TABLE api_token
+    ADD CONSTRAINT api_token_role_check
+        CHECK (role IN ('viewer', 'director', 'admin', 'operator'));
+
+ALTER TABLE api_token
+    DROP CONSTRAINT IF EXISTS api_token_operator_has_no_tenant;
+
+ALTER TABLE api_token
+    ADD CONSTRAINT api_token_operator_has_no_tenant
+        CHECK ((role = 'operator') = (tenant_uuid IS NULL));
+
+COMMIT;
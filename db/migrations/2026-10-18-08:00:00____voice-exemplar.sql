@@ -0,0 +1,27 @@
+-- voice-exemplar
+
+BEGIN;
+
+-- A few things a person has said, or might say, that show how they talk.
+-- Shown to the model as examples when the person reacts
+ALTER TABLE person
+    ADD COLUMN IF NOT EXISTS voice_exemplars TEXT[] NOT NULL DEFAULT '{}';
+
+-- Each world has its own database, so one row is one world's setting.
+-- 'ab' shows the examples in a random half of reactions, to compare
+CREATE TABLE IF NOT EXISTS voice_exemplar_setting
+(
+    id   BOOLEAN PRIMARY KEY DEFAULT TRUE,
+    mode TEXT NOT NULL DEFAULT 'on' CHECK (mode IN ('off', 'on', 'ab'))
+);
+
+INSERT INTO voice_exemplar_setting (id)
+VALUES (TRUE)
+ON CONFLICT (id) DO NOTHING;
+
+-- Whether the reaction's prompt had the person's examples in it. NULL when
+-- the person had none
+ALTER TABLE reaction_context
+    ADD COLUMN IF NOT EXISTS used_voice_exemplars BOOLEAN NULL;
+
+COMMIT;
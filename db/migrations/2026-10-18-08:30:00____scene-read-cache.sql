@@ -0,0 +1,75 @@
+-- scene-read-cache
+
+BEGIN;
+
+-- The worker keeps recent scene reads in memory and listens on
+-- 'scene_changed' to know when to drop them. The payload is the scene's
+-- uuid
+CREATE OR REPLACE FUNCTION notify_scene_changed() RETURNS TRIGGER AS $$
+DECLARE
+    changed_scene_uuid UUID;
+BEGIN
+    IF TG_OP = 'DELETE' THEN
+        changed_scene_uuid := OLD.scene_uuid;
+    ELSE
+        changed_scene_uuid := NEW.scene_uuid;
+    END IF;
+
+    IF changed_scene_uuid IS NOT NULL THEN
+        PERFORM pg_notify('scene_changed', changed_scene_uuid::TEXT);
+    END IF;
+
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+-- A person's name shows up in every scene they have been in, so a rename
+-- sends an empty payload, which drops everything
+CREATE OR REPLACE FUNCTION notify_every_scene_changed() RETURNS TRIGGER AS $$
+BEGIN
+    PERFORM pg_notify('scene_changed', '');
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+DROP TRIGGER IF EXISTS message_scene_changed ON message;
+CREATE TRIGGER message_scene_changed
+    AFTER INSERT OR UPDATE OR DELETE ON message
+    FOR EACH ROW EXECUTE FUNCTION notify_scene_changed();
+
+DROP TRIGGER IF EXISTS scene_participant_scene_changed ON scene_participant;
+CREATE TRIGGER scene_participant_scene_changed
+    AFTER INSERT OR UPDATE OR DELETE ON scene_participant
+    FOR EACH ROW EXECUTE FUNCTION notify_scene_changed();
+
+DROP TRIGGER IF EXISTS scene_arrival_observation_scene_changed ON scene_arrival_observation;
+CREATE TRIGGER scene_arrival_observation_scene_changed
+    AFTER INSERT OR UPDATE OR DELETE ON scene_arrival_observation
+    FOR EACH ROW EXECUTE FUNCTION notify_scene_changed();
+
+DROP TRIGGER IF EXISTS scene_snapshot_scene_changed ON scene_snapshot;
+CREATE TRIGGER scene_snapshot_scene_changed
+    AFTER INSERT OR UPDATE OR DELETE ON scene_snapshot
+    FOR EACH ROW EXECUTE FUNCTION notify_scene_changed();
+
+DROP TRIGGER IF EXISTS scene_ambience_change_scene_changed ON scene_ambience_change;
+CREATE TRIGGER scene_ambience_change_scene_changed
+    AFTER INSERT OR UPDATE OR DELETE ON scene_ambience_change
+    FOR EACH ROW EXECUTE FUNCTION notify_scene_changed();
+
+DROP TRIGGER IF EXISTS scene_event_scene_changed ON scene_event;
+CREATE TRIGGER scene_event_scene_changed
+    AFTER INSERT OR UPDATE OR DELETE ON scene_event
+    FOR EACH ROW EXECUTE FUNCTION notify_scene_changed();
+
+DROP TRIGGER IF EXISTS real_world_user_scene_presence_scene_changed ON real_world_user_scene_presence;
+CREATE TRIGGER real_world_user_scene_presence_scene_changed
+    AFTER INSERT OR UPDATE OR DELETE ON real_world_user_scene_presence
+    FOR EACH ROW EXECUTE FUNCTION notify_scene_changed();
+
+DROP TRIGGER IF EXISTS person_name_scene_changed ON person;
+CREATE TRIGGER person_name_scene_changed
+    AFTER UPDATE OF name ON person
+    FOR EACH STATEMENT EXECUTE FUNCTION notify_every_scene_changed();
+
+COMMIT;
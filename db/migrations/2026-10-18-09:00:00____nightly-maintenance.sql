@@ -0,0 +1,32 @@
+-- nightly-maintenance
+
+BEGIN;
+
+-- One row per provider, model and day, added up from `llm_call` so usage
+-- is still known after old calls are pruned
+CREATE TABLE IF NOT EXISTS llm_usage_daily
+(
+    day               DATE        NOT NULL,
+    provider          TEXT        NOT NULL,
+    model             TEXT        NOT NULL,
+    calls             BIGINT      NOT NULL,
+    failed_calls      BIGINT      NOT NULL,
+    prompt_tokens     BIGINT      NOT NULL,
+    completion_tokens BIGINT      NOT NULL,
+    duration_ms       BIGINT      NOT NULL,
+    rolled_up_at      TIMESTAMPTZ NOT NULL DEFAULT now(),
+    PRIMARY KEY (day, provider, model)
+);
+
+-- Runs every night at 4 UTC. The uuid is fixed so this only ever adds one
+INSERT INTO cron_job (uuid, name, expression, job_name, next_run_at)
+VALUES (
+    md5('nightly maintenance')::UUID,
+    'nightly maintenance',
+    '0 4 * * *',
+    'nightly maintenance',
+    (date_trunc('day', now() AT TIME ZONE 'UTC') + INTERVAL '1 day 4 hours') AT TIME ZONE 'UTC'
+)
+ON CONFLICT (uuid) DO NOTHING;
+
+COMMIT;
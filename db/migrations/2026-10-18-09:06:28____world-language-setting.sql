@@ -0,0 +1,18 @@
+-- world-language-setting
+
+BEGIN;
+
+-- Each world has its own database, so one row is the language one world
+-- speaks. The code is checked by the app, which rejects codes it does not
+-- know rather than guessing.
+CREATE TABLE IF NOT EXISTS world_language_setting
+(
+    id       BOOLEAN PRIMARY KEY DEFAULT TRUE,
+    language TEXT NOT NULL DEFAULT 'en'
+);
+
+INSERT INTO world_language_setting (id)
+VALUES (TRUE)
+ON CONFLICT (id) DO NOTHING;
+
+COMMIT;
@@ -0,0 +1,11 @@
+-- scene-tone-profile
+
+BEGIN;
+
+-- How the scene sounds, like 'noir'. It is added to the reaction prompts of
+-- persons in the scene and moves its moderation thresholds. NULL means the
+-- world's voice and threshold
+ALTER TABLE scene
+    ADD COLUMN IF NOT EXISTS tone_profile TEXT NULL;
+
+COMMIT;
@@ -0,0 +1,24 @@
+-- external-tool-call
+
+BEGIN;
+
+-- Every call a person made with the `use tool` action, whether or not the
+-- tool answered
+CREATE TABLE IF NOT EXISTS external_tool_call
+(
+    uuid        UUID PRIMARY KEY,
+    person_uuid UUID        NOT NULL REFERENCES person (uuid),
+    scene_uuid  UUID REFERENCES scene (uuid),
+    tool_name   TEXT        NOT NULL,
+    arguments   JSONB       NOT NULL,
+    -- Exactly one of these is set
+    result      TEXT,
+    error       TEXT,
+    duration_ms BIGINT      NOT NULL,
+    created_at  TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+CREATE INDEX IF NOT EXISTS idx_external_tool_call_person_uuid_created_at
+    ON external_tool_call (person_uuid, created_at DESC);
+
+COMMIT;
@@ -0,0 +1,22 @@
+-- scene-invitation
+
+BEGIN;
+
+-- One person asking another to come to a scene. Answered by the invitee's
+-- next reaction, so at most one of accepted_at and declined_at is set
+CREATE TABLE IF NOT EXISTS scene_invitation
+(
+    uuid                UUID PRIMARY KEY,
+    scene_uuid          UUID        NOT NULL REFERENCES scene (uuid) ON DELETE CASCADE,
+    inviter_person_uuid UUID        NOT NULL REFERENCES person (uuid),
+    invitee_person_uuid UUID        NOT NULL REFERENCES person (uuid),
+    created_at          TIMESTAMPTZ NOT NULL DEFAULT now(),
+    accepted_at         TIMESTAMPTZ,
+    declined_at         TIMESTAMPTZ
+);
+
+CREATE INDEX IF NOT EXISTS idx_scene_invitation_pending_invitee
+    ON scene_invitation (invitee_person_uuid, created_at)
+    WHERE accepted_at IS NULL AND declined_at IS NULL;
+
+COMMIT;
@@ -0,0 +1,22 @@
+-- person-arc
+
+BEGIN;
+
+-- A person's long-term storyline, rewritten every world month. The latest
+-- row is the current arc, and the ones before it are how it got there
+CREATE TABLE IF NOT EXISTS person_arc
+(
+    uuid                 UUID PRIMARY KEY,
+    person_uuid          UUID        NOT NULL REFERENCES person (uuid) ON DELETE CASCADE,
+    storyline            TEXT        NOT NULL,
+    stakes               TEXT        NOT NULL,
+    progress_notes       TEXT        NOT NULL,
+    -- On the active clock, which is what a world month is measured on
+    written_at_active_ms BIGINT      NOT NULL,
+    created_at           TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+CREATE INDEX IF NOT EXISTS idx_person_arc_person_created_at
+    ON person_arc (person_uuid, created_at DESC);
+
+COMMIT;
@@ -1,36 +1,54 @@
+mod bookmarks_page;
+mod budget_page;
 mod call;
+mod canvas_layout;
+mod conversation_graph_page;
+mod cost_ticker;
+mod cron_page;
+mod delivery_page;
+mod draft;
 mod job_page;
+mod json_view;
 mod memory_page;
 mod messages_page;
+mod moderation_page;
 mod motivation_page;
 mod new_identity_page;
+mod pending_operations;
 mod person_page;
 mod person_task_page;
 mod prompt_lab_page;
+mod prompt_page;
 mod reaction_page;
 mod scene_page;
+mod scene_template_page;
+mod schema_page;
+mod settings_page;
+mod speed_controls;
 mod state_of_mind_page;
 mod style;
+mod style_guide_form;
+mod topics_page;
+mod training_page;
+mod voice_exemplars_form;
+mod world_map_page;
 
 use self::style as s;
 use crate::capability::job_runner_settings::JobRunnerSettingsCapability;
 use crate::domain::logger::{Level, Logger};
-use crate::nice_display::NiceDisplay;
-use crate::open_ai::completion::CompletionError;
-use crate::worker;
-use crate::worker::Worker;
+use crate::nice_display::{with_context, NiceDisplay};
+use crate::worker::{ProcessRole, Worker};
 use iced::{widget as w, Element, Length, Subscription, Task, Theme};
 use serde::{Deserialize, Serialize};
 use std::fs::File;
-use std::io::Write;
 use std::path::Path;
 use std::sync::Arc;
+use std::time::Instant;
 
 const STORAGE_FILE_PATH: &str = "storage.json";
 
 struct Model {
-    prompt_field: String,
-    prompt_status: PromptStatus,
+    prompt_page: prompt_page::Model,
     new_identity_page: new_identity_page::Model,
     person_page: person_page::Model,
     memory_page: memory_page::Model,
@@ -42,6 +60,21 @@ struct Model {
     job_page: job_page::Model,
     reaction_page: reaction_page::Model,
     prompt_lab_page: prompt_lab_page::Model,
+    training_page: training_page::Model,
+    moderation_page: moderation_page::Model,
+    budget_page: budget_page::Model,
+    scene_template_page: scene_template_page::Model,
+    world_map_page: world_map_page::Model,
+    conversation_graph_page: conversation_graph_page::Model,
+    delivery_page: delivery_page::Model,
+    settings_page: settings_page::Model,
+    cron_page: cron_page::Model,
+    schema_page: schema_page::Model,
+    bookmarks_page: bookmarks_page::Model,
+    topics_page: topics_page::Model,
+    pending_operations: pending_operations::Model,
+    cost_ticker: cost_ticker::Model,
+    speed_controls: speed_controls::Model,
     tab: Tab,
     worker: Arc<Worker>,
     error: Option<Error>,
@@ -54,7 +87,8 @@ struct Model {
 impl Model {
     pub fn to_storage(&self) -> Storage {
         Storage {
-            prompt: self.prompt_field.clone(),
+            prompt: self.prompt_page.prompt(),
+            prompt_options: self.prompt_page.to_storage(),
             new_identity: self.new_identity_page.to_storage(),
             person: self.person_page.to_storage(),
             memory: self.memory_page.to_storage(),
@@ -66,17 +100,23 @@ impl Model {
             job: self.job_page.to_storage(),
             reaction: self.reaction_page.to_storage(),
             prompt_lab: self.prompt_lab_page.to_storage(),
+            training: self.training_page.to_storage(),
+            moderation: self.moderation_page.to_storage(),
+            budget: self.budget_page.to_storage(),
+            scene_template: self.scene_template_page.to_storage(),
+            world_map: self.world_map_page.to_storage(),
+            conversation_graph: self.conversation_graph_page.to_storage(),
+            delivery: self.delivery_page.to_storage(),
+            settings: self.settings_page.to_storage(),
+            cron: self.cron_page.to_storage(),
+            schema: self.schema_page.to_storage(),
+            bookmarks: self.bookmarks_page.to_storage(),
+            topics: self.topics_page.to_storage(),
             tab: self.tab,
         }
     }
 }
 
-enum PromptStatus {
-    Ready,
-    Response(String),
-    Error(CompletionError),
-}
-
 enum JobRunnerPollIntervalStatus {
     Loading,
     Ready,
@@ -95,6 +135,8 @@ enum JobRunnerEnabledStatus {
 struct Storage {
     prompt: String,
     #[serde(default)]
+    prompt_options: prompt_page::Storage,
+    #[serde(default)]
     tab: Tab,
     #[serde(default)]
     new_identity: new_identity_page::Storage,
@@ -119,6 +161,30 @@ struct Storage {
     reaction: reaction_page::Storage,
     #[serde(default)]
     prompt_lab: prompt_lab_page::Storage,
+    #[serde(default)]
+    training: training_page::Storage,
+    #[serde(default)]
+    moderation: moderation_page::Storage,
+    #[serde(default)]
+    budget: budget_page::Storage,
+    #[serde(default)]
+    scene_template: scene_template_page::Storage,
+    #[serde(default)]
+    world_map: world_map_page::Storage,
+    #[serde(default)]
+    conversation_graph: conversation_graph_page::Storage,
+    #[serde(default)]
+    delivery: delivery_page::Storage,
+    #[serde(default)]
+    settings: settings_page::Storage,
+    #[serde(default)]
+    cron: cron_page::Storage,
+    #[serde(default)]
+    schema: schema_page::Storage,
+    #[serde(default)]
+    bookmarks: bookmarks_page::Storage,
+    #[serde(default)]
+    topics: topics_page::Storage,
 }
 
 impl Storage {
@@ -127,13 +193,9 @@ impl Storage {
         let json = serde_json::to_string_pretty(self)
             .map_err(|e| Error::StorageSerialization(e.to_string()))?;
 
-        // Create a file to save the JSON
+        // Write through a temporary file so a crash never leaves half a file
         let path = Path::new(STORAGE_FILE_PATH);
-        let mut file = File::create(path).map_err(Error::StorageFileCreation)?;
-
-        // Write the JSON to the file
-        file.write_all(json.as_bytes())
-            .map_err(Error::StorageFileWrite)?;
+        draft::write_atomically(path, json.as_bytes()).map_err(Error::StorageFileWrite)?;
 
         Ok(())
     }
@@ -158,6 +220,7 @@ impl Storage {
     pub fn default() -> Self {
         Storage {
             prompt: String::new(),
+            prompt_options: prompt_page::Storage::default(),
             tab: Tab::default(),
             new_identity: new_identity_page::Storage::default(),
             person: person_page::Storage::default(),
@@ -170,6 +233,18 @@ impl Storage {
             job: job_page::Storage::default(),
             reaction: reaction_page::Storage::default(),
             prompt_lab: prompt_lab_page::Storage::default(),
+            training: training_page::Storage::default(),
+            moderation: moderation_page::Storage::default(),
+            budget: budget_page::Storage::default(),
+            scene_template: scene_template_page::Storage::default(),
+            world_map: world_map_page::Storage::default(),
+            conversation_graph: conversation_graph_page::Storage::default(),
+            delivery: delivery_page::Storage::default(),
+            settings: settings_page::Storage::default(),
+            cron: cron_page::Storage::default(),
+            schema: schema_page::Storage::default(),
+            bookmarks: bookmarks_page::Storage::default(),
+            topics: topics_page::Storage::default(),
         }
     }
 }
@@ -190,6 +265,18 @@ enum Tab {
     StateOfMind,
     Scene,
     Job,
+    Training,
+    Moderation,
+    Budget,
+    SceneTemplate,
+    WorldMap,
+    ConversationGraph,
+    Delivery,
+    Settings,
+    Cron,
+    Schema,
+    Bookmarks,
+    Topics,
 }
 
 impl Tab {
@@ -207,6 +294,18 @@ impl Tab {
             Tab::StateOfMind => "State of Mind".to_string(),
             Tab::Scene => "Scene".to_string(),
             Tab::Job => "Job".to_string(),
+            Tab::Training => "Training".to_string(),
+            Tab::Moderation => "Moderation".to_string(),
+            Tab::Budget => "Budget".to_string(),
+            Tab::SceneTemplate => "Scene Templates".to_string(),
+            Tab::WorldMap => "World Map".to_string(),
+            Tab::ConversationGraph => "Conversation Graph".to_string(),
+            Tab::Delivery => "Deliveries".to_string(),
+            Tab::Settings => "Settings".to_string(),
+            Tab::Cron => "Cron".to_string(),
+            Tab::Schema => "Schema".to_string(),
+            Tab::Bookmarks => "Bookmarks".to_string(),
+            Tab::Topics => "Topics".to_string(),
         }
     }
 
@@ -224,6 +323,18 @@ impl Tab {
             Tab::PersonTask,
             Tab::StateOfMind,
             Tab::Scene,
+            Tab::SceneTemplate,
+            Tab::WorldMap,
+            Tab::ConversationGraph,
+            Tab::Training,
+            Tab::Moderation,
+            Tab::Budget,
+            Tab::Delivery,
+            Tab::Settings,
+            Tab::Cron,
+            Tab::Schema,
+            Tab::Bookmarks,
+            Tab::Topics,
         ]
     }
 
@@ -245,23 +356,103 @@ struct Flags {
     storage: Storage,
 }
 
-impl Flags {
-    async fn get() -> Result<Self, Error> {
-        let logger = Logger::init(Level::Warning);
+/// Wraps the admin ui so it can keep retrying the database connection
+/// instead of exiting when Postgres is not up yet.
+enum App {
+    Connecting(Storage),
+    ConnectionFailed { storage: Storage, error: String },
+    Ready(Box<Model>),
+}
 
-        let worker = Worker::new(logger).await.map_err(Error::WorkerInit)?;
+impl App {
+    fn new(storage: Storage) -> (Self, Task<Msg>) {
+        (App::Connecting(storage), connect_worker())
+    }
 
-        let storage = Storage::read_from_file_system()?;
+    fn title(&self) -> String {
+        match self {
+            App::Ready(model) => model.title(),
+            App::Connecting(_) | App::ConnectionFailed { .. } => "Arizona 2 Admin".to_string(),
+        }
+    }
 
-        Ok(Flags { worker, storage })
+    fn update(&mut self, message: Msg) -> Task<Msg> {
+        match self {
+            App::Ready(model) => model.update(message),
+            App::Connecting(storage) => match message {
+                Msg::WorkerConnected(Ok(worker)) => {
+                    let storage = std::mem::replace(storage, Storage::default());
+                    let (model, task) = Model::new(Flags { worker, storage });
+                    *self = App::Ready(Box::new(model));
+                    task
+                }
+                Msg::WorkerConnected(Err(error)) => {
+                    let storage = std::mem::replace(storage, Storage::default());
+                    *self = App::ConnectionFailed { storage, error };
+                    Task::none()
+                }
+                _ => Task::none(),
+            },
+            App::ConnectionFailed { storage, .. } => match message {
+                Msg::ClickedRetryConnection => {
+                    let storage = std::mem::replace(storage, Storage::default());
+                    *self = App::Connecting(storage);
+                    connect_worker()
+                }
+                _ => Task::none(),
+            },
+        }
+    }
+
+    fn view(&self) -> Element<'_, Msg> {
+        match self {
+            App::Ready(model) => model.view(),
+            App::Connecting(_) => w::container(w::text("Connecting to the database..."))
+                .padding(s::S4)
+                .into(),
+            App::ConnectionFailed { error, .. } => w::container(
+                w::column![
+                    w::text("Could not connect to the database").size(20),
+                    w::text(error.as_str()).color(s::RED_SOFT),
+                    w::button("Retry connection").on_press(Msg::ClickedRetryConnection),
+                ]
+                .spacing(s::S4),
+            )
+            .padding(s::S4)
+            .into(),
+        }
+    }
+
+    fn subscription(&self) -> Subscription<Msg> {
+        match self {
+            App::Ready(model) => model.subscription(),
+            App::Connecting(_) | App::ConnectionFailed { .. } => Subscription::none(),
+        }
+    }
+
+    fn theme(&self) -> Theme {
+        theme()
     }
 }
 
+fn connect_worker() -> Task<Msg> {
+    Task::perform(
+        async {
+            let logger = Logger::init(Level::Warning);
+
+            Worker::new(logger, ProcessRole::AdminUi)
+                .await
+                .map_err(|err| err.to_nice_error().to_string())
+        },
+        Msg::WorkerConnected,
+    )
+}
+
 #[derive(Debug, Clone)]
 enum Msg {
-    PromptFieldChanged(String),
-    ClickedSubmitPrompt,
-    SubmissionResult(Result<String, CompletionError>),
+    WorkerConnected(Result<Worker, String>),
+    ClickedRetryConnection,
+    PromptPage(prompt_page::Msg),
     TabSelected(Tab),
     NewIdentityPage(new_identity_page::Msg),
     PersonPage(person_page::Msg),
@@ -274,6 +465,21 @@ enum Msg {
     JobPage(job_page::Msg),
     ReactionPage(reaction_page::Msg),
     PromptLab(prompt_lab_page::Msg),
+    TrainingPage(training_page::Msg),
+    ModerationPage(moderation_page::Msg),
+    BudgetPage(budget_page::Msg),
+    DeliveryPage(delivery_page::Msg),
+    SettingsPage(settings_page::Msg),
+    CronPage(cron_page::Msg),
+    SchemaPage(schema_page::Msg),
+    BookmarksPage(bookmarks_page::Msg),
+    TopicsPage(topics_page::Msg),
+    PendingOperations(pending_operations::Msg),
+    CostTicker(cost_ticker::Msg),
+    SpeedControls(speed_controls::Msg),
+    SceneTemplatePage(scene_template_page::Msg),
+    WorldMapPage(world_map_page::Msg),
+    ConversationGraphPage(conversation_graph_page::Msg),
     WarmedUpDb,
     JobRunnerPollIntervalLoaded(Result<u64, String>),
     JobRunnerPollIntervalInputChanged(String),
@@ -287,8 +493,6 @@ enum Msg {
 #[derive(Debug)]
 pub enum Error {
     IcedRun(iced::Error),
-    WorkerInit(worker::InitError),
-    StorageFileCreation(std::io::Error),
     StorageFileWrite(std::io::Error),
     StorageSerialization(String),
     StorageFileRead(std::io::Error),
@@ -298,16 +502,12 @@ pub enum Error {
 impl NiceDisplay for Error {
     fn message(&self) -> String {
         match self {
-            Error::IcedRun(err) => format!("Iced run error: {}", err),
-            Error::WorkerInit(err) => err.message(),
-            Error::StorageFileCreation(err) => format!("Storage file creation error: {}", err),
-            Error::StorageFileWrite(err) => format!("Storage file write error: {}", err),
-            Error::StorageSerialization(msg) => {
-                format!("Storage serialization error: {}", msg)
-            }
-            Error::StorageFileRead(err) => format!("Storage file read error: {}", err),
+            Error::IcedRun(err) => with_context("Iced run error", err),
+            Error::StorageFileWrite(err) => with_context("Storage file write error", err),
+            Error::StorageSerialization(msg) => with_context("Storage serialization error", msg),
+            Error::StorageFileRead(err) => with_context("Storage file read error", err),
             Error::StorageDeserialization(msg) => {
-                format!("Storage deserialization error: {}", msg)
+                with_context("Storage deserialization error", msg)
             }
         }
     }
@@ -318,8 +518,11 @@ impl Model {
         let tab = flags.storage.tab;
 
         let mut model = Model {
-            prompt_field: flags.storage.prompt,
-            prompt_status: PromptStatus::Ready,
+            prompt_page: prompt_page::Model::new(
+                &flags.storage.prompt,
+                &flags.storage.prompt_options,
+                &flags.worker.world,
+            ),
             new_identity_page: new_identity_page::Model::new(&flags.storage.new_identity),
             person_page: person_page::Model::new(&flags.storage.person),
             memory_page: memory_page::Model::new(&flags.storage.memory),
@@ -330,6 +533,23 @@ impl Model {
             job_page: job_page::Model::new(&flags.storage.job),
             reaction_page: reaction_page::Model::new(&flags.storage.reaction),
             prompt_lab_page: prompt_lab_page::Model::new(&flags.storage.prompt_lab),
+            training_page: training_page::Model::new(&flags.storage.training),
+            moderation_page: moderation_page::Model::new(&flags.storage.moderation),
+            budget_page: budget_page::Model::new(&flags.storage.budget),
+            scene_template_page: scene_template_page::Model::new(&flags.storage.scene_template),
+            world_map_page: world_map_page::Model::new(&flags.storage.world_map),
+            conversation_graph_page: conversation_graph_page::Model::new(
+                &flags.storage.conversation_graph,
+            ),
+            delivery_page: delivery_page::Model::new(&flags.storage.delivery),
+            settings_page: settings_page::Model::new(&flags.storage.settings),
+            cron_page: cron_page::Model::new(&flags.storage.cron),
+            schema_page: schema_page::Model::new(&flags.storage.schema),
+            bookmarks_page: bookmarks_page::Model::new(&flags.storage.bookmarks),
+            topics_page: topics_page::Model::new(&flags.storage.topics),
+            pending_operations: pending_operations::Model::new(),
+            cost_ticker: cost_ticker::Model::new(),
+            speed_controls: speed_controls::Model::new(),
             tab,
             worker: Arc::new(flags.worker),
             error: None,
@@ -344,6 +564,14 @@ impl Model {
         let worker3 = model.worker.clone();
         let worker4 = model.worker.clone();
 
+        let cost_ticker_task = model
+            .cost_ticker
+            .load(model.worker.clone())
+            .map(Msg::CostTicker);
+        let speed_controls_task = model
+            .speed_controls
+            .load(model.worker.clone())
+            .map(Msg::SpeedControls);
         let tab_task = tab.init_task(&model.worker);
         let messages_tab_task = if tab == Tab::Messages {
             model
@@ -361,6 +589,111 @@ impl Model {
         } else {
             Task::none()
         };
+        let training_tab_task = if tab == Tab::Training {
+            model
+                .training_page
+                .on_tab_activated(model.worker.clone())
+                .map(Msg::TrainingPage)
+        } else {
+            Task::none()
+        };
+        let moderation_tab_task = if tab == Tab::Moderation {
+            model
+                .moderation_page
+                .on_tab_activated(model.worker.clone())
+                .map(Msg::ModerationPage)
+        } else {
+            Task::none()
+        };
+        let budget_tab_task = if tab == Tab::Budget {
+            model
+                .budget_page
+                .on_tab_activated(model.worker.clone())
+                .map(Msg::BudgetPage)
+        } else {
+            Task::none()
+        };
+
+        let scene_template_tab_task = if tab == Tab::SceneTemplate {
+            model
+                .scene_template_page
+                .on_tab_activated(model.worker.clone())
+                .map(Msg::SceneTemplatePage)
+        } else {
+            Task::none()
+        };
+
+        let world_map_tab_task = if tab == Tab::WorldMap {
+            model
+                .world_map_page
+                .on_tab_activated(model.worker.clone())
+                .map(Msg::WorldMapPage)
+        } else {
+            Task::none()
+        };
+
+        let conversation_graph_tab_task = if tab == Tab::ConversationGraph {
+            model
+                .conversation_graph_page
+                .on_tab_activated(model.worker.clone())
+                .map(Msg::ConversationGraphPage)
+        } else {
+            Task::none()
+        };
+
+        let delivery_tab_task = if tab == Tab::Delivery {
+            model
+                .delivery_page
+                .on_tab_activated(model.worker.clone())
+                .map(Msg::DeliveryPage)
+        } else {
+            Task::none()
+        };
+
+        let settings_tab_task = if tab == Tab::Settings {
+            model
+                .settings_page
+                .on_tab_activated(model.worker.clone())
+                .map(Msg::SettingsPage)
+        } else {
+            Task::none()
+        };
+
+        let cron_tab_task = if tab == Tab::Cron {
+            model
+                .cron_page
+                .on_tab_activated(model.worker.clone())
+                .map(Msg::CronPage)
+        } else {
+            Task::none()
+        };
+
+        let schema_tab_task = if tab == Tab::Schema {
+            model
+                .schema_page
+                .on_tab_activated(model.worker.clone())
+                .map(Msg::SchemaPage)
+        } else {
+            Task::none()
+        };
+
+        let bookmarks_tab_task = if tab == Tab::Bookmarks {
+            model
+                .bookmarks_page
+                .on_tab_activated(model.worker.clone())
+                .map(Msg::BookmarksPage)
+        } else {
+            Task::none()
+        };
+
+        let topics_tab_task = if tab == Tab::Topics {
+            model
+                .topics_page
+                .on_tab_activated(model.worker.clone())
+                .map(Msg::TopicsPage)
+        } else {
+            Task::none()
+        };
 
         (
             model,
@@ -376,43 +709,44 @@ impl Model {
                     async move { worker4.get_job_runner_enabled().await },
                     Msg::JobRunnerEnabledLoaded,
                 ),
+                cost_ticker_task,
+                speed_controls_task,
                 tab_task,
                 messages_tab_task,
                 scene_tab_task,
+                training_tab_task,
+                moderation_tab_task,
+                budget_tab_task,
+                scene_template_tab_task,
+                world_map_tab_task,
+                conversation_graph_tab_task,
+                delivery_tab_task,
+                settings_tab_task,
+                cron_tab_task,
+                schema_tab_task,
+                bookmarks_tab_task,
+                topics_tab_task,
             ]),
         )
     }
 
     fn title(&self) -> String {
-        "Arizona 2 Admin".to_string()
+        match &self.worker.world {
+            Some(world) => format!("Arizona 2 Admin - {}", world),
+            None => "Arizona 2 Admin".to_string(),
+        }
     }
 
     fn update(&mut self, message: Msg) -> Task<Msg> {
         match message {
-            Msg::PromptFieldChanged(field) => {
-                self.prompt_field = field;
+            Msg::PromptPage(sub_msg) => {
+                let task = self.prompt_page.update(self.worker.clone(), sub_msg);
 
                 if let Err(err) = self.to_storage().save_to_file_system() {
                     self.error = Some(err);
                 }
 
-                Task::none()
-            }
-            Msg::ClickedSubmitPrompt => {
-                let open_ai_key = self.worker.open_ai_key.clone();
-                let reqwest_client = self.worker.reqwest_client.clone();
-                Task::perform(
-                    call::submit_prompt(open_ai_key, reqwest_client, self.prompt_field.clone()),
-                    Msg::SubmissionResult,
-                )
-            }
-            Msg::SubmissionResult(result) => {
-                self.prompt_status = match result {
-                    Ok(response) => PromptStatus::Response(response.clone()),
-                    Err(err) => PromptStatus::Error(err.clone()),
-                };
-
-                Task::none()
+                task.map(Msg::PromptPage)
             }
             Msg::TabSelected(tab) => {
                 self.tab = tab;
@@ -431,6 +765,54 @@ impl Model {
                         .scene_page
                         .on_tab_activated(self.worker.clone())
                         .map(Msg::ScenePage),
+                    Tab::Training => self
+                        .training_page
+                        .on_tab_activated(self.worker.clone())
+                        .map(Msg::TrainingPage),
+                    Tab::Moderation => self
+                        .moderation_page
+                        .on_tab_activated(self.worker.clone())
+                        .map(Msg::ModerationPage),
+                    Tab::Budget => self
+                        .budget_page
+                        .on_tab_activated(self.worker.clone())
+                        .map(Msg::BudgetPage),
+                    Tab::SceneTemplate => self
+                        .scene_template_page
+                        .on_tab_activated(self.worker.clone())
+                        .map(Msg::SceneTemplatePage),
+                    Tab::WorldMap => self
+                        .world_map_page
+                        .on_tab_activated(self.worker.clone())
+                        .map(Msg::WorldMapPage),
+                    Tab::ConversationGraph => self
+                        .conversation_graph_page
+                        .on_tab_activated(self.worker.clone())
+                        .map(Msg::ConversationGraphPage),
+                    Tab::Delivery => self
+                        .delivery_page
+                        .on_tab_activated(self.worker.clone())
+                        .map(Msg::DeliveryPage),
+                    Tab::Settings => self
+                        .settings_page
+                        .on_tab_activated(self.worker.clone())
+                        .map(Msg::SettingsPage),
+                    Tab::Cron => self
+                        .cron_page
+                        .on_tab_activated(self.worker.clone())
+                        .map(Msg::CronPage),
+                    Tab::Schema => self
+                        .schema_page
+                        .on_tab_activated(self.worker.clone())
+                        .map(Msg::SchemaPage),
+                    Tab::Bookmarks => self
+                        .bookmarks_page
+                        .on_tab_activated(self.worker.clone())
+                        .map(Msg::BookmarksPage),
+                    Tab::Topics => self
+                        .topics_page
+                        .on_tab_activated(self.worker.clone())
+                        .map(Msg::TopicsPage),
                     _ => Task::none(),
                 };
                 Task::batch(vec![init_task, tab_task])
@@ -444,6 +826,26 @@ impl Model {
 
                 task.map(Msg::NewIdentityPage)
             }
+            Msg::PersonPage(person_page::Msg::StageOperation(operation))
+            | Msg::MemoryPage(memory_page::Msg::StageOperation(operation))
+            | Msg::ScenePage(scene_page::Msg::SceneLookUpMsg(
+                scene_page::SceneLookUpMsg::StageOperation(operation),
+            )) => {
+                self.pending_operations.stage(operation, Instant::now());
+                Task::none()
+            }
+            Msg::PendingOperations(sub_msg) => self
+                .pending_operations
+                .update(self.worker.clone(), sub_msg)
+                .map(Msg::PendingOperations),
+            Msg::CostTicker(sub_msg) => self
+                .cost_ticker
+                .update(self.worker.clone(), sub_msg)
+                .map(Msg::CostTicker),
+            Msg::SpeedControls(sub_msg) => self
+                .speed_controls
+                .update(self.worker.clone(), sub_msg)
+                .map(Msg::SpeedControls),
             Msg::PersonPage(sub_msg) => {
                 let task = self.person_page.update(self.worker.clone(), sub_msg);
 
@@ -489,6 +891,7 @@ impl Model {
 
                 task.map(Msg::MessagesPage)
             }
+            Msg::WorkerConnected(_) | Msg::ClickedRetryConnection => Task::none(),
             Msg::WarmedUpDb => Task::none(),
             Msg::JobRunnerPollIntervalLoaded(result) => {
                 match result {
@@ -599,6 +1002,52 @@ impl Model {
 
                 task.map(Msg::ScenePage)
             }
+            Msg::JobPage(job_page::Msg::ClickedJobLink(link)) => {
+                let (tab, page_task) = match link {
+                    job_page::JobLink::Person { name } => {
+                        let worker = self.worker.clone();
+                        let lookup_task = self
+                            .person_page
+                            .update(worker.clone(), person_page::Msg::LookupNameChanged(name));
+                        let load_task = self
+                            .person_page
+                            .update(worker, person_page::Msg::ClickedLoadIdentity);
+                        (
+                            Tab::Person,
+                            Task::batch(vec![lookup_task, load_task]).map(Msg::PersonPage),
+                        )
+                    }
+                    job_page::JobLink::Scene { name } => (
+                        Tab::Scene,
+                        self.scene_page
+                            .update(
+                                self.worker.clone(),
+                                scene_page::Msg::SceneDropdownSelected(name),
+                            )
+                            .map(Msg::ScenePage),
+                    ),
+                    job_page::JobLink::Message { scene_name } => {
+                        let worker = self.worker.clone();
+                        let mode_task = self.messages_page.update(
+                            worker.clone(),
+                            messages_page::Msg::ViewModeRadioSelected(
+                                messages_page::ViewMode::Scene,
+                            ),
+                        );
+                        let scene_task = self.messages_page.update(
+                            worker,
+                            messages_page::Msg::SceneDropdownSelected(scene_name),
+                        );
+                        (
+                            Tab::Messages,
+                            Task::batch(vec![mode_task, scene_task]).map(Msg::MessagesPage),
+                        )
+                    }
+                };
+
+                let tab_task = self.update(Msg::TabSelected(tab));
+                Task::batch(vec![tab_task, page_task])
+            }
             Msg::JobPage(sub_msg) => {
                 let task = self.job_page.update(self.worker.clone(), sub_msg);
 
@@ -626,6 +1075,118 @@ impl Model {
 
                 task.map(Msg::PromptLab)
             }
+            Msg::TrainingPage(sub_msg) => {
+                let task = self.training_page.update(self.worker.clone(), sub_msg);
+
+                if let Err(err) = self.to_storage().save_to_file_system() {
+                    self.error = Some(err);
+                }
+
+                task.map(Msg::TrainingPage)
+            }
+            Msg::ModerationPage(sub_msg) => {
+                let task = self.moderation_page.update(self.worker.clone(), sub_msg);
+
+                if let Err(err) = self.to_storage().save_to_file_system() {
+                    self.error = Some(err);
+                }
+
+                task.map(Msg::ModerationPage)
+            }
+            Msg::BudgetPage(sub_msg) => {
+                let task = self.budget_page.update(self.worker.clone(), sub_msg);
+
+                if let Err(err) = self.to_storage().save_to_file_system() {
+                    self.error = Some(err);
+                }
+
+                task.map(Msg::BudgetPage)
+            }
+            Msg::DeliveryPage(sub_msg) => {
+                let task = self.delivery_page.update(self.worker.clone(), sub_msg);
+
+                if let Err(err) = self.to_storage().save_to_file_system() {
+                    self.error = Some(err);
+                }
+
+                task.map(Msg::DeliveryPage)
+            }
+            Msg::SettingsPage(sub_msg) => {
+                let task = self.settings_page.update(self.worker.clone(), sub_msg);
+
+                if let Err(err) = self.to_storage().save_to_file_system() {
+                    self.error = Some(err);
+                }
+
+                task.map(Msg::SettingsPage)
+            }
+            Msg::CronPage(sub_msg) => {
+                let task = self.cron_page.update(self.worker.clone(), sub_msg);
+
+                if let Err(err) = self.to_storage().save_to_file_system() {
+                    self.error = Some(err);
+                }
+
+                task.map(Msg::CronPage)
+            }
+            Msg::SchemaPage(sub_msg) => {
+                let task = self.schema_page.update(self.worker.clone(), sub_msg);
+
+                if let Err(err) = self.to_storage().save_to_file_system() {
+                    self.error = Some(err);
+                }
+
+                task.map(Msg::SchemaPage)
+            }
+            Msg::BookmarksPage(sub_msg) => {
+                let task = self.bookmarks_page.update(self.worker.clone(), sub_msg);
+
+                if let Err(err) = self.to_storage().save_to_file_system() {
+                    self.error = Some(err);
+                }
+
+                task.map(Msg::BookmarksPage)
+            }
+            Msg::TopicsPage(sub_msg) => {
+                let task = self.topics_page.update(self.worker.clone(), sub_msg);
+
+                if let Err(err) = self.to_storage().save_to_file_system() {
+                    self.error = Some(err);
+                }
+
+                task.map(Msg::TopicsPage)
+            }
+            Msg::SceneTemplatePage(sub_msg) => {
+                let task = self
+                    .scene_template_page
+                    .update(self.worker.clone(), sub_msg);
+
+                if let Err(err) = self.to_storage().save_to_file_system() {
+                    self.error = Some(err);
+                }
+
+                task.map(Msg::SceneTemplatePage)
+            }
+            Msg::WorldMapPage(sub_msg) => {
+                let task = self.world_map_page.update(self.worker.clone(), sub_msg);
+
+                if let Err(err) = self.to_storage().save_to_file_system() {
+                    self.error = Some(err);
+                }
+
+                task.map(Msg::WorldMapPage)
+            }
+            Msg::ConversationGraphPage(sub_msg) => {
+                let task = self
+                    .conversation_graph_page
+                    .update(self.worker.clone(), sub_msg);
+
+                if let Err(err) = self.to_storage().save_to_file_system() {
+                    self.error = Some(err);
+                }
+
+                task.map(Msg::ConversationGraphPage)
+            }
         }
     }
 
@@ -659,28 +1220,12 @@ impl Model {
             w::button(job_runner_enabled_label(self.job_runner_enabled))
                 .on_press(Msg::JobRunnerEnabledToggled),
             view_enabled_status(&self.job_runner_enabled_status),
+            self.cost_ticker.view().map(Msg::CostTicker),
         ]
         .spacing(s::S4);
 
         let tab_content: Element<Msg> = match self.tab {
-            Tab::Prompt => {
-                let prompt_response_view: Element<Msg> = match &self.prompt_status {
-                    PromptStatus::Ready => w::Column::new().into(),
-                    PromptStatus::Response(response) => {
-                        w::text(format!("Response: {}", response)).into()
-                    }
-                    PromptStatus::Error(err) => {
-                        w::text(format!("Error: {}", err.to_nice_error())).into()
-                    }
-                };
-
-                w::column![
-                    w::text_input("", &self.prompt_field).on_input(Msg::PromptFieldChanged),
-                    w::button("Submit").on_press(Msg::ClickedSubmitPrompt),
-                    prompt_response_view,
-                ]
-                .into()
-            }
+            Tab::Prompt => self.prompt_page.view().map(Msg::PromptPage),
             Tab::PromptLab => self.prompt_lab_page.view().map(Msg::PromptLab),
             Tab::Reaction => self.reaction_page.view().map(Msg::ReactionPage),
             Tab::Identity => self.new_identity_page.view().map(Msg::NewIdentityPage),
@@ -692,10 +1237,31 @@ impl Model {
             Tab::StateOfMind => self.state_of_mind_page.view().map(Msg::StateOfMindPage),
             Tab::Scene => self.scene_page.view().map(Msg::ScenePage),
             Tab::Job => self.job_page.view().map(Msg::JobPage),
+            Tab::Training => self.training_page.view().map(Msg::TrainingPage),
+            Tab::Moderation => self.moderation_page.view().map(Msg::ModerationPage),
+            Tab::Budget => self.budget_page.view().map(Msg::BudgetPage),
+            Tab::Delivery => self.delivery_page.view().map(Msg::DeliveryPage),
+            Tab::Settings => self.settings_page.view().map(Msg::SettingsPage),
+            Tab::Cron => self.cron_page.view().map(Msg::CronPage),
+            Tab::Schema => self.schema_page.view().map(Msg::SchemaPage),
+            Tab::Bookmarks => self.bookmarks_page.view().map(Msg::BookmarksPage),
+            Tab::Topics => self.topics_page.view().map(Msg::TopicsPage),
+            Tab::SceneTemplate => self.scene_template_page.view().map(Msg::SceneTemplatePage),
+            Tab::WorldMap => self.world_map_page.view().map(Msg::WorldMapPage),
+            Tab::ConversationGraph => self
+                .conversation_graph_page
+                .view()
+                .map(Msg::ConversationGraphPage),
         };
 
         let scrollable_content = w::scrollable(tab_content);
-        let main_content = w::column![time_controls, scrollable_content].spacing(s::S4);
+        let main_content = w::column![
+            time_controls,
+            self.speed_controls.view().map(Msg::SpeedControls),
+            self.pending_operations.view().map(Msg::PendingOperations),
+            scrollable_content
+        ]
+        .spacing(s::S4);
 
         w::container(w::row![tab_column, main_content].spacing(s::S4))
             .padding(s::S4)
@@ -703,7 +1269,13 @@ impl Model {
     }
 
     fn subscription(&self) -> Subscription<Msg> {
-        let mut subs = Vec::new();
+        let mut subs = vec![self
+            .pending_operations
+            .subscription()
+            .map(Msg::PendingOperations)];
+
+        subs.push(self.cost_ticker.subscription().map(Msg::CostTicker));
+        subs.push(self.speed_controls.subscription().map(Msg::SpeedControls));
 
         if self.tab == Tab::Job {
             subs.push(self.job_page.subscription().map(Msg::JobPage));
@@ -713,21 +1285,25 @@ impl Model {
             subs.push(self.messages_page.subscription().map(Msg::MessagesPage));
         }
 
+        if self.tab == Tab::WorldMap {
+            subs.push(self.world_map_page.subscription().map(Msg::WorldMapPage));
+        }
+
         Subscription::batch(subs)
     }
+}
 
-    fn theme(&self) -> Theme {
-        Theme::custom(
-            "arizona2".to_string(),
-            iced::theme::Palette {
-                background: s::GRAY_VERY_DEEP,
-                text: s::GRAY_VERY_SOFT,
-                primary: s::GOLD_SOFT,
-                success: s::GREEN_SOFT,
-                danger: s::RED_SOFT,
-            },
-        )
-    }
+fn theme() -> Theme {
+    Theme::custom(
+        "arizona2".to_string(),
+        iced::theme::Palette {
+            background: s::GRAY_VERY_DEEP,
+            text: s::GRAY_VERY_SOFT,
+            primary: s::GOLD_SOFT,
+            success: s::GREEN_SOFT,
+            danger: s::RED_SOFT,
+        },
+    )
 }
 
 fn view_poll_interval_status(status: &JobRunnerPollIntervalStatus) -> Element<'_, Msg> {
@@ -757,12 +1333,12 @@ fn view_enabled_status(status: &JobRunnerEnabledStatus) -> Element<'_, Msg> {
 }
 
 pub async fn run() -> Result<(), Error> {
-    let flags = Flags::get().await?;
+    let storage = Storage::read_from_file_system()?;
 
-    let iced_result = iced::application(Model::title, Model::update, Model::view)
-        .theme(Model::theme)
-        .subscription(Model::subscription)
-        .run_with(move || Model::new(flags));
+    let iced_result = iced::application(App::title, App::update, App::view)
+        .theme(App::theme)
+        .subscription(App::subscription)
+        .run_with(move || App::new(storage));
 
     iced_result.map_err(Error::IcedRun)
 }
@@ -0,0 +1,542 @@
+use crate::admin_ui::s;
+use crate::capability::annotation::AnnotationCapability;
+use crate::capability::scene::SceneCapability;
+use crate::capability::scene_timeline::SceneTimelineCapability;
+use crate::domain::annotation::{Annotation, NewAnnotation};
+use crate::domain::annotation_uuid::AnnotationUuid;
+use crate::domain::scene_timeline::{TimelineItem, TimelinePage, TimelineQuery};
+use crate::domain::scene_uuid::SceneUuid;
+use crate::time_display;
+use crate::worker::Worker;
+use chrono::{DateTime, Duration, Utc};
+use iced::{widget as w, Element, Length, Task};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+/// How much of the timeline is shown leading up to a moment.
+const MOMENT_ITEMS: i64 = 12;
+
+pub struct Model {
+    scene_field: String,
+    bookmarks_only: bool,
+    status: Status,
+}
+
+enum Status {
+    Ready,
+    Loading,
+    Loaded(LoadedScene),
+    Error(String),
+}
+
+struct LoadedScene {
+    scene_uuid: SceneUuid,
+    scene_name: String,
+    annotations: Vec<Annotation>,
+    moment: MomentStatus,
+    draft: Option<Draft>,
+    error: Option<String>,
+}
+
+enum MomentStatus {
+    Loading,
+    Loaded(TimelinePage),
+    Error(String),
+}
+
+struct Draft {
+    item: TimelineItem,
+    note: String,
+    is_bookmark: bool,
+    include_in_export: bool,
+}
+
+#[derive(Debug, Clone)]
+pub enum Msg {
+    SceneFieldChanged(String),
+    ClickedLoad,
+    LoadedScene(Result<(SceneUuid, String, Vec<Annotation>), String>),
+    ToggledBookmarksOnly(bool),
+    ClickedJump(DateTime<Utc>),
+    ClickedEarlier(DateTime<Utc>),
+    ClickedLatest,
+    LoadedMoment(Result<TimelinePage, String>),
+    ClickedAnnotate(usize),
+    DraftNoteChanged(String),
+    DraftToggledBookmark(bool),
+    DraftToggledExport(bool),
+    ClickedSave,
+    ClickedCancelDraft,
+    ToggledBookmark(AnnotationUuid, bool),
+    ToggledExport(AnnotationUuid, bool),
+    ClickedDelete(AnnotationUuid),
+    AnnotationsChanged(Result<Vec<Annotation>, String>),
+}
+
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct Storage {
+    #[serde(default)]
+    scene_field: String,
+    #[serde(default)]
+    bookmarks_only: bool,
+}
+
+impl Model {
+    pub fn new(storage: &Storage) -> Self {
+        Self {
+            scene_field: storage.scene_field.clone(),
+            bookmarks_only: storage.bookmarks_only,
+            status: Status::Ready,
+        }
+    }
+
+    pub fn to_storage(&self) -> Storage {
+        Storage {
+            scene_field: self.scene_field.clone(),
+            bookmarks_only: self.bookmarks_only,
+        }
+    }
+
+    pub fn on_tab_activated(&mut self, worker: Arc<Worker>) -> Task<Msg> {
+        match self.status {
+            Status::Ready if !self.scene_field.trim().is_empty() => self.load_scene(worker),
+            _ => Task::none(),
+        }
+    }
+
+    pub fn update(&mut self, worker: Arc<Worker>, msg: Msg) -> Task<Msg> {
+        match msg {
+            Msg::SceneFieldChanged(value) => {
+                self.scene_field = value;
+                Task::none()
+            }
+            Msg::ClickedLoad => self.load_scene(worker),
+            Msg::LoadedScene(result) => match result {
+                Ok((scene_uuid, scene_name, annotations)) => {
+                    self.status = Status::Loaded(LoadedScene {
+                        scene_uuid: scene_uuid.clone(),
+                        scene_name,
+                        annotations,
+                        moment: MomentStatus::Loading,
+                        draft: None,
+                        error: None,
+                    });
+                    load_moment(worker, scene_uuid, None)
+                }
+                Err(err) => {
+                    self.status = Status::Error(err);
+                    Task::none()
+                }
+            },
+            Msg::ToggledBookmarksOnly(value) => {
+                self.bookmarks_only = value;
+                Task::none()
+            }
+            Msg::ClickedJump(at) => self.show_moment(worker, Some(at + Duration::seconds(1))),
+            Msg::ClickedEarlier(before) => self.show_moment(worker, Some(before)),
+            Msg::ClickedLatest => self.show_moment(worker, None),
+            Msg::LoadedMoment(result) => {
+                if let Status::Loaded(loaded) = &mut self.status {
+                    loaded.moment = match result {
+                        Ok(page) => MomentStatus::Loaded(page),
+                        Err(err) => MomentStatus::Error(err),
+                    };
+                }
+                Task::none()
+            }
+            Msg::ClickedAnnotate(index) => {
+                if let Status::Loaded(loaded) = &mut self.status {
+                    if let MomentStatus::Loaded(page) = &loaded.moment {
+                        loaded.draft = page.items.get(index).map(|item| Draft {
+                            item: item.clone(),
+                            note: String::new(),
+                            is_bookmark: false,
+                            include_in_export: false,
+                        });
+                    }
+                }
+                Task::none()
+            }
+            Msg::DraftNoteChanged(value) => {
+                if let Some(draft) = self.draft_mut() {
+                    draft.note = value;
+                }
+                Task::none()
+            }
+            Msg::DraftToggledBookmark(value) => {
+                if let Some(draft) = self.draft_mut() {
+                    draft.is_bookmark = value;
+                }
+                Task::none()
+            }
+            Msg::DraftToggledExport(value) => {
+                if let Some(draft) = self.draft_mut() {
+                    draft.include_in_export = value;
+                }
+                Task::none()
+            }
+            Msg::ClickedSave => {
+                let loaded = match &mut self.status {
+                    Status::Loaded(loaded) => loaded,
+                    _ => return Task::none(),
+                };
+                let draft = match loaded.draft.take() {
+                    Some(draft) => draft,
+                    None => return Task::none(),
+                };
+
+                let new_annotation = match NewAnnotation::for_item(
+                    loaded.scene_uuid.clone(),
+                    &draft.item,
+                    draft.note.clone(),
+                    draft.is_bookmark,
+                    draft.include_in_export,
+                ) {
+                    Ok(new_annotation) => new_annotation,
+                    Err(err) => {
+                        loaded.error = Some(err);
+                        loaded.draft = Some(draft);
+                        return Task::none();
+                    }
+                };
+
+                let scene_uuid = loaded.scene_uuid.clone();
+                Task::perform(
+                    async move {
+                        worker.add_annotation(&new_annotation).await?;
+                        worker.get_scene_annotations(&scene_uuid).await
+                    },
+                    Msg::AnnotationsChanged,
+                )
+            }
+            Msg::ClickedCancelDraft => {
+                if let Status::Loaded(loaded) = &mut self.status {
+                    loaded.draft = None;
+                }
+                Task::none()
+            }
+            Msg::ToggledBookmark(annotation_uuid, is_bookmark) => {
+                self.set_flags(worker, annotation_uuid, |annotation| {
+                    (is_bookmark, annotation.include_in_export)
+                })
+            }
+            Msg::ToggledExport(annotation_uuid, include_in_export) => {
+                self.set_flags(worker, annotation_uuid, |annotation| {
+                    (annotation.is_bookmark, include_in_export)
+                })
+            }
+            Msg::ClickedDelete(annotation_uuid) => {
+                let scene_uuid = match &self.status {
+                    Status::Loaded(loaded) => loaded.scene_uuid.clone(),
+                    _ => return Task::none(),
+                };
+
+                Task::perform(
+                    async move {
+                        worker.delete_annotation(&annotation_uuid).await?;
+                        worker.get_scene_annotations(&scene_uuid).await
+                    },
+                    Msg::AnnotationsChanged,
+                )
+            }
+            Msg::AnnotationsChanged(result) => {
+                if let Status::Loaded(loaded) = &mut self.status {
+                    match result {
+                        Ok(annotations) => {
+                            loaded.annotations = annotations;
+                            loaded.error = None;
+                        }
+                        Err(err) => loaded.error = Some(err),
+                    }
+                }
+                Task::none()
+            }
+        }
+    }
+
+    fn load_scene(&mut self, worker: Arc<Worker>) -> Task<Msg> {
+        let scene_name = self.scene_field.trim().to_string();
+        if scene_name.is_empty() {
+            return Task::none();
+        }
+
+        self.status = Status::Loading;
+
+        Task::perform(
+            async move {
+                let scene = worker
+                    .get_scene_from_name(scene_name.clone())
+                    .await?
+                    .ok_or_else(|| format!("No scene named \"{}\"", scene_name))?;
+                let annotations = worker.get_scene_annotations(&scene.uuid).await?;
+
+                Ok((scene.uuid, scene.name, annotations))
+            },
+            Msg::LoadedScene,
+        )
+    }
+
+    fn show_moment(&mut self, worker: Arc<Worker>, before: Option<DateTime<Utc>>) -> Task<Msg> {
+        match &mut self.status {
+            Status::Loaded(loaded) => {
+                loaded.moment = MomentStatus::Loading;
+                loaded.draft = None;
+                load_moment(worker, loaded.scene_uuid.clone(), before)
+            }
+            _ => Task::none(),
+        }
+    }
+
+    fn draft_mut(&mut self) -> Option<&mut Draft> {
+        match &mut self.status {
+            Status::Loaded(loaded) => loaded.draft.as_mut(),
+            _ => None,
+        }
+    }
+
+    fn set_flags(
+        &mut self,
+        worker: Arc<Worker>,
+        annotation_uuid: AnnotationUuid,
+        flags: impl Fn(&Annotation) -> (bool, bool),
+    ) -> Task<Msg> {
+        let loaded = match &self.status {
+            Status::Loaded(loaded) => loaded,
+            _ => return Task::none(),
+        };
+        let (is_bookmark, include_in_export) = match loaded
+            .annotations
+            .iter()
+            .find(|annotation| annotation.uuid == annotation_uuid)
+        {
+            Some(annotation) => flags(annotation),
+            None => return Task::none(),
+        };
+
+        let scene_uuid = loaded.scene_uuid.clone();
+        Task::perform(
+            async move {
+                worker
+                    .set_annotation_flags(&annotation_uuid, is_bookmark, include_in_export)
+                    .await?;
+                worker.get_scene_annotations(&scene_uuid).await
+            },
+            Msg::AnnotationsChanged,
+        )
+    }
+
+    pub fn view(&self) -> Element<'_, Msg> {
+        w::column![
+            w::text("Bookmarks").size(20),
+            w::text(
+                "Notes are private. Only notes marked for export are written into archived transcripts."
+            )
+            .size(s::S3),
+            w::row![
+                w::text_input("Scene name", &self.scene_field)
+                    .on_input(Msg::SceneFieldChanged)
+                    .on_submit(Msg::ClickedLoad),
+                w::button("Load").on_press(Msg::ClickedLoad),
+            ]
+            .spacing(s::S1),
+            w::horizontal_rule(1),
+            status_view(&self.status, self.bookmarks_only),
+        ]
+        .spacing(s::S4)
+        .into()
+    }
+}
+
+fn load_moment(
+    worker: Arc<Worker>,
+    scene_uuid: SceneUuid,
+    before: Option<DateTime<Utc>>,
+) -> Task<Msg> {
+    Task::perform(
+        async move {
+            worker
+                .get_scene_timeline(&scene_uuid, TimelineQuery::new(before, Some(MOMENT_ITEMS)))
+                .await
+        },
+        Msg::LoadedMoment,
+    )
+}
+
+fn status_view(status: &Status, bookmarks_only: bool) -> Element<'_, Msg> {
+    let loaded = match status {
+        Status::Ready => return w::text("Load a scene to see its annotations").into(),
+        Status::Loading => return w::text("Loading...").into(),
+        Status::Error(err) => return w::text(format!("Error: {}", err)).color(s::RED_SOFT).into(),
+        Status::Loaded(loaded) => loaded,
+    };
+
+    let error: Element<'_, Msg> = match &loaded.error {
+        Some(err) => w::text(format!("Error: {}", err)).color(s::RED_SOFT).into(),
+        None => w::text("").into(),
+    };
+
+    w::row![
+        w::column![
+            w::text(format!("Annotations in {}", loaded.scene_name)).size(s::S4),
+            w::checkbox("Bookmarks only", bookmarks_only).on_toggle(Msg::ToggledBookmarksOnly),
+            annotations_view(&loaded.annotations, bookmarks_only),
+        ]
+        .spacing(s::S2)
+        .width(Length::FillPortion(2)),
+        w::column![
+            error,
+            moment_view(&loaded.moment, &loaded.annotations),
+            draft_view(&loaded.draft),
+        ]
+        .spacing(s::S2)
+        .width(Length::FillPortion(3)),
+    ]
+    .spacing(s::S4)
+    .into()
+}
+
+fn annotations_view(annotations: &[Annotation], bookmarks_only: bool) -> Element<'_, Msg> {
+    let shown = annotations
+        .iter()
+        .filter(|annotation| annotation.is_bookmark || !bookmarks_only)
+        .collect::<Vec<&Annotation>>();
+
+    if shown.is_empty() {
+        return w::text("Nothing yet, annotate an item in the timeline").into();
+    }
+
+    let col = shown.into_iter().fold(w::column![], |col, annotation| {
+        let mut entry = w::column![w::row![
+            w::button(w::text(time_display::format_absolute(annotation.at)).size(s::S3))
+                .on_press(Msg::ClickedJump(annotation.at)),
+            w::text(&annotation.item_summary).size(s::S3),
+        ]
+        .spacing(s::S1)
+        .align_y(iced::Alignment::Center)]
+        .spacing(s::S1);
+
+        if !annotation.note.is_empty() {
+            entry = entry.push(w::text(&annotation.note).color(s::GOLD_SOFT));
+        }
+
+        let annotation_uuid = annotation.uuid.clone();
+        let export_uuid = annotation.uuid.clone();
+        entry = entry.push(
+            w::row![
+                w::checkbox("Bookmark", annotation.is_bookmark)
+                    .on_toggle(move |value| Msg::ToggledBookmark(annotation_uuid.clone(), value)),
+                w::checkbox("Include in export", annotation.include_in_export)
+                    .on_toggle(move |value| Msg::ToggledExport(export_uuid.clone(), value)),
+                w::button(w::text("Delete").size(s::S3))
+                    .on_press(Msg::ClickedDelete(annotation.uuid.clone())),
+            ]
+            .spacing(s::S2)
+            .align_y(iced::Alignment::Center),
+        );
+
+        col.push(entry)
+    });
+
+    w::scrollable(col.spacing(s::S3))
+        .height(Length::Fixed(s::LIST_HEIGHT))
+        .into()
+}
+
+fn moment_view<'a>(moment: &'a MomentStatus, annotations: &'a [Annotation]) -> Element<'a, Msg> {
+    let page = match moment {
+        MomentStatus::Loading => return w::text("Loading...").into(),
+        MomentStatus::Error(err) => {
+            return w::text(format!("Error: {}", err)).color(s::RED_SOFT).into()
+        }
+        MomentStatus::Loaded(page) => page,
+    };
+
+    let mut nav = w::row![w::button("Latest").on_press(Msg::ClickedLatest)].spacing(s::S1);
+    if let Some(next_before) = page.next_before {
+        nav = nav.push(w::button("Earlier").on_press(Msg::ClickedEarlier(next_before)));
+    }
+
+    let mut col = w::column![nav].spacing(s::S2);
+
+    if page.items.is_empty() {
+        col = col.push(w::text("Nothing happened in this scene yet"));
+    }
+
+    for (index, item) in page.items.iter().enumerate() {
+        col = col.push(
+            w::row![
+                w::text(format!(
+                    "[{}] {}",
+                    time_display::format_clock(item.at()),
+                    item_text(item)
+                )),
+                w::button(w::text("Annotate").size(s::S3))
+                    .style(w::button::text)
+                    .padding(0)
+                    .on_press(Msg::ClickedAnnotate(index)),
+            ]
+            .spacing(s::S1),
+        );
+
+        for annotation in annotations.iter().filter(|a| a.at == item.at()) {
+            let marker = if annotation.is_bookmark { "★" } else { "✎" };
+            col = col.push(
+                w::text(format!("  {} {}", marker, annotation.note))
+                    .size(s::S3)
+                    .color(s::GOLD_SOFT),
+            );
+        }
+    }
+
+    col.into()
+}
+
+fn draft_view(draft: &Option<Draft>) -> Element<'_, Msg> {
+    let draft = match draft {
+        Some(draft) => draft,
+        None => return w::text("").into(),
+    };
+
+    w::column![
+        w::text(format!(
+            "Annotate [{}] {}",
+            time_display::format_clock(draft.item.at()),
+            item_text(&draft.item)
+        ))
+        .size(s::S3),
+        w::text_input("Note", &draft.note)
+            .on_input(Msg::DraftNoteChanged)
+            .on_submit(Msg::ClickedSave),
+        w::row![
+            w::checkbox("Bookmark", draft.is_bookmark).on_toggle(Msg::DraftToggledBookmark),
+            w::checkbox("Include in export", draft.include_in_export)
+                .on_toggle(Msg::DraftToggledExport),
+            w::button("Save").on_press(Msg::ClickedSave),
+            w::button("Cancel").on_press(Msg::ClickedCancelDraft),
+        ]
+        .spacing(s::S2)
+        .align_y(iced::Alignment::Center),
+    ]
+    .spacing(s::S1)
+    .into()
+}
+
+fn item_text(item: &TimelineItem) -> String {
+    match item {
+        TimelineItem::Message {
+            sender, content, ..
+        } => format!("{}: {}", sender.name, content),
+        TimelineItem::Joined { person_name, .. } => format!("→ {} joined", person_name),
+        TimelineItem::Left { person_name, .. } => format!("← {} left", person_name),
+        TimelineItem::ArrivalSummary {
+            person_name,
+            summary,
+            ..
+        } => format!("{} noticed: {}", person_name, summary),
+        TimelineItem::SceneSnapshot { description, .. } => format!("Scene: {}", description),
+        TimelineItem::AmbienceChanged { ambience, .. } => {
+            format!("Ambience: {}", ambience.to_label())
+        }
+        TimelineItem::SceneEvent { description, .. } => format!("Event: {}", description),
+    }
+}
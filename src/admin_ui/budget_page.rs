@@ -0,0 +1,299 @@
+use crate::admin_ui::s;
+use crate::capability::budget::BudgetCapability;
+use crate::domain::budget::{self, BudgetLedger};
+use crate::domain::chat_latency::{self, ChatLatency};
+use crate::time_display;
+use crate::worker::Worker;
+use chrono::Utc;
+use iced::{widget as w, Element, Task};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+pub struct Model {
+    budget_input: String,
+    ledger: LedgerStatus,
+    chat_latency: ChatLatencyStatus,
+    save_status: SaveStatus,
+}
+
+enum LedgerStatus {
+    Loading,
+    Loaded(BudgetLedger),
+    Error(String),
+}
+
+enum ChatLatencyStatus {
+    Loading,
+    Loaded(Option<ChatLatency>),
+    Error(String),
+}
+
+enum SaveStatus {
+    Ready,
+    Saving,
+    Error(String),
+}
+
+#[derive(Debug, Clone)]
+pub enum Msg {
+    ClickedRefresh,
+    LoadedLedger(Result<BudgetLedger, String>),
+    LoadedChatLatency(Result<Option<ChatLatency>, String>),
+    BudgetInputChanged(String),
+    ClickedStartRun,
+    ClickedStartRunWithoutBudget,
+    RunStarted(Result<(), String>),
+}
+
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct Storage {}
+
+impl Model {
+    pub fn new(_storage: &Storage) -> Self {
+        Self {
+            budget_input: String::new(),
+            ledger: LedgerStatus::Loading,
+            chat_latency: ChatLatencyStatus::Loading,
+            save_status: SaveStatus::Ready,
+        }
+    }
+
+    pub fn to_storage(&self) -> Storage {
+        Storage {}
+    }
+
+    pub fn on_tab_activated(&mut self, worker: Arc<Worker>) -> Task<Msg> {
+        self.ledger = LedgerStatus::Loading;
+        self.chat_latency = ChatLatencyStatus::Loading;
+
+        let latency_worker = worker.clone();
+
+        Task::batch([
+            Task::perform(
+                async move { BudgetLedger::load(worker.as_ref()).await },
+                Msg::LoadedLedger,
+            ),
+            Task::perform(
+                async move { ChatLatency::load(latency_worker.as_ref()).await },
+                Msg::LoadedChatLatency,
+            ),
+        ])
+    }
+
+    pub fn update(&mut self, worker: Arc<Worker>, msg: Msg) -> Task<Msg> {
+        match msg {
+            Msg::ClickedRefresh => self.on_tab_activated(worker),
+            Msg::LoadedLedger(result) => {
+                self.ledger = match result {
+                    Ok(ledger) => {
+                        if let Some(budget_usd) = ledger.budget.budget_usd {
+                            self.budget_input = format!("{:.2}", budget_usd);
+                        }
+                        LedgerStatus::Loaded(ledger)
+                    }
+                    Err(err) => LedgerStatus::Error(err),
+                };
+                Task::none()
+            }
+            Msg::LoadedChatLatency(result) => {
+                self.chat_latency = match result {
+                    Ok(latency) => ChatLatencyStatus::Loaded(latency),
+                    Err(err) => ChatLatencyStatus::Error(err),
+                };
+                Task::none()
+            }
+            Msg::BudgetInputChanged(value) => {
+                self.budget_input = value;
+                Task::none()
+            }
+            Msg::ClickedStartRun => match budget::parse_budget_usd(&self.budget_input) {
+                Ok(budget_usd) => self.start_run(worker, Some(budget_usd)),
+                Err(err) => {
+                    self.save_status = SaveStatus::Error(err);
+                    Task::none()
+                }
+            },
+            Msg::ClickedStartRunWithoutBudget => self.start_run(worker, None),
+            Msg::RunStarted(result) => match result {
+                Ok(()) => {
+                    self.save_status = SaveStatus::Ready;
+                    self.on_tab_activated(worker)
+                }
+                Err(err) => {
+                    self.save_status = SaveStatus::Error(err);
+                    Task::none()
+                }
+            },
+        }
+    }
+
+    fn start_run(&mut self, worker: Arc<Worker>, budget_usd: Option<f64>) -> Task<Msg> {
+        self.save_status = SaveStatus::Saving;
+
+        Task::perform(
+            async move { worker.set_run_budget(budget_usd).await },
+            Msg::RunStarted,
+        )
+    }
+
+    pub fn view(&self) -> Element<'_, Msg> {
+        let budget_row = w::row![
+            w::text("Budget ($)"),
+            w::text_input("", &self.budget_input)
+                .on_input(Msg::BudgetInputChanged)
+                .on_submit(Msg::ClickedStartRun)
+                .width(iced::Length::Fixed(120.0)),
+            w::button("Start run").on_press(Msg::ClickedStartRun),
+            w::button("Start run without budget").on_press(Msg::ClickedStartRunWithoutBudget),
+            save_status_view(&self.save_status),
+        ]
+        .spacing(s::S4);
+
+        w::column![
+            w::text("Budget").size(20),
+            w::text(
+                "Starting a run resets the spend. The job runner pauses once the budget is spent."
+            )
+            .size(s::S3),
+            budget_row,
+            w::button("Refresh").on_press(Msg::ClickedRefresh),
+            w::horizontal_rule(1),
+            chat_latency_view(&self.chat_latency),
+            w::horizontal_rule(1),
+            ledger_view(&self.ledger),
+        ]
+        .spacing(s::S4)
+        .into()
+    }
+}
+
+fn save_status_view(status: &SaveStatus) -> Element<'_, Msg> {
+    match status {
+        SaveStatus::Ready => w::text("").into(),
+        SaveStatus::Saving => w::text("Saving...").into(),
+        SaveStatus::Error(err) => w::text(format!("Error: {}", err)).color(s::RED_SOFT).into(),
+    }
+}
+
+fn chat_latency_view(status: &ChatLatencyStatus) -> Element<'_, Msg> {
+    let latency = match status {
+        ChatLatencyStatus::Loading => return w::text("Loading...").into(),
+        ChatLatencyStatus::Error(err) => return w::text(format!("Error: {}", err)).into(),
+        ChatLatencyStatus::Loaded(None) => {
+            return w::text("Chat latency: no replies to you in the last day").into()
+        }
+        ChatLatencyStatus::Loaded(Some(latency)) => latency,
+    };
+
+    let p95 = w::text(format!("p95 {}", seconds_text(latency.p95_ms)));
+    let p95 = if latency.meets_target() {
+        p95.color(s::GREEN_SOFT)
+    } else {
+        p95.color(s::RED_SOFT)
+    };
+
+    w::row![
+        w::text(format!(
+            "Chat latency over {} replies in the last day: p50 {},",
+            latency.exchanges,
+            seconds_text(latency.p50_ms)
+        )),
+        p95,
+        w::text(format!(
+            "(target {})",
+            seconds_text(chat_latency::P95_TARGET_MS)
+        ))
+        .size(s::S3),
+    ]
+    .spacing(s::S2)
+    .into()
+}
+
+fn seconds_text(ms: i64) -> String {
+    format!("{:.1}s", ms as f64 / 1000.0)
+}
+
+fn ledger_view(status: &LedgerStatus) -> Element<'_, Msg> {
+    let ledger = match status {
+        LedgerStatus::Loading => return w::text("Loading...").into(),
+        LedgerStatus::Error(err) => return w::text(format!("Error: {}", err)).into(),
+        LedgerStatus::Loaded(ledger) => ledger,
+    };
+
+    let now = Utc::now();
+
+    let budget_text = match ledger.budget.budget_usd {
+        Some(budget_usd) => format!("${:.2}", budget_usd),
+        None => "none".to_string(),
+    };
+
+    let remaining = match ledger.remaining_usd() {
+        Some(remaining_usd) if ledger.is_exhausted() => {
+            w::text(format!("${:.2} (spent)", remaining_usd)).color(s::RED_SOFT)
+        }
+        Some(remaining_usd) => w::text(format!("${:.2}", remaining_usd)).color(s::GREEN_SOFT),
+        None => w::text("-"),
+    };
+
+    let projection = match ledger.projected_exhaustion(now) {
+        Some(at) => format!(
+            "{} (in {})",
+            time_display::format_absolute(at),
+            hours_text((at - now).num_minutes())
+        ),
+        None => "-".to_string(),
+    };
+
+    let mut col = w::column![
+        w::text(format!(
+            "Run started {}",
+            time_display::format_absolute(ledger.budget.set_at)
+        )),
+        w::text(format!("Budget: {}", budget_text)),
+        w::text(format!("Spent: ${:.4}", ledger.spend.spent_usd)),
+        w::row![w::text("Remaining:"), remaining].spacing(s::S2),
+        w::text(format!(
+            "Burn rate: ${:.4} an hour",
+            ledger.burn_rate_usd_per_hour(now)
+        )),
+        w::text(format!("Projected to run out: {}", projection)),
+    ]
+    .spacing(s::S2);
+
+    if ledger.spend.unpriced_calls > 0 {
+        col = col.push(
+            w::text(format!(
+                "{} calls used models without a price in llm_price and are not counted",
+                ledger.spend.unpriced_calls
+            ))
+            .color(s::GOLD_SOFT),
+        );
+    }
+
+    let burn_down = ledger.burn_down();
+    if !burn_down.is_empty() {
+        col = col.push(w::horizontal_rule(1));
+        col = col.push(w::text("Burn-down").size(s::S4));
+
+        for (hour, remaining_usd) in burn_down {
+            col = col.push(
+                w::text(format!(
+                    "{}  ${:.4} left",
+                    hour.format("%Y-%m-%d %H:00"),
+                    remaining_usd
+                ))
+                .size(s::S3),
+            );
+        }
+    }
+
+    w::scrollable(col).into()
+}
+
+fn hours_text(minutes: i64) -> String {
+    if minutes < 60 {
+        format!("{}m", minutes)
+    } else {
+        format!("{}h {}m", minutes / 60, minutes % 60)
+    }
+}
@@ -1,23 +1,62 @@
+use crate::open_ai::client::OpenAiClient;
 use crate::open_ai::completion::{Completion, CompletionError};
+use crate::open_ai::model::Model;
 use crate::open_ai::role::Role;
 use crate::open_ai_key::OpenAiKey;
 use crate::person_actions::{PersonActionError, PersonActionKind, PersonReaction};
 
+#[derive(Debug, Clone)]
+pub struct PromptRequest {
+    pub system_prompt: String,
+    /// Sent in order between the system prompt and the prompt.
+    pub turns: Vec<(Role, String)>,
+    pub prompt: String,
+    pub model: Model,
+    pub temperature: Option<f32>,
+    pub max_tokens: Option<u32>,
+    pub json_mode: bool,
+}
+
 pub async fn submit_prompt(
-    open_ai_key: OpenAiKey,
-    client: reqwest::Client,
-    prompt: String,
+    open_ai_key: Option<OpenAiKey>,
+    client: OpenAiClient,
+    request: PromptRequest,
 ) -> Result<String, CompletionError> {
-    let response = Completion::new()
-        .add_message(Role::User, prompt.as_str())
-        .send_request(&open_ai_key, client)
+    let mut completion = Completion::new();
+    completion.set_model(request.model);
+    completion.set_json_mode(request.json_mode);
+
+    if let Some(temperature) = request.temperature {
+        completion.set_temperature(temperature);
+    }
+
+    if let Some(max_tokens) = request.max_tokens {
+        completion.set_max_tokens(max_tokens);
+    }
+
+    if !request.system_prompt.trim().is_empty() {
+        completion.add_message(Role::System, request.system_prompt.trim());
+    }
+
+    for (role, content) in request.turns {
+        completion.add_message(role, content.as_str());
+    }
+
+    // The history can end on a user message of its own
+    if !request.prompt.trim().is_empty() {
+        completion.add_message(Role::User, request.prompt.as_str());
+    }
+
+    let response = completion
+        .send_request(open_ai_key.as_ref(), client)
         .await?;
 
     response.as_message().map_err(Into::into)
 }
 
 pub async fn submit_reaction(
-    open_ai_key: OpenAiKey,
+    open_ai_key: Option<OpenAiKey>,
+    client: OpenAiClient,
     memories: Vec<String>,
     person_identity: String,
     situation: String,
@@ -50,7 +89,7 @@ pub async fn submit_reaction(
     completion.add_tool_call(PersonActionKind::to_choice_tool());
 
     let response = completion
-        .send_request(&open_ai_key, reqwest::Client::new())
+        .send_request(open_ai_key.as_ref(), client)
         .await?;
 
     let tool_calls = response
@@ -67,7 +106,8 @@ pub async fn submit_reaction(
 }
 
 pub async fn submit_prompt_lab(
-    open_ai_key: OpenAiKey,
+    open_ai_key: Option<OpenAiKey>,
+    client: OpenAiClient,
     system_prompt: String,
     user_prompt: String,
 ) -> Result<String, CompletionError> {
@@ -81,7 +121,7 @@ pub async fn submit_prompt_lab(
     completion.add_tool_call(PersonActionKind::to_choice_tool());
 
     let response = completion
-        .send_request(&open_ai_key, reqwest::Client::new())
+        .send_request(open_ai_key.as_ref(), client)
         .await?;
 
     Ok(response.as_pretty_json())
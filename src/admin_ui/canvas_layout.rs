@@ -0,0 +1,20 @@
+use iced::Point;
+use std::f32::consts::PI;
+
+/// Spreads `count` nodes evenly around a circle, starting at the top and
+/// going clockwise. A single node sits in the middle.
+pub fn ring_positions(count: usize, center: Point, radius: f32) -> Vec<Point> {
+    if count == 1 {
+        return vec![center];
+    }
+
+    (0..count)
+        .map(|index| {
+            let angle = 2.0 * PI * index as f32 / count as f32 - PI / 2.0;
+            Point::new(
+                center.x + radius * angle.cos(),
+                center.y + radius * angle.sin(),
+            )
+        })
+        .collect()
+}
@@ -0,0 +1,374 @@
+use crate::admin_ui::{canvas_layout, s};
+use crate::capability::conversation_graph::ConversationGraphCapability;
+use crate::domain::conversation_graph::{ConversationEdge, ConversationGraph};
+use crate::worker::Worker;
+use chrono::{DateTime, Duration, Utc};
+use iced::widget::canvas::{self, Canvas, Frame, Geometry, Path, Stroke, Text};
+use iced::{
+    alignment, mouse, widget as w, Color, Element, Length, Pixels, Point, Rectangle, Renderer,
+    Task, Theme, Vector,
+};
+use serde::{Deserialize, Serialize};
+use std::cmp::Reverse;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+const GRAPH_HEIGHT: f32 = 560.0;
+const NODE_RADIUS: f32 = 10.0;
+/// Room left around the ring for the name labels.
+const GRAPH_MARGIN: f32 = 64.0;
+/// How far apart the two directions of a conversation are drawn, so both
+/// stay visible.
+const EDGE_OFFSET: f32 = 3.0;
+const TOP_EDGES_SHOWN: usize = 10;
+
+pub struct Model {
+    range: TimeRange,
+    status: Status,
+    cache: canvas::Cache,
+}
+
+enum Status {
+    Loading,
+    Loaded(ConversationGraph),
+    Error(String),
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TimeRange {
+    LastHour,
+    #[default]
+    LastDay,
+    LastWeek,
+    AllTime,
+}
+
+impl TimeRange {
+    fn all() -> Vec<TimeRange> {
+        vec![
+            TimeRange::LastHour,
+            TimeRange::LastDay,
+            TimeRange::LastWeek,
+            TimeRange::AllTime,
+        ]
+    }
+
+    fn to_label(self) -> String {
+        match self {
+            TimeRange::LastHour => "Last hour".to_string(),
+            TimeRange::LastDay => "Last day".to_string(),
+            TimeRange::LastWeek => "Last week".to_string(),
+            TimeRange::AllTime => "All time".to_string(),
+        }
+    }
+
+    fn since(self, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        match self {
+            TimeRange::LastHour => Some(now - Duration::hours(1)),
+            TimeRange::LastDay => Some(now - Duration::days(1)),
+            TimeRange::LastWeek => Some(now - Duration::weeks(1)),
+            TimeRange::AllTime => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum Msg {
+    ClickedRefresh,
+    RangeSelected(TimeRange),
+    Loaded(Result<ConversationGraph, String>),
+}
+
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct Storage {
+    #[serde(default)]
+    range: TimeRange,
+}
+
+impl Model {
+    pub fn new(storage: &Storage) -> Self {
+        Self {
+            range: storage.range,
+            status: Status::Loading,
+            cache: canvas::Cache::new(),
+        }
+    }
+
+    pub fn to_storage(&self) -> Storage {
+        Storage { range: self.range }
+    }
+
+    pub fn on_tab_activated(&mut self, worker: Arc<Worker>) -> Task<Msg> {
+        self.load(worker)
+    }
+
+    pub fn update(&mut self, worker: Arc<Worker>, msg: Msg) -> Task<Msg> {
+        match msg {
+            Msg::ClickedRefresh => self.load(worker),
+            Msg::RangeSelected(range) => {
+                self.range = range;
+                self.load(worker)
+            }
+            Msg::Loaded(result) => {
+                self.status = match result {
+                    Ok(graph) => Status::Loaded(graph),
+                    Err(err) => Status::Error(err),
+                };
+                self.cache.clear();
+                Task::none()
+            }
+        }
+    }
+
+    fn load(&mut self, worker: Arc<Worker>) -> Task<Msg> {
+        self.status = Status::Loading;
+        let since = self.range.since(Utc::now());
+
+        Task::perform(
+            async move { worker.get_conversation_graph(since).await },
+            Msg::Loaded,
+        )
+    }
+
+    pub fn view(&self) -> Element<'_, Msg> {
+        let mut range_row = w::row![].spacing(s::S4);
+        for range in TimeRange::all() {
+            range_row = range_row.push(w::radio(
+                range.to_label(),
+                range,
+                Some(self.range),
+                Msg::RangeSelected,
+            ));
+        }
+        range_row = range_row.push(w::button("Refresh").on_press(Msg::ClickedRefresh));
+
+        let body: Element<Msg> = match &self.status {
+            Status::Loading => w::text("Loading conversations...").into(),
+            Status::Error(err) => w::text(format!("Error: {}", err)).color(s::RED_SOFT).into(),
+            Status::Loaded(graph) => {
+                if graph.people.is_empty() {
+                    w::text("There is nobody to graph").into()
+                } else {
+                    let canvas = Canvas::new(GraphCanvas {
+                        graph,
+                        cache: &self.cache,
+                    })
+                    .width(Length::Fill)
+                    .height(Length::Fixed(GRAPH_HEIGHT));
+
+                    w::column![
+                        canvas,
+                        w::text("Line width is how many messages, color is how warm they were")
+                            .size(s::S3)
+                            .color(s::GRAY_MID),
+                        view_summary(graph),
+                    ]
+                    .spacing(s::S4)
+                    .into()
+                }
+            }
+        };
+
+        w::column![w::text("Conversation Graph").size(20), range_row, body]
+            .spacing(s::S4)
+            .into()
+    }
+}
+
+fn view_summary(graph: &ConversationGraph) -> Element<'_, Msg> {
+    let names = graph
+        .people
+        .iter()
+        .map(|person| (person.person_uuid, person.name.as_str()))
+        .collect::<HashMap<Option<uuid::Uuid>, &str>>();
+    let name_of = |person_uuid: &Option<uuid::Uuid>| -> String {
+        match names.get(person_uuid) {
+            Some(name) => name.to_string(),
+            None => "someone disabled".to_string(),
+        }
+    };
+
+    let isolated = graph
+        .isolated_people()
+        .iter()
+        .map(|person| person.name.clone())
+        .collect::<Vec<String>>();
+    let isolated_text = if isolated.is_empty() {
+        "Nobody".to_string()
+    } else {
+        isolated.join(", ")
+    };
+
+    let mut groups_col = w::column![w::text("Groups").size(16)].spacing(s::S2);
+    let groups = graph.groups();
+    if groups.is_empty() {
+        groups_col = groups_col.push(w::text("Nobody talked"));
+    }
+    for group in groups {
+        let members = group
+            .iter()
+            .map(|person| person.name.clone())
+            .collect::<Vec<String>>();
+        groups_col = groups_col.push(w::text(members.join(", ")));
+    }
+
+    let mut top_edges = graph.edges.iter().collect::<Vec<&ConversationEdge>>();
+    top_edges.sort_by_key(|edge| Reverse(edge.message_count));
+
+    let mut edges_col = w::column![w::text("Most talkative pairs").size(16)].spacing(s::S2);
+    for edge in top_edges.into_iter().take(TOP_EDGES_SHOWN) {
+        edges_col = edges_col.push(
+            w::text(format!(
+                "{} -> {}: {} messages, sentiment {:+.2}",
+                name_of(&edge.from),
+                name_of(&edge.to),
+                edge.message_count,
+                edge.sentiment()
+            ))
+            .color(sentiment_color(edge.sentiment())),
+        );
+    }
+
+    w::column![
+        w::row![
+            w::text("Isolated").size(16),
+            w::text(isolated_text).color(s::GRAY_SOFT)
+        ]
+        .spacing(s::S4),
+        groups_col,
+        edges_col,
+    ]
+    .spacing(s::S4)
+    .into()
+}
+
+/// Red for negative, gray for neutral, green for positive.
+fn sentiment_color(sentiment: f32) -> Color {
+    let (towards, amount) = if sentiment < 0.0 {
+        (s::RED_SOFT, -sentiment)
+    } else {
+        (s::GREEN_SOFT, sentiment)
+    };
+    let amount = amount.clamp(0.0, 1.0);
+
+    Color::from_rgb(
+        s::GRAY_MID.r + (towards.r - s::GRAY_MID.r) * amount,
+        s::GRAY_MID.g + (towards.g - s::GRAY_MID.g) * amount,
+        s::GRAY_MID.b + (towards.b - s::GRAY_MID.b) * amount,
+    )
+}
+
+struct GraphCanvas<'a> {
+    graph: &'a ConversationGraph,
+    cache: &'a canvas::Cache,
+}
+
+impl<Msg> canvas::Program<Msg> for GraphCanvas<'_> {
+    type State = ();
+
+    fn draw(
+        &self,
+        _state: &Self::State,
+        renderer: &Renderer,
+        _theme: &Theme,
+        bounds: Rectangle,
+        _cursor: mouse::Cursor,
+    ) -> Vec<Geometry> {
+        let geometry = self.cache.draw(renderer, bounds.size(), |frame| {
+            draw_graph(frame, self.graph);
+        });
+
+        vec![geometry]
+    }
+}
+
+fn draw_graph(frame: &mut Frame, graph: &ConversationGraph) {
+    let center = frame.center();
+    let ring_radius = (frame.width().min(frame.height()) / 2.0 - GRAPH_MARGIN).max(0.0);
+
+    let positions = graph
+        .people
+        .iter()
+        .map(|person| person.person_uuid)
+        .zip(canvas_layout::ring_positions(
+            graph.people.len(),
+            center,
+            ring_radius,
+        ))
+        .collect::<HashMap<Option<uuid::Uuid>, Point>>();
+
+    let max_count = graph
+        .edges
+        .iter()
+        .map(|edge| edge.message_count)
+        .max()
+        .unwrap_or(1)
+        .max(1);
+
+    for edge in &graph.edges {
+        let (from, to) = match (positions.get(&edge.from), positions.get(&edge.to)) {
+            (Some(from), Some(to)) => (*from, *to),
+            _ => continue,
+        };
+
+        // Shift each direction to its own side of the line between the two
+        let direction = to - from;
+        let length = (direction.x * direction.x + direction.y * direction.y).sqrt();
+        let offset = if length > 0.0 {
+            Vector::new(
+                -direction.y / length * EDGE_OFFSET,
+                direction.x / length * EDGE_OFFSET,
+            )
+        } else {
+            Vector::new(0.0, 0.0)
+        };
+
+        let width = 1.0 + 5.0 * edge.message_count as f32 / max_count as f32;
+        frame.stroke(
+            &Path::line(from + offset, to + offset),
+            Stroke::default()
+                .with_width(width)
+                .with_color(sentiment_color(edge.sentiment())),
+        );
+    }
+
+    let isolated = graph
+        .isolated_people()
+        .iter()
+        .map(|person| person.person_uuid)
+        .collect::<Vec<Option<uuid::Uuid>>>();
+
+    for person in &graph.people {
+        let position = match positions.get(&person.person_uuid) {
+            Some(position) => *position,
+            None => continue,
+        };
+
+        let fill = if isolated.contains(&person.person_uuid) {
+            s::GRAY_DEEP
+        } else {
+            s::GOLD_SOFT
+        };
+
+        frame.fill(&Path::circle(position, NODE_RADIUS), fill);
+
+        // Labels go outward from the middle so they do not sit on the lines
+        let outward = position - center;
+        let distance = (outward.x * outward.x + outward.y * outward.y).sqrt();
+        let label_position = if distance > 0.0 {
+            position + outward * ((NODE_RADIUS + s::S3) / distance)
+        } else {
+            Point::new(position.x, position.y + NODE_RADIUS + s::S2)
+        };
+
+        frame.fill_text(Text {
+            content: person.name.clone(),
+            position: label_position,
+            color: s::GRAY_VERY_SOFT,
+            size: Pixels(13.0),
+            horizontal_alignment: alignment::Horizontal::Center,
+            vertical_alignment: alignment::Vertical::Center,
+            ..Text::default()
+        });
+    }
+}
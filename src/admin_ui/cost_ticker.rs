@@ -0,0 +1,108 @@
+use crate::admin_ui::s;
+use crate::capability::budget::BudgetCapability;
+use crate::domain::budget::{BudgetLedger, SpendLevel};
+use crate::time_display;
+use crate::worker::Worker;
+use chrono::Utc;
+use iced::{time, widget as w, Element, Subscription, Task};
+use std::sync::Arc;
+use std::time::Duration;
+
+const POLL_INTERVAL: Duration = Duration::from_secs(30);
+
+/// What the completions have cost today and this run, shown above every tab
+/// so runaway spending is noticed while it is happening.
+pub struct Model {
+    status: Status,
+}
+
+enum Status {
+    Loading,
+    Loaded(CostSnapshot),
+    Error(String),
+}
+
+#[derive(Debug, Clone)]
+pub struct CostSnapshot {
+    today_usd: f64,
+    ledger: BudgetLedger,
+}
+
+#[derive(Debug, Clone)]
+pub enum Msg {
+    Tick,
+    Loaded(Result<CostSnapshot, String>),
+}
+
+impl Model {
+    pub fn new() -> Self {
+        Self {
+            status: Status::Loading,
+        }
+    }
+
+    pub fn load(&self, worker: Arc<Worker>) -> Task<Msg> {
+        Task::perform(async move { load_snapshot(&worker).await }, Msg::Loaded)
+    }
+
+    pub fn update(&mut self, worker: Arc<Worker>, msg: Msg) -> Task<Msg> {
+        match msg {
+            Msg::Tick => self.load(worker),
+            Msg::Loaded(result) => {
+                self.status = match result {
+                    Ok(snapshot) => Status::Loaded(snapshot),
+                    Err(err) => Status::Error(err),
+                };
+                Task::none()
+            }
+        }
+    }
+
+    pub fn view(&self) -> Element<'_, Msg> {
+        match &self.status {
+            Status::Loading => w::text("Spend: loading...").color(s::GRAY_MID).into(),
+            Status::Loaded(snapshot) => {
+                let run_text = match snapshot.ledger.budget.budget_usd {
+                    Some(budget_usd) => format!(
+                        "${:.2} of ${:.2}",
+                        snapshot.ledger.spend.spent_usd, budget_usd
+                    ),
+                    None => format!("${:.2}", snapshot.ledger.spend.spent_usd),
+                };
+
+                let color = match SpendLevel::of(snapshot.today_usd, &snapshot.ledger) {
+                    SpendLevel::Normal => s::GREEN_SOFT,
+                    SpendLevel::Warning => s::GOLD_SOFT,
+                    SpendLevel::Alert => s::RED_SOFT,
+                };
+
+                w::text(format!(
+                    "Spent today: ${:.2}, this run: {}",
+                    snapshot.today_usd, run_text
+                ))
+                .color(color)
+                .into()
+            }
+            Status::Error(err) => w::text(format!("Spend: {}", err))
+                .size(s::S3)
+                .color(s::RED_SOFT)
+                .into(),
+        }
+    }
+
+    pub fn subscription(&self) -> Subscription<Msg> {
+        time::every(POLL_INTERVAL).map(|_| Msg::Tick)
+    }
+}
+
+async fn load_snapshot(worker: &Worker) -> Result<CostSnapshot, String> {
+    let today = time_display::start_of_day(Utc::now());
+
+    let (today_spend, ledger) =
+        tokio::try_join!(worker.get_llm_spend(today), BudgetLedger::load(worker))?;
+
+    Ok(CostSnapshot {
+        today_usd: today_spend.spent_usd,
+        ledger,
+    })
+}
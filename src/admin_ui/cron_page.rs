@@ -0,0 +1,392 @@
+use crate::admin_ui::s;
+use crate::capability::cron_job::CronJobCapability;
+use crate::capability::maintenance::MaintenanceCapability;
+use crate::domain::cron_job::{self, CronJob, NewCronJob};
+use crate::domain::cron_job_uuid::CronJobUuid;
+use crate::domain::job::nightly_maintenance::MaintenanceReport;
+use crate::time_display;
+use crate::worker::Worker;
+use chrono::Utc;
+use iced::{widget as w, Element, Task};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+pub struct Model {
+    cron_jobs: CronJobsStatus,
+    maintenance_report: MaintenanceReportStatus,
+    name_input: String,
+    expression_input: String,
+    job_name_input: String,
+    job_data: w::text_editor::Content,
+    /// Set while the form is editing an existing cron job rather than
+    /// adding one.
+    editing: Option<CronJobUuid>,
+    save_status: SaveStatus,
+}
+
+enum CronJobsStatus {
+    Loading,
+    Loaded(Vec<CronJob>),
+    Error(String),
+}
+
+enum MaintenanceReportStatus {
+    Loading,
+    Loaded(Option<MaintenanceReport>),
+    Error(String),
+}
+
+enum SaveStatus {
+    Ready,
+    Saving,
+    Error(String),
+}
+
+#[derive(Debug, Clone)]
+pub enum Msg {
+    ClickedRefresh,
+    LoadedCronJobs(Result<Vec<CronJob>, String>),
+    LoadedMaintenanceReport(Result<Option<MaintenanceReport>, String>),
+    NameInputChanged(String),
+    ExpressionInputChanged(String),
+    JobNameInputChanged(String),
+    JobDataUpdated(w::text_editor::Action),
+    ClickedSave,
+    ClickedEdit(CronJob),
+    ClickedCancelEdit,
+    ToggledEnabled(CronJobUuid, bool),
+    ClickedDelete(CronJobUuid),
+    Saved(Result<(), String>),
+}
+
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct Storage {}
+
+impl Model {
+    pub fn new(_storage: &Storage) -> Self {
+        Self {
+            cron_jobs: CronJobsStatus::Loading,
+            maintenance_report: MaintenanceReportStatus::Loading,
+            name_input: String::new(),
+            expression_input: String::new(),
+            job_name_input: String::new(),
+            job_data: w::text_editor::Content::new(),
+            editing: None,
+            save_status: SaveStatus::Ready,
+        }
+    }
+
+    pub fn to_storage(&self) -> Storage {
+        Storage {}
+    }
+
+    pub fn on_tab_activated(&mut self, worker: Arc<Worker>) -> Task<Msg> {
+        self.cron_jobs = CronJobsStatus::Loading;
+        self.maintenance_report = MaintenanceReportStatus::Loading;
+
+        let report_worker = worker.clone();
+
+        Task::batch([
+            Task::perform(
+                async move { worker.get_cron_jobs().await },
+                Msg::LoadedCronJobs,
+            ),
+            Task::perform(
+                async move { report_worker.get_latest_maintenance_report().await },
+                Msg::LoadedMaintenanceReport,
+            ),
+        ])
+    }
+
+    pub fn update(&mut self, worker: Arc<Worker>, msg: Msg) -> Task<Msg> {
+        match msg {
+            Msg::ClickedRefresh => self.on_tab_activated(worker),
+            Msg::LoadedCronJobs(result) => {
+                self.cron_jobs = match result {
+                    Ok(cron_jobs) => CronJobsStatus::Loaded(cron_jobs),
+                    Err(err) => CronJobsStatus::Error(err),
+                };
+                Task::none()
+            }
+            Msg::LoadedMaintenanceReport(result) => {
+                self.maintenance_report = match result {
+                    Ok(report) => MaintenanceReportStatus::Loaded(report),
+                    Err(err) => MaintenanceReportStatus::Error(err),
+                };
+                Task::none()
+            }
+            Msg::NameInputChanged(value) => {
+                self.name_input = value;
+                Task::none()
+            }
+            Msg::ExpressionInputChanged(value) => {
+                self.expression_input = value;
+                Task::none()
+            }
+            Msg::JobNameInputChanged(value) => {
+                self.job_name_input = value;
+                Task::none()
+            }
+            Msg::JobDataUpdated(action) => {
+                self.job_data.perform(action);
+                Task::none()
+            }
+            Msg::ClickedSave => {
+                let new_cron_job = match NewCronJob::parse(
+                    &self.name_input,
+                    &self.expression_input,
+                    &self.job_name_input,
+                    &self.job_data.text(),
+                ) {
+                    Ok(new_cron_job) => new_cron_job,
+                    Err(err) => {
+                        self.save_status = SaveStatus::Error(err);
+                        return Task::none();
+                    }
+                };
+
+                self.save_status = SaveStatus::Saving;
+                let editing = self.editing.clone();
+                Task::perform(
+                    async move {
+                        match editing {
+                            Some(cron_job_uuid) => {
+                                worker.update_cron_job(&cron_job_uuid, &new_cron_job).await
+                            }
+                            None => worker.create_cron_job(&new_cron_job).await.map(|_| ()),
+                        }
+                    },
+                    Msg::Saved,
+                )
+            }
+            Msg::ClickedEdit(cron_job) => {
+                let job_data = match &cron_job.job_data {
+                    Some(data) => serde_json::to_string_pretty(data).unwrap_or_default(),
+                    None => String::new(),
+                };
+
+                self.name_input = cron_job.name;
+                self.expression_input = cron_job.expression;
+                self.job_name_input = cron_job.job_name;
+                self.job_data = w::text_editor::Content::with_text(&job_data);
+                self.editing = Some(cron_job.uuid);
+                self.save_status = SaveStatus::Ready;
+                Task::none()
+            }
+            Msg::ClickedCancelEdit => {
+                self.clear_form();
+                Task::none()
+            }
+            Msg::ToggledEnabled(cron_job_uuid, enabled) => Task::perform(
+                async move { worker.set_cron_job_enabled(&cron_job_uuid, enabled).await },
+                Msg::Saved,
+            ),
+            Msg::ClickedDelete(cron_job_uuid) => {
+                if self.editing.as_ref() == Some(&cron_job_uuid) {
+                    self.clear_form();
+                }
+
+                Task::perform(
+                    async move { worker.delete_cron_job(&cron_job_uuid).await },
+                    Msg::Saved,
+                )
+            }
+            Msg::Saved(result) => match result {
+                Ok(()) => {
+                    if let SaveStatus::Saving = self.save_status {
+                        self.clear_form();
+                    }
+                    self.on_tab_activated(worker)
+                }
+                Err(err) => {
+                    self.save_status = SaveStatus::Error(err);
+                    Task::none()
+                }
+            },
+        }
+    }
+
+    fn clear_form(&mut self) {
+        self.name_input = String::new();
+        self.expression_input = String::new();
+        self.job_name_input = String::new();
+        self.job_data = w::text_editor::Content::new();
+        self.editing = None;
+        self.save_status = SaveStatus::Ready;
+    }
+
+    pub fn view(&self) -> Element<'_, Msg> {
+        let form_title = match self.editing {
+            Some(_) => "Edit cron job",
+            None => "New cron job",
+        };
+
+        let mut buttons = w::row![w::button("Save").on_press(Msg::ClickedSave)].spacing(s::S4);
+        if self.editing.is_some() {
+            buttons = buttons.push(w::button("Cancel").on_press(Msg::ClickedCancelEdit));
+        }
+        buttons = buttons.push(save_status_view(&self.save_status));
+
+        let form = w::column![
+            w::text(form_title).size(s::S4),
+            w::row![
+                w::text("Name"),
+                w::text_input("like \"Nightly idle scan\"", &self.name_input)
+                    .on_input(Msg::NameInputChanged),
+            ]
+            .spacing(s::S4),
+            w::row![
+                w::text("Schedule"),
+                w::text_input("like \"0 3 * * *\"", &self.expression_input)
+                    .on_input(Msg::ExpressionInputChanged)
+                    .on_submit(Msg::ClickedSave),
+            ]
+            .spacing(s::S4),
+            expression_preview(&self.expression_input),
+            w::row![
+                w::text("Job"),
+                w::text_input("like \"wake idle persons\"", &self.job_name_input)
+                    .on_input(Msg::JobNameInputChanged),
+            ]
+            .spacing(s::S4),
+            w::text("Job data (json, blank for jobs that take none)"),
+            w::text_editor(&self.job_data)
+                .on_action(Msg::JobDataUpdated)
+                .height(iced::Length::Fixed(100.0)),
+            buttons,
+        ]
+        .spacing(s::S4);
+
+        w::column![
+            w::text("Cron").size(20),
+            w::text(
+                "The job runner enqueues these jobs on their schedule, in UTC, while it is running. Runs missed while it was off are not made up."
+            )
+            .size(s::S3),
+            form,
+            w::horizontal_rule(1),
+            maintenance_report_view(&self.maintenance_report),
+            w::horizontal_rule(1),
+            w::button("Refresh").on_press(Msg::ClickedRefresh),
+            cron_jobs_view(&self.cron_jobs),
+        ]
+        .spacing(s::S4)
+        .into()
+    }
+}
+
+fn expression_preview(expression: &str) -> Element<'_, Msg> {
+    if expression.trim().is_empty() {
+        return w::text("Minute, hour, day of month, month, day of week")
+            .size(s::S3)
+            .into();
+    }
+
+    match cron_job::next_run_after(expression, Utc::now()) {
+        Ok(next_run_at) => w::text(format!(
+            "Next run: {}",
+            time_display::format_absolute(next_run_at)
+        ))
+        .size(s::S3)
+        .into(),
+        Err(err) => w::text(err).size(s::S3).color(s::RED_SOFT).into(),
+    }
+}
+
+fn save_status_view(status: &SaveStatus) -> Element<'_, Msg> {
+    match status {
+        SaveStatus::Ready => w::text("").into(),
+        SaveStatus::Saving => w::text("Saving...").into(),
+        SaveStatus::Error(err) => w::text(format!("Error: {}", err)).color(s::RED_SOFT).into(),
+    }
+}
+
+fn maintenance_report_view(status: &MaintenanceReportStatus) -> Element<'_, Msg> {
+    let report = match status {
+        MaintenanceReportStatus::Loading => return w::text("Loading...").into(),
+        MaintenanceReportStatus::Error(err) => {
+            return w::text(format!("Error: {}", err)).color(s::RED_SOFT).into()
+        }
+        MaintenanceReportStatus::Loaded(None) => {
+            return w::text("Nightly maintenance has not run yet").into()
+        }
+        MaintenanceReportStatus::Loaded(Some(report)) => report,
+    };
+
+    let failed = report.failed_step_count();
+    let summary = if failed == 0 {
+        w::text(format!(
+            "Finished {}, every step went fine",
+            time_display::format_recent(report.finished_at)
+        ))
+    } else {
+        w::text(format!(
+            "Finished {}, {} of {} steps failed",
+            time_display::format_recent(report.finished_at),
+            failed,
+            report.steps.len()
+        ))
+        .color(s::RED_SOFT)
+    };
+
+    let mut col = w::column![
+        w::text("Last nightly maintenance").size(s::S4),
+        summary.size(s::S3)
+    ]
+    .spacing(s::S2);
+
+    for line in report.to_lines() {
+        col = col.push(w::text(line).size(s::S3));
+    }
+
+    col.into()
+}
+
+fn cron_jobs_view(status: &CronJobsStatus) -> Element<'_, Msg> {
+    let cron_jobs = match status {
+        CronJobsStatus::Loading => return w::text("Loading...").into(),
+        CronJobsStatus::Error(err) => return w::text(format!("Error: {}", err)).into(),
+        CronJobsStatus::Loaded(cron_jobs) => cron_jobs,
+    };
+
+    if cron_jobs.is_empty() {
+        return w::text("No cron jobs yet").into();
+    }
+
+    let mut col = w::column![].spacing(s::S4);
+
+    for cron_job in cron_jobs {
+        let next_run = match cron_job.next_run_at {
+            Some(next_run_at) => time_display::format_absolute(next_run_at),
+            None => "-".to_string(),
+        };
+        let last_run = match cron_job.last_run_at {
+            Some(last_run_at) => time_display::format_absolute(last_run_at),
+            None => "never".to_string(),
+        };
+        let cron_job_uuid = cron_job.uuid.clone();
+
+        col = col.push(
+            w::column![
+                w::row![
+                    w::checkbox("", cron_job.enabled).on_toggle(
+                        move |enabled| Msg::ToggledEnabled(cron_job_uuid.clone(), enabled)
+                    ),
+                    w::text(&cron_job.name).size(s::S4),
+                    w::text(&cron_job.expression).color(s::GOLD_SOFT),
+                    w::text(&cron_job.job_name),
+                ]
+                .spacing(s::S4),
+                w::text(format!("Next run: {}  Last run: {}", next_run, last_run)).size(s::S3),
+                w::row![
+                    w::button("Edit").on_press(Msg::ClickedEdit(cron_job.clone())),
+                    w::button("Delete").on_press(Msg::ClickedDelete(cron_job.uuid.clone())),
+                ]
+                .spacing(s::S4),
+            ]
+            .spacing(s::S2),
+        );
+    }
+
+    w::scrollable(col).into()
+}
@@ -0,0 +1,181 @@
+use crate::admin_ui::s;
+use crate::capability::delivery::DeliveryCapability;
+use crate::domain::delivery::{DeliveryCounts, FailedDelivery};
+use crate::time_display;
+use crate::worker::Worker;
+use chrono::{Duration, Utc};
+use iced::{widget as w, Element, Task};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+const FAILED_DELIVERIES_PAGE_SIZE: i64 = 50;
+const COUNTS_WINDOW_HOURS: i64 = 24;
+
+pub struct Model {
+    counts: CountsStatus,
+    failures: FailuresStatus,
+}
+
+enum CountsStatus {
+    Loading,
+    Loaded(DeliveryCounts),
+    Error(String),
+}
+
+enum FailuresStatus {
+    Loading,
+    Loaded(Vec<FailedDelivery>),
+    Error(String),
+}
+
+#[derive(Debug, Clone)]
+pub enum Msg {
+    ClickedRefresh,
+    LoadedCounts(Result<DeliveryCounts, String>),
+    LoadedFailures(Result<Vec<FailedDelivery>, String>),
+}
+
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct Storage {}
+
+impl Model {
+    pub fn new(_storage: &Storage) -> Self {
+        Self {
+            counts: CountsStatus::Loading,
+            failures: FailuresStatus::Loading,
+        }
+    }
+
+    pub fn to_storage(&self) -> Storage {
+        Storage {}
+    }
+
+    pub fn on_tab_activated(&mut self, worker: Arc<Worker>) -> Task<Msg> {
+        self.counts = CountsStatus::Loading;
+        self.failures = FailuresStatus::Loading;
+
+        let worker2 = worker.clone();
+        let since = Utc::now() - Duration::hours(COUNTS_WINDOW_HOURS);
+        Task::batch(vec![
+            Task::perform(
+                async move { worker.get_delivery_counts_since(since).await },
+                Msg::LoadedCounts,
+            ),
+            Task::perform(
+                async move {
+                    worker2
+                        .get_failed_deliveries(FAILED_DELIVERIES_PAGE_SIZE)
+                        .await
+                },
+                Msg::LoadedFailures,
+            ),
+        ])
+    }
+
+    pub fn update(&mut self, worker: Arc<Worker>, msg: Msg) -> Task<Msg> {
+        match msg {
+            Msg::ClickedRefresh => self.on_tab_activated(worker),
+            Msg::LoadedCounts(result) => {
+                self.counts = match result {
+                    Ok(counts) => CountsStatus::Loaded(counts),
+                    Err(err) => CountsStatus::Error(err),
+                };
+                Task::none()
+            }
+            Msg::LoadedFailures(result) => {
+                self.failures = match result {
+                    Ok(failures) => FailuresStatus::Loaded(failures),
+                    Err(err) => FailuresStatus::Error(err),
+                };
+                Task::none()
+            }
+        }
+    }
+
+    pub fn view(&self) -> Element<'_, Msg> {
+        w::column![
+            w::text("Deliveries").size(20),
+            w::text("Every attempt to hand an outbox event to an integration.").size(s::S3),
+            counts_view(&self.counts),
+            w::button("Refresh").on_press(Msg::ClickedRefresh),
+            w::horizontal_rule(1),
+            failures_view(&self.failures),
+        ]
+        .spacing(s::S4)
+        .into()
+    }
+}
+
+fn counts_view(status: &CountsStatus) -> Element<'_, Msg> {
+    match status {
+        CountsStatus::Loading => w::text("Loading...").into(),
+        CountsStatus::Error(err) => w::text(format!("Error: {}", err)).into(),
+        CountsStatus::Loaded(counts) => {
+            let failed = w::text(format!("{} failed", counts.failed));
+            let failed = if counts.failed > 0 {
+                failed.color(s::RED_SOFT)
+            } else {
+                failed
+            };
+
+            w::row![
+                w::text(format!("Last {} hours:", COUNTS_WINDOW_HOURS)),
+                w::text(format!("{} sent", counts.sent)),
+                w::text(format!("{} retried", counts.retried)),
+                failed,
+            ]
+            .spacing(s::S4)
+            .into()
+        }
+    }
+}
+
+fn failures_view(status: &FailuresStatus) -> Element<'_, Msg> {
+    match status {
+        FailuresStatus::Loading => w::text("Loading failed deliveries...").into(),
+        FailuresStatus::Error(err) => w::text(format!("Error: {}", err)).into(),
+        FailuresStatus::Loaded(failures) => {
+            if failures.is_empty() {
+                return w::text("No delivery has failed").into();
+            }
+
+            let mut col = w::column![].spacing(s::S4);
+
+            for failure in failures {
+                let outcome = if failure.delivered_since {
+                    w::text("delivered since").color(s::GREEN_SOFT)
+                } else {
+                    w::text("still undelivered").color(s::RED_SOFT)
+                };
+
+                let mut item = w::column![
+                    w::row![
+                        w::text(format!(
+                            "{} {} to {}",
+                            time_display::format_recent(failure.created_at),
+                            failure.event_name,
+                            failure.integration,
+                        )),
+                        outcome,
+                    ]
+                    .spacing(s::S2),
+                    w::text(format!("Outbox {}", failure.outbox_uuid.to_uuid()))
+                        .size(s::S3)
+                        .color(s::GRAY_MID),
+                    w::text(failure.details.as_str())
+                        .size(s::S3)
+                        .color(s::RED_SOFT),
+                ]
+                .spacing(s::S2);
+
+                if let Some(content) = &failure.content {
+                    item = item.push(w::text(content.as_str()).size(s::S3));
+                }
+
+                col = col.push(item.push(w::horizontal_rule(1)));
+            }
+
+            col.into()
+        }
+    }
+}
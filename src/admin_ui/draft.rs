@@ -0,0 +1,294 @@
+use crate::admin_ui::s;
+use iced::keyboard;
+use iced::widget::text_editor::{Action, Binding, Content, Edit, KeyPress, Motion};
+use iced::{widget as w, Element};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+const DRAFTS_DIR: &str = "drafts";
+const UNDO_LIMIT: usize = 200;
+
+/// A text editor that remembers its earlier text so edits can be undone and
+/// redone.
+pub struct Editor {
+    content: Content,
+    history: UndoHistory,
+}
+
+impl Editor {
+    pub fn with_text(text: &str) -> Self {
+        Editor {
+            content: Content::with_text(text),
+            history: UndoHistory::new(),
+        }
+    }
+
+    pub fn content(&self) -> &Content {
+        &self.content
+    }
+
+    pub fn text(&self) -> String {
+        self.content.text()
+    }
+
+    /// Returns whether the text changed.
+    pub fn perform(&mut self, action: Action) -> bool {
+        match &action {
+            Action::Edit(edit) => {
+                self.history.record(self.content.text(), is_typing(edit));
+                self.content.perform(action);
+                true
+            }
+            _ => {
+                // Moving the cursor ends a run of typing
+                self.history.end_run();
+                self.content.perform(action);
+                false
+            }
+        }
+    }
+
+    /// Returns whether there was anything to undo.
+    pub fn undo(&mut self) -> bool {
+        match self.history.undo(self.content.text()) {
+            Some(text) => {
+                self.set_text(&text);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Returns whether there was anything to redo.
+    pub fn redo(&mut self) -> bool {
+        match self.history.redo(self.content.text()) {
+            Some(text) => {
+                self.set_text(&text);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Replaces the text as one undoable edit.
+    pub fn replace_text(&mut self, text: &str) {
+        self.history.record(self.content.text(), false);
+        self.set_text(text);
+    }
+
+    fn set_text(&mut self, text: &str) {
+        self.content = Content::with_text(text);
+        self.content.perform(Action::Move(Motion::DocumentEnd));
+    }
+}
+
+/// Ctrl+Z undoes, and Ctrl+Shift+Z or Ctrl+Y redoes. Every other key does
+/// what it normally does in a text editor.
+pub fn undo_key_binding<Msg>(key_press: KeyPress, undo: Msg, redo: Msg) -> Option<Binding<Msg>> {
+    if key_press.modifiers.command() {
+        match key_press.key.as_ref() {
+            keyboard::Key::Character("z") | keyboard::Key::Character("Z") => {
+                if key_press.modifiers.shift() {
+                    return Some(Binding::Custom(redo));
+                }
+                return Some(Binding::Custom(undo));
+            }
+            keyboard::Key::Character("y") => return Some(Binding::Custom(redo)),
+            _ => {}
+        }
+    }
+
+    Binding::from_key_press(key_press)
+}
+
+fn is_typing(edit: &Edit) -> bool {
+    match edit {
+        Edit::Insert(c) => !c.is_whitespace(),
+        Edit::Paste(_) | Edit::Enter | Edit::Backspace | Edit::Delete => false,
+    }
+}
+
+/// Earlier versions of some text. A run of typed characters is one step, so
+/// undo takes back a word at a time rather than a letter at a time.
+#[derive(Debug, Default)]
+pub struct UndoHistory {
+    undo: Vec<String>,
+    redo: Vec<String>,
+    typing: bool,
+}
+
+impl UndoHistory {
+    pub fn new() -> Self {
+        UndoHistory {
+            undo: Vec::new(),
+            redo: Vec::new(),
+            typing: false,
+        }
+    }
+
+    /// Call with the text as it was just before an edit.
+    pub fn record(&mut self, before: String, is_typing: bool) {
+        let continues_run = is_typing && self.typing;
+        self.typing = is_typing;
+        self.redo.clear();
+
+        if continues_run || self.undo.last() == Some(&before) {
+            return;
+        }
+
+        self.undo.push(before);
+        if self.undo.len() > UNDO_LIMIT {
+            self.undo.remove(0);
+        }
+    }
+
+    pub fn end_run(&mut self) {
+        self.typing = false;
+    }
+
+    pub fn undo(&mut self, current: String) -> Option<String> {
+        let previous = self.undo.pop()?;
+        self.redo.push(current);
+        self.typing = false;
+        Some(previous)
+    }
+
+    pub fn redo(&mut self, current: String) -> Option<String> {
+        let next = self.redo.pop()?;
+        self.undo.push(current);
+        self.typing = false;
+        Some(next)
+    }
+}
+
+/// Where a page's draft file stands.
+pub enum DraftStatus<T> {
+    Saved,
+    /// A draft found at start up that differs from what the page loaded,
+    /// waiting for the user to restore or discard it.
+    Offered(T),
+    Error(String),
+}
+
+impl<T: DeserializeOwned + PartialEq> DraftStatus<T> {
+    pub fn check(name: &str, current: &T) -> Self {
+        match load::<T>(name) {
+            Ok(Some(draft)) if &draft != current => DraftStatus::Offered(draft),
+            Ok(_) => DraftStatus::Saved,
+            Err(err) => DraftStatus::Error(err),
+        }
+    }
+}
+
+impl<T> DraftStatus<T> {
+    pub fn is_offered(&self) -> bool {
+        match self {
+            DraftStatus::Offered(_) => true,
+            DraftStatus::Saved | DraftStatus::Error(_) => false,
+        }
+    }
+}
+
+/// Nothing while drafts are saving fine. Otherwise the restore prompt or the
+/// error.
+pub fn view_status<'a, T, Msg: Clone + 'a>(
+    status: &DraftStatus<T>,
+    restore: Msg,
+    discard: Msg,
+) -> Element<'a, Msg> {
+    match status {
+        DraftStatus::Saved => w::column![].into(),
+        DraftStatus::Offered(_) => w::row![
+            w::text("Found unsaved work from last time. Restore draft?").color(s::GOLD_SOFT),
+            w::button("Restore").on_press(restore),
+            w::button("Discard").on_press(discard),
+        ]
+        .spacing(s::S4)
+        .into(),
+        DraftStatus::Error(err) => w::text(format!("Draft error: {}", err))
+            .color(s::RED_SOFT)
+            .into(),
+    }
+}
+
+pub fn view_undo_buttons<'a, Msg: Clone + 'a>(undo: Msg, redo: Msg) -> Element<'a, Msg> {
+    w::row![
+        w::button("Undo").on_press(undo),
+        w::button("Redo").on_press(redo),
+    ]
+    .spacing(s::S1)
+    .into()
+}
+
+/// Writes the draft so that a crash part way through leaves either the old
+/// draft or the new one, never half of one.
+pub fn save<T: Serialize>(name: &str, draft: &T) -> Result<(), String> {
+    let json = serde_json::to_string_pretty(draft)
+        .map_err(|err| format!("Error serializing {} draft: {}", name, err))?;
+
+    fs::create_dir_all(DRAFTS_DIR)
+        .map_err(|err| format!("Error creating drafts directory: {}", err))?;
+
+    write_atomically(&draft_path(name), json.as_bytes())
+        .map_err(|err| format!("Error writing {} draft: {}", name, err))
+}
+
+pub fn load<T: DeserializeOwned>(name: &str) -> Result<Option<T>, String> {
+    let path = draft_path(name);
+    if !path.exists() {
+        return Ok(None);
+    }
+
+    let file =
+        fs::File::open(&path).map_err(|err| format!("Error opening {} draft: {}", name, err))?;
+
+    serde_json::from_reader(file)
+        .map(Some)
+        .map_err(|err| format!("Error reading {} draft: {}", name, err))
+}
+
+/// Writes to a temporary file next to `path`, syncs it, and renames it over
+/// `path`.
+pub fn write_atomically(path: &Path, bytes: &[u8]) -> std::io::Result<()> {
+    let mut temp_path = path.as_os_str().to_owned();
+    temp_path.push(".tmp");
+    let temp_path = PathBuf::from(temp_path);
+
+    let mut file = fs::File::create(&temp_path)?;
+    file.write_all(bytes)?;
+    file.sync_all()?;
+
+    fs::rename(&temp_path, path)
+}
+
+fn draft_path(name: &str) -> PathBuf {
+    Path::new(DRAFTS_DIR).join(format!("{}.json", name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_typing_runs_undo_together_and_redo_is_cleared_by_new_edits() {
+        let mut history = UndoHistory::new();
+
+        history.record("".to_string(), true);
+        history.record("h".to_string(), true);
+        history.record("hi".to_string(), false);
+        history.record("hi ".to_string(), true);
+
+        assert_eq!(history.undo("hi y".to_string()), Some("hi ".to_string()));
+        assert_eq!(history.undo("hi ".to_string()), Some("hi".to_string()));
+        assert_eq!(history.undo("hi".to_string()), Some("".to_string()));
+        assert_eq!(history.undo("".to_string()), None);
+
+        assert_eq!(history.redo("".to_string()), Some("hi".to_string()));
+
+        history.record("hi".to_string(), false);
+        assert_eq!(history.redo("hi!".to_string()), None);
+    }
+}
@@ -1,15 +1,20 @@
-use super::s;
+use super::{json_view, s};
 use crate::capability::job::JobCapability;
 use crate::capability::message::MessageCapability;
 use crate::capability::person::PersonCapability;
 use crate::capability::reaction::ReactionPromptPreview;
+use crate::capability::scene::SceneCapability;
 use crate::domain::job::process_reaction_common::{self, SceneReactionTrigger};
 use crate::domain::job::{Job, JobKind, JobStatus};
+use crate::domain::job_event::{self, JobEventKind, NewJobEvent};
 use crate::domain::job_uuid::JobUuid;
 use crate::domain::message::MessageSender;
+use crate::domain::message_uuid::MessageUuid;
 use crate::domain::person_uuid::PersonUuid;
+use crate::domain::scene_uuid::SceneUuid;
 use crate::job_runner::{self, RunNextJobResult};
 use crate::nice_display::NiceDisplay;
+use crate::time_display;
 use crate::worker::Worker;
 use iced::widget::container;
 use iced::widget::scrollable;
@@ -21,6 +26,7 @@ use std::sync::Arc;
 
 const JOB_PAGE_SIZE: usize = 100;
 const LOAD_MORE_THRESHOLD: f32 = 0.95;
+const MESSAGE_SNIPPET_CHARS: usize = 48;
 
 pub struct Model {
     add_ping_status: AddPingStatus,
@@ -87,12 +93,39 @@ enum SelectedJobStatus {
 #[derive(Debug, Clone)]
 pub(super) struct SelectedJobModel {
     job: Job,
-    related_people: Vec<String>,
+    related: Vec<Related>,
+    event_lines: Vec<String>,
     delete_status: DeleteStatus,
     reset_status: ResetJobStatus,
     preview_status: PromptPreviewStatus,
 }
 
+/// Something a job refers to, like the person it is for.
+#[derive(Debug, Clone)]
+struct Related {
+    role: String,
+    label: String,
+    link: Option<JobLink>,
+}
+
+impl Related {
+    fn text(role: &str, label: &str) -> Related {
+        Related {
+            role: role.to_string(),
+            label: label.to_string(),
+            link: None,
+        }
+    }
+}
+
+/// Where a related entity is shown in the admin ui.
+#[derive(Debug, Clone)]
+pub enum JobLink {
+    Person { name: String },
+    Scene { name: String },
+    Message { scene_name: String },
+}
+
 #[derive(Debug, Clone)]
 enum PromptPreviewStatus {
     Ready,
@@ -123,6 +156,8 @@ pub enum Msg {
     ResetAllFailedJobs(Result<(), String>),
     LoadedRecent(Result<Vec<Job>, String>),
     ClickedSelectJob(JobUuid),
+    /// Handled by the admin ui, which opens the link on its tab
+    ClickedJobLink(JobLink),
     LoadedJob(Result<Option<SelectedJobModel>, String>),
     ClickedDeleteSelected,
     ClickedConfirmDelete(JobUuid),
@@ -305,6 +340,7 @@ impl Model {
                 };
                 Task::none()
             }
+            Msg::ClickedJobLink(_) => Task::none(),
             Msg::ClickedSelectJob(job_uuid) => {
                 self.selected_job_status = SelectedJobStatus::Loading;
                 let worker = worker.clone();
@@ -605,27 +641,46 @@ fn selected_job_view(selected: &SelectedJobStatus) -> Element<'_, Msg> {
             let finished_at = format_job_time("Finished", selected_job.job.finished_at());
             let deleted_at = format_job_time("Deleted", selected_job.job.deleted_at());
 
-            let error_text = match selected_job.job.error() {
-                Some(err) => format!("Error: {}", err),
-                None => "Error: none".to_string(),
-            };
+            let failure: Option<Element<Msg>> = selected_job.job.error().map(|err| {
+                w::column![
+                    w::text("Failure").color(s::RED_SOFT),
+                    w::text(err.to_string()).color(s::RED_SOFT),
+                ]
+                .spacing(s::S1)
+                .into()
+            });
 
-            let related_people_text = if selected_job.related_people.is_empty() {
+            let related: Option<Element<Msg>> = if selected_job.related.is_empty() {
                 None
             } else {
-                Some(format!(
-                    "Related people:\n{}",
-                    selected_job.related_people.join("\n")
-                ))
+                let mut rows = w::column![w::text("Related")].spacing(s::S1);
+                for related in &selected_job.related {
+                    let label: Element<Msg> = match &related.link {
+                        Some(link) => w::button(w::text(related.label.clone()).color(s::BLUE_SOFT))
+                            .style(w::button::text)
+                            .padding(0)
+                            .on_press(Msg::ClickedJobLink(link.clone()))
+                            .into(),
+                        None => w::text(related.label.clone()).into(),
+                    };
+                    rows = rows
+                        .push(w::row![w::text(format!("{}:", related.role)), label].spacing(s::S2));
+                }
+                Some(rows.into())
+            };
+
+            let events_text = if selected_job.event_lines.is_empty() {
+                "Events: none".to_string()
+            } else {
+                format!("Events:\n{}", selected_job.event_lines.join("\n"))
             };
 
-            let data_text = match selected_job.job.data() {
-                Ok(Some(data)) => match serde_json::to_string_pretty(&data) {
-                    Ok(pretty) => format!("Data:\n{}", pretty),
-                    Err(err) => format!("Data: <failed to format JSON: {}>", err),
-                },
-                Ok(None) => "Data: none".to_string(),
-                Err(err) => format!("Data error: {}", err),
+            let data: Element<Msg> = match selected_job.job.data() {
+                Ok(Some(data)) => w::column![w::text("Data"), json_view::view(&data)]
+                    .spacing(s::S1)
+                    .into(),
+                Ok(None) => w::text("Data: none").into(),
+                Err(err) => w::text(format!("Data error: {}", err)).into(),
             };
 
             let reset_controls: Element<Msg> = match &selected_job.reset_status {
@@ -725,15 +780,19 @@ fn selected_job_view(selected: &SelectedJobStatus) -> Element<'_, Msg> {
                 w::text(started_at),
                 w::text(finished_at),
                 w::text(deleted_at),
-                w::text(error_text),
             ]
             .spacing(s::S2);
 
-            if let Some(related_people_text) = related_people_text {
-                details = details.push(w::text(related_people_text));
+            if let Some(failure) = failure {
+                details = details.push(failure);
             }
 
-            details = details.push(w::text(data_text));
+            if let Some(related) = related {
+                details = details.push(related);
+            }
+
+            details = details.push(w::text(events_text));
+            details = details.push(data);
             details = details.push(action_row);
             details = details.push(preview_controls);
 
@@ -773,7 +832,7 @@ fn job_row_style(_theme: &iced::Theme, status: w::button::Status) -> w::button::
 
 fn format_job_time(label: &str, timestamp: Option<chrono::DateTime<chrono::Utc>>) -> String {
     match timestamp {
-        Some(time) => format!("{}: {}", label, time.format("%Y-%m-%d %H:%M:%S UTC")),
+        Some(time) => format!("{}: {}", label, time_display::format_recent(time)),
         None => format!("{}: none", label),
     }
 }
@@ -829,6 +888,13 @@ async fn reset_job(worker: Arc<Worker>, job_uuid: JobUuid) -> Result<JobUuid, St
         .reset_job(&job_uuid)
         .await
         .map_err(|err| format!("Error resetting job:\n{}", err))?;
+    worker
+        .record_job_event(
+            &job_uuid,
+            &NewJobEvent::new(JobEventKind::Retried, job_event::ADMIN_UI_RUNNER),
+        )
+        .await
+        .map_err(|err| format!("Job reset, but recording that failed:\n{}", err))?;
     Ok(job_uuid)
 }
 
@@ -913,77 +979,223 @@ async fn preview_job_prompts(
 }
 
 async fn build_selected_job_model(worker: &Worker, job: Job) -> SelectedJobModel {
-    let related_people = describe_related_people(worker, &job).await;
+    let related = describe_related(worker, &job).await;
+    let event_lines = match worker.get_job_events(job.uuid()).await {
+        Ok(events) => events.iter().map(|event| event.to_line()).collect(),
+        Err(err) => vec![format!("Could not load job events: {}", err)],
+    };
 
     SelectedJobModel {
         job,
-        related_people,
+        related,
+        event_lines,
         delete_status: DeleteStatus::Ready,
         reset_status: ResetJobStatus::Ready,
         preview_status: PromptPreviewStatus::Ready,
     }
 }
 
-async fn describe_related_people(worker: &Worker, job: &Job) -> Vec<String> {
+/// The people, scenes and messages a job refers to, by their names.
+async fn describe_related(worker: &Worker, job: &Job) -> Vec<Related> {
     match job.kind() {
         JobKind::Ping => vec![],
+        JobKind::DispatchOutbox(_) => vec![],
+        JobKind::PollLlmBatch(_) => vec![],
+        JobKind::HandleBatchCompletion(_) => vec![],
+        JobKind::WakeIdlePersons => vec![],
+        JobKind::CheckSceneGoals => vec![],
+        JobKind::TagTopics => vec![],
+        JobKind::InjectSceneEvents => vec![],
+        JobKind::NightlyMaintenance => vec![],
+        JobKind::UpdatePersonArcs => vec![],
+        JobKind::BalanceChattiness => vec![],
+        JobKind::UseTool(use_tool_job) => {
+            let mut related =
+                vec![related_person(worker, "Person", &use_tool_job.person_uuid).await];
+            if let Some(scene_uuid) = &use_tool_job.scene_uuid {
+                related.push(related_scene(worker, "Scene", scene_uuid).await);
+            }
+            related
+        }
+        JobKind::ReactToSceneInvitation(react_to_scene_invitation_job) => {
+            vec![
+                related_person(
+                    worker,
+                    "Invitee",
+                    &react_to_scene_invitation_job.person_uuid,
+                )
+                .await,
+            ]
+        }
         JobKind::SendMessageToScene(send_message_to_scene_job) => {
-            match &send_message_to_scene_job.sender {
+            let sender = match &send_message_to_scene_job.sender {
                 MessageSender::AiPerson(person_uuid) => {
-                    vec![format!(
-                        "Sender: {}",
-                        format_person_label(worker, person_uuid).await
-                    )]
+                    related_person(worker, "Sender", person_uuid).await
                 }
-                MessageSender::RealWorldUser => vec!["Sender: Real World User".to_string()],
-            }
+                MessageSender::RealWorldUser => Related::text("Sender", "Real World User"),
+            };
+
+            vec![
+                sender,
+                related_scene(worker, "Scene", &send_message_to_scene_job.scene_uuid).await,
+            ]
         }
         JobKind::ProcessPersonJoin(process_person_join_job) => {
             vec![
-                format!(
-                    "Recipient: {}",
-                    format_person_label(worker, &process_person_join_job.recipient_person_uuid)
-                        .await
-                ),
-                format!(
-                    "Joined person: {}",
-                    format_person_label(worker, &process_person_join_job.joined_person_uuid).await
-                ),
+                related_person(
+                    worker,
+                    "Recipient",
+                    &process_person_join_job.recipient_person_uuid,
+                )
+                .await,
+                related_person(
+                    worker,
+                    "Joined person",
+                    &process_person_join_job.joined_person_uuid,
+                )
+                .await,
+                related_scene(worker, "Scene", &process_person_join_job.scene_uuid).await,
             ]
         }
         JobKind::ProcessMessage(process_message_job) => {
-            vec![format!(
-                "Recipient: {}",
-                format_person_label(worker, &process_message_job.recipient_person_uuid).await
-            )]
+            vec![
+                related_person(
+                    worker,
+                    "Recipient",
+                    &process_message_job.recipient_person_uuid,
+                )
+                .await,
+                related_message(worker, "Message", &process_message_job.message_uuid).await,
+                Related::text("Urgency", &process_message_job.urgency.to_name()),
+            ]
         }
         JobKind::ProcessSceneGaze(process_scene_gaze_job) => {
-            vec![format!(
-                "Gazing person: {}",
-                format_person_label(worker, &process_scene_gaze_job.gazing_person_uuid).await
-            )]
+            vec![
+                related_person(
+                    worker,
+                    "Gazing person",
+                    &process_scene_gaze_job.gazing_person_uuid,
+                )
+                .await,
+                related_scene(worker, "Scene", &process_scene_gaze_job.scene_uuid).await,
+            ]
         }
         JobKind::PersonWaiting(person_waiting_job) => match person_waiting_job.person_uuid() {
-            Some(person_uuid) => {
-                vec![format!(
-                    "Waiting person: {}",
-                    format_person_label(worker, person_uuid).await
-                )]
-            }
-            None => vec!["Waiting person: missing person uuid".to_string()],
+            Some(person_uuid) => vec![related_person(worker, "Waiting person", person_uuid).await],
+            None => vec![Related::text("Waiting person", "missing person uuid")],
         },
         JobKind::PersonHibernating(person_hibernating_job) => {
-            vec![format!(
-                "Hibernating person: {}",
-                format_person_label(worker, person_hibernating_job.person_uuid()).await
-            )]
+            vec![
+                related_person(
+                    worker,
+                    "Hibernating person",
+                    person_hibernating_job.person_uuid(),
+                )
+                .await,
+            ]
+        }
+        JobKind::CheckExpectedReply(check_expected_reply_job) => {
+            vec![
+                related_person(worker, "Asker", &check_expected_reply_job.asker_person_uuid).await,
+                related_person(
+                    worker,
+                    "Recipient",
+                    &check_expected_reply_job.recipient_person_uuid,
+                )
+                .await,
+                related_message(worker, "Question", &check_expected_reply_job.message_uuid).await,
+                related_scene(worker, "Scene", &check_expected_reply_job.scene_uuid).await,
+            ]
+        }
+        JobKind::NoticeConversation(notice_conversation_job) => {
+            vec![
+                related_person(worker, "Idle person", &notice_conversation_job.person_uuid).await,
+                related_scene(worker, "Scene", &notice_conversation_job.scene_uuid).await,
+            ]
+        }
+        JobKind::CheckPersonaConsistency(check_persona_consistency_job) => {
+            vec![related_person(worker, "Person", &check_persona_consistency_job.person_uuid).await]
+        }
+        JobKind::CloseScene(close_scene_job) => {
+            vec![related_scene(worker, "Scene", &close_scene_job.scene_uuid).await]
+        }
+        JobKind::ArchiveScene(archive_scene_job) => {
+            vec![related_scene(worker, "Scene", &archive_scene_job.scene_uuid).await]
+        }
+        JobKind::ReactToSceneEvent(react_to_scene_event_job) => {
+            vec![
+                related_person(worker, "Person", &react_to_scene_event_job.person_uuid).await,
+                related_scene(worker, "Scene", &react_to_scene_event_job.scene_uuid).await,
+            ]
+        }
+        JobKind::ChangeSceneAmbience(change_scene_ambience_job) => {
+            vec![related_scene(worker, "Scene", &change_scene_ambience_job.scene_uuid).await]
+        }
+        JobKind::RunCustomAction(run_custom_action_job) => {
+            let mut related =
+                vec![related_person(worker, "Person", &run_custom_action_job.person_uuid).await];
+            if let Some(scene_uuid) = &run_custom_action_job.scene_uuid {
+                related.push(related_scene(worker, "Scene", scene_uuid).await);
+            }
+            related
+        }
+        JobKind::RefillAcknowledgements(refill_acknowledgements_job) => {
+            vec![related_person(worker, "Person", &refill_acknowledgements_job.person_uuid).await]
         }
     }
 }
 
-async fn format_person_label(worker: &Worker, person_uuid: &PersonUuid) -> String {
+async fn related_person(worker: &Worker, role: &str, person_uuid: &PersonUuid) -> Related {
     match worker.get_persons_name(person_uuid.clone()).await {
-        Ok(person_name) => format!("{} ({})", person_name, person_uuid.to_uuid()),
-        Err(_) => person_uuid.to_uuid().to_string(),
+        Ok(person_name) => Related {
+            role: role.to_string(),
+            label: format!("{} ({})", person_name, person_uuid.to_uuid()),
+            link: Some(JobLink::Person {
+                name: person_name.as_str().to_string(),
+            }),
+        },
+        Err(_) => Related::text(role, &person_uuid.to_uuid().to_string()),
+    }
+}
+
+async fn related_scene(worker: &Worker, role: &str, scene_uuid: &SceneUuid) -> Related {
+    match worker.get_scene_name(scene_uuid).await {
+        Ok(Some(scene_name)) => Related {
+            role: role.to_string(),
+            label: scene_name.clone(),
+            link: Some(JobLink::Scene { name: scene_name }),
+        },
+        Ok(None) => Related::text(role, &format!("{} (deleted)", scene_uuid.to_uuid())),
+        Err(_) => Related::text(role, &scene_uuid.to_uuid().to_string()),
+    }
+}
+
+/// Links to the scene the message was said in, since messages are read there.
+async fn related_message(worker: &Worker, role: &str, message_uuid: &MessageUuid) -> Related {
+    let message = match worker.get_message_by_uuid(message_uuid).await {
+        Ok(Some(message)) => message,
+        Ok(None) => {
+            return Related::text(role, &format!("{} (deleted)", message_uuid.to_uuid()));
+        }
+        Err(_) => return Related::text(role, &message_uuid.to_uuid().to_string()),
+    };
+
+    let snippet = shorten(&message.content, MESSAGE_SNIPPET_CHARS);
+
+    match worker.get_scene_name(&message.scene_uuid).await {
+        Ok(Some(scene_name)) => Related {
+            role: role.to_string(),
+            label: format!("\"{}\" in {}", snippet, scene_name),
+            link: Some(JobLink::Message { scene_name }),
+        },
+        _ => Related::text(role, &format!("\"{}\"", snippet)),
+    }
+}
+
+fn shorten(text: &str, max_chars: usize) -> String {
+    if text.chars().count() <= max_chars {
+        text.to_string()
+    } else {
+        format!("{}...", text.chars().take(max_chars).collect::<String>())
     }
 }
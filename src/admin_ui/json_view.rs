@@ -0,0 +1,128 @@
+use super::s;
+use iced::{widget as w, Color, Element, Font};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TokenKind {
+    Key,
+    String,
+    Number,
+    /// `true`, `false` and `null`
+    Literal,
+    /// Brackets, commas, colons and the whitespace between them
+    Punctuation,
+}
+
+/// Pretty prints `value` with keys, strings, numbers and literals in their
+/// own colors.
+pub fn view<'a, Msg: Clone + 'static>(value: &serde_json::Value) -> Element<'a, Msg> {
+    let pretty = match serde_json::to_string_pretty(value) {
+        Ok(pretty) => pretty,
+        Err(err) => return w::text(format!("<failed to format JSON: {}>", err)).into(),
+    };
+
+    let spans = tokenize(&pretty)
+        .into_iter()
+        .map(|(kind, text)| w::span(text).color(token_color(kind)))
+        .collect::<Vec<_>>();
+
+    w::rich_text(spans).font(Font::MONOSPACE).into()
+}
+
+fn token_color(kind: TokenKind) -> Color {
+    match kind {
+        TokenKind::Key => s::BLUE_SOFT,
+        TokenKind::String => s::GREEN_SOFT,
+        TokenKind::Number => s::GOLD_SOFT,
+        TokenKind::Literal => s::RED_SOFT,
+        TokenKind::Punctuation => s::GRAY_MID,
+    }
+}
+
+/// Splits json into pieces to color. Joining the pieces gives back `json`.
+fn tokenize(json: &str) -> Vec<(TokenKind, String)> {
+    let chars = json.chars().collect::<Vec<char>>();
+    let mut tokens: Vec<(TokenKind, String)> = Vec::new();
+    let mut index = 0;
+
+    while index < chars.len() {
+        let start = index;
+        let kind = match chars[index] {
+            '"' => {
+                index += 1;
+                while index < chars.len() && chars[index] != '"' {
+                    if chars[index] == '\\' {
+                        index += 1;
+                    }
+                    index += 1;
+                }
+                index = (index + 1).min(chars.len());
+
+                let next = chars[index..].iter().find(|c| !c.is_whitespace());
+                if next == Some(&':') {
+                    TokenKind::Key
+                } else {
+                    TokenKind::String
+                }
+            }
+            c if c == '-' || c.is_ascii_digit() => {
+                while index < chars.len()
+                    && (chars[index].is_ascii_digit() || "+-.eE".contains(chars[index]))
+                {
+                    index += 1;
+                }
+                TokenKind::Number
+            }
+            c if c.is_ascii_alphabetic() => {
+                while index < chars.len() && chars[index].is_ascii_alphabetic() {
+                    index += 1;
+                }
+                TokenKind::Literal
+            }
+            _ => {
+                index += 1;
+                TokenKind::Punctuation
+            }
+        };
+
+        let text = chars[start..index].iter().collect::<String>();
+        match tokens.last_mut() {
+            Some((TokenKind::Punctuation, last)) if kind == TokenKind::Punctuation => {
+                last.push_str(&text);
+            }
+            _ => tokens.push((kind, text)),
+        }
+    }
+
+    tokens
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tokenize_tells_keys_from_values() {
+        let json = "{\n  \"name\": \"say \\\"hi\\\"\",\n  \"delay\": -1.5e3,\n  \"done\": null\n}";
+        let tokens = tokenize(json);
+
+        assert_eq!(
+            tokens
+                .iter()
+                .filter(|(kind, _)| *kind != TokenKind::Punctuation)
+                .cloned()
+                .collect::<Vec<_>>(),
+            vec![
+                (TokenKind::Key, "\"name\"".to_string()),
+                (TokenKind::String, "\"say \\\"hi\\\"\"".to_string()),
+                (TokenKind::Key, "\"delay\"".to_string()),
+                (TokenKind::Number, "-1.5e3".to_string()),
+                (TokenKind::Key, "\"done\"".to_string()),
+                (TokenKind::Literal, "null".to_string()),
+            ]
+        );
+        assert_eq!(
+            tokens.into_iter().map(|(_, text)| text).collect::<String>(),
+            json
+        );
+    }
+}
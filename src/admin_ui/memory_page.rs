@@ -1,9 +1,12 @@
+use crate::admin_ui::pending_operations::Operation;
 use crate::capability::memory::{MemoryCapability, MemorySearchResult};
 use crate::capability::person::PersonCapability;
+use crate::domain::memory::MemoryRetrieval;
 use crate::domain::memory_uuid::MemoryUuid;
 use crate::domain::person_name::PersonName;
 use crate::worker::Worker;
 use crate::{admin_ui::s, capability, capability::memory::NewMemory};
+use chrono::Utc;
 use iced::{widget as w, Element, Task};
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
@@ -20,6 +23,9 @@ pub struct Model {
     query_recent_events_field: Vec<String>,
     query_state_of_mind_field: String,
     query_situation_field: String,
+    query_top_k_field: String,
+    query_max_distance_field: String,
+    query_recency_weight_field: String,
     query_status: QueryStatus,
 }
 
@@ -34,6 +40,7 @@ enum Status {
 
 #[derive(Clone, Debug)]
 pub struct MemoryQueryResult {
+    pub person_name: String,
     pub prompt: String,
     pub memories: Vec<MemorySearchResult>,
 }
@@ -61,10 +68,15 @@ pub enum Msg {
     QueryRecentEventChanged(usize, String),
     QueryStateOfMindChanged(String),
     QuerySituationChanged(String),
+    QueryTopKChanged(String),
+    QueryMaxDistanceChanged(String),
+    QueryRecencyWeightChanged(String),
     ClickedAddRecentEvent,
     ClickedRemoveRecentEvent(usize),
     ClickedGeneratePrompt,
     GeneratedPromptAndSearched(Result<MemoryQueryResult, String>),
+    /// Handled by the admin ui, which runs it once its undo window is over.
+    StageOperation(Operation),
 }
 
 #[derive(Serialize, Deserialize, Debug, Default)]
@@ -87,10 +99,18 @@ pub struct Storage {
     query_state_of_mind_field: String,
     #[serde(default)]
     query_situation_field: String,
+    #[serde(default)]
+    query_top_k_field: String,
+    #[serde(default)]
+    query_max_distance_field: String,
+    #[serde(default)]
+    query_recency_weight_field: String,
 }
 
 impl Model {
     pub fn new(storage: &Storage) -> Self {
+        let default_retrieval = MemoryRetrieval::default();
+
         Self {
             name_field: storage.name_field.clone(),
             memory_field: storage.memory_field.clone(),
@@ -102,6 +122,15 @@ impl Model {
             query_recent_events_field: storage.query_recent_events_field.clone(),
             query_state_of_mind_field: storage.query_state_of_mind_field.clone(),
             query_situation_field: storage.query_situation_field.clone(),
+            query_top_k_field: or_default(&storage.query_top_k_field, default_retrieval.top_k),
+            query_max_distance_field: or_default(
+                &storage.query_max_distance_field,
+                default_retrieval.max_distance,
+            ),
+            query_recency_weight_field: or_default(
+                &storage.query_recency_weight_field,
+                default_retrieval.recency_weight,
+            ),
             query_status: QueryStatus::Ready,
         }
     }
@@ -136,6 +165,17 @@ impl Model {
                 .on_input(Msg::QueryStateOfMindChanged),
             w::text("Situation"),
             w::text_input("", &self.query_situation_field).on_input(Msg::QuerySituationChanged),
+            w::row![
+                w::text("Top K"),
+                w::text_input("", &self.query_top_k_field).on_input(Msg::QueryTopKChanged),
+                w::text("Max distance"),
+                w::text_input("", &self.query_max_distance_field)
+                    .on_input(Msg::QueryMaxDistanceChanged),
+                w::text("Recency weight"),
+                w::text_input("", &self.query_recency_weight_field)
+                    .on_input(Msg::QueryRecencyWeightChanged),
+            ]
+            .spacing(s::S4),
             w::text("Recent Events"),
         ]
         .spacing(s::S4);
@@ -169,6 +209,9 @@ impl Model {
             query_recent_events_field: self.query_recent_events_field.clone(),
             query_state_of_mind_field: self.query_state_of_mind_field.clone(),
             query_situation_field: self.query_situation_field.clone(),
+            query_top_k_field: self.query_top_k_field.clone(),
+            query_max_distance_field: self.query_max_distance_field.clone(),
+            query_recency_weight_field: self.query_recency_weight_field.clone(),
         }
     }
 
@@ -248,6 +291,18 @@ impl Model {
                 self.query_situation_field = value;
                 Task::none()
             }
+            Msg::QueryTopKChanged(value) => {
+                self.query_top_k_field = value;
+                Task::none()
+            }
+            Msg::QueryMaxDistanceChanged(value) => {
+                self.query_max_distance_field = value;
+                Task::none()
+            }
+            Msg::QueryRecencyWeightChanged(value) => {
+                self.query_recency_weight_field = value;
+                Task::none()
+            }
             Msg::ClickedAddRecentEvent => {
                 self.query_recent_events_field.push(String::new());
                 Task::none()
@@ -259,6 +314,18 @@ impl Model {
                 Task::none()
             }
             Msg::ClickedGeneratePrompt => {
+                let retrieval = match MemoryRetrieval::parse(
+                    &self.query_top_k_field,
+                    &self.query_max_distance_field,
+                    &self.query_recency_weight_field,
+                ) {
+                    Ok(retrieval) => retrieval,
+                    Err(err) => {
+                        self.query_status = QueryStatus::Failed(err);
+                        return Task::none();
+                    }
+                };
+
                 self.query_status = QueryStatus::GeneratingPrompt;
 
                 let person_recalling =
@@ -282,6 +349,7 @@ impl Model {
                     recent_events,
                     state_of_mind,
                     situation,
+                    retrieval,
                 };
 
                 Task::perform(
@@ -297,6 +365,7 @@ impl Model {
 
                 Task::none()
             }
+            Msg::StageOperation(_) => Task::none(),
         }
     }
 }
@@ -355,6 +424,15 @@ struct GeneratePromptAndSearchMemoriesInput {
     recent_events: Vec<String>,
     state_of_mind: String,
     situation: String,
+    retrieval: MemoryRetrieval,
+}
+
+fn or_default(field: &str, default: impl ToString) -> String {
+    if field.trim().is_empty() {
+        default.to_string()
+    } else {
+        field.to_string()
+    }
 }
 
 fn query_status_view(status: &QueryStatus) -> Element<'_, Msg> {
@@ -370,15 +448,30 @@ fn query_status_view(status: &QueryStatus) -> Element<'_, Msg> {
             ]
             .spacing(s::S4);
 
+            let now = Utc::now();
             for (i, memory) in result.memories.iter().enumerate() {
                 col = col.push(
                     w::column![
                         w::text(format!(
-                            "Memory {} (distance: {:.3})",
+                            "Memory {} (score: {:.3}, distance: {:.3}, {} days old{})",
                             i + 1,
-                            memory.distance
+                            memory.score,
+                            memory.distance,
+                            (now - memory.created_at).num_days(),
+                            if memory.mentions_participant {
+                                ", mentions someone present"
+                            } else {
+                                ""
+                            }
                         )),
                         w::text(&memory.content),
+                        w::button("Delete").on_press(Msg::StageOperation(
+                            Operation::DeleteMemory {
+                                memory_uuid: memory.memory_uuid.clone(),
+                                person_name: result.person_name.clone(),
+                                content: memory.content.clone(),
+                            }
+                        )),
                         w::horizontal_rule(1),
                     ]
                     .spacing(s::S2),
@@ -403,8 +496,21 @@ async fn generate_prompt_and_search_memories(
         recent_events,
         state_of_mind,
         situation,
+        retrieval,
     } = input;
 
+    // Names that are not persons, like the real world user, just are not
+    // favored
+    let mut participants = Vec::new();
+    for name in people.iter() {
+        if let Ok(participant) = worker
+            .get_person_uuid_by_name(PersonName::from_string(name.clone()))
+            .await
+        {
+            participants.push(participant);
+        }
+    }
+
     let message_type_args = capability::memory::MessageTypeArgs::Scene {
         scene_name: scene_name.clone(),
         scene_description: scene_description.clone(),
@@ -422,12 +528,19 @@ async fn generate_prompt_and_search_memories(
         .await?;
 
     // Search for memories using the generated prompt
+    let person_name = person_recalling.as_str().to_string();
     let person_uuid = worker.get_person_uuid_by_name(person_recalling).await?;
     let memories = worker
-        .search_memories(person_uuid, prompt_result.prompt.clone(), 10)
+        .search_memories(
+            person_uuid,
+            prompt_result.prompt.clone(),
+            &participants,
+            &retrieval,
+        )
         .await?;
 
     Ok(MemoryQueryResult {
+        person_name,
         prompt: prompt_result.prompt,
         memories,
     })
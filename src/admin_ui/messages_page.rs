@@ -1,8 +1,14 @@
 use super::s;
+use crate::capability::message_revision::MessageRevisionCapability;
 use crate::capability::scene::{Scene, SceneCapability, SceneParticipant};
+use crate::domain::acknowledgement;
 use crate::domain::actor_uuid::ActorUuid;
-use crate::domain::job::send_message_to_scene::send_scene_message_and_enqueue_recipients;
-use crate::domain::message::MessageSender;
+use crate::domain::job::send_message_to_scene::{
+    send_scene_message_and_enqueue_recipients, SceneMessageOutcome,
+};
+use crate::domain::message::{Message, MessageSender};
+use crate::domain::message_revision;
+use crate::domain::message_uuid::MessageUuid;
 use crate::domain::random_seed::RandomSeed;
 use crate::domain::scene_uuid::SceneUuid;
 use crate::nice_display::NiceDisplay;
@@ -13,6 +19,10 @@ use std::sync::Arc;
 
 mod scene_timeline;
 
+/// How many of the real world user's unread messages the chat offers to
+/// edit or retract.
+const PENDING_MESSAGE_LIMIT: i64 = 10;
+
 pub struct Model {
     // For direct message view between two people
     selected_person_1: Option<String>,
@@ -26,6 +36,8 @@ pub struct Model {
     // Message composition
     message_input: w::text_editor::Content,
     send_status: SendStatus,
+    editing_message: Option<MessageUuid>,
+    retract_status: RetractStatus,
 
     // View mode toggle
     view_mode: ViewMode,
@@ -41,6 +53,9 @@ pub struct LoadedSceneModel {
     pub participants: Vec<SceneParticipant>,
     pub is_real_world_user_in_scene: bool,
     pub real_world_user_presence_status: RealWorldUserPresenceStatus,
+    /// The real world user's messages nobody has reacted to yet, newest
+    /// first. These can still be edited or retracted.
+    pub pending_messages: Vec<Message>,
 }
 
 enum SceneLoadStatus {
@@ -84,6 +99,13 @@ enum SendStatus {
     Error(String),
 }
 
+#[derive(Debug, Clone)]
+enum RetractStatus {
+    Ready,
+    Retracting,
+    Error(String),
+}
+
 #[derive(Debug, Clone)]
 pub enum RealWorldUserPresenceStatus {
     Ready,
@@ -107,6 +129,11 @@ pub enum Msg {
     MessageInputChanged(w::text_editor::Action),
     SubmitMessage,
     MessageSent(Result<(), String>),
+    PendingMessagesLoaded(Result<Vec<Message>, String>),
+    ClickedEditMessage(MessageUuid),
+    ClickedCancelEdit,
+    ClickedRetractMessage(MessageUuid),
+    MessageRetracted(Result<(), String>),
     Timeline(scene_timeline::Msg),
     ClickedToggleAutoRefresh,
     AutoRefreshTick,
@@ -136,6 +163,8 @@ impl Model {
             scene_load_status: SceneLoadStatus::Ready,
             message_input: w::text_editor::Content::new(),
             send_status: SendStatus::Ready,
+            editing_message: None,
+            retract_status: RetractStatus::Ready,
             view_mode: storage.view_mode,
             auto_refresh: false,
         }
@@ -187,12 +216,16 @@ impl Model {
                         participants: Vec::new(),
                         is_real_world_user_in_scene: false,
                         real_world_user_presence_status: RealWorldUserPresenceStatus::Ready,
+                        pending_messages: Vec::new(),
                     };
 
                     self.scene_load_status = SceneLoadStatus::Loaded(Box::new(loaded_scene));
                     self.send_status = SendStatus::Ready;
+                    self.editing_message = None;
+                    self.retract_status = RetractStatus::Ready;
 
                     let scene_timeline_worker = worker.clone();
+                    let pending_task = load_pending_messages(worker.clone(), scene_uuid.clone());
 
                     Task::batch(vec![
                         Task::perform(
@@ -210,6 +243,7 @@ impl Model {
                             },
                             Msg::ParticipantsLoaded,
                         ),
+                        pending_task,
                     ])
                 }
                 Ok(None) => {
@@ -335,18 +369,60 @@ impl Model {
                     let random_seed = RandomSeed::from_u64(rand::random());
                     self.send_status = SendStatus::Sending;
 
+                    if let Some(message_uuid) = self.editing_message.clone() {
+                        return Task::perform(
+                            async move {
+                                message_revision::edit_message(
+                                    worker.as_ref(),
+                                    &message_uuid,
+                                    content,
+                                    random_seed,
+                                )
+                                .await
+                                .map_err(|err| err.to_nice_error().to_string())
+                                .and_then(
+                                    |outcome| match outcome {
+                                        SceneMessageOutcome::Sent { .. } => Ok(()),
+                                        SceneMessageOutcome::Blocked { categories } => {
+                                            Err(format!(
+                                                "Edit blocked by moderation: {}",
+                                                categories.join(", ")
+                                            ))
+                                        }
+                                    },
+                                )
+                            },
+                            Msg::MessageSent,
+                        );
+                    }
+
                     Task::perform(
                         async move {
-                            send_scene_message_and_enqueue_recipients(
+                            let outcome = send_scene_message_and_enqueue_recipients(
                                 worker.as_ref(),
                                 sender,
-                                scene_uuid,
-                                content,
+                                scene_uuid.clone(),
+                                content.clone(),
                                 random_seed,
                             )
                             .await
-                            .map(|_| ())
-                            .map_err(|err| err.to_nice_error().to_string())
+                            .map_err(|err| err.to_nice_error().to_string())?;
+
+                            match outcome {
+                                SceneMessageOutcome::Sent { .. } => {
+                                    acknowledgement::send_acknowledgement(
+                                        worker.as_ref(),
+                                        &scene_uuid,
+                                        &content,
+                                    )
+                                    .await;
+                                    Ok(())
+                                }
+                                SceneMessageOutcome::Blocked { categories } => Err(format!(
+                                    "Message blocked by moderation: {}",
+                                    categories.join(", ")
+                                )),
+                            }
                         },
                         Msg::MessageSent,
                     )
@@ -359,6 +435,7 @@ impl Model {
                     Ok(_) => {
                         self.send_status = SendStatus::Sent;
                         self.message_input = w::text_editor::Content::new();
+                        self.editing_message = None;
 
                         // Reload messages after a brief moment to show the newly sent message
                         return self.refresh_loaded_scene(worker);
@@ -369,6 +446,59 @@ impl Model {
                 }
                 Task::none()
             }
+            Msg::PendingMessagesLoaded(result) => {
+                if let SceneLoadStatus::Loaded(loaded_scene) = &mut self.scene_load_status {
+                    match result {
+                        Ok(messages) => loaded_scene.pending_messages = messages,
+                        Err(err) => self.retract_status = RetractStatus::Error(err),
+                    }
+                }
+                Task::none()
+            }
+            Msg::ClickedEditMessage(message_uuid) => {
+                if let SceneLoadStatus::Loaded(loaded_scene) = &self.scene_load_status {
+                    if let Some(message) = loaded_scene
+                        .pending_messages
+                        .iter()
+                        .find(|message| message.uuid == message_uuid)
+                    {
+                        self.message_input =
+                            w::text_editor::Content::with_text(message.content.as_str());
+                        self.editing_message = Some(message_uuid);
+                        self.send_status = SendStatus::Ready;
+                    }
+                }
+                Task::none()
+            }
+            Msg::ClickedCancelEdit => {
+                self.editing_message = None;
+                self.message_input = w::text_editor::Content::new();
+                self.send_status = SendStatus::Ready;
+                Task::none()
+            }
+            Msg::ClickedRetractMessage(message_uuid) => {
+                if self.editing_message.as_ref() == Some(&message_uuid) {
+                    self.editing_message = None;
+                    self.message_input = w::text_editor::Content::new();
+                }
+                self.retract_status = RetractStatus::Retracting;
+
+                Task::perform(
+                    async move {
+                        message_revision::retract_message(worker.as_ref(), &message_uuid)
+                            .await
+                            .map_err(|err| err.to_nice_error().to_string())
+                    },
+                    Msg::MessageRetracted,
+                )
+            }
+            Msg::MessageRetracted(result) => {
+                self.retract_status = match result {
+                    Ok(()) => RetractStatus::Ready,
+                    Err(err) => RetractStatus::Error(err),
+                };
+                self.refresh_loaded_scene(worker)
+            }
             Msg::Timeline(sub_msg) => {
                 if let SceneLoadStatus::Loaded(loaded_scene) = &mut self.scene_load_status {
                     if let Some(timeline_model) = timeline_model_mut(&mut loaded_scene.messages) {
@@ -647,6 +777,7 @@ impl Model {
                     auto_refresh_button,
                     refresh_status,
                     view_messages(&scene.messages),
+                    self.view_pending_messages(&scene.pending_messages),
                     message_composer
                 ]
                 .spacing(s::S4)
@@ -683,18 +814,30 @@ impl Model {
         .width(Length::Fill)
         .into();
 
+        let send_label = match self.editing_message {
+            Some(_) => "Save edit",
+            None => "Send",
+        };
+
         let send_button = if !can_send {
-            w::button("Send")
+            w::button(send_label)
         } else {
             match &self.send_status {
                 SendStatus::Ready | SendStatus::Sent => {
-                    w::button("Send").on_press(Msg::SubmitMessage)
+                    w::button(send_label).on_press(Msg::SubmitMessage)
                 }
                 SendStatus::Sending => w::button("Sending..."),
-                SendStatus::Error(_) => w::button("Send").on_press(Msg::SubmitMessage),
+                SendStatus::Error(_) => w::button(send_label).on_press(Msg::SubmitMessage),
             }
         };
 
+        let cancel_edit_button: Element<'_, Msg> = match self.editing_message {
+            Some(_) => w::button("Cancel edit")
+                .on_press(Msg::ClickedCancelEdit)
+                .into(),
+            None => w::row![].into(),
+        };
+
         let status_text: Element<'_, Msg> = match &self.send_status {
             SendStatus::Ready => {
                 if can_send {
@@ -708,9 +851,52 @@ impl Model {
             SendStatus::Error(err) => w::text(format!("Error: {}", err)).into(),
         };
 
-        w::column![w::row![input, send_button].spacing(s::S1), status_text]
-            .spacing(s::S1)
-            .into()
+        w::column![
+            w::row![input, send_button, cancel_edit_button].spacing(s::S1),
+            status_text
+        ]
+        .spacing(s::S1)
+        .into()
+    }
+
+    fn view_pending_messages<'a>(&'a self, pending_messages: &'a [Message]) -> Element<'a, Msg> {
+        let status: Element<'_, Msg> = match &self.retract_status {
+            RetractStatus::Ready => w::text("").into(),
+            RetractStatus::Retracting => w::text("Retracting...").size(s::S3).into(),
+            RetractStatus::Error(err) => w::text(format!("Error: {}", err))
+                .size(s::S3)
+                .color(s::RED_SOFT)
+                .into(),
+        };
+
+        if pending_messages.is_empty() {
+            return status;
+        }
+
+        let mut col = w::column![w::text("Not read by anyone yet").size(s::S3)].spacing(s::S1);
+
+        for message in pending_messages {
+            let is_editing = self.editing_message.as_ref() == Some(&message.uuid);
+            let edit_button = if is_editing {
+                w::button("Editing...")
+            } else {
+                w::button("Edit").on_press(Msg::ClickedEditMessage(message.uuid.clone()))
+            };
+
+            col = col.push(
+                w::row![
+                    w::text(message.content.as_str())
+                        .size(s::S3)
+                        .color(s::GRAY_MID)
+                        .width(Length::Fill),
+                    edit_button,
+                    w::button("Retract").on_press(Msg::ClickedRetractMessage(message.uuid.clone())),
+                ]
+                .spacing(s::S1),
+            );
+        }
+
+        col.push(status).into()
     }
 
     fn view_auto_refresh_button(&self) -> Element<'_, Msg> {
@@ -750,6 +936,7 @@ impl Model {
 
             let scene_timeline_worker = worker.clone();
             let participation_worker = worker.clone();
+            let pending_task = load_pending_messages(worker.clone(), scene.uuid.clone());
             return Task::batch(vec![
                 Task::perform(
                     async move { scene_timeline::Model::load(&scene_timeline_worker, scene_uuid).await },
@@ -773,6 +960,7 @@ impl Model {
                     },
                     Msg::ParticipantsLoaded,
                 ),
+                pending_task,
             ]);
         }
 
@@ -819,6 +1007,17 @@ impl Model {
     }
 }
 
+fn load_pending_messages(worker: Arc<Worker>, scene_uuid: SceneUuid) -> Task<Msg> {
+    Task::perform(
+        async move {
+            worker
+                .get_pending_real_world_user_messages(&scene_uuid, PENDING_MESSAGE_LIMIT)
+                .await
+        },
+        Msg::PendingMessagesLoaded,
+    )
+}
+
 fn view_messages(messages_status: &MessagesStatus) -> Element<'_, Msg> {
     match &messages_status {
         MessagesStatus::Loading => w::text("Loading messages...").into(),
@@ -1,11 +1,11 @@
 use crate::admin_ui::style as s;
-use crate::capability::message::MessageCapability;
 use crate::capability::person::PersonCapability;
 use crate::capability::scene::SceneCapability;
-use crate::domain::message::MessageSender;
+use crate::capability::utterance::UtteranceCapability;
 use crate::domain::person_uuid::PersonUuid;
 use crate::domain::scene_uuid::SceneUuid;
 use crate::text_utils::normalize_message_content;
+use crate::time_display;
 use crate::worker::Worker;
 use chrono::{DateTime, Utc};
 use iced::clipboard;
@@ -35,6 +35,8 @@ pub enum TimelineItem {
         sender_label: String,
         content: String,
         timestamp: DateTime<Utc>,
+        /// How many persons heard it.
+        deliveries: i64,
     },
     PersonJoined {
         person_label: String,
@@ -255,9 +257,10 @@ impl Model {
                 sender_label,
                 content,
                 timestamp,
+                deliveries,
             } => {
                 let display_content = normalize_message_content(content);
-                let time_str = timestamp.format("%Y-%m-%d %H:%M:%S").to_string();
+                let time_str = time_display::format_recent(*timestamp);
                 let is_you = sender_label == "You";
                 let name_color = if is_you { s::GREEN_SOFT } else { s::GOLD_SOFT };
                 let header_text = format!("[{}] {}", time_str, sender_label);
@@ -269,6 +272,9 @@ impl Model {
                         w::text(format!("[{}]", time_str))
                             .size(s::S3)
                             .color(s::GRAY_MID),
+                        w::text(format!("heard by {}", deliveries))
+                            .size(s::S3)
+                            .color(s::GRAY_MID),
                         w::button(w::text("Copy").size(s::S3))
                             .style(w::button::text)
                             .padding(0)
@@ -285,7 +291,7 @@ impl Model {
                 person_label,
                 timestamp,
             } => {
-                let time_str = timestamp.format("%H:%M:%S").to_string();
+                let time_str = time_display::format_clock(*timestamp);
                 let message = format!("[{}] → {} joined the scene", time_str, person_label);
                 let copy_text = message.clone();
                 w::row![
@@ -303,7 +309,7 @@ impl Model {
                 person_label,
                 timestamp,
             } => {
-                let time_str = timestamp.format("%H:%M:%S").to_string();
+                let time_str = time_display::format_clock(*timestamp);
                 let message = format!("[{}] ← {} left the scene", time_str, person_label);
                 let copy_text = message.clone();
                 w::row![
@@ -374,42 +380,43 @@ async fn load_message_page(
     before: Option<DateTime<Utc>>,
     known_keys: HashSet<String>,
 ) -> Result<LoadOlderResult, String> {
-    let mut name_cache: HashMap<String, String> = HashMap::new();
     let mut items = Vec::new();
     let mut keys = Vec::new();
     let mut oldest_message_at: Option<DateTime<Utc>> = None;
 
-    let messages = worker
-        .get_messages_in_scene_page(&scene_uuid, limit as i64, before)
+    let utterances = worker
+        .get_scene_utterances(&scene_uuid, limit as i64, before)
         .await?;
 
-    for message in messages.iter() {
-        let message_key = message_key(message);
+    // Each utterance is shown once, however many persons it was delivered to
+    for utterance in utterances.iter() {
+        let message_key = utterance.message_uuid.to_uuid().to_string();
         if known_keys.contains(&message_key) {
             continue;
         }
 
-        let sender_label = match &message.sender {
-            MessageSender::AiPerson(uuid) => person_label(worker, &mut name_cache, uuid).await?,
-            MessageSender::RealWorldUser => "You".to_string(),
+        let sender_label = match &utterance.speaker_person_uuid {
+            Some(_) => utterance.speaker_name.clone(),
+            None => "You".to_string(),
         };
 
         items.push(TimelineItem::Message {
             sender_label,
-            content: message.content.clone(),
-            timestamp: message.sent_at,
+            content: utterance.content.clone(),
+            timestamp: utterance.sent_at,
+            deliveries: utterance.deliveries,
         });
         keys.push(message_key);
 
         oldest_message_at = match oldest_message_at {
             Some(oldest) => {
-                if message.sent_at < oldest {
-                    Some(message.sent_at)
+                if utterance.sent_at < oldest {
+                    Some(utterance.sent_at)
                 } else {
                     Some(oldest)
                 }
             }
-            None => Some(message.sent_at),
+            None => Some(utterance.sent_at),
         };
     }
 
@@ -417,24 +424,10 @@ async fn load_message_page(
         items,
         keys,
         oldest_message_at,
-        has_more: messages.len() == limit,
+        has_more: utterances.len() == limit,
     })
 }
 
-fn message_key(message: &crate::domain::message::Message) -> String {
-    let sender_key = match &message.sender {
-        MessageSender::AiPerson(uuid) => uuid.to_uuid().to_string(),
-        MessageSender::RealWorldUser => "real_world_user".to_string(),
-    };
-
-    format!(
-        "{}|{}|{}",
-        sender_key,
-        message.content,
-        message.sent_at.timestamp()
-    )
-}
-
 async fn person_label(
     worker: &Worker,
     cache: &mut HashMap<String, String>,
@@ -0,0 +1,265 @@
+use crate::admin_ui::s;
+use crate::capability::content_scrub::ContentScrubCapability;
+use crate::capability::moderation::{BlockedContent, ModerationCapability};
+use crate::domain::content_scrub::ContentScrubSettings;
+use crate::time_display;
+use crate::worker::Worker;
+use iced::{widget as w, Element, Task};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+const BLOCKED_CONTENT_PAGE_SIZE: i64 = 50;
+
+pub struct Model {
+    threshold_input: String,
+    threshold_status: ThresholdStatus,
+    scrub_settings: ScrubSettingsStatus,
+    blocked_content: BlockedContentStatus,
+}
+
+enum ThresholdStatus {
+    Loading,
+    Ready,
+    Saving,
+    Error(String),
+}
+
+enum ScrubSettingsStatus {
+    Loading,
+    Loaded(ContentScrubSettings),
+    Error(String),
+}
+
+enum BlockedContentStatus {
+    Loading,
+    Loaded(Vec<BlockedContent>),
+    Error(String),
+}
+
+#[derive(Debug, Clone)]
+pub enum Msg {
+    ClickedRefresh,
+    LoadedThreshold(Result<f64, String>),
+    ThresholdInputChanged(String),
+    ThresholdSubmitted,
+    ThresholdSaved(Result<(), String>),
+    LoadedScrubSettings(Result<ContentScrubSettings, String>),
+    ScrubPersonalDataToggled(bool),
+    ScrubProfanityToggled(bool),
+    ScrubSettingsSaved(Result<ContentScrubSettings, String>),
+    LoadedBlockedContent(Result<Vec<BlockedContent>, String>),
+}
+
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct Storage {}
+
+impl Model {
+    pub fn new(_storage: &Storage) -> Self {
+        Self {
+            threshold_input: String::new(),
+            threshold_status: ThresholdStatus::Loading,
+            scrub_settings: ScrubSettingsStatus::Loading,
+            blocked_content: BlockedContentStatus::Loading,
+        }
+    }
+
+    pub fn to_storage(&self) -> Storage {
+        Storage {}
+    }
+
+    pub fn on_tab_activated(&mut self, worker: Arc<Worker>) -> Task<Msg> {
+        self.threshold_status = ThresholdStatus::Loading;
+        self.scrub_settings = ScrubSettingsStatus::Loading;
+        self.blocked_content = BlockedContentStatus::Loading;
+
+        let worker2 = worker.clone();
+        let worker3 = worker.clone();
+        Task::batch(vec![
+            Task::perform(
+                async move { worker.get_moderation_threshold().await },
+                Msg::LoadedThreshold,
+            ),
+            Task::perform(
+                async move { worker3.get_content_scrub_settings().await },
+                Msg::LoadedScrubSettings,
+            ),
+            Task::perform(
+                async move { worker2.get_blocked_content(BLOCKED_CONTENT_PAGE_SIZE).await },
+                Msg::LoadedBlockedContent,
+            ),
+        ])
+    }
+
+    pub fn update(&mut self, worker: Arc<Worker>, msg: Msg) -> Task<Msg> {
+        match msg {
+            Msg::ClickedRefresh => self.on_tab_activated(worker),
+            Msg::LoadedThreshold(result) => {
+                match result {
+                    Ok(threshold) => {
+                        self.threshold_input = threshold.to_string();
+                        self.threshold_status = ThresholdStatus::Ready;
+                    }
+                    Err(err) => {
+                        self.threshold_status = ThresholdStatus::Error(err);
+                    }
+                }
+                Task::none()
+            }
+            Msg::ThresholdInputChanged(value) => {
+                self.threshold_input = value;
+                Task::none()
+            }
+            Msg::ThresholdSubmitted => {
+                let threshold = match self.threshold_input.trim().parse::<f64>() {
+                    Ok(threshold) => threshold,
+                    Err(_) => {
+                        self.threshold_status =
+                            ThresholdStatus::Error("Enter a number between 0 and 1".to_string());
+                        return Task::none();
+                    }
+                };
+
+                self.threshold_status = ThresholdStatus::Saving;
+                Task::perform(
+                    async move { worker.set_moderation_threshold(threshold).await },
+                    Msg::ThresholdSaved,
+                )
+            }
+            Msg::ThresholdSaved(result) => {
+                self.threshold_status = match result {
+                    Ok(()) => ThresholdStatus::Ready,
+                    Err(err) => ThresholdStatus::Error(err),
+                };
+                Task::none()
+            }
+            Msg::LoadedScrubSettings(result) | Msg::ScrubSettingsSaved(result) => {
+                self.scrub_settings = match result {
+                    Ok(settings) => ScrubSettingsStatus::Loaded(settings),
+                    Err(err) => ScrubSettingsStatus::Error(err),
+                };
+                Task::none()
+            }
+            Msg::ScrubPersonalDataToggled(enabled) => {
+                self.save_scrub_settings(worker, |settings| {
+                    settings.scrub_personal_data = enabled;
+                })
+            }
+            Msg::ScrubProfanityToggled(enabled) => self.save_scrub_settings(worker, |settings| {
+                settings.scrub_profanity = enabled;
+            }),
+            Msg::LoadedBlockedContent(result) => {
+                self.blocked_content = match result {
+                    Ok(blocked_content) => BlockedContentStatus::Loaded(blocked_content),
+                    Err(err) => BlockedContentStatus::Error(err),
+                };
+                Task::none()
+            }
+        }
+    }
+
+    fn save_scrub_settings(
+        &mut self,
+        worker: Arc<Worker>,
+        change: impl FnOnce(&mut ContentScrubSettings),
+    ) -> Task<Msg> {
+        let mut settings = match &self.scrub_settings {
+            ScrubSettingsStatus::Loaded(settings) => *settings,
+            ScrubSettingsStatus::Loading | ScrubSettingsStatus::Error(_) => return Task::none(),
+        };
+
+        change(&mut settings);
+        self.scrub_settings = ScrubSettingsStatus::Loaded(settings);
+
+        Task::perform(
+            async move {
+                worker.set_content_scrub_settings(settings).await?;
+                Ok(settings)
+            },
+            Msg::ScrubSettingsSaved,
+        )
+    }
+
+    pub fn view(&self) -> Element<'_, Msg> {
+        let threshold_row = w::row![
+            w::text("Block at score"),
+            w::text_input("", &self.threshold_input)
+                .on_input(Msg::ThresholdInputChanged)
+                .on_submit(Msg::ThresholdSubmitted)
+                .width(iced::Length::Fixed(120.0)),
+            w::button("Save").on_press(Msg::ThresholdSubmitted),
+            threshold_status_view(&self.threshold_status),
+        ]
+        .spacing(s::S4);
+
+        w::column![
+            w::text("Moderation").size(20),
+            threshold_row,
+            scrub_settings_view(&self.scrub_settings),
+            w::button("Refresh").on_press(Msg::ClickedRefresh),
+            w::horizontal_rule(1),
+            blocked_content_view(&self.blocked_content),
+        ]
+        .spacing(s::S4)
+        .into()
+    }
+}
+
+fn threshold_status_view(status: &ThresholdStatus) -> Element<'_, Msg> {
+    match status {
+        ThresholdStatus::Loading => w::text("Loading...").into(),
+        ThresholdStatus::Saving => w::text("Saving...").into(),
+        ThresholdStatus::Ready => w::text("").into(),
+        ThresholdStatus::Error(err) => w::text(format!("Error: {}", err)).into(),
+    }
+}
+
+fn scrub_settings_view(status: &ScrubSettingsStatus) -> Element<'_, Msg> {
+    match status {
+        ScrubSettingsStatus::Loading => w::text("Loading scrub settings...").into(),
+        ScrubSettingsStatus::Error(err) => w::text(format!("Error: {}", err)).into(),
+        ScrubSettingsStatus::Loaded(settings) => w::row![
+            w::checkbox(
+                "Scrub emails and phone numbers",
+                settings.scrub_personal_data
+            )
+            .on_toggle(Msg::ScrubPersonalDataToggled),
+            w::checkbox("Scrub profanity", settings.scrub_profanity)
+                .on_toggle(Msg::ScrubProfanityToggled),
+        ]
+        .spacing(s::S4)
+        .into(),
+    }
+}
+
+fn blocked_content_view(status: &BlockedContentStatus) -> Element<'_, Msg> {
+    match status {
+        BlockedContentStatus::Loading => w::text("Loading blocked content...").into(),
+        BlockedContentStatus::Error(err) => w::text(format!("Error: {}", err)).into(),
+        BlockedContentStatus::Loaded(blocked_content) => {
+            if blocked_content.is_empty() {
+                return w::text("Nothing has been blocked").into();
+            }
+
+            let mut col = w::column![].spacing(s::S4);
+
+            for blocked in blocked_content {
+                col = col.push(
+                    w::column![
+                        w::text(format!(
+                            "{} {} ({})",
+                            blocked.sender_label,
+                            time_display::format_recent(blocked.created_at),
+                            blocked.categories.join(", ")
+                        ))
+                        .color(s::RED_SOFT),
+                        w::text(blocked.content.as_str()).size(s::S3),
+                        w::horizontal_rule(1),
+                    ]
+                    .spacing(s::S2),
+                );
+            }
+
+            col.into()
+        }
+    }
+}
@@ -5,6 +5,7 @@ use crate::domain::motivation::Motivation;
 use crate::domain::motivation_uuid::MotivationUuid;
 use crate::domain::person_name::PersonName;
 use crate::domain::person_uuid::PersonUuid;
+use crate::time_display;
 use crate::worker::Worker;
 use iced::{widget as w, Element, Task};
 use serde::{Deserialize, Serialize};
@@ -311,12 +312,9 @@ fn motivations_view<'a>(
 
             let mut col = w::column![];
             for motivation in motivations {
-                let created_at = motivation
-                    .created_at
-                    .format("%Y-%m-%d %H:%M:%S")
-                    .to_string();
+                let created_at = time_display::format_recent(motivation.created_at);
                 let ended_at = match motivation.ended_at {
-                    Some(time) => time.format("%Y-%m-%d %H:%M:%S").to_string(),
+                    Some(time) => time_display::format_recent(time),
                     None => "active".to_string(),
                 };
 
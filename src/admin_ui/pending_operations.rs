@@ -0,0 +1,269 @@
+use crate::admin_ui::s;
+use crate::capability::memory::MemoryCapability;
+use crate::capability::person::PersonCapability;
+use crate::capability::scene::SceneCapability;
+use crate::domain::memory_uuid::MemoryUuid;
+use crate::domain::person_name::PersonName;
+use crate::domain::person_uuid::PersonUuid;
+use crate::domain::scene_uuid::SceneUuid;
+use crate::worker::Worker;
+use iced::{time, widget as w, Element, Subscription, Task};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// How long a destructive operation waits before it runs, so it can still
+/// be undone.
+pub const UNDO_WINDOW: Duration = Duration::from_secs(30);
+
+/// Admin operations that cannot be taken back once they run.
+#[derive(Debug, Clone)]
+pub enum Operation {
+    DeleteMemory {
+        memory_uuid: MemoryUuid,
+        person_name: String,
+        content: String,
+    },
+    RemoveFromScene {
+        scene_uuid: SceneUuid,
+        scene_name: String,
+        person_name: PersonName,
+    },
+    ArchivePerson {
+        person_uuid: PersonUuid,
+        person_name: String,
+    },
+}
+
+/// Staged operations, each waiting out its undo window. Anything still
+/// waiting when the admin ui closes never runs.
+pub struct Model {
+    next_id: u64,
+    pending: Vec<Pending>,
+    /// Operations that ran and failed, until they are dismissed.
+    failures: Vec<(u64, String)>,
+}
+
+struct Pending {
+    id: u64,
+    operation: Operation,
+    run_at: Instant,
+}
+
+#[derive(Debug, Clone)]
+pub enum Msg {
+    Tick(Instant),
+    ClickedUndo(u64),
+    ClickedDismiss(u64),
+    Ran {
+        id: u64,
+        description: String,
+        result: Result<(), String>,
+    },
+}
+
+impl Operation {
+    pub fn description(&self) -> String {
+        match self {
+            Operation::DeleteMemory {
+                person_name,
+                content,
+                ..
+            } => format!(
+                "Delete {}'s memory \"{}\"",
+                person_name,
+                shorten(content, 60)
+            ),
+            Operation::RemoveFromScene {
+                scene_name,
+                person_name,
+                ..
+            } => format!("Remove {} from {}", person_name.as_str(), scene_name),
+            Operation::ArchivePerson { person_name, .. } => format!("Archive {}", person_name),
+        }
+    }
+
+    async fn run(self, worker: Arc<Worker>) -> Result<(), String> {
+        match self {
+            Operation::DeleteMemory { memory_uuid, .. } => worker.delete_memory(&memory_uuid).await,
+            Operation::RemoveFromScene {
+                scene_uuid,
+                person_name,
+                ..
+            } => worker
+                .remove_person_from_scene(scene_uuid, person_name)
+                .await
+                .map(|_| ()),
+            Operation::ArchivePerson { person_uuid, .. } => {
+                worker.archive_person(&person_uuid).await
+            }
+        }
+    }
+}
+
+impl Model {
+    pub fn new() -> Self {
+        Self {
+            next_id: 0,
+            pending: Vec::new(),
+            failures: Vec::new(),
+        }
+    }
+
+    pub fn stage(&mut self, operation: Operation, now: Instant) {
+        self.pending.push(Pending {
+            id: self.next_id,
+            operation,
+            run_at: now + UNDO_WINDOW,
+        });
+        self.next_id += 1;
+    }
+
+    fn undo(&mut self, id: u64) {
+        self.pending.retain(|pending| pending.id != id);
+    }
+
+    /// Removes and returns the operations whose undo window is over.
+    fn take_due(&mut self, now: Instant) -> Vec<(u64, Operation)> {
+        let (due, waiting): (Vec<Pending>, Vec<Pending>) = std::mem::take(&mut self.pending)
+            .into_iter()
+            .partition(|pending| pending.run_at <= now);
+        self.pending = waiting;
+
+        due.into_iter()
+            .map(|pending| (pending.id, pending.operation))
+            .collect()
+    }
+
+    pub fn update(&mut self, worker: Arc<Worker>, msg: Msg) -> Task<Msg> {
+        match msg {
+            Msg::Tick(now) => {
+                let tasks = self
+                    .take_due(now)
+                    .into_iter()
+                    .map(|(id, operation)| {
+                        let description = operation.description();
+                        let worker = worker.clone();
+                        Task::perform(async move { operation.run(worker).await }, move |result| {
+                            Msg::Ran {
+                                id,
+                                description: description.clone(),
+                                result,
+                            }
+                        })
+                    })
+                    .collect::<Vec<_>>();
+
+                Task::batch(tasks)
+            }
+            Msg::ClickedUndo(id) => {
+                self.undo(id);
+                Task::none()
+            }
+            Msg::ClickedDismiss(id) => {
+                self.failures.retain(|(failure_id, _)| *failure_id != id);
+                Task::none()
+            }
+            Msg::Ran {
+                id,
+                description,
+                result,
+            } => {
+                if let Err(err) = result {
+                    self.failures
+                        .push((id, format!("{} failed: {}", description, err)));
+                }
+                Task::none()
+            }
+        }
+    }
+
+    pub fn subscription(&self) -> Subscription<Msg> {
+        if self.pending.is_empty() {
+            Subscription::none()
+        } else {
+            time::every(Duration::from_secs(1)).map(Msg::Tick)
+        }
+    }
+
+    pub fn view(&self) -> Element<'_, Msg> {
+        let now = Instant::now();
+        let mut col = w::column![].spacing(s::S1);
+
+        for pending in &self.pending {
+            let secs_left = pending.run_at.saturating_duration_since(now).as_secs();
+            col = col.push(
+                w::row![
+                    w::text(format!(
+                        "{} in {}s",
+                        pending.operation.description(),
+                        secs_left
+                    ))
+                    .color(s::GOLD_SOFT),
+                    w::button("Undo").on_press(Msg::ClickedUndo(pending.id)),
+                ]
+                .spacing(s::S2)
+                .align_y(iced::Alignment::Center),
+            );
+        }
+
+        for (id, failure) in &self.failures {
+            col = col.push(
+                w::row![
+                    w::text(failure).color(s::RED_SOFT),
+                    w::button("Dismiss").on_press(Msg::ClickedDismiss(*id)),
+                ]
+                .spacing(s::S2)
+                .align_y(iced::Alignment::Center),
+            );
+        }
+
+        col.into()
+    }
+}
+
+fn shorten(text: &str, max_chars: usize) -> String {
+    if text.chars().count() <= max_chars {
+        text.to_string()
+    } else {
+        format!("{}...", text.chars().take(max_chars).collect::<String>())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn archive(name: &str) -> Operation {
+        Operation::ArchivePerson {
+            person_uuid: PersonUuid::new(),
+            person_name: name.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_operations_wait_out_the_undo_window() {
+        let mut model = Model::new();
+        let start = Instant::now();
+
+        model.stage(archive("Alice"), start);
+        model.stage(archive("Bob"), start + Duration::from_secs(10));
+
+        assert!(model.take_due(start + Duration::from_secs(29)).is_empty());
+
+        let due = model.take_due(start + UNDO_WINDOW);
+        assert_eq!(due.len(), 1);
+        assert_eq!(due[0].1.description(), "Archive Alice");
+        assert_eq!(model.pending.len(), 1);
+    }
+
+    #[test]
+    fn test_undone_operations_never_run() {
+        let mut model = Model::new();
+        let start = Instant::now();
+
+        model.stage(archive("Alice"), start);
+        model.undo(0);
+
+        assert!(model.take_due(start + UNDO_WINDOW).is_empty());
+    }
+}
@@ -1,22 +1,50 @@
+use crate::admin_ui::draft::{self, DraftStatus};
+use crate::admin_ui::pending_operations::Operation;
 use crate::admin_ui::s;
+use crate::admin_ui::style_guide_form;
+use crate::admin_ui::voice_exemplars_form;
+use crate::capability::idle_person::IdlePersonCapability;
+use crate::capability::job::JobCapability;
 use crate::capability::person::{NewPerson, PersonCapability};
 use crate::capability::person_identity::{NewPersonIdentity, PersonIdentityCapability};
 use crate::capability::person_task::PersonTaskCapability;
+use crate::capability::persona_consistency::{
+    PersonaConsistencyCapability, RecordedPersonaInconsistency,
+};
+use crate::capability::style_guide::StyleGuideCapability;
+use crate::capability::voice_exemplar::VoiceExemplarCapability;
+use crate::domain::job::check_persona_consistency::CheckPersonaConsistencyJob;
+use crate::domain::job::{wake_idle_persons, JobKind};
 use crate::domain::person_identity_uuid::PersonIdentityUuid;
 use crate::domain::person_name::PersonName;
 use crate::domain::person_task::PersonTask;
 use crate::domain::person_uuid::PersonUuid;
+use crate::domain::style_guide::StyleGuide;
+use crate::nice_display::NiceDisplay;
+use crate::tasks::export_autobiography;
+use crate::time_display;
 use crate::worker::Worker;
 use iced::{clipboard, widget as w, Element, Task};
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 
+mod person_merge;
+mod persona_generator;
+mod persons_list;
+
+const DRAFT_NAME: &str = "person";
+const INCONSISTENCY_LIMIT: i64 = 20;
+
 pub struct Model {
     name_field: String,
-    identity_field: w::text_editor::Content,
+    identity_field: draft::Editor,
+    draft_status: DraftStatus<Draft>,
     status: Status,
     lookup_name_field: String,
     lookup_status: LookupStatus,
+    persona_generator: persona_generator::Model,
+    persons_list: persons_list::Model,
+    person_merge: person_merge::Model,
 }
 
 enum Status {
@@ -33,12 +61,21 @@ enum LookupStatus {
     Loading,
     Loaded {
         person_uuid: PersonUuid,
+        person_name: String,
         identity: Option<String>,
         current_task: Option<PersonTask>,
         is_hibernating: bool,
         hibernation_status: HibernationStatus,
         is_enabled: bool,
         enabled_status: EnabledStatus,
+        chattiness_field: String,
+        chattiness_status: ChattinessStatus,
+        style_guide_form: style_guide_form::Model,
+        voice_exemplars_form: voice_exemplars_form::Model,
+        inconsistencies: Vec<RecordedPersonaInconsistency>,
+        consistency_check_status: ConsistencyCheckStatus,
+        autobiography_path_field: String,
+        autobiography_status: AutobiographyStatus,
     },
     Error(String),
 }
@@ -46,10 +83,15 @@ enum LookupStatus {
 #[derive(Debug, Clone)]
 pub struct LoadedPersonLookupData {
     person_uuid: PersonUuid,
+    person_name: String,
     identity: Option<String>,
     current_task: Option<PersonTask>,
     is_hibernating: bool,
     is_enabled: bool,
+    chattiness: f64,
+    style_guide: StyleGuide,
+    voice_exemplars: Vec<String>,
+    inconsistencies: Vec<RecordedPersonaInconsistency>,
 }
 
 enum HibernationStatus {
@@ -64,6 +106,27 @@ enum EnabledStatus {
     Error(String),
 }
 
+enum ChattinessStatus {
+    Ready,
+    Updating,
+    Saved,
+    Error(String),
+}
+
+enum AutobiographyStatus {
+    Ready,
+    Writing,
+    Done(usize),
+    Error(String),
+}
+
+enum ConsistencyCheckStatus {
+    Ready,
+    Enqueuing,
+    Enqueued,
+    Error(String),
+}
+
 #[derive(Serialize, Deserialize, Debug, Default)]
 pub struct Storage {
     #[serde(default)]
@@ -72,11 +135,24 @@ pub struct Storage {
     name_field: String,
     #[serde(default)]
     lookup_name_field: String,
+    #[serde(default)]
+    persona_concept_field: String,
+}
+
+/// The fields of a person still being written.
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct Draft {
+    name_field: String,
+    identity_field: String,
 }
 
 #[derive(Debug, Clone)]
 pub enum Msg {
     IdentityFieldChanged(w::text_editor::Action),
+    ClickedUndoIdentity,
+    ClickedRedoIdentity,
+    ClickedRestoreDraft,
+    ClickedDiscardDraft,
     NameFieldChanged(String),
     ClickedCreatePerson,
     PersonCreated(Result<PersonUuid, String>),
@@ -103,16 +179,46 @@ pub enum Msg {
         is_enabled: bool,
         result: Result<(), String>,
     },
+    ChattinessFieldChanged(String),
+    ClickedSaveChattiness {
+        person_uuid: PersonUuid,
+    },
+    ChattinessSaved(Result<(), String>),
+    StyleGuideForm(style_guide_form::Msg),
+    VoiceExemplarsForm(voice_exemplars_form::Msg),
+    ClickedCheckConsistency {
+        person_uuid: PersonUuid,
+    },
+    ConsistencyCheckEnqueued(Result<(), String>),
+    AutobiographyPathChanged(String),
+    ClickedExportAutobiography {
+        person_uuid: PersonUuid,
+    },
+    ExportedAutobiography(Result<usize, String>),
+    PersonaGenerator(persona_generator::Msg),
+    PersonsList(persons_list::Msg),
+    PersonMerge(person_merge::Msg),
+    /// Handled by the admin ui, which runs it once its undo window is over.
+    StageOperation(Operation),
 }
 
 impl Model {
     pub fn new(storage: &Storage) -> Self {
+        let current = Draft {
+            name_field: storage.name_field.clone(),
+            identity_field: storage.identity_field.clone(),
+        };
+
         Self {
-            identity_field: w::text_editor::Content::with_text(&storage.identity_field),
+            identity_field: draft::Editor::with_text(&storage.identity_field),
+            draft_status: DraftStatus::check(DRAFT_NAME, &current),
             name_field: storage.name_field.clone(),
             status: Status::Ready,
             lookup_name_field: storage.lookup_name_field.clone(),
             lookup_status: LookupStatus::Ready,
+            persona_generator: persona_generator::Model::new(storage.persona_concept_field.clone()),
+            persons_list: persons_list::Model::new(),
+            person_merge: person_merge::Model::new(),
         }
     }
     pub fn to_storage(&self) -> Storage {
@@ -120,17 +226,64 @@ impl Model {
             identity_field: self.identity_field.text(),
             name_field: self.name_field.clone(),
             lookup_name_field: self.lookup_name_field.clone(),
+            persona_concept_field: self.persona_generator.concept_field(),
         }
     }
 
     pub fn update(&mut self, worker: Arc<Worker>, msg: Msg) -> Task<Msg> {
         match msg {
+            Msg::PersonaGenerator(sub_msg) => self
+                .persona_generator
+                .update(worker, sub_msg)
+                .map(Msg::PersonaGenerator),
+            Msg::PersonsList(persons_list::Msg::ClickedLookUp(person_name)) => {
+                self.lookup_name_field = person_name;
+                self.update(worker, Msg::ClickedLoadIdentity)
+            }
+            Msg::PersonsList(sub_msg) => self
+                .persons_list
+                .update(worker, sub_msg)
+                .map(Msg::PersonsList),
+            Msg::StageOperation(_) => Task::none(),
+            Msg::PersonMerge(sub_msg) => self
+                .person_merge
+                .update(worker, sub_msg)
+                .map(Msg::PersonMerge),
             Msg::IdentityFieldChanged(action) => {
-                self.identity_field.perform(action);
+                if self.identity_field.perform(action) {
+                    self.save_draft();
+                }
+                Task::none()
+            }
+            Msg::ClickedUndoIdentity => {
+                if self.identity_field.undo() {
+                    self.save_draft();
+                }
+                Task::none()
+            }
+            Msg::ClickedRedoIdentity => {
+                if self.identity_field.redo() {
+                    self.save_draft();
+                }
+                Task::none()
+            }
+            Msg::ClickedRestoreDraft => {
+                let status = std::mem::replace(&mut self.draft_status, DraftStatus::Saved);
+                if let DraftStatus::Offered(draft) = status {
+                    self.name_field = draft.name_field;
+                    self.identity_field.replace_text(&draft.identity_field);
+                }
+                self.save_draft();
+                Task::none()
+            }
+            Msg::ClickedDiscardDraft => {
+                self.draft_status = DraftStatus::Saved;
+                self.save_draft();
                 Task::none()
             }
             Msg::NameFieldChanged(value) => {
                 self.name_field = value;
+                self.save_draft();
                 Task::none()
             }
             Msg::LookupNameChanged(value) => {
@@ -194,19 +347,44 @@ impl Model {
                 self.lookup_status = match result {
                     Ok(LoadedPersonLookupData {
                         person_uuid,
+                        person_name,
                         identity,
                         current_task,
                         is_hibernating,
                         is_enabled,
-                    }) => LookupStatus::Loaded {
-                        person_uuid,
-                        identity,
-                        current_task,
-                        is_hibernating,
-                        hibernation_status: HibernationStatus::Ready,
-                        is_enabled,
-                        enabled_status: EnabledStatus::Ready,
-                    },
+                        chattiness,
+                        style_guide,
+                        voice_exemplars,
+                        inconsistencies,
+                    }) => {
+                        let style_guide_form = style_guide_form::Model::new(
+                            style_guide_form::Target::Person(person_uuid.clone()),
+                            &style_guide,
+                        );
+                        let voice_exemplars_form =
+                            voice_exemplars_form::Model::new(person_uuid.clone(), &voice_exemplars);
+                        let autobiography_path_field =
+                            export_autobiography::default_output_path(person_name.as_str());
+
+                        LookupStatus::Loaded {
+                            person_uuid,
+                            person_name,
+                            identity,
+                            current_task,
+                            is_hibernating,
+                            hibernation_status: HibernationStatus::Ready,
+                            is_enabled,
+                            enabled_status: EnabledStatus::Ready,
+                            chattiness_field: chattiness.to_string(),
+                            chattiness_status: ChattinessStatus::Ready,
+                            style_guide_form,
+                            voice_exemplars_form,
+                            inconsistencies,
+                            consistency_check_status: ConsistencyCheckStatus::Ready,
+                            autobiography_path_field,
+                            autobiography_status: AutobiographyStatus::Ready,
+                        }
+                    }
                     Err(err) => LookupStatus::Error(err),
                 };
                 Task::none()
@@ -281,9 +459,174 @@ impl Model {
                 }
                 Task::none()
             }
+            Msg::ChattinessFieldChanged(value) => {
+                if let LookupStatus::Loaded {
+                    chattiness_field,
+                    chattiness_status,
+                    ..
+                } = &mut self.lookup_status
+                {
+                    *chattiness_field = value;
+                    *chattiness_status = ChattinessStatus::Ready;
+                }
+                Task::none()
+            }
+            Msg::ClickedSaveChattiness { person_uuid } => {
+                let LookupStatus::Loaded {
+                    chattiness_field,
+                    chattiness_status,
+                    ..
+                } = &mut self.lookup_status
+                else {
+                    return Task::none();
+                };
+
+                let chattiness = match wake_idle_persons::parse_chattiness(chattiness_field) {
+                    Ok(chattiness) => chattiness,
+                    Err(err) => {
+                        *chattiness_status = ChattinessStatus::Error(err);
+                        return Task::none();
+                    }
+                };
+                *chattiness_status = ChattinessStatus::Updating;
+
+                Task::perform(
+                    async move { worker.set_person_chattiness(&person_uuid, chattiness).await },
+                    Msg::ChattinessSaved,
+                )
+            }
+            Msg::ChattinessSaved(result) => {
+                if let LookupStatus::Loaded {
+                    chattiness_status, ..
+                } = &mut self.lookup_status
+                {
+                    *chattiness_status = match result {
+                        Ok(()) => ChattinessStatus::Saved,
+                        Err(err) => ChattinessStatus::Error(err),
+                    };
+                }
+                Task::none()
+            }
+            Msg::StyleGuideForm(sub_msg) => match &mut self.lookup_status {
+                LookupStatus::Loaded {
+                    style_guide_form, ..
+                } => style_guide_form
+                    .update(worker, sub_msg)
+                    .map(Msg::StyleGuideForm),
+                _ => Task::none(),
+            },
+            Msg::VoiceExemplarsForm(sub_msg) => match &mut self.lookup_status {
+                LookupStatus::Loaded {
+                    voice_exemplars_form,
+                    ..
+                } => voice_exemplars_form
+                    .update(worker, sub_msg)
+                    .map(Msg::VoiceExemplarsForm),
+                _ => Task::none(),
+            },
+            Msg::ClickedCheckConsistency { person_uuid } => {
+                if let LookupStatus::Loaded {
+                    consistency_check_status,
+                    ..
+                } = &mut self.lookup_status
+                {
+                    *consistency_check_status = ConsistencyCheckStatus::Enqueuing;
+                }
+
+                Task::perform(
+                    async move {
+                        worker
+                            .unshift_job(JobKind::CheckPersonaConsistency(
+                                CheckPersonaConsistencyJob { person_uuid },
+                            ))
+                            .await
+                    },
+                    Msg::ConsistencyCheckEnqueued,
+                )
+            }
+            Msg::ConsistencyCheckEnqueued(result) => {
+                if let LookupStatus::Loaded {
+                    consistency_check_status,
+                    ..
+                } = &mut self.lookup_status
+                {
+                    *consistency_check_status = match result {
+                        Ok(()) => ConsistencyCheckStatus::Enqueued,
+                        Err(err) => ConsistencyCheckStatus::Error(err),
+                    };
+                }
+                Task::none()
+            }
+            Msg::AutobiographyPathChanged(path) => {
+                if let LookupStatus::Loaded {
+                    autobiography_path_field,
+                    ..
+                } = &mut self.lookup_status
+                {
+                    *autobiography_path_field = path;
+                }
+                Task::none()
+            }
+            Msg::ClickedExportAutobiography { person_uuid } => {
+                let output_path = match &mut self.lookup_status {
+                    LookupStatus::Loaded {
+                        autobiography_path_field,
+                        autobiography_status,
+                        ..
+                    } => {
+                        *autobiography_status = AutobiographyStatus::Writing;
+                        autobiography_path_field.trim().to_string()
+                    }
+                    _ => return Task::none(),
+                };
+
+                Task::perform(
+                    async move {
+                        export_autobiography::export(
+                            worker.as_ref(),
+                            &person_uuid,
+                            output_path.as_str(),
+                        )
+                        .await
+                        .map_err(|err| err.message())
+                    },
+                    Msg::ExportedAutobiography,
+                )
+            }
+            Msg::ExportedAutobiography(result) => {
+                if let LookupStatus::Loaded {
+                    autobiography_status,
+                    ..
+                } = &mut self.lookup_status
+                {
+                    *autobiography_status = match result {
+                        Ok(chapter_count) => AutobiographyStatus::Done(chapter_count),
+                        Err(err) => AutobiographyStatus::Error(err),
+                    };
+                }
+                Task::none()
+            }
         }
     }
 
+    /// Keeps a copy of the new person on disk after every edit. Skipped
+    /// while an older draft is waiting to be restored or discarded.
+    fn save_draft(&mut self) {
+        if self.draft_status.is_offered() {
+            return;
+        }
+
+        let current = Draft {
+            name_field: self.name_field.clone(),
+            identity_field: self.identity_field.text(),
+        };
+
+        self.draft_status = match draft::save(DRAFT_NAME, &current) {
+            Ok(()) => DraftStatus::Saved,
+            Err(err) => DraftStatus::Error(err),
+        };
+    }
+
     fn update_person_enabled(
         &mut self,
         worker: Arc<Worker>,
@@ -302,12 +645,25 @@ impl Model {
     pub fn view(&self) -> Element<'_, Msg> {
         let create_section = w::column![
             w::text("Create Person"),
+            draft::view_status(
+                &self.draft_status,
+                Msg::ClickedRestoreDraft,
+                Msg::ClickedDiscardDraft
+            ),
             w::text("Person Name"),
             w::text_input("", &self.name_field).on_input(Msg::NameFieldChanged),
             w::text("Identity"),
-            w::text_editor(&self.identity_field)
+            w::text_editor(self.identity_field.content())
+                .key_binding(|key_press| {
+                    draft::undo_key_binding(
+                        key_press,
+                        Msg::ClickedUndoIdentity,
+                        Msg::ClickedRedoIdentity,
+                    )
+                })
                 .on_action(Msg::IdentityFieldChanged)
                 .height(iced::Length::Fixed(220.0)),
+            draft::view_undo_buttons(Msg::ClickedUndoIdentity, Msg::ClickedRedoIdentity),
             w::button("Create Person").on_press(Msg::ClickedCreatePerson),
             status_view(&self.status),
         ]
@@ -326,9 +682,19 @@ impl Model {
         ]
         .spacing(s::S2);
 
-        w::column![lookup_section, create_section]
-            .spacing(s::S4)
-            .into()
+        w::column![
+            lookup_section,
+            w::horizontal_rule(1),
+            self.persons_list.view().map(Msg::PersonsList),
+            w::horizontal_rule(1),
+            create_section,
+            w::horizontal_rule(1),
+            self.persona_generator.view().map(Msg::PersonaGenerator),
+            w::horizontal_rule(1),
+            self.person_merge.view().map(Msg::PersonMerge),
+        ]
+        .spacing(s::S4)
+        .into()
     }
 }
 
@@ -353,12 +719,21 @@ fn lookup_status_view(status: &LookupStatus) -> Element<'_, Msg> {
         LookupStatus::Loading => w::text("Loading...").into(),
         LookupStatus::Loaded {
             person_uuid,
+            person_name,
             identity,
             current_task,
             is_hibernating,
             hibernation_status,
             is_enabled,
             enabled_status,
+            chattiness_field,
+            chattiness_status,
+            style_guide_form,
+            voice_exemplars_form,
+            inconsistencies,
+            consistency_check_status,
+            autobiography_path_field,
+            autobiography_status,
         } => {
             let identity_text = match identity {
                 Some(text) => text.as_str(),
@@ -451,6 +826,15 @@ fn lookup_status_view(status: &LookupStatus) -> Element<'_, Msg> {
                     .into()
             };
 
+            let chattiness_status_view: Element<'_, Msg> = match chattiness_status {
+                ChattinessStatus::Ready => w::text("").into(),
+                ChattinessStatus::Updating => w::text("Saving chattiness...").into(),
+                ChattinessStatus::Saved => w::text("Saved").color(s::GREEN_SOFT).into(),
+                ChattinessStatus::Error(err) => {
+                    w::text(format!("Error saving chattiness: {}", err)).into()
+                }
+            };
+
             w::column![
                 w::text(format!("Person UUID: {}", person_uuid.to_uuid())),
                 w::text(identity_text),
@@ -462,6 +846,27 @@ fn lookup_status_view(status: &LookupStatus) -> Element<'_, Msg> {
                 w::text(hibernation_state_text),
                 w::row![hibernate_button, wake_button].spacing(s::S1),
                 hibernation_status_view,
+                w::button("Archive").on_press(Msg::StageOperation(Operation::ArchivePerson {
+                    person_uuid: person_uuid.clone(),
+                    person_name: person_name.clone(),
+                })),
+                w::text("Chattiness (0 to 1, the chance of joining back in when idle)"),
+                w::row![
+                    w::text_input("0.3", chattiness_field).on_input(Msg::ChattinessFieldChanged),
+                    w::button("Save").on_press(Msg::ClickedSaveChattiness {
+                        person_uuid: person_uuid.clone(),
+                    }),
+                ]
+                .spacing(s::S1),
+                chattiness_status_view,
+                w::text("Style guide (blank or World's uses the world's, from the Settings tab)"),
+                style_guide_form.view().map(Msg::StyleGuideForm),
+                w::text(
+                    "Voice exemplars (one a line, shown to reactions as examples of how they talk)"
+                ),
+                voice_exemplars_form.view().map(Msg::VoiceExemplarsForm),
+                persona_consistency_view(person_uuid, inconsistencies, consistency_check_status),
+                autobiography_view(person_uuid, autobiography_path_field, autobiography_status),
             ]
             .spacing(s::S1)
             .into()
@@ -473,7 +878,7 @@ fn lookup_status_view(status: &LookupStatus) -> Element<'_, Msg> {
 fn person_current_task_view(current_task: &Option<PersonTask>) -> Element<'_, Msg> {
     match current_task {
         Some(task) => {
-            let created_at = task.created_at.format("%Y-%m-%d %H:%M:%S").to_string();
+            let created_at = time_display::format_recent(task.created_at);
             w::column![
                 w::text("Current Task"),
                 w::text(format!("Created: {}", created_at)).size(s::S3),
@@ -494,6 +899,100 @@ fn person_current_task_view(current_task: &Option<PersonTask>) -> Element<'_, Ms
     }
 }
 
+fn autobiography_view<'a>(
+    person_uuid: &PersonUuid,
+    path_field: &'a str,
+    status: &'a AutobiographyStatus,
+) -> Element<'a, Msg> {
+    let export_button = match status {
+        AutobiographyStatus::Writing => w::button("Export autobiography"),
+        _ => w::button("Export autobiography").on_press(Msg::ClickedExportAutobiography {
+            person_uuid: person_uuid.clone(),
+        }),
+    };
+
+    let status_view: Element<'_, Msg> = match status {
+        AutobiographyStatus::Ready => w::text("").into(),
+        AutobiographyStatus::Writing => {
+            w::text("Writing autobiography, a chapter at a time...").into()
+        }
+        AutobiographyStatus::Done(chapter_count) => w::text(format!(
+            "Wrote {} chapters to {}",
+            chapter_count, path_field
+        ))
+        .color(s::GREEN_SOFT)
+        .into(),
+        AutobiographyStatus::Error(err) => {
+            w::text(format!("Error writing autobiography: {}", err)).into()
+        }
+    };
+
+    w::column![
+        w::text("Autobiography (their life story from their memories, identities and scenes)"),
+        w::row![
+            w::text_input("", path_field).on_input(Msg::AutobiographyPathChanged),
+            export_button,
+        ]
+        .spacing(s::S1),
+        status_view,
+    ]
+    .spacing(s::S1)
+    .into()
+}
+
+fn persona_consistency_view<'a>(
+    person_uuid: &PersonUuid,
+    inconsistencies: &'a [RecordedPersonaInconsistency],
+    status: &'a ConsistencyCheckStatus,
+) -> Element<'a, Msg> {
+    let check_button = match status {
+        ConsistencyCheckStatus::Enqueuing => w::button("Check persona consistency"),
+        _ => w::button("Check persona consistency").on_press(Msg::ClickedCheckConsistency {
+            person_uuid: person_uuid.clone(),
+        }),
+    };
+
+    let status_view: Element<'_, Msg> = match status {
+        ConsistencyCheckStatus::Ready => w::text("").into(),
+        ConsistencyCheckStatus::Enqueuing => w::text("Enqueuing consistency check...").into(),
+        ConsistencyCheckStatus::Enqueued => w::text(
+            "Consistency check enqueued. Load the person again once the job runner has run it.",
+        )
+        .color(s::GREEN_SOFT)
+        .into(),
+        ConsistencyCheckStatus::Error(err) => {
+            w::text(format!("Error enqueuing consistency check: {}", err)).into()
+        }
+    };
+
+    let report: Element<'_, Msg> = if inconsistencies.is_empty() {
+        w::text("No inconsistencies flagged.").into()
+    } else {
+        w::column(inconsistencies.iter().map(|recorded| {
+            let inconsistency = &recorded.inconsistency;
+            w::column![
+                w::text(time_display::format_recent(recorded.created_at)).size(s::S3),
+                w::text(format!("\"{}\"", inconsistency.utterance)),
+                w::text(format!("Contradicts: {}", inconsistency.identity_claim)).size(s::S3),
+                w::text(&inconsistency.explanation).size(s::S3),
+            ]
+            .spacing(s::S1)
+            .into()
+        }))
+        .spacing(s::S2)
+        .into()
+    };
+
+    w::column![
+        w::text("Persona Consistency"),
+        check_button,
+        status_view,
+        report,
+    ]
+    .spacing(s::S1)
+    .into()
+}
+
 fn optional_condition_view<'a>(label: &'static str, value: Option<&'a str>) -> Element<'a, Msg> {
     match value {
         Some(value) => w::text(format!("{}: {}", label, value)).size(s::S3).into(),
@@ -521,17 +1020,28 @@ async fn load_person_lookup_data(
     }
 
     let person_uuid = worker
-        .get_person_uuid_by_name(PersonName::from_string(person_name))
+        .get_person_uuid_by_name(PersonName::from_string(person_name.clone()))
         .await?;
     let identity = worker.get_person_identity(&person_uuid).await?;
     let current_task = worker.get_persons_current_active_task(&person_uuid).await?;
     let is_hibernating = worker.is_person_hibernating(&person_uuid).await?;
     let is_enabled = worker.is_person_enabled(&person_uuid).await?;
+    let chattiness = worker.get_person_chattiness(&person_uuid).await?;
+    let style_guide = worker.get_person_style_guide(&person_uuid).await?;
+    let voice_exemplars = worker.get_voice_exemplars(&person_uuid).await?;
+    let inconsistencies = worker
+        .get_persona_inconsistencies(&person_uuid, INCONSISTENCY_LIMIT)
+        .await?;
     Ok(LoadedPersonLookupData {
         person_uuid,
+        person_name,
         identity,
         current_task,
         is_hibernating,
         is_enabled,
+        chattiness,
+        style_guide,
+        voice_exemplars,
+        inconsistencies,
     })
 }
@@ -0,0 +1,214 @@
+use crate::admin_ui::s;
+use crate::capability::person::PersonCapability;
+use crate::capability::person_merge::PersonMergeCapability;
+use crate::domain::person_merge::PersonMergeCounts;
+use crate::domain::person_name::PersonName;
+use crate::domain::person_uuid::PersonUuid;
+use crate::worker::Worker;
+use iced::{widget as w, Element, Task};
+use std::sync::Arc;
+
+pub struct Model {
+    keep_field: String,
+    merge_field: String,
+    status: Status,
+}
+
+enum Status {
+    Ready,
+    Previewing,
+    /// Waiting for the merge to be confirmed.
+    Preview(Preview),
+    Merging(Preview),
+    Merged {
+        merged_name: String,
+        kept_name: String,
+        counts: PersonMergeCounts,
+    },
+    Error(String),
+}
+
+#[derive(Debug, Clone)]
+pub struct Preview {
+    keep: PersonUuid,
+    keep_name: String,
+    merge: PersonUuid,
+    merge_name: String,
+    counts: PersonMergeCounts,
+}
+
+#[derive(Debug, Clone)]
+pub enum Msg {
+    KeepFieldChanged(String),
+    MergeFieldChanged(String),
+    ClickedPreview,
+    LoadedPreview(Result<Preview, String>),
+    ClickedConfirm,
+    ClickedCancel,
+    Merged(Result<PersonMergeCounts, String>),
+}
+
+impl Model {
+    pub fn new() -> Self {
+        Self {
+            keep_field: String::new(),
+            merge_field: String::new(),
+            status: Status::Ready,
+        }
+    }
+
+    pub fn update(&mut self, worker: Arc<Worker>, msg: Msg) -> Task<Msg> {
+        match msg {
+            Msg::KeepFieldChanged(value) => {
+                self.keep_field = value;
+                self.status = Status::Ready;
+                Task::none()
+            }
+            Msg::MergeFieldChanged(value) => {
+                self.merge_field = value;
+                self.status = Status::Ready;
+                Task::none()
+            }
+            Msg::ClickedPreview => {
+                self.status = Status::Previewing;
+                let keep_name = self.keep_field.trim().to_string();
+                let merge_name = self.merge_field.trim().to_string();
+
+                Task::perform(
+                    async move { load_preview(&worker, keep_name, merge_name).await },
+                    Msg::LoadedPreview,
+                )
+            }
+            Msg::LoadedPreview(result) => {
+                self.status = match result {
+                    Ok(preview) => Status::Preview(preview),
+                    Err(err) => Status::Error(err),
+                };
+                Task::none()
+            }
+            Msg::ClickedConfirm => {
+                let preview = match &self.status {
+                    Status::Preview(preview) => preview.clone(),
+                    _ => return Task::none(),
+                };
+                self.status = Status::Merging(preview.clone());
+
+                Task::perform(
+                    async move { worker.merge_persons(&preview.keep, &preview.merge).await },
+                    Msg::Merged,
+                )
+            }
+            Msg::ClickedCancel => {
+                self.status = Status::Ready;
+                Task::none()
+            }
+            Msg::Merged(result) => {
+                let preview = match &self.status {
+                    Status::Merging(preview) => preview.clone(),
+                    _ => return Task::none(),
+                };
+                self.status = match result {
+                    Ok(counts) => Status::Merged {
+                        merged_name: preview.merge_name,
+                        kept_name: preview.keep_name,
+                        counts,
+                    },
+                    Err(err) => Status::Error(err),
+                };
+                Task::none()
+            }
+        }
+    }
+
+    pub fn view(&self) -> Element<'_, Msg> {
+        w::column![
+            w::text("Merge Duplicate Persons"),
+            w::row![
+                w::text_input("Person to keep", &self.keep_field).on_input(Msg::KeepFieldChanged),
+                w::text_input("Duplicate to merge into them", &self.merge_field)
+                    .on_input(Msg::MergeFieldChanged)
+                    .on_submit(Msg::ClickedPreview),
+                w::button("Preview").on_press(Msg::ClickedPreview),
+            ]
+            .spacing(s::S1),
+            status_view(&self.status),
+        ]
+        .spacing(s::S2)
+        .into()
+    }
+}
+
+fn status_view(status: &Status) -> Element<'_, Msg> {
+    match status {
+        Status::Ready => w::text("Nothing moves until the preview is confirmed").into(),
+        Status::Previewing => w::text("Loading...").into(),
+        Status::Preview(preview) => {
+            let mut col = w::column![w::text(format!(
+                "Moves to {} from {}:",
+                preview.keep_name, preview.merge_name
+            ))]
+            .spacing(s::S1);
+
+            for line in preview.counts.lines() {
+                col = col.push(w::text(format!("  {}", line)).size(s::S3));
+            }
+
+            col.push(
+                w::text(format!(
+                    "{} is then archived. Their other history stays with them.",
+                    preview.merge_name
+                ))
+                .size(s::S3)
+                .color(s::GOLD_SOFT),
+            )
+            .push(
+                w::row![
+                    w::button("Merge").on_press(Msg::ClickedConfirm),
+                    w::button("Cancel").on_press(Msg::ClickedCancel),
+                ]
+                .spacing(s::S1),
+            )
+            .into()
+        }
+        Status::Merging(_) => w::text("Merging...").into(),
+        Status::Merged {
+            merged_name,
+            kept_name,
+            counts,
+        } => w::text(format!(
+            "Merged {} into {}: {}",
+            merged_name,
+            kept_name,
+            counts.lines().join(", ")
+        ))
+        .color(s::GREEN_SOFT)
+        .into(),
+        Status::Error(err) => w::text(format!("Error: {}", err)).color(s::RED_SOFT).into(),
+    }
+}
+
+async fn load_preview(
+    worker: &Worker,
+    keep_name: String,
+    merge_name: String,
+) -> Result<Preview, String> {
+    if keep_name.is_empty() || merge_name.is_empty() {
+        return Err("Enter both persons' names".to_string());
+    }
+
+    let keep = worker
+        .get_person_uuid_by_name(PersonName::from_string(keep_name.clone()))
+        .await?;
+    let merge = worker
+        .get_person_uuid_by_name(PersonName::from_string(merge_name.clone()))
+        .await?;
+    let counts = worker.get_person_merge_counts(&keep, &merge).await?;
+
+    Ok(Preview {
+        keep,
+        keep_name,
+        merge,
+        merge_name,
+        counts,
+    })
+}
@@ -0,0 +1,214 @@
+use crate::admin_ui::s;
+use crate::capability::persona::PersonaCapability;
+use crate::domain::person_name::PersonName;
+use crate::domain::person_uuid::PersonUuid;
+use crate::domain::persona::{self, Persona};
+use crate::nice_display::NiceDisplay;
+use crate::worker::Worker;
+use iced::{widget as w, Element, Length, Task};
+use std::sync::Arc;
+
+pub struct Model {
+    concept_field: String,
+    status: Status,
+}
+
+enum Status {
+    Ready,
+    Generating,
+    Preview(Preview),
+    Creating,
+    Created(PersonName),
+    Error(String),
+}
+
+/// The generated persona, held as editable fields until it gets created.
+struct Preview {
+    name_field: String,
+    identity_field: w::text_editor::Content,
+    state_of_mind_field: String,
+    memories_field: w::text_editor::Content,
+}
+
+#[derive(Debug, Clone)]
+pub enum Msg {
+    ConceptFieldChanged(String),
+    ClickedGenerate,
+    Generated(Result<Persona, String>),
+    NameFieldChanged(String),
+    IdentityFieldChanged(w::text_editor::Action),
+    StateOfMindFieldChanged(String),
+    MemoriesFieldChanged(w::text_editor::Action),
+    ClickedCreate,
+    ClickedDiscard,
+    Created(Result<(PersonName, PersonUuid), String>),
+}
+
+impl Preview {
+    fn from_persona(persona: Persona) -> Self {
+        Self {
+            name_field: persona.name.as_str().to_string(),
+            identity_field: w::text_editor::Content::with_text(&persona.identity),
+            state_of_mind_field: persona.state_of_mind,
+            memories_field: w::text_editor::Content::with_text(&persona.memories.join("\n")),
+        }
+    }
+
+    fn to_persona(&self) -> Persona {
+        Persona {
+            name: PersonName::from_string(self.name_field.trim().to_string()),
+            identity: self.identity_field.text(),
+            state_of_mind: self.state_of_mind_field.clone(),
+            memories: self
+                .memories_field
+                .text()
+                .lines()
+                .map(|line| line.trim())
+                .filter(|line| !line.is_empty())
+                .map(|line| line.to_string())
+                .collect(),
+        }
+    }
+}
+
+impl Model {
+    pub fn new(concept_field: String) -> Self {
+        Self {
+            concept_field,
+            status: Status::Ready,
+        }
+    }
+
+    pub fn concept_field(&self) -> String {
+        self.concept_field.clone()
+    }
+
+    pub fn update(&mut self, worker: Arc<Worker>, msg: Msg) -> Task<Msg> {
+        match msg {
+            Msg::ConceptFieldChanged(value) => {
+                self.concept_field = value;
+                Task::none()
+            }
+            Msg::ClickedGenerate => {
+                self.status = Status::Generating;
+                let concept = self.concept_field.clone();
+                Task::perform(
+                    async move { worker.generate_persona(concept.as_str()).await },
+                    Msg::Generated,
+                )
+            }
+            Msg::Generated(result) => {
+                self.status = match result {
+                    Ok(persona) => Status::Preview(Preview::from_persona(persona)),
+                    Err(err) => Status::Error(err),
+                };
+                Task::none()
+            }
+            Msg::NameFieldChanged(value) => {
+                if let Status::Preview(preview) = &mut self.status {
+                    preview.name_field = value;
+                }
+                Task::none()
+            }
+            Msg::IdentityFieldChanged(action) => {
+                if let Status::Preview(preview) = &mut self.status {
+                    preview.identity_field.perform(action);
+                }
+                Task::none()
+            }
+            Msg::StateOfMindFieldChanged(value) => {
+                if let Status::Preview(preview) = &mut self.status {
+                    preview.state_of_mind_field = value;
+                }
+                Task::none()
+            }
+            Msg::MemoriesFieldChanged(action) => {
+                if let Status::Preview(preview) = &mut self.status {
+                    preview.memories_field.perform(action);
+                }
+                Task::none()
+            }
+            Msg::ClickedCreate => {
+                let persona = match &self.status {
+                    Status::Preview(preview) => preview.to_persona(),
+                    _ => return Task::none(),
+                };
+
+                self.status = Status::Creating;
+                let person_name = persona.name.clone();
+                Task::perform(
+                    async move {
+                        persona::create_persona(worker.as_ref(), persona)
+                            .await
+                            .map(|person_uuid| (person_name, person_uuid))
+                            .map_err(|err| err.message())
+                    },
+                    Msg::Created,
+                )
+            }
+            Msg::ClickedDiscard => {
+                self.status = Status::Ready;
+                Task::none()
+            }
+            Msg::Created(result) => {
+                self.status = match result {
+                    Ok((person_name, _)) => Status::Created(person_name),
+                    Err(err) => Status::Error(err),
+                };
+                Task::none()
+            }
+        }
+    }
+
+    pub fn view(&self) -> Element<'_, Msg> {
+        let concept_row = w::row![
+            w::text_input(
+                "Retired trucker who now runs a bait shop",
+                &self.concept_field
+            )
+            .on_input(Msg::ConceptFieldChanged)
+            .on_submit(Msg::ClickedGenerate),
+            w::button("Generate Persona").on_press(Msg::ClickedGenerate),
+        ]
+        .spacing(s::S2);
+
+        let status_view: Element<Msg> = match &self.status {
+            Status::Ready => w::text("").into(),
+            Status::Generating => w::text("Generating persona...").into(),
+            Status::Creating => w::text("Creating person...").into(),
+            Status::Created(person_name) => w::text(format!("Created {}", person_name))
+                .color(s::GREEN_SOFT)
+                .into(),
+            Status::Error(err) => w::text(format!("Error: {}", err)).color(s::RED_SOFT).into(),
+            Status::Preview(preview) => preview_view(preview),
+        };
+
+        w::column![w::text("Generate Persona"), concept_row, status_view]
+            .spacing(s::S2)
+            .into()
+    }
+}
+
+fn preview_view(preview: &Preview) -> Element<'_, Msg> {
+    w::column![
+        w::text("Name"),
+        w::text_input("", &preview.name_field).on_input(Msg::NameFieldChanged),
+        w::text("Identity"),
+        w::text_editor(&preview.identity_field)
+            .on_action(Msg::IdentityFieldChanged)
+            .height(Length::Fixed(220.0)),
+        w::text("State of mind"),
+        w::text_input("", &preview.state_of_mind_field).on_input(Msg::StateOfMindFieldChanged),
+        w::text("Seed memories (one per line)"),
+        w::text_editor(&preview.memories_field)
+            .on_action(Msg::MemoriesFieldChanged)
+            .height(Length::Fixed(220.0)),
+        w::row![
+            w::button("Create Person").on_press(Msg::ClickedCreate),
+            w::button("Discard").on_press(Msg::ClickedDiscard),
+        ]
+        .spacing(s::S2),
+    ]
+    .spacing(s::S2)
+    .into()
+}
@@ -0,0 +1,238 @@
+use crate::admin_ui::s;
+use crate::capability::person_query::{PersonQueryCapability, PersonSummary};
+use crate::capability::scene::SceneCapability;
+use crate::domain::person_filter::{self, PersonFilter, PersonTag};
+use crate::domain::topic::Topic;
+use crate::time_display;
+use crate::worker::Worker;
+use iced::{widget as w, Element, Task};
+use std::sync::Arc;
+
+pub struct Model {
+    scene_field: String,
+    tag_field: String,
+    idle_field: String,
+    topic_field: String,
+    has_unread_messages: bool,
+    status: Status,
+}
+
+enum Status {
+    Ready,
+    Loading,
+    Loaded(Vec<PersonSummary>),
+    Error(String),
+}
+
+#[derive(Debug, Clone)]
+pub enum Msg {
+    SceneFieldChanged(String),
+    TagFieldChanged(String),
+    IdleFieldChanged(String),
+    TopicFieldChanged(String),
+    ToggledUnread(bool),
+    ClickedSearch,
+    Loaded(Result<Vec<PersonSummary>, String>),
+    /// Handled by the person page, which loads the person into its lookup.
+    ClickedLookUp(String),
+}
+
+impl Model {
+    pub fn new() -> Self {
+        Self {
+            scene_field: String::new(),
+            tag_field: String::new(),
+            idle_field: String::new(),
+            topic_field: String::new(),
+            has_unread_messages: false,
+            status: Status::Ready,
+        }
+    }
+
+    pub fn update(&mut self, worker: Arc<Worker>, msg: Msg) -> Task<Msg> {
+        match msg {
+            Msg::SceneFieldChanged(value) => {
+                self.scene_field = value;
+                Task::none()
+            }
+            Msg::TagFieldChanged(value) => {
+                self.tag_field = value;
+                Task::none()
+            }
+            Msg::IdleFieldChanged(value) => {
+                self.idle_field = value;
+                Task::none()
+            }
+            Msg::TopicFieldChanged(value) => {
+                self.topic_field = value;
+                Task::none()
+            }
+            Msg::ToggledUnread(value) => {
+                self.has_unread_messages = value;
+                Task::none()
+            }
+            Msg::ClickedSearch => {
+                self.status = Status::Loading;
+                let scene_name = self.scene_field.trim().to_string();
+                let tag = self.tag_field.clone();
+                let idle = self.idle_field.clone();
+                let topic = self.topic_field.clone();
+                let has_unread_messages = self.has_unread_messages;
+
+                Task::perform(
+                    async move {
+                        let filter = load_filter(
+                            &worker,
+                            scene_name,
+                            &tag,
+                            &idle,
+                            &topic,
+                            has_unread_messages,
+                        )
+                        .await?;
+                        worker.query_persons(&filter).await
+                    },
+                    Msg::Loaded,
+                )
+            }
+            Msg::Loaded(result) => {
+                self.status = match result {
+                    Ok(persons) => Status::Loaded(persons),
+                    Err(err) => Status::Error(err),
+                };
+                Task::none()
+            }
+            Msg::ClickedLookUp(_) => Task::none(),
+        }
+    }
+
+    pub fn view(&self) -> Element<'_, Msg> {
+        w::column![
+            w::text("Find Persons"),
+            w::row![
+                w::text_input("In scene", &self.scene_field)
+                    .on_input(Msg::SceneFieldChanged)
+                    .on_submit(Msg::ClickedSearch),
+                w::text_input("Tagged", &self.tag_field)
+                    .on_input(Msg::TagFieldChanged)
+                    .on_submit(Msg::ClickedSearch),
+                w::text_input("Idle for, like 1h", &self.idle_field)
+                    .on_input(Msg::IdleFieldChanged)
+                    .on_submit(Msg::ClickedSearch),
+                w::text_input("Talked about", &self.topic_field)
+                    .on_input(Msg::TopicFieldChanged)
+                    .on_submit(Msg::ClickedSearch),
+                w::checkbox("Has unread messages", self.has_unread_messages)
+                    .on_toggle(Msg::ToggledUnread),
+                w::button("Search").on_press(Msg::ClickedSearch),
+            ]
+            .spacing(s::S1),
+            status_view(&self.status),
+        ]
+        .spacing(s::S2)
+        .into()
+    }
+}
+
+fn status_view(status: &Status) -> Element<'_, Msg> {
+    let persons = match status {
+        Status::Ready => return w::text("Leave every field blank to list everyone").into(),
+        Status::Loading => return w::text("Loading...").into(),
+        Status::Error(err) => return w::text(format!("Error: {}", err)).color(s::RED_SOFT).into(),
+        Status::Loaded(persons) => persons,
+    };
+
+    if persons.is_empty() {
+        return w::text("No persons match").into();
+    }
+
+    let mut col =
+        w::column![w::text(format!("{} persons", persons.len())).size(s::S3)].spacing(s::S1);
+
+    for person in persons {
+        let last_reacted = match person.last_reacted_at {
+            Some(last_reacted_at) => time_display::format_recent(last_reacted_at),
+            None => "never".to_string(),
+        };
+        let mut details = vec![format!("last reacted {}", last_reacted)];
+        if let Some(scene_name) = &person.scene_name {
+            details.insert(0, format!("in {}", scene_name));
+        }
+        if person.is_hibernating {
+            details.push("hibernating".to_string());
+        }
+        if !person.is_enabled {
+            details.push("disabled".to_string());
+        }
+        if person.unread_messages > 0 {
+            details.push(format!("{} unread", person.unread_messages));
+        }
+
+        let tags: Element<'_, Msg> = if person.tags.is_empty() {
+            w::text("").into()
+        } else {
+            w::text(person.tags.join(", "))
+                .size(s::S3)
+                .color(s::GOLD_SOFT)
+                .into()
+        };
+
+        col = col.push(
+            w::row![
+                w::button(w::text(person.name.as_str()))
+                    .on_press(Msg::ClickedLookUp(person.name.as_str().to_string())),
+                w::text(details.join(", ")).size(s::S3),
+                tags,
+            ]
+            .spacing(s::S2)
+            .align_y(iced::Alignment::Center),
+        );
+    }
+
+    col.into()
+}
+
+async fn load_filter(
+    worker: &Worker,
+    scene_name: String,
+    tag: &str,
+    idle: &str,
+    topic: &str,
+    has_unread_messages: bool,
+) -> Result<PersonFilter, String> {
+    let scene_uuid = if scene_name.is_empty() {
+        None
+    } else {
+        let scene = worker
+            .get_scene_from_name(scene_name.clone())
+            .await?
+            .ok_or_else(|| format!("No scene named \"{}\"", scene_name))?;
+        Some(scene.uuid)
+    };
+
+    let tag = if tag.trim().is_empty() {
+        None
+    } else {
+        Some(PersonTag::parse(tag)?)
+    };
+
+    let idle_for = if idle.trim().is_empty() {
+        None
+    } else {
+        Some(person_filter::parse_duration(idle)?)
+    };
+
+    let topic = if topic.trim().is_empty() {
+        None
+    } else {
+        Some(Topic::parse(topic)?)
+    };
+
+    Ok(PersonFilter {
+        scene_uuid,
+        tag,
+        idle_for,
+        has_unread_messages,
+        topic,
+    })
+}
@@ -5,6 +5,7 @@ use crate::domain::person_name::PersonName;
 use crate::domain::person_task::PersonTask;
 use crate::domain::person_task_uuid::PersonTaskUuid;
 use crate::domain::person_uuid::PersonUuid;
+use crate::time_display;
 use crate::worker::Worker;
 use iced::{widget as w, Element, Task};
 use serde::{Deserialize, Serialize};
@@ -315,7 +316,7 @@ fn current_task_view(status: &LoadStatus) -> Element<'_, Msg> {
     match status {
         LoadStatus::Loaded { current_task, .. } => match current_task {
             Some(task) => {
-                let created_at = task.created_at.format("%Y-%m-%d %H:%M:%S").to_string();
+                let created_at = time_display::format_recent(task.created_at);
                 w::column![
                     w::text("Current Active Task"),
                     w::text(format!("Priority: {}", task.priority)).size(s::S3),
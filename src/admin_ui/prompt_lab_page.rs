@@ -69,7 +69,12 @@ impl Model {
                 self.status = Status::Submitting;
 
                 Task::perform(
-                    call::submit_prompt_lab(open_ai_key, system_prompt, user_prompt),
+                    call::submit_prompt_lab(
+                        open_ai_key,
+                        worker.open_ai_client.clone(),
+                        system_prompt,
+                        user_prompt,
+                    ),
                     Msg::SubmissionResult,
                 )
             }
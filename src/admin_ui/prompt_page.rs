@@ -0,0 +1,660 @@
+use super::call::{self, PromptRequest};
+use super::style as s;
+use crate::db::WorldName;
+use crate::nice_display::NiceDisplay;
+use crate::open_ai::completion::CompletionError;
+use crate::open_ai::model::Model as OpenAiModel;
+use crate::open_ai::role::Role;
+use crate::time_display;
+use crate::worker::Worker;
+use chrono::{DateTime, Utc};
+use iced::{widget as w, Element, Task};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+const HISTORY_LIMIT: usize = 50;
+const HISTORY_PREVIEW_CHARS: usize = 80;
+const DEFAULT_PROFILE: &str = "default";
+
+pub struct Model {
+    /// History is kept separately for each world, so prompts written
+    /// against one world's people do not clutter another's.
+    profile: String,
+    system_prompt: w::text_editor::Content,
+    /// Earlier messages sent before the prompt, to pick a conversation up
+    /// part way through.
+    turns: Vec<TurnEditor>,
+    prompt: w::text_editor::Content,
+    model_field: String,
+    temperature_field: String,
+    max_tokens_field: String,
+    json_mode: bool,
+    history: HashMap<String, Vec<HistoryEntry>>,
+    presets: Vec<Preset>,
+    preset_name_field: String,
+    status: Status,
+}
+
+struct TurnEditor {
+    role: TurnRole,
+    content: w::text_editor::Content,
+}
+
+enum Status {
+    Ready,
+    Submitting,
+    Response(String),
+    Error(String),
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct Storage {
+    #[serde(default)]
+    pub system_prompt: String,
+    #[serde(default)]
+    pub model: String,
+    #[serde(default)]
+    pub temperature: String,
+    #[serde(default)]
+    pub max_tokens: String,
+    #[serde(default)]
+    pub json_mode: bool,
+    #[serde(default)]
+    pub history: HashMap<String, Vec<HistoryEntry>>,
+    #[serde(default)]
+    pub turns: Vec<Turn>,
+    #[serde(default)]
+    pub presets: Vec<Preset>,
+    #[serde(default)]
+    pub preset_name_field: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TurnRole {
+    User,
+    Assistant,
+}
+
+impl TurnRole {
+    fn to_role(self) -> Role {
+        match self {
+            TurnRole::User => Role::User,
+            TurnRole::Assistant => Role::Assistant,
+        }
+    }
+
+    fn to_label(self) -> String {
+        match self {
+            TurnRole::User => "User".to_string(),
+            TurnRole::Assistant => "Assistant".to_string(),
+        }
+    }
+
+    fn other(self) -> TurnRole {
+        match self {
+            TurnRole::User => TurnRole::Assistant,
+            TurnRole::Assistant => TurnRole::User,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Turn {
+    role: TurnRole,
+    content: String,
+}
+
+/// A named conversation that can be loaded back into the prompt tab, for
+/// reproducing things that only go wrong a few messages in.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Preset {
+    name: String,
+    system_prompt: String,
+    #[serde(default)]
+    turns: Vec<Turn>,
+    #[serde(default)]
+    prompt: String,
+}
+
+/// One prompt that was sent, with the options it was sent with. The options
+/// are kept as they were typed so loading an entry puts the fields back
+/// exactly.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct HistoryEntry {
+    sent_at: DateTime<Utc>,
+    system_prompt: String,
+    #[serde(default)]
+    turns: Vec<Turn>,
+    prompt: String,
+    model: String,
+    temperature: String,
+    max_tokens: String,
+    json_mode: bool,
+    response: Result<String, String>,
+}
+
+#[derive(Debug, Clone)]
+pub enum Msg {
+    SystemPromptUpdated(w::text_editor::Action),
+    PromptUpdated(w::text_editor::Action),
+    ModelFieldChanged(String),
+    TemperatureFieldChanged(String),
+    MaxTokensFieldChanged(String),
+    JsonModeToggled(bool),
+    ClickedSubmit,
+    SubmissionResult {
+        entry: Box<HistoryEntry>,
+        result: Result<String, CompletionError>,
+    },
+    ClickedLoadHistoryEntry(usize),
+    ClickedClearHistory,
+    ClickedAddTurn,
+    TurnUpdated {
+        index: usize,
+        action: w::text_editor::Action,
+    },
+    ClickedSwapTurnRole(usize),
+    ClickedMoveTurnUp(usize),
+    ClickedRemoveTurn(usize),
+    PresetNameFieldChanged(String),
+    ClickedSavePreset,
+    ClickedLoadPreset(usize),
+    ClickedDeletePreset(usize),
+}
+
+impl Model {
+    pub fn new(prompt: &str, storage: &Storage, world: &Option<WorldName>) -> Self {
+        let profile = match world {
+            Some(world) => world.to_string(),
+            None => DEFAULT_PROFILE.to_string(),
+        };
+
+        Self {
+            profile,
+            system_prompt: w::text_editor::Content::with_text(&storage.system_prompt),
+            turns: storage.turns.iter().map(TurnEditor::new).collect(),
+            prompt: w::text_editor::Content::with_text(prompt),
+            model_field: storage.model.clone(),
+            temperature_field: storage.temperature.clone(),
+            max_tokens_field: storage.max_tokens.clone(),
+            json_mode: storage.json_mode,
+            history: storage.history.clone(),
+            presets: storage.presets.clone(),
+            preset_name_field: storage.preset_name_field.clone(),
+            status: Status::Ready,
+        }
+    }
+
+    pub fn prompt(&self) -> String {
+        self.prompt.text()
+    }
+
+    pub fn to_storage(&self) -> Storage {
+        Storage {
+            system_prompt: self.system_prompt.text(),
+            model: self.model_field.clone(),
+            temperature: self.temperature_field.clone(),
+            max_tokens: self.max_tokens_field.clone(),
+            json_mode: self.json_mode,
+            history: self.history.clone(),
+            turns: self.to_turns(),
+            presets: self.presets.clone(),
+            preset_name_field: self.preset_name_field.clone(),
+        }
+    }
+
+    fn to_turns(&self) -> Vec<Turn> {
+        self.turns.iter().map(TurnEditor::to_turn).collect()
+    }
+
+    fn load_conversation(&mut self, system_prompt: &str, turns: &[Turn], prompt: &str) {
+        self.system_prompt = w::text_editor::Content::with_text(system_prompt);
+        self.turns = turns.iter().map(TurnEditor::new).collect();
+        self.prompt = w::text_editor::Content::with_text(prompt);
+    }
+
+    fn profile_history(&self) -> &[HistoryEntry] {
+        match self.history.get(&self.profile) {
+            Some(entries) => entries,
+            None => &[],
+        }
+    }
+
+    pub fn update(&mut self, worker: Arc<Worker>, msg: Msg) -> Task<Msg> {
+        match msg {
+            Msg::SystemPromptUpdated(action) => {
+                self.system_prompt.perform(action);
+                Task::none()
+            }
+            Msg::PromptUpdated(action) => {
+                self.prompt.perform(action);
+                Task::none()
+            }
+            Msg::ModelFieldChanged(field) => {
+                self.model_field = field;
+                Task::none()
+            }
+            Msg::TemperatureFieldChanged(field) => {
+                self.temperature_field = field;
+                Task::none()
+            }
+            Msg::MaxTokensFieldChanged(field) => {
+                self.max_tokens_field = field;
+                Task::none()
+            }
+            Msg::JsonModeToggled(json_mode) => {
+                self.json_mode = json_mode;
+                Task::none()
+            }
+            Msg::ClickedSubmit => {
+                let request = match self.to_request() {
+                    Ok(request) => request,
+                    Err(err) => {
+                        self.status = Status::Error(err);
+                        return Task::none();
+                    }
+                };
+
+                let entry = HistoryEntry {
+                    sent_at: Utc::now(),
+                    system_prompt: request.system_prompt.clone(),
+                    turns: self.to_turns(),
+                    prompt: request.prompt.clone(),
+                    model: self.model_field.clone(),
+                    temperature: self.temperature_field.clone(),
+                    max_tokens: self.max_tokens_field.clone(),
+                    json_mode: self.json_mode,
+                    response: Ok(String::new()),
+                };
+
+                self.status = Status::Submitting;
+
+                Task::perform(
+                    call::submit_prompt(
+                        worker.open_ai_key.clone(),
+                        worker.open_ai_client.clone(),
+                        request,
+                    ),
+                    move |result| Msg::SubmissionResult {
+                        entry: Box::new(entry.clone()),
+                        result,
+                    },
+                )
+            }
+            Msg::SubmissionResult { entry, result } => {
+                let response = result.map_err(|err| err.to_nice_error().to_string());
+
+                self.status = match &response {
+                    Ok(text) => Status::Response(text.clone()),
+                    Err(err) => Status::Error(err.clone()),
+                };
+
+                let entries = self.history.entry(self.profile.clone()).or_default();
+                entries.insert(0, HistoryEntry { response, ..*entry });
+                entries.truncate(HISTORY_LIMIT);
+
+                Task::none()
+            }
+            Msg::ClickedLoadHistoryEntry(index) => {
+                if let Some(entry) = self.profile_history().get(index).cloned() {
+                    self.load_conversation(&entry.system_prompt, &entry.turns, &entry.prompt);
+                    self.model_field = entry.model;
+                    self.temperature_field = entry.temperature;
+                    self.max_tokens_field = entry.max_tokens;
+                    self.json_mode = entry.json_mode;
+                    self.status = match entry.response {
+                        Ok(text) => Status::Response(text),
+                        Err(err) => Status::Error(err),
+                    };
+                }
+                Task::none()
+            }
+            Msg::ClickedClearHistory => {
+                self.history.remove(&self.profile);
+                Task::none()
+            }
+            Msg::ClickedAddTurn => {
+                let role = match self.turns.last() {
+                    Some(turn) => turn.role.other(),
+                    None => TurnRole::User,
+                };
+                self.turns.push(TurnEditor {
+                    role,
+                    content: w::text_editor::Content::new(),
+                });
+                Task::none()
+            }
+            Msg::TurnUpdated { index, action } => {
+                if let Some(turn) = self.turns.get_mut(index) {
+                    turn.content.perform(action);
+                }
+                Task::none()
+            }
+            Msg::ClickedSwapTurnRole(index) => {
+                if let Some(turn) = self.turns.get_mut(index) {
+                    turn.role = turn.role.other();
+                }
+                Task::none()
+            }
+            Msg::ClickedMoveTurnUp(index) => {
+                if index > 0 && index < self.turns.len() {
+                    self.turns.swap(index - 1, index);
+                }
+                Task::none()
+            }
+            Msg::ClickedRemoveTurn(index) => {
+                if index < self.turns.len() {
+                    self.turns.remove(index);
+                }
+                Task::none()
+            }
+            Msg::PresetNameFieldChanged(field) => {
+                self.preset_name_field = field;
+                Task::none()
+            }
+            Msg::ClickedSavePreset => {
+                let name = self.preset_name_field.trim().to_string();
+                if name.is_empty() {
+                    self.status = Status::Error("Give the preset a name first".to_string());
+                    return Task::none();
+                }
+
+                let preset = Preset {
+                    name,
+                    system_prompt: self.system_prompt.text(),
+                    turns: self.to_turns(),
+                    prompt: self.prompt.text(),
+                };
+
+                // Saving under a name that is taken replaces that preset
+                match self
+                    .presets
+                    .iter_mut()
+                    .find(|existing| existing.name == preset.name)
+                {
+                    Some(existing) => *existing = preset,
+                    None => self.presets.push(preset),
+                }
+                Task::none()
+            }
+            Msg::ClickedLoadPreset(index) => {
+                if let Some(preset) = self.presets.get(index).cloned() {
+                    self.load_conversation(&preset.system_prompt, &preset.turns, &preset.prompt);
+                    self.preset_name_field = preset.name;
+                }
+                Task::none()
+            }
+            Msg::ClickedDeletePreset(index) => {
+                if index < self.presets.len() {
+                    self.presets.remove(index);
+                }
+                Task::none()
+            }
+        }
+    }
+
+    fn to_request(&self) -> Result<PromptRequest, String> {
+        let model = if self.model_field.trim().is_empty() {
+            OpenAiModel::DEFAULT
+        } else {
+            OpenAiModel::from_id(&self.model_field)
+        };
+
+        let turns = self
+            .turns
+            .iter()
+            .map(|turn| (turn.role.to_role(), turn.content.text()))
+            .collect::<Vec<(Role, String)>>();
+        let prompt = self.prompt.text();
+
+        if turns.is_empty() && prompt.trim().is_empty() {
+            return Err("There are no messages to send".to_string());
+        }
+
+        Ok(PromptRequest {
+            system_prompt: self.system_prompt.text(),
+            turns,
+            prompt,
+            model,
+            temperature: parse_temperature(&self.temperature_field)?,
+            max_tokens: parse_max_tokens(&self.max_tokens_field)?,
+            json_mode: self.json_mode,
+        })
+    }
+
+    pub fn view(&self) -> Element<'_, Msg> {
+        let options = w::row![
+            w::column![
+                w::text("Model").size(s::S3),
+                w::text_input(&OpenAiModel::DEFAULT.to_string(), &self.model_field)
+                    .on_input(Msg::ModelFieldChanged),
+            ]
+            .spacing(s::S1),
+            w::column![
+                w::text("Temperature").size(s::S3),
+                w::text_input("default", &self.temperature_field)
+                    .on_input(Msg::TemperatureFieldChanged),
+            ]
+            .spacing(s::S1),
+            w::column![
+                w::text("Max tokens").size(s::S3),
+                w::text_input("default", &self.max_tokens_field)
+                    .on_input(Msg::MaxTokensFieldChanged),
+            ]
+            .spacing(s::S1),
+            w::checkbox("JSON mode", self.json_mode).on_toggle(Msg::JsonModeToggled),
+        ]
+        .spacing(s::S4)
+        .align_y(iced::Alignment::End);
+
+        let response_view: Element<'_, Msg> = match &self.status {
+            Status::Ready => w::text("").into(),
+            Status::Submitting => w::text("Submitting...").into(),
+            Status::Response(text) => w::text(format!("Response: {}", text)).into(),
+            Status::Error(err) => w::text(format!("Error: {}", err)).color(s::RED_SOFT).into(),
+        };
+
+        let submit_button = match self.status {
+            Status::Submitting => w::button("Submitting..."),
+            _ => w::button("Submit").on_press(Msg::ClickedSubmit),
+        };
+
+        w::column![
+            w::text("System prompt").size(s::S3),
+            w::text_editor(&self.system_prompt)
+                .on_action(Msg::SystemPromptUpdated)
+                .height(iced::Length::Fixed(120.0)),
+            self.view_turns(),
+            w::text("Prompt").size(s::S3),
+            w::text_editor(&self.prompt)
+                .on_action(Msg::PromptUpdated)
+                .height(iced::Length::Fixed(220.0)),
+            options,
+            submit_button,
+            response_view,
+            w::horizontal_rule(1),
+            self.view_presets(),
+            w::horizontal_rule(1),
+            self.view_history(),
+        ]
+        .spacing(s::S2)
+        .into()
+    }
+
+    fn view_turns(&self) -> Element<'_, Msg> {
+        let mut col = w::column![w::text("Earlier messages").size(s::S3)].spacing(s::S2);
+
+        for (index, turn) in self.turns.iter().enumerate() {
+            let role_color = match turn.role {
+                TurnRole::User => s::GOLD_SOFT,
+                TurnRole::Assistant => s::GREEN_SOFT,
+            };
+
+            col = col.push(
+                w::column![
+                    w::row![
+                        w::text(turn.role.to_label()).color(role_color),
+                        w::button("Swap Role").on_press(Msg::ClickedSwapTurnRole(index)),
+                        w::button("Move Up").on_press(Msg::ClickedMoveTurnUp(index)),
+                        w::button("Remove").on_press(Msg::ClickedRemoveTurn(index)),
+                    ]
+                    .spacing(s::S2),
+                    w::text_editor(&turn.content)
+                        .on_action(move |action| Msg::TurnUpdated { index, action })
+                        .height(iced::Length::Fixed(100.0)),
+                ]
+                .spacing(s::S1),
+            );
+        }
+
+        col.push(w::button("Add Message").on_press(Msg::ClickedAddTurn))
+            .into()
+    }
+
+    fn view_presets(&self) -> Element<'_, Msg> {
+        let mut col = w::column![
+            w::text("Presets").size(16),
+            w::row![
+                w::text_input("Preset name", &self.preset_name_field)
+                    .on_input(Msg::PresetNameFieldChanged),
+                w::button("Save Preset").on_press(Msg::ClickedSavePreset),
+            ]
+            .spacing(s::S2),
+        ]
+        .spacing(s::S2);
+
+        for (index, preset) in self.presets.iter().enumerate() {
+            col = col.push(
+                w::row![
+                    w::button("Load").on_press(Msg::ClickedLoadPreset(index)),
+                    w::button("Delete").on_press(Msg::ClickedDeletePreset(index)),
+                    w::text(&preset.name),
+                    w::text(format!("{} earlier messages", preset.turns.len())).color(s::GRAY_SOFT),
+                ]
+                .spacing(s::S2),
+            );
+        }
+
+        col.into()
+    }
+
+    fn view_history(&self) -> Element<'_, Msg> {
+        let entries = self.profile_history();
+
+        let mut col = w::column![w::row![
+            w::text(format!("History ({})", self.profile)).size(16),
+            w::button("Clear History").on_press(Msg::ClickedClearHistory),
+        ]
+        .spacing(s::S4)]
+        .spacing(s::S2);
+
+        if entries.is_empty() {
+            col = col.push(w::text("Nothing sent yet").color(s::GRAY_SOFT));
+        }
+
+        for (index, entry) in entries.iter().enumerate() {
+            let outcome = match &entry.response {
+                Ok(_) => w::text("ok").color(s::GREEN_SOFT),
+                Err(_) => w::text("error").color(s::RED_SOFT),
+            };
+
+            col = col.push(
+                w::row![
+                    w::button("Load").on_press(Msg::ClickedLoadHistoryEntry(index)),
+                    w::text(time_display::format_recent(entry.sent_at)).color(s::GRAY_SOFT),
+                    outcome,
+                    w::text(preview(&entry.prompt)),
+                ]
+                .spacing(s::S2),
+            );
+        }
+
+        col.into()
+    }
+}
+
+impl TurnEditor {
+    fn new(turn: &Turn) -> Self {
+        TurnEditor {
+            role: turn.role,
+            content: w::text_editor::Content::with_text(&turn.content),
+        }
+    }
+
+    fn to_turn(&self) -> Turn {
+        Turn {
+            role: self.role,
+            content: self.content.text(),
+        }
+    }
+}
+
+/// The first line of the text, cut short.
+fn preview(text: &str) -> String {
+    let first_line = text.lines().next().unwrap_or("");
+    if first_line.chars().count() > HISTORY_PREVIEW_CHARS {
+        let cut = first_line
+            .chars()
+            .take(HISTORY_PREVIEW_CHARS)
+            .collect::<String>();
+        format!("{}...", cut)
+    } else {
+        first_line.to_string()
+    }
+}
+
+/// An empty field means the model's default.
+fn parse_temperature(field: &str) -> Result<Option<f32>, String> {
+    let field = field.trim();
+    if field.is_empty() {
+        return Ok(None);
+    }
+
+    let temperature = field
+        .parse::<f32>()
+        .map_err(|_| format!("Temperature \"{}\" is not a number", field))?;
+
+    if !(0.0..=2.0).contains(&temperature) {
+        return Err(format!(
+            "Temperature must be between 0 and 2, got {}",
+            temperature
+        ));
+    }
+
+    Ok(Some(temperature))
+}
+
+/// An empty field means no limit beyond the model's own.
+fn parse_max_tokens(field: &str) -> Result<Option<u32>, String> {
+    let field = field.trim();
+    if field.is_empty() {
+        return Ok(None);
+    }
+
+    match field.parse::<u32>() {
+        Ok(0) | Err(_) => Err(format!(
+            "Max tokens \"{}\" must be a positive whole number",
+            field
+        )),
+        Ok(max_tokens) => Ok(Some(max_tokens)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_empty_options_use_defaults_and_bad_ones_are_rejected() {
+        assert_eq!(parse_temperature(" "), Ok(None));
+        assert_eq!(parse_temperature("0.7"), Ok(Some(0.7)));
+        assert!(parse_temperature("3").is_err());
+        assert!(parse_temperature("warm").is_err());
+
+        assert_eq!(parse_max_tokens(""), Ok(None));
+        assert_eq!(parse_max_tokens("256"), Ok(Some(256)));
+        assert!(parse_max_tokens("0").is_err());
+        assert!(parse_max_tokens("-5").is_err());
+    }
+}
@@ -1,24 +1,74 @@
 use super::call;
+use super::draft::{self, DraftStatus};
 use super::style as s;
+use crate::capability::memory::{MemoryCapability, MemorySearchResult};
 use crate::capability::person::PersonCapability;
-use crate::capability::reaction::{ReactionCapability, ReactionPromptPreview};
-use crate::domain::memory::Memory;
+use crate::capability::reaction::{ContextTimings, ReactionCapability, ReactionPromptPreview};
+use crate::capability::scene::{SceneCapability, SceneParticipant};
+use crate::domain::memory::{Memory, MemoryRetrieval};
 use crate::domain::person_name::PersonName;
 use crate::nice_display::NiceDisplay;
 use crate::open_ai::completion::CompletionError;
 use crate::person_actions::PersonReaction;
 use crate::worker::Worker;
-use iced::{widget as w, Element, Task};
+use iced::{clipboard, widget as w, Element, Task};
 use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
 use std::sync::Arc;
 
+const DRAFT_NAME: &str = "reaction";
+const DEFAULT_CONTEXT_PATH: &str = "reaction_context.json";
+
 pub struct Model {
     person_name_field: String,
     identity_field: String,
-    memory_fields: Vec<w::text_editor::Content>,
+    memory_fields: Vec<draft::Editor>,
     situation_field: String,
     state_of_mind_field: String,
     reaction_status: ReactionStatus,
+    draft_status: DraftStatus<Storage>,
+    context_path_field: String,
+    context_status: ContextStatus,
+    retrieval_status: RetrievalStatus,
+}
+
+enum RetrievalStatus {
+    Ready,
+    Retrieving,
+    Retrieved(RetrievedMemories),
+    Error(String),
+}
+
+/// Memories retrieved for the situation the way a reaction would pick them,
+/// with the scores they were ranked by.
+#[derive(Debug, Clone)]
+pub struct RetrievedMemories {
+    retrieval: MemoryRetrieval,
+    results: Vec<MemorySearchResult>,
+    used: Vec<Memory>,
+}
+
+enum ContextStatus {
+    Ready,
+    Done(String),
+    Error(String),
+}
+
+/// Everything the reaction is made from, in a file of its own so a prompt
+/// session can be shared or kept under version control.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+struct ReactionContext {
+    #[serde(default)]
+    person_name: String,
+    #[serde(default)]
+    identity: String,
+    #[serde(default)]
+    memories: Vec<String>,
+    #[serde(default)]
+    situation: String,
+    #[serde(default)]
+    state_of_mind: String,
 }
 
 enum ReactionStatus {
@@ -28,7 +78,7 @@ enum ReactionStatus {
     Error(String),
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
 pub struct Storage {
     #[serde(default)]
     pub person_name_field: String,
@@ -40,6 +90,8 @@ pub struct Storage {
     pub situation_field: String,
     #[serde(default)]
     pub state_of_mind_field: String,
+    #[serde(default)]
+    pub context_path_field: String,
 }
 
 #[derive(Debug, Clone)]
@@ -49,10 +101,23 @@ pub enum Msg {
         index: usize,
         action: w::text_editor::Action,
     },
+    ClickedUndoMemory(usize),
+    ClickedRedoMemory(usize),
+    ClickedRestoreDraft,
+    ClickedDiscardDraft,
+    ContextPathFieldChanged(String),
+    ClickedExportContextToFile,
+    ClickedImportContextFromFile,
+    ClickedCopyContext,
+    ClickedPasteContext,
+    PastedContext(Option<String>),
     PersonNameFieldChanged(String),
     IdentityFieldChanged(String),
     ClickedPreviewPrompts,
     ClickedSubmitReaction,
+    ClickedRetrieveMemories,
+    RetrievedMemories(Result<RetrievedMemories, String>),
+    ClickedUseRetrievedMemories,
     SituationFieldChanged(String),
     StateOfMindFieldChanged(String),
     ReactionSubmissionResult(Result<Vec<PersonReaction>, CompletionError>),
@@ -67,14 +132,36 @@ impl Model {
             memory_fields: storage
                 .memories
                 .iter()
-                .map(|content_str| w::text_editor::Content::with_text(content_str))
+                .map(|content_str| draft::Editor::with_text(content_str))
                 .collect(),
             situation_field: storage.situation_field.clone(),
             state_of_mind_field: storage.state_of_mind_field.clone(),
             reaction_status: ReactionStatus::Ready,
+            draft_status: DraftStatus::check(DRAFT_NAME, storage),
+            context_path_field: if storage.context_path_field.is_empty() {
+                DEFAULT_CONTEXT_PATH.to_string()
+            } else {
+                storage.context_path_field.clone()
+            },
+            context_status: ContextStatus::Ready,
+            retrieval_status: RetrievalStatus::Ready,
         }
     }
 
+    /// Keeps a copy of the fields on disk after every edit, so a crash
+    /// between saves of the main storage file does not lose them. Skipped
+    /// while an older draft is waiting to be restored or discarded.
+    fn save_draft(&mut self) {
+        if self.draft_status.is_offered() {
+            return;
+        }
+
+        self.draft_status = match draft::save(DRAFT_NAME, &self.to_storage()) {
+            Ok(()) => DraftStatus::Saved,
+            Err(err) => DraftStatus::Error(err),
+        };
+    }
+
     pub fn to_storage(&self) -> Storage {
         Storage {
             person_name_field: self.person_name_field.clone(),
@@ -86,27 +173,139 @@ impl Model {
                 .collect(),
             situation_field: self.situation_field.clone(),
             state_of_mind_field: self.state_of_mind_field.clone(),
+            context_path_field: self.context_path_field.clone(),
+        }
+    }
+
+    fn to_context(&self) -> ReactionContext {
+        ReactionContext {
+            person_name: self.person_name_field.clone(),
+            identity: self.identity_field.clone(),
+            memories: self
+                .memory_fields
+                .iter()
+                .map(|editor_content| editor_content.text())
+                .collect(),
+            situation: self.situation_field.clone(),
+            state_of_mind: self.state_of_mind_field.clone(),
         }
     }
 
+    /// Replaces every field with the imported ones, keeping the memory edits
+    /// undoable.
+    fn import_context(&mut self, context: ReactionContext) {
+        self.restore(Storage {
+            person_name_field: context.person_name,
+            identity_field: context.identity,
+            memories: context.memories,
+            situation_field: context.situation,
+            state_of_mind_field: context.state_of_mind,
+            context_path_field: self.context_path_field.clone(),
+        });
+        self.save_draft();
+    }
+
     pub fn update(&mut self, worker: Arc<Worker>, message: Msg) -> Task<Msg> {
         match message {
             Msg::ClickedAddMemory => {
-                self.memory_fields.push(w::text_editor::Content::new());
+                self.memory_fields.push(draft::Editor::with_text(""));
+                self.save_draft();
                 Task::none()
             }
             Msg::MemoryUpdated { index, action } => {
                 if let Some(memory) = self.memory_fields.get_mut(index) {
-                    memory.perform(action);
+                    if memory.perform(action) {
+                        self.save_draft();
+                    }
+                }
+                Task::none()
+            }
+            Msg::ClickedUndoMemory(index) => {
+                if let Some(memory) = self.memory_fields.get_mut(index) {
+                    if memory.undo() {
+                        self.save_draft();
+                    }
+                }
+                Task::none()
+            }
+            Msg::ClickedRedoMemory(index) => {
+                if let Some(memory) = self.memory_fields.get_mut(index) {
+                    if memory.redo() {
+                        self.save_draft();
+                    }
+                }
+                Task::none()
+            }
+            Msg::ClickedRestoreDraft => {
+                let status = std::mem::replace(&mut self.draft_status, DraftStatus::Saved);
+                if let DraftStatus::Offered(storage) = status {
+                    self.restore(storage);
+                }
+                self.save_draft();
+                Task::none()
+            }
+            Msg::ClickedDiscardDraft => {
+                self.draft_status = DraftStatus::Saved;
+                self.save_draft();
+                Task::none()
+            }
+            Msg::ContextPathFieldChanged(new_field) => {
+                self.context_path_field = new_field;
+                Task::none()
+            }
+            Msg::ClickedExportContextToFile => {
+                let path = self.context_path_field.clone();
+                self.context_status = match export_context(&self.to_context(), Path::new(&path)) {
+                    Ok(()) => ContextStatus::Done(format!("Exported to {}", path)),
+                    Err(err) => ContextStatus::Error(err),
+                };
+                Task::none()
+            }
+            Msg::ClickedImportContextFromFile => {
+                let path = self.context_path_field.clone();
+                match import_context(Path::new(&path)) {
+                    Ok(context) => {
+                        self.import_context(context);
+                        self.context_status = ContextStatus::Done(format!("Imported {}", path));
+                    }
+                    Err(err) => self.context_status = ContextStatus::Error(err),
+                }
+                Task::none()
+            }
+            Msg::ClickedCopyContext => match context_to_json(&self.to_context()) {
+                Ok(json) => {
+                    self.context_status = ContextStatus::Done("Copied to clipboard".to_string());
+                    clipboard::write(json)
+                }
+                Err(err) => {
+                    self.context_status = ContextStatus::Error(err);
+                    Task::none()
+                }
+            },
+            Msg::ClickedPasteContext => clipboard::read().map(Msg::PastedContext),
+            Msg::PastedContext(maybe_json) => {
+                let result = match maybe_json {
+                    Some(json) => context_from_json(&json),
+                    None => Err("The clipboard is empty".to_string()),
+                };
+                match result {
+                    Ok(context) => {
+                        self.import_context(context);
+                        self.context_status =
+                            ContextStatus::Done("Pasted from clipboard".to_string());
+                    }
+                    Err(err) => self.context_status = ContextStatus::Error(err),
                 }
                 Task::none()
             }
             Msg::PersonNameFieldChanged(new_field) => {
                 self.person_name_field = new_field;
+                self.save_draft();
                 Task::none()
             }
             Msg::IdentityFieldChanged(new_field) => {
                 self.identity_field = new_field;
+                self.save_draft();
                 Task::none()
             }
             Msg::ClickedPreviewPrompts => {
@@ -141,6 +340,7 @@ impl Model {
                 Task::perform(
                     call::submit_reaction(
                         open_ai_key,
+                        worker.open_ai_client.clone(),
                         memories,
                         person_identity,
                         situation,
@@ -149,12 +349,44 @@ impl Model {
                     Msg::ReactionSubmissionResult,
                 )
             }
+            Msg::ClickedRetrieveMemories => {
+                self.retrieval_status = RetrievalStatus::Retrieving;
+                let person_name = self.person_name_field.clone();
+                let situation = self.situation_field.clone();
+
+                Task::perform(
+                    async move { retrieve_memories(&worker, person_name, situation).await },
+                    Msg::RetrievedMemories,
+                )
+            }
+            Msg::RetrievedMemories(result) => {
+                self.retrieval_status = match result {
+                    Ok(retrieved) => RetrievalStatus::Retrieved(retrieved),
+                    Err(err) => RetrievalStatus::Error(err),
+                };
+                Task::none()
+            }
+            Msg::ClickedUseRetrievedMemories => {
+                if let RetrievalStatus::Retrieved(retrieved) = &self.retrieval_status {
+                    let mut storage = self.to_storage();
+                    storage.memories = retrieved
+                        .used
+                        .iter()
+                        .map(|memory| memory.content.clone())
+                        .collect();
+                    self.restore(storage);
+                    self.save_draft();
+                }
+                Task::none()
+            }
             Msg::SituationFieldChanged(new_field) => {
                 self.situation_field = new_field;
+                self.save_draft();
                 Task::none()
             }
             Msg::StateOfMindFieldChanged(new_field) => {
                 self.state_of_mind_field = new_field;
+                self.save_draft();
                 Task::none()
             }
             Msg::ReactionSubmissionResult(result) => {
@@ -174,16 +406,47 @@ impl Model {
         }
     }
 
+    /// Memories that are still there are restored as an undoable edit.
+    fn restore(&mut self, storage: Storage) {
+        self.person_name_field = storage.person_name_field;
+        self.identity_field = storage.identity_field;
+        self.situation_field = storage.situation_field;
+        self.state_of_mind_field = storage.state_of_mind_field;
+
+        self.memory_fields.truncate(storage.memories.len());
+        for (index, memory) in storage.memories.iter().enumerate() {
+            match self.memory_fields.get_mut(index) {
+                Some(editor) => editor.replace_text(memory),
+                None => self.memory_fields.push(draft::Editor::with_text(memory)),
+            }
+        }
+    }
+
     pub fn view(&self) -> Element<'_, Msg> {
         let mut memories_children: Vec<Element<_>> = vec![];
 
         for (i, memory) in self.memory_fields.iter().enumerate() {
-            let memories_editor = w::text_editor(memory).on_action(move |act| Msg::MemoryUpdated {
-                index: i,
-                action: act,
-            });
+            let memories_editor = w::text_editor(memory.content())
+                .key_binding(move |key_press| {
+                    draft::undo_key_binding(
+                        key_press,
+                        Msg::ClickedUndoMemory(i),
+                        Msg::ClickedRedoMemory(i),
+                    )
+                })
+                .on_action(move |act| Msg::MemoryUpdated {
+                    index: i,
+                    action: act,
+                });
 
-            memories_children.push(memories_editor.into());
+            memories_children.push(
+                w::column![
+                    memories_editor,
+                    draft::view_undo_buttons(Msg::ClickedUndoMemory(i), Msg::ClickedRedoMemory(i)),
+                ]
+                .spacing(s::S1)
+                .into(),
+            );
         }
 
         let reaction_response_view: Element<Msg> = match &self.reaction_status {
@@ -196,6 +459,8 @@ impl Model {
             )
             .into(),
             ReactionStatus::PromptPreview(preview) => w::column![
+                context_timings_view(&preview.context_timings),
+                w::horizontal_rule(1),
                 w::text("Thinking System Prompt"),
                 w::text(&preview.thinking_system_prompt),
                 w::horizontal_rule(1),
@@ -213,7 +478,38 @@ impl Model {
             ReactionStatus::Error(err) => w::text(format!("Error: {}", err)).into(),
         };
 
+        let context_status_view: Element<Msg> = match &self.context_status {
+            ContextStatus::Ready => w::Column::new().into(),
+            ContextStatus::Done(message) => w::text(message).color(s::GREEN_SOFT).into(),
+            ContextStatus::Error(err) => {
+                w::text(format!("Error: {}", err)).color(s::RED_SOFT).into()
+            }
+        };
+
+        let context_controls = w::column![
+            w::row![
+                w::text_input("Context file", &self.context_path_field)
+                    .on_input(Msg::ContextPathFieldChanged),
+                w::button("Export to File").on_press(Msg::ClickedExportContextToFile),
+                w::button("Import from File").on_press(Msg::ClickedImportContextFromFile),
+            ]
+            .spacing(s::S2),
+            w::row![
+                w::button("Copy Context").on_press(Msg::ClickedCopyContext),
+                w::button("Paste Context").on_press(Msg::ClickedPasteContext),
+            ]
+            .spacing(s::S2),
+            context_status_view,
+        ]
+        .spacing(s::S2);
+
         w::column![
+            draft::view_status(
+                &self.draft_status,
+                Msg::ClickedRestoreDraft,
+                Msg::ClickedDiscardDraft
+            ),
+            context_controls,
             w::text("Person Name"),
             w::text_input("Person Name", &self.person_name_field)
                 .on_input(Msg::PersonNameFieldChanged),
@@ -227,6 +523,8 @@ impl Model {
             w::text("State of Mind"),
             w::text_input("State of Mind", &self.state_of_mind_field)
                 .on_input(Msg::StateOfMindFieldChanged),
+            w::button("Retrieve Memories For Situation").on_press(Msg::ClickedRetrieveMemories),
+            retrieval_view(&self.retrieval_status),
             w::button("Preview Prompts (No LLM)").on_press(Msg::ClickedPreviewPrompts),
             w::button("Submit Reaction").on_press(Msg::ClickedSubmitReaction),
             reaction_response_view,
@@ -236,6 +534,102 @@ impl Model {
     }
 }
 
+fn context_timings_view(timings: &ContextTimings) -> Element<'_, Msg> {
+    let mut col = w::column![w::text(format!(
+        "Context fetched in {} ms, {} ms if fetched one at a time",
+        timings.total.as_millis(),
+        timings.sequential_total().as_millis()
+    ))]
+    .spacing(s::S1);
+
+    for fetch in &timings.fetches {
+        col = col.push(
+            w::text(format!("{}: {} ms", fetch.name, fetch.elapsed.as_millis()))
+                .size(s::S3)
+                .color(s::GRAY_MID),
+        );
+    }
+
+    col.into()
+}
+
+fn retrieval_view(status: &RetrievalStatus) -> Element<'_, Msg> {
+    match status {
+        RetrievalStatus::Ready => w::Column::new().into(),
+        RetrievalStatus::Retrieving => w::text("Retrieving memories...").into(),
+        RetrievalStatus::Retrieved(retrieved) => {
+            let mut col = w::column![w::text(format!(
+                "{} of {} retrieved memories would be used (top {}, max distance {}, recency weight {})",
+                retrieved.used.len(),
+                retrieved.results.len(),
+                retrieved.retrieval.top_k,
+                retrieved.retrieval.max_distance,
+                retrieved.retrieval.recency_weight
+            ))]
+            .spacing(s::S1);
+
+            for result in &retrieved.results {
+                let is_used = retrieved
+                    .used
+                    .iter()
+                    .any(|memory| memory.content == result.content);
+
+                col = col.push(
+                    w::text(format!(
+                        "score {:.3}, distance {:.3}{}: {}",
+                        result.score,
+                        result.distance,
+                        if result.mentions_participant {
+                            ", mentions someone present"
+                        } else {
+                            ""
+                        },
+                        result.content
+                    ))
+                    .size(s::S3)
+                    .color(if is_used { s::GREEN_SOFT } else { s::GRAY_MID }),
+                );
+            }
+
+            col.push(w::button("Use As Memories").on_press(Msg::ClickedUseRetrievedMemories))
+                .into()
+        }
+        RetrievalStatus::Error(err) => w::text(format!("Error: {}", err)).color(s::RED_SOFT).into(),
+    }
+}
+
+/// Searches the person's memories with the situation, using the world's
+/// retrieval settings and whoever is in the person's scene right now.
+async fn retrieve_memories(
+    worker: &Worker,
+    person_name: String,
+    situation: String,
+) -> Result<RetrievedMemories, String> {
+    let person_uuid = worker
+        .get_person_uuid_by_name(PersonName::from_string(person_name))
+        .await?;
+
+    let participants = match worker.get_persons_current_scene_uuid(&person_uuid).await? {
+        Some(scene_uuid) => SceneParticipant::other_persons(
+            &worker.get_scene_current_participants(&scene_uuid).await?,
+            &person_uuid,
+        ),
+        None => Vec::new(),
+    };
+
+    let retrieval = worker.get_memory_retrieval().await?;
+    let results = worker
+        .search_memories(person_uuid, situation, &participants, &retrieval)
+        .await?;
+    let used = retrieval.filter(results.clone());
+
+    Ok(RetrievedMemories {
+        retrieval,
+        results,
+        used,
+    })
+}
+
 async fn preview_reaction_prompts(
     worker: &Worker,
     person_name: String,
@@ -250,3 +644,26 @@ async fn preview_reaction_prompts(
         .preview_reaction_prompts(memories, person_uuid, situation)
         .await
 }
+
+fn context_to_json(context: &ReactionContext) -> Result<String, String> {
+    serde_json::to_string_pretty(context)
+        .map_err(|err| format!("Error serializing reaction context: {}", err))
+}
+
+fn context_from_json(json: &str) -> Result<ReactionContext, String> {
+    serde_json::from_str(json).map_err(|err| format!("Error parsing reaction context: {}", err))
+}
+
+fn export_context(context: &ReactionContext, path: &Path) -> Result<(), String> {
+    let json = context_to_json(context)?;
+
+    draft::write_atomically(path, json.as_bytes())
+        .map_err(|err| format!("Error writing {}: {}", path.display(), err))
+}
+
+fn import_context(path: &Path) -> Result<ReactionContext, String> {
+    let json = fs::read_to_string(path)
+        .map_err(|err| format!("Error reading {}: {}", path.display(), err))?;
+
+    context_from_json(&json)
+}
@@ -1,5 +1,20 @@
+use crate::admin_ui::pending_operations::Operation;
+use crate::capability::job::JobCapability;
+use crate::capability::job_runner_settings::JobRunnerSettingsCapability;
 use crate::capability::scene::{NewScene, Scene, SceneParticipant};
+use crate::capability::scene_drama::SceneDramaCapability;
+use crate::capability::scene_goal::SceneGoalCapability;
+use crate::capability::scene_tone::SceneToneCapability;
+use crate::domain::actor_uuid::ActorUuid;
+use crate::domain::job::archive_scene::ArchiveSceneJob;
+use crate::domain::job::change_scene_ambience::ChangeSceneAmbienceJob;
+use crate::domain::job::inject_scene_events;
+use crate::domain::job::JobKind;
+use crate::domain::message_audience::HearingRadius;
+use crate::domain::scene_ambience::{self, Ambience, Lighting, NoiseLevel, Weather};
+use crate::domain::scene_goal::{self, SceneGoal};
 use crate::domain::scene_participant_uuid::SceneParticipantUuid;
+use crate::domain::scene_tone::ToneProfile;
 use crate::domain::scene_uuid::SceneUuid;
 use crate::worker::Worker;
 use crate::{admin_ui::s, capability::scene::SceneCapability};
@@ -33,6 +48,49 @@ struct SceneModel {
     delete_scene_status: DeleteSceneStatus,
     is_real_world_user_in_scene: bool,
     real_world_user_presence_status: RealWorldUserPresenceStatus,
+    hearing_radius: HearingRadius,
+    hearing_radius_status: HearingRadiusStatus,
+    goal: SceneGoal,
+    current_active_ms: i64,
+    goal_time_limit_field: String,
+    goal_closing_phrase_field: String,
+    goal_consensus_field: String,
+    goal_status: SceneGoalStatus,
+    ambience: Ambience,
+    ambience_field: Ambience,
+    ambience_delay_field: String,
+    ambience_status: AmbienceStatus,
+    drama_intensity: f64,
+    drama_status: DramaStatus,
+    tone_profile: Option<ToneProfile>,
+    tone_profile_status: ToneProfileStatus,
+}
+
+enum ToneProfileStatus {
+    Ready,
+    Saving,
+    Error(String),
+}
+
+enum DramaStatus {
+    Ready,
+    Saving,
+    Saved,
+    Error(String),
+}
+
+enum AmbienceStatus {
+    Ready,
+    Queueing,
+    Queued { run_at_active_ms: Option<i64> },
+    Error(String),
+}
+
+enum SceneGoalStatus {
+    Ready,
+    Saving,
+    Saved,
+    Error(String),
 }
 
 enum NewParticipantStatus {
@@ -57,6 +115,12 @@ enum RealWorldUserPresenceStatus {
     Error(String),
 }
 
+enum HearingRadiusStatus {
+    Ready,
+    Updating,
+    Error(String),
+}
+
 enum NewSceneStatus {
     Ready,
     CreatingScene,
@@ -76,6 +140,12 @@ pub struct SceneAggregate {
     scene: Scene,
     participants: Vec<SceneParticipant>,
     is_real_world_user_in_scene: bool,
+    hearing_radius: HearingRadius,
+    goal: Option<SceneGoal>,
+    ambience: Option<Ambience>,
+    drama_intensity: f64,
+    tone_profile: Option<ToneProfile>,
+    current_active_ms: i64,
 }
 
 impl SceneAggregate {
@@ -91,10 +161,28 @@ impl SceneAggregate {
 
         let is_real_world_user_in_scene = worker.is_real_world_user_in_scene(&scene.uuid).await?;
 
+        let hearing_radius = worker.get_scene_hearing_radius(&scene.uuid).await?;
+
+        let goal = worker.get_scene_goal(&scene.uuid).await?;
+
+        let ambience = worker.get_scene_ambience(&scene.uuid).await?;
+
+        let drama_intensity = worker.get_scene_drama_intensity(&scene.uuid).await?;
+
+        let tone_profile = worker.get_scene_tone_profile(&scene.uuid).await?;
+
+        let current_active_ms = worker.get_active_clock_ms().await?;
+
         let ret = Self {
             scene,
             participants,
             is_real_world_user_in_scene,
+            hearing_radius,
+            goal,
+            ambience,
+            drama_intensity,
+            tone_profile,
+            current_active_ms,
         };
 
         Ok(Some(ret))
@@ -125,6 +213,27 @@ pub enum SceneLookUpMsg {
     ClickedSetRealWorldUserInScene(bool),
     SetRealWorldUserInScene(Result<bool, String>),
     GotRefreshedParticipantsAfterRealWorldUserUpdate(Result<Vec<SceneParticipant>, String>),
+    ClickedSetHearingRadius(HearingRadius),
+    SetHearingRadius(Result<HearingRadius, String>),
+    GoalTimeLimitChanged(String),
+    GoalClosingPhraseChanged(String),
+    GoalConsensusChanged(String),
+    ClickedSaveGoal,
+    SavedGoal(Result<(SceneGoal, i64), String>),
+    WeatherSelected(Weather),
+    NoiseSelected(NoiseLevel),
+    LightingSelected(Lighting),
+    AmbienceDelayChanged(String),
+    ClickedChangeAmbience,
+    QueuedAmbienceChange(Result<Option<i64>, String>),
+    DramaDialMoved(f64),
+    ReleasedDramaDial,
+    SavedDramaIntensity(Result<(), String>),
+    ToneProfileSelected(ToneProfile),
+    ClickedClearToneProfile,
+    SavedToneProfile(Result<Option<ToneProfile>, String>),
+    /// Handled by the admin ui, which runs it once its undo window is over.
+    StageOperation(Operation),
 }
 
 #[derive(Serialize, Deserialize, Debug, Default)]
@@ -140,6 +249,17 @@ pub struct Storage {
 impl SceneModel {
     fn init(scene_agg: SceneAggregate) -> Self {
         let scene = scene_agg.scene;
+        let goal = scene_agg.goal.unwrap_or_default();
+        let ambience = scene_agg.ambience.unwrap_or_default();
+
+        // The time limit field counts from now, so it starts at whatever is left
+        let goal_time_limit_field = match goal.deadline_active_ms {
+            Some(deadline_active_ms) => {
+                let minutes_left = (deadline_active_ms - scene_agg.current_active_ms) / 60_000;
+                minutes_left.max(1).to_string()
+            }
+            None => "".to_string(),
+        };
 
         Self {
             scene_name: scene.name,
@@ -151,11 +271,50 @@ impl SceneModel {
             delete_scene_status: DeleteSceneStatus::Ready,
             is_real_world_user_in_scene: scene_agg.is_real_world_user_in_scene,
             real_world_user_presence_status: RealWorldUserPresenceStatus::Ready,
+            hearing_radius: scene_agg.hearing_radius,
+            hearing_radius_status: HearingRadiusStatus::Ready,
+            goal_closing_phrase_field: goal.closing_phrase.clone().unwrap_or_default(),
+            goal_consensus_field: goal.consensus_on.clone().unwrap_or_default(),
+            goal_time_limit_field,
+            goal,
+            current_active_ms: scene_agg.current_active_ms,
+            goal_status: SceneGoalStatus::Ready,
+            ambience,
+            ambience_field: ambience,
+            ambience_delay_field: "".to_string(),
+            ambience_status: AmbienceStatus::Ready,
+            drama_intensity: scene_agg.drama_intensity,
+            drama_status: DramaStatus::Ready,
+            tone_profile: scene_agg.tone_profile,
+            tone_profile_status: ToneProfileStatus::Ready,
         }
     }
 
+    fn save_tone_profile(
+        &mut self,
+        worker: Arc<Worker>,
+        tone_profile: Option<ToneProfile>,
+    ) -> Task<SceneLookUpMsg> {
+        if let ToneProfileStatus::Saving = self.tone_profile_status {
+            return Task::none();
+        }
+
+        self.tone_profile_status = ToneProfileStatus::Saving;
+        let scene_uuid = self.scene_uuid.clone();
+        Task::perform(
+            async move {
+                worker
+                    .set_scene_tone_profile(&scene_uuid, tone_profile)
+                    .await
+                    .map(|_| tone_profile)
+            },
+            SceneLookUpMsg::SavedToneProfile,
+        )
+    }
+
     fn update(&mut self, worker: Arc<Worker>, msg: SceneLookUpMsg) -> Task<SceneLookUpMsg> {
         match msg {
+            SceneLookUpMsg::StageOperation(_) => Task::none(),
             SceneLookUpMsg::NewParticipantFieldChanged(field) => {
                 self.new_participant_field = field;
                 Task::none()
@@ -209,7 +368,12 @@ impl SceneModel {
                     self.delete_scene_status = DeleteSceneStatus::DeletingScene;
                     let scene_uuid = self.scene_uuid.clone();
                     Task::perform(
-                        async move { worker.delete_scene(&scene_uuid).await },
+                        async move {
+                            worker.delete_scene(&scene_uuid).await?;
+                            worker
+                                .unshift_job(JobKind::ArchiveScene(ArchiveSceneJob { scene_uuid }))
+                                .await
+                        },
                         SceneLookUpMsg::DeletedScene,
                     )
                 }
@@ -259,6 +423,36 @@ impl SceneModel {
                     Task::none()
                 }
             },
+            SceneLookUpMsg::ClickedSetHearingRadius(hearing_radius) => {
+                match self.hearing_radius_status {
+                    HearingRadiusStatus::Updating => Task::none(),
+                    HearingRadiusStatus::Ready | HearingRadiusStatus::Error(_) => {
+                        self.hearing_radius_status = HearingRadiusStatus::Updating;
+                        let scene_uuid = self.scene_uuid.clone();
+                        Task::perform(
+                            async move {
+                                worker
+                                    .set_scene_hearing_radius(&scene_uuid, hearing_radius)
+                                    .await
+                                    .map(|_| hearing_radius)
+                            },
+                            SceneLookUpMsg::SetHearingRadius,
+                        )
+                    }
+                }
+            }
+            SceneLookUpMsg::SetHearingRadius(result) => {
+                match result {
+                    Ok(hearing_radius) => {
+                        self.hearing_radius = hearing_radius;
+                        self.hearing_radius_status = HearingRadiusStatus::Ready;
+                    }
+                    Err(err) => {
+                        self.hearing_radius_status = HearingRadiusStatus::Error(err);
+                    }
+                }
+                Task::none()
+            }
             SceneLookUpMsg::GotRefreshedParticipantsAfterRealWorldUserUpdate(result) => {
                 match result {
                     Ok(participants) => {
@@ -272,6 +466,163 @@ impl SceneModel {
                 }
                 Task::none()
             }
+            SceneLookUpMsg::GoalTimeLimitChanged(field) => {
+                self.goal_time_limit_field = field;
+                Task::none()
+            }
+            SceneLookUpMsg::GoalClosingPhraseChanged(field) => {
+                self.goal_closing_phrase_field = field;
+                Task::none()
+            }
+            SceneLookUpMsg::GoalConsensusChanged(field) => {
+                self.goal_consensus_field = field;
+                Task::none()
+            }
+            SceneLookUpMsg::ClickedSaveGoal => match self.goal_status {
+                SceneGoalStatus::Saving => Task::none(),
+                SceneGoalStatus::Ready | SceneGoalStatus::Saved | SceneGoalStatus::Error(_) => {
+                    let time_limit_mins =
+                        match scene_goal::parse_time_limit_mins(&self.goal_time_limit_field) {
+                            Ok(time_limit_mins) => time_limit_mins,
+                            Err(err) => {
+                                self.goal_status = SceneGoalStatus::Error(err);
+                                return Task::none();
+                            }
+                        };
+
+                    self.goal_status = SceneGoalStatus::Saving;
+                    let scene_uuid = self.scene_uuid.clone();
+                    let closing_phrase = scene_goal::optional_text(&self.goal_closing_phrase_field);
+                    let consensus_on = scene_goal::optional_text(&self.goal_consensus_field);
+
+                    Task::perform(
+                        async move {
+                            let current_active_ms = worker.get_active_clock_ms().await?;
+                            let goal = SceneGoal {
+                                deadline_active_ms: time_limit_mins.map(|minutes| {
+                                    scene_goal::deadline_after_mins(current_active_ms, minutes)
+                                }),
+                                closing_phrase,
+                                consensus_on,
+                            };
+
+                            worker.set_scene_goal(&scene_uuid, &goal).await?;
+
+                            Ok((goal, current_active_ms))
+                        },
+                        SceneLookUpMsg::SavedGoal,
+                    )
+                }
+            },
+            SceneLookUpMsg::SavedGoal(result) => {
+                match result {
+                    Ok((goal, current_active_ms)) => {
+                        self.goal = goal;
+                        self.current_active_ms = current_active_ms;
+                        self.goal_status = SceneGoalStatus::Saved;
+                    }
+                    Err(err) => {
+                        self.goal_status = SceneGoalStatus::Error(err);
+                    }
+                }
+                Task::none()
+            }
+            SceneLookUpMsg::WeatherSelected(weather) => {
+                self.ambience_field.weather = weather;
+                Task::none()
+            }
+            SceneLookUpMsg::NoiseSelected(noise) => {
+                self.ambience_field.noise = noise;
+                Task::none()
+            }
+            SceneLookUpMsg::LightingSelected(lighting) => {
+                self.ambience_field.lighting = lighting;
+                Task::none()
+            }
+            SceneLookUpMsg::AmbienceDelayChanged(field) => {
+                self.ambience_delay_field = field;
+                Task::none()
+            }
+            SceneLookUpMsg::ClickedChangeAmbience => match self.ambience_status {
+                AmbienceStatus::Queueing => Task::none(),
+                _ => {
+                    self.ambience_status = AmbienceStatus::Queueing;
+                    let scene_uuid = self.scene_uuid.clone();
+                    let ambience = self.ambience_field;
+                    let delay_field = self.ambience_delay_field.clone();
+
+                    Task::perform(
+                        async move {
+                            let current_active_ms = worker.get_active_clock_ms().await?;
+                            let run_at_active_ms =
+                                scene_ambience::parse_run_at(&delay_field, current_active_ms)?;
+
+                            worker
+                                .unshift_job(JobKind::ChangeSceneAmbience(ChangeSceneAmbienceJob {
+                                    scene_uuid,
+                                    ambience,
+                                    run_at_active_ms,
+                                }))
+                                .await?;
+
+                            Ok(run_at_active_ms)
+                        },
+                        SceneLookUpMsg::QueuedAmbienceChange,
+                    )
+                }
+            },
+            SceneLookUpMsg::QueuedAmbienceChange(result) => {
+                match result {
+                    Ok(run_at_active_ms) => {
+                        self.ambience_status = AmbienceStatus::Queued { run_at_active_ms };
+                    }
+                    Err(err) => {
+                        self.ambience_status = AmbienceStatus::Error(err);
+                    }
+                }
+                Task::none()
+            }
+            SceneLookUpMsg::DramaDialMoved(intensity) => {
+                self.drama_intensity = intensity;
+                self.drama_status = DramaStatus::Ready;
+                Task::none()
+            }
+            SceneLookUpMsg::ReleasedDramaDial => {
+                self.drama_status = DramaStatus::Saving;
+                let scene_uuid = self.scene_uuid.clone();
+                let intensity = self.drama_intensity;
+                Task::perform(
+                    async move {
+                        worker
+                            .set_scene_drama_intensity(&scene_uuid, intensity)
+                            .await
+                    },
+                    SceneLookUpMsg::SavedDramaIntensity,
+                )
+            }
+            SceneLookUpMsg::SavedDramaIntensity(result) => {
+                self.drama_status = match result {
+                    Ok(()) => DramaStatus::Saved,
+                    Err(err) => DramaStatus::Error(err),
+                };
+                Task::none()
+            }
+            SceneLookUpMsg::ToneProfileSelected(tone_profile) => {
+                self.save_tone_profile(worker, Some(tone_profile))
+            }
+            SceneLookUpMsg::ClickedClearToneProfile => self.save_tone_profile(worker, None),
+            SceneLookUpMsg::SavedToneProfile(result) => {
+                match result {
+                    Ok(tone_profile) => {
+                        self.tone_profile = tone_profile;
+                        self.tone_profile_status = ToneProfileStatus::Ready;
+                    }
+                    Err(err) => {
+                        self.tone_profile_status = ToneProfileStatus::Error(err);
+                    }
+                }
+                Task::none()
+            }
         }
     }
 }
@@ -460,6 +811,30 @@ fn scene_look_up_view(scene_look_up: &LookUpScene) -> Element<'_, SceneLookUpMsg
     w::column![look_up_view].spacing(s::S4).into()
 }
 
+fn participant_view<'a>(
+    scene_model: &'a SceneModel,
+    participant: &'a SceneParticipant,
+) -> Element<'a, SceneLookUpMsg> {
+    let name = w::text(participant.person_name.as_str());
+
+    match participant.actor_uuid {
+        ActorUuid::AiPerson(_) => w::row![
+            name,
+            w::button("Remove").on_press(SceneLookUpMsg::StageOperation(
+                Operation::RemoveFromScene {
+                    scene_uuid: scene_model.scene_uuid.clone(),
+                    scene_name: scene_model.scene_name.clone(),
+                    person_name: participant.person_name.clone(),
+                }
+            )),
+        ]
+        .spacing(s::S2)
+        .align_y(iced::Alignment::Center)
+        .into(),
+        ActorUuid::RealWorldUser => name.into(),
+    }
+}
+
 fn scene_loaded_view(scene_model: &SceneModel) -> Element<'_, SceneLookUpMsg> {
     let description = scene_model
         .scene_snapshot
@@ -473,9 +848,10 @@ fn scene_loaded_view(scene_model: &SceneModel) -> Element<'_, SceneLookUpMsg> {
             scene_model
                 .participants
                 .iter()
-                .map(|p| w::text(p.person_name.as_str()).into())
+                .map(|participant| participant_view(scene_model, participant))
                 .collect::<Vec<_>>(),
         )
+        .spacing(s::S1)
         .into()
     };
 
@@ -556,6 +932,91 @@ fn scene_loaded_view(scene_model: &SceneModel) -> Element<'_, SceneLookUpMsg> {
             }
         };
 
+    let hearing_radius_status: Element<SceneLookUpMsg> = match &scene_model.hearing_radius_status {
+        HearingRadiusStatus::Ready => {
+            let text = match scene_model.hearing_radius {
+                HearingRadius::Room => "Everyone in the scene overhears side conversations.",
+                HearingRadius::Close => "Only the people addressed hear side conversations.",
+            };
+            w::text(text).into()
+        }
+        HearingRadiusStatus::Updating => w::text("Updating hearing radius...").into(),
+        HearingRadiusStatus::Error(err) => {
+            w::text(format!("Error updating hearing radius: {}", err)).into()
+        }
+    };
+
+    let hearing_radius_button: Element<SceneLookUpMsg> = match scene_model.hearing_radius_status {
+        HearingRadiusStatus::Updating => w::button("Toggle Hearing Radius").into(),
+        HearingRadiusStatus::Ready | HearingRadiusStatus::Error(_) => {
+            let next = match scene_model.hearing_radius {
+                HearingRadius::Room => HearingRadius::Close,
+                HearingRadius::Close => HearingRadius::Room,
+            };
+            w::button("Toggle Hearing Radius")
+                .on_press(SceneLookUpMsg::ClickedSetHearingRadius(next))
+                .into()
+        }
+    };
+
+    let goal_status: Element<SceneLookUpMsg> = match &scene_model.goal_status {
+        SceneGoalStatus::Ready => w::text("").into(),
+        SceneGoalStatus::Saving => w::text("Saving goal...").into(),
+        SceneGoalStatus::Saved => w::text("Goal saved.").into(),
+        SceneGoalStatus::Error(err) => w::text(format!("Error saving goal: {}", err)).into(),
+    };
+
+    let save_goal_button: Element<SceneLookUpMsg> = match scene_model.goal_status {
+        SceneGoalStatus::Saving => w::button("Save Goal").into(),
+        _ => w::button("Save Goal")
+            .on_press(SceneLookUpMsg::ClickedSaveGoal)
+            .into(),
+    };
+
+    let ambience_status: Element<SceneLookUpMsg> = match &scene_model.ambience_status {
+        AmbienceStatus::Ready => w::text("").into(),
+        AmbienceStatus::Queueing => w::text("Queueing ambience change...").into(),
+        AmbienceStatus::Queued {
+            run_at_active_ms: None,
+        } => w::text("The ambience will change once the job runner picks it up.").into(),
+        AmbienceStatus::Queued {
+            run_at_active_ms: Some(run_at_active_ms),
+        } => w::text(format!(
+            "The ambience will change in {} active minutes.",
+            (run_at_active_ms - scene_model.current_active_ms).max(0) / 60_000
+        ))
+        .into(),
+        AmbienceStatus::Error(err) => {
+            w::text(format!("Error changing the ambience: {}", err)).into()
+        }
+    };
+
+    let drama_status: Element<SceneLookUpMsg> = match &scene_model.drama_status {
+        DramaStatus::Ready => w::text("").into(),
+        DramaStatus::Saving => w::text("Saving drama intensity...").into(),
+        DramaStatus::Saved => w::text("Saved").color(s::GREEN_SOFT).into(),
+        DramaStatus::Error(err) => w::text(format!("Error saving drama intensity: {}", err)).into(),
+    };
+
+    let tone_profile_status: Element<SceneLookUpMsg> = match &scene_model.tone_profile_status {
+        ToneProfileStatus::Ready => match scene_model.tone_profile {
+            Some(_) => w::text(
+                "Persons here are told to talk this way, and moderation here follows the tone.",
+            )
+            .into(),
+            None => w::text("Persons here talk and are moderated like anywhere else.").into(),
+        },
+        ToneProfileStatus::Saving => w::text("Saving tone...").into(),
+        ToneProfileStatus::Error(err) => w::text(format!("Error saving tone: {}", err)).into(),
+    };
+
+    let change_ambience_button: Element<SceneLookUpMsg> = match scene_model.ambience_status {
+        AmbienceStatus::Queueing => w::button("Change Ambience").into(),
+        _ => w::button("Change Ambience")
+            .on_press(SceneLookUpMsg::ClickedChangeAmbience)
+            .into(),
+    };
+
     w::column![
         w::text("Scene Name"),
         w::text(&scene_model.scene_name),
@@ -573,6 +1034,80 @@ fn scene_loaded_view(scene_model: &SceneModel) -> Element<'_, SceneLookUpMsg> {
         w::text("My Presence"),
         w::row![set_me_in_scene_button, set_me_out_of_scene_button].spacing(s::S1),
         real_world_user_presence_status,
+        w::text("Hearing Radius"),
+        hearing_radius_button,
+        hearing_radius_status,
+        w::text("Goal"),
+        w::text(scene_model.goal.to_text(scene_model.current_active_ms)),
+        w::text_input(
+            "Time limit in active minutes",
+            scene_model.goal_time_limit_field.as_str()
+        )
+        .on_input(SceneLookUpMsg::GoalTimeLimitChanged),
+        w::text_input(
+            "Closing phrase, like \"good night\"",
+            scene_model.goal_closing_phrase_field.as_str()
+        )
+        .on_input(SceneLookUpMsg::GoalClosingPhraseChanged),
+        w::text_input(
+            "Agree on, like \"where to eat dinner\"",
+            scene_model.goal_consensus_field.as_str()
+        )
+        .on_input(SceneLookUpMsg::GoalConsensusChanged),
+        save_goal_button,
+        goal_status,
+        w::text("Ambience"),
+        w::text(scene_model.ambience.to_prompt_text()),
+        w::row![
+            w::pick_list(
+                &Weather::ALL[..],
+                Some(scene_model.ambience_field.weather),
+                SceneLookUpMsg::WeatherSelected
+            ),
+            w::pick_list(
+                &NoiseLevel::ALL[..],
+                Some(scene_model.ambience_field.noise),
+                SceneLookUpMsg::NoiseSelected
+            ),
+            w::pick_list(
+                &Lighting::ALL[..],
+                Some(scene_model.ambience_field.lighting),
+                SceneLookUpMsg::LightingSelected
+            ),
+        ]
+        .spacing(s::S1),
+        w::text_input(
+            "Active minutes from now, blank for right away",
+            scene_model.ambience_delay_field.as_str()
+        )
+        .on_input(SceneLookUpMsg::AmbienceDelayChanged),
+        change_ambience_button,
+        ambience_status,
+        w::text(format!(
+            "Drama ({:.0}% chance of something unexpected every {} minutes)",
+            scene_model.drama_intensity * 100.0,
+            inject_scene_events::SCAN_INTERVAL.as_secs() / 60
+        )),
+        w::slider(
+            0.0..=1.0,
+            scene_model.drama_intensity,
+            SceneLookUpMsg::DramaDialMoved
+        )
+        .step(0.05)
+        .on_release(SceneLookUpMsg::ReleasedDramaDial),
+        drama_status,
+        w::text("Tone"),
+        w::row![
+            w::pick_list(
+                &ToneProfile::ALL[..],
+                scene_model.tone_profile,
+                SceneLookUpMsg::ToneProfileSelected
+            )
+            .placeholder("the world's own"),
+            w::button("Clear").on_press(SceneLookUpMsg::ClickedClearToneProfile),
+        ]
+        .spacing(s::S1),
+        tone_profile_status,
         delete_scene_button,
         delete_scene_status
     ]
@@ -0,0 +1,361 @@
+use crate::admin_ui::s;
+use crate::capability::scene_template::SceneTemplateCapability;
+use crate::domain::person_name::PersonName;
+use crate::domain::random_seed::RandomSeed;
+use crate::domain::scene_template::{self, SceneTemplate};
+use crate::domain::world_time::TimeOfDay;
+use crate::nice_display::NiceDisplay;
+use crate::worker::Worker;
+use iced::{widget as w, Element, Length, Task};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+pub struct Model {
+    name_field: String,
+    name_pattern_field: String,
+    description_field: String,
+    starting_cast_field: String,
+    opening_message_field: String,
+    time_of_day_field: String,
+    scene_name_field: String,
+    templates: TemplatesStatus,
+    save_status: ActionStatus,
+    start_status: ActionStatus,
+}
+
+enum TemplatesStatus {
+    Loading,
+    Loaded(Vec<SceneTemplate>),
+    Error(String),
+}
+
+enum ActionStatus {
+    Ready,
+    Working,
+    Done(String),
+    Error(String),
+}
+
+#[derive(Debug, Clone)]
+pub enum Msg {
+    ClickedRefresh,
+    LoadedTemplates(Result<Vec<SceneTemplate>, String>),
+    ClickedTemplate(SceneTemplate),
+    NameChanged(String),
+    NamePatternChanged(String),
+    DescriptionChanged(String),
+    StartingCastChanged(String),
+    OpeningMessageChanged(String),
+    TimeOfDayChanged(String),
+    ClickedSave,
+    Saved(Result<(), String>),
+    ClickedDelete,
+    Deleted(Result<(), String>),
+    SceneNameChanged(String),
+    ClickedStartScene,
+    StartedScene(Result<String, String>),
+}
+
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct Storage {
+    #[serde(default)]
+    name_field: String,
+    #[serde(default)]
+    name_pattern_field: String,
+    #[serde(default)]
+    description_field: String,
+    #[serde(default)]
+    starting_cast_field: String,
+    #[serde(default)]
+    opening_message_field: String,
+    #[serde(default)]
+    time_of_day_field: String,
+}
+
+impl Model {
+    pub fn new(storage: &Storage) -> Self {
+        Self {
+            name_field: storage.name_field.clone(),
+            name_pattern_field: storage.name_pattern_field.clone(),
+            description_field: storage.description_field.clone(),
+            starting_cast_field: storage.starting_cast_field.clone(),
+            opening_message_field: storage.opening_message_field.clone(),
+            time_of_day_field: storage.time_of_day_field.clone(),
+            scene_name_field: String::new(),
+            templates: TemplatesStatus::Loading,
+            save_status: ActionStatus::Ready,
+            start_status: ActionStatus::Ready,
+        }
+    }
+
+    pub fn to_storage(&self) -> Storage {
+        Storage {
+            name_field: self.name_field.clone(),
+            name_pattern_field: self.name_pattern_field.clone(),
+            description_field: self.description_field.clone(),
+            starting_cast_field: self.starting_cast_field.clone(),
+            opening_message_field: self.opening_message_field.clone(),
+            time_of_day_field: self.time_of_day_field.clone(),
+        }
+    }
+
+    pub fn on_tab_activated(&mut self, worker: Arc<Worker>) -> Task<Msg> {
+        self.templates = TemplatesStatus::Loading;
+        Task::perform(
+            async move { worker.get_scene_templates().await },
+            Msg::LoadedTemplates,
+        )
+    }
+
+    pub fn update(&mut self, worker: Arc<Worker>, msg: Msg) -> Task<Msg> {
+        match msg {
+            Msg::ClickedRefresh => self.on_tab_activated(worker),
+            Msg::LoadedTemplates(result) => {
+                self.templates = match result {
+                    Ok(templates) => TemplatesStatus::Loaded(templates),
+                    Err(err) => TemplatesStatus::Error(err),
+                };
+                Task::none()
+            }
+            Msg::ClickedTemplate(template) => {
+                self.scene_name_field = template.scene_name(chrono::Utc::now());
+                self.name_field = template.name;
+                self.name_pattern_field = template.name_pattern;
+                self.description_field = template.description;
+                self.starting_cast_field = template
+                    .starting_cast
+                    .iter()
+                    .map(|person_name| person_name.as_str().to_string())
+                    .collect::<Vec<String>>()
+                    .join(", ");
+                self.opening_message_field = template.opening_message;
+                self.time_of_day_field = match template.time_of_day {
+                    Some(time_of_day) => time_of_day.to_string(),
+                    None => String::new(),
+                };
+                self.start_status = ActionStatus::Ready;
+                Task::none()
+            }
+            Msg::NameChanged(value) => {
+                self.name_field = value;
+                Task::none()
+            }
+            Msg::NamePatternChanged(value) => {
+                self.name_pattern_field = value;
+                self.scene_name_field = self.to_scene_name();
+                Task::none()
+            }
+            Msg::DescriptionChanged(value) => {
+                self.description_field = value;
+                Task::none()
+            }
+            Msg::StartingCastChanged(value) => {
+                self.starting_cast_field = value;
+                Task::none()
+            }
+            Msg::OpeningMessageChanged(value) => {
+                self.opening_message_field = value;
+                Task::none()
+            }
+            Msg::TimeOfDayChanged(value) => {
+                self.time_of_day_field = value;
+                Task::none()
+            }
+            Msg::ClickedSave => {
+                let template = match self.to_template() {
+                    Ok(template) => template,
+                    Err(err) => {
+                        self.save_status = ActionStatus::Error(err);
+                        return Task::none();
+                    }
+                };
+                self.save_status = ActionStatus::Working;
+                Task::perform(
+                    async move { worker.save_scene_template(template).await },
+                    Msg::Saved,
+                )
+            }
+            Msg::Saved(result) => {
+                match result {
+                    Ok(()) => {
+                        self.save_status = ActionStatus::Done("Saved template".to_string());
+                        return self.on_tab_activated(worker);
+                    }
+                    Err(err) => {
+                        self.save_status = ActionStatus::Error(err);
+                    }
+                }
+                Task::none()
+            }
+            Msg::ClickedDelete => {
+                self.save_status = ActionStatus::Working;
+                let template_name = self.name_field.trim().to_string();
+                Task::perform(
+                    async move { worker.delete_scene_template(template_name.as_str()).await },
+                    Msg::Deleted,
+                )
+            }
+            Msg::Deleted(result) => {
+                match result {
+                    Ok(()) => {
+                        self.save_status = ActionStatus::Done("Deleted template".to_string());
+                        return self.on_tab_activated(worker);
+                    }
+                    Err(err) => {
+                        self.save_status = ActionStatus::Error(err);
+                    }
+                }
+                Task::none()
+            }
+            Msg::SceneNameChanged(value) => {
+                self.scene_name_field = value;
+                Task::none()
+            }
+            Msg::ClickedStartScene => {
+                let template = match self.to_template() {
+                    Ok(template) => template,
+                    Err(err) => {
+                        self.start_status = ActionStatus::Error(err);
+                        return Task::none();
+                    }
+                };
+                self.start_status = ActionStatus::Working;
+                let scene_name = self.scene_name_field.trim().to_string();
+                let random_seed = RandomSeed::from_u64(rand::random());
+                Task::perform(
+                    async move {
+                        scene_template::start_scene(
+                            worker.as_ref(),
+                            &template,
+                            scene_name.clone(),
+                            random_seed,
+                        )
+                        .await
+                        .map(|_| scene_name)
+                        .map_err(|err| err.message())
+                    },
+                    Msg::StartedScene,
+                )
+            }
+            Msg::StartedScene(result) => {
+                self.start_status = match result {
+                    Ok(scene_name) => ActionStatus::Done(format!("Started scene {}", scene_name)),
+                    Err(err) => ActionStatus::Error(err),
+                };
+                Task::none()
+            }
+        }
+    }
+
+    fn to_scene_name(&self) -> String {
+        SceneTemplate {
+            name: self.name_field.trim().to_string(),
+            name_pattern: self.name_pattern_field.trim().to_string(),
+            description: String::new(),
+            starting_cast: vec![],
+            opening_message: String::new(),
+            time_of_day: None,
+        }
+        .scene_name(chrono::Utc::now())
+    }
+
+    fn to_template(&self) -> Result<SceneTemplate, String> {
+        let time_of_day = if self.time_of_day_field.trim().is_empty() {
+            None
+        } else {
+            Some(TimeOfDay::parse(self.time_of_day_field.as_str())?)
+        };
+
+        Ok(SceneTemplate {
+            name: self.name_field.trim().to_string(),
+            name_pattern: self.name_pattern_field.trim().to_string(),
+            description: self.description_field.clone(),
+            starting_cast: self
+                .starting_cast_field
+                .split(',')
+                .map(|name| name.trim())
+                .filter(|name| !name.is_empty())
+                .map(|name| PersonName::from_string(name.to_string()))
+                .collect(),
+            opening_message: self.opening_message_field.clone(),
+            time_of_day,
+        })
+    }
+
+    pub fn view(&self) -> Element<'_, Msg> {
+        let form = w::column![
+            w::text("Template").size(20),
+            w::text("Name"),
+            w::text_input("Diner regulars", &self.name_field).on_input(Msg::NameChanged),
+            w::text("Scene name pattern ({date} and {time} get filled in)"),
+            w::text_input("Diner {date}", &self.name_pattern_field)
+                .on_input(Msg::NamePatternChanged),
+            w::text("Description"),
+            w::text_input("", &self.description_field).on_input(Msg::DescriptionChanged),
+            w::text("Starting cast (comma separated)"),
+            w::text_input("", &self.starting_cast_field).on_input(Msg::StartingCastChanged),
+            w::text("Opening message"),
+            w::text_input("", &self.opening_message_field).on_input(Msg::OpeningMessageChanged),
+            w::text("Time of day in the scene (HH:MM, blank keeps the world clock's)"),
+            w::text_input("08:00", &self.time_of_day_field).on_input(Msg::TimeOfDayChanged),
+            w::row![
+                w::button("Save template").on_press(Msg::ClickedSave),
+                w::button("Delete template").on_press(Msg::ClickedDelete),
+                action_status_view(&self.save_status),
+            ]
+            .spacing(s::S4),
+            w::horizontal_rule(1),
+            w::text("New from template").size(20),
+            w::text("Scene name"),
+            w::text_input("", &self.scene_name_field).on_input(Msg::SceneNameChanged),
+            w::row![
+                w::button("Start scene").on_press(Msg::ClickedStartScene),
+                action_status_view(&self.start_status),
+            ]
+            .spacing(s::S4),
+        ]
+        .spacing(s::S2)
+        .width(Length::Fill);
+
+        let template_list = w::column![
+            w::button("Refresh").on_press(Msg::ClickedRefresh),
+            templates_view(&self.templates),
+        ]
+        .spacing(s::S4)
+        .width(Length::Fixed(240.0));
+
+        w::row![template_list, form].spacing(s::S4).into()
+    }
+}
+
+fn templates_view(status: &TemplatesStatus) -> Element<'_, Msg> {
+    match status {
+        TemplatesStatus::Loading => w::text("Loading templates...").into(),
+        TemplatesStatus::Error(err) => w::text(format!("Error: {}", err)).into(),
+        TemplatesStatus::Loaded(templates) => {
+            if templates.is_empty() {
+                return w::text("No templates yet").into();
+            }
+
+            let buttons = templates
+                .iter()
+                .map(|template| {
+                    w::button(w::text(template.name.as_str()))
+                        .on_press(Msg::ClickedTemplate(template.clone()))
+                        .into()
+                })
+                .collect::<Vec<Element<Msg>>>();
+
+            w::Column::with_children(buttons).spacing(s::S2).into()
+        }
+    }
+}
+
+fn action_status_view(status: &ActionStatus) -> Element<'_, Msg> {
+    match status {
+        ActionStatus::Ready => w::text("").into(),
+        ActionStatus::Working => w::text("Working...").into(),
+        ActionStatus::Done(message) => w::text(message.as_str()).color(s::GREEN_SOFT).into(),
+        ActionStatus::Error(err) => w::text(format!("Error: {}", err)).color(s::RED_SOFT).into(),
+    }
+}
@@ -0,0 +1,168 @@
+use crate::admin_ui::s;
+use crate::domain::schema_overview::{self, IndexHealth, SchemaOverview};
+use crate::migrations;
+use crate::nice_display::NiceDisplay;
+use crate::time_display;
+use crate::worker::Worker;
+use iced::{widget as w, Element, Task};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+pub struct Model {
+    overview: OverviewStatus,
+}
+
+enum OverviewStatus {
+    Loading,
+    Loaded(SchemaOverview),
+    Error(String),
+}
+
+#[derive(Debug, Clone)]
+pub enum Msg {
+    ClickedRefresh,
+    LoadedOverview(Result<SchemaOverview, String>),
+}
+
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct Storage {}
+
+impl Model {
+    pub fn new(_storage: &Storage) -> Self {
+        Self {
+            overview: OverviewStatus::Loading,
+        }
+    }
+
+    pub fn to_storage(&self) -> Storage {
+        Storage {}
+    }
+
+    pub fn on_tab_activated(&mut self, worker: Arc<Worker>) -> Task<Msg> {
+        self.overview = OverviewStatus::Loading;
+
+        Task::perform(
+            async move {
+                let migration_names = migrations::migration_names().map_err(|err| err.message())?;
+                SchemaOverview::load(worker.as_ref(), &migration_names).await
+            },
+            Msg::LoadedOverview,
+        )
+    }
+
+    pub fn update(&mut self, worker: Arc<Worker>, msg: Msg) -> Task<Msg> {
+        match msg {
+            Msg::ClickedRefresh => self.on_tab_activated(worker),
+            Msg::LoadedOverview(result) => {
+                self.overview = match result {
+                    Ok(overview) => OverviewStatus::Loaded(overview),
+                    Err(err) => OverviewStatus::Error(err),
+                };
+                Task::none()
+            }
+        }
+    }
+
+    pub fn view(&self) -> Element<'_, Msg> {
+        w::column![
+            w::text("Schema").size(20),
+            w::text(
+                "Row counts are Postgres' estimates. Index scans count from the last stats reset."
+            )
+            .size(s::S3),
+            w::button("Refresh").on_press(Msg::ClickedRefresh),
+            w::horizontal_rule(1),
+            overview_view(&self.overview),
+        ]
+        .spacing(s::S4)
+        .into()
+    }
+}
+
+fn overview_view(status: &OverviewStatus) -> Element<'_, Msg> {
+    let overview = match status {
+        OverviewStatus::Loading => return w::text("Loading...").into(),
+        OverviewStatus::Error(err) => return w::text(format!("Error: {}", err)).into(),
+        OverviewStatus::Loaded(overview) => overview,
+    };
+
+    let version = match overview.schema_version() {
+        Some(version) => w::text(format!("Schema version: {}", version)),
+        None => w::text("No migrations recorded yet, run run-migrations to record them")
+            .color(s::GOLD_SOFT),
+    };
+
+    let pending = if overview.pending_migrations.is_empty() {
+        w::text("No pending migrations").color(s::GREEN_SOFT)
+    } else {
+        w::text(format!(
+            "{} pending: {}",
+            overview.pending_migrations.len(),
+            overview.pending_migrations.join(", ")
+        ))
+        .color(s::RED_SOFT)
+    };
+
+    let mut col = w::column![version, pending].spacing(s::S2);
+
+    col = col.push(w::horizontal_rule(1));
+    col = col.push(w::text("Tables").size(s::S4));
+    for table in &overview.tables {
+        col = col.push(
+            w::text(format!(
+                "{}  ~{} rows  {}  ({} seq scans, {} index scans)",
+                table.name,
+                table.estimated_rows,
+                schema_overview::format_bytes(table.total_bytes),
+                table.seq_scans,
+                table.index_scans
+            ))
+            .size(s::S3),
+        );
+    }
+
+    col = col.push(w::horizontal_rule(1));
+    col = col.push(w::text("Index health").size(s::S4));
+    let unhealthy = overview.unhealthy_indexes();
+    if unhealthy.is_empty() {
+        col = col.push(
+            w::text(format!(
+                "All {} indexes are valid and in use",
+                overview.indexes.len()
+            ))
+            .color(s::GREEN_SOFT),
+        );
+    }
+    for index in unhealthy {
+        let color = match index.health() {
+            IndexHealth::Invalid => s::RED_SOFT,
+            _ => s::GOLD_SOFT,
+        };
+        col = col.push(
+            w::text(format!(
+                "{} on {}: {}, {}",
+                index.name,
+                index.table_name,
+                index.health().to_label(),
+                schema_overview::format_bytes(index.bytes)
+            ))
+            .size(s::S3)
+            .color(color),
+        );
+    }
+
+    col = col.push(w::horizontal_rule(1));
+    col = col.push(w::text("Applied migrations").size(s::S4));
+    for migration in overview.applied_migrations.iter().rev() {
+        col = col.push(
+            w::text(format!(
+                "{}  {}",
+                time_display::format_absolute(migration.applied_at),
+                migration.name
+            ))
+            .size(s::S3),
+        );
+    }
+
+    w::scrollable(col).into()
+}
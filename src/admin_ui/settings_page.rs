@@ -0,0 +1,504 @@
+use crate::admin_ui::s;
+use crate::admin_ui::style_guide_form;
+use crate::capability::guardrail::GuardrailCapability;
+use crate::capability::memory::MemoryCapability;
+use crate::capability::style_guide::StyleGuideCapability;
+use crate::capability::voice_exemplar::VoiceExemplarCapability;
+use crate::capability::world_language::WorldLanguageCapability;
+use crate::domain::guardrail::{self, GuardrailSettings};
+use crate::domain::memory::MemoryRetrieval;
+use crate::domain::style_guide::StyleGuide;
+use crate::domain::voice_exemplar::{VoiceExemplarComparison, VoiceExemplarMode};
+use crate::worker::Worker;
+use crate::world_language::WorldLanguage;
+use iced::{widget as w, Element, Task};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+pub struct Model {
+    guardrail_prompt: w::text_editor::Content,
+    refused_topics: w::text_editor::Content,
+    tone_input: String,
+    guardrail_status: GuardrailStatus,
+    style_guide: StyleGuideStatus,
+    top_k_input: String,
+    max_distance_input: String,
+    recency_weight_input: String,
+    retrieval_status: RetrievalStatus,
+    voice_exemplars: VoiceExemplarStatus,
+    language: LanguageStatus,
+}
+
+enum GuardrailStatus {
+    Loading,
+    Ready,
+    Saving,
+    Saved,
+    Error(String),
+}
+
+enum RetrievalStatus {
+    Loading,
+    Ready,
+    Saving,
+    Saved,
+    Error(String),
+}
+
+enum VoiceExemplarStatus {
+    Loading,
+    Loaded {
+        mode: VoiceExemplarMode,
+        comparison: VoiceExemplarComparison,
+    },
+    Saving,
+    Error(String),
+}
+
+enum LanguageStatus {
+    Loading,
+    Loaded(WorldLanguage),
+    Saving,
+    Error(String),
+}
+
+enum StyleGuideStatus {
+    Loading,
+    Loaded(style_guide_form::Model),
+    Error(String),
+}
+
+#[derive(Debug, Clone)]
+pub enum Msg {
+    ClickedRefresh,
+    LoadedGuardrails(Result<GuardrailSettings, String>),
+    GuardrailPromptUpdated(w::text_editor::Action),
+    RefusedTopicsUpdated(w::text_editor::Action),
+    ToneInputChanged(String),
+    ClickedSaveGuardrails,
+    GuardrailsSaved(Result<(), String>),
+    LoadedStyleGuide(Result<StyleGuide, String>),
+    StyleGuideForm(style_guide_form::Msg),
+    LoadedRetrieval(Result<MemoryRetrieval, String>),
+    TopKInputChanged(String),
+    MaxDistanceInputChanged(String),
+    RecencyWeightInputChanged(String),
+    ClickedSaveRetrieval,
+    RetrievalSaved(Result<(), String>),
+    LoadedVoiceExemplars(Result<(VoiceExemplarMode, VoiceExemplarComparison), String>),
+    ClickedVoiceExemplarMode(VoiceExemplarMode),
+    VoiceExemplarModeSaved(Result<(), String>),
+    LoadedLanguage(Result<WorldLanguage, String>),
+    ClickedLanguage(WorldLanguage),
+    LanguageSaved(Result<(), String>),
+}
+
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct Storage {}
+
+impl Model {
+    pub fn new(_storage: &Storage) -> Self {
+        Self {
+            guardrail_prompt: w::text_editor::Content::new(),
+            refused_topics: w::text_editor::Content::new(),
+            tone_input: String::new(),
+            guardrail_status: GuardrailStatus::Loading,
+            style_guide: StyleGuideStatus::Loading,
+            top_k_input: String::new(),
+            max_distance_input: String::new(),
+            recency_weight_input: String::new(),
+            retrieval_status: RetrievalStatus::Loading,
+            voice_exemplars: VoiceExemplarStatus::Loading,
+            language: LanguageStatus::Loading,
+        }
+    }
+
+    pub fn to_storage(&self) -> Storage {
+        Storage {}
+    }
+
+    pub fn on_tab_activated(&mut self, worker: Arc<Worker>) -> Task<Msg> {
+        self.guardrail_status = GuardrailStatus::Loading;
+        self.style_guide = StyleGuideStatus::Loading;
+        self.retrieval_status = RetrievalStatus::Loading;
+
+        let style_guide_worker = worker.clone();
+        let retrieval_worker = worker.clone();
+        Task::batch([
+            self.load_voice_exemplars(worker.clone()),
+            self.load_language(worker.clone()),
+            Task::perform(
+                async move { worker.get_guardrail_settings().await },
+                Msg::LoadedGuardrails,
+            ),
+            Task::perform(
+                async move { style_guide_worker.get_world_style_guide().await },
+                Msg::LoadedStyleGuide,
+            ),
+            Task::perform(
+                async move { retrieval_worker.get_memory_retrieval().await },
+                Msg::LoadedRetrieval,
+            ),
+        ])
+    }
+
+    fn load_voice_exemplars(&mut self, worker: Arc<Worker>) -> Task<Msg> {
+        self.voice_exemplars = VoiceExemplarStatus::Loading;
+
+        Task::perform(
+            async move {
+                let mode = worker.get_voice_exemplar_mode().await?;
+                let comparison = worker.get_voice_exemplar_comparison().await?;
+                Ok((mode, comparison))
+            },
+            Msg::LoadedVoiceExemplars,
+        )
+    }
+
+    fn load_language(&mut self, worker: Arc<Worker>) -> Task<Msg> {
+        self.language = LanguageStatus::Loading;
+
+        Task::perform(
+            async move { worker.get_world_language().await },
+            Msg::LoadedLanguage,
+        )
+    }
+
+    pub fn update(&mut self, worker: Arc<Worker>, msg: Msg) -> Task<Msg> {
+        match msg {
+            Msg::ClickedRefresh => self.on_tab_activated(worker),
+            Msg::LoadedGuardrails(result) => {
+                match result {
+                    Ok(settings) => {
+                        self.guardrail_prompt =
+                            w::text_editor::Content::with_text(&settings.prompt);
+                        self.refused_topics =
+                            w::text_editor::Content::with_text(&settings.refused_topics.join("\n"));
+                        self.tone_input = settings.tone;
+                        self.guardrail_status = GuardrailStatus::Ready;
+                    }
+                    Err(err) => {
+                        self.guardrail_status = GuardrailStatus::Error(err);
+                    }
+                }
+                Task::none()
+            }
+            Msg::GuardrailPromptUpdated(action) => {
+                self.guardrail_prompt.perform(action);
+                Task::none()
+            }
+            Msg::RefusedTopicsUpdated(action) => {
+                self.refused_topics.perform(action);
+                Task::none()
+            }
+            Msg::ToneInputChanged(value) => {
+                self.tone_input = value;
+                Task::none()
+            }
+            Msg::ClickedSaveGuardrails => {
+                let settings = GuardrailSettings {
+                    prompt: self.guardrail_prompt.text(),
+                    refused_topics: guardrail::parse_topics(&self.refused_topics.text()),
+                    tone: self.tone_input.clone(),
+                };
+
+                // Show the topics the way they were saved
+                self.refused_topics =
+                    w::text_editor::Content::with_text(&settings.refused_topics.join("\n"));
+                self.guardrail_status = GuardrailStatus::Saving;
+                Task::perform(
+                    async move { worker.set_guardrail_settings(&settings).await },
+                    Msg::GuardrailsSaved,
+                )
+            }
+            Msg::GuardrailsSaved(result) => {
+                self.guardrail_status = match result {
+                    Ok(()) => GuardrailStatus::Saved,
+                    Err(err) => GuardrailStatus::Error(err),
+                };
+                Task::none()
+            }
+            Msg::LoadedStyleGuide(result) => {
+                self.style_guide = match result {
+                    Ok(style_guide) => StyleGuideStatus::Loaded(style_guide_form::Model::new(
+                        style_guide_form::Target::World,
+                        &style_guide,
+                    )),
+                    Err(err) => StyleGuideStatus::Error(err),
+                };
+                Task::none()
+            }
+            Msg::StyleGuideForm(sub_msg) => match &mut self.style_guide {
+                StyleGuideStatus::Loaded(form) => {
+                    form.update(worker, sub_msg).map(Msg::StyleGuideForm)
+                }
+                _ => Task::none(),
+            },
+            Msg::LoadedRetrieval(result) => {
+                match result {
+                    Ok(retrieval) => {
+                        self.top_k_input = retrieval.top_k.to_string();
+                        self.max_distance_input = retrieval.max_distance.to_string();
+                        self.recency_weight_input = retrieval.recency_weight.to_string();
+                        self.retrieval_status = RetrievalStatus::Ready;
+                    }
+                    Err(err) => {
+                        self.retrieval_status = RetrievalStatus::Error(err);
+                    }
+                }
+                Task::none()
+            }
+            Msg::TopKInputChanged(value) => {
+                self.top_k_input = value;
+                Task::none()
+            }
+            Msg::MaxDistanceInputChanged(value) => {
+                self.max_distance_input = value;
+                Task::none()
+            }
+            Msg::RecencyWeightInputChanged(value) => {
+                self.recency_weight_input = value;
+                Task::none()
+            }
+            Msg::ClickedSaveRetrieval => {
+                let retrieval = match MemoryRetrieval::parse(
+                    &self.top_k_input,
+                    &self.max_distance_input,
+                    &self.recency_weight_input,
+                ) {
+                    Ok(retrieval) => retrieval,
+                    Err(err) => {
+                        self.retrieval_status = RetrievalStatus::Error(err);
+                        return Task::none();
+                    }
+                };
+
+                self.retrieval_status = RetrievalStatus::Saving;
+                Task::perform(
+                    async move { worker.set_memory_retrieval(&retrieval).await },
+                    Msg::RetrievalSaved,
+                )
+            }
+            Msg::RetrievalSaved(result) => {
+                self.retrieval_status = match result {
+                    Ok(()) => RetrievalStatus::Saved,
+                    Err(err) => RetrievalStatus::Error(err),
+                };
+                Task::none()
+            }
+            Msg::LoadedVoiceExemplars(result) => {
+                self.voice_exemplars = match result {
+                    Ok((mode, comparison)) => VoiceExemplarStatus::Loaded { mode, comparison },
+                    Err(err) => VoiceExemplarStatus::Error(err),
+                };
+                Task::none()
+            }
+            Msg::ClickedVoiceExemplarMode(mode) => {
+                self.voice_exemplars = VoiceExemplarStatus::Saving;
+                Task::perform(
+                    async move { worker.set_voice_exemplar_mode(mode).await },
+                    Msg::VoiceExemplarModeSaved,
+                )
+            }
+            Msg::VoiceExemplarModeSaved(result) => match result {
+                Ok(()) => self.load_voice_exemplars(worker),
+                Err(err) => {
+                    self.voice_exemplars = VoiceExemplarStatus::Error(err);
+                    Task::none()
+                }
+            },
+            Msg::LoadedLanguage(result) => {
+                self.language = match result {
+                    Ok(language) => LanguageStatus::Loaded(language),
+                    Err(err) => LanguageStatus::Error(err),
+                };
+                Task::none()
+            }
+            Msg::ClickedLanguage(language) => {
+                self.language = LanguageStatus::Saving;
+                Task::perform(
+                    async move { worker.set_world_language(language).await },
+                    Msg::LanguageSaved,
+                )
+            }
+            Msg::LanguageSaved(result) => match result {
+                Ok(()) => self.load_language(worker),
+                Err(err) => {
+                    self.language = LanguageStatus::Error(err);
+                    Task::none()
+                }
+            },
+        }
+    }
+
+    pub fn view(&self) -> Element<'_, Msg> {
+        let prompt_editor = w::text_editor(&self.guardrail_prompt)
+            .on_action(Msg::GuardrailPromptUpdated)
+            .height(iced::Length::Fixed(140.0));
+
+        let topics_editor = w::text_editor(&self.refused_topics)
+            .on_action(Msg::RefusedTopicsUpdated)
+            .height(iced::Length::Fixed(120.0));
+
+        w::column![
+            w::text("Settings").size(20),
+            w::text("Guardrails").size(s::S4),
+            w::text(
+                "Added to every reaction system prompt in this world. Messages from persons that mention a refused topic are blocked by moderation."
+            )
+            .size(s::S3),
+            w::text("Rules"),
+            prompt_editor,
+            w::text("Refused topics (one per line)"),
+            topics_editor,
+            w::row![
+                w::text("Tone"),
+                w::text_input("like \"friendly, never insulting\"", &self.tone_input)
+                    .on_input(Msg::ToneInputChanged)
+                    .on_submit(Msg::ClickedSaveGuardrails),
+            ]
+            .spacing(s::S4),
+            w::row![
+                w::button("Save").on_press(Msg::ClickedSaveGuardrails),
+                w::button("Refresh").on_press(Msg::ClickedRefresh),
+                guardrail_status_view(&self.guardrail_status),
+            ]
+            .spacing(s::S4),
+            w::text("Style guide").size(s::S4),
+            w::text(
+                "How persons in this world phrase what they say. Each person can override any part of it on the Person tab. Comments longer than the max are sent back to be said again, and cut short if they are still too long."
+            )
+            .size(s::S3),
+            style_guide_view(&self.style_guide),
+            w::text("Memory retrieval").size(s::S4),
+            w::text(
+                "How memories are picked for reactions. The closest top K are kept, counting memories that mention someone present as closer and adding the recency weight for each day since a memory was made. Anything scoring past the max distance is left out."
+            )
+            .size(s::S3),
+            w::row![
+                w::text("Top K"),
+                w::text_input("5", &self.top_k_input)
+                    .on_input(Msg::TopKInputChanged)
+                    .on_submit(Msg::ClickedSaveRetrieval),
+                w::text("Max distance"),
+                w::text_input("0.45", &self.max_distance_input)
+                    .on_input(Msg::MaxDistanceInputChanged)
+                    .on_submit(Msg::ClickedSaveRetrieval),
+                w::text("Recency weight"),
+                w::text_input("0", &self.recency_weight_input)
+                    .on_input(Msg::RecencyWeightInputChanged)
+                    .on_submit(Msg::ClickedSaveRetrieval),
+            ]
+            .spacing(s::S4),
+            w::row![
+                w::button("Save").on_press(Msg::ClickedSaveRetrieval),
+                retrieval_status_view(&self.retrieval_status),
+            ]
+            .spacing(s::S4),
+            w::text("Voice exemplars").size(s::S4),
+            w::text(
+                "Whether reactions are shown the example lines set for each person on the Person tab. A/B shows them to a random half of reactions, so marking reactions good on the Reaction tab tells you whether they help."
+            )
+            .size(s::S3),
+            voice_exemplars_view(&self.voice_exemplars),
+            w::text("Language").size(s::S4),
+            w::text(
+                "What persons in this world speak and write. Every completion is told to answer in it, and dates are written in its order. The job runner picks up a change before its next job."
+            )
+            .size(s::S3),
+            language_view(&self.language),
+        ]
+        .spacing(s::S4)
+        .into()
+    }
+}
+
+fn style_guide_view(status: &StyleGuideStatus) -> Element<'_, Msg> {
+    match status {
+        StyleGuideStatus::Loading => w::text("Loading...").into(),
+        StyleGuideStatus::Loaded(form) => form.view().map(Msg::StyleGuideForm),
+        StyleGuideStatus::Error(err) => {
+            w::text(format!("Error: {}", err)).color(s::RED_SOFT).into()
+        }
+    }
+}
+
+fn voice_exemplars_view(status: &VoiceExemplarStatus) -> Element<'_, Msg> {
+    let (mode, comparison) = match status {
+        VoiceExemplarStatus::Loading => return w::text("Loading...").into(),
+        VoiceExemplarStatus::Saving => return w::text("Saving...").into(),
+        VoiceExemplarStatus::Error(err) => {
+            return w::text(format!("Error: {}", err)).color(s::RED_SOFT).into()
+        }
+        VoiceExemplarStatus::Loaded { mode, comparison } => (mode, comparison),
+    };
+
+    let mut modes = w::row![w::text("Mode")].spacing(s::S1);
+    for option in VoiceExemplarMode::ALL {
+        let button = w::button(w::text(option.to_label()));
+        modes = modes.push(if option == *mode {
+            button
+        } else {
+            button.on_press(Msg::ClickedVoiceExemplarMode(option))
+        });
+    }
+
+    w::column![
+        modes,
+        w::text(format!(
+            "With exemplars: {}",
+            comparison.with_exemplars.to_text()
+        ))
+        .size(s::S3),
+        w::text(format!(
+            "Without exemplars: {}",
+            comparison.without_exemplars.to_text()
+        ))
+        .size(s::S3),
+    ]
+    .spacing(s::S1)
+    .into()
+}
+
+fn language_view(status: &LanguageStatus) -> Element<'_, Msg> {
+    let current = match status {
+        LanguageStatus::Loading => return w::text("Loading...").into(),
+        LanguageStatus::Saving => return w::text("Saving...").into(),
+        LanguageStatus::Error(err) => {
+            return w::text(format!("Error: {}", err)).color(s::RED_SOFT).into()
+        }
+        LanguageStatus::Loaded(language) => language,
+    };
+
+    let mut languages = w::row![].spacing(s::S1);
+    for option in WorldLanguage::ALL {
+        let button = w::button(w::text(option.name()));
+        languages = languages.push(if option == *current {
+            button
+        } else {
+            button.on_press(Msg::ClickedLanguage(option))
+        });
+    }
+
+    languages.into()
+}
+
+fn retrieval_status_view(status: &RetrievalStatus) -> Element<'_, Msg> {
+    match status {
+        RetrievalStatus::Loading => w::text("Loading...").into(),
+        RetrievalStatus::Saving => w::text("Saving...").into(),
+        RetrievalStatus::Saved => w::text("Saved").color(s::GREEN_SOFT).into(),
+        RetrievalStatus::Ready => w::text("").into(),
+        RetrievalStatus::Error(err) => w::text(format!("Error: {}", err)).color(s::RED_SOFT).into(),
+    }
+}
+
+fn guardrail_status_view(status: &GuardrailStatus) -> Element<'_, Msg> {
+    match status {
+        GuardrailStatus::Loading => w::text("Loading...").into(),
+        GuardrailStatus::Saving => w::text("Saving...").into(),
+        GuardrailStatus::Saved => w::text("Saved").color(s::GREEN_SOFT).into(),
+        GuardrailStatus::Ready => w::text("").into(),
+        GuardrailStatus::Error(err) => w::text(format!("Error: {}", err)).color(s::RED_SOFT).into(),
+    }
+}
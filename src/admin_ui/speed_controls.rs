@@ -0,0 +1,142 @@
+use crate::admin_ui::s;
+use crate::capability::job_runner_settings::JobRunnerSettingsCapability;
+use crate::domain::simulation_speed::{self, TIME_FACTOR_PRESETS};
+use crate::domain::world_time::WorldTime;
+use crate::worker::Worker;
+use iced::{time, widget as w, Element, Subscription, Task};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Often enough to watch the clock rush through a fast forward.
+const POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Sets how fast the active clock runs and skips waits, next to the job
+/// runner's on and off switch.
+pub struct Model {
+    time_factor: Option<f64>,
+    active_ms: Option<i64>,
+    status: Status,
+}
+
+enum Status {
+    Ready,
+    Saving,
+    SkipRequested,
+    Error(String),
+}
+
+#[derive(Debug, Clone)]
+pub enum Msg {
+    Tick,
+    Loaded(Result<(f64, i64), String>),
+    ClickedTimeFactor(f64),
+    TimeFactorSaved(Result<f64, String>),
+    ClickedSkipWait,
+    SkipRequested(Result<(), String>),
+}
+
+impl Model {
+    pub fn new() -> Self {
+        Self {
+            time_factor: None,
+            active_ms: None,
+            status: Status::Ready,
+        }
+    }
+
+    pub fn load(&self, worker: Arc<Worker>) -> Task<Msg> {
+        Task::perform(
+            async move { tokio::try_join!(worker.get_time_factor(), worker.get_active_clock_ms()) },
+            Msg::Loaded,
+        )
+    }
+
+    pub fn update(&mut self, worker: Arc<Worker>, msg: Msg) -> Task<Msg> {
+        match msg {
+            Msg::Tick => self.load(worker),
+            Msg::Loaded(result) => {
+                match result {
+                    Ok((time_factor, active_ms)) => {
+                        self.time_factor = Some(time_factor);
+                        self.active_ms = Some(active_ms);
+                        if let Status::Error(_) = self.status {
+                            self.status = Status::Ready;
+                        }
+                    }
+                    Err(err) => self.status = Status::Error(err),
+                }
+                Task::none()
+            }
+            Msg::ClickedTimeFactor(time_factor) => {
+                self.status = Status::Saving;
+                Task::perform(
+                    async move {
+                        let time_factor = simulation_speed::validate_time_factor(time_factor)?;
+                        worker.set_time_factor(time_factor).await?;
+                        Ok(time_factor)
+                    },
+                    Msg::TimeFactorSaved,
+                )
+            }
+            Msg::TimeFactorSaved(result) => {
+                match result {
+                    Ok(time_factor) => {
+                        self.time_factor = Some(time_factor);
+                        self.status = Status::Ready;
+                    }
+                    Err(err) => self.status = Status::Error(err),
+                }
+                Task::none()
+            }
+            Msg::ClickedSkipWait => Task::perform(
+                async move { worker.request_wait_skip().await },
+                Msg::SkipRequested,
+            ),
+            Msg::SkipRequested(result) => {
+                self.status = match result {
+                    Ok(()) => Status::SkipRequested,
+                    Err(err) => Status::Error(err),
+                };
+                Task::none()
+            }
+        }
+    }
+
+    pub fn view(&self) -> Element<'_, Msg> {
+        let clock_text = match self.active_ms {
+            Some(active_ms) => {
+                let world_time = WorldTime::new(active_ms, 0);
+                format!("Day {} {}", world_time.day(), world_time.time_of_day())
+            }
+            None => "Day ?".to_string(),
+        };
+
+        let mut row = w::row![w::text(clock_text)].spacing(s::S1);
+
+        for preset in TIME_FACTOR_PRESETS {
+            let button = w::button(w::text(simulation_speed::time_factor_label(preset)));
+            row = row.push(if self.time_factor == Some(preset) {
+                button
+            } else {
+                button.on_press(Msg::ClickedTimeFactor(preset))
+            });
+        }
+
+        let status_view: Element<'_, Msg> = match &self.status {
+            Status::Ready => w::text("").into(),
+            Status::Saving => w::text("Saving...").into(),
+            Status::SkipRequested => w::text("Skipping at the next poll")
+                .color(s::GRAY_MID)
+                .into(),
+            Status::Error(err) => w::text(format!("Error: {}", err)).color(s::RED_SOFT).into(),
+        };
+
+        row.push(w::button("Skip Wait").on_press(Msg::ClickedSkipWait))
+            .push(status_view)
+            .into()
+    }
+
+    pub fn subscription(&self) -> Subscription<Msg> {
+        time::every(POLL_INTERVAL).map(|_| Msg::Tick)
+    }
+}
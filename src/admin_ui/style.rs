@@ -10,6 +10,7 @@ pub const LIST_HEIGHT: f32 = 520.0;
 pub const GREEN_SOFT: Color = Color::from_rgb(0.55, 0.78, 0.54);
 pub const RED_SOFT: Color = Color::from_rgb(0.85, 0.45, 0.45);
 pub const GOLD_SOFT: Color = Color::from_rgb(0.78, 0.72, 0.46);
+pub const BLUE_SOFT: Color = Color::from_rgb(0.53, 0.68, 0.86);
 pub const GRAY_VERY_SOFT: Color = Color::from_rgb(0.92, 0.92, 0.92);
 pub const GRAY_SOFT: Color = Color::from_rgb(0.8, 0.8, 0.8);
 pub const GRAY_MID: Color = Color::from_rgb(0.65, 0.65, 0.65);
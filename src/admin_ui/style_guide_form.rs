@@ -0,0 +1,161 @@
+use crate::admin_ui::s;
+use crate::capability::style_guide::StyleGuideCapability;
+use crate::domain::person_uuid::PersonUuid;
+use crate::domain::style_guide::{self, Formality, StyleGuide};
+use crate::worker::Worker;
+use iced::{widget as w, Element, Task};
+use std::sync::Arc;
+
+/// Edits either the world's style guide or one person's overrides of it.
+pub struct Model {
+    target: Target,
+    max_sentences_field: String,
+    dialect_field: String,
+    formality: Option<Formality>,
+    status: Status,
+}
+
+#[derive(Debug, Clone)]
+pub enum Target {
+    World,
+    Person(PersonUuid),
+}
+
+enum Status {
+    Ready,
+    Saving,
+    Saved,
+    Error(String),
+}
+
+#[derive(Debug, Clone)]
+pub enum Msg {
+    MaxSentencesFieldChanged(String),
+    DialectFieldChanged(String),
+    ClickedFormality(Option<Formality>),
+    ClickedSave,
+    Saved(Result<(), String>),
+}
+
+impl Model {
+    pub fn new(target: Target, style_guide: &StyleGuide) -> Self {
+        Self {
+            target,
+            max_sentences_field: style_guide
+                .max_sentences
+                .map(|max_sentences| max_sentences.to_string())
+                .unwrap_or_default(),
+            dialect_field: style_guide.dialect.clone(),
+            formality: style_guide.formality,
+            status: Status::Ready,
+        }
+    }
+
+    pub fn update(&mut self, worker: Arc<Worker>, msg: Msg) -> Task<Msg> {
+        match msg {
+            Msg::MaxSentencesFieldChanged(value) => {
+                self.max_sentences_field = value;
+                self.status = Status::Ready;
+                Task::none()
+            }
+            Msg::DialectFieldChanged(value) => {
+                self.dialect_field = value;
+                self.status = Status::Ready;
+                Task::none()
+            }
+            Msg::ClickedFormality(formality) => {
+                self.formality = formality;
+                self.status = Status::Ready;
+                Task::none()
+            }
+            Msg::ClickedSave => {
+                let max_sentences =
+                    match style_guide::parse_max_sentences(&self.max_sentences_field) {
+                        Ok(max_sentences) => max_sentences,
+                        Err(err) => {
+                            self.status = Status::Error(err);
+                            return Task::none();
+                        }
+                    };
+
+                let style_guide = StyleGuide {
+                    max_sentences,
+                    dialect: self.dialect_field.clone(),
+                    formality: self.formality,
+                };
+
+                self.status = Status::Saving;
+                let target = self.target.clone();
+                Task::perform(
+                    async move {
+                        match target {
+                            Target::World => worker.set_world_style_guide(&style_guide).await,
+                            Target::Person(person_uuid) => {
+                                worker
+                                    .set_person_style_guide(&person_uuid, &style_guide)
+                                    .await
+                            }
+                        }
+                    },
+                    Msg::Saved,
+                )
+            }
+            Msg::Saved(result) => {
+                self.status = match result {
+                    Ok(()) => Status::Saved,
+                    Err(err) => Status::Error(err),
+                };
+                Task::none()
+            }
+        }
+    }
+
+    pub fn view(&self) -> Element<'_, Msg> {
+        let unset_label = match self.target {
+            Target::World => "Any",
+            Target::Person(_) => "World's",
+        };
+
+        let mut formalities = w::row![w::text("Formality")].spacing(s::S1);
+        for formality in std::iter::once(None).chain(Formality::ALL.into_iter().map(Some)) {
+            let label = match formality {
+                Some(formality) => formality.to_name(),
+                None => unset_label,
+            };
+            let button = w::button(w::text(label));
+            formalities = formalities.push(if formality == self.formality {
+                button
+            } else {
+                button.on_press(Msg::ClickedFormality(formality))
+            });
+        }
+
+        let status_view: Element<'_, Msg> = match &self.status {
+            Status::Ready => w::text("").into(),
+            Status::Saving => w::text("Saving...").into(),
+            Status::Saved => w::text("Saved").color(s::GREEN_SOFT).into(),
+            Status::Error(err) => w::text(format!("Error: {}", err)).color(s::RED_SOFT).into(),
+        };
+
+        w::column![
+            w::row![
+                w::text("Max sentences"),
+                w::text_input("blank for no limit", &self.max_sentences_field)
+                    .on_input(Msg::MaxSentencesFieldChanged)
+                    .on_submit(Msg::ClickedSave),
+            ]
+            .spacing(s::S4),
+            w::row![
+                w::text("Dialect"),
+                w::text_input("like \"Texan drawl\"", &self.dialect_field)
+                    .on_input(Msg::DialectFieldChanged)
+                    .on_submit(Msg::ClickedSave),
+            ]
+            .spacing(s::S4),
+            formalities,
+            w::row![w::button("Save").on_press(Msg::ClickedSave), status_view].spacing(s::S4),
+        ]
+        .spacing(s::S1)
+        .into()
+    }
+}
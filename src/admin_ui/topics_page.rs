@@ -0,0 +1,245 @@
+use crate::admin_ui::s;
+use crate::capability::topic::TopicCapability;
+use crate::domain::topic::{Topic, TopicScene, TrendingTopic};
+use crate::time_display;
+use crate::worker::Worker;
+use chrono::{DateTime, Duration, Utc};
+use iced::{widget as w, Element, Length, Task};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+const TRENDING_LIMIT: i64 = 30;
+
+pub struct Model {
+    window: Window,
+    topics: TopicsStatus,
+    selected: Option<(Topic, ScenesStatus)>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Window {
+    #[default]
+    Day,
+    Week,
+    AllTime,
+}
+
+enum TopicsStatus {
+    Loading,
+    Loaded(Vec<TrendingTopic>),
+    Error(String),
+}
+
+enum ScenesStatus {
+    Loading,
+    Loaded(Vec<TopicScene>),
+    Error(String),
+}
+
+#[derive(Debug, Clone)]
+pub enum Msg {
+    ClickedWindow(Window),
+    ClickedRefresh,
+    LoadedTopics(Result<Vec<TrendingTopic>, String>),
+    ClickedTopic(Topic),
+    LoadedScenes(Topic, Result<Vec<TopicScene>, String>),
+}
+
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct Storage {
+    #[serde(default)]
+    window: Window,
+}
+
+impl Window {
+    const ALL: [Window; 3] = [Window::Day, Window::Week, Window::AllTime];
+
+    fn label(&self) -> &'static str {
+        match self {
+            Window::Day => "Past day",
+            Window::Week => "Past week",
+            Window::AllTime => "All time",
+        }
+    }
+
+    fn since(&self, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        match self {
+            Window::Day => Some(now - Duration::days(1)),
+            Window::Week => Some(now - Duration::weeks(1)),
+            Window::AllTime => None,
+        }
+    }
+}
+
+impl Model {
+    pub fn new(storage: &Storage) -> Self {
+        Self {
+            window: storage.window,
+            topics: TopicsStatus::Loading,
+            selected: None,
+        }
+    }
+
+    pub fn to_storage(&self) -> Storage {
+        Storage {
+            window: self.window,
+        }
+    }
+
+    pub fn on_tab_activated(&mut self, worker: Arc<Worker>) -> Task<Msg> {
+        self.topics = TopicsStatus::Loading;
+        let since = self.window.since(Utc::now());
+
+        Task::perform(
+            async move { worker.get_trending_topics(since, TRENDING_LIMIT).await },
+            Msg::LoadedTopics,
+        )
+    }
+
+    pub fn update(&mut self, worker: Arc<Worker>, msg: Msg) -> Task<Msg> {
+        match msg {
+            Msg::ClickedWindow(window) => {
+                self.window = window;
+                self.on_tab_activated(worker)
+            }
+            Msg::ClickedRefresh => self.on_tab_activated(worker),
+            Msg::LoadedTopics(result) => {
+                self.topics = match result {
+                    Ok(topics) => TopicsStatus::Loaded(topics),
+                    Err(err) => TopicsStatus::Error(err),
+                };
+                Task::none()
+            }
+            Msg::ClickedTopic(topic) => {
+                self.selected = Some((topic.clone(), ScenesStatus::Loading));
+
+                Task::perform(
+                    async move {
+                        let result = worker.get_topic_scenes(&topic).await;
+                        (topic, result)
+                    },
+                    |(topic, result)| Msg::LoadedScenes(topic, result),
+                )
+            }
+            Msg::LoadedScenes(topic, result) => {
+                // A slower load for a topic clicked earlier should not replace this one
+                if let Some((selected_topic, status)) = &mut self.selected {
+                    if *selected_topic == topic {
+                        *status = match result {
+                            Ok(scenes) => ScenesStatus::Loaded(scenes),
+                            Err(err) => ScenesStatus::Error(err),
+                        };
+                    }
+                }
+                Task::none()
+            }
+        }
+    }
+
+    pub fn view(&self) -> Element<'_, Msg> {
+        let mut windows = w::row![].spacing(s::S1);
+        for window in Window::ALL {
+            let button = w::button(w::text(window.label()));
+            windows = windows.push(if window == self.window {
+                button
+            } else {
+                button.on_press(Msg::ClickedWindow(window))
+            });
+        }
+
+        w::column![
+            w::text("What the world has been talking about").size(20),
+            w::text(
+                "The job runner tags scenes with the words they keep coming back to every ten minutes. Find the persons who were there with the Talked about filter on the Person tab."
+            )
+            .size(s::S3),
+            w::row![windows, w::button("Refresh").on_press(Msg::ClickedRefresh)]
+                .spacing(s::S4),
+            w::row![
+                topics_view(&self.topics),
+                selected_view(self.selected.as_ref()),
+            ]
+            .spacing(s::S4),
+        ]
+        .spacing(s::S4)
+        .into()
+    }
+}
+
+fn topics_view(status: &TopicsStatus) -> Element<'_, Msg> {
+    let topics = match status {
+        TopicsStatus::Loading => return w::text("Loading...").into(),
+        TopicsStatus::Error(err) => {
+            return w::text(format!("Error: {}", err)).color(s::RED_SOFT).into()
+        }
+        TopicsStatus::Loaded(topics) => topics,
+    };
+
+    if topics.is_empty() {
+        return w::text("Nothing has been tagged yet").into();
+    }
+
+    let mut col = w::column![].spacing(s::S1);
+
+    for topic in topics {
+        col = col.push(
+            w::row![
+                w::button(w::text(topic.topic.as_str()))
+                    .on_press(Msg::ClickedTopic(topic.topic.clone())),
+                w::text(format!(
+                    "{} mentions in {} scenes, last {}",
+                    topic.mentions,
+                    topic.scene_count,
+                    time_display::format_recent(topic.last_mentioned_at)
+                ))
+                .size(s::S3),
+            ]
+            .spacing(s::S2)
+            .align_y(iced::Alignment::Center),
+        );
+    }
+
+    w::scrollable(col)
+        .height(Length::Fixed(s::LIST_HEIGHT))
+        .width(Length::FillPortion(1))
+        .into()
+}
+
+fn selected_view(selected: Option<&(Topic, ScenesStatus)>) -> Element<'_, Msg> {
+    let (topic, status) = match selected {
+        Some(selected) => selected,
+        None => return w::text("Pick a topic to see where it came up").into(),
+    };
+
+    let scenes = match status {
+        ScenesStatus::Loading => return w::text("Loading...").into(),
+        ScenesStatus::Error(err) => {
+            return w::text(format!("Error: {}", err)).color(s::RED_SOFT).into()
+        }
+        ScenesStatus::Loaded(scenes) => scenes,
+    };
+
+    let mut col = w::column![w::text(format!("Scenes about \"{}\"", topic.as_str())).size(s::S4)]
+        .spacing(s::S1);
+
+    for scene in scenes {
+        col = col.push(
+            w::row![
+                w::text(&scene.scene_name),
+                w::text(format!(
+                    "{} mentions, last {}",
+                    scene.mentions,
+                    time_display::format_recent(scene.last_mentioned_at)
+                ))
+                .size(s::S3)
+                .color(s::GRAY_MID),
+            ]
+            .spacing(s::S2),
+        );
+    }
+
+    w::scrollable(col)
+        .height(Length::Fixed(s::LIST_HEIGHT))
+        .width(Length::FillPortion(1))
+        .into()
+}
@@ -0,0 +1,232 @@
+use crate::admin_ui::s;
+use crate::capability::reaction_context::{ReactionContext, ReactionContextCapability};
+use crate::domain::reaction_context_uuid::ReactionContextUuid;
+use crate::nice_display::NiceDisplay;
+use crate::tasks::export_training_data;
+use crate::time_display;
+use crate::worker::Worker;
+use iced::{widget as w, Element, Task};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+const REACTION_CONTEXT_PAGE_SIZE: i64 = 50;
+const DEFAULT_EXPORT_PATH: &str = "training_data.jsonl";
+
+pub struct Model {
+    export_path_field: String,
+    reaction_contexts: ReactionContextsStatus,
+    export_status: ExportStatus,
+}
+
+enum ReactionContextsStatus {
+    Loading,
+    Loaded(Vec<ReactionContext>),
+    Error(String),
+}
+
+enum ExportStatus {
+    Ready,
+    Exporting,
+    Done(usize),
+    Error(String),
+}
+
+#[derive(Debug, Clone)]
+pub enum Msg {
+    ClickedRefresh,
+    LoadedRecent(Result<Vec<ReactionContext>, String>),
+    ClickedToggleGood(ReactionContextUuid, bool),
+    ToggledGood(Result<(ReactionContextUuid, bool), String>),
+    ExportPathChanged(String),
+    ClickedExport,
+    Exported(Result<usize, String>),
+}
+
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct Storage {
+    #[serde(default)]
+    export_path_field: String,
+}
+
+impl Model {
+    pub fn new(storage: &Storage) -> Self {
+        let export_path_field = if storage.export_path_field.trim().is_empty() {
+            DEFAULT_EXPORT_PATH.to_string()
+        } else {
+            storage.export_path_field.clone()
+        };
+
+        Self {
+            export_path_field,
+            reaction_contexts: ReactionContextsStatus::Loading,
+            export_status: ExportStatus::Ready,
+        }
+    }
+
+    pub fn to_storage(&self) -> Storage {
+        Storage {
+            export_path_field: self.export_path_field.clone(),
+        }
+    }
+
+    pub fn on_tab_activated(&mut self, worker: Arc<Worker>) -> Task<Msg> {
+        self.reaction_contexts = ReactionContextsStatus::Loading;
+        Task::perform(get_recent_reaction_contexts(worker), Msg::LoadedRecent)
+    }
+
+    pub fn update(&mut self, worker: Arc<Worker>, msg: Msg) -> Task<Msg> {
+        match msg {
+            Msg::ClickedRefresh => self.on_tab_activated(worker),
+            Msg::LoadedRecent(result) => {
+                self.reaction_contexts = match result {
+                    Ok(reaction_contexts) => ReactionContextsStatus::Loaded(reaction_contexts),
+                    Err(err) => ReactionContextsStatus::Error(err),
+                };
+                Task::none()
+            }
+            Msg::ClickedToggleGood(reaction_context_uuid, is_good) => Task::perform(
+                set_reaction_context_good(worker, reaction_context_uuid, is_good),
+                Msg::ToggledGood,
+            ),
+            Msg::ToggledGood(result) => {
+                match result {
+                    Ok((reaction_context_uuid, is_good)) => {
+                        if let ReactionContextsStatus::Loaded(reaction_contexts) =
+                            &mut self.reaction_contexts
+                        {
+                            for reaction_context in reaction_contexts.iter_mut() {
+                                if reaction_context.uuid == reaction_context_uuid {
+                                    reaction_context.is_good = is_good;
+                                }
+                            }
+                        }
+                    }
+                    Err(err) => {
+                        self.reaction_contexts = ReactionContextsStatus::Error(err);
+                    }
+                }
+                Task::none()
+            }
+            Msg::ExportPathChanged(path) => {
+                self.export_path_field = path;
+                Task::none()
+            }
+            Msg::ClickedExport => {
+                self.export_status = ExportStatus::Exporting;
+                let output_path = self.export_path_field.trim().to_string();
+
+                Task::perform(
+                    async move {
+                        export_training_data::export(worker.as_ref(), output_path.as_str())
+                            .await
+                            .map_err(|err| err.message())
+                    },
+                    Msg::Exported,
+                )
+            }
+            Msg::Exported(result) => {
+                self.export_status = match result {
+                    Ok(count) => ExportStatus::Done(count),
+                    Err(err) => ExportStatus::Error(err),
+                };
+                Task::none()
+            }
+        }
+    }
+
+    pub fn view(&self) -> Element<'_, Msg> {
+        let export_row = w::row![
+            w::text("Export path"),
+            w::text_input("", &self.export_path_field).on_input(Msg::ExportPathChanged),
+            w::button("Export good exchanges").on_press(Msg::ClickedExport),
+            export_status_view(&self.export_status),
+        ]
+        .spacing(s::S4);
+
+        w::column![
+            w::text("Training Data").size(20),
+            export_row,
+            w::button("Refresh").on_press(Msg::ClickedRefresh),
+            w::horizontal_rule(1),
+            reaction_contexts_view(&self.reaction_contexts),
+        ]
+        .spacing(s::S4)
+        .into()
+    }
+}
+
+fn export_status_view(status: &ExportStatus) -> Element<'_, Msg> {
+    match status {
+        ExportStatus::Ready => w::text("").into(),
+        ExportStatus::Exporting => w::text("Exporting...").into(),
+        ExportStatus::Done(count) => w::text(format!("Exported {} examples", count)).into(),
+        ExportStatus::Error(err) => w::text(format!("Error: {}", err)).into(),
+    }
+}
+
+fn reaction_contexts_view(status: &ReactionContextsStatus) -> Element<'_, Msg> {
+    match status {
+        ReactionContextsStatus::Loading => w::text("Loading reactions...").into(),
+        ReactionContextsStatus::Error(err) => w::text(format!("Error: {}", err)).into(),
+        ReactionContextsStatus::Loaded(reaction_contexts) => {
+            if reaction_contexts.is_empty() {
+                return w::text("No reactions recorded yet").into();
+            }
+
+            let mut col = w::column![].spacing(s::S4);
+
+            for reaction_context in reaction_contexts {
+                let toggle_button = if reaction_context.is_good {
+                    w::button("Unmark good")
+                        .on_press(Msg::ClickedToggleGood(reaction_context.uuid.clone(), false))
+                } else {
+                    w::button("Mark good")
+                        .on_press(Msg::ClickedToggleGood(reaction_context.uuid.clone(), true))
+                };
+
+                let label_color = if reaction_context.is_good {
+                    s::GREEN_SOFT
+                } else {
+                    s::GRAY_MID
+                };
+
+                col = col.push(
+                    w::column![
+                        w::row![
+                            w::text(format!(
+                                "{} at {}",
+                                reaction_context.person_name,
+                                time_display::format_absolute(reaction_context.created_at)
+                            ))
+                            .color(label_color),
+                            toggle_button,
+                        ]
+                        .spacing(s::S4),
+                        w::text(reaction_context.assistant_response.as_str()).size(s::S3),
+                        w::horizontal_rule(1),
+                    ]
+                    .spacing(s::S2),
+                );
+            }
+
+            col.into()
+        }
+    }
+}
+
+async fn get_recent_reaction_contexts(worker: Arc<Worker>) -> Result<Vec<ReactionContext>, String> {
+    worker
+        .get_recent_reaction_contexts(REACTION_CONTEXT_PAGE_SIZE)
+        .await
+}
+
+async fn set_reaction_context_good(
+    worker: Arc<Worker>,
+    reaction_context_uuid: ReactionContextUuid,
+    is_good: bool,
+) -> Result<(ReactionContextUuid, bool), String> {
+    worker
+        .set_reaction_context_good(&reaction_context_uuid, is_good)
+        .await?;
+    Ok((reaction_context_uuid, is_good))
+}
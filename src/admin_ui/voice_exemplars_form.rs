@@ -0,0 +1,89 @@
+use crate::admin_ui::s;
+use crate::capability::voice_exemplar::VoiceExemplarCapability;
+use crate::domain::person_uuid::PersonUuid;
+use crate::domain::voice_exemplar;
+use crate::worker::Worker;
+use iced::{widget as w, Element, Task};
+use std::sync::Arc;
+
+/// Edits the lines a person's reactions are shown as examples of their voice.
+pub struct Model {
+    person_uuid: PersonUuid,
+    exemplars: w::text_editor::Content,
+    status: Status,
+}
+
+enum Status {
+    Ready,
+    Saving,
+    Saved,
+    Error(String),
+}
+
+#[derive(Debug, Clone)]
+pub enum Msg {
+    ExemplarsUpdated(w::text_editor::Action),
+    ClickedSave,
+    Saved(Result<(), String>),
+}
+
+impl Model {
+    pub fn new(person_uuid: PersonUuid, exemplars: &[String]) -> Self {
+        Self {
+            person_uuid,
+            exemplars: w::text_editor::Content::with_text(&exemplars.join("\n")),
+            status: Status::Ready,
+        }
+    }
+
+    pub fn update(&mut self, worker: Arc<Worker>, msg: Msg) -> Task<Msg> {
+        match msg {
+            Msg::ExemplarsUpdated(action) => {
+                self.exemplars.perform(action);
+                self.status = Status::Ready;
+                Task::none()
+            }
+            Msg::ClickedSave => {
+                let exemplars = match voice_exemplar::parse_exemplars(&self.exemplars.text()) {
+                    Ok(exemplars) => exemplars,
+                    Err(err) => {
+                        self.status = Status::Error(err);
+                        return Task::none();
+                    }
+                };
+
+                self.status = Status::Saving;
+                let person_uuid = self.person_uuid.clone();
+                Task::perform(
+                    async move { worker.set_voice_exemplars(&person_uuid, &exemplars).await },
+                    Msg::Saved,
+                )
+            }
+            Msg::Saved(result) => {
+                self.status = match result {
+                    Ok(()) => Status::Saved,
+                    Err(err) => Status::Error(err),
+                };
+                Task::none()
+            }
+        }
+    }
+
+    pub fn view(&self) -> Element<'_, Msg> {
+        let status_view: Element<'_, Msg> = match &self.status {
+            Status::Ready => w::text("").into(),
+            Status::Saving => w::text("Saving...").into(),
+            Status::Saved => w::text("Saved").color(s::GREEN_SOFT).into(),
+            Status::Error(err) => w::text(format!("Error: {}", err)).color(s::RED_SOFT).into(),
+        };
+
+        w::column![
+            w::text_editor(&self.exemplars)
+                .on_action(Msg::ExemplarsUpdated)
+                .height(iced::Length::Fixed(120.0)),
+            w::row![w::button("Save").on_press(Msg::ClickedSave), status_view].spacing(s::S4),
+        ]
+        .spacing(s::S1)
+        .into()
+    }
+}
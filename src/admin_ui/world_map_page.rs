@@ -0,0 +1,244 @@
+use crate::admin_ui::{canvas_layout, s};
+use crate::capability::world_map::WorldMapCapability;
+use crate::domain::world_map::{MapScene, WorldMap};
+use crate::worker::Worker;
+use iced::widget::canvas::{self, Canvas, Frame, Geometry, Path, Stroke, Text};
+use iced::{
+    alignment, mouse, time, widget as w, Element, Length, Pixels, Point, Rectangle, Renderer,
+    Subscription, Task, Theme,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+const MAP_HEIGHT: f32 = 560.0;
+const NODE_RADIUS: f32 = 28.0;
+/// Room left around the ring for the scene name labels.
+const MAP_MARGIN: f32 = 72.0;
+
+pub struct Model {
+    status: Status,
+    auto_refresh: bool,
+    cache: canvas::Cache,
+}
+
+enum Status {
+    Loading,
+    Loaded(WorldMap),
+    Error(String),
+}
+
+#[derive(Debug, Clone)]
+pub enum Msg {
+    ClickedRefresh,
+    AutoRefreshToggled(bool),
+    AutoRefreshTick,
+    Loaded(Result<WorldMap, String>),
+}
+
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct Storage {}
+
+impl Model {
+    pub fn new(_storage: &Storage) -> Self {
+        Self {
+            status: Status::Loading,
+            auto_refresh: true,
+            cache: canvas::Cache::new(),
+        }
+    }
+
+    pub fn to_storage(&self) -> Storage {
+        Storage {}
+    }
+
+    pub fn on_tab_activated(&mut self, worker: Arc<Worker>) -> Task<Msg> {
+        load(worker)
+    }
+
+    pub fn update(&mut self, worker: Arc<Worker>, msg: Msg) -> Task<Msg> {
+        match msg {
+            Msg::ClickedRefresh | Msg::AutoRefreshTick => load(worker),
+            Msg::AutoRefreshToggled(auto_refresh) => {
+                self.auto_refresh = auto_refresh;
+                Task::none()
+            }
+            Msg::Loaded(result) => {
+                self.status = match result {
+                    Ok(world_map) => Status::Loaded(world_map),
+                    Err(err) => Status::Error(err),
+                };
+                self.cache.clear();
+                Task::none()
+            }
+        }
+    }
+
+    pub fn subscription(&self) -> Subscription<Msg> {
+        if self.auto_refresh {
+            time::every(std::time::Duration::from_secs(2)).map(|_| Msg::AutoRefreshTick)
+        } else {
+            Subscription::none()
+        }
+    }
+
+    pub fn view(&self) -> Element<'_, Msg> {
+        let controls = w::row![
+            w::button("Refresh").on_press(Msg::ClickedRefresh),
+            w::checkbox("Auto refresh", self.auto_refresh).on_toggle(Msg::AutoRefreshToggled),
+        ]
+        .spacing(s::S4);
+
+        let body: Element<Msg> = match &self.status {
+            Status::Loading => w::text("Loading world map...").into(),
+            Status::Error(err) => w::text(format!("Error: {}", err)).color(s::RED_SOFT).into(),
+            Status::Loaded(world_map) => {
+                if world_map.scenes.is_empty() {
+                    w::text("There are no active scenes").into()
+                } else {
+                    let map = Canvas::new(MapCanvas {
+                        world_map,
+                        cache: &self.cache,
+                    })
+                    .width(Length::Fill)
+                    .height(Length::Fixed(MAP_HEIGHT));
+
+                    w::column![map, view_scene_list(&world_map.scenes)]
+                        .spacing(s::S4)
+                        .into()
+                }
+            }
+        };
+
+        w::column![w::text("World Map").size(20), controls, body]
+            .spacing(s::S4)
+            .into()
+    }
+}
+
+fn load(worker: Arc<Worker>) -> Task<Msg> {
+    Task::perform(async move { worker.get_world_map().await }, Msg::Loaded)
+}
+
+fn view_scene_list(scenes: &[MapScene]) -> Element<'_, Msg> {
+    let mut col = w::column![].spacing(s::S2);
+
+    for scene in scenes {
+        let mut names = scene
+            .people
+            .iter()
+            .map(|name| name.as_str().to_string())
+            .collect::<Vec<String>>();
+
+        if scene.real_world_user_present {
+            names.push("you".to_string());
+        }
+
+        let people = if names.is_empty() {
+            "empty".to_string()
+        } else {
+            names.join(", ")
+        };
+
+        col = col.push(w::text(format!(
+            "{} ({}): {}",
+            scene.name,
+            scene.head_count(),
+            people
+        )));
+    }
+
+    col.into()
+}
+
+struct MapCanvas<'a> {
+    world_map: &'a WorldMap,
+    cache: &'a canvas::Cache,
+}
+
+impl<Msg> canvas::Program<Msg> for MapCanvas<'_> {
+    type State = ();
+
+    fn draw(
+        &self,
+        _state: &Self::State,
+        renderer: &Renderer,
+        _theme: &Theme,
+        bounds: Rectangle,
+        _cursor: mouse::Cursor,
+    ) -> Vec<Geometry> {
+        let geometry = self.cache.draw(renderer, bounds.size(), |frame| {
+            draw_map(frame, self.world_map);
+        });
+
+        vec![geometry]
+    }
+}
+
+fn draw_map(frame: &mut Frame, world_map: &WorldMap) {
+    let center = frame.center();
+    let ring_radius = (frame.width().min(frame.height()) / 2.0 - MAP_MARGIN).max(0.0);
+    let scene_count = world_map.scenes.len();
+
+    let positions = world_map
+        .scenes
+        .iter()
+        .map(|scene| scene.scene_uuid.to_uuid())
+        .zip(canvas_layout::ring_positions(
+            scene_count,
+            center,
+            ring_radius,
+        ))
+        .collect::<HashMap<uuid::Uuid, Point>>();
+
+    for edge in &world_map.edges {
+        if let (Some(a), Some(b)) = (positions.get(&edge.scene_a), positions.get(&edge.scene_b)) {
+            let width = 1.0 + (edge.trips as f32).ln_1p() * 1.5;
+            frame.stroke(
+                &Path::line(*a, *b),
+                Stroke::default().with_width(width).with_color(s::GRAY_DEEP),
+            );
+        }
+    }
+
+    for scene in &world_map.scenes {
+        let position = match positions.get(&scene.scene_uuid.to_uuid()) {
+            Some(position) => *position,
+            None => continue,
+        };
+
+        let head_count = scene.head_count();
+        let fill = if head_count == 0 {
+            s::GRAY_DEEP
+        } else {
+            s::GOLD_SOFT
+        };
+
+        let node = Path::circle(position, NODE_RADIUS);
+        frame.fill(&node, fill);
+        frame.stroke(
+            &node,
+            Stroke::default().with_width(2.0).with_color(s::GRAY_SOFT),
+        );
+
+        frame.fill_text(Text {
+            content: head_count.to_string(),
+            position,
+            color: s::GRAY_VERY_DEEP,
+            size: Pixels(18.0),
+            horizontal_alignment: alignment::Horizontal::Center,
+            vertical_alignment: alignment::Vertical::Center,
+            ..Text::default()
+        });
+
+        frame.fill_text(Text {
+            content: scene.name.clone(),
+            position: Point::new(position.x, position.y + NODE_RADIUS + s::S2),
+            color: s::GRAY_VERY_SOFT,
+            size: Pixels(14.0),
+            horizontal_alignment: alignment::Horizontal::Center,
+            vertical_alignment: alignment::Vertical::Top,
+            ..Text::default()
+        });
+    }
+}
@@ -0,0 +1,356 @@
+mod docs;
+mod events;
+mod jobs;
+mod persons;
+mod scene_actions;
+mod scene_timeline;
+
+use crate::capability::tenant::TenantCapability;
+use crate::domain::job_uuid::JobUuid;
+use crate::domain::logger::{Level, Logger};
+use crate::domain::person_uuid::PersonUuid;
+use crate::domain::rate_limit::{self, RateLimiter, RateLimits, RateScope};
+use crate::domain::scene_uuid::SceneUuid;
+use crate::domain::tenant::{self, ApiAccess, ApiRole, ApiTokenGrant};
+use crate::domain::tenant_uuid::TenantUuid;
+use crate::nice_display::{self, nest, with_context, ErrorCode, NiceDisplay};
+use crate::worker;
+use crate::worker::{ProcessRole, Worker};
+use actix_web::{web, App, HttpRequest, HttpResponse, HttpServer};
+use serde_json::json;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+pub enum Error {
+    WorkerInit(worker::InitError),
+    RateLimits(String),
+    Bind {
+        address: String,
+        details: std::io::Error,
+    },
+    Serve(std::io::Error),
+}
+
+/// Why a request could not be answered. Every error response has a body
+/// like `{"error": {"code": "scene not found", "message": "..."}}`.
+pub enum RequestError {
+    /// No token, or one that is unknown or revoked.
+    Unauthorized,
+    /// The token's role is below what the request needs, or it is a tenant
+    /// token where an operator token is needed, or the other way around.
+    Forbidden {
+        required: ApiAccess,
+    },
+    /// The token used up its requests for now.
+    RateLimited {
+        retry_after: Duration,
+    },
+    UnsupportedFormat(String),
+    /// A WebSocket path was requested without the upgrade headers.
+    WebSocketRequired,
+    SceneNotFound(SceneUuid),
+    /// A job to enqueue that the job registry cannot read.
+    InvalidJob(String),
+    JobNotFound(JobUuid),
+    /// A request body that is not json or is missing something it needs.
+    InvalidBody(String),
+    /// No person with that uuid is in the scene.
+    PersonNotFound(PersonUuid),
+    Internal(String),
+}
+
+impl NiceDisplay for Error {
+    fn message(&self) -> String {
+        match self {
+            Error::WorkerInit(err) => nest("Worker initialization error", err),
+            Error::RateLimits(err) => with_context("Invalid api rate limits", err),
+            Error::Bind { address, details } => {
+                with_context(format!("Could not listen on {}", address), details)
+            }
+            Error::Serve(err) => with_context("The api server stopped unexpectedly", err),
+        }
+    }
+}
+
+impl NiceDisplay for RequestError {
+    fn message(&self) -> String {
+        match self {
+            RequestError::Unauthorized => {
+                "Send a valid api token as \"Authorization: Bearer <token>\"".to_string()
+            }
+            RequestError::Forbidden { required } => match required {
+                ApiAccess::Tenant(role) => format!(
+                    "This request needs a tenant token with the {} role or above",
+                    role.to_name()
+                ),
+                ApiAccess::Operator => "This request needs an operator token".to_string(),
+            },
+            RequestError::RateLimited { retry_after } => format!(
+                "Too many requests, try again in {} seconds",
+                rate_limit::retry_after_secs(*retry_after)
+            ),
+            RequestError::UnsupportedFormat(format) => {
+                format!("Unsupported format \"{}\", only json is available", format)
+            }
+            RequestError::WebSocketRequired => {
+                "This path only answers WebSocket upgrade requests".to_string()
+            }
+            RequestError::SceneNotFound(scene_uuid) => {
+                format!("No scene with uuid {}", scene_uuid.to_uuid())
+            }
+            RequestError::InvalidJob(details) => with_context("Invalid job", details),
+            RequestError::JobNotFound(job_uuid) => {
+                format!("No job with uuid {}", job_uuid.to_uuid())
+            }
+            RequestError::InvalidBody(details) => with_context("Invalid request body", details),
+            RequestError::PersonNotFound(person_uuid) => {
+                format!("No person with uuid {} in the scene", person_uuid.to_uuid())
+            }
+            RequestError::Internal(details) => with_context("Internal error", details),
+        }
+    }
+}
+
+impl ErrorCode for RequestError {
+    fn code(&self) -> &'static str {
+        match self {
+            RequestError::Unauthorized => "unauthorized",
+            RequestError::Forbidden { .. } => "forbidden",
+            RequestError::RateLimited { .. } => "rate limited",
+            RequestError::UnsupportedFormat(_) => "unsupported format",
+            RequestError::WebSocketRequired => "websocket required",
+            RequestError::SceneNotFound(_) => "scene not found",
+            RequestError::InvalidJob(_) => "invalid job",
+            RequestError::JobNotFound(_) => "job not found",
+            RequestError::InvalidBody(_) => "invalid body",
+            RequestError::PersonNotFound(_) => "person not found",
+            RequestError::Internal(_) => "internal",
+        }
+    }
+}
+
+impl RequestError {
+    pub fn to_response(&self) -> HttpResponse {
+        let mut response = match self {
+            RequestError::Unauthorized => HttpResponse::Unauthorized(),
+            RequestError::Forbidden { .. } => HttpResponse::Forbidden(),
+            RequestError::RateLimited { retry_after } => {
+                let mut response = HttpResponse::TooManyRequests();
+                response.insert_header((
+                    "Retry-After",
+                    rate_limit::retry_after_secs(*retry_after).to_string(),
+                ));
+                response
+            }
+            RequestError::UnsupportedFormat(_) => HttpResponse::BadRequest(),
+            RequestError::WebSocketRequired => HttpResponse::BadRequest(),
+            RequestError::SceneNotFound(_) => HttpResponse::NotFound(),
+            RequestError::InvalidJob(_) => HttpResponse::BadRequest(),
+            RequestError::JobNotFound(_) => HttpResponse::NotFound(),
+            RequestError::InvalidBody(_) => HttpResponse::BadRequest(),
+            RequestError::PersonNotFound(_) => HttpResponse::NotFound(),
+            RequestError::Internal(_) => HttpResponse::InternalServerError(),
+        };
+
+        response.json(json!({ "error": nice_display::to_json(self) }))
+    }
+}
+
+/// The tenant whose api token the request carries, as long as the token's
+/// role is at least `required` and it has not run out of requests.
+pub async fn authenticate(
+    worker: &Worker,
+    limiter: &Mutex<RateLimiter>,
+    request: &HttpRequest,
+    required: ApiRole,
+) -> Result<TenantUuid, RequestError> {
+    let (grant, token_hash) = get_grant(worker, request).await?;
+    let tenant_uuid = tenant_access(grant, required)?;
+
+    check_rate_limit(limiter, request, &token_hash)?;
+
+    Ok(tenant_uuid)
+}
+
+/// The tenant a token may act for on a request that needs `required`.
+/// Operator tokens belong to no tenant, so they never may.
+fn tenant_access(grant: ApiTokenGrant, required: ApiRole) -> Result<TenantUuid, RequestError> {
+    match grant {
+        ApiTokenGrant::Tenant { tenant_uuid, role } if role.allows(required) => Ok(tenant_uuid),
+        ApiTokenGrant::Tenant { .. } | ApiTokenGrant::Operator => Err(RequestError::Forbidden {
+            required: ApiAccess::Tenant(required),
+        }),
+    }
+}
+
+/// Scenes outside the tenant are reported as not found, the same as scenes
+/// that do not exist.
+async fn check_scene_in_tenant(
+    worker: &Worker,
+    scene_uuid: &SceneUuid,
+    tenant_uuid: &TenantUuid,
+) -> Result<(), RequestError> {
+    match worker.is_scene_in_tenant(scene_uuid, tenant_uuid).await {
+        Ok(true) => Ok(()),
+        Ok(false) => Err(RequestError::SceneNotFound(scene_uuid.clone())),
+        Err(err) => {
+            tracing::error!("Error looking up scene for its tenant: {}", err);
+            Err(RequestError::Internal(err))
+        }
+    }
+}
+
+/// Passes requests carrying an operator token that has not run out of
+/// requests. Tenant tokens are refused whatever their role.
+pub async fn authenticate_operator(
+    worker: &Worker,
+    limiter: &Mutex<RateLimiter>,
+    request: &HttpRequest,
+) -> Result<(), RequestError> {
+    let (grant, token_hash) = get_grant(worker, request).await?;
+
+    match grant {
+        ApiTokenGrant::Operator => {}
+        ApiTokenGrant::Tenant { .. } => {
+            return Err(RequestError::Forbidden {
+                required: ApiAccess::Operator,
+            })
+        }
+    }
+
+    check_rate_limit(limiter, request, &token_hash)
+}
+
+/// The grant of the bearer token the request carries, and the token's hash.
+async fn get_grant(
+    worker: &Worker,
+    request: &HttpRequest,
+) -> Result<(ApiTokenGrant, String), RequestError> {
+    let token = request
+        .headers()
+        .get("Authorization")
+        .and_then(|header| header.to_str().ok())
+        .and_then(tenant::bearer_token)
+        .ok_or(RequestError::Unauthorized)?;
+
+    let token_hash = tenant::hash_token(token);
+
+    match worker.get_api_token_grant(&token_hash).await {
+        Ok(Some(grant)) => Ok((grant, token_hash)),
+        Ok(None) => Err(RequestError::Unauthorized),
+        Err(err) => {
+            tracing::error!("Error looking up api token: {}", err);
+            Err(RequestError::Internal(err))
+        }
+    }
+}
+
+// Only known tokens get a bucket, so made up tokens cannot grow the limiter
+fn check_rate_limit(
+    limiter: &Mutex<RateLimiter>,
+    request: &HttpRequest,
+    token_hash: &str,
+) -> Result<(), RequestError> {
+    let scope = RateScope::from_method(request.method().as_str());
+    let checked = match limiter.lock() {
+        Ok(mut limiter) => limiter.check(token_hash, scope, Instant::now()),
+        Err(err) => return Err(RequestError::Internal(err.to_string())),
+    };
+
+    checked.map_err(|retry_after| RequestError::RateLimited { retry_after })
+}
+
+pub async fn run(host: String, port: u16) -> Result<(), Error> {
+    let logger = Logger::init(Level::Info).log_to_file();
+    let worker = Worker::new(logger, ProcessRole::Server)
+        .await
+        .map_err(Error::WorkerInit)?;
+    let worker = web::Data::new(worker);
+    let limits = RateLimits::load().map_err(Error::RateLimits)?;
+    let limiter = web::Data::new(Mutex::new(RateLimiter::new(limits)));
+    let address = format!("{}:{}", host, port);
+
+    let server = HttpServer::new(move || {
+        App::new()
+            .app_data(worker.clone())
+            .app_data(limiter.clone())
+            .service(docs::get_docs)
+            .service(events::get_events)
+            .service(jobs::post_job)
+            .service(jobs::get_job)
+            .service(persons::post_person)
+            .service(persons::delete_person)
+            .service(scene_actions::post_scene_message)
+            .service(scene_actions::post_scene_event)
+            .service(scene_timeline::get_scene_timeline)
+    })
+    .bind((host.as_str(), port))
+    .map_err(|details| Error::Bind {
+        address: address.clone(),
+        details,
+    })?;
+
+    tracing::info!("Api listening on {}", address);
+
+    server.run().await.map_err(Error::Serve)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::http::StatusCode;
+
+    fn grant(role: ApiRole) -> ApiTokenGrant {
+        ApiTokenGrant::Tenant {
+            tenant_uuid: TenantUuid::new(),
+            role,
+        }
+    }
+
+    fn status(result: Result<TenantUuid, RequestError>) -> StatusCode {
+        match result {
+            Ok(_) => StatusCode::OK,
+            Err(err) => err.to_response().status(),
+        }
+    }
+
+    #[test]
+    fn test_viewers_are_forbidden_from_steering_scenes_and_managing_persons() {
+        for required in [scene_actions::REQUIRED_ROLE, persons::REQUIRED_ROLE] {
+            assert_eq!(
+                status(tenant_access(grant(ApiRole::Viewer), required)),
+                StatusCode::FORBIDDEN
+            );
+        }
+    }
+
+    #[test]
+    fn test_directors_steer_scenes_but_only_admins_manage_persons() {
+        assert_eq!(
+            status(tenant_access(
+                grant(ApiRole::Director),
+                scene_actions::REQUIRED_ROLE
+            )),
+            StatusCode::OK
+        );
+        assert_eq!(
+            status(tenant_access(
+                grant(ApiRole::Director),
+                persons::REQUIRED_ROLE
+            )),
+            StatusCode::FORBIDDEN
+        );
+        assert_eq!(
+            status(tenant_access(grant(ApiRole::Admin), persons::REQUIRED_ROLE)),
+            StatusCode::OK
+        );
+    }
+
+    #[test]
+    fn test_operator_tokens_read_no_tenant() {
+        assert_eq!(
+            status(tenant_access(ApiTokenGrant::Operator, ApiRole::Viewer)),
+            StatusCode::FORBIDDEN
+        );
+    }
+}
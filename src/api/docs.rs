@@ -0,0 +1,630 @@
+use super::RequestError;
+use crate::domain::job::registry;
+use crate::domain::job_uuid::JobUuid;
+use crate::domain::person_uuid::PersonUuid;
+use crate::domain::scene_timeline::{self, SCHEMA_VERSION};
+use crate::domain::scene_uuid::SceneUuid;
+use crate::domain::tenant::{ApiAccess, ApiRole};
+use crate::nice_display::ErrorCode;
+use actix_web::{get, HttpResponse};
+use serde_json::{json, Value};
+use std::time::Duration;
+
+/// `GET /api/docs`
+///
+/// The OpenAPI document for the whole api, so clients can be generated
+/// from it. It needs no token.
+#[get("/api/docs")]
+pub async fn get_docs() -> HttpResponse {
+    HttpResponse::Ok().json(open_api())
+}
+
+/// One of every request error, so the documented codes come from the same
+/// `code()` the responses use.
+fn error_examples() -> Vec<RequestError> {
+    vec![
+        RequestError::Unauthorized,
+        RequestError::Forbidden {
+            required: ApiAccess::Tenant(ApiRole::Viewer),
+        },
+        RequestError::RateLimited {
+            retry_after: Duration::from_secs(1),
+        },
+        RequestError::UnsupportedFormat("xml".to_string()),
+        RequestError::WebSocketRequired,
+        RequestError::SceneNotFound(SceneUuid::new()),
+        RequestError::InvalidJob(String::new()),
+        RequestError::JobNotFound(JobUuid::new()),
+        RequestError::InvalidBody(String::new()),
+        RequestError::PersonNotFound(PersonUuid::new()),
+        RequestError::Internal(String::new()),
+    ]
+}
+
+pub fn open_api() -> Value {
+    let error_codes = error_examples()
+        .iter()
+        .map(|error| error.code())
+        .collect::<Vec<&str>>();
+    let job_names = registry::REGISTRY
+        .iter()
+        .map(|registration| registration.name)
+        .collect::<Vec<&str>>();
+
+    json!({
+        "openapi": "3.0.3",
+        "info": {
+            "title": "Arizona2 api",
+            "version": env!("CARGO_PKG_VERSION"),
+            "description": "Read access to scenes for external renderers, steering them for directors, managing their persons for admins, and a job queue for orchestration scripts. Every path except this document needs an api token, see `cargo run -- tenant issue-token`, or `tenant issue-operator-token` for the job queue.",
+        },
+        "components": {
+            "securitySchemes": {
+                "bearer": { "type": "http", "scheme": "bearer" },
+            },
+            "responses": {
+                "Unauthorized": error_response("No token, or one that is unknown or revoked (`unauthorized`)."),
+                "Forbidden": error_response("The token's role is too low for this request, or it is a tenant token where an operator token is needed or the other way around (`forbidden`)."),
+                "RateLimited": {
+                    "description": "The token used up its requests for now (`rate limited`).",
+                    "headers": {
+                        "Retry-After": {
+                            "description": "Seconds to wait before trying again.",
+                            "schema": { "type": "integer" },
+                        },
+                    },
+                    "content": {
+                        "application/json": {
+                            "schema": { "$ref": "#/components/schemas/ErrorResponse" },
+                        },
+                    },
+                },
+                "Internal": error_response("Something went wrong on the server (`internal`)."),
+            },
+            "schemas": {
+                "ErrorResponse": {
+                    "type": "object",
+                    "required": ["error"],
+                    "properties": {
+                        "error": {
+                            "type": "object",
+                            "required": ["code", "message"],
+                            "properties": {
+                                "code": {
+                                    "type": "string",
+                                    "enum": error_codes,
+                                    "description": "Stable between releases, branch on this.",
+                                },
+                                "message": {
+                                    "type": "string",
+                                    "description": "For people, may change at any time.",
+                                },
+                            },
+                        },
+                    },
+                },
+                "EnqueueJob": {
+                    "type": "object",
+                    "required": ["name"],
+                    "properties": {
+                        "name": { "type": "string", "enum": job_names },
+                        "data": {
+                            "type": "object",
+                            "description": "The job's fields, the same as it is stored with in the `job` table. Jobs without fields leave it out.",
+                        },
+                    },
+                },
+                "JobReport": {
+                    "type": "object",
+                    "required": ["job_uuid", "name", "status"],
+                    "properties": {
+                        "job_uuid": { "type": "string", "format": "uuid" },
+                        "name": { "type": "string" },
+                        "status": {
+                            "type": "string",
+                            "enum": ["not started", "in progress", "finished", "failed"],
+                        },
+                        "started_at": { "type": "string", "format": "date-time", "nullable": true },
+                        "finished_at": { "type": "string", "format": "date-time", "nullable": true },
+                        "error": { "type": "string", "nullable": true },
+                    },
+                },
+                "SceneMessage": {
+                    "type": "object",
+                    "required": ["content"],
+                    "properties": {
+                        "content": { "type": "string", "description": "Said by the real world user." },
+                    },
+                },
+                "SceneEvent": {
+                    "type": "object",
+                    "required": ["description"],
+                    "properties": {
+                        "description": { "type": "string", "example": "A phone starts ringing." },
+                    },
+                },
+                "NewPerson": {
+                    "type": "object",
+                    "required": ["name", "identity", "state_of_mind"],
+                    "properties": {
+                        "name": { "type": "string" },
+                        "identity": { "type": "string", "description": "Who they are, in their own words." },
+                        "state_of_mind": { "type": "string" },
+                    },
+                },
+                "NewPersonReport": {
+                    "type": "object",
+                    "required": ["person_uuid"],
+                    "properties": {
+                        "person_uuid": { "type": "string", "format": "uuid" },
+                    },
+                },
+                "TimelinePage": {
+                    "type": "object",
+                    "required": ["schema_version", "scene_uuid", "items"],
+                    "properties": {
+                        "schema_version": { "type": "integer", "example": SCHEMA_VERSION },
+                        "scene_uuid": { "type": "string", "format": "uuid" },
+                        "items": {
+                            "type": "array",
+                            "description": "Oldest first.",
+                            "items": { "$ref": "#/components/schemas/TimelineItem" },
+                        },
+                        "next_before": {
+                            "type": "string",
+                            "format": "date-time",
+                            "nullable": true,
+                            "description": "Pass as `before` to get the page before this one. Missing on the first page of the scene.",
+                        },
+                    },
+                },
+                "TimelineItem": {
+                    "oneOf": [
+                        { "$ref": "#/components/schemas/MessageItem" },
+                        { "$ref": "#/components/schemas/JoinedItem" },
+                        { "$ref": "#/components/schemas/LeftItem" },
+                        { "$ref": "#/components/schemas/ArrivalSummaryItem" },
+                        { "$ref": "#/components/schemas/SceneSnapshotItem" },
+                        { "$ref": "#/components/schemas/AmbienceChangedItem" },
+                        { "$ref": "#/components/schemas/SceneEventItem" },
+                    ],
+                    "discriminator": {
+                        "propertyName": "type",
+                        "mapping": {
+                            "message": "#/components/schemas/MessageItem",
+                            "joined": "#/components/schemas/JoinedItem",
+                            "left": "#/components/schemas/LeftItem",
+                            "arrival_summary": "#/components/schemas/ArrivalSummaryItem",
+                            "scene_snapshot": "#/components/schemas/SceneSnapshotItem",
+                            "ambience_changed": "#/components/schemas/AmbienceChangedItem",
+                            "scene_event": "#/components/schemas/SceneEventItem",
+                        },
+                    },
+                },
+                "MessageItem": timeline_item("message", json!({
+                    "message_uuid": { "type": "string", "format": "uuid" },
+                    "sender": { "$ref": "#/components/schemas/TimelineSpeaker" },
+                    "content": { "type": "string" },
+                    "audience": {
+                        "type": "string",
+                        "description": "\"everyone\", \"whisper\" or \"side conversation\"",
+                    },
+                })),
+                "JoinedItem": timeline_item("joined", person_properties()),
+                "LeftItem": timeline_item("left", person_properties()),
+                "ArrivalSummaryItem": timeline_item("arrival_summary", {
+                    let mut properties = person_properties();
+                    properties["summary"] = json!({ "type": "string" });
+                    properties
+                }),
+                "SceneSnapshotItem": timeline_item("scene_snapshot", json!({
+                    "description": { "type": "string" },
+                })),
+                "AmbienceChangedItem": timeline_item("ambience_changed", json!({
+                    "ambience": { "$ref": "#/components/schemas/Ambience" },
+                })),
+                "SceneEventItem": timeline_item("scene_event", json!({
+                    "description": { "type": "string" },
+                })),
+                "Ambience": {
+                    "type": "object",
+                    "required": ["weather", "noise", "lighting"],
+                    "properties": {
+                        "weather": {
+                            "type": "string",
+                            "enum": ["clear", "cloudy", "rain", "storm", "snow", "fog"],
+                        },
+                        "noise": {
+                            "type": "string",
+                            "enum": ["silent", "quiet", "lively", "loud"],
+                        },
+                        "lighting": {
+                            "type": "string",
+                            "enum": ["bright", "dim", "dark"],
+                        },
+                    },
+                },
+                "TimelineSpeaker": {
+                    "type": "object",
+                    "required": ["name"],
+                    "properties": {
+                        "person_uuid": {
+                            "type": "string",
+                            "format": "uuid",
+                            "nullable": true,
+                            "description": "Missing when the real world user said it.",
+                        },
+                        "name": { "type": "string" },
+                    },
+                },
+            },
+        },
+        "security": [{ "bearer": [] }],
+        "paths": {
+            "/api/docs": {
+                "get": {
+                    "summary": "This document",
+                    "security": [],
+                    "responses": {
+                        "200": { "description": "The OpenAPI document." },
+                    },
+                },
+            },
+            "/api/events": {
+                "get": {
+                    "summary": "A WebSocket of new events, filtered by subscription",
+                    "description": "Needs a viewer token. Nothing is sent until the client sends `{\"type\": \"subscribe\", \"scenes\": [...], \"persons\": [...], \"events\": [...]}`; missing or empty lists match anything, and each subscribe replaces the last. The server answers `{\"type\": \"subscribed\", \"filter\": ...}`, then sends matching events as `{\"type\": \"event\", \"id\", \"event\", \"created_at\", \"data\"}`, the same shape the outbox webhook gets. `{\"type\": \"unsubscribe\"}` stops them. Unreadable messages are answered with `{\"type\": \"error\", \"error\": {\"code\": \"invalid message\", ...}}`. Events about other tenants' scenes are never sent.",
+                    "responses": {
+                        "101": { "description": "Switched to the WebSocket protocol." },
+                        "400": error_response("The request is not a WebSocket upgrade (`websocket required`)."),
+                        "401": { "$ref": "#/components/responses/Unauthorized" },
+                        "403": { "$ref": "#/components/responses/Forbidden" },
+                        "429": { "$ref": "#/components/responses/RateLimited" },
+                        "500": { "$ref": "#/components/responses/Internal" },
+                    },
+                },
+            },
+            "/api/jobs": {
+                "post": {
+                    "summary": "Queue a job for the job runner",
+                    "description": "Needs an operator token, since jobs can reach any tenant's scenes and persons. The body is checked against the same job registry the runner reads the queue with.",
+                    "requestBody": {
+                        "required": true,
+                        "content": {
+                            "application/json": {
+                                "schema": { "$ref": "#/components/schemas/EnqueueJob" },
+                            },
+                        },
+                    },
+                    "responses": {
+                        "202": {
+                            "description": "The job is queued. Poll `/api/jobs/{job_uuid}` for how it went.",
+                            "content": {
+                                "application/json": {
+                                    "schema": { "$ref": "#/components/schemas/JobReport" },
+                                },
+                            },
+                        },
+                        "400": error_response("The body is not a job the registry knows (`invalid job`)."),
+                        "401": { "$ref": "#/components/responses/Unauthorized" },
+                        "403": { "$ref": "#/components/responses/Forbidden" },
+                        "429": { "$ref": "#/components/responses/RateLimited" },
+                        "500": { "$ref": "#/components/responses/Internal" },
+                    },
+                },
+            },
+            "/api/jobs/{job_uuid}": {
+                "get": {
+                    "summary": "Where a job is at",
+                    "description": "Needs an operator token.",
+                    "parameters": [
+                        {
+                            "name": "job_uuid",
+                            "in": "path",
+                            "required": true,
+                            "schema": { "type": "string", "format": "uuid" },
+                        },
+                    ],
+                    "responses": {
+                        "200": {
+                            "description": "The job's status.",
+                            "content": {
+                                "application/json": {
+                                    "schema": { "$ref": "#/components/schemas/JobReport" },
+                                },
+                            },
+                        },
+                        "401": { "$ref": "#/components/responses/Unauthorized" },
+                        "403": { "$ref": "#/components/responses/Forbidden" },
+                        "404": error_response("No job with that uuid, or it was deleted (`job not found`)."),
+                        "429": { "$ref": "#/components/responses/RateLimited" },
+                        "500": { "$ref": "#/components/responses/Internal" },
+                    },
+                },
+            },
+            "/api/scenes/{scene_uuid}/messages": {
+                "post": {
+                    "summary": "Say something in a scene as the real world user",
+                    "description": "Needs a director token. The message is sent by the job runner, so the response is the job that sends it.",
+                    "parameters": [scene_uuid_parameter()],
+                    "requestBody": json_body("SceneMessage"),
+                    "responses": scene_write_responses(json!({
+                        "202": {
+                            "description": "The message is queued to be sent.",
+                            "content": {
+                                "application/json": {
+                                    "schema": { "$ref": "#/components/schemas/JobReport" },
+                                },
+                            },
+                        },
+                    })),
+                },
+            },
+            "/api/scenes/{scene_uuid}/events": {
+                "post": {
+                    "summary": "Make something happen in a scene",
+                    "description": "Needs a director token. Everyone in the scene gets a job to react.",
+                    "parameters": [scene_uuid_parameter()],
+                    "requestBody": json_body("SceneEvent"),
+                    "responses": scene_write_responses(json!({
+                        "204": { "description": "The event is in the scene's timeline." },
+                    })),
+                },
+            },
+            "/api/scenes/{scene_uuid}/persons": {
+                "post": {
+                    "summary": "Make a person and put them in a scene",
+                    "description": "Needs an admin token.",
+                    "parameters": [scene_uuid_parameter()],
+                    "requestBody": json_body("NewPerson"),
+                    "responses": scene_write_responses(json!({
+                        "201": {
+                            "description": "The person is in the scene.",
+                            "content": {
+                                "application/json": {
+                                    "schema": { "$ref": "#/components/schemas/NewPersonReport" },
+                                },
+                            },
+                        },
+                    })),
+                },
+            },
+            "/api/scenes/{scene_uuid}/persons/{person_uuid}": {
+                "delete": {
+                    "summary": "Archive a person who is in a scene",
+                    "description": "Needs an admin token. Persons are shared by the world, so only those in one of the tenant's scenes can be archived.",
+                    "parameters": [
+                        scene_uuid_parameter(),
+                        {
+                            "name": "person_uuid",
+                            "in": "path",
+                            "required": true,
+                            "schema": { "type": "string", "format": "uuid" },
+                        },
+                    ],
+                    "responses": {
+                        "204": { "description": "The person is archived and has left the scene." },
+                        "401": { "$ref": "#/components/responses/Unauthorized" },
+                        "403": { "$ref": "#/components/responses/Forbidden" },
+                        "404": error_response("No scene with that uuid in the token's tenant (`scene not found`), or the person is not in it (`person not found`)."),
+                        "429": { "$ref": "#/components/responses/RateLimited" },
+                        "500": { "$ref": "#/components/responses/Internal" },
+                    },
+                },
+            },
+            "/api/scenes/{scene_uuid}/timeline": {
+                "get": {
+                    "summary": "What happened in a scene, a page at a time",
+                    "description": "Needs a viewer token. Scenes outside the token's tenant answer `scene not found`.",
+                    "parameters": [
+                        {
+                            "name": "scene_uuid",
+                            "in": "path",
+                            "required": true,
+                            "schema": { "type": "string", "format": "uuid" },
+                        },
+                        {
+                            "name": "format",
+                            "in": "query",
+                            "schema": { "type": "string", "enum": ["json"], "default": "json" },
+                        },
+                        {
+                            "name": "before",
+                            "in": "query",
+                            "description": "Only items strictly before this time.",
+                            "schema": { "type": "string", "format": "date-time" },
+                        },
+                        {
+                            "name": "limit",
+                            "in": "query",
+                            "schema": {
+                                "type": "integer",
+                                "minimum": 1,
+                                "maximum": scene_timeline::MAX_PAGE_SIZE,
+                                "default": scene_timeline::DEFAULT_PAGE_SIZE,
+                            },
+                        },
+                    ],
+                    "responses": {
+                        "200": {
+                            "description": "A page of the timeline.",
+                            "content": {
+                                "application/json": {
+                                    "schema": { "$ref": "#/components/schemas/TimelinePage" },
+                                },
+                            },
+                        },
+                        "400": error_response("The format is not json (`unsupported format`)."),
+                        "401": { "$ref": "#/components/responses/Unauthorized" },
+                        "403": { "$ref": "#/components/responses/Forbidden" },
+                        "404": error_response("No scene with that uuid in the token's tenant (`scene not found`)."),
+                        "429": { "$ref": "#/components/responses/RateLimited" },
+                        "500": { "$ref": "#/components/responses/Internal" },
+                    },
+                },
+            },
+        },
+    })
+}
+
+fn error_response(description: &str) -> Value {
+    json!({
+        "description": description,
+        "content": {
+            "application/json": {
+                "schema": { "$ref": "#/components/schemas/ErrorResponse" },
+            },
+        },
+    })
+}
+
+fn scene_uuid_parameter() -> Value {
+    json!({
+        "name": "scene_uuid",
+        "in": "path",
+        "required": true,
+        "schema": { "type": "string", "format": "uuid" },
+    })
+}
+
+fn json_body(schema_name: &str) -> Value {
+    json!({
+        "required": true,
+        "content": {
+            "application/json": {
+                "schema": { "$ref": format!("#/components/schemas/{}", schema_name) },
+            },
+        },
+    })
+}
+
+/// What every request that changes one of the tenant's scenes can answer,
+/// besides `success`.
+fn scene_write_responses(success: Value) -> Value {
+    let mut responses = json!({
+        "400": error_response("The body is not json or is missing a field (`invalid body`)."),
+        "401": { "$ref": "#/components/responses/Unauthorized" },
+        "403": { "$ref": "#/components/responses/Forbidden" },
+        "404": error_response("No scene with that uuid in the token's tenant (`scene not found`)."),
+        "429": { "$ref": "#/components/responses/RateLimited" },
+        "500": { "$ref": "#/components/responses/Internal" },
+    });
+
+    if let (Some(all), Some(success)) = (responses.as_object_mut(), success.as_object()) {
+        for (status, response) in success {
+            all.insert(status.clone(), response.clone());
+        }
+    }
+
+    responses
+}
+
+fn person_properties() -> Value {
+    json!({
+        "person_uuid": { "type": "string", "format": "uuid" },
+        "person_name": { "type": "string" },
+    })
+}
+
+fn timeline_item(type_tag: &str, properties: Value) -> Value {
+    let mut all_properties = json!({
+        "type": { "type": "string", "enum": [type_tag] },
+        "at": { "type": "string", "format": "date-time" },
+    });
+
+    let mut required = vec!["type".to_string(), "at".to_string()];
+
+    if let (Some(all), Some(extra)) = (all_properties.as_object_mut(), properties.as_object()) {
+        for (name, schema) in extra {
+            let is_nullable = schema.get("nullable") == Some(&Value::Bool(true));
+            if !is_nullable {
+                required.push(name.clone());
+            }
+            all.insert(name.clone(), schema.clone());
+        }
+    }
+
+    json!({
+        "type": "object",
+        "required": required,
+        "properties": all_properties,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::scene_ambience::Ambience;
+    use crate::domain::scene_timeline::{TimelineItem, TimelineSpeaker};
+    use chrono::Utc;
+    use uuid::Uuid;
+
+    #[test]
+    fn test_documented_item_fields_match_serialized_items() {
+        let spec = open_api();
+        let at = Utc::now();
+        let items = vec![
+            TimelineItem::Message {
+                message_uuid: Uuid::now_v7(),
+                at,
+                sender: TimelineSpeaker {
+                    person_uuid: None,
+                    name: "Chadtech".to_string(),
+                },
+                content: "Hi".to_string(),
+                audience: "everyone".to_string(),
+            },
+            TimelineItem::ArrivalSummary {
+                at,
+                person_uuid: Uuid::now_v7(),
+                person_name: "Dolores".to_string(),
+                summary: "Busy".to_string(),
+            },
+            TimelineItem::SceneSnapshot {
+                at,
+                description: "A cafe".to_string(),
+            },
+            TimelineItem::AmbienceChanged {
+                at,
+                ambience: Ambience::default(),
+            },
+            TimelineItem::SceneEvent {
+                at,
+                description: "A car alarm goes off outside.".to_string(),
+            },
+        ];
+
+        for item in items {
+            let value = serde_json::to_value(&item).unwrap();
+            let type_tag = value["type"].as_str().unwrap();
+            let schema_ref = spec["components"]["schemas"]["TimelineItem"]["discriminator"]
+                ["mapping"][type_tag]
+                .as_str()
+                .unwrap();
+            let schema_name = schema_ref.trim_start_matches("#/components/schemas/");
+            let properties = &spec["components"]["schemas"][schema_name]["properties"];
+
+            for field in value.as_object().unwrap().keys() {
+                assert!(
+                    properties.get(field).is_some(),
+                    "{} is missing {}",
+                    schema_name,
+                    field
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_every_error_code_is_documented() {
+        let spec = open_api();
+        let codes = &spec["components"]["schemas"]["ErrorResponse"]["properties"]["error"]
+            ["properties"]["code"]["enum"];
+
+        assert_eq!(codes.as_array().map(|codes| codes.len()), Some(11));
+        assert!(codes
+            .as_array()
+            .unwrap()
+            .contains(&json!("scene not found")));
+    }
+}
@@ -0,0 +1,176 @@
+use super::RequestError;
+use crate::capability::event_stream::EventStreamCapability;
+use crate::domain::event_subscription::{
+    self, ClientMessage, EventFilter, POLL_INTERVAL, POLL_LIMIT,
+};
+use crate::domain::outbox_uuid::OutboxUuid;
+use crate::domain::rate_limit::RateLimiter;
+use crate::domain::tenant::ApiRole;
+use crate::domain::tenant_uuid::TenantUuid;
+use crate::nice_display::{self, with_context, ErrorCode, NiceDisplay};
+use crate::worker::Worker;
+use actix_web::{get, web, HttpRequest, HttpResponse};
+use actix_ws::{Closed, Message, MessageStream, Session};
+use serde_json::{json, Value};
+use std::sync::{Arc, Mutex};
+
+/// Sent on the stream as `{"type": "error", "error": {"code": ..., "message": ...}}`.
+/// The stream stays open after them.
+enum StreamError {
+    InvalidMessage(String),
+}
+
+impl NiceDisplay for StreamError {
+    fn message(&self) -> String {
+        match self {
+            StreamError::InvalidMessage(details) => {
+                with_context("Could not read that subscription message", details)
+            }
+        }
+    }
+}
+
+impl ErrorCode for StreamError {
+    fn code(&self) -> &'static str {
+        match self {
+            StreamError::InvalidMessage(_) => "invalid message",
+        }
+    }
+}
+
+/// `GET /api/events`, upgraded to a WebSocket.
+///
+/// Nothing is sent until the client subscribes. From then on it gets every
+/// new outbox event that passes its filter, leaving out events about other
+/// tenants' scenes.
+#[get("/api/events")]
+pub async fn get_events(
+    worker: web::Data<Worker>,
+    limiter: web::Data<Mutex<RateLimiter>>,
+    request: HttpRequest,
+    body: web::Payload,
+) -> HttpResponse {
+    let tenant_uuid = match super::authenticate(&worker, &limiter, &request, ApiRole::Viewer).await
+    {
+        Ok(tenant_uuid) => tenant_uuid,
+        Err(err) => return err.to_response(),
+    };
+
+    let (response, session, messages) = match actix_ws::handle(&request, body) {
+        Ok(handled) => handled,
+        Err(_) => return RequestError::WebSocketRequired.to_response(),
+    };
+
+    actix_web::rt::spawn(stream_events(
+        worker.into_inner(),
+        tenant_uuid,
+        session,
+        messages,
+    ));
+
+    response
+}
+
+struct Subscription {
+    filter: EventFilter,
+    /// The last outbox entry this stream has looked at.
+    cursor: OutboxUuid,
+}
+
+async fn stream_events(
+    worker: Arc<Worker>,
+    tenant_uuid: TenantUuid,
+    mut session: Session,
+    mut messages: MessageStream,
+) {
+    let mut subscription: Option<Subscription> = None;
+    let mut poll = tokio::time::interval(POLL_INTERVAL);
+
+    loop {
+        let sent = tokio::select! {
+            _ = poll.tick() => match subscription.as_mut() {
+                Some(subscription) => {
+                    send_new_events(&worker, &tenant_uuid, subscription, &mut session).await
+                }
+                None => Ok(()),
+            },
+            message = messages.recv() => match message {
+                Some(Ok(Message::Text(text))) => {
+                    let reply = answer(&text, &mut subscription);
+                    send(&mut session, reply).await
+                }
+                Some(Ok(Message::Ping(bytes))) => session.pong(&bytes).await,
+                Some(Ok(Message::Close(reason))) => {
+                    let _ = session.close(reason).await;
+                    return;
+                }
+                Some(Ok(_)) => Ok(()),
+                None | Some(Err(_)) => return,
+            },
+        };
+
+        if sent.is_err() {
+            return;
+        }
+    }
+}
+
+/// Applies a client message and returns the reply for it.
+fn answer(text: &str, subscription: &mut Option<Subscription>) -> Value {
+    match event_subscription::parse_client_message(text) {
+        Ok(ClientMessage::Subscribe { filter }) => {
+            let reply = event_subscription::subscribed_message(&filter);
+            match subscription {
+                // Changing the filter does not skip or repeat events
+                Some(subscription) => subscription.filter = filter,
+                None => {
+                    *subscription = Some(Subscription {
+                        filter,
+                        cursor: OutboxUuid::new(),
+                    })
+                }
+            }
+            reply
+        }
+        Ok(ClientMessage::Unsubscribe) => {
+            *subscription = None;
+            event_subscription::unsubscribed_message()
+        }
+        Err(err) => json!({
+            "type": "error",
+            "error": nice_display::to_json(&StreamError::InvalidMessage(err)),
+        }),
+    }
+}
+
+async fn send_new_events(
+    worker: &Worker,
+    tenant_uuid: &TenantUuid,
+    subscription: &mut Subscription,
+    session: &mut Session,
+) -> Result<(), Closed> {
+    let entries = match worker
+        .get_events_after(tenant_uuid, &subscription.cursor, POLL_LIMIT)
+        .await
+    {
+        Ok(entries) => entries,
+        Err(err) => {
+            // Try again next poll, the client has nothing to do about it
+            tracing::error!("Error getting events for event stream: {}", err);
+            return Ok(());
+        }
+    };
+
+    for entry in entries {
+        subscription.cursor = entry.uuid.clone();
+        if subscription.filter.matches(&entry) {
+            send(session, event_subscription::event_message(&entry)).await?;
+        }
+    }
+
+    Ok(())
+}
+
+async fn send(session: &mut Session, message: Value) -> Result<(), Closed> {
+    session.text(message.to_string()).await
+}
@@ -0,0 +1,146 @@
+use super::RequestError;
+use crate::capability::job::JobCapability;
+use crate::domain::job::{Job, JobKind};
+use crate::domain::job_uuid::JobUuid;
+use crate::domain::rate_limit::RateLimiter;
+use crate::nice_display::NiceDisplay;
+use crate::worker::Worker;
+use actix_web::{get, post, web, HttpRequest, HttpResponse};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+use uuid::Uuid;
+
+/// The body of `POST /api/jobs`: a job's registry name and the same data it
+/// is stored with.
+#[derive(Debug, Deserialize)]
+pub struct EnqueueJobRequest {
+    name: String,
+    data: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct JobReport {
+    pub job_uuid: Uuid,
+    pub name: String,
+    /// "not started", "in progress", "finished" or "failed"
+    pub status: String,
+    pub started_at: Option<DateTime<Utc>>,
+    pub finished_at: Option<DateTime<Utc>>,
+    pub error: Option<String>,
+}
+
+impl EnqueueJobRequest {
+    /// Reads the body with the same registry the job runner reads the queue
+    /// with, so anything accepted here can be run.
+    pub fn parse(body: &[u8]) -> Result<JobKind, RequestError> {
+        let request: EnqueueJobRequest = serde_json::from_slice(body)
+            .map_err(|err| RequestError::InvalidJob(err.to_string()))?;
+
+        JobKind::parse(request.name, request.data)
+            .map_err(|err| RequestError::InvalidJob(err.message()))
+    }
+}
+
+impl JobReport {
+    pub fn from_job(job: &Job) -> Self {
+        JobReport {
+            job_uuid: job.uuid().to_uuid(),
+            name: job.kind_label(),
+            status: job.status().to_name().to_string(),
+            started_at: job.started_at(),
+            finished_at: job.finished_at(),
+            error: job.error().cloned(),
+        }
+    }
+}
+
+/// `POST /api/jobs`
+///
+/// Jobs can reach any scene and any person, whichever tenant they are in, so
+/// this needs an operator token rather than a tenant's.
+#[post("/api/jobs")]
+pub async fn post_job(
+    worker: web::Data<Worker>,
+    limiter: web::Data<Mutex<RateLimiter>>,
+    request: HttpRequest,
+    body: web::Bytes,
+) -> HttpResponse {
+    if let Err(err) = super::authenticate_operator(&worker, &limiter, &request).await {
+        return err.to_response();
+    }
+
+    let job = match EnqueueJobRequest::parse(&body) {
+        Ok(job) => job,
+        Err(err) => return err.to_response(),
+    };
+
+    let name = job.to_name();
+
+    match worker.enqueue_job(job).await {
+        Ok(job_uuid) => HttpResponse::Accepted().json(JobReport {
+            job_uuid: job_uuid.to_uuid(),
+            name,
+            status: "not started".to_string(),
+            started_at: None,
+            finished_at: None,
+            error: None,
+        }),
+        Err(err) => {
+            tracing::error!("Error enqueueing a job from the api: {}", err);
+            RequestError::Internal(err).to_response()
+        }
+    }
+}
+
+/// `GET /api/jobs/{job_uuid}`
+///
+/// Jobs belong to no tenant, so this needs an operator token too.
+#[get("/api/jobs/{job_uuid}")]
+pub async fn get_job(
+    worker: web::Data<Worker>,
+    limiter: web::Data<Mutex<RateLimiter>>,
+    request: HttpRequest,
+    path: web::Path<Uuid>,
+) -> HttpResponse {
+    if let Err(err) = super::authenticate_operator(&worker, &limiter, &request).await {
+        return err.to_response();
+    }
+
+    let job_uuid = JobUuid::from_uuid(path.into_inner());
+
+    match worker.get_job_by_uuid(&job_uuid).await {
+        Ok(Some(job)) => HttpResponse::Ok().json(JobReport::from_job(&job)),
+        Ok(None) => RequestError::JobNotFound(job_uuid).to_response(),
+        Err(err) => {
+            tracing::error!("Error getting a job for the api: {}", err);
+            RequestError::Internal(err).to_response()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::nice_display::ErrorCode;
+
+    #[test]
+    fn test_enqueue_requests_are_checked_against_the_registry() {
+        let job = EnqueueJobRequest::parse(br#"{"name": "check scene goals"}"#);
+        match job {
+            Ok(JobKind::CheckSceneGoals) => {}
+            Ok(_) | Err(_) => panic!("expected the check scene goals job"),
+        }
+
+        let unknown = EnqueueJobRequest::parse(br#"{"name": "make coffee"}"#);
+        match &unknown {
+            Err(err) => assert_eq!(err.code(), "invalid job"),
+            Ok(_) => panic!("expected an unknown job to be rejected"),
+        }
+
+        let missing_data = EnqueueJobRequest::parse(br#"{"name": "close scene"}"#);
+        assert!(missing_data.is_err());
+
+        assert!(EnqueueJobRequest::parse(b"not json").is_err());
+    }
+}
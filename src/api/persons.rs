@@ -0,0 +1,179 @@
+use super::RequestError;
+use crate::capability::person::PersonCapability;
+use crate::capability::scene::SceneCapability;
+use crate::domain::person_name::PersonName;
+use crate::domain::person_uuid::PersonUuid;
+use crate::domain::persona::{self, Persona};
+use crate::domain::rate_limit::RateLimiter;
+use crate::domain::scene_uuid::SceneUuid;
+use crate::domain::tenant::ApiRole;
+use crate::nice_display::NiceDisplay;
+use crate::worker::Worker;
+use actix_web::{delete, post, web, HttpRequest, HttpResponse};
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+use uuid::Uuid;
+
+/// Making and removing persons is for admins.
+pub const REQUIRED_ROLE: ApiRole = ApiRole::Admin;
+
+/// The body of `POST /api/scenes/{scene_uuid}/persons`.
+#[derive(Debug, Deserialize)]
+pub struct NewPersonRequest {
+    name: String,
+    identity: String,
+    state_of_mind: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct NewPersonReport {
+    pub person_uuid: Uuid,
+}
+
+impl NewPersonRequest {
+    pub fn parse(body: &[u8]) -> Result<Persona, RequestError> {
+        let request: NewPersonRequest = serde_json::from_slice(body)
+            .map_err(|err| RequestError::InvalidBody(err.to_string()))?;
+
+        for (field, value) in [
+            ("name", &request.name),
+            ("identity", &request.identity),
+            ("state_of_mind", &request.state_of_mind),
+        ] {
+            if value.trim().is_empty() {
+                return Err(RequestError::InvalidBody(format!(
+                    "{} cannot be blank",
+                    field
+                )));
+            }
+        }
+
+        Ok(Persona {
+            name: PersonName::from_string(request.name.trim().to_string()),
+            identity: request.identity.trim().to_string(),
+            state_of_mind: request.state_of_mind.trim().to_string(),
+            memories: Vec::new(),
+        })
+    }
+}
+
+/// `POST /api/scenes/{scene_uuid}/persons`
+///
+/// Makes a person, the same way the admin ui does, and puts them in the
+/// scene.
+#[post("/api/scenes/{scene_uuid}/persons")]
+pub async fn post_person(
+    worker: web::Data<Worker>,
+    limiter: web::Data<Mutex<RateLimiter>>,
+    request: HttpRequest,
+    path: web::Path<Uuid>,
+    body: web::Bytes,
+) -> HttpResponse {
+    let tenant_uuid = match super::authenticate(&worker, &limiter, &request, REQUIRED_ROLE).await {
+        Ok(tenant_uuid) => tenant_uuid,
+        Err(err) => return err.to_response(),
+    };
+
+    let scene_uuid = SceneUuid::from_uuid(path.into_inner());
+
+    if let Err(err) = super::check_scene_in_tenant(&worker, &scene_uuid, &tenant_uuid).await {
+        return err.to_response();
+    }
+
+    let persona = match NewPersonRequest::parse(&body) {
+        Ok(persona) => persona,
+        Err(err) => return err.to_response(),
+    };
+    let person_name = persona.name.clone();
+
+    let person_uuid = match persona::create_persona(worker.get_ref(), persona).await {
+        Ok(person_uuid) => person_uuid,
+        Err(err) => {
+            tracing::error!("Error creating a person from the api: {}", err.message());
+            return RequestError::Internal(err.message()).to_response();
+        }
+    };
+
+    match worker.add_person_to_scene(scene_uuid, person_name).await {
+        Ok(_) => HttpResponse::Created().json(NewPersonReport {
+            person_uuid: person_uuid.to_uuid(),
+        }),
+        Err(err) => {
+            tracing::error!("Error adding a person from the api to a scene: {}", err);
+            RequestError::Internal(err).to_response()
+        }
+    }
+}
+
+/// `DELETE /api/scenes/{scene_uuid}/persons/{person_uuid}`
+///
+/// Archives a person who is in the scene. Persons are shared by the world,
+/// so only those in one of the tenant's scenes can be reached.
+#[delete("/api/scenes/{scene_uuid}/persons/{person_uuid}")]
+pub async fn delete_person(
+    worker: web::Data<Worker>,
+    limiter: web::Data<Mutex<RateLimiter>>,
+    request: HttpRequest,
+    path: web::Path<(Uuid, Uuid)>,
+) -> HttpResponse {
+    let tenant_uuid = match super::authenticate(&worker, &limiter, &request, REQUIRED_ROLE).await {
+        Ok(tenant_uuid) => tenant_uuid,
+        Err(err) => return err.to_response(),
+    };
+
+    let (scene_uuid, person_uuid) = path.into_inner();
+    let scene_uuid = SceneUuid::from_uuid(scene_uuid);
+    let person_uuid = PersonUuid::from_uuid(person_uuid);
+
+    if let Err(err) = super::check_scene_in_tenant(&worker, &scene_uuid, &tenant_uuid).await {
+        return err.to_response();
+    }
+
+    match worker.get_persons_current_scene_uuid(&person_uuid).await {
+        Ok(Some(current_scene_uuid)) if current_scene_uuid == scene_uuid => {}
+        Ok(_) => return RequestError::PersonNotFound(person_uuid).to_response(),
+        Err(err) => {
+            tracing::error!("Error looking up a person's scene for the api: {}", err);
+            return RequestError::Internal(err).to_response();
+        }
+    }
+
+    match worker.archive_person(&person_uuid).await {
+        Ok(()) => HttpResponse::NoContent().finish(),
+        Err(err) => {
+            tracing::error!("Error archiving a person from the api: {}", err);
+            RequestError::Internal(err).to_response()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::nice_display::ErrorCode;
+
+    #[test]
+    fn test_new_person_requests_need_every_field() {
+        let persona = NewPersonRequest::parse(
+            br#"{"name": " Dolores ", "identity": "A retired pilot.", "state_of_mind": "Calm."}"#,
+        );
+        match persona {
+            Ok(persona) => {
+                assert_eq!(persona.name.as_str(), "Dolores");
+                assert!(persona.memories.is_empty());
+            }
+            Err(_) => panic!("expected the person to be read"),
+        }
+
+        let blank = NewPersonRequest::parse(
+            br#"{"name": "Dolores", "identity": " ", "state_of_mind": "Calm."}"#,
+        );
+        match &blank {
+            Err(err) => assert_eq!(err.code(), "invalid body"),
+            Ok(_) => panic!("expected a blank identity to be rejected"),
+        }
+
+        let missing = NewPersonRequest::parse(br#"{"name": "Dolores"}"#);
+        assert!(missing.is_err());
+    }
+}
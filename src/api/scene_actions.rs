@@ -0,0 +1,176 @@
+use super::jobs::JobReport;
+use super::RequestError;
+use crate::capability::job::JobCapability;
+use crate::domain::job::inject_scene_events;
+use crate::domain::job::send_message_to_scene::SendMessageToSceneJob;
+use crate::domain::job::JobKind;
+use crate::domain::message::MessageSender;
+use crate::domain::rate_limit::RateLimiter;
+use crate::domain::scene_uuid::SceneUuid;
+use crate::domain::tenant::ApiRole;
+use crate::nice_display::NiceDisplay;
+use crate::worker::Worker;
+use actix_web::{post, web, HttpRequest, HttpResponse};
+use serde::Deserialize;
+use std::sync::Mutex;
+use uuid::Uuid;
+
+/// Steering a scene, by chatting in it or making something happen in it,
+/// is for directors.
+pub const REQUIRED_ROLE: ApiRole = ApiRole::Director;
+
+/// The body of `POST /api/scenes/{scene_uuid}/messages`.
+#[derive(Debug, Deserialize)]
+pub struct SceneMessageRequest {
+    content: String,
+}
+
+/// The body of `POST /api/scenes/{scene_uuid}/events`.
+#[derive(Debug, Deserialize)]
+pub struct SceneEventRequest {
+    description: String,
+}
+
+/// Reads a json body with one text field, which cannot be blank.
+fn parse_body<T: serde::de::DeserializeOwned>(
+    body: &[u8],
+    text: impl Fn(&T) -> &str,
+) -> Result<T, RequestError> {
+    let request: T =
+        serde_json::from_slice(body).map_err(|err| RequestError::InvalidBody(err.to_string()))?;
+
+    if text(&request).trim().is_empty() {
+        return Err(RequestError::InvalidBody(
+            "The text cannot be blank".to_string(),
+        ));
+    }
+
+    Ok(request)
+}
+
+/// `POST /api/scenes/{scene_uuid}/messages`
+///
+/// Says something in the scene as the real world user. The message is sent
+/// by the job runner, like one typed into the admin ui.
+#[post("/api/scenes/{scene_uuid}/messages")]
+pub async fn post_scene_message(
+    worker: web::Data<Worker>,
+    limiter: web::Data<Mutex<RateLimiter>>,
+    request: HttpRequest,
+    path: web::Path<Uuid>,
+    body: web::Bytes,
+) -> HttpResponse {
+    let tenant_uuid = match super::authenticate(&worker, &limiter, &request, REQUIRED_ROLE).await {
+        Ok(tenant_uuid) => tenant_uuid,
+        Err(err) => return err.to_response(),
+    };
+
+    let scene_uuid = SceneUuid::from_uuid(path.into_inner());
+
+    if let Err(err) = super::check_scene_in_tenant(&worker, &scene_uuid, &tenant_uuid).await {
+        return err.to_response();
+    }
+
+    let message = match parse_body(&body, |message: &SceneMessageRequest| &message.content) {
+        Ok(message) => message,
+        Err(err) => return err.to_response(),
+    };
+
+    let random_seed = match worker.get_random_seed() {
+        Ok(random_seed) => random_seed,
+        Err(err) => return RequestError::Internal(err).to_response(),
+    };
+
+    let job = JobKind::SendMessageToScene(SendMessageToSceneJob {
+        sender: MessageSender::RealWorldUser,
+        scene_uuid,
+        content: message.content.trim().to_string(),
+        random_seed,
+    });
+    let name = job.to_name();
+
+    match worker.enqueue_job(job).await {
+        Ok(job_uuid) => HttpResponse::Accepted().json(JobReport {
+            job_uuid: job_uuid.to_uuid(),
+            name,
+            status: "not started".to_string(),
+            started_at: None,
+            finished_at: None,
+            error: None,
+        }),
+        Err(err) => {
+            tracing::error!("Error enqueueing a scene message from the api: {}", err);
+            RequestError::Internal(err).to_response()
+        }
+    }
+}
+
+/// `POST /api/scenes/{scene_uuid}/events`
+///
+/// Something happens in the scene, like a phone ringing, and everyone in it
+/// gets a job to react.
+#[post("/api/scenes/{scene_uuid}/events")]
+pub async fn post_scene_event(
+    worker: web::Data<Worker>,
+    limiter: web::Data<Mutex<RateLimiter>>,
+    request: HttpRequest,
+    path: web::Path<Uuid>,
+    body: web::Bytes,
+) -> HttpResponse {
+    let tenant_uuid = match super::authenticate(&worker, &limiter, &request, REQUIRED_ROLE).await {
+        Ok(tenant_uuid) => tenant_uuid,
+        Err(err) => return err.to_response(),
+    };
+
+    let scene_uuid = SceneUuid::from_uuid(path.into_inner());
+
+    if let Err(err) = super::check_scene_in_tenant(&worker, &scene_uuid, &tenant_uuid).await {
+        return err.to_response();
+    }
+
+    let event = match parse_body(&body, |event: &SceneEventRequest| &event.description) {
+        Ok(event) => event,
+        Err(err) => return err.to_response(),
+    };
+
+    match inject_scene_events::inject(worker.get_ref(), &scene_uuid, event.description.trim()).await
+    {
+        Ok(()) => HttpResponse::NoContent().finish(),
+        Err(err) => {
+            tracing::error!(
+                "Error injecting a scene event from the api: {}",
+                err.message()
+            );
+            RequestError::Internal(err.message()).to_response()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::nice_display::ErrorCode;
+
+    #[test]
+    fn test_bodies_need_their_text() {
+        let message = parse_body(
+            br#"{"content": " hi "}"#,
+            |message: &SceneMessageRequest| &message.content,
+        );
+        match message {
+            Ok(message) => assert_eq!(message.content, " hi "),
+            Err(_) => panic!("expected the message to be read"),
+        }
+
+        let blank = parse_body(br#"{"description": "  "}"#, |event: &SceneEventRequest| {
+            &event.description
+        });
+        match &blank {
+            Err(err) => assert_eq!(err.code(), "invalid body"),
+            Ok(_) => panic!("expected a blank event to be rejected"),
+        }
+
+        let missing = parse_body(br#"{}"#, |event: &SceneEventRequest| &event.description);
+        assert!(missing.is_err());
+    }
+}
@@ -0,0 +1,67 @@
+use super::RequestError;
+use crate::capability::scene_timeline::SceneTimelineCapability;
+use crate::capability::tenant::TenantCapability;
+use crate::domain::rate_limit::RateLimiter;
+use crate::domain::scene_timeline::TimelineQuery;
+use crate::domain::scene_uuid::SceneUuid;
+use crate::domain::tenant::ApiRole;
+use crate::worker::Worker;
+use actix_web::{get, web, HttpRequest, HttpResponse};
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use std::sync::Mutex;
+use uuid::Uuid;
+
+#[derive(Debug, Deserialize)]
+pub struct TimelineParams {
+    /// Only `json` is supported, and it is the default.
+    format: Option<String>,
+    /// RFC 3339 time. Pass the previous page's `next_before` to page backwards.
+    before: Option<DateTime<Utc>>,
+    limit: Option<i64>,
+}
+
+/// `GET /api/scenes/{scene_uuid}/timeline?format=json&before=...&limit=...`
+///
+/// Only scenes that belong to the token's tenant are served, anything else
+/// is reported as not found.
+#[get("/api/scenes/{scene_uuid}/timeline")]
+pub async fn get_scene_timeline(
+    worker: web::Data<Worker>,
+    limiter: web::Data<Mutex<RateLimiter>>,
+    request: HttpRequest,
+    path: web::Path<Uuid>,
+    params: web::Query<TimelineParams>,
+) -> HttpResponse {
+    let tenant_uuid = match super::authenticate(&worker, &limiter, &request, ApiRole::Viewer).await
+    {
+        Ok(tenant_uuid) => tenant_uuid,
+        Err(err) => return err.to_response(),
+    };
+
+    match params.format.as_deref() {
+        None | Some("json") => {}
+        Some(format) => return RequestError::UnsupportedFormat(format.to_string()).to_response(),
+    }
+
+    let scene_uuid = SceneUuid::from_uuid(path.into_inner());
+
+    match worker.is_scene_in_tenant(&scene_uuid, &tenant_uuid).await {
+        Ok(true) => {}
+        Ok(false) => return RequestError::SceneNotFound(scene_uuid).to_response(),
+        Err(err) => {
+            tracing::error!("Error looking up scene for timeline: {}", err);
+            return RequestError::Internal(err).to_response();
+        }
+    }
+
+    let query = TimelineQuery::new(params.before, params.limit);
+
+    match worker.get_scene_timeline(&scene_uuid, query).await {
+        Ok(page) => HttpResponse::Ok().json(page),
+        Err(err) => {
+            tracing::error!("Error getting scene timeline: {}", err);
+            RequestError::Internal(err).to_response()
+        }
+    }
+}
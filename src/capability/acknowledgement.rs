@@ -0,0 +1,22 @@
+use crate::domain::person_uuid::PersonUuid;
+
+pub trait AcknowledgementCapability {
+    /// Removes and returns the oldest acknowledgement the person has ready.
+    async fn take_acknowledgement(
+        &self,
+        person_uuid: &PersonUuid,
+    ) -> Result<Option<String>, String>;
+    async fn count_acknowledgements(&self, person_uuid: &PersonUuid) -> Result<usize, String>;
+    async fn add_acknowledgements(
+        &self,
+        person_uuid: &PersonUuid,
+        acknowledgements: &[String],
+    ) -> Result<(), String>;
+    /// Asks the model for `count` acknowledgements in the person's voice, one
+    /// a line.
+    async fn generate_acknowledgements(
+        &self,
+        person_uuid: &PersonUuid,
+        count: usize,
+    ) -> Result<String, String>;
+}
@@ -0,0 +1,14 @@
+use crate::domain::action_budget::ActionBudget;
+use crate::domain::person_uuid::PersonUuid;
+
+pub trait ActionBudgetCapability {
+    async fn get_action_budgets(&self) -> Result<Vec<ActionBudget>, String>;
+
+    /// How many of the reactions a person recorded in the current world-day
+    /// were one of the given kinds.
+    async fn count_reactions_in_world_day(
+        &self,
+        person_uuid: &PersonUuid,
+        reaction_kinds: Vec<String>,
+    ) -> Result<i64, String>;
+}
@@ -0,0 +1,19 @@
+use crate::domain::annotation::{Annotation, NewAnnotation};
+use crate::domain::annotation_uuid::AnnotationUuid;
+use crate::domain::scene_uuid::SceneUuid;
+
+pub trait AnnotationCapability {
+    async fn add_annotation(&self, annotation: &NewAnnotation) -> Result<AnnotationUuid, String>;
+    /// Oldest moment first.
+    async fn get_scene_annotations(
+        &self,
+        scene_uuid: &SceneUuid,
+    ) -> Result<Vec<Annotation>, String>;
+    async fn set_annotation_flags(
+        &self,
+        annotation_uuid: &AnnotationUuid,
+        is_bookmark: bool,
+        include_in_export: bool,
+    ) -> Result<(), String>;
+    async fn delete_annotation(&self, annotation_uuid: &AnnotationUuid) -> Result<(), String>;
+}
@@ -0,0 +1,26 @@
+use crate::domain::message::Message;
+use crate::domain::person_uuid::PersonUuid;
+use crate::domain::scene_uuid::SceneUuid;
+use chrono::{DateTime, Utc};
+
+pub trait ArrivalObservationCapability {
+    /// Messages said to the whole scene since `since`, oldest first. Whispers
+    /// and side conversations are left out, since someone walking in would
+    /// not have caught them.
+    async fn get_recent_public_scene_messages(
+        &self,
+        scene_uuid: &SceneUuid,
+        since: DateTime<Utc>,
+    ) -> Result<Vec<Message>, String>;
+    async fn summarize_arrival_observation(
+        &self,
+        scene_description: &str,
+        transcript: &str,
+    ) -> Result<String, String>;
+    async fn record_arrival_observation(
+        &self,
+        person_uuid: &PersonUuid,
+        scene_uuid: &SceneUuid,
+        observation: String,
+    ) -> Result<(), String>;
+}
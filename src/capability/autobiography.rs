@@ -0,0 +1,16 @@
+use crate::domain::autobiography::LifeEvent;
+use crate::domain::person_uuid::PersonUuid;
+
+pub trait AutobiographyCapability {
+    /// Everything recorded about the person, oldest first: their memories,
+    /// each identity they have had and the scenes they came to and left.
+    async fn get_life_events(&self, person_uuid: &PersonUuid) -> Result<Vec<LifeEvent>, String>;
+    /// One chapter of their life in their own voice, picking up from the
+    /// chapter before it if there is one.
+    async fn write_autobiography_chapter(
+        &self,
+        person_name: &str,
+        previous_chapter: Option<&str>,
+        events: &[LifeEvent],
+    ) -> Result<String, String>;
+}
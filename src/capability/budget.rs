@@ -0,0 +1,10 @@
+use crate::domain::budget::{LlmSpend, RunBudget};
+use chrono::{DateTime, Utc};
+
+pub trait BudgetCapability {
+    async fn get_run_budget(&self) -> Result<RunBudget, String>;
+    /// Starts a new run, so only completions from now on count against the
+    /// budget. `None` takes the budget off.
+    async fn set_run_budget(&self, budget_usd: Option<f64>) -> Result<(), String>;
+    async fn get_llm_spend(&self, since: DateTime<Utc>) -> Result<LlmSpend, String>;
+}
@@ -0,0 +1,7 @@
+use chrono::{DateTime, Utc};
+
+pub trait ChatLatencyCapability {
+    /// How long each message the real world user sent since `since` waited
+    /// for a reply, in milliseconds.
+    async fn get_chat_latencies_ms(&self, since: DateTime<Utc>) -> Result<Vec<i64>, String>;
+}
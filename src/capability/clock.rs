@@ -0,0 +1,7 @@
+use chrono::{DateTime, Utc};
+
+pub trait ClockCapability {
+    /// The time to stamp and schedule things with. Read this rather than
+    /// `Utc::now()` so tests can move time along themselves.
+    fn now(&self) -> DateTime<Utc>;
+}
@@ -0,0 +1,13 @@
+use crate::domain::content_scrub::ContentScrubSettings;
+
+pub trait ContentScrubCapability {
+    async fn get_content_scrub_settings(&self) -> Result<ContentScrubSettings, String>;
+
+    async fn set_content_scrub_settings(
+        &self,
+        settings: ContentScrubSettings,
+    ) -> Result<(), String>;
+
+    /// Scrubs content with the current settings before it gets persisted.
+    async fn scrub_content(&self, content: &str) -> Result<String, String>;
+}
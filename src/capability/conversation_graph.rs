@@ -0,0 +1,11 @@
+use crate::domain::conversation_graph::ConversationGraph;
+use chrono::{DateTime, Utc};
+
+pub trait ConversationGraphCapability {
+    /// Counts the messages each speaker got to each listener that were sent
+    /// at or after `since`, or ever when it is missing.
+    async fn get_conversation_graph(
+        &self,
+        since: Option<DateTime<Utc>>,
+    ) -> Result<ConversationGraph, String>;
+}
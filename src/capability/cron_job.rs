@@ -0,0 +1,32 @@
+use crate::domain::cron_job::{CronJob, NewCronJob};
+use crate::domain::cron_job_uuid::CronJobUuid;
+use chrono::{DateTime, Utc};
+
+pub trait CronJobCapability {
+    /// Sorted by name.
+    async fn get_cron_jobs(&self) -> Result<Vec<CronJob>, String>;
+    async fn create_cron_job(&self, cron_job: &NewCronJob) -> Result<CronJobUuid, String>;
+    async fn update_cron_job(
+        &self,
+        cron_job_uuid: &CronJobUuid,
+        cron_job: &NewCronJob,
+    ) -> Result<(), String>;
+    async fn set_cron_job_enabled(
+        &self,
+        cron_job_uuid: &CronJobUuid,
+        enabled: bool,
+    ) -> Result<(), String>;
+    async fn delete_cron_job(&self, cron_job_uuid: &CronJobUuid) -> Result<(), String>;
+    /// Enabled cron jobs whose next run is at or before `now`.
+    async fn get_due_cron_jobs(&self, now: DateTime<Utc>) -> Result<Vec<CronJob>, String>;
+    /// Moves a cron job from the run that was due at `due_at` on to
+    /// `next_run_at`. False if it was already moved on, by another job
+    /// runner or an edit.
+    async fn claim_cron_job_run(
+        &self,
+        cron_job_uuid: &CronJobUuid,
+        due_at: DateTime<Utc>,
+        ran_at: DateTime<Utc>,
+        next_run_at: Option<DateTime<Utc>>,
+    ) -> Result<bool, String>;
+}
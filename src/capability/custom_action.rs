@@ -0,0 +1,4 @@
+pub trait CustomActionCapability {
+    /// Posts a custom action someone took to its webhook handler.
+    async fn post_custom_action(&self, url: &str, body: &serde_json::Value) -> Result<(), String>;
+}
@@ -0,0 +1,13 @@
+use crate::domain::delivery::{DeliveryCounts, FailedDelivery};
+use chrono::{DateTime, Utc};
+
+/// Delivery rows are written by the outbox capability as entries are marked
+/// delivered or failed.
+pub trait DeliveryCapability {
+    /// Newest first.
+    async fn get_failed_deliveries(&self, limit: i64) -> Result<Vec<FailedDelivery>, String>;
+    async fn get_delivery_counts_since(
+        &self,
+        since: DateTime<Utc>,
+    ) -> Result<DeliveryCounts, String>;
+}
@@ -0,0 +1,17 @@
+use crate::domain::embedding_backfill::MemoryToEmbed;
+use crate::domain::memory_uuid::MemoryUuid;
+
+pub trait EmbeddingBackfillCapability {
+    /// Memories with a missing, all zero or wrongly sized embedding.
+    async fn count_memories_missing_embeddings(&self) -> Result<i64, String>;
+    /// The next `limit` of them in uuid order, starting after `after`, so a
+    /// memory that fails to embed is not fetched again.
+    async fn get_memories_missing_embeddings(
+        &self,
+        after: Option<&MemoryUuid>,
+        limit: i64,
+    ) -> Result<Vec<MemoryToEmbed>, String>;
+    /// Embeds the memory with the current model, checks the vector and
+    /// stores it with its model and dimension.
+    async fn re_embed_memory(&self, memory: &MemoryToEmbed) -> Result<(), String>;
+}
@@ -0,0 +1,14 @@
+use crate::domain::outbox::OutboxEntry;
+use crate::domain::outbox_uuid::OutboxUuid;
+use crate::domain::tenant_uuid::TenantUuid;
+
+pub trait EventStreamCapability {
+    /// Outbox entries written after `after`, oldest first, delivered or not.
+    /// Entries about scenes outside the tenant are left out.
+    async fn get_events_after(
+        &self,
+        tenant_uuid: &TenantUuid,
+        after: &OutboxUuid,
+        limit: i64,
+    ) -> Result<Vec<OutboxEntry>, String>;
+}
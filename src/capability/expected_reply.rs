@@ -0,0 +1,38 @@
+use crate::domain::message_uuid::MessageUuid;
+use crate::domain::person_uuid::PersonUuid;
+
+#[derive(Debug, Clone)]
+pub struct NewExpectedReply {
+    /// The message that asked the question.
+    pub message_uuid: MessageUuid,
+    pub asker_person_uuid: PersonUuid,
+    pub recipient_person_uuid: PersonUuid,
+    pub reply_window_ms: i64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ExpectedReplyOutcome {
+    Replied,
+    Ignored,
+}
+
+impl ExpectedReplyOutcome {
+    pub fn to_name(&self) -> String {
+        match self {
+            ExpectedReplyOutcome::Replied => "replied".to_string(),
+            ExpectedReplyOutcome::Ignored => "ignored".to_string(),
+        }
+    }
+}
+
+pub trait ExpectedReplyCapability {
+    async fn expect_reply(&self, new_expected_reply: NewExpectedReply) -> Result<(), String>;
+    /// Whether the recipient has said anything in the question's scene since
+    /// the question was asked.
+    async fn has_recipient_replied(&self, message_uuid: &MessageUuid) -> Result<bool, String>;
+    async fn resolve_expected_reply(
+        &self,
+        message_uuid: &MessageUuid,
+        outcome: ExpectedReplyOutcome,
+    ) -> Result<(), String>;
+}
@@ -0,0 +1,24 @@
+use crate::domain::external_tool::ExternalTool;
+use crate::domain::person_uuid::PersonUuid;
+use crate::domain::scene_uuid::SceneUuid;
+
+/// One call a person made to an external tool, as it is recorded.
+pub struct NewExternalToolCall {
+    pub person_uuid: PersonUuid,
+    pub scene_uuid: Option<SceneUuid>,
+    pub tool_name: String,
+    pub arguments: serde_json::Value,
+    /// What the tool responded with, or why calling it failed.
+    pub result: Result<String, String>,
+    pub duration_ms: i64,
+}
+
+pub trait ExternalToolCapability {
+    /// Calls the tool's url with the arguments and returns the response body.
+    async fn call_external_tool(
+        &self,
+        tool: &ExternalTool,
+        arguments: &serde_json::Value,
+    ) -> Result<String, String>;
+    async fn record_external_tool_call(&self, call: NewExternalToolCall) -> Result<(), String>;
+}
@@ -0,0 +1,20 @@
+use crate::domain::person_uuid::PersonUuid;
+use crate::open_ai::fine_tune::FineTuneJob;
+use crate::open_ai::model::Model;
+
+pub trait FineTuneCapability {
+    async fn record_fine_tune_job(
+        &self,
+        job: &FineTuneJob,
+        base_model: &Model,
+        person_uuid: Option<&PersonUuid>,
+    ) -> Result<(), String>;
+
+    async fn update_fine_tune_job(&self, job: &FineTuneJob) -> Result<(), String>;
+
+    /// The most recent successfully fine-tuned model registered for this person, if any.
+    async fn get_person_fine_tuned_model(
+        &self,
+        person_uuid: &PersonUuid,
+    ) -> Result<Option<Model>, String>;
+}
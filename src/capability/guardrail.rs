@@ -0,0 +1,7 @@
+use crate::domain::guardrail::GuardrailSettings;
+
+pub trait GuardrailCapability {
+    async fn get_guardrail_settings(&self) -> Result<GuardrailSettings, String>;
+
+    async fn set_guardrail_settings(&self, settings: &GuardrailSettings) -> Result<(), String>;
+}
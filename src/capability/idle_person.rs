@@ -0,0 +1,33 @@
+use crate::domain::person_uuid::PersonUuid;
+use crate::domain::scene_uuid::SceneUuid;
+use crate::domain::talk_share::TalkCount;
+use chrono::{DateTime, Utc};
+
+/// A person who has not reacted to anything in a while, in a scene where
+/// others are still talking.
+#[derive(Debug, Clone)]
+pub struct IdlePerson {
+    pub person_uuid: PersonUuid,
+    pub scene_uuid: SceneUuid,
+    pub chattiness: f64,
+}
+
+pub trait IdlePersonCapability {
+    /// Enabled, awake persons with nothing queued who have not reacted since
+    /// `idle_since`, in scenes someone else has spoken in since
+    /// `active_since`.
+    async fn get_idle_persons_in_active_scenes(
+        &self,
+        idle_since: DateTime<Utc>,
+        active_since: DateTime<Utc>,
+    ) -> Result<Vec<IdlePerson>, String>;
+    async fn get_person_chattiness(&self, person_uuid: &PersonUuid) -> Result<f64, String>;
+    async fn set_person_chattiness(
+        &self,
+        person_uuid: &PersonUuid,
+        chattiness: f64,
+    ) -> Result<(), String>;
+    /// How many messages each enabled, awake person sent to each open scene
+    /// they are in since `since`, with a row of zero for those who sent none.
+    async fn get_talk_counts(&self, since: DateTime<Utc>) -> Result<Vec<TalkCount>, String>;
+}
@@ -0,0 +1,24 @@
+use crate::domain::item::{Item, ItemLocation};
+use crate::domain::item_uuid::ItemUuid;
+use crate::domain::person_uuid::PersonUuid;
+use crate::domain::scene_uuid::SceneUuid;
+
+pub struct NewItem {
+    pub name: String,
+    pub description: String,
+    pub location: ItemLocation,
+}
+
+pub trait ItemCapability {
+    async fn create_item(&self, new_item: NewItem) -> Result<ItemUuid, String>;
+    async fn get_items_carried_by(&self, person_uuid: &PersonUuid) -> Result<Vec<Item>, String>;
+    async fn get_items_in_scene(&self, scene_uuid: &SceneUuid) -> Result<Vec<Item>, String>;
+    /// Fails if the giver is no longer carrying the item, so an item handed
+    /// over twice at once only reaches one person.
+    async fn give_item(
+        &self,
+        item_uuid: &ItemUuid,
+        giver_person_uuid: &PersonUuid,
+        recipient_person_uuid: &PersonUuid,
+    ) -> Result<(), String>;
+}
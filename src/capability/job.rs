@@ -1,9 +1,26 @@
+use crate::capability::clock::ClockCapability;
+use crate::domain::fan_out::QueuePressure;
 use crate::domain::job::{Job, JobKind, PoppedJob};
+use crate::domain::job_event::{JobEvent, NewJobEvent};
 use crate::domain::job_uuid::JobUuid;
+use crate::domain::person_uuid::PersonUuid;
 
-pub trait JobCapability {
+pub trait JobCapability: ClockCapability {
     async fn unshift_job(&self, job: JobKind) -> Result<(), String>;
+    /// Like `unshift_job`, but says which uuid the job was queued under.
+    async fn enqueue_job(&self, job: JobKind) -> Result<JobUuid, String>;
     async fn pop_next_job(&self, current_active_ms: i64) -> Result<Option<PoppedJob>, String>;
+    /// When the soonest job still waiting on the active clock comes due.
+    async fn get_next_delayed_job_active_ms(
+        &self,
+        current_active_ms: i64,
+    ) -> Result<Option<i64>, String>;
+    /// When the person's unfinished wait comes due on the active clock, if
+    /// they are waiting.
+    async fn get_persons_wait_end_active_ms(
+        &self,
+        person_uuid: &PersonUuid,
+    ) -> Result<Option<i64>, String>;
     async fn recent_jobs(&self, limit: i64) -> Result<Vec<Job>, String>;
     async fn get_job_by_uuid(&self, job_uuid: &JobUuid) -> Result<Option<Job>, String>;
     async fn mark_job_finished(&self, job_uuid: &JobUuid) -> Result<(), String>;
@@ -11,4 +28,9 @@ pub trait JobCapability {
     async fn reset_job(&self, job_uuid: &JobUuid) -> Result<(), String>;
     async fn reset_all_failed_jobs(&self) -> Result<(), String>;
     async fn delete_job(&self, job_uuid: &JobUuid) -> Result<(), String>;
+    async fn record_job_event(&self, job_uuid: &JobUuid, event: &NewJobEvent)
+        -> Result<(), String>;
+    /// Oldest first.
+    async fn get_job_events(&self, job_uuid: &JobUuid) -> Result<Vec<JobEvent>, String>;
+    async fn get_queue_pressure(&self) -> Result<QueuePressure, String>;
 }
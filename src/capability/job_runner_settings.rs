@@ -6,4 +6,16 @@ pub trait JobRunnerSettingsCapability {
     async fn set_job_runner_poll_interval_secs(&self, secs: u64) -> Result<(), String>;
     async fn get_job_runner_enabled(&self) -> Result<bool, String>;
     async fn set_job_runner_enabled(&self, enabled: bool) -> Result<(), String>;
+    /// The last active clock time the job runner stored. Delayed jobs are
+    /// scheduled against this clock.
+    async fn get_active_clock_ms(&self) -> Result<i64, String>;
+    /// How many active clock ms pass for each real ms while the job runner
+    /// is on.
+    async fn get_time_factor(&self) -> Result<f64, String>;
+    async fn set_time_factor(&self, time_factor: f64) -> Result<(), String>;
+    /// Asks the job runner to move the active clock ahead to the next
+    /// delayed job.
+    async fn request_wait_skip(&self) -> Result<(), String>;
+    /// Whether a skip was requested, clearing the request.
+    async fn take_wait_skip_request(&self) -> Result<bool, String>;
 }
@@ -0,0 +1,11 @@
+use crate::open_ai::batch::{Batch, BatchRequest, BatchResult};
+
+pub trait LlmBatchCapability {
+    /// Uploads the requests as one Batch API batch and returns its id.
+    async fn submit_llm_batch(&self, requests: Vec<BatchRequest>) -> Result<String, String>;
+
+    /// Asks OpenAI how the batch is doing and records its status.
+    async fn refresh_llm_batch(&self, batch_id: &str) -> Result<Batch, String>;
+
+    async fn get_llm_batch_results(&self, file_id: &str) -> Result<Vec<BatchResult>, String>;
+}
@@ -0,0 +1,34 @@
+use crate::domain::job::nightly_maintenance::{MaintenanceReport, PrunedRows, RecentMemories};
+use crate::domain::scene_uuid::SceneUuid;
+use chrono::{DateTime, Utc};
+
+pub trait MaintenanceCapability {
+    /// Fails every job still running that started before `started_before`,
+    /// since its runner most likely died. Returns how many were failed.
+    async fn reap_stale_jobs(&self, started_before: DateTime<Utc>) -> Result<u64, String>;
+    /// Adds up the llm calls of every day before the one `before` falls on
+    /// into `llm_usage_daily`. Days already rolled up are left alone, since
+    /// their calls may have been pruned since. Returns how many daily rows
+    /// were written.
+    async fn roll_up_llm_usage(&self, before: DateTime<Utc>) -> Result<u64, String>;
+    /// Deletes llm calls and log events from before `before`, keeping the
+    /// maintenance reports.
+    async fn prune_logs(&self, before: DateTime<Utc>) -> Result<PrunedRows, String>;
+    /// Deletes memories a person has more than once, ignoring case and
+    /// surrounding whitespace, keeping the oldest. Returns how many were
+    /// deleted.
+    async fn remove_duplicate_memories(&self) -> Result<u64, String>;
+    /// The memories made since `since` by persons who are enabled, awake and
+    /// not archived, oldest first.
+    async fn get_recent_memories(
+        &self,
+        since: DateTime<Utc>,
+    ) -> Result<Vec<RecentMemories>, String>;
+    /// Open scenes with events or ambience changes newer than their latest
+    /// snapshot.
+    async fn get_scenes_changed_since_snapshot(&self) -> Result<Vec<SceneUuid>, String>;
+    /// Rewrites the scene's description to take in what has changed since
+    /// its latest snapshot, and saves it as a new snapshot.
+    async fn refresh_scene_snapshot(&self, scene_uuid: &SceneUuid) -> Result<(), String>;
+    async fn get_latest_maintenance_report(&self) -> Result<Option<MaintenanceReport>, String>;
+}
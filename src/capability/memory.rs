@@ -1,9 +1,11 @@
 use super::scene::SceneCapability;
+use crate::domain::memory::MemoryRetrieval;
 use crate::domain::memory_uuid::MemoryUuid;
 use crate::domain::message::MessageSender;
 use crate::domain::person_name::PersonName;
 use crate::domain::person_uuid::PersonUuid;
 use crate::domain::scene_uuid::SceneUuid;
+use chrono::{DateTime, Utc};
 
 pub struct NewMemory {
     pub memory_uuid: MemoryUuid,
@@ -17,8 +19,17 @@ pub struct MemoryQueryPrompt {
 
 #[derive(Clone, Debug)]
 pub struct MemorySearchResult {
+    pub memory_uuid: MemoryUuid,
     pub content: String,
+    /// How far the memory is from the query, by embedding alone.
     pub distance: f64,
+    /// Whether the memory mentions one of the persons the search was asked
+    /// to favor.
+    pub mentions_participant: bool,
+    pub created_at: DateTime<Utc>,
+    /// What the memory was ranked by, lower being better. See
+    /// `MemoryRetrieval::rank`.
+    pub score: f64,
 }
 
 pub enum MessageTypeArgs {
@@ -54,6 +65,12 @@ pub trait MemoryCapability: SceneCapability {
         &self,
         person_uuid: PersonUuid,
         query: String,
-        limit: i64,
+        participants: &[PersonUuid],
+        retrieval: &MemoryRetrieval,
     ) -> Result<Vec<MemorySearchResult>, String>;
+    /// The world's retrieval settings, for searches that do not bring
+    /// their own.
+    async fn get_memory_retrieval(&self) -> Result<MemoryRetrieval, String>;
+    async fn set_memory_retrieval(&self, retrieval: &MemoryRetrieval) -> Result<(), String>;
+    async fn delete_memory(&self, memory_uuid: &MemoryUuid) -> Result<(), String>;
 }
@@ -1,8 +1,11 @@
 use crate::domain::message::{Message, MessageSender};
+use crate::domain::message_audience::MessageAudience;
+use crate::domain::message_quote::MessageQuote;
 use crate::domain::message_uuid::MessageUuid;
 use crate::domain::person_uuid::PersonUuid;
 use crate::domain::scene_uuid::SceneUuid;
 use chrono::{DateTime, Utc};
+use std::collections::HashMap;
 
 pub trait MessageCapability {
     async fn send_scene_message(
@@ -16,6 +19,18 @@ pub trait MessageCapability {
         message_uuid: &MessageUuid,
         recipients: Vec<PersonUuid>,
     ) -> Result<(), String>;
+    async fn set_message_audience(
+        &self,
+        message_uuid: &MessageUuid,
+        audience: &MessageAudience,
+    ) -> Result<(), String>;
+    /// Records people who overheard a message without it being meant for
+    /// them. They see it in their history but are not prompted to react.
+    async fn add_scene_message_overhearers(
+        &self,
+        message_uuid: &MessageUuid,
+        overhearers: Vec<PersonUuid>,
+    ) -> Result<(), String>;
     async fn get_messages_in_scene_page(
         &self,
         scene_uuid: &SceneUuid,
@@ -36,4 +51,15 @@ pub trait MessageCapability {
         person_uuid: &PersonUuid,
         message_uuids: Vec<MessageUuid>,
     ) -> Result<(), String>;
+    async fn set_message_quote(
+        &self,
+        message_uuid: &MessageUuid,
+        quote: &MessageQuote,
+    ) -> Result<(), String>;
+    /// The quotes attached to any of the messages. Messages without one are
+    /// left out.
+    async fn get_message_quotes(
+        &self,
+        message_uuids: Vec<MessageUuid>,
+    ) -> Result<HashMap<MessageUuid, MessageQuote>, String>;
 }
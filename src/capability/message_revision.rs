@@ -0,0 +1,27 @@
+use crate::domain::message::Message;
+use crate::domain::message_uuid::MessageUuid;
+use crate::domain::scene_uuid::SceneUuid;
+use std::collections::HashSet;
+
+pub trait MessageRevisionCapability {
+    /// Marks a real world user's message superseded and cancels the pending
+    /// reactions to it. Returns false, changing nothing, once anyone has
+    /// started reacting to it or it was already superseded.
+    async fn retract_pending_message(&self, message_uuid: &MessageUuid) -> Result<bool, String>;
+    async fn set_message_superseded_by(
+        &self,
+        message_uuid: &MessageUuid,
+        superseded_by: &MessageUuid,
+    ) -> Result<(), String>;
+    /// The real world user's messages in the scene that nobody has reacted
+    /// to yet, newest first.
+    async fn get_pending_real_world_user_messages(
+        &self,
+        scene_uuid: &SceneUuid,
+        limit: i64,
+    ) -> Result<Vec<Message>, String>;
+    async fn get_superseded_message_uuids(
+        &self,
+        message_uuids: Vec<MessageUuid>,
+    ) -> Result<HashSet<MessageUuid>, String>;
+}
@@ -1,16 +1,66 @@
+pub mod acknowledgement;
+pub mod action_budget;
+pub mod annotation;
+pub mod arrival_observation;
+pub mod autobiography;
+pub mod budget;
+pub mod chat_latency;
+pub mod clock;
+pub mod content_scrub;
+pub mod conversation_graph;
+pub mod cron_job;
+pub mod custom_action;
+pub mod delivery;
+pub mod embedding_backfill;
 pub mod event;
+pub mod event_stream;
+pub mod expected_reply;
+pub mod external_tool;
+pub mod fine_tune;
+pub mod guardrail;
+pub mod idle_person;
+pub mod item;
 pub mod job;
 pub mod job_runner_settings;
+pub mod llm_batch;
 pub mod log_event;
 pub mod logging;
+pub mod maintenance;
 pub mod memory;
 pub mod message;
+pub mod message_revision;
+pub mod moderation;
 pub mod motivation;
+pub mod outbox;
 pub mod person;
+pub mod person_arc;
 pub mod person_identity;
+pub mod person_merge;
+pub mod person_query;
+pub mod person_tag;
 pub mod person_task;
+pub mod persona;
+pub mod persona_consistency;
 pub mod reaction;
+pub mod reaction_context;
 pub mod reaction_history;
 pub mod reflection;
+pub mod relationship;
+pub mod run_report;
 pub mod scene;
+pub mod scene_archive;
+pub mod scene_drama;
+pub mod scene_goal;
+pub mod scene_invitation;
+pub mod scene_template;
+pub mod scene_timeline;
+pub mod scene_tone;
+pub mod schema;
 pub mod state_of_mind;
+pub mod style_guide;
+pub mod tenant;
+pub mod topic;
+pub mod utterance;
+pub mod voice_exemplar;
+pub mod world_language;
+pub mod world_map;
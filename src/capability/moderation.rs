@@ -0,0 +1,30 @@
+use crate::domain::message::MessageSender;
+use crate::domain::moderation::ModerationVerdict;
+use crate::domain::scene_uuid::SceneUuid;
+use chrono::{DateTime, Utc};
+
+#[derive(Debug, Clone)]
+pub struct BlockedContent {
+    pub sender_label: String,
+    pub content: String,
+    pub categories: Vec<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+pub trait ModerationCapability {
+    /// Runs content said in a scene through moderation, held to the scene's
+    /// tone profile if it has one, and records it in `blocked_content` when
+    /// blocked.
+    async fn moderate_content(
+        &self,
+        sender: &MessageSender,
+        scene_uuid: &SceneUuid,
+        content: &str,
+    ) -> Result<ModerationVerdict, String>;
+
+    async fn get_blocked_content(&self, limit: i64) -> Result<Vec<BlockedContent>, String>;
+
+    async fn get_moderation_threshold(&self) -> Result<f64, String>;
+
+    async fn set_moderation_threshold(&self, threshold: f64) -> Result<(), String>;
+}
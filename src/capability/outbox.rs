@@ -0,0 +1,23 @@
+use crate::domain::outbox::{OutboxEntry, OutboxEvent};
+use crate::domain::outbox_uuid::OutboxUuid;
+use chrono::{DateTime, Utc};
+
+pub trait OutboxCapability {
+    fn get_outbox_webhook_url(&self) -> Option<String>;
+    /// For events that are not part of some other change's transaction.
+    async fn add_outbox_event(&self, event: &OutboxEvent) -> Result<(), String>;
+    /// Claims up to `limit` undelivered entries that are due, oldest first.
+    /// Claimed entries are not handed out again for a few minutes, in case the
+    /// dispatcher dies before it marks them.
+    async fn claim_due_outbox_entries(&self, limit: i64) -> Result<Vec<OutboxEntry>, String>;
+    async fn post_outbox_entry(&self, webhook_url: &str, entry: &OutboxEntry)
+        -> Result<(), String>;
+    async fn mark_outbox_entry_delivered(&self, outbox_uuid: &OutboxUuid) -> Result<(), String>;
+    async fn mark_outbox_entry_failed(
+        &self,
+        outbox_uuid: &OutboxUuid,
+        details: &str,
+        retry_at: DateTime<Utc>,
+    ) -> Result<(), String>;
+    async fn has_undelivered_outbox_entries(&self) -> Result<bool, String>;
+}
@@ -22,4 +22,7 @@ pub trait PersonCapability {
         is_enabled: bool,
     ) -> Result<(), String>;
     async fn is_person_enabled(&self, person_uuid: &PersonUuid) -> Result<bool, String>;
+    /// Disables the person and takes them out of their scenes, keeping
+    /// their history.
+    async fn archive_person(&self, person_uuid: &PersonUuid) -> Result<(), String>;
 }
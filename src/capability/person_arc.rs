@@ -0,0 +1,32 @@
+use crate::domain::autobiography::LifeEvent;
+use crate::domain::person_arc::{ArcDraft, PersonArc};
+use crate::domain::person_uuid::PersonUuid;
+
+pub struct NewPersonArc {
+    pub person_uuid: PersonUuid,
+    pub draft: ArcDraft,
+    pub written_at_active_ms: i64,
+}
+
+pub trait PersonArcCapability {
+    async fn create_person_arc(&self, new_person_arc: NewPersonArc) -> Result<(), String>;
+    async fn get_latest_person_arc(
+        &self,
+        person_uuid: &PersonUuid,
+    ) -> Result<Option<PersonArc>, String>;
+    /// Enabled, awake persons with memories newer than their latest arc,
+    /// and whose latest arc was written at or before
+    /// `written_before_active_ms` on the active clock, or who have none.
+    /// Longest overdue first.
+    async fn get_persons_due_an_arc(
+        &self,
+        written_before_active_ms: i64,
+        limit: i64,
+    ) -> Result<Vec<PersonUuid>, String>;
+    async fn write_person_arc(
+        &self,
+        person_name: &str,
+        previous_arc: Option<&PersonArc>,
+        events: &[LifeEvent],
+    ) -> Result<ArcDraft, String>;
+}
@@ -1,5 +1,6 @@
 use async_trait::async_trait;
 
+use crate::domain::person_directory::PersonDirectoryEntry;
 use crate::domain::person_identity_uuid::PersonIdentityUuid;
 use crate::domain::person_uuid::PersonUuid;
 
@@ -26,4 +27,16 @@ pub trait PersonIdentityCapability {
         &self,
         person_uuid: &PersonUuid,
     ) -> Result<Option<String>, String>;
+    async fn set_person_identity_summary(
+        &self,
+        person_identity_uuid: &PersonIdentityUuid,
+        summary: &str,
+    ) -> Result<(), String>;
+    /// What `viewer` knows about each of `others`, looked up all at once.
+    /// Persons that do not exist are left out.
+    async fn get_person_directory(
+        &self,
+        viewer: &PersonUuid,
+        others: &[PersonUuid],
+    ) -> Result<Vec<PersonDirectoryEntry>, String>;
 }
@@ -0,0 +1,19 @@
+use crate::domain::person_merge::PersonMergeCounts;
+use crate::domain::person_uuid::PersonUuid;
+
+pub trait PersonMergeCapability {
+    /// What `merge_persons` would move, without moving anything.
+    async fn get_person_merge_counts(
+        &self,
+        keep: &PersonUuid,
+        merge: &PersonUuid,
+    ) -> Result<PersonMergeCounts, String>;
+    /// Moves the messages, memories, identities, states of mind and scene
+    /// participations of `merge` to `keep`, then archives `merge`, all in
+    /// one transaction. Everything else stays with the archived duplicate.
+    async fn merge_persons(
+        &self,
+        keep: &PersonUuid,
+        merge: &PersonUuid,
+    ) -> Result<PersonMergeCounts, String>;
+}
@@ -0,0 +1,23 @@
+use crate::domain::person_filter::PersonFilter;
+use crate::domain::person_name::PersonName;
+use crate::domain::person_uuid::PersonUuid;
+use chrono::{DateTime, Utc};
+
+/// A person as the persons list and the `persons` command show them.
+#[derive(Debug, Clone)]
+pub struct PersonSummary {
+    pub person_uuid: PersonUuid,
+    pub name: PersonName,
+    pub is_enabled: bool,
+    pub is_hibernating: bool,
+    /// The scene they are in now, if any.
+    pub scene_name: Option<String>,
+    pub tags: Vec<String>,
+    pub last_reacted_at: Option<DateTime<Utc>>,
+    pub unread_messages: i64,
+}
+
+pub trait PersonQueryCapability {
+    /// Persons matching every condition in the filter, by name.
+    async fn query_persons(&self, filter: &PersonFilter) -> Result<Vec<PersonSummary>, String>;
+}
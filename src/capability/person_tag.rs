@@ -0,0 +1,13 @@
+use crate::domain::person_filter::PersonTag;
+use crate::domain::person_uuid::PersonUuid;
+
+pub trait PersonTagCapability {
+    /// Does nothing if the person already has the tag.
+    async fn add_person_tag(&self, person_uuid: &PersonUuid, tag: &PersonTag)
+        -> Result<(), String>;
+    async fn remove_person_tag(
+        &self,
+        person_uuid: &PersonUuid,
+        tag: &PersonTag,
+    ) -> Result<(), String>;
+}
@@ -0,0 +1,10 @@
+use crate::domain::cast::Cast;
+use crate::domain::persona::Persona;
+
+pub trait PersonaCapability {
+    /// Asks the LLM for a person that fits a one-line concept, without saving anything.
+    async fn generate_persona(&self, concept: &str) -> Result<Persona, String>;
+    /// Asks the LLM for `count` people who share a scenario and already know
+    /// each other, without saving anything.
+    async fn generate_cast(&self, concept: &str, count: usize) -> Result<Cast, String>;
+}
@@ -0,0 +1,36 @@
+use crate::domain::person_uuid::PersonUuid;
+use crate::domain::persona_consistency::PersonaInconsistency;
+use chrono::{DateTime, Utc};
+
+#[derive(Debug, Clone)]
+pub struct RecordedPersonaInconsistency {
+    pub inconsistency: PersonaInconsistency,
+    pub created_at: DateTime<Utc>,
+}
+
+pub trait PersonaConsistencyCapability {
+    /// What the person has said in scenes, newest first. Retracted and
+    /// edited messages are left out.
+    async fn get_recent_utterances(
+        &self,
+        person_uuid: &PersonUuid,
+        limit: i64,
+    ) -> Result<Vec<String>, String>;
+    /// Asks the LLM which utterances contradict the identity, without saving anything.
+    async fn judge_persona_consistency(
+        &self,
+        identity: &str,
+        utterances: &[String],
+    ) -> Result<Vec<PersonaInconsistency>, String>;
+    async fn record_persona_inconsistencies(
+        &self,
+        person_uuid: &PersonUuid,
+        inconsistencies: &[PersonaInconsistency],
+    ) -> Result<(), String>;
+    /// Newest first.
+    async fn get_persona_inconsistencies(
+        &self,
+        person_uuid: &PersonUuid,
+        limit: i64,
+    ) -> Result<Vec<RecordedPersonaInconsistency>, String>;
+}
@@ -4,6 +4,7 @@ use crate::domain::person_task::PersonTask;
 use crate::domain::person_task::PersonTaskOutcomeCheck;
 use crate::domain::person_uuid::PersonUuid;
 use crate::person_actions::PersonReaction;
+use std::time::Duration;
 
 #[derive(Debug, Clone)]
 pub struct ReactionPromptPreview {
@@ -11,6 +12,28 @@ pub struct ReactionPromptPreview {
     pub thinking_user_prompt: String,
     pub action_system_prompt: String,
     pub action_user_prompt: String,
+    pub context_timings: ContextTimings,
+}
+
+/// How long it took to fetch what goes into a reaction's prompts. The
+/// fetches run at the same time, so `total` is less than their sum.
+#[derive(Debug, Clone, Default)]
+pub struct ContextTimings {
+    pub fetches: Vec<ContextFetchTiming>,
+    pub total: Duration,
+}
+
+#[derive(Debug, Clone)]
+pub struct ContextFetchTiming {
+    pub name: &'static str,
+    pub elapsed: Duration,
+}
+
+impl ContextTimings {
+    /// How long the fetches would have taken one after another.
+    pub fn sequential_total(&self) -> Duration {
+        self.fetches.iter().map(|fetch| fetch.elapsed).sum()
+    }
 }
 
 pub trait ReactionCapability {
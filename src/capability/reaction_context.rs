@@ -0,0 +1,45 @@
+use crate::domain::person_name::PersonName;
+use crate::domain::person_uuid::PersonUuid;
+use crate::domain::reaction_context_uuid::ReactionContextUuid;
+use chrono::{DateTime, Utc};
+
+pub struct NewReactionContext {
+    pub person_uuid: PersonUuid,
+    pub system_prompt: String,
+    pub user_prompt: String,
+    pub assistant_response: String,
+    /// Whether the prompt had the person's voice exemplars in it, if they
+    /// have any.
+    pub used_voice_exemplars: Option<bool>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ReactionContext {
+    pub uuid: ReactionContextUuid,
+    pub person_name: PersonName,
+    pub system_prompt: String,
+    pub user_prompt: String,
+    pub assistant_response: String,
+    pub is_good: bool,
+    pub created_at: DateTime<Utc>,
+}
+
+pub trait ReactionContextCapability {
+    async fn record_reaction_context(
+        &self,
+        new_reaction_context: NewReactionContext,
+    ) -> Result<ReactionContextUuid, String>;
+
+    async fn get_recent_reaction_contexts(
+        &self,
+        limit: i64,
+    ) -> Result<Vec<ReactionContext>, String>;
+
+    async fn get_good_reaction_contexts(&self) -> Result<Vec<ReactionContext>, String>;
+
+    async fn set_reaction_context_good(
+        &self,
+        reaction_context_uuid: &ReactionContextUuid,
+        is_good: bool,
+    ) -> Result<(), String>;
+}
@@ -0,0 +1,16 @@
+use crate::domain::person_uuid::PersonUuid;
+
+#[derive(Debug, Clone)]
+pub struct NewRelationship {
+    pub person_uuid: PersonUuid,
+    pub other_person_uuid: PersonUuid,
+    /// How `person_uuid` sees `other_person_uuid`, in their own terms.
+    pub description: String,
+}
+
+pub trait RelationshipCapability {
+    /// Inserts every relationship in one statement. Existing relationships
+    /// between the same two people are overwritten.
+    async fn create_relationships(&self, relationships: Vec<NewRelationship>)
+        -> Result<(), String>;
+}
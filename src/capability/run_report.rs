@@ -0,0 +1,16 @@
+use crate::domain::run_report::{CastMember, ReportBookmark, ReportScene};
+use chrono::{DateTime, Utc};
+
+pub trait RunReportCapability {
+    /// Persons who were in a scene at any point since `since`, most
+    /// messages first.
+    async fn get_run_cast(&self, since: DateTime<Utc>) -> Result<Vec<CastMember>, String>;
+    /// Scenes that were open at any point since `since`, oldest first.
+    async fn get_run_scenes(&self, since: DateTime<Utc>) -> Result<Vec<ReportScene>, String>;
+    /// Bookmarks on moments since `since`, the ones with notes first.
+    async fn get_run_bookmarks(
+        &self,
+        since: DateTime<Utc>,
+        limit: i64,
+    ) -> Result<Vec<ReportBookmark>, String>;
+}
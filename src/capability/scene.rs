@@ -1,6 +1,10 @@
 use crate::domain::actor_uuid::ActorUuid;
+use crate::domain::knowledge_boundary::Attendance;
+use crate::domain::message_audience::HearingRadius;
 use crate::domain::person_uuid::PersonUuid;
+use crate::domain::scene_ambience::Ambience;
 use crate::domain::scene_participant_uuid::SceneParticipantUuid;
+use crate::domain::world_time::{TimeOfDay, WorldTime};
 use crate::domain::{person_name::PersonName, scene_uuid::SceneUuid};
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
@@ -28,6 +32,26 @@ pub struct SceneParticipant {
     pub actor_uuid: ActorUuid,
 }
 
+impl SceneParticipant {
+    /// The AI persons among `participants`, leaving out `person_uuid`.
+    pub fn other_persons(
+        participants: &[SceneParticipant],
+        person_uuid: &PersonUuid,
+    ) -> Vec<PersonUuid> {
+        participants
+            .iter()
+            .filter_map(|participant| match &participant.actor_uuid {
+                ActorUuid::AiPerson(other_uuid)
+                    if other_uuid.to_uuid() != person_uuid.to_uuid() =>
+                {
+                    Some(other_uuid.clone())
+                }
+                _ => None,
+            })
+            .collect()
+    }
+}
+
 pub struct SceneParticipation {
     pub person_uuid: PersonUuid,
     pub joined_at: DateTime<Utc>,
@@ -74,15 +98,47 @@ pub trait SceneCapability {
         &self,
         scene_uuid: &SceneUuid,
     ) -> Result<Vec<SceneParticipation>, String>;
+    /// Every scene the person has been in and when, most recent first.
+    async fn get_persons_attendance(
+        &self,
+        person_uuid: &PersonUuid,
+    ) -> Result<Vec<Attendance>, String>;
     async fn set_real_world_user_in_scene(
         &self,
         scene_uuid: &SceneUuid,
         is_in_scene: bool,
     ) -> Result<(), String>;
     async fn is_real_world_user_in_scene(&self, scene_uuid: &SceneUuid) -> Result<bool, String>;
+    async fn get_scene_hearing_radius(
+        &self,
+        scene_uuid: &SceneUuid,
+    ) -> Result<HearingRadius, String>;
+    async fn set_scene_hearing_radius(
+        &self,
+        scene_uuid: &SceneUuid,
+        hearing_radius: HearingRadius,
+    ) -> Result<(), String>;
     async fn get_scene_name(&self, scene_uuid: &SceneUuid) -> Result<Option<String>, String>;
     async fn get_scene_description(&self, scene_uuid: &SceneUuid)
         -> Result<Option<String>, String>;
+    async fn set_scene_time_of_day(
+        &self,
+        scene_uuid: &SceneUuid,
+        time_of_day: TimeOfDay,
+    ) -> Result<(), String>;
+    async fn get_scene_world_time(&self, scene_uuid: &SceneUuid) -> Result<WorldTime, String>;
+    /// None until the scene's ambience is first set.
+    async fn get_scene_ambience(&self, scene_uuid: &SceneUuid) -> Result<Option<Ambience>, String>;
+    async fn set_scene_ambience(
+        &self,
+        scene_uuid: &SceneUuid,
+        ambience: Ambience,
+    ) -> Result<(), String>;
+    /// The chattiness of every person currently in the scene.
+    async fn get_scene_participant_chattiness(
+        &self,
+        scene_uuid: &SceneUuid,
+    ) -> Result<Vec<(PersonUuid, f64)>, String>;
 
     async fn create_scene_from_travel(
         &self,
@@ -0,0 +1,24 @@
+use crate::domain::annotation::Annotation;
+use crate::domain::scene_uuid::SceneUuid;
+
+pub trait SceneArchiveCapability {
+    /// Removes jobs about the scene that have not started yet. Returns how
+    /// many were removed.
+    async fn cancel_pending_scene_jobs(&self, scene_uuid: &SceneUuid) -> Result<u64, String>;
+    /// The annotations marked to be included in the exported transcript.
+    async fn get_exported_annotations(
+        &self,
+        scene_uuid: &SceneUuid,
+    ) -> Result<Vec<Annotation>, String>;
+    /// Writes the transcript into the archive directory and returns its path.
+    async fn export_scene_transcript(
+        &self,
+        file_name: &str,
+        transcript: &str,
+    ) -> Result<String, String>;
+    async fn mark_scene_archived(
+        &self,
+        scene_uuid: &SceneUuid,
+        transcript_path: &str,
+    ) -> Result<(), String>;
+}
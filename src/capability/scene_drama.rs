@@ -0,0 +1,24 @@
+use crate::domain::scene_uuid::SceneUuid;
+
+/// An active scene with its drama dial turned above zero and someone in it
+/// who could react.
+#[derive(Debug, Clone)]
+pub struct DramaticScene {
+    pub scene_uuid: SceneUuid,
+    pub intensity: f64,
+}
+
+pub trait SceneDramaCapability {
+    async fn get_dramatic_scenes(&self) -> Result<Vec<DramaticScene>, String>;
+    async fn get_scene_drama_intensity(&self, scene_uuid: &SceneUuid) -> Result<f64, String>;
+    async fn set_scene_drama_intensity(
+        &self,
+        scene_uuid: &SceneUuid,
+        intensity: f64,
+    ) -> Result<(), String>;
+    async fn add_scene_event(
+        &self,
+        scene_uuid: &SceneUuid,
+        description: &str,
+    ) -> Result<(), String>;
+}
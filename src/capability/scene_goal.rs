@@ -0,0 +1,37 @@
+use crate::domain::scene_goal::{OpenSceneGoal, SceneGoal, SceneGoalMet, TranscriptLine};
+use crate::domain::scene_uuid::SceneUuid;
+use chrono::{DateTime, Utc};
+
+pub trait SceneGoalCapability {
+    /// Replaces the scene's goal, met or not. An empty goal removes it.
+    async fn set_scene_goal(&self, scene_uuid: &SceneUuid, goal: &SceneGoal) -> Result<(), String>;
+    async fn get_scene_goal(&self, scene_uuid: &SceneUuid) -> Result<Option<SceneGoal>, String>;
+    /// Goals not yet met, in scenes that have not ended.
+    async fn get_open_scene_goals(&self) -> Result<Vec<OpenSceneGoal>, String>;
+    /// Everything said to the whole scene since `since`, oldest first.
+    async fn get_scene_transcript_since(
+        &self,
+        scene_uuid: &SceneUuid,
+        since: DateTime<Utc>,
+    ) -> Result<Vec<TranscriptLine>, String>;
+    /// Asks a cheap model whether everyone has agreed on `consensus_on`.
+    async fn judge_scene_consensus(
+        &self,
+        consensus_on: &str,
+        transcript: &str,
+    ) -> Result<bool, String>;
+    /// The description the scene is left with once it closes.
+    async fn summarize_scene_ending(
+        &self,
+        scene_description: &str,
+        transcript: &str,
+        met: &SceneGoalMet,
+    ) -> Result<String, String>;
+    /// Returns false when the goal was already met or removed, so a scene
+    /// is only closed once.
+    async fn mark_scene_goal_met(
+        &self,
+        scene_uuid: &SceneUuid,
+        met: &SceneGoalMet,
+    ) -> Result<bool, String>;
+}
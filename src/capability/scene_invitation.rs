@@ -0,0 +1,29 @@
+use crate::domain::person_uuid::PersonUuid;
+use crate::domain::scene_invitation::SceneInvitation;
+use crate::domain::scene_invitation_uuid::SceneInvitationUuid;
+use crate::domain::scene_uuid::SceneUuid;
+
+pub struct NewSceneInvitation {
+    pub scene_uuid: SceneUuid,
+    pub inviter_person_uuid: PersonUuid,
+    pub invitee_person_uuid: PersonUuid,
+}
+
+pub trait SceneInvitationCapability {
+    async fn create_scene_invitation(
+        &self,
+        new_invitation: NewSceneInvitation,
+    ) -> Result<SceneInvitationUuid, String>;
+    /// Oldest first.
+    async fn get_pending_scene_invitations(
+        &self,
+        person_uuid: &PersonUuid,
+    ) -> Result<Vec<SceneInvitation>, String>;
+    /// Answers everything the person has pending. Invitations to the scene
+    /// they are now in are accepted and the rest declined.
+    async fn respond_to_scene_invitations(
+        &self,
+        person_uuid: &PersonUuid,
+        current_scene_uuid: Option<&SceneUuid>,
+    ) -> Result<(), String>;
+}
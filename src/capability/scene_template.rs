@@ -0,0 +1,10 @@
+use crate::domain::scene_template::SceneTemplate;
+
+pub trait SceneTemplateCapability {
+    /// Creates the template, or replaces the one with the same name.
+    async fn save_scene_template(&self, template: SceneTemplate) -> Result<(), String>;
+
+    async fn get_scene_templates(&self) -> Result<Vec<SceneTemplate>, String>;
+
+    async fn delete_scene_template(&self, template_name: &str) -> Result<(), String>;
+}
@@ -0,0 +1,10 @@
+use crate::domain::scene_timeline::{TimelinePage, TimelineQuery};
+use crate::domain::scene_uuid::SceneUuid;
+
+pub trait SceneTimelineCapability {
+    async fn get_scene_timeline(
+        &self,
+        scene_uuid: &SceneUuid,
+        query: TimelineQuery,
+    ) -> Result<TimelinePage, String>;
+}
@@ -0,0 +1,15 @@
+use crate::domain::scene_tone::ToneProfile;
+use crate::domain::scene_uuid::SceneUuid;
+
+pub trait SceneToneCapability {
+    async fn get_scene_tone_profile(
+        &self,
+        scene_uuid: &SceneUuid,
+    ) -> Result<Option<ToneProfile>, String>;
+    /// `None` goes back to the world's voice and moderation threshold.
+    async fn set_scene_tone_profile(
+        &self,
+        scene_uuid: &SceneUuid,
+        tone_profile: Option<ToneProfile>,
+    ) -> Result<(), String>;
+}
@@ -0,0 +1,9 @@
+use crate::domain::schema_overview::{AppliedMigration, IndexStats, TableStats};
+
+pub trait SchemaCapability {
+    /// Oldest first. Empty until `run-migrations` has recorded a run.
+    async fn get_applied_migrations(&self) -> Result<Vec<AppliedMigration>, String>;
+    /// Largest first.
+    async fn get_table_stats(&self) -> Result<Vec<TableStats>, String>;
+    async fn get_index_stats(&self) -> Result<Vec<IndexStats>, String>;
+}
@@ -0,0 +1,18 @@
+use crate::domain::person_uuid::PersonUuid;
+use crate::domain::style_guide::StyleGuide;
+
+pub trait StyleGuideCapability {
+    async fn get_world_style_guide(&self) -> Result<StyleGuide, String>;
+
+    async fn set_world_style_guide(&self, style_guide: &StyleGuide) -> Result<(), String>;
+
+    /// Only what this person overrides. Merge it over the world's with
+    /// `StyleGuide::overridden_by`.
+    async fn get_person_style_guide(&self, person_uuid: &PersonUuid) -> Result<StyleGuide, String>;
+
+    async fn set_person_style_guide(
+        &self,
+        person_uuid: &PersonUuid,
+        style_guide: &StyleGuide,
+    ) -> Result<(), String>;
+}
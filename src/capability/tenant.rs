@@ -0,0 +1,36 @@
+use crate::domain::scene_uuid::SceneUuid;
+use crate::domain::tenant::{ApiRole, ApiTokenGrant, Tenant, TenantName};
+use crate::domain::tenant_uuid::TenantUuid;
+
+pub trait TenantCapability {
+    async fn create_tenant(&self, name: &TenantName) -> Result<TenantUuid, String>;
+    async fn get_tenants(&self) -> Result<Vec<Tenant>, String>;
+    async fn get_tenant_uuid_by_name(
+        &self,
+        name: &TenantName,
+    ) -> Result<Option<TenantUuid>, String>;
+    async fn add_api_token(
+        &self,
+        tenant_uuid: &TenantUuid,
+        token_hash: &str,
+        role: ApiRole,
+    ) -> Result<(), String>;
+    /// Revokes every token the tenant has. Returns how many were revoked.
+    async fn revoke_api_tokens(&self, tenant_uuid: &TenantUuid) -> Result<u64, String>;
+    async fn add_operator_token(&self, token_hash: &str) -> Result<(), String>;
+    /// Revokes every operator token. Returns how many were revoked.
+    async fn revoke_operator_tokens(&self) -> Result<u64, String>;
+    /// The tenant an unrevoked token belongs to and its role, or that it is
+    /// an operator's.
+    async fn get_api_token_grant(&self, token_hash: &str) -> Result<Option<ApiTokenGrant>, String>;
+    async fn set_scene_tenant(
+        &self,
+        scene_uuid: &SceneUuid,
+        tenant_uuid: Option<&TenantUuid>,
+    ) -> Result<(), String>;
+    async fn is_scene_in_tenant(
+        &self,
+        scene_uuid: &SceneUuid,
+        tenant_uuid: &TenantUuid,
+    ) -> Result<bool, String>;
+}
@@ -0,0 +1,25 @@
+use crate::domain::scene_uuid::SceneUuid;
+use crate::domain::topic::{Topic, TopicCount, TopicScene, TrendingTopic, UntaggedScene};
+use chrono::{DateTime, Utc};
+
+pub trait TopicCapability {
+    /// Scenes with messages newer than the last time their topics were tagged.
+    async fn get_untagged_scenes(&self) -> Result<Vec<UntaggedScene>, String>;
+    /// Adds the mentions to the scene's topics, and remembers that messages
+    /// up to `tagged_through` have been read.
+    async fn record_scene_topics(
+        &self,
+        scene_uuid: &SceneUuid,
+        topics: &[TopicCount],
+        tagged_through: DateTime<Utc>,
+    ) -> Result<(), String>;
+    /// The most mentioned topics across scenes, counting only topics
+    /// mentioned since `since` when it is given.
+    async fn get_trending_topics(
+        &self,
+        since: Option<DateTime<Utc>>,
+        limit: i64,
+    ) -> Result<Vec<TrendingTopic>, String>;
+    /// The scenes that have talked about a topic, most recent first.
+    async fn get_topic_scenes(&self, topic: &Topic) -> Result<Vec<TopicScene>, String>;
+}
@@ -0,0 +1,14 @@
+use crate::domain::scene_uuid::SceneUuid;
+use crate::domain::utterance::Utterance;
+use chrono::{DateTime, Utc};
+
+pub trait UtteranceCapability {
+    /// The newest utterances in a scene sent before `before`, newest first.
+    /// Revised messages are left out in favor of their revisions.
+    async fn get_scene_utterances(
+        &self,
+        scene_uuid: &SceneUuid,
+        limit: i64,
+        before: Option<DateTime<Utc>>,
+    ) -> Result<Vec<Utterance>, String>;
+}
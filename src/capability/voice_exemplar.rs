@@ -0,0 +1,15 @@
+use crate::domain::person_uuid::PersonUuid;
+use crate::domain::voice_exemplar::{VoiceExemplarComparison, VoiceExemplarMode};
+
+pub trait VoiceExemplarCapability {
+    async fn get_voice_exemplars(&self, person_uuid: &PersonUuid) -> Result<Vec<String>, String>;
+    async fn set_voice_exemplars(
+        &self,
+        person_uuid: &PersonUuid,
+        exemplars: &[String],
+    ) -> Result<(), String>;
+    async fn get_voice_exemplar_mode(&self) -> Result<VoiceExemplarMode, String>;
+    async fn set_voice_exemplar_mode(&self, mode: VoiceExemplarMode) -> Result<(), String>;
+    /// How reactions with the exemplars fared against those without.
+    async fn get_voice_exemplar_comparison(&self) -> Result<VoiceExemplarComparison, String>;
+}
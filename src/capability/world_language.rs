@@ -0,0 +1,7 @@
+use crate::world_language::WorldLanguage;
+
+pub trait WorldLanguageCapability {
+    async fn get_world_language(&self) -> Result<WorldLanguage, String>;
+
+    async fn set_world_language(&self, language: WorldLanguage) -> Result<(), String>;
+}
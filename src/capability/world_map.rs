@@ -0,0 +1,5 @@
+use crate::domain::world_map::WorldMap;
+
+pub trait WorldMapCapability {
+    async fn get_world_map(&self) -> Result<WorldMap, String>;
+}
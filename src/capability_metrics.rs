@@ -0,0 +1,206 @@
+pub mod chaos;
+pub mod endpoint;
+mod metered_worker;
+
+pub use metered_worker::MeteredWorker;
+
+use std::collections::HashMap;
+use std::sync::{Mutex, MutexGuard};
+use std::time::Duration;
+
+/// Set to `1` or `true` to wrap the job runner's worker in a `MeteredWorker`.
+pub const ENABLED_VAR: &str = "CAPABILITY_METRICS";
+
+/// How many of the slowest methods the periodic summary lists.
+pub const SUMMARY_LIMIT: usize = 15;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MethodStats {
+    pub calls: u64,
+    pub errors: u64,
+    pub total: Duration,
+    pub max: Duration,
+}
+
+/// Per-method call counts and latencies for capability calls, keyed by
+/// `trait.method`, e.g. `job.pop_next_job`.
+#[derive(Debug, Default)]
+pub struct CapabilityMetrics {
+    methods: Mutex<HashMap<&'static str, MethodStats>>,
+}
+
+pub fn enabled_from_env() -> bool {
+    match dotenv::var(ENABLED_VAR) {
+        Ok(value) => {
+            let value = value.trim().to_lowercase();
+            value == "1" || value == "true"
+        }
+        Err(_) => false,
+    }
+}
+
+impl MethodStats {
+    pub fn mean(&self) -> Duration {
+        match u32::try_from(self.calls) {
+            Ok(0) => Duration::ZERO,
+            Ok(calls) => self.total / calls,
+            Err(_) => Duration::from_secs_f64(self.total.as_secs_f64() / self.calls as f64),
+        }
+    }
+}
+
+impl CapabilityMetrics {
+    pub fn new() -> Self {
+        CapabilityMetrics::default()
+    }
+
+    pub fn record(&self, method: &'static str, elapsed: Duration, is_error: bool) {
+        let mut methods = self.lock();
+        let stats = methods.entry(method).or_default();
+
+        stats.calls += 1;
+        if is_error {
+            stats.errors += 1;
+        }
+        stats.total += elapsed;
+        stats.max = stats.max.max(elapsed);
+    }
+
+    /// Every method seen so far, the ones that took the most time overall first.
+    pub fn snapshot(&self) -> Vec<(&'static str, MethodStats)> {
+        let mut snapshot = self
+            .lock()
+            .iter()
+            .map(|(method, stats)| (*method, stats.clone()))
+            .collect::<Vec<(&'static str, MethodStats)>>();
+
+        snapshot.sort_by(|(a_method, a), (b_method, b)| {
+            b.total.cmp(&a.total).then_with(|| a_method.cmp(b_method))
+        });
+
+        snapshot
+    }
+
+    /// Renders the metrics in the Prometheus text exposition format, as the
+    /// job runner serves them at `/metrics`.
+    pub fn to_prometheus_text(&self) -> String {
+        let snapshot = self.snapshot();
+        let mut lines = vec![
+            "# HELP arizona2_capability_calls_total Capability method calls.".to_string(),
+            "# TYPE arizona2_capability_calls_total counter".to_string(),
+        ];
+        for (method, stats) in snapshot.iter() {
+            lines.push(format!(
+                "arizona2_capability_calls_total{{method=\"{}\"}} {}",
+                method, stats.calls
+            ));
+        }
+
+        lines.push(
+            "# HELP arizona2_capability_errors_total Capability method calls that returned an error."
+                .to_string(),
+        );
+        lines.push("# TYPE arizona2_capability_errors_total counter".to_string());
+        for (method, stats) in snapshot.iter() {
+            lines.push(format!(
+                "arizona2_capability_errors_total{{method=\"{}\"}} {}",
+                method, stats.errors
+            ));
+        }
+
+        lines.push(
+            "# HELP arizona2_capability_seconds_total Time spent in capability methods."
+                .to_string(),
+        );
+        lines.push("# TYPE arizona2_capability_seconds_total counter".to_string());
+        for (method, stats) in snapshot.iter() {
+            lines.push(format!(
+                "arizona2_capability_seconds_total{{method=\"{}\"}} {:.6}",
+                method,
+                stats.total.as_secs_f64()
+            ));
+        }
+
+        lines.push(
+            "# HELP arizona2_capability_max_seconds Slowest single call of a capability method."
+                .to_string(),
+        );
+        lines.push("# TYPE arizona2_capability_max_seconds gauge".to_string());
+        for (method, stats) in snapshot.iter() {
+            lines.push(format!(
+                "arizona2_capability_max_seconds{{method=\"{}\"}} {:.6}",
+                method,
+                stats.max.as_secs_f64()
+            ));
+        }
+
+        lines.push(String::new());
+        lines.join("\n")
+    }
+
+    /// A short human readable table of the methods that dominate the run.
+    pub fn to_summary(&self, limit: usize) -> String {
+        let snapshot = self.snapshot();
+
+        if snapshot.is_empty() {
+            return "No capability calls recorded".to_string();
+        }
+
+        let mut lines = vec![format!(
+            "Capability calls, by total time ({} methods)",
+            snapshot.len()
+        )];
+        for (method, stats) in snapshot.iter().take(limit) {
+            lines.push(format!(
+                "{}: {} calls, {} errors, {}ms total, {}ms mean, {}ms max",
+                method,
+                stats.calls,
+                stats.errors,
+                stats.total.as_millis(),
+                stats.mean().as_millis(),
+                stats.max.as_millis()
+            ));
+        }
+
+        lines.join("\n")
+    }
+
+    fn lock(&self) -> MutexGuard<'_, HashMap<&'static str, MethodStats>> {
+        // A panic while recording leaves the counts usable, so keep going
+        match self.methods.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_metrics_rank_methods_by_total_time() {
+        let metrics = CapabilityMetrics::new();
+        metrics.record("job.pop_next_job", Duration::from_millis(5), false);
+        metrics.record("job.pop_next_job", Duration::from_millis(7), true);
+        metrics.record("reaction.get_reaction", Duration::from_millis(900), false);
+
+        let snapshot = metrics.snapshot();
+
+        assert_eq!(snapshot[0].0, "reaction.get_reaction");
+        assert_eq!(
+            snapshot[1].1,
+            MethodStats {
+                calls: 2,
+                errors: 1,
+                total: Duration::from_millis(12),
+                max: Duration::from_millis(7),
+            }
+        );
+        assert_eq!(snapshot[1].1.mean(), Duration::from_millis(6));
+
+        let text = metrics.to_prometheus_text();
+        assert!(text.contains("arizona2_capability_calls_total{method=\"job.pop_next_job\"} 2"));
+        assert!(text.contains("arizona2_capability_errors_total{method=\"job.pop_next_job\"} 1"));
+    }
+}
@@ -0,0 +1,176 @@
+use rand::rngs::SmallRng;
+use rand::{Rng, SeedableRng};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+
+/// Set to a rate like `0.05` to fail that share of capability calls in the
+/// job runner on purpose.
+pub const FAULT_RATE_VAR: &str = "CHAOS_FAULT_RATE";
+
+/// Makes a chaos run repeatable. Random when not set.
+pub const SEED_VAR: &str = "CHAOS_SEED";
+
+/// Methods that call OpenAI, which fail with timeouts and bad tool calls
+/// instead of database errors.
+const LLM_METHODS: [&str; 15] = [
+    "reaction.summarize_reaction_events",
+    "reaction.get_reaction",
+    "reaction.infer_person_task_to_adopt",
+    "reaction.classify_current_task_outcome",
+    "reaction.infer_updated_task_state",
+    "memory.maybe_create_memories_from_description",
+    "memory.create_memory_query_prompt",
+    "moderation.moderate_content",
+    "person_identity.summarize_person_identity",
+    "persona_consistency.judge_persona_consistency",
+    "arrival_observation.summarize_arrival_observation",
+    "reflection.get_reflection_changes",
+    "scene_goal.judge_scene_consensus",
+    "scene_goal.summarize_scene_ending",
+    "llm_batch.submit_llm_batch",
+];
+
+/// The runner's own bookkeeping is left alone, so chaos runs test how jobs
+/// recover rather than how the queue survives losing its own state.
+const EXEMPT_PREFIXES: [&str; 2] = ["job.", "log_event."];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FaultKind {
+    LlmTimeout,
+    MalformedToolCall,
+    Database,
+}
+
+impl FaultKind {
+    pub fn to_error(self, method: &str) -> String {
+        let details = match self {
+            FaultKind::LlmTimeout => "OpenAI request timed out",
+            FaultKind::MalformedToolCall => "OpenAI returned a tool call that is not valid json",
+            FaultKind::Database => "database connection was reset",
+        };
+
+        format!("Injected fault in {}: {}", method, details)
+    }
+}
+
+/// Decides which capability calls fail during a chaos run.
+#[derive(Debug)]
+pub struct FaultInjector {
+    rate: f64,
+    rng: Mutex<SmallRng>,
+    injected: AtomicU64,
+}
+
+impl FaultInjector {
+    pub fn new(rate: f64, seed: u64) -> Self {
+        FaultInjector {
+            rate,
+            rng: Mutex::new(SmallRng::seed_from_u64(seed)),
+            injected: AtomicU64::new(0),
+        }
+    }
+
+    /// `None` unless `CHAOS_FAULT_RATE` is set.
+    pub fn from_env() -> Result<Option<Self>, String> {
+        let rate = match dotenv::var(FAULT_RATE_VAR) {
+            Ok(value) => match value.trim().parse::<f64>() {
+                Ok(rate) if rate > 0.0 && rate <= 1.0 => rate,
+                _ => {
+                    return Err(format!(
+                        "{} must be a number above 0 and at most 1, but it was \"{}\"",
+                        FAULT_RATE_VAR, value
+                    ))
+                }
+            },
+            Err(_) => return Ok(None),
+        };
+
+        let seed = match dotenv::var(SEED_VAR) {
+            Ok(value) => value.trim().parse::<u64>().map_err(|_| {
+                format!(
+                    "{} must be a whole number, but it was \"{}\"",
+                    SEED_VAR, value
+                )
+            })?,
+            Err(_) => rand::random::<u64>(),
+        };
+
+        Ok(Some(FaultInjector::new(rate, seed)))
+    }
+
+    pub fn rate(&self) -> f64 {
+        self.rate
+    }
+
+    /// The error `method` should fail with this time, if any.
+    pub fn maybe_fault(&self, method: &str) -> Option<String> {
+        if EXEMPT_PREFIXES
+            .iter()
+            .any(|prefix| method.starts_with(prefix))
+        {
+            return None;
+        }
+
+        let mut rng = match self.rng.lock() {
+            Ok(rng) => rng,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+
+        if !rng.gen_bool(self.rate) {
+            return None;
+        }
+
+        let kind = if !LLM_METHODS.contains(&method) {
+            FaultKind::Database
+        } else if rng.gen_bool(0.5) {
+            FaultKind::LlmTimeout
+        } else {
+            FaultKind::MalformedToolCall
+        };
+
+        self.injected.fetch_add(1, Ordering::Relaxed);
+        Some(kind.to_error(method))
+    }
+
+    pub fn injected(&self) -> u64 {
+        self.injected.load(Ordering::Relaxed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_never_faults_the_runners_bookkeeping() {
+        let injector = FaultInjector::new(1.0, 0);
+
+        assert_eq!(injector.maybe_fault("job.mark_job_failed"), None);
+        assert_eq!(injector.maybe_fault("log_event.log_event"), None);
+        assert_eq!(injector.injected(), 0);
+    }
+
+    #[test]
+    fn test_database_methods_get_database_errors() {
+        let injector = FaultInjector::new(1.0, 0);
+
+        assert_eq!(
+            injector.maybe_fault("scene.get_scenes"),
+            Some(FaultKind::Database.to_error("scene.get_scenes"))
+        );
+        assert_eq!(injector.injected(), 1);
+    }
+
+    #[test]
+    fn test_same_seed_fails_the_same_calls() {
+        let first = FaultInjector::new(0.3, 7);
+        let second = FaultInjector::new(0.3, 7);
+
+        for _ in 0..50 {
+            assert_eq!(
+                first.maybe_fault("reaction.get_reaction"),
+                second.maybe_fault("reaction.get_reaction")
+            );
+        }
+    }
+}
@@ -0,0 +1,43 @@
+use super::CapabilityMetrics;
+use actix_web::{web, App, HttpResponse, HttpServer};
+use std::sync::Arc;
+
+/// Where the job runner serves `/metrics` while capability metrics are on,
+/// like `0.0.0.0:9464`.
+pub const ADDRESS_VAR: &str = "CAPABILITY_METRICS_ADDRESS";
+
+pub const DEFAULT_ADDRESS: &str = "127.0.0.1:9464";
+
+pub fn address_from_env() -> String {
+    match dotenv::var(ADDRESS_VAR) {
+        Ok(address) if !address.trim().is_empty() => address.trim().to_string(),
+        _ => DEFAULT_ADDRESS.to_string(),
+    }
+}
+
+/// Serves `GET /metrics` in the Prometheus text format from a small server
+/// next to the job runner's loop. The job runner handles ctrl c itself, and
+/// the server goes away when it exits.
+pub fn serve(metrics: Arc<CapabilityMetrics>, address: &str) -> Result<(), String> {
+    let metrics = web::Data::from(metrics);
+
+    let server = HttpServer::new(move || {
+        App::new()
+            .app_data(metrics.clone())
+            .route("/metrics", web::get().to(get_metrics))
+    })
+    .workers(1)
+    .disable_signals()
+    .bind(address)
+    .map_err(|err| format!("Could not listen on {}: {}", address, err))?
+    .run();
+
+    actix_web::rt::spawn(server);
+    Ok(())
+}
+
+async fn get_metrics(metrics: web::Data<CapabilityMetrics>) -> HttpResponse {
+    HttpResponse::Ok()
+        .content_type("text/plain; version=0.0.4")
+        .body(metrics.to_prometheus_text())
+}
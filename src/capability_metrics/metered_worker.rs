@@ -0,0 +1,904 @@
+use super::chaos::FaultInjector;
+use super::CapabilityMetrics;
+use crate::capability::acknowledgement::AcknowledgementCapability;
+use crate::capability::arrival_observation::ArrivalObservationCapability;
+use crate::capability::autobiography::AutobiographyCapability;
+use crate::capability::clock::ClockCapability;
+use crate::capability::custom_action::CustomActionCapability;
+use crate::capability::event::{EventCapability, GetArgs};
+use crate::capability::expected_reply::{
+    ExpectedReplyCapability, ExpectedReplyOutcome, NewExpectedReply,
+};
+use crate::capability::external_tool::{ExternalToolCapability, NewExternalToolCall};
+use crate::capability::idle_person::{IdlePerson, IdlePersonCapability};
+use crate::capability::item::{ItemCapability, NewItem};
+use crate::capability::job::JobCapability;
+use crate::capability::llm_batch::LlmBatchCapability;
+use crate::capability::log_event::LogEventCapability;
+use crate::capability::logging::LogCapability;
+use crate::capability::maintenance::MaintenanceCapability;
+use crate::capability::memory::{
+    MemoryCapability, MemoryQueryPrompt, MemorySearchResult, MessageTypeArgs, NewMemory,
+};
+use crate::capability::message::MessageCapability;
+use crate::capability::moderation::{BlockedContent, ModerationCapability};
+use crate::capability::motivation::{MotivationCapability, NewMotivation};
+use crate::capability::outbox::OutboxCapability;
+use crate::capability::person::{NewPerson, PersonCapability};
+use crate::capability::person_arc::{NewPersonArc, PersonArcCapability};
+use crate::capability::person_identity::{NewPersonIdentity, PersonIdentityCapability};
+use crate::capability::person_task::{NewPersonTask, PersonTaskCapability};
+use crate::capability::persona_consistency::{
+    PersonaConsistencyCapability, RecordedPersonaInconsistency,
+};
+use crate::capability::reaction::{ReactionCapability, ReactionPromptPreview};
+use crate::capability::reaction_history::ReactionHistoryCapability;
+use crate::capability::reflection::{ReflectionCapability, ReflectionChange};
+use crate::capability::scene::{
+    CurrentScene, NewScene, NewSceneSnapshot, Scene, SceneCapability, SceneParticipant,
+    SceneParticipation,
+};
+use crate::capability::scene_archive::SceneArchiveCapability;
+use crate::capability::scene_drama::{DramaticScene, SceneDramaCapability};
+use crate::capability::scene_goal::SceneGoalCapability;
+use crate::capability::scene_invitation::{NewSceneInvitation, SceneInvitationCapability};
+use crate::capability::state_of_mind::{NewStateOfMind, StateOfMindCapability};
+use crate::capability::topic::TopicCapability;
+use crate::domain::annotation::Annotation;
+use crate::domain::autobiography::LifeEvent;
+use crate::domain::event::Event;
+use crate::domain::external_tool::ExternalTool;
+use crate::domain::fan_out::QueuePressure;
+use crate::domain::item::Item;
+use crate::domain::item_uuid::ItemUuid;
+use crate::domain::job::nightly_maintenance::{MaintenanceReport, PrunedRows, RecentMemories};
+use crate::domain::job::{Job, JobKind, PoppedJob};
+use crate::domain::job_event::{JobEvent, NewJobEvent};
+use crate::domain::job_uuid::JobUuid;
+use crate::domain::knowledge_boundary::Attendance;
+use crate::domain::logger::Level;
+use crate::domain::memory::{Memory, MemoryRetrieval};
+use crate::domain::memory_uuid::MemoryUuid;
+use crate::domain::message::{Message, MessageSender};
+use crate::domain::message_audience::{HearingRadius, MessageAudience};
+use crate::domain::message_quote::MessageQuote;
+use crate::domain::message_uuid::MessageUuid;
+use crate::domain::moderation::ModerationVerdict;
+use crate::domain::motivation::Motivation;
+use crate::domain::motivation_uuid::MotivationUuid;
+use crate::domain::outbox::{OutboxEntry, OutboxEvent};
+use crate::domain::outbox_uuid::OutboxUuid;
+use crate::domain::person_arc::{ArcDraft, PersonArc};
+use crate::domain::person_directory::PersonDirectoryEntry;
+use crate::domain::person_identity_uuid::PersonIdentityUuid;
+use crate::domain::person_name::PersonName;
+use crate::domain::person_task::{PersonTask, PersonTaskOutcomeCheck, PersonTaskTerminalOutcome};
+use crate::domain::person_task_uuid::PersonTaskUuid;
+use crate::domain::person_uuid::PersonUuid;
+use crate::domain::persona_consistency::PersonaInconsistency;
+use crate::domain::scene_ambience::Ambience;
+use crate::domain::scene_goal::{OpenSceneGoal, SceneGoal, SceneGoalMet, TranscriptLine};
+use crate::domain::scene_invitation::SceneInvitation;
+use crate::domain::scene_invitation_uuid::SceneInvitationUuid;
+use crate::domain::scene_participant_uuid::SceneParticipantUuid;
+use crate::domain::scene_uuid::SceneUuid;
+use crate::domain::state_of_mind::StateOfMind;
+use crate::domain::state_of_mind_uuid::StateOfMindUuid;
+use crate::domain::talk_share::TalkCount;
+use crate::domain::topic::{Topic, TopicCount, TopicScene, TrendingTopic, UntaggedScene};
+use crate::domain::world_time::{TimeOfDay, WorldTime};
+use crate::open_ai::batch::{Batch, BatchRequest, BatchResult};
+use crate::person_actions::PersonReaction;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Wraps a worker and times every capability call that passes through it,
+/// so a run can show which database and OpenAI operations dominate. With a
+/// fault injector it also fails some of those calls on purpose.
+#[derive(Clone, Debug)]
+pub struct MeteredWorker<W> {
+    pub inner: W,
+    pub metrics: Arc<CapabilityMetrics>,
+    pub chaos: Option<Arc<FaultInjector>>,
+}
+
+impl<W> MeteredWorker<W> {
+    pub fn new(inner: W, metrics: Arc<CapabilityMetrics>) -> Self {
+        MeteredWorker {
+            inner,
+            metrics,
+            chaos: None,
+        }
+    }
+
+    pub fn with_chaos(mut self, chaos: Arc<FaultInjector>) -> Self {
+        self.chaos = Some(chaos);
+        self
+    }
+
+    async fn timed<T>(
+        &self,
+        method: &'static str,
+        call: impl Future<Output = Result<T, String>>,
+    ) -> Result<T, String> {
+        // Injected faults skip the real call, like a request that never landed
+        if let Some(fault) = self
+            .chaos
+            .as_ref()
+            .and_then(|chaos| chaos.maybe_fault(method))
+        {
+            self.metrics.record(method, Duration::ZERO, true);
+            return Err(fault);
+        }
+
+        let start = Instant::now();
+        let result = call.await;
+        self.metrics
+            .record(method, start.elapsed(), result.is_err());
+        result
+    }
+}
+
+/// Implements a capability for `MeteredWorker` by handing every method to
+/// the inner worker. Async methods are timed as `prefix.method`, and plain
+/// ones, which do no io, are handed over as they are.
+macro_rules! metered {
+    (
+        $(#[$attr:meta])*
+        impl $capability:ident $(+ $bound:ident)* as $prefix:literal {
+            $(fn $plain:ident(&self $(, $plain_arg:ident: $plain_arg_ty:ty)* $(,)?) -> $plain_output:ty;)*
+            $(async fn $method:ident(&self $(, $arg:ident: $arg_ty:ty)* $(,)?) -> $output:ty;)*
+        }
+    ) => {
+        $(#[$attr])*
+        impl<W: $capability $(+ $bound)*> $capability for MeteredWorker<W> {
+            $(
+                fn $plain(&self $(, $plain_arg: $plain_arg_ty)*) -> $plain_output {
+                    self.inner.$plain($($plain_arg),*)
+                }
+            )*
+
+            $(
+                async fn $method(&self $(, $arg: $arg_ty)*) -> $output {
+                    self.timed(
+                        concat!($prefix, ".", stringify!($method)),
+                        self.inner.$method($($arg),*),
+                    )
+                    .await
+                }
+            )*
+        }
+    };
+}
+
+impl<W: ClockCapability> ClockCapability for MeteredWorker<W> {
+    fn now(&self) -> DateTime<Utc> {
+        self.inner.now()
+    }
+}
+
+metered! {
+    impl JobCapability as "job" {
+        async fn unshift_job(&self, job: JobKind) -> Result<(), String>;
+        async fn enqueue_job(&self, job: JobKind) -> Result<JobUuid, String>;
+        async fn pop_next_job(&self, current_active_ms: i64) -> Result<Option<PoppedJob>, String>;
+        async fn recent_jobs(&self, limit: i64) -> Result<Vec<Job>, String>;
+        async fn get_job_by_uuid(&self, job_uuid: &JobUuid) -> Result<Option<Job>, String>;
+        async fn mark_job_finished(&self, job_uuid: &JobUuid) -> Result<(), String>;
+        async fn mark_job_failed(&self, job_uuid: &JobUuid, details: &str) -> Result<(), String>;
+        async fn reset_job(&self, job_uuid: &JobUuid) -> Result<(), String>;
+        async fn reset_all_failed_jobs(&self) -> Result<(), String>;
+        async fn delete_job(&self, job_uuid: &JobUuid) -> Result<(), String>;
+        async fn record_job_event(
+            &self,
+            job_uuid: &JobUuid,
+            event: &NewJobEvent,
+        ) -> Result<(), String>;
+        async fn get_job_events(&self, job_uuid: &JobUuid) -> Result<Vec<JobEvent>, String>;
+        async fn get_queue_pressure(&self) -> Result<QueuePressure, String>;
+        async fn get_next_delayed_job_active_ms(
+            &self,
+            current_active_ms: i64,
+        ) -> Result<Option<i64>, String>;
+        async fn get_persons_wait_end_active_ms(
+            &self,
+            person_uuid: &PersonUuid,
+        ) -> Result<Option<i64>, String>;
+    }
+}
+
+metered! {
+    impl MessageCapability as "message" {
+        async fn send_scene_message(
+            &self,
+            sender: MessageSender,
+            scene_uuid: SceneUuid,
+            content: String,
+        ) -> Result<MessageUuid, String>;
+        async fn add_scene_message_recipients(
+            &self,
+            message_uuid: &MessageUuid,
+            recipients: Vec<PersonUuid>,
+        ) -> Result<(), String>;
+        async fn set_message_audience(
+            &self,
+            message_uuid: &MessageUuid,
+            audience: &MessageAudience,
+        ) -> Result<(), String>;
+        async fn add_scene_message_overhearers(
+            &self,
+            message_uuid: &MessageUuid,
+            overhearers: Vec<PersonUuid>,
+        ) -> Result<(), String>;
+        async fn get_messages_in_scene_page(
+            &self,
+            scene_uuid: &SceneUuid,
+            limit: i64,
+            before_sent_at: Option<DateTime<Utc>>,
+        ) -> Result<Vec<Message>, String>;
+        async fn get_message_by_uuid(
+            &self,
+            message_uuid: &MessageUuid,
+        ) -> Result<Option<Message>, String>;
+        async fn get_unhandled_scene_messages_for_person(
+            &self,
+            person_uuid: &PersonUuid,
+            scene_uuid: &SceneUuid,
+        ) -> Result<Vec<Message>, String>;
+        async fn mark_scene_messages_handled_for_person(
+            &self,
+            person_uuid: &PersonUuid,
+            message_uuids: Vec<MessageUuid>,
+        ) -> Result<(), String>;
+        async fn set_message_quote(
+            &self,
+            message_uuid: &MessageUuid,
+            quote: &MessageQuote,
+        ) -> Result<(), String>;
+        async fn get_message_quotes(
+            &self,
+            message_uuids: Vec<MessageUuid>,
+        ) -> Result<HashMap<MessageUuid, MessageQuote>, String>;
+    }
+}
+
+metered! {
+    impl ModerationCapability as "moderation" {
+        async fn moderate_content(
+            &self,
+            sender: &MessageSender,
+            scene_uuid: &SceneUuid,
+            content: &str,
+        ) -> Result<ModerationVerdict, String>;
+        async fn get_blocked_content(&self, limit: i64) -> Result<Vec<BlockedContent>, String>;
+        async fn get_moderation_threshold(&self) -> Result<f64, String>;
+        async fn set_moderation_threshold(&self, threshold: f64) -> Result<(), String>;
+    }
+}
+
+metered! {
+    #[async_trait]
+    impl SceneCapability + Send + Sync as "scene" {
+        async fn create_scene(&self, new_scene: NewScene) -> Result<SceneUuid, String>;
+        async fn delete_scene(&self, scene_uuid: &SceneUuid) -> Result<(), String>;
+        async fn get_scenes(&self) -> Result<Vec<Scene>, String>;
+        async fn add_person_to_scene(
+            &self,
+            scene_uuid: SceneUuid,
+            person_name: PersonName,
+        ) -> Result<SceneParticipantUuid, String>;
+        async fn remove_person_from_scene(
+            &self,
+            scene_uuid: SceneUuid,
+            person_name: PersonName,
+        ) -> Result<SceneParticipantUuid, String>;
+        async fn get_persons_current_scene(
+            &self,
+            person_name: PersonName,
+        ) -> Result<Option<CurrentScene>, String>;
+        async fn get_persons_current_scene_uuid(
+            &self,
+            person_uuid: &PersonUuid,
+        ) -> Result<Option<SceneUuid>, String>;
+        async fn create_scene_snapshot(
+            &self,
+            new_scene_snapshot: NewSceneSnapshot,
+        ) -> Result<(), String>;
+        async fn get_scene_from_name(&self, scene_name: String) -> Result<Option<Scene>, String>;
+        async fn get_scene_current_participants(
+            &self,
+            scene_uuid: &SceneUuid,
+        ) -> Result<Vec<SceneParticipant>, String>;
+        async fn get_scene_participation_history(
+            &self,
+            scene_uuid: &SceneUuid,
+        ) -> Result<Vec<SceneParticipation>, String>;
+        async fn get_persons_attendance(
+            &self,
+            person_uuid: &PersonUuid,
+        ) -> Result<Vec<Attendance>, String>;
+        async fn set_real_world_user_in_scene(
+            &self,
+            scene_uuid: &SceneUuid,
+            is_in_scene: bool,
+        ) -> Result<(), String>;
+        async fn is_real_world_user_in_scene(&self, scene_uuid: &SceneUuid) -> Result<bool, String>;
+        async fn get_scene_hearing_radius(
+            &self,
+            scene_uuid: &SceneUuid,
+        ) -> Result<HearingRadius, String>;
+        async fn set_scene_hearing_radius(
+            &self,
+            scene_uuid: &SceneUuid,
+            hearing_radius: HearingRadius,
+        ) -> Result<(), String>;
+        async fn get_scene_name(&self, scene_uuid: &SceneUuid) -> Result<Option<String>, String>;
+        async fn get_scene_description(
+            &self,
+            scene_uuid: &SceneUuid,
+        ) -> Result<Option<String>, String>;
+        async fn set_scene_time_of_day(
+            &self,
+            scene_uuid: &SceneUuid,
+            time_of_day: TimeOfDay,
+        ) -> Result<(), String>;
+        async fn get_scene_ambience(
+            &self,
+            scene_uuid: &SceneUuid,
+        ) -> Result<Option<Ambience>, String>;
+        async fn set_scene_ambience(
+            &self,
+            scene_uuid: &SceneUuid,
+            ambience: Ambience,
+        ) -> Result<(), String>;
+        async fn get_scene_world_time(&self, scene_uuid: &SceneUuid) -> Result<WorldTime, String>;
+        async fn get_scene_participant_chattiness(
+            &self,
+            scene_uuid: &SceneUuid,
+        ) -> Result<Vec<(PersonUuid, f64)>, String>;
+    }
+}
+
+metered! {
+    impl ReactionCapability as "reaction" {
+        async fn summarize_reaction_events(&self, events_text: String) -> Result<String, String>;
+        async fn preview_reaction_prompts(
+            &self,
+            memories: Vec<Memory>,
+            person_uuid: PersonUuid,
+            situation: String,
+        ) -> Result<ReactionPromptPreview, String>;
+        async fn get_reaction(
+            &self,
+            memories: Vec<Memory>,
+            person_uuid: PersonUuid,
+            state_of_mind: String,
+            situation: String,
+        ) -> Result<PersonReaction, String>;
+        async fn infer_person_task_to_adopt(
+            &self,
+            memories: Vec<Memory>,
+            person_uuid: PersonUuid,
+            state_of_mind: String,
+            situation: String,
+        ) -> Result<NewPersonTask, String>;
+        async fn classify_current_task_outcome(
+            &self,
+            task: PersonTask,
+            situation: String,
+            action_summary: Option<String>,
+        ) -> Result<PersonTaskOutcomeCheck, String>;
+        async fn infer_updated_task_state(
+            &self,
+            task: PersonTask,
+            situation: String,
+            action_summary: Option<String>,
+        ) -> Result<String, String>;
+    }
+}
+
+metered! {
+    impl MemoryCapability + Send + Sync as "memory" {
+        async fn create_memory(&self, new_memory: NewMemory) -> Result<MemoryUuid, String>;
+        async fn maybe_create_memories_from_description(
+            &self,
+            person_uuid: PersonUuid,
+            description: String,
+        ) -> Result<Vec<MemoryUuid>, String>;
+        async fn create_memory_query_prompt(
+            &self,
+            person_recalling: &PersonName,
+            message_type_args: MessageTypeArgs,
+            recent_events: Vec<String>,
+            state_of_mind: &str,
+            situation: &str,
+        ) -> Result<MemoryQueryPrompt, String>;
+        async fn search_memories(
+            &self,
+            person_uuid: PersonUuid,
+            query: String,
+            participants: &[PersonUuid],
+            retrieval: &MemoryRetrieval,
+        ) -> Result<Vec<MemorySearchResult>, String>;
+        async fn get_memory_retrieval(&self) -> Result<MemoryRetrieval, String>;
+        async fn set_memory_retrieval(&self, retrieval: &MemoryRetrieval) -> Result<(), String>;
+        async fn delete_memory(&self, memory_uuid: &MemoryUuid) -> Result<(), String>;
+    }
+}
+
+metered! {
+    impl PersonCapability as "person" {
+        async fn create_person(&self, new_person: NewPerson) -> Result<PersonUuid, String>;
+        async fn get_all_person_uuids(&self) -> Result<Vec<PersonUuid>, String>;
+        async fn get_persons_name(&self, person_uuid: PersonUuid) -> Result<PersonName, String>;
+        async fn get_person_uuid_by_name(
+            &self,
+            person_name: PersonName,
+        ) -> Result<PersonUuid, String>;
+        async fn set_person_hibernating(
+            &self,
+            person_uuid: &PersonUuid,
+            is_hibernating: bool,
+        ) -> Result<(), String>;
+        async fn is_person_hibernating(&self, person_uuid: &PersonUuid) -> Result<bool, String>;
+        async fn set_person_enabled(
+            &self,
+            person_uuid: &PersonUuid,
+            is_enabled: bool,
+        ) -> Result<(), String>;
+        async fn is_person_enabled(&self, person_uuid: &PersonUuid) -> Result<bool, String>;
+        async fn archive_person(&self, person_uuid: &PersonUuid) -> Result<(), String>;
+    }
+}
+
+metered! {
+    impl EventCapability as "event" {
+        async fn get_events(&self, args: GetArgs) -> Result<Vec<Event>, String>;
+    }
+}
+
+metered! {
+    #[async_trait]
+    impl StateOfMindCapability + Send + Sync as "state_of_mind" {
+        async fn create_state_of_mind(
+            &self,
+            new_state_of_mind: NewStateOfMind,
+        ) -> Result<StateOfMindUuid, String>;
+        async fn get_latest_state_of_mind(
+            &self,
+            person_uuid: &PersonUuid,
+        ) -> Result<Option<StateOfMind>, String>;
+    }
+}
+
+metered! {
+    #[async_trait]
+    impl PersonIdentityCapability + Send + Sync as "person_identity" {
+        async fn summarize_person_identity(
+            &self,
+            person_name: &str,
+            identity: &str,
+        ) -> Result<String, String>;
+        async fn create_person_identity(
+            &self,
+            new_person_identity: NewPersonIdentity,
+        ) -> Result<PersonIdentityUuid, String>;
+        async fn get_person_identity(
+            &self,
+            person_uuid: &PersonUuid,
+        ) -> Result<Option<String>, String>;
+        async fn get_person_identity_summary(
+            &self,
+            person_uuid: &PersonUuid,
+        ) -> Result<Option<String>, String>;
+        async fn get_person_directory(
+            &self,
+            viewer: &PersonUuid,
+            others: &[PersonUuid],
+        ) -> Result<Vec<PersonDirectoryEntry>, String>;
+        async fn set_person_identity_summary(
+            &self,
+            person_identity_uuid: &PersonIdentityUuid,
+            summary: &str,
+        ) -> Result<(), String>;
+    }
+}
+
+metered! {
+    impl IdlePersonCapability as "idle_person" {
+        async fn get_idle_persons_in_active_scenes(
+            &self,
+            idle_since: DateTime<Utc>,
+            active_since: DateTime<Utc>,
+        ) -> Result<Vec<IdlePerson>, String>;
+        async fn get_person_chattiness(&self, person_uuid: &PersonUuid) -> Result<f64, String>;
+        async fn set_person_chattiness(
+            &self,
+            person_uuid: &PersonUuid,
+            chattiness: f64,
+        ) -> Result<(), String>;
+        async fn get_talk_counts(&self, since: DateTime<Utc>) -> Result<Vec<TalkCount>, String>;
+    }
+}
+
+metered! {
+    impl SceneDramaCapability as "scene_drama" {
+        async fn get_dramatic_scenes(&self) -> Result<Vec<DramaticScene>, String>;
+        async fn get_scene_drama_intensity(&self, scene_uuid: &SceneUuid) -> Result<f64, String>;
+        async fn set_scene_drama_intensity(
+            &self,
+            scene_uuid: &SceneUuid,
+            intensity: f64,
+        ) -> Result<(), String>;
+        async fn add_scene_event(
+            &self,
+            scene_uuid: &SceneUuid,
+            description: &str,
+        ) -> Result<(), String>;
+    }
+}
+
+metered! {
+    impl CustomActionCapability as "custom_action" {
+        async fn post_custom_action(
+            &self,
+            url: &str,
+            body: &serde_json::Value,
+        ) -> Result<(), String>;
+    }
+}
+
+metered! {
+    impl ExternalToolCapability as "external_tool" {
+        async fn call_external_tool(
+            &self,
+            tool: &ExternalTool,
+            arguments: &serde_json::Value,
+        ) -> Result<String, String>;
+        async fn record_external_tool_call(&self, call: NewExternalToolCall) -> Result<(), String>;
+    }
+}
+
+metered! {
+    impl SceneInvitationCapability as "scene_invitation" {
+        async fn create_scene_invitation(
+            &self,
+            new_scene_invitation: NewSceneInvitation,
+        ) -> Result<SceneInvitationUuid, String>;
+        async fn get_pending_scene_invitations(
+            &self,
+            person_uuid: &PersonUuid,
+        ) -> Result<Vec<SceneInvitation>, String>;
+        async fn respond_to_scene_invitations(
+            &self,
+            person_uuid: &PersonUuid,
+            current_scene_uuid: Option<&SceneUuid>,
+        ) -> Result<(), String>;
+    }
+}
+
+metered! {
+    impl PersonArcCapability as "person_arc" {
+        async fn create_person_arc(&self, new_person_arc: NewPersonArc) -> Result<(), String>;
+        async fn get_latest_person_arc(
+            &self,
+            person_uuid: &PersonUuid,
+        ) -> Result<Option<PersonArc>, String>;
+        async fn get_persons_due_an_arc(
+            &self,
+            written_before_active_ms: i64,
+            limit: i64,
+        ) -> Result<Vec<PersonUuid>, String>;
+        async fn write_person_arc(
+            &self,
+            person_name: &str,
+            previous_arc: Option<&PersonArc>,
+            events: &[LifeEvent],
+        ) -> Result<ArcDraft, String>;
+    }
+}
+
+metered! {
+    impl AutobiographyCapability as "autobiography" {
+        async fn get_life_events(&self, person_uuid: &PersonUuid) -> Result<Vec<LifeEvent>, String>;
+        async fn write_autobiography_chapter(
+            &self,
+            person_name: &str,
+            previous_chapter: Option<&str>,
+            events: &[LifeEvent],
+        ) -> Result<String, String>;
+    }
+}
+
+metered! {
+    impl AcknowledgementCapability as "acknowledgement" {
+        async fn take_acknowledgement(
+            &self,
+            person_uuid: &PersonUuid,
+        ) -> Result<Option<String>, String>;
+        async fn count_acknowledgements(&self, person_uuid: &PersonUuid) -> Result<usize, String>;
+        async fn add_acknowledgements(
+            &self,
+            person_uuid: &PersonUuid,
+            acknowledgements: &[String],
+        ) -> Result<(), String>;
+        async fn generate_acknowledgements(
+            &self,
+            person_uuid: &PersonUuid,
+            count: usize,
+        ) -> Result<String, String>;
+    }
+}
+
+metered! {
+    impl PersonaConsistencyCapability as "persona_consistency" {
+        async fn get_recent_utterances(
+            &self,
+            person_uuid: &PersonUuid,
+            limit: i64,
+        ) -> Result<Vec<String>, String>;
+        async fn judge_persona_consistency(
+            &self,
+            identity: &str,
+            utterances: &[String],
+        ) -> Result<Vec<PersonaInconsistency>, String>;
+        async fn record_persona_inconsistencies(
+            &self,
+            person_uuid: &PersonUuid,
+            inconsistencies: &[PersonaInconsistency],
+        ) -> Result<(), String>;
+        async fn get_persona_inconsistencies(
+            &self,
+            person_uuid: &PersonUuid,
+            limit: i64,
+        ) -> Result<Vec<RecordedPersonaInconsistency>, String>;
+    }
+}
+
+metered! {
+    impl LlmBatchCapability as "llm_batch" {
+        async fn submit_llm_batch(&self, requests: Vec<BatchRequest>) -> Result<String, String>;
+        async fn refresh_llm_batch(&self, batch_id: &str) -> Result<Batch, String>;
+        async fn get_llm_batch_results(&self, file_id: &str) -> Result<Vec<BatchResult>, String>;
+    }
+}
+
+metered! {
+    impl PersonTaskCapability as "person_task" {
+        async fn get_persons_current_active_task(
+            &self,
+            person_uuid: &PersonUuid,
+        ) -> Result<Option<PersonTask>, String>;
+        async fn set_persons_current_active_task(
+            &self,
+            new_person_task: NewPersonTask,
+        ) -> Result<PersonTaskUuid, String>;
+        async fn transition_person_task(
+            &self,
+            person_uuid: &PersonUuid,
+            person_task_uuid: &PersonTaskUuid,
+            outcome: PersonTaskTerminalOutcome,
+        ) -> Result<(), String>;
+        async fn update_person_task_state(
+            &self,
+            person_uuid: &PersonUuid,
+            person_task_uuid: &PersonTaskUuid,
+            state: String,
+        ) -> Result<(), String>;
+    }
+}
+
+metered! {
+    impl ReactionHistoryCapability as "reaction_history" {
+        async fn record_reaction(
+            &self,
+            person_uuid: &PersonUuid,
+            action_kind: &str,
+        ) -> Result<(), String>;
+        async fn has_reacted_since(
+            &self,
+            person_uuid: &PersonUuid,
+            since: DateTime<Utc>,
+        ) -> Result<bool, String>;
+    }
+}
+
+metered! {
+    impl ExpectedReplyCapability as "expected_reply" {
+        async fn expect_reply(&self, new_expected_reply: NewExpectedReply) -> Result<(), String>;
+        async fn has_recipient_replied(&self, message_uuid: &MessageUuid) -> Result<bool, String>;
+        async fn resolve_expected_reply(
+            &self,
+            message_uuid: &MessageUuid,
+            outcome: ExpectedReplyOutcome,
+        ) -> Result<(), String>;
+    }
+}
+
+metered! {
+    impl ItemCapability as "item" {
+        async fn create_item(&self, new_item: NewItem) -> Result<ItemUuid, String>;
+        async fn get_items_carried_by(&self, person_uuid: &PersonUuid) -> Result<Vec<Item>, String>;
+        async fn get_items_in_scene(&self, scene_uuid: &SceneUuid) -> Result<Vec<Item>, String>;
+        async fn give_item(
+            &self,
+            item_uuid: &ItemUuid,
+            giver_person_uuid: &PersonUuid,
+            recipient_person_uuid: &PersonUuid,
+        ) -> Result<(), String>;
+    }
+}
+
+metered! {
+    impl ArrivalObservationCapability as "arrival_observation" {
+        async fn get_recent_public_scene_messages(
+            &self,
+            scene_uuid: &SceneUuid,
+            since: DateTime<Utc>,
+        ) -> Result<Vec<Message>, String>;
+        async fn summarize_arrival_observation(
+            &self,
+            scene_description: &str,
+            transcript: &str,
+        ) -> Result<String, String>;
+        async fn record_arrival_observation(
+            &self,
+            person_uuid: &PersonUuid,
+            scene_uuid: &SceneUuid,
+            observation: String,
+        ) -> Result<(), String>;
+    }
+}
+
+metered! {
+    impl LogEventCapability as "log_event" {
+        async fn log_event(&self, event_name: String, data: Option<Value>) -> Result<(), String>;
+    }
+}
+
+metered! {
+    impl ReflectionCapability as "reflection" {
+        async fn get_reflection_changes(
+            &self,
+            memories: Vec<Memory>,
+            person_uuid: PersonUuid,
+            person_identity: String,
+            state_of_mind: String,
+            situation: String,
+        ) -> Result<Vec<ReflectionChange>, String>;
+    }
+}
+
+metered! {
+    impl MotivationCapability as "motivation" {
+        async fn create_motivation(
+            &self,
+            new_motivation: NewMotivation,
+        ) -> Result<MotivationUuid, String>;
+        async fn get_motivations_for_person(
+            &self,
+            person_uuid: &PersonUuid,
+        ) -> Result<Vec<Motivation>, String>;
+        async fn delete_motivation(&self, motivation_uuid: MotivationUuid) -> Result<(), String>;
+    }
+}
+
+metered! {
+    impl OutboxCapability as "outbox" {
+        fn get_outbox_webhook_url(&self) -> Option<String>;
+        async fn add_outbox_event(&self, event: &OutboxEvent) -> Result<(), String>;
+        async fn claim_due_outbox_entries(&self, limit: i64) -> Result<Vec<OutboxEntry>, String>;
+        async fn post_outbox_entry(
+            &self,
+            webhook_url: &str,
+            entry: &OutboxEntry,
+        ) -> Result<(), String>;
+        async fn mark_outbox_entry_delivered(&self, outbox_uuid: &OutboxUuid) -> Result<(), String>;
+        async fn mark_outbox_entry_failed(
+            &self,
+            outbox_uuid: &OutboxUuid,
+            details: &str,
+            retry_at: DateTime<Utc>,
+        ) -> Result<(), String>;
+        async fn has_undelivered_outbox_entries(&self) -> Result<bool, String>;
+    }
+}
+
+// Logging is not timed, it only writes to the local logger
+impl<W: LogCapability> LogCapability for MeteredWorker<W> {
+    fn log(&self, level: Level, message: &str) {
+        self.inner.log(level, message)
+    }
+}
+
+metered! {
+    impl SceneGoalCapability as "scene_goal" {
+        async fn set_scene_goal(
+            &self,
+            scene_uuid: &SceneUuid,
+            goal: &SceneGoal,
+        ) -> Result<(), String>;
+        async fn get_scene_goal(&self, scene_uuid: &SceneUuid) -> Result<Option<SceneGoal>, String>;
+        async fn get_open_scene_goals(&self) -> Result<Vec<OpenSceneGoal>, String>;
+        async fn get_scene_transcript_since(
+            &self,
+            scene_uuid: &SceneUuid,
+            since: DateTime<Utc>,
+        ) -> Result<Vec<TranscriptLine>, String>;
+        async fn judge_scene_consensus(
+            &self,
+            consensus_on: &str,
+            transcript: &str,
+        ) -> Result<bool, String>;
+        async fn summarize_scene_ending(
+            &self,
+            scene_description: &str,
+            transcript: &str,
+            met: &SceneGoalMet,
+        ) -> Result<String, String>;
+        async fn mark_scene_goal_met(
+            &self,
+            scene_uuid: &SceneUuid,
+            met: &SceneGoalMet,
+        ) -> Result<bool, String>;
+    }
+}
+
+metered! {
+    impl SceneArchiveCapability as "scene_archive" {
+        async fn cancel_pending_scene_jobs(&self, scene_uuid: &SceneUuid) -> Result<u64, String>;
+        async fn get_exported_annotations(
+            &self,
+            scene_uuid: &SceneUuid,
+        ) -> Result<Vec<Annotation>, String>;
+        async fn export_scene_transcript(
+            &self,
+            file_name: &str,
+            transcript: &str,
+        ) -> Result<String, String>;
+        async fn mark_scene_archived(
+            &self,
+            scene_uuid: &SceneUuid,
+            transcript_path: &str,
+        ) -> Result<(), String>;
+    }
+}
+
+metered! {
+    impl MaintenanceCapability as "maintenance" {
+        async fn reap_stale_jobs(&self, started_before: DateTime<Utc>) -> Result<u64, String>;
+        async fn roll_up_llm_usage(&self, before: DateTime<Utc>) -> Result<u64, String>;
+        async fn prune_logs(&self, before: DateTime<Utc>) -> Result<PrunedRows, String>;
+        async fn remove_duplicate_memories(&self) -> Result<u64, String>;
+        async fn get_recent_memories(
+            &self,
+            since: DateTime<Utc>,
+        ) -> Result<Vec<RecentMemories>, String>;
+        async fn get_scenes_changed_since_snapshot(&self) -> Result<Vec<SceneUuid>, String>;
+        async fn refresh_scene_snapshot(&self, scene_uuid: &SceneUuid) -> Result<(), String>;
+        async fn get_latest_maintenance_report(&self) -> Result<Option<MaintenanceReport>, String>;
+    }
+}
+
+metered! {
+    impl TopicCapability as "topic" {
+        async fn get_untagged_scenes(&self) -> Result<Vec<UntaggedScene>, String>;
+        async fn record_scene_topics(
+            &self,
+            scene_uuid: &SceneUuid,
+            topics: &[TopicCount],
+            tagged_through: DateTime<Utc>,
+        ) -> Result<(), String>;
+        async fn get_trending_topics(
+            &self,
+            since: Option<DateTime<Utc>>,
+            limit: i64,
+        ) -> Result<Vec<TrendingTopic>, String>;
+        async fn get_topic_scenes(&self, topic: &Topic) -> Result<Vec<TopicScene>, String>;
+    }
+}
@@ -0,0 +1,165 @@
+//! A typed client for the json api, for Rust programs that want to read a
+//! simulation without copying its serde types. Enable the `client` feature
+//! to use it.
+
+use crate::domain::scene_timeline::{TimelinePage, TimelineQuery};
+use crate::nice_display::{with_context, ErrorCode, NiceDisplay};
+use serde::Deserialize;
+use std::time::Duration;
+use uuid::Uuid;
+
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+pub struct Client {
+    base_url: String,
+    token: String,
+    http: reqwest::Client,
+}
+
+pub enum Error {
+    Build(reqwest::Error),
+    Request(reqwest::Error),
+    Decode(String),
+    /// The api answered with one of its documented errors.
+    Api {
+        status: u16,
+        code: String,
+        message: String,
+    },
+}
+
+#[derive(Debug, Deserialize)]
+struct ErrorBody {
+    error: ApiErrorDetails,
+}
+
+#[derive(Debug, Deserialize)]
+struct ApiErrorDetails {
+    code: String,
+    message: String,
+}
+
+impl NiceDisplay for Error {
+    fn message(&self) -> String {
+        match self {
+            Error::Build(err) => with_context("Could not build the http client", err),
+            Error::Request(err) => with_context("Could not reach the api", err),
+            Error::Decode(details) => with_context("Could not read the api's response", details),
+            Error::Api {
+                status, message, ..
+            } => format!("The api answered {}: {}", status, message),
+        }
+    }
+}
+
+impl ErrorCode for Error {
+    /// The api's own code for api errors, so callers can branch on codes like
+    /// `rate limited` the same way the api documents them.
+    fn code(&self) -> &'static str {
+        match self {
+            Error::Build(_) => "client build",
+            Error::Request(_) => "client request",
+            Error::Decode(_) => "client decode",
+            Error::Api { code, .. } => match code.as_str() {
+                "unauthorized" => "unauthorized",
+                "forbidden" => "forbidden",
+                "rate limited" => "rate limited",
+                "unsupported format" => "unsupported format",
+                "websocket required" => "websocket required",
+                "scene not found" => "scene not found",
+                "internal" => "internal",
+                _ => "unknown",
+            },
+        }
+    }
+}
+
+impl Client {
+    /// `base_url` is where `serve-api` listens, like `http://127.0.0.1:8080`.
+    pub fn new(base_url: &str, token: &str) -> Result<Self, Error> {
+        let http = reqwest::Client::builder()
+            .timeout(REQUEST_TIMEOUT)
+            .build()
+            .map_err(Error::Build)?;
+
+        Ok(Client {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            token: token.trim().to_string(),
+            http,
+        })
+    }
+
+    pub async fn get_scene_timeline(
+        &self,
+        scene_uuid: Uuid,
+        query: TimelineQuery,
+    ) -> Result<TimelinePage, Error> {
+        let mut params = vec![("limit", query.limit.to_string())];
+        if let Some(before) = query.before {
+            params.push(("before", before.to_rfc3339()));
+        }
+
+        let response = self
+            .http
+            .get(format!(
+                "{}/api/scenes/{}/timeline",
+                self.base_url, scene_uuid
+            ))
+            .bearer_auth(&self.token)
+            .query(&params)
+            .send()
+            .await
+            .map_err(Error::Request)?;
+
+        decode(response).await
+    }
+
+    /// The api's OpenAPI document.
+    pub async fn get_docs(&self) -> Result<serde_json::Value, Error> {
+        let response = self
+            .http
+            .get(format!("{}/api/docs", self.base_url))
+            .send()
+            .await
+            .map_err(Error::Request)?;
+
+        decode(response).await
+    }
+}
+
+async fn decode<T: serde::de::DeserializeOwned>(response: reqwest::Response) -> Result<T, Error> {
+    let status = response.status();
+    let body = response.text().await.map_err(Error::Request)?;
+
+    if status.is_success() {
+        return serde_json::from_str(&body).map_err(|err| Error::Decode(err.to_string()));
+    }
+
+    match serde_json::from_str::<ErrorBody>(&body) {
+        Ok(error_body) => Err(Error::Api {
+            status: status.as_u16(),
+            code: error_body.error.code,
+            message: error_body.error.message,
+        }),
+        Err(_) => Err(Error::Decode(format!("HTTP {}: {}", status, body))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::RequestError;
+    use crate::nice_display;
+
+    #[test]
+    fn reads_the_error_body_the_api_sends() {
+        let sent = serde_json::json!({
+            "error": nice_display::to_json(&RequestError::Unauthorized)
+        });
+
+        let body: ErrorBody = serde_json::from_value(sent).unwrap();
+
+        assert_eq!(body.error.code, "unauthorized");
+        assert!(!body.error.message.is_empty());
+    }
+}
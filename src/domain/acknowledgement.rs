@@ -0,0 +1,236 @@
+use crate::capability::acknowledgement::AcknowledgementCapability;
+use crate::capability::job::JobCapability;
+use crate::capability::message::MessageCapability;
+use crate::capability::scene::{SceneCapability, SceneParticipant};
+use crate::domain::actor_uuid::ActorUuid;
+use crate::domain::job::refill_acknowledgements::RefillAcknowledgementsJob;
+use crate::domain::job::JobKind;
+use crate::domain::message::MessageSender;
+use crate::domain::message_urgency;
+use crate::domain::person_uuid::PersonUuid;
+use crate::domain::scene_uuid::SceneUuid;
+use crate::nice_display::{with_context, NiceDisplay};
+
+/// `INSTANT_REPLIES=on` has a person in the scene answer the real world user
+/// right away with a short acknowledgement, while their full reaction is
+/// still being worked out. Off by default.
+pub const INSTANT_REPLIES_VAR: &str = "INSTANT_REPLIES";
+
+/// How many acknowledgements each person keeps ready.
+pub const POOL_SIZE: usize = 3;
+
+pub const SYSTEM_PROMPT: &str = "You write short in-character acknowledgements a person says the moment someone talks to them, before they have had time to think of a real answer, like \"Hm, give me a second.\" or \"Oh! Hang on.\". Each is a few words, commits to nothing, and could follow almost anything. Return one a line, with nothing else.";
+
+pub enum Error {
+    GetParticipants(String),
+    TakeAcknowledgement(String),
+    Send(String),
+    EnqueueRefill(String),
+}
+
+impl NiceDisplay for Error {
+    fn message(&self) -> String {
+        match self {
+            Error::GetParticipants(details) => {
+                with_context("Could not get who is in the scene", details)
+            }
+            Error::TakeAcknowledgement(details) => {
+                with_context("Could not take a ready acknowledgement", details)
+            }
+            Error::Send(details) => with_context("Could not send the acknowledgement", details),
+            Error::EnqueueRefill(details) => with_context(
+                "Could not enqueue a job to refill the acknowledgements",
+                details,
+            ),
+        }
+    }
+}
+
+pub fn is_enabled() -> bool {
+    match dotenv::var(INSTANT_REPLIES_VAR) {
+        Ok(value) => value.trim().eq_ignore_ascii_case("on"),
+        Err(_) => false,
+    }
+}
+
+pub fn prompt(
+    person_name: &str,
+    identity_summary: Option<&str>,
+    scene_name: Option<&str>,
+    count: usize,
+) -> String {
+    let mut prompt = format!(
+        "Write {} different acknowledgements for {}.",
+        count, person_name
+    );
+
+    if let Some(identity_summary) = identity_summary {
+        prompt.push_str(&format!("\n\nWho they are:\n{}", identity_summary));
+    }
+
+    if let Some(scene_name) = scene_name {
+        prompt.push_str(&format!("\n\nThey are in {}.", scene_name));
+    }
+
+    prompt
+}
+
+/// Reads the model's acknowledgements, one a line, dropping any list markers
+/// or quotes it put around them.
+pub fn parse(text: &str) -> Vec<String> {
+    text.lines()
+        .map(|line| {
+            line.trim()
+                .trim_start_matches(|c: char| c.is_ascii_digit())
+                .trim_start_matches(['-', '*', '.', ')'])
+                .trim()
+                .trim_matches('"')
+                .trim()
+                .to_string()
+        })
+        .filter(|line| !line.is_empty())
+        .take(POOL_SIZE)
+        .collect()
+}
+
+/// The person the real world user named, or else whoever comes first.
+fn pick_responder(participants: &[SceneParticipant], content: &str) -> Option<PersonUuid> {
+    let persons = participants
+        .iter()
+        .filter_map(|participant| match &participant.actor_uuid {
+            ActorUuid::AiPerson(person_uuid) => Some((person_uuid, &participant.person_name)),
+            ActorUuid::RealWorldUser => None,
+        })
+        .collect::<Vec<_>>();
+
+    persons
+        .iter()
+        .find(|(_, person_name)| message_urgency::mentions_name(content, person_name))
+        .or(persons.first())
+        .map(|(person_uuid, _)| (*person_uuid).clone())
+}
+
+/// Has one person in the scene answer what the real world user just said
+/// with an acknowledgement they had ready, then tops their pool back up.
+/// The others get the acknowledgement as a message, without reacting to it
+/// on its own. Does nothing unless instant replies are on. The real world
+/// user's message has already gone out, so a failure here is only logged.
+pub async fn send_acknowledgement<
+    W: AcknowledgementCapability + SceneCapability + MessageCapability + JobCapability,
+>(
+    worker: &W,
+    scene_uuid: &SceneUuid,
+    content: &str,
+) {
+    if !is_enabled() {
+        return;
+    }
+
+    if let Err(err) = try_send_acknowledgement(worker, scene_uuid, content).await {
+        tracing::warn!(
+            "No instant reply in scene {}: {}",
+            scene_uuid.to_uuid(),
+            err.message()
+        );
+    }
+}
+
+async fn try_send_acknowledgement<
+    W: AcknowledgementCapability + SceneCapability + MessageCapability + JobCapability,
+>(
+    worker: &W,
+    scene_uuid: &SceneUuid,
+    content: &str,
+) -> Result<(), Error> {
+    let participants = worker
+        .get_scene_current_participants(scene_uuid)
+        .await
+        .map_err(Error::GetParticipants)?;
+
+    let person_uuid = match pick_responder(&participants, content) {
+        Some(person_uuid) => person_uuid,
+        None => return Ok(()),
+    };
+
+    let maybe_acknowledgement = worker
+        .take_acknowledgement(&person_uuid)
+        .await
+        .map_err(Error::TakeAcknowledgement)?;
+
+    if let Some(acknowledgement) = maybe_acknowledgement {
+        let message_uuid = worker
+            .send_scene_message(
+                MessageSender::AiPerson(person_uuid.clone()),
+                scene_uuid.clone(),
+                acknowledgement,
+            )
+            .await
+            .map_err(Error::Send)?;
+
+        worker
+            .add_scene_message_recipients(
+                &message_uuid,
+                SceneParticipant::other_persons(&participants, &person_uuid),
+            )
+            .await
+            .map_err(Error::Send)?;
+    }
+
+    worker
+        .unshift_job(JobKind::RefillAcknowledgements(RefillAcknowledgementsJob {
+            person_uuid,
+        }))
+        .await
+        .map_err(Error::EnqueueRefill)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::person_name::PersonName;
+    use uuid::Uuid;
+
+    #[test]
+    fn test_parse_strips_list_markers_and_keeps_a_pool() {
+        let text = "1. \"Hm, give me a second.\"\n\n- Oh! Hang on.\n* Right, right...\nOne more.";
+
+        assert_eq!(
+            parse(text),
+            vec![
+                "Hm, give me a second.".to_string(),
+                "Oh! Hang on.".to_string(),
+                "Right, right...".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_the_named_person_acknowledges_first() {
+        let hank = PersonUuid::from_uuid(Uuid::from_u128(1));
+        let peggy = PersonUuid::from_uuid(Uuid::from_u128(2));
+        let participants = vec![
+            SceneParticipant {
+                person_name: PersonName::from_string("You".to_string()),
+                actor_uuid: ActorUuid::RealWorldUser,
+            },
+            SceneParticipant {
+                person_name: PersonName::from_string("Hank Hill".to_string()),
+                actor_uuid: ActorUuid::AiPerson(hank.clone()),
+            },
+            SceneParticipant {
+                person_name: PersonName::from_string("Peggy Hill".to_string()),
+                actor_uuid: ActorUuid::AiPerson(peggy.clone()),
+            },
+        ];
+
+        assert_eq!(
+            pick_responder(&participants, "Peggy, what's for dinner?").map(|p| p.to_uuid()),
+            Some(peggy.to_uuid())
+        );
+        assert_eq!(
+            pick_responder(&participants, "Evening, everyone.").map(|p| p.to_uuid()),
+            Some(hank.to_uuid())
+        );
+        assert!(pick_responder(&participants[..1], "Hello?").is_none());
+    }
+}
@@ -0,0 +1,139 @@
+use crate::capability::action_budget::ActionBudgetCapability;
+use crate::domain::custom_action::CustomAction;
+use crate::domain::external_tool;
+use crate::domain::person_uuid::PersonUuid;
+use crate::person_actions::{PersonAction, PersonActionKind};
+
+/// One day of simulated time on the active clock.
+pub const WORLD_DAY_MS: i64 = 24 * 60 * 60 * 1000;
+
+/// How many times a person may take one kind of action in a world-day.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ActionBudget {
+    pub action_name: String,
+    pub per_world_day: i64,
+}
+
+/// How much of an action's budget a person has used today.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ActionBudgetUsage {
+    pub action_name: String,
+    pub used: i64,
+    pub per_world_day: i64,
+}
+
+impl ActionBudgetUsage {
+    pub fn is_exhausted(&self) -> bool {
+        self.used >= self.per_world_day
+    }
+
+    /// What the action validator tells the model when the budget is spent.
+    pub fn to_feedback(&self) -> String {
+        format!(
+            "Action budget exhausted: this person has already used {} of their {} '{}' actions for today. Choose a different action, and save the rest of the day's '{}' actions for what matters most.",
+            self.used, self.per_world_day, self.action_name, self.action_name
+        )
+    }
+}
+
+/// The budgeted name of an action, and the reaction history kinds that count
+/// against it. Waiting, hibernating and idling are always free, so a person
+/// who has spent everything can still do those.
+fn budgeted_reaction_kinds(action: &PersonAction) -> Option<(String, Vec<String>)> {
+    let (kind, reaction_kinds) = match action {
+        PersonAction::Wait { .. } | PersonAction::Hibernate { .. } | PersonAction::Idle => {
+            return None
+        }
+        PersonAction::GazeInScene => (PersonActionKind::GazeInScene, vec!["gaze_in_scene"]),
+        PersonAction::SayInScene { .. } => (
+            PersonActionKind::SayInScene,
+            vec!["say_in_scene", "say_in_scene_and_move_to_scene"],
+        ),
+        PersonAction::Ask { .. } => (PersonActionKind::Ask, vec!["ask"]),
+        PersonAction::MoveToScene { .. } => (
+            PersonActionKind::MoveToScene,
+            vec!["move_to_scene", "say_in_scene_and_move_to_scene"],
+        ),
+        PersonAction::GiveItem { .. } => (PersonActionKind::GiveItem, vec!["give_item"]),
+        PersonAction::Invite { .. } => (PersonActionKind::Invite, vec!["invite"]),
+        PersonAction::UseTool { .. } => {
+            return Some((
+                PersonActionKind::UseTool.to_name(),
+                vec![external_tool::reaction_kind()],
+            ))
+        }
+        PersonAction::Custom { name, .. } => {
+            return Some((
+                PersonActionKind::Custom(name.clone()).to_name(),
+                vec![CustomAction::reaction_kind(name)],
+            ))
+        }
+    };
+
+    Some((
+        kind.to_name(),
+        reaction_kinds
+            .into_iter()
+            .map(|reaction_kind| reaction_kind.to_string())
+            .collect(),
+    ))
+}
+
+/// The person's usage of the action's budget, or None when the action has no
+/// budget.
+pub async fn get_action_budget_usage<W: ActionBudgetCapability>(
+    worker: &W,
+    person_uuid: &PersonUuid,
+    action: &PersonAction,
+) -> Result<Option<ActionBudgetUsage>, String> {
+    let (action_name, reaction_kinds) = match budgeted_reaction_kinds(action) {
+        Some(budgeted) => budgeted,
+        None => return Ok(None),
+    };
+
+    let budget = worker
+        .get_action_budgets()
+        .await?
+        .into_iter()
+        .find(|budget| budget.action_name == action_name);
+
+    let per_world_day = match budget {
+        Some(budget) => budget.per_world_day,
+        None => return Ok(None),
+    };
+
+    let used = worker
+        .count_reactions_in_world_day(person_uuid, reaction_kinds)
+        .await?;
+
+    Ok(Some(ActionBudgetUsage {
+        action_name,
+        used,
+        per_world_day,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_exhausted_budget_feedback_and_free_actions() {
+        let usage = ActionBudgetUsage {
+            action_name: "ask".to_string(),
+            used: 12,
+            per_world_day: 12,
+        };
+        assert!(usage.is_exhausted());
+        assert!(usage.to_feedback().contains("12 of their 12 'ask' actions"));
+
+        assert!(budgeted_reaction_kinds(&PersonAction::Idle).is_none());
+
+        let (action_name, reaction_kinds) = budgeted_reaction_kinds(&PersonAction::MoveToScene {
+            scene_name: "Cafe".to_string(),
+        })
+        .expect("moving is budgeted");
+        assert_eq!(action_name, "move to scene");
+        assert!(reaction_kinds.contains(&"say_in_scene_and_move_to_scene".to_string()));
+    }
+}
@@ -0,0 +1,141 @@
+use crate::domain::annotation_uuid::AnnotationUuid;
+use crate::domain::scene_timeline::TimelineItem;
+use crate::domain::scene_uuid::SceneUuid;
+use chrono::{DateTime, Utc};
+
+const MAX_SUMMARY_CHARS: usize = 80;
+
+/// A private note on a moment in a scene. Annotations point at the time of
+/// a timeline item rather than the item itself, so any kind of item can be
+/// annotated.
+#[derive(Debug, Clone)]
+pub struct Annotation {
+    pub uuid: AnnotationUuid,
+    pub at: DateTime<Utc>,
+    /// What the annotated item said, so a bookmark reads well without
+    /// loading the timeline.
+    pub item_summary: String,
+    pub note: String,
+    pub is_bookmark: bool,
+    /// Notes stay out of exported transcripts unless this is set.
+    pub include_in_export: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct NewAnnotation {
+    pub scene_uuid: SceneUuid,
+    pub at: DateTime<Utc>,
+    pub item_summary: String,
+    pub note: String,
+    pub is_bookmark: bool,
+    pub include_in_export: bool,
+}
+
+impl NewAnnotation {
+    pub fn for_item(
+        scene_uuid: SceneUuid,
+        item: &TimelineItem,
+        note: String,
+        is_bookmark: bool,
+        include_in_export: bool,
+    ) -> Result<Self, String> {
+        let note = note.trim().to_string();
+        if note.is_empty() && !is_bookmark {
+            return Err("Write a note or make it a bookmark".to_string());
+        }
+
+        Ok(NewAnnotation {
+            scene_uuid,
+            at: item.at(),
+            item_summary: summarize_item(item),
+            note,
+            is_bookmark,
+            include_in_export,
+        })
+    }
+}
+
+/// One line about a timeline item, like `Hank: Nice to meet you`.
+pub fn summarize_item(item: &TimelineItem) -> String {
+    let summary = match item {
+        TimelineItem::Message {
+            sender, content, ..
+        } => format!("{}: {}", sender.name, content),
+        TimelineItem::Joined { person_name, .. } => format!("{} joined", person_name),
+        TimelineItem::Left { person_name, .. } => format!("{} left", person_name),
+        TimelineItem::ArrivalSummary { person_name, .. } => {
+            format!("{} arrived", person_name)
+        }
+        TimelineItem::SceneSnapshot { description, .. } => {
+            format!("Scene: {}", description)
+        }
+        TimelineItem::AmbienceChanged { ambience, .. } => {
+            format!("Ambience: {}", ambience.to_label())
+        }
+        TimelineItem::SceneEvent { description, .. } => format!("Event: {}", description),
+    };
+
+    let summary = summary.split_whitespace().collect::<Vec<&str>>().join(" ");
+
+    if summary.chars().count() <= MAX_SUMMARY_CHARS {
+        summary
+    } else {
+        format!(
+            "{}...",
+            summary.chars().take(MAX_SUMMARY_CHARS).collect::<String>()
+        )
+    }
+}
+
+/// The annotations that belong in exported transcripts, oldest first.
+pub fn exported(annotations: &[Annotation]) -> Vec<&Annotation> {
+    let mut exported = annotations
+        .iter()
+        .filter(|annotation| annotation.include_in_export && !annotation.note.is_empty())
+        .collect::<Vec<&Annotation>>();
+    exported.sort_by_key(|annotation| annotation.at);
+    exported
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::scene_timeline::TimelineSpeaker;
+    use chrono::TimeZone;
+    use uuid::Uuid;
+
+    fn message(content: &str) -> TimelineItem {
+        TimelineItem::Message {
+            message_uuid: Uuid::from_u128(1),
+            at: Utc.with_ymd_and_hms(2026, 1, 1, 0, 0, 0).unwrap(),
+            sender: TimelineSpeaker {
+                person_uuid: None,
+                name: "You".to_string(),
+            },
+            content: content.to_string(),
+            audience: "everyone".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_summary_is_one_short_line() {
+        assert_eq!(summarize_item(&message("Hi\n\n  there")), "You: Hi there");
+        assert_eq!(
+            summarize_item(&message(&"a".repeat(100))).chars().count(),
+            MAX_SUMMARY_CHARS + 3
+        );
+    }
+
+    #[test]
+    fn test_an_annotation_needs_a_note_or_a_bookmark() {
+        let item = message("Hi");
+
+        assert!(
+            NewAnnotation::for_item(SceneUuid::new(), &item, " ".to_string(), false, false)
+                .is_err()
+        );
+        assert!(
+            NewAnnotation::for_item(SceneUuid::new(), &item, String::new(), true, false).is_ok()
+        );
+    }
+}
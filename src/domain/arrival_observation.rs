@@ -0,0 +1,133 @@
+use crate::capability::arrival_observation::ArrivalObservationCapability;
+use crate::capability::clock::ClockCapability;
+use crate::capability::person::PersonCapability;
+use crate::capability::scene::SceneCapability;
+use crate::domain::message::MessageSender;
+use crate::domain::person_uuid::PersonUuid;
+use crate::domain::scene_uuid::SceneUuid;
+use crate::domain::utterance::REAL_WORLD_USER_NAME;
+use crate::nice_display::{with_context, NiceDisplay};
+use chrono::Duration;
+
+/// How far back someone walking in can pick up on a conversation.
+pub const LOOKBACK_MINUTES: i64 = 5;
+
+/// Keeps the summarizer prompt small in busy scenes.
+const MAX_TRANSCRIPT_LINES: usize = 30;
+
+pub enum Error {
+    GetMessages(String),
+    GetSceneDescription(String),
+    GetSpeakerName(String),
+    Summarize(String),
+    Record(String),
+}
+
+impl NiceDisplay for Error {
+    fn message(&self) -> String {
+        match self {
+            Error::GetMessages(details) => {
+                with_context("Failed to get recent scene messages", details)
+            }
+            Error::GetSceneDescription(details) => {
+                with_context("Failed to get scene description", details)
+            }
+            Error::GetSpeakerName(details) => with_context("Failed to get speaker's name", details),
+            Error::Summarize(details) => {
+                with_context("Failed to summarize what the person saw", details)
+            }
+            Error::Record(details) => with_context("Failed to record arrival observation", details),
+        }
+    }
+}
+
+/// Condenses the last few minutes of a scene into what someone notices as
+/// they walk in, and saves it as the first event of their visit. Returns
+/// `None` when nobody has been talking.
+pub async fn observe_arrival<
+    W: ArrivalObservationCapability + SceneCapability + PersonCapability + ClockCapability,
+>(
+    worker: &W,
+    person_uuid: &PersonUuid,
+    scene_uuid: &SceneUuid,
+) -> Result<Option<String>, Error> {
+    let since = worker.now() - Duration::minutes(LOOKBACK_MINUTES);
+
+    let messages = worker
+        .get_recent_public_scene_messages(scene_uuid, since)
+        .await
+        .map_err(Error::GetMessages)?;
+
+    let mut lines = Vec::with_capacity(messages.len());
+
+    for message in messages {
+        let speaker_name = match message.sender {
+            MessageSender::AiPerson(sender_uuid) => {
+                if sender_uuid.to_uuid() == person_uuid.to_uuid() {
+                    continue;
+                }
+
+                worker
+                    .get_persons_name(sender_uuid)
+                    .await
+                    .map_err(Error::GetSpeakerName)?
+                    .as_str()
+                    .to_string()
+            }
+            MessageSender::RealWorldUser => REAL_WORLD_USER_NAME.to_string(),
+        };
+
+        lines.push((speaker_name, message.content));
+    }
+
+    if lines.is_empty() {
+        return Ok(None);
+    }
+
+    let scene_description = worker
+        .get_scene_description(scene_uuid)
+        .await
+        .map_err(Error::GetSceneDescription)?
+        .unwrap_or_else(|| "No description.".to_string());
+
+    let observation = worker
+        .summarize_arrival_observation(&scene_description, &to_transcript(&lines))
+        .await
+        .map_err(Error::Summarize)?;
+
+    worker
+        .record_arrival_observation(person_uuid, scene_uuid, observation.clone())
+        .await
+        .map_err(Error::Record)?;
+
+    Ok(Some(observation))
+}
+
+fn to_transcript(lines: &[(String, String)]) -> String {
+    let skip = lines.len().saturating_sub(MAX_TRANSCRIPT_LINES);
+
+    lines
+        .iter()
+        .skip(skip)
+        .map(|(speaker_name, content)| format!("{}: {}", speaker_name, content))
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_transcript_keeps_only_the_latest_lines() {
+        let lines = (0..MAX_TRANSCRIPT_LINES + 2)
+            .map(|index| ("Bob".to_string(), format!("line {}", index)))
+            .collect::<Vec<(String, String)>>();
+
+        let transcript = to_transcript(&lines);
+
+        assert_eq!(transcript.lines().count(), MAX_TRANSCRIPT_LINES);
+        assert!(transcript.starts_with("Bob: line 2\n"));
+        assert!(transcript.ends_with(&format!("Bob: line {}", MAX_TRANSCRIPT_LINES + 1)));
+    }
+}
@@ -0,0 +1,272 @@
+use crate::capability::autobiography::AutobiographyCapability;
+use crate::capability::clock::ClockCapability;
+use crate::capability::person::PersonCapability;
+use crate::domain::person_uuid::PersonUuid;
+use crate::nice_display::{with_context, NiceDisplay};
+use chrono::{DateTime, Utc};
+
+/// Each chapter is one completion, so a long life gets longer chapters
+/// rather than more of them.
+const MAX_CHAPTERS: usize = 30;
+const MIN_EVENTS_PER_CHAPTER: usize = 40;
+/// Long memories are cut so one of them cannot crowd out the rest of its
+/// chapter.
+const MAX_EVENT_CHARS: usize = 500;
+
+/// Something that happened to a person, as far as the database knows.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LifeEvent {
+    pub at: DateTime<Utc>,
+    pub kind: LifeEventKind,
+    pub text: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LifeEventKind {
+    Memory,
+    /// A new identity, from when they were made or rewritten
+    Identity,
+    JoinedScene,
+    LeftScene,
+}
+
+impl LifeEventKind {
+    fn to_label(self) -> &'static str {
+        match self {
+            LifeEventKind::Memory => "remembered",
+            LifeEventKind::Identity => "became",
+            LifeEventKind::JoinedScene => "arrived at",
+            LifeEventKind::LeftScene => "left",
+        }
+    }
+}
+
+impl LifeEvent {
+    pub fn to_line(&self) -> String {
+        let text = self.text.trim();
+        let text = match text.char_indices().nth(MAX_EVENT_CHARS) {
+            Some((end, _)) => format!("{}...", &text[..end]),
+            None => text.to_string(),
+        };
+
+        format!(
+            "{} ({}) {}",
+            self.at.format("%Y-%m-%d %H:%M"),
+            self.kind.to_label(),
+            text
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Chapter {
+    pub from: DateTime<Utc>,
+    pub to: DateTime<Utc>,
+    pub text: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct Autobiography {
+    pub person_name: String,
+    pub written_at: DateTime<Utc>,
+    pub event_count: usize,
+    pub chapters: Vec<Chapter>,
+}
+
+impl Autobiography {
+    pub fn to_markdown(&self) -> String {
+        let mut markdown = format!(
+            "# The Life of {}\n\n_Written {} from {} events._\n",
+            self.person_name,
+            self.written_at.format("%Y-%m-%d"),
+            self.event_count
+        );
+
+        for (index, chapter) in self.chapters.iter().enumerate() {
+            markdown.push_str(
+                format!(
+                    "\n## Chapter {}: {} to {}\n\n{}\n",
+                    index + 1,
+                    chapter.from.format("%Y-%m-%d"),
+                    chapter.to.format("%Y-%m-%d"),
+                    chapter.text.trim()
+                )
+                .as_str(),
+            );
+        }
+
+        markdown
+    }
+}
+
+pub enum Error {
+    GetPersonName(String),
+    GetLifeEvents(String),
+    NothingHappened,
+    WriteChapter { number: usize, details: String },
+}
+
+impl NiceDisplay for Error {
+    fn message(&self) -> String {
+        match self {
+            Error::GetPersonName(details) => {
+                with_context("Could not get the person's name", details)
+            }
+            Error::GetLifeEvents(details) => {
+                with_context("Could not get what happened to the person", details)
+            }
+            Error::NothingHappened => {
+                "The person has no memories, identities or scenes to write about".to_string()
+            }
+            Error::WriteChapter { number, details } => {
+                with_context(format!("Could not write chapter {}", number), details)
+            }
+        }
+    }
+}
+
+/// Splits the events, oldest first, into chapters of about the same size.
+pub fn into_chapters(events: Vec<LifeEvent>) -> Vec<Vec<LifeEvent>> {
+    let per_chapter = events
+        .len()
+        .div_ceil(MAX_CHAPTERS)
+        .max(MIN_EVENTS_PER_CHAPTER);
+
+    let mut chapters = Vec::new();
+    let mut events = events.into_iter().peekable();
+    while events.peek().is_some() {
+        chapters.push(events.by_ref().take(per_chapter).collect());
+    }
+    chapters
+}
+
+pub fn system_prompt() -> &'static str {
+    "You are ghostwriting a person's autobiography, one chapter at a time, from a log of what happened to them. Write in the first person, in their voice, as flowing prose in a few paragraphs. No headings, lists or dates at the start of lines. Tell what happened in the order it happened and what it meant to them. Use only what is in the log. Do not invent events, people or places, and do not foreshadow anything after the chapter ends."
+}
+
+pub fn chapter_prompt(
+    person_name: &str,
+    previous_chapter: Option<&str>,
+    events: &[LifeEvent],
+) -> String {
+    let lines = events
+        .iter()
+        .map(LifeEvent::to_line)
+        .collect::<Vec<String>>()
+        .join("\n");
+
+    match previous_chapter {
+        Some(previous_chapter) => format!(
+            "You are {}.\n\nThe chapter before this one:\n{}\n\nWhat happened next, oldest first:\n{}\n\nWrite the next chapter, picking up where the last one left off.",
+            person_name, previous_chapter, lines
+        ),
+        None => format!(
+            "You are {}.\n\nWhat happened, oldest first:\n{}\n\nWrite the first chapter of your life story.",
+            person_name, lines
+        ),
+    }
+}
+
+/// Writes the person's life story from everything recorded about them,
+/// a chapter at a time so a long life fits in the prompts.
+pub async fn write<W: AutobiographyCapability + PersonCapability + ClockCapability>(
+    worker: &W,
+    person_uuid: &PersonUuid,
+) -> Result<Autobiography, Error> {
+    let person_name = worker
+        .get_persons_name(person_uuid.clone())
+        .await
+        .map_err(Error::GetPersonName)?
+        .to_string();
+
+    let events = worker
+        .get_life_events(person_uuid)
+        .await
+        .map_err(Error::GetLifeEvents)?;
+
+    if events.is_empty() {
+        return Err(Error::NothingHappened);
+    }
+
+    let event_count = events.len();
+    let mut chapters: Vec<Chapter> = Vec::new();
+
+    for (index, chapter_events) in into_chapters(events).into_iter().enumerate() {
+        let previous_chapter = chapters.last().map(|chapter| chapter.text.as_str());
+
+        let text = worker
+            .write_autobiography_chapter(person_name.as_str(), previous_chapter, &chapter_events)
+            .await
+            .map_err(|details| Error::WriteChapter {
+                number: index + 1,
+                details,
+            })?;
+
+        // Chapters are never empty, so these are always there
+        if let (Some(first), Some(last)) = (chapter_events.first(), chapter_events.last()) {
+            chapters.push(Chapter {
+                from: first.at,
+                to: last.at,
+                text,
+            });
+        }
+    }
+
+    Ok(Autobiography {
+        person_name,
+        written_at: worker.now(),
+        event_count,
+        chapters,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, TimeZone};
+
+    fn events(count: usize) -> Vec<LifeEvent> {
+        let start = Utc.with_ymd_and_hms(2026, 3, 18, 9, 0, 0).unwrap();
+        (0..count)
+            .map(|index| LifeEvent {
+                at: start + Duration::minutes(index as i64),
+                kind: LifeEventKind::Memory,
+                text: format!("Memory {}", index),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_short_lives_get_few_chapters() {
+        let chapters = into_chapters(events(90));
+
+        assert_eq!(
+            chapters.iter().map(Vec::len).collect::<Vec<usize>>(),
+            vec![40, 40, 10]
+        );
+    }
+
+    #[test]
+    fn test_long_lives_get_longer_chapters_instead_of_more() {
+        let chapters = into_chapters(events(3_000));
+
+        assert_eq!(chapters.len(), MAX_CHAPTERS);
+        assert!(chapters.iter().all(|chapter| chapter.len() == 100));
+        assert_eq!(chapters[0][0].text, "Memory 0");
+        assert_eq!(chapters[29][99].text, "Memory 2999");
+    }
+
+    #[test]
+    fn test_event_lines_are_dated_and_cut_short() {
+        let event = LifeEvent {
+            at: Utc.with_ymd_and_hms(2026, 3, 18, 9, 5, 0).unwrap(),
+            kind: LifeEventKind::JoinedScene,
+            text: "x".repeat(MAX_EVENT_CHARS + 10),
+        };
+
+        assert_eq!(
+            event.to_line(),
+            format!("2026-03-18 09:05 (arrived at) {}...", "x".repeat(500))
+        );
+    }
+}
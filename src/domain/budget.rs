@@ -0,0 +1,261 @@
+use crate::capability::budget::BudgetCapability;
+use chrono::{DateTime, Duration, DurationRound, Utc};
+
+/// How far back the burn rate looks. Long enough to smooth over one slow
+/// minute, short enough that a busy scene shows up in the projection.
+const BURN_RATE_WINDOW_HOURS: i64 = 3;
+
+/// A day's spend past these is worth a look, and then worth stopping for.
+const DAILY_SPEND_WARNING_USD: f64 = 10.0;
+const DAILY_SPEND_ALERT_USD: f64 = 25.0;
+/// How much of the run budget can go before the cost ticker warns.
+const RUN_BUDGET_WARNING_FRACTION: f64 = 0.75;
+
+/// The dollar budget for the current run. A run starts whenever a budget is
+/// set, and only the completions made since then count against it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RunBudget {
+    pub budget_usd: Option<f64>,
+    pub set_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct HourlySpend {
+    pub hour: DateTime<Utc>,
+    pub spent_usd: f64,
+}
+
+/// What the completions in `llm_call` cost, going by `llm_price`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LlmSpend {
+    pub spent_usd: f64,
+    /// Calls that used tokens on a model without a price. Their cost is
+    /// left out of `spent_usd` rather than guessed at.
+    pub unpriced_calls: i64,
+    /// Oldest first, one entry per hour that had any calls.
+    pub hourly: Vec<HourlySpend>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct BudgetLedger {
+    pub budget: RunBudget,
+    pub spend: LlmSpend,
+}
+
+impl BudgetLedger {
+    pub async fn load<W: BudgetCapability>(worker: &W) -> Result<Self, String> {
+        let budget = worker.get_run_budget().await?;
+        let spend = worker.get_llm_spend(budget.set_at).await?;
+
+        Ok(BudgetLedger { budget, spend })
+    }
+
+    pub fn remaining_usd(&self) -> Option<f64> {
+        self.budget
+            .budget_usd
+            .map(|budget_usd| budget_usd - self.spend.spent_usd)
+    }
+
+    pub fn is_exhausted(&self) -> bool {
+        match self.remaining_usd() {
+            Some(remaining_usd) => remaining_usd <= 0.0,
+            None => false,
+        }
+    }
+
+    /// Dollars an hour over the last few hours of the run.
+    pub fn burn_rate_usd_per_hour(&self, now: DateTime<Utc>) -> f64 {
+        let window_start = std::cmp::max(
+            self.budget.set_at,
+            now - Duration::hours(BURN_RATE_WINDOW_HOURS),
+        );
+        let window_start = match window_start.duration_trunc(Duration::hours(1)) {
+            Ok(hour) => hour,
+            Err(_) => window_start,
+        };
+
+        let hours = (now - window_start).num_seconds() as f64 / 3600.0;
+        if hours <= 0.0 {
+            return 0.0;
+        }
+
+        let recent_usd: f64 = self
+            .spend
+            .hourly
+            .iter()
+            .filter(|hourly| hourly.hour >= window_start)
+            .map(|hourly| hourly.spent_usd)
+            .sum();
+
+        recent_usd / hours
+    }
+
+    /// When the budget runs out if spending keeps up its recent pace. None
+    /// when there is no budget, it is already spent, or nothing is being
+    /// spent.
+    pub fn projected_exhaustion(&self, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        let remaining_usd = self.remaining_usd()?;
+        if remaining_usd <= 0.0 {
+            return None;
+        }
+
+        let burn_rate = self.burn_rate_usd_per_hour(now);
+        if burn_rate <= 0.0 {
+            return None;
+        }
+
+        let secs = (remaining_usd / burn_rate * 3600.0).min(i64::MAX as f64) as i64;
+        now.checked_add_signed(Duration::seconds(secs))
+    }
+
+    /// What was left of the budget at the end of each hour of the run.
+    pub fn burn_down(&self) -> Vec<(DateTime<Utc>, f64)> {
+        let budget_usd = match self.budget.budget_usd {
+            Some(budget_usd) => budget_usd,
+            None => return Vec::new(),
+        };
+
+        let mut remaining_usd = budget_usd;
+        self.spend
+            .hourly
+            .iter()
+            .map(|hourly| {
+                remaining_usd -= hourly.spent_usd;
+                (hourly.hour, remaining_usd)
+            })
+            .collect()
+    }
+}
+
+/// How worried the admin ui's cost ticker looks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum SpendLevel {
+    Normal,
+    Warning,
+    Alert,
+}
+
+impl SpendLevel {
+    /// The worse of what was spent today and how much of the run budget is
+    /// gone.
+    pub fn of(today_usd: f64, ledger: &BudgetLedger) -> Self {
+        let daily = if today_usd >= DAILY_SPEND_ALERT_USD {
+            SpendLevel::Alert
+        } else if today_usd >= DAILY_SPEND_WARNING_USD {
+            SpendLevel::Warning
+        } else {
+            SpendLevel::Normal
+        };
+
+        let run = match ledger.budget.budget_usd {
+            Some(budget_usd) if ledger.spend.spent_usd >= budget_usd => SpendLevel::Alert,
+            Some(budget_usd)
+                if ledger.spend.spent_usd >= budget_usd * RUN_BUDGET_WARNING_FRACTION =>
+            {
+                SpendLevel::Warning
+            }
+            _ => SpendLevel::Normal,
+        };
+
+        daily.max(run)
+    }
+}
+
+pub fn parse_budget_usd(input: &str) -> Result<f64, String> {
+    let trimmed = input.trim().trim_start_matches('$');
+
+    match trimmed.parse::<f64>() {
+        Ok(budget_usd) if budget_usd.is_finite() && budget_usd > 0.0 => Ok(budget_usd),
+        _ => Err(format!(
+            "The budget must be a dollar amount above zero, but it was \"{}\"",
+            input
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn hour(hour: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 10, 17, hour, 0, 0)
+            .single()
+            .expect("valid test time")
+    }
+
+    fn ledger(budget_usd: Option<f64>, hourly: Vec<(u32, f64)>) -> BudgetLedger {
+        let hourly: Vec<HourlySpend> = hourly
+            .into_iter()
+            .map(|(h, spent_usd)| HourlySpend {
+                hour: hour(h),
+                spent_usd,
+            })
+            .collect();
+
+        BudgetLedger {
+            budget: RunBudget {
+                budget_usd,
+                set_at: hour(8),
+            },
+            spend: LlmSpend {
+                spent_usd: hourly.iter().map(|hourly| hourly.spent_usd).sum(),
+                unpriced_calls: 0,
+                hourly,
+            },
+        }
+    }
+
+    #[test]
+    fn test_projects_exhaustion_from_the_recent_burn_rate() {
+        // Spending picked up over the last three hours, so the early quiet
+        // hours should not slow the projection down
+        let ledger = ledger(
+            Some(10.0),
+            vec![(8, 0.25), (9, 0.25), (10, 1.0), (11, 1.0), (12, 1.0)],
+        );
+
+        assert_eq!(ledger.remaining_usd(), Some(6.5));
+        assert_eq!(ledger.burn_rate_usd_per_hour(hour(13)), 1.0);
+        assert_eq!(
+            ledger.projected_exhaustion(hour(13)),
+            Some(hour(13) + Duration::minutes(390))
+        );
+        assert_eq!(
+            ledger.burn_down(),
+            vec![
+                (hour(8), 9.75),
+                (hour(9), 9.5),
+                (hour(10), 8.5),
+                (hour(11), 7.5),
+                (hour(12), 6.5),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_exhausted_and_unbudgeted_runs_have_no_projection() {
+        let spent = ledger(Some(1.0), vec![(8, 0.6), (9, 0.6)]);
+        assert!(spent.is_exhausted());
+        assert_eq!(spent.projected_exhaustion(hour(10)), None);
+
+        let unbudgeted = ledger(None, vec![(8, 0.6)]);
+        assert!(!unbudgeted.is_exhausted());
+        assert_eq!(unbudgeted.projected_exhaustion(hour(10)), None);
+        assert!(unbudgeted.burn_down().is_empty());
+    }
+
+    #[test]
+    fn test_spend_level_is_the_worse_of_today_and_the_run() {
+        let quarter_spent = ledger(Some(10.0), vec![(8, 2.5)]);
+        assert_eq!(SpendLevel::of(2.5, &quarter_spent), SpendLevel::Normal);
+        assert_eq!(SpendLevel::of(12.0, &quarter_spent), SpendLevel::Warning);
+
+        let mostly_spent = ledger(Some(10.0), vec![(8, 8.0)]);
+        assert_eq!(SpendLevel::of(1.0, &mostly_spent), SpendLevel::Warning);
+        assert_eq!(SpendLevel::of(30.0, &mostly_spent), SpendLevel::Alert);
+
+        let unbudgeted = ledger(None, vec![(8, 40.0)]);
+        assert_eq!(SpendLevel::of(1.0, &unbudgeted), SpendLevel::Normal);
+    }
+}
@@ -0,0 +1,288 @@
+use crate::capability::memory::MemoryCapability;
+use crate::capability::person::PersonCapability;
+use crate::capability::person_identity::PersonIdentityCapability;
+use crate::capability::relationship::{NewRelationship, RelationshipCapability};
+use crate::capability::state_of_mind::StateOfMindCapability;
+use crate::domain::person_name::PersonName;
+use crate::domain::person_uuid::PersonUuid;
+use crate::domain::persona::{self, Persona};
+use crate::nice_display::{nest, with_context, NiceDisplay};
+use crate::open_ai::tool::{ToolFunction, ToolFunctionParameter};
+use crate::open_ai::tool_call::ToolCall;
+use std::collections::HashMap;
+
+pub const TOOL_NAME: &str = "create_cast";
+
+/// A group of generated people who already know each other.
+#[derive(Debug, Clone)]
+pub struct Cast {
+    pub personas: Vec<Persona>,
+    pub relationships: Vec<CastRelationship>,
+}
+
+#[derive(Debug, Clone)]
+pub struct CastRelationship {
+    pub person_name: PersonName,
+    pub other_person_name: PersonName,
+    pub description: String,
+}
+
+pub enum Error {
+    CreatePersona {
+        person_name: PersonName,
+        error: persona::Error,
+    },
+    CreateRelationships(String),
+}
+
+impl NiceDisplay for Error {
+    fn message(&self) -> String {
+        match self {
+            Error::CreatePersona { person_name, error } => {
+                nest(format!("Error creating {}", person_name), error)
+            }
+            Error::CreateRelationships(err) => with_context("Error creating relationships", err),
+        }
+    }
+}
+
+impl Cast {
+    pub fn tool(count: usize) -> ToolFunction {
+        ToolFunction::new(
+            TOOL_NAME.to_string(),
+            "Create a group of people for the simulation who share a setting and know each other.".to_string(),
+            vec![
+                ToolFunctionParameter::ObjectArray {
+                    name: "personas".to_string(),
+                    description: format!("Exactly {} people, each with a unique name.", count),
+                    required: true,
+                    fields: Persona::tool_parameters(),
+                },
+                ToolFunctionParameter::ObjectArray {
+                    name: "relationships".to_string(),
+                    description: "How the people see each other. Include both directions when both people know each other.".to_string(),
+                    required: true,
+                    fields: vec![
+                        ToolFunctionParameter::String {
+                            name: "person_name".to_string(),
+                            description: "The name of the person who holds this view.".to_string(),
+                            required: true,
+                        },
+                        ToolFunctionParameter::String {
+                            name: "other_person_name".to_string(),
+                            description: "The name of the person they are describing.".to_string(),
+                            required: true,
+                        },
+                        ToolFunctionParameter::String {
+                            name: "description".to_string(),
+                            description: "One or two sentences in the first person about who the other person is to them and how they feel about them.".to_string(),
+                            required: true,
+                        },
+                    ],
+                },
+            ],
+        )
+    }
+
+    pub fn from_tool_call(call: &ToolCall) -> Result<Self, String> {
+        let personas = object_array_argument(call, "personas")?
+            .into_iter()
+            .map(Persona::from_arguments)
+            .collect::<Result<Vec<Persona>, String>>()?;
+
+        let mut names = Vec::with_capacity(personas.len());
+        for persona in personas.iter() {
+            if persona.name.as_str().is_empty() {
+                return Err("Every persona in the cast needs a name".to_string());
+            }
+
+            if names.contains(&persona.name.as_str()) {
+                return Err(format!(
+                    "The cast has more than one person named {}",
+                    persona.name
+                ));
+            }
+
+            names.push(persona.name.as_str());
+        }
+
+        let mut relationships = Vec::new();
+        for arguments in object_array_argument(call, "relationships")? {
+            let person_name = persona::string_argument(arguments, "person_name")?;
+            let other_person_name = persona::string_argument(arguments, "other_person_name")?;
+            let person_name = person_name.trim();
+            let other_person_name = other_person_name.trim();
+
+            if !names.contains(&person_name) || !names.contains(&other_person_name) {
+                return Err(format!(
+                    "The relationship between {} and {} names someone who is not in the cast",
+                    person_name, other_person_name
+                ));
+            }
+
+            if person_name == other_person_name {
+                continue;
+            }
+
+            relationships.push(CastRelationship {
+                person_name: PersonName::from_string(person_name.to_string()),
+                other_person_name: PersonName::from_string(other_person_name.to_string()),
+                description: persona::string_argument(arguments, "description")?,
+            });
+        }
+
+        Ok(Cast {
+            personas,
+            relationships,
+        })
+    }
+}
+
+/// Saves every persona, then seeds all of their relationships in one batch.
+pub async fn create_cast<
+    W: PersonCapability
+        + PersonIdentityCapability
+        + StateOfMindCapability
+        + MemoryCapability
+        + RelationshipCapability,
+>(
+    worker: &W,
+    cast: Cast,
+) -> Result<Vec<(PersonName, PersonUuid)>, Error> {
+    let mut created = Vec::with_capacity(cast.personas.len());
+
+    for persona in cast.personas {
+        let person_name = persona.name.clone();
+        let person_uuid = persona::create_persona(worker, persona)
+            .await
+            .map_err(|error| Error::CreatePersona {
+                person_name: person_name.clone(),
+                error,
+            })?;
+
+        created.push((person_name, person_uuid));
+    }
+
+    let uuids_by_name = created
+        .iter()
+        .map(|(person_name, person_uuid)| (person_name.as_str(), person_uuid))
+        .collect::<HashMap<&str, &PersonUuid>>();
+
+    let relationships = cast
+        .relationships
+        .into_iter()
+        .filter_map(|relationship| {
+            let person_uuid = uuids_by_name.get(relationship.person_name.as_str())?;
+            let other_person_uuid = uuids_by_name.get(relationship.other_person_name.as_str())?;
+
+            Some(NewRelationship {
+                person_uuid: (*person_uuid).clone(),
+                other_person_uuid: (*other_person_uuid).clone(),
+                description: relationship.description,
+            })
+        })
+        .collect::<Vec<NewRelationship>>();
+
+    worker
+        .create_relationships(relationships)
+        .await
+        .map_err(Error::CreateRelationships)?;
+
+    Ok(created)
+}
+
+fn object_array_argument<'a>(
+    call: &'a ToolCall,
+    key: &str,
+) -> Result<Vec<&'a serde_json::Map<String, serde_json::Value>>, String> {
+    call.arguments
+        .iter()
+        .find(|(name, _)| name == key)
+        .map(|(_, value)| value)
+        .ok_or_else(|| format!("Missing '{}' in cast tool arguments", key))?
+        .as_array()
+        .ok_or_else(|| format!("'{}' must be an array", key))?
+        .iter()
+        .map(|item| {
+            item.as_object()
+                .ok_or_else(|| format!("'{}' must contain only objects", key))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn persona_json(name: &str) -> serde_json::Value {
+        serde_json::json!({
+            "name": name,
+            "identity": format!("You are {}.", name),
+            "state_of_mind": "Fine.",
+            "memories": ["I moved here last spring."],
+        })
+    }
+
+    #[test]
+    fn test_cast_from_tool_call_reads_personas_and_relationships() {
+        let call = ToolCall {
+            name: TOOL_NAME.to_string(),
+            arguments: vec![
+                (
+                    "personas".to_string(),
+                    serde_json::json!([persona_json("Dolores"), persona_json("Hank")]),
+                ),
+                (
+                    "relationships".to_string(),
+                    serde_json::json!([{
+                        "person_name": "Dolores",
+                        "other_person_name": " Hank ",
+                        "description": "Hank is my most loyal customer.",
+                    }]),
+                ),
+            ],
+        };
+
+        let cast = Cast::from_tool_call(&call).unwrap();
+
+        assert_eq!(cast.personas.len(), 2);
+        assert_eq!(cast.relationships.len(), 1);
+        assert_eq!(cast.relationships[0].person_name.as_str(), "Dolores");
+        assert_eq!(cast.relationships[0].other_person_name.as_str(), "Hank");
+    }
+
+    #[test]
+    fn test_cast_from_tool_call_rejects_strangers_and_duplicate_names() {
+        let stranger = ToolCall {
+            name: TOOL_NAME.to_string(),
+            arguments: vec![
+                (
+                    "personas".to_string(),
+                    serde_json::json!([persona_json("Dolores")]),
+                ),
+                (
+                    "relationships".to_string(),
+                    serde_json::json!([{
+                        "person_name": "Dolores",
+                        "other_person_name": "Hank",
+                        "description": "Hank is my most loyal customer.",
+                    }]),
+                ),
+            ],
+        };
+
+        let duplicate = ToolCall {
+            name: TOOL_NAME.to_string(),
+            arguments: vec![
+                (
+                    "personas".to_string(),
+                    serde_json::json!([persona_json("Dolores"), persona_json("Dolores")]),
+                ),
+                ("relationships".to_string(), serde_json::json!([])),
+            ],
+        };
+
+        assert!(Cast::from_tool_call(&stranger).is_err());
+        assert!(Cast::from_tool_call(&duplicate).is_err());
+    }
+}
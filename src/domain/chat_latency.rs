@@ -0,0 +1,77 @@
+use crate::capability::chat_latency::ChatLatencyCapability;
+use crate::capability::clock::ClockCapability;
+use chrono::Duration;
+
+/// How far back the latency figures look.
+const WINDOW_HOURS: i64 = 24;
+
+/// Replies slower than this feel like the person has wandered off. The p95
+/// should stay under it.
+pub const P95_TARGET_MS: i64 = 30_000;
+
+/// How quickly persons answered the real world user, over the last day.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChatLatency {
+    pub exchanges: usize,
+    pub p50_ms: i64,
+    pub p95_ms: i64,
+}
+
+impl ChatLatency {
+    /// `None` if the real world user has not been answered in the window.
+    pub async fn load<W: ChatLatencyCapability + ClockCapability>(
+        worker: &W,
+    ) -> Result<Option<Self>, String> {
+        let since = worker.now() - Duration::hours(WINDOW_HOURS);
+        let latencies_ms = worker.get_chat_latencies_ms(since).await?;
+
+        Ok(ChatLatency::from_latencies_ms(latencies_ms))
+    }
+
+    pub fn from_latencies_ms(mut latencies_ms: Vec<i64>) -> Option<Self> {
+        if latencies_ms.is_empty() {
+            return None;
+        }
+
+        latencies_ms.sort_unstable();
+
+        Some(ChatLatency {
+            exchanges: latencies_ms.len(),
+            p50_ms: percentile(&latencies_ms, 50),
+            p95_ms: percentile(&latencies_ms, 95),
+        })
+    }
+
+    pub fn meets_target(&self) -> bool {
+        self.p95_ms <= P95_TARGET_MS
+    }
+}
+
+/// Nearest rank, so the figure is always a latency someone actually waited.
+fn percentile(sorted_ms: &[i64], percent: usize) -> i64 {
+    let rank = (sorted_ms.len() * percent).div_ceil(100).max(1);
+
+    sorted_ms[rank - 1]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_percentiles_use_the_nearest_rank() {
+        let latencies_ms = (1..=20).rev().map(|second| second * 1_000).collect();
+        let latency = ChatLatency::from_latencies_ms(latencies_ms).unwrap();
+
+        assert_eq!(latency.exchanges, 20);
+        assert_eq!(latency.p50_ms, 10_000);
+        assert_eq!(latency.p95_ms, 19_000);
+        assert!(latency.meets_target());
+
+        let slow = ChatLatency::from_latencies_ms(vec![45_000]).unwrap();
+        assert_eq!(slow.p50_ms, 45_000);
+        assert!(!slow.meets_target());
+
+        assert_eq!(ChatLatency::from_latencies_ms(Vec::new()), None);
+    }
+}
@@ -0,0 +1,59 @@
+#[cfg(test)]
+use chrono::Duration;
+use chrono::{DateTime, Utc};
+#[cfg(test)]
+use std::sync::{Arc, Mutex};
+
+/// Where the worker reads the time from. Tests use a fake one that only
+/// moves when they advance it, so waits and schedules can be checked
+/// without sleeping.
+#[derive(Debug, Clone)]
+pub enum Clock {
+    System,
+    #[cfg(test)]
+    Fake(Arc<Mutex<DateTime<Utc>>>),
+}
+
+impl Clock {
+    #[cfg(test)]
+    pub fn fake(at: DateTime<Utc>) -> Self {
+        Clock::Fake(Arc::new(Mutex::new(at)))
+    }
+
+    pub fn now(&self) -> DateTime<Utc> {
+        match self {
+            Clock::System => Utc::now(),
+            #[cfg(test)]
+            Clock::Fake(at) => *at.lock().unwrap_or_else(|poisoned| poisoned.into_inner()),
+        }
+    }
+
+    /// Moves a fake clock forward. The system clock moves on its own, so
+    /// this does nothing to it.
+    #[cfg(test)]
+    pub fn advance(&self, by: Duration) {
+        if let Clock::Fake(at) = self {
+            let mut at = at.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+            *at += by;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_fake_clock_only_moves_when_advanced() {
+        let start = Utc.with_ymd_and_hms(2026, 3, 18, 9, 0, 0).unwrap();
+        let clock = Clock::fake(start);
+        let shared = clock.clone();
+
+        assert_eq!(clock.now(), start);
+
+        shared.advance(Duration::minutes(5));
+
+        assert_eq!(clock.now(), start + Duration::minutes(5));
+    }
+}
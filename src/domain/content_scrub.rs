@@ -0,0 +1,119 @@
+use regex::Regex;
+use std::sync::OnceLock;
+
+const EMAIL_PATTERN: &str = r"(?i)\b[A-Z0-9._%+-]+@[A-Z0-9.-]+\.[A-Z]{2,}\b";
+const PHONE_PATTERN: &str = r"(?:\+\d{1,3}[\s.-]?)?(?:\(\d{3}\)|\b\d{3})[\s.-]?\d{3}[\s.-]?\d{4}\b";
+const PROFANITY_PATTERN: &str = r"(?i)\b(?:fuck|shit|cunt|bitch|asshole|motherfuck)\w*";
+
+const EMAIL_REPLACEMENT: &str = "[email removed]";
+const PHONE_REPLACEMENT: &str = "[phone removed]";
+
+static EMAIL_REGEX: OnceLock<Result<Regex, regex::Error>> = OnceLock::new();
+static PHONE_REGEX: OnceLock<Result<Regex, regex::Error>> = OnceLock::new();
+static PROFANITY_REGEX: OnceLock<Result<Regex, regex::Error>> = OnceLock::new();
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ContentScrubSettings {
+    pub scrub_personal_data: bool,
+    pub scrub_profanity: bool,
+}
+
+impl ContentScrubSettings {
+    /// Applies the enabled scrubbers to content that is about to be stored.
+    pub fn scrub(&self, content: &str) -> Result<String, String> {
+        let mut scrubbed = content.to_string();
+
+        if self.scrub_personal_data {
+            let email_regex = compiled(&EMAIL_REGEX, EMAIL_PATTERN)?;
+            scrubbed = email_regex
+                .replace_all(scrubbed.as_str(), EMAIL_REPLACEMENT)
+                .into_owned();
+
+            let phone_regex = compiled(&PHONE_REGEX, PHONE_PATTERN)?;
+            scrubbed = phone_regex
+                .replace_all(scrubbed.as_str(), PHONE_REPLACEMENT)
+                .into_owned();
+        }
+
+        if self.scrub_profanity {
+            let profanity_regex = compiled(&PROFANITY_REGEX, PROFANITY_PATTERN)?;
+            scrubbed = profanity_regex
+                .replace_all(scrubbed.as_str(), |caps: &regex::Captures| {
+                    mask_word(&caps[0])
+                })
+                .into_owned();
+        }
+
+        Ok(scrubbed)
+    }
+}
+
+fn compiled<'a>(
+    cell: &'a OnceLock<Result<Regex, regex::Error>>,
+    pattern: &str,
+) -> Result<&'a Regex, String> {
+    cell.get_or_init(|| Regex::new(pattern))
+        .as_ref()
+        .map_err(|err| format!("Invalid scrub pattern {}: {}", pattern, err))
+}
+
+fn mask_word(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => {
+            let mut masked = first.to_string();
+            for _ in chars {
+                masked.push('*');
+            }
+            masked
+        }
+        None => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ALL: ContentScrubSettings = ContentScrubSettings {
+        scrub_personal_data: true,
+        scrub_profanity: true,
+    };
+
+    #[test]
+    fn test_scrub_removes_emails_and_phone_numbers() {
+        let scrubbed = ALL
+            .scrub("Mail me at jane.doe@example.com or call (555) 123-4567.")
+            .unwrap();
+
+        assert_eq!(
+            scrubbed,
+            "Mail me at [email removed] or call [phone removed]."
+        );
+    }
+
+    #[test]
+    fn test_scrub_masks_profanity() {
+        let scrubbed = ALL.scrub("Oh shit, that's fucking great").unwrap();
+
+        assert_eq!(scrubbed, "Oh s***, that's f****** great");
+    }
+
+    #[test]
+    fn test_scrub_leaves_content_alone_when_disabled() {
+        let settings = ContentScrubSettings {
+            scrub_personal_data: false,
+            scrub_profanity: false,
+        };
+        let content = "shit, jane@example.com 555-123-4567";
+
+        assert_eq!(settings.scrub(content).unwrap(), content);
+    }
+
+    #[test]
+    fn test_scrub_keeps_short_numbers() {
+        let scrubbed = ALL.scrub("Meet at 10:30 in room 204").unwrap();
+
+        assert_eq!(scrubbed, "Meet at 10:30 in room 204");
+    }
+}
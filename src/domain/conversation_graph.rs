@@ -0,0 +1,226 @@
+use std::collections::{HashMap, HashSet};
+use uuid::Uuid;
+
+/// Words that make a message read as warm. Matched against lowercased
+/// whole words.
+pub const POSITIVE_WORDS: &[&str] = &[
+    "thanks",
+    "thank",
+    "love",
+    "great",
+    "glad",
+    "happy",
+    "nice",
+    "good",
+    "wonderful",
+    "kind",
+    "appreciate",
+    "welcome",
+    "sorry",
+    "please",
+    "friend",
+    "awesome",
+    "lovely",
+    "haha",
+    "fun",
+    "beautiful",
+    "agree",
+    "yes",
+    "sure",
+    "excited",
+    "congratulations",
+];
+
+/// Words that make a message read as hostile or upset.
+pub const NEGATIVE_WORDS: &[&str] = &[
+    "hate",
+    "angry",
+    "stupid",
+    "idiot",
+    "shut",
+    "annoying",
+    "terrible",
+    "awful",
+    "wrong",
+    "liar",
+    "leave",
+    "stop",
+    "never",
+    "ugh",
+    "worst",
+    "sad",
+    "upset",
+    "disgusting",
+    "ridiculous",
+    "no",
+    "blame",
+    "fault",
+    "rude",
+    "jerk",
+    "damn",
+];
+
+/// Who talked to whom over some stretch of time.
+#[derive(Debug, Clone)]
+pub struct ConversationGraph {
+    pub people: Vec<GraphPerson>,
+    pub edges: Vec<ConversationEdge>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GraphPerson {
+    /// Missing for the real world user.
+    pub person_uuid: Option<Uuid>,
+    pub name: String,
+}
+
+/// Every message one speaker got to one listener, in one direction.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConversationEdge {
+    pub from: Option<Uuid>,
+    pub to: Option<Uuid>,
+    pub message_count: i64,
+    pub positive_words: i64,
+    pub negative_words: i64,
+}
+
+impl ConversationEdge {
+    /// From -1.0 (all negative words) to 1.0 (all positive words), and 0.0
+    /// when the messages had neither.
+    pub fn sentiment(&self) -> f32 {
+        let total = self.positive_words + self.negative_words;
+        if total == 0 {
+            0.0
+        } else {
+            (self.positive_words - self.negative_words) as f32 / total as f32
+        }
+    }
+}
+
+impl ConversationGraph {
+    /// People who neither said nor heard anything in the range.
+    pub fn isolated_people(&self) -> Vec<&GraphPerson> {
+        let connected = self
+            .edges
+            .iter()
+            .flat_map(|edge| [edge.from, edge.to])
+            .collect::<HashSet<Option<Uuid>>>();
+
+        self.people
+            .iter()
+            .filter(|person| !connected.contains(&person.person_uuid))
+            .collect()
+    }
+
+    /// Groups of two or more people who talk among themselves, biggest
+    /// first. Two people are in the same group when a chain of messages in
+    /// either direction links them.
+    pub fn groups(&self) -> Vec<Vec<&GraphPerson>> {
+        let index_by_uuid = self
+            .people
+            .iter()
+            .enumerate()
+            .map(|(index, person)| (person.person_uuid, index))
+            .collect::<HashMap<Option<Uuid>, usize>>();
+
+        let mut parents = (0..self.people.len()).collect::<Vec<usize>>();
+
+        for edge in &self.edges {
+            if let (Some(from), Some(to)) =
+                (index_by_uuid.get(&edge.from), index_by_uuid.get(&edge.to))
+            {
+                let from_root = find_root(&mut parents, *from);
+                let to_root = find_root(&mut parents, *to);
+                parents[from_root] = to_root;
+            }
+        }
+
+        let mut groups: HashMap<usize, Vec<&GraphPerson>> = HashMap::new();
+        for (index, person) in self.people.iter().enumerate() {
+            let root = find_root(&mut parents, index);
+            groups.entry(root).or_default().push(person);
+        }
+
+        let mut groups = groups
+            .into_values()
+            .filter(|group| group.len() > 1)
+            .collect::<Vec<Vec<&GraphPerson>>>();
+
+        groups.sort_by(|a, b| b.len().cmp(&a.len()).then(a[0].name.cmp(&b[0].name)));
+
+        groups
+    }
+}
+
+fn find_root(parents: &mut [usize], index: usize) -> usize {
+    let mut root = index;
+    while parents[root] != root {
+        root = parents[root];
+    }
+
+    // Point everything on the way straight at the root for next time
+    let mut current = index;
+    while parents[current] != root {
+        let next = parents[current];
+        parents[current] = root;
+        current = next;
+    }
+
+    root
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn person(id: u128, name: &str) -> GraphPerson {
+        GraphPerson {
+            person_uuid: Some(Uuid::from_u128(id)),
+            name: name.to_string(),
+        }
+    }
+
+    fn edge(from: u128, to: u128) -> ConversationEdge {
+        ConversationEdge {
+            from: Some(Uuid::from_u128(from)),
+            to: Some(Uuid::from_u128(to)),
+            message_count: 1,
+            positive_words: 0,
+            negative_words: 0,
+        }
+    }
+
+    #[test]
+    fn test_isolated_people_and_groups() {
+        let graph = ConversationGraph {
+            people: vec![
+                person(1, "Ann"),
+                person(2, "Bob"),
+                person(3, "Cat"),
+                person(4, "Dan"),
+                person(5, "Eve"),
+                person(6, "Fay"),
+            ],
+            edges: vec![edge(1, 2), edge(3, 2), edge(4, 5)],
+        };
+
+        assert_eq!(graph.isolated_people(), vec![&person(6, "Fay")]);
+        assert_eq!(
+            graph.groups(),
+            vec![
+                vec![&person(1, "Ann"), &person(2, "Bob"), &person(3, "Cat")],
+                vec![&person(4, "Dan"), &person(5, "Eve")],
+            ]
+        );
+    }
+
+    #[test]
+    fn test_sentiment_ranges_from_negative_to_positive() {
+        let mut edge = edge(1, 2);
+        assert_eq!(edge.sentiment(), 0.0);
+
+        edge.positive_words = 3;
+        edge.negative_words = 1;
+        assert_eq!(edge.sentiment(), 0.5);
+    }
+}
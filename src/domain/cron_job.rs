@@ -0,0 +1,192 @@
+use crate::capability::cron_job::CronJobCapability;
+use crate::capability::job::JobCapability;
+use crate::domain::cron_job_uuid::CronJobUuid;
+use crate::domain::job::JobKind;
+use crate::nice_display::NiceDisplay;
+use chrono::{DateTime, Utc};
+use cron::Schedule;
+use std::str::FromStr;
+use std::time::Duration;
+
+/// How often the job runner looks for cron jobs that are due.
+pub const CHECK_INTERVAL: Duration = Duration::from_secs(30);
+
+/// A job the job runner enqueues on a schedule, like a `wake idle persons`
+/// scan every night at 3.
+#[derive(Debug, Clone)]
+pub struct CronJob {
+    pub uuid: CronJobUuid,
+    pub name: String,
+    pub expression: String,
+    pub job_name: String,
+    pub job_data: Option<serde_json::Value>,
+    pub enabled: bool,
+    pub last_run_at: Option<DateTime<Utc>>,
+    /// Not set while the cron job is disabled.
+    pub next_run_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct NewCronJob {
+    pub name: String,
+    pub expression: String,
+    pub job_name: String,
+    pub job_data: Option<serde_json::Value>,
+}
+
+/// One due cron job the job runner got to.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CronRun {
+    pub name: String,
+    /// Set when the cron job could not be enqueued. It is still moved on to
+    /// its next run.
+    pub error: Option<String>,
+}
+
+impl NewCronJob {
+    /// Checks the expression and that the payload makes a job, so that
+    /// whatever is saved can be enqueued later. Blank job data means the job
+    /// takes none.
+    pub fn parse(
+        name: &str,
+        expression: &str,
+        job_name: &str,
+        job_data: &str,
+    ) -> Result<NewCronJob, String> {
+        let name = name.trim();
+        if name.is_empty() {
+            return Err("A cron job needs a name".to_string());
+        }
+
+        let expression = expression.trim();
+        parse_schedule(expression)?;
+
+        let job_data = match job_data.trim() {
+            "" => None,
+            text => Some(
+                serde_json::from_str::<serde_json::Value>(text)
+                    .map_err(|err| format!("Job data is not valid json: {}", err))?,
+            ),
+        };
+
+        let job_name = job_name.trim();
+        JobKind::parse(job_name.to_string(), job_data.clone()).map_err(|err| err.message())?;
+
+        Ok(NewCronJob {
+            name: name.to_string(),
+            expression: expression.to_string(),
+            job_name: job_name.to_string(),
+            job_data,
+        })
+    }
+}
+
+impl CronJob {
+    pub fn to_job_kind(&self) -> Result<JobKind, String> {
+        JobKind::parse(self.job_name.clone(), self.job_data.clone()).map_err(|err| err.message())
+    }
+}
+
+/// Takes the usual five fields (minute, hour, day of month, month, day of
+/// week), or six with seconds first. Times are UTC.
+pub fn parse_schedule(expression: &str) -> Result<Schedule, String> {
+    let expression = expression.trim();
+    let with_seconds = if expression.split_whitespace().count() == 5 {
+        format!("0 {}", expression)
+    } else {
+        expression.to_string()
+    };
+
+    Schedule::from_str(&with_seconds)
+        .map_err(|err| format!("\"{}\" is not a cron expression: {}", expression, err))
+}
+
+pub fn next_run_after(expression: &str, after: DateTime<Utc>) -> Result<DateTime<Utc>, String> {
+    parse_schedule(expression)?
+        .after(&after)
+        .next()
+        .ok_or_else(|| format!("\"{}\" never runs again", expression))
+}
+
+/// Enqueues every enabled cron job whose next run has come, and moves each
+/// on to its following run. Runs missed while the job runner was off are
+/// not made up, a cron job runs at most once per check.
+pub async fn enqueue_due_cron_jobs<W: CronJobCapability + JobCapability>(
+    worker: &W,
+    now: DateTime<Utc>,
+) -> Result<Vec<CronRun>, String> {
+    let due = worker.get_due_cron_jobs(now).await?;
+    let mut runs = Vec::new();
+
+    for cron_job in due {
+        let due_at = match cron_job.next_run_at {
+            Some(due_at) => due_at,
+            None => continue,
+        };
+
+        let next = next_run_after(&cron_job.expression, now);
+
+        // Another job runner may have got to it first
+        let claimed = worker
+            .claim_cron_job_run(&cron_job.uuid, due_at, now, next.as_ref().ok().copied())
+            .await?;
+        if !claimed {
+            continue;
+        }
+
+        let enqueued = match next {
+            Err(err) => Err(err),
+            Ok(_) => match cron_job.to_job_kind() {
+                Ok(job) => worker.unshift_job(job).await,
+                Err(err) => Err(err),
+            },
+        };
+
+        runs.push(CronRun {
+            name: cron_job.name,
+            error: enqueued.err(),
+        });
+    }
+
+    Ok(runs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_five_field_expressions_run_on_the_minute() {
+        let after = Utc.with_ymd_and_hms(2026, 3, 4, 10, 15, 30).unwrap();
+
+        assert_eq!(
+            next_run_after("30 3 * * *", after),
+            Ok(Utc.with_ymd_and_hms(2026, 3, 5, 3, 30, 0).unwrap())
+        );
+        assert_eq!(
+            next_run_after("*/10 * * * * *", after),
+            Ok(Utc.with_ymd_and_hms(2026, 3, 4, 10, 15, 40).unwrap())
+        );
+        assert!(parse_schedule("every day").is_err());
+    }
+
+    #[test]
+    fn test_new_cron_job_needs_a_real_job() {
+        let cron_job = NewCronJob::parse(" Nightly scan ", "0 3 * * *", "wake idle persons", " ");
+
+        assert_eq!(
+            cron_job,
+            Ok(NewCronJob {
+                name: "Nightly scan".to_string(),
+                expression: "0 3 * * *".to_string(),
+                job_name: "wake idle persons".to_string(),
+                job_data: None,
+            })
+        );
+        assert!(NewCronJob::parse("Scan", "0 3 * * *", "dance", "").is_err());
+        assert!(NewCronJob::parse("Close", "0 3 * * *", "close scene", "").is_err());
+        assert!(NewCronJob::parse("Scan", "0 3 * * *", "wake idle persons", "{").is_err());
+        assert!(NewCronJob::parse("", "0 3 * * *", "wake idle persons", "").is_err());
+    }
+}
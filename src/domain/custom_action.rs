@@ -0,0 +1,253 @@
+use crate::domain::person_uuid::PersonUuid;
+use crate::domain::scene_uuid::SceneUuid;
+use crate::person_actions::PersonActionKind;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+use std::sync::OnceLock;
+
+/// A json file of world-specific actions persons can take on top of the
+/// built in ones, like `pick lock`. Unset means there are none.
+pub const CUSTOM_ACTIONS_FILE_VAR: &str = "CUSTOM_ACTIONS_FILE";
+
+/// An action loaded from the custom actions file.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CustomAction {
+    /// Like `pick lock`. Offered to the model next to `say in scene`.
+    pub name: String,
+    /// Tells the model when to use it.
+    pub description: String,
+    /// A json schema object for what the model fills in when it picks the
+    /// action. Missing means it takes no arguments.
+    #[serde(default = "empty_parameters")]
+    pub parameters: Value,
+    pub handler: CustomActionHandler,
+}
+
+/// What happens once a person takes a custom action.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum CustomActionHandler {
+    /// Posts the action, who took it, where, and its arguments as json.
+    Webhook { url: String },
+    /// Enqueues a job, like `change scene ambience`. Its data is `data`, with
+    /// `person_uuid`, `scene_uuid` and then the action's arguments filling in
+    /// any fields `data` leaves out.
+    Job {
+        job_name: String,
+        #[serde(default)]
+        data: Map<String, Value>,
+    },
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CustomActions {
+    actions: Vec<CustomAction>,
+}
+
+fn empty_parameters() -> Value {
+    json!({ "type": "object", "properties": {} })
+}
+
+impl CustomAction {
+    /// The `choose_action` parameter the model fills in with this action's
+    /// arguments, like `pick_lock_arguments`.
+    pub fn arguments_parameter_name(&self) -> String {
+        let snake_name = self
+            .name
+            .split_whitespace()
+            .collect::<Vec<&str>>()
+            .join("_");
+
+        format!("{}_arguments", snake_name)
+    }
+
+    /// The reaction history kind an action is recorded and budgeted under.
+    pub fn reaction_kind(name: &str) -> String {
+        format!("custom:{}", name)
+    }
+}
+
+impl CustomActions {
+    /// Reads the actions from the file at `CUSTOM_ACTIONS_FILE`. Read once,
+    /// since every reaction builds its tool from them. A file that can't be
+    /// read or parsed is logged and ignored rather than keeping persons from
+    /// acting at all.
+    pub fn registered() -> &'static CustomActions {
+        static CUSTOM_ACTIONS: OnceLock<CustomActions> = OnceLock::new();
+
+        CUSTOM_ACTIONS.get_or_init(|| match dotenv::var(CUSTOM_ACTIONS_FILE_VAR) {
+            Ok(path) if !path.trim().is_empty() => match CustomActions::load(path.trim()) {
+                Ok(custom_actions) => custom_actions,
+                Err(err) => {
+                    tracing::warn!("{}, going without custom actions", err);
+                    CustomActions::default()
+                }
+            },
+            _ => CustomActions::default(),
+        })
+    }
+
+    pub fn load(path: &str) -> Result<CustomActions, String> {
+        let json = std::fs::read_to_string(path)
+            .map_err(|err| format!("Could not read custom actions file {}: {}", path, err))?;
+
+        CustomActions::parse(&json, &PersonActionKind::builtin_action_names())
+            .map_err(|err| format!("Invalid custom actions file {}: {}", path, err))
+    }
+
+    /// `reserved_names` are the built in actions, which custom actions may
+    /// not shadow.
+    pub fn parse(json: &str, reserved_names: &[String]) -> Result<CustomActions, String> {
+        let actions = serde_json::from_str::<Vec<CustomAction>>(json)
+            .map_err(|err| format!("could not parse the actions: {}", err))?;
+
+        let mut seen_names: Vec<String> = reserved_names.to_vec();
+
+        for action in actions.iter() {
+            let name = action.name.trim();
+
+            if name.is_empty() {
+                return Err("an action has no name".to_string());
+            }
+            if name != action.name {
+                return Err(format!("\"{}\" has spaces around its name", action.name));
+            }
+            if seen_names.iter().any(|seen| seen == name) {
+                return Err(format!("\"{}\" is already an action", name));
+            }
+            if action.parameters.get("type") != Some(&json!("object")) {
+                return Err(format!(
+                    "the parameters of \"{}\" must be a json schema with \"type\": \"object\"",
+                    name
+                ));
+            }
+
+            seen_names.push(name.to_string());
+        }
+
+        Ok(CustomActions { actions })
+    }
+
+    pub fn all(&self) -> &[CustomAction] {
+        &self.actions
+    }
+
+    pub fn find(&self, name: &str) -> Option<&CustomAction> {
+        self.actions.iter().find(|action| action.name == name)
+    }
+
+    pub fn find_by_arguments_parameter(&self, parameter_name: &str) -> Option<&CustomAction> {
+        self.actions
+            .iter()
+            .find(|action| action.arguments_parameter_name() == parameter_name)
+    }
+}
+
+/// The data for a job handler's job. Fields set in the file win over the
+/// person and scene, which win over what the model filled in.
+pub fn job_data(
+    data: &Map<String, Value>,
+    person_uuid: &PersonUuid,
+    scene_uuid: Option<&SceneUuid>,
+    arguments: &Value,
+) -> Value {
+    let mut job_data = data.clone();
+
+    job_data
+        .entry("person_uuid")
+        .or_insert_with(|| json!(person_uuid.to_uuid()));
+
+    if let Some(scene_uuid) = scene_uuid {
+        job_data
+            .entry("scene_uuid")
+            .or_insert_with(|| json!(scene_uuid.to_uuid()));
+    }
+
+    if let Some(arguments) = arguments.as_object() {
+        for (key, value) in arguments {
+            job_data.entry(key.clone()).or_insert_with(|| value.clone());
+        }
+    }
+
+    Value::Object(job_data)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    const ACTIONS: &str = r#"[
+        {
+            "name": "pick lock",
+            "description": "Try to pick the lock on something.",
+            "parameters": {
+                "type": "object",
+                "properties": { "target": { "type": "string" } },
+                "required": ["target"]
+            },
+            "handler": { "type": "webhook", "url": "http://localhost:9000/actions" }
+        },
+        {
+            "name": "turn off the lights",
+            "description": "Plunge the room into darkness.",
+            "handler": {
+                "type": "job",
+                "job_name": "change scene ambience",
+                "data": { "ambience": { "weather": "clear", "noise": "quiet", "lighting": "dark" } }
+            }
+        }
+    ]"#;
+
+    #[test]
+    fn test_parse_reads_handlers_and_defaults_parameters() {
+        let custom_actions = CustomActions::parse(ACTIONS, &["ask".to_string()]).unwrap();
+
+        let pick_lock = custom_actions.find("pick lock").unwrap();
+        assert_eq!(pick_lock.arguments_parameter_name(), "pick_lock_arguments");
+        assert_eq!(
+            pick_lock.handler,
+            CustomActionHandler::Webhook {
+                url: "http://localhost:9000/actions".to_string()
+            }
+        );
+
+        let lights = custom_actions
+            .find_by_arguments_parameter("turn_off_the_lights_arguments")
+            .unwrap();
+        assert_eq!(lights.parameters, empty_parameters());
+        assert_eq!(
+            CustomAction::reaction_kind(&lights.name),
+            "custom:turn off the lights"
+        );
+    }
+
+    #[test]
+    fn test_parse_rejects_shadowed_names_and_non_object_schemas() {
+        let shadowing = r#"[{ "name": "ask", "description": "", "handler": { "type": "webhook", "url": "x" } }]"#;
+        assert!(CustomActions::parse(shadowing, &["ask".to_string()]).is_err());
+
+        let not_an_object = r#"[{ "name": "shrug", "description": "", "parameters": { "type": "string" }, "handler": { "type": "webhook", "url": "x" } }]"#;
+        assert!(CustomActions::parse(not_an_object, &[]).is_err());
+    }
+
+    #[test]
+    fn test_job_data_prefers_the_file_then_context_then_arguments() {
+        let person_uuid = PersonUuid::from_uuid(Uuid::from_u128(1));
+        let scene_uuid = SceneUuid::from_uuid(Uuid::from_u128(2));
+        let mut data = Map::new();
+        data.insert("mood".to_string(), json!("grim"));
+
+        let job_data = job_data(
+            &data,
+            &person_uuid,
+            Some(&scene_uuid),
+            &json!({ "mood": "cheerful", "person_uuid": "someone else", "target": "door" }),
+        );
+
+        assert_eq!(job_data["mood"], json!("grim"));
+        assert_eq!(job_data["person_uuid"], json!(person_uuid.to_uuid()));
+        assert_eq!(job_data["scene_uuid"], json!(scene_uuid.to_uuid()));
+        assert_eq!(job_data["target"], json!("door"));
+    }
+}
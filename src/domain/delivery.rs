@@ -0,0 +1,102 @@
+use crate::domain::outbox_uuid::OutboxUuid;
+use chrono::{DateTime, Utc};
+
+/// The only integration there is so far, the outbox webhook.
+pub const WEBHOOK_INTEGRATION: &str = "webhook";
+
+/// How one attempt to hand an outbox entry to an integration went.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeliveryStatus {
+    /// Accepted on the first try.
+    Sent,
+    Failed,
+    /// Accepted after failing before.
+    Retried,
+}
+
+/// A failed attempt, and whether the entry has gotten through since.
+#[derive(Debug, Clone)]
+pub struct FailedDelivery {
+    pub outbox_uuid: OutboxUuid,
+    pub integration: String,
+    pub event_name: String,
+    /// The message's content, for `scene message sent` events.
+    pub content: Option<String>,
+    pub details: String,
+    pub delivered_since: bool,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DeliveryCounts {
+    pub sent: i64,
+    pub failed: i64,
+    pub retried: i64,
+}
+
+impl DeliveryStatus {
+    pub fn to_name(self) -> &'static str {
+        match self {
+            DeliveryStatus::Sent => "sent",
+            DeliveryStatus::Failed => "failed",
+            DeliveryStatus::Retried => "retried",
+        }
+    }
+
+    pub fn parse(name: &str) -> Option<Self> {
+        [
+            DeliveryStatus::Sent,
+            DeliveryStatus::Failed,
+            DeliveryStatus::Retried,
+        ]
+        .into_iter()
+        .find(|status| status.to_name() == name)
+    }
+
+    /// `attempts` counts the attempt that just succeeded.
+    pub fn for_success(attempts: i32) -> Self {
+        if attempts > 1 {
+            DeliveryStatus::Retried
+        } else {
+            DeliveryStatus::Sent
+        }
+    }
+}
+
+impl DeliveryCounts {
+    pub fn add(&mut self, status: DeliveryStatus, count: i64) {
+        match status {
+            DeliveryStatus::Sent => self.sent += count,
+            DeliveryStatus::Failed => self.failed += count,
+            DeliveryStatus::Retried => self.retried += count,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_success_after_a_failure_is_a_retry() {
+        assert_eq!(DeliveryStatus::for_success(1), DeliveryStatus::Sent);
+        assert_eq!(DeliveryStatus::for_success(2), DeliveryStatus::Retried);
+    }
+
+    #[test]
+    fn test_counts_read_every_status_name() {
+        let mut counts = DeliveryCounts::default();
+        for (name, count) in [("sent", 3), ("failed", 2), ("retried", 1)] {
+            counts.add(DeliveryStatus::parse(name).unwrap(), count);
+        }
+
+        assert_eq!(
+            counts,
+            DeliveryCounts {
+                sent: 3,
+                failed: 2,
+                retried: 1,
+            }
+        );
+    }
+}
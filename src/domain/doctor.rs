@@ -0,0 +1,154 @@
+use std::collections::HashSet;
+
+/// Environment variables nothing can run without.
+pub const REQUIRED_VARS: [&str; 4] = [
+    "DATABASE_USER",
+    "DATABASE_PASSWORD",
+    "DATABASE_HOST",
+    "OPEN_AI_API_KEY",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CheckStatus {
+    Pass,
+    /// Works, but probably not what you want for a long run.
+    Warn,
+    Fail,
+    /// Not checked, because a check it depends on failed.
+    Skipped,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Check {
+    pub name: String,
+    pub status: CheckStatus,
+    pub details: String,
+}
+
+impl CheckStatus {
+    pub fn to_label(self) -> &'static str {
+        match self {
+            CheckStatus::Pass => "PASS",
+            CheckStatus::Warn => "WARN",
+            CheckStatus::Fail => "FAIL",
+            CheckStatus::Skipped => "SKIP",
+        }
+    }
+}
+
+impl Check {
+    pub fn pass(name: &str, details: impl Into<String>) -> Self {
+        Check::new(name, CheckStatus::Pass, details)
+    }
+
+    pub fn warn(name: &str, details: impl Into<String>) -> Self {
+        Check::new(name, CheckStatus::Warn, details)
+    }
+
+    pub fn fail(name: &str, details: impl Into<String>) -> Self {
+        Check::new(name, CheckStatus::Fail, details)
+    }
+
+    pub fn skipped(name: &str, details: impl Into<String>) -> Self {
+        Check::new(name, CheckStatus::Skipped, details)
+    }
+
+    fn new(name: &str, status: CheckStatus, details: impl Into<String>) -> Self {
+        Check {
+            name: name.to_string(),
+            status,
+            details: details.into(),
+        }
+    }
+}
+
+/// Migrations in `db/migrations` that have not been recorded as run, in the
+/// order they would run.
+pub fn pending_migrations(migration_names: &[String], applied: &[String]) -> Vec<String> {
+    let applied: HashSet<&String> = applied.iter().collect();
+
+    migration_names
+        .iter()
+        .filter(|name| !applied.contains(name))
+        .cloned()
+        .collect()
+}
+
+/// One line per check, then a summary line.
+pub fn render_report(checks: &[Check]) -> String {
+    let name_width = checks
+        .iter()
+        .map(|check| check.name.len())
+        .max()
+        .unwrap_or(0);
+
+    let mut lines: Vec<String> = checks
+        .iter()
+        .map(|check| {
+            format!(
+                "[{}] {:width$}  {}",
+                check.status.to_label(),
+                check.name,
+                check.details,
+                width = name_width
+            )
+        })
+        .collect();
+
+    let failed = count(checks, CheckStatus::Fail);
+    let warned = count(checks, CheckStatus::Warn);
+    let summary = if failed == 0 && warned == 0 {
+        "All checks passed".to_string()
+    } else {
+        format!("{} failed, {} warned", failed, warned)
+    };
+
+    lines.push(String::new());
+    lines.push(summary);
+    lines.join("\n")
+}
+
+pub fn count(checks: &[Check], status: CheckStatus) -> usize {
+    checks.iter().filter(|check| check.status == status).count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pending_migrations_keep_file_order() {
+        let names = vec![
+            "2026-01-01-00:00:00____a.sql".to_string(),
+            "2026-01-02-00:00:00____b.sql".to_string(),
+            "2026-01-03-00:00:00____c.sql".to_string(),
+        ];
+        let applied = vec!["2026-01-02-00:00:00____b.sql".to_string()];
+
+        assert_eq!(
+            pending_migrations(&names, &applied),
+            vec![
+                "2026-01-01-00:00:00____a.sql".to_string(),
+                "2026-01-03-00:00:00____c.sql".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_report_lines_up_names_and_counts_problems() {
+        let checks = vec![
+            Check::pass("database", "connected"),
+            Check::fail("pgvector", "not installed"),
+            Check::warn("chaos", "on"),
+        ];
+
+        assert_eq!(
+            render_report(&checks),
+            "[PASS] database  connected\n[FAIL] pgvector  not installed\n[WARN] chaos     on\n\n1 failed, 1 warned"
+        );
+        assert_eq!(
+            render_report(&[Check::pass("database", "connected")]),
+            "[PASS] database  connected\n\nAll checks passed"
+        );
+    }
+}
@@ -0,0 +1,92 @@
+use crate::domain::memory_uuid::MemoryUuid;
+use crate::open_ai::embedding::EmbeddingModel;
+
+/// A memory whose stored embedding cannot be searched: missing, all zeros,
+/// or a different size than the dimension recorded next to it.
+#[derive(Debug, Clone)]
+pub struct MemoryToEmbed {
+    pub memory_uuid: MemoryUuid,
+    pub content: String,
+    pub retrieval_summary: String,
+}
+
+impl MemoryToEmbed {
+    /// Memories are embedded from their retrieval summary, like when they are
+    /// made. Imported ones can have a blank one, so those go by their content.
+    pub fn embedding_text(&self) -> &str {
+        match self.retrieval_summary.trim() {
+            "" => self.content.trim(),
+            retrieval_summary => retrieval_summary,
+        }
+    }
+}
+
+/// Whether a fresh embedding is fit to store. A wrong size would be skipped
+/// by every search, and a zero vector has no direction to compare.
+pub fn check_embedding(model: EmbeddingModel, embedding: &[f32]) -> Result<(), String> {
+    if i32::try_from(embedding.len()).ok() != Some(model.dimension()) {
+        return Err(format!(
+            "{} returned {} dimensions instead of {}",
+            model,
+            embedding.len(),
+            model.dimension()
+        ));
+    }
+
+    if embedding.iter().all(|value| *value == 0.0) {
+        return Err(format!("{} returned an embedding of all zeros", model));
+    }
+
+    Ok(())
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BackfillProgress {
+    pub total: i64,
+    pub embedded: i64,
+    pub failed: i64,
+}
+
+impl BackfillProgress {
+    pub fn to_line(&self) -> String {
+        format!(
+            "{} of {} memories checked: {} embedded, {} failed",
+            self.embedded + self.failed,
+            self.total,
+            self.embedded,
+            self.failed
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_blank_retrieval_summaries_fall_back_to_the_content() {
+        let mut memory = MemoryToEmbed {
+            memory_uuid: MemoryUuid::test_id(1),
+            content: " Hank lost his keys at the diner. ".to_string(),
+            retrieval_summary: "  ".to_string(),
+        };
+        assert_eq!(memory.embedding_text(), "Hank lost his keys at the diner.");
+
+        memory.retrieval_summary = "Hank lost his keys.".to_string();
+        assert_eq!(memory.embedding_text(), "Hank lost his keys.");
+    }
+
+    #[test]
+    fn test_check_embedding_rejects_wrong_sizes_and_zeros() {
+        let model = EmbeddingModel::TextEmbedding3Small;
+        let dimension = model.dimension() as usize;
+
+        let mut embedding = vec![0.0; dimension];
+        assert!(check_embedding(model, &embedding).is_err());
+
+        embedding[7] = 0.25;
+        assert_eq!(check_embedding(model, &embedding), Ok(()));
+
+        assert!(check_embedding(model, &embedding[..512]).is_err());
+    }
+}
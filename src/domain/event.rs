@@ -15,6 +15,16 @@ impl Event {
         }
     }
 
+    pub fn scene_name(&self) -> &str {
+        match &self.event_type {
+            EventType::Said { scene_name, .. }
+            | EventType::Overheard { scene_name, .. }
+            | EventType::Observed { scene_name, .. }
+            | EventType::Entered { scene_name, .. }
+            | EventType::Left { scene_name, .. } => scene_name,
+        }
+    }
+
     pub fn to_text(&self) -> String {
         match &self.event_type {
             EventType::Said {
@@ -26,6 +36,22 @@ impl Event {
                 "In scene {}, {} said: \"{}\"",
                 scene_name, speaker_name, comment
             ),
+            EventType::Overheard {
+                scene_name,
+                speaker_name,
+                comment,
+                message_uuid: _,
+            } => format!(
+                "In scene {}, you overheard {} say to someone else: \"{}\"",
+                scene_name, speaker_name, comment
+            ),
+            EventType::Observed {
+                scene_name,
+                observation,
+            } => format!(
+                "As you walked into scene {}, you noticed: {}",
+                scene_name, observation
+            ),
             EventType::Entered {
                 person_name,
                 scene_name,
@@ -63,6 +89,20 @@ pub enum EventType {
         comment: String,
         message_uuid: MessageUuid,
     },
+    /// A side conversation the person was close enough to hear but that was
+    /// not meant for them.
+    Overheard {
+        scene_name: String,
+        speaker_name: String,
+        comment: String,
+        message_uuid: MessageUuid,
+    },
+    /// What the person picked up on as they walked into a scene that was
+    /// already mid-conversation.
+    Observed {
+        scene_name: String,
+        observation: String,
+    },
     Entered {
         person_name: String,
         scene_name: String,
@@ -0,0 +1,173 @@
+use crate::domain::outbox::OutboxEntry;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::time::Duration;
+use uuid::Uuid;
+
+/// How often an open event stream checks the outbox for new events.
+pub const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// The most events one stream reads from the outbox per poll.
+pub const POLL_LIMIT: i64 = 200;
+
+/// Payload fields that name the persons an event is about.
+const PERSON_FIELDS: [&str; 2] = ["sender_person_uuid", "person_uuid"];
+
+/// What a client sends on the event stream, like
+/// `{"type": "subscribe", "scenes": ["..."], "events": ["scene message sent"]}`.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ClientMessage {
+    /// Replaces the stream's filter.
+    Subscribe {
+        #[serde(flatten)]
+        filter: EventFilter,
+    },
+    /// No more events until the next subscribe.
+    Unsubscribe,
+}
+
+/// Which events a stream wants. A missing or empty list matches anything, so
+/// `{}` subscribes to everything the token may see.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct EventFilter {
+    #[serde(default)]
+    pub scenes: Vec<Uuid>,
+    #[serde(default)]
+    pub persons: Vec<Uuid>,
+    /// Event names, like "scene closed"
+    #[serde(default)]
+    pub events: Vec<String>,
+}
+
+impl EventFilter {
+    /// Events that are not about any scene or person only pass filters that
+    /// do not ask for specific scenes or persons.
+    pub fn matches(&self, entry: &OutboxEntry) -> bool {
+        if !self.events.is_empty() && !self.events.contains(&entry.event_name) {
+            return false;
+        }
+
+        if !self.scenes.is_empty() {
+            let scene_uuid = uuid_field(&entry.payload, "scene_uuid");
+            if !scene_uuid.is_some_and(|scene_uuid| self.scenes.contains(&scene_uuid)) {
+                return false;
+            }
+        }
+
+        if !self.persons.is_empty() {
+            let is_about_a_person = PERSON_FIELDS
+                .iter()
+                .filter_map(|field| uuid_field(&entry.payload, field))
+                .any(|person_uuid| self.persons.contains(&person_uuid));
+            if !is_about_a_person {
+                return false;
+            }
+        }
+
+        true
+    }
+}
+
+fn uuid_field(payload: &Value, field: &str) -> Option<Uuid> {
+    payload
+        .get(field)
+        .and_then(Value::as_str)
+        .and_then(|text| Uuid::parse_str(text).ok())
+}
+
+pub fn parse_client_message(text: &str) -> Result<ClientMessage, String> {
+    serde_json::from_str(text).map_err(|err| err.to_string())
+}
+
+/// Sent back after every subscribe, so clients can see the filter in effect.
+pub fn subscribed_message(filter: &EventFilter) -> Value {
+    json!({
+        "type": "subscribed",
+        "filter": filter,
+    })
+}
+
+pub fn unsubscribed_message() -> Value {
+    json!({ "type": "unsubscribed" })
+}
+
+/// An event, in the same shape the outbox webhook gets.
+pub fn event_message(entry: &OutboxEntry) -> Value {
+    let mut message = entry.to_webhook_body();
+    message["type"] = json!("event");
+    message
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::outbox_uuid::OutboxUuid;
+    use chrono::Utc;
+
+    fn entry(event_name: &str, payload: Value) -> OutboxEntry {
+        OutboxEntry {
+            uuid: OutboxUuid::new(),
+            event_name: event_name.to_string(),
+            payload,
+            attempts: 0,
+            created_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_empty_filter_matches_everything() {
+        let filter = EventFilter::default();
+
+        assert!(filter.matches(&entry("simulation paused", json!({}))));
+    }
+
+    #[test]
+    fn test_filter_needs_every_list_to_match() {
+        let scene_uuid = Uuid::now_v7();
+        let person_uuid = Uuid::now_v7();
+        let filter = EventFilter {
+            scenes: vec![scene_uuid],
+            persons: vec![person_uuid],
+            events: vec!["scene message sent".to_string()],
+        };
+
+        let message = entry(
+            "scene message sent",
+            json!({ "scene_uuid": scene_uuid, "sender_person_uuid": person_uuid }),
+        );
+        let other_sender = entry(
+            "scene message sent",
+            json!({ "scene_uuid": scene_uuid, "sender_person_uuid": Uuid::now_v7() }),
+        );
+        let closed = entry("scene closed", json!({ "scene_uuid": scene_uuid }));
+        let paused = entry("simulation paused", json!({}));
+
+        assert!(filter.matches(&message));
+        assert!(!filter.matches(&other_sender));
+        assert!(!filter.matches(&closed));
+        assert!(!filter.matches(&paused));
+    }
+
+    #[test]
+    fn test_parse_subscribe_with_missing_lists() {
+        let scene_uuid = Uuid::now_v7();
+        let text = format!(r#"{{"type": "subscribe", "scenes": ["{}"]}}"#, scene_uuid);
+
+        assert_eq!(
+            parse_client_message(&text),
+            Ok(ClientMessage::Subscribe {
+                filter: EventFilter {
+                    scenes: vec![scene_uuid],
+                    persons: vec![],
+                    events: vec![],
+                }
+            })
+        );
+        assert_eq!(
+            parse_client_message(r#"{"type": "unsubscribe"}"#),
+            Ok(ClientMessage::Unsubscribe)
+        );
+        assert!(parse_client_message(r#"{"type": "shout"}"#).is_err());
+    }
+}
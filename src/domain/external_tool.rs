@@ -0,0 +1,259 @@
+use crate::person_actions::PersonActionKind;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::sync::OnceLock;
+
+/// A json file of http tools persons can call with the `use tool` action,
+/// like a weather lookup or a calculator. Unset means there are none, and
+/// `use tool` is not offered.
+pub const EXTERNAL_TOOLS_FILE_VAR: &str = "EXTERNAL_TOOLS_FILE";
+
+/// Tool responses are cut to this before they go into a prompt, so one
+/// chatty api cannot crowd out the scene.
+const MAX_RESULT_CHARS: usize = 2_000;
+
+/// A tool loaded from the external tools file.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExternalTool {
+    /// Like `weather`. Offered to the model as a `tool_name` for `use tool`.
+    pub name: String,
+    /// Tells the model what the tool is for.
+    pub description: String,
+    /// A json schema object for the arguments the model fills in. Missing
+    /// means it takes none.
+    #[serde(default = "empty_parameters")]
+    pub parameters: Value,
+    pub url: String,
+    #[serde(default)]
+    pub method: ExternalToolMethod,
+}
+
+/// How the arguments are sent to the tool's url.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExternalToolMethod {
+    /// As the query string, for apis like `?city=Tucson`.
+    Get,
+    /// As a json body.
+    #[default]
+    Post,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ExternalTools {
+    tools: Vec<ExternalTool>,
+}
+
+fn empty_parameters() -> Value {
+    json!({ "type": "object", "properties": {} })
+}
+
+impl ExternalTool {
+    /// The `choose_action` parameter the model fills in with this tool's
+    /// arguments, like `weather_tool_arguments`.
+    pub fn arguments_parameter_name(&self) -> String {
+        let snake_name = self
+            .name
+            .split_whitespace()
+            .collect::<Vec<&str>>()
+            .join("_");
+
+        format!("{}_tool_arguments", snake_name)
+    }
+
+    /// The arguments as query string pairs. Strings go as they are, anything
+    /// else as its json.
+    pub fn query_pairs(arguments: &Value) -> Vec<(String, String)> {
+        match arguments.as_object() {
+            Some(arguments) => arguments
+                .iter()
+                .map(|(key, value)| {
+                    let value = match value {
+                        Value::String(value) => value.clone(),
+                        other => other.to_string(),
+                    };
+                    (key.clone(), value)
+                })
+                .collect(),
+            None => vec![],
+        }
+    }
+}
+
+impl ExternalTools {
+    /// Reads the tools from the file at `EXTERNAL_TOOLS_FILE`. Read once,
+    /// since every reaction builds its tool from them. A file that can't be
+    /// read or parsed is logged and ignored, like the custom actions file.
+    pub fn registered() -> &'static ExternalTools {
+        static EXTERNAL_TOOLS: OnceLock<ExternalTools> = OnceLock::new();
+
+        EXTERNAL_TOOLS.get_or_init(|| match dotenv::var(EXTERNAL_TOOLS_FILE_VAR) {
+            Ok(path) if !path.trim().is_empty() => match ExternalTools::load(path.trim()) {
+                Ok(external_tools) => external_tools,
+                Err(err) => {
+                    tracing::warn!("{}, going without external tools", err);
+                    ExternalTools::default()
+                }
+            },
+            _ => ExternalTools::default(),
+        })
+    }
+
+    pub fn load(path: &str) -> Result<ExternalTools, String> {
+        let json = std::fs::read_to_string(path)
+            .map_err(|err| format!("Could not read external tools file {}: {}", path, err))?;
+
+        ExternalTools::parse(&json)
+            .map_err(|err| format!("Invalid external tools file {}: {}", path, err))
+    }
+
+    pub fn parse(json: &str) -> Result<ExternalTools, String> {
+        let tools = serde_json::from_str::<Vec<ExternalTool>>(json)
+            .map_err(|err| format!("could not parse the tools: {}", err))?;
+
+        let mut seen_names: Vec<&str> = Vec::new();
+
+        for tool in tools.iter() {
+            let name = tool.name.trim();
+
+            if name.is_empty() {
+                return Err("a tool has no name".to_string());
+            }
+            if name != tool.name {
+                return Err(format!("\"{}\" has spaces around its name", tool.name));
+            }
+            if seen_names.contains(&name) {
+                return Err(format!("there are two tools named \"{}\"", name));
+            }
+            if tool.url.trim().is_empty() {
+                return Err(format!("\"{}\" has no url", name));
+            }
+            if tool.parameters.get("type") != Some(&json!("object")) {
+                return Err(format!(
+                    "the parameters of \"{}\" must be a json schema with \"type\": \"object\"",
+                    name
+                ));
+            }
+
+            seen_names.push(name);
+        }
+
+        Ok(ExternalTools { tools })
+    }
+
+    pub fn all(&self) -> &[ExternalTool] {
+        &self.tools
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tools.is_empty()
+    }
+
+    pub fn find(&self, name: &str) -> Option<&ExternalTool> {
+        self.tools.iter().find(|tool| tool.name == name)
+    }
+
+    pub fn find_by_arguments_parameter(&self, parameter_name: &str) -> Option<&ExternalTool> {
+        self.tools
+            .iter()
+            .find(|tool| tool.arguments_parameter_name() == parameter_name)
+    }
+}
+
+/// The reaction history kind tool use is recorded and budgeted under.
+pub fn reaction_kind() -> String {
+    PersonActionKind::UseTool.to_name().replace(' ', "_")
+}
+
+/// What the person notices once the call comes back, to react to.
+pub fn to_observation(tool_name: &str, result: &Result<String, String>) -> String {
+    match result {
+        Ok(response) => {
+            let response = response.trim();
+            let response = match response.char_indices().nth(MAX_RESULT_CHARS) {
+                Some((end, _)) => format!("{}...", &response[..end]),
+                None => response.to_string(),
+            };
+
+            format!("You used {} and it returned:\n{}", tool_name, response)
+        }
+        Err(_) => format!(
+            "You tried to use {}, but it did not work and returned nothing useful.",
+            tool_name
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TOOLS: &str = r#"[
+        {
+            "name": "weather",
+            "description": "Look up the current weather in a city.",
+            "parameters": {
+                "type": "object",
+                "properties": { "city": { "type": "string" } },
+                "required": ["city"]
+            },
+            "url": "http://localhost:9000/weather",
+            "method": "get"
+        },
+        {
+            "name": "pocket calculator",
+            "description": "Evaluate arithmetic like 12 * (3 + 4).",
+            "url": "http://localhost:9000/calculate"
+        }
+    ]"#;
+
+    #[test]
+    fn test_parse_reads_methods_and_defaults_parameters() {
+        let external_tools = ExternalTools::parse(TOOLS).unwrap();
+
+        let weather = external_tools.find("weather").unwrap();
+        assert_eq!(weather.method, ExternalToolMethod::Get);
+        assert_eq!(weather.arguments_parameter_name(), "weather_tool_arguments");
+
+        let calculator = external_tools
+            .find_by_arguments_parameter("pocket_calculator_tool_arguments")
+            .unwrap();
+        assert_eq!(calculator.method, ExternalToolMethod::Post);
+        assert_eq!(calculator.parameters, empty_parameters());
+    }
+
+    #[test]
+    fn test_parse_rejects_duplicates_and_missing_urls() {
+        let duplicate = r#"[
+            { "name": "weather", "description": "", "url": "x" },
+            { "name": "weather", "description": "", "url": "y" }
+        ]"#;
+        assert!(ExternalTools::parse(duplicate).is_err());
+
+        let no_url = r#"[{ "name": "weather", "description": "", "url": " " }]"#;
+        assert!(ExternalTools::parse(no_url).is_err());
+    }
+
+    #[test]
+    fn test_observations_cut_long_results_and_hide_errors() {
+        let long = Ok("7".repeat(MAX_RESULT_CHARS + 5));
+        assert_eq!(
+            to_observation("pocket calculator", &long),
+            format!(
+                "You used pocket calculator and it returned:\n{}...",
+                "7".repeat(MAX_RESULT_CHARS)
+            )
+        );
+
+        let failed = Err("Connection refused".to_string());
+        assert!(!to_observation("weather", &failed).contains("refused"));
+
+        assert_eq!(
+            ExternalTool::query_pairs(&json!({ "city": "Tucson", "days": 2 })),
+            vec![
+                ("city".to_string(), "Tucson".to_string()),
+                ("days".to_string(), "2".to_string()),
+            ]
+        );
+    }
+}
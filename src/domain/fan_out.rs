@@ -0,0 +1,236 @@
+use crate::domain::message_urgency::MessageUrgency;
+
+const DEFAULT_DEFER_ABOVE: i64 = 200;
+const DEFAULT_SKIP_ABOVE: i64 = 1_000;
+const DEFAULT_DEFER_MS: i64 = 60_000;
+const DEFAULT_BATCH_SIZE: i64 = 10;
+
+/// Under heavy load, background recipients at least this chatty still get
+/// to react. Quieter ones are skipped.
+pub const MIN_CHATTINESS_UNDER_LOAD: f64 = 0.5;
+
+/// How busy the job queue is when a scene message fans out.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct QueuePressure {
+    /// Jobs that have not been started yet, including ones held back.
+    pub pending_jobs: i64,
+    pub active_ms: i64,
+}
+
+/// How a scene message's reactions are enqueued once the job queue backs up.
+/// Recipients who were spoken to directly always get a reaction job right
+/// away.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FanOutPolicy {
+    /// Above this many pending jobs, background recipients' reactions are
+    /// held back, `batch_size` at a time, `defer_ms` apart.
+    pub defer_above: i64,
+    /// Above this many pending jobs, quiet background recipients get no
+    /// reaction job at all.
+    pub skip_above: i64,
+    pub defer_ms: i64,
+    pub batch_size: i64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum RecipientPlan {
+    Enqueue {
+        run_at_active_ms: Option<i64>,
+    },
+    /// The recipient still gets the message, it just does not trigger a
+    /// reaction. It is still unhandled the next time they react.
+    Skip,
+}
+
+/// Works out each recipient's reaction job for one message.
+#[derive(Debug)]
+pub struct FanOut<'a> {
+    policy: &'a FanOutPolicy,
+    pressure: QueuePressure,
+    deferred: i64,
+}
+
+impl FanOutPolicy {
+    /// Reads FAN_OUT_DEFER_ABOVE, FAN_OUT_SKIP_ABOVE, FAN_OUT_DEFER_MS and
+    /// FAN_OUT_BATCH_SIZE, falling back to defaults when they are not set.
+    pub fn load() -> Result<Self, String> {
+        let defer_above = count_from_env("FAN_OUT_DEFER_ABOVE", DEFAULT_DEFER_ABOVE)?;
+        let skip_above = count_from_env("FAN_OUT_SKIP_ABOVE", DEFAULT_SKIP_ABOVE)?;
+
+        if skip_above < defer_above {
+            return Err(format!(
+                "FAN_OUT_SKIP_ABOVE ({}) must not be below FAN_OUT_DEFER_ABOVE ({})",
+                skip_above, defer_above
+            ));
+        }
+
+        Ok(FanOutPolicy {
+            defer_above,
+            skip_above,
+            defer_ms: count_from_env("FAN_OUT_DEFER_MS", DEFAULT_DEFER_MS)?,
+            batch_size: count_from_env("FAN_OUT_BATCH_SIZE", DEFAULT_BATCH_SIZE)?,
+        })
+    }
+
+    pub fn is_under_pressure(&self, pressure: &QueuePressure) -> bool {
+        pressure.pending_jobs > self.defer_above
+    }
+
+    pub fn should_skip_quiet_recipients(&self, pressure: &QueuePressure) -> bool {
+        pressure.pending_jobs > self.skip_above
+    }
+
+    pub fn fan_out(&self, pressure: QueuePressure) -> FanOut<'_> {
+        FanOut {
+            policy: self,
+            pressure,
+            deferred: 0,
+        }
+    }
+}
+
+impl FanOut<'_> {
+    /// `chattiness` is only looked at under heavy load. A recipient whose
+    /// chattiness is not known counts as quiet.
+    pub fn plan(
+        &mut self,
+        urgency: MessageUrgency,
+        chattiness: Option<f64>,
+        run_at_active_ms: Option<i64>,
+    ) -> RecipientPlan {
+        if urgency != MessageUrgency::Background || !self.policy.is_under_pressure(&self.pressure) {
+            return RecipientPlan::Enqueue { run_at_active_ms };
+        }
+
+        if self.policy.should_skip_quiet_recipients(&self.pressure)
+            && chattiness.unwrap_or(0.0) < MIN_CHATTINESS_UNDER_LOAD
+        {
+            return RecipientPlan::Skip;
+        }
+
+        let batch = 1 + self.deferred / self.policy.batch_size;
+        self.deferred += 1;
+
+        let deferred_until = self
+            .pressure
+            .active_ms
+            .saturating_add(self.policy.defer_ms.saturating_mul(batch));
+
+        RecipientPlan::Enqueue {
+            run_at_active_ms: Some(run_at_active_ms.unwrap_or(0).max(deferred_until)),
+        }
+    }
+}
+
+fn count_from_env(var_name: &str, default: i64) -> Result<i64, String> {
+    let value = match dotenv::var(var_name) {
+        Ok(value) => value,
+        Err(_) => return Ok(default),
+    };
+
+    match value.trim().parse::<i64>() {
+        Ok(count) if count > 0 => Ok(count),
+        _ => Err(format!(
+            "{} must be a whole number greater than zero, but it was \"{}\"",
+            var_name, value
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy() -> FanOutPolicy {
+        FanOutPolicy {
+            defer_above: 100,
+            skip_above: 500,
+            defer_ms: 60_000,
+            batch_size: 2,
+        }
+    }
+
+    fn pressure(pending_jobs: i64) -> QueuePressure {
+        QueuePressure {
+            pending_jobs,
+            active_ms: 1_000,
+        }
+    }
+
+    #[test]
+    fn test_quiet_queue_enqueues_everyone_as_asked() {
+        let policy = policy();
+        let mut fan_out = policy.fan_out(pressure(10));
+
+        assert_eq!(
+            fan_out.plan(MessageUrgency::Background, None, Some(5_000)),
+            RecipientPlan::Enqueue {
+                run_at_active_ms: Some(5_000)
+            }
+        );
+        assert_eq!(
+            fan_out.plan(MessageUrgency::Background, None, None),
+            RecipientPlan::Enqueue {
+                run_at_active_ms: None
+            }
+        );
+    }
+
+    #[test]
+    fn test_busy_queue_defers_background_recipients_in_batches() {
+        let policy = policy();
+        let mut fan_out = policy.fan_out(pressure(200));
+
+        let plans: Vec<RecipientPlan> = (0..3)
+            .map(|_| fan_out.plan(MessageUrgency::Background, Some(0.1), None))
+            .collect();
+
+        assert_eq!(
+            plans,
+            vec![
+                RecipientPlan::Enqueue {
+                    run_at_active_ms: Some(61_000)
+                },
+                RecipientPlan::Enqueue {
+                    run_at_active_ms: Some(61_000)
+                },
+                RecipientPlan::Enqueue {
+                    run_at_active_ms: Some(121_000)
+                },
+            ]
+        );
+        assert_eq!(
+            fan_out.plan(MessageUrgency::Direct, None, None),
+            RecipientPlan::Enqueue {
+                run_at_active_ms: None
+            }
+        );
+    }
+
+    #[test]
+    fn test_overloaded_queue_skips_quiet_background_recipients() {
+        let policy = policy();
+        let mut fan_out = policy.fan_out(pressure(600));
+
+        assert_eq!(
+            fan_out.plan(MessageUrgency::Background, Some(0.3), None),
+            RecipientPlan::Skip
+        );
+        assert_eq!(
+            fan_out.plan(MessageUrgency::Background, None, None),
+            RecipientPlan::Skip
+        );
+        assert_eq!(
+            fan_out.plan(MessageUrgency::Background, Some(0.8), None),
+            RecipientPlan::Enqueue {
+                run_at_active_ms: Some(61_000)
+            }
+        );
+        assert_eq!(
+            fan_out.plan(MessageUrgency::Direct, Some(0.1), None),
+            RecipientPlan::Enqueue {
+                run_at_active_ms: None
+            }
+        );
+    }
+}
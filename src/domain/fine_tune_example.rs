@@ -0,0 +1,93 @@
+use crate::capability::reaction_context::ReactionContext;
+use serde::Serialize;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct FineTuneExample {
+    messages: Vec<FineTuneMessage>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct FineTuneMessage {
+    role: &'static str,
+    content: String,
+}
+
+impl FineTuneExample {
+    pub fn from_reaction_context(reaction_context: &ReactionContext) -> Self {
+        Self::new(
+            reaction_context.system_prompt.clone(),
+            reaction_context.user_prompt.clone(),
+            reaction_context.assistant_response.clone(),
+        )
+    }
+
+    fn new(system_prompt: String, user_prompt: String, assistant_response: String) -> Self {
+        Self {
+            messages: vec![
+                FineTuneMessage {
+                    role: "system",
+                    content: system_prompt,
+                },
+                FineTuneMessage {
+                    role: "user",
+                    content: user_prompt,
+                },
+                FineTuneMessage {
+                    role: "assistant",
+                    content: assistant_response,
+                },
+            ],
+        }
+    }
+
+    /// Renders examples in the OpenAI fine-tuning JSONL format, one example per line.
+    pub fn many_to_jsonl(examples: &[FineTuneExample]) -> Result<String, serde_json::Error> {
+        let mut jsonl = String::new();
+
+        for example in examples {
+            jsonl.push_str(serde_json::to_string(example)?.as_str());
+            jsonl.push('\n');
+        }
+
+        Ok(jsonl)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_many_to_jsonl_writes_one_line_per_example() {
+        let examples = vec![
+            FineTuneExample::new("sys".to_string(), "user".to_string(), "reply".to_string()),
+            FineTuneExample::new(
+                "sys 2".to_string(),
+                "line one\nline two".to_string(),
+                "reply 2".to_string(),
+            ),
+        ];
+
+        let jsonl = FineTuneExample::many_to_jsonl(&examples).unwrap();
+
+        assert_eq!(jsonl.lines().count(), 2);
+    }
+
+    #[test]
+    fn test_example_messages_are_system_user_assistant() {
+        let example =
+            FineTuneExample::new("sys".to_string(), "user".to_string(), "reply".to_string());
+
+        let json = serde_json::to_value(&example).unwrap();
+
+        let expected = serde_json::json!({
+            "messages": [
+                { "role": "system", "content": "sys" },
+                { "role": "user", "content": "user" },
+                { "role": "assistant", "content": "reply" },
+            ]
+        });
+
+        assert_eq!(json, expected);
+    }
+}
@@ -0,0 +1,141 @@
+use regex::RegexBuilder;
+
+/// A world's rules for every person in it. Empty settings change nothing.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GuardrailSettings {
+    /// Free text added to every reaction system prompt.
+    pub prompt: String,
+    /// Topics persons must refuse to talk about. Messages that mention one
+    /// are blocked by moderation.
+    pub refused_topics: Vec<String>,
+    /// How persons should sound, like "no insults, keep it friendly".
+    pub tone: String,
+}
+
+impl GuardrailSettings {
+    pub fn is_empty(&self) -> bool {
+        self.prompt.trim().is_empty()
+            && self.refused_topics.is_empty()
+            && self.tone.trim().is_empty()
+    }
+
+    /// The section appended to reaction system prompts, if there is anything
+    /// to say.
+    pub fn to_prompt_section(&self) -> Option<String> {
+        if self.is_empty() {
+            return None;
+        }
+
+        let mut lines = vec!["World rules (these override everything above):".to_string()];
+
+        if !self.prompt.trim().is_empty() {
+            lines.push(self.prompt.trim().to_string());
+        }
+
+        if !self.refused_topics.is_empty() {
+            lines.push(format!(
+                "- Refuse to talk about these topics, even when asked directly: {}.",
+                self.refused_topics.join(", ")
+            ));
+        }
+
+        if !self.tone.trim().is_empty() {
+            lines.push(format!("- Tone: {}", self.tone.trim()));
+        }
+
+        Some(lines.join("\n"))
+    }
+
+    /// The refused topics `content` mentions, matched as whole words
+    /// regardless of case.
+    pub fn refused_topics_in(&self, content: &str) -> Result<Vec<String>, String> {
+        let mut mentioned = Vec::new();
+
+        for topic in self.refused_topics.iter() {
+            // Not `\b`, so topics that end in punctuation like "c++" still match
+            let pattern = format!(r"(?:^|\W){}(?:$|\W)", regex::escape(topic));
+            let regex = RegexBuilder::new(pattern.as_str())
+                .case_insensitive(true)
+                .build()
+                .map_err(|err| format!("Invalid refused topic \"{}\": {}", topic, err))?;
+
+            if regex.is_match(content) {
+                mentioned.push(topic.clone());
+            }
+        }
+
+        Ok(mentioned)
+    }
+}
+
+/// Reads topics typed one per line or separated by commas.
+pub fn parse_topics(text: &str) -> Vec<String> {
+    let mut topics: Vec<String> = Vec::new();
+
+    for topic in text.split([',', '\n']).map(str::trim) {
+        if !topic.is_empty() && !topics.iter().any(|known| known.eq_ignore_ascii_case(topic)) {
+            topics.push(topic.to_string());
+        }
+    }
+
+    topics
+}
+
+/// The moderation category recorded when a refused topic comes up.
+pub fn blocked_category(topic: &str) -> String {
+    format!("guardrail: {}", topic)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_empty_settings_add_nothing_to_prompts() {
+        assert_eq!(GuardrailSettings::default().to_prompt_section(), None);
+    }
+
+    #[test]
+    fn test_prompt_section_lists_topics_and_tone() {
+        let settings = GuardrailSettings {
+            prompt: "Everyone is an adult.".to_string(),
+            refused_topics: vec!["politics".to_string(), "religion".to_string()],
+            tone: "friendly".to_string(),
+        };
+
+        let section = settings.to_prompt_section().unwrap();
+
+        assert!(section.contains("Everyone is an adult."));
+        assert!(section.contains("politics, religion"));
+        assert!(section.contains("Tone: friendly"));
+    }
+
+    #[test]
+    fn test_refused_topics_match_whole_words_in_any_case() {
+        let settings = GuardrailSettings {
+            refused_topics: vec!["politics".to_string(), "c++".to_string()],
+            ..GuardrailSettings::default()
+        };
+
+        assert_eq!(
+            settings.refused_topics_in("Let's talk Politics").unwrap(),
+            vec!["politics".to_string()]
+        );
+        assert_eq!(
+            settings.refused_topics_in("I write c++ at work").unwrap(),
+            vec!["c++".to_string()]
+        );
+        assert!(settings
+            .refused_topics_in("geopolitics is fun")
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_parse_topics_splits_and_drops_repeats() {
+        assert_eq!(
+            parse_topics("politics, Religion\nreligion\n\n"),
+            vec!["politics".to_string(), "Religion".to_string()]
+        );
+    }
+}
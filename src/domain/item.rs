@@ -0,0 +1,101 @@
+use crate::domain::item_uuid::ItemUuid;
+use crate::domain::person_uuid::PersonUuid;
+use crate::domain::scene_uuid::SceneUuid;
+
+#[derive(Debug, Clone)]
+pub struct Item {
+    pub uuid: ItemUuid,
+    pub name: String,
+    pub description: String,
+}
+
+/// Where an item is. An item is carried by one person or lies in one scene.
+#[derive(Debug, Clone)]
+pub enum ItemLocation {
+    CarriedBy(PersonUuid),
+    InScene(SceneUuid),
+}
+
+impl Item {
+    pub fn many_to_list_text(items: &[Item]) -> String {
+        if items.is_empty() {
+            "Nothing.".to_string()
+        } else {
+            items
+                .iter()
+                .map(|item| item.to_list_text())
+                .collect::<Vec<String>>()
+                .join("\n")
+        }
+    }
+
+    fn to_list_text(&self) -> String {
+        if self.description.trim().is_empty() {
+            format!("- {}", self.name)
+        } else {
+            format!("- {}: {}", self.name, self.description.trim())
+        }
+    }
+
+    /// The item among `items` a person means by `name`. Models drop or add
+    /// articles ("letter", "the letter"), so those and case are ignored.
+    pub fn find_by_name<'a>(items: &'a [Item], name: &str) -> Option<&'a Item> {
+        let wanted = normalize_name(name);
+        items
+            .iter()
+            .find(|item| normalize_name(&item.name) == wanted)
+    }
+}
+
+fn normalize_name(name: &str) -> String {
+    let lowercase = name.trim().to_lowercase();
+    let without_article = ["the ", "a ", "an ", "my "]
+        .iter()
+        .find_map(|article| lowercase.strip_prefix(article))
+        .unwrap_or(lowercase.as_str());
+
+    without_article.trim().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    fn item(name: &str, description: &str) -> Item {
+        Item {
+            uuid: ItemUuid::from_uuid(Uuid::from_u128(1)),
+            name: name.to_string(),
+            description: description.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_items_list_with_their_descriptions() {
+        let items = vec![
+            item("letter", "A sealed envelope addressed to Hank."),
+            item("keys", ""),
+        ];
+
+        assert_eq!(Item::many_to_list_text(&[]), "Nothing.");
+        assert_eq!(
+            Item::many_to_list_text(&items),
+            "- letter: A sealed envelope addressed to Hank.\n- keys"
+        );
+    }
+
+    #[test]
+    fn test_find_by_name_ignores_case_and_articles() {
+        let items = vec![item("Letter", ""), item("the keys", "")];
+
+        assert_eq!(
+            Item::find_by_name(&items, " the letter ").map(|item| item.name.as_str()),
+            Some("Letter")
+        );
+        assert_eq!(
+            Item::find_by_name(&items, "Keys").map(|item| item.name.as_str()),
+            Some("the keys")
+        );
+        assert!(Item::find_by_name(&items, "map").is_none());
+    }
+}
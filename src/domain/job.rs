@@ -1,23 +1,74 @@
+pub mod archive_scene;
+pub mod balance_chattiness;
+pub mod change_scene_ambience;
+pub mod check_expected_reply;
+pub mod check_persona_consistency;
+pub mod check_scene_goals;
+pub mod close_scene;
+pub mod dispatch_outbox;
+pub mod handle_batch_completion;
+pub mod inject_scene_events;
+pub mod nightly_maintenance;
+pub mod notice_conversation;
 pub mod person_action_handler;
 pub mod person_hibernating;
 pub mod person_waiting;
+pub mod poll_llm_batch;
 pub mod process_message;
 pub mod process_person_join;
 pub mod process_reaction_common;
 pub mod process_scene_gaze;
+pub mod react_to_scene_event;
+pub mod react_to_scene_invitation;
+pub mod refill_acknowledgements;
+pub mod registry;
+pub mod run_custom_action;
 pub mod send_message_to_scene;
+pub mod tag_topics;
+pub mod update_person_arcs;
+pub mod use_tool;
+pub mod wake_idle_persons;
 
 use super::job_uuid::JobUuid;
+use crate::domain::job::archive_scene::ArchiveSceneJob;
+use crate::domain::job::change_scene_ambience::ChangeSceneAmbienceJob;
+use crate::domain::job::check_expected_reply::CheckExpectedReplyJob;
+use crate::domain::job::check_persona_consistency::CheckPersonaConsistencyJob;
+use crate::domain::job::close_scene::CloseSceneJob;
+use crate::domain::job::dispatch_outbox::DispatchOutboxJob;
+use crate::domain::job::handle_batch_completion::HandleBatchCompletionJob;
+use crate::domain::job::notice_conversation::NoticeConversationJob;
 use crate::domain::job::person_hibernating::PersonHibernatingJob;
 use crate::domain::job::person_waiting::PersonWaitingJob;
+use crate::domain::job::poll_llm_batch::PollLlmBatchJob;
+use crate::domain::job::react_to_scene_event::ReactToSceneEventJob;
+use crate::domain::job::react_to_scene_invitation::ReactToSceneInvitationJob;
+use crate::domain::job::refill_acknowledgements::RefillAcknowledgementsJob;
+use crate::domain::job::run_custom_action::RunCustomActionJob;
 use crate::domain::job::send_message_to_scene::SendMessageToSceneJob;
-use crate::nice_display::NiceDisplay;
+use crate::domain::job::use_tool::UseToolJob;
+use crate::domain::message::MessageSender;
+use crate::domain::person_uuid::PersonUuid;
+use crate::nice_display::{with_context, NiceDisplay};
+use crate::time_display;
 use chrono::{DateTime, Utc};
 use process_message::ProcessMessageJob;
 use process_person_join::ProcessPersonJoinJob;
 use process_scene_gaze::ProcessSceneGazeJob;
 use serde_json;
-use std::fmt::Display;
+
+pub const OUTBOX_LOCK_KEY: &str = "outbox";
+pub const WAKE_IDLE_PERSONS_LOCK_KEY: &str = "wake idle persons";
+pub const CHECK_SCENE_GOALS_LOCK_KEY: &str = "check scene goals";
+pub const TAG_TOPICS_LOCK_KEY: &str = "tag topics";
+pub const INJECT_SCENE_EVENTS_LOCK_KEY: &str = "inject scene events";
+pub const NIGHTLY_MAINTENANCE_LOCK_KEY: &str = "nightly maintenance";
+pub const UPDATE_PERSON_ARCS_LOCK_KEY: &str = "update person arcs";
+pub const BALANCE_CHATTINESS_LOCK_KEY: &str = "balance chattiness";
+
+pub fn person_lock_key(person_uuid: &PersonUuid) -> String {
+    format!("person:{}", person_uuid.to_uuid())
+}
 
 #[derive(Debug, Clone)]
 pub struct Job {
@@ -43,6 +94,17 @@ pub enum JobStatus {
     NotStarted,
 }
 
+impl JobStatus {
+    pub fn to_name(&self) -> &'static str {
+        match self {
+            JobStatus::Finished => "finished",
+            JobStatus::Failed => "failed",
+            JobStatus::InProgress => "in progress",
+            JobStatus::NotStarted => "not started",
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub enum JobKind {
     Ping,
@@ -52,6 +114,27 @@ pub enum JobKind {
     ProcessSceneGaze(ProcessSceneGazeJob),
     PersonWaiting(PersonWaitingJob),
     PersonHibernating(PersonHibernatingJob),
+    CheckExpectedReply(CheckExpectedReplyJob),
+    DispatchOutbox(DispatchOutboxJob),
+    PollLlmBatch(PollLlmBatchJob),
+    HandleBatchCompletion(HandleBatchCompletionJob),
+    WakeIdlePersons,
+    NoticeConversation(NoticeConversationJob),
+    CheckPersonaConsistency(CheckPersonaConsistencyJob),
+    CheckSceneGoals,
+    CloseScene(CloseSceneJob),
+    ArchiveScene(ArchiveSceneJob),
+    TagTopics,
+    ChangeSceneAmbience(ChangeSceneAmbienceJob),
+    InjectSceneEvents,
+    ReactToSceneEvent(ReactToSceneEventJob),
+    RunCustomAction(RunCustomActionJob),
+    RefillAcknowledgements(RefillAcknowledgementsJob),
+    NightlyMaintenance,
+    UseTool(UseToolJob),
+    ReactToSceneInvitation(ReactToSceneInvitationJob),
+    UpdatePersonArcs,
+    BalanceChattiness,
 }
 
 pub enum ParseError {
@@ -70,74 +153,149 @@ impl NiceDisplay for ParseError {
                     job_name
                 )
             }
-            ParseError::FailedToParseJobData { job_name, details } => {
-                format!(
-                    "Failed to parse job data for a \"{}\" job\n\n{}",
-                    job_name, details
-                )
-            }
+            ParseError::FailedToParseJobData { job_name, details } => with_context(
+                format!("Failed to parse job data for a \"{}\" job", job_name),
+                details,
+            ),
         }
     }
 }
 
 impl JobKind {
-    pub fn to_name(&self) -> String {
+    /// The name the job is stored under. Reading it back goes through the
+    /// same `registry`, so the two cannot drift apart.
+    pub fn name(&self) -> &'static str {
         match self {
-            JobKind::Ping => "ping".to_string(),
-            JobKind::SendMessageToScene(_) => "send message to scene".to_string(),
-            JobKind::ProcessPersonJoin(_) => "process person join".to_string(),
-            JobKind::ProcessMessage(_) => "process message".to_string(),
-            JobKind::ProcessSceneGaze(_) => "process scene gaze".to_string(),
-            JobKind::PersonWaiting(_) => "person waiting".to_string(),
-            JobKind::PersonHibernating(_) => "person hibernating".to_string(),
+            JobKind::Ping => registry::PING,
+            JobKind::SendMessageToScene(_) => registry::SEND_MESSAGE_TO_SCENE,
+            JobKind::ProcessPersonJoin(_) => registry::PROCESS_PERSON_JOIN,
+            JobKind::ProcessMessage(_) => registry::PROCESS_MESSAGE,
+            JobKind::ProcessSceneGaze(_) => registry::PROCESS_SCENE_GAZE,
+            JobKind::PersonWaiting(_) => registry::PERSON_WAITING,
+            JobKind::PersonHibernating(_) => registry::PERSON_HIBERNATING,
+            JobKind::CheckExpectedReply(_) => registry::CHECK_EXPECTED_REPLY,
+            JobKind::DispatchOutbox(_) => registry::DISPATCH_OUTBOX,
+            JobKind::PollLlmBatch(_) => registry::POLL_LLM_BATCH,
+            JobKind::HandleBatchCompletion(_) => registry::HANDLE_BATCH_COMPLETION,
+            JobKind::WakeIdlePersons => registry::WAKE_IDLE_PERSONS,
+            JobKind::NoticeConversation(_) => registry::NOTICE_CONVERSATION,
+            JobKind::CheckPersonaConsistency(_) => registry::CHECK_PERSONA_CONSISTENCY,
+            JobKind::CheckSceneGoals => registry::CHECK_SCENE_GOALS,
+            JobKind::CloseScene(_) => registry::CLOSE_SCENE,
+            JobKind::ArchiveScene(_) => registry::ARCHIVE_SCENE,
+            JobKind::TagTopics => registry::TAG_TOPICS,
+            JobKind::ChangeSceneAmbience(_) => registry::CHANGE_SCENE_AMBIENCE,
+            JobKind::InjectSceneEvents => registry::INJECT_SCENE_EVENTS,
+            JobKind::ReactToSceneEvent(_) => registry::REACT_TO_SCENE_EVENT,
+            JobKind::RunCustomAction(_) => registry::RUN_CUSTOM_ACTION,
+            JobKind::RefillAcknowledgements(_) => registry::REFILL_ACKNOWLEDGEMENTS,
+            JobKind::NightlyMaintenance => registry::NIGHTLY_MAINTENANCE,
+            JobKind::UseTool(_) => registry::USE_TOOL,
+            JobKind::ReactToSceneInvitation(_) => registry::REACT_TO_SCENE_INVITATION,
+            JobKind::UpdatePersonArcs => registry::UPDATE_PERSON_ARCS,
+            JobKind::BalanceChattiness => registry::BALANCE_CHATTINESS,
         }
     }
 
-    pub fn to_data(&self) -> Result<Option<serde_json::Value>, String> {
-        match self {
-            JobKind::Ping => Ok(None),
-            JobKind::SendMessageToScene(job) => {
-                let data = serde_json::to_value(job)
-                    .map_err(|err| format!("Failed to serialize SendMessageToSceneJob: {}", err))?;
-                Ok(Some(data))
-            }
-            JobKind::ProcessPersonJoin(job) => {
-                let data = serde_json::to_value(job)
-                    .map_err(|err| format!("Failed to serialize ProcessPersonJoinJob: {}", err))?;
-                Ok(Some(data))
-            }
-            JobKind::ProcessMessage(job) => {
-                let data = serde_json::to_value(job)
-                    .map_err(|err| format!("Failed to serialize ProcessMessageJob: {}", err))?;
-                Ok(Some(data))
-            }
-            JobKind::ProcessSceneGaze(job) => {
-                let data = serde_json::to_value(job)
-                    .map_err(|err| format!("Failed to serialize ProcessSceneGazeJob: {}", err))?;
-                Ok(Some(data))
-            }
-            JobKind::PersonWaiting(job) => {
-                let data = serde_json::to_value(job)
-                    .map_err(|err| format!("Failed to serialize PersonWaitingJob: {}", err))?;
-                Ok(Some(data))
-            }
-            JobKind::PersonHibernating(job) => {
-                let data = serde_json::to_value(job)
-                    .map_err(|err| format!("Failed to serialize PersonHibernatingJob: {}", err))?;
-                Ok(Some(data))
-            }
-        }
+    pub fn to_name(&self) -> String {
+        self.name().to_string()
     }
-}
 
-impl Display for JobUuid {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let str = match self {
-            JobUuid::Real(uuid) => uuid.to_string(),
-            JobUuid::Test(id) => id.to_string(),
+    /// Jobs with the same lock key never run at the same time. Anything that
+    /// makes a person react is keyed by that person, so two workers cannot
+    /// interleave contradictory reactions for them.
+    pub fn lock_key(&self) -> Option<String> {
+        let person_uuid = match self {
+            JobKind::Ping => None,
+            JobKind::SendMessageToScene(job) => match &job.sender {
+                MessageSender::AiPerson(person_uuid) => Some(person_uuid),
+                MessageSender::RealWorldUser => None,
+            },
+            JobKind::ProcessPersonJoin(job) => Some(&job.recipient_person_uuid),
+            JobKind::ProcessMessage(job) => Some(&job.recipient_person_uuid),
+            JobKind::ProcessSceneGaze(job) => Some(&job.gazing_person_uuid),
+            JobKind::PersonWaiting(job) => job.person_uuid(),
+            JobKind::PersonHibernating(job) => Some(job.person_uuid()),
+            JobKind::CheckExpectedReply(job) => Some(&job.asker_person_uuid),
+            // Only one dispatcher delivers at a time, which keeps deliveries in order
+            JobKind::DispatchOutbox(_) => return Some(OUTBOX_LOCK_KEY.to_string()),
+            JobKind::PollLlmBatch(_) => None,
+            JobKind::HandleBatchCompletion(_) => None,
+            // Overlapping scans would nudge the same people twice
+            JobKind::WakeIdlePersons => return Some(WAKE_IDLE_PERSONS_LOCK_KEY.to_string()),
+            JobKind::NoticeConversation(job) => Some(&job.person_uuid),
+            JobKind::CheckPersonaConsistency(_) => None,
+            // Overlapping checks could both see a goal as met before either marks it
+            JobKind::CheckSceneGoals => return Some(CHECK_SCENE_GOALS_LOCK_KEY.to_string()),
+            JobKind::CloseScene(_) => None,
+            JobKind::ArchiveScene(_) => None,
+            // Overlapping scans would count the same messages twice
+            JobKind::TagTopics => return Some(TAG_TOPICS_LOCK_KEY.to_string()),
+            JobKind::ChangeSceneAmbience(_) => None,
+            // Overlapping scans could give one scene two events at once
+            JobKind::InjectSceneEvents => return Some(INJECT_SCENE_EVENTS_LOCK_KEY.to_string()),
+            JobKind::ReactToSceneEvent(job) => Some(&job.person_uuid),
+            JobKind::RunCustomAction(_) => None,
+            JobKind::UseTool(job) => Some(&job.person_uuid),
+            JobKind::ReactToSceneInvitation(job) => Some(&job.person_uuid),
+            // Two refills at once would both see the pool short and overfill it
+            JobKind::RefillAcknowledgements(job) => {
+                return Some(format!(
+                    "{}:{}",
+                    registry::REFILL_ACKNOWLEDGEMENTS,
+                    job.person_uuid.to_uuid()
+                ))
+            }
+            // A second run would reflect on the same day twice
+            JobKind::NightlyMaintenance => return Some(NIGHTLY_MAINTENANCE_LOCK_KEY.to_string()),
+            // Overlapping scans would write the same person's arc twice
+            JobKind::UpdatePersonArcs => return Some(UPDATE_PERSON_ARCS_LOCK_KEY.to_string()),
+            // Overlapping runs would both step the same person
+            JobKind::BalanceChattiness => return Some(BALANCE_CHATTINESS_LOCK_KEY.to_string()),
         };
 
-        write!(f, "{}", str)
+        person_uuid.map(person_lock_key)
+    }
+
+    /// Jobs with a higher priority are popped first.
+    pub fn priority(&self) -> i32 {
+        match self {
+            JobKind::ProcessMessage(job) => job.urgency.to_job_priority(),
+            _ => 0,
+        }
+    }
+
+    pub fn to_data(&self) -> Result<Option<serde_json::Value>, String> {
+        match self {
+            JobKind::Ping
+            | JobKind::WakeIdlePersons
+            | JobKind::CheckSceneGoals
+            | JobKind::TagTopics
+            | JobKind::InjectSceneEvents
+            | JobKind::NightlyMaintenance
+            | JobKind::UpdatePersonArcs
+            | JobKind::BalanceChattiness => Ok(None),
+            JobKind::SendMessageToScene(job) => registry::to_data(self.name(), job),
+            JobKind::ProcessPersonJoin(job) => registry::to_data(self.name(), job),
+            JobKind::ProcessMessage(job) => registry::to_data(self.name(), job),
+            JobKind::ProcessSceneGaze(job) => registry::to_data(self.name(), job),
+            JobKind::PersonWaiting(job) => registry::to_data(self.name(), job),
+            JobKind::PersonHibernating(job) => registry::to_data(self.name(), job),
+            JobKind::CheckExpectedReply(job) => registry::to_data(self.name(), job),
+            JobKind::DispatchOutbox(job) => registry::to_data(self.name(), job),
+            JobKind::PollLlmBatch(job) => registry::to_data(self.name(), job),
+            JobKind::HandleBatchCompletion(job) => registry::to_data(self.name(), job),
+            JobKind::NoticeConversation(job) => registry::to_data(self.name(), job),
+            JobKind::CheckPersonaConsistency(job) => registry::to_data(self.name(), job),
+            JobKind::CloseScene(job) => registry::to_data(self.name(), job),
+            JobKind::ArchiveScene(job) => registry::to_data(self.name(), job),
+            JobKind::ChangeSceneAmbience(job) => registry::to_data(self.name(), job),
+            JobKind::ReactToSceneEvent(job) => registry::to_data(self.name(), job),
+            JobKind::RunCustomAction(job) => registry::to_data(self.name(), job),
+            JobKind::RefillAcknowledgements(job) => registry::to_data(self.name(), job),
+            JobKind::UseTool(job) => registry::to_data(self.name(), job),
+            JobKind::ReactToSceneInvitation(job) => registry::to_data(self.name(), job),
+        }
     }
 }
 
@@ -158,8 +316,7 @@ impl Job {
         match self.status() {
             JobStatus::Finished => match self.finished_at {
                 Some(finished_at) => {
-                    let date = finished_at.format("%Y-%m-%d %H:%M:%S UTC").to_string();
-                    format!("finished at {}", date)
+                    format!("finished {}", time_display::format_recent(finished_at))
                 }
                 None => "finished".to_string(),
             },
@@ -243,91 +400,48 @@ impl JobKind {
         name: String,
         maybe_data: Option<serde_json::Value>,
     ) -> Result<JobKind, ParseError> {
-        match name.as_str() {
-            "ping" => Ok(JobKind::Ping),
-            "send message to scene" => match maybe_data {
-                None => Err(ParseError::NoJobDataForJobThatReuiresIt { job_name: name }),
-                Some(data) => {
-                    let job: SendMessageToSceneJob =
-                        serde_json::from_value(data).map_err(|error| {
-                            ParseError::FailedToParseJobData {
-                                job_name: name.clone(),
-                                details: error.to_string(),
-                            }
-                        })?;
-
-                    Ok(JobKind::SendMessageToScene(job))
-                }
-            },
-            "process message" => match maybe_data {
-                None => Err(ParseError::NoJobDataForJobThatReuiresIt { job_name: name }),
-                Some(data) => {
-                    let job: ProcessMessageJob = serde_json::from_value(data).map_err(|error| {
-                        ParseError::FailedToParseJobData {
-                            job_name: name.clone(),
-                            details: error.to_string(),
-                        }
-                    })?;
-
-                    Ok(JobKind::ProcessMessage(job))
-                }
-            },
-            "process person join" => match maybe_data {
-                None => Err(ParseError::NoJobDataForJobThatReuiresIt { job_name: name }),
-                Some(data) => {
-                    let job: ProcessPersonJoinJob =
-                        serde_json::from_value(data).map_err(|error| {
-                            ParseError::FailedToParseJobData {
-                                job_name: name.clone(),
-                                details: error.to_string(),
-                            }
-                        })?;
-
-                    Ok(JobKind::ProcessPersonJoin(job))
-                }
-            },
-            "process scene gaze" => match maybe_data {
-                None => Err(ParseError::NoJobDataForJobThatReuiresIt { job_name: name }),
-                Some(data) => {
-                    let job: ProcessSceneGazeJob =
-                        serde_json::from_value(data).map_err(|error| {
-                            ParseError::FailedToParseJobData {
-                                job_name: name.clone(),
-                                details: error.to_string(),
-                            }
-                        })?;
-
-                    Ok(JobKind::ProcessSceneGaze(job))
-                }
-            },
-            "person waiting" => match maybe_data {
-                None => Err(ParseError::NoJobDataForJobThatReuiresIt { job_name: name }),
-                Some(data) => {
-                    let job: PersonWaitingJob = serde_json::from_value(data).map_err(|error| {
-                        ParseError::FailedToParseJobData {
-                            job_name: name.clone(),
-                            details: error.to_string(),
-                        }
-                    })?;
-
-                    Ok(JobKind::PersonWaiting(job))
-                }
-            },
-            "person hibernating" => match maybe_data {
-                None => Err(ParseError::NoJobDataForJobThatReuiresIt { job_name: name }),
-                Some(data) => {
-                    let job: PersonHibernatingJob =
-                        serde_json::from_value(data).map_err(|error| {
-                            ParseError::FailedToParseJobData {
-                                job_name: name.clone(),
-                                details: error.to_string(),
-                            }
-                        })?;
-
-                    Ok(JobKind::PersonHibernating(job))
-                }
-            },
-            _ => Err(ParseError::UnknownJobName(name)),
+        match registry::find(name.as_str()) {
+            Some(registration) => (registration.parse)(maybe_data),
+            None => Err(ParseError::UnknownJobName(name)),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::job::person_hibernating::PersonHibernatingJob;
+    use crate::domain::message_urgency::MessageUrgency;
+    use crate::domain::message_uuid::MessageUuid;
+    use uuid::Uuid;
+
+    #[test]
+    fn test_reactions_for_the_same_person_share_a_lock_key() {
+        let person_uuid = PersonUuid::from_uuid(Uuid::from_u128(7));
+        let other_person_uuid = PersonUuid::from_uuid(Uuid::from_u128(8));
+
+        let message_job = JobKind::ProcessMessage(ProcessMessageJob {
+            message_uuid: MessageUuid::new(),
+            recipient_person_uuid: person_uuid.clone(),
+            run_at_active_ms: None,
+            urgency: MessageUrgency::Background,
+        });
+        let hibernating_job = JobKind::PersonHibernating(PersonHibernatingJob::new(
+            person_uuid.clone(),
+            1_000,
+            0,
+            Utc::now(),
+        ));
+        let other_message_job = JobKind::ProcessMessage(ProcessMessageJob {
+            message_uuid: MessageUuid::new(),
+            recipient_person_uuid: other_person_uuid,
+            run_at_active_ms: None,
+            urgency: MessageUrgency::Direct,
+        });
+
+        assert_eq!(message_job.lock_key(), Some(person_lock_key(&person_uuid)));
+        assert_eq!(message_job.lock_key(), hibernating_job.lock_key());
+        assert_ne!(message_job.lock_key(), other_message_job.lock_key());
+        assert_eq!(JobKind::Ping.lock_key(), None);
+    }
+}
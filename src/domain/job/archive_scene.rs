@@ -0,0 +1,130 @@
+use crate::capability::logging::LogCapability;
+use crate::capability::scene::SceneCapability;
+use crate::capability::scene_archive::SceneArchiveCapability;
+use crate::capability::scene_goal::SceneGoalCapability;
+use crate::domain::logger::Level;
+use crate::domain::scene_archive;
+use crate::domain::scene_uuid::SceneUuid;
+use crate::nice_display::{with_context, NiceDisplay};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Tidies up after a scene has ended: cancels whatever was still queued for
+/// it, makes sure nobody is left in it, and writes its transcript to disk.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchiveSceneJob {
+    pub scene_uuid: SceneUuid,
+}
+
+pub enum Error {
+    CancelJobs(String),
+    ReleaseParticipants(String),
+    GetName(String),
+    SceneNotFound,
+    GetDescription(String),
+    GetTranscript(String),
+    GetAnnotations(String),
+    Export(String),
+    MarkArchived(String),
+}
+
+impl NiceDisplay for Error {
+    fn message(&self) -> String {
+        match self {
+            Error::CancelJobs(details) => {
+                with_context("Could not cancel the scene's pending jobs", details)
+            }
+            Error::ReleaseParticipants(details) => {
+                with_context("Could not release the scene's participants", details)
+            }
+            Error::GetName(details) => with_context("Could not get the scene name", details),
+            Error::SceneNotFound => "The scene does not exist".to_string(),
+            Error::GetDescription(details) => {
+                with_context("Could not get the scene description", details)
+            }
+            Error::GetTranscript(details) => {
+                with_context("Could not get the scene transcript", details)
+            }
+            Error::GetAnnotations(details) => {
+                with_context("Could not get the annotations to export", details)
+            }
+            Error::Export(details) => with_context("Could not export the transcript", details),
+            Error::MarkArchived(details) => {
+                with_context("Could not mark the scene archived", details)
+            }
+        }
+    }
+}
+
+impl ArchiveSceneJob {
+    pub async fn run<
+        W: SceneCapability + SceneGoalCapability + SceneArchiveCapability + LogCapability,
+    >(
+        self,
+        worker: &W,
+    ) -> Result<(), Error> {
+        let cancelled = worker
+            .cancel_pending_scene_jobs(&self.scene_uuid)
+            .await
+            .map_err(Error::CancelJobs)?;
+
+        // Ending an already ended scene only releases anyone still in it
+        worker
+            .delete_scene(&self.scene_uuid)
+            .await
+            .map_err(Error::ReleaseParticipants)?;
+
+        let scene_name = worker
+            .get_scene_name(&self.scene_uuid)
+            .await
+            .map_err(Error::GetName)?
+            .ok_or(Error::SceneNotFound)?;
+
+        let scene_description = worker
+            .get_scene_description(&self.scene_uuid)
+            .await
+            .map_err(Error::GetDescription)?;
+
+        let lines = worker
+            .get_scene_transcript_since(&self.scene_uuid, DateTime::<Utc>::MIN_UTC)
+            .await
+            .map_err(Error::GetTranscript)?;
+
+        let annotations = worker
+            .get_exported_annotations(&self.scene_uuid)
+            .await
+            .map_err(Error::GetAnnotations)?;
+
+        let transcript = scene_archive::render_transcript(
+            &scene_name,
+            scene_description.as_deref(),
+            &lines,
+            &annotations,
+        );
+
+        let transcript_path = worker
+            .export_scene_transcript(
+                &scene_archive::transcript_file_name(&scene_name, &self.scene_uuid),
+                &transcript,
+            )
+            .await
+            .map_err(Error::Export)?;
+
+        worker
+            .mark_scene_archived(&self.scene_uuid, &transcript_path)
+            .await
+            .map_err(Error::MarkArchived)?;
+
+        worker.log(
+            Level::Info,
+            &format!(
+                "Archived scene {} to {}, cancelling {} pending jobs",
+                self.scene_uuid.to_uuid(),
+                transcript_path,
+                cancelled
+            ),
+        );
+
+        Ok(())
+    }
+}
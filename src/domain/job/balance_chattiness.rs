@@ -0,0 +1,65 @@
+use crate::capability::clock::ClockCapability;
+use crate::capability::idle_person::IdlePersonCapability;
+use crate::capability::logging::LogCapability;
+use crate::domain::logger::Level;
+use crate::domain::talk_share;
+use crate::nice_display::{with_context, NiceDisplay};
+use chrono::Duration;
+
+/// How often the job runner rebalances chattiness.
+pub const SCAN_INTERVAL: std::time::Duration = std::time::Duration::from_secs(60 * 60);
+/// How far back messages are counted. Longer than the scan interval, so one
+/// burst does not swing anyone on its own.
+const WINDOW_HOURS: i64 = 6;
+
+pub enum Error {
+    GetTalkCounts(String),
+    SetChattiness(String),
+}
+
+impl NiceDisplay for Error {
+    fn message(&self) -> String {
+        match self {
+            Error::GetTalkCounts(details) => {
+                with_context("Could not count who has been talking", details)
+            }
+            Error::SetChattiness(details) => {
+                with_context("Could not save a rebalanced chattiness", details)
+            }
+        }
+    }
+}
+
+/// Turns down the chattiness of persons who have been sending far more than
+/// their share of their scenes' messages, and turns up the ones being
+/// talked over, a step per run. Returns how many were changed.
+pub async fn run<W: IdlePersonCapability + ClockCapability + LogCapability>(
+    worker: &W,
+) -> Result<usize, Error> {
+    let counts = worker
+        .get_talk_counts(worker.now() - Duration::hours(WINDOW_HOURS))
+        .await
+        .map_err(Error::GetTalkCounts)?;
+
+    let adjustments = talk_share::adjustments(&counts);
+
+    for adjustment in adjustments.iter() {
+        worker
+            .set_person_chattiness(&adjustment.person_uuid, adjustment.to)
+            .await
+            .map_err(Error::SetChattiness)?;
+
+        worker.log(
+            Level::Info,
+            &format!(
+                "Set the chattiness of person {} from {:.2} to {:.2}, since they sent {:.2} times their share of messages",
+                adjustment.person_uuid.to_uuid(),
+                adjustment.from,
+                adjustment.to,
+                adjustment.share_ratio
+            ),
+        );
+    }
+
+    Ok(adjustments.len())
+}
@@ -0,0 +1,83 @@
+use crate::capability::job::JobCapability;
+use crate::capability::logging::LogCapability;
+use crate::capability::scene::SceneCapability;
+use crate::domain::actor_uuid::ActorUuid;
+use crate::domain::job::process_scene_gaze::ProcessSceneGazeJob;
+use crate::domain::job::JobKind;
+use crate::domain::logger::Level;
+use crate::domain::scene_ambience::Ambience;
+use crate::domain::scene_uuid::SceneUuid;
+use crate::nice_display::{with_context, NiceDisplay};
+use serde::{Deserialize, Serialize};
+
+/// Changes the weather, noise or lighting of a scene, then has everyone in
+/// it look around so they can react to the change. Queued with a
+/// `run_at_active_ms` to schedule the change for later.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChangeSceneAmbienceJob {
+    pub scene_uuid: SceneUuid,
+    pub ambience: Ambience,
+    pub run_at_active_ms: Option<i64>,
+}
+
+pub enum Error {
+    SetAmbience(String),
+    GetParticipants(String),
+    EnqueueGaze(String),
+}
+
+impl NiceDisplay for Error {
+    fn message(&self) -> String {
+        match self {
+            Error::SetAmbience(details) => {
+                with_context("Could not set the scene ambience", details)
+            }
+            Error::GetParticipants(details) => {
+                with_context("Could not get the scene participants", details)
+            }
+            Error::EnqueueGaze(details) => {
+                with_context("Could not enqueue a scene gaze job", details)
+            }
+        }
+    }
+}
+
+impl ChangeSceneAmbienceJob {
+    pub async fn run<W: SceneCapability + JobCapability + LogCapability>(
+        self,
+        worker: &W,
+    ) -> Result<(), Error> {
+        worker
+            .set_scene_ambience(&self.scene_uuid, self.ambience)
+            .await
+            .map_err(Error::SetAmbience)?;
+
+        let participants = worker
+            .get_scene_current_participants(&self.scene_uuid)
+            .await
+            .map_err(Error::GetParticipants)?;
+
+        for participant in participants {
+            if let ActorUuid::AiPerson(person_uuid) = participant.actor_uuid {
+                worker
+                    .unshift_job(JobKind::ProcessSceneGaze(ProcessSceneGazeJob {
+                        scene_uuid: self.scene_uuid.clone(),
+                        gazing_person_uuid: person_uuid,
+                    }))
+                    .await
+                    .map_err(Error::EnqueueGaze)?;
+            }
+        }
+
+        worker.log(
+            Level::Info,
+            &format!(
+                "Changed the ambience of scene {} to {}",
+                self.scene_uuid.to_uuid(),
+                self.ambience.to_label()
+            ),
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,133 @@
+use crate::capability::event::EventCapability;
+use crate::capability::expected_reply::{ExpectedReplyCapability, ExpectedReplyOutcome};
+use crate::capability::item::ItemCapability;
+use crate::capability::job::JobCapability;
+use crate::capability::log_event::LogEventCapability;
+use crate::capability::logging::LogCapability;
+use crate::capability::memory::MemoryCapability;
+use crate::capability::message::MessageCapability;
+use crate::capability::moderation::ModerationCapability;
+use crate::capability::motivation::MotivationCapability;
+use crate::capability::person::PersonCapability;
+use crate::capability::person_identity::PersonIdentityCapability;
+use crate::capability::person_task::PersonTaskCapability;
+use crate::capability::reaction::ReactionCapability;
+use crate::capability::reaction_history::ReactionHistoryCapability;
+use crate::capability::reflection::ReflectionCapability;
+use crate::capability::scene::SceneCapability;
+use crate::capability::scene_invitation::SceneInvitationCapability;
+use crate::capability::state_of_mind::StateOfMindCapability;
+use crate::domain::job::process_reaction_common::{self, SceneReactionTrigger};
+use crate::domain::message_uuid::MessageUuid;
+use crate::domain::person_uuid::PersonUuid;
+use crate::domain::random_seed::RandomSeed;
+use crate::domain::scene_uuid::SceneUuid;
+use crate::nice_display::{with_context, NiceDisplay};
+use serde::{Deserialize, Serialize};
+
+/// Runs once the reply window of an `ask` has passed, and lets the asker
+/// react if the recipient never answered.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CheckExpectedReplyJob {
+    pub message_uuid: MessageUuid,
+    pub asker_person_uuid: PersonUuid,
+    pub recipient_person_uuid: PersonUuid,
+    pub scene_uuid: SceneUuid,
+    pub question: String,
+    pub run_at_active_ms: i64,
+}
+
+pub enum Error {
+    CheckReply(String),
+    Resolve(String),
+    AskerScene(String),
+    Reaction(process_reaction_common::Error),
+}
+
+impl NiceDisplay for Error {
+    fn message(&self) -> String {
+        match self {
+            Error::CheckReply(details) => with_context("Could not check for a reply", details),
+            Error::Resolve(details) => {
+                with_context("Could not resolve the expected reply", details)
+            }
+            Error::AskerScene(details) => with_context("Could not get the asker's scene", details),
+            Error::Reaction(err) => err.message(),
+        }
+    }
+}
+
+impl CheckExpectedReplyJob {
+    pub async fn run<
+        W: SceneCapability
+            + ReactionCapability
+            + MemoryCapability
+            + MessageCapability
+            + ModerationCapability
+            + PersonCapability
+            + EventCapability
+            + StateOfMindCapability
+            + PersonIdentityCapability
+            + PersonTaskCapability
+            + ReflectionCapability
+            + LogCapability
+            + LogEventCapability
+            + MotivationCapability
+            + ReactionHistoryCapability
+            + ExpectedReplyCapability
+            + ItemCapability
+            + SceneInvitationCapability
+            + JobCapability
+            + Sync,
+    >(
+        self,
+        worker: &W,
+        random_seed: RandomSeed,
+        current_active_ms: i64,
+    ) -> Result<(), Error> {
+        let replied = worker
+            .has_recipient_replied(&self.message_uuid)
+            .await
+            .map_err(Error::CheckReply)?;
+
+        if replied {
+            return worker
+                .resolve_expected_reply(&self.message_uuid, ExpectedReplyOutcome::Replied)
+                .await
+                .map_err(Error::Resolve);
+        }
+
+        worker
+            .resolve_expected_reply(&self.message_uuid, ExpectedReplyOutcome::Ignored)
+            .await
+            .map_err(Error::Resolve)?;
+
+        // If the asker has since walked away, being ignored is moot.
+        let asker_scene_uuid = worker
+            .get_persons_current_scene_uuid(&self.asker_person_uuid)
+            .await
+            .map_err(Error::AskerScene)?;
+
+        let still_in_scene = asker_scene_uuid
+            .map(|scene_uuid| scene_uuid.to_uuid() == self.scene_uuid.to_uuid())
+            .unwrap_or(false);
+
+        if !still_in_scene {
+            return Ok(());
+        }
+
+        process_reaction_common::run_scene_reaction(
+            worker,
+            &self.asker_person_uuid,
+            &self.scene_uuid,
+            SceneReactionTrigger::QuestionIgnored {
+                recipient_person_uuid: self.recipient_person_uuid,
+                question: self.question,
+            },
+            random_seed,
+            current_active_ms,
+        )
+        .await
+        .map_err(Error::Reaction)
+    }
+}
@@ -0,0 +1,100 @@
+use crate::capability::logging::LogCapability;
+use crate::capability::person_identity::PersonIdentityCapability;
+use crate::capability::persona_consistency::PersonaConsistencyCapability;
+use crate::domain::logger::Level;
+use crate::domain::person_uuid::PersonUuid;
+use crate::domain::persona_consistency;
+use crate::domain::random_seed::RandomSeed;
+use crate::nice_display::{with_context, NiceDisplay};
+use serde::{Deserialize, Serialize};
+
+/// Has an LLM judge a sample of what the person said recently against their
+/// identity, and records whatever does not fit.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CheckPersonaConsistencyJob {
+    pub person_uuid: PersonUuid,
+}
+
+pub enum Error {
+    Identity(String),
+    NoIdentity,
+    Utterances(String),
+    Judge(String),
+    Record(String),
+}
+
+impl NiceDisplay for Error {
+    fn message(&self) -> String {
+        match self {
+            Error::Identity(details) => {
+                with_context("Could not get the person's identity", details)
+            }
+            Error::NoIdentity => "The person has no identity to check against".to_string(),
+            Error::Utterances(details) => {
+                with_context("Could not get what the person said recently", details)
+            }
+            Error::Judge(details) => {
+                with_context("Could not judge the person's consistency", details)
+            }
+            Error::Record(details) => with_context("Could not record the inconsistencies", details),
+        }
+    }
+}
+
+impl CheckPersonaConsistencyJob {
+    /// Returns how many inconsistencies were flagged.
+    pub async fn run<W: PersonIdentityCapability + PersonaConsistencyCapability + LogCapability>(
+        self,
+        worker: &W,
+        random_seed: RandomSeed,
+    ) -> Result<usize, Error> {
+        let identity = worker
+            .get_person_identity(&self.person_uuid)
+            .await
+            .map_err(Error::Identity)?
+            .ok_or(Error::NoIdentity)?;
+
+        let utterances = worker
+            .get_recent_utterances(
+                &self.person_uuid,
+                persona_consistency::RECENT_UTTERANCE_LIMIT,
+            )
+            .await
+            .map_err(Error::Utterances)?;
+
+        if utterances.is_empty() {
+            worker.log(
+                Level::Info,
+                &format!(
+                    "Skipping consistency check for person {}: they have not said anything",
+                    self.person_uuid.to_uuid()
+                ),
+            );
+            return Ok(0);
+        }
+
+        let sample = persona_consistency::sample_utterances(utterances, random_seed);
+
+        let inconsistencies = worker
+            .judge_persona_consistency(&identity, &sample)
+            .await
+            .map_err(Error::Judge)?;
+
+        worker
+            .record_persona_inconsistencies(&self.person_uuid, &inconsistencies)
+            .await
+            .map_err(Error::Record)?;
+
+        worker.log(
+            Level::Info,
+            &format!(
+                "Flagged {} of {} utterances by person {} as out of character",
+                inconsistencies.len(),
+                sample.len(),
+                self.person_uuid.to_uuid()
+            ),
+        );
+
+        Ok(inconsistencies.len())
+    }
+}
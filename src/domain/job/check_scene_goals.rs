@@ -0,0 +1,127 @@
+use crate::capability::clock::ClockCapability;
+use crate::capability::job::JobCapability;
+use crate::capability::logging::LogCapability;
+use crate::capability::scene_goal::SceneGoalCapability;
+use crate::domain::job::close_scene::CloseSceneJob;
+use crate::domain::job::JobKind;
+use crate::domain::logger::Level;
+use crate::domain::scene_goal::{self, OpenSceneGoal, SceneGoalMet};
+use crate::nice_display::{with_context, NiceDisplay};
+
+/// How often the job runner checks whether scenes have met their goals.
+pub const SCAN_INTERVAL: std::time::Duration = std::time::Duration::from_secs(60);
+
+pub enum Error {
+    GetGoals(String),
+    GetTranscript(String),
+    JudgeConsensus(String),
+    MarkMet(String),
+    Enqueue(String),
+}
+
+impl NiceDisplay for Error {
+    fn message(&self) -> String {
+        match self {
+            Error::GetGoals(details) => with_context("Could not get the open scene goals", details),
+            Error::GetTranscript(details) => {
+                with_context("Could not get the scene transcript", details)
+            }
+            Error::JudgeConsensus(details) => with_context(
+                "Could not judge whether the scene reached consensus",
+                details,
+            ),
+            Error::MarkMet(details) => with_context("Could not mark the scene goal met", details),
+            Error::Enqueue(details) => with_context("Could not enqueue closing the scene", details),
+        }
+    }
+}
+
+/// Checks every open scene goal and enqueues a `close scene` job for each
+/// one that has been met. Returns how many scenes are being closed.
+pub async fn run<W: SceneGoalCapability + JobCapability + LogCapability>(
+    worker: &W,
+    current_active_ms: i64,
+) -> Result<usize, Error> {
+    let open_goals = worker
+        .get_open_scene_goals()
+        .await
+        .map_err(Error::GetGoals)?;
+
+    let mut closing = 0;
+
+    for open_goal in open_goals {
+        let met = match check_goal(worker, &open_goal, current_active_ms).await? {
+            Some(met) => met,
+            None => continue,
+        };
+
+        let newly_met = worker
+            .mark_scene_goal_met(&open_goal.scene_uuid, &met)
+            .await
+            .map_err(Error::MarkMet)?;
+
+        if !newly_met {
+            continue;
+        }
+
+        worker.log(
+            Level::Info,
+            &format!(
+                "Scene {} met its goal: {}",
+                open_goal.scene_uuid.to_uuid(),
+                met.to_text()
+            ),
+        );
+
+        worker
+            .unshift_job(JobKind::CloseScene(CloseSceneJob {
+                scene_uuid: open_goal.scene_uuid,
+                reason: met,
+            }))
+            .await
+            .map_err(Error::Enqueue)?;
+        closing += 1;
+    }
+
+    Ok(closing)
+}
+
+async fn check_goal<W: SceneGoalCapability + ClockCapability>(
+    worker: &W,
+    open_goal: &OpenSceneGoal,
+    current_active_ms: i64,
+) -> Result<Option<SceneGoalMet>, Error> {
+    let lines = worker
+        .get_scene_transcript_since(&open_goal.scene_uuid, open_goal.set_at)
+        .await
+        .map_err(Error::GetTranscript)?;
+
+    if let Some(met) = open_goal.goal.check_without_llm(current_active_ms, &lines) {
+        return Ok(Some(met));
+    }
+
+    let consensus_on = match &open_goal.goal.consensus_on {
+        Some(consensus_on) => consensus_on,
+        None => return Ok(None),
+    };
+
+    // Nothing new has been said since the last scan, so the answer would not change
+    let last_scan = worker.now() - SCAN_INTERVAL;
+    let has_new_lines = lines.iter().any(|line| line.sent_at >= last_scan);
+    if !has_new_lines {
+        return Ok(None);
+    }
+
+    let reached = worker
+        .judge_scene_consensus(consensus_on, &scene_goal::to_transcript(&lines))
+        .await
+        .map_err(Error::JudgeConsensus)?;
+
+    if reached {
+        Ok(Some(SceneGoalMet::ConsensusReached {
+            consensus_on: consensus_on.clone(),
+        }))
+    } else {
+        Ok(None)
+    }
+}
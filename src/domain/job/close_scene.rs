@@ -0,0 +1,129 @@
+use crate::capability::job::JobCapability;
+use crate::capability::logging::LogCapability;
+use crate::capability::outbox::OutboxCapability;
+use crate::capability::scene::{NewSceneSnapshot, SceneCapability};
+use crate::capability::scene_goal::SceneGoalCapability;
+use crate::domain::job::archive_scene::ArchiveSceneJob;
+use crate::domain::job::JobKind;
+use crate::domain::logger::Level;
+use crate::domain::outbox::OutboxEvent;
+use crate::domain::scene_goal::{self, SceneGoalMet};
+use crate::domain::scene_uuid::SceneUuid;
+use crate::nice_display::{with_context, NiceDisplay};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Ends a scene that met its goal: leaves it with a closing snapshot,
+/// releases everyone in it, tells the outbox how it ended, and queues it
+/// for archiving.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CloseSceneJob {
+    pub scene_uuid: SceneUuid,
+    pub reason: SceneGoalMet,
+}
+
+pub enum Error {
+    GetDescription(String),
+    GetTranscript(String),
+    Summarize(String),
+    Snapshot(String),
+    EndScene(String),
+    Outbox(String),
+    Archive(String),
+}
+
+impl NiceDisplay for Error {
+    fn message(&self) -> String {
+        match self {
+            Error::GetDescription(details) => {
+                with_context("Could not get the scene description", details)
+            }
+            Error::GetTranscript(details) => {
+                with_context("Could not get the scene transcript", details)
+            }
+            Error::Summarize(details) => {
+                with_context("Could not summarize how the scene ended", details)
+            }
+            Error::Snapshot(details) => {
+                with_context("Could not save the closing snapshot", details)
+            }
+            Error::EndScene(details) => with_context("Could not end the scene", details),
+            Error::Outbox(details) => with_context(
+                "Could not add the scene closed event to the outbox",
+                details,
+            ),
+            Error::Archive(details) => {
+                with_context("Could not enqueue archiving the scene", details)
+            }
+        }
+    }
+}
+
+impl CloseSceneJob {
+    pub async fn run<
+        W: SceneCapability + SceneGoalCapability + OutboxCapability + JobCapability + LogCapability,
+    >(
+        self,
+        worker: &W,
+    ) -> Result<(), Error> {
+        let scene_description = worker
+            .get_scene_description(&self.scene_uuid)
+            .await
+            .map_err(Error::GetDescription)?
+            .unwrap_or_else(|| "No description.".to_string());
+
+        let lines = worker
+            .get_scene_transcript_since(&self.scene_uuid, DateTime::<Utc>::MIN_UTC)
+            .await
+            .map_err(Error::GetTranscript)?;
+
+        let summary = worker
+            .summarize_scene_ending(
+                &scene_description,
+                &scene_goal::to_transcript(&lines),
+                &self.reason,
+            )
+            .await
+            .map_err(Error::Summarize)?;
+
+        worker
+            .create_scene_snapshot(NewSceneSnapshot {
+                scene_uuid: self.scene_uuid.clone(),
+                description: summary.clone(),
+            })
+            .await
+            .map_err(Error::Snapshot)?;
+
+        worker
+            .delete_scene(&self.scene_uuid)
+            .await
+            .map_err(Error::EndScene)?;
+
+        worker
+            .add_outbox_event(&OutboxEvent::SceneClosed {
+                scene_uuid: self.scene_uuid.clone(),
+                reason: self.reason.clone(),
+                summary,
+            })
+            .await
+            .map_err(Error::Outbox)?;
+
+        worker
+            .unshift_job(JobKind::ArchiveScene(ArchiveSceneJob {
+                scene_uuid: self.scene_uuid.clone(),
+            }))
+            .await
+            .map_err(Error::Archive)?;
+
+        worker.log(
+            Level::Info,
+            &format!(
+                "Closed scene {}: {}",
+                self.scene_uuid.to_uuid(),
+                self.reason.to_text()
+            ),
+        );
+
+        Ok(())
+    }
+}
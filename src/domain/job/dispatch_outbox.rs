@@ -0,0 +1,140 @@
+use crate::capability::job::JobCapability;
+use crate::capability::logging::LogCapability;
+use crate::capability::outbox::OutboxCapability;
+use crate::domain::job::JobKind;
+use crate::domain::logger::Level;
+use crate::domain::outbox;
+use crate::nice_display::{with_context, NiceDisplay};
+use chrono::Duration;
+use serde::{Deserialize, Serialize};
+
+const BATCH_SIZE: i64 = 50;
+/// How long to wait before looking at the outbox again while entries are
+/// still waiting on a retry.
+const RECHECK_AFTER_MS: i64 = 30_000;
+
+/// Delivers outbox entries to the configured webhook. Every entry is posted
+/// with its outbox uuid as an idempotency key and only marked delivered once
+/// the webhook accepts it, so receivers see each event exactly once as long
+/// as they drop keys they have already seen.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DispatchOutboxJob {
+    pub run_at_active_ms: Option<i64>,
+}
+
+pub enum Error {
+    Claim(String),
+    MarkDelivered(String),
+    MarkFailed(String),
+    CheckUndelivered(String),
+    Reschedule(String),
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct DispatchReport {
+    pub delivered: usize,
+    pub failed: usize,
+}
+
+impl NiceDisplay for Error {
+    fn message(&self) -> String {
+        match self {
+            Error::Claim(details) => with_context("Could not claim outbox entries", details),
+            Error::MarkDelivered(details) => {
+                with_context("Could not mark an outbox entry delivered", details)
+            }
+            Error::MarkFailed(details) => {
+                with_context("Could not mark an outbox entry failed", details)
+            }
+            Error::CheckUndelivered(details) => {
+                with_context("Could not check for undelivered outbox entries", details)
+            }
+            Error::Reschedule(details) => {
+                with_context("Could not reschedule the outbox dispatcher", details)
+            }
+        }
+    }
+}
+
+impl DispatchOutboxJob {
+    pub fn now() -> Self {
+        DispatchOutboxJob {
+            run_at_active_ms: None,
+        }
+    }
+
+    pub async fn run<W: OutboxCapability + JobCapability + LogCapability>(
+        &self,
+        worker: &W,
+        current_active_ms: i64,
+    ) -> Result<DispatchReport, Error> {
+        let mut report = DispatchReport::default();
+
+        let webhook_url = match worker.get_outbox_webhook_url() {
+            Some(url) => url,
+            None => {
+                worker.log(
+                    Level::Debug,
+                    &format!(
+                        "{} is not set, leaving outbox entries undelivered",
+                        outbox::WEBHOOK_URL_VAR
+                    ),
+                );
+                return Ok(report);
+            }
+        };
+
+        let entries = worker
+            .claim_due_outbox_entries(BATCH_SIZE)
+            .await
+            .map_err(Error::Claim)?;
+
+        for entry in entries.iter() {
+            match worker.post_outbox_entry(&webhook_url, entry).await {
+                Ok(()) => {
+                    worker
+                        .mark_outbox_entry_delivered(&entry.uuid)
+                        .await
+                        .map_err(Error::MarkDelivered)?;
+                    report.delivered += 1;
+                }
+                Err(details) => {
+                    let attempts = entry.attempts.saturating_add(1);
+                    let retry_at =
+                        worker.now() + Duration::seconds(outbox::retry_delay_secs(attempts));
+
+                    worker.log(
+                        Level::Warning,
+                        &format!(
+                            "Failed to deliver outbox entry {} (attempt {}): {}",
+                            entry.uuid.to_uuid(),
+                            attempts,
+                            details
+                        ),
+                    );
+                    worker
+                        .mark_outbox_entry_failed(&entry.uuid, &details, retry_at)
+                        .await
+                        .map_err(Error::MarkFailed)?;
+                    report.failed += 1;
+                }
+            }
+        }
+
+        let has_undelivered = worker
+            .has_undelivered_outbox_entries()
+            .await
+            .map_err(Error::CheckUndelivered)?;
+
+        if has_undelivered {
+            worker
+                .unshift_job(JobKind::DispatchOutbox(DispatchOutboxJob {
+                    run_at_active_ms: Some(current_active_ms.saturating_add(RECHECK_AFTER_MS)),
+                }))
+                .await
+                .map_err(Error::Reschedule)?;
+        }
+
+        Ok(report)
+    }
+}
@@ -0,0 +1,39 @@
+use crate::capability::person_identity::PersonIdentityCapability;
+use crate::domain::llm_batch::BatchHandler;
+use crate::nice_display::{with_context, NiceDisplay};
+use serde::{Deserialize, Serialize};
+
+/// Does whatever the request that produced a Batch API answer wanted done
+/// with it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HandleBatchCompletionJob {
+    pub handler: BatchHandler,
+    pub content: String,
+}
+
+pub enum Error {
+    SetPersonIdentitySummary(String),
+}
+
+impl NiceDisplay for Error {
+    fn message(&self) -> String {
+        match self {
+            Error::SetPersonIdentitySummary(details) => {
+                with_context("Could not save the person identity summary", details)
+            }
+        }
+    }
+}
+
+impl HandleBatchCompletionJob {
+    pub async fn run<W: PersonIdentityCapability>(&self, worker: &W) -> Result<(), Error> {
+        match &self.handler {
+            BatchHandler::PersonIdentitySummary {
+                person_identity_uuid,
+            } => worker
+                .set_person_identity_summary(person_identity_uuid, self.content.trim())
+                .await
+                .map_err(Error::SetPersonIdentitySummary),
+        }
+    }
+}
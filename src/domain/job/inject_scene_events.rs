@@ -0,0 +1,114 @@
+use crate::capability::job::JobCapability;
+use crate::capability::logging::LogCapability;
+use crate::capability::scene::SceneCapability;
+use crate::capability::scene_drama::SceneDramaCapability;
+use crate::domain::actor_uuid::ActorUuid;
+use crate::domain::job::react_to_scene_event::ReactToSceneEventJob;
+use crate::domain::job::JobKind;
+use crate::domain::logger::Level;
+use crate::domain::random_seed::RandomSeed;
+use crate::domain::scene_drama::{self, EVENT_TEMPLATES};
+use crate::domain::scene_uuid::SceneUuid;
+use crate::nice_display::{with_context, NiceDisplay};
+use rand::{Rng, SeedableRng};
+
+/// How often the job runner rolls each scene's drama dial.
+pub const SCAN_INTERVAL: std::time::Duration = std::time::Duration::from_secs(5 * 60);
+
+pub enum Error {
+    GetScenes(String),
+    AddEvent(String),
+    GetParticipants(String),
+    Enqueue(String),
+}
+
+impl NiceDisplay for Error {
+    fn message(&self) -> String {
+        match self {
+            Error::GetScenes(details) => {
+                with_context("Could not get the scenes with drama turned up", details)
+            }
+            Error::AddEvent(details) => with_context("Could not add the scene event", details),
+            Error::GetParticipants(details) => {
+                with_context("Could not get the scene participants", details)
+            }
+            Error::Enqueue(details) => {
+                with_context("Could not enqueue a reaction to the scene event", details)
+            }
+        }
+    }
+}
+
+/// Gives every scene a chance, its drama intensity, of something unexpected
+/// happening, and has everyone in it react. Returns how many scenes got an
+/// event.
+pub async fn run<W: SceneDramaCapability + SceneCapability + JobCapability + LogCapability>(
+    worker: &W,
+    random_seed: RandomSeed,
+) -> Result<usize, Error> {
+    let scenes = worker
+        .get_dramatic_scenes()
+        .await
+        .map_err(Error::GetScenes)?;
+
+    let mut rng = rand::rngs::SmallRng::seed_from_u64(random_seed.value());
+    let mut injected = 0;
+
+    for scene in scenes {
+        if !scene_drama::should_inject(scene.intensity, rng.gen::<f64>()) {
+            continue;
+        }
+
+        let template = match scene_drama::pick_template(&EVENT_TEMPLATES, rng.gen::<f64>()) {
+            Some(template) => template,
+            None => continue,
+        };
+
+        inject(worker, &scene.scene_uuid, template.description).await?;
+
+        worker.log(
+            Level::Info,
+            &format!(
+                "Scene event in {}: {}",
+                scene.scene_uuid.to_uuid(),
+                template.description
+            ),
+        );
+        injected += 1;
+    }
+
+    Ok(injected)
+}
+
+/// Adds the event to the scene and has everyone in it react. Also how
+/// directors inject events through the api.
+pub async fn inject<W: SceneDramaCapability + SceneCapability + JobCapability>(
+    worker: &W,
+    scene_uuid: &SceneUuid,
+    description: &str,
+) -> Result<(), Error> {
+    worker
+        .add_scene_event(scene_uuid, description)
+        .await
+        .map_err(Error::AddEvent)?;
+
+    let participants = worker
+        .get_scene_current_participants(scene_uuid)
+        .await
+        .map_err(Error::GetParticipants)?;
+
+    for participant in participants {
+        if let ActorUuid::AiPerson(person_uuid) = participant.actor_uuid {
+            worker
+                .unshift_job(JobKind::ReactToSceneEvent(ReactToSceneEventJob {
+                    person_uuid,
+                    scene_uuid: scene_uuid.clone(),
+                    description: description.to_string(),
+                }))
+                .await
+                .map_err(Error::Enqueue)?;
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,328 @@
+use crate::capability::clock::ClockCapability;
+use crate::capability::log_event::LogEventCapability;
+use crate::capability::maintenance::MaintenanceCapability;
+use crate::capability::memory::MemoryCapability;
+use crate::capability::motivation::MotivationCapability;
+use crate::capability::person::PersonCapability;
+use crate::capability::person_identity::PersonIdentityCapability;
+use crate::capability::reflection::ReflectionCapability;
+use crate::capability::state_of_mind::StateOfMindCapability;
+use crate::domain::job::process_reaction_common::{apply_reflection_changes, ReflectionInput};
+use crate::domain::memory::Memory;
+use crate::domain::person_uuid::PersonUuid;
+use crate::nice_display::{with_context, NiceDisplay};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+
+/// What the reports are stored under in `log_event`.
+pub const REPORT_EVENT_NAME: &str = "nightly_maintenance";
+
+/// Well past the 15 minutes after which a running job stops holding its
+/// lock key, so anything this old is not coming back.
+const STALE_JOB_AGE_HOURS: i64 = 1;
+const LOG_RETENTION_DAYS: i64 = 90;
+/// Each reflection and refresh is a completion, so a busy day is capped.
+const MAX_REFLECTIONS: usize = 50;
+const MAX_SNAPSHOT_REFRESHES: usize = 20;
+
+const REFLECTION_SITUATION: &str =
+    "The day is over. You are alone, looking back on what happened today before you sleep.";
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PrunedRows {
+    pub llm_calls: u64,
+    pub log_events: u64,
+}
+
+#[derive(Debug, Clone)]
+pub struct RecentMemories {
+    pub person_uuid: PersonUuid,
+    pub memories: Vec<Memory>,
+}
+
+/// What one night's maintenance did, step by step.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MaintenanceReport {
+    pub started_at: DateTime<Utc>,
+    pub finished_at: DateTime<Utc>,
+    pub steps: Vec<StepReport>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StepReport {
+    pub step: String,
+    pub summary: String,
+    pub failed: bool,
+}
+
+impl MaintenanceReport {
+    pub fn failed_step_count(&self) -> usize {
+        self.steps.iter().filter(|step| step.failed).count()
+    }
+
+    pub fn to_lines(&self) -> Vec<String> {
+        self.steps
+            .iter()
+            .map(|step| {
+                let status = if step.failed { "failed" } else { "ok" };
+                format!("{} ({}): {}", step.step, status, step.summary)
+            })
+            .collect()
+    }
+}
+
+pub enum Error {
+    SerializeReport(serde_json::Error),
+    SaveReport(String),
+}
+
+impl NiceDisplay for Error {
+    fn message(&self) -> String {
+        match self {
+            Error::SerializeReport(err) => {
+                with_context("Could not serialize the maintenance report", err)
+            }
+            Error::SaveReport(details) => {
+                with_context("Could not save the maintenance report", details)
+            }
+        }
+    }
+}
+
+/// Runs every step, one after another, and saves a report of how each
+/// went to the log events. A step failing does not stop the ones after it,
+/// except that old llm calls are only pruned once they have been rolled up.
+pub async fn run<
+    W: MaintenanceCapability
+        + ClockCapability
+        + LogEventCapability
+        + PersonCapability
+        + PersonIdentityCapability
+        + StateOfMindCapability
+        + ReflectionCapability
+        + MemoryCapability
+        + MotivationCapability,
+>(
+    worker: &W,
+) -> Result<MaintenanceReport, Error> {
+    let started_at = worker.now();
+    let mut steps = Vec::new();
+
+    steps.push(step_report(
+        "consolidate memories",
+        worker
+            .remove_duplicate_memories()
+            .await
+            .map(|count| format!("removed {} duplicate memories", count)),
+    ));
+
+    steps.push(step_report(
+        "reflect",
+        reflect(worker, started_at - Duration::days(1)).await,
+    ));
+
+    let roll_up = worker
+        .roll_up_llm_usage(started_at)
+        .await
+        .map(|count| format!("wrote {} daily usage rows", count));
+    let rolled_up = roll_up.is_ok();
+    steps.push(step_report("roll up usage", roll_up));
+
+    let prune = if rolled_up {
+        worker
+            .prune_logs(started_at - Duration::days(LOG_RETENTION_DAYS))
+            .await
+            .map(|pruned| {
+                format!(
+                    "deleted {} llm calls and {} log events older than {} days",
+                    pruned.llm_calls, pruned.log_events, LOG_RETENTION_DAYS
+                )
+            })
+    } else {
+        Err("Skipped, since llm usage could not be rolled up first".to_string())
+    };
+    steps.push(step_report("prune", prune));
+
+    steps.push(step_report(
+        "refresh scene snapshots",
+        refresh_snapshots(worker).await,
+    ));
+
+    steps.push(step_report(
+        "reap stale jobs",
+        worker
+            .reap_stale_jobs(started_at - Duration::hours(STALE_JOB_AGE_HOURS))
+            .await
+            .map(|count| format!("failed {} jobs that never finished", count)),
+    ));
+
+    let report = MaintenanceReport {
+        started_at,
+        finished_at: worker.now(),
+        steps,
+    };
+
+    let data = serde_json::to_value(&report).map_err(Error::SerializeReport)?;
+    worker
+        .log_event(REPORT_EVENT_NAME.to_string(), Some(data))
+        .await
+        .map_err(Error::SaveReport)?;
+
+    Ok(report)
+}
+
+fn step_report(step: &str, result: Result<String, String>) -> StepReport {
+    match result {
+        Ok(summary) => StepReport {
+            step: step.to_string(),
+            summary,
+            failed: false,
+        },
+        Err(details) => StepReport {
+            step: step.to_string(),
+            summary: details,
+            failed: true,
+        },
+    }
+}
+
+/// Has everyone who made memories today look back on them, the same way
+/// they sometimes do after reacting.
+async fn reflect<
+    W: MaintenanceCapability
+        + LogEventCapability
+        + PersonCapability
+        + PersonIdentityCapability
+        + StateOfMindCapability
+        + ReflectionCapability
+        + MemoryCapability
+        + MotivationCapability,
+>(
+    worker: &W,
+    since: DateTime<Utc>,
+) -> Result<String, String> {
+    let recent = worker.get_recent_memories(since).await?;
+
+    let mut reflected = 0;
+    let mut skipped = 0;
+    let mut errors = Vec::new();
+
+    for recent_memories in recent.into_iter().take(MAX_REFLECTIONS) {
+        match reflect_person(worker, recent_memories).await {
+            Ok(true) => reflected += 1,
+            Ok(false) => skipped += 1,
+            Err(err) => errors.push(err),
+        }
+    }
+
+    let summary = format!(
+        "{} persons reflected on their day, {} had no identity or state of mind",
+        reflected, skipped
+    );
+
+    match errors.first() {
+        None => Ok(summary),
+        Some(first) => Err(format!(
+            "{}, {} failed. The first failure: {}",
+            summary,
+            errors.len(),
+            first
+        )),
+    }
+}
+
+async fn reflect_person<
+    W: LogEventCapability
+        + PersonCapability
+        + PersonIdentityCapability
+        + StateOfMindCapability
+        + ReflectionCapability
+        + MemoryCapability
+        + MotivationCapability,
+>(
+    worker: &W,
+    recent_memories: RecentMemories,
+) -> Result<bool, String> {
+    let person_uuid = recent_memories.person_uuid;
+
+    let state_of_mind = match worker.get_latest_state_of_mind(&person_uuid).await? {
+        Some(state_of_mind) => state_of_mind.content,
+        None => return Ok(false),
+    };
+
+    let person_identity = match worker.get_person_identity_summary(&person_uuid).await? {
+        Some(person_identity) => person_identity,
+        None => return Ok(false),
+    };
+
+    let reflection_input = ReflectionInput {
+        person_name: worker.get_persons_name(person_uuid.clone()).await?,
+        memories: recent_memories.memories,
+        person_identity,
+        state_of_mind,
+    };
+
+    let changes = worker
+        .get_reflection_changes(
+            reflection_input.memories.clone(),
+            person_uuid.clone(),
+            reflection_input.person_identity.clone(),
+            reflection_input.state_of_mind.clone(),
+            REFLECTION_SITUATION.to_string(),
+        )
+        .await?;
+
+    apply_reflection_changes(worker, &person_uuid, &reflection_input, changes)
+        .await
+        .map_err(|err| err.message())?;
+
+    Ok(true)
+}
+
+async fn refresh_snapshots<W: MaintenanceCapability>(worker: &W) -> Result<String, String> {
+    let scene_uuids = worker.get_scenes_changed_since_snapshot().await?;
+    let left_for_tomorrow = scene_uuids.len().saturating_sub(MAX_SNAPSHOT_REFRESHES);
+
+    let mut refreshed = 0;
+    for scene_uuid in scene_uuids.iter().take(MAX_SNAPSHOT_REFRESHES) {
+        worker.refresh_scene_snapshot(scene_uuid).await?;
+        refreshed += 1;
+    }
+
+    Ok(format!(
+        "refreshed {} scene descriptions, {} left for tomorrow",
+        refreshed, left_for_tomorrow
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_reports_survive_the_log_event_round_trip() {
+        let started_at = Utc.with_ymd_and_hms(2026, 3, 18, 4, 0, 0).unwrap();
+        let report = MaintenanceReport {
+            started_at,
+            finished_at: started_at + Duration::minutes(3),
+            steps: vec![
+                step_report("roll up usage", Ok("wrote 2 daily usage rows".to_string())),
+                step_report("prune", Err("Skipped".to_string())),
+            ],
+        };
+
+        let data = serde_json::to_value(&report).unwrap();
+        let read_back: MaintenanceReport = serde_json::from_value(data).unwrap();
+
+        assert_eq!(read_back, report);
+        assert_eq!(report.failed_step_count(), 1);
+        assert_eq!(
+            report.to_lines(),
+            vec![
+                "roll up usage (ok): wrote 2 daily usage rows".to_string(),
+                "prune (failed): Skipped".to_string(),
+            ]
+        );
+    }
+}
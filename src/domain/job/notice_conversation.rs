@@ -0,0 +1,104 @@
+use crate::capability::event::EventCapability;
+use crate::capability::expected_reply::ExpectedReplyCapability;
+use crate::capability::item::ItemCapability;
+use crate::capability::job::JobCapability;
+use crate::capability::log_event::LogEventCapability;
+use crate::capability::logging::LogCapability;
+use crate::capability::memory::MemoryCapability;
+use crate::capability::message::MessageCapability;
+use crate::capability::moderation::ModerationCapability;
+use crate::capability::motivation::MotivationCapability;
+use crate::capability::person::PersonCapability;
+use crate::capability::person_identity::PersonIdentityCapability;
+use crate::capability::person_task::PersonTaskCapability;
+use crate::capability::reaction::ReactionCapability;
+use crate::capability::reaction_history::ReactionHistoryCapability;
+use crate::capability::reflection::ReflectionCapability;
+use crate::capability::scene::SceneCapability;
+use crate::capability::scene_invitation::SceneInvitationCapability;
+use crate::capability::state_of_mind::StateOfMindCapability;
+use crate::domain::job::process_reaction_common::{self, SceneReactionTrigger};
+use crate::domain::person_uuid::PersonUuid;
+use crate::domain::random_seed::RandomSeed;
+use crate::domain::scene_uuid::SceneUuid;
+use crate::nice_display::{with_context, NiceDisplay};
+use serde::{Deserialize, Serialize};
+
+/// Gives a person who has gone quiet in a busy scene a chance to join back
+/// in. Enqueued by `wake idle persons`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NoticeConversationJob {
+    pub person_uuid: PersonUuid,
+    pub scene_uuid: SceneUuid,
+}
+
+pub enum Error {
+    PersonScene(String),
+    Reaction(process_reaction_common::Error),
+}
+
+impl NiceDisplay for Error {
+    fn message(&self) -> String {
+        match self {
+            Error::PersonScene(details) => {
+                with_context("Could not get the person's current scene", details)
+            }
+            Error::Reaction(err) => err.message(),
+        }
+    }
+}
+
+impl NoticeConversationJob {
+    pub async fn run<
+        W: SceneCapability
+            + ReactionCapability
+            + MemoryCapability
+            + MessageCapability
+            + ModerationCapability
+            + PersonCapability
+            + EventCapability
+            + StateOfMindCapability
+            + PersonIdentityCapability
+            + PersonTaskCapability
+            + ReflectionCapability
+            + LogCapability
+            + LogEventCapability
+            + MotivationCapability
+            + ReactionHistoryCapability
+            + ExpectedReplyCapability
+            + ItemCapability
+            + SceneInvitationCapability
+            + JobCapability
+            + Sync,
+    >(
+        self,
+        worker: &W,
+        random_seed: RandomSeed,
+        current_active_ms: i64,
+    ) -> Result<(), Error> {
+        // They may have moved on between the scan and this job running
+        let current_scene_uuid = worker
+            .get_persons_current_scene_uuid(&self.person_uuid)
+            .await
+            .map_err(Error::PersonScene)?;
+
+        let still_in_scene = current_scene_uuid
+            .map(|scene_uuid| scene_uuid.to_uuid() == self.scene_uuid.to_uuid())
+            .unwrap_or(false);
+
+        if !still_in_scene {
+            return Ok(());
+        }
+
+        process_reaction_common::run_scene_reaction(
+            worker,
+            &self.person_uuid,
+            &self.scene_uuid,
+            SceneReactionTrigger::ConversationContinuing,
+            random_seed,
+            current_active_ms,
+        )
+        .await
+        .map_err(Error::Reaction)
+    }
+}
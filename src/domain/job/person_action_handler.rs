@@ -1,22 +1,40 @@
+use crate::capability::expected_reply::{ExpectedReplyCapability, NewExpectedReply};
+use crate::capability::item::ItemCapability;
 use crate::capability::job::JobCapability;
 use crate::capability::logging::LogCapability;
 use crate::capability::message::MessageCapability;
+use crate::capability::moderation::ModerationCapability;
 use crate::capability::person::PersonCapability;
 use crate::capability::reaction_history::ReactionHistoryCapability;
 use crate::capability::scene::SceneCapability;
+use crate::capability::scene_invitation::{NewSceneInvitation, SceneInvitationCapability};
 use crate::domain::actor_uuid::ActorUuid;
+use crate::domain::custom_action::CustomAction;
+use crate::domain::external_tool;
+use crate::domain::item::Item;
+use crate::domain::job::check_expected_reply::CheckExpectedReplyJob;
 use crate::domain::job::person_hibernating::PersonHibernatingJob;
 use crate::domain::job::person_waiting::PersonWaitingJob;
 use crate::domain::job::process_person_join::ProcessPersonJoinJob;
 use crate::domain::job::process_scene_gaze::ProcessSceneGazeJob;
-use crate::domain::job::send_message_to_scene::send_scene_message_and_enqueue_recipients;
+use crate::domain::job::react_to_scene_invitation::ReactToSceneInvitationJob;
+use crate::domain::job::run_custom_action::RunCustomActionJob;
+use crate::domain::job::send_message_to_scene::{
+    send_scene_message_and_enqueue_recipients, send_scene_message_to_audience, SceneMessageOutcome,
+};
+use crate::domain::job::use_tool::UseToolJob;
 use crate::domain::job::JobKind;
 use crate::domain::logger::Level;
-use crate::domain::message::MessageSender;
+use crate::domain::message::{Message, MessageSender};
+use crate::domain::message_audience::MessageAudience;
+use crate::domain::message_quote::{find_quoted_message, MessageQuote};
+use crate::domain::message_uuid::MessageUuid;
+use crate::domain::person_name::PersonName;
 use crate::domain::person_uuid::PersonUuid;
 use crate::domain::random_seed::RandomSeed;
 use crate::domain::scene_uuid::SceneUuid;
-use crate::nice_display::NiceDisplay;
+use crate::domain::utterance::REAL_WORLD_USER_NAME;
+use crate::nice_display::{with_context, NiceDisplay};
 use crate::person_actions::PersonAction;
 
 pub enum ActionHandleError {
@@ -32,44 +50,63 @@ pub enum ActionHandleError {
         details: String,
     },
     MoveToScene(String),
+    Ask(String),
+    GiveItem(String),
+    Invite(String),
+    AnswerInvitations(String),
+    UseTool(String),
+    Custom(String),
 }
 
 impl NiceDisplay for ActionHandleError {
     fn message(&self) -> String {
         match self {
-            ActionHandleError::Wait(details) => {
-                format!("Person could not wait: {}", details)
-            }
+            ActionHandleError::Wait(details) => with_context("Person could not wait", details),
             ActionHandleError::Hibernate(details) => {
-                format!("Person could not hibernate: {}", details)
+                with_context("Person could not hibernate", details)
             }
             ActionHandleError::HibernationState(details) => {
-                format!("Could not set hibernation state: {}", details)
+                with_context("Could not set hibernation state", details)
             }
             ActionHandleError::ReactionLog(details) => {
-                format!("Could not record reaction: {}", details)
+                with_context("Could not record reaction", details)
             }
             ActionHandleError::PersonName(details) => {
-                format!("Could not get person's name: {}", details)
+                with_context("Could not get person's name", details)
             }
             ActionHandleError::SceneMissing(details) => {
-                format!("Could not get person's scene: {}", details)
+                with_context("Could not get person's scene", details)
             }
             ActionHandleError::GazeInScene(details) => {
-                format!("Person could not gaze in scene: {}", details)
+                with_context("Person could not gaze in scene", details)
             }
             ActionHandleError::Say {
                 scene_uuid,
                 details,
-            } => {
-                format!(
-                    "Person could not say in scene {}: {}",
-                    scene_uuid.to_uuid(),
-                    details
-                )
-            }
+            } => with_context(
+                format!("Person could not say in scene {}", scene_uuid.to_uuid()),
+                details,
+            ),
             ActionHandleError::MoveToScene(details) => {
-                format!("Person could not move to scene: {}", details)
+                with_context("Person could not move to scene", details)
+            }
+            ActionHandleError::Ask(details) => {
+                with_context("Person could not ask a question", details)
+            }
+            ActionHandleError::GiveItem(details) => {
+                with_context("Person could not give an item", details)
+            }
+            ActionHandleError::Invite(details) => {
+                with_context("Person could not invite someone", details)
+            }
+            ActionHandleError::AnswerInvitations(details) => {
+                with_context("Could not answer the person's invitations", details)
+            }
+            ActionHandleError::UseTool(details) => {
+                with_context("Person could not use a tool", details)
+            }
+            ActionHandleError::Custom(details) => {
+                with_context("Person could not take a custom action", details)
             }
         }
     }
@@ -78,13 +115,53 @@ impl NiceDisplay for ActionHandleError {
 const IDLE_DURATION_MS: i64 = 4 * 60 * 1000;
 const POST_MESSAGE_WAIT_MS: u64 = 0;
 const POST_MOVE_WAIT_MS: u64 = 30 * 1000;
+/// How many recent scene messages a quote is looked for in.
+const QUOTE_SEARCH_LIMIT: i64 = 30;
 
 pub async fn handle_person_action<
     W: SceneCapability
         + JobCapability
         + PersonCapability
         + MessageCapability
+        + ModerationCapability
+        + ReactionHistoryCapability
+        + ExpectedReplyCapability
+        + ItemCapability
+        + SceneInvitationCapability
+        + LogCapability
+        + Sync,
+>(
+    worker: &W,
+    action: &PersonAction,
+    person_uuid: &PersonUuid,
+    random_seed: RandomSeed,
+    current_active_ms: i64,
+) -> Result<(), ActionHandleError> {
+    take_action(worker, action, person_uuid, random_seed, current_active_ms).await?;
+
+    // Whatever the person just did answers the invitations they had. Only
+    // being in an invitation's scene afterwards accepts it
+    let current_scene_uuid = worker
+        .get_persons_current_scene_uuid(person_uuid)
+        .await
+        .map_err(ActionHandleError::SceneMissing)?;
+
+    worker
+        .respond_to_scene_invitations(person_uuid, current_scene_uuid.as_ref())
+        .await
+        .map_err(ActionHandleError::AnswerInvitations)
+}
+
+async fn take_action<
+    W: SceneCapability
+        + JobCapability
+        + PersonCapability
+        + MessageCapability
+        + ModerationCapability
         + ReactionHistoryCapability
+        + ExpectedReplyCapability
+        + ItemCapability
+        + SceneInvitationCapability
         + LogCapability
         + Sync,
 >(
@@ -127,6 +204,9 @@ pub async fn handle_person_action<
         PersonAction::SayInScene {
             comment,
             destination_scene_name,
+            addressed_to,
+            whisper,
+            quote,
         } => {
             let sender = MessageSender::AiPerson(person_uuid.clone());
             let person_name = worker
@@ -142,20 +222,54 @@ pub async fn handle_person_action<
                     ActionHandleError::SceneMissing("Person is not in any scene".to_string())
                 })?;
 
-            send_scene_message_and_enqueue_recipients(
+            let audience = resolve_audience(
+                worker,
+                &scene_uuid,
+                person_uuid,
+                person_name.as_str(),
+                addressed_to,
+                *whisper,
+            )
+            .await?;
+
+            let outcome = send_scene_message_to_audience(
                 worker,
                 sender,
                 scene_uuid.clone(),
                 comment.clone(),
                 random_seed.clone(),
+                audience,
             )
             .await
             .map_err(|err| ActionHandleError::Say {
-                scene_uuid,
+                scene_uuid: scene_uuid.clone(),
                 details: err.to_nice_error().to_string(),
             })?;
 
             let person_label = person_name.to_string();
+
+            let message_uuid = match outcome {
+                SceneMessageOutcome::Sent { message_uuid } => message_uuid,
+                SceneMessageOutcome::Blocked { categories } => {
+                    return handle_blocked_message(
+                        worker,
+                        person_uuid,
+                        &person_label,
+                        &categories,
+                        comment,
+                        "say_in_scene_blocked",
+                        current_active_ms,
+                    )
+                    .await;
+                }
+            };
+
+            if let Some(quote) = quote {
+                if !addressed_to.is_empty() {
+                    attach_quote(worker, &scene_uuid, &message_uuid, quote).await;
+                }
+            }
+
             worker.log(
                 Level::Info,
                 format!("AI person {} said in scene: {}", person_label, comment).as_str(),
@@ -184,6 +298,134 @@ pub async fn handle_person_action<
 
             Ok(())
         }
+        PersonAction::Ask {
+            recipient_name,
+            question,
+            reply_window_ms,
+            quote,
+        } => {
+            let person_name = worker
+                .get_persons_name(person_uuid.clone())
+                .await
+                .map_err(ActionHandleError::PersonName)?;
+
+            let scene_uuid = worker
+                .get_persons_current_scene_uuid(person_uuid)
+                .await
+                .map_err(ActionHandleError::SceneMissing)?
+                .ok_or_else(|| {
+                    ActionHandleError::SceneMissing("Person is not in any scene".to_string())
+                })?;
+
+            let participants = worker
+                .get_scene_current_participants(&scene_uuid)
+                .await
+                .map_err(ActionHandleError::Ask)?;
+
+            let maybe_recipient_uuid =
+                participants
+                    .into_iter()
+                    .find_map(|participant| match participant.actor_uuid {
+                        ActorUuid::AiPerson(participant_uuid)
+                            if participant.person_name.as_str() == recipient_name.as_str()
+                                && participant_uuid.to_uuid() != person_uuid.to_uuid() =>
+                        {
+                            Some(participant_uuid)
+                        }
+                        _ => None,
+                    });
+
+            let outcome = send_scene_message_and_enqueue_recipients(
+                worker,
+                MessageSender::AiPerson(person_uuid.clone()),
+                scene_uuid.clone(),
+                question.clone(),
+                random_seed.clone(),
+            )
+            .await
+            .map_err(|err| ActionHandleError::Say {
+                scene_uuid: scene_uuid.clone(),
+                details: err.to_nice_error().to_string(),
+            })?;
+
+            let person_label = person_name.to_string();
+
+            let message_uuid = match outcome {
+                SceneMessageOutcome::Sent { message_uuid } => message_uuid,
+                SceneMessageOutcome::Blocked { categories } => {
+                    return handle_blocked_message(
+                        worker,
+                        person_uuid,
+                        &person_label,
+                        &categories,
+                        question,
+                        "ask_blocked",
+                        current_active_ms,
+                    )
+                    .await;
+                }
+            };
+
+            if let Some(quote) = quote {
+                attach_quote(worker, &scene_uuid, &message_uuid, quote).await;
+            }
+
+            worker.log(
+                Level::Info,
+                format!(
+                    "AI person {} asked {}: {}",
+                    person_label, recipient_name, question
+                )
+                .as_str(),
+            );
+
+            match maybe_recipient_uuid {
+                Some(recipient_person_uuid) => {
+                    let reply_window_ms = i64::try_from(*reply_window_ms).unwrap_or(i64::MAX);
+
+                    worker
+                        .expect_reply(NewExpectedReply {
+                            message_uuid: message_uuid.clone(),
+                            asker_person_uuid: person_uuid.clone(),
+                            recipient_person_uuid: recipient_person_uuid.clone(),
+                            reply_window_ms,
+                        })
+                        .await
+                        .map_err(ActionHandleError::Ask)?;
+
+                    let check_job = CheckExpectedReplyJob {
+                        message_uuid,
+                        asker_person_uuid: person_uuid.clone(),
+                        recipient_person_uuid,
+                        scene_uuid,
+                        question: question.clone(),
+                        run_at_active_ms: current_active_ms.saturating_add(reply_window_ms),
+                    };
+
+                    worker
+                        .unshift_job(JobKind::CheckExpectedReply(check_job))
+                        .await
+                        .map_err(ActionHandleError::Ask)?;
+                }
+                None => {
+                    worker.log(
+                        Level::Warning,
+                        format!(
+                            "AI person {} asked {}, who is not an ai person in the scene; not waiting for a reply",
+                            person_label, recipient_name
+                        )
+                        .as_str(),
+                    );
+                }
+            }
+
+            worker
+                .record_reaction(person_uuid, "ask")
+                .await
+                .map_err(ActionHandleError::ReactionLog)?;
+
+            enqueue_wait(worker, person_uuid, POST_MESSAGE_WAIT_MS, current_active_ms).await
+        }
         PersonAction::MoveToScene { scene_name } => {
             move_person_to_scene(worker, person_uuid, scene_name, current_active_ms).await?;
 
@@ -194,7 +436,283 @@ pub async fn handle_person_action<
 
             Ok(())
         }
+        PersonAction::GiveItem {
+            item_name,
+            recipient_name,
+        } => {
+            give_item(worker, person_uuid, item_name, recipient_name).await?;
+
+            worker
+                .record_reaction(person_uuid, "give_item")
+                .await
+                .map_err(ActionHandleError::ReactionLog)?;
+
+            enqueue_wait(worker, person_uuid, POST_MESSAGE_WAIT_MS, current_active_ms).await
+        }
+        PersonAction::Invite {
+            recipient_name,
+            scene_name,
+        } => {
+            invite(worker, person_uuid, recipient_name, scene_name.as_deref()).await?;
+
+            worker
+                .record_reaction(person_uuid, "invite")
+                .await
+                .map_err(ActionHandleError::ReactionLog)?;
+
+            enqueue_wait(worker, person_uuid, POST_MESSAGE_WAIT_MS, current_active_ms).await
+        }
+        PersonAction::UseTool {
+            tool_name,
+            arguments,
+        } => {
+            let scene_uuid = worker
+                .get_persons_current_scene_uuid(person_uuid)
+                .await
+                .map_err(ActionHandleError::SceneMissing)?;
+
+            worker
+                .unshift_job(JobKind::UseTool(UseToolJob {
+                    tool_name: tool_name.clone(),
+                    person_uuid: person_uuid.clone(),
+                    scene_uuid,
+                    arguments: arguments.clone(),
+                }))
+                .await
+                .map_err(ActionHandleError::UseTool)?;
+
+            worker
+                .record_reaction(person_uuid, &external_tool::reaction_kind())
+                .await
+                .map_err(ActionHandleError::ReactionLog)?;
+
+            enqueue_wait(worker, person_uuid, POST_MESSAGE_WAIT_MS, current_active_ms).await
+        }
+        PersonAction::Custom { name, arguments } => {
+            let scene_uuid = worker
+                .get_persons_current_scene_uuid(person_uuid)
+                .await
+                .map_err(ActionHandleError::SceneMissing)?;
+
+            worker
+                .unshift_job(JobKind::RunCustomAction(RunCustomActionJob {
+                    action_name: name.clone(),
+                    person_uuid: person_uuid.clone(),
+                    scene_uuid,
+                    arguments: arguments.clone(),
+                }))
+                .await
+                .map_err(ActionHandleError::Custom)?;
+
+            worker
+                .record_reaction(person_uuid, &CustomAction::reaction_kind(name))
+                .await
+                .map_err(ActionHandleError::ReactionLog)?;
+
+            enqueue_wait(worker, person_uuid, POST_MESSAGE_WAIT_MS, current_active_ms).await
+        }
+    }
+}
+
+/// Hands a carried item to an ai person in the same scene. Giving something
+/// the person does not have, or to someone who is not here, is logged and
+/// skipped like a blocked message, since the model only imagined it.
+async fn give_item<W: SceneCapability + PersonCapability + ItemCapability + LogCapability>(
+    worker: &W,
+    person_uuid: &PersonUuid,
+    item_name: &str,
+    recipient_name: &str,
+) -> Result<(), ActionHandleError> {
+    let person_name = worker
+        .get_persons_name(person_uuid.clone())
+        .await
+        .map_err(ActionHandleError::PersonName)?;
+
+    let scene_uuid = worker
+        .get_persons_current_scene_uuid(person_uuid)
+        .await
+        .map_err(ActionHandleError::SceneMissing)?
+        .ok_or_else(|| ActionHandleError::SceneMissing("Person is not in any scene".to_string()))?;
+
+    let participants = worker
+        .get_scene_current_participants(&scene_uuid)
+        .await
+        .map_err(ActionHandleError::GiveItem)?;
+
+    let maybe_recipient_uuid =
+        participants
+            .into_iter()
+            .find_map(|participant| match participant.actor_uuid {
+                ActorUuid::AiPerson(participant_uuid)
+                    if participant.person_name.as_str() == recipient_name
+                        && participant_uuid.to_uuid() != person_uuid.to_uuid() =>
+                {
+                    Some(participant_uuid)
+                }
+                _ => None,
+            });
+
+    let carried_items = worker
+        .get_items_carried_by(person_uuid)
+        .await
+        .map_err(ActionHandleError::GiveItem)?;
+
+    let (item, recipient_uuid) = match (
+        Item::find_by_name(&carried_items, item_name),
+        maybe_recipient_uuid,
+    ) {
+        (Some(item), Some(recipient_uuid)) => (item, recipient_uuid),
+        (None, _) => {
+            worker.log(
+                Level::Warning,
+                format!(
+                    "AI person {} tried to give {}, which they are not carrying",
+                    person_name, item_name
+                )
+                .as_str(),
+            );
+            return Ok(());
+        }
+        (Some(_), None) => {
+            worker.log(
+                Level::Warning,
+                format!(
+                    "AI person {} tried to give {} to {}, who is not an ai person in the scene",
+                    person_name, item_name, recipient_name
+                )
+                .as_str(),
+            );
+            return Ok(());
+        }
+    };
+
+    worker
+        .give_item(&item.uuid, person_uuid, &recipient_uuid)
+        .await
+        .map_err(ActionHandleError::GiveItem)?;
+
+    worker.log(
+        Level::Info,
+        format!(
+            "AI person {} gave {} to {}",
+            person_name, item.name, recipient_name
+        )
+        .as_str(),
+    );
+
+    Ok(())
+}
+
+/// Invites someone to a scene, the person's own unless they name another,
+/// and has the invitee react to it. Like giving an item, inviting someone
+/// who does not exist, or who is already there, is logged and skipped.
+async fn invite<
+    W: SceneCapability + PersonCapability + JobCapability + SceneInvitationCapability + LogCapability,
+>(
+    worker: &W,
+    person_uuid: &PersonUuid,
+    recipient_name: &str,
+    scene_name: Option<&str>,
+) -> Result<(), ActionHandleError> {
+    let person_name = worker
+        .get_persons_name(person_uuid.clone())
+        .await
+        .map_err(ActionHandleError::PersonName)?;
+
+    let maybe_scene_uuid = match scene_name {
+        Some(scene_name) => worker
+            .get_scene_from_name(scene_name.to_string())
+            .await
+            .map_err(ActionHandleError::Invite)?
+            .map(|scene| scene.uuid),
+        None => worker
+            .get_persons_current_scene_uuid(person_uuid)
+            .await
+            .map_err(ActionHandleError::SceneMissing)?,
+    };
+
+    let scene_uuid = match maybe_scene_uuid {
+        Some(scene_uuid) => scene_uuid,
+        None => {
+            worker.log(
+                Level::Warning,
+                format!(
+                    "AI person {} tried to invite {} to {}, which is not a scene",
+                    person_name,
+                    recipient_name,
+                    scene_name.unwrap_or("their scene")
+                )
+                .as_str(),
+            );
+            return Ok(());
+        }
+    };
+
+    let invitee_person_uuid = match worker
+        .get_person_uuid_by_name(PersonName::from_string(recipient_name.to_string()))
+        .await
+    {
+        Ok(invitee_person_uuid) if invitee_person_uuid != *person_uuid => invitee_person_uuid,
+        Ok(_) => {
+            worker.log(
+                Level::Warning,
+                format!("AI person {} tried to invite themselves", person_name).as_str(),
+            );
+            return Ok(());
+        }
+        Err(details) => {
+            worker.log(
+                Level::Warning,
+                format!(
+                    "AI person {} tried to invite {}, who could not be found: {}",
+                    person_name, recipient_name, details
+                )
+                .as_str(),
+            );
+            return Ok(());
+        }
+    };
+
+    let invitee_scene_uuid = worker
+        .get_persons_current_scene_uuid(&invitee_person_uuid)
+        .await
+        .map_err(ActionHandleError::SceneMissing)?;
+
+    if invitee_scene_uuid.as_ref() == Some(&scene_uuid) {
+        worker.log(
+            Level::Warning,
+            format!(
+                "AI person {} tried to invite {} to the scene they are already in",
+                person_name, recipient_name
+            )
+            .as_str(),
+        );
+        return Ok(());
     }
+
+    let invitation_uuid = worker
+        .create_scene_invitation(NewSceneInvitation {
+            scene_uuid,
+            inviter_person_uuid: person_uuid.clone(),
+            invitee_person_uuid: invitee_person_uuid.clone(),
+        })
+        .await
+        .map_err(ActionHandleError::Invite)?;
+
+    worker
+        .unshift_job(JobKind::ReactToSceneInvitation(ReactToSceneInvitationJob {
+            person_uuid: invitee_person_uuid,
+            invitation_uuid,
+        }))
+        .await
+        .map_err(ActionHandleError::Invite)?;
+
+    worker.log(
+        Level::Info,
+        format!("AI person {} invited {}", person_name, recipient_name).as_str(),
+    );
+
+    Ok(())
 }
 
 async fn enqueue_wait<W: JobCapability>(
@@ -204,8 +722,12 @@ async fn enqueue_wait<W: JobCapability>(
     current_active_ms: i64,
 ) -> Result<(), ActionHandleError> {
     let duration_i64: i64 = duration_ms.min(i64::MAX as u64) as i64;
-    let person_waiting_job =
-        PersonWaitingJob::new(person_uuid.clone(), duration_i64, current_active_ms);
+    let person_waiting_job = PersonWaitingJob::new(
+        person_uuid.clone(),
+        duration_i64,
+        current_active_ms,
+        worker.now(),
+    );
     let wait_job = JobKind::PersonWaiting(person_waiting_job);
     worker
         .unshift_job(wait_job)
@@ -214,11 +736,167 @@ async fn enqueue_wait<W: JobCapability>(
     Ok(())
 }
 
+/// Matches the names a comment is addressed to against the other ai people in
+/// the scene. Names that match nobody are dropped, so a whisper to someone
+/// who is not here reaches no one.
+async fn resolve_audience<W: SceneCapability + LogCapability>(
+    worker: &W,
+    scene_uuid: &SceneUuid,
+    person_uuid: &PersonUuid,
+    person_label: &str,
+    addressed_to: &[String],
+    whisper: bool,
+) -> Result<MessageAudience, ActionHandleError> {
+    if addressed_to.is_empty() {
+        return Ok(MessageAudience::Everyone);
+    }
+
+    let participants = worker
+        .get_scene_current_participants(scene_uuid)
+        .await
+        .map_err(|details| ActionHandleError::Say {
+            scene_uuid: scene_uuid.clone(),
+            details,
+        })?;
+
+    let mut addressed = Vec::new();
+    let mut unknown_names = Vec::new();
+
+    for name in addressed_to {
+        let maybe_person_uuid =
+            participants
+                .iter()
+                .find_map(|participant| match &participant.actor_uuid {
+                    ActorUuid::AiPerson(participant_uuid)
+                        if participant.person_name.as_str() == name.as_str()
+                            && participant_uuid.to_uuid() != person_uuid.to_uuid() =>
+                    {
+                        Some(participant_uuid.clone())
+                    }
+                    _ => None,
+                });
+
+        match maybe_person_uuid {
+            Some(addressed_person_uuid) => addressed.push(addressed_person_uuid),
+            None => unknown_names.push(name.as_str()),
+        }
+    }
+
+    if !unknown_names.is_empty() {
+        worker.log(
+            Level::Warning,
+            format!(
+                "AI person {} addressed people who are not ai people in the scene: {}",
+                person_label,
+                unknown_names.join(", ")
+            )
+            .as_str(),
+        );
+    }
+
+    if whisper {
+        Ok(MessageAudience::Whisper(addressed))
+    } else {
+        Ok(MessageAudience::SideConversation(addressed))
+    }
+}
+
+/// Finds the scene message the quote came from and keeps a snippet of it with
+/// the new message. A quote that matches nothing, or fails to save, is logged
+/// and dropped rather than failing the action that was already sent.
+async fn attach_quote<W: MessageCapability + PersonCapability + LogCapability>(
+    worker: &W,
+    scene_uuid: &SceneUuid,
+    message_uuid: &MessageUuid,
+    quote: &str,
+) {
+    if let Err(err) = try_attach_quote(worker, scene_uuid, message_uuid, quote).await {
+        worker.log(
+            Level::Warning,
+            format!(
+                "Could not attach quote to message {}: {}",
+                message_uuid.to_uuid(),
+                err
+            )
+            .as_str(),
+        );
+    }
+}
+
+async fn try_attach_quote<W: MessageCapability + PersonCapability>(
+    worker: &W,
+    scene_uuid: &SceneUuid,
+    message_uuid: &MessageUuid,
+    quote: &str,
+) -> Result<(), String> {
+    let earlier_messages: Vec<Message> = worker
+        .get_messages_in_scene_page(scene_uuid, QUOTE_SEARCH_LIMIT, None)
+        .await?
+        .into_iter()
+        .filter(|message| &message.uuid != message_uuid)
+        .collect();
+
+    let quoted_message = match find_quoted_message(&earlier_messages, quote) {
+        Some(message) => message,
+        None => return Err(format!("no recent scene message contains \"{}\"", quote)),
+    };
+
+    let speaker_name = match &quoted_message.sender {
+        MessageSender::AiPerson(speaker_uuid) => worker
+            .get_persons_name(speaker_uuid.clone())
+            .await?
+            .to_string(),
+        MessageSender::RealWorldUser => REAL_WORLD_USER_NAME.to_string(),
+    };
+
+    worker
+        .set_message_quote(
+            message_uuid,
+            &MessageQuote::new(quoted_message, speaker_name),
+        )
+        .await
+}
+
+async fn handle_blocked_message<W: JobCapability + ReactionHistoryCapability + LogCapability>(
+    worker: &W,
+    person_uuid: &PersonUuid,
+    person_label: &str,
+    categories: &[String],
+    text: &str,
+    reaction_kind: &str,
+    current_active_ms: i64,
+) -> Result<(), ActionHandleError> {
+    worker.log(
+        Level::Warning,
+        format!(
+            "Moderation blocked AI person {} from saying ({}): {}",
+            person_label,
+            categories.join(", "),
+            text
+        )
+        .as_str(),
+    );
+
+    worker
+        .record_reaction(person_uuid, reaction_kind)
+        .await
+        .map_err(ActionHandleError::ReactionLog)?;
+
+    enqueue_wait(
+        worker,
+        person_uuid,
+        IDLE_DURATION_MS as u64,
+        current_active_ms,
+    )
+    .await
+}
+
 async fn move_person_to_scene<
     W: SceneCapability
         + JobCapability
         + PersonCapability
         + MessageCapability
+        + ModerationCapability
         + ReactionHistoryCapability
         + LogCapability
         + Sync,
@@ -317,6 +995,7 @@ async fn enqueue_scene_gaze<
         + JobCapability
         + PersonCapability
         + MessageCapability
+        + ModerationCapability
         + ReactionHistoryCapability
         + LogCapability
         + Sync,
@@ -380,8 +1059,12 @@ async fn enqueue_hibernation<W: JobCapability>(
     current_active_ms: i64,
 ) -> Result<(), ActionHandleError> {
     let duration_i64: i64 = duration_ms.min(i64::MAX as u64) as i64;
-    let person_hibernating_job =
-        PersonHibernatingJob::new(person_uuid.clone(), duration_i64, current_active_ms);
+    let person_hibernating_job = PersonHibernatingJob::new(
+        person_uuid.clone(),
+        duration_i64,
+        current_active_ms,
+        worker.now(),
+    );
     let hibernation_job = JobKind::PersonHibernating(person_hibernating_job);
     worker
         .unshift_job(hibernation_job)
@@ -1,6 +1,6 @@
 use crate::capability::person::PersonCapability;
 use crate::domain::person_uuid::PersonUuid;
-use crate::nice_display::NiceDisplay;
+use crate::nice_display::{with_context, NiceDisplay};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
@@ -26,20 +26,25 @@ impl NiceDisplay for Error {
     fn message(&self) -> String {
         match self {
             Error::FailedToGetHibernationState(err) => {
-                format!("Failed to get hibernation state: {}", err)
+                with_context("Failed to get hibernation state", err)
             }
             Error::FailedToSetHibernationState(err) => {
-                format!("Failed to set hibernation state: {}", err)
+                with_context("Failed to set hibernation state", err)
             }
         }
     }
 }
 
 impl PersonHibernatingJob {
-    pub fn new(person_uuid: PersonUuid, duration_ms: i64, start_active_ms: i64) -> Self {
+    pub fn new(
+        person_uuid: PersonUuid,
+        duration_ms: i64,
+        start_active_ms: i64,
+        started_at: DateTime<Utc>,
+    ) -> Self {
         Self {
             person_uuid,
-            started_at: Utc::now(),
+            started_at,
             duration_ms: duration_ms.max(0),
             start_active_ms: start_active_ms.max(0),
         }
@@ -1,17 +1,25 @@
 use crate::capability::event::EventCapability;
+use crate::capability::expected_reply::ExpectedReplyCapability;
+use crate::capability::item::ItemCapability;
 use crate::capability::job::JobCapability;
+use crate::capability::log_event::LogEventCapability;
 use crate::capability::logging::LogCapability;
 use crate::capability::memory::{MemoryCapability, MessageTypeArgs};
 use crate::capability::message::MessageCapability;
+use crate::capability::moderation::ModerationCapability;
+use crate::capability::motivation::MotivationCapability;
 use crate::capability::person::PersonCapability;
 use crate::capability::person_identity::PersonIdentityCapability;
 use crate::capability::person_task::PersonTaskCapability;
 use crate::capability::reaction::ReactionCapability;
 use crate::capability::reaction_history::ReactionHistoryCapability;
-use crate::capability::scene::SceneCapability;
+use crate::capability::reflection::ReflectionCapability;
+use crate::capability::scene::{SceneCapability, SceneParticipant};
+use crate::capability::scene_invitation::SceneInvitationCapability;
 use crate::capability::state_of_mind::StateOfMindCapability;
 use crate::domain::event::Event;
 use crate::domain::job::person_action_handler::{self, ActionHandleError};
+use crate::domain::job::process_reaction_common::{self, SceneReactionTrigger};
 use crate::domain::memory::Memory;
 use crate::domain::message::MessageSender;
 use crate::domain::person_name::PersonName;
@@ -19,7 +27,7 @@ use crate::domain::person_task::PersonTaskOutcomeCheck;
 use crate::domain::person_uuid::PersonUuid;
 use crate::domain::random_seed::RandomSeed;
 use crate::domain::state_of_mind::StateOfMind;
-use crate::nice_display::NiceDisplay;
+use crate::nice_display::{with_context, NiceDisplay};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
@@ -50,6 +58,7 @@ pub enum Error {
     FailedToGetPersonsName(String),
     CouldNotCreateMemoriesPrompt(String),
     FailedToSearchMemories(String),
+    FailedToGetSceneParticipants(String),
     GetPersonReaction(String),
     CouldNotGetPersonsScene {
         person_uuid: PersonUuid,
@@ -61,6 +70,8 @@ pub enum Error {
     TaskStatePersistence(String),
     TaskTransition(String),
     Action(ActionHandleError),
+    FailedToGetMissedMessages(String),
+    MissedMessagesReaction(process_reaction_common::Error),
 }
 
 pub enum WaitDecision {
@@ -74,76 +85,68 @@ impl NiceDisplay for Error {
             Error::MissingPersonUuid => "Missing person uuid on wait job".to_string(),
             Error::MissingStartedAt => "Missing started_at on wait job".to_string(),
             Error::FailedToGetHibernationState(err) => {
-                format!("Failed to get hibernation state: {}", err)
-            }
-            Error::FailedToGetEnabledState(err) => {
-                format!("Failed to get enabled state: {}", err)
-            }
-            Error::FailedToGetEvents(err) => {
-                format!("Failed to get events: {}", err)
+                with_context("Failed to get hibernation state", err)
             }
+            Error::FailedToGetEnabledState(err) => with_context("Failed to get enabled state", err),
+            Error::FailedToGetEvents(err) => with_context("Failed to get events", err),
             Error::FailedToSummarizeRecentEvents(err) => {
-                format!("Failed to summarize recent events: {}", err)
+                with_context("Failed to summarize recent events", err)
             }
             Error::FailedToGetReactionHistory(err) => {
-                format!("Failed to get reaction history: {}", err)
-            }
-            Error::FailedToGetStateOfMind(err) => {
-                format!("Failed to get state of mind: {}", err)
+                with_context("Failed to get reaction history", err)
             }
+            Error::FailedToGetStateOfMind(err) => with_context("Failed to get state of mind", err),
             Error::NoStateOfMindFound { person_uuid } => {
                 format!(
                     "No state of mind found for person {}",
                     person_uuid.to_uuid()
                 )
             }
-            Error::FailedToGetPersonsName(err) => {
-                format!("Failed to get person's name: {}", err)
-            }
+            Error::FailedToGetPersonsName(err) => with_context("Failed to get person's name", err),
             Error::CouldNotCreateMemoriesPrompt(err) => {
-                format!("Could not create memories prompt: {}", err)
-            }
-            Error::FailedToSearchMemories(err) => {
-                format!("Failed to search memories: {}", err)
+                with_context("Could not create memories prompt", err)
             }
-            Error::GetPersonReaction(err) => {
-                format!("Failed to get person reaction: {}", err)
+            Error::FailedToSearchMemories(err) => with_context("Failed to search memories", err),
+            Error::FailedToGetSceneParticipants(err) => {
+                with_context("Failed to get scene participants", err)
             }
+            Error::GetPersonReaction(err) => with_context("Failed to get person reaction", err),
             Error::CouldNotGetPersonsScene {
                 person_uuid,
                 details,
-            } => {
+            } => with_context(
                 format!(
-                    "Could not get current scene for person {}: {}",
-                    person_uuid.to_uuid(),
-                    details
-                )
-            }
-            Error::FailedToGetCurrentTask(err) => {
-                format!("Failed to get current task: {}", err)
-            }
+                    "Could not get current scene for person {}",
+                    person_uuid.to_uuid()
+                ),
+                details,
+            ),
+            Error::FailedToGetCurrentTask(err) => with_context("Failed to get current task", err),
             Error::TaskOutcomeClassification(err) => {
-                format!("Task outcome classification failed: {}", err)
-            }
-            Error::TaskStateUpdate(err) => {
-                format!("Task state update failed: {}", err)
-            }
-            Error::TaskStatePersistence(err) => {
-                format!("Task state persistence failed: {}", err)
-            }
-            Error::TaskTransition(err) => {
-                format!("Task transition failed: {}", err)
+                with_context("Task outcome classification failed", err)
             }
+            Error::TaskStateUpdate(err) => with_context("Task state update failed", err),
+            Error::TaskStatePersistence(err) => with_context("Task state persistence failed", err),
+            Error::TaskTransition(err) => with_context("Task transition failed", err),
             Error::Action(err) => err.to_nice_error().to_string(),
+            Error::FailedToGetMissedMessages(err) => {
+                with_context("Failed to get messages missed while waiting", err)
+            }
+            Error::MissedMessagesReaction(err) => err.message(),
         }
     }
 }
 
 impl PersonWaitingJob {
-    pub fn new(person_uuid: PersonUuid, duration_ms: i64, start_active_ms: i64) -> Self {
+    pub fn new(
+        person_uuid: PersonUuid,
+        duration_ms: i64,
+        start_active_ms: i64,
+        started_at: DateTime<Utc>,
+    ) -> Self {
         Self {
             person_uuid: Some(person_uuid),
-            started_at: Some(Utc::now()),
+            started_at: Some(started_at),
             duration_ms: duration_ms.max(0),
             start_active_ms: start_active_ms.max(0),
         }
@@ -162,6 +165,7 @@ impl PersonWaitingJob {
             + SceneCapability
             + ReactionCapability
             + MessageCapability
+            + ModerationCapability
             + MemoryCapability
             + PersonCapability
             + EventCapability
@@ -169,6 +173,12 @@ impl PersonWaitingJob {
             + PersonIdentityCapability
             + PersonTaskCapability
             + ReactionHistoryCapability
+            + ExpectedReplyCapability
+            + ItemCapability
+            + SceneInvitationCapability
+            + ReflectionCapability
+            + LogEventCapability
+            + MotivationCapability
             + LogCapability
             + Sync,
     >(
@@ -197,6 +207,37 @@ impl PersonWaitingJob {
 
         let elapsed = current_active_ms.saturating_sub(self.start_active_ms);
         if elapsed >= self.duration_ms {
+            let scene_uuid = worker
+                .get_persons_current_scene_uuid(&person_uuid)
+                .await
+                .map_err(|err| Error::CouldNotGetPersonsScene {
+                    person_uuid: person_uuid.clone(),
+                    details: err,
+                })?;
+
+            // Messages held back during the wait get one reaction together
+            if let Some(scene_uuid) = &scene_uuid {
+                let missed_messages = worker
+                    .get_unhandled_scene_messages_for_person(&person_uuid, scene_uuid)
+                    .await
+                    .map_err(Error::FailedToGetMissedMessages)?;
+
+                if !missed_messages.is_empty() {
+                    process_reaction_common::run_scene_reaction(
+                        worker,
+                        &person_uuid,
+                        scene_uuid,
+                        SceneReactionTrigger::WaitEnded,
+                        random_seed,
+                        current_active_ms,
+                    )
+                    .await
+                    .map_err(Error::MissedMessagesReaction)?;
+
+                    return Ok(WaitDecision::FinishedWaiting);
+                }
+            }
+
             let get_args =
                 crate::capability::event::GetArgs::new().with_person_uuid(person_uuid.clone());
             let events = worker
@@ -223,13 +264,16 @@ impl PersonWaitingJob {
                 .await
                 .map_err(Error::FailedToGetPersonsName)?;
 
-            let scene_uuid = worker
-                .get_persons_current_scene_uuid(&person_uuid)
-                .await
-                .map_err(|err| Error::CouldNotGetPersonsScene {
-                    person_uuid: person_uuid.clone(),
-                    details: err,
-                })?;
+            let participants = match &scene_uuid {
+                Some(scene_uuid) => SceneParticipant::other_persons(
+                    &worker
+                        .get_scene_current_participants(scene_uuid)
+                        .await
+                        .map_err(Error::FailedToGetSceneParticipants)?,
+                    &person_uuid,
+                ),
+                None => vec![],
+            };
 
             let message_type_args = match scene_uuid.clone() {
                 Some(scene_uuid) => MessageTypeArgs::SceneByUuid { scene_uuid },
@@ -282,9 +326,19 @@ impl PersonWaitingJob {
                 .await
                 .map_err(Error::CouldNotCreateMemoriesPrompt)?;
 
-            let memories: Vec<Memory> = crate::domain::memory::filter_memory_results(
+            let retrieval = worker
+                .get_memory_retrieval()
+                .await
+                .map_err(Error::FailedToSearchMemories)?;
+
+            let memories: Vec<Memory> = retrieval.filter(
                 worker
-                    .search_memories(person_uuid.clone(), memories_prompt.prompt, 5)
+                    .search_memories(
+                        person_uuid.clone(),
+                        memories_prompt.prompt,
+                        &participants,
+                        &retrieval,
+                    )
                     .await
                     .map_err(Error::FailedToSearchMemories)?,
             );
@@ -329,38 +383,67 @@ impl PersonWaitingJob {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::capability::clock::ClockCapability;
     use crate::capability::event::GetArgs;
+    use crate::capability::expected_reply::{
+        ExpectedReplyCapability, ExpectedReplyOutcome, NewExpectedReply,
+    };
+    use crate::capability::item::{ItemCapability, NewItem};
     use crate::capability::job::JobCapability;
+    use crate::capability::llm_batch::LlmBatchCapability;
     use crate::capability::memory::{MemoryQueryPrompt, MemorySearchResult, NewMemory};
+    use crate::capability::moderation::{BlockedContent, ModerationCapability};
+    use crate::capability::motivation::NewMotivation;
     use crate::capability::person::NewPerson;
     use crate::capability::person_identity::NewPersonIdentity;
     use crate::capability::person_task::NewPersonTask;
     use crate::capability::reaction::ReactionPromptPreview;
+    use crate::capability::reflection::ReflectionChange;
     use crate::capability::scene::{
         CurrentScene, NewScene, NewSceneSnapshot, Scene, SceneParticipant, SceneParticipation,
     };
+    use crate::capability::scene_invitation::NewSceneInvitation;
     use crate::capability::state_of_mind::NewStateOfMind;
+    use crate::domain::clock::Clock;
     use crate::domain::event::{Event, EventType};
+    use crate::domain::fan_out::QueuePressure;
+    use crate::domain::item::Item;
+    use crate::domain::item_uuid::ItemUuid;
     use crate::domain::job::{Job, JobKind, PoppedJob};
+    use crate::domain::job_event::{JobEvent, NewJobEvent};
     use crate::domain::job_uuid::JobUuid;
+    use crate::domain::knowledge_boundary::Attendance;
     use crate::domain::memory_uuid::MemoryUuid;
     use crate::domain::message::Message;
+    use crate::domain::message_audience::{HearingRadius, MessageAudience};
+    use crate::domain::message_quote::MessageQuote;
     use crate::domain::message_uuid::MessageUuid;
+    use crate::domain::moderation::ModerationVerdict;
+    use crate::domain::motivation::Motivation;
+    use crate::domain::motivation_uuid::MotivationUuid;
+    use crate::domain::person_directory::PersonDirectoryEntry;
     use crate::domain::person_identity_uuid::PersonIdentityUuid;
     use crate::domain::person_task::{PersonTask, PersonTaskTerminalOutcome};
     use crate::domain::person_task_uuid::PersonTaskUuid;
+    use crate::domain::scene_ambience::Ambience;
+    use crate::domain::scene_invitation::SceneInvitation;
+    use crate::domain::scene_invitation_uuid::SceneInvitationUuid;
     use crate::domain::scene_participant_uuid::SceneParticipantUuid;
     use crate::domain::scene_uuid::SceneUuid;
     use crate::domain::state_of_mind_uuid::StateOfMindUuid;
+    use crate::domain::world_time::{TimeOfDay, WorldTime};
+    use crate::open_ai::batch::{Batch, BatchRequest, BatchResult, BatchStatus};
     use crate::person_actions::{PersonAction, PersonReaction, ReflectionDecision};
     use async_trait::async_trait;
-    use chrono::{Duration, Utc};
+    use chrono::{Duration, TimeZone};
+    use std::collections::HashMap;
     use std::sync::Arc;
     use tokio::sync::Mutex;
 
     #[derive(Clone)]
     struct MockWorker {
         state: Arc<Mutex<MockState>>,
+        clock: Clock,
     }
 
     struct MockState {
@@ -377,8 +460,9 @@ mod tests {
         fn new() -> Self {
             let person_uuid = PersonUuid::new();
             let scene_uuid = SceneUuid::new();
+            let clock = Clock::fake(Utc.with_ymd_and_hms(2026, 3, 18, 9, 0, 0).unwrap());
             let events = vec![Event::new(
-                Utc::now() - Duration::minutes(10),
+                clock.now() - Duration::minutes(10),
                 EventType::Said {
                     scene_name: "Cafe".to_string(),
                     speaker_name: "Counter".to_string(),
@@ -399,10 +483,17 @@ mod tests {
                     reaction_situations: vec![],
                     jobs: vec![],
                 })),
+                clock,
             }
         }
     }
 
+    impl ClockCapability for MockWorker {
+        fn now(&self) -> DateTime<Utc> {
+            self.clock.now()
+        }
+    }
+
     impl ReactionCapability for MockWorker {
         async fn summarize_reaction_events(&self, events_text: String) -> Result<String, String> {
             let mut state = self.state.lock().await;
@@ -421,6 +512,7 @@ mod tests {
                 thinking_user_prompt: "user".to_string(),
                 action_system_prompt: "action-sys".to_string(),
                 action_user_prompt: "action-user".to_string(),
+                context_timings: Default::default(),
             })
         }
 
@@ -483,6 +575,11 @@ mod tests {
             Ok(())
         }
 
+        async fn enqueue_job(&self, job: JobKind) -> Result<JobUuid, String> {
+            self.unshift_job(job).await?;
+            Ok(JobUuid::new())
+        }
+
         async fn pop_next_job(&self, _current_active_ms: i64) -> Result<Option<PoppedJob>, String> {
             Ok(None)
         }
@@ -514,6 +611,39 @@ mod tests {
         async fn delete_job(&self, _job_uuid: &JobUuid) -> Result<(), String> {
             Ok(())
         }
+
+        async fn record_job_event(
+            &self,
+            _job_uuid: &JobUuid,
+            _event: &NewJobEvent,
+        ) -> Result<(), String> {
+            Ok(())
+        }
+
+        async fn get_job_events(&self, _job_uuid: &JobUuid) -> Result<Vec<JobEvent>, String> {
+            Ok(vec![])
+        }
+
+        async fn get_queue_pressure(&self) -> Result<QueuePressure, String> {
+            Ok(QueuePressure {
+                pending_jobs: 0,
+                active_ms: 0,
+            })
+        }
+
+        async fn get_next_delayed_job_active_ms(
+            &self,
+            _current_active_ms: i64,
+        ) -> Result<Option<i64>, String> {
+            Ok(None)
+        }
+
+        async fn get_persons_wait_end_active_ms(
+            &self,
+            _person_uuid: &PersonUuid,
+        ) -> Result<Option<i64>, String> {
+            Ok(None)
+        }
     }
 
     #[async_trait]
@@ -586,6 +716,13 @@ mod tests {
             Ok(vec![])
         }
 
+        async fn get_persons_attendance(
+            &self,
+            _person_uuid: &PersonUuid,
+        ) -> Result<Vec<Attendance>, String> {
+            Ok(vec![])
+        }
+
         async fn set_real_world_user_in_scene(
             &self,
             _scene_uuid: &SceneUuid,
@@ -601,6 +738,21 @@ mod tests {
             Ok(false)
         }
 
+        async fn get_scene_hearing_radius(
+            &self,
+            _scene_uuid: &SceneUuid,
+        ) -> Result<HearingRadius, String> {
+            Ok(HearingRadius::Room)
+        }
+
+        async fn set_scene_hearing_radius(
+            &self,
+            _scene_uuid: &SceneUuid,
+            _hearing_radius: HearingRadius,
+        ) -> Result<(), String> {
+            Ok(())
+        }
+
         async fn get_scene_name(&self, _scene_uuid: &SceneUuid) -> Result<Option<String>, String> {
             Ok(Some("Cafe".to_string()))
         }
@@ -611,6 +763,40 @@ mod tests {
         ) -> Result<Option<String>, String> {
             Ok(Some("A quiet cafe.".to_string()))
         }
+
+        async fn set_scene_time_of_day(
+            &self,
+            _scene_uuid: &SceneUuid,
+            _time_of_day: TimeOfDay,
+        ) -> Result<(), String> {
+            Ok(())
+        }
+
+        async fn get_scene_ambience(
+            &self,
+            _scene_uuid: &SceneUuid,
+        ) -> Result<Option<Ambience>, String> {
+            Ok(None)
+        }
+
+        async fn set_scene_ambience(
+            &self,
+            _scene_uuid: &SceneUuid,
+            _ambience: Ambience,
+        ) -> Result<(), String> {
+            Ok(())
+        }
+
+        async fn get_scene_world_time(&self, _scene_uuid: &SceneUuid) -> Result<WorldTime, String> {
+            Ok(WorldTime::new(0, 0))
+        }
+
+        async fn get_scene_participant_chattiness(
+            &self,
+            _scene_uuid: &SceneUuid,
+        ) -> Result<Vec<(PersonUuid, f64)>, String> {
+            Ok(vec![])
+        }
     }
 
     impl MessageCapability for MockWorker {
@@ -631,6 +817,22 @@ mod tests {
             Ok(())
         }
 
+        async fn set_message_audience(
+            &self,
+            _message_uuid: &MessageUuid,
+            _audience: &MessageAudience,
+        ) -> Result<(), String> {
+            Ok(())
+        }
+
+        async fn add_scene_message_overhearers(
+            &self,
+            _message_uuid: &MessageUuid,
+            _overhearers: Vec<PersonUuid>,
+        ) -> Result<(), String> {
+            Ok(())
+        }
+
         async fn get_messages_in_scene_page(
             &self,
             _scene_uuid: &SceneUuid,
@@ -662,6 +864,21 @@ mod tests {
         ) -> Result<(), String> {
             Ok(())
         }
+
+        async fn set_message_quote(
+            &self,
+            _message_uuid: &MessageUuid,
+            _quote: &MessageQuote,
+        ) -> Result<(), String> {
+            Ok(())
+        }
+
+        async fn get_message_quotes(
+            &self,
+            _message_uuids: Vec<MessageUuid>,
+        ) -> Result<HashMap<MessageUuid, MessageQuote>, String> {
+            Ok(HashMap::new())
+        }
     }
 
     impl MemoryCapability for MockWorker {
@@ -694,10 +911,28 @@ mod tests {
             &self,
             _person_uuid: PersonUuid,
             _query: String,
-            _limit: i64,
+            _participants: &[PersonUuid],
+            _retrieval: &crate::domain::memory::MemoryRetrieval,
         ) -> Result<Vec<MemorySearchResult>, String> {
             Ok(vec![])
         }
+
+        async fn get_memory_retrieval(
+            &self,
+        ) -> Result<crate::domain::memory::MemoryRetrieval, String> {
+            Ok(crate::domain::memory::MemoryRetrieval::default())
+        }
+
+        async fn set_memory_retrieval(
+            &self,
+            _retrieval: &crate::domain::memory::MemoryRetrieval,
+        ) -> Result<(), String> {
+            Ok(())
+        }
+
+        async fn delete_memory(&self, _memory_uuid: &MemoryUuid) -> Result<(), String> {
+            Ok(())
+        }
     }
 
     impl PersonCapability for MockWorker {
@@ -748,6 +983,10 @@ mod tests {
         async fn is_person_enabled(&self, _person_uuid: &PersonUuid) -> Result<bool, String> {
             Ok(true)
         }
+
+        async fn archive_person(&self, _person_uuid: &PersonUuid) -> Result<(), String> {
+            Ok(())
+        }
     }
 
     impl EventCapability for MockWorker {
@@ -810,6 +1049,41 @@ mod tests {
         ) -> Result<Option<String>, String> {
             Ok(None)
         }
+
+        async fn get_person_directory(
+            &self,
+            _viewer: &PersonUuid,
+            _others: &[PersonUuid],
+        ) -> Result<Vec<PersonDirectoryEntry>, String> {
+            Ok(vec![])
+        }
+
+        async fn set_person_identity_summary(
+            &self,
+            _person_identity_uuid: &PersonIdentityUuid,
+            _summary: &str,
+        ) -> Result<(), String> {
+            Ok(())
+        }
+    }
+
+    impl LlmBatchCapability for MockWorker {
+        async fn submit_llm_batch(&self, _requests: Vec<BatchRequest>) -> Result<String, String> {
+            Ok("batch_test".to_string())
+        }
+
+        async fn refresh_llm_batch(&self, batch_id: &str) -> Result<Batch, String> {
+            Ok(Batch {
+                id: batch_id.to_string(),
+                status: BatchStatus::InProgress,
+                output_file_id: None,
+                error_file_id: None,
+            })
+        }
+
+        async fn get_llm_batch_results(&self, _file_id: &str) -> Result<Vec<BatchResult>, String> {
+            Ok(vec![])
+        }
     }
 
     impl PersonTaskCapability for MockWorker {
@@ -864,10 +1138,144 @@ mod tests {
         }
     }
 
+    impl ModerationCapability for MockWorker {
+        async fn moderate_content(
+            &self,
+            _sender: &MessageSender,
+            _scene_uuid: &SceneUuid,
+            _content: &str,
+        ) -> Result<ModerationVerdict, String> {
+            Ok(ModerationVerdict::Allowed)
+        }
+
+        async fn get_blocked_content(&self, _limit: i64) -> Result<Vec<BlockedContent>, String> {
+            Ok(vec![])
+        }
+
+        async fn get_moderation_threshold(&self) -> Result<f64, String> {
+            Ok(1.0)
+        }
+
+        async fn set_moderation_threshold(&self, _threshold: f64) -> Result<(), String> {
+            Ok(())
+        }
+    }
+
+    impl SceneInvitationCapability for MockWorker {
+        async fn create_scene_invitation(
+            &self,
+            _new_scene_invitation: NewSceneInvitation,
+        ) -> Result<SceneInvitationUuid, String> {
+            Ok(SceneInvitationUuid::new())
+        }
+
+        async fn get_pending_scene_invitations(
+            &self,
+            _person_uuid: &PersonUuid,
+        ) -> Result<Vec<SceneInvitation>, String> {
+            Ok(vec![])
+        }
+
+        async fn respond_to_scene_invitations(
+            &self,
+            _person_uuid: &PersonUuid,
+            _current_scene_uuid: Option<&SceneUuid>,
+        ) -> Result<(), String> {
+            Ok(())
+        }
+    }
+
+    impl ItemCapability for MockWorker {
+        async fn create_item(&self, _new_item: NewItem) -> Result<ItemUuid, String> {
+            Ok(ItemUuid::new())
+        }
+
+        async fn get_items_carried_by(
+            &self,
+            _person_uuid: &PersonUuid,
+        ) -> Result<Vec<Item>, String> {
+            Ok(vec![])
+        }
+
+        async fn get_items_in_scene(&self, _scene_uuid: &SceneUuid) -> Result<Vec<Item>, String> {
+            Ok(vec![])
+        }
+
+        async fn give_item(
+            &self,
+            _item_uuid: &ItemUuid,
+            _giver_person_uuid: &PersonUuid,
+            _recipient_person_uuid: &PersonUuid,
+        ) -> Result<(), String> {
+            Ok(())
+        }
+    }
+
+    impl ExpectedReplyCapability for MockWorker {
+        async fn expect_reply(&self, _new_expected_reply: NewExpectedReply) -> Result<(), String> {
+            Ok(())
+        }
+
+        async fn has_recipient_replied(&self, _message_uuid: &MessageUuid) -> Result<bool, String> {
+            Ok(false)
+        }
+
+        async fn resolve_expected_reply(
+            &self,
+            _message_uuid: &MessageUuid,
+            _outcome: ExpectedReplyOutcome,
+        ) -> Result<(), String> {
+            Ok(())
+        }
+    }
+
     impl LogCapability for MockWorker {
         fn log(&self, _level: crate::domain::logger::Level, _message: &str) {}
     }
 
+    impl ReflectionCapability for MockWorker {
+        async fn get_reflection_changes(
+            &self,
+            _memories: Vec<Memory>,
+            _person_uuid: PersonUuid,
+            _person_identity: String,
+            _state_of_mind: String,
+            _situation: String,
+        ) -> Result<Vec<ReflectionChange>, String> {
+            Ok(vec![])
+        }
+    }
+
+    impl LogEventCapability for MockWorker {
+        async fn log_event(
+            &self,
+            _event_name: String,
+            _data: Option<serde_json::Value>,
+        ) -> Result<(), String> {
+            Ok(())
+        }
+    }
+
+    impl MotivationCapability for MockWorker {
+        async fn create_motivation(
+            &self,
+            _new_motivation: NewMotivation,
+        ) -> Result<MotivationUuid, String> {
+            Ok(MotivationUuid::new())
+        }
+
+        async fn get_motivations_for_person(
+            &self,
+            _person_uuid: &PersonUuid,
+        ) -> Result<Vec<Motivation>, String> {
+            Ok(vec![])
+        }
+
+        async fn delete_motivation(&self, _motivation_uuid: MotivationUuid) -> Result<(), String> {
+            Ok(())
+        }
+    }
+
     #[tokio::test]
     async fn wait_job_reaction_includes_recent_events_context() {
         let worker = MockWorker::new();
@@ -875,7 +1283,7 @@ mod tests {
         let person_uuid = state.person_uuid.clone();
         drop(state);
 
-        let wait_job = PersonWaitingJob::new(person_uuid.clone(), 60_000, 0);
+        let wait_job = PersonWaitingJob::new(person_uuid.clone(), 60_000, 0, worker.now());
 
         let result = wait_job.run(&worker, RandomSeed::from_u64(1), 60_000).await;
 
@@ -896,6 +1304,37 @@ mod tests {
         assert!(situation.contains("nothing happened"));
         assert!(situation.contains("There were no new messages or events"));
     }
+
+    #[tokio::test]
+    async fn wait_job_finishes_quietly_when_something_happened_during_the_wait() {
+        let worker = MockWorker::new();
+        let person_uuid = worker.state.lock().await.person_uuid.clone();
+
+        let wait_job = PersonWaitingJob::new(person_uuid.clone(), 60_000, 0, worker.now());
+
+        worker.clock.advance(Duration::seconds(30));
+        worker.state.lock().await.events.push(Event::new(
+            worker.now(),
+            EventType::Said {
+                scene_name: "Cafe".to_string(),
+                speaker_name: "Counter".to_string(),
+                comment: "two".to_string(),
+                message_uuid: MessageUuid::new(),
+            },
+        ));
+
+        let result = wait_job.run(&worker, RandomSeed::from_u64(1), 60_000).await;
+
+        match result {
+            Ok(WaitDecision::FinishedWaiting) => {}
+            Ok(WaitDecision::ContinueWaiting) => panic!("wait job should have finished"),
+            Err(err) => panic!("wait job should succeed: {}", err.message()),
+        }
+
+        let state = worker.state.lock().await;
+        assert!(state.summarize_inputs.is_empty());
+        assert!(state.reaction_situations.is_empty());
+    }
 }
 
 async fn maybe_transition_current_task<W: PersonTaskCapability + ReactionCapability + Sync>(
@@ -0,0 +1,137 @@
+use crate::capability::job::JobCapability;
+use crate::capability::llm_batch::LlmBatchCapability;
+use crate::capability::logging::LogCapability;
+use crate::domain::job::handle_batch_completion::HandleBatchCompletionJob;
+use crate::domain::job::JobKind;
+use crate::domain::llm_batch::BatchHandler;
+use crate::domain::logger::Level;
+use crate::nice_display::{with_context, NiceDisplay};
+use crate::open_ai::batch::BatchResult;
+use serde::{Deserialize, Serialize};
+
+/// Batches take minutes to hours, so there is no point asking often.
+const RECHECK_AFTER_MS: i64 = 5 * 60 * 1000;
+
+/// Watches a Batch API batch until it is done, then hands every answer to
+/// its own completion handler job.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PollLlmBatchJob {
+    pub batch_id: String,
+    pub run_at_active_ms: Option<i64>,
+}
+
+pub enum Error {
+    Refresh(String),
+    Results(String),
+    Enqueue(String),
+    Reschedule(String),
+}
+
+impl NiceDisplay for Error {
+    fn message(&self) -> String {
+        match self {
+            Error::Refresh(details) => with_context("Could not check on the llm batch", details),
+            Error::Results(details) => {
+                with_context("Could not download the llm batch results", details)
+            }
+            Error::Enqueue(details) => {
+                with_context("Could not enqueue a batch completion handler", details)
+            }
+            Error::Reschedule(details) => {
+                with_context("Could not reschedule the llm batch poll", details)
+            }
+        }
+    }
+}
+
+impl PollLlmBatchJob {
+    pub fn now(batch_id: String) -> Self {
+        PollLlmBatchJob {
+            batch_id,
+            run_at_active_ms: None,
+        }
+    }
+
+    pub async fn run<W: LlmBatchCapability + JobCapability + LogCapability>(
+        &self,
+        worker: &W,
+        current_active_ms: i64,
+    ) -> Result<(), Error> {
+        let batch = worker
+            .refresh_llm_batch(&self.batch_id)
+            .await
+            .map_err(Error::Refresh)?;
+
+        if !batch.status.is_terminal() {
+            worker
+                .unshift_job(JobKind::PollLlmBatch(PollLlmBatchJob {
+                    batch_id: self.batch_id.clone(),
+                    run_at_active_ms: Some(current_active_ms.saturating_add(RECHECK_AFTER_MS)),
+                }))
+                .await
+                .map_err(Error::Reschedule)?;
+            return Ok(());
+        }
+
+        worker.log(
+            Level::Info,
+            &format!(
+                "Llm batch {} finished as {}",
+                self.batch_id,
+                batch.status.to_name()
+            ),
+        );
+
+        if let Some(output_file_id) = &batch.output_file_id {
+            let results = worker
+                .get_llm_batch_results(output_file_id)
+                .await
+                .map_err(Error::Results)?;
+
+            for result in results {
+                dispatch_result(worker, result).await?;
+            }
+        }
+
+        if let Some(error_file_id) = &batch.error_file_id {
+            let failures = worker
+                .get_llm_batch_results(error_file_id)
+                .await
+                .map_err(Error::Results)?;
+
+            for failure in failures {
+                dispatch_result(worker, failure).await?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+async fn dispatch_result<W: JobCapability + LogCapability>(
+    worker: &W,
+    result: BatchResult,
+) -> Result<(), Error> {
+    let content = result
+        .response
+        .and_then(|response| response.as_message().map_err(|err| err.message()));
+
+    let handler = BatchHandler::from_custom_id(&result.custom_id);
+
+    match (handler, content) {
+        (Ok(handler), Ok(content)) => worker
+            .unshift_job(JobKind::HandleBatchCompletion(HandleBatchCompletionJob {
+                handler,
+                content,
+            }))
+            .await
+            .map_err(Error::Enqueue),
+        (Err(err), _) | (_, Err(err)) => {
+            worker.log(
+                Level::Warning,
+                &format!("Dropping llm batch result {}: {}", result.custom_id, err),
+            );
+            Ok(())
+        }
+    }
+}
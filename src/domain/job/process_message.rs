@@ -1,9 +1,12 @@
 use crate::capability::event::EventCapability;
+use crate::capability::expected_reply::ExpectedReplyCapability;
+use crate::capability::item::ItemCapability;
 use crate::capability::job::JobCapability;
 use crate::capability::log_event::LogEventCapability;
 use crate::capability::logging::LogCapability;
 use crate::capability::memory::MemoryCapability;
 use crate::capability::message::MessageCapability;
+use crate::capability::moderation::ModerationCapability;
 use crate::capability::motivation::MotivationCapability;
 use crate::capability::person::PersonCapability;
 use crate::capability::person_identity::PersonIdentityCapability;
@@ -12,21 +15,31 @@ use crate::capability::reaction::ReactionCapability;
 use crate::capability::reaction_history::ReactionHistoryCapability;
 use crate::capability::reflection::ReflectionCapability;
 use crate::capability::scene::SceneCapability;
+use crate::capability::scene_invitation::SceneInvitationCapability;
 use crate::capability::state_of_mind::StateOfMindCapability;
 use crate::domain::job::process_reaction_common::{self, SceneReactionTrigger};
+use crate::domain::message_urgency::MessageUrgency;
 use crate::domain::message_uuid::MessageUuid;
 use crate::domain::person_uuid::PersonUuid;
 use crate::domain::random_seed::RandomSeed;
-use crate::nice_display::NiceDisplay;
+use crate::domain::wait_coalescing;
+use crate::nice_display::{with_context, NiceDisplay};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ProcessMessageJob {
     pub message_uuid: MessageUuid,
     pub recipient_person_uuid: PersonUuid,
+    /// Holds the job back until the active clock reaches this time. Used to
+    /// stagger reactions when a scene is kicked off.
+    #[serde(default)]
+    pub run_at_active_ms: Option<i64>,
+    #[serde(default)]
+    pub urgency: MessageUrgency,
 }
 
 pub enum Error {
+    FailedToGetWait(String),
     FailedToGetMessage(String),
     MessageNotFound,
     Reaction(process_reaction_common::Error),
@@ -35,9 +48,10 @@ pub enum Error {
 impl NiceDisplay for Error {
     fn message(&self) -> String {
         match self {
-            Error::FailedToGetMessage(details) => {
-                format!("Failed to get message: {}", details)
+            Error::FailedToGetWait(details) => {
+                with_context("Failed to get the recipient's wait", details)
             }
+            Error::FailedToGetMessage(details) => with_context("Failed to get message", details),
             Error::MessageNotFound => "Message not found".to_string(),
             Error::Reaction(err) => err.message(),
         }
@@ -47,6 +61,7 @@ impl NiceDisplay for Error {
 impl ProcessMessageJob {
     pub async fn run<
         W: MessageCapability
+            + ModerationCapability
             + SceneCapability
             + ReactionCapability
             + MemoryCapability
@@ -56,6 +71,9 @@ impl ProcessMessageJob {
             + PersonIdentityCapability
             + PersonTaskCapability
             + ReactionHistoryCapability
+            + ExpectedReplyCapability
+            + ItemCapability
+            + SceneInvitationCapability
             + ReflectionCapability
             + LogCapability
             + LogEventCapability
@@ -68,6 +86,24 @@ impl ProcessMessageJob {
         random_seed: RandomSeed,
         current_active_ms: i64,
     ) -> Result<(), Error> {
+        // Background chatter waits with the person, who catches up on all of
+        // it at once when the wait ends. Being spoken to still interrupts.
+        if self.urgency == MessageUrgency::Background {
+            let wait_ends_at_active_ms = worker
+                .get_persons_wait_end_active_ms(&self.recipient_person_uuid)
+                .await
+                .map_err(Error::FailedToGetWait)?;
+
+            if wait_coalescing::holds_messages(wait_ends_at_active_ms, current_active_ms) {
+                tracing::info!(
+                    "Holding message {} for person {} until their wait ends",
+                    self.message_uuid.to_uuid(),
+                    self.recipient_person_uuid.to_uuid()
+                );
+                return Ok(());
+            }
+        }
+
         let maybe_message = worker
             .get_message_by_uuid(&self.message_uuid)
             .await
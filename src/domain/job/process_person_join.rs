@@ -1,9 +1,13 @@
+use crate::capability::arrival_observation::ArrivalObservationCapability;
 use crate::capability::event::EventCapability;
+use crate::capability::expected_reply::ExpectedReplyCapability;
+use crate::capability::item::ItemCapability;
 use crate::capability::job::JobCapability;
 use crate::capability::log_event::LogEventCapability;
 use crate::capability::logging::LogCapability;
 use crate::capability::memory::MemoryCapability;
 use crate::capability::message::MessageCapability;
+use crate::capability::moderation::ModerationCapability;
 use crate::capability::motivation::MotivationCapability;
 use crate::capability::person::PersonCapability;
 use crate::capability::person_identity::PersonIdentityCapability;
@@ -12,8 +16,11 @@ use crate::capability::reaction::ReactionCapability;
 use crate::capability::reaction_history::ReactionHistoryCapability;
 use crate::capability::reflection::ReflectionCapability;
 use crate::capability::scene::SceneCapability;
+use crate::capability::scene_invitation::SceneInvitationCapability;
 use crate::capability::state_of_mind::StateOfMindCapability;
+use crate::domain::arrival_observation;
 use crate::domain::job::process_reaction_common::{self, SceneReactionTrigger};
+use crate::domain::logger::Level;
 use crate::domain::person_uuid::PersonUuid;
 use crate::domain::random_seed::RandomSeed;
 use crate::domain::scene_uuid::SceneUuid;
@@ -45,6 +52,7 @@ impl ProcessPersonJoinJob {
             + ReactionCapability
             + MemoryCapability
             + MessageCapability
+            + ModerationCapability
             + PersonCapability
             + EventCapability
             + StateOfMindCapability
@@ -55,6 +63,10 @@ impl ProcessPersonJoinJob {
             + LogEventCapability
             + MotivationCapability
             + ReactionHistoryCapability
+            + ExpectedReplyCapability
+            + ItemCapability
+            + SceneInvitationCapability
+            + ArrivalObservationCapability
             + JobCapability
             + Sync,
     >(
@@ -63,13 +75,44 @@ impl ProcessPersonJoinJob {
         random_seed: RandomSeed,
         current_active_ms: i64,
     ) -> Result<(), Error> {
+        let is_own_arrival =
+            self.recipient_person_uuid.to_uuid() == self.joined_person_uuid.to_uuid();
+
+        let maybe_observation = if is_own_arrival {
+            // Walking in without a summary is better than not reacting at all.
+            arrival_observation::observe_arrival(
+                worker,
+                &self.recipient_person_uuid,
+                &self.scene_uuid,
+            )
+            .await
+            .unwrap_or_else(|err| {
+                worker.log(
+                    Level::Warning,
+                    format!(
+                        "Could not summarize the scene on arrival: {}",
+                        err.message()
+                    )
+                    .as_str(),
+                );
+                None
+            })
+        } else {
+            None
+        };
+
+        let trigger = match maybe_observation {
+            Some(observation) => SceneReactionTrigger::Arrived { observation },
+            None => SceneReactionTrigger::PersonJoined {
+                joined_person_uuid: self.joined_person_uuid,
+            },
+        };
+
         process_reaction_common::run_scene_reaction(
             worker,
             &self.recipient_person_uuid,
             &self.scene_uuid,
-            SceneReactionTrigger::PersonJoined {
-                joined_person_uuid: self.joined_person_uuid,
-            },
+            trigger,
             random_seed,
             current_active_ms,
         )
@@ -1,10 +1,13 @@
 use crate::capability;
 use crate::capability::event::EventCapability;
+use crate::capability::expected_reply::ExpectedReplyCapability;
+use crate::capability::item::ItemCapability;
 use crate::capability::job::JobCapability;
 use crate::capability::log_event::LogEventCapability;
 use crate::capability::logging::LogCapability;
 use crate::capability::memory::NewMemory;
 use crate::capability::memory::{MemoryCapability, MessageTypeArgs};
+use crate::capability::moderation::ModerationCapability;
 use crate::capability::motivation::MotivationCapability;
 use crate::capability::motivation::NewMotivation;
 use crate::capability::person::PersonCapability;
@@ -15,34 +18,42 @@ use crate::capability::reaction::ReactionPromptPreview;
 use crate::capability::reaction_history::ReactionHistoryCapability;
 use crate::capability::reflection::ReflectionCapability;
 use crate::capability::reflection::ReflectionChange;
+use crate::capability::scene_invitation::SceneInvitationCapability;
 use crate::capability::state_of_mind::NewStateOfMind;
 use crate::capability::state_of_mind::StateOfMindCapability;
 use crate::domain::event::{Event, EventType};
 use crate::domain::job::person_action_handler;
 use crate::domain::job::person_action_handler::ActionHandleError;
-use crate::domain::memory::Memory;
+use crate::domain::knowledge_boundary::KnowledgeBoundary;
+use crate::domain::memory::{Memory, MemoryRetrieval};
 use crate::domain::memory_uuid::MemoryUuid;
 use crate::domain::message::{Message, MessageSender};
 use crate::domain::person_name::PersonName;
 use crate::domain::person_task::PersonTaskOutcomeCheck;
 use crate::domain::person_uuid::PersonUuid;
 use crate::domain::random_seed::RandomSeed;
+use crate::domain::scene_invitation::SceneInvitation;
 use crate::domain::scene_uuid::SceneUuid;
 use crate::domain::situation;
 use crate::domain::situation::Situation;
 use crate::domain::state_of_mind::StateOfMind;
 use crate::domain::state_of_mind_uuid::StateOfMindUuid;
-use crate::nice_display::NiceDisplay;
+use crate::domain::utterance::REAL_WORLD_USER_NAME;
+use crate::domain::wait_coalescing;
+use crate::nice_display::{with_context, NiceDisplay};
 use crate::person_actions::ReflectionDecision;
 use crate::text_utils::normalize_message_content;
-use crate::{capability::message::MessageCapability, capability::scene::SceneCapability};
+use crate::{
+    capability::message::MessageCapability,
+    capability::scene::{SceneCapability, SceneParticipant},
+};
 use std::collections::HashSet;
 
-struct ReflectionInput {
-    person_name: PersonName,
-    memories: Vec<Memory>,
-    person_identity: String,
-    state_of_mind: String,
+pub(crate) struct ReflectionInput {
+    pub(crate) person_name: PersonName,
+    pub(crate) memories: Vec<Memory>,
+    pub(crate) person_identity: String,
+    pub(crate) state_of_mind: String,
 }
 
 struct ReactionExecutionInput {
@@ -50,12 +61,40 @@ struct ReactionExecutionInput {
     reflection_input: ReflectionInput,
     reaction_situation: String,
     description_prefix: Option<String>,
+    knowledge: KnowledgeBoundary,
 }
 
 pub enum SceneReactionTrigger {
     NewMessages,
-    PersonJoined { joined_person_uuid: PersonUuid },
+    PersonJoined {
+        joined_person_uuid: PersonUuid,
+    },
     SceneDescriptionGaze,
+    QuestionIgnored {
+        recipient_person_uuid: PersonUuid,
+        question: String,
+    },
+    /// The person themselves just walked into a scene that was already busy.
+    Arrived {
+        observation: String,
+    },
+    /// The person has been quiet for a while as others keep talking.
+    ConversationContinuing,
+    /// Something happened in the scene that nobody said, like a phone ringing.
+    SceneEvent {
+        description: String,
+    },
+    /// An external tool the person used just answered.
+    ToolResult {
+        observation: String,
+    },
+    /// Someone just invited the person to another scene.
+    Invited {
+        invitation: SceneInvitation,
+    },
+    /// The person's wait just ended, and messages came in while they waited.
+    /// Reacts to all of them at once, condensed when there are many.
+    WaitEnded,
 }
 
 pub enum Error {
@@ -102,6 +141,7 @@ pub enum Error {
         scene_uuid: SceneUuid,
         details: String,
     },
+    FailedToGetMessageQuotes(String),
     FailedToGetHibernationState {
         person_uuid: PersonUuid,
         details: String,
@@ -122,20 +162,16 @@ pub enum Error {
     TaskTransition(String),
     Action(ActionHandleError),
     Reflection(String),
+    GetPersonDirectory(String),
+    GetAttendance(String),
 }
 
 impl NiceDisplay for Error {
     fn message(&self) -> String {
         match self {
-            Error::GetPersonReaction(err) => {
-                format!("Failed to get person reaction: {}", err)
-            }
-            Error::FailedToGetEvents(err) => {
-                format!("Failed to get events: {}", err)
-            }
-            Error::FailedToGetStateOfMind(err) => {
-                format!("Failed to get state of mind: {}", err)
-            }
+            Error::GetPersonReaction(err) => with_context("Failed to get person reaction", err),
+            Error::FailedToGetEvents(err) => with_context("Failed to get events", err),
+            Error::FailedToGetStateOfMind(err) => with_context("Failed to get state of mind", err),
             Error::NoStateOfMindFound { person_uuid } => {
                 format!(
                     "No state of mind found for person {}",
@@ -143,13 +179,11 @@ impl NiceDisplay for Error {
                 )
             }
             Error::CouldNotCreateMemoriesPrompt(err) => {
-                format!("Could not create memories prompt: {}", err)
-            }
-            Error::FailedToSearchMemories(err) => {
-                format!("Failed to search memories: {}", err)
+                with_context("Could not create memories prompt", err)
             }
+            Error::FailedToSearchMemories(err) => with_context("Failed to search memories", err),
             Error::FailedToGetPersonIdentity(err) => {
-                format!("Failed to get person identity: {}", err)
+                with_context("Failed to get person identity", err)
             }
             Error::NoPersonIdentityFound { person_uuid } => {
                 format!(
@@ -160,132 +194,119 @@ impl NiceDisplay for Error {
             Error::FailedToGetSendersName {
                 person_uuid,
                 details,
-            } => {
-                format!(
-                    "Failed to get person's name for {}: {}",
-                    person_uuid.to_uuid(),
-                    details
-                )
+            } => with_context(
+                format!("Failed to get person's name for {}", person_uuid.to_uuid()),
+                details,
+            ),
+            Error::FailedToGetPersonsName(err) => with_context("Failed to get person's name", err),
+            Error::GetPersonDirectory(err) => {
+                with_context("Failed to look up the people present", err)
             }
-            Error::FailedToGetPersonsName(err) => {
-                format!("Failed to get person's name: {}", err)
+            Error::GetAttendance(err) => {
+                with_context("Failed to look up which scenes the person has been in", err)
             }
             Error::FailedToGetSceneParticipants {
                 scene_uuid,
                 details,
-            } => {
+            } => with_context(
                 format!(
-                    "Failed to get scene participants for {}: {}",
-                    scene_uuid.to_uuid(),
-                    details
-                )
-            }
+                    "Failed to get scene participants for {}",
+                    scene_uuid.to_uuid()
+                ),
+                details,
+            ),
             Error::FailedToGetSceneName {
                 scene_uuid,
                 details,
-            } => {
-                format!(
-                    "Failed to get scene name for {}: {}",
-                    scene_uuid.to_uuid(),
-                    details
-                )
-            }
+            } => with_context(
+                format!("Failed to get scene name for {}", scene_uuid.to_uuid()),
+                details,
+            ),
             Error::SceneNameNotFound { scene_uuid } => {
                 format!("Scene name not found for {}", scene_uuid.to_uuid())
             }
             Error::FailedToGetSceneDescription {
                 scene_uuid,
                 details,
-            } => {
+            } => with_context(
                 format!(
-                    "Failed to get scene description for {}: {}",
-                    scene_uuid.to_uuid(),
-                    details
-                )
-            }
+                    "Failed to get scene description for {}",
+                    scene_uuid.to_uuid()
+                ),
+                details,
+            ),
             Error::SceneDescriptionNotFound { scene_uuid } => {
                 format!("Scene description not found for {}", scene_uuid.to_uuid())
             }
             Error::FailedToGetUnhandledSceneMessages {
                 scene_uuid,
                 details,
-            } => {
+            } => with_context(
                 format!(
-                    "Failed to get unhandled scene messages for {}: {}",
-                    scene_uuid.to_uuid(),
-                    details
-                )
-            }
+                    "Failed to get unhandled scene messages for {}",
+                    scene_uuid.to_uuid()
+                ),
+                details,
+            ),
             Error::FailedToMarkSceneMessagesHandled {
                 scene_uuid,
                 details,
-            } => {
+            } => with_context(
                 format!(
-                    "Failed to mark scene messages handled for {}: {}",
-                    scene_uuid.to_uuid(),
-                    details
-                )
+                    "Failed to mark scene messages handled for {}",
+                    scene_uuid.to_uuid()
+                ),
+                details,
+            ),
+            Error::FailedToGetMessageQuotes(err) => {
+                with_context("Failed to get message quotes", err)
             }
             Error::FailedToGetHibernationState {
                 person_uuid,
                 details,
-            } => {
+            } => with_context(
                 format!(
-                    "Failed to get hibernation state for {}: {}",
-                    person_uuid.to_uuid(),
-                    details
-                )
-            }
+                    "Failed to get hibernation state for {}",
+                    person_uuid.to_uuid()
+                ),
+                details,
+            ),
             Error::FailedToGetEnabledState {
                 person_uuid,
                 details,
-            } => {
-                format!(
-                    "Failed to get enabled state for {}: {}",
-                    person_uuid.to_uuid(),
-                    details
-                )
-            }
-            Error::FailedToCreateMemory(err) => {
-                format!("Failed to create memory:\n{}", err)
-            }
+            } => with_context(
+                format!("Failed to get enabled state for {}", person_uuid.to_uuid()),
+                details,
+            ),
+            Error::FailedToCreateMemory(err) => with_context("Failed to create memory", err),
             Error::FailedToCreateReflectionStateOfMind(err) => {
-                format!("Failed to create reflection state of mind:\n{}", err)
+                with_context("Failed to create reflection state of mind", err)
             }
             Error::FailedToCreateReflectionMemory(err) => {
-                format!("Failed to create reflection memory:\n{}", err)
+                with_context("Failed to create reflection memory", err)
             }
             Error::FailedToCreateReflectionMotivation(err) => {
-                format!("Failed to create reflection motivation:\n{}", err)
+                with_context("Failed to create reflection motivation", err)
             }
             Error::FailedToDeleteReflectionMotivation(err) => {
-                format!("Failed to delete reflection motivation:\n{}", err)
-            }
-            Error::FailedToGetCurrentTask(err) => {
-                format!("Failed to get current task:\n{}", err)
+                with_context("Failed to delete reflection motivation", err)
             }
+            Error::FailedToGetCurrentTask(err) => with_context("Failed to get current task", err),
             Error::TaskOutcomeClassification(err) => {
-                format!("Task outcome classification failed:\n{}", err)
-            }
-            Error::TaskStateUpdate(err) => {
-                format!("Task state update failed:\n{}", err)
-            }
-            Error::TaskStatePersistence(err) => {
-                format!("Task state persistence failed:\n{}", err)
-            }
-            Error::TaskTransition(err) => {
-                format!("Task transition failed:\n{}", err)
+                with_context("Task outcome classification failed", err)
             }
+            Error::TaskStateUpdate(err) => with_context("Task state update failed", err),
+            Error::TaskStatePersistence(err) => with_context("Task state persistence failed", err),
+            Error::TaskTransition(err) => with_context("Task transition failed", err),
             Error::Action(err) => err.to_nice_error().to_string(),
-            Error::Reflection(err) => {
-                format!("Reflection error:\n{}", err)
-            }
+            Error::Reflection(err) => with_context("Reflection error", err),
         }
     }
 }
 
 pub async fn run_scene_reaction<
     W: MessageCapability
+        + ModerationCapability
         + SceneCapability
         + ReactionCapability
         + MemoryCapability
@@ -298,6 +319,9 @@ pub async fn run_scene_reaction<
         + LogEventCapability
         + MotivationCapability
         + ReactionHistoryCapability
+        + ExpectedReplyCapability
+        + ItemCapability
+        + SceneInvitationCapability
         + PersonTaskCapability
         + JobCapability
         + Sync,
@@ -319,6 +343,19 @@ pub async fn run_scene_reaction<
             })?,
         SceneReactionTrigger::PersonJoined { .. } => vec![],
         SceneReactionTrigger::SceneDescriptionGaze => vec![],
+        SceneReactionTrigger::QuestionIgnored { .. } => vec![],
+        SceneReactionTrigger::Arrived { .. } => vec![],
+        SceneReactionTrigger::ConversationContinuing => vec![],
+        SceneReactionTrigger::SceneEvent { .. } => vec![],
+        SceneReactionTrigger::ToolResult { .. } => vec![],
+        SceneReactionTrigger::Invited { .. } => vec![],
+        SceneReactionTrigger::WaitEnded => worker
+            .get_unhandled_scene_messages_for_person(person_uuid, scene_uuid)
+            .await
+            .map_err(|err| Error::FailedToGetUnhandledSceneMessages {
+                scene_uuid: scene_uuid.clone(),
+                details: err,
+            })?,
     };
 
     let is_enabled = worker.is_person_enabled(person_uuid).await.map_err(|err| {
@@ -348,6 +385,13 @@ pub async fn run_scene_reaction<
             SceneReactionTrigger::NewMessages => "Skipping reaction",
             SceneReactionTrigger::PersonJoined { .. } => "Skipping join reaction",
             SceneReactionTrigger::SceneDescriptionGaze => "Skipping scene gaze reaction",
+            SceneReactionTrigger::QuestionIgnored { .. } => "Skipping ignored question reaction",
+            SceneReactionTrigger::Arrived { .. } => "Skipping arrival reaction",
+            SceneReactionTrigger::ConversationContinuing => "Skipping wake up reaction",
+            SceneReactionTrigger::SceneEvent { .. } => "Skipping scene event reaction",
+            SceneReactionTrigger::ToolResult { .. } => "Skipping tool result reaction",
+            SceneReactionTrigger::Invited { .. } => "Skipping invitation reaction",
+            SceneReactionTrigger::WaitEnded => "Skipping reaction to messages missed while waiting",
         };
         tracing::info!(
             "{} for person {} in scene {}: person is disabled",
@@ -386,6 +430,13 @@ pub async fn run_scene_reaction<
             SceneReactionTrigger::NewMessages => "Skipping reaction",
             SceneReactionTrigger::PersonJoined { .. } => "Skipping join reaction",
             SceneReactionTrigger::SceneDescriptionGaze => "Skipping scene gaze reaction",
+            SceneReactionTrigger::QuestionIgnored { .. } => "Skipping ignored question reaction",
+            SceneReactionTrigger::Arrived { .. } => "Skipping arrival reaction",
+            SceneReactionTrigger::ConversationContinuing => "Skipping wake up reaction",
+            SceneReactionTrigger::SceneEvent { .. } => "Skipping scene event reaction",
+            SceneReactionTrigger::ToolResult { .. } => "Skipping tool result reaction",
+            SceneReactionTrigger::Invited { .. } => "Skipping invitation reaction",
+            SceneReactionTrigger::WaitEnded => "Skipping reaction to messages missed while waiting",
         };
         tracing::info!(
             "{} for person {} in scene {}: person is hibernating",
@@ -400,6 +451,13 @@ pub async fn run_scene_reaction<
         SceneReactionTrigger::NewMessages => true,
         SceneReactionTrigger::PersonJoined { .. } => false,
         SceneReactionTrigger::SceneDescriptionGaze => false,
+        SceneReactionTrigger::QuestionIgnored { .. } => false,
+        SceneReactionTrigger::Arrived { .. } => false,
+        SceneReactionTrigger::ConversationContinuing => false,
+        SceneReactionTrigger::SceneEvent { .. } => false,
+        SceneReactionTrigger::ToolResult { .. } => false,
+        SceneReactionTrigger::Invited { .. } => false,
+        SceneReactionTrigger::WaitEnded => true,
     };
 
     if is_new_messages_trigger && pending_messages.is_empty() {
@@ -458,6 +516,7 @@ pub async fn run_scene_reaction<
                     scene_uuid: scene_uuid.clone(),
                 },
                 person_uuid,
+                &reaction_input.knowledge,
             )
             .await?;
 
@@ -596,6 +655,19 @@ pub async fn preview_scene_reaction_prompts<
             })?,
         SceneReactionTrigger::PersonJoined { .. } => vec![],
         SceneReactionTrigger::SceneDescriptionGaze => vec![],
+        SceneReactionTrigger::QuestionIgnored { .. } => vec![],
+        SceneReactionTrigger::Arrived { .. } => vec![],
+        SceneReactionTrigger::ConversationContinuing => vec![],
+        SceneReactionTrigger::SceneEvent { .. } => vec![],
+        SceneReactionTrigger::ToolResult { .. } => vec![],
+        SceneReactionTrigger::Invited { .. } => vec![],
+        SceneReactionTrigger::WaitEnded => worker
+            .get_unhandled_scene_messages_for_person(person_uuid, scene_uuid)
+            .await
+            .map_err(|err| Error::FailedToGetUnhandledSceneMessages {
+                scene_uuid: scene_uuid.clone(),
+                details: err,
+            })?,
     };
 
     let reaction_input = build_reaction_execution_input(
@@ -638,59 +710,87 @@ async fn build_reaction_execution_input<
         SceneReactionTrigger::SceneDescriptionGaze => true,
         SceneReactionTrigger::NewMessages => false,
         SceneReactionTrigger::PersonJoined { .. } => false,
+        SceneReactionTrigger::QuestionIgnored { .. } => false,
+        SceneReactionTrigger::Arrived { .. } => false,
+        SceneReactionTrigger::ConversationContinuing => false,
+        SceneReactionTrigger::SceneEvent { .. } => false,
+        SceneReactionTrigger::ToolResult { .. } => false,
+        SceneReactionTrigger::Invited { .. } => false,
+        SceneReactionTrigger::WaitEnded => false,
     };
-    let situation = build_scene_situation(
-        worker,
-        scene_uuid,
-        pending_messages,
-        person_uuid,
-        include_scene_context,
-    )
-    .await?;
+    let knowledge = load_knowledge_boundary(worker, person_uuid).await?;
     let prompt_situation_messages = match trigger {
         SceneReactionTrigger::NewMessages => &[],
         SceneReactionTrigger::PersonJoined { .. } => pending_messages,
         SceneReactionTrigger::SceneDescriptionGaze => &[],
+        SceneReactionTrigger::QuestionIgnored { .. } => &[],
+        SceneReactionTrigger::Arrived { .. } => &[],
+        SceneReactionTrigger::ConversationContinuing => &[],
+        SceneReactionTrigger::SceneEvent { .. } => &[],
+        SceneReactionTrigger::ToolResult { .. } => &[],
+        SceneReactionTrigger::Invited { .. } => &[],
+        SceneReactionTrigger::WaitEnded => &[],
     };
-    let prompt_situation = build_scene_situation(
-        worker,
-        scene_uuid,
-        prompt_situation_messages,
-        person_uuid,
-        include_scene_context,
-    )
-    .await?;
+
+    // Everything below the knowledge boundary is fetched side by side
+    let (situation, prompt_situation, reaction_recent_events) = tokio::try_join!(
+        build_scene_situation(
+            worker,
+            scene_uuid,
+            pending_messages,
+            person_uuid,
+            include_scene_context,
+            &knowledge,
+        ),
+        build_scene_situation(
+            worker,
+            scene_uuid,
+            prompt_situation_messages,
+            person_uuid,
+            include_scene_context,
+            &knowledge,
+        ),
+        get_recent_events_text(
+            worker,
+            MessageTypeArgs::SceneByUuid {
+                scene_uuid: scene_uuid.clone(),
+            },
+            person_uuid,
+            &knowledge,
+        ),
+    )?;
     let prompt_situation_text = match trigger {
         SceneReactionTrigger::NewMessages => prompt_situation.to_people_present_text(),
         SceneReactionTrigger::PersonJoined { .. } => prompt_situation.to_string(),
         SceneReactionTrigger::SceneDescriptionGaze => prompt_situation.to_people_present_text(),
+        SceneReactionTrigger::QuestionIgnored { .. } => prompt_situation.to_people_present_text(),
+        SceneReactionTrigger::Arrived { .. } => prompt_situation.to_people_present_text(),
+        SceneReactionTrigger::ConversationContinuing => prompt_situation.to_people_present_text(),
+        SceneReactionTrigger::SceneEvent { .. } => prompt_situation.to_people_present_text(),
+        SceneReactionTrigger::ToolResult { .. } => prompt_situation.to_people_present_text(),
+        SceneReactionTrigger::Invited { .. } => prompt_situation.to_people_present_text(),
+        SceneReactionTrigger::WaitEnded => prompt_situation.to_people_present_text(),
     };
 
-    let reflection_input = build_reflection_input(
-        worker,
-        MessageTypeArgs::SceneByUuid {
-            scene_uuid: scene_uuid.clone(),
-        },
-        &situation,
-        person_uuid,
-    )
-    .await?;
-
-    let reaction_recent_events = get_recent_events_text(
-        worker,
-        MessageTypeArgs::SceneByUuid {
-            scene_uuid: scene_uuid.clone(),
-        },
-        person_uuid,
-    )
-    .await?;
-
     let reaction_events = filter_reaction_events(reaction_recent_events, pending_messages);
     let recent_events_text = Event::many_to_prompt_list(reaction_events);
-    let recent_events_summary = worker
-        .summarize_reaction_events(recent_events_text)
-        .await
-        .map_err(Error::GetPersonReaction)?;
+
+    let (reflection_input, recent_events_summary) = tokio::try_join!(
+        build_reflection_input(
+            worker,
+            MessageTypeArgs::SceneByUuid {
+                scene_uuid: scene_uuid.clone(),
+            },
+            &situation,
+            person_uuid,
+        ),
+        async {
+            worker
+                .summarize_reaction_events(recent_events_text)
+                .await
+                .map_err(Error::GetPersonReaction)
+        },
+    )?;
 
     let priority_instruction = match trigger {
         SceneReactionTrigger::NewMessages => {
@@ -702,6 +802,27 @@ async fn build_reaction_execution_input<
         SceneReactionTrigger::SceneDescriptionGaze => {
             "React to the current scene description first. Prioritize the SCENE GAZE EVENT lines below when deciding what to do now."
         }
+        SceneReactionTrigger::QuestionIgnored { .. } => {
+            "React to being ignored first. Prioritize the IGNORED QUESTION EVENT lines below when deciding what to do now."
+        }
+        SceneReactionTrigger::Arrived { .. } => {
+            "React to what you notice as you walk in first. Prioritize the ARRIVAL EVENT lines below when deciding what to do now."
+        }
+        SceneReactionTrigger::ConversationContinuing => {
+            "You have been quiet for a while. Decide whether to join back in, keeping to what you would plausibly do. Prioritize the CONVERSATION CONTINUING EVENT lines below when deciding what to do now."
+        }
+        SceneReactionTrigger::SceneEvent { .. } => {
+            "React to what just happened around you first. Prioritize the SCENE EVENT lines below when deciding what to do now."
+        }
+        SceneReactionTrigger::ToolResult { .. } => {
+            "React to what the tool you used returned first. Only you saw it, so share it if others should know. Prioritize the TOOL RESULT EVENT lines below when deciding what to do now."
+        }
+        SceneReactionTrigger::Invited { .. } => {
+            "Decide whether to accept the invitation first. Move to its scene to accept; anything else declines it. Prioritize the INVITATION EVENT lines below when deciding what to do now."
+        }
+        SceneReactionTrigger::WaitEnded => {
+            "You just finished waiting. Catch up on what was said while you waited, then decide what to do now. Prioritize the messages below over older context."
+        }
     };
 
     let new_event_section_label = match trigger {
@@ -712,12 +833,26 @@ async fn build_reaction_execution_input<
             "New join events (newest; primary reaction target):"
         }
         SceneReactionTrigger::SceneDescriptionGaze => "Scene gaze event (primary reaction target):",
+        SceneReactionTrigger::QuestionIgnored { .. } => {
+            "Ignored question event (primary reaction target):"
+        }
+        SceneReactionTrigger::Arrived { .. } => "Arrival event (primary reaction target):",
+        SceneReactionTrigger::ConversationContinuing => {
+            "Conversation continuing event (primary reaction target):"
+        }
+        SceneReactionTrigger::SceneEvent { .. } => "Scene event (primary reaction target):",
+        SceneReactionTrigger::ToolResult { .. } => "Tool result event (primary reaction target):",
+        SceneReactionTrigger::Invited { .. } => "Invitation event (primary reaction target):",
+        SceneReactionTrigger::WaitEnded => {
+            "Messages while you waited (newest; primary reaction target):"
+        }
     };
 
     let new_event_section_text = match trigger {
         SceneReactionTrigger::NewMessages => {
             let new_message_event_lines =
-                pending_messages_to_event_lines(worker, pending_messages, person_uuid).await?;
+                pending_messages_to_event_lines(worker, pending_messages, person_uuid, &knowledge)
+                    .await?;
             if new_message_event_lines.is_empty() {
                 "None.".to_string()
             } else {
@@ -768,6 +903,53 @@ async fn build_reaction_execution_input<
                 scene_name, scene_description
             )
         }
+        SceneReactionTrigger::QuestionIgnored {
+            recipient_person_uuid,
+            question,
+        } => {
+            let recipient_name = worker
+                .get_persons_name(recipient_person_uuid.clone())
+                .await
+                .map_err(Error::FailedToGetPersonsName)?;
+            format!(
+                "In the current scene, you asked {} \"{}\" and they have not answered [IGNORED QUESTION EVENT]",
+                recipient_name.as_str(),
+                question
+            )
+        }
+        SceneReactionTrigger::Arrived { observation } => format!(
+            "You just walked into the current scene. As you walk in:\n{}\n[ARRIVAL EVENT]",
+            observation
+        ),
+        SceneReactionTrigger::ConversationContinuing => {
+            "You have not said or done anything in a while, and you notice the conversation in the current scene continuing around you [CONVERSATION CONTINUING EVENT]".to_string()
+        }
+        SceneReactionTrigger::SceneEvent { description } => format!(
+            "Something just happened in the current scene: {} [SCENE EVENT]",
+            description
+        ),
+        SceneReactionTrigger::ToolResult { observation } => {
+            format!("{}\n[TOOL RESULT EVENT]", observation)
+        }
+        SceneReactionTrigger::Invited { invitation } => {
+            format!("{} [INVITATION EVENT]", invitation.to_text())
+        }
+        SceneReactionTrigger::WaitEnded => {
+            let new_message_event_lines =
+                pending_messages_to_event_lines(worker, pending_messages, person_uuid, &knowledge)
+                    .await?;
+            if new_message_event_lines.is_empty() {
+                "None.".to_string()
+            } else if wait_coalescing::should_condense(new_message_event_lines.len()) {
+                let summary = worker
+                    .summarize_reaction_events(new_message_event_lines.join("\n"))
+                    .await
+                    .map_err(Error::GetPersonReaction)?;
+                wait_coalescing::condensed_event_text(new_message_event_lines.len(), &summary)
+            } else {
+                new_message_event_lines.join("\n")
+            }
+        }
     };
 
     let description_prefix = match trigger {
@@ -778,6 +960,30 @@ async fn build_reaction_execution_input<
         SceneReactionTrigger::SceneDescriptionGaze => {
             Some(format!("Scene gaze event:\n{}", new_event_section_text))
         }
+        SceneReactionTrigger::QuestionIgnored { .. } => Some(format!(
+            "Ignored question event:\n{}",
+            new_event_section_text
+        )),
+        SceneReactionTrigger::Arrived { .. } => {
+            Some(format!("Arrival event:\n{}", new_event_section_text))
+        }
+        SceneReactionTrigger::ConversationContinuing => Some(format!(
+            "Conversation continuing event:\n{}",
+            new_event_section_text
+        )),
+        SceneReactionTrigger::SceneEvent { .. } => {
+            Some(format!("Scene event:\n{}", new_event_section_text))
+        }
+        SceneReactionTrigger::ToolResult { .. } => {
+            Some(format!("Tool result event:\n{}", new_event_section_text))
+        }
+        SceneReactionTrigger::Invited { .. } => {
+            Some(format!("Invitation event:\n{}", new_event_section_text))
+        }
+        SceneReactionTrigger::WaitEnded => Some(format!(
+            "Messages while waiting:\n{}",
+            new_event_section_text
+        )),
     };
 
     let reaction_situation = format!(
@@ -794,47 +1000,96 @@ async fn build_reaction_execution_input<
         reflection_input,
         reaction_situation,
         description_prefix,
+        knowledge,
     })
 }
 
-async fn build_scene_situation<W: SceneCapability + PersonCapability>(
+async fn load_knowledge_boundary<W: SceneCapability + PersonCapability>(
+    worker: &W,
+    person_uuid: &PersonUuid,
+) -> Result<KnowledgeBoundary, Error> {
+    let (person_name, attendance) = tokio::try_join!(
+        async {
+            worker
+                .get_persons_name(person_uuid.clone())
+                .await
+                .map_err(Error::FailedToGetPersonsName)
+        },
+        async {
+            worker
+                .get_persons_attendance(person_uuid)
+                .await
+                .map_err(Error::GetAttendance)
+        },
+    )?;
+
+    Ok(KnowledgeBoundary::new(person_name.to_string(), attendance))
+}
+
+async fn build_scene_situation<W: SceneCapability + PersonCapability + PersonIdentityCapability>(
     worker: &W,
     scene_uuid: &SceneUuid,
     messages: &[Message],
     person_uuid: &PersonUuid,
     include_scene_context: bool,
+    knowledge: &KnowledgeBoundary,
 ) -> Result<Situation, Error> {
-    let person_name = worker
-        .get_persons_name(person_uuid.clone())
-        .await
-        .map_err(Error::FailedToGetPersonsName)?;
+    let (person_name, participants, (scene_name, scene_description), world_time, ambience) = tokio::try_join!(
+        async {
+            worker
+                .get_persons_name(person_uuid.clone())
+                .await
+                .map_err(Error::FailedToGetPersonsName)
+        },
+        async {
+            worker
+                .get_scene_current_participants(scene_uuid)
+                .await
+                .map_err(|err| Error::FailedToGetSceneParticipants {
+                    scene_uuid: scene_uuid.clone(),
+                    details: err,
+                })
+        },
+        async {
+            if !include_scene_context {
+                return Ok((None, None));
+            }
 
-    let participants = worker
-        .get_scene_current_participants(scene_uuid)
-        .await
-        .map_err(|err| Error::FailedToGetSceneParticipants {
-            scene_uuid: scene_uuid.clone(),
-            details: err,
-        })?;
+            let scene_name = worker
+                .get_scene_name(scene_uuid)
+                .await
+                .map_err(Error::GetPersonReaction)?;
+            let scene_description = worker
+                .get_scene_description(scene_uuid)
+                .await
+                .map_err(Error::GetPersonReaction)?;
+            Ok((scene_name, scene_description))
+        },
+        async {
+            worker
+                .get_scene_world_time(scene_uuid)
+                .await
+                .map_err(Error::GetPersonReaction)
+        },
+        async {
+            worker
+                .get_scene_ambience(scene_uuid)
+                .await
+                .map_err(Error::GetPersonReaction)
+        },
+    )?;
 
     let participant_names = participants
         .iter()
         .map(|participant| participant.person_name.to_string())
         .collect::<Vec<String>>();
 
-    let (scene_name, scene_description) = if include_scene_context {
-        let scene_name = worker
-            .get_scene_name(scene_uuid)
-            .await
-            .map_err(Error::GetPersonReaction)?;
-        let scene_description = worker
-            .get_scene_description(scene_uuid)
-            .await
-            .map_err(Error::GetPersonReaction)?;
-        (scene_name, scene_description)
-    } else {
-        (None, None)
-    };
+    let other_person_uuids = SceneParticipant::other_persons(&participants, person_uuid);
+
+    let directory = worker
+        .get_person_directory(person_uuid, &other_person_uuids)
+        .await
+        .map_err(Error::GetPersonDirectory)?;
 
     let mut lines = Vec::new();
     for message in messages {
@@ -852,7 +1107,7 @@ async fn build_scene_situation<W: SceneCapability + PersonCapability>(
                     })?
                     .to_string()
             }
-            MessageSender::RealWorldUser => "Chadtech".to_string(),
+            MessageSender::RealWorldUser => REAL_WORLD_USER_NAME.to_string(),
         };
 
         lines.push(format!(
@@ -867,7 +1122,11 @@ async fn build_scene_situation<W: SceneCapability + PersonCapability>(
         scene_name,
         scene_description,
         particpants: participant_names,
+        directory,
+        knowledge: Some(knowledge.to_prompt_text()),
         messages: lines,
+        world_time,
+        ambience,
     });
 
     Ok(situation)
@@ -882,17 +1141,30 @@ fn filter_reaction_events(events: Vec<Event>, messages: &[Message]) -> Vec<Event
     events
         .into_iter()
         .filter(|event| match &event.event_type {
-            EventType::Said { message_uuid, .. } => !message_ids.contains(message_uuid),
+            EventType::Said { message_uuid, .. } | EventType::Overheard { message_uuid, .. } => {
+                !message_ids.contains(message_uuid)
+            }
             _ => true,
         })
         .collect()
 }
 
-async fn pending_messages_to_event_lines<W: PersonCapability + Sync>(
+async fn pending_messages_to_event_lines<W: PersonCapability + MessageCapability + Sync>(
     worker: &W,
     pending_messages: &[Message],
     person_uuid: &PersonUuid,
+    knowledge: &KnowledgeBoundary,
 ) -> Result<Vec<String>, Error> {
+    let quotes = worker
+        .get_message_quotes(
+            pending_messages
+                .iter()
+                .map(|message| message.uuid.clone())
+                .collect(),
+        )
+        .await
+        .map_err(Error::FailedToGetMessageQuotes)?;
+
     let mut lines = Vec::new();
 
     for message in pending_messages {
@@ -910,20 +1182,32 @@ async fn pending_messages_to_event_lines<W: PersonCapability + Sync>(
                     })?
                     .to_string()
             }
-            MessageSender::RealWorldUser => "Chadtech".to_string(),
+            MessageSender::RealWorldUser => REAL_WORLD_USER_NAME.to_string(),
+        };
+
+        let quote_text = match quotes.get(&message.uuid) {
+            Some(quote) if knowledge.was_present(&message.scene_uuid, quote.sent_at) => {
+                format!(" ({})", quote.to_prompt_text())
+            }
+            Some(quote) => format!(
+                " ({})",
+                quote.to_unheard_prompt_text(knowledge.person_name())
+            ),
+            None => String::new(),
         };
 
         lines.push(format!(
-            "In the current scene, {} said: \"{}\" [NEW MESSAGE EVENT]",
+            "In the current scene, {} said: \"{}\"{} [NEW MESSAGE EVENT]",
             sender_label,
-            normalize_message_content(&message.content)
+            normalize_message_content(&message.content),
+            quote_text
         ));
     }
 
     Ok(lines)
 }
 
-async fn apply_reflection_changes<
+pub(crate) async fn apply_reflection_changes<
     W: StateOfMindCapability + MemoryCapability + LogEventCapability + MotivationCapability,
 >(
     worker: &W,
@@ -1034,11 +1318,6 @@ async fn build_reflection_input<
     situation: &Situation,
     person_uuid: &PersonUuid,
 ) -> Result<ReflectionInput, Error> {
-    let persons_name: PersonName = worker
-        .get_persons_name(person_uuid.clone())
-        .await
-        .map_err(Error::FailedToGetPersonsName)?;
-
     let get_args: capability::event::GetArgs = match &message_type_args {
         MessageTypeArgs::SceneByUuid { scene_uuid } => capability::event::GetArgs::new()
             .with_person_uuid(person_uuid.clone())
@@ -1051,19 +1330,72 @@ async fn build_reflection_input<
         }
     };
 
-    let events = worker
-        .get_events(get_args)
-        .await
-        .map_err(Error::FailedToGetEvents)?
+    let scene_uuid = match &message_type_args {
+        MessageTypeArgs::SceneByUuid { scene_uuid } => Some(scene_uuid.clone()),
+        MessageTypeArgs::Scene { .. } => None,
+        MessageTypeArgs::Direct { .. } => None,
+    };
+
+    let (persons_name, events, maybe_state_of_mind, maybe_person_identity, participants, retrieval): (
+        PersonName,
+        Vec<Event>,
+        Option<StateOfMind>,
+        Option<String>,
+        Vec<PersonUuid>,
+        MemoryRetrieval,
+    ) = tokio::try_join!(
+        async {
+            worker
+                .get_persons_name(person_uuid.clone())
+                .await
+                .map_err(Error::FailedToGetPersonsName)
+        },
+        async {
+            worker
+                .get_events(get_args)
+                .await
+                .map_err(Error::FailedToGetEvents)
+        },
+        async {
+            worker
+                .get_latest_state_of_mind(person_uuid)
+                .await
+                .map_err(Error::FailedToGetStateOfMind)
+        },
+        async {
+            worker
+                .get_person_identity_summary(person_uuid)
+                .await
+                .map_err(Error::FailedToGetPersonIdentity)
+        },
+        async {
+            let scene_uuid = match &scene_uuid {
+                Some(scene_uuid) => scene_uuid,
+                None => return Ok(vec![]),
+            };
+
+            let participants = worker
+                .get_scene_current_participants(scene_uuid)
+                .await
+                .map_err(|err| Error::FailedToGetSceneParticipants {
+                    scene_uuid: scene_uuid.clone(),
+                    details: err,
+                })?;
+            Ok(SceneParticipant::other_persons(&participants, person_uuid))
+        },
+        async {
+            worker
+                .get_memory_retrieval()
+                .await
+                .map_err(Error::FailedToSearchMemories)
+        },
+    )?;
+
+    let events = events
         .iter()
         .map(|event| event.to_text())
         .collect::<Vec<String>>();
 
-    let maybe_state_of_mind: Option<StateOfMind> = worker
-        .get_latest_state_of_mind(person_uuid)
-        .await
-        .map_err(Error::FailedToGetStateOfMind)?;
-
     let state_of_mind: StateOfMind = match maybe_state_of_mind {
         Some(som) => som,
         None => Err(Error::NoStateOfMindFound {
@@ -1082,18 +1414,18 @@ async fn build_reflection_input<
         .await
         .map_err(Error::CouldNotCreateMemoriesPrompt)?;
 
-    let memories: Vec<Memory> = crate::domain::memory::filter_memory_results(
+    let memories: Vec<Memory> = retrieval.filter(
         worker
-            .search_memories(person_uuid.clone(), memories_prompt.prompt, 5)
+            .search_memories(
+                person_uuid.clone(),
+                memories_prompt.prompt,
+                &participants,
+                &retrieval,
+            )
             .await
             .map_err(Error::FailedToSearchMemories)?,
     );
 
-    let maybe_person_identity: Option<String> = worker
-        .get_person_identity_summary(person_uuid)
-        .await
-        .map_err(Error::FailedToGetPersonIdentity)?;
-
     let person_identity: String = match maybe_person_identity {
         Some(identity) => identity,
         None => Err(Error::NoPersonIdentityFound {
@@ -1113,6 +1445,7 @@ async fn get_recent_events_text<W: EventCapability>(
     worker: &W,
     message_type_args: MessageTypeArgs,
     person_uuid: &PersonUuid,
+    knowledge: &KnowledgeBoundary,
 ) -> Result<Vec<Event>, Error> {
     let get_args: capability::event::GetArgs = match &message_type_args {
         MessageTypeArgs::SceneByUuid { .. } => {
@@ -1126,20 +1459,29 @@ async fn get_recent_events_text<W: EventCapability>(
         }
     };
 
-    worker
+    let events = worker
         .get_events(get_args)
         .await
-        .map_err(Error::FailedToGetEvents)
+        .map_err(Error::FailedToGetEvents)?;
+
+    Ok(knowledge.retain_known(events))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::capability::clock::ClockCapability;
     use crate::capability::event::GetArgs;
+    use crate::capability::expected_reply::{
+        ExpectedReplyCapability, ExpectedReplyOutcome, NewExpectedReply,
+    };
+    use crate::capability::item::{ItemCapability, NewItem};
     use crate::capability::job::JobCapability;
+    use crate::capability::llm_batch::LlmBatchCapability;
     use crate::capability::log_event::LogEventCapability;
     use crate::capability::logging::LogCapability;
     use crate::capability::memory::{MemoryCapability, MemoryQueryPrompt, MemorySearchResult};
+    use crate::capability::moderation::{BlockedContent, ModerationCapability};
     use crate::capability::motivation::{MotivationCapability, NewMotivation};
     use crate::capability::person::{NewPerson, PersonCapability};
     use crate::capability::person_identity::{NewPersonIdentity, PersonIdentityCapability};
@@ -1151,27 +1493,44 @@ mod tests {
         CurrentScene, NewScene, NewSceneSnapshot, Scene, SceneCapability, SceneParticipant,
         SceneParticipation,
     };
+    use crate::capability::scene_invitation::NewSceneInvitation;
     use crate::capability::state_of_mind::{NewStateOfMind, StateOfMindCapability};
     use crate::domain::actor_uuid::ActorUuid;
     use crate::domain::event::{Event, EventType};
+    use crate::domain::fan_out::QueuePressure;
+    use crate::domain::item::Item;
+    use crate::domain::item_uuid::ItemUuid;
     use crate::domain::job::process_message::ProcessMessageJob;
     use crate::domain::job::JobKind;
+    use crate::domain::job_event::{JobEvent, NewJobEvent};
+    use crate::domain::job_uuid::JobUuid;
+    use crate::domain::knowledge_boundary::Attendance;
     use crate::domain::logger::Level;
     use crate::domain::memory_uuid::MemoryUuid;
+    use crate::domain::message_audience::{HearingRadius, MessageAudience};
+    use crate::domain::message_quote::MessageQuote;
     use crate::domain::message_uuid::MessageUuid;
+    use crate::domain::moderation::ModerationVerdict;
     use crate::domain::motivation::Motivation;
     use crate::domain::motivation_uuid::MotivationUuid;
+    use crate::domain::person_directory::PersonDirectoryEntry;
     use crate::domain::person_identity_uuid::PersonIdentityUuid;
     use crate::domain::person_task::{
         PersonTask, PersonTaskOutcomeCheck, PersonTaskTerminalOutcome,
     };
     use crate::domain::person_task_uuid::PersonTaskUuid;
+    use crate::domain::scene_ambience::Ambience;
+    use crate::domain::scene_invitation::SceneInvitation;
+    use crate::domain::scene_invitation_uuid::SceneInvitationUuid;
     use crate::domain::scene_participant_uuid::SceneParticipantUuid;
+    use crate::domain::world_time::{TimeOfDay, WorldTime};
     use crate::nice_display::NiceDisplay;
+    use crate::open_ai::batch::{Batch, BatchRequest, BatchResult, BatchStatus};
     use crate::person_actions::{PersonAction, PersonReaction, ReflectionDecision};
     use async_trait::async_trait;
     use chrono::{Duration, Utc};
     use serde_json::Value;
+    use std::collections::HashMap;
     use std::sync::Arc;
     use tokio::sync::Mutex;
 
@@ -1244,8 +1603,12 @@ mod tests {
                         ),
                     ],
                     search_results: vec![MemorySearchResult {
+                        memory_uuid: MemoryUuid::new(),
                         content: "Alice trusts Bob when he sounds urgent.".to_string(),
                         distance: 0.2,
+                        mentions_participant: false,
+                        created_at: Utc::now(),
+                        score: 0.2,
                     }],
                     summarize_inputs: vec![],
                     preview_situations: vec![],
@@ -1262,6 +1625,9 @@ mod tests {
                         action: PersonAction::SayInScene {
                             comment: "On my way.".to_string(),
                             destination_scene_name: None,
+                            addressed_to: vec![],
+                            whisper: false,
+                            quote: None,
                         },
                         reflection: ReflectionDecision::NoReflection,
                     },
@@ -1296,6 +1662,22 @@ mod tests {
             Ok(())
         }
 
+        async fn set_message_audience(
+            &self,
+            _message_uuid: &MessageUuid,
+            _audience: &MessageAudience,
+        ) -> Result<(), String> {
+            Ok(())
+        }
+
+        async fn add_scene_message_overhearers(
+            &self,
+            _message_uuid: &MessageUuid,
+            _overhearers: Vec<PersonUuid>,
+        ) -> Result<(), String> {
+            Ok(())
+        }
+
         async fn get_messages_in_scene_page(
             &self,
             _scene_uuid: &SceneUuid,
@@ -1330,6 +1712,21 @@ mod tests {
             state.handled_message_ids.push(message_uuids);
             Ok(())
         }
+
+        async fn set_message_quote(
+            &self,
+            _message_uuid: &MessageUuid,
+            _quote: &MessageQuote,
+        ) -> Result<(), String> {
+            Ok(())
+        }
+
+        async fn get_message_quotes(
+            &self,
+            _message_uuids: Vec<MessageUuid>,
+        ) -> Result<HashMap<MessageUuid, MessageQuote>, String> {
+            Ok(HashMap::new())
+        }
     }
 
     #[async_trait]
@@ -1423,6 +1820,19 @@ mod tests {
             Ok(vec![])
         }
 
+        async fn get_persons_attendance(
+            &self,
+            _person_uuid: &PersonUuid,
+        ) -> Result<Vec<Attendance>, String> {
+            let state = self.state.lock().await;
+            Ok(vec![Attendance {
+                scene_uuid: state.scene_uuid.clone(),
+                scene_name: "Cafe".to_string(),
+                joined_at: Utc::now() - Duration::days(1),
+                left_at: None,
+            }])
+        }
+
         async fn set_real_world_user_in_scene(
             &self,
             _scene_uuid: &SceneUuid,
@@ -1438,6 +1848,21 @@ mod tests {
             Ok(false)
         }
 
+        async fn get_scene_hearing_radius(
+            &self,
+            _scene_uuid: &SceneUuid,
+        ) -> Result<HearingRadius, String> {
+            Ok(HearingRadius::Room)
+        }
+
+        async fn set_scene_hearing_radius(
+            &self,
+            _scene_uuid: &SceneUuid,
+            _hearing_radius: HearingRadius,
+        ) -> Result<(), String> {
+            Ok(())
+        }
+
         async fn get_scene_name(&self, scene_uuid: &SceneUuid) -> Result<Option<String>, String> {
             let state = self.state.lock().await;
             if scene_uuid.to_uuid() == state.scene_uuid.to_uuid() {
@@ -1458,6 +1883,40 @@ mod tests {
                 Ok(None)
             }
         }
+
+        async fn set_scene_time_of_day(
+            &self,
+            _scene_uuid: &SceneUuid,
+            _time_of_day: TimeOfDay,
+        ) -> Result<(), String> {
+            Ok(())
+        }
+
+        async fn get_scene_ambience(
+            &self,
+            _scene_uuid: &SceneUuid,
+        ) -> Result<Option<Ambience>, String> {
+            Ok(None)
+        }
+
+        async fn set_scene_ambience(
+            &self,
+            _scene_uuid: &SceneUuid,
+            _ambience: Ambience,
+        ) -> Result<(), String> {
+            Ok(())
+        }
+
+        async fn get_scene_world_time(&self, _scene_uuid: &SceneUuid) -> Result<WorldTime, String> {
+            Ok(WorldTime::new(0, 0))
+        }
+
+        async fn get_scene_participant_chattiness(
+            &self,
+            _scene_uuid: &SceneUuid,
+        ) -> Result<Vec<(PersonUuid, f64)>, String> {
+            Ok(vec![])
+        }
     }
 
     impl ReactionCapability for MockWorker {
@@ -1480,6 +1939,7 @@ mod tests {
                 thinking_user_prompt: "user".to_string(),
                 action_system_prompt: "action-sys".to_string(),
                 action_user_prompt: "action-user".to_string(),
+                context_timings: Default::default(),
             })
         }
 
@@ -1598,11 +2058,29 @@ mod tests {
             &self,
             _person_uuid: PersonUuid,
             _query: String,
-            _limit: i64,
+            _participants: &[PersonUuid],
+            _retrieval: &crate::domain::memory::MemoryRetrieval,
         ) -> Result<Vec<MemorySearchResult>, String> {
             let state = self.state.lock().await;
             Ok(state.search_results.clone())
         }
+
+        async fn get_memory_retrieval(
+            &self,
+        ) -> Result<crate::domain::memory::MemoryRetrieval, String> {
+            Ok(crate::domain::memory::MemoryRetrieval::default())
+        }
+
+        async fn set_memory_retrieval(
+            &self,
+            _retrieval: &crate::domain::memory::MemoryRetrieval,
+        ) -> Result<(), String> {
+            Ok(())
+        }
+
+        async fn delete_memory(&self, _memory_uuid: &MemoryUuid) -> Result<(), String> {
+            Ok(())
+        }
     }
 
     impl PersonCapability for MockWorker {
@@ -1659,6 +2137,10 @@ mod tests {
             let state = self.state.lock().await;
             Ok(state.is_enabled)
         }
+
+        async fn archive_person(&self, _person_uuid: &PersonUuid) -> Result<(), String> {
+            Ok(())
+        }
     }
 
     impl EventCapability for MockWorker {
@@ -1719,6 +2201,41 @@ mod tests {
             let state = self.state.lock().await;
             Ok(state.person_identity_summary.clone())
         }
+
+        async fn get_person_directory(
+            &self,
+            _viewer: &PersonUuid,
+            _others: &[PersonUuid],
+        ) -> Result<Vec<PersonDirectoryEntry>, String> {
+            Ok(vec![])
+        }
+
+        async fn set_person_identity_summary(
+            &self,
+            _person_identity_uuid: &PersonIdentityUuid,
+            _summary: &str,
+        ) -> Result<(), String> {
+            Ok(())
+        }
+    }
+
+    impl LlmBatchCapability for MockWorker {
+        async fn submit_llm_batch(&self, _requests: Vec<BatchRequest>) -> Result<String, String> {
+            Ok("batch_test".to_string())
+        }
+
+        async fn refresh_llm_batch(&self, batch_id: &str) -> Result<Batch, String> {
+            Ok(Batch {
+                id: batch_id.to_string(),
+                status: BatchStatus::InProgress,
+                output_file_id: None,
+                error_file_id: None,
+            })
+        }
+
+        async fn get_llm_batch_results(&self, _file_id: &str) -> Result<Vec<BatchResult>, String> {
+            Ok(vec![])
+        }
     }
 
     impl ReflectionCapability for MockWorker {
@@ -1734,6 +2251,97 @@ mod tests {
         }
     }
 
+    impl ModerationCapability for MockWorker {
+        async fn moderate_content(
+            &self,
+            _sender: &MessageSender,
+            _scene_uuid: &SceneUuid,
+            _content: &str,
+        ) -> Result<ModerationVerdict, String> {
+            Ok(ModerationVerdict::Allowed)
+        }
+
+        async fn get_blocked_content(&self, _limit: i64) -> Result<Vec<BlockedContent>, String> {
+            Ok(vec![])
+        }
+
+        async fn get_moderation_threshold(&self) -> Result<f64, String> {
+            Ok(1.0)
+        }
+
+        async fn set_moderation_threshold(&self, _threshold: f64) -> Result<(), String> {
+            Ok(())
+        }
+    }
+
+    impl SceneInvitationCapability for MockWorker {
+        async fn create_scene_invitation(
+            &self,
+            _new_scene_invitation: NewSceneInvitation,
+        ) -> Result<SceneInvitationUuid, String> {
+            Ok(SceneInvitationUuid::new())
+        }
+
+        async fn get_pending_scene_invitations(
+            &self,
+            _person_uuid: &PersonUuid,
+        ) -> Result<Vec<SceneInvitation>, String> {
+            Ok(vec![])
+        }
+
+        async fn respond_to_scene_invitations(
+            &self,
+            _person_uuid: &PersonUuid,
+            _current_scene_uuid: Option<&SceneUuid>,
+        ) -> Result<(), String> {
+            Ok(())
+        }
+    }
+
+    impl ItemCapability for MockWorker {
+        async fn create_item(&self, _new_item: NewItem) -> Result<ItemUuid, String> {
+            Ok(ItemUuid::new())
+        }
+
+        async fn get_items_carried_by(
+            &self,
+            _person_uuid: &PersonUuid,
+        ) -> Result<Vec<Item>, String> {
+            Ok(vec![])
+        }
+
+        async fn get_items_in_scene(&self, _scene_uuid: &SceneUuid) -> Result<Vec<Item>, String> {
+            Ok(vec![])
+        }
+
+        async fn give_item(
+            &self,
+            _item_uuid: &ItemUuid,
+            _giver_person_uuid: &PersonUuid,
+            _recipient_person_uuid: &PersonUuid,
+        ) -> Result<(), String> {
+            Ok(())
+        }
+    }
+
+    impl ExpectedReplyCapability for MockWorker {
+        async fn expect_reply(&self, _new_expected_reply: NewExpectedReply) -> Result<(), String> {
+            Ok(())
+        }
+
+        async fn has_recipient_replied(&self, _message_uuid: &MessageUuid) -> Result<bool, String> {
+            Ok(false)
+        }
+
+        async fn resolve_expected_reply(
+            &self,
+            _message_uuid: &MessageUuid,
+            _outcome: ExpectedReplyOutcome,
+        ) -> Result<(), String> {
+            Ok(())
+        }
+    }
+
     impl LogCapability for MockWorker {
         fn log(&self, _level: Level, _message: &str) {}
     }
@@ -1784,6 +2392,12 @@ mod tests {
         }
     }
 
+    impl ClockCapability for MockWorker {
+        fn now(&self) -> chrono::DateTime<chrono::Utc> {
+            chrono::Utc::now()
+        }
+    }
+
     impl JobCapability for MockWorker {
         async fn unshift_job(&self, job: JobKind) -> Result<(), String> {
             let mut state = self.state.lock().await;
@@ -1791,6 +2405,11 @@ mod tests {
             Ok(())
         }
 
+        async fn enqueue_job(&self, job: JobKind) -> Result<JobUuid, String> {
+            self.unshift_job(job).await?;
+            Ok(JobUuid::new())
+        }
+
         async fn pop_next_job(
             &self,
             _current_active_ms: i64,
@@ -1841,6 +2460,42 @@ mod tests {
         ) -> Result<(), String> {
             Ok(())
         }
+
+        async fn record_job_event(
+            &self,
+            _job_uuid: &crate::domain::job_uuid::JobUuid,
+            _event: &NewJobEvent,
+        ) -> Result<(), String> {
+            Ok(())
+        }
+
+        async fn get_job_events(
+            &self,
+            _job_uuid: &crate::domain::job_uuid::JobUuid,
+        ) -> Result<Vec<JobEvent>, String> {
+            Ok(vec![])
+        }
+
+        async fn get_queue_pressure(&self) -> Result<QueuePressure, String> {
+            Ok(QueuePressure {
+                pending_jobs: 0,
+                active_ms: 0,
+            })
+        }
+
+        async fn get_next_delayed_job_active_ms(
+            &self,
+            _current_active_ms: i64,
+        ) -> Result<Option<i64>, String> {
+            Ok(None)
+        }
+
+        async fn get_persons_wait_end_active_ms(
+            &self,
+            _person_uuid: &PersonUuid,
+        ) -> Result<Option<i64>, String> {
+            Ok(None)
+        }
     }
 
     #[tokio::test]
@@ -1986,4 +2641,54 @@ mod tests {
         assert_eq!(state.memory_descriptions.len(), 1);
         assert!(state.memory_descriptions[0].contains("Response:\nSpoke in scene: On my way."));
     }
+
+    #[tokio::test]
+    async fn run_scene_reaction_condenses_many_messages_missed_while_waiting() {
+        let worker = MockWorker::new();
+        let mut state = worker.state.lock().await;
+        let alice_uuid = state.alice_uuid.clone();
+        let bob_uuid = state.bob_uuid.clone();
+        let scene_uuid = state.scene_uuid.clone();
+        state.pending_messages = (0..wait_coalescing::VERBATIM_LIMIT + 2)
+            .map(|index| Message {
+                uuid: MessageUuid::new(),
+                sender: MessageSender::AiPerson(bob_uuid.clone()),
+                scene_uuid: scene_uuid.clone(),
+                content: format!("Chatter number {}", index),
+                sent_at: Utc::now(),
+            })
+            .collect();
+        let pending_uuids = state
+            .pending_messages
+            .iter()
+            .map(|message| message.uuid.clone())
+            .collect::<Vec<_>>();
+        drop(state);
+
+        match run_scene_reaction(
+            &worker,
+            &alice_uuid,
+            &scene_uuid,
+            SceneReactionTrigger::WaitEnded,
+            RandomSeed::from_u64(11),
+            120_000,
+        )
+        .await
+        {
+            Ok(()) => {}
+            Err(err) => panic!("wait ended reaction should complete: {}", err.message()),
+        }
+
+        let state = worker.state.lock().await;
+        assert!(state
+            .summarize_inputs
+            .iter()
+            .any(|input| input.contains("Chatter number 0") && input.contains("Chatter number 4")));
+        assert_eq!(state.reaction_situations.len(), 1);
+        let situation = &state.reaction_situations[0];
+        assert!(situation.contains("5 messages came in"));
+        assert!(situation.contains("[MISSED MESSAGES EVENT]"));
+        assert!(!situation.contains("Chatter number 0"));
+        assert_eq!(state.handled_message_ids, vec![pending_uuids]);
+    }
 }
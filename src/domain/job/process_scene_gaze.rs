@@ -1,9 +1,12 @@
 use crate::capability::event::EventCapability;
+use crate::capability::expected_reply::ExpectedReplyCapability;
+use crate::capability::item::ItemCapability;
 use crate::capability::job::JobCapability;
 use crate::capability::log_event::LogEventCapability;
 use crate::capability::logging::LogCapability;
 use crate::capability::memory::MemoryCapability;
 use crate::capability::message::MessageCapability;
+use crate::capability::moderation::ModerationCapability;
 use crate::capability::motivation::MotivationCapability;
 use crate::capability::person::PersonCapability;
 use crate::capability::person_identity::PersonIdentityCapability;
@@ -12,6 +15,7 @@ use crate::capability::reaction::ReactionCapability;
 use crate::capability::reaction_history::ReactionHistoryCapability;
 use crate::capability::reflection::ReflectionCapability;
 use crate::capability::scene::SceneCapability;
+use crate::capability::scene_invitation::SceneInvitationCapability;
 use crate::capability::state_of_mind::StateOfMindCapability;
 use crate::domain::job::process_reaction_common::{self, SceneReactionTrigger};
 use crate::domain::person_uuid::PersonUuid;
@@ -44,6 +48,7 @@ impl ProcessSceneGazeJob {
             + ReactionCapability
             + MemoryCapability
             + MessageCapability
+            + ModerationCapability
             + PersonCapability
             + EventCapability
             + StateOfMindCapability
@@ -54,6 +59,9 @@ impl ProcessSceneGazeJob {
             + LogEventCapability
             + MotivationCapability
             + ReactionHistoryCapability
+            + ExpectedReplyCapability
+            + ItemCapability
+            + SceneInvitationCapability
             + JobCapability
             + Sync,
     >(
@@ -0,0 +1,119 @@
+use crate::capability::event::EventCapability;
+use crate::capability::expected_reply::ExpectedReplyCapability;
+use crate::capability::item::ItemCapability;
+use crate::capability::job::JobCapability;
+use crate::capability::log_event::LogEventCapability;
+use crate::capability::logging::LogCapability;
+use crate::capability::memory::MemoryCapability;
+use crate::capability::message::MessageCapability;
+use crate::capability::moderation::ModerationCapability;
+use crate::capability::motivation::MotivationCapability;
+use crate::capability::person::PersonCapability;
+use crate::capability::person_identity::PersonIdentityCapability;
+use crate::capability::person_task::PersonTaskCapability;
+use crate::capability::reaction::ReactionCapability;
+use crate::capability::reaction_history::ReactionHistoryCapability;
+use crate::capability::reflection::ReflectionCapability;
+use crate::capability::scene::SceneCapability;
+use crate::capability::scene_invitation::SceneInvitationCapability;
+use crate::capability::state_of_mind::StateOfMindCapability;
+use crate::domain::job::process_reaction_common::{self, SceneReactionTrigger};
+use crate::domain::person_uuid::PersonUuid;
+use crate::domain::random_seed::RandomSeed;
+use crate::domain::scene_invitation_uuid::SceneInvitationUuid;
+use crate::nice_display::{with_context, NiceDisplay};
+use serde::{Deserialize, Serialize};
+
+/// Has someone who was just invited to a scene react to the invitation from
+/// wherever they are. Enqueued by the `invite` action.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReactToSceneInvitationJob {
+    /// Who was invited.
+    pub person_uuid: PersonUuid,
+    pub invitation_uuid: SceneInvitationUuid,
+}
+
+pub enum Error {
+    GetInvitations(String),
+    PersonScene(String),
+    Reaction(process_reaction_common::Error),
+}
+
+impl NiceDisplay for Error {
+    fn message(&self) -> String {
+        match self {
+            Error::GetInvitations(details) => {
+                with_context("Could not get the person's pending invitations", details)
+            }
+            Error::PersonScene(details) => {
+                with_context("Could not get the person's current scene", details)
+            }
+            Error::Reaction(err) => err.message(),
+        }
+    }
+}
+
+impl ReactToSceneInvitationJob {
+    pub async fn run<
+        W: SceneCapability
+            + SceneInvitationCapability
+            + ReactionCapability
+            + MemoryCapability
+            + MessageCapability
+            + ModerationCapability
+            + PersonCapability
+            + EventCapability
+            + StateOfMindCapability
+            + PersonIdentityCapability
+            + PersonTaskCapability
+            + ReflectionCapability
+            + LogCapability
+            + LogEventCapability
+            + MotivationCapability
+            + ReactionHistoryCapability
+            + ExpectedReplyCapability
+            + ItemCapability
+            + JobCapability
+            + Sync,
+    >(
+        self,
+        worker: &W,
+        random_seed: RandomSeed,
+        current_active_ms: i64,
+    ) -> Result<(), Error> {
+        // They may have answered it in a reaction since
+        let maybe_invitation = worker
+            .get_pending_scene_invitations(&self.person_uuid)
+            .await
+            .map_err(Error::GetInvitations)?
+            .into_iter()
+            .find(|invitation| invitation.uuid == self.invitation_uuid);
+
+        let invitation = match maybe_invitation {
+            Some(invitation) => invitation,
+            None => return Ok(()),
+        };
+
+        // Reactions happen in a scene. Someone who is not in one sees the
+        // invitation the next time they react anywhere
+        let scene_uuid = match worker
+            .get_persons_current_scene_uuid(&self.person_uuid)
+            .await
+            .map_err(Error::PersonScene)?
+        {
+            Some(scene_uuid) if scene_uuid != invitation.scene_uuid => scene_uuid,
+            _ => return Ok(()),
+        };
+
+        process_reaction_common::run_scene_reaction(
+            worker,
+            &self.person_uuid,
+            &scene_uuid,
+            SceneReactionTrigger::Invited { invitation },
+            random_seed,
+            current_active_ms,
+        )
+        .await
+        .map_err(Error::Reaction)
+    }
+}
@@ -0,0 +1,77 @@
+use crate::capability::acknowledgement::AcknowledgementCapability;
+use crate::capability::logging::LogCapability;
+use crate::domain::acknowledgement::{self, POOL_SIZE};
+use crate::domain::logger::Level;
+use crate::domain::person_uuid::PersonUuid;
+use crate::nice_display::{with_context, NiceDisplay};
+use serde::{Deserialize, Serialize};
+
+/// Tops a person's acknowledgements back up to `POOL_SIZE`, after they used
+/// one or found they had none ready.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RefillAcknowledgementsJob {
+    pub person_uuid: PersonUuid,
+}
+
+pub enum Error {
+    Count(String),
+    Generate(String),
+    Add(String),
+}
+
+impl NiceDisplay for Error {
+    fn message(&self) -> String {
+        match self {
+            Error::Count(details) => {
+                with_context("Could not count the ready acknowledgements", details)
+            }
+            Error::Generate(details) => {
+                with_context("Could not generate acknowledgements", details)
+            }
+            Error::Add(details) => with_context("Could not store the acknowledgements", details),
+        }
+    }
+}
+
+impl RefillAcknowledgementsJob {
+    pub async fn run<W: AcknowledgementCapability + LogCapability>(
+        self,
+        worker: &W,
+    ) -> Result<(), Error> {
+        let ready = worker
+            .count_acknowledgements(&self.person_uuid)
+            .await
+            .map_err(Error::Count)?;
+
+        let missing = POOL_SIZE.saturating_sub(ready);
+        if missing == 0 {
+            return Ok(());
+        }
+
+        let text = worker
+            .generate_acknowledgements(&self.person_uuid, missing)
+            .await
+            .map_err(Error::Generate)?;
+
+        let acknowledgements = acknowledgement::parse(&text)
+            .into_iter()
+            .take(missing)
+            .collect::<Vec<String>>();
+
+        worker
+            .add_acknowledgements(&self.person_uuid, &acknowledgements)
+            .await
+            .map_err(Error::Add)?;
+
+        worker.log(
+            Level::Info,
+            &format!(
+                "Readied {} acknowledgements for person {}",
+                acknowledgements.len(),
+                self.person_uuid.to_uuid()
+            ),
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,378 @@
+use super::{JobKind, ParseError};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::Value;
+
+// The names jobs are stored under in `job.name`. Renaming one strands the
+// jobs already queued under the old name.
+pub const PING: &str = "ping";
+pub const SEND_MESSAGE_TO_SCENE: &str = "send message to scene";
+pub const PROCESS_PERSON_JOIN: &str = "process person join";
+pub const PROCESS_MESSAGE: &str = "process message";
+pub const PROCESS_SCENE_GAZE: &str = "process scene gaze";
+pub const PERSON_WAITING: &str = "person waiting";
+pub const PERSON_HIBERNATING: &str = "person hibernating";
+pub const CHECK_EXPECTED_REPLY: &str = "check expected reply";
+pub const DISPATCH_OUTBOX: &str = "dispatch outbox";
+pub const POLL_LLM_BATCH: &str = "poll llm batch";
+pub const HANDLE_BATCH_COMPLETION: &str = "handle batch completion";
+pub const WAKE_IDLE_PERSONS: &str = "wake idle persons";
+pub const NOTICE_CONVERSATION: &str = "notice conversation";
+pub const CHECK_PERSONA_CONSISTENCY: &str = "check persona consistency";
+pub const CHECK_SCENE_GOALS: &str = "check scene goals";
+pub const CLOSE_SCENE: &str = "close scene";
+pub const ARCHIVE_SCENE: &str = "archive scene";
+pub const TAG_TOPICS: &str = "tag topics";
+pub const CHANGE_SCENE_AMBIENCE: &str = "change scene ambience";
+pub const INJECT_SCENE_EVENTS: &str = "inject scene events";
+pub const REACT_TO_SCENE_EVENT: &str = "react to scene event";
+pub const RUN_CUSTOM_ACTION: &str = "run custom action";
+pub const REFILL_ACKNOWLEDGEMENTS: &str = "refill acknowledgements";
+pub const NIGHTLY_MAINTENANCE: &str = "nightly maintenance";
+pub const USE_TOOL: &str = "use tool";
+pub const REACT_TO_SCENE_INVITATION: &str = "react to scene invitation";
+pub const UPDATE_PERSON_ARCS: &str = "update person arcs";
+pub const BALANCE_CHATTINESS: &str = "balance chattiness";
+
+/// How to read a stored job of one kind back into a `JobKind`.
+pub struct Registration {
+    pub name: &'static str,
+    pub parse: fn(Option<Value>) -> Result<JobKind, ParseError>,
+}
+
+/// Every kind of job. `JobKind::parse` only reads names listed here, so a
+/// new kind needs an entry as well as a `JobKind::name` arm.
+pub static REGISTRY: [Registration; 28] = [
+    Registration {
+        name: PING,
+        parse: |_| Ok(JobKind::Ping),
+    },
+    Registration {
+        name: SEND_MESSAGE_TO_SCENE,
+        parse: |data| from_data(SEND_MESSAGE_TO_SCENE, data).map(JobKind::SendMessageToScene),
+    },
+    Registration {
+        name: PROCESS_PERSON_JOIN,
+        parse: |data| from_data(PROCESS_PERSON_JOIN, data).map(JobKind::ProcessPersonJoin),
+    },
+    Registration {
+        name: PROCESS_MESSAGE,
+        parse: |data| from_data(PROCESS_MESSAGE, data).map(JobKind::ProcessMessage),
+    },
+    Registration {
+        name: PROCESS_SCENE_GAZE,
+        parse: |data| from_data(PROCESS_SCENE_GAZE, data).map(JobKind::ProcessSceneGaze),
+    },
+    Registration {
+        name: PERSON_WAITING,
+        parse: |data| from_data(PERSON_WAITING, data).map(JobKind::PersonWaiting),
+    },
+    Registration {
+        name: PERSON_HIBERNATING,
+        parse: |data| from_data(PERSON_HIBERNATING, data).map(JobKind::PersonHibernating),
+    },
+    Registration {
+        name: CHECK_EXPECTED_REPLY,
+        parse: |data| from_data(CHECK_EXPECTED_REPLY, data).map(JobKind::CheckExpectedReply),
+    },
+    Registration {
+        name: DISPATCH_OUTBOX,
+        parse: |data| from_data(DISPATCH_OUTBOX, data).map(JobKind::DispatchOutbox),
+    },
+    Registration {
+        name: POLL_LLM_BATCH,
+        parse: |data| from_data(POLL_LLM_BATCH, data).map(JobKind::PollLlmBatch),
+    },
+    Registration {
+        name: HANDLE_BATCH_COMPLETION,
+        parse: |data| from_data(HANDLE_BATCH_COMPLETION, data).map(JobKind::HandleBatchCompletion),
+    },
+    Registration {
+        name: WAKE_IDLE_PERSONS,
+        parse: |_| Ok(JobKind::WakeIdlePersons),
+    },
+    Registration {
+        name: NOTICE_CONVERSATION,
+        parse: |data| from_data(NOTICE_CONVERSATION, data).map(JobKind::NoticeConversation),
+    },
+    Registration {
+        name: CHECK_PERSONA_CONSISTENCY,
+        parse: |data| {
+            from_data(CHECK_PERSONA_CONSISTENCY, data).map(JobKind::CheckPersonaConsistency)
+        },
+    },
+    Registration {
+        name: CHECK_SCENE_GOALS,
+        parse: |_| Ok(JobKind::CheckSceneGoals),
+    },
+    Registration {
+        name: CLOSE_SCENE,
+        parse: |data| from_data(CLOSE_SCENE, data).map(JobKind::CloseScene),
+    },
+    Registration {
+        name: ARCHIVE_SCENE,
+        parse: |data| from_data(ARCHIVE_SCENE, data).map(JobKind::ArchiveScene),
+    },
+    Registration {
+        name: TAG_TOPICS,
+        parse: |_| Ok(JobKind::TagTopics),
+    },
+    Registration {
+        name: CHANGE_SCENE_AMBIENCE,
+        parse: |data| from_data(CHANGE_SCENE_AMBIENCE, data).map(JobKind::ChangeSceneAmbience),
+    },
+    Registration {
+        name: INJECT_SCENE_EVENTS,
+        parse: |_| Ok(JobKind::InjectSceneEvents),
+    },
+    Registration {
+        name: REACT_TO_SCENE_EVENT,
+        parse: |data| from_data(REACT_TO_SCENE_EVENT, data).map(JobKind::ReactToSceneEvent),
+    },
+    Registration {
+        name: RUN_CUSTOM_ACTION,
+        parse: |data| from_data(RUN_CUSTOM_ACTION, data).map(JobKind::RunCustomAction),
+    },
+    Registration {
+        name: REFILL_ACKNOWLEDGEMENTS,
+        parse: |data| from_data(REFILL_ACKNOWLEDGEMENTS, data).map(JobKind::RefillAcknowledgements),
+    },
+    Registration {
+        name: NIGHTLY_MAINTENANCE,
+        parse: |_| Ok(JobKind::NightlyMaintenance),
+    },
+    Registration {
+        name: USE_TOOL,
+        parse: |data| from_data(USE_TOOL, data).map(JobKind::UseTool),
+    },
+    Registration {
+        name: REACT_TO_SCENE_INVITATION,
+        parse: |data| {
+            from_data(REACT_TO_SCENE_INVITATION, data).map(JobKind::ReactToSceneInvitation)
+        },
+    },
+    Registration {
+        name: UPDATE_PERSON_ARCS,
+        parse: |_| Ok(JobKind::UpdatePersonArcs),
+    },
+    Registration {
+        name: BALANCE_CHATTINESS,
+        parse: |_| Ok(JobKind::BalanceChattiness),
+    },
+];
+
+pub fn find(name: &str) -> Option<&'static Registration> {
+    REGISTRY
+        .iter()
+        .find(|registration| registration.name == name)
+}
+
+pub fn to_data<T: Serialize>(name: &str, job: &T) -> Result<Option<Value>, String> {
+    let data = serde_json::to_value(job)
+        .map_err(|err| format!("Failed to serialize a \"{}\" job: {}", name, err))?;
+
+    Ok(Some(data))
+}
+
+fn from_data<T: DeserializeOwned>(name: &str, maybe_data: Option<Value>) -> Result<T, ParseError> {
+    let data = maybe_data.ok_or_else(|| ParseError::NoJobDataForJobThatReuiresIt {
+        job_name: name.to_string(),
+    })?;
+
+    serde_json::from_value(data).map_err(|error| ParseError::FailedToParseJobData {
+        job_name: name.to_string(),
+        details: error.to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::job::archive_scene::ArchiveSceneJob;
+    use crate::domain::job::change_scene_ambience::ChangeSceneAmbienceJob;
+    use crate::domain::job::check_expected_reply::CheckExpectedReplyJob;
+    use crate::domain::job::check_persona_consistency::CheckPersonaConsistencyJob;
+    use crate::domain::job::close_scene::CloseSceneJob;
+    use crate::domain::job::dispatch_outbox::DispatchOutboxJob;
+    use crate::domain::job::handle_batch_completion::HandleBatchCompletionJob;
+    use crate::domain::job::notice_conversation::NoticeConversationJob;
+    use crate::domain::job::person_hibernating::PersonHibernatingJob;
+    use crate::domain::job::person_waiting::PersonWaitingJob;
+    use crate::domain::job::poll_llm_batch::PollLlmBatchJob;
+    use crate::domain::job::process_message::ProcessMessageJob;
+    use crate::domain::job::process_person_join::ProcessPersonJoinJob;
+    use crate::domain::job::process_scene_gaze::ProcessSceneGazeJob;
+    use crate::domain::job::react_to_scene_event::ReactToSceneEventJob;
+    use crate::domain::job::react_to_scene_invitation::ReactToSceneInvitationJob;
+    use crate::domain::job::refill_acknowledgements::RefillAcknowledgementsJob;
+    use crate::domain::job::run_custom_action::RunCustomActionJob;
+    use crate::domain::job::send_message_to_scene::SendMessageToSceneJob;
+    use crate::domain::job::use_tool::UseToolJob;
+    use crate::domain::llm_batch::BatchHandler;
+    use crate::domain::message::MessageSender;
+    use crate::domain::message_urgency::MessageUrgency;
+    use crate::domain::message_uuid::MessageUuid;
+    use crate::domain::person_identity_uuid::PersonIdentityUuid;
+    use crate::domain::person_uuid::PersonUuid;
+    use crate::domain::random_seed::RandomSeed;
+    use crate::domain::scene_ambience::{Ambience, Lighting, NoiseLevel, Weather};
+    use crate::domain::scene_goal::SceneGoalMet;
+    use crate::domain::scene_invitation_uuid::SceneInvitationUuid;
+    use crate::domain::scene_uuid::SceneUuid;
+    use crate::nice_display::NiceDisplay;
+    use std::collections::HashSet;
+    use uuid::Uuid;
+
+    /// One job of every kind.
+    fn every_kind() -> Vec<JobKind> {
+        let person_uuid = PersonUuid::from_uuid(Uuid::from_u128(1));
+        let other_person_uuid = PersonUuid::from_uuid(Uuid::from_u128(2));
+        let scene_uuid = SceneUuid::from_uuid(Uuid::from_u128(3));
+        let message_uuid = MessageUuid::from_uuid(Uuid::from_u128(4));
+
+        vec![
+            JobKind::Ping,
+            JobKind::SendMessageToScene(SendMessageToSceneJob {
+                sender: MessageSender::AiPerson(person_uuid.clone()),
+                scene_uuid: scene_uuid.clone(),
+                content: "Anybody seen my keys?".to_string(),
+                random_seed: RandomSeed::from_u64(7),
+            }),
+            JobKind::ProcessPersonJoin(ProcessPersonJoinJob {
+                scene_uuid: scene_uuid.clone(),
+                joined_person_uuid: person_uuid.clone(),
+                recipient_person_uuid: other_person_uuid.clone(),
+            }),
+            JobKind::ProcessMessage(ProcessMessageJob {
+                message_uuid: message_uuid.clone(),
+                recipient_person_uuid: other_person_uuid.clone(),
+                run_at_active_ms: Some(5_000),
+                urgency: MessageUrgency::Direct,
+            }),
+            JobKind::ProcessSceneGaze(ProcessSceneGazeJob {
+                scene_uuid: scene_uuid.clone(),
+                gazing_person_uuid: person_uuid.clone(),
+            }),
+            JobKind::PersonWaiting(PersonWaitingJob::new(
+                person_uuid.clone(),
+                60_000,
+                1_000,
+                chrono::Utc::now(),
+            )),
+            JobKind::PersonHibernating(PersonHibernatingJob::new(
+                person_uuid.clone(),
+                60_000,
+                1_000,
+                chrono::Utc::now(),
+            )),
+            JobKind::CheckExpectedReply(CheckExpectedReplyJob {
+                message_uuid,
+                asker_person_uuid: person_uuid.clone(),
+                recipient_person_uuid: other_person_uuid.clone(),
+                scene_uuid: scene_uuid.clone(),
+                question: "Where were you?".to_string(),
+                run_at_active_ms: 9_000,
+            }),
+            JobKind::DispatchOutbox(DispatchOutboxJob::now()),
+            JobKind::PollLlmBatch(PollLlmBatchJob::now("batch_1".to_string())),
+            JobKind::HandleBatchCompletion(HandleBatchCompletionJob {
+                handler: BatchHandler::PersonIdentitySummary {
+                    person_identity_uuid: PersonIdentityUuid::from_uuid(Uuid::from_u128(5)),
+                },
+                content: "A retired mailman.".to_string(),
+            }),
+            JobKind::WakeIdlePersons,
+            JobKind::NoticeConversation(NoticeConversationJob {
+                person_uuid: person_uuid.clone(),
+                scene_uuid: scene_uuid.clone(),
+            }),
+            JobKind::CheckPersonaConsistency(CheckPersonaConsistencyJob { person_uuid }),
+            JobKind::CheckSceneGoals,
+            JobKind::CloseScene(CloseSceneJob {
+                scene_uuid: scene_uuid.clone(),
+                reason: SceneGoalMet::TimeLimit,
+            }),
+            JobKind::ArchiveScene(ArchiveSceneJob {
+                scene_uuid: scene_uuid.clone(),
+            }),
+            JobKind::TagTopics,
+            JobKind::ChangeSceneAmbience(ChangeSceneAmbienceJob {
+                scene_uuid: scene_uuid.clone(),
+                ambience: Ambience {
+                    weather: Weather::Storm,
+                    noise: NoiseLevel::Loud,
+                    lighting: Lighting::Dark,
+                },
+                run_at_active_ms: Some(12_000),
+            }),
+            JobKind::InjectSceneEvents,
+            JobKind::ReactToSceneEvent(ReactToSceneEventJob {
+                person_uuid: other_person_uuid,
+                scene_uuid,
+                description: "A car alarm goes off outside.".to_string(),
+            }),
+            JobKind::RunCustomAction(RunCustomActionJob {
+                action_name: "pick lock".to_string(),
+                person_uuid: PersonUuid::new(),
+                scene_uuid: None,
+                arguments: serde_json::json!({ "target": "the back door" }),
+            }),
+            JobKind::RefillAcknowledgements(RefillAcknowledgementsJob {
+                person_uuid: PersonUuid::new(),
+            }),
+            JobKind::NightlyMaintenance,
+            JobKind::UseTool(UseToolJob {
+                tool_name: "weather".to_string(),
+                person_uuid: PersonUuid::new(),
+                scene_uuid: Some(SceneUuid::new()),
+                arguments: serde_json::json!({ "city": "Tucson" }),
+            }),
+            JobKind::ReactToSceneInvitation(ReactToSceneInvitationJob {
+                person_uuid: PersonUuid::new(),
+                invitation_uuid: SceneInvitationUuid::new(),
+            }),
+            JobKind::UpdatePersonArcs,
+            JobKind::BalanceChattiness,
+        ]
+    }
+
+    #[test]
+    fn test_every_job_kind_round_trips_through_its_name_and_data() {
+        let kinds = every_kind();
+        let names = kinds.iter().map(|kind| kind.name()).collect::<HashSet<_>>();
+
+        assert_eq!(names.len(), kinds.len(), "two kinds share a name");
+        assert_eq!(
+            REGISTRY.len(),
+            kinds.len(),
+            "every kind needs a registration and a sample here"
+        );
+
+        for kind in kinds {
+            let data = kind.to_data().unwrap();
+            let parsed = match JobKind::parse(kind.to_name(), data.clone()) {
+                Ok(parsed) => parsed,
+                Err(err) => panic!("{} did not parse: {}", kind.name(), err.message()),
+            };
+
+            assert_eq!(parsed.name(), kind.name());
+            assert_eq!(parsed.to_data().unwrap(), data);
+        }
+    }
+
+    #[test]
+    fn test_parse_explains_unknown_names_and_missing_data() {
+        assert_eq!(
+            JobKind::parse("send message".to_string(), None)
+                .err()
+                .map(|err| err.message()),
+            Some("Unknown job name: send message".to_string())
+        );
+        assert_eq!(
+            JobKind::parse(PROCESS_MESSAGE.to_string(), None)
+                .err()
+                .map(|err| err.message()),
+            Some("No job data provided for a \"process message\" job that requires it".to_string())
+        );
+    }
+}
@@ -0,0 +1,101 @@
+use crate::capability::custom_action::CustomActionCapability;
+use crate::capability::job::JobCapability;
+use crate::capability::logging::LogCapability;
+use crate::domain::custom_action::{self, CustomActionHandler, CustomActions};
+use crate::domain::job::JobKind;
+use crate::domain::logger::Level;
+use crate::domain::person_uuid::PersonUuid;
+use crate::domain::scene_uuid::SceneUuid;
+use crate::nice_display::{with_context, NiceDisplay};
+use serde::{Deserialize, Serialize};
+
+/// Carries out a custom action a person took, through the handler the custom
+/// actions file gives it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RunCustomActionJob {
+    pub action_name: String,
+    pub person_uuid: PersonUuid,
+    /// Where the person was when they took the action, if anywhere.
+    pub scene_uuid: Option<SceneUuid>,
+    pub arguments: serde_json::Value,
+}
+
+pub enum Error {
+    UnknownAction(String),
+    Webhook(String),
+    ParseJob(String),
+    EnqueueJob(String),
+}
+
+impl NiceDisplay for Error {
+    fn message(&self) -> String {
+        match self {
+            Error::UnknownAction(action_name) => format!(
+                "There is no custom action named \"{}\" anymore",
+                action_name
+            ),
+            Error::Webhook(details) => {
+                with_context("Could not post the custom action to its webhook", details)
+            }
+            Error::ParseJob(details) => {
+                with_context("The custom action's job data is invalid", details)
+            }
+            Error::EnqueueJob(details) => {
+                with_context("Could not enqueue the custom action's job", details)
+            }
+        }
+    }
+}
+
+impl RunCustomActionJob {
+    pub async fn run<W: CustomActionCapability + JobCapability + LogCapability>(
+        self,
+        worker: &W,
+    ) -> Result<(), Error> {
+        let action = CustomActions::registered()
+            .find(&self.action_name)
+            .ok_or_else(|| Error::UnknownAction(self.action_name.clone()))?;
+
+        match &action.handler {
+            CustomActionHandler::Webhook { url } => {
+                worker
+                    .post_custom_action(url, &self.to_webhook_body())
+                    .await
+                    .map_err(Error::Webhook)?;
+            }
+            CustomActionHandler::Job { job_name, data } => {
+                let job_data = custom_action::job_data(
+                    data,
+                    &self.person_uuid,
+                    self.scene_uuid.as_ref(),
+                    &self.arguments,
+                );
+
+                let job = JobKind::parse(job_name.clone(), Some(job_data))
+                    .map_err(|err| Error::ParseJob(err.message()))?;
+
+                worker.unshift_job(job).await.map_err(Error::EnqueueJob)?;
+            }
+        }
+
+        worker.log(
+            Level::Info,
+            &format!(
+                "Ran custom action {} for person {}",
+                self.action_name,
+                self.person_uuid.to_uuid()
+            ),
+        );
+
+        Ok(())
+    }
+
+    fn to_webhook_body(&self) -> serde_json::Value {
+        serde_json::json!({
+            "action": self.action_name,
+            "person_uuid": self.person_uuid.to_uuid(),
+            "scene_uuid": self.scene_uuid.as_ref().map(|scene_uuid| scene_uuid.to_uuid()),
+            "arguments": self.arguments,
+        })
+    }
+}
@@ -1,12 +1,19 @@
+use crate::capability::acknowledgement::AcknowledgementCapability;
 use crate::capability::job::JobCapability;
 use crate::capability::message::MessageCapability;
+use crate::capability::moderation::ModerationCapability;
+use crate::domain::acknowledgement;
 use crate::domain::actor_uuid::ActorUuid;
+use crate::domain::fan_out::{FanOutPolicy, RecipientPlan};
 use crate::domain::job::process_message::ProcessMessageJob;
 use crate::domain::job::JobKind;
+use crate::domain::message_audience::{HearingRadius, MessageAudience};
+use crate::domain::message_urgency::MessageUrgency;
 use crate::domain::message_uuid::MessageUuid;
+use crate::domain::moderation::ModerationVerdict;
 use crate::domain::random_seed::RandomSeed;
 use crate::domain::scene_uuid::SceneUuid;
-use crate::nice_display::NiceDisplay;
+use crate::nice_display::{with_context, NiceDisplay};
 use crate::{capability::scene::SceneCapability, domain::message::MessageSender};
 use rand::seq::SliceRandom;
 use rand::SeedableRng;
@@ -20,7 +27,29 @@ pub struct SendMessageToSceneJob {
     pub random_seed: RandomSeed,
 }
 
+/// Spaces out when recipients react to a message, so a scene does not open
+/// with everyone talking over each other.
+#[derive(Debug, Clone)]
+pub struct ReactionStagger {
+    pub start_active_ms: i64,
+    pub step_ms: i64,
+}
+
+impl ReactionStagger {
+    fn run_at_active_ms(&self, recipient_index: usize) -> i64 {
+        let index = i64::try_from(recipient_index).unwrap_or(i64::MAX);
+        self.start_active_ms
+            .saturating_add(self.step_ms.max(0).saturating_mul(index))
+    }
+}
+
+pub enum SceneMessageOutcome {
+    Sent { message_uuid: MessageUuid },
+    Blocked { categories: Vec<String> },
+}
+
 pub enum Error {
+    Moderation(String),
     GetSceneParticipants {
         scene_uuid: SceneUuid,
         details: String,
@@ -33,64 +62,213 @@ pub enum Error {
         message_uuid: MessageUuid,
         details: String,
     },
+    Audience {
+        message_uuid: MessageUuid,
+        details: String,
+    },
+    Backpressure {
+        message_uuid: MessageUuid,
+        details: String,
+    },
 }
 
 impl NiceDisplay for Error {
     fn message(&self) -> String {
         match self {
+            Error::Moderation(details) => {
+                with_context("Failed to moderate message content", details)
+            }
             Error::GetSceneParticipants {
                 scene_uuid,
                 details,
-            } => {
+            } => with_context(
                 format!(
-                    "Failed to get participants for scene {}: {}",
-                    scene_uuid.to_uuid(),
-                    details
-                )
-            }
+                    "Failed to get participants for scene {}",
+                    scene_uuid.to_uuid()
+                ),
+                details,
+            ),
             Error::SendMessage {
                 participant,
                 details,
-            } => {
+            } => with_context(
                 format!(
-                    "Failed to send message to participant {}: {}",
-                    participant.to_label(),
-                    details
-                )
-            }
+                    "Failed to send message to participant {}",
+                    participant.to_label()
+                ),
+                details,
+            ),
             Error::UnshiftJob {
                 message_uuid,
                 details,
-            } => {
+            } => with_context(
                 format!(
-                    "Failed to unshift process message job for message {}: {}",
-                    message_uuid.to_uuid(),
-                    details
-                )
-            }
+                    "Failed to unshift process message job for message {}",
+                    message_uuid.to_uuid()
+                ),
+                details,
+            ),
+            Error::Audience {
+                message_uuid,
+                details,
+            } => with_context(
+                format!(
+                    "Failed to work out who hears message {}",
+                    message_uuid.to_uuid()
+                ),
+                details,
+            ),
+            Error::Backpressure {
+                message_uuid,
+                details,
+            } => with_context(
+                format!(
+                    "Failed to check the job queue before fanning out message {}",
+                    message_uuid.to_uuid()
+                ),
+                details,
+            ),
         }
     }
 }
 
 impl SendMessageToSceneJob {
-    pub async fn run<W: SceneCapability + MessageCapability + JobCapability>(
+    pub async fn run<
+        W: SceneCapability
+            + MessageCapability
+            + JobCapability
+            + ModerationCapability
+            + AcknowledgementCapability,
+    >(
         self,
         worker: &W,
     ) -> Result<(), Error> {
-        send_scene_message_and_enqueue_recipients(
+        let outcome = send_scene_message_and_enqueue_recipients(
             worker,
-            self.sender,
-            self.scene_uuid,
-            self.content,
+            self.sender.clone(),
+            self.scene_uuid.clone(),
+            self.content.clone(),
             self.random_seed,
         )
         .await?;
 
+        if let (MessageSender::RealWorldUser, SceneMessageOutcome::Sent { .. }) =
+            (&self.sender, outcome)
+        {
+            acknowledgement::send_acknowledgement(worker, &self.scene_uuid, &self.content).await;
+        }
+
         Ok(())
     }
 }
 
 pub async fn send_scene_message_and_enqueue_recipients<
+    W: SceneCapability + MessageCapability + JobCapability + ModerationCapability,
+>(
+    worker: &W,
+    sender: MessageSender,
+    scene_uuid: SceneUuid,
+    content: String,
+    random_seed: RandomSeed,
+) -> Result<SceneMessageOutcome, Error> {
+    send_and_enqueue(
+        worker,
+        sender,
+        scene_uuid,
+        content,
+        random_seed,
+        MessageAudience::Everyone,
+        None,
+    )
+    .await
+}
+
+/// Like `send_scene_message_and_enqueue_recipients`, but only the people the
+/// audience names get the message. Anyone else in the scene may overhear it,
+/// depending on the scene's hearing radius.
+pub async fn send_scene_message_to_audience<
+    W: SceneCapability + MessageCapability + JobCapability + ModerationCapability,
+>(
+    worker: &W,
+    sender: MessageSender,
+    scene_uuid: SceneUuid,
+    content: String,
+    random_seed: RandomSeed,
+    audience: MessageAudience,
+) -> Result<SceneMessageOutcome, Error> {
+    send_and_enqueue(
+        worker,
+        sender,
+        scene_uuid,
+        content,
+        random_seed,
+        audience,
+        None,
+    )
+    .await
+}
+
+/// Like `send_scene_message_and_enqueue_recipients`, but each recipient's
+/// reaction is held back a little longer than the one before it.
+pub async fn send_scene_message_with_staggered_reactions<
+    W: SceneCapability + MessageCapability + JobCapability + ModerationCapability,
+>(
+    worker: &W,
+    sender: MessageSender,
+    scene_uuid: SceneUuid,
+    content: String,
+    random_seed: RandomSeed,
+    stagger: ReactionStagger,
+) -> Result<SceneMessageOutcome, Error> {
+    send_and_enqueue(
+        worker,
+        sender,
+        scene_uuid,
+        content,
+        random_seed,
+        MessageAudience::Everyone,
+        Some(stagger),
+    )
+    .await
+}
+
+async fn send_and_enqueue<
+    W: SceneCapability + MessageCapability + JobCapability + ModerationCapability,
+>(
+    worker: &W,
+    sender: MessageSender,
+    scene_uuid: SceneUuid,
+    content: String,
+    random_seed: RandomSeed,
+    audience: MessageAudience,
+    stagger: Option<ReactionStagger>,
+) -> Result<SceneMessageOutcome, Error> {
+    let verdict = worker
+        .moderate_content(&sender, &scene_uuid, content.as_str())
+        .await
+        .map_err(Error::Moderation)?;
+
+    if let ModerationVerdict::Blocked { categories } = verdict {
+        return Ok(SceneMessageOutcome::Blocked { categories });
+    }
+
+    let message_uuid = deliver_and_enqueue(
+        worker,
+        sender,
+        scene_uuid,
+        content,
+        random_seed,
+        audience,
+        stagger,
+    )
+    .await?;
+
+    Ok(SceneMessageOutcome::Sent { message_uuid })
+}
+
+/// Like `send_scene_message_and_enqueue_recipients`, for content that has
+/// already been through moderation.
+pub async fn send_moderated_scene_message<
     W: SceneCapability + MessageCapability + JobCapability,
 >(
     worker: &W,
@@ -98,6 +276,27 @@ pub async fn send_scene_message_and_enqueue_recipients<
     scene_uuid: SceneUuid,
     content: String,
     random_seed: RandomSeed,
+) -> Result<MessageUuid, Error> {
+    deliver_and_enqueue(
+        worker,
+        sender,
+        scene_uuid,
+        content,
+        random_seed,
+        MessageAudience::Everyone,
+        None,
+    )
+    .await
+}
+
+async fn deliver_and_enqueue<W: SceneCapability + MessageCapability + JobCapability>(
+    worker: &W,
+    sender: MessageSender,
+    scene_uuid: SceneUuid,
+    content: String,
+    random_seed: RandomSeed,
+    audience: MessageAudience,
+    stagger: Option<ReactionStagger>,
 ) -> Result<MessageUuid, Error> {
     let mut participants = worker
         .get_scene_current_participants(&scene_uuid)
@@ -112,15 +311,15 @@ pub async fn send_scene_message_and_enqueue_recipients<
     participants.shuffle(&mut rng);
 
     let message_uuid = worker
-        .send_scene_message(sender.clone(), scene_uuid.clone(), content)
+        .send_scene_message(sender.clone(), scene_uuid.clone(), content.clone())
         .await
         .map_err(|err| Error::SendMessage {
             participant: ActorUuid::RealWorldUser,
             details: err,
         })?;
 
-    let mut recipient_uuids = Vec::new();
-    let mut recipient_participants = Vec::new();
+    let mut listeners = Vec::new();
+    let mut listener_names = Vec::new();
 
     for participant in participants {
         let is_sender = match (&sender, &participant.actor_uuid) {
@@ -134,41 +333,130 @@ pub async fn send_scene_message_and_enqueue_recipients<
             continue;
         }
 
-        if let ActorUuid::AiPerson(person_uuid) = participant.actor_uuid.clone() {
-            recipient_uuids.push(person_uuid);
+        if let ActorUuid::AiPerson(person_uuid) = participant.actor_uuid {
+            listener_names.push((person_uuid.clone(), participant.person_name));
+            listeners.push(person_uuid);
         }
-
-        recipient_participants.push(participant);
     }
 
+    let fan_out = match &audience {
+        MessageAudience::Everyone => audience.fan_out(HearingRadius::Room, listeners),
+        MessageAudience::Whisper(_) | MessageAudience::SideConversation(_) => {
+            let hearing_radius =
+                worker
+                    .get_scene_hearing_radius(&scene_uuid)
+                    .await
+                    .map_err(|details| Error::Audience {
+                        message_uuid: message_uuid.clone(),
+                        details,
+                    })?;
+
+            worker
+                .set_message_audience(&message_uuid, &audience)
+                .await
+                .map_err(|details| Error::Audience {
+                    message_uuid: message_uuid.clone(),
+                    details,
+                })?;
+
+            audience.fan_out(hearing_radius, listeners)
+        }
+    };
+
     worker
-        .add_scene_message_recipients(&message_uuid, recipient_uuids)
+        .add_scene_message_recipients(&message_uuid, fan_out.delivered.clone())
         .await
         .map_err(|err| Error::SendMessage {
             participant: ActorUuid::RealWorldUser,
             details: err,
         })?;
 
-    for participant in recipient_participants {
-        match participant.actor_uuid {
-            ActorUuid::AiPerson(person_uuid) => {
-                let message_uuid = message_uuid.clone();
-                let process_message_job = ProcessMessageJob {
-                    message_uuid: message_uuid.clone(),
-                    recipient_person_uuid: person_uuid,
-                };
+    worker
+        .add_scene_message_overhearers(&message_uuid, fan_out.overheard)
+        .await
+        .map_err(|details| Error::Audience {
+            message_uuid: message_uuid.clone(),
+            details,
+        })?;
 
-                worker
-                    .unshift_job(JobKind::ProcessMessage(process_message_job))
-                    .await
-                    .map_err(|err| Error::UnshiftJob {
-                        message_uuid,
-                        details: err,
-                    })?;
-            }
-            ActorUuid::RealWorldUser => {}
-        }
+    let backpressure = |details| Error::Backpressure {
+        message_uuid: message_uuid.clone(),
+        details,
+    };
+    let policy = FanOutPolicy::load().map_err(backpressure)?;
+    let pressure = worker.get_queue_pressure().await.map_err(backpressure)?;
+    let chattiness = if policy.should_skip_quiet_recipients(&pressure) {
+        worker
+            .get_scene_participant_chattiness(&scene_uuid)
+            .await
+            .map_err(backpressure)?
+    } else {
+        Vec::new()
+    };
+    let mut recipient_plans = policy.fan_out(pressure);
+
+    for (recipient_index, person_uuid) in fan_out.delivered.into_iter().enumerate() {
+        let message_uuid = message_uuid.clone();
+        let urgency = listener_names
+            .iter()
+            .find(|(listener_uuid, _)| listener_uuid.to_uuid() == person_uuid.to_uuid())
+            .map(|(_, person_name)| {
+                MessageUrgency::for_recipient(
+                    &sender,
+                    &audience,
+                    &content,
+                    &person_uuid,
+                    person_name,
+                )
+            })
+            .unwrap_or_default();
+        let recipient_chattiness = chattiness
+            .iter()
+            .find(|(chatty_uuid, _)| chatty_uuid.to_uuid() == person_uuid.to_uuid())
+            .map(|(_, chattiness)| *chattiness);
+        let staggered_run_at_active_ms = stagger
+            .as_ref()
+            .map(|stagger| stagger.run_at_active_ms(recipient_index));
+
+        // Under backpressure, skipped recipients keep the message as
+        // unhandled, so they still see it the next time they react.
+        let run_at_active_ms =
+            match recipient_plans.plan(urgency, recipient_chattiness, staggered_run_at_active_ms) {
+                RecipientPlan::Enqueue { run_at_active_ms } => run_at_active_ms,
+                RecipientPlan::Skip => continue,
+            };
+
+        let process_message_job = ProcessMessageJob {
+            message_uuid: message_uuid.clone(),
+            recipient_person_uuid: person_uuid,
+            run_at_active_ms,
+            urgency,
+        };
+
+        worker
+            .unshift_job(JobKind::ProcessMessage(process_message_job))
+            .await
+            .map_err(|err| Error::UnshiftJob {
+                message_uuid,
+                details: err,
+            })?;
     }
 
     Ok(message_uuid)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reaction_stagger_spaces_out_recipients() {
+        let stagger = ReactionStagger {
+            start_active_ms: 1_000,
+            step_ms: 15_000,
+        };
+
+        assert_eq!(stagger.run_at_active_ms(0), 1_000);
+        assert_eq!(stagger.run_at_active_ms(2), 31_000);
+    }
+}
@@ -0,0 +1,88 @@
+use crate::capability::logging::LogCapability;
+use crate::capability::scene_goal::SceneGoalCapability;
+use crate::capability::topic::TopicCapability;
+use crate::domain::logger::Level;
+use crate::domain::topic::{self, UntaggedScene};
+use crate::nice_display::{with_context, NiceDisplay};
+use chrono::DateTime;
+
+/// How often the job runner tags what scenes have been talking about.
+pub const SCAN_INTERVAL: std::time::Duration = std::time::Duration::from_secs(600);
+
+pub enum Error {
+    GetScenes(String),
+    GetTranscript(String),
+    RecordTopics(String),
+}
+
+impl NiceDisplay for Error {
+    fn message(&self) -> String {
+        match self {
+            Error::GetScenes(details) => {
+                with_context("Could not get the scenes with untagged messages", details)
+            }
+            Error::GetTranscript(details) => {
+                with_context("Could not get the scene transcript", details)
+            }
+            Error::RecordTopics(details) => {
+                with_context("Could not record the scene topics", details)
+            }
+        }
+    }
+}
+
+/// Tags every scene that has new messages with the topics those messages
+/// talk about. Returns how many scenes were tagged.
+pub async fn run<W: TopicCapability + SceneGoalCapability + LogCapability>(
+    worker: &W,
+) -> Result<usize, Error> {
+    let scenes = worker
+        .get_untagged_scenes()
+        .await
+        .map_err(Error::GetScenes)?;
+
+    for scene in &scenes {
+        tag_scene(worker, scene).await?;
+    }
+
+    Ok(scenes.len())
+}
+
+async fn tag_scene<W: TopicCapability + SceneGoalCapability + LogCapability>(
+    worker: &W,
+    scene: &UntaggedScene,
+) -> Result<(), Error> {
+    let since = scene.tagged_through.unwrap_or(DateTime::UNIX_EPOCH);
+
+    let mut lines = worker
+        .get_scene_transcript_since(&scene.scene_uuid, since)
+        .await
+        .map_err(Error::GetTranscript)?;
+    // The transcript includes the last message already tagged
+    lines.retain(|line| Some(line.sent_at) > scene.tagged_through);
+
+    let topics = topic::extract_topics(&lines);
+
+    // Recorded even when nothing stood out, so the same messages are not read again
+    worker
+        .record_scene_topics(&scene.scene_uuid, &topics, scene.latest_message_at)
+        .await
+        .map_err(Error::RecordTopics)?;
+
+    if !topics.is_empty() {
+        worker.log(
+            Level::Info,
+            &format!(
+                "Scene {} talked about {}",
+                scene.scene_uuid.to_uuid(),
+                topics
+                    .iter()
+                    .map(|count| count.topic.as_str())
+                    .collect::<Vec<&str>>()
+                    .join(", ")
+            ),
+        );
+    }
+
+    Ok(())
+}
@@ -0,0 +1,106 @@
+use crate::capability::autobiography::AutobiographyCapability;
+use crate::capability::logging::LogCapability;
+use crate::capability::person::PersonCapability;
+use crate::capability::person_arc::{NewPersonArc, PersonArcCapability};
+use crate::domain::logger::Level;
+use crate::domain::person_arc::WORLD_MONTH_MS;
+use crate::domain::person_uuid::PersonUuid;
+use crate::nice_display::{with_context, NiceDisplay};
+
+/// How often the job runner looks for persons due a new arc. A world month
+/// is long, so this only needs to keep up with fast simulation speeds.
+pub const SCAN_INTERVAL: std::time::Duration = std::time::Duration::from_secs(15 * 60);
+/// Each arc is a completion, so a scan is capped. Whoever is left over is
+/// the most overdue next scan.
+const MAX_ARCS_PER_SCAN: i64 = 10;
+/// The newest events since the last arc, so a busy month still fits in the
+/// prompt.
+const MAX_EVENTS: usize = 200;
+
+pub enum Error {
+    GetDuePersons(String),
+    UpdateArc {
+        person_uuid: PersonUuid,
+        details: String,
+    },
+}
+
+impl NiceDisplay for Error {
+    fn message(&self) -> String {
+        match self {
+            Error::GetDuePersons(details) => {
+                with_context("Could not get the persons due a new arc", details)
+            }
+            Error::UpdateArc {
+                person_uuid,
+                details,
+            } => with_context(
+                format!(
+                    "Could not update the arc of person {}",
+                    person_uuid.to_uuid()
+                ),
+                details,
+            ),
+        }
+    }
+}
+
+/// Rewrites the arc of everyone whose last one is a world month old, or
+/// who has none yet, from what happened to them since. Returns how many
+/// were rewritten.
+pub async fn run<
+    W: PersonArcCapability + AutobiographyCapability + PersonCapability + LogCapability,
+>(
+    worker: &W,
+    current_active_ms: i64,
+) -> Result<usize, Error> {
+    let person_uuids = worker
+        .get_persons_due_an_arc(current_active_ms - WORLD_MONTH_MS, MAX_ARCS_PER_SCAN)
+        .await
+        .map_err(Error::GetDuePersons)?;
+
+    for person_uuid in person_uuids.iter() {
+        update_arc(worker, person_uuid, current_active_ms)
+            .await
+            .map_err(|details| Error::UpdateArc {
+                person_uuid: person_uuid.clone(),
+                details,
+            })?;
+    }
+
+    if !person_uuids.is_empty() {
+        worker.log(
+            Level::Info,
+            &format!("Updated the arcs of {} persons", person_uuids.len()),
+        );
+    }
+
+    Ok(person_uuids.len())
+}
+
+async fn update_arc<W: PersonArcCapability + AutobiographyCapability + PersonCapability>(
+    worker: &W,
+    person_uuid: &PersonUuid,
+    current_active_ms: i64,
+) -> Result<(), String> {
+    let person_name = worker.get_persons_name(person_uuid.clone()).await?;
+    let previous_arc = worker.get_latest_person_arc(person_uuid).await?;
+
+    let mut events = worker.get_life_events(person_uuid).await?;
+    if let Some(previous_arc) = &previous_arc {
+        events.retain(|event| event.at > previous_arc.created_at);
+    }
+    let events = events.split_off(events.len().saturating_sub(MAX_EVENTS));
+
+    let draft = worker
+        .write_person_arc(person_name.as_str(), previous_arc.as_ref(), &events)
+        .await?;
+
+    worker
+        .create_person_arc(NewPersonArc {
+            person_uuid: person_uuid.clone(),
+            draft,
+            written_at_active_ms: current_active_ms,
+        })
+        .await
+}
@@ -0,0 +1,158 @@
+use crate::capability::event::EventCapability;
+use crate::capability::expected_reply::ExpectedReplyCapability;
+use crate::capability::external_tool::{ExternalToolCapability, NewExternalToolCall};
+use crate::capability::item::ItemCapability;
+use crate::capability::job::JobCapability;
+use crate::capability::log_event::LogEventCapability;
+use crate::capability::logging::LogCapability;
+use crate::capability::memory::MemoryCapability;
+use crate::capability::message::MessageCapability;
+use crate::capability::moderation::ModerationCapability;
+use crate::capability::motivation::MotivationCapability;
+use crate::capability::person::PersonCapability;
+use crate::capability::person_identity::PersonIdentityCapability;
+use crate::capability::person_task::PersonTaskCapability;
+use crate::capability::reaction::ReactionCapability;
+use crate::capability::reaction_history::ReactionHistoryCapability;
+use crate::capability::reflection::ReflectionCapability;
+use crate::capability::scene::SceneCapability;
+use crate::capability::scene_invitation::SceneInvitationCapability;
+use crate::capability::state_of_mind::StateOfMindCapability;
+use crate::domain::external_tool::{self, ExternalTools};
+use crate::domain::job::process_reaction_common::{self, SceneReactionTrigger};
+use crate::domain::logger::Level;
+use crate::domain::person_uuid::PersonUuid;
+use crate::domain::random_seed::RandomSeed;
+use crate::domain::scene_uuid::SceneUuid;
+use crate::nice_display::{with_context, NiceDisplay};
+use serde::{Deserialize, Serialize};
+use std::time::Instant;
+
+/// Calls the external tool a person chose to use, records the call, and has
+/// them react to what it returned.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UseToolJob {
+    pub tool_name: String,
+    pub person_uuid: PersonUuid,
+    /// Where the person was when they used the tool, if anywhere.
+    pub scene_uuid: Option<SceneUuid>,
+    pub arguments: serde_json::Value,
+}
+
+pub enum Error {
+    UnknownTool(String),
+    RecordCall(String),
+    PersonScene(String),
+    Reaction(process_reaction_common::Error),
+}
+
+impl NiceDisplay for Error {
+    fn message(&self) -> String {
+        match self {
+            Error::UnknownTool(tool_name) => {
+                format!("There is no external tool named \"{}\" anymore", tool_name)
+            }
+            Error::RecordCall(details) => {
+                with_context("Could not record the external tool call", details)
+            }
+            Error::PersonScene(details) => {
+                with_context("Could not get the person's current scene", details)
+            }
+            Error::Reaction(err) => err.message(),
+        }
+    }
+}
+
+impl UseToolJob {
+    pub async fn run<
+        W: ExternalToolCapability
+            + SceneCapability
+            + ReactionCapability
+            + MemoryCapability
+            + MessageCapability
+            + ModerationCapability
+            + PersonCapability
+            + EventCapability
+            + StateOfMindCapability
+            + PersonIdentityCapability
+            + PersonTaskCapability
+            + ReflectionCapability
+            + LogCapability
+            + LogEventCapability
+            + MotivationCapability
+            + ReactionHistoryCapability
+            + ExpectedReplyCapability
+            + ItemCapability
+            + SceneInvitationCapability
+            + JobCapability
+            + Sync,
+    >(
+        self,
+        worker: &W,
+        random_seed: RandomSeed,
+        current_active_ms: i64,
+    ) -> Result<(), Error> {
+        let tool = ExternalTools::registered()
+            .find(&self.tool_name)
+            .ok_or_else(|| Error::UnknownTool(self.tool_name.clone()))?;
+
+        let started = Instant::now();
+        let result = worker.call_external_tool(tool, &self.arguments).await;
+        let duration_ms = started.elapsed().as_millis() as i64;
+
+        if let Err(details) = &result {
+            worker.log(
+                Level::Warning,
+                &format!(
+                    "External tool {} failed for person {}: {}",
+                    self.tool_name,
+                    self.person_uuid.to_uuid(),
+                    details
+                ),
+            );
+        }
+
+        let observation = external_tool::to_observation(&self.tool_name, &result);
+
+        worker
+            .record_external_tool_call(NewExternalToolCall {
+                person_uuid: self.person_uuid.clone(),
+                scene_uuid: self.scene_uuid.clone(),
+                tool_name: self.tool_name.clone(),
+                arguments: self.arguments,
+                result,
+                duration_ms,
+            })
+            .await
+            .map_err(Error::RecordCall)?;
+
+        // Reactions happen in a scene, so someone who used a tool out of
+        // one, or has left since, only gets the call recorded
+        let scene_uuid = match self.scene_uuid {
+            Some(scene_uuid) => scene_uuid,
+            None => return Ok(()),
+        };
+
+        let still_in_scene = worker
+            .get_persons_current_scene_uuid(&self.person_uuid)
+            .await
+            .map_err(Error::PersonScene)?
+            .map(|current_scene_uuid| current_scene_uuid.to_uuid() == scene_uuid.to_uuid())
+            .unwrap_or(false);
+
+        if !still_in_scene {
+            return Ok(());
+        }
+
+        process_reaction_common::run_scene_reaction(
+            worker,
+            &self.person_uuid,
+            &scene_uuid,
+            SceneReactionTrigger::ToolResult { observation },
+            random_seed,
+            current_active_ms,
+        )
+        .await
+        .map_err(Error::Reaction)
+    }
+}
@@ -0,0 +1,123 @@
+use crate::capability::idle_person::IdlePersonCapability;
+use crate::capability::job::JobCapability;
+use crate::capability::logging::LogCapability;
+use crate::domain::job::notice_conversation::NoticeConversationJob;
+use crate::domain::job::JobKind;
+use crate::domain::logger::Level;
+use crate::domain::random_seed::RandomSeed;
+use crate::nice_display::{with_context, NiceDisplay};
+use chrono::Duration;
+use rand::{Rng, SeedableRng};
+
+/// How often the job runner looks for idle persons.
+pub const SCAN_INTERVAL: std::time::Duration = std::time::Duration::from_secs(5 * 60);
+/// How long a person has to go without reacting to count as idle.
+const IDLE_AFTER_MINS: i64 = 10;
+/// How recently someone else must have spoken for a scene to count as active.
+const ACTIVE_WITHIN_MINS: i64 = 5;
+
+pub enum Error {
+    GetIdlePersons(String),
+    Enqueue(String),
+}
+
+impl NiceDisplay for Error {
+    fn message(&self) -> String {
+        match self {
+            Error::GetIdlePersons(details) => {
+                with_context("Could not look for idle persons", details)
+            }
+            Error::Enqueue(details) => {
+                with_context("Could not enqueue a conversation nudge", details)
+            }
+        }
+    }
+}
+
+/// Finds persons who have gone quiet in scenes that are still talking, and
+/// gives each a chance, their chattiness, to notice and react. Returns how
+/// many were nudged.
+pub async fn run<W: IdlePersonCapability + JobCapability + LogCapability>(
+    worker: &W,
+    random_seed: RandomSeed,
+) -> Result<usize, Error> {
+    let now = worker.now();
+    let idle_persons = worker
+        .get_idle_persons_in_active_scenes(
+            now - Duration::minutes(IDLE_AFTER_MINS),
+            now - Duration::minutes(ACTIVE_WITHIN_MINS),
+        )
+        .await
+        .map_err(Error::GetIdlePersons)?;
+
+    let mut rng = rand::rngs::SmallRng::seed_from_u64(random_seed.value());
+    let mut nudged = 0;
+
+    for idle_person in idle_persons {
+        if !should_wake(idle_person.chattiness, rng.gen::<f64>()) {
+            continue;
+        }
+
+        worker
+            .unshift_job(JobKind::NoticeConversation(NoticeConversationJob {
+                person_uuid: idle_person.person_uuid,
+                scene_uuid: idle_person.scene_uuid,
+            }))
+            .await
+            .map_err(Error::Enqueue)?;
+        nudged += 1;
+    }
+
+    if nudged > 0 {
+        worker.log(
+            Level::Info,
+            &format!("Nudged {} idle persons to notice the conversation", nudged),
+        );
+    }
+
+    Ok(nudged)
+}
+
+/// `roll` is uniform in `[0, 1)`.
+fn should_wake(chattiness: f64, roll: f64) -> bool {
+    roll < chattiness.clamp(0.0, 1.0)
+}
+
+pub fn parse_chattiness(text: &str) -> Result<f64, String> {
+    let chattiness = text
+        .trim()
+        .parse::<f64>()
+        .map_err(|_| format!("\"{}\" is not a number", text.trim()))?;
+
+    if (0.0..=1.0).contains(&chattiness) {
+        Ok(chattiness)
+    } else {
+        Err(format!(
+            "Chattiness must be from 0 to 1, got {}",
+            chattiness
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_chattiness_is_the_chance_of_waking() {
+        assert!(should_wake(0.3, 0.29));
+        assert!(!should_wake(0.3, 0.3));
+        assert!(!should_wake(0.0, 0.0));
+        assert!(should_wake(1.0, 0.999));
+        assert!(should_wake(4.0, 0.999));
+    }
+
+    #[test]
+    fn test_parse_chattiness_keeps_to_zero_through_one() {
+        assert_eq!(parse_chattiness(" 0.5 "), Ok(0.5));
+        assert_eq!(parse_chattiness("1"), Ok(1.0));
+        assert!(parse_chattiness("1.5").is_err());
+        assert!(parse_chattiness("-0.1").is_err());
+        assert!(parse_chattiness("chatty").is_err());
+    }
+}
@@ -0,0 +1,197 @@
+use crate::time_display;
+use chrono::{DateTime, Utc};
+use std::time::Duration;
+
+/// Set to tell several job runners apart in the job event log. Defaults to
+/// the process id.
+pub const RUNNER_NAME_VAR: &str = "JOB_RUNNER_NAME";
+
+/// The runner name recorded for jobs run by hand from the admin ui.
+pub const ADMIN_UI_RUNNER: &str = "admin ui";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum JobEventKind {
+    Enqueued,
+    PickedUp,
+    Finished,
+    Failed,
+    /// The job was not ready yet and went back in the queue.
+    Deferred,
+    /// The runner shut down partway through and put the job back.
+    Cancelled,
+    /// Someone reset the job so it runs again.
+    Retried,
+}
+
+/// How long a job ran, and how much of that was spent on OpenAI requests.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JobTiming {
+    pub duration_ms: i64,
+    pub llm_ms: i64,
+}
+
+#[derive(Debug, Clone)]
+pub struct NewJobEvent {
+    pub kind: JobEventKind,
+    pub runner: Option<String>,
+    pub error_class: Option<String>,
+    pub timing: Option<JobTiming>,
+}
+
+#[derive(Debug, Clone)]
+pub struct JobEvent {
+    pub kind: JobEventKind,
+    pub runner: Option<String>,
+    pub error_class: Option<String>,
+    pub timing: Option<JobTiming>,
+    pub created_at: DateTime<Utc>,
+}
+
+pub fn runner_name() -> String {
+    match dotenv::var(RUNNER_NAME_VAR) {
+        Ok(name) if !name.trim().is_empty() => name.trim().to_string(),
+        _ => format!("pid {}", std::process::id()),
+    }
+}
+
+impl JobEventKind {
+    pub fn to_name(&self) -> String {
+        match self {
+            JobEventKind::Enqueued => "enqueued".to_string(),
+            JobEventKind::PickedUp => "picked up".to_string(),
+            JobEventKind::Finished => "finished".to_string(),
+            JobEventKind::Failed => "failed".to_string(),
+            JobEventKind::Deferred => "deferred".to_string(),
+            JobEventKind::Cancelled => "cancelled".to_string(),
+            JobEventKind::Retried => "retried".to_string(),
+        }
+    }
+
+    pub fn from_name(name: &str) -> Result<Self, String> {
+        match name {
+            "enqueued" => Ok(JobEventKind::Enqueued),
+            "picked up" => Ok(JobEventKind::PickedUp),
+            "finished" => Ok(JobEventKind::Finished),
+            "failed" => Ok(JobEventKind::Failed),
+            "deferred" => Ok(JobEventKind::Deferred),
+            "cancelled" => Ok(JobEventKind::Cancelled),
+            "retried" => Ok(JobEventKind::Retried),
+            _ => Err(format!("Unknown job event kind \"{}\"", name)),
+        }
+    }
+}
+
+impl JobTiming {
+    pub fn new(duration: Duration, llm: Duration) -> Self {
+        let duration_ms = i64::try_from(duration.as_millis()).unwrap_or(i64::MAX);
+        let llm_ms = i64::try_from(llm.as_millis()).unwrap_or(i64::MAX);
+
+        JobTiming {
+            duration_ms,
+            // Requests that were still going when the job ended can't have
+            // taken longer than the job
+            llm_ms: llm_ms.min(duration_ms),
+        }
+    }
+
+    /// Everything that was not an OpenAI request, which is mostly the database.
+    pub fn other_ms(&self) -> i64 {
+        self.duration_ms.saturating_sub(self.llm_ms)
+    }
+}
+
+impl NewJobEvent {
+    pub fn new(kind: JobEventKind, runner: &str) -> Self {
+        NewJobEvent {
+            kind,
+            runner: Some(runner.to_string()),
+            error_class: None,
+            timing: None,
+        }
+    }
+
+    pub fn with_timing(self, timing: JobTiming) -> Self {
+        NewJobEvent {
+            timing: Some(timing),
+            ..self
+        }
+    }
+
+    pub fn with_error_class(self, error_class: &str) -> Self {
+        NewJobEvent {
+            error_class: Some(error_class.to_string()),
+            ..self
+        }
+    }
+}
+
+impl JobEvent {
+    /// One line for the job detail view, like `2026-10-17 12:00:03 UTC failed
+    /// (process message) on pid 41: 5200ms, 4900ms llm, 300ms other`.
+    pub fn to_line(&self) -> String {
+        let mut line = format!(
+            "{} {}",
+            time_display::format_absolute(self.created_at),
+            self.kind.to_name()
+        );
+
+        if let Some(error_class) = &self.error_class {
+            line.push_str(&format!(" ({})", error_class));
+        }
+
+        if let Some(runner) = &self.runner {
+            line.push_str(&format!(" on {}", runner));
+        }
+
+        if let Some(timing) = &self.timing {
+            line.push_str(&format!(
+                ": {}ms, {}ms llm, {}ms other",
+                timing.duration_ms,
+                timing.llm_ms,
+                timing.other_ms()
+            ));
+        }
+
+        line
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_job_event_line_shows_the_time_breakdown() {
+        let event = JobEvent {
+            kind: JobEventKind::Failed,
+            runner: Some("pid 41".to_string()),
+            error_class: Some("process message".to_string()),
+            timing: Some(JobTiming::new(
+                Duration::from_millis(5200),
+                Duration::from_millis(4900),
+            )),
+            created_at: Utc.with_ymd_and_hms(2026, 10, 17, 12, 0, 3).unwrap(),
+        };
+
+        assert_eq!(
+            event.to_line(),
+            format!(
+                "{} failed (process message) on pid 41: 5200ms, 4900ms llm, 300ms other",
+                time_display::format_absolute(event.created_at)
+            )
+        );
+        assert_eq!(
+            JobEventKind::from_name(&event.kind.to_name()),
+            Ok(JobEventKind::Failed)
+        );
+    }
+
+    #[test]
+    fn test_job_timing_caps_llm_time_at_the_job_duration() {
+        let timing = JobTiming::new(Duration::from_millis(100), Duration::from_millis(250));
+
+        assert_eq!(timing.llm_ms, 100);
+        assert_eq!(timing.other_ms(), 0);
+    }
+}
@@ -1,27 +1,3 @@
-use uuid::Uuid;
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
-#[allow(dead_code)]
-pub enum JobUuid {
-    Real(Uuid),
-    Test(u64),
-}
+use crate::domain::uuid_newtype::uuid_newtype;
 
-#[allow(dead_code)]
-impl JobUuid {
-    pub fn new() -> Self {
-        JobUuid::Real(Uuid::now_v7())
-    }
-    pub fn to_uuid(&self) -> Result<Uuid, String> {
-        match self {
-            JobUuid::Real(uuid) => Ok(*uuid),
-            JobUuid::Test(test_id) => Err(format!("Cannot turn test id {} into a uuid", test_id)),
-        }
-    }
-    pub fn from_uuid(uuid: Uuid) -> Self {
-        JobUuid::Real(uuid)
-    }
-
-    pub fn test_id(id: u64) -> Self {
-        JobUuid::Test(id)
-    }
-}
+uuid_newtype!(JobUuid);
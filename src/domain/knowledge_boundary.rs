@@ -0,0 +1,193 @@
+use crate::domain::event::Event;
+use crate::domain::scene_uuid::SceneUuid;
+use chrono::{DateTime, Utc};
+use std::cmp::Reverse;
+
+const MAX_LISTED_SCENES: usize = 8;
+
+/// One stretch of time a person spent in a scene.
+#[derive(Debug, Clone)]
+pub struct Attendance {
+    pub scene_uuid: SceneUuid,
+    pub scene_name: String,
+    pub joined_at: DateTime<Utc>,
+    /// None while they are still there.
+    pub left_at: Option<DateTime<Utc>>,
+}
+
+/// What a person could have witnessed, going by the scenes they have been
+/// in and when. Anything outside of it they can only know by being told.
+#[derive(Debug, Clone)]
+pub struct KnowledgeBoundary {
+    person_name: String,
+    /// Most recent first.
+    attendance: Vec<Attendance>,
+}
+
+impl Attendance {
+    fn covers(&self, at: DateTime<Utc>) -> bool {
+        self.joined_at <= at && self.left_at.is_none_or(|left_at| at <= left_at)
+    }
+}
+
+impl KnowledgeBoundary {
+    pub fn new(person_name: String, mut attendance: Vec<Attendance>) -> Self {
+        attendance.sort_by_key(|attendance| Reverse(attendance.joined_at));
+
+        Self {
+            person_name,
+            attendance,
+        }
+    }
+
+    pub fn person_name(&self) -> &str {
+        self.person_name.as_str()
+    }
+
+    pub fn was_present(&self, scene_uuid: &SceneUuid, at: DateTime<Utc>) -> bool {
+        self.attendance.iter().any(|attendance| {
+            attendance.scene_uuid.to_uuid() == scene_uuid.to_uuid() && attendance.covers(at)
+        })
+    }
+
+    /// Drops the events that happened in a scene while the person was not
+    /// there to see them.
+    pub fn retain_known(&self, events: Vec<Event>) -> Vec<Event> {
+        events
+            .into_iter()
+            .filter(|event| {
+                self.attendance.iter().any(|attendance| {
+                    attendance.scene_name == event.scene_name()
+                        && attendance.covers(event.timestamp)
+                })
+            })
+            .collect()
+    }
+
+    /// Tells the model where the person has been, so they stop knowing
+    /// about scenes they never attended.
+    pub fn to_prompt_text(&self) -> String {
+        let mut scene_names: Vec<&str> = Vec::new();
+        for attendance in &self.attendance {
+            if !scene_names.contains(&attendance.scene_name.as_str()) {
+                scene_names.push(attendance.scene_name.as_str());
+            }
+        }
+
+        let listed = if scene_names.len() > MAX_LISTED_SCENES {
+            format!(
+                "{} and {} others",
+                scene_names[..MAX_LISTED_SCENES].join(", "),
+                scene_names.len() - MAX_LISTED_SCENES
+            )
+        } else {
+            scene_names.join(", ")
+        };
+
+        if listed.is_empty() {
+            return format!(
+                "{} has not been in any scene before, and only knows what others say.",
+                self.person_name
+            );
+        }
+
+        format!(
+            "{name} has only been in these scenes: {listed}. {name} does not know what happened anywhere else, or in these scenes while {name} was away, except what someone says about it.",
+            name = self.person_name,
+            listed = listed
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::event::EventType;
+    use crate::domain::message_uuid::MessageUuid;
+    use chrono::Duration;
+    use uuid::Uuid;
+
+    fn attendance(
+        scene_name: &str,
+        joined_at: DateTime<Utc>,
+        left_at: Option<DateTime<Utc>>,
+    ) -> Attendance {
+        Attendance {
+            scene_uuid: SceneUuid::from_uuid(Uuid::from_u128(scene_name.len() as u128)),
+            scene_name: scene_name.to_string(),
+            joined_at,
+            left_at,
+        }
+    }
+
+    fn said(scene_name: &str, at: DateTime<Utc>) -> Event {
+        Event::new(
+            at,
+            EventType::Said {
+                scene_name: scene_name.to_string(),
+                speaker_name: "Walt".to_string(),
+                comment: "Did you hear?".to_string(),
+                message_uuid: MessageUuid::new(),
+            },
+        )
+    }
+
+    #[test]
+    fn test_retain_known_drops_what_happened_while_away() {
+        let now = Utc::now();
+        let boundary = KnowledgeBoundary::new(
+            "Ruth".to_string(),
+            vec![
+                attendance(
+                    "Diner",
+                    now - Duration::hours(3),
+                    Some(now - Duration::hours(2)),
+                ),
+                attendance("Park", now - Duration::hours(1), None),
+            ],
+        );
+
+        let kept = boundary.retain_known(vec![
+            said("Diner", now - Duration::minutes(150)),
+            said("Diner", now - Duration::minutes(90)),
+            said("Garage", now - Duration::minutes(30)),
+            said("Park", now - Duration::minutes(30)),
+        ]);
+
+        assert_eq!(
+            kept.iter()
+                .map(|event| (event.scene_name(), event.timestamp))
+                .collect::<Vec<_>>(),
+            vec![
+                ("Diner", now - Duration::minutes(150)),
+                ("Park", now - Duration::minutes(30)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_prompt_text_lists_each_scene_once_most_recent_first() {
+        let now = Utc::now();
+        let boundary = KnowledgeBoundary::new(
+            "Ruth".to_string(),
+            vec![
+                attendance(
+                    "Diner",
+                    now - Duration::hours(5),
+                    Some(now - Duration::hours(4)),
+                ),
+                attendance(
+                    "Park",
+                    now - Duration::hours(3),
+                    Some(now - Duration::hours(2)),
+                ),
+                attendance("Diner", now - Duration::hours(1), None),
+            ],
+        );
+
+        assert_eq!(
+            boundary.to_prompt_text(),
+            "Ruth has only been in these scenes: Diner, Park. Ruth does not know what happened anywhere else, or in these scenes while Ruth was away, except what someone says about it."
+        );
+    }
+}
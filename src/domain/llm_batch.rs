@@ -0,0 +1,60 @@
+use crate::domain::person_identity_uuid::PersonIdentityUuid;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+const PERSON_IDENTITY_SUMMARY_PREFIX: &str = "person identity summary:";
+
+/// What to do with the answer to one request in a Batch API batch. It is
+/// written into the request's `custom_id`, so the results can be routed
+/// without remembering anything about the batch besides its id.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum BatchHandler {
+    PersonIdentitySummary {
+        person_identity_uuid: PersonIdentityUuid,
+    },
+}
+
+impl BatchHandler {
+    pub fn to_custom_id(&self) -> String {
+        match self {
+            BatchHandler::PersonIdentitySummary {
+                person_identity_uuid,
+            } => format!(
+                "{}{}",
+                PERSON_IDENTITY_SUMMARY_PREFIX,
+                person_identity_uuid.to_uuid()
+            ),
+        }
+    }
+
+    pub fn from_custom_id(custom_id: &str) -> Result<Self, String> {
+        if let Some(uuid) = custom_id.strip_prefix(PERSON_IDENTITY_SUMMARY_PREFIX) {
+            let uuid = Uuid::parse_str(uuid)
+                .map_err(|err| format!("Invalid uuid in custom_id \"{}\": {}", custom_id, err))?;
+
+            return Ok(BatchHandler::PersonIdentitySummary {
+                person_identity_uuid: PersonIdentityUuid::from_uuid(uuid),
+            });
+        }
+
+        Err(format!("Unrecognized batch custom_id \"{}\"", custom_id))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_custom_id_round_trips() {
+        let handler = BatchHandler::PersonIdentitySummary {
+            person_identity_uuid: PersonIdentityUuid::new(),
+        };
+
+        assert_eq!(
+            BatchHandler::from_custom_id(&handler.to_custom_id()),
+            Ok(handler)
+        );
+        assert!(BatchHandler::from_custom_id("something else").is_err());
+    }
+}
@@ -1,35 +1,127 @@
 use crate::capability::memory::MemorySearchResult;
+use chrono::{DateTime, Utc};
 
-#[derive(Clone)]
+#[derive(Debug, Clone)]
 pub struct Memory {
     pub content: String,
 }
 
 const MEMORY_DISTANCE_TIERS: [f64; 3] = [0.30, 0.38, 0.45];
+/// How much closer a memory that mentions someone in the scene counts as.
+/// Enough to lift it a distance tier, not enough to surface one about
+/// something else entirely.
+const PARTICIPANT_MENTION_BOOST: f64 = 0.08;
+pub const MAX_TOP_K: i64 = 50;
 
-impl From<MemorySearchResult> for Memory {
-    fn from(value: MemorySearchResult) -> Self {
-        Memory {
-            content: value.content,
+/// How memories are picked for a prompt. Each world has its own, and calls
+/// like the Memory tab's search can pass their own.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MemoryRetrieval {
+    /// How many memories a search returns at most.
+    pub top_k: i64,
+    /// Memories scoring further than this are never used, however few are
+    /// left.
+    pub max_distance: f64,
+    /// Added to a memory's score for each day since it was made, so newer
+    /// memories win ties. 0 ignores age.
+    pub recency_weight: f64,
+}
+
+impl Default for MemoryRetrieval {
+    fn default() -> Self {
+        Self {
+            top_k: 5,
+            max_distance: 0.45,
+            recency_weight: 0.0,
         }
     }
 }
 
-pub fn filter_memory_results(results: Vec<MemorySearchResult>) -> Vec<Memory> {
-    for threshold in MEMORY_DISTANCE_TIERS {
-        let filtered = results
-            .iter()
-            .filter(|memory| memory.distance <= threshold)
-            .map(|memory| Memory {
-                content: memory.content.clone(),
-            })
-            .collect::<Vec<Memory>>();
-        if !filtered.is_empty() {
-            return filtered;
+impl MemoryRetrieval {
+    pub fn parse(top_k: &str, max_distance: &str, recency_weight: &str) -> Result<Self, String> {
+        let top_k = match top_k.trim().parse::<i64>() {
+            Ok(top_k) if (1..=MAX_TOP_K).contains(&top_k) => top_k,
+            _ => {
+                return Err(format!(
+                    "Top K must be a whole number from 1 to {}",
+                    MAX_TOP_K
+                ))
+            }
+        };
+
+        let max_distance = match max_distance.trim().parse::<f64>() {
+            Ok(max_distance) if (0.0..=2.0).contains(&max_distance) => max_distance,
+            _ => return Err("Max distance must be a number from 0 to 2".to_string()),
+        };
+
+        let recency_weight = match recency_weight.trim().parse::<f64>() {
+            Ok(recency_weight) if (0.0..=1.0).contains(&recency_weight) => recency_weight,
+            _ => return Err("Recency weight must be a number from 0 to 1".to_string()),
+        };
+
+        Ok(Self {
+            top_k,
+            max_distance,
+            recency_weight,
+        })
+    }
+
+    /// Scores each result, lower being better, and keeps the best `top_k`.
+    /// Memories that mention someone in the scene are boosted and older ones
+    /// pay the recency weight.
+    pub fn rank(
+        &self,
+        mut results: Vec<MemorySearchResult>,
+        now: DateTime<Utc>,
+    ) -> Vec<MemorySearchResult> {
+        for result in results.iter_mut() {
+            let age_days = (now - result.created_at).num_seconds().max(0) as f64 / 86_400.0;
+            let boost = if result.mentions_participant {
+                PARTICIPANT_MENTION_BOOST
+            } else {
+                0.0
+            };
+
+            result.score = result.distance - boost + self.recency_weight * age_days;
         }
+
+        results.sort_by(|a, b| a.score.total_cmp(&b.score));
+        results.truncate(self.top_k.max(0) as usize);
+        results
     }
 
-    Vec::new()
+    /// The closest tier of ranked results that has anything in it, never
+    /// going past `max_distance`.
+    pub fn filter(&self, results: Vec<MemorySearchResult>) -> Vec<Memory> {
+        let mut thresholds = MEMORY_DISTANCE_TIERS
+            .into_iter()
+            .filter(|threshold| *threshold < self.max_distance)
+            .collect::<Vec<f64>>();
+        thresholds.push(self.max_distance);
+
+        for threshold in thresholds {
+            let filtered = results
+                .iter()
+                .filter(|memory| memory.score <= threshold)
+                .map(|memory| Memory {
+                    content: memory.content.clone(),
+                })
+                .collect::<Vec<Memory>>();
+            if !filtered.is_empty() {
+                return filtered;
+            }
+        }
+
+        Vec::new()
+    }
+}
+
+impl From<MemorySearchResult> for Memory {
+    fn from(value: MemorySearchResult) -> Self {
+        Memory {
+            content: value.content,
+        }
+    }
 }
 
 impl Memory {
@@ -49,3 +141,83 @@ impl Memory {
         format!("- {}", self.content)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::memory_uuid::MemoryUuid;
+    use chrono::Duration;
+
+    fn result(
+        content: &str,
+        distance: f64,
+        mentions_participant: bool,
+        created_at: DateTime<Utc>,
+    ) -> MemorySearchResult {
+        MemorySearchResult {
+            memory_uuid: MemoryUuid::new(),
+            content: content.to_string(),
+            distance,
+            mentions_participant,
+            created_at,
+            score: distance,
+        }
+    }
+
+    fn contents(results: &[MemorySearchResult]) -> Vec<&str> {
+        results
+            .iter()
+            .map(|result| result.content.as_str())
+            .collect::<Vec<&str>>()
+    }
+
+    #[test]
+    fn test_rank_lifts_memories_about_people_present() {
+        let now = Utc::now();
+        let results = vec![
+            result("I like rain", 0.30, false, now),
+            result("Walt owes me money", 0.34, true, now),
+            result("The diner closes early", 0.36, false, now),
+        ];
+
+        let retrieval = MemoryRetrieval {
+            top_k: 2,
+            ..MemoryRetrieval::default()
+        };
+
+        assert_eq!(
+            contents(&retrieval.rank(results, now)),
+            vec!["Walt owes me money", "I like rain"]
+        );
+    }
+
+    #[test]
+    fn test_rank_weighs_recency_and_filter_stops_at_max_distance() {
+        let now = Utc::now();
+        let results = vec![
+            result("Old argument", 0.20, false, now - Duration::days(10)),
+            result("Fresh argument", 0.25, false, now),
+            result("Barely related", 0.40, false, now),
+        ];
+
+        let retrieval = MemoryRetrieval {
+            top_k: 3,
+            max_distance: 0.28,
+            recency_weight: 0.01,
+        };
+
+        let ranked = retrieval.rank(results, now);
+        assert_eq!(
+            contents(&ranked),
+            vec!["Fresh argument", "Old argument", "Barely related"]
+        );
+        assert_eq!(
+            retrieval
+                .filter(ranked)
+                .iter()
+                .map(|memory| memory.content.as_str())
+                .collect::<Vec<&str>>(),
+            vec!["Fresh argument"]
+        );
+    }
+}
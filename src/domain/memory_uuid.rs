@@ -1,18 +1,3 @@
-use uuid::Uuid;
+use crate::domain::uuid_newtype::uuid_newtype;
 
-#[derive(Debug, Clone)]
-pub struct MemoryUuid(Uuid);
-
-impl MemoryUuid {
-    pub fn to_uuid(&self) -> Uuid {
-        self.0
-    }
-
-    pub fn new() -> Self {
-        Self(Uuid::now_v7())
-    }
-
-    pub fn from_uuid(uuid: Uuid) -> MemoryUuid {
-        MemoryUuid(uuid)
-    }
-}
+uuid_newtype!(MemoryUuid);
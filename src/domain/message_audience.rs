@@ -0,0 +1,135 @@
+use crate::domain::person_uuid::PersonUuid;
+
+/// Who a scene message is meant for.
+#[derive(Debug, Clone)]
+pub enum MessageAudience {
+    Everyone,
+    /// Only these people hear it. Nobody overhears a whisper.
+    Whisper(Vec<PersonUuid>),
+    /// Addressed to these people, but anyone else within earshot overhears it.
+    SideConversation(Vec<PersonUuid>),
+}
+
+/// How far a side conversation carries within a scene.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HearingRadius {
+    /// Everyone in the scene overhears side conversations.
+    Room,
+    /// Only the people addressed hear side conversations.
+    Close,
+}
+
+#[derive(Debug, Clone)]
+pub struct FanOut {
+    pub delivered: Vec<PersonUuid>,
+    pub overheard: Vec<PersonUuid>,
+}
+
+impl MessageAudience {
+    pub fn to_name(&self) -> String {
+        match self {
+            MessageAudience::Everyone => "everyone".to_string(),
+            MessageAudience::Whisper(_) => "whisper".to_string(),
+            MessageAudience::SideConversation(_) => "side conversation".to_string(),
+        }
+    }
+
+    /// Splits the people who could hear a message into those it is delivered
+    /// to and those who only overhear it. `listeners` should not include the
+    /// sender.
+    pub fn fan_out(&self, hearing_radius: HearingRadius, listeners: Vec<PersonUuid>) -> FanOut {
+        let addressed = match self {
+            MessageAudience::Everyone => {
+                return FanOut {
+                    delivered: listeners,
+                    overheard: vec![],
+                }
+            }
+            MessageAudience::Whisper(addressed) => addressed,
+            MessageAudience::SideConversation(addressed) => addressed,
+        };
+
+        let mut delivered = Vec::new();
+        let mut overheard = Vec::new();
+
+        for listener in listeners {
+            let is_addressed = addressed
+                .iter()
+                .any(|person_uuid| person_uuid.to_uuid() == listener.to_uuid());
+
+            if is_addressed {
+                delivered.push(listener);
+                continue;
+            }
+
+            if let (MessageAudience::SideConversation(_), HearingRadius::Room) =
+                (self, hearing_radius)
+            {
+                overheard.push(listener);
+            }
+        }
+
+        FanOut {
+            delivered,
+            overheard,
+        }
+    }
+}
+
+impl HearingRadius {
+    pub fn to_name(self) -> String {
+        match self {
+            HearingRadius::Room => "room".to_string(),
+            HearingRadius::Close => "close".to_string(),
+        }
+    }
+
+    pub fn from_name(name: &str) -> Result<Self, String> {
+        match name {
+            "room" => Ok(HearingRadius::Room),
+            "close" => Ok(HearingRadius::Close),
+            _ => Err(format!("Unknown hearing radius: {}", name)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    fn person(n: u128) -> PersonUuid {
+        PersonUuid::from_uuid(Uuid::from_u128(n))
+    }
+
+    fn ids(people: &[PersonUuid]) -> Vec<Uuid> {
+        people
+            .iter()
+            .map(|person_uuid| person_uuid.to_uuid())
+            .collect()
+    }
+
+    #[test]
+    fn test_side_conversation_is_overheard_across_a_room() {
+        let fan_out = MessageAudience::SideConversation(vec![person(2)])
+            .fan_out(HearingRadius::Room, vec![person(2), person(3)]);
+
+        assert_eq!(ids(&fan_out.delivered), vec![Uuid::from_u128(2)]);
+        assert_eq!(ids(&fan_out.overheard), vec![Uuid::from_u128(3)]);
+
+        let close = MessageAudience::SideConversation(vec![person(2)])
+            .fan_out(HearingRadius::Close, vec![person(2), person(3)]);
+
+        assert_eq!(ids(&close.delivered), vec![Uuid::from_u128(2)]);
+        assert!(close.overheard.is_empty());
+    }
+
+    #[test]
+    fn test_whispers_are_never_overheard() {
+        let fan_out = MessageAudience::Whisper(vec![person(2)])
+            .fan_out(HearingRadius::Room, vec![person(2), person(3)]);
+
+        assert_eq!(ids(&fan_out.delivered), vec![Uuid::from_u128(2)]);
+        assert!(fan_out.overheard.is_empty());
+    }
+}
@@ -0,0 +1,30 @@
+use sha2::{Digest, Sha256};
+
+/// Message bodies are stored once per distinct text, keyed by this hash, so a
+/// director event sent to many scenes takes one row. It is the hex sha256 of
+/// the text, the same as the `message-content` migration computes in sql.
+pub fn content_hash(body: &str) -> String {
+    format!("{:x}", Sha256::digest(body.as_bytes()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_content_hash_matches_postgres_sha256() {
+        // SELECT encode(sha256(convert_to('abc', 'UTF8')), 'hex')
+        assert_eq!(
+            content_hash("abc"),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+        assert_eq!(
+            content_hash("The lights go out."),
+            content_hash("The lights go out.")
+        );
+        assert_ne!(
+            content_hash("The lights go out."),
+            content_hash("The lights go out")
+        );
+    }
+}
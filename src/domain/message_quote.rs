@@ -0,0 +1,126 @@
+use crate::domain::message::Message;
+use crate::domain::message_uuid::MessageUuid;
+use crate::text_utils::normalize_message_content;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Long messages are cut down to this many characters when quoted, so the
+/// quote reminds the recipient of the moment without retelling it.
+const SNIPPET_MAX_CHARS: usize = 240;
+
+/// The earlier scene message a comment is about, kept with the comment so
+/// whoever it is addressed to knows what is being referred to.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MessageQuote {
+    pub message_uuid: MessageUuid,
+    pub speaker_name: String,
+    pub snippet: String,
+    pub sent_at: DateTime<Utc>,
+}
+
+impl MessageQuote {
+    pub fn new(message: &Message, speaker_name: String) -> Self {
+        MessageQuote {
+            message_uuid: message.uuid.clone(),
+            speaker_name,
+            snippet: to_snippet(&message.content),
+            sent_at: message.sent_at,
+        }
+    }
+
+    pub fn to_prompt_text(&self) -> String {
+        format!(
+            "referring to what {} said earlier: \"{}\"",
+            self.speaker_name, self.snippet
+        )
+    }
+
+    /// For a listener who was not there when the quoted message was said,
+    /// so they learn it from the quote instead of remembering it.
+    pub fn to_unheard_prompt_text(&self, listener_name: &str) -> String {
+        format!(
+            "bringing up something {} said before {} was there: \"{}\"",
+            self.speaker_name, listener_name, self.snippet
+        )
+    }
+}
+
+/// The newest message that contains the quoted words, ignoring case and
+/// spacing. Quotes the model made up match nothing, so nothing is attached.
+pub fn find_quoted_message<'a>(messages: &'a [Message], quote: &str) -> Option<&'a Message> {
+    let quote = comparable(quote);
+    if quote.is_empty() {
+        return None;
+    }
+
+    messages
+        .iter()
+        .filter(|message| comparable(&message.content).contains(quote.as_str()))
+        .max_by_key(|message| message.sent_at)
+}
+
+fn comparable(text: &str) -> String {
+    text.split_whitespace()
+        .map(|word| {
+            word.trim_matches(|c: char| !c.is_alphanumeric())
+                .to_lowercase()
+        })
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+fn to_snippet(content: &str) -> String {
+    let content = normalize_message_content(content);
+    if content.chars().count() <= SNIPPET_MAX_CHARS {
+        return content;
+    }
+
+    let cut: String = content.chars().take(SNIPPET_MAX_CHARS).collect();
+    format!("{}...", cut.trim_end())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::message::MessageSender;
+    use crate::domain::scene_uuid::SceneUuid;
+    use chrono::Duration;
+
+    fn message(content: &str, minutes_ago: i64) -> Message {
+        Message {
+            uuid: MessageUuid::new(),
+            sender: MessageSender::RealWorldUser,
+            scene_uuid: SceneUuid::new(),
+            content: content.to_string(),
+            sent_at: Utc::now() - Duration::minutes(minutes_ago),
+        }
+    }
+
+    #[test]
+    fn test_finds_the_newest_message_containing_the_quote() {
+        let messages = vec![
+            message("The bakery closes at noon today.", 10),
+            message("I heard the BAKERY closes at noon, sadly!", 2),
+            message("Anyone want coffee?", 1),
+        ];
+
+        let found = find_quoted_message(&messages, "bakery   closes at noon");
+        assert_eq!(
+            found.map(|message| message.uuid.clone()),
+            Some(messages[1].uuid.clone())
+        );
+
+        assert!(find_quoted_message(&messages, "the library is closed").is_none());
+        assert!(find_quoted_message(&messages, "  ...  ").is_none());
+    }
+
+    #[test]
+    fn test_long_messages_are_cut_to_a_snippet() {
+        let long = "word ".repeat(100);
+        let quote = MessageQuote::new(&message(&long, 1), "Ada".to_string());
+
+        assert!(quote.snippet.ends_with("..."));
+        assert_eq!(quote.snippet.chars().count(), SNIPPET_MAX_CHARS + 2);
+    }
+}
@@ -0,0 +1,143 @@
+use crate::capability::job::JobCapability;
+use crate::capability::message::MessageCapability;
+use crate::capability::message_revision::MessageRevisionCapability;
+use crate::capability::moderation::ModerationCapability;
+use crate::capability::scene::SceneCapability;
+use crate::domain::job::send_message_to_scene::{
+    self, send_moderated_scene_message, SceneMessageOutcome,
+};
+use crate::domain::message::{Message, MessageSender};
+use crate::domain::message_uuid::MessageUuid;
+use crate::domain::moderation::ModerationVerdict;
+use crate::domain::random_seed::RandomSeed;
+use crate::nice_display::{nest, with_context, NiceDisplay};
+
+pub enum Error {
+    GetMessage(String),
+    MessageNotFound,
+    NotSentByRealWorldUser,
+    AlreadyProcessed,
+    Retract(String),
+    BlankEdit,
+    Moderation(String),
+    Send(send_message_to_scene::Error),
+    LinkEdit(String),
+}
+
+impl NiceDisplay for Error {
+    fn message(&self) -> String {
+        match self {
+            Error::GetMessage(details) => with_context("Failed to get message", details),
+            Error::MessageNotFound => "Message not found".to_string(),
+            Error::NotSentByRealWorldUser => {
+                "Only messages you sent can be edited or retracted".to_string()
+            }
+            Error::AlreadyProcessed => {
+                "Someone already reacted to this message, so it can no longer be changed"
+                    .to_string()
+            }
+            Error::Retract(details) => with_context("Failed to retract message", details),
+            Error::BlankEdit => "The edited message cannot be blank".to_string(),
+            Error::Moderation(details) => {
+                with_context("Failed to moderate edited message", details)
+            }
+            Error::Send(err) => nest("Failed to send edited message", err),
+            Error::LinkEdit(details) => with_context(
+                "The edit was sent, but could not be linked to the original",
+                details,
+            ),
+        }
+    }
+}
+
+/// Takes back a message the real world user sent, as long as nobody has
+/// started reacting to it yet.
+pub async fn retract_message<W: MessageCapability + MessageRevisionCapability>(
+    worker: &W,
+    message_uuid: &MessageUuid,
+) -> Result<(), Error> {
+    get_own_message(worker, message_uuid).await?;
+
+    let retracted = worker
+        .retract_pending_message(message_uuid)
+        .await
+        .map_err(Error::Retract)?;
+
+    if !retracted {
+        return Err(Error::AlreadyProcessed);
+    }
+
+    Ok(())
+}
+
+/// Replaces an unprocessed message with new content. The new content is
+/// moderated before the original is retracted, so a blocked edit leaves the
+/// original in place.
+pub async fn edit_message<
+    W: SceneCapability
+        + MessageCapability
+        + JobCapability
+        + ModerationCapability
+        + MessageRevisionCapability,
+>(
+    worker: &W,
+    message_uuid: &MessageUuid,
+    content: String,
+    random_seed: RandomSeed,
+) -> Result<SceneMessageOutcome, Error> {
+    if content.trim().is_empty() {
+        return Err(Error::BlankEdit);
+    }
+
+    let original = get_own_message(worker, message_uuid).await?;
+
+    let verdict = worker
+        .moderate_content(
+            &MessageSender::RealWorldUser,
+            &original.scene_uuid,
+            content.as_str(),
+        )
+        .await
+        .map_err(Error::Moderation)?;
+
+    if let ModerationVerdict::Blocked { categories } = verdict {
+        return Ok(SceneMessageOutcome::Blocked { categories });
+    }
+
+    retract_message(worker, message_uuid).await?;
+
+    let edited_uuid = send_moderated_scene_message(
+        worker,
+        MessageSender::RealWorldUser,
+        original.scene_uuid,
+        content,
+        random_seed,
+    )
+    .await
+    .map_err(Error::Send)?;
+
+    worker
+        .set_message_superseded_by(message_uuid, &edited_uuid)
+        .await
+        .map_err(Error::LinkEdit)?;
+
+    Ok(SceneMessageOutcome::Sent {
+        message_uuid: edited_uuid,
+    })
+}
+
+async fn get_own_message<W: MessageCapability>(
+    worker: &W,
+    message_uuid: &MessageUuid,
+) -> Result<Message, Error> {
+    let message = worker
+        .get_message_by_uuid(message_uuid)
+        .await
+        .map_err(Error::GetMessage)?
+        .ok_or(Error::MessageNotFound)?;
+
+    match message.sender {
+        MessageSender::RealWorldUser => Ok(message),
+        MessageSender::AiPerson(_) => Err(Error::NotSentByRealWorldUser),
+    }
+}
@@ -0,0 +1,134 @@
+use crate::domain::message::MessageSender;
+use crate::domain::message_audience::MessageAudience;
+use crate::domain::person_name::PersonName;
+use crate::domain::person_uuid::PersonUuid;
+use serde::{Deserialize, Serialize};
+
+/// How pressing a message is for one of its recipients. Urgent messages get
+/// their reaction jobs run ahead of background chatter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum MessageUrgency {
+    #[default]
+    Background,
+    /// The recipient was spoken to directly, by name or by addressing them.
+    Direct,
+    /// The real world user said it. Someone is sitting there waiting for an
+    /// answer, so it goes ahead of everything the simulation is doing.
+    Chat,
+}
+
+impl MessageUrgency {
+    pub fn for_recipient(
+        sender: &MessageSender,
+        audience: &MessageAudience,
+        content: &str,
+        recipient_person_uuid: &PersonUuid,
+        recipient_name: &PersonName,
+    ) -> Self {
+        if let MessageSender::RealWorldUser = sender {
+            return MessageUrgency::Chat;
+        }
+
+        let is_addressed = match audience {
+            MessageAudience::Everyone => false,
+            MessageAudience::Whisper(addressed) | MessageAudience::SideConversation(addressed) => {
+                addressed
+                    .iter()
+                    .any(|person_uuid| person_uuid.to_uuid() == recipient_person_uuid.to_uuid())
+            }
+        };
+
+        if is_addressed || mentions_name(content, recipient_name) {
+            MessageUrgency::Direct
+        } else {
+            MessageUrgency::Background
+        }
+    }
+
+    /// Higher runs first.
+    pub fn to_job_priority(self) -> i32 {
+        match self {
+            MessageUrgency::Background => 0,
+            MessageUrgency::Direct => 10,
+            MessageUrgency::Chat => 20,
+        }
+    }
+
+    pub fn to_name(self) -> String {
+        match self {
+            MessageUrgency::Background => "background".to_string(),
+            MessageUrgency::Direct => "direct".to_string(),
+            MessageUrgency::Chat => "chat".to_string(),
+        }
+    }
+}
+
+/// Whether the content uses the person's full name or first name as a whole
+/// word, ignoring case.
+pub fn mentions_name(content: &str, person_name: &PersonName) -> bool {
+    let words = to_words(content);
+    let name_words = to_words(person_name.as_str());
+
+    let first_name = match name_words.first() {
+        Some(first_name) => first_name,
+        None => return false,
+    };
+
+    let full_name_said = words
+        .windows(name_words.len())
+        .any(|window| window == name_words.as_slice());
+
+    full_name_said || words.iter().any(|word| word == first_name)
+}
+
+fn to_words(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric() && c != '\'')
+        .map(|word| word.trim_matches('\'').to_lowercase())
+        .filter(|word| !word.is_empty())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    #[test]
+    fn test_direct_address_is_urgent() {
+        let hank = PersonUuid::from_uuid(Uuid::from_u128(1));
+        let dolores = PersonUuid::from_uuid(Uuid::from_u128(2));
+        let hank_name = PersonName::from_string("Hank Hill".to_string());
+        let sender = MessageSender::AiPerson(dolores.clone());
+
+        let urgency = |audience: &MessageAudience, content: &str| {
+            MessageUrgency::for_recipient(&sender, audience, content, &hank, &hank_name)
+        };
+
+        assert_eq!(
+            urgency(&MessageAudience::Everyone, "Hank, can you pass the salt?"),
+            MessageUrgency::Direct
+        );
+        assert_eq!(
+            urgency(&MessageAudience::Everyone, "Nice weather for a hankering."),
+            MessageUrgency::Background
+        );
+        assert_eq!(
+            urgency(
+                &MessageAudience::SideConversation(vec![hank.clone()]),
+                "Did you see that?"
+            ),
+            MessageUrgency::Direct
+        );
+        assert_eq!(
+            MessageUrgency::for_recipient(
+                &MessageSender::RealWorldUser,
+                &MessageAudience::Everyone,
+                "Morning all.",
+                &hank,
+                &hank_name
+            ),
+            MessageUrgency::Chat
+        );
+        assert!(MessageUrgency::Chat.to_job_priority() > MessageUrgency::Direct.to_job_priority());
+    }
+}
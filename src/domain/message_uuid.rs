@@ -1,22 +1,3 @@
-use serde::{Deserialize, Serialize};
+use crate::domain::uuid_newtype::uuid_newtype;
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
-pub struct MessageUuid(uuid::Uuid);
-
-impl MessageUuid {
-    pub fn new() -> Self {
-        MessageUuid(uuid::Uuid::now_v7())
-    }
-    pub fn to_uuid(&self) -> uuid::Uuid {
-        self.0
-    }
-    pub fn from_uuid(uuid: uuid::Uuid) -> Self {
-        MessageUuid(uuid)
-    }
-}
-
-impl From<uuid::Uuid> for MessageUuid {
-    fn from(value: uuid::Uuid) -> Self {
-        MessageUuid(value)
-    }
-}
+uuid_newtype!(MessageUuid);
@@ -1,22 +1,94 @@
+pub mod acknowledgement;
+pub mod action_budget;
 pub mod actor_uuid;
+pub mod annotation;
+pub mod annotation_uuid;
+pub mod arrival_observation;
+pub mod autobiography;
+pub mod budget;
+pub mod cast;
+pub mod chat_latency;
+pub mod clock;
+pub mod content_scrub;
+pub mod conversation_graph;
+pub mod cron_job;
+pub mod cron_job_uuid;
+pub mod custom_action;
+pub mod delivery;
+pub mod doctor;
+pub mod embedding_backfill;
 pub mod event;
+pub mod event_subscription;
+pub mod external_tool;
+pub mod fan_out;
+pub mod fine_tune_example;
+pub mod guardrail;
+pub mod item;
+pub mod item_uuid;
 pub mod job;
+pub mod job_event;
 pub mod job_uuid;
+pub mod knowledge_boundary;
+pub mod llm_batch;
 pub mod logger;
 pub mod memory;
 pub mod memory_uuid;
 pub mod message;
+pub mod message_audience;
+pub mod message_content;
+pub mod message_quote;
+pub mod message_revision;
+pub mod message_urgency;
 pub mod message_uuid;
+pub mod moderation;
 pub mod motivation;
 pub mod motivation_uuid;
+pub mod outbox;
+pub mod outbox_uuid;
+pub mod pause_policy;
+pub mod person_arc;
+pub mod person_directory;
+pub mod person_filter;
 pub mod person_identity_uuid;
+pub mod person_merge;
 pub mod person_name;
 pub mod person_task;
 pub mod person_task_uuid;
 pub mod person_uuid;
+pub mod persona;
+pub mod persona_consistency;
 pub mod random_seed;
+pub mod rate_limit;
+pub mod reaction_context_uuid;
+pub mod run_report;
+pub mod scene_ambience;
+pub mod scene_archive;
+pub mod scene_drama;
+pub mod scene_goal;
+pub mod scene_invitation;
+pub mod scene_invitation_uuid;
+pub mod scene_kickoff;
 pub mod scene_participant_uuid;
+pub mod scene_read_cache;
+pub mod scene_template;
+pub mod scene_timeline;
+pub mod scene_tone;
 pub mod scene_uuid;
+pub mod schema_overview;
+pub mod simulation_speed;
 pub mod situation;
+pub mod smoke_test;
 pub mod state_of_mind;
 pub mod state_of_mind_uuid;
+pub mod style_guide;
+pub mod talk_share;
+pub mod tenant;
+pub mod tenant_uuid;
+pub mod topic;
+pub mod transcript_import;
+pub mod utterance;
+pub mod uuid_newtype;
+pub mod voice_exemplar;
+pub mod wait_coalescing;
+pub mod world_map;
+pub mod world_time;
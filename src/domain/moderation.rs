@@ -0,0 +1,93 @@
+use crate::open_ai::moderation::ModerationResult;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ModerationVerdict {
+    Allowed,
+    Blocked { categories: Vec<String> },
+}
+
+impl ModerationVerdict {
+    /// Blocks content when any category score reaches the threshold, so the
+    /// threshold is the only knob rather than OpenAI's own `flagged` bit.
+    pub fn from_result(result: &ModerationResult, threshold: f64) -> Self {
+        ModerationVerdict::from_result_by_category(result, |_| threshold)
+    }
+
+    /// Like `from_result`, with each category held to its own threshold.
+    pub fn from_result_by_category(
+        result: &ModerationResult,
+        threshold_for: impl Fn(&str) -> f64,
+    ) -> Self {
+        let mut categories = result
+            .category_scores
+            .iter()
+            .filter(|(category, score)| *score >= threshold_for(category))
+            .map(|(category, _)| category.clone())
+            .collect::<Vec<String>>();
+
+        if categories.is_empty() {
+            ModerationVerdict::Allowed
+        } else {
+            categories.sort();
+            ModerationVerdict::Blocked { categories }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(scores: Vec<(&str, f64)>) -> ModerationResult {
+        ModerationResult {
+            category_scores: scores
+                .into_iter()
+                .map(|(category, score)| (category.to_string(), score))
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_from_result_allows_scores_below_threshold() {
+        let verdict = ModerationVerdict::from_result(
+            &result(vec![("harassment", 0.4), ("violence", 0.1)]),
+            0.5,
+        );
+
+        assert_eq!(verdict, ModerationVerdict::Allowed);
+    }
+
+    #[test]
+    fn test_from_result_by_category_holds_each_category_to_its_own_threshold() {
+        let verdict = ModerationVerdict::from_result_by_category(
+            &result(vec![("violence", 0.6), ("harassment", 0.6)]),
+            |category| if category == "violence" { 0.8 } else { 0.5 },
+        );
+
+        assert_eq!(
+            verdict,
+            ModerationVerdict::Blocked {
+                categories: vec!["harassment".to_string()]
+            }
+        );
+    }
+
+    #[test]
+    fn test_from_result_blocks_categories_at_or_above_threshold() {
+        let verdict = ModerationVerdict::from_result(
+            &result(vec![
+                ("violence", 0.5),
+                ("harassment", 0.9),
+                ("self-harm", 0.2),
+            ]),
+            0.5,
+        );
+
+        assert_eq!(
+            verdict,
+            ModerationVerdict::Blocked {
+                categories: vec!["harassment".to_string(), "violence".to_string()]
+            }
+        );
+    }
+}
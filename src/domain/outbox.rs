@@ -0,0 +1,145 @@
+use crate::domain::message_uuid::MessageUuid;
+use crate::domain::outbox_uuid::OutboxUuid;
+use crate::domain::pause_policy::PauseReason;
+use crate::domain::person_uuid::PersonUuid;
+use crate::domain::scene_goal::SceneGoalMet;
+use crate::domain::scene_uuid::SceneUuid;
+use crate::nice_display::{self, NiceDisplay};
+use chrono::{DateTime, Utc};
+use serde_json::{json, Value};
+
+/// Where outbox entries get delivered. Entries wait in the outbox until it is set.
+pub const WEBHOOK_URL_VAR: &str = "OUTBOX_WEBHOOK_URL";
+
+const FIRST_RETRY_DELAY_SECS: i64 = 30;
+const MAX_RETRY_DELAY_SECS: i64 = 60 * 60;
+
+/// Something external integrations should hear about. Written to the outbox
+/// in the same transaction as the change it describes.
+#[derive(Debug, Clone)]
+pub enum OutboxEvent {
+    SceneMessageSent {
+        message_uuid: MessageUuid,
+        scene_uuid: SceneUuid,
+        sender_person_uuid: Option<PersonUuid>,
+        content: String,
+    },
+    /// The job runner stopped itself because too many jobs were failing.
+    SimulationPaused { reason: PauseReason },
+    /// A scene met its goal and was closed.
+    SceneClosed {
+        scene_uuid: SceneUuid,
+        reason: SceneGoalMet,
+        summary: String,
+    },
+}
+
+#[derive(Debug, Clone)]
+pub struct OutboxEntry {
+    pub uuid: OutboxUuid,
+    pub event_name: String,
+    pub payload: Value,
+    pub attempts: i32,
+    pub created_at: DateTime<Utc>,
+}
+
+impl OutboxEvent {
+    pub fn to_name(&self) -> String {
+        match self {
+            OutboxEvent::SceneMessageSent { .. } => "scene message sent".to_string(),
+            OutboxEvent::SimulationPaused { .. } => "simulation paused".to_string(),
+            OutboxEvent::SceneClosed { .. } => "scene closed".to_string(),
+        }
+    }
+
+    pub fn to_payload(&self) -> Value {
+        match self {
+            OutboxEvent::SceneMessageSent {
+                message_uuid,
+                scene_uuid,
+                sender_person_uuid,
+                content,
+            } => json!({
+                "message_uuid": message_uuid.to_uuid(),
+                "scene_uuid": scene_uuid.to_uuid(),
+                "sender_person_uuid": sender_person_uuid
+                    .as_ref()
+                    .map(|person_uuid| person_uuid.to_uuid()),
+                "content": content,
+            }),
+            OutboxEvent::SimulationPaused { reason } => json!({
+                "reason": reason.message(),
+                "error": nice_display::to_json(reason),
+            }),
+            OutboxEvent::SceneClosed {
+                scene_uuid,
+                reason,
+                summary,
+            } => json!({
+                "scene_uuid": scene_uuid.to_uuid(),
+                "reason": reason.to_text(),
+                "summary": summary,
+            }),
+        }
+    }
+}
+
+impl OutboxEntry {
+    /// The body posted to the webhook. `id` never changes between attempts, so
+    /// receivers can drop an entry they have already seen.
+    pub fn to_webhook_body(&self) -> Value {
+        json!({
+            "id": self.uuid.to_uuid(),
+            "event": self.event_name,
+            "created_at": self.created_at,
+            "data": self.payload,
+        })
+    }
+}
+
+/// How long to wait before trying an entry again after its `attempts`th
+/// failure. Doubles every time, up to an hour, and never gives up.
+pub fn retry_delay_secs(attempts: i32) -> i64 {
+    let doublings = u32::try_from(attempts.saturating_sub(1))
+        .unwrap_or(0)
+        .min(16);
+
+    FIRST_RETRY_DELAY_SECS
+        .saturating_mul(2i64.saturating_pow(doublings))
+        .min(MAX_RETRY_DELAY_SECS)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_simulation_paused_payload_has_an_error_code() {
+        let event = OutboxEvent::SimulationPaused {
+            reason: PauseReason::ErrorRate {
+                failed: 11,
+                ran: 20,
+            },
+        };
+
+        assert_eq!(
+            event.to_payload(),
+            json!({
+                "reason": "11 of the last 20 jobs failed",
+                "error": {
+                    "code": "error rate",
+                    "message": "11 of the last 20 jobs failed",
+                },
+            })
+        );
+    }
+
+    #[test]
+    fn test_retry_delay_doubles_up_to_an_hour() {
+        assert_eq!(retry_delay_secs(1), 30);
+        assert_eq!(retry_delay_secs(2), 60);
+        assert_eq!(retry_delay_secs(4), 240);
+        assert_eq!(retry_delay_secs(8), 3600);
+        assert_eq!(retry_delay_secs(1000), 3600);
+    }
+}
@@ -0,0 +1,215 @@
+use crate::nice_display::{ErrorCode, NiceDisplay};
+use std::collections::{HashMap, VecDeque};
+
+const DEFAULT_MAX_ERROR_RATE: f64 = 0.5;
+const DEFAULT_ERROR_WINDOW: usize = 20;
+const DEFAULT_MAX_KIND_FAILURES: usize = 5;
+
+/// When the job runner should stop popping jobs by itself, rather than keep
+/// spending tokens on jobs that keep failing.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PausePolicy {
+    /// Pause once more than this share of the last `error_window` jobs
+    /// failed. Only checked once that many jobs have run.
+    pub max_error_rate: f64,
+    pub error_window: usize,
+    /// Pause once one kind of job fails this many times in a row.
+    pub max_kind_failures: usize,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum PauseReason {
+    ErrorRate { failed: usize, ran: usize },
+    KindKeepsFailing { job_kind: String, failures: usize },
+    BudgetExhausted { spent_usd: f64, budget_usd: f64 },
+}
+
+/// The jobs the runner finished since it was last started or unpaused.
+#[derive(Debug, Default)]
+pub struct JobFailureTracker {
+    recent_failed: VecDeque<bool>,
+    kind_failures: HashMap<String, usize>,
+}
+
+impl PausePolicy {
+    /// Reads PAUSE_ON_ERROR, PAUSE_ON_ERROR_RATE, PAUSE_ON_ERROR_WINDOW and
+    /// PAUSE_ON_KIND_FAILURES, falling back to defaults when they are not
+    /// set. `PAUSE_ON_ERROR=off` turns pausing off altogether.
+    pub fn load() -> Result<Option<Self>, String> {
+        if let Ok(value) = dotenv::var("PAUSE_ON_ERROR") {
+            if value.trim().eq_ignore_ascii_case("off") {
+                return Ok(None);
+            }
+        }
+
+        Ok(Some(PausePolicy {
+            max_error_rate: rate_from_env("PAUSE_ON_ERROR_RATE", DEFAULT_MAX_ERROR_RATE)?,
+            error_window: count_from_env("PAUSE_ON_ERROR_WINDOW", DEFAULT_ERROR_WINDOW)?,
+            max_kind_failures: count_from_env("PAUSE_ON_KIND_FAILURES", DEFAULT_MAX_KIND_FAILURES)?,
+        }))
+    }
+}
+
+impl ErrorCode for PauseReason {
+    fn code(&self) -> &'static str {
+        match self {
+            PauseReason::ErrorRate { .. } => "error rate",
+            PauseReason::KindKeepsFailing { .. } => "kind keeps failing",
+            PauseReason::BudgetExhausted { .. } => "budget exhausted",
+        }
+    }
+}
+
+impl NiceDisplay for PauseReason {
+    fn message(&self) -> String {
+        match self {
+            PauseReason::ErrorRate { failed, ran } => {
+                format!("{} of the last {} jobs failed", failed, ran)
+            }
+            PauseReason::KindKeepsFailing { job_kind, failures } => {
+                format!("{} jobs failed {} times in a row", job_kind, failures)
+            }
+            PauseReason::BudgetExhausted {
+                spent_usd,
+                budget_usd,
+            } => {
+                format!(
+                    "${:.2} of the ${:.2} run budget has been spent",
+                    spent_usd, budget_usd
+                )
+            }
+        }
+    }
+}
+
+impl JobFailureTracker {
+    pub fn new() -> Self {
+        JobFailureTracker {
+            recent_failed: VecDeque::new(),
+            kind_failures: HashMap::new(),
+        }
+    }
+
+    /// Notes how a job went, and says why to pause if that tipped the policy
+    /// over.
+    pub fn record(
+        &mut self,
+        policy: &PausePolicy,
+        job_kind: &str,
+        failed: bool,
+    ) -> Option<PauseReason> {
+        self.recent_failed.push_back(failed);
+        while self.recent_failed.len() > policy.error_window {
+            self.recent_failed.pop_front();
+        }
+
+        let kind_failures = if failed {
+            let count = self.kind_failures.entry(job_kind.to_string()).or_insert(0);
+            *count += 1;
+            *count
+        } else {
+            self.kind_failures.remove(job_kind);
+            0
+        };
+
+        if kind_failures >= policy.max_kind_failures {
+            return Some(PauseReason::KindKeepsFailing {
+                job_kind: job_kind.to_string(),
+                failures: kind_failures,
+            });
+        }
+
+        let ran = self.recent_failed.len();
+        if ran < policy.error_window {
+            return None;
+        }
+
+        let failed = self.recent_failed.iter().filter(|failed| **failed).count();
+        if failed as f64 / ran as f64 > policy.max_error_rate {
+            return Some(PauseReason::ErrorRate { failed, ran });
+        }
+
+        None
+    }
+
+    /// Forgets everything, so a run that was unpaused gets a fresh start.
+    pub fn reset(&mut self) {
+        self.recent_failed.clear();
+        self.kind_failures.clear();
+    }
+}
+
+fn rate_from_env(var_name: &str, default: f64) -> Result<f64, String> {
+    let value = match dotenv::var(var_name) {
+        Ok(value) => value,
+        Err(_) => return Ok(default),
+    };
+
+    match value.trim().parse::<f64>() {
+        Ok(rate) if rate > 0.0 && rate <= 1.0 => Ok(rate),
+        _ => Err(format!(
+            "{} must be a number above 0 and at most 1, but it was \"{}\"",
+            var_name, value
+        )),
+    }
+}
+
+fn count_from_env(var_name: &str, default: usize) -> Result<usize, String> {
+    let value = match dotenv::var(var_name) {
+        Ok(value) => value,
+        Err(_) => return Ok(default),
+    };
+
+    match value.trim().parse::<usize>() {
+        Ok(count) if count > 0 => Ok(count),
+        _ => Err(format!(
+            "{} must be a whole number greater than zero, but it was \"{}\"",
+            var_name, value
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy() -> PausePolicy {
+        PausePolicy {
+            max_error_rate: 0.5,
+            error_window: 4,
+            max_kind_failures: 3,
+        }
+    }
+
+    #[test]
+    fn test_pauses_when_one_kind_keeps_failing() {
+        let mut tracker = JobFailureTracker::new();
+
+        assert_eq!(tracker.record(&policy(), "process message", true), None);
+        assert_eq!(tracker.record(&policy(), "ping", false), None);
+        assert_eq!(tracker.record(&policy(), "process message", true), None);
+        assert_eq!(
+            tracker.record(&policy(), "process message", true),
+            Some(PauseReason::KindKeepsFailing {
+                job_kind: "process message".to_string(),
+                failures: 3,
+            })
+        );
+    }
+
+    #[test]
+    fn test_pauses_when_the_error_rate_is_too_high_over_a_full_window() {
+        let mut tracker = JobFailureTracker::new();
+
+        assert_eq!(tracker.record(&policy(), "a", true), None);
+        assert_eq!(tracker.record(&policy(), "b", true), None);
+        assert_eq!(tracker.record(&policy(), "c", false), None);
+        assert_eq!(
+            tracker.record(&policy(), "d", true),
+            Some(PauseReason::ErrorRate { failed: 3, ran: 4 })
+        );
+
+        tracker.reset();
+        assert_eq!(tracker.record(&policy(), "d", true), None);
+    }
+}
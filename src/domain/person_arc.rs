@@ -0,0 +1,119 @@
+use crate::domain::action_budget::WORLD_DAY_MS;
+use crate::domain::autobiography::LifeEvent;
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+
+/// Arcs are rewritten once this much time has passed on the active clock,
+/// so how often depends on how fast the world runs rather than the wall.
+pub const WORLD_MONTH_MS: i64 = 30 * WORLD_DAY_MS;
+
+/// A person's long-term storyline: what their life has been building
+/// toward over the last weeks, what they stand to gain or lose, and how it
+/// has gone so far. Rewritten every world month from what happened since.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PersonArc {
+    pub storyline: String,
+    pub stakes: String,
+    pub progress_notes: String,
+    pub created_at: DateTime<Utc>,
+}
+
+/// What the model writes for an arc.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct ArcDraft {
+    pub storyline: String,
+    pub stakes: String,
+    pub progress_notes: String,
+}
+
+impl PersonArc {
+    /// Reactions are about the present, so the arc goes in last and is
+    /// framed as background rather than as something to act on.
+    pub fn to_prompt_section(&self) -> String {
+        format!(
+            "\n\nLonger arc (background only, weigh it lightly against what is happening now):\n- Storyline: {}\n- Stakes: {}\n- So far: {}",
+            self.storyline.trim(),
+            self.stakes.trim(),
+            self.progress_notes.trim()
+        )
+    }
+}
+
+impl ArcDraft {
+    pub fn parse(json: &str) -> Result<ArcDraft, String> {
+        let draft = serde_json::from_str::<ArcDraft>(json)
+            .map_err(|err| format!("Could not parse the arc: {}", err))?;
+
+        if draft.storyline.trim().is_empty() {
+            return Err("The arc has no storyline".to_string());
+        }
+
+        Ok(draft)
+    }
+}
+
+pub fn system_prompt() -> &'static str {
+    "You track the long-term storyline of a character in an ongoing roleplay world. From what happened to them, write where their life is heading over weeks and months, not what they are doing today. Build on their previous arc if there is one: keep what still holds, and move it along with what happened since. Respond with a json object with three string fields: \"storyline\", one or two sentences on the arc they are living through; \"stakes\", what they stand to gain or lose; and \"progress_notes\", a few sentences on how it has gone so far and what changed recently. Use only what is in the log. Do not invent events or people."
+}
+
+pub fn update_prompt(
+    person_name: &str,
+    previous_arc: Option<&PersonArc>,
+    events: &[LifeEvent],
+) -> String {
+    let lines = events
+        .iter()
+        .map(LifeEvent::to_line)
+        .collect::<Vec<String>>()
+        .join("\n");
+
+    match previous_arc {
+        Some(previous_arc) => format!(
+            "Character: {}\n\nTheir arc as of a month ago:\nStoryline: {}\nStakes: {}\nProgress: {}\n\nWhat happened since, oldest first:\n{}\n\nWrite their arc as it stands now.",
+            person_name,
+            previous_arc.storyline.trim(),
+            previous_arc.stakes.trim(),
+            previous_arc.progress_notes.trim(),
+            lines
+        ),
+        None => format!(
+            "Character: {}\n\nWhat has happened to them, oldest first:\n{}\n\nWrite their arc as it stands now.",
+            person_name, lines
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_parse_needs_a_storyline() {
+        let draft = ArcDraft::parse(
+            r#"{"storyline": "Hank is saving up to leave town.", "stakes": "His sister's trust.", "progress_notes": "He took a second job."}"#,
+        )
+        .unwrap();
+        assert_eq!(draft.stakes, "His sister's trust.");
+
+        assert!(
+            ArcDraft::parse(r#"{"storyline": " ", "stakes": "", "progress_notes": ""}"#).is_err()
+        );
+        assert!(ArcDraft::parse("Hank is saving up").is_err());
+    }
+
+    #[test]
+    fn test_prompt_section_is_marked_as_background() {
+        let arc = PersonArc {
+            storyline: "Hank is saving up to leave town.".to_string(),
+            stakes: "His sister's trust.".to_string(),
+            progress_notes: "He took a second job.".to_string(),
+            created_at: Utc.with_ymd_and_hms(2026, 3, 18, 9, 0, 0).unwrap(),
+        };
+
+        assert_eq!(
+            arc.to_prompt_section(),
+            "\n\nLonger arc (background only, weigh it lightly against what is happening now):\n- Storyline: Hank is saving up to leave town.\n- Stakes: His sister's trust.\n- So far: He took a second job."
+        );
+    }
+}
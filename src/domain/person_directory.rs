@@ -0,0 +1,136 @@
+const MAX_SNIPPET_CHARS: usize = 160;
+
+/// What one person knows about another who is in the scene with them.
+#[derive(Debug, Clone)]
+pub struct PersonDirectoryEntry {
+    pub name: String,
+    /// The other person's latest identity summary.
+    pub identity_summary: Option<String>,
+    /// How the viewer sees the other person, from `person_relationship`.
+    pub relationship: Option<String>,
+    /// Whether they were in a scene together before the current one.
+    pub has_met_before: bool,
+}
+
+impl PersonDirectoryEntry {
+    /// The first sentence of the identity summary, kept short.
+    pub fn identity_snippet(&self) -> Option<String> {
+        let summary = self.identity_summary.as_deref()?.trim();
+        if summary.is_empty() {
+            return None;
+        }
+
+        let first_sentence = match summary.find(". ") {
+            Some(index) => &summary[..=index],
+            None => summary,
+        };
+
+        if first_sentence.chars().count() <= MAX_SNIPPET_CHARS {
+            Some(first_sentence.to_string())
+        } else {
+            Some(format!(
+                "{}...",
+                first_sentence
+                    .chars()
+                    .take(MAX_SNIPPET_CHARS)
+                    .collect::<String>()
+            ))
+        }
+    }
+
+    pub fn relationship_descriptor(&self) -> String {
+        match &self.relationship {
+            Some(relationship) if !relationship.trim().is_empty() => {
+                relationship.trim().to_string()
+            }
+            _ if self.has_met_before => "met before".to_string(),
+            _ => "never met before".to_string(),
+        }
+    }
+
+    fn to_line(&self) -> String {
+        match self.identity_snippet() {
+            Some(snippet) => format!(
+                "- {}: {} ({})",
+                self.name,
+                snippet,
+                self.relationship_descriptor()
+            ),
+            None => format!("- {} ({})", self.name, self.relationship_descriptor()),
+        }
+    }
+}
+
+/// The roster added to reaction prompts, so the model works from what the
+/// person actually knows about everyone present instead of inventing it.
+pub fn to_prompt_text(person_name: &str, entries: &[PersonDirectoryEntry]) -> Option<String> {
+    if entries.is_empty() {
+        return None;
+    }
+
+    let lines = entries
+        .iter()
+        .map(PersonDirectoryEntry::to_line)
+        .collect::<Vec<String>>()
+        .join("\n");
+
+    Some(format!(
+        "What {} knows about them:\n{}\nDo not make up anything else about them, such as shared history.",
+        person_name, lines
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(
+        identity_summary: Option<&str>,
+        relationship: Option<&str>,
+        has_met_before: bool,
+    ) -> PersonDirectoryEntry {
+        PersonDirectoryEntry {
+            name: "Walt".to_string(),
+            identity_summary: identity_summary.map(|summary| summary.to_string()),
+            relationship: relationship.map(|relationship| relationship.to_string()),
+            has_met_before,
+        }
+    }
+
+    #[test]
+    fn test_relationship_descriptor_falls_back_to_whether_they_met() {
+        assert_eq!(
+            entry(None, Some("my oldest friend"), false).relationship_descriptor(),
+            "my oldest friend"
+        );
+        assert_eq!(
+            entry(None, None, true).relationship_descriptor(),
+            "met before"
+        );
+        assert_eq!(
+            entry(None, Some(" "), false).relationship_descriptor(),
+            "never met before"
+        );
+    }
+
+    #[test]
+    fn test_prompt_text_lists_each_person_on_one_line() {
+        let entries = vec![
+            entry(
+                Some("A retired trucker who fixes radios. He hums constantly."),
+                None,
+                true,
+            ),
+            PersonDirectoryEntry {
+                name: "Ann".to_string(),
+                ..entry(None, None, false)
+            },
+        ];
+
+        assert_eq!(
+            to_prompt_text("Ruth", &entries).unwrap(),
+            "What Ruth knows about them:\n- Walt: A retired trucker who fixes radios. (met before)\n- Ann (never met before)\nDo not make up anything else about them, such as shared history."
+        );
+        assert!(to_prompt_text("Ruth", &[]).is_none());
+    }
+}
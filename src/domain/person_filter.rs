@@ -0,0 +1,134 @@
+use crate::domain::scene_uuid::SceneUuid;
+use crate::domain::topic::Topic;
+use chrono::{DateTime, Duration, Utc};
+
+const MAX_TAG_LENGTH: usize = 40;
+
+/// A short label for grouping persons, like `regular` or `night_shift`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PersonTag(String);
+
+/// Which persons to find. Every condition that is set has to hold, and an
+/// empty filter matches everyone.
+#[derive(Debug, Clone, Default)]
+pub struct PersonFilter {
+    /// Currently in this scene.
+    pub scene_uuid: Option<SceneUuid>,
+    pub tag: Option<PersonTag>,
+    /// Has not reacted to anything for at least this long.
+    pub idle_for: Option<Duration>,
+    /// Has scene messages they have not handled yet.
+    pub has_unread_messages: bool,
+    /// Was in a scene that talked about this topic.
+    pub topic: Option<Topic>,
+}
+
+impl PersonTag {
+    pub fn parse(tag: &str) -> Result<Self, String> {
+        let tag = tag.trim().to_lowercase();
+
+        if tag.is_empty() {
+            return Err("Tag cannot be empty".to_string());
+        }
+
+        if tag.len() > MAX_TAG_LENGTH {
+            return Err(format!(
+                "Tag \"{}\" is longer than {} characters",
+                tag, MAX_TAG_LENGTH
+            ));
+        }
+
+        let is_valid = tag
+            .chars()
+            .all(|c| c.is_alphanumeric() || c == '_' || c == '-');
+
+        if !is_valid {
+            return Err(format!(
+                "Tag \"{}\" can only contain letters, digits, dashes, and underscores",
+                tag
+            ));
+        }
+
+        Ok(PersonTag(tag))
+    }
+
+    pub fn as_str(&self) -> &str {
+        self.0.as_str()
+    }
+}
+
+impl PersonFilter {
+    pub fn is_empty(&self) -> bool {
+        self.scene_uuid.is_none()
+            && self.tag.is_none()
+            && self.idle_for.is_none()
+            && !self.has_unread_messages
+            && self.topic.is_none()
+    }
+
+    /// Persons who have reacted since this are not idle.
+    pub fn idle_since(&self, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        self.idle_for.map(|idle_for| now - idle_for)
+    }
+}
+
+/// Like `30m`, `1h` or `2d`.
+pub fn parse_duration(text: &str) -> Result<Duration, String> {
+    let text = text.trim();
+    let invalid = || {
+        format!(
+            "Invalid duration \"{}\", expected a number followed by s, m, h or d, like 1h",
+            text
+        )
+    };
+
+    let split_at = text
+        .find(|c: char| !c.is_ascii_digit())
+        .ok_or_else(invalid)?;
+    let (amount, unit) = text.split_at(split_at);
+    let amount = amount.parse::<i64>().map_err(|_| invalid())?;
+
+    match unit {
+        "s" => Ok(Duration::seconds(amount)),
+        "m" => Ok(Duration::minutes(amount)),
+        "h" => Ok(Duration::hours(amount)),
+        "d" => Ok(Duration::days(amount)),
+        _ => Err(invalid()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_tag() {
+        assert_eq!(PersonTag::parse(" Regular ").unwrap().as_str(), "regular");
+        assert_eq!(
+            PersonTag::parse("night_shift").unwrap().as_str(),
+            "night_shift"
+        );
+        assert!(PersonTag::parse("").is_err());
+        assert!(PersonTag::parse("two words").is_err());
+    }
+
+    #[test]
+    fn test_parse_duration() {
+        assert_eq!(parse_duration("90m"), Ok(Duration::minutes(90)));
+        assert_eq!(parse_duration("1h"), Ok(Duration::hours(1)));
+        assert_eq!(parse_duration("2d"), Ok(Duration::days(2)));
+        assert!(parse_duration("h").is_err());
+        assert!(parse_duration("1").is_err());
+        assert!(parse_duration("1w").is_err());
+    }
+
+    #[test]
+    fn test_empty_filter() {
+        assert!(PersonFilter::default().is_empty());
+        assert!(!PersonFilter {
+            has_unread_messages: true,
+            ..PersonFilter::default()
+        }
+        .is_empty());
+    }
+}
@@ -0,0 +1,105 @@
+use crate::domain::person_uuid::PersonUuid;
+
+/// What merging a duplicate person moves to the person being kept.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PersonMergeCounts {
+    pub messages: u64,
+    pub memories: u64,
+    pub identities: u64,
+    pub states_of_mind: u64,
+    pub scene_participations: u64,
+    /// Scenes both persons are in right now. The duplicate leaves these
+    /// rather than having the kept person in them twice.
+    pub shared_scenes: u64,
+}
+
+impl PersonMergeCounts {
+    /// One line per kind of history, for confirming a merge.
+    pub fn lines(&self) -> Vec<String> {
+        let mut lines = vec![
+            count_line(self.messages, "message", "messages"),
+            count_line(self.memories, "memory", "memories"),
+            count_line(self.identities, "identity", "identities"),
+            count_line(self.states_of_mind, "state of mind", "states of mind"),
+            count_line(
+                self.scene_participations,
+                "scene participation",
+                "scene participations",
+            ),
+        ];
+
+        if self.shared_scenes > 0 {
+            lines.push(format!(
+                "{}, the duplicate leaves them",
+                count_line(
+                    self.shared_scenes,
+                    "scene they are both in",
+                    "scenes they are both in"
+                )
+            ));
+        }
+
+        lines
+    }
+}
+
+pub fn validate(keep: &PersonUuid, merge: &PersonUuid) -> Result<(), String> {
+    if keep.to_uuid() == merge.to_uuid() {
+        return Err("Cannot merge a person into themselves".to_string());
+    }
+
+    Ok(())
+}
+
+fn count_line(count: u64, singular: &str, plural: &str) -> String {
+    if count == 1 {
+        format!("1 {}", singular)
+    } else {
+        format!("{} {}", count, plural)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lines_mention_shared_scenes_only_when_there_are_some() {
+        let counts = PersonMergeCounts {
+            messages: 12,
+            memories: 1,
+            identities: 2,
+            states_of_mind: 0,
+            scene_participations: 3,
+            shared_scenes: 0,
+        };
+
+        assert_eq!(
+            counts.lines(),
+            vec![
+                "12 messages",
+                "1 memory",
+                "2 identities",
+                "0 states of mind",
+                "3 scene participations",
+            ]
+        );
+
+        let counts = PersonMergeCounts {
+            shared_scenes: 1,
+            ..counts
+        };
+        assert_eq!(
+            counts.lines().last().unwrap(),
+            "1 scene they are both in, the duplicate leaves them"
+        );
+    }
+
+    #[test]
+    fn test_validate_rejects_the_same_person() {
+        let person_uuid = PersonUuid::new();
+
+        assert!(validate(&person_uuid, &person_uuid).is_err());
+        assert!(validate(&person_uuid, &PersonUuid::new()).is_ok());
+    }
+}
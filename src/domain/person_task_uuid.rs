@@ -1,18 +1,3 @@
-use uuid::Uuid;
+use crate::domain::uuid_newtype::uuid_newtype;
 
-#[derive(Clone, Debug, Eq, PartialEq, Hash)]
-pub struct PersonTaskUuid(Uuid);
-
-impl PersonTaskUuid {
-    pub fn new() -> Self {
-        Self(Uuid::now_v7())
-    }
-
-    pub fn to_uuid(&self) -> Uuid {
-        self.0
-    }
-
-    pub fn from_uuid(uuid: Uuid) -> Self {
-        Self(uuid)
-    }
-}
+uuid_newtype!(PersonTaskUuid);
@@ -0,0 +1,236 @@
+use crate::capability::memory::{MemoryCapability, NewMemory};
+use crate::capability::person::{NewPerson, PersonCapability};
+use crate::capability::person_identity::{NewPersonIdentity, PersonIdentityCapability};
+use crate::capability::state_of_mind::{NewStateOfMind, StateOfMindCapability};
+use crate::domain::memory_uuid::MemoryUuid;
+use crate::domain::person_identity_uuid::PersonIdentityUuid;
+use crate::domain::person_name::PersonName;
+use crate::domain::person_uuid::PersonUuid;
+use crate::domain::state_of_mind_uuid::StateOfMindUuid;
+use crate::nice_display::{with_context, NiceDisplay};
+use crate::open_ai::tool::{ToolFunction, ToolFunctionParameter};
+use crate::open_ai::tool_call::ToolCall;
+
+pub const SEED_MEMORY_COUNT: usize = 10;
+pub const TOOL_NAME: &str = "create_persona";
+
+/// A generated person that has not been saved yet, so it can be previewed
+/// and edited first.
+#[derive(Debug, Clone)]
+pub struct Persona {
+    pub name: PersonName,
+    pub identity: String,
+    pub state_of_mind: String,
+    pub memories: Vec<String>,
+}
+
+pub enum Error {
+    Person(String),
+    Identity(String),
+    StateOfMind(String),
+    Memory(String),
+}
+
+impl NiceDisplay for Error {
+    fn message(&self) -> String {
+        match self {
+            Error::Person(err) => with_context("Error creating person", err),
+            Error::Identity(err) => with_context("Error creating identity", err),
+            Error::StateOfMind(err) => with_context("Error creating state of mind", err),
+            Error::Memory(err) => with_context("Error creating seed memory", err),
+        }
+    }
+}
+
+impl Persona {
+    pub fn tool() -> ToolFunction {
+        ToolFunction::new(
+            TOOL_NAME.to_string(),
+            "Create one new person for the simulation.".to_string(),
+            Persona::tool_parameters(),
+        )
+    }
+
+    /// The fields of one persona, shared with tools that create several at once.
+    pub fn tool_parameters() -> Vec<ToolFunctionParameter> {
+        vec![
+            ToolFunctionParameter::String {
+                name: "name".to_string(),
+                description: "The person's first name, or first and last name.".to_string(),
+                required: true,
+            },
+            ToolFunctionParameter::String {
+                name: "identity".to_string(),
+                description: "A few paragraphs in the second person (\"You are ...\") describing who they are: background, personality, habits, how they talk, and what they want.".to_string(),
+                required: true,
+            },
+            ToolFunctionParameter::String {
+                name: "state_of_mind".to_string(),
+                description: "One or two sentences about how they feel right now.".to_string(),
+                required: true,
+            },
+            ToolFunctionParameter::StringArray {
+                name: "memories".to_string(),
+                description: format!(
+                    "Exactly {} short first-person memories from their past that fit their identity.",
+                    SEED_MEMORY_COUNT
+                ),
+                required: true,
+            },
+        ]
+    }
+
+    pub fn from_tool_call(call: &ToolCall) -> Result<Self, String> {
+        let arguments = call
+            .arguments
+            .iter()
+            .cloned()
+            .collect::<serde_json::Map<String, serde_json::Value>>();
+
+        Persona::from_arguments(&arguments)
+    }
+
+    /// Reads a persona out of a json object with the same fields as the
+    /// `create_persona` tool, so other tools can embed personas.
+    pub fn from_arguments(
+        arguments: &serde_json::Map<String, serde_json::Value>,
+    ) -> Result<Self, String> {
+        let memories = string_array_argument(arguments, "memories")?
+            .into_iter()
+            .map(|memory| memory.trim().to_string())
+            .filter(|memory| !memory.is_empty())
+            .collect::<Vec<String>>();
+
+        Ok(Persona {
+            name: PersonName::from_string(string_argument(arguments, "name")?.trim().to_string()),
+            identity: string_argument(arguments, "identity")?,
+            state_of_mind: string_argument(arguments, "state_of_mind")?,
+            memories,
+        })
+    }
+}
+
+/// Saves the persona through the same capabilities the admin ui uses when
+/// people are made by hand.
+pub async fn create_persona<
+    W: PersonCapability + PersonIdentityCapability + StateOfMindCapability + MemoryCapability,
+>(
+    worker: &W,
+    persona: Persona,
+) -> Result<PersonUuid, Error> {
+    let person_uuid = worker
+        .create_person(NewPerson {
+            person_uuid: PersonUuid::new(),
+            person_name: persona.name.clone(),
+        })
+        .await
+        .map_err(Error::Person)?;
+
+    worker
+        .create_person_identity(NewPersonIdentity {
+            person_identity_uuid: PersonIdentityUuid::new(),
+            person_name: persona.name.as_str().to_string(),
+            identity: persona.identity,
+        })
+        .await
+        .map_err(Error::Identity)?;
+
+    worker
+        .create_state_of_mind(NewStateOfMind {
+            uuid: StateOfMindUuid::new(),
+            person_name: persona.name,
+            state_of_mind: persona.state_of_mind,
+        })
+        .await
+        .map_err(Error::StateOfMind)?;
+
+    for memory in persona.memories {
+        worker
+            .create_memory(NewMemory {
+                memory_uuid: MemoryUuid::new(),
+                content: memory,
+                person_uuid: person_uuid.clone(),
+            })
+            .await
+            .map_err(Error::Memory)?;
+    }
+
+    Ok(person_uuid)
+}
+
+fn argument<'a>(
+    arguments: &'a serde_json::Map<String, serde_json::Value>,
+    key: &str,
+) -> Result<&'a serde_json::Value, String> {
+    arguments
+        .get(key)
+        .ok_or_else(|| format!("Missing '{}' in persona tool arguments", key))
+}
+
+pub(crate) fn string_argument(
+    arguments: &serde_json::Map<String, serde_json::Value>,
+    key: &str,
+) -> Result<String, String> {
+    argument(arguments, key)?
+        .as_str()
+        .map(|value| value.to_string())
+        .ok_or_else(|| format!("'{}' must be a string", key))
+}
+
+fn string_array_argument(
+    arguments: &serde_json::Map<String, serde_json::Value>,
+    key: &str,
+) -> Result<Vec<String>, String> {
+    argument(arguments, key)?
+        .as_array()
+        .ok_or_else(|| format!("'{}' must be an array", key))?
+        .iter()
+        .map(|item| {
+            item.as_str()
+                .map(|value| value.to_string())
+                .ok_or_else(|| format!("'{}' must contain only strings", key))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_persona_from_tool_call_drops_blank_memories() {
+        let call = ToolCall {
+            name: TOOL_NAME.to_string(),
+            arguments: vec![
+                ("name".to_string(), serde_json::json!(" Dolores ")),
+                (
+                    "identity".to_string(),
+                    serde_json::json!("You run the diner."),
+                ),
+                ("state_of_mind".to_string(), serde_json::json!("Tired.")),
+                (
+                    "memories".to_string(),
+                    serde_json::json!(["I opened the diner in 1998.", "  "]),
+                ),
+            ],
+        };
+
+        let persona = Persona::from_tool_call(&call).unwrap();
+
+        assert_eq!(persona.name.as_str(), "Dolores");
+        assert_eq!(
+            persona.memories,
+            vec!["I opened the diner in 1998.".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_persona_from_tool_call_requires_identity() {
+        let call = ToolCall {
+            name: TOOL_NAME.to_string(),
+            arguments: vec![("name".to_string(), serde_json::json!("Dolores"))],
+        };
+
+        assert!(Persona::from_tool_call(&call).is_err());
+    }
+}
@@ -0,0 +1,180 @@
+use crate::domain::persona;
+use crate::domain::random_seed::RandomSeed;
+use crate::open_ai::tool::{ToolFunction, ToolFunctionParameter};
+use crate::open_ai::tool_call::ToolCall;
+use rand::SeedableRng;
+
+pub const TOOL_NAME: &str = "report_persona_inconsistencies";
+/// How far back to look for things the person said.
+pub const RECENT_UTTERANCE_LIMIT: i64 = 60;
+/// How many of those are shown to the judge at once.
+pub const SAMPLE_SIZE: usize = 20;
+
+/// Something a person said that does not fit who they are supposed to be.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PersonaInconsistency {
+    pub utterance: String,
+    pub identity_claim: String,
+    pub explanation: String,
+}
+
+pub fn tool() -> ToolFunction {
+    ToolFunction::new(
+        TOOL_NAME.to_string(),
+        "Report every utterance that contradicts the person's identity. Report an empty list when they all fit.".to_string(),
+        vec![ToolFunctionParameter::ObjectArray {
+            name: "inconsistencies".to_string(),
+            description: "One entry per contradicting utterance.".to_string(),
+            required: true,
+            fields: vec![
+                ToolFunctionParameter::String {
+                    name: "utterance".to_string(),
+                    description: "The utterance, copied exactly as it was given.".to_string(),
+                    required: true,
+                },
+                ToolFunctionParameter::String {
+                    name: "identity_claim".to_string(),
+                    description: "The part of the identity it contradicts, like \"is a vegetarian\".".to_string(),
+                    required: true,
+                },
+                ToolFunctionParameter::String {
+                    name: "explanation".to_string(),
+                    description: "One sentence on why the two do not fit together, like \"claims to be vegetarian, ordered steak\".".to_string(),
+                    required: true,
+                },
+            ],
+        }],
+    )
+}
+
+pub fn system_prompt() -> &'static str {
+    "You check whether characters in a social simulation stay true to who they are. You are given a character's identity and things they recently said. Flag only clear contradictions of facts, values or habits stated in the identity, not changes of mood, jokes, or details the identity does not mention. Use only the provided tool call and call it exactly once."
+}
+
+pub fn judge_prompt(identity: &str, utterances: &[String]) -> String {
+    let utterance_lines = utterances
+        .iter()
+        .map(|utterance| format!("- {}", utterance))
+        .collect::<Vec<String>>()
+        .join("\n");
+
+    format!(
+        "Identity:\n{}\n\nRecent utterances:\n{}",
+        identity.trim(),
+        utterance_lines
+    )
+}
+
+/// Picks up to `SAMPLE_SIZE` utterances at random, kept in their original order.
+pub fn sample_utterances(utterances: Vec<String>, random_seed: RandomSeed) -> Vec<String> {
+    if utterances.len() <= SAMPLE_SIZE {
+        return utterances;
+    }
+
+    let mut rng = rand::rngs::SmallRng::seed_from_u64(random_seed.value());
+    let mut indices = rand::seq::index::sample(&mut rng, utterances.len(), SAMPLE_SIZE).into_vec();
+    indices.sort_unstable();
+
+    indices
+        .into_iter()
+        .filter_map(|index| utterances.get(index).cloned())
+        .collect()
+}
+
+pub fn from_tool_call(call: &ToolCall) -> Result<Vec<PersonaInconsistency>, String> {
+    let entries = call
+        .arguments
+        .iter()
+        .find(|(name, _)| name == "inconsistencies")
+        .map(|(_, value)| value)
+        .ok_or_else(|| "Missing 'inconsistencies' in the consistency report".to_string())?
+        .as_array()
+        .ok_or_else(|| "'inconsistencies' must be an array".to_string())?;
+
+    let mut inconsistencies = Vec::with_capacity(entries.len());
+    for entry in entries {
+        let arguments = entry
+            .as_object()
+            .ok_or_else(|| "'inconsistencies' must contain only objects".to_string())?;
+
+        let inconsistency = PersonaInconsistency {
+            utterance: persona::string_argument(arguments, "utterance")?
+                .trim()
+                .to_string(),
+            identity_claim: persona::string_argument(arguments, "identity_claim")?
+                .trim()
+                .to_string(),
+            explanation: persona::string_argument(arguments, "explanation")?
+                .trim()
+                .to_string(),
+        };
+
+        if inconsistency.utterance.is_empty() || inconsistency.explanation.is_empty() {
+            continue;
+        }
+
+        inconsistencies.push(inconsistency);
+    }
+
+    Ok(inconsistencies)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_tool_call_skips_blank_entries() {
+        let call = ToolCall {
+            name: TOOL_NAME.to_string(),
+            arguments: vec![(
+                "inconsistencies".to_string(),
+                serde_json::json!([
+                    {
+                        "utterance": " I'll have the ribeye, rare. ",
+                        "identity_claim": "is a vegetarian",
+                        "explanation": "Claims to be vegetarian, ordered steak."
+                    },
+                    {
+                        "utterance": "",
+                        "identity_claim": "is shy",
+                        "explanation": "Nothing"
+                    }
+                ]),
+            )],
+        };
+
+        assert_eq!(
+            from_tool_call(&call),
+            Ok(vec![PersonaInconsistency {
+                utterance: "I'll have the ribeye, rare.".to_string(),
+                identity_claim: "is a vegetarian".to_string(),
+                explanation: "Claims to be vegetarian, ordered steak.".to_string(),
+            }])
+        );
+    }
+
+    #[test]
+    fn test_sample_utterances_keeps_order_and_size() {
+        let utterances = (0..50).map(|i| i.to_string()).collect::<Vec<String>>();
+
+        let sample = sample_utterances(utterances.clone(), RandomSeed::from_u64(7));
+        let positions = sample
+            .iter()
+            .filter_map(|utterance| utterances.iter().position(|u| u == utterance))
+            .collect::<Vec<usize>>();
+
+        assert_eq!(sample.len(), SAMPLE_SIZE);
+        assert!(positions.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+
+    #[test]
+    fn test_sample_utterances_keeps_short_lists_whole() {
+        let utterances = vec!["Hi".to_string(), "Bye".to_string()];
+
+        assert_eq!(
+            sample_utterances(utterances.clone(), RandomSeed::from_u64(7)),
+            utterances
+        );
+    }
+}
@@ -0,0 +1,172 @@
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+const DEFAULT_READS_PER_MINUTE: u32 = 120;
+const DEFAULT_WRITES_PER_MINUTE: u32 = 20;
+
+/// Requests that change the world are limited separately from, and more
+/// tightly than, requests that only read it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RateScope {
+    Read,
+    Write,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RateLimits {
+    pub reads_per_minute: u32,
+    pub writes_per_minute: u32,
+}
+
+/// Token buckets per api token and scope. A bucket holds a minute's worth of
+/// requests and refills continuously, so a script can burst briefly but not
+/// keep up more than its rate.
+#[derive(Debug)]
+pub struct RateLimiter {
+    limits: RateLimits,
+    buckets: HashMap<(String, RateScope), Bucket>,
+}
+
+#[derive(Debug)]
+struct Bucket {
+    available: f64,
+    updated_at: Instant,
+}
+
+impl RateScope {
+    /// Reads are `GET` and `HEAD`, everything else is a write.
+    pub fn from_method(method: &str) -> Self {
+        if method.eq_ignore_ascii_case("GET") || method.eq_ignore_ascii_case("HEAD") {
+            RateScope::Read
+        } else {
+            RateScope::Write
+        }
+    }
+}
+
+impl RateLimits {
+    /// Reads API_RATE_LIMIT_READS and API_RATE_LIMIT_WRITES, in requests per
+    /// minute per token, falling back to defaults when they are not set.
+    pub fn load() -> Result<Self, String> {
+        Ok(RateLimits {
+            reads_per_minute: per_minute_from_env(
+                "API_RATE_LIMIT_READS",
+                DEFAULT_READS_PER_MINUTE,
+            )?,
+            writes_per_minute: per_minute_from_env(
+                "API_RATE_LIMIT_WRITES",
+                DEFAULT_WRITES_PER_MINUTE,
+            )?,
+        })
+    }
+
+    fn per_minute(&self, scope: RateScope) -> u32 {
+        match scope {
+            RateScope::Read => self.reads_per_minute,
+            RateScope::Write => self.writes_per_minute,
+        }
+    }
+}
+
+impl RateLimiter {
+    pub fn new(limits: RateLimits) -> Self {
+        RateLimiter {
+            limits,
+            buckets: HashMap::new(),
+        }
+    }
+
+    /// Spends one request from the key's bucket, or says how long to wait
+    /// until there is one.
+    pub fn check(&mut self, key: &str, scope: RateScope, now: Instant) -> Result<(), Duration> {
+        let capacity = f64::from(self.limits.per_minute(scope));
+        let per_second = capacity / 60.0;
+
+        let bucket = self
+            .buckets
+            .entry((key.to_string(), scope))
+            .or_insert(Bucket {
+                available: capacity,
+                updated_at: now,
+            });
+
+        let elapsed = now.saturating_duration_since(bucket.updated_at);
+        bucket.available = (bucket.available + elapsed.as_secs_f64() * per_second).min(capacity);
+        bucket.updated_at = now;
+
+        if bucket.available >= 1.0 {
+            bucket.available -= 1.0;
+            Ok(())
+        } else {
+            Err(Duration::from_secs_f64(
+                (1.0 - bucket.available) / per_second,
+            ))
+        }
+    }
+}
+
+/// Whole seconds for a `Retry-After` header, never zero.
+pub fn retry_after_secs(wait: Duration) -> u64 {
+    wait.as_secs_f64().ceil().max(1.0) as u64
+}
+
+fn per_minute_from_env(var_name: &str, default: u32) -> Result<u32, String> {
+    let value = match dotenv::var(var_name) {
+        Ok(value) => value,
+        Err(_) => return Ok(default),
+    };
+
+    match value.trim().parse::<u32>() {
+        Ok(count) if count > 0 => Ok(count),
+        _ => Err(format!(
+            "{} must be a whole number of requests per minute greater than zero, but it was \"{}\"",
+            var_name, value
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn limiter() -> RateLimiter {
+        RateLimiter::new(RateLimits {
+            reads_per_minute: 3,
+            writes_per_minute: 1,
+        })
+    }
+
+    #[test]
+    fn test_bursts_up_to_the_limit_then_waits() {
+        let mut limiter = limiter();
+        let now = Instant::now();
+
+        for _ in 0..3 {
+            assert_eq!(limiter.check("token", RateScope::Read, now), Ok(()));
+        }
+
+        let wait = limiter.check("token", RateScope::Read, now).unwrap_err();
+        assert_eq!(retry_after_secs(wait), 20);
+        assert_eq!(
+            limiter.check("token", RateScope::Read, now + Duration::from_secs(20)),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn test_tokens_and_scopes_have_their_own_buckets() {
+        let mut limiter = limiter();
+        let now = Instant::now();
+
+        assert_eq!(limiter.check("a", RateScope::Write, now), Ok(()));
+        assert!(limiter.check("a", RateScope::Write, now).is_err());
+        assert_eq!(limiter.check("a", RateScope::Read, now), Ok(()));
+        assert_eq!(limiter.check("b", RateScope::Write, now), Ok(()));
+    }
+
+    #[test]
+    fn test_scope_from_method() {
+        assert_eq!(RateScope::from_method("GET"), RateScope::Read);
+        assert_eq!(RateScope::from_method("POST"), RateScope::Write);
+    }
+}
@@ -0,0 +1,3 @@
+use crate::domain::uuid_newtype::uuid_newtype;
+
+uuid_newtype!(ReactionContextUuid);
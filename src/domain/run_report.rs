@@ -0,0 +1,401 @@
+use crate::capability::budget::BudgetCapability;
+use crate::capability::run_report::RunReportCapability;
+use crate::domain::budget::BudgetLedger;
+use crate::time_display;
+use chrono::{DateTime, Utc};
+
+/// Enough bookmarks to recall the highlights of a run, without the report
+/// turning into a transcript.
+pub const MAX_BOOKMARKS: i64 = 20;
+
+/// A person who was in a scene at some point during the run.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CastMember {
+    pub name: String,
+    pub scene_count: i64,
+    pub message_count: i64,
+}
+
+/// A scene that was open at some point during the run.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReportScene {
+    pub name: String,
+    pub started_at: DateTime<Utc>,
+    pub ended_at: Option<DateTime<Utc>>,
+    /// Sent during the run, leaving out messages that were revised.
+    pub message_count: i64,
+    pub participant_names: Vec<String>,
+    /// What the scene closing generated about how it ended.
+    pub summary: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReportBookmark {
+    pub scene_name: String,
+    pub at: DateTime<Utc>,
+    pub item_summary: String,
+    pub note: String,
+}
+
+/// What happened during a simulation run, to share after an experiment
+/// without handing out database access. A run starts whenever a budget is
+/// set with `start-run`.
+#[derive(Debug, Clone)]
+pub struct RunReport {
+    pub generated_at: DateTime<Utc>,
+    pub ledger: BudgetLedger,
+    /// Most messages first.
+    pub cast: Vec<CastMember>,
+    /// Oldest first.
+    pub scenes: Vec<ReportScene>,
+    /// Bookmarks with notes first, then the newest.
+    pub bookmarks: Vec<ReportBookmark>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReportFormat {
+    Markdown,
+    Html,
+}
+
+impl ReportFormat {
+    /// HTML for `.html` and `.htm` files, Markdown for anything else.
+    pub fn from_path(path: &str) -> Self {
+        let lower = path.to_lowercase();
+        if lower.ends_with(".html") || lower.ends_with(".htm") {
+            ReportFormat::Html
+        } else {
+            ReportFormat::Markdown
+        }
+    }
+}
+
+impl RunReport {
+    pub async fn load<W: BudgetCapability + RunReportCapability>(
+        worker: &W,
+        now: DateTime<Utc>,
+    ) -> Result<Self, String> {
+        let ledger = BudgetLedger::load(worker).await?;
+        let since = ledger.budget.set_at;
+
+        let (cast, scenes, bookmarks) = tokio::try_join!(
+            worker.get_run_cast(since),
+            worker.get_run_scenes(since),
+            worker.get_run_bookmarks(since, MAX_BOOKMARKS),
+        )?;
+
+        Ok(RunReport {
+            generated_at: now,
+            ledger,
+            cast,
+            scenes,
+            bookmarks,
+        })
+    }
+
+    pub fn message_count(&self) -> i64 {
+        self.scenes.iter().map(|scene| scene.message_count).sum()
+    }
+
+    pub fn render(&self, format: ReportFormat) -> String {
+        match format {
+            ReportFormat::Markdown => self.to_markdown(),
+            ReportFormat::Html => self.to_html(),
+        }
+    }
+
+    pub fn to_markdown(&self) -> String {
+        let mut lines = vec![
+            "# Run report".to_string(),
+            String::new(),
+            format!(
+                "From {} to {}.",
+                time_display::format_absolute(self.ledger.budget.set_at),
+                time_display::format_absolute(self.generated_at)
+            ),
+            String::new(),
+            "## Overview".to_string(),
+            String::new(),
+        ];
+
+        for (label, value) in self.overview() {
+            lines.push(format!("- {}: {}", label, value));
+        }
+
+        lines.push(String::new());
+        lines.push("## Cast".to_string());
+        lines.push(String::new());
+        if self.cast.is_empty() {
+            lines.push("Nobody was in a scene.".to_string());
+        } else {
+            lines.push("| Person | Scenes | Messages |".to_string());
+            lines.push("| --- | --- | --- |".to_string());
+            for member in &self.cast {
+                lines.push(format!(
+                    "| {} | {} | {} |",
+                    markdown_cell(&member.name),
+                    member.scene_count,
+                    member.message_count
+                ));
+            }
+        }
+
+        lines.push(String::new());
+        lines.push("## Scenes".to_string());
+        for scene in &self.scenes {
+            lines.push(String::new());
+            lines.push(format!("### {}", scene.name));
+            lines.push(String::new());
+            lines.push(format!("- {}", scene.span_text()));
+            lines.push(format!("- Messages: {}", scene.message_count));
+            lines.push(format!("- Participants: {}", scene.participants_text()));
+            if let Some(summary) = &scene.summary {
+                lines.push(String::new());
+                lines.push(format!("> {}", summary.trim().replace('\n', "\n> ")));
+            }
+        }
+        if self.scenes.is_empty() {
+            lines.push(String::new());
+            lines.push("No scenes were open.".to_string());
+        }
+
+        lines.push(String::new());
+        lines.push("## Notable events".to_string());
+        lines.push(String::new());
+        if self.bookmarks.is_empty() {
+            lines.push("Nothing was bookmarked.".to_string());
+        }
+        for bookmark in &self.bookmarks {
+            lines.push(format!("- {}", bookmark.to_text()));
+        }
+
+        lines.push(String::new());
+        lines.join("\n")
+    }
+
+    pub fn to_html(&self) -> String {
+        let mut html = vec![
+            "<!DOCTYPE html>".to_string(),
+            "<html>".to_string(),
+            "<head><meta charset=\"utf-8\"><title>Run report</title></head>".to_string(),
+            "<body>".to_string(),
+            "<h1>Run report</h1>".to_string(),
+            format!(
+                "<p>From {} to {}.</p>",
+                escape_html(&time_display::format_absolute(self.ledger.budget.set_at)),
+                escape_html(&time_display::format_absolute(self.generated_at))
+            ),
+            "<h2>Overview</h2>".to_string(),
+            "<ul>".to_string(),
+        ];
+
+        for (label, value) in self.overview() {
+            html.push(format!(
+                "<li>{}: {}</li>",
+                escape_html(label),
+                escape_html(&value)
+            ));
+        }
+        html.push("</ul>".to_string());
+
+        html.push("<h2>Cast</h2>".to_string());
+        if self.cast.is_empty() {
+            html.push("<p>Nobody was in a scene.</p>".to_string());
+        } else {
+            html.push("<table>".to_string());
+            html.push("<tr><th>Person</th><th>Scenes</th><th>Messages</th></tr>".to_string());
+            for member in &self.cast {
+                html.push(format!(
+                    "<tr><td>{}</td><td>{}</td><td>{}</td></tr>",
+                    escape_html(&member.name),
+                    member.scene_count,
+                    member.message_count
+                ));
+            }
+            html.push("</table>".to_string());
+        }
+
+        html.push("<h2>Scenes</h2>".to_string());
+        if self.scenes.is_empty() {
+            html.push("<p>No scenes were open.</p>".to_string());
+        }
+        for scene in &self.scenes {
+            html.push(format!("<h3>{}</h3>", escape_html(&scene.name)));
+            html.push("<ul>".to_string());
+            html.push(format!("<li>{}</li>", escape_html(&scene.span_text())));
+            html.push(format!("<li>Messages: {}</li>", scene.message_count));
+            html.push(format!(
+                "<li>Participants: {}</li>",
+                escape_html(&scene.participants_text())
+            ));
+            html.push("</ul>".to_string());
+            if let Some(summary) = &scene.summary {
+                html.push(format!(
+                    "<blockquote>{}</blockquote>",
+                    escape_html(summary.trim())
+                ));
+            }
+        }
+
+        html.push("<h2>Notable events</h2>".to_string());
+        if self.bookmarks.is_empty() {
+            html.push("<p>Nothing was bookmarked.</p>".to_string());
+        } else {
+            html.push("<ul>".to_string());
+            for bookmark in &self.bookmarks {
+                html.push(format!("<li>{}</li>", escape_html(&bookmark.to_text())));
+            }
+            html.push("</ul>".to_string());
+        }
+
+        html.push("</body>".to_string());
+        html.push("</html>".to_string());
+        html.push(String::new());
+        html.join("\n")
+    }
+
+    fn overview(&self) -> Vec<(&'static str, String)> {
+        let mut overview = vec![
+            ("Persons", self.cast.len().to_string()),
+            ("Scenes", self.scenes.len().to_string()),
+            ("Messages", self.message_count().to_string()),
+            ("Cost", format!("${:.2}", self.ledger.spend.spent_usd)),
+        ];
+
+        if let Some(budget_usd) = self.ledger.budget.budget_usd {
+            overview.push(("Budget", format!("${:.2}", budget_usd)));
+        }
+
+        if self.ledger.spend.unpriced_calls > 0 {
+            overview.push((
+                "Calls left out of the cost for having no price",
+                self.ledger.spend.unpriced_calls.to_string(),
+            ));
+        }
+
+        overview
+    }
+}
+
+impl ReportScene {
+    fn span_text(&self) -> String {
+        match self.ended_at {
+            Some(ended_at) => format!(
+                "Open from {} to {}",
+                time_display::format_absolute(self.started_at),
+                time_display::format_absolute(ended_at)
+            ),
+            None => format!(
+                "Open since {}",
+                time_display::format_absolute(self.started_at)
+            ),
+        }
+    }
+
+    fn participants_text(&self) -> String {
+        if self.participant_names.is_empty() {
+            "nobody".to_string()
+        } else {
+            self.participant_names.join(", ")
+        }
+    }
+}
+
+impl ReportBookmark {
+    fn to_text(&self) -> String {
+        let mut text = format!(
+            "{} in {}: {}",
+            time_display::format_absolute(self.at),
+            self.scene_name,
+            self.item_summary
+        );
+
+        if !self.note.trim().is_empty() {
+            text.push_str(&format!(" ({})", self.note.trim()));
+        }
+
+        text
+    }
+}
+
+fn markdown_cell(text: &str) -> String {
+    text.replace('|', "\\|")
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::budget::{LlmSpend, RunBudget};
+    use chrono::Duration;
+
+    fn report() -> RunReport {
+        let now = Utc::now();
+
+        RunReport {
+            generated_at: now,
+            ledger: BudgetLedger {
+                budget: RunBudget {
+                    budget_usd: Some(5.0),
+                    set_at: now - Duration::hours(2),
+                },
+                spend: LlmSpend {
+                    spent_usd: 1.234,
+                    unpriced_calls: 0,
+                    hourly: Vec::new(),
+                },
+            },
+            cast: vec![CastMember {
+                name: "Walt".to_string(),
+                scene_count: 1,
+                message_count: 3,
+            }],
+            scenes: vec![
+                ReportScene {
+                    name: "Diner".to_string(),
+                    started_at: now - Duration::hours(2),
+                    ended_at: Some(now - Duration::hours(1)),
+                    message_count: 3,
+                    participant_names: vec!["Walt".to_string()],
+                    summary: Some("Walt left <angry>.".to_string()),
+                },
+                ReportScene {
+                    name: "Park".to_string(),
+                    started_at: now - Duration::minutes(30),
+                    ended_at: None,
+                    message_count: 2,
+                    participant_names: Vec::new(),
+                    summary: None,
+                },
+            ],
+            bookmarks: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_markdown_report_lists_cast_scenes_and_cost() {
+        let markdown = report().to_markdown();
+
+        assert!(markdown.contains("- Messages: 5\n- Cost: $1.23\n- Budget: $5.00"));
+        assert!(markdown.contains("| Walt | 1 | 3 |"));
+        assert!(markdown.contains("### Diner"));
+        assert!(markdown.contains("> Walt left <angry>."));
+        assert!(markdown.contains("- Participants: nobody"));
+        assert!(markdown.contains("Nothing was bookmarked."));
+    }
+
+    #[test]
+    fn test_html_report_escapes_generated_text() {
+        assert!(report()
+            .to_html()
+            .contains("<blockquote>Walt left &lt;angry&gt;.</blockquote>"));
+        assert_eq!(ReportFormat::from_path("run.HTML"), ReportFormat::Html);
+        assert_eq!(ReportFormat::from_path("run.md"), ReportFormat::Markdown);
+    }
+}
@@ -0,0 +1,260 @@
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+
+const MINUTE_MS: i64 = 60_000;
+
+/// The weather, noise and lighting of a scene. Persons in the scene are told
+/// about it in their reaction prompts, so a storm or a blackout can change
+/// what they do.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Ambience {
+    pub weather: Weather,
+    pub noise: NoiseLevel,
+    pub lighting: Lighting,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Weather {
+    Clear,
+    Cloudy,
+    Rain,
+    Storm,
+    Snow,
+    Fog,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NoiseLevel {
+    Silent,
+    Quiet,
+    Lively,
+    Loud,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Lighting {
+    Bright,
+    Dim,
+    Dark,
+}
+
+impl Weather {
+    pub const ALL: [Weather; 6] = [
+        Weather::Clear,
+        Weather::Cloudy,
+        Weather::Rain,
+        Weather::Storm,
+        Weather::Snow,
+        Weather::Fog,
+    ];
+
+    pub fn to_name(&self) -> &'static str {
+        match self {
+            Weather::Clear => "clear",
+            Weather::Cloudy => "cloudy",
+            Weather::Rain => "rain",
+            Weather::Storm => "storm",
+            Weather::Snow => "snow",
+            Weather::Fog => "fog",
+        }
+    }
+
+    pub fn parse(name: &str) -> Result<Weather, String> {
+        Weather::ALL
+            .into_iter()
+            .find(|weather| weather.to_name() == name.trim())
+            .ok_or_else(|| format!("Unknown weather: {}", name))
+    }
+
+    fn to_prompt_text(self) -> &'static str {
+        match self {
+            Weather::Clear => "the sky is clear",
+            Weather::Cloudy => "it is cloudy",
+            Weather::Rain => "it is raining",
+            Weather::Storm => "a storm is raging",
+            Weather::Snow => "it is snowing",
+            Weather::Fog => "it is foggy",
+        }
+    }
+}
+
+impl NoiseLevel {
+    pub const ALL: [NoiseLevel; 4] = [
+        NoiseLevel::Silent,
+        NoiseLevel::Quiet,
+        NoiseLevel::Lively,
+        NoiseLevel::Loud,
+    ];
+
+    pub fn to_name(&self) -> &'static str {
+        match self {
+            NoiseLevel::Silent => "silent",
+            NoiseLevel::Quiet => "quiet",
+            NoiseLevel::Lively => "lively",
+            NoiseLevel::Loud => "loud",
+        }
+    }
+
+    pub fn parse(name: &str) -> Result<NoiseLevel, String> {
+        NoiseLevel::ALL
+            .into_iter()
+            .find(|noise| noise.to_name() == name.trim())
+            .ok_or_else(|| format!("Unknown noise level: {}", name))
+    }
+
+    fn to_prompt_text(self) -> &'static str {
+        match self {
+            NoiseLevel::Silent => "it is completely silent",
+            NoiseLevel::Quiet => "it is quiet",
+            NoiseLevel::Lively => "there is a lively hum of noise",
+            NoiseLevel::Loud => "it is so loud it is hard to hear",
+        }
+    }
+}
+
+impl Lighting {
+    pub const ALL: [Lighting; 3] = [Lighting::Bright, Lighting::Dim, Lighting::Dark];
+
+    pub fn to_name(&self) -> &'static str {
+        match self {
+            Lighting::Bright => "bright",
+            Lighting::Dim => "dim",
+            Lighting::Dark => "dark",
+        }
+    }
+
+    pub fn parse(name: &str) -> Result<Lighting, String> {
+        Lighting::ALL
+            .into_iter()
+            .find(|lighting| lighting.to_name() == name.trim())
+            .ok_or_else(|| format!("Unknown lighting: {}", name))
+    }
+
+    fn to_prompt_text(self) -> &'static str {
+        match self {
+            Lighting::Bright => "the light is bright",
+            Lighting::Dim => "the light is dim",
+            Lighting::Dark => "it is dark",
+        }
+    }
+}
+
+impl Ambience {
+    pub fn to_prompt_text(&self) -> String {
+        let text = format!(
+            "{}, {} and {}.",
+            self.weather.to_prompt_text(),
+            self.noise.to_prompt_text(),
+            self.lighting.to_prompt_text()
+        );
+
+        let mut chars = text.chars();
+        match chars.next() {
+            Some(first) => first.to_uppercase().chain(chars).collect(),
+            None => text,
+        }
+    }
+
+    /// Short enough for a timeline row, like "rain, loud, dim".
+    pub fn to_label(&self) -> String {
+        format!(
+            "{}, {}, {}",
+            self.weather.to_name(),
+            self.noise.to_name(),
+            self.lighting.to_name()
+        )
+    }
+}
+
+impl Display for Weather {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.to_name())
+    }
+}
+
+impl Display for NoiseLevel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.to_name())
+    }
+}
+
+impl Display for Lighting {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.to_name())
+    }
+}
+
+/// When a change typed in as "minutes from now" should happen. Blank means
+/// right away.
+pub fn parse_run_at(text: &str, current_active_ms: i64) -> Result<Option<i64>, String> {
+    let text = text.trim();
+
+    if text.is_empty() {
+        return Ok(None);
+    }
+
+    match text.parse::<i64>() {
+        Ok(minutes) if minutes > 0 => Ok(Some(
+            current_active_ms.saturating_add(minutes.saturating_mul(MINUTE_MS)),
+        )),
+        _ => Err(format!(
+            "The delay must be a whole number of minutes above zero, got \"{}\"",
+            text
+        )),
+    }
+}
+
+impl Default for Ambience {
+    fn default() -> Self {
+        Ambience {
+            weather: Weather::Clear,
+            noise: NoiseLevel::Quiet,
+            lighting: Lighting::Bright,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ambience_reads_as_one_sentence() {
+        let ambience = Ambience {
+            weather: Weather::Storm,
+            noise: NoiseLevel::Loud,
+            lighting: Lighting::Dim,
+        };
+
+        assert_eq!(
+            ambience.to_prompt_text(),
+            "A storm is raging, it is so loud it is hard to hear and the light is dim."
+        );
+        assert_eq!(ambience.to_label(), "storm, loud, dim");
+    }
+
+    #[test]
+    fn test_names_parse_back() {
+        for weather in Weather::ALL {
+            assert_eq!(Weather::parse(weather.to_name()), Ok(weather));
+        }
+        for noise in NoiseLevel::ALL {
+            assert_eq!(NoiseLevel::parse(noise.to_name()), Ok(noise));
+        }
+        for lighting in Lighting::ALL {
+            assert_eq!(Lighting::parse(lighting.to_name()), Ok(lighting));
+        }
+        assert!(Weather::parse("hail").is_err());
+    }
+
+    #[test]
+    fn test_parse_run_at_counts_minutes_from_now() {
+        assert_eq!(parse_run_at("", 1_000), Ok(None));
+        assert_eq!(parse_run_at(" 2 ", 1_000), Ok(Some(121_000)));
+        assert!(parse_run_at("0", 1_000).is_err());
+        assert!(parse_run_at("soon", 1_000).is_err());
+    }
+}
@@ -0,0 +1,144 @@
+use crate::domain::annotation::Annotation;
+use crate::domain::scene_goal::TranscriptLine;
+use crate::domain::scene_uuid::SceneUuid;
+use crate::time_display;
+
+const ARCHIVE_DIR_VAR: &str = "SCENE_ARCHIVE_DIR";
+const DEFAULT_ARCHIVE_DIR: &str = "scene_archive";
+
+/// Where archived scene transcripts are written.
+pub fn archive_dir() -> String {
+    std::env::var(ARCHIVE_DIR_VAR)
+        .ok()
+        .filter(|dir| !dir.trim().is_empty())
+        .unwrap_or_else(|| DEFAULT_ARCHIVE_DIR.to_string())
+}
+
+/// Like `dinner-party-0199f3c2-....txt`. The uuid keeps scenes that share a
+/// name from overwriting each other.
+pub fn transcript_file_name(scene_name: &str, scene_uuid: &SceneUuid) -> String {
+    let slug = scene_name
+        .trim()
+        .to_lowercase()
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+        .collect::<String>()
+        .split('-')
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<&str>>()
+        .join("-");
+
+    if slug.is_empty() {
+        format!("{}.txt", scene_uuid)
+    } else {
+        format!("{}-{}.txt", slug, scene_uuid)
+    }
+}
+
+pub fn render_transcript(
+    scene_name: &str,
+    scene_description: Option<&str>,
+    lines: &[TranscriptLine],
+    annotations: &[Annotation],
+) -> String {
+    let mut transcript = format!("{}\n\n", scene_name);
+
+    if let Some(description) = scene_description {
+        transcript.push_str(description.trim());
+        transcript.push_str("\n\n");
+    }
+
+    if lines.is_empty() {
+        transcript.push_str("Nothing was said.\n");
+    }
+
+    // Each note goes right after the last line said by its moment
+    let mut notes = annotations.iter().peekable();
+
+    for line in lines {
+        while let Some(note) = notes.next_if(|note| note.at < line.sent_at) {
+            push_note(&mut transcript, note);
+        }
+
+        transcript.push_str(&format!(
+            "[{}] {}: {}\n",
+            time_display::format_absolute(line.sent_at),
+            line.speaker_name,
+            line.content
+        ));
+    }
+
+    for note in notes {
+        push_note(&mut transcript, note);
+    }
+
+    transcript
+}
+
+fn push_note(transcript: &mut String, note: &Annotation) {
+    transcript.push_str(&format!("    Note: {}\n", note.note));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::annotation_uuid::AnnotationUuid;
+    use chrono::{TimeZone, Utc};
+
+    #[test]
+    fn test_transcript_file_name_is_a_slug() {
+        let scene_uuid = SceneUuid::new();
+
+        assert_eq!(
+            transcript_file_name("  Dinner at Bob's! ", &scene_uuid),
+            format!("dinner-at-bob-s-{}.txt", scene_uuid)
+        );
+        assert_eq!(
+            transcript_file_name("???", &scene_uuid),
+            format!("{}.txt", scene_uuid)
+        );
+    }
+
+    #[test]
+    fn test_render_transcript_without_messages() {
+        assert_eq!(
+            render_transcript("Park", Some("A quiet park. "), &[], &[]),
+            "Park\n\nA quiet park.\n\nNothing was said.\n"
+        );
+    }
+
+    #[test]
+    fn test_render_transcript_puts_notes_after_their_moment() {
+        let at = |second: u32| Utc.with_ymd_and_hms(2026, 1, 1, 0, 0, second).unwrap();
+        let line = |second: u32, content: &str| TranscriptLine {
+            speaker_name: "Hank".to_string(),
+            content: content.to_string(),
+            sent_at: at(second),
+        };
+        let note = |second: u32, note: &str| Annotation {
+            uuid: AnnotationUuid::new(),
+            at: at(second),
+            item_summary: String::new(),
+            note: note.to_string(),
+            is_bookmark: false,
+            include_in_export: true,
+        };
+
+        let transcript = render_transcript(
+            "Park",
+            None,
+            &[line(1, "Hi"), line(3, "Bye")],
+            &[note(1, "Opening line"), note(9, "After the end")],
+        );
+
+        assert_eq!(
+            transcript.lines().skip(2).collect::<Vec<&str>>(),
+            vec![
+                format!("[{}] Hank: Hi", time_display::format_absolute(at(1))).as_str(),
+                "    Note: Opening line",
+                format!("[{}] Hank: Bye", time_display::format_absolute(at(3))).as_str(),
+                "    Note: After the end",
+            ]
+        );
+    }
+}
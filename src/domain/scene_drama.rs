@@ -0,0 +1,120 @@
+/// A complication that can happen in any scene, and how often it comes up
+/// compared to the others.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EventTemplate {
+    pub description: &'static str,
+    pub weight: u32,
+}
+
+pub const EVENT_TEMPLATES: [EventTemplate; 10] = [
+    EventTemplate {
+        description: "A phone starts ringing and nobody seems to know whose it is.",
+        weight: 5,
+    },
+    EventTemplate {
+        description: "Someone knocks over a drink and it spills everywhere.",
+        weight: 5,
+    },
+    EventTemplate {
+        description: "A car alarm goes off outside.",
+        weight: 4,
+    },
+    EventTemplate {
+        description: "The lights flicker for a moment.",
+        weight: 3,
+    },
+    EventTemplate {
+        description: "A stranger walks in, looks around, and leaves without a word.",
+        weight: 3,
+    },
+    EventTemplate {
+        description: "A dog starts barking outside and does not stop.",
+        weight: 3,
+    },
+    EventTemplate {
+        description: "Something heavy falls to the floor with a loud bang.",
+        weight: 2,
+    },
+    EventTemplate {
+        description: "A wallet is lying on the floor. Nobody saw who dropped it.",
+        weight: 2,
+    },
+    EventTemplate {
+        description: "Someone outside shouts a name nobody here recognizes.",
+        weight: 2,
+    },
+    EventTemplate {
+        description: "The power goes out for a few seconds, then comes back.",
+        weight: 1,
+    },
+];
+
+/// `roll` is uniform in `[0, 1)`. The intensity is the chance of an event
+/// each time the job runner looks.
+pub fn should_inject(intensity: f64, roll: f64) -> bool {
+    roll < intensity.clamp(0.0, 1.0)
+}
+
+/// Picks a template by weight. `roll` is uniform in `[0, 1)`.
+pub fn pick_template(templates: &[EventTemplate], roll: f64) -> Option<&EventTemplate> {
+    let total_weight = templates
+        .iter()
+        .map(|template| template.weight as u64)
+        .sum::<u64>();
+
+    if total_weight == 0 {
+        return None;
+    }
+
+    let mut remaining = (roll.clamp(0.0, 1.0) * total_weight as f64) as u64;
+
+    for template in templates {
+        let weight = template.weight as u64;
+        if remaining < weight {
+            return Some(template);
+        }
+        remaining -= weight;
+    }
+
+    templates.iter().rev().find(|template| template.weight > 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_intensity_is_the_chance_of_an_event() {
+        assert!(should_inject(0.2, 0.19));
+        assert!(!should_inject(0.2, 0.2));
+        assert!(!should_inject(0.0, 0.0));
+        assert!(should_inject(1.0, 0.999));
+    }
+
+    #[test]
+    fn test_templates_are_picked_by_weight() {
+        let templates = [
+            EventTemplate {
+                description: "rare",
+                weight: 1,
+            },
+            EventTemplate {
+                description: "never",
+                weight: 0,
+            },
+            EventTemplate {
+                description: "common",
+                weight: 3,
+            },
+        ];
+
+        let pick = |roll| pick_template(&templates, roll).map(|template| template.description);
+
+        assert_eq!(pick(0.0), Some("rare"));
+        assert_eq!(pick(0.24), Some("rare"));
+        assert_eq!(pick(0.25), Some("common"));
+        assert_eq!(pick(0.999), Some("common"));
+        assert_eq!(pick(1.0), Some("common"));
+        assert_eq!(pick_template(&[], 0.5), None);
+    }
+}
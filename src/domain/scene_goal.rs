@@ -0,0 +1,262 @@
+use crate::domain::scene_uuid::SceneUuid;
+use crate::open_ai::tool::{ToolFunction, ToolFunctionParameter};
+use crate::open_ai::tool_call::ToolCall;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+const MINUTE_MS: i64 = 60 * 1000;
+/// Keeps the judge and summarizer prompts small in long scenes.
+const MAX_TRANSCRIPT_LINES: usize = 60;
+
+pub const CONSENSUS_TOOL_NAME: &str = "report_consensus";
+const REACHED: &str = "reached";
+const NOT_REACHED: &str = "not reached";
+
+/// What ends a scene. Whichever condition is met first closes it, and a goal
+/// with no conditions never does.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct SceneGoal {
+    /// The active clock time the scene has to wrap up by.
+    pub deadline_active_ms: Option<i64>,
+    /// Closes the scene as soon as anyone says it.
+    pub closing_phrase: Option<String>,
+    /// What everyone in the scene has to agree on, like "where to eat dinner".
+    pub consensus_on: Option<String>,
+}
+
+/// A scene goal that has not been met yet, in a scene that is still going.
+#[derive(Debug, Clone)]
+pub struct OpenSceneGoal {
+    pub scene_uuid: SceneUuid,
+    pub goal: SceneGoal,
+    pub set_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum SceneGoalMet {
+    TimeLimit,
+    PhraseSpoken {
+        speaker_name: String,
+        phrase: String,
+    },
+    ConsensusReached {
+        consensus_on: String,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TranscriptLine {
+    pub speaker_name: String,
+    pub content: String,
+    pub sent_at: DateTime<Utc>,
+}
+
+impl SceneGoal {
+    pub fn is_empty(&self) -> bool {
+        self.deadline_active_ms.is_none()
+            && self.closing_phrase.is_none()
+            && self.consensus_on.is_none()
+    }
+
+    /// The conditions that can be checked without asking the LLM.
+    pub fn check_without_llm(
+        &self,
+        current_active_ms: i64,
+        lines: &[TranscriptLine],
+    ) -> Option<SceneGoalMet> {
+        if let Some(deadline_active_ms) = self.deadline_active_ms {
+            if current_active_ms >= deadline_active_ms {
+                return Some(SceneGoalMet::TimeLimit);
+            }
+        }
+
+        let phrase = self.closing_phrase.as_ref()?;
+        let needle = phrase.to_lowercase();
+
+        lines
+            .iter()
+            .find(|line| line.content.to_lowercase().contains(&needle))
+            .map(|line| SceneGoalMet::PhraseSpoken {
+                speaker_name: line.speaker_name.clone(),
+                phrase: phrase.clone(),
+            })
+    }
+
+    /// How the goal reads in the admin ui.
+    pub fn to_text(&self, current_active_ms: i64) -> String {
+        let mut parts = Vec::new();
+
+        if let Some(deadline_active_ms) = self.deadline_active_ms {
+            let minutes_left = (deadline_active_ms - current_active_ms).max(0) / MINUTE_MS;
+            parts.push(format!("ends in {} active minutes", minutes_left));
+        }
+
+        if let Some(phrase) = &self.closing_phrase {
+            parts.push(format!("ends when someone says \"{}\"", phrase));
+        }
+
+        if let Some(consensus_on) = &self.consensus_on {
+            parts.push(format!("ends once everyone agrees on {}", consensus_on));
+        }
+
+        if parts.is_empty() {
+            "No goal, the scene runs until it is closed by hand.".to_string()
+        } else {
+            format!("The scene {}.", parts.join(", or "))
+        }
+    }
+}
+
+impl SceneGoalMet {
+    pub fn to_text(&self) -> String {
+        match self {
+            SceneGoalMet::TimeLimit => "The scene ran out of time.".to_string(),
+            SceneGoalMet::PhraseSpoken {
+                speaker_name,
+                phrase,
+            } => format!("{} said \"{}\".", speaker_name, phrase),
+            SceneGoalMet::ConsensusReached { consensus_on } => {
+                format!("Everyone agreed on {}.", consensus_on)
+            }
+        }
+    }
+}
+
+/// Reads a time limit in minutes from the admin ui. Blank means no limit.
+pub fn parse_time_limit_mins(text: &str) -> Result<Option<i64>, String> {
+    let text = text.trim();
+
+    if text.is_empty() {
+        return Ok(None);
+    }
+
+    match text.parse::<i64>() {
+        Ok(minutes) if minutes > 0 => Ok(Some(minutes)),
+        _ => Err(format!(
+            "The time limit must be a whole number of minutes above zero, got \"{}\"",
+            text
+        )),
+    }
+}
+
+pub fn deadline_after_mins(current_active_ms: i64, minutes: i64) -> i64 {
+    current_active_ms.saturating_add(minutes.saturating_mul(MINUTE_MS))
+}
+
+/// Blank fields in the admin ui mean the condition is not used.
+pub fn optional_text(text: &str) -> Option<String> {
+    let text = text.trim();
+
+    if text.is_empty() {
+        None
+    } else {
+        Some(text.to_string())
+    }
+}
+
+pub fn to_transcript(lines: &[TranscriptLine]) -> String {
+    let skip = lines.len().saturating_sub(MAX_TRANSCRIPT_LINES);
+
+    lines
+        .iter()
+        .skip(skip)
+        .map(|line| format!("{}: {}", line.speaker_name, line.content))
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+pub fn consensus_tool() -> ToolFunction {
+    ToolFunction::new(
+        CONSENSUS_TOOL_NAME.to_string(),
+        "Report whether the people in the conversation have reached agreement.".to_string(),
+        vec![ToolFunctionParameter::StringEnum {
+            name: "consensus".to_string(),
+            description: "\"reached\" only when everyone who spoke has clearly agreed, otherwise \"not reached\".".to_string(),
+            required: true,
+            values: vec![REACHED.to_string(), NOT_REACHED.to_string()],
+        }],
+    )
+}
+
+pub fn consensus_from_tool_call(call: &ToolCall) -> Result<bool, String> {
+    let value = call
+        .arguments
+        .iter()
+        .find(|(name, _)| name == "consensus")
+        .map(|(_, value)| value)
+        .ok_or_else(|| "Missing 'consensus' in the consensus report".to_string())?
+        .as_str()
+        .ok_or_else(|| "'consensus' must be a string".to_string())?;
+
+    match value {
+        REACHED => Ok(true),
+        NOT_REACHED => Ok(false),
+        other => Err(format!("Unknown consensus value \"{}\"", other)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn line(speaker_name: &str, content: &str) -> TranscriptLine {
+        TranscriptLine {
+            speaker_name: speaker_name.to_string(),
+            content: content.to_string(),
+            sent_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_time_limit_is_met_at_the_deadline() {
+        let goal = SceneGoal {
+            deadline_active_ms: Some(10_000),
+            ..SceneGoal::default()
+        };
+
+        assert_eq!(goal.check_without_llm(9_999, &[]), None);
+        assert_eq!(
+            goal.check_without_llm(10_000, &[]),
+            Some(SceneGoalMet::TimeLimit)
+        );
+    }
+
+    #[test]
+    fn test_closing_phrase_matches_regardless_of_case() {
+        let goal = SceneGoal {
+            closing_phrase: Some("Good night".to_string()),
+            ..SceneGoal::default()
+        };
+        let lines = vec![
+            line("Dolores", "One more coffee?"),
+            line("Bob", "No thanks, good night everyone!"),
+        ];
+
+        assert_eq!(
+            goal.check_without_llm(0, &lines),
+            Some(SceneGoalMet::PhraseSpoken {
+                speaker_name: "Bob".to_string(),
+                phrase: "Good night".to_string(),
+            })
+        );
+        assert_eq!(goal.check_without_llm(0, &lines[..1]), None);
+    }
+
+    #[test]
+    fn test_parse_time_limit_mins() {
+        assert_eq!(parse_time_limit_mins("  "), Ok(None));
+        assert_eq!(parse_time_limit_mins(" 30 "), Ok(Some(30)));
+        assert!(parse_time_limit_mins("0").is_err());
+        assert!(parse_time_limit_mins("half an hour").is_err());
+    }
+
+    #[test]
+    fn test_consensus_from_tool_call() {
+        let call = ToolCall {
+            name: CONSENSUS_TOOL_NAME.to_string(),
+            arguments: vec![("consensus".to_string(), serde_json::json!("reached"))],
+        };
+
+        assert_eq!(consensus_from_tool_call(&call), Ok(true));
+    }
+}
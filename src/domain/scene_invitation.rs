@@ -0,0 +1,61 @@
+use crate::domain::scene_invitation_uuid::SceneInvitationUuid;
+use crate::domain::scene_uuid::SceneUuid;
+
+/// An invitation someone is yet to answer. Their next reaction answers it:
+/// moving to the scene accepts, and doing anything else declines.
+#[derive(Debug, Clone)]
+pub struct SceneInvitation {
+    pub uuid: SceneInvitationUuid,
+    pub scene_uuid: SceneUuid,
+    pub scene_name: String,
+    pub inviter_name: String,
+}
+
+impl SceneInvitation {
+    pub fn to_text(&self) -> String {
+        format!(
+            "{} invited you to come to the scene \"{}\"",
+            self.inviter_name, self.scene_name
+        )
+    }
+
+    /// For the reaction prompts of someone with invitations to answer.
+    pub fn many_to_prompt_section(invitations: &[SceneInvitation]) -> Option<String> {
+        if invitations.is_empty() {
+            return None;
+        }
+
+        let lines = invitations
+            .iter()
+            .map(|invitation| format!("- {}.", invitation.to_text()))
+            .collect::<Vec<String>>()
+            .join("\n");
+
+        Some(format!(
+            "Invitations waiting for your answer:\n{}\nTo accept one, move to its scene. Anything else you do now declines them.",
+            lines
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_prompt_section_lists_invitations_and_how_to_answer() {
+        assert_eq!(SceneInvitation::many_to_prompt_section(&[]), None);
+
+        let invitation = SceneInvitation {
+            uuid: SceneInvitationUuid::test_id(1),
+            scene_uuid: SceneUuid::test_id(2),
+            scene_name: "the roof".to_string(),
+            inviter_name: "Bob".to_string(),
+        };
+
+        assert_eq!(
+            SceneInvitation::many_to_prompt_section(&[invitation]),
+            Some("Invitations waiting for your answer:\n- Bob invited you to come to the scene \"the roof\".\nTo accept one, move to its scene. Anything else you do now declines them.".to_string())
+        );
+    }
+}
@@ -0,0 +1,148 @@
+use crate::capability::job::JobCapability;
+use crate::capability::message::MessageCapability;
+use crate::capability::moderation::ModerationCapability;
+use crate::capability::person::PersonCapability;
+use crate::capability::scene::SceneCapability;
+use crate::capability::state_of_mind::StateOfMindCapability;
+use crate::domain::actor_uuid::ActorUuid;
+use crate::domain::job::send_message_to_scene::{
+    send_scene_message_with_staggered_reactions, ReactionStagger, SceneMessageOutcome,
+};
+use crate::domain::message::MessageSender;
+use crate::domain::person_name::PersonName;
+use crate::domain::random_seed::RandomSeed;
+use crate::domain::scene_uuid::SceneUuid;
+use crate::nice_display::{with_context, NiceDisplay};
+
+pub struct Kickoff {
+    pub scene_uuid: SceneUuid,
+    pub opener: String,
+    pub stagger: ReactionStagger,
+    pub random_seed: RandomSeed,
+}
+
+pub enum Error {
+    GetParticipants(String),
+    CheckParticipant {
+        person_name: PersonName,
+        details: String,
+    },
+    NoParticipants,
+    ParticipantsNotReady(Vec<String>),
+    Opener(String),
+    OpenerBlocked(Vec<String>),
+}
+
+impl NiceDisplay for Error {
+    fn message(&self) -> String {
+        match self {
+            Error::GetParticipants(details) => {
+                with_context("Failed to get scene participants", details)
+            }
+            Error::CheckParticipant {
+                person_name,
+                details,
+            } => with_context(format!("Failed to check on {}", person_name), details),
+            Error::NoParticipants => "The scene has no ai participants to kick off".to_string(),
+            Error::ParticipantsNotReady(problems) => {
+                format!(
+                    "Some participants cannot react yet:\n{}",
+                    problems.join("\n")
+                )
+            }
+            Error::Opener(details) => with_context("Failed to send the opener", details),
+            Error::OpenerBlocked(categories) => {
+                format!(
+                    "The opener was blocked by moderation: {}",
+                    categories.join(", ")
+                )
+            }
+        }
+    }
+}
+
+/// Makes sure every ai participant can react, then sends the opener and
+/// schedules each of their first reactions a little apart. Returns the names
+/// of the participants who will react.
+pub async fn kickoff_scene<
+    W: SceneCapability
+        + MessageCapability
+        + JobCapability
+        + ModerationCapability
+        + PersonCapability
+        + StateOfMindCapability,
+>(
+    worker: &W,
+    kickoff: Kickoff,
+) -> Result<Vec<PersonName>, Error> {
+    let participants = worker
+        .get_scene_current_participants(&kickoff.scene_uuid)
+        .await
+        .map_err(Error::GetParticipants)?;
+
+    let mut person_names = Vec::new();
+    let mut problems = Vec::new();
+
+    for participant in participants {
+        let person_uuid = match participant.actor_uuid {
+            ActorUuid::AiPerson(person_uuid) => person_uuid,
+            ActorUuid::RealWorldUser => continue,
+        };
+
+        let check_error = |details: String| Error::CheckParticipant {
+            person_name: participant.person_name.clone(),
+            details,
+        };
+
+        if !worker
+            .is_person_enabled(&person_uuid)
+            .await
+            .map_err(check_error)?
+        {
+            problems.push(format!("{} is disabled", participant.person_name));
+        }
+
+        if worker
+            .is_person_hibernating(&person_uuid)
+            .await
+            .map_err(check_error)?
+        {
+            problems.push(format!("{} is hibernating", participant.person_name));
+        }
+
+        if worker
+            .get_latest_state_of_mind(&person_uuid)
+            .await
+            .map_err(check_error)?
+            .is_none()
+        {
+            problems.push(format!("{} has no state of mind", participant.person_name));
+        }
+
+        person_names.push(participant.person_name);
+    }
+
+    if person_names.is_empty() {
+        return Err(Error::NoParticipants);
+    }
+
+    if !problems.is_empty() {
+        return Err(Error::ParticipantsNotReady(problems));
+    }
+
+    let outcome = send_scene_message_with_staggered_reactions(
+        worker,
+        MessageSender::RealWorldUser,
+        kickoff.scene_uuid,
+        kickoff.opener,
+        kickoff.random_seed,
+        kickoff.stagger,
+    )
+    .await
+    .map_err(|err| Error::Opener(err.message()))?;
+
+    match outcome {
+        SceneMessageOutcome::Sent { .. } => Ok(person_names),
+        SceneMessageOutcome::Blocked { categories } => Err(Error::OpenerBlocked(categories)),
+    }
+}
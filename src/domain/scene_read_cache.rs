@@ -0,0 +1,187 @@
+use crate::capability::scene::SceneParticipant;
+use crate::domain::scene_timeline::TimelinePage;
+use crate::domain::scene_uuid::SceneUuid;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+/// Recent reads of busy scenes, kept so refreshing a timeline does not
+/// re-run every query. The database says when a scene changes, and the
+/// scene is dropped from here. Until the worker is listening for that,
+/// nothing is cached, since nothing would tell it the reads went stale.
+#[derive(Debug, Clone, Default)]
+pub struct SceneReadCache {
+    state: Arc<Mutex<State>>,
+}
+
+#[derive(Debug, Default)]
+struct State {
+    live: bool,
+    /// Goes up whenever anything is dropped. A read that started before a
+    /// drop might have seen the old rows, so it is not kept.
+    generation: u64,
+    scenes: HashMap<SceneUuid, CachedScene>,
+}
+
+#[derive(Debug, Default)]
+struct CachedScene {
+    participants: Option<Vec<SceneParticipant>>,
+    description: Option<Option<String>>,
+    /// The newest page of the timeline, by how many items were asked for
+    timelines: HashMap<i64, TimelinePage>,
+}
+
+impl SceneReadCache {
+    pub fn new() -> Self {
+        SceneReadCache::default()
+    }
+
+    fn lock(&self) -> MutexGuard<'_, State> {
+        self.state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// Take this before reading from the database, and pass it back when
+    /// keeping what was read.
+    pub fn generation(&self) -> u64 {
+        self.lock().generation
+    }
+
+    pub fn set_live(&self, live: bool) {
+        let mut state = self.lock();
+        state.live = live;
+        state.generation += 1;
+        state.scenes.clear();
+    }
+
+    pub fn invalidate(&self, scene_uuid: &SceneUuid) {
+        let mut state = self.lock();
+        state.generation += 1;
+        state.scenes.remove(scene_uuid);
+    }
+
+    pub fn clear(&self) {
+        let mut state = self.lock();
+        state.generation += 1;
+        state.scenes.clear();
+    }
+
+    fn get<T>(&self, scene_uuid: &SceneUuid, read: impl FnOnce(&CachedScene) -> T) -> Option<T> {
+        let state = self.lock();
+        if !state.live {
+            return None;
+        }
+        state.scenes.get(scene_uuid).map(read)
+    }
+
+    fn put(&self, scene_uuid: &SceneUuid, generation: u64, write: impl FnOnce(&mut CachedScene)) {
+        let mut state = self.lock();
+        if !state.live || state.generation != generation {
+            return;
+        }
+        write(state.scenes.entry(scene_uuid.clone()).or_default());
+    }
+
+    pub fn get_participants(&self, scene_uuid: &SceneUuid) -> Option<Vec<SceneParticipant>> {
+        self.get(scene_uuid, |scene| scene.participants.clone())
+            .flatten()
+    }
+
+    pub fn put_participants(
+        &self,
+        scene_uuid: &SceneUuid,
+        generation: u64,
+        participants: Vec<SceneParticipant>,
+    ) {
+        self.put(scene_uuid, generation, |scene| {
+            scene.participants = Some(participants)
+        });
+    }
+
+    pub fn get_description(&self, scene_uuid: &SceneUuid) -> Option<Option<String>> {
+        self.get(scene_uuid, |scene| scene.description.clone())
+            .flatten()
+    }
+
+    pub fn put_description(
+        &self,
+        scene_uuid: &SceneUuid,
+        generation: u64,
+        description: Option<String>,
+    ) {
+        self.put(scene_uuid, generation, |scene| {
+            scene.description = Some(description)
+        });
+    }
+
+    pub fn get_timeline(&self, scene_uuid: &SceneUuid, limit: i64) -> Option<TimelinePage> {
+        self.get(scene_uuid, |scene| scene.timelines.get(&limit).cloned())
+            .flatten()
+    }
+
+    pub fn put_timeline(
+        &self,
+        scene_uuid: &SceneUuid,
+        generation: u64,
+        limit: i64,
+        page: TimelinePage,
+    ) {
+        self.put(scene_uuid, generation, |scene| {
+            scene.timelines.insert(limit, page);
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn live_cache() -> SceneReadCache {
+        let cache = SceneReadCache::new();
+        cache.set_live(true);
+        cache
+    }
+
+    #[test]
+    fn test_reads_are_kept_until_their_scene_changes() {
+        let cache = live_cache();
+        let scene_uuid = SceneUuid::test_id(1);
+        let other_scene_uuid = SceneUuid::test_id(2);
+
+        let generation = cache.generation();
+        cache.put_description(&scene_uuid, generation, Some("A foggy pier".to_string()));
+        cache.put_description(&other_scene_uuid, generation, None);
+
+        assert_eq!(
+            cache.get_description(&scene_uuid),
+            Some(Some("A foggy pier".to_string()))
+        );
+
+        cache.invalidate(&scene_uuid);
+
+        assert_eq!(cache.get_description(&scene_uuid), None);
+        assert_eq!(cache.get_description(&other_scene_uuid), Some(None));
+    }
+
+    #[test]
+    fn test_reads_that_raced_a_change_are_not_kept() {
+        let cache = live_cache();
+        let scene_uuid = SceneUuid::test_id(1);
+
+        let generation = cache.generation();
+        cache.invalidate(&scene_uuid);
+        cache.put_description(&scene_uuid, generation, Some("Stale".to_string()));
+
+        assert_eq!(cache.get_description(&scene_uuid), None);
+    }
+
+    #[test]
+    fn test_nothing_is_cached_while_not_listening() {
+        let cache = SceneReadCache::new();
+        let scene_uuid = SceneUuid::test_id(1);
+
+        cache.put_description(&scene_uuid, cache.generation(), None);
+
+        assert_eq!(cache.get_description(&scene_uuid), None);
+    }
+}
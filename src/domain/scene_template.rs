@@ -0,0 +1,162 @@
+use crate::capability::job::JobCapability;
+use crate::capability::message::MessageCapability;
+use crate::capability::moderation::ModerationCapability;
+use crate::capability::scene::{NewScene, SceneCapability};
+use crate::domain::job::send_message_to_scene::{
+    send_scene_message_and_enqueue_recipients, SceneMessageOutcome,
+};
+use crate::domain::message::MessageSender;
+use crate::domain::person_name::PersonName;
+use crate::domain::random_seed::RandomSeed;
+use crate::domain::scene_uuid::SceneUuid;
+use crate::domain::world_time::TimeOfDay;
+use crate::nice_display::{with_context, NiceDisplay};
+use chrono::{DateTime, Utc};
+
+const DATE_PLACEHOLDER: &str = "{date}";
+const TIME_PLACEHOLDER: &str = "{time}";
+
+#[derive(Debug, Clone)]
+pub struct SceneTemplate {
+    pub name: String,
+    /// Pattern for the names of scenes made from this template. `{date}` and
+    /// `{time}` are replaced with when the scene was started.
+    pub name_pattern: String,
+    pub description: String,
+    pub starting_cast: Vec<PersonName>,
+    /// Sent into the scene by the real world user once the cast is in place.
+    pub opening_message: String,
+    /// What time it is in the scene when it starts. Without one the scene
+    /// keeps the active clock's time of day.
+    pub time_of_day: Option<TimeOfDay>,
+}
+
+pub enum Error {
+    CreateScene(String),
+    SetTimeOfDay(String),
+    AddParticipant {
+        person_name: PersonName,
+        details: String,
+    },
+    OpeningMessage(String),
+    OpeningMessageBlocked(Vec<String>),
+}
+
+impl NiceDisplay for Error {
+    fn message(&self) -> String {
+        match self {
+            Error::CreateScene(details) => {
+                with_context("Failed to create scene from template", details)
+            }
+            Error::SetTimeOfDay(details) => {
+                with_context("Failed to set the new scene's time of day", details)
+            }
+            Error::AddParticipant {
+                person_name,
+                details,
+            } => with_context(
+                format!("Failed to add {} to the new scene", person_name),
+                details,
+            ),
+            Error::OpeningMessage(details) => {
+                with_context("Failed to send the opening message", details)
+            }
+            Error::OpeningMessageBlocked(categories) => {
+                format!(
+                    "The opening message was blocked by moderation: {}",
+                    categories.join(", ")
+                )
+            }
+        }
+    }
+}
+
+impl SceneTemplate {
+    pub fn scene_name(&self, now: DateTime<Utc>) -> String {
+        self.name_pattern
+            .replace(
+                DATE_PLACEHOLDER,
+                now.format("%Y-%m-%d").to_string().as_str(),
+            )
+            .replace(TIME_PLACEHOLDER, now.format("%H:%M").to_string().as_str())
+    }
+}
+
+/// Creates a scene from the template, adds the starting cast, and sends the
+/// opening message so the participants start reacting right away.
+pub async fn start_scene<
+    W: SceneCapability + MessageCapability + JobCapability + ModerationCapability,
+>(
+    worker: &W,
+    template: &SceneTemplate,
+    scene_name: String,
+    random_seed: RandomSeed,
+) -> Result<SceneUuid, Error> {
+    let scene_uuid = worker
+        .create_scene(NewScene {
+            name: scene_name,
+            description: template.description.clone(),
+        })
+        .await
+        .map_err(Error::CreateScene)?;
+
+    if let Some(time_of_day) = template.time_of_day {
+        worker
+            .set_scene_time_of_day(&scene_uuid, time_of_day)
+            .await
+            .map_err(Error::SetTimeOfDay)?;
+    }
+
+    for person_name in template.starting_cast.iter() {
+        worker
+            .add_person_to_scene(scene_uuid.clone(), person_name.clone())
+            .await
+            .map_err(|details| Error::AddParticipant {
+                person_name: person_name.clone(),
+                details,
+            })?;
+    }
+
+    if template.opening_message.trim().is_empty() {
+        return Ok(scene_uuid);
+    }
+
+    let outcome = send_scene_message_and_enqueue_recipients(
+        worker,
+        MessageSender::RealWorldUser,
+        scene_uuid.clone(),
+        template.opening_message.clone(),
+        random_seed,
+    )
+    .await
+    .map_err(|err| Error::OpeningMessage(err.message()))?;
+
+    match outcome {
+        SceneMessageOutcome::Sent { .. } => Ok(scene_uuid),
+        SceneMessageOutcome::Blocked { categories } => {
+            Err(Error::OpeningMessageBlocked(categories))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_scene_name_fills_in_date_and_time() {
+        let template = SceneTemplate {
+            name: "Diner".to_string(),
+            name_pattern: "Diner morning {date} {time}".to_string(),
+            description: "A small diner".to_string(),
+            starting_cast: vec![],
+            opening_message: String::new(),
+            time_of_day: None,
+        };
+
+        let now = Utc.with_ymd_and_hms(2026, 10, 17, 8, 30, 0).unwrap();
+
+        assert_eq!(template.scene_name(now), "Diner morning 2026-10-17 08:30");
+    }
+}
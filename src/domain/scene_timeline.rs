@@ -0,0 +1,233 @@
+use crate::domain::scene_ambience::Ambience;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Bumped whenever a field is removed or changes meaning. Adding fields or
+/// item types does not bump it, so renderers should ignore what they do not
+/// know.
+pub const SCHEMA_VERSION: u32 = 1;
+
+pub const DEFAULT_PAGE_SIZE: i64 = 50;
+pub const MAX_PAGE_SIZE: i64 = 200;
+
+/// One thing that happened in a scene, with names already resolved.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum TimelineItem {
+    Message {
+        message_uuid: Uuid,
+        at: DateTime<Utc>,
+        sender: TimelineSpeaker,
+        content: String,
+        /// "everyone", "whisper" or "side conversation"
+        audience: String,
+    },
+    Joined {
+        at: DateTime<Utc>,
+        person_uuid: Uuid,
+        person_name: String,
+    },
+    Left {
+        at: DateTime<Utc>,
+        person_uuid: Uuid,
+        person_name: String,
+    },
+    /// What someone took in about the scene as they walked in.
+    ArrivalSummary {
+        at: DateTime<Utc>,
+        person_uuid: Uuid,
+        person_name: String,
+        summary: String,
+    },
+    /// The latest snapshot of how the scene looks.
+    SceneSnapshot {
+        at: DateTime<Utc>,
+        description: String,
+    },
+    /// The weather, noise or lighting of the scene changed.
+    AmbienceChanged {
+        at: DateTime<Utc>,
+        ambience: Ambience,
+    },
+    /// Something happened that nobody said, like a phone ringing.
+    SceneEvent {
+        at: DateTime<Utc>,
+        description: String,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TimelineSpeaker {
+    /// Missing when the real world user said it.
+    pub person_uuid: Option<Uuid>,
+    pub name: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TimelineQuery {
+    /// Only items strictly before this time. The newest items when missing.
+    pub before: Option<DateTime<Utc>>,
+    pub limit: i64,
+}
+
+/// A page of the timeline, oldest item first. Pass `next_before` back as
+/// `before` to get the page before this one.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TimelinePage {
+    pub schema_version: u32,
+    pub scene_uuid: Uuid,
+    pub items: Vec<TimelineItem>,
+    pub next_before: Option<DateTime<Utc>>,
+}
+
+impl TimelineItem {
+    pub fn at(&self) -> DateTime<Utc> {
+        match self {
+            TimelineItem::Message { at, .. } => *at,
+            TimelineItem::Joined { at, .. } => *at,
+            TimelineItem::Left { at, .. } => *at,
+            TimelineItem::ArrivalSummary { at, .. } => *at,
+            TimelineItem::SceneSnapshot { at, .. } => *at,
+            TimelineItem::AmbienceChanged { at, .. } => *at,
+            TimelineItem::SceneEvent { at, .. } => *at,
+        }
+    }
+}
+
+impl TimelineQuery {
+    pub fn new(before: Option<DateTime<Utc>>, limit: Option<i64>) -> Self {
+        TimelineQuery {
+            before,
+            limit: limit.unwrap_or(DEFAULT_PAGE_SIZE).clamp(1, MAX_PAGE_SIZE),
+        }
+    }
+}
+
+impl TimelinePage {
+    /// Builds a page from up to `limit + 1` items, newest first. The extra item
+    /// only tells us there is another page.
+    pub fn from_newest_first(
+        scene_uuid: Uuid,
+        mut newest_first: Vec<TimelineItem>,
+        limit: i64,
+    ) -> Self {
+        let limit = usize::try_from(limit).unwrap_or(0);
+        let has_more = newest_first.len() > limit;
+        newest_first.truncate(limit);
+        newest_first.reverse();
+
+        let next_before = if has_more {
+            newest_first.first().map(|item| item.at())
+        } else {
+            None
+        };
+
+        TimelinePage {
+            schema_version: SCHEMA_VERSION,
+            scene_uuid,
+            items: newest_first,
+            next_before,
+        }
+    }
+}
+
+/// Remembers how far a scene has been followed, so polling the newest page
+/// over and over only yields each item once.
+#[derive(Debug, Clone, Default)]
+pub struct TailCursor {
+    last_at: Option<DateTime<Utc>>,
+    /// Items at exactly `last_at`, since more can still show up at that time.
+    seen_at_last: Vec<TimelineItem>,
+}
+
+impl TailCursor {
+    pub fn new() -> Self {
+        TailCursor::default()
+    }
+
+    /// The items of an oldest first page that have not been taken before.
+    pub fn take_new(&mut self, items: Vec<TimelineItem>) -> Vec<TimelineItem> {
+        let mut new_items = Vec::new();
+
+        for item in items {
+            let at = item.at();
+            let is_new = match self.last_at {
+                None => true,
+                Some(last_at) if at > last_at => true,
+                Some(last_at) if at == last_at => !self.seen_at_last.contains(&item),
+                Some(_) => false,
+            };
+
+            if !is_new {
+                continue;
+            }
+
+            if self.last_at != Some(at) {
+                self.last_at = Some(at);
+                self.seen_at_last.clear();
+            }
+            self.seen_at_last.push(item.clone());
+            new_items.push(item);
+        }
+
+        new_items
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn joined(second: u32) -> TimelineItem {
+        TimelineItem::Joined {
+            at: Utc.with_ymd_and_hms(2026, 1, 1, 0, 0, second).unwrap(),
+            person_uuid: Uuid::from_u128(1),
+            person_name: "Hank".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_page_is_oldest_first_with_a_cursor_when_more_remain() {
+        let page = TimelinePage::from_newest_first(
+            Uuid::from_u128(9),
+            vec![joined(3), joined(2), joined(1)],
+            2,
+        );
+
+        assert_eq!(page.items, vec![joined(2), joined(3)]);
+        assert_eq!(page.next_before, Some(joined(2).at()));
+
+        let last_page = TimelinePage::from_newest_first(Uuid::from_u128(9), vec![joined(1)], 2);
+        assert_eq!(last_page.next_before, None);
+    }
+
+    #[test]
+    fn test_tail_cursor_takes_each_item_once() {
+        let left = TimelineItem::Left {
+            at: joined(2).at(),
+            person_uuid: Uuid::from_u128(1),
+            person_name: "Hank".to_string(),
+        };
+        let mut cursor = TailCursor::new();
+
+        assert_eq!(
+            cursor.take_new(vec![joined(1), joined(2)]),
+            vec![joined(1), joined(2)]
+        );
+        assert_eq!(
+            cursor.take_new(vec![joined(1), joined(2), left.clone(), joined(3)]),
+            vec![left, joined(3)]
+        );
+        assert!(cursor.take_new(vec![joined(2), joined(3)]).is_empty());
+    }
+
+    #[test]
+    fn test_items_serialize_with_a_type_tag() {
+        let json = serde_json::to_value(joined(1)).unwrap();
+
+        assert_eq!(json["type"], "joined");
+        assert_eq!(json["person_name"], "Hank");
+    }
+}
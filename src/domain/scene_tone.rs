@@ -0,0 +1,135 @@
+use std::fmt::Display;
+
+/// How a scene sounds. Persons in a scene with a tone profile are told to
+/// talk that way in their reaction prompts, and what gets through
+/// moderation there is tightened or loosened to match, so a nursery and a
+/// back alley in the same world can be moderated differently. Scenes
+/// without one use the world's voice and threshold.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ToneProfile {
+    FamilyFriendly,
+    Noir,
+    Shakespearean,
+}
+
+/// However loose a scene's tone, moderation still blocks anything scoring
+/// this high.
+const LOOSENED_THRESHOLD_CEILING: f64 = 0.9;
+
+impl ToneProfile {
+    pub const ALL: [ToneProfile; 3] = [
+        ToneProfile::FamilyFriendly,
+        ToneProfile::Noir,
+        ToneProfile::Shakespearean,
+    ];
+
+    pub fn to_name(&self) -> &'static str {
+        match self {
+            ToneProfile::FamilyFriendly => "family-friendly",
+            ToneProfile::Noir => "noir",
+            ToneProfile::Shakespearean => "shakespearean",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Result<Self, String> {
+        ToneProfile::ALL
+            .into_iter()
+            .find(|tone_profile| tone_profile.to_name() == name.trim())
+            .ok_or_else(|| format!("Unknown tone profile \"{}\"", name))
+    }
+
+    pub fn to_prompt_section(&self) -> String {
+        let instruction = match self {
+            ToneProfile::FamilyFriendly => {
+                "This scene is family friendly. Keep what you say clean: no swearing, crude jokes, innuendo or graphic violence, even when provoked."
+            }
+            ToneProfile::Noir => {
+                "This scene is noir. Talk terse and world-weary, with dry wit, hard-boiled slang and a cynical edge. Menace and talk of violence fit here."
+            }
+            ToneProfile::Shakespearean => {
+                "This scene is Shakespearean. Speak in Early Modern English, with thee and thou, flourishes and the occasional line of verse, like a player on the Elizabethan stage."
+            }
+        };
+
+        format!("Scene tone:\n- {}", instruction)
+    }
+
+    /// The threshold a moderation category is held to in this scene, given
+    /// the world's. Hate, sexual content and self harm are never loosened.
+    pub fn moderation_threshold(&self, category: &str, world_threshold: f64) -> f64 {
+        let loosened_to =
+            |threshold: f64| world_threshold.max(threshold.min(LOOSENED_THRESHOLD_CEILING));
+
+        match self {
+            ToneProfile::FamilyFriendly => world_threshold.min(0.2),
+            ToneProfile::Noir => {
+                if category.starts_with("violence") || category.starts_with("harassment") {
+                    loosened_to(0.8)
+                } else {
+                    world_threshold
+                }
+            }
+            ToneProfile::Shakespearean => {
+                if category.starts_with("violence") {
+                    loosened_to(0.7)
+                } else {
+                    world_threshold
+                }
+            }
+        }
+    }
+}
+
+impl Display for ToneProfile {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.to_name())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_names_round_trip() {
+        for tone_profile in ToneProfile::ALL {
+            assert_eq!(
+                ToneProfile::from_name(tone_profile.to_name()),
+                Ok(tone_profile)
+            );
+        }
+
+        assert!(ToneProfile::from_name("gothic").is_err());
+    }
+
+    #[test]
+    fn test_moderation_thresholds_follow_the_tone() {
+        assert_eq!(
+            ToneProfile::FamilyFriendly.moderation_threshold("violence", 0.5),
+            0.2
+        );
+        assert_eq!(ToneProfile::Noir.moderation_threshold("violence", 0.5), 0.8);
+        assert_eq!(
+            ToneProfile::Noir.moderation_threshold("harassment/threatening", 0.5),
+            0.8
+        );
+        assert_eq!(ToneProfile::Noir.moderation_threshold("hate", 0.5), 0.5);
+        assert_eq!(
+            ToneProfile::Shakespearean.moderation_threshold("violence/graphic", 0.5),
+            0.7
+        );
+    }
+
+    #[test]
+    fn test_tones_only_move_thresholds_their_own_way() {
+        assert_eq!(ToneProfile::Noir.moderation_threshold("violence", 0.1), 0.8);
+        assert_eq!(
+            ToneProfile::Noir.moderation_threshold("violence", 0.95),
+            0.95
+        );
+        assert_eq!(
+            ToneProfile::FamilyFriendly.moderation_threshold("sexual", 0.1),
+            0.1
+        );
+    }
+}
@@ -0,0 +1,212 @@
+use crate::capability::schema::SchemaCapability;
+use crate::domain::doctor;
+use chrono::{DateTime, Utc};
+
+#[derive(Debug, Clone)]
+pub struct AppliedMigration {
+    pub name: String,
+    pub applied_at: DateTime<Utc>,
+}
+
+/// From `pg_stat_user_tables`, so the row count is Postgres' estimate
+/// rather than a `COUNT(*)`.
+#[derive(Debug, Clone)]
+pub struct TableStats {
+    pub name: String,
+    pub estimated_rows: i64,
+    /// Including indexes and toast.
+    pub total_bytes: i64,
+    pub seq_scans: i64,
+    pub index_scans: i64,
+}
+
+#[derive(Debug, Clone)]
+pub struct IndexStats {
+    pub table_name: String,
+    pub name: String,
+    pub scans: i64,
+    pub bytes: i64,
+    pub is_valid: bool,
+    pub is_unique: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IndexHealth {
+    Healthy,
+    /// Never scanned since the stats were last reset. Unique indexes and
+    /// primary keys are still doing their job, so they never count.
+    Unused,
+    /// Left behind by a failed `CREATE INDEX CONCURRENTLY`, it slows writes
+    /// without being used for reads.
+    Invalid,
+}
+
+#[derive(Debug, Clone)]
+pub struct SchemaOverview {
+    /// Oldest first.
+    pub applied_migrations: Vec<AppliedMigration>,
+    /// Migration files that have not been recorded as run.
+    pub pending_migrations: Vec<String>,
+    /// Largest first.
+    pub tables: Vec<TableStats>,
+    pub indexes: Vec<IndexStats>,
+}
+
+impl IndexStats {
+    pub fn health(&self) -> IndexHealth {
+        if !self.is_valid {
+            IndexHealth::Invalid
+        } else if self.scans == 0 && !self.is_unique {
+            IndexHealth::Unused
+        } else {
+            IndexHealth::Healthy
+        }
+    }
+}
+
+impl IndexHealth {
+    pub fn to_label(self) -> &'static str {
+        match self {
+            IndexHealth::Healthy => "healthy",
+            IndexHealth::Unused => "unused",
+            IndexHealth::Invalid => "invalid",
+        }
+    }
+}
+
+impl SchemaOverview {
+    pub fn new(
+        migration_names: &[String],
+        applied_migrations: Vec<AppliedMigration>,
+        tables: Vec<TableStats>,
+        indexes: Vec<IndexStats>,
+    ) -> Self {
+        let applied_names: Vec<String> = applied_migrations
+            .iter()
+            .map(|migration| migration.name.clone())
+            .collect();
+
+        SchemaOverview {
+            pending_migrations: doctor::pending_migrations(migration_names, &applied_names),
+            applied_migrations,
+            tables,
+            indexes,
+        }
+    }
+
+    /// `migration_names` are the files in `db/migrations`.
+    pub async fn load<W: SchemaCapability>(
+        worker: &W,
+        migration_names: &[String],
+    ) -> Result<Self, String> {
+        let applied_migrations = worker.get_applied_migrations().await?;
+        let tables = worker.get_table_stats().await?;
+        let indexes = worker.get_index_stats().await?;
+
+        Ok(SchemaOverview::new(
+            migration_names,
+            applied_migrations,
+            tables,
+            indexes,
+        ))
+    }
+
+    /// The newest migration that has run, by file name.
+    pub fn schema_version(&self) -> Option<&str> {
+        self.applied_migrations
+            .iter()
+            .map(|migration| migration.name.as_str())
+            .max()
+    }
+
+    /// Invalid and unused indexes, invalid ones first.
+    pub fn unhealthy_indexes(&self) -> Vec<&IndexStats> {
+        let mut unhealthy: Vec<&IndexStats> = self
+            .indexes
+            .iter()
+            .filter(|index| index.health() != IndexHealth::Healthy)
+            .collect();
+
+        unhealthy.sort_by_key(|index| (index.health() != IndexHealth::Invalid, index.name.clone()));
+        unhealthy
+    }
+}
+
+/// Like `1.5 MB`.
+pub fn format_bytes(bytes: i64) -> String {
+    const UNITS: [&str; 5] = ["B", "kB", "MB", "GB", "TB"];
+
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+
+    if unit == 0 {
+        format!("{} B", bytes)
+    } else {
+        format!("{:.1} {}", size, UNITS[unit])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn index(name: &str, scans: i64, is_valid: bool, is_unique: bool) -> IndexStats {
+        IndexStats {
+            table_name: "message".to_string(),
+            name: name.to_string(),
+            scans,
+            bytes: 8192,
+            is_valid,
+            is_unique,
+        }
+    }
+
+    #[test]
+    fn test_index_health() {
+        assert_eq!(
+            index("idx_a", 10, true, false).health(),
+            IndexHealth::Healthy
+        );
+        assert_eq!(index("idx_b", 0, true, false).health(), IndexHealth::Unused);
+        assert_eq!(
+            index("message_pkey", 0, true, true).health(),
+            IndexHealth::Healthy
+        );
+        assert_eq!(
+            index("idx_c", 5, false, false).health(),
+            IndexHealth::Invalid
+        );
+    }
+
+    #[test]
+    fn test_unhealthy_indexes_put_invalid_first() {
+        let overview = SchemaOverview::new(
+            &[],
+            vec![],
+            vec![],
+            vec![
+                index("idx_a", 0, true, false),
+                index("idx_b", 3, true, false),
+                index("idx_c", 3, false, false),
+            ],
+        );
+
+        let names: Vec<&str> = overview
+            .unhealthy_indexes()
+            .iter()
+            .map(|index| index.name.as_str())
+            .collect();
+        assert_eq!(names, vec!["idx_c", "idx_a"]);
+    }
+
+    #[test]
+    fn test_format_bytes() {
+        assert_eq!(format_bytes(512), "512 B");
+        assert_eq!(format_bytes(1536), "1.5 kB");
+        assert_eq!(format_bytes(3 * 1024 * 1024 * 1024), "3.0 GB");
+    }
+}
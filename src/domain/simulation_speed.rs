@@ -0,0 +1,63 @@
+use std::time::Duration;
+
+/// Slow enough to follow a busy scene line by line.
+pub const MIN_TIME_FACTOR: f64 = 0.1;
+/// A world-day in under three real minutes.
+pub const MAX_TIME_FACTOR: f64 = 600.0;
+
+/// The speeds the admin ui offers, from half speed to a world-hour every
+/// six real seconds.
+pub const TIME_FACTOR_PRESETS: [f64; 5] = [0.5, 1.0, 10.0, 60.0, 600.0];
+
+pub fn validate_time_factor(time_factor: f64) -> Result<f64, String> {
+    if time_factor.is_finite() && (MIN_TIME_FACTOR..=MAX_TIME_FACTOR).contains(&time_factor) {
+        Ok(time_factor)
+    } else {
+        Err(format!(
+            "The time factor must be from {} to {}, but it was {}",
+            MIN_TIME_FACTOR, MAX_TIME_FACTOR, time_factor
+        ))
+    }
+}
+
+/// Like `60x` or `0.5x`.
+pub fn time_factor_label(time_factor: f64) -> String {
+    format!("{}x", time_factor)
+}
+
+/// How many active clock ms pass in `elapsed` real time at `time_factor`.
+pub fn scaled_elapsed_ms(elapsed: Duration, time_factor: f64) -> i64 {
+    let scaled = elapsed.as_millis() as f64 * time_factor;
+
+    if scaled >= i64::MAX as f64 {
+        i64::MAX
+    } else {
+        scaled.max(0.0) as i64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scaled_elapsed_ms_speeds_up_and_slows_down() {
+        let elapsed = Duration::from_secs(6);
+
+        assert_eq!(scaled_elapsed_ms(elapsed, 1.0), 6_000);
+        assert_eq!(scaled_elapsed_ms(elapsed, 600.0), 60 * 60 * 1000);
+        assert_eq!(scaled_elapsed_ms(elapsed, 0.5), 3_000);
+        assert_eq!(time_factor_label(0.5), "0.5x");
+        assert_eq!(time_factor_label(60.0), "60x");
+    }
+
+    #[test]
+    fn test_validate_time_factor() {
+        for preset in TIME_FACTOR_PRESETS {
+            assert_eq!(validate_time_factor(preset), Ok(preset));
+        }
+        assert!(validate_time_factor(0.0).is_err());
+        assert!(validate_time_factor(f64::NAN).is_err());
+        assert!(validate_time_factor(601.0).is_err());
+    }
+}
@@ -1,3 +1,6 @@
+use crate::domain::person_directory::{self, PersonDirectoryEntry};
+use crate::domain::scene_ambience::Ambience;
+use crate::domain::world_time::WorldTime;
 use std::fmt::Display;
 
 #[derive(Clone, Debug)]
@@ -6,7 +9,11 @@ pub struct Situation {
     scene_name: Option<String>,
     scene_description: Option<String>,
     participants: Vec<String>,
+    directory: Vec<PersonDirectoryEntry>,
+    knowledge: Option<String>,
     messages: Vec<String>,
+    world_time: WorldTime,
+    ambience: Option<Ambience>,
 }
 
 pub struct Input {
@@ -14,7 +21,14 @@ pub struct Input {
     pub scene_name: Option<String>,
     pub scene_description: Option<String>,
     pub particpants: Vec<String>,
+    /// What the person knows about the others present.
+    pub directory: Vec<PersonDirectoryEntry>,
+    /// Which scenes the person has been in, so they do not know about the rest.
+    pub knowledge: Option<String>,
     pub messages: Vec<String>,
+    pub world_time: WorldTime,
+    /// The weather, noise and lighting where the person is, once set.
+    pub ambience: Option<Ambience>,
 }
 
 impl Situation {
@@ -24,7 +38,11 @@ impl Situation {
             scene_name: input.scene_name,
             scene_description: input.scene_description,
             participants: input.particpants,
+            directory: input.directory,
+            knowledge: input.knowledge,
             messages: input.messages,
+            world_time: input.world_time,
+            ambience: input.ambience,
         }
     }
 
@@ -50,10 +68,36 @@ impl Situation {
             None => "".to_string(),
         };
 
-        format!(
+        let time_text = match &self.ambience {
+            Some(ambience) => format!(
+                "{} {}",
+                self.world_time.to_prompt_text(),
+                ambience.to_prompt_text()
+            ),
+            None => self.world_time.to_prompt_text(),
+        };
+
+        let scene_text = if scene_text.is_empty() {
+            time_text
+        } else {
+            format!("{} {}", time_text, scene_text)
+        };
+
+        let people_text = format!(
             "{}\n\nPeople present (complete list): {}",
             scene_text, participant_list
-        )
+        );
+
+        let people_text = match person_directory::to_prompt_text(&self.person_name, &self.directory)
+        {
+            Some(directory_text) => format!("{}\n\n{}", people_text, directory_text),
+            None => people_text,
+        };
+
+        match &self.knowledge {
+            Some(knowledge) => format!("{}\n\n{}", people_text, knowledge),
+            None => people_text,
+        }
     }
 }
 
@@ -74,3 +118,55 @@ impl Display for Situation {
         write!(f, "{}", s)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::action_budget::WORLD_DAY_MS;
+    use crate::domain::scene_ambience::{Lighting, NoiseLevel, Weather};
+
+    fn situation(scene_name: Option<&str>) -> Situation {
+        Situation::new(Input {
+            person_name: "Ruth".to_string(),
+            scene_name: scene_name.map(|name| name.to_string()),
+            scene_description: None,
+            particpants: vec!["Ruth".to_string(), "Walt".to_string()],
+            directory: vec![],
+            knowledge: None,
+            messages: vec!["Walt: \"Morning!\"".to_string()],
+            world_time: WorldTime::new(WORLD_DAY_MS + 9 * 60 * 60 * 1000, 0),
+            ambience: None,
+        })
+    }
+
+    #[test]
+    fn test_situation_opens_with_the_world_time() {
+        assert_eq!(
+            situation(Some("Diner")).to_people_present_text(),
+            "It is 09:00 in the morning on day 2. Ruth is in the scene \"Diner\".\n\nPeople present (complete list): Ruth, Walt"
+        );
+    }
+
+    #[test]
+    fn test_situation_keeps_the_world_time_without_scene_context() {
+        assert_eq!(
+            situation(None).to_string(),
+            "It is 09:00 in the morning on day 2.\n\nPeople present (complete list): Ruth, Walt\n\nNew messages received (oldest to newest):\nWalt: \"Morning!\""
+        );
+    }
+
+    #[test]
+    fn test_situation_describes_the_ambience_after_the_time() {
+        let mut situation = situation(Some("Diner"));
+        situation.ambience = Some(Ambience {
+            weather: Weather::Rain,
+            noise: NoiseLevel::Lively,
+            lighting: Lighting::Dim,
+        });
+
+        assert_eq!(
+            situation.to_people_present_text(),
+            "It is 09:00 in the morning on day 2. It is raining, there is a lively hum of noise and the light is dim. Ruth is in the scene \"Diner\".\n\nPeople present (complete list): Ruth, Walt"
+        );
+    }
+}
@@ -0,0 +1,168 @@
+use crate::open_ai::embedding::EmbeddingModel;
+use serde_json::{json, Value};
+
+/// What the smoke test person always says back. Finding it in the scene
+/// means the message went all the way through the pipeline.
+pub const REPLY: &str = "Hello! Yes, I can hear you.";
+pub const OPENER: &str = "Hello, is anyone here?";
+
+/// The canned answer the stub gives a chat completion request. Asked to
+/// choose an action, the person says `REPLY`. The reaction validator gets
+/// the json it wants. Everything else, like summaries and the memory
+/// decision, gets a short plain answer and no tool call.
+pub fn completion_response(request: &Value) -> Value {
+    let offers_choose_action = request["tools"]
+        .as_array()
+        .map(|tools| {
+            tools
+                .iter()
+                .any(|tool| tool["function"]["name"] == json!("choose_action"))
+        })
+        .unwrap_or(false);
+
+    let message = if offers_choose_action {
+        let arguments = json!({
+            "action": "say in scene",
+            "comment": REPLY,
+        });
+
+        json!({
+            "role": "assistant",
+            "content": null,
+            "tool_calls": [{
+                "id": "call_smoke_test",
+                "type": "function",
+                "function": {
+                    "name": "choose_action",
+                    "arguments": arguments.to_string(),
+                },
+            }],
+        })
+    } else if asks_for_validation(request) {
+        json!({
+            "role": "assistant",
+            "content": json!({ "is_valid": true, "reason": "smoke test" }).to_string(),
+        })
+    } else {
+        json!({
+            "role": "assistant",
+            "content": "Nothing much is going on.",
+        })
+    };
+
+    json!({
+        "id": "chatcmpl-smoke-test",
+        "object": "chat.completion",
+        "model": request["model"],
+        "choices": [{
+            "index": 0,
+            "message": message,
+            "finish_reason": if offers_choose_action { "tool_calls" } else { "stop" },
+        }],
+    })
+}
+
+fn asks_for_validation(request: &Value) -> bool {
+    request["messages"]
+        .as_array()
+        .map(|messages| {
+            messages.iter().any(|message| {
+                message["content"]
+                    .as_str()
+                    .map(|content| content.contains("is_valid"))
+                    .unwrap_or(false)
+            })
+        })
+        .unwrap_or(false)
+}
+
+/// An embedding of the right size. Not all zero, since those are treated as
+/// missing.
+pub fn embedding_response(model: EmbeddingModel) -> Value {
+    let dimension = usize::try_from(model.dimension()).unwrap_or(0);
+    let embedding = (0..dimension)
+        .map(|index| if index == 0 { 1.0 } else { 0.0 })
+        .collect::<Vec<f32>>();
+
+    json!({
+        "object": "list",
+        "data": [{ "object": "embedding", "index": 0, "embedding": embedding }],
+        "model": model.to_string(),
+    })
+}
+
+/// Nothing is ever flagged.
+pub fn moderation_response() -> Value {
+    json!({
+        "id": "modr-smoke-test",
+        "results": [{
+            "flagged": false,
+            "category_scores": { "harassment": 0.0, "violence": 0.0 },
+        }],
+    })
+}
+
+/// A fresh name every run, so two smoke tests never share a database.
+pub fn database_name(base_name: &str, suffix: u32) -> String {
+    format!("{}_smoke_{:08x}", base_name, suffix)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::open_ai::tool_call::ToolCall;
+    use crate::person_actions::{PersonAction, PersonReaction};
+
+    #[test]
+    fn test_choosing_an_action_says_the_reply() {
+        let request = json!({
+            "model": "gpt-4.1",
+            "messages": [{ "role": "user", "content": "What do you do?" }],
+            "tools": [{ "type": "function", "function": { "name": "choose_action" } }],
+        });
+
+        let tool_call = ToolCall::from_json(&completion_response(&request))
+            .unwrap()
+            .remove(0);
+        let reaction = PersonReaction::from_open_ai_tool_call(tool_call).unwrap();
+
+        match reaction.action {
+            PersonAction::SayInScene { comment, .. } => assert_eq!(comment, REPLY),
+            other => panic!("unexpected action: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_other_requests_get_no_tool_call() {
+        let memory_request = json!({
+            "messages": [{ "role": "user", "content": "Is this worth remembering?" }],
+            "tools": [{ "type": "function", "function": { "name": "create_memory" } }],
+        });
+        let response = completion_response(&memory_request);
+        assert!(response["choices"][0]["message"]
+            .get("tool_calls")
+            .is_none());
+
+        let validator_request = json!({
+            "messages": [{ "role": "system", "content": "Return is_valid and reason." }],
+        });
+        let content = completion_response(&validator_request)["choices"][0]["message"]["content"]
+            .as_str()
+            .unwrap()
+            .to_string();
+        assert_eq!(
+            serde_json::from_str::<Value>(&content).unwrap()["is_valid"],
+            json!(true)
+        );
+    }
+
+    #[test]
+    fn test_embeddings_are_sized_for_the_model() {
+        let response = embedding_response(EmbeddingModel::TextEmbedding3Small);
+        let embedding = response["data"][0]["embedding"].as_array().unwrap();
+
+        assert_eq!(embedding.len(), 1536);
+        assert_eq!(embedding[0], json!(1.0));
+        assert_eq!(database_name("arizona2", 255), "arizona2_smoke_000000ff");
+    }
+}
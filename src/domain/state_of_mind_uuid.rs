@@ -1,16 +1,3 @@
-use uuid::Uuid;
+use crate::domain::uuid_newtype::uuid_newtype;
 
-#[derive(Debug, Clone)]
-pub struct StateOfMindUuid(Uuid);
-
-impl StateOfMindUuid {
-    pub fn new() -> Self {
-        StateOfMindUuid(Uuid::now_v7())
-    }
-    pub fn to_uuid(&self) -> Uuid {
-        self.0
-    }
-    pub fn from_uuid(uuid: Uuid) -> Self {
-        StateOfMindUuid(uuid)
-    }
-}
+uuid_newtype!(StateOfMindUuid);
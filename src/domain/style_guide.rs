@@ -0,0 +1,239 @@
+/// The most sentences anyone can be held to, so a typo like 300 does not
+/// read as "no limit" to the model.
+const MAX_SENTENCES_LIMIT: i32 = 20;
+
+/// How persons phrase what they say. A world has one, and each person can
+/// override any part of it. Empty guides change nothing.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct StyleGuide {
+    /// The most sentences in one thing said out loud.
+    pub max_sentences: Option<i32>,
+    /// Free text like "Texan drawl" or "drops the ends of words".
+    pub dialect: String,
+    pub formality: Option<Formality>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Formality {
+    Casual,
+    Neutral,
+    Formal,
+}
+
+impl Formality {
+    pub const ALL: [Formality; 3] = [Formality::Casual, Formality::Neutral, Formality::Formal];
+
+    pub fn to_name(self) -> &'static str {
+        match self {
+            Formality::Casual => "casual",
+            Formality::Neutral => "neutral",
+            Formality::Formal => "formal",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Result<Self, String> {
+        match name {
+            "casual" => Ok(Formality::Casual),
+            "neutral" => Ok(Formality::Neutral),
+            "formal" => Ok(Formality::Formal),
+            _ => Err(format!("Unknown formality \"{}\"", name)),
+        }
+    }
+
+    fn to_instruction(self) -> &'static str {
+        match self {
+            Formality::Casual => "Speak casually, the way people talk with friends.",
+            Formality::Neutral => "Speak plainly, neither stiff nor slangy.",
+            Formality::Formal => "Speak formally and politely, without slang.",
+        }
+    }
+}
+
+impl StyleGuide {
+    pub fn is_empty(&self) -> bool {
+        self.max_sentences.is_none() && self.dialect.trim().is_empty() && self.formality.is_none()
+    }
+
+    /// This guide with every part `person` sets replaced by theirs.
+    pub fn overridden_by(&self, person: &StyleGuide) -> StyleGuide {
+        StyleGuide {
+            max_sentences: person.max_sentences.or(self.max_sentences),
+            dialect: if person.dialect.trim().is_empty() {
+                self.dialect.clone()
+            } else {
+                person.dialect.clone()
+            },
+            formality: person.formality.or(self.formality),
+        }
+    }
+
+    /// The section appended to reaction system prompts, if there is anything
+    /// to say.
+    pub fn to_prompt_section(&self) -> Option<String> {
+        if self.is_empty() {
+            return None;
+        }
+
+        let mut lines = vec!["Speaking style:".to_string()];
+
+        if let Some(max_sentences) = self.max_sentences {
+            lines.push(format!(
+                "- Say at most {} {} at a time.",
+                max_sentences,
+                if max_sentences == 1 {
+                    "sentence"
+                } else {
+                    "sentences"
+                }
+            ));
+        }
+
+        if !self.dialect.trim().is_empty() {
+            lines.push(format!("- Dialect: {}", self.dialect.trim()));
+        }
+
+        if let Some(formality) = self.formality {
+            lines.push(format!("- {}", formality.to_instruction()));
+        }
+
+        Some(lines.join("\n"))
+    }
+
+    /// Feedback for the model when `comment` runs longer than the guide
+    /// allows.
+    pub fn overlong_feedback(&self, comment: &str) -> Option<String> {
+        let max_sentences = self.max_sentences?;
+        let sentences = count_sentences(comment);
+
+        if sentences as i32 <= max_sentences {
+            return None;
+        }
+
+        Some(format!(
+            "The comment has {} sentences, but this person says at most {} at a time. Say it again, shorter.",
+            sentences, max_sentences
+        ))
+    }
+
+    /// `comment` cut down to the sentences the guide allows.
+    pub fn truncate(&self, comment: &str) -> String {
+        match self.max_sentences {
+            Some(max_sentences) => truncate_to_sentences(comment, max_sentences.max(1) as usize),
+            None => comment.to_string(),
+        }
+    }
+}
+
+/// Reads a max sentences field, where blank means no limit.
+pub fn parse_max_sentences(text: &str) -> Result<Option<i32>, String> {
+    let text = text.trim();
+
+    if text.is_empty() {
+        return Ok(None);
+    }
+
+    match text.parse::<i32>() {
+        Ok(max_sentences) if (1..=MAX_SENTENCES_LIMIT).contains(&max_sentences) => {
+            Ok(Some(max_sentences))
+        }
+        _ => Err(format!(
+            "Max sentences must be a whole number from 1 to {}, or blank",
+            MAX_SENTENCES_LIMIT
+        )),
+    }
+}
+
+pub fn count_sentences(text: &str) -> usize {
+    sentence_ends(text).len()
+}
+
+pub fn truncate_to_sentences(text: &str, max_sentences: usize) -> String {
+    match sentence_ends(text).get(max_sentences.saturating_sub(1)) {
+        Some(end) if max_sentences > 0 => text[..*end].trim().to_string(),
+        _ => text.trim().to_string(),
+    }
+}
+
+/// The byte offset just past each sentence. A sentence ends at a run of
+/// `.`, `!` or `?` followed by whitespace, or at the end of the text, so
+/// "Wait... what?!" is two sentences and "3.5" is not.
+fn sentence_ends(text: &str) -> Vec<usize> {
+    let mut ends = Vec::new();
+    let mut has_words = false;
+    let mut chars = text.char_indices().peekable();
+
+    while let Some((index, c)) = chars.next() {
+        match c {
+            '.' | '!' | '?' => {
+                let next = chars.peek().map(|(_, next)| *next);
+                let closes = match next {
+                    None => true,
+                    Some(next) => next.is_whitespace(),
+                };
+
+                if closes && has_words {
+                    ends.push(index + c.len_utf8());
+                    has_words = false;
+                }
+            }
+            _ => {
+                if c.is_alphanumeric() {
+                    has_words = true;
+                }
+            }
+        }
+    }
+
+    if has_words {
+        ends.push(text.len());
+    }
+
+    ends
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_count_and_truncate_sentences() {
+        let comment = "Wait... what?! The rent went up 3.5 percent. I can't believe it";
+
+        assert_eq!(count_sentences(comment), 4);
+        assert_eq!(
+            truncate_to_sentences(comment, 3),
+            "Wait... what?! The rent went up 3.5 percent."
+        );
+        assert_eq!(truncate_to_sentences(comment, 9), comment);
+    }
+
+    #[test]
+    fn test_person_overrides_world() {
+        let world = StyleGuide {
+            max_sentences: Some(3),
+            dialect: "Southern".to_string(),
+            formality: Some(Formality::Casual),
+        };
+        let person = StyleGuide {
+            max_sentences: Some(1),
+            dialect: String::new(),
+            formality: None,
+        };
+
+        let merged = world.overridden_by(&person);
+
+        assert_eq!(
+            merged,
+            StyleGuide {
+                max_sentences: Some(1),
+                dialect: "Southern".to_string(),
+                formality: Some(Formality::Casual),
+            }
+        );
+        assert_eq!(
+            merged.overlong_feedback("Hi. Bye."),
+            Some("The comment has 2 sentences, but this person says at most 1 at a time. Say it again, shorter.".to_string())
+        );
+        assert_eq!(merged.truncate("Hi. Bye."), "Hi.");
+    }
+}
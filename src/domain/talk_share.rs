@@ -0,0 +1,169 @@
+use crate::domain::person_uuid::PersonUuid;
+use crate::domain::scene_uuid::SceneUuid;
+
+/// Scenes quieter than this over the window say too little about who
+/// talks too much to tune on.
+const MIN_SCENE_MESSAGES: i64 = 20;
+/// A person sending more than this many times their even share of a
+/// scene's messages is talking over everyone, and less than
+/// `UNDER_SHARE_RATIO` times it is being talked over.
+const OVER_SHARE_RATIO: f64 = 1.5;
+const UNDER_SHARE_RATIO: f64 = 0.5;
+/// How far one run moves someone's chattiness. Small, so the loop settles
+/// over a few runs instead of swinging back and forth.
+const STEP: f64 = 0.05;
+/// Tuning never silences anyone or lets them answer every nudge.
+const MIN_CHATTINESS: f64 = 0.05;
+const MAX_CHATTINESS: f64 = 0.9;
+
+/// How many messages a person in a scene sent to it over the window.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TalkCount {
+    pub scene_uuid: SceneUuid,
+    pub person_uuid: PersonUuid,
+    pub chattiness: f64,
+    pub messages: i64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChattinessAdjustment {
+    pub person_uuid: PersonUuid,
+    pub from: f64,
+    pub to: f64,
+    /// Their share of the messages over their even share, across the scenes
+    /// they are in, weighted by how busy each scene was.
+    pub share_ratio: f64,
+}
+
+/// Nudges the chattiness of everyone who talks much more or much less than
+/// the others in their scenes toward an even share. `counts` has a row for
+/// every person in every scene, including the ones who said nothing.
+pub fn adjustments(counts: &[TalkCount]) -> Vec<ChattinessAdjustment> {
+    // Per person: chattiness, weighted ratio sum and weight
+    let mut per_person: Vec<(PersonUuid, f64, f64, f64)> = Vec::new();
+
+    let mut scene_uuids: Vec<&SceneUuid> = Vec::new();
+    for count in counts {
+        if !scene_uuids.contains(&&count.scene_uuid) {
+            scene_uuids.push(&count.scene_uuid);
+        }
+    }
+
+    for scene_uuid in scene_uuids {
+        let scene_counts = counts
+            .iter()
+            .filter(|count| &count.scene_uuid == scene_uuid)
+            .collect::<Vec<&TalkCount>>();
+
+        let total = scene_counts.iter().map(|count| count.messages).sum::<i64>();
+        if scene_counts.len() < 2 || total < MIN_SCENE_MESSAGES {
+            continue;
+        }
+
+        let even_share = total as f64 / scene_counts.len() as f64;
+
+        for count in scene_counts {
+            let ratio = count.messages as f64 / even_share;
+            let weight = total as f64;
+
+            match per_person
+                .iter_mut()
+                .find(|(person_uuid, ..)| person_uuid == &count.person_uuid)
+            {
+                Some((_, _, ratio_sum, weight_sum)) => {
+                    *ratio_sum += ratio * weight;
+                    *weight_sum += weight;
+                }
+                None => per_person.push((
+                    count.person_uuid.clone(),
+                    count.chattiness,
+                    ratio * weight,
+                    weight,
+                )),
+            }
+        }
+    }
+
+    per_person
+        .into_iter()
+        .filter_map(|(person_uuid, chattiness, ratio_sum, weight_sum)| {
+            let share_ratio = ratio_sum / weight_sum;
+
+            // Whoever is at a limit already, or was set past it by hand, is left be
+            let to = if share_ratio > OVER_SHARE_RATIO && chattiness > MIN_CHATTINESS {
+                (chattiness - STEP).max(MIN_CHATTINESS)
+            } else if share_ratio < UNDER_SHARE_RATIO && chattiness < MAX_CHATTINESS {
+                (chattiness + STEP).min(MAX_CHATTINESS)
+            } else {
+                return None;
+            };
+
+            Some(ChattinessAdjustment {
+                person_uuid,
+                from: chattiness,
+                to,
+                share_ratio,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn count(scene: u64, person: u64, chattiness: f64, messages: i64) -> TalkCount {
+        TalkCount {
+            scene_uuid: SceneUuid::test_id(scene),
+            person_uuid: PersonUuid::test_id(person),
+            chattiness,
+            messages,
+        }
+    }
+
+    #[test]
+    fn test_talkers_are_turned_down_and_quiet_ones_up() {
+        let counts = vec![
+            count(1, 1, 0.3, 30),
+            count(1, 2, 0.3, 8),
+            count(1, 3, 0.3, 2),
+        ];
+
+        let adjustments = adjustments(&counts);
+
+        assert_eq!(adjustments.len(), 2);
+        assert_eq!(adjustments[0].person_uuid, PersonUuid::test_id(1));
+        assert!((adjustments[0].to - 0.25).abs() < 1e-9);
+        assert!((adjustments[0].share_ratio - 2.25).abs() < 1e-9);
+        assert_eq!(adjustments[1].person_uuid, PersonUuid::test_id(3));
+        assert!((adjustments[1].to - 0.35).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_quiet_scenes_and_limits_are_left_alone() {
+        let quiet = vec![count(1, 1, 0.3, 15), count(1, 2, 0.3, 0)];
+        assert_eq!(adjustments(&quiet), vec![]);
+
+        let at_limits = vec![count(1, 1, 0.05, 30), count(1, 2, 0.95, 0)];
+        assert_eq!(adjustments(&at_limits), vec![]);
+    }
+
+    #[test]
+    fn test_busier_scenes_count_for_more() {
+        let counts = vec![
+            count(1, 1, 0.3, 90),
+            count(1, 2, 0.3, 10),
+            count(2, 1, 0.3, 5),
+            count(2, 2, 0.3, 15),
+        ];
+
+        // Unweighted, 1.15 and 0.85 would both count as even
+        let adjustments = adjustments(&counts);
+
+        assert_eq!(adjustments.len(), 2);
+        assert!((adjustments[0].share_ratio - 190.0 / 120.0).abs() < 1e-9);
+        assert!((adjustments[0].to - 0.25).abs() < 1e-9);
+        assert!((adjustments[1].share_ratio - 50.0 / 120.0).abs() < 1e-9);
+        assert!((adjustments[1].to - 0.35).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,199 @@
+use crate::domain::tenant_uuid::TenantUuid;
+use chrono::{DateTime, Utc};
+use rand::RngCore;
+use sha2::{Digest, Sha256};
+use std::fmt::Display;
+
+const TOKEN_PREFIX: &str = "az2_";
+const TOKEN_BYTES: usize = 32;
+/// Stored in `api_token.role` for operator tokens, which have no tenant.
+pub const OPERATOR_ROLE_NAME: &str = "operator";
+
+/// Someone whose scenes this server hosts. Only scenes are isolated per
+/// tenant. Persons, memories, messages and jobs belong to the world and are
+/// only served to a tenant through its scenes.
+#[derive(Debug, Clone)]
+pub struct Tenant {
+    pub uuid: TenantUuid,
+    pub name: TenantName,
+    pub created_at: DateTime<Utc>,
+    pub scene_count: i64,
+    pub active_token_count: i64,
+}
+
+/// What an api token may do. Each role can do everything the roles before
+/// it can: viewers read timelines, directors also inject events and chat,
+/// and admins also create and delete persons and run migrations.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ApiRole {
+    Viewer,
+    Director,
+    Admin,
+}
+
+/// Who an api token belongs to and what it may do.
+#[derive(Debug, Clone)]
+pub enum ApiTokenGrant {
+    Tenant {
+        tenant_uuid: TenantUuid,
+        role: ApiRole,
+    },
+    /// Runs the whole world, like queueing jobs. It belongs to no tenant, so
+    /// it cannot read any tenant's scenes.
+    Operator,
+}
+
+/// The kind of token a request needs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ApiAccess {
+    Tenant(ApiRole),
+    Operator,
+}
+
+impl ApiRole {
+    pub fn to_name(&self) -> &'static str {
+        match self {
+            ApiRole::Viewer => "viewer",
+            ApiRole::Director => "director",
+            ApiRole::Admin => "admin",
+        }
+    }
+
+    pub fn parse(name: &str) -> Result<Self, String> {
+        match name.trim().to_lowercase().as_str() {
+            "viewer" => Ok(ApiRole::Viewer),
+            "director" => Ok(ApiRole::Director),
+            "admin" => Ok(ApiRole::Admin),
+            other => Err(format!(
+                "Unknown role \"{}\", expected viewer, director or admin",
+                other
+            )),
+        }
+    }
+
+    pub fn allows(&self, required: ApiRole) -> bool {
+        *self >= required
+    }
+}
+
+/// Same rules as world names, so tenant names are safe to put in urls and
+/// file names.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TenantName(String);
+
+impl TenantName {
+    pub fn parse(name: &str) -> Result<Self, String> {
+        let name = name.trim();
+
+        if name.is_empty() {
+            return Err("Tenant name cannot be empty".to_string());
+        }
+
+        let is_valid = name
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_');
+
+        if !is_valid {
+            return Err(format!(
+                "Tenant name \"{}\" can only contain lowercase letters, digits, and underscores",
+                name
+            ));
+        }
+
+        Ok(TenantName(name.to_string()))
+    }
+
+    pub fn as_str(&self) -> &str {
+        self.0.as_str()
+    }
+}
+
+impl Display for TenantName {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// A fresh api token, like `az2_3f9c...`. Only its hash is ever stored.
+pub fn generate_token() -> String {
+    let mut bytes = [0u8; TOKEN_BYTES];
+    rand::thread_rng().fill_bytes(&mut bytes);
+
+    let hex = bytes
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect::<String>();
+
+    format!("{}{}", TOKEN_PREFIX, hex)
+}
+
+pub fn hash_token(token: &str) -> String {
+    format!("{:x}", Sha256::digest(token.trim().as_bytes()))
+}
+
+/// Reads the token out of an `Authorization: Bearer <token>` header.
+pub fn bearer_token(header: &str) -> Option<&str> {
+    let (scheme, token) = header.trim().split_once(' ')?;
+
+    if !scheme.eq_ignore_ascii_case("bearer") {
+        return None;
+    }
+
+    let token = token.trim();
+
+    if token.is_empty() {
+        None
+    } else {
+        Some(token)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tenant_name_parse() {
+        assert_eq!(
+            TenantName::parse(" acme_2 ").map(|name| name.to_string()),
+            Ok("acme_2".to_string())
+        );
+        assert!(TenantName::parse("").is_err());
+        assert!(TenantName::parse("Acme Corp").is_err());
+    }
+
+    #[test]
+    fn test_generated_tokens_differ_and_hash_stably() {
+        let token = generate_token();
+
+        assert!(token.starts_with(TOKEN_PREFIX));
+        assert_ne!(token, generate_token());
+        assert_eq!(hash_token(&token), hash_token(&format!(" {} ", token)));
+        assert_eq!(hash_token(&token).len(), 64);
+    }
+
+    #[test]
+    fn test_api_roles_include_the_roles_below_them() {
+        assert!(ApiRole::Admin.allows(ApiRole::Director));
+        assert!(ApiRole::Director.allows(ApiRole::Viewer));
+        assert!(!ApiRole::Viewer.allows(ApiRole::Director));
+        assert!(!ApiRole::Director.allows(ApiRole::Admin));
+    }
+
+    #[test]
+    fn test_api_role_parse_round_trips() {
+        for role in [ApiRole::Viewer, ApiRole::Director, ApiRole::Admin] {
+            assert_eq!(ApiRole::parse(role.to_name()), Ok(role));
+        }
+        assert_eq!(ApiRole::parse(" Admin "), Ok(ApiRole::Admin));
+        assert!(ApiRole::parse("owner").is_err());
+    }
+
+    #[test]
+    fn test_bearer_token() {
+        assert_eq!(bearer_token("Bearer az2_abc"), Some("az2_abc"));
+        assert_eq!(bearer_token("bearer  az2_abc "), Some("az2_abc"));
+        assert_eq!(bearer_token("Basic dXNlcjpwYXNz"), None);
+        assert_eq!(bearer_token("Bearer "), None);
+    }
+}
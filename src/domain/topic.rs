@@ -0,0 +1,397 @@
+use crate::domain::scene_goal::TranscriptLine;
+use crate::domain::scene_uuid::SceneUuid;
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+
+const MAX_TOPIC_LENGTH: usize = 40;
+const MIN_WORD_LENGTH: usize = 4;
+/// A word said only once in a scan is more likely filler than a topic.
+const MIN_MENTIONS: i32 = 2;
+const MAX_TOPICS_PER_SCAN: usize = 5;
+
+/// Words common enough in conversation that they never make a topic.
+const STOPWORDS: &[&str] = &[
+    "about",
+    "above",
+    "after",
+    "again",
+    "against",
+    "all",
+    "almost",
+    "also",
+    "although",
+    "always",
+    "another",
+    "anyone",
+    "anything",
+    "anyway",
+    "around",
+    "away",
+    "back",
+    "because",
+    "been",
+    "before",
+    "being",
+    "believe",
+    "below",
+    "best",
+    "better",
+    "between",
+    "both",
+    "came",
+    "cannot",
+    "come",
+    "comes",
+    "coming",
+    "could",
+    "couldn't",
+    "didn't",
+    "does",
+    "doesn't",
+    "doing",
+    "done",
+    "don't",
+    "down",
+    "during",
+    "each",
+    "either",
+    "else",
+    "enough",
+    "even",
+    "ever",
+    "every",
+    "everyone",
+    "everything",
+    "feel",
+    "find",
+    "first",
+    "from",
+    "getting",
+    "give",
+    "going",
+    "gone",
+    "good",
+    "gonna",
+    "great",
+    "guess",
+    "hadn't",
+    "happen",
+    "hasn't",
+    "have",
+    "haven't",
+    "having",
+    "hear",
+    "heard",
+    "hello",
+    "here",
+    "hers",
+    "herself",
+    "hey",
+    "himself",
+    "hmm",
+    "into",
+    "isn't",
+    "it's",
+    "itself",
+    "just",
+    "keep",
+    "kind",
+    "know",
+    "last",
+    "least",
+    "less",
+    "let's",
+    "like",
+    "little",
+    "long",
+    "look",
+    "looks",
+    "made",
+    "make",
+    "many",
+    "maybe",
+    "mean",
+    "might",
+    "mine",
+    "more",
+    "most",
+    "much",
+    "must",
+    "myself",
+    "need",
+    "never",
+    "next",
+    "nice",
+    "nothing",
+    "okay",
+    "once",
+    "only",
+    "other",
+    "others",
+    "ours",
+    "ourselves",
+    "over",
+    "pretty",
+    "probably",
+    "quite",
+    "rather",
+    "really",
+    "right",
+    "said",
+    "same",
+    "saying",
+    "says",
+    "seem",
+    "seems",
+    "shall",
+    "she's",
+    "should",
+    "shouldn't",
+    "since",
+    "some",
+    "someone",
+    "something",
+    "sometimes",
+    "soon",
+    "sorry",
+    "still",
+    "such",
+    "sure",
+    "take",
+    "tell",
+    "than",
+    "thank",
+    "thanks",
+    "that",
+    "that's",
+    "their",
+    "theirs",
+    "them",
+    "themselves",
+    "then",
+    "there",
+    "there's",
+    "these",
+    "they",
+    "they're",
+    "thing",
+    "things",
+    "think",
+    "this",
+    "those",
+    "though",
+    "thought",
+    "through",
+    "time",
+    "today",
+    "together",
+    "told",
+    "too",
+    "totally",
+    "toward",
+    "under",
+    "until",
+    "upon",
+    "very",
+    "want",
+    "wanted",
+    "wasn't",
+    "we're",
+    "well",
+    "went",
+    "were",
+    "weren't",
+    "what",
+    "what's",
+    "when",
+    "where",
+    "whether",
+    "which",
+    "while",
+    "who's",
+    "whole",
+    "whom",
+    "whose",
+    "will",
+    "with",
+    "within",
+    "without",
+    "won't",
+    "wonder",
+    "would",
+    "wouldn't",
+    "yeah",
+    "year",
+    "years",
+    "you'd",
+    "you'll",
+    "you're",
+    "you've",
+    "your",
+    "yours",
+    "yourself",
+    "yourselves",
+];
+
+/// A word or short phrase a scene has been talking about, like `radios`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Topic(String);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TopicCount {
+    pub topic: Topic,
+    pub mentions: i32,
+}
+
+/// A scene with messages the topic tagger has not read yet.
+#[derive(Debug, Clone)]
+pub struct UntaggedScene {
+    pub scene_uuid: SceneUuid,
+    /// The newest message already tagged, if any.
+    pub tagged_through: Option<DateTime<Utc>>,
+    pub latest_message_at: DateTime<Utc>,
+}
+
+/// A topic across every scene that mentioned it.
+#[derive(Debug, Clone)]
+pub struct TrendingTopic {
+    pub topic: Topic,
+    pub mentions: i64,
+    pub scene_count: i64,
+    pub last_mentioned_at: DateTime<Utc>,
+}
+
+/// A scene that has talked about a topic.
+#[derive(Debug, Clone)]
+pub struct TopicScene {
+    pub scene_name: String,
+    pub mentions: i32,
+    pub last_mentioned_at: DateTime<Utc>,
+}
+
+impl Topic {
+    pub fn parse(topic: &str) -> Result<Self, String> {
+        let topic = topic.trim().to_lowercase();
+
+        if topic.is_empty() {
+            return Err("Topic cannot be empty".to_string());
+        }
+
+        if topic.chars().count() > MAX_TOPIC_LENGTH {
+            return Err(format!(
+                "Topic \"{}\" is longer than {} characters",
+                topic, MAX_TOPIC_LENGTH
+            ));
+        }
+
+        Ok(Topic(topic))
+    }
+
+    pub fn from_string(topic: String) -> Self {
+        Topic(topic)
+    }
+
+    pub fn as_str(&self) -> &str {
+        self.0.as_str()
+    }
+}
+
+/// The words said most often in these lines, leaving out common words and
+/// the speakers' own names. Cheap enough to run on every scan, with no
+/// completion call.
+pub fn extract_topics(lines: &[TranscriptLine]) -> Vec<TopicCount> {
+    let speaker_names = lines
+        .iter()
+        .flat_map(|line| words(&line.speaker_name))
+        .collect::<Vec<String>>();
+
+    let mut counts: HashMap<String, i32> = HashMap::new();
+
+    for line in lines {
+        for word in words(&line.content) {
+            let is_candidate = word.chars().count() >= MIN_WORD_LENGTH
+                && word.chars().count() <= MAX_TOPIC_LENGTH
+                && word.chars().any(|c| c.is_alphabetic())
+                && !STOPWORDS.contains(&word.as_str())
+                && !speaker_names.contains(&word);
+
+            if is_candidate {
+                *counts.entry(word).or_insert(0) += 1;
+            }
+        }
+    }
+
+    let mut topics = counts
+        .into_iter()
+        .filter(|(_, mentions)| *mentions >= MIN_MENTIONS)
+        .map(|(word, mentions)| TopicCount {
+            topic: Topic(word),
+            mentions,
+        })
+        .collect::<Vec<TopicCount>>();
+
+    topics.sort_by(|a, b| {
+        b.mentions
+            .cmp(&a.mentions)
+            .then_with(|| a.topic.as_str().cmp(b.topic.as_str()))
+    });
+    topics.truncate(MAX_TOPICS_PER_SCAN);
+
+    topics
+}
+
+fn words(text: &str) -> Vec<String> {
+    text.split(|c: char| !(c.is_alphanumeric() || c == '\''))
+        .map(|word| word.trim_matches('\'').to_lowercase())
+        .filter(|word| !word.is_empty())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn line(speaker_name: &str, content: &str) -> TranscriptLine {
+        TranscriptLine {
+            speaker_name: speaker_name.to_string(),
+            content: content.to_string(),
+            sent_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_extract_topics_counts_repeated_words() {
+        let lines = vec![
+            line(
+                "Walt",
+                "Did you fix the radio? I think the radio is broken.",
+            ),
+            line("Ann", "The radio works fine, Walt. The antenna though..."),
+            line(
+                "Walt",
+                "Antenna? Really? I'll look at the antenna tomorrow.",
+            ),
+            line("Ann", "Bring coffee."),
+        ];
+
+        assert_eq!(
+            extract_topics(&lines),
+            vec![
+                TopicCount {
+                    topic: Topic("antenna".to_string()),
+                    mentions: 3,
+                },
+                TopicCount {
+                    topic: Topic("radio".to_string()),
+                    mentions: 3,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_topic() {
+        assert_eq!(Topic::parse(" Radio ").unwrap().as_str(), "radio");
+        assert!(Topic::parse("  ").is_err());
+    }
+}
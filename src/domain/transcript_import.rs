@@ -0,0 +1,543 @@
+use crate::capability::memory::MemoryCapability;
+use crate::capability::message::MessageCapability;
+use crate::capability::person::{NewPerson, PersonCapability};
+use crate::capability::scene::{NewScene, SceneCapability};
+use crate::domain::actor_uuid::ActorUuid;
+use crate::domain::message::MessageSender;
+use crate::domain::message_uuid::MessageUuid;
+use crate::domain::person_name::PersonName;
+use crate::domain::person_uuid::PersonUuid;
+use crate::domain::scene_uuid::SceneUuid;
+use crate::domain::utterance::REAL_WORLD_USER_NAME;
+use crate::nice_display::{with_context, NiceDisplay};
+use std::collections::HashMap;
+
+/// How many lines of the conversation each person is asked to remember at
+/// once. Whole novels do not fit in one prompt.
+const MEMORY_CHUNK_LINES: usize = 40;
+
+/// Cues longer than this are more likely a line of action than a name.
+const MAX_SPEAKER_WORDS: usize = 4;
+const MAX_SPEAKER_CHARS: usize = 40;
+
+/// One thing someone said in the transcript.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TranscriptLine {
+    pub speaker: String,
+    pub content: String,
+}
+
+pub struct Import {
+    pub scene_name: String,
+    pub lines: Vec<TranscriptLine>,
+    /// Speakers who should be an existing person under another name, by
+    /// the speaker's name in lower case
+    pub speaker_map: HashMap<String, PersonName>,
+    pub create_memories: bool,
+}
+
+pub struct Imported {
+    pub scene_uuid: SceneUuid,
+    pub created_persons: Vec<PersonName>,
+    pub existing_persons: Vec<PersonName>,
+    pub message_count: usize,
+    pub memory_count: usize,
+}
+
+pub enum Error {
+    NoLines,
+    InvalidMapping(String),
+    LookUpPersons(String),
+    CreatePerson {
+        person_name: PersonName,
+        details: String,
+    },
+    Scene(String),
+    JoinScene {
+        person_name: PersonName,
+        details: String,
+    },
+    Replay(String),
+    Memories {
+        person_name: PersonName,
+        details: String,
+    },
+}
+
+impl NiceDisplay for Error {
+    fn message(&self) -> String {
+        match self {
+            Error::NoLines => {
+                "No lines of dialogue were found. Lines should look like \"Name: line\", or a screenplay's speaker cue in capitals followed by their dialogue".to_string()
+            }
+            Error::InvalidMapping(mapping) => format!(
+                "\"{}\" is not a mapping. Mappings look like SPEAKER=Person Name",
+                mapping
+            ),
+            Error::LookUpPersons(details) => with_context("Failed to look up persons", details),
+            Error::CreatePerson {
+                person_name,
+                details,
+            } => with_context(format!("Failed to create {}", person_name), details),
+            Error::Scene(details) => with_context("Failed to set up the scene", details),
+            Error::JoinScene {
+                person_name,
+                details,
+            } => with_context(
+                format!("Failed to add {} to the scene", person_name),
+                details,
+            ),
+            Error::Replay(details) => with_context("Failed to replay the conversation", details),
+            Error::Memories {
+                person_name,
+                details,
+            } => with_context(
+                format!("Failed to create memories for {}", person_name),
+                details,
+            ),
+        }
+    }
+}
+
+/// Reads a `SPEAKER=Person Name` argument.
+pub fn parse_mapping(mapping: &str) -> Result<(String, PersonName), Error> {
+    match mapping.split_once('=') {
+        Some((speaker, person_name))
+            if !speaker.trim().is_empty() && !person_name.trim().is_empty() =>
+        {
+            Ok((
+                speaker.trim().to_lowercase(),
+                PersonName::from_string(person_name.trim().to_string()),
+            ))
+        }
+        _ => Err(Error::InvalidMapping(mapping.to_string())),
+    }
+}
+
+/// Reads the dialogue out of a chat log (`Name: line`, optionally after a
+/// `[timestamp]`) or a screenplay (a speaker cue in capitals, then their
+/// lines). Headings, action and parentheticals are left out, and the two
+/// forms can be mixed.
+pub fn parse_transcript(text: &str) -> Vec<TranscriptLine> {
+    let mut lines = Vec::new();
+    let mut speech: Option<TranscriptLine> = None;
+
+    fn finish(speech: &mut Option<TranscriptLine>, lines: &mut Vec<TranscriptLine>) {
+        if let Some(line) = speech.take() {
+            if !line.content.is_empty() {
+                lines.push(line);
+            }
+        }
+    }
+
+    for raw_line in text.lines() {
+        let line = raw_line.trim();
+
+        if line.is_empty() {
+            finish(&mut speech, &mut lines);
+            continue;
+        }
+
+        if let Some(chat_line) = parse_chat_line(line) {
+            finish(&mut speech, &mut lines);
+            lines.push(chat_line);
+            continue;
+        }
+
+        if let Some(speaker) = parse_cue(line) {
+            finish(&mut speech, &mut lines);
+            speech = Some(TranscriptLine {
+                speaker,
+                content: String::new(),
+            });
+            continue;
+        }
+
+        if let Some(current) = speech.as_mut() {
+            if line.starts_with('(') && line.ends_with(')') {
+                continue;
+            }
+
+            if !current.content.is_empty() {
+                current.content.push(' ');
+            }
+            current.content.push_str(line);
+        }
+    }
+
+    finish(&mut speech, &mut lines);
+    lines
+}
+
+fn parse_chat_line(line: &str) -> Option<TranscriptLine> {
+    let line = match line.strip_prefix('[') {
+        Some(rest) => rest.split_once(']')?.1.trim_start(),
+        None => line,
+    };
+
+    let (speaker, content) = line.split_once(':')?;
+    let content = content.trim();
+
+    if content.is_empty() || !is_speaker_name(speaker.trim()) {
+        return None;
+    }
+
+    Some(TranscriptLine {
+        speaker: normalize_speaker(speaker.trim()),
+        content: content.to_string(),
+    })
+}
+
+fn parse_cue(line: &str) -> Option<String> {
+    // Extensions like (V.O.) or (CONT'D) are not part of the name
+    let name = match line.split_once('(') {
+        Some((name, _)) => name.trim(),
+        None => line,
+    };
+
+    let is_heading = ["INT.", "EXT.", "INT/EXT", "I/E"]
+        .iter()
+        .any(|prefix| name.starts_with(prefix));
+
+    if is_heading || name.ends_with(':') || name.chars().any(char::is_lowercase) {
+        return None;
+    }
+
+    if !is_speaker_name(name) {
+        return None;
+    }
+
+    Some(normalize_speaker(name))
+}
+
+fn is_speaker_name(name: &str) -> bool {
+    let starts_with_letter = name.chars().next().is_some_and(char::is_alphabetic);
+    let has_only_name_characters = name
+        .chars()
+        .all(|c| c.is_alphanumeric() || c == ' ' || c == '\'' || c == '-' || c == '.');
+
+    starts_with_letter
+        && has_only_name_characters
+        && name.chars().count() <= MAX_SPEAKER_CHARS
+        && name.split_whitespace().count() <= MAX_SPEAKER_WORDS
+}
+
+/// Screenplays shout their speakers' names. `SHERLOCK HOLMES` is imported
+/// as `Sherlock Holmes`, and names written any other way are kept as is.
+fn normalize_speaker(name: &str) -> String {
+    let name = name.split_whitespace().collect::<Vec<&str>>().join(" ");
+
+    if name.chars().any(char::is_lowercase) {
+        return name;
+    }
+
+    name.split(' ')
+        .map(|word| {
+            let mut chars = word.chars();
+            match chars.next() {
+                Some(first) => first.to_string() + chars.as_str().to_lowercase().as_str(),
+                None => String::new(),
+            }
+        })
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+/// Who a speaker turned out to be.
+#[derive(Clone)]
+enum Speaker {
+    Person {
+        person_uuid: PersonUuid,
+        person_name: PersonName,
+    },
+    RealWorldUser,
+}
+
+/// Makes or finds a person for each speaker, puts them in the scene and
+/// replays the conversation into it. Nobody reacts to the replayed lines:
+/// they are delivered to everyone in the scene already handled. Each person
+/// can then be asked to remember the conversation.
+pub async fn import_transcript<
+    W: PersonCapability + SceneCapability + MessageCapability + MemoryCapability,
+>(
+    worker: &W,
+    import: Import,
+) -> Result<Imported, Error> {
+    if import.lines.is_empty() {
+        return Err(Error::NoLines);
+    }
+
+    let mut existing_uuids: HashMap<String, PersonUuid> = HashMap::new();
+    for person_uuid in worker
+        .get_all_person_uuids()
+        .await
+        .map_err(Error::LookUpPersons)?
+    {
+        let person_name = worker
+            .get_persons_name(person_uuid.clone())
+            .await
+            .map_err(Error::LookUpPersons)?;
+        existing_uuids.insert(person_name.as_str().to_lowercase(), person_uuid);
+    }
+
+    let mut speakers: HashMap<String, Speaker> = HashMap::new();
+    let mut persons: Vec<(PersonUuid, PersonName)> = Vec::new();
+    let mut created_persons = Vec::new();
+    let mut existing_persons = Vec::new();
+
+    for line in import.lines.iter() {
+        if speakers.contains_key(line.speaker.as_str()) {
+            continue;
+        }
+
+        let person_name = import
+            .speaker_map
+            .get(&line.speaker.to_lowercase())
+            .cloned()
+            .unwrap_or_else(|| PersonName::from_string(line.speaker.clone()));
+
+        let speaker = if person_name.as_str() == REAL_WORLD_USER_NAME {
+            Speaker::RealWorldUser
+        } else {
+            let existing = existing_uuids
+                .get(&person_name.as_str().to_lowercase())
+                .cloned();
+            let person_uuid = match existing {
+                Some(person_uuid) => {
+                    existing_persons.push(person_name.clone());
+                    person_uuid
+                }
+                None => {
+                    let person_uuid = worker
+                        .create_person(NewPerson {
+                            person_uuid: PersonUuid::new(),
+                            person_name: person_name.clone(),
+                        })
+                        .await
+                        .map_err(|details| Error::CreatePerson {
+                            person_name: person_name.clone(),
+                            details,
+                        })?;
+                    existing_uuids.insert(person_name.as_str().to_lowercase(), person_uuid.clone());
+                    created_persons.push(person_name.clone());
+                    person_uuid
+                }
+            };
+
+            // Two speakers mapped to one person speak as that person
+            if !persons.iter().any(|(uuid, _)| *uuid == person_uuid) {
+                persons.push((person_uuid.clone(), person_name.clone()));
+            }
+
+            Speaker::Person {
+                person_uuid,
+                person_name,
+            }
+        };
+
+        speakers.insert(line.speaker.clone(), speaker);
+    }
+
+    let scene_uuid = match worker
+        .get_scene_from_name(import.scene_name.clone())
+        .await
+        .map_err(Error::Scene)?
+    {
+        Some(scene) => scene.uuid,
+        None => worker
+            .create_scene(NewScene {
+                name: import.scene_name.clone(),
+                description: format!(
+                    "A conversation between {}, imported from a transcript.",
+                    persons
+                        .iter()
+                        .map(|(_, person_name)| person_name.as_str())
+                        .collect::<Vec<&str>>()
+                        .join(", ")
+                ),
+            })
+            .await
+            .map_err(Error::Scene)?,
+    };
+
+    let participant_uuids = worker
+        .get_scene_current_participants(&scene_uuid)
+        .await
+        .map_err(Error::Scene)?
+        .into_iter()
+        .filter_map(|participant| match participant.actor_uuid {
+            ActorUuid::AiPerson(person_uuid) => Some(person_uuid),
+            ActorUuid::RealWorldUser => None,
+        })
+        .collect::<Vec<PersonUuid>>();
+
+    for (person_uuid, person_name) in persons.iter() {
+        if participant_uuids.contains(person_uuid) {
+            continue;
+        }
+
+        worker
+            .add_person_to_scene(scene_uuid.clone(), person_name.clone())
+            .await
+            .map_err(|details| Error::JoinScene {
+                person_name: person_name.clone(),
+                details,
+            })?;
+    }
+
+    if speakers.values().any(|speaker| match speaker {
+        Speaker::RealWorldUser => true,
+        Speaker::Person { .. } => false,
+    }) {
+        worker
+            .set_real_world_user_in_scene(&scene_uuid, true)
+            .await
+            .map_err(Error::Scene)?;
+    }
+
+    let mut delivered: HashMap<PersonUuid, Vec<MessageUuid>> = HashMap::new();
+    let mut transcript = Vec::with_capacity(import.lines.len());
+
+    for line in import.lines.iter() {
+        let (sender, sender_uuid, sender_name) = match &speakers[&line.speaker] {
+            Speaker::Person {
+                person_uuid,
+                person_name,
+            } => (
+                MessageSender::AiPerson(person_uuid.clone()),
+                Some(person_uuid),
+                person_name.as_str(),
+            ),
+            Speaker::RealWorldUser => (MessageSender::RealWorldUser, None, REAL_WORLD_USER_NAME),
+        };
+
+        let message_uuid = worker
+            .send_scene_message(sender, scene_uuid.clone(), line.content.clone())
+            .await
+            .map_err(Error::Replay)?;
+
+        let recipients = persons
+            .iter()
+            .map(|(person_uuid, _)| person_uuid.clone())
+            .filter(|person_uuid| Some(person_uuid) != sender_uuid)
+            .collect::<Vec<PersonUuid>>();
+
+        for person_uuid in recipients.iter() {
+            delivered
+                .entry(person_uuid.clone())
+                .or_default()
+                .push(message_uuid.clone());
+        }
+
+        worker
+            .add_scene_message_recipients(&message_uuid, recipients)
+            .await
+            .map_err(Error::Replay)?;
+
+        transcript.push(format!("{}: {}", sender_name, line.content));
+    }
+
+    for (person_uuid, message_uuids) in delivered {
+        worker
+            .mark_scene_messages_handled_for_person(&person_uuid, message_uuids)
+            .await
+            .map_err(Error::Replay)?;
+    }
+
+    let mut memory_count = 0;
+    if import.create_memories {
+        for (person_uuid, person_name) in persons.iter() {
+            for chunk in transcript.chunks(MEMORY_CHUNK_LINES) {
+                let description = format!(
+                    "A conversation in {}:\n{}",
+                    import.scene_name,
+                    chunk.join("\n")
+                );
+
+                let memory_uuids = worker
+                    .maybe_create_memories_from_description(person_uuid.clone(), description)
+                    .await
+                    .map_err(|details| Error::Memories {
+                        person_name: person_name.clone(),
+                        details,
+                    })?;
+
+                memory_count += memory_uuids.len();
+            }
+        }
+    }
+
+    Ok(Imported {
+        scene_uuid,
+        created_persons,
+        existing_persons,
+        message_count: transcript.len(),
+        memory_count,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn line(speaker: &str, content: &str) -> TranscriptLine {
+        TranscriptLine {
+            speaker: speaker.to_string(),
+            content: content.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_chat_logs_are_read_with_or_without_timestamps() {
+        let text = "[12:01] Ann: Are you coming?\nBob: In a minute.\n\nThe rain kept on.\nAnn: Hurry: it's late";
+
+        assert_eq!(
+            parse_transcript(text),
+            vec![
+                line("Ann", "Are you coming?"),
+                line("Bob", "In a minute."),
+                line("Ann", "Hurry: it's late"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_screenplays_keep_dialogue_and_leave_out_the_rest() {
+        let text = "\
+INT. BAKER STREET - NIGHT
+
+Rain against the window. Holmes does not look up.
+
+SHERLOCK HOLMES
+(without turning)
+You have been in Afghanistan,
+I perceive.
+
+WATSON (CONT'D)
+How did you know?
+
+CUT TO:
+";
+
+        assert_eq!(
+            parse_transcript(text),
+            vec![
+                line(
+                    "Sherlock Holmes",
+                    "You have been in Afghanistan, I perceive."
+                ),
+                line("Watson", "How did you know?"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_mappings_need_both_sides() {
+        let (speaker, person_name) = parse_mapping("HOLMES = Sherlock Holmes").ok().unwrap();
+
+        assert_eq!(speaker, "holmes");
+        assert_eq!(person_name.as_str(), "Sherlock Holmes");
+        assert!(parse_mapping("Holmes").is_err());
+        assert!(parse_mapping("=Sherlock").is_err());
+    }
+}
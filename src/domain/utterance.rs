@@ -0,0 +1,31 @@
+use crate::domain::message_uuid::MessageUuid;
+use crate::domain::person_uuid::PersonUuid;
+use chrono::{DateTime, Utc};
+
+/// What the real world user is called wherever a speaker is named.
+pub const REAL_WORLD_USER_NAME: &str = "Chadtech";
+
+/// One thing said in a scene, by one speaker. It is stored once as a
+/// `message`, and everyone who heard it gets a delivery in
+/// `scene_message_recipient` pointing back at it, so renderers should show
+/// each utterance once no matter how many heard it.
+#[derive(Debug, Clone)]
+pub struct Utterance {
+    pub message_uuid: MessageUuid,
+    /// Missing when the real world user said it.
+    pub speaker_person_uuid: Option<PersonUuid>,
+    pub speaker_name: String,
+    pub content: String,
+    pub sent_at: DateTime<Utc>,
+    /// How many persons it was delivered to, including those who overheard it.
+    pub deliveries: i64,
+}
+
+/// The name to attribute a line to, given the sending person's name or
+/// nothing for the real world user.
+pub fn speaker_name(person_name: Option<&str>) -> String {
+    match person_name {
+        Some(person_name) => person_name.to_string(),
+        None => REAL_WORLD_USER_NAME.to_string(),
+    }
+}
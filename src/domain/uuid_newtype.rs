@@ -0,0 +1,110 @@
+/// Declares an id type that wraps a `uuid::Uuid`, so ids of different
+/// things cannot be mixed up. Each one gets `new`, `from_uuid`, `to_uuid`,
+/// serde and sqlx support, and `test_id` for tests that want ids they can
+/// predict. The optional prefix is shown before the uuid when displayed,
+/// like `Person/`.
+macro_rules! uuid_newtype {
+    ($name:ident) => {
+        uuid_newtype!($name, "");
+    };
+    ($name:ident, $display_prefix:literal) => {
+        #[derive(Debug, Clone, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+        #[serde(transparent)]
+        pub struct $name(uuid::Uuid);
+
+        #[allow(dead_code)]
+        impl $name {
+            pub fn new() -> Self {
+                $name(uuid::Uuid::now_v7())
+            }
+
+            pub fn from_uuid(uuid: uuid::Uuid) -> Self {
+                $name(uuid)
+            }
+
+            pub fn to_uuid(&self) -> uuid::Uuid {
+                self.0
+            }
+
+            /// An id made from a small number. Real ids are v7 uuids, which
+            /// are never this small, so the two cannot collide.
+            pub fn test_id(id: u64) -> Self {
+                $name(uuid::Uuid::from_u128(u128::from(id)))
+            }
+
+            /// The number a `test_id` was made from.
+            pub fn to_test_id(&self) -> Option<u64> {
+                u64::try_from(self.0.as_u128()).ok()
+            }
+        }
+
+        impl From<uuid::Uuid> for $name {
+            fn from(value: uuid::Uuid) -> Self {
+                $name(value)
+            }
+        }
+
+        impl std::fmt::Display for $name {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                write!(f, "{}{}", $display_prefix, self.0)
+            }
+        }
+
+        impl sqlx::Type<sqlx::Postgres> for $name {
+            fn type_info() -> sqlx::postgres::PgTypeInfo {
+                <uuid::Uuid as sqlx::Type<sqlx::Postgres>>::type_info()
+            }
+        }
+
+        impl sqlx::postgres::PgHasArrayType for $name {
+            fn array_type_info() -> sqlx::postgres::PgTypeInfo {
+                <uuid::Uuid as sqlx::postgres::PgHasArrayType>::array_type_info()
+            }
+        }
+
+        impl<'q> sqlx::Encode<'q, sqlx::Postgres> for $name {
+            fn encode_by_ref(
+                &self,
+                buf: &mut sqlx::postgres::PgArgumentBuffer,
+            ) -> sqlx::encode::IsNull {
+                <uuid::Uuid as sqlx::Encode<'q, sqlx::Postgres>>::encode_by_ref(&self.0, buf)
+            }
+        }
+
+        impl<'r> sqlx::Decode<'r, sqlx::Postgres> for $name {
+            fn decode(
+                value: sqlx::postgres::PgValueRef<'r>,
+            ) -> Result<Self, sqlx::error::BoxDynError> {
+                <uuid::Uuid as sqlx::Decode<'r, sqlx::Postgres>>::decode(value).map($name)
+            }
+        }
+    };
+}
+
+pub(crate) use uuid_newtype;
+
+#[cfg(test)]
+mod tests {
+    uuid_newtype!(ThingUuid, "Thing/");
+
+    #[test]
+    fn test_test_ids_round_trip_and_real_ids_are_not_test_ids() {
+        let test_uuid = ThingUuid::test_id(7);
+
+        assert_eq!(test_uuid.to_test_id(), Some(7));
+        assert_eq!(ThingUuid::new().to_test_id(), None);
+        assert_eq!(
+            test_uuid.to_string(),
+            "Thing/00000000-0000-0000-0000-000000000007"
+        );
+    }
+
+    #[test]
+    fn test_ids_serialize_as_the_bare_uuid() {
+        let thing_uuid = ThingUuid::test_id(1);
+
+        let json = serde_json::to_value(&thing_uuid).unwrap();
+
+        assert_eq!(json, serde_json::json!(thing_uuid.to_uuid().to_string()));
+    }
+}
@@ -0,0 +1,154 @@
+/// More than this and the examples start to crowd out the situation.
+pub const MAX_EXEMPLARS: usize = 8;
+
+/// Whether reactions are shown the person's voice exemplars. `Compare` shows
+/// them to a random half, so the two halves can be told apart afterwards.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VoiceExemplarMode {
+    Off,
+    On,
+    Compare,
+}
+
+/// Reactions recorded with or without exemplars, and how many of those were
+/// marked good on the Reaction tab.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MarkedReactions {
+    pub total: i64,
+    pub good: i64,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct VoiceExemplarComparison {
+    pub with_exemplars: MarkedReactions,
+    pub without_exemplars: MarkedReactions,
+}
+
+impl VoiceExemplarMode {
+    pub const ALL: [VoiceExemplarMode; 3] = [
+        VoiceExemplarMode::Off,
+        VoiceExemplarMode::On,
+        VoiceExemplarMode::Compare,
+    ];
+
+    pub fn to_name(&self) -> &'static str {
+        match self {
+            VoiceExemplarMode::Off => "off",
+            VoiceExemplarMode::On => "on",
+            VoiceExemplarMode::Compare => "ab",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Result<Self, String> {
+        match name {
+            "off" => Ok(VoiceExemplarMode::Off),
+            "on" => Ok(VoiceExemplarMode::On),
+            "ab" => Ok(VoiceExemplarMode::Compare),
+            _ => Err(format!("Unknown voice exemplar mode \"{}\"", name)),
+        }
+    }
+
+    pub fn to_label(&self) -> &'static str {
+        match self {
+            VoiceExemplarMode::Off => "Off",
+            VoiceExemplarMode::On => "On",
+            VoiceExemplarMode::Compare => "A/B",
+        }
+    }
+
+    /// Whether one reaction gets the exemplars, if the person has any.
+    /// `coin_flip` only matters when comparing.
+    pub fn includes(&self, has_exemplars: bool, coin_flip: bool) -> Option<bool> {
+        if !has_exemplars {
+            return None;
+        }
+
+        Some(match self {
+            VoiceExemplarMode::Off => false,
+            VoiceExemplarMode::On => true,
+            VoiceExemplarMode::Compare => coin_flip,
+        })
+    }
+}
+
+impl MarkedReactions {
+    pub fn to_text(&self) -> String {
+        if self.total == 0 {
+            return "no reactions yet".to_string();
+        }
+
+        format!(
+            "{} of {} marked good ({:.0}%)",
+            self.good,
+            self.total,
+            self.good as f64 * 100.0 / self.total as f64
+        )
+    }
+}
+
+/// One exemplar a line, blank lines dropped.
+pub fn parse_exemplars(text: &str) -> Result<Vec<String>, String> {
+    let exemplars = text
+        .lines()
+        .map(|line| line.trim().to_string())
+        .filter(|line| !line.is_empty())
+        .collect::<Vec<String>>();
+
+    if exemplars.len() > MAX_EXEMPLARS {
+        return Err(format!(
+            "A person can have at most {} exemplars, not {}",
+            MAX_EXEMPLARS,
+            exemplars.len()
+        ));
+    }
+
+    Ok(exemplars)
+}
+
+/// The section appended to the action system prompt, if there are any.
+pub fn to_prompt_section(exemplars: &[String]) -> Option<String> {
+    if exemplars.is_empty() {
+        return None;
+    }
+
+    let examples = exemplars
+        .iter()
+        .map(|exemplar| format!("- \"{}\"", exemplar))
+        .collect::<Vec<String>>()
+        .join("\n");
+
+    Some(format!(
+        "How $name$ talks, for example:\n{}\nMatch this voice, its word choice and rhythm, but do not repeat these lines or their content.",
+        examples
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_only_comparing_flips_a_coin() {
+        assert_eq!(VoiceExemplarMode::On.includes(true, false), Some(true));
+        assert_eq!(VoiceExemplarMode::Off.includes(true, true), Some(false));
+        assert_eq!(
+            VoiceExemplarMode::Compare.includes(true, false),
+            Some(false)
+        );
+        assert_eq!(VoiceExemplarMode::Compare.includes(true, true), Some(true));
+        assert_eq!(VoiceExemplarMode::On.includes(false, true), None);
+    }
+
+    #[test]
+    fn test_parse_exemplars_drops_blank_lines_and_caps_the_count() {
+        assert_eq!(
+            parse_exemplars("  I tell you what.\n\nThat boy ain't right.\n").unwrap(),
+            vec![
+                "I tell you what.".to_string(),
+                "That boy ain't right.".to_string()
+            ]
+        );
+        assert!(parse_exemplars(&"Yep.\n".repeat(MAX_EXEMPLARS + 1)).is_err());
+        assert_eq!(to_prompt_section(&[]), None);
+    }
+}
@@ -0,0 +1,44 @@
+/// Up to this many messages that came in during a wait are shown as they
+/// were said. Any more are condensed into a short summary, so a long wait in
+/// a busy scene does not turn into a huge prompt.
+pub const VERBATIM_LIMIT: usize = 3;
+
+/// Whether a background message should be held until the person's wait is
+/// over, rather than reacted to right away. A wait that is due now or has
+/// already passed holds nothing.
+pub fn holds_messages(wait_ends_at_active_ms: Option<i64>, current_active_ms: i64) -> bool {
+    match wait_ends_at_active_ms {
+        Some(wait_ends_at_active_ms) => wait_ends_at_active_ms > current_active_ms,
+        None => false,
+    }
+}
+
+pub fn should_condense(message_count: usize) -> bool {
+    message_count > VERBATIM_LIMIT
+}
+
+pub fn condensed_event_text(message_count: usize, summary: &str) -> String {
+    format!(
+        "While you were waiting, {} messages came in around you. In short:\n{}\n[MISSED MESSAGES EVENT]",
+        message_count, summary
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_only_a_wait_still_running_holds_messages() {
+        assert!(holds_messages(Some(60_000), 30_000));
+        assert!(!holds_messages(Some(60_000), 60_000));
+        assert!(!holds_messages(None, 30_000));
+    }
+
+    #[test]
+    fn test_a_few_messages_stay_verbatim() {
+        assert!(!should_condense(VERBATIM_LIMIT));
+        assert!(should_condense(VERBATIM_LIMIT + 1));
+        assert!(condensed_event_text(5, "- Bob left").contains("5 messages"));
+    }
+}
@@ -0,0 +1,130 @@
+use crate::domain::person_name::PersonName;
+use crate::domain::scene_uuid::SceneUuid;
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+/// Every active scene, who is in it right now, and the routes people have
+/// taken between scenes.
+#[derive(Debug, Clone)]
+pub struct WorldMap {
+    pub scenes: Vec<MapScene>,
+    pub edges: Vec<TravelEdge>,
+}
+
+#[derive(Debug, Clone)]
+pub struct MapScene {
+    pub scene_uuid: SceneUuid,
+    pub name: String,
+    pub people: Vec<PersonName>,
+    pub real_world_user_present: bool,
+}
+
+/// Two scenes someone has walked between, in either direction.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TravelEdge {
+    pub scene_a: Uuid,
+    pub scene_b: Uuid,
+    pub trips: u32,
+}
+
+/// One stay of one person in one scene.
+#[derive(Debug, Clone)]
+pub struct SceneVisit {
+    pub person_uuid: Uuid,
+    pub scene_uuid: Uuid,
+    pub joined_at: DateTime<Utc>,
+}
+
+impl MapScene {
+    pub fn head_count(&self) -> usize {
+        if self.real_world_user_present {
+            self.people.len() + 1
+        } else {
+            self.people.len()
+        }
+    }
+}
+
+/// Follows each person from one scene to the next and counts the trips
+/// along each route. Rejoining the scene you were already in is not a trip.
+pub fn travel_edges(mut visits: Vec<SceneVisit>) -> Vec<TravelEdge> {
+    visits.sort_by(|a, b| {
+        a.person_uuid
+            .cmp(&b.person_uuid)
+            .then(a.joined_at.cmp(&b.joined_at))
+    });
+
+    let mut trips: HashMap<(Uuid, Uuid), u32> = HashMap::new();
+
+    for pair in visits.windows(2) {
+        let (from, to) = (&pair[0], &pair[1]);
+
+        if from.person_uuid != to.person_uuid || from.scene_uuid == to.scene_uuid {
+            continue;
+        }
+
+        let key = if from.scene_uuid < to.scene_uuid {
+            (from.scene_uuid, to.scene_uuid)
+        } else {
+            (to.scene_uuid, from.scene_uuid)
+        };
+
+        *trips.entry(key).or_insert(0) += 1;
+    }
+
+    let mut edges = trips
+        .into_iter()
+        .map(|((scene_a, scene_b), trips)| TravelEdge {
+            scene_a,
+            scene_b,
+            trips,
+        })
+        .collect::<Vec<TravelEdge>>();
+
+    edges.sort_by_key(|edge| (edge.scene_a, edge.scene_b));
+
+    edges
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn visit(person: u128, scene: u128, minute: u32) -> SceneVisit {
+        SceneVisit {
+            person_uuid: Uuid::from_u128(person),
+            scene_uuid: Uuid::from_u128(scene),
+            joined_at: Utc.with_ymd_and_hms(2026, 1, 1, 0, minute, 0).unwrap(),
+        }
+    }
+
+    #[test]
+    fn test_trips_are_counted_per_route_in_either_direction() {
+        let edges = travel_edges(vec![
+            visit(1, 20, 5),
+            visit(1, 10, 0),
+            visit(1, 10, 9),
+            visit(2, 20, 1),
+            visit(2, 20, 2),
+            visit(2, 30, 3),
+        ]);
+
+        assert_eq!(
+            edges,
+            vec![
+                TravelEdge {
+                    scene_a: Uuid::from_u128(10),
+                    scene_b: Uuid::from_u128(20),
+                    trips: 2,
+                },
+                TravelEdge {
+                    scene_a: Uuid::from_u128(20),
+                    scene_b: Uuid::from_u128(30),
+                    trips: 1,
+                },
+            ]
+        );
+    }
+}
@@ -0,0 +1,149 @@
+use crate::domain::action_budget::WORLD_DAY_MS;
+use std::fmt::Display;
+
+const MINUTE_MS: i64 = 60 * 1000;
+const MINUTES_PER_DAY: i64 = 24 * 60;
+
+/// A time on a scene's clock: the active clock plus the scene's offset.
+/// Active ms 0 is midnight at the start of day 1.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WorldTime {
+    ms: i64,
+}
+
+/// Minutes after midnight.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TimeOfDay {
+    minutes: i64,
+}
+
+impl WorldTime {
+    pub fn new(active_ms: i64, clock_offset_ms: i64) -> Self {
+        WorldTime {
+            ms: active_ms.saturating_add(clock_offset_ms).max(0),
+        }
+    }
+
+    pub fn day(&self) -> i64 {
+        self.ms / WORLD_DAY_MS + 1
+    }
+
+    pub fn time_of_day(&self) -> TimeOfDay {
+        TimeOfDay {
+            minutes: (self.ms % WORLD_DAY_MS) / MINUTE_MS,
+        }
+    }
+
+    /// The line reaction prompts open with, like `It is 09:15 in the
+    /// morning on day 3.`
+    pub fn to_prompt_text(&self) -> String {
+        let time_of_day = self.time_of_day();
+
+        format!(
+            "It is {} {} on day {}.",
+            time_of_day,
+            time_of_day.part_of_day(),
+            self.day()
+        )
+    }
+}
+
+impl TimeOfDay {
+    pub fn from_minutes(minutes: i64) -> Result<Self, String> {
+        if (0..MINUTES_PER_DAY).contains(&minutes) {
+            Ok(TimeOfDay { minutes })
+        } else {
+            Err(format!(
+                "{} minutes is not a time of day, it must be from 0 to {}",
+                minutes,
+                MINUTES_PER_DAY - 1
+            ))
+        }
+    }
+
+    /// Parses `HH:MM` on a 24 hour clock.
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let not_a_time = || format!("\"{}\" is not a time of day like 07:30", text.trim());
+
+        let (hours, minutes) = text.trim().split_once(':').ok_or_else(not_a_time)?;
+        let hours = hours.parse::<i64>().map_err(|_| not_a_time())?;
+        let minutes = minutes.parse::<i64>().map_err(|_| not_a_time())?;
+
+        if !(0..24).contains(&hours) || !(0..60).contains(&minutes) {
+            return Err(not_a_time());
+        }
+
+        Ok(TimeOfDay {
+            minutes: hours * 60 + minutes,
+        })
+    }
+
+    pub fn to_minutes(self) -> i64 {
+        self.minutes
+    }
+
+    pub fn part_of_day(&self) -> &'static str {
+        match self.minutes / 60 {
+            5..=7 => "in the early morning",
+            8..=11 => "in the morning",
+            12..=16 => "in the afternoon",
+            17..=20 => "in the evening",
+            _ => "at night",
+        }
+    }
+
+    /// The offset that makes a scene's clock read this time of day when the
+    /// active clock reads `active_ms`. Always less than a day, so a scene
+    /// started in the evening for a morning template skips ahead to the
+    /// next morning rather than going back in time.
+    pub fn clock_offset_ms(&self, active_ms: i64) -> i64 {
+        (self.minutes * MINUTE_MS - active_ms).rem_euclid(WORLD_DAY_MS)
+    }
+}
+
+impl Display for TimeOfDay {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:02}:{:02}", self.minutes / 60, self.minutes % 60)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_world_time_prompt_text_names_the_day_and_part_of_day() {
+        let ms = 2 * WORLD_DAY_MS + (7 * 60 + 45) * MINUTE_MS;
+
+        assert_eq!(
+            WorldTime::new(ms, 0).to_prompt_text(),
+            "It is 07:45 in the early morning on day 3."
+        );
+        assert_eq!(
+            WorldTime::new(0, 0).to_prompt_text(),
+            "It is 00:00 at night on day 1."
+        );
+    }
+
+    #[test]
+    fn test_clock_offset_starts_the_scene_at_the_time_of_day() {
+        let time_of_day = TimeOfDay::parse("08:30").unwrap();
+        let active_ms = WORLD_DAY_MS + 22 * 60 * MINUTE_MS;
+
+        let offset = time_of_day.clock_offset_ms(active_ms);
+        let world_time = WorldTime::new(active_ms, offset);
+
+        assert!((0..WORLD_DAY_MS).contains(&offset));
+        assert_eq!(world_time.time_of_day(), time_of_day);
+        assert_eq!(world_time.day(), 3);
+    }
+
+    #[test]
+    fn test_time_of_day_parses_a_24_hour_clock() {
+        assert_eq!(TimeOfDay::parse(" 7:05 ").unwrap().to_string(), "07:05");
+        assert_eq!(TimeOfDay::parse("23:59").unwrap().to_minutes(), 1439);
+        assert!(TimeOfDay::parse("24:00").is_err());
+        assert!(TimeOfDay::parse("noon").is_err());
+        assert!(TimeOfDay::from_minutes(1440).is_err());
+    }
+}
@@ -1,37 +1,79 @@
+use crate::capability::acknowledgement::AcknowledgementCapability;
+use crate::capability::arrival_observation::ArrivalObservationCapability;
+use crate::capability::autobiography::AutobiographyCapability;
+use crate::capability::clock::ClockCapability;
+use crate::capability::custom_action::CustomActionCapability;
 use crate::capability::event::EventCapability;
+use crate::capability::expected_reply::ExpectedReplyCapability;
+use crate::capability::external_tool::ExternalToolCapability;
+use crate::capability::idle_person::IdlePersonCapability;
+use crate::capability::item::ItemCapability;
 use crate::capability::job::JobCapability;
 use crate::capability::job_runner_settings::JobRunnerSettingsCapability;
+use crate::capability::llm_batch::LlmBatchCapability;
 use crate::capability::log_event::LogEventCapability;
 use crate::capability::logging::LogCapability;
+use crate::capability::maintenance::MaintenanceCapability;
 use crate::capability::memory::MemoryCapability;
 use crate::capability::message::MessageCapability;
+use crate::capability::moderation::ModerationCapability;
 use crate::capability::motivation::MotivationCapability;
+use crate::capability::outbox::OutboxCapability;
 use crate::capability::person::PersonCapability;
+use crate::capability::person_arc::PersonArcCapability;
 use crate::capability::person_identity::PersonIdentityCapability;
 use crate::capability::person_task::PersonTaskCapability;
+use crate::capability::persona_consistency::PersonaConsistencyCapability;
 use crate::capability::reaction::ReactionCapability;
 use crate::capability::reaction_history::ReactionHistoryCapability;
 use crate::capability::reflection::ReflectionCapability;
 use crate::capability::scene::SceneCapability;
+use crate::capability::scene_archive::SceneArchiveCapability;
+use crate::capability::scene_drama::SceneDramaCapability;
+use crate::capability::scene_goal::SceneGoalCapability;
+use crate::capability::scene_invitation::SceneInvitationCapability;
 use crate::capability::state_of_mind::StateOfMindCapability;
+use crate::capability::topic::TopicCapability;
+use crate::capability::world_language::WorldLanguageCapability;
+use crate::capability_metrics::chaos::FaultInjector;
+use crate::capability_metrics::{self, endpoint, CapabilityMetrics, MeteredWorker};
+use crate::domain::budget::BudgetLedger;
+use crate::domain::cron_job;
+use crate::domain::job::dispatch_outbox::DispatchOutboxJob;
 use crate::domain::job::{
-    person_hibernating, person_waiting, process_message, process_person_join, process_scene_gaze,
-    send_message_to_scene, JobKind, PoppedJob,
+    archive_scene, balance_chattiness, change_scene_ambience, check_expected_reply,
+    check_persona_consistency, check_scene_goals, close_scene, dispatch_outbox,
+    handle_batch_completion, inject_scene_events, nightly_maintenance, notice_conversation,
+    person_hibernating, person_waiting, poll_llm_batch, process_message, process_person_join,
+    process_scene_gaze, react_to_scene_event, react_to_scene_invitation, refill_acknowledgements,
+    registry, run_custom_action, send_message_to_scene, tag_topics, update_person_arcs, use_tool,
+    wake_idle_persons, JobKind, PoppedJob,
 };
+use crate::domain::job_event::{self, JobEventKind, JobTiming, NewJobEvent};
 use crate::domain::job_uuid::JobUuid;
 use crate::domain::logger::{Level, Logger};
+use crate::domain::outbox::OutboxEvent;
+use crate::domain::pause_policy::{JobFailureTracker, PausePolicy, PauseReason};
 use crate::domain::random_seed::RandomSeed;
-use crate::nice_display::NiceDisplay;
+use crate::domain::simulation_speed;
+use crate::nice_display::{nest, with_context, ErrorCode, NiceDisplay};
+use crate::open_ai::client::measure_open_ai_time;
 use crate::worker;
-use crate::worker::Worker;
-use sqlx::Row;
-use std::time::Instant;
+use crate::worker::{ProcessRole, Worker};
+use crate::world_language;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio_util::sync::CancellationToken;
 
 const DEFAULT_JOB_RUNNER_POLL_INTERVAL_SECS: u64 = 45;
+const CAPABILITY_METRICS_SUMMARY_INTERVAL: Duration = Duration::from_secs(600);
 
 pub enum Error {
     WorkerInit(worker::InitError),
     ActiveClock(String),
+    PausePolicy(String),
+    Chaos(String),
+    MetricsEndpoint(String),
     PopJob(String),
     RunJob((JobUuid, RunJobError)),
 }
@@ -53,65 +95,124 @@ pub enum RunJobError {
     SendMessageToSceneError(send_message_to_scene::Error),
     PersonWaitingError(person_waiting::Error),
     PersonHibernatingError(person_hibernating::Error),
+    CheckExpectedReplyError(check_expected_reply::Error),
+    DispatchOutboxError(dispatch_outbox::Error),
+    PollLlmBatchError(poll_llm_batch::Error),
+    HandleBatchCompletionError(handle_batch_completion::Error),
+    WakeIdlePersonsError(wake_idle_persons::Error),
+    NoticeConversationError(notice_conversation::Error),
+    CheckPersonaConsistencyError(check_persona_consistency::Error),
+    CheckSceneGoalsError(check_scene_goals::Error),
+    CloseSceneError(close_scene::Error),
+    ArchiveSceneError(archive_scene::Error),
+    TagTopicsError(tag_topics::Error),
+    ChangeSceneAmbienceError(change_scene_ambience::Error),
+    InjectSceneEventsError(inject_scene_events::Error),
+    ReactToSceneEventError(react_to_scene_event::Error),
+    RunCustomActionError(run_custom_action::Error),
+    RefillAcknowledgementsError(refill_acknowledgements::Error),
+    NightlyMaintenanceError(nightly_maintenance::Error),
+    UseToolError(use_tool::Error),
+    ReactToSceneInvitationError(react_to_scene_invitation::Error),
+    UpdatePersonArcsError(update_person_arcs::Error),
+    BalanceChattinessError(balance_chattiness::Error),
 }
 
 enum RunJobOutcome {
     Completed,
+    /// The job ran into an error and was marked failed.
+    Failed,
     Deferred,
+    Cancelled,
+}
+
+/// A job the runner got to the end of, one way or the other.
+struct FinishedJob {
+    job_kind: String,
+    failed: bool,
 }
 
 impl NiceDisplay for Error {
     fn message(&self) -> String {
         match self {
-            Error::WorkerInit(err) => {
-                format!("Worker initialization error\n{}", err.message())
-            }
-            Error::ActiveClock(err) => {
-                format!("Active clock error\n{}", err)
-            }
+            Error::WorkerInit(err) => nest("Worker initialization error", err),
+            Error::ActiveClock(err) => with_context("Active clock error", err),
+            Error::PausePolicy(err) => with_context("Pause policy error", err),
+            Error::Chaos(err) => with_context("Chaos mode error", err),
+            Error::MetricsEndpoint(err) => with_context("Capability metrics endpoint error", err),
             Error::RunJob(err) => err.message(),
-            Error::PopJob(err) => {
-                format!("Failed to pop next job\n{}", err)
-            }
+            Error::PopJob(err) => with_context("Failed to pop next job", err),
         }
     }
 }
 
+/// The clock delayed jobs wait on. It runs at the time factor while the job
+/// runner is on and stands still while it is paused.
 struct ActiveClock {
     base_active_ms: i64,
     start: Instant,
+    time_factor: f64,
+    running: bool,
 }
 
 impl ActiveClock {
     async fn load(worker: &Worker) -> Result<Self, String> {
-        let row = sqlx::query(
-            r#"
-                SELECT active_ms
-                FROM active_clock
-                WHERE id = TRUE
-            "#,
-        )
-        .fetch_optional(&worker.sqlx)
-        .await
-        .map_err(|err| format!("Error loading active clock: {}", err))?;
+        let base_active_ms = worker.get_active_clock_ms().await?;
 
-        let base_active_ms = match row {
-            Some(row) => row
-                .try_get::<i64, _>("active_ms")
-                .map_err(|err| format!("Error reading active_ms: {}", err))?,
-            None => 0,
-        };
+        Ok(Self::new(base_active_ms, Instant::now()))
+    }
 
-        Ok(Self {
+    fn new(base_active_ms: i64, start: Instant) -> Self {
+        Self {
             base_active_ms,
-            start: Instant::now(),
-        })
+            start,
+            time_factor: 1.0,
+            running: true,
+        }
     }
 
     fn current_ms(&self) -> i64 {
-        let elapsed = self.start.elapsed();
-        let elapsed_ms = i64::try_from(elapsed.as_millis()).unwrap_or(i64::MAX);
-        self.base_active_ms.saturating_add(elapsed_ms)
+        self.ms_at(Instant::now())
+    }
+
+    fn ms_at(&self, now: Instant) -> i64 {
+        if !self.running {
+            return self.base_active_ms;
+        }
+
+        let elapsed = now.saturating_duration_since(self.start);
+        self.base_active_ms
+            .saturating_add(simulation_speed::scaled_elapsed_ms(
+                elapsed,
+                self.time_factor,
+            ))
+    }
+
+    /// Starts counting again from `now`, so a change only affects the time
+    /// from here on.
+    fn rebase(&mut self, now: Instant) {
+        self.base_active_ms = self.ms_at(now);
+        self.start = now;
+    }
+
+    fn set_time_factor(&mut self, time_factor: f64, now: Instant) {
+        if time_factor != self.time_factor {
+            self.rebase(now);
+            self.time_factor = time_factor;
+        }
+    }
+
+    fn set_running(&mut self, running: bool, now: Instant) {
+        if running != self.running {
+            self.rebase(now);
+            self.running = running;
+        }
+    }
+
+    /// Moves the clock ahead to `active_ms`, never back.
+    fn skip_to(&mut self, active_ms: i64, now: Instant) {
+        self.rebase(now);
+        self.base_active_ms = self.base_active_ms.max(active_ms);
     }
 
     async fn persist(&self, worker: &Worker) -> Result<(), String> {
@@ -135,64 +236,171 @@ impl NiceDisplay for (JobUuid, RunJobError) {
     fn message(&self) -> String {
         let (job_uuid, err) = self;
 
-        let err_msg = err.to_nice_error().to_string();
-
-        format!("I failed to run job {}\n{}", job_uuid, err_msg)
+        nest(format!("I failed to run job {}", job_uuid), err)
     }
 }
 
 impl NiceDisplay for RunJobError {
     fn message(&self) -> String {
         match self {
-            RunJobError::FailedToMarkJobFinished(err) => {
-                format!(
-                    "I ran into the following problem trying to mark the job as finished\n{}",
-                    err
-                )
+            RunJobError::FailedToMarkJobFinished(err) => with_context(
+                "I ran into the following problem trying to mark the job as finished",
+                err,
+            ),
+            RunJobError::ProcessMessageError(err) => nest("Error processing message job", err),
+            RunJobError::ProcessPersonJoinError(err) => {
+                nest("Error processing person join job", err)
             }
-            RunJobError::ProcessMessageError(err) => {
-                format!("Error processing message job\n{}", err.message())
+            RunJobError::ProcessSceneGazeError(err) => nest("Error processing scene gaze job", err),
+            RunJobError::SendMessageToSceneError(err) => {
+                nest("Error sending message to scene job", err)
             }
-            RunJobError::ProcessPersonJoinError(err) => {
-                format!("Error processing person join job\n{}", err.message())
+            RunJobError::FailedToMarkJobFailed(err) => with_context(
+                "I ran into the following problem trying to mark the job as failed",
+                err,
+            ),
+            RunJobError::FailedToResetJob(err) => with_context(
+                "I ran into the following problem trying to reset the job",
+                err,
+            ),
+            RunJobError::PersonWaitingError(err) => {
+                nest("Error processing person waiting job", err)
             }
-            RunJobError::ProcessSceneGazeError(err) => {
-                format!("Error processing scene gaze job\n{}", err.message())
+            RunJobError::PersonHibernatingError(err) => {
+                nest("Error processing person hibernating job", err)
             }
-            RunJobError::SendMessageToSceneError(err) => {
-                format!("Error sending message to scene job\n{}", err.message())
+            RunJobError::CheckExpectedReplyError(err) => {
+                nest("Error checking for an expected reply", err)
             }
-            RunJobError::FailedToMarkJobFailed(err) => {
-                format!(
-                    "I ran into the following problem trying to mark the job as failed\n{}",
-                    err
-                )
+            RunJobError::DispatchOutboxError(err) => nest("Error dispatching the outbox", err),
+            RunJobError::PollLlmBatchError(err) => nest("Error polling an llm batch", err),
+            RunJobError::HandleBatchCompletionError(err) => {
+                nest("Error handling a batch completion", err)
             }
-            RunJobError::FailedToResetJob(err) => {
-                format!(
-                    "I ran into the following problem trying to reset the job\n{}",
-                    err
-                )
+            RunJobError::WakeIdlePersonsError(err) => nest("Error waking idle persons", err),
+            RunJobError::NoticeConversationError(err) => {
+                nest("Error processing notice conversation job", err)
             }
-            RunJobError::PersonWaitingError(err) => {
-                format!("Error processing person waiting job\n{}", err.message())
+            RunJobError::CheckPersonaConsistencyError(err) => {
+                nest("Error checking persona consistency", err)
             }
-            RunJobError::PersonHibernatingError(err) => {
-                format!("Error processing person hibernating job\n{}", err.message())
+            RunJobError::CheckSceneGoalsError(err) => nest("Error checking scene goals", err),
+            RunJobError::CloseSceneError(err) => nest("Error closing a scene", err),
+            RunJobError::ArchiveSceneError(err) => nest("Error archiving a scene", err),
+            RunJobError::TagTopicsError(err) => nest("Error tagging topics", err),
+            RunJobError::ChangeSceneAmbienceError(err) => {
+                nest("Error changing a scene's ambience", err)
+            }
+            RunJobError::InjectSceneEventsError(err) => nest("Error injecting scene events", err),
+            RunJobError::ReactToSceneEventError(err) => {
+                nest("Error reacting to a scene event", err)
+            }
+            RunJobError::RunCustomActionError(err) => nest("Error running a custom action", err),
+            RunJobError::RefillAcknowledgementsError(err) => {
+                nest("Error refilling acknowledgements", err)
+            }
+            RunJobError::NightlyMaintenanceError(err) => {
+                nest("Error running nightly maintenance", err)
+            }
+            RunJobError::UseToolError(err) => nest("Error using an external tool", err),
+            RunJobError::ReactToSceneInvitationError(err) => {
+                nest("Error reacting to a scene invitation", err)
             }
+            RunJobError::UpdatePersonArcsError(err) => nest("Error updating person arcs", err),
+            RunJobError::BalanceChattinessError(err) => nest("Error balancing chattiness", err),
         }
     }
 }
+// The codes double as the error class in the job event log
+impl ErrorCode for RunJobError {
+    fn code(&self) -> &'static str {
+        match self {
+            RunJobError::FailedToMarkJobFinished(_) => "mark job finished",
+            RunJobError::FailedToMarkJobFailed(_) => "mark job failed",
+            RunJobError::FailedToResetJob(_) => "reset job",
+            RunJobError::ProcessMessageError(_) => registry::PROCESS_MESSAGE,
+            RunJobError::ProcessPersonJoinError(_) => registry::PROCESS_PERSON_JOIN,
+            RunJobError::ProcessSceneGazeError(_) => registry::PROCESS_SCENE_GAZE,
+            RunJobError::SendMessageToSceneError(_) => registry::SEND_MESSAGE_TO_SCENE,
+            RunJobError::PersonWaitingError(_) => registry::PERSON_WAITING,
+            RunJobError::PersonHibernatingError(_) => registry::PERSON_HIBERNATING,
+            RunJobError::CheckExpectedReplyError(_) => registry::CHECK_EXPECTED_REPLY,
+            RunJobError::DispatchOutboxError(_) => registry::DISPATCH_OUTBOX,
+            RunJobError::PollLlmBatchError(_) => registry::POLL_LLM_BATCH,
+            RunJobError::HandleBatchCompletionError(_) => registry::HANDLE_BATCH_COMPLETION,
+            RunJobError::WakeIdlePersonsError(_) => registry::WAKE_IDLE_PERSONS,
+            RunJobError::NoticeConversationError(_) => registry::NOTICE_CONVERSATION,
+            RunJobError::CheckPersonaConsistencyError(_) => registry::CHECK_PERSONA_CONSISTENCY,
+            RunJobError::CheckSceneGoalsError(_) => registry::CHECK_SCENE_GOALS,
+            RunJobError::CloseSceneError(_) => registry::CLOSE_SCENE,
+            RunJobError::ArchiveSceneError(_) => registry::ARCHIVE_SCENE,
+            RunJobError::TagTopicsError(_) => registry::TAG_TOPICS,
+            RunJobError::ChangeSceneAmbienceError(_) => registry::CHANGE_SCENE_AMBIENCE,
+            RunJobError::InjectSceneEventsError(_) => registry::INJECT_SCENE_EVENTS,
+            RunJobError::ReactToSceneEventError(_) => registry::REACT_TO_SCENE_EVENT,
+            RunJobError::RunCustomActionError(_) => registry::RUN_CUSTOM_ACTION,
+            RunJobError::RefillAcknowledgementsError(_) => registry::REFILL_ACKNOWLEDGEMENTS,
+            RunJobError::NightlyMaintenanceError(_) => registry::NIGHTLY_MAINTENANCE,
+            RunJobError::UseToolError(_) => registry::USE_TOOL,
+            RunJobError::ReactToSceneInvitationError(_) => registry::REACT_TO_SCENE_INVITATION,
+            RunJobError::UpdatePersonArcsError(_) => registry::UPDATE_PERSON_ARCS,
+            RunJobError::BalanceChattinessError(_) => registry::BALANCE_CHATTINESS,
+        }
+    }
+}
+
 pub async fn run() -> Result<(), Error> {
     let logger = Logger::init(Level::Info).log_to_file();
 
-    let worker = Worker::new(logger).await.map_err(Error::WorkerInit)?;
-    let active_clock = ActiveClock::load(&worker)
+    let worker = Worker::new(logger, ProcessRole::Runner)
+        .await
+        .map_err(Error::WorkerInit)?;
+    let mut active_clock = ActiveClock::load(&worker)
         .await
         .map_err(Error::ActiveClock)?;
-    tracing::info!("Job runner started, polling for jobs");
-    let shutdown = tokio::signal::ctrl_c();
-    tokio::pin!(shutdown);
+    let chaos = FaultInjector::from_env()
+        .map_err(Error::Chaos)?
+        .map(Arc::new);
+    if let Some(chaos) = &chaos {
+        tracing::warn!(
+            "Chaos mode on, failing {:.1}% of capability calls on purpose",
+            chaos.rate() * 100.0
+        );
+    }
+    // Faults are injected by the metered worker, so chaos runs are metered too
+    let metrics = if capability_metrics::enabled_from_env() || chaos.is_some() {
+        let metrics = Arc::new(CapabilityMetrics::new());
+        let address = endpoint::address_from_env();
+        endpoint::serve(metrics.clone(), address.as_str()).map_err(Error::MetricsEndpoint)?;
+        tracing::info!(
+            "Capability metrics enabled, served at http://{}/metrics",
+            address
+        );
+        Some(metrics)
+    } else {
+        None
+    };
+    let mut last_metrics_summary = Instant::now();
+    let mut last_idle_scan = Instant::now();
+    let mut last_scene_goal_scan = Instant::now();
+    let mut last_topic_scan = Instant::now();
+    let mut last_scene_event_scan = Instant::now();
+    let mut last_arc_scan = Instant::now();
+    let mut last_chattiness_balance = Instant::now();
+    let mut last_cron_check = Instant::now();
+    let pause_policy = PausePolicy::load().map_err(Error::PausePolicy)?;
+    let mut failure_tracker = JobFailureTracker::new();
+    let mut was_enabled = false;
+    let runner = job_event::runner_name();
+    tracing::info!("Job runner {} started, polling for jobs", runner);
+    let cancel = CancellationToken::new();
+    let shutdown_cancel = cancel.clone();
+    tokio::spawn(async move {
+        if let Err(err) = tokio::signal::ctrl_c().await {
+            tracing::error!("Job runner shutdown signal error: {}", err);
+        }
+        shutdown_cancel.cancel();
+    });
     loop {
         let poll_interval_secs = match worker.get_job_runner_poll_interval_secs().await {
             Ok(secs) => secs,
@@ -202,7 +410,13 @@ pub async fn run() -> Result<(), Error> {
             }
         };
 
-        let job_runner_enabled = match worker.get_job_runner_enabled().await {
+        // A language saved in the admin ui applies from the next job on
+        match worker.get_world_language().await {
+            Ok(language) => world_language::set_world_language(language),
+            Err(err) => tracing::error!("Job runner world language error: {}", err),
+        }
+
+        let mut job_runner_enabled = match worker.get_job_runner_enabled().await {
             Ok(enabled) => enabled,
             Err(err) => {
                 tracing::error!("Job runner enabled flag error: {}", err);
@@ -211,6 +425,21 @@ pub async fn run() -> Result<(), Error> {
         };
 
         if job_runner_enabled {
+            if let Some(reason) = budget_pause_reason(&worker).await {
+                pause(&worker, &reason, active_clock.current_ms()).await;
+                job_runner_enabled = false;
+            }
+        }
+
+        update_active_clock(&worker, &mut active_clock, job_runner_enabled).await;
+
+        if job_runner_enabled {
+            // Failures from before a pause should not count against the
+            // run after someone unpauses it
+            if !was_enabled {
+                failure_tracker.reset();
+            }
+
             let random_seed = match worker.get_random_seed() {
                 Ok(seed) => seed,
                 Err(err) => {
@@ -219,34 +448,120 @@ pub async fn run() -> Result<(), Error> {
                 }
             };
             let current_active_ms = active_clock.current_ms();
-            let job_fut = run_next_job(worker.clone(), random_seed, current_active_ms);
 
-            tokio::select! {
-                _ = &mut shutdown => {
-                    if let Err(err) = active_clock.persist(&worker).await {
-                        tracing::error!("Job runner active clock error: {}", err);
-                    }
-                    tracing::info!("Job runner shutting down");
-                    break;
+            // The job itself watches the token, so a stuck OpenAI call gets
+            // abandoned and its job reset instead of holding up shutdown.
+            let result = match &metrics {
+                Some(metrics) => {
+                    let metered = MeteredWorker::new(worker.clone(), metrics.clone());
+                    let metered = match &chaos {
+                        Some(chaos) => metered.with_chaos(chaos.clone()),
+                        None => metered,
+                    };
+                    run_next_job(metered, &runner, random_seed, current_active_ms, &cancel).await
+                }
+                None => {
+                    run_next_job(
+                        worker.clone(),
+                        &runner,
+                        random_seed,
+                        current_active_ms,
+                        &cancel,
+                    )
+                    .await
                 }
-                res = job_fut => {
-                    if let Err(err) = res {
-                        // Log the error but continue processing other jobs
-                        let err_message = err.to_nice_error().to_string();
-                        tracing::error!("Job runner error: {}", err_message);
-                        worker
-                            .logger
-                            .log(Level::Error, &format!("Job runner error: {}", err_message));
+            };
+
+            match result {
+                Ok(Some(finished)) => {
+                    let pause_reason = match &pause_policy {
+                        Some(policy) => {
+                            failure_tracker.record(policy, &finished.job_kind, finished.failed)
+                        }
+                        None => None,
+                    };
+
+                    if let Some(reason) = pause_reason {
+                        pause(&worker, &reason, current_active_ms).await;
                     }
                 }
+                Ok(None) => {}
+                Err(err) => {
+                    // Log the error but continue processing other jobs
+                    let err_message = err.to_nice_error().to_string();
+                    tracing::error!("Job runner error: {}", err_message);
+                    worker
+                        .logger
+                        .log(Level::Error, &format!("Job runner error: {}", err_message));
+                }
+            }
+        }
+        if job_runner_enabled && last_idle_scan.elapsed() >= wake_idle_persons::SCAN_INTERVAL {
+            if let Err(err) = worker.unshift_job(JobKind::WakeIdlePersons).await {
+                tracing::error!("Could not enqueue the idle person scan: {}", err);
+            }
+            last_idle_scan = Instant::now();
+        }
+        if job_runner_enabled && last_scene_goal_scan.elapsed() >= check_scene_goals::SCAN_INTERVAL
+        {
+            if let Err(err) = worker.unshift_job(JobKind::CheckSceneGoals).await {
+                tracing::error!("Could not enqueue the scene goal check: {}", err);
+            }
+            last_scene_goal_scan = Instant::now();
+        }
+        if job_runner_enabled && last_topic_scan.elapsed() >= tag_topics::SCAN_INTERVAL {
+            if let Err(err) = worker.unshift_job(JobKind::TagTopics).await {
+                tracing::error!("Could not enqueue the topic scan: {}", err);
+            }
+            last_topic_scan = Instant::now();
+        }
+        if job_runner_enabled
+            && last_scene_event_scan.elapsed() >= inject_scene_events::SCAN_INTERVAL
+        {
+            if let Err(err) = worker.unshift_job(JobKind::InjectSceneEvents).await {
+                tracing::error!("Could not enqueue the scene event scan: {}", err);
+            }
+            last_scene_event_scan = Instant::now();
+        }
+        if job_runner_enabled && last_arc_scan.elapsed() >= update_person_arcs::SCAN_INTERVAL {
+            if let Err(err) = worker.unshift_job(JobKind::UpdatePersonArcs).await {
+                tracing::error!("Could not enqueue the person arc scan: {}", err);
+            }
+            last_arc_scan = Instant::now();
+        }
+        if job_runner_enabled
+            && last_chattiness_balance.elapsed() >= balance_chattiness::SCAN_INTERVAL
+        {
+            if let Err(err) = worker.unshift_job(JobKind::BalanceChattiness).await {
+                tracing::error!("Could not enqueue the chattiness rebalance: {}", err);
+            }
+            last_chattiness_balance = Instant::now();
+        }
+        if job_runner_enabled && last_cron_check.elapsed() >= cron_job::CHECK_INTERVAL {
+            enqueue_due_cron_jobs(&worker).await;
+            last_cron_check = Instant::now();
+        }
+
+        was_enabled = job_runner_enabled;
+
+        if let Some(metrics) = &metrics {
+            if last_metrics_summary.elapsed() >= CAPABILITY_METRICS_SUMMARY_INTERVAL {
+                log_capability_metrics(&worker, metrics);
+                last_metrics_summary = Instant::now();
             }
         }
 
         tokio::select! {
-            _ = &mut shutdown => {
+            _ = cancel.cancelled() => {
                 if let Err(err) = active_clock.persist(&worker).await {
                     tracing::error!("Job runner active clock error: {}", err);
                 }
+                if let Some(metrics) = &metrics {
+                    log_capability_metrics(&worker, metrics);
+                }
+                if let Some(chaos) = &chaos {
+                    tracing::info!("Chaos mode injected {} faults", chaos.injected());
+                }
                 tracing::info!("Job runner shutting down");
                 break;
             }
@@ -256,6 +571,124 @@ pub async fn run() -> Result<(), Error> {
     Ok(())
 }
 
+/// Follows the speed set in the admin ui, stops the clock while the job
+/// runner is paused, and skips ahead to the next delayed job when asked.
+/// Stored every time around, so the admin ui can show the world time.
+async fn update_active_clock(worker: &Worker, active_clock: &mut ActiveClock, running: bool) {
+    let now = Instant::now();
+
+    match worker.get_time_factor().await {
+        Ok(time_factor) => match simulation_speed::validate_time_factor(time_factor) {
+            Ok(time_factor) => active_clock.set_time_factor(time_factor, now),
+            Err(err) => tracing::error!("Job runner time factor error: {}", err),
+        },
+        Err(err) => tracing::error!("Job runner time factor error: {}", err),
+    }
+
+    active_clock.set_running(running, now);
+
+    match worker.take_wait_skip_request().await {
+        Ok(true) => {
+            let current_ms = active_clock.ms_at(now);
+            match worker.get_next_delayed_job_active_ms(current_ms).await {
+                Ok(Some(run_at_active_ms)) => {
+                    tracing::info!(
+                        "Skipping {} s of active time to the next delayed job",
+                        (run_at_active_ms - current_ms) / 1000
+                    );
+                    active_clock.skip_to(run_at_active_ms, now);
+                }
+                Ok(None) => tracing::info!("No delayed jobs to skip ahead to"),
+                Err(err) => tracing::error!("Job runner wait skip error: {}", err),
+            }
+        }
+        Ok(false) => {}
+        Err(err) => tracing::error!("Job runner wait skip error: {}", err),
+    }
+
+    if let Err(err) = active_clock.persist(worker).await {
+        tracing::error!("Job runner active clock error: {}", err);
+    }
+}
+
+/// Checked before every job, so a run stops within one completion of
+/// spending its budget.
+async fn budget_pause_reason(worker: &Worker) -> Option<PauseReason> {
+    let ledger = match BudgetLedger::load(worker).await {
+        Ok(ledger) => ledger,
+        Err(err) => {
+            tracing::error!("Job runner budget error: {}", err);
+            return None;
+        }
+    };
+
+    let budget_usd = ledger.budget.budget_usd?;
+    if !ledger.is_exhausted() {
+        return None;
+    }
+
+    Some(PauseReason::BudgetExhausted {
+        spent_usd: ledger.spend.spent_usd,
+        budget_usd,
+    })
+}
+
+/// Turns the job runner off the same way the admin ui switch does, and
+/// sends an alert through the outbox. The outbox dispatcher is itself a job,
+/// so the alert is delivered here rather than left for a runner that is no
+/// longer popping jobs.
+async fn pause(worker: &Worker, reason: &PauseReason, current_active_ms: i64) {
+    let message = format!("Pausing the simulation: {}", reason.message());
+    tracing::error!("{}", message);
+    worker.logger.log(Level::Error, &message);
+
+    if let Err(err) = worker.set_job_runner_enabled(false).await {
+        tracing::error!("Could not pause the job runner: {}", err);
+    }
+
+    let event = OutboxEvent::SimulationPaused {
+        reason: reason.clone(),
+    };
+    if let Err(err) = worker.add_outbox_event(&event).await {
+        tracing::error!("Could not write the pause alert to the outbox: {}", err);
+        return;
+    }
+
+    if let Err(err) = DispatchOutboxJob::now()
+        .run(worker, current_active_ms)
+        .await
+    {
+        tracing::error!("Could not deliver the pause alert: {}", err.message());
+    }
+}
+
+async fn enqueue_due_cron_jobs(worker: &Worker) {
+    let runs = match cron_job::enqueue_due_cron_jobs(worker, worker.now()).await {
+        Ok(runs) => runs,
+        Err(err) => {
+            tracing::error!("Could not check cron jobs: {}", err);
+            return;
+        }
+    };
+
+    for run in runs {
+        match run.error {
+            None => tracing::info!("Enqueued cron job {}", run.name),
+            Some(err) => {
+                let message = format!("Could not enqueue cron job {}: {}", run.name, err);
+                tracing::error!("{}", message);
+                worker.logger.log(Level::Error, &message);
+            }
+        }
+    }
+}
+
+fn log_capability_metrics(worker: &Worker, metrics: &CapabilityMetrics) {
+    let summary = metrics.to_summary(capability_metrics::SUMMARY_LIMIT);
+    tracing::info!("{}", summary);
+    worker.logger.log(Level::Info, &summary);
+}
+
 pub async fn run_one_job(
     worker: Worker,
     random_seed: RandomSeed,
@@ -280,7 +713,19 @@ pub async fn run_one_job(
     let job_kind = job.kind.to_name();
     tracing::info!("Processing job {} of type {:?}", job_uuid, job.kind);
 
-    let outcome = match run_job(worker.clone(), random_seed, current_active_ms, job).await {
+    // Jobs run from the admin ui are never cancelled
+    let cancel = CancellationToken::new();
+
+    let outcome = match run_job(
+        worker.clone(),
+        job_event::ADMIN_UI_RUNNER,
+        random_seed,
+        current_active_ms,
+        job,
+        &cancel,
+    )
+    .await
+    {
         Ok(outcome) => outcome,
         Err(err) => {
             let err_message = err.to_nice_error().to_string();
@@ -297,14 +742,19 @@ pub async fn run_one_job(
         .map_err(Error::ActiveClock)?;
 
     match outcome {
-        RunJobOutcome::Completed => Ok(RunNextJobResult::RanJob { job_uuid, job_kind }),
-        RunJobOutcome::Deferred => Ok(RunNextJobResult::Deferred { job_uuid, job_kind }),
+        RunJobOutcome::Completed | RunJobOutcome::Failed => {
+            Ok(RunNextJobResult::RanJob { job_uuid, job_kind })
+        }
+        RunJobOutcome::Deferred | RunJobOutcome::Cancelled => {
+            Ok(RunNextJobResult::Deferred { job_uuid, job_kind })
+        }
     }
 }
 
 async fn run_next_job<
     W: JobCapability
         + MessageCapability
+        + ModerationCapability
         + SceneCapability
         + ReactionCapability
         + MemoryCapability
@@ -314,16 +764,36 @@ async fn run_next_job<
         + PersonIdentityCapability
         + PersonTaskCapability
         + ReactionHistoryCapability
+        + ExpectedReplyCapability
+        + ItemCapability
+        + SceneInvitationCapability
+        + PersonArcCapability
+        + AutobiographyCapability
+        + ArrivalObservationCapability
         + LogEventCapability
         + ReflectionCapability
         + MotivationCapability
+        + OutboxCapability
+        + LlmBatchCapability
+        + IdlePersonCapability
+        + PersonaConsistencyCapability
+        + SceneGoalCapability
+        + SceneArchiveCapability
+        + TopicCapability
+        + SceneDramaCapability
+        + CustomActionCapability
+        + AcknowledgementCapability
         + LogCapability
+        + MaintenanceCapability
+        + ExternalToolCapability
         + Sync,
 >(
     worker: W,
+    runner: &str,
     random_seed: RandomSeed,
     current_active_ms: i64,
-) -> Result<(), Error> {
+    cancel: &CancellationToken,
+) -> Result<Option<FinishedJob>, Error> {
     let job = match worker
         .pop_next_job(current_active_ms)
         .await
@@ -331,25 +801,35 @@ async fn run_next_job<
     {
         Some(j) => j,
         None => {
-            return Ok(());
+            return Ok(None);
         }
     };
 
     let job_uuid = job.uuid.clone();
+    let job_kind = job.kind.to_name();
     tracing::info!("Processing job {} of type {:?}", job_uuid, job.kind);
 
-    match run_job(worker, random_seed, current_active_ms, job)
+    match run_job(worker, runner, random_seed, current_active_ms, job, cancel)
         .await
         .map_err(|err| Error::RunJob((job_uuid, err)))?
     {
-        RunJobOutcome::Completed => Ok(()),
-        RunJobOutcome::Deferred => Ok(()),
+        RunJobOutcome::Completed => Ok(Some(FinishedJob {
+            job_kind,
+            failed: false,
+        })),
+        RunJobOutcome::Failed => Ok(Some(FinishedJob {
+            job_kind,
+            failed: true,
+        })),
+        RunJobOutcome::Deferred => Ok(None),
+        RunJobOutcome::Cancelled => Ok(None),
     }
 }
 
 async fn run_job<
     W: JobCapability
         + MessageCapability
+        + ModerationCapability
         + SceneCapability
         + ReactionCapability
         + MemoryCapability
@@ -359,18 +839,179 @@ async fn run_job<
         + PersonIdentityCapability
         + PersonTaskCapability
         + ReactionHistoryCapability
+        + ExpectedReplyCapability
+        + ItemCapability
+        + SceneInvitationCapability
+        + PersonArcCapability
+        + AutobiographyCapability
+        + ArrivalObservationCapability
         + LogEventCapability
         + ReflectionCapability
         + MotivationCapability
+        + OutboxCapability
+        + LlmBatchCapability
+        + IdlePersonCapability
+        + PersonaConsistencyCapability
+        + SceneGoalCapability
+        + SceneArchiveCapability
+        + TopicCapability
+        + SceneDramaCapability
+        + CustomActionCapability
+        + AcknowledgementCapability
         + LogCapability
+        + MaintenanceCapability
+        + ExternalToolCapability
         + Sync,
 >(
     worker: W,
+    runner: &str,
     random_seed: RandomSeed,
     current_active_ms: i64,
     job: PoppedJob,
+    cancel: &CancellationToken,
+) -> Result<RunJobOutcome, RunJobError> {
+    record_job_event(
+        &worker,
+        &job.uuid,
+        NewJobEvent::new(JobEventKind::PickedUp, runner),
+    )
+    .await;
+    let started = Instant::now();
+
+    let (res, timing) = tokio::select! {
+        biased;
+        _ = cancel.cancelled() => {
+            tracing::info!("Job {} cancelled, resetting it for later", job.uuid);
+            worker
+                .reset_job(&job.uuid)
+                .await
+                .map_err(RunJobError::FailedToResetJob)?;
+            record_job_event(
+                &worker,
+                &job.uuid,
+                NewJobEvent::new(JobEventKind::Cancelled, runner),
+            )
+            .await;
+            return Ok(RunJobOutcome::Cancelled);
+        }
+        (res, open_ai_time) = measure_open_ai_time(
+            execute_job(&worker, random_seed, current_active_ms, &job.uuid, job.kind)
+        ) => (res, JobTiming::new(started.elapsed(), open_ai_time)),
+    };
+
+    match res {
+        Ok(RunJobOutcome::Completed) => {
+            tracing::info!("Job {} completed successfully", job.uuid);
+            worker
+                .mark_job_finished(&job.uuid)
+                .await
+                .map_err(RunJobError::FailedToMarkJobFinished)?;
+            record_job_event(
+                &worker,
+                &job.uuid,
+                NewJobEvent::new(JobEventKind::Finished, runner).with_timing(timing),
+            )
+            .await;
+            Ok(RunJobOutcome::Completed)
+        }
+        Ok(RunJobOutcome::Failed) => Ok(RunJobOutcome::Failed),
+        Ok(RunJobOutcome::Deferred) => {
+            record_job_event(
+                &worker,
+                &job.uuid,
+                NewJobEvent::new(JobEventKind::Deferred, runner).with_timing(timing),
+            )
+            .await;
+            Ok(RunJobOutcome::Deferred)
+        }
+        Ok(RunJobOutcome::Cancelled) => Ok(RunJobOutcome::Cancelled),
+        Err(ref err) => {
+            tracing::error!(
+                "Job {} failed: {}",
+                job.uuid,
+                err.to_nice_error().to_string()
+            );
+            worker
+                .mark_job_failed(&job.uuid, err.to_nice_error().to_string().as_str())
+                .await
+                .map_err(RunJobError::FailedToMarkJobFailed)?;
+            record_job_event(
+                &worker,
+                &job.uuid,
+                NewJobEvent::new(JobEventKind::Failed, runner)
+                    .with_error_class(err.code())
+                    .with_timing(timing),
+            )
+            .await;
+            Ok(RunJobOutcome::Failed)
+        }
+    }
+}
+
+/// The event log is only there for debugging, so failing to write to it
+/// never fails the job.
+async fn record_job_event<W: JobCapability + LogCapability>(
+    worker: &W,
+    job_uuid: &JobUuid,
+    event: NewJobEvent,
+) {
+    if let Err(err) = worker.record_job_event(job_uuid, &event).await {
+        worker.log(
+            Level::Warning,
+            &format!(
+                "Could not record the {} event of job {}: {}",
+                event.kind.to_name(),
+                job_uuid,
+                err
+            ),
+        );
+    }
+}
+
+async fn execute_job<
+    W: JobCapability
+        + MessageCapability
+        + ModerationCapability
+        + SceneCapability
+        + ReactionCapability
+        + MemoryCapability
+        + PersonCapability
+        + EventCapability
+        + StateOfMindCapability
+        + PersonIdentityCapability
+        + PersonTaskCapability
+        + ReactionHistoryCapability
+        + ExpectedReplyCapability
+        + ItemCapability
+        + SceneInvitationCapability
+        + PersonArcCapability
+        + AutobiographyCapability
+        + ArrivalObservationCapability
+        + LogEventCapability
+        + ReflectionCapability
+        + MotivationCapability
+        + OutboxCapability
+        + LlmBatchCapability
+        + IdlePersonCapability
+        + PersonaConsistencyCapability
+        + SceneGoalCapability
+        + SceneArchiveCapability
+        + TopicCapability
+        + SceneDramaCapability
+        + CustomActionCapability
+        + AcknowledgementCapability
+        + LogCapability
+        + MaintenanceCapability
+        + ExternalToolCapability
+        + Sync,
+>(
+    worker: &W,
+    random_seed: RandomSeed,
+    current_active_ms: i64,
+    job_uuid: &JobUuid,
+    job_kind: JobKind,
 ) -> Result<RunJobOutcome, RunJobError> {
-    let res: Result<RunJobOutcome, RunJobError> = match job.kind {
+    match job_kind {
         JobKind::Ping => {
             tracing::debug!("Ping job received");
             println!("Pong");
@@ -379,7 +1020,7 @@ async fn run_job<
         JobKind::SendMessageToScene(job_data) => {
             tracing::debug!("Executing SendMessageToScene job");
             job_data
-                .run(&worker)
+                .run(worker)
                 .await
                 .map_err(RunJobError::SendMessageToSceneError)
                 .map(|_| RunJobOutcome::Completed)
@@ -387,7 +1028,7 @@ async fn run_job<
         JobKind::ProcessMessage(process_message_job) => {
             tracing::debug!("Executing ProcessMessage job");
             process_message_job
-                .run(&worker, random_seed, current_active_ms)
+                .run(worker, random_seed, current_active_ms)
                 .await
                 .map_err(RunJobError::ProcessMessageError)
                 .map(|_| RunJobOutcome::Completed)
@@ -395,7 +1036,7 @@ async fn run_job<
         JobKind::ProcessPersonJoin(process_person_join_job) => {
             tracing::debug!("Executing ProcessPersonJoin job");
             process_person_join_job
-                .run(&worker, random_seed, current_active_ms)
+                .run(worker, random_seed, current_active_ms)
                 .await
                 .map_err(RunJobError::ProcessPersonJoinError)
                 .map(|_| RunJobOutcome::Completed)
@@ -403,7 +1044,7 @@ async fn run_job<
         JobKind::ProcessSceneGaze(process_scene_gaze_job) => {
             tracing::debug!("Executing ProcessSceneGaze job");
             process_scene_gaze_job
-                .run(&worker, random_seed, current_active_ms)
+                .run(worker, random_seed, current_active_ms)
                 .await
                 .map_err(RunJobError::ProcessSceneGazeError)
                 .map(|_| RunJobOutcome::Completed)
@@ -411,14 +1052,14 @@ async fn run_job<
         JobKind::PersonWaiting(person_waiting_job) => {
             tracing::debug!("Executing PersonWaiting job");
             match person_waiting_job
-                .run(&worker, random_seed, current_active_ms)
+                .run(worker, random_seed, current_active_ms)
                 .await
                 .map_err(RunJobError::PersonWaitingError)?
             {
                 person_waiting::WaitDecision::FinishedWaiting => Ok(RunJobOutcome::Completed),
                 person_waiting::WaitDecision::ContinueWaiting => {
                     worker
-                        .reset_job(&job.uuid)
+                        .reset_job(job_uuid)
                         .await
                         .map_err(RunJobError::FailedToResetJob)?;
                     Ok(RunJobOutcome::Deferred)
@@ -428,62 +1069,221 @@ async fn run_job<
         JobKind::PersonHibernating(person_hibernating_job) => {
             tracing::debug!("Executing PersonHibernating job");
             match person_hibernating_job
-                .run(&worker, current_active_ms)
+                .run(worker, current_active_ms)
                 .await
                 .map_err(RunJobError::PersonHibernatingError)?
             {
                 person_hibernating::HibernateOutcome::Ready => Ok(RunJobOutcome::Completed),
                 person_hibernating::HibernateOutcome::NotReady => {
                     worker
-                        .reset_job(&job.uuid)
+                        .reset_job(job_uuid)
                         .await
                         .map_err(RunJobError::FailedToResetJob)?;
                     Ok(RunJobOutcome::Deferred)
                 }
             }
         }
-    };
-
-    match res {
-        Ok(RunJobOutcome::Completed) => {
-            tracing::info!("Job {} completed successfully", job.uuid);
-            worker
-                .mark_job_finished(&job.uuid)
+        JobKind::CheckExpectedReply(check_expected_reply_job) => {
+            tracing::debug!("Executing CheckExpectedReply job");
+            check_expected_reply_job
+                .run(worker, random_seed, current_active_ms)
                 .await
-                .map_err(RunJobError::FailedToMarkJobFinished)?;
-            Ok(RunJobOutcome::Completed)
+                .map_err(RunJobError::CheckExpectedReplyError)
+                .map(|_| RunJobOutcome::Completed)
         }
-        Ok(RunJobOutcome::Deferred) => Ok(RunJobOutcome::Deferred),
-        Err(ref err) => {
-            tracing::error!(
-                "Job {} failed: {}",
-                job.uuid,
-                err.to_nice_error().to_string()
-            );
-            worker
-                .mark_job_failed(&job.uuid, err.to_nice_error().to_string().as_str())
+        JobKind::DispatchOutbox(dispatch_outbox_job) => {
+            tracing::debug!("Executing DispatchOutbox job");
+            dispatch_outbox_job
+                .run(worker, current_active_ms)
                 .await
-                .map_err(RunJobError::FailedToMarkJobFailed)?;
+                .map_err(RunJobError::DispatchOutboxError)
+                .map(|_| RunJobOutcome::Completed)
+        }
+        JobKind::PollLlmBatch(poll_llm_batch_job) => {
+            tracing::debug!("Executing PollLlmBatch job");
+            poll_llm_batch_job
+                .run(worker, current_active_ms)
+                .await
+                .map_err(RunJobError::PollLlmBatchError)
+                .map(|_| RunJobOutcome::Completed)
+        }
+        JobKind::HandleBatchCompletion(handle_batch_completion_job) => {
+            tracing::debug!("Executing HandleBatchCompletion job");
+            handle_batch_completion_job
+                .run(worker)
+                .await
+                .map_err(RunJobError::HandleBatchCompletionError)
+                .map(|_| RunJobOutcome::Completed)
+        }
+        JobKind::WakeIdlePersons => {
+            tracing::debug!("Executing WakeIdlePersons job");
+            wake_idle_persons::run(worker, random_seed)
+                .await
+                .map_err(RunJobError::WakeIdlePersonsError)
+                .map(|_| RunJobOutcome::Completed)
+        }
+        JobKind::NoticeConversation(notice_conversation_job) => {
+            tracing::debug!("Executing NoticeConversation job");
+            notice_conversation_job
+                .run(worker, random_seed, current_active_ms)
+                .await
+                .map_err(RunJobError::NoticeConversationError)
+                .map(|_| RunJobOutcome::Completed)
+        }
+        JobKind::CheckPersonaConsistency(check_persona_consistency_job) => {
+            tracing::debug!("Executing CheckPersonaConsistency job");
+            check_persona_consistency_job
+                .run(worker, random_seed)
+                .await
+                .map_err(RunJobError::CheckPersonaConsistencyError)
+                .map(|_| RunJobOutcome::Completed)
+        }
+        JobKind::CheckSceneGoals => {
+            tracing::debug!("Executing CheckSceneGoals job");
+            check_scene_goals::run(worker, current_active_ms)
+                .await
+                .map_err(RunJobError::CheckSceneGoalsError)
+                .map(|_| RunJobOutcome::Completed)
+        }
+        JobKind::CloseScene(close_scene_job) => {
+            tracing::debug!("Executing CloseScene job");
+            close_scene_job
+                .run(worker)
+                .await
+                .map_err(RunJobError::CloseSceneError)
+                .map(|_| RunJobOutcome::Completed)
+        }
+        JobKind::ArchiveScene(archive_scene_job) => {
+            tracing::debug!("Executing ArchiveScene job");
+            archive_scene_job
+                .run(worker)
+                .await
+                .map_err(RunJobError::ArchiveSceneError)
+                .map(|_| RunJobOutcome::Completed)
+        }
+        JobKind::NightlyMaintenance => {
+            tracing::debug!("Executing NightlyMaintenance job");
+            let report = nightly_maintenance::run(worker)
+                .await
+                .map_err(RunJobError::NightlyMaintenanceError)?;
+            tracing::info!(
+                "Nightly maintenance finished with {} of {} steps failed",
+                report.failed_step_count(),
+                report.steps.len()
+            );
             Ok(RunJobOutcome::Completed)
         }
+        JobKind::TagTopics => {
+            tracing::debug!("Executing TagTopics job");
+            tag_topics::run(worker)
+                .await
+                .map_err(RunJobError::TagTopicsError)
+                .map(|_| RunJobOutcome::Completed)
+        }
+        JobKind::ChangeSceneAmbience(change_scene_ambience_job) => {
+            tracing::debug!("Executing ChangeSceneAmbience job");
+            change_scene_ambience_job
+                .run(worker)
+                .await
+                .map_err(RunJobError::ChangeSceneAmbienceError)
+                .map(|_| RunJobOutcome::Completed)
+        }
+        JobKind::InjectSceneEvents => {
+            tracing::debug!("Executing InjectSceneEvents job");
+            inject_scene_events::run(worker, random_seed)
+                .await
+                .map_err(RunJobError::InjectSceneEventsError)
+                .map(|_| RunJobOutcome::Completed)
+        }
+        JobKind::ReactToSceneEvent(react_to_scene_event_job) => {
+            tracing::debug!("Executing ReactToSceneEvent job");
+            react_to_scene_event_job
+                .run(worker, random_seed, current_active_ms)
+                .await
+                .map_err(RunJobError::ReactToSceneEventError)
+                .map(|_| RunJobOutcome::Completed)
+        }
+        JobKind::RunCustomAction(run_custom_action_job) => {
+            tracing::debug!("Executing RunCustomAction job");
+            run_custom_action_job
+                .run(worker)
+                .await
+                .map_err(RunJobError::RunCustomActionError)
+                .map(|_| RunJobOutcome::Completed)
+        }
+        JobKind::RefillAcknowledgements(refill_acknowledgements_job) => {
+            tracing::debug!("Executing RefillAcknowledgements job");
+            refill_acknowledgements_job
+                .run(worker)
+                .await
+                .map_err(RunJobError::RefillAcknowledgementsError)
+                .map(|_| RunJobOutcome::Completed)
+        }
+        JobKind::UseTool(use_tool_job) => {
+            tracing::debug!("Executing UseTool job");
+            use_tool_job
+                .run(worker, random_seed, current_active_ms)
+                .await
+                .map_err(RunJobError::UseToolError)
+                .map(|_| RunJobOutcome::Completed)
+        }
+        JobKind::ReactToSceneInvitation(react_to_scene_invitation_job) => {
+            tracing::debug!("Executing ReactToSceneInvitation job");
+            react_to_scene_invitation_job
+                .run(worker, random_seed, current_active_ms)
+                .await
+                .map_err(RunJobError::ReactToSceneInvitationError)
+                .map(|_| RunJobOutcome::Completed)
+        }
+        JobKind::UpdatePersonArcs => {
+            tracing::debug!("Executing UpdatePersonArcs job");
+            update_person_arcs::run(worker, current_active_ms)
+                .await
+                .map_err(RunJobError::UpdatePersonArcsError)
+                .map(|_| RunJobOutcome::Completed)
+        }
+        JobKind::BalanceChattiness => {
+            tracing::debug!("Executing BalanceChattiness job");
+            balance_chattiness::run(worker)
+                .await
+                .map_err(RunJobError::BalanceChattinessError)
+                .map(|_| RunJobOutcome::Completed)
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::capability::acknowledgement::AcknowledgementCapability;
+    use crate::capability::arrival_observation::ArrivalObservationCapability;
+    use crate::capability::custom_action::CustomActionCapability;
     use crate::capability::event::{EventCapability, GetArgs};
+    use crate::capability::expected_reply::{
+        ExpectedReplyCapability, ExpectedReplyOutcome, NewExpectedReply,
+    };
+    use crate::capability::external_tool::{ExternalToolCapability, NewExternalToolCall};
+    use crate::capability::idle_person::{IdlePerson, IdlePersonCapability};
+    use crate::capability::item::{ItemCapability, NewItem};
     use crate::capability::job::JobCapability;
+    use crate::capability::llm_batch::LlmBatchCapability;
     use crate::capability::log_event::LogEventCapability;
     use crate::capability::logging::LogCapability;
+    use crate::capability::maintenance::MaintenanceCapability;
     use crate::capability::memory::{
         MemoryCapability, MemoryQueryPrompt, MemorySearchResult, MessageTypeArgs, NewMemory,
     };
     use crate::capability::message::MessageCapability;
+    use crate::capability::moderation::{BlockedContent, ModerationCapability};
     use crate::capability::motivation::{MotivationCapability, NewMotivation};
+    use crate::capability::outbox::OutboxCapability;
     use crate::capability::person::{NewPerson, PersonCapability};
+    use crate::capability::person_arc::NewPersonArc;
     use crate::capability::person_identity::{NewPersonIdentity, PersonIdentityCapability};
     use crate::capability::person_task::{NewPersonTask, PersonTaskCapability};
+    use crate::capability::persona_consistency::{
+        PersonaConsistencyCapability, RecordedPersonaInconsistency,
+    };
     use crate::capability::reaction::ReactionCapability;
     use crate::capability::reaction_history::ReactionHistoryCapability;
     use crate::capability::reflection::{ReflectionCapability, ReflectionChange};
@@ -491,16 +1291,38 @@ mod tests {
         CurrentScene, NewScene, NewSceneSnapshot, Scene, SceneCapability, SceneParticipant,
         SceneParticipation,
     };
+    use crate::capability::scene_archive::SceneArchiveCapability;
+    use crate::capability::scene_drama::{DramaticScene, SceneDramaCapability};
+    use crate::capability::scene_goal::SceneGoalCapability;
+    use crate::capability::scene_invitation::NewSceneInvitation;
     use crate::capability::state_of_mind::{NewStateOfMind, StateOfMindCapability};
+    use crate::capability::topic::TopicCapability;
+    use crate::domain::annotation::Annotation;
+    use crate::domain::autobiography::LifeEvent;
+    use crate::domain::external_tool::ExternalTool;
+    use crate::domain::fan_out::QueuePressure;
+    use crate::domain::item::Item;
+    use crate::domain::item_uuid::ItemUuid;
+    use crate::domain::job::nightly_maintenance::{MaintenanceReport, PrunedRows, RecentMemories};
+    use crate::domain::job::send_message_to_scene::SendMessageToSceneJob;
     use crate::domain::job::{JobKind, PoppedJob};
+    use crate::domain::job_event::{JobEvent, JobEventKind, NewJobEvent};
     use crate::domain::job_uuid::JobUuid;
+    use crate::domain::knowledge_boundary::Attendance;
     use crate::domain::logger::Level;
     use crate::domain::memory::Memory;
     use crate::domain::memory_uuid::MemoryUuid;
     use crate::domain::message::{Message, MessageSender};
+    use crate::domain::message_audience::{HearingRadius, MessageAudience};
+    use crate::domain::message_quote::MessageQuote;
     use crate::domain::message_uuid::MessageUuid;
+    use crate::domain::moderation::ModerationVerdict;
     use crate::domain::motivation::Motivation;
     use crate::domain::motivation_uuid::MotivationUuid;
+    use crate::domain::outbox::{OutboxEntry, OutboxEvent};
+    use crate::domain::outbox_uuid::OutboxUuid;
+    use crate::domain::person_arc::{ArcDraft, PersonArc};
+    use crate::domain::person_directory::PersonDirectoryEntry;
     use crate::domain::person_identity_uuid::PersonIdentityUuid;
     use crate::domain::person_name::PersonName;
     use crate::domain::person_task::{
@@ -508,14 +1330,23 @@ mod tests {
     };
     use crate::domain::person_task_uuid::PersonTaskUuid;
     use crate::domain::person_uuid::PersonUuid;
+    use crate::domain::persona_consistency::PersonaInconsistency;
+    use crate::domain::scene_ambience::Ambience;
+    use crate::domain::scene_goal::{OpenSceneGoal, SceneGoal, SceneGoalMet, TranscriptLine};
+    use crate::domain::scene_invitation::SceneInvitation;
+    use crate::domain::scene_invitation_uuid::SceneInvitationUuid;
     use crate::domain::scene_participant_uuid::SceneParticipantUuid;
     use crate::domain::scene_uuid::SceneUuid;
     use crate::domain::state_of_mind::StateOfMind;
     use crate::domain::state_of_mind_uuid::StateOfMindUuid;
+    use crate::domain::talk_share::TalkCount;
+    use crate::domain::topic::{Topic, TopicCount, TopicScene, TrendingTopic, UntaggedScene};
+    use crate::domain::world_time::{TimeOfDay, WorldTime};
+    use crate::open_ai::batch::{Batch, BatchRequest, BatchResult, BatchStatus};
     use crate::person_actions::{PersonAction, PersonReaction, ReflectionDecision};
     use async_trait::async_trait;
     use chrono::{DateTime, Utc};
-    use std::collections::HashSet;
+    use std::collections::{HashMap, HashSet};
     use std::sync::Arc;
     use tokio::sync::Mutex;
 
@@ -528,6 +1359,10 @@ mod tests {
     struct MockState {
         jobs: Vec<PoppedJob>,
         finished_jobs: HashSet<JobUuid>,
+        failed_jobs: HashSet<JobUuid>,
+        reset_jobs: HashSet<JobUuid>,
+        job_events: Vec<JobEventKind>,
+        sent_messages: Vec<String>,
     }
 
     impl MockWorker {
@@ -535,7 +1370,7 @@ mod tests {
             Self {
                 state: Arc::new(Mutex::new(MockState {
                     jobs: vec![job],
-                    finished_jobs: HashSet::new(),
+                    ..MockState::default()
                 })),
             }
         }
@@ -549,8 +1384,10 @@ mod tests {
             &self,
             _sender: MessageSender,
             _scene_uuid: SceneUuid,
-            _content: String,
+            content: String,
         ) -> Result<MessageUuid, String> {
+            let mut st = self.state.lock().await;
+            st.sent_messages.push(content);
             Ok(MessageUuid::new())
         }
 
@@ -562,6 +1399,22 @@ mod tests {
             Ok(())
         }
 
+        async fn set_message_audience(
+            &self,
+            _message_uuid: &MessageUuid,
+            _audience: &MessageAudience,
+        ) -> Result<(), String> {
+            Ok(())
+        }
+
+        async fn add_scene_message_overhearers(
+            &self,
+            _message_uuid: &MessageUuid,
+            _overhearers: Vec<PersonUuid>,
+        ) -> Result<(), String> {
+            Ok(())
+        }
+
         async fn get_messages_in_scene_page(
             &self,
             _scene_uuid: &SceneUuid,
@@ -593,6 +1446,27 @@ mod tests {
         ) -> Result<(), String> {
             Ok(())
         }
+
+        async fn set_message_quote(
+            &self,
+            _message_uuid: &MessageUuid,
+            _quote: &MessageQuote,
+        ) -> Result<(), String> {
+            Ok(())
+        }
+
+        async fn get_message_quotes(
+            &self,
+            _message_uuids: Vec<MessageUuid>,
+        ) -> Result<HashMap<MessageUuid, MessageQuote>, String> {
+            Ok(HashMap::new())
+        }
+    }
+
+    impl ClockCapability for MockWorker {
+        fn now(&self) -> chrono::DateTime<chrono::Utc> {
+            chrono::Utc::now()
+        }
     }
 
     impl JobCapability for MockWorker {
@@ -607,6 +1481,11 @@ mod tests {
             );
             Ok(())
         }
+        async fn enqueue_job(&self, job: JobKind) -> Result<JobUuid, String> {
+            self.unshift_job(job).await?;
+            Ok(JobUuid::new())
+        }
+
         async fn pop_next_job(&self, _current_active_ms: i64) -> Result<Option<PoppedJob>, String> {
             let mut st = self.state.lock().await;
             Ok(st.jobs.pop())
@@ -628,11 +1507,15 @@ mod tests {
             Ok(())
         }
 
-        async fn mark_job_failed(&self, _job_uuid: &JobUuid, _details: &str) -> Result<(), String> {
+        async fn mark_job_failed(&self, job_uuid: &JobUuid, _details: &str) -> Result<(), String> {
+            let mut st = self.state.lock().await;
+            st.failed_jobs.insert(job_uuid.clone());
             Ok(())
         }
 
-        async fn reset_job(&self, _job_uuid: &JobUuid) -> Result<(), String> {
+        async fn reset_job(&self, job_uuid: &JobUuid) -> Result<(), String> {
+            let mut st = self.state.lock().await;
+            st.reset_jobs.insert(job_uuid.clone());
             Ok(())
         }
 
@@ -643,6 +1526,42 @@ mod tests {
         async fn delete_job(&self, _job_uuid: &JobUuid) -> Result<(), String> {
             Ok(())
         }
+
+        async fn record_job_event(
+            &self,
+            _job_uuid: &JobUuid,
+            event: &NewJobEvent,
+        ) -> Result<(), String> {
+            let mut st = self.state.lock().await;
+            st.job_events.push(event.kind.clone());
+            Ok(())
+        }
+
+        async fn get_job_events(&self, _job_uuid: &JobUuid) -> Result<Vec<JobEvent>, String> {
+            Ok(vec![])
+        }
+
+        async fn get_queue_pressure(&self) -> Result<QueuePressure, String> {
+            let st = self.state.lock().await;
+            Ok(QueuePressure {
+                pending_jobs: st.jobs.len() as i64,
+                active_ms: 0,
+            })
+        }
+
+        async fn get_next_delayed_job_active_ms(
+            &self,
+            _current_active_ms: i64,
+        ) -> Result<Option<i64>, String> {
+            Ok(None)
+        }
+
+        async fn get_persons_wait_end_active_ms(
+            &self,
+            _person_uuid: &PersonUuid,
+        ) -> Result<Option<i64>, String> {
+            Ok(None)
+        }
     }
 
     #[async_trait]
@@ -714,6 +1633,13 @@ mod tests {
             Ok(vec![])
         }
 
+        async fn get_persons_attendance(
+            &self,
+            _person_uuid: &PersonUuid,
+        ) -> Result<Vec<Attendance>, String> {
+            Ok(vec![])
+        }
+
         async fn set_real_world_user_in_scene(
             &self,
             _scene_uuid: &SceneUuid,
@@ -729,6 +1655,21 @@ mod tests {
             Ok(false)
         }
 
+        async fn get_scene_hearing_radius(
+            &self,
+            _scene_uuid: &SceneUuid,
+        ) -> Result<HearingRadius, String> {
+            Ok(HearingRadius::Room)
+        }
+
+        async fn set_scene_hearing_radius(
+            &self,
+            _scene_uuid: &SceneUuid,
+            _hearing_radius: HearingRadius,
+        ) -> Result<(), String> {
+            Ok(())
+        }
+
         async fn get_scene_name(&self, _scene_uuid: &SceneUuid) -> Result<Option<String>, String> {
             Ok(None)
         }
@@ -739,6 +1680,40 @@ mod tests {
         ) -> Result<Option<String>, String> {
             Ok(None)
         }
+
+        async fn set_scene_time_of_day(
+            &self,
+            _scene_uuid: &SceneUuid,
+            _time_of_day: TimeOfDay,
+        ) -> Result<(), String> {
+            Ok(())
+        }
+
+        async fn get_scene_ambience(
+            &self,
+            _scene_uuid: &SceneUuid,
+        ) -> Result<Option<Ambience>, String> {
+            Ok(None)
+        }
+
+        async fn set_scene_ambience(
+            &self,
+            _scene_uuid: &SceneUuid,
+            _ambience: Ambience,
+        ) -> Result<(), String> {
+            Ok(())
+        }
+
+        async fn get_scene_world_time(&self, _scene_uuid: &SceneUuid) -> Result<WorldTime, String> {
+            Ok(WorldTime::new(0, 0))
+        }
+
+        async fn get_scene_participant_chattiness(
+            &self,
+            _scene_uuid: &SceneUuid,
+        ) -> Result<Vec<(PersonUuid, f64)>, String> {
+            Ok(vec![])
+        }
     }
 
     impl ReactionCapability for MockWorker {
@@ -757,6 +1732,7 @@ mod tests {
                 thinking_user_prompt: String::new(),
                 action_system_prompt: String::new(),
                 action_user_prompt: String::new(),
+                context_timings: Default::default(),
             })
         }
 
@@ -925,10 +1901,28 @@ mod tests {
             &self,
             _person_uuid: PersonUuid,
             _query: String,
-            _limit: i64,
+            _participants: &[PersonUuid],
+            _retrieval: &crate::domain::memory::MemoryRetrieval,
         ) -> Result<Vec<MemorySearchResult>, String> {
             Ok(vec![])
         }
+
+        async fn get_memory_retrieval(
+            &self,
+        ) -> Result<crate::domain::memory::MemoryRetrieval, String> {
+            Ok(crate::domain::memory::MemoryRetrieval::default())
+        }
+
+        async fn set_memory_retrieval(
+            &self,
+            _retrieval: &crate::domain::memory::MemoryRetrieval,
+        ) -> Result<(), String> {
+            Ok(())
+        }
+
+        async fn delete_memory(&self, _memory_uuid: &MemoryUuid) -> Result<(), String> {
+            Ok(())
+        }
     }
 
     impl PersonCapability for MockWorker {
@@ -974,6 +1968,10 @@ mod tests {
         async fn is_person_enabled(&self, _person_uuid: &PersonUuid) -> Result<bool, String> {
             Ok(true)
         }
+
+        async fn archive_person(&self, _person_uuid: &PersonUuid) -> Result<(), String> {
+            Ok(())
+        }
     }
 
     impl EventCapability for MockWorker {
@@ -985,6 +1983,218 @@ mod tests {
         }
     }
 
+    impl ModerationCapability for MockWorker {
+        async fn moderate_content(
+            &self,
+            _sender: &MessageSender,
+            _scene_uuid: &SceneUuid,
+            _content: &str,
+        ) -> Result<ModerationVerdict, String> {
+            Ok(ModerationVerdict::Allowed)
+        }
+
+        async fn get_blocked_content(&self, _limit: i64) -> Result<Vec<BlockedContent>, String> {
+            Ok(vec![])
+        }
+
+        async fn get_moderation_threshold(&self) -> Result<f64, String> {
+            Ok(1.0)
+        }
+
+        async fn set_moderation_threshold(&self, _threshold: f64) -> Result<(), String> {
+            Ok(())
+        }
+    }
+
+    impl PersonArcCapability for MockWorker {
+        async fn create_person_arc(&self, _new_person_arc: NewPersonArc) -> Result<(), String> {
+            Ok(())
+        }
+
+        async fn get_latest_person_arc(
+            &self,
+            _person_uuid: &PersonUuid,
+        ) -> Result<Option<PersonArc>, String> {
+            Ok(None)
+        }
+
+        async fn get_persons_due_an_arc(
+            &self,
+            _written_before_active_ms: i64,
+            _limit: i64,
+        ) -> Result<Vec<PersonUuid>, String> {
+            Ok(vec![])
+        }
+
+        async fn write_person_arc(
+            &self,
+            _person_name: &str,
+            _previous_arc: Option<&PersonArc>,
+            _events: &[LifeEvent],
+        ) -> Result<ArcDraft, String> {
+            Ok(ArcDraft {
+                storyline: String::new(),
+                stakes: String::new(),
+                progress_notes: String::new(),
+            })
+        }
+    }
+
+    impl AutobiographyCapability for MockWorker {
+        async fn get_life_events(
+            &self,
+            _person_uuid: &PersonUuid,
+        ) -> Result<Vec<LifeEvent>, String> {
+            Ok(vec![])
+        }
+
+        async fn write_autobiography_chapter(
+            &self,
+            _person_name: &str,
+            _previous_chapter: Option<&str>,
+            _events: &[LifeEvent],
+        ) -> Result<String, String> {
+            Ok(String::new())
+        }
+    }
+
+    impl SceneInvitationCapability for MockWorker {
+        async fn create_scene_invitation(
+            &self,
+            _new_scene_invitation: NewSceneInvitation,
+        ) -> Result<SceneInvitationUuid, String> {
+            Ok(SceneInvitationUuid::new())
+        }
+
+        async fn get_pending_scene_invitations(
+            &self,
+            _person_uuid: &PersonUuid,
+        ) -> Result<Vec<SceneInvitation>, String> {
+            Ok(vec![])
+        }
+
+        async fn respond_to_scene_invitations(
+            &self,
+            _person_uuid: &PersonUuid,
+            _current_scene_uuid: Option<&SceneUuid>,
+        ) -> Result<(), String> {
+            Ok(())
+        }
+    }
+
+    impl ItemCapability for MockWorker {
+        async fn create_item(&self, _new_item: NewItem) -> Result<ItemUuid, String> {
+            Ok(ItemUuid::new())
+        }
+
+        async fn get_items_carried_by(
+            &self,
+            _person_uuid: &PersonUuid,
+        ) -> Result<Vec<Item>, String> {
+            Ok(vec![])
+        }
+
+        async fn get_items_in_scene(&self, _scene_uuid: &SceneUuid) -> Result<Vec<Item>, String> {
+            Ok(vec![])
+        }
+
+        async fn give_item(
+            &self,
+            _item_uuid: &ItemUuid,
+            _giver_person_uuid: &PersonUuid,
+            _recipient_person_uuid: &PersonUuid,
+        ) -> Result<(), String> {
+            Ok(())
+        }
+    }
+
+    impl ExpectedReplyCapability for MockWorker {
+        async fn expect_reply(&self, _new_expected_reply: NewExpectedReply) -> Result<(), String> {
+            Ok(())
+        }
+
+        async fn has_recipient_replied(&self, _message_uuid: &MessageUuid) -> Result<bool, String> {
+            Ok(false)
+        }
+
+        async fn resolve_expected_reply(
+            &self,
+            _message_uuid: &MessageUuid,
+            _outcome: ExpectedReplyOutcome,
+        ) -> Result<(), String> {
+            Ok(())
+        }
+    }
+
+    impl ArrivalObservationCapability for MockWorker {
+        async fn get_recent_public_scene_messages(
+            &self,
+            _scene_uuid: &SceneUuid,
+            _since: DateTime<Utc>,
+        ) -> Result<Vec<Message>, String> {
+            Ok(vec![])
+        }
+
+        async fn summarize_arrival_observation(
+            &self,
+            _scene_description: &str,
+            _transcript: &str,
+        ) -> Result<String, String> {
+            Ok(String::new())
+        }
+
+        async fn record_arrival_observation(
+            &self,
+            _person_uuid: &PersonUuid,
+            _scene_uuid: &SceneUuid,
+            _observation: String,
+        ) -> Result<(), String> {
+            Ok(())
+        }
+    }
+
+    impl OutboxCapability for MockWorker {
+        fn get_outbox_webhook_url(&self) -> Option<String> {
+            None
+        }
+
+        async fn add_outbox_event(&self, _event: &OutboxEvent) -> Result<(), String> {
+            Ok(())
+        }
+
+        async fn claim_due_outbox_entries(&self, _limit: i64) -> Result<Vec<OutboxEntry>, String> {
+            Ok(vec![])
+        }
+
+        async fn post_outbox_entry(
+            &self,
+            _webhook_url: &str,
+            _entry: &OutboxEntry,
+        ) -> Result<(), String> {
+            Ok(())
+        }
+
+        async fn mark_outbox_entry_delivered(
+            &self,
+            _outbox_uuid: &OutboxUuid,
+        ) -> Result<(), String> {
+            Ok(())
+        }
+
+        async fn mark_outbox_entry_failed(
+            &self,
+            _outbox_uuid: &OutboxUuid,
+            _details: &str,
+            _retry_at: DateTime<Utc>,
+        ) -> Result<(), String> {
+            Ok(())
+        }
+
+        async fn has_undelivered_outbox_entries(&self) -> Result<bool, String> {
+            Ok(false)
+        }
+    }
+
     impl LogCapability for MockWorker {
         fn log(&self, _level: Level, _message: &str) {
             // no-op for tests
@@ -1048,12 +2258,343 @@ mod tests {
         ) -> Result<Option<String>, String> {
             Ok(None)
         }
+
+        async fn get_person_directory(
+            &self,
+            _viewer: &PersonUuid,
+            _others: &[PersonUuid],
+        ) -> Result<Vec<PersonDirectoryEntry>, String> {
+            Ok(vec![])
+        }
+
+        async fn set_person_identity_summary(
+            &self,
+            _person_identity_uuid: &PersonIdentityUuid,
+            _summary: &str,
+        ) -> Result<(), String> {
+            Ok(())
+        }
+    }
+
+    impl IdlePersonCapability for MockWorker {
+        async fn get_idle_persons_in_active_scenes(
+            &self,
+            _idle_since: DateTime<Utc>,
+            _active_since: DateTime<Utc>,
+        ) -> Result<Vec<IdlePerson>, String> {
+            Ok(vec![])
+        }
+
+        async fn get_person_chattiness(&self, _person_uuid: &PersonUuid) -> Result<f64, String> {
+            Ok(0.0)
+        }
+
+        async fn set_person_chattiness(
+            &self,
+            _person_uuid: &PersonUuid,
+            _chattiness: f64,
+        ) -> Result<(), String> {
+            Ok(())
+        }
+
+        async fn get_talk_counts(&self, _since: DateTime<Utc>) -> Result<Vec<TalkCount>, String> {
+            Ok(vec![])
+        }
+    }
+
+    impl PersonaConsistencyCapability for MockWorker {
+        async fn get_recent_utterances(
+            &self,
+            _person_uuid: &PersonUuid,
+            _limit: i64,
+        ) -> Result<Vec<String>, String> {
+            Ok(vec![])
+        }
+
+        async fn judge_persona_consistency(
+            &self,
+            _identity: &str,
+            _utterances: &[String],
+        ) -> Result<Vec<PersonaInconsistency>, String> {
+            Ok(vec![])
+        }
+
+        async fn record_persona_inconsistencies(
+            &self,
+            _person_uuid: &PersonUuid,
+            _inconsistencies: &[PersonaInconsistency],
+        ) -> Result<(), String> {
+            Ok(())
+        }
+
+        async fn get_persona_inconsistencies(
+            &self,
+            _person_uuid: &PersonUuid,
+            _limit: i64,
+        ) -> Result<Vec<RecordedPersonaInconsistency>, String> {
+            Ok(vec![])
+        }
+    }
+
+    impl SceneArchiveCapability for MockWorker {
+        async fn cancel_pending_scene_jobs(&self, _scene_uuid: &SceneUuid) -> Result<u64, String> {
+            Ok(0)
+        }
+
+        async fn get_exported_annotations(
+            &self,
+            _scene_uuid: &SceneUuid,
+        ) -> Result<Vec<Annotation>, String> {
+            Ok(vec![])
+        }
+
+        async fn export_scene_transcript(
+            &self,
+            file_name: &str,
+            _transcript: &str,
+        ) -> Result<String, String> {
+            Ok(file_name.to_string())
+        }
+
+        async fn mark_scene_archived(
+            &self,
+            _scene_uuid: &SceneUuid,
+            _transcript_path: &str,
+        ) -> Result<(), String> {
+            Ok(())
+        }
+    }
+
+    impl SceneGoalCapability for MockWorker {
+        async fn set_scene_goal(
+            &self,
+            _scene_uuid: &SceneUuid,
+            _goal: &SceneGoal,
+        ) -> Result<(), String> {
+            Ok(())
+        }
+
+        async fn get_scene_goal(
+            &self,
+            _scene_uuid: &SceneUuid,
+        ) -> Result<Option<SceneGoal>, String> {
+            Ok(None)
+        }
+
+        async fn get_open_scene_goals(&self) -> Result<Vec<OpenSceneGoal>, String> {
+            Ok(vec![])
+        }
+
+        async fn get_scene_transcript_since(
+            &self,
+            _scene_uuid: &SceneUuid,
+            _since: DateTime<Utc>,
+        ) -> Result<Vec<TranscriptLine>, String> {
+            Ok(vec![])
+        }
+
+        async fn judge_scene_consensus(
+            &self,
+            _consensus_on: &str,
+            _transcript: &str,
+        ) -> Result<bool, String> {
+            Ok(false)
+        }
+
+        async fn summarize_scene_ending(
+            &self,
+            _scene_description: &str,
+            _transcript: &str,
+            _met: &SceneGoalMet,
+        ) -> Result<String, String> {
+            Ok(String::new())
+        }
+
+        async fn mark_scene_goal_met(
+            &self,
+            _scene_uuid: &SceneUuid,
+            _met: &SceneGoalMet,
+        ) -> Result<bool, String> {
+            Ok(false)
+        }
+    }
+
+    impl SceneDramaCapability for MockWorker {
+        async fn get_dramatic_scenes(&self) -> Result<Vec<DramaticScene>, String> {
+            Ok(vec![])
+        }
+
+        async fn get_scene_drama_intensity(&self, _scene_uuid: &SceneUuid) -> Result<f64, String> {
+            Ok(0.0)
+        }
+
+        async fn set_scene_drama_intensity(
+            &self,
+            _scene_uuid: &SceneUuid,
+            _intensity: f64,
+        ) -> Result<(), String> {
+            Ok(())
+        }
+
+        async fn add_scene_event(
+            &self,
+            _scene_uuid: &SceneUuid,
+            _description: &str,
+        ) -> Result<(), String> {
+            Ok(())
+        }
+    }
+
+    impl CustomActionCapability for MockWorker {
+        async fn post_custom_action(
+            &self,
+            _url: &str,
+            _body: &serde_json::Value,
+        ) -> Result<(), String> {
+            Ok(())
+        }
+    }
+
+    impl AcknowledgementCapability for MockWorker {
+        async fn take_acknowledgement(
+            &self,
+            _person_uuid: &PersonUuid,
+        ) -> Result<Option<String>, String> {
+            Ok(None)
+        }
+
+        async fn count_acknowledgements(&self, _person_uuid: &PersonUuid) -> Result<usize, String> {
+            Ok(0)
+        }
+
+        async fn add_acknowledgements(
+            &self,
+            _person_uuid: &PersonUuid,
+            _acknowledgements: &[String],
+        ) -> Result<(), String> {
+            Ok(())
+        }
+
+        async fn generate_acknowledgements(
+            &self,
+            _person_uuid: &PersonUuid,
+            _count: usize,
+        ) -> Result<String, String> {
+            Ok(String::new())
+        }
+    }
+
+    impl ExternalToolCapability for MockWorker {
+        async fn call_external_tool(
+            &self,
+            _tool: &ExternalTool,
+            _arguments: &serde_json::Value,
+        ) -> Result<String, String> {
+            Ok(String::new())
+        }
+
+        async fn record_external_tool_call(
+            &self,
+            _call: NewExternalToolCall,
+        ) -> Result<(), String> {
+            Ok(())
+        }
+    }
+
+    impl MaintenanceCapability for MockWorker {
+        async fn reap_stale_jobs(&self, _started_before: DateTime<Utc>) -> Result<u64, String> {
+            Ok(0)
+        }
+
+        async fn roll_up_llm_usage(&self, _before: DateTime<Utc>) -> Result<u64, String> {
+            Ok(0)
+        }
+
+        async fn prune_logs(&self, _before: DateTime<Utc>) -> Result<PrunedRows, String> {
+            Ok(PrunedRows::default())
+        }
+
+        async fn remove_duplicate_memories(&self) -> Result<u64, String> {
+            Ok(0)
+        }
+
+        async fn get_recent_memories(
+            &self,
+            _since: DateTime<Utc>,
+        ) -> Result<Vec<RecentMemories>, String> {
+            Ok(vec![])
+        }
+
+        async fn get_scenes_changed_since_snapshot(&self) -> Result<Vec<SceneUuid>, String> {
+            Ok(vec![])
+        }
+
+        async fn refresh_scene_snapshot(&self, _scene_uuid: &SceneUuid) -> Result<(), String> {
+            Ok(())
+        }
+
+        async fn get_latest_maintenance_report(&self) -> Result<Option<MaintenanceReport>, String> {
+            Ok(None)
+        }
+    }
+
+    impl TopicCapability for MockWorker {
+        async fn get_untagged_scenes(&self) -> Result<Vec<UntaggedScene>, String> {
+            Ok(vec![])
+        }
+
+        async fn record_scene_topics(
+            &self,
+            _scene_uuid: &SceneUuid,
+            _topics: &[TopicCount],
+            _tagged_through: DateTime<Utc>,
+        ) -> Result<(), String> {
+            Ok(())
+        }
+
+        async fn get_trending_topics(
+            &self,
+            _since: Option<DateTime<Utc>>,
+            _limit: i64,
+        ) -> Result<Vec<TrendingTopic>, String> {
+            Ok(vec![])
+        }
+
+        async fn get_topic_scenes(&self, _topic: &Topic) -> Result<Vec<TopicScene>, String> {
+            Ok(vec![])
+        }
+    }
+
+    impl LlmBatchCapability for MockWorker {
+        async fn submit_llm_batch(&self, _requests: Vec<BatchRequest>) -> Result<String, String> {
+            Ok("batch_test".to_string())
+        }
+
+        async fn refresh_llm_batch(&self, batch_id: &str) -> Result<Batch, String> {
+            Ok(Batch {
+                id: batch_id.to_string(),
+                status: BatchStatus::InProgress,
+                output_file_id: None,
+                error_file_id: None,
+            })
+        }
+
+        async fn get_llm_batch_results(&self, _file_id: &str) -> Result<Vec<BatchResult>, String> {
+            Ok(vec![])
+        }
     }
 
     #[tokio::test]
     async fn returns_ok_when_no_job_available() {
         let mock = MockWorker::empty();
-        let res = run_next_job(mock.clone(), RandomSeed::from_u64(0), 0).await;
+        let res = run_next_job(
+            mock.clone(),
+            "test runner",
+            RandomSeed::from_u64(0),
+            0,
+            &CancellationToken::new(),
+        )
+        .await;
         assert!(res.is_ok());
     }
 
@@ -1065,9 +2606,156 @@ mod tests {
             kind: JobKind::Ping,
         };
         let mock = MockWorker::with_next_job(popped);
-        let res = run_next_job(mock.clone(), RandomSeed::from_u64(0), 0).await;
+        let res = run_next_job(
+            mock.clone(),
+            "test runner",
+            RandomSeed::from_u64(0),
+            0,
+            &CancellationToken::new(),
+        )
+        .await;
         assert!(res.is_ok());
         let st = mock.state.lock().await;
         assert!(st.finished_jobs.contains(&job_uuid));
+        assert_eq!(
+            st.job_events,
+            vec![JobEventKind::PickedUp, JobEventKind::Finished]
+        );
+    }
+
+    #[tokio::test]
+    async fn resets_job_instead_of_running_it_when_cancelled() {
+        let job_uuid = JobUuid::test_id(0);
+        let popped = PoppedJob {
+            uuid: job_uuid.clone(),
+            kind: JobKind::Ping,
+        };
+        let mock = MockWorker::with_next_job(popped);
+        let cancel = CancellationToken::new();
+        cancel.cancel();
+        let res = run_next_job(
+            mock.clone(),
+            "test runner",
+            RandomSeed::from_u64(0),
+            0,
+            &cancel,
+        )
+        .await;
+        assert!(res.is_ok());
+        let st = mock.state.lock().await;
+        assert!(st.reset_jobs.contains(&job_uuid));
+        assert!(!st.finished_jobs.contains(&job_uuid));
+        assert_eq!(
+            st.job_events,
+            vec![JobEventKind::PickedUp, JobEventKind::Cancelled]
+        );
+    }
+
+    #[tokio::test]
+    async fn chaos_run_loses_no_jobs_and_failed_jobs_recover_on_retry() {
+        let mock = MockWorker::empty();
+        let contents = (0..20)
+            .map(|index| format!("message {}", index))
+            .collect::<Vec<String>>();
+        {
+            let mut st = mock.state.lock().await;
+            for (index, content) in contents.iter().enumerate() {
+                st.jobs.push(PoppedJob {
+                    uuid: JobUuid::test_id(index as u64),
+                    kind: JobKind::SendMessageToScene(SendMessageToSceneJob {
+                        sender: MessageSender::RealWorldUser,
+                        scene_uuid: SceneUuid::new(),
+                        content: content.clone(),
+                        random_seed: RandomSeed::from_u64(index as u64),
+                    }),
+                });
+            }
+        }
+
+        let chaos = Arc::new(FaultInjector::new(0.5, 42));
+        let metered = MeteredWorker::new(mock.clone(), Arc::new(CapabilityMetrics::new()))
+            .with_chaos(chaos.clone());
+        for _ in 0..contents.len() {
+            let res = run_next_job(
+                metered.clone(),
+                "test runner",
+                RandomSeed::from_u64(0),
+                0,
+                &CancellationToken::new(),
+            )
+            .await;
+            assert!(res.is_ok());
+        }
+
+        // Every job either finished or is waiting in the failed jobs
+        let failed = {
+            let st = mock.state.lock().await;
+            assert_eq!(
+                st.finished_jobs.len() + st.failed_jobs.len(),
+                contents.len()
+            );
+            st.failed_jobs.clone()
+        };
+        assert!(chaos.injected() > 0);
+        assert!(!failed.is_empty());
+
+        // Retrying the failed jobs without faults delivers every message
+        {
+            let mut st = mock.state.lock().await;
+            for job_uuid in failed.iter() {
+                let Some(index) = job_uuid.to_test_id() else {
+                    panic!("Expected a test job uuid");
+                };
+                st.jobs.push(PoppedJob {
+                    uuid: job_uuid.clone(),
+                    kind: JobKind::SendMessageToScene(SendMessageToSceneJob {
+                        sender: MessageSender::RealWorldUser,
+                        scene_uuid: SceneUuid::new(),
+                        content: contents[index as usize].clone(),
+                        random_seed: RandomSeed::from_u64(index),
+                    }),
+                });
+            }
+        }
+        for _ in 0..failed.len() {
+            let res = run_next_job(
+                mock.clone(),
+                "test runner",
+                RandomSeed::from_u64(0),
+                0,
+                &CancellationToken::new(),
+            )
+            .await;
+            assert!(res.is_ok());
+        }
+
+        let st = mock.state.lock().await;
+        assert_eq!(st.finished_jobs.len(), contents.len());
+        for content in contents.iter() {
+            assert!(st.sent_messages.contains(content), "{} was lost", content);
+        }
+    }
+
+    #[test]
+    fn active_clock_follows_speed_pauses_and_skips() {
+        let start = Instant::now();
+        let at = |secs: u64| start + Duration::from_secs(secs);
+        let mut clock = ActiveClock::new(1_000, start);
+
+        assert_eq!(clock.ms_at(at(2)), 3_000);
+
+        // Only the time after the change runs faster
+        clock.set_time_factor(60.0, at(2));
+        assert_eq!(clock.ms_at(at(3)), 63_000);
+
+        clock.set_running(false, at(3));
+        assert_eq!(clock.ms_at(at(10)), 63_000);
+
+        clock.set_running(true, at(10));
+        clock.skip_to(500_000, at(10));
+        assert_eq!(clock.ms_at(at(11)), 560_000);
+
+        clock.skip_to(0, at(11));
+        assert_eq!(clock.ms_at(at(11)), 560_000);
     }
 }
@@ -1,16 +1,23 @@
 #![allow(clippy::match_like_matches_macro)]
 
 pub mod admin_ui;
+pub mod api;
 pub mod capability;
+pub mod capability_metrics;
+#[cfg(feature = "client")]
+pub mod client;
 pub mod db;
 pub mod domain;
 pub mod job_runner;
 pub mod migrations;
 pub mod nice_display;
+pub mod offload;
 pub mod open_ai;
 pub mod open_ai_key;
 pub mod person_actions;
 pub mod tasks;
 pub mod temporary_event_cutoff;
 pub mod text_utils;
+pub mod time_display;
 pub mod worker;
+pub mod world_language;
@@ -1,24 +1,44 @@
 #![allow(clippy::match_like_matches_macro)]
 
 mod admin_ui;
+mod api;
 mod capability;
+mod capability_metrics;
 mod db;
 mod domain;
 mod job_runner;
 mod migrations;
 mod nice_display;
+mod offload;
 mod open_ai;
 mod open_ai_key;
 mod person_actions;
 mod tasks;
 mod temporary_event_cutoff;
 mod text_utils;
+mod time_display;
 mod worker;
+mod world_language;
 
-use crate::nice_display::NiceDisplay;
+use crate::nice_display::{with_context, NiceDisplay};
+use crate::tasks::backfill_embeddings;
+use crate::tasks::doctor;
+use crate::tasks::export_autobiography;
+use crate::tasks::export_training_data;
+use crate::tasks::fine_tune_persona;
+use crate::tasks::generate_cast;
+use crate::tasks::import_transcript;
+use crate::tasks::items;
+use crate::tasks::kickoff_scene;
+use crate::tasks::persons;
+use crate::tasks::run_report;
+use crate::tasks::smoke_test;
+use crate::tasks::start_run;
 use crate::tasks::summarize_memories_v2;
 use crate::tasks::summarize_person_identities;
-use clap::Parser;
+use crate::tasks::tail;
+use crate::tasks::tenant;
+use clap::{Parser, Subcommand};
 
 #[derive(Debug, Parser, Clone)]
 #[clap(
@@ -26,24 +46,169 @@ use clap::Parser;
     version = "0.1",
     about = "Commands for Arizona2"
 )]
+struct Cli {
+    /// Use the named world, which lives in its own `arizona2_<world>` database.
+    /// Overrides DATABASE_WORLD.
+    #[clap(long, global = true)]
+    world: Option<String>,
+    #[clap(subcommand)]
+    cmd: Cmd,
+}
+
+#[derive(Debug, Subcommand, Clone)]
 enum Cmd {
-    NewMigration { migration_name: String },
+    NewMigration {
+        migration_name: String,
+    },
     RunMigrations,
     RunTestMigrations,
     AdminUi,
     RunJobRunner,
-    SummarizePersonIdentities,
+    /// Serve the json api, e.g. scene timelines for external renderers.
+    ServeApi {
+        #[clap(long, default_value = "127.0.0.1")]
+        host: String,
+        #[clap(long, default_value_t = 8080)]
+        port: u16,
+    },
+    SummarizePersonIdentities {
+        /// Send the summaries through the cheaper, slower Batch API
+        #[clap(long)]
+        batch: bool,
+    },
     SummarizeMemoriesV2,
+    /// Re-embed memories whose embedding is missing, all zeros or the wrong
+    /// size, so memory search can find them again.
+    BackfillEmbeddings {
+        /// How many memories to fetch and embed between progress lines
+        #[clap(long, default_value_t = backfill_embeddings::DEFAULT_BATCH_SIZE)]
+        batch_size: i64,
+        /// Only count the memories that need it
+        #[clap(long)]
+        dry_run: bool,
+    },
+    ExportTrainingData {
+        output_path: String,
+    },
+    /// Write a person's life story from their memories, identities and
+    /// scenes, a chapter at a time, to a markdown file.
+    ExportAutobiography {
+        person_name: String,
+        /// Defaults to <name>-autobiography.md
+        #[clap(long)]
+        output: Option<String>,
+    },
+    FineTunePersona {
+        training_file_path: String,
+        #[clap(long)]
+        person_name: Option<String>,
+    },
+    /// Generate a group of related people from a one-line scenario concept.
+    GenerateCast {
+        concept: String,
+        #[clap(long, default_value_t = 4)]
+        count: usize,
+    },
+    /// Send an opener into a scene and line up every participant's first
+    /// reaction, a few seconds apart.
+    KickoffScene {
+        scene: String,
+        opener: String,
+        #[clap(long, default_value_t = 20)]
+        stagger_secs: u64,
+    },
+    /// Start a new simulation run, optionally with a dollar budget for its
+    /// completions. The job runner pauses once the budget is spent.
+    StartRun {
+        #[clap(long)]
+        budget_usd: Option<String>,
+    },
+    /// Write a shareable report of the current run: cast, scenes, message
+    /// counts, cost, bookmarks and scene summaries. Paths ending in `.html`
+    /// get HTML, anything else Markdown.
+    RunReport {
+        output_path: String,
+    },
+    /// Manage the tenants whose worlds the api serves, and their api tokens.
+    Tenant {
+        #[clap(subcommand)]
+        cmd: tenant::Command,
+    },
+    /// Check the database, migrations, OpenAI key and settings before a
+    /// long run.
+    Doctor,
+    /// List, tag, hibernate or wake the persons matching a filter.
+    Persons {
+        #[clap(subcommand)]
+        cmd: persons::Command,
+    },
+    /// Add items to persons and scenes, or list what they hold.
+    Items {
+        #[clap(subcommand)]
+        cmd: items::Command,
+    },
+    /// Follow a scene's timeline, printing each new item as it happens.
+    Tail {
+        scene: String,
+        /// How many of the latest items to print before following
+        #[clap(long, default_value_t = 10)]
+        lines: i64,
+        /// Print each item as a line of json
+        #[clap(long)]
+        json: bool,
+    },
+    /// Bootstrap a world from existing writing: read a screenplay or chat
+    /// log, make a person for each speaker, replay the conversation into a
+    /// scene and have everyone remember it.
+    ImportTranscript {
+        transcript_path: String,
+        /// The scene to replay into. Made if there is none by this name
+        #[clap(long)]
+        scene: String,
+        /// Speak as an existing person instead, like HOLMES="Sherlock Holmes".
+        /// Can be given more than once
+        #[clap(long = "map")]
+        mappings: Vec<String>,
+        /// Skip asking each person what they remember, which costs a
+        /// completion per person for every 40 lines
+        #[clap(long)]
+        no_memories: bool,
+    },
+    /// Check that the whole pipeline is wired up: in a new database, send a
+    /// message to a person in a scene, run the jobs with a stub standing in
+    /// for OpenAI, and fail unless the person replies.
+    SmokeTest {
+        /// Leave the smoke test database behind to look through afterwards
+        #[clap(long)]
+        keep_database: bool,
+    },
 }
 
 enum Error {
     NewMigration(migrations::NewMigrationError),
     RunMigrations(migrations::RunError),
     EnvVars(dotenv::Error),
+    World(String),
     AdminUi(admin_ui::Error),
     JobRunner(job_runner::Error),
+    Api(api::Error),
     SummarizePersonIdentities(summarize_person_identities::Error),
     SummarizeMemoriesV2(summarize_memories_v2::Error),
+    BackfillEmbeddings(backfill_embeddings::Error),
+    ExportTrainingData(export_training_data::Error),
+    ExportAutobiography(export_autobiography::Error),
+    FineTunePersona(fine_tune_persona::Error),
+    GenerateCast(generate_cast::Error),
+    KickoffScene(kickoff_scene::Error),
+    StartRun(start_run::Error),
+    RunReport(run_report::Error),
+    Tenant(tenant::Error),
+    Doctor(doctor::Error),
+    Persons(persons::Error),
+    Items(items::Error),
+    Tail(tail::Error),
+    ImportTranscript(import_transcript::Error),
+    SmokeTest(smoke_test::Error),
 }
 
 impl NiceDisplay for Error {
@@ -51,13 +216,28 @@ impl NiceDisplay for Error {
         match self {
             Error::NewMigration(err) => err.message(),
             Error::RunMigrations(err) => err.message(),
-            Error::EnvVars(err) => {
-                format!("Error loading environment variables: {}", err)
-            }
+            Error::EnvVars(err) => with_context("Error loading environment variables", err),
+            Error::World(err) => with_context("Invalid world", err),
             Error::AdminUi(err) => err.message(),
             Error::JobRunner(err) => err.message(),
+            Error::Api(err) => err.message(),
             Error::SummarizePersonIdentities(err) => err.message(),
             Error::SummarizeMemoriesV2(err) => err.message(),
+            Error::BackfillEmbeddings(err) => err.message(),
+            Error::ExportTrainingData(err) => err.message(),
+            Error::ExportAutobiography(err) => err.message(),
+            Error::FineTunePersona(err) => err.message(),
+            Error::GenerateCast(err) => err.message(),
+            Error::KickoffScene(err) => err.message(),
+            Error::StartRun(err) => err.message(),
+            Error::RunReport(err) => err.message(),
+            Error::Tenant(err) => err.message(),
+            Error::Doctor(err) => err.message(),
+            Error::Persons(err) => err.message(),
+            Error::Items(err) => err.message(),
+            Error::Tail(err) => err.message(),
+            Error::ImportTranscript(err) => err.message(),
+            Error::SmokeTest(err) => err.message(),
         }
     }
 }
@@ -70,8 +250,24 @@ impl Cmd {
             Cmd::RunTestMigrations => "test-migrations",
             Cmd::AdminUi => "admin-ui",
             Cmd::RunJobRunner => "job-runner",
-            Cmd::SummarizePersonIdentities => "summarize-person-identities",
+            Cmd::ServeApi { .. } => "api",
+            Cmd::SummarizePersonIdentities { .. } => "summarize-person-identities",
             Cmd::SummarizeMemoriesV2 => "summarize-memories-v2",
+            Cmd::BackfillEmbeddings { .. } => "backfill-embeddings",
+            Cmd::ExportTrainingData { .. } => "export-training-data",
+            Cmd::ExportAutobiography { .. } => "export-autobiography",
+            Cmd::FineTunePersona { .. } => "fine-tune-persona",
+            Cmd::GenerateCast { .. } => "generate-cast",
+            Cmd::KickoffScene { .. } => "kickoff-scene",
+            Cmd::StartRun { .. } => "start-run",
+            Cmd::RunReport { .. } => "run-report",
+            Cmd::Tenant { .. } => "tenant",
+            Cmd::Doctor => "doctor",
+            Cmd::Persons { .. } => "persons",
+            Cmd::Items { .. } => "items",
+            Cmd::Tail { .. } => "tail",
+            Cmd::ImportTranscript { .. } => "import-transcript",
+            Cmd::SmokeTest { .. } => "smoke-test",
         }
     }
 }
@@ -82,8 +278,8 @@ async fn main() -> Result<(), String> {
     use tracing_subscriber::util::SubscriberInitExt;
 
     // Parse command first to determine log file name
-    let cmd = Cmd::parse();
-    let log_file_name = format!("arizona2-{}.log", cmd.log_file_name());
+    let cli = Cli::parse();
+    let log_file_name = format!("arizona2-{}.log", cli.cmd.log_file_name());
 
     // Create logs directory if it doesn't exist
     std::fs::create_dir_all("logs").ok();
@@ -118,15 +314,20 @@ async fn main() -> Result<(), String> {
         .with(console_layer)
         .init();
 
-    nice_main(cmd)
+    nice_main(cli)
         .await
         .map_err(|err| err.to_nice_error().to_string())
 }
 
-async fn nice_main(cmd: Cmd) -> Result<(), Error> {
+async fn nice_main(cli: Cli) -> Result<(), Error> {
     dotenv::dotenv().map_err(Error::EnvVars)?;
 
-    match cmd {
+    if let Some(world) = cli.world {
+        let world = db::WorldName::parse(world.as_str()).map_err(Error::World)?;
+        std::env::set_var(db::WORLD_ENV_VAR, world.to_string());
+    }
+
+    match cli.cmd {
         Cmd::NewMigration { migration_name } => migrations::new(migration_name)
             .await
             .map_err(Error::NewMigration),
@@ -134,11 +335,67 @@ async fn nice_main(cmd: Cmd) -> Result<(), Error> {
         Cmd::RunTestMigrations => migrations::run_test().await.map_err(Error::RunMigrations),
         Cmd::AdminUi => admin_ui::run().await.map_err(Error::AdminUi),
         Cmd::RunJobRunner => job_runner::run().await.map_err(Error::JobRunner),
-        Cmd::SummarizePersonIdentities => tasks::summarize_person_identities::run()
+        Cmd::ServeApi { host, port } => api::run(host, port).await.map_err(Error::Api),
+        Cmd::SummarizePersonIdentities { batch } => tasks::summarize_person_identities::run(batch)
             .await
             .map_err(Error::SummarizePersonIdentities),
         Cmd::SummarizeMemoriesV2 => tasks::summarize_memories_v2::run()
             .await
             .map_err(Error::SummarizeMemoriesV2),
+        Cmd::BackfillEmbeddings {
+            batch_size,
+            dry_run,
+        } => tasks::backfill_embeddings::run(batch_size, dry_run)
+            .await
+            .map_err(Error::BackfillEmbeddings),
+        Cmd::ExportTrainingData { output_path } => tasks::export_training_data::run(output_path)
+            .await
+            .map_err(Error::ExportTrainingData),
+        Cmd::ExportAutobiography {
+            person_name,
+            output,
+        } => tasks::export_autobiography::run(person_name, output)
+            .await
+            .map_err(Error::ExportAutobiography),
+        Cmd::FineTunePersona {
+            training_file_path,
+            person_name,
+        } => tasks::fine_tune_persona::run(training_file_path, person_name)
+            .await
+            .map_err(Error::FineTunePersona),
+        Cmd::GenerateCast { concept, count } => tasks::generate_cast::run(concept, count)
+            .await
+            .map_err(Error::GenerateCast),
+        Cmd::KickoffScene {
+            scene,
+            opener,
+            stagger_secs,
+        } => tasks::kickoff_scene::run(scene, opener, stagger_secs)
+            .await
+            .map_err(Error::KickoffScene),
+        Cmd::StartRun { budget_usd } => tasks::start_run::run(budget_usd)
+            .await
+            .map_err(Error::StartRun),
+        Cmd::RunReport { output_path } => {
+            run_report::run(output_path).await.map_err(Error::RunReport)
+        }
+        Cmd::Tenant { cmd } => tenant::run(cmd).await.map_err(Error::Tenant),
+        Cmd::Doctor => doctor::run().await.map_err(Error::Doctor),
+        Cmd::Persons { cmd } => persons::run(cmd).await.map_err(Error::Persons),
+        Cmd::Items { cmd } => items::run(cmd).await.map_err(Error::Items),
+        Cmd::Tail { scene, lines, json } => {
+            tail::run(scene, lines, json).await.map_err(Error::Tail)
+        }
+        Cmd::ImportTranscript {
+            transcript_path,
+            scene,
+            mappings,
+            no_memories,
+        } => import_transcript::run(transcript_path, scene, mappings, no_memories)
+            .await
+            .map_err(Error::ImportTranscript),
+        Cmd::SmokeTest { keep_database } => smoke_test::run(keep_database)
+            .await
+            .map_err(Error::SmokeTest),
     }
 }
@@ -1,5 +1,5 @@
 use crate::db;
-use crate::nice_display::NiceDisplay;
+use crate::nice_display::{with_context, NiceDisplay};
 use chrono::NaiveDateTime;
 use std::{fs, io};
 use tokio_postgres::NoTls;
@@ -7,6 +7,16 @@ use tokio_postgres::NoTls;
 const SEPARATOR: &str = "____";
 const DATE_FORMAT: &str = "%Y-%m-%d-%H:%M:%S";
 
+/// Every migration that has been run against a database is recorded here,
+/// so `doctor` can tell which ones are still pending.
+const CREATE_SCHEMA_MIGRATION_TABLE: &str = r#"
+    CREATE TABLE IF NOT EXISTS schema_migration
+    (
+        name       TEXT PRIMARY KEY,
+        applied_at TIMESTAMPTZ NOT NULL DEFAULT now()
+    );
+"#;
+
 struct Migration {
     name: String,
     timestamp: i64,
@@ -21,7 +31,9 @@ pub enum RunError {
     DbConfig(db::ConfigError),
     ReadingMigrationFile(io::Error),
     ExecutingMigration(tokio_postgres::Error),
+    RecordingMigration(tokio_postgres::Error),
     ConnectingToDb(tokio_postgres::Error),
+    MissingDatabase(String),
 }
 
 pub enum GetMigrationsError {
@@ -38,7 +50,9 @@ pub enum GetMigrationsError {
 impl NiceDisplay for NewMigrationError {
     fn message(&self) -> String {
         match self {
-            NewMigrationError::WritingFile(err) => format!("Error writing migration file: {}", err),
+            NewMigrationError::WritingFile(err) => {
+                with_context("Error writing migration file", err)
+            }
         }
     }
 }
@@ -47,11 +61,15 @@ impl NiceDisplay for RunError {
         match self {
             RunError::GetMigrations(err) => err.message(),
             RunError::DbConfig(err) => err.message(),
-            RunError::ReadingMigrationFile(err) => format!("Error reading migration file: {}", err),
-            RunError::ExecutingMigration(err) => format!("Error executing migration: {}", err),
-            RunError::ConnectingToDb(err) => {
-                format!("Error connecting to database: {}", err)
+            RunError::ReadingMigrationFile(err) => {
+                with_context("Error reading migration file", err)
             }
+            RunError::ExecutingMigration(err) => with_context("Error executing migration", err),
+            RunError::RecordingMigration(err) => {
+                with_context("Error recording that a migration ran", err)
+            }
+            RunError::ConnectingToDb(err) => with_context("Error connecting to database", err),
+            RunError::MissingDatabase(message) => message.clone(),
         }
     }
 }
@@ -60,11 +78,12 @@ impl NiceDisplay for GetMigrationsError {
     fn message(&self) -> String {
         match self {
             GetMigrationsError::GettingMigrations(err) => {
-                format!("Error getting migrations: {}", err)
-            }
-            GetMigrationsError::ParsingDateFromFileName { file_name, err } => {
-                format!("Error parsing date from file name '{}': {}", file_name, err)
+                with_context("Error getting migrations", err)
             }
+            GetMigrationsError::ParsingDateFromFileName { file_name, err } => with_context(
+                format!("Error parsing date from file name '{}'", file_name),
+                err,
+            ),
             GetMigrationsError::SplittingFileName { file_name } => {
                 format!("Error splitting file name '{}'", file_name)
             }
@@ -92,21 +111,18 @@ COMMIT;"#
 }
 
 pub async fn run() -> Result<(), RunError> {
-    run_for_database("arizona2").await
+    let config = db::Config::load().await.map_err(RunError::DbConfig)?;
+    let database_name = config.database_name();
+    run_for_database(&config, database_name.as_str()).await
 }
 
 pub async fn run_test() -> Result<(), RunError> {
-    run_for_database("arizona2_test").await
-}
-
-async fn run_for_database(database_name: &str) -> Result<(), RunError> {
-    // Get migrations
-    let migrations: Vec<Migration> = get_migrations().map_err(RunError::GetMigrations)?;
-    let migrations_len = migrations.len();
-
-    // Connect to the database
     let config = db::Config::load().await.map_err(RunError::DbConfig)?;
+    let database_name = config.test_database_name();
+    run_for_database(&config, database_name.as_str()).await
+}
 
+async fn run_for_database(config: &db::Config, database_name: &str) -> Result<(), RunError> {
     println!(
         "Should I run migrations against database '{}' at host {} with password {}? (Y/n): ",
         database_name, config.host, config.password
@@ -123,15 +139,42 @@ async fn run_for_database(database_name: &str) -> Result<(), RunError> {
         return Ok(());
     }
 
+    let ran_at_least_one_migration = apply(config, database_name, true).await? > 0;
+
+    let finish_msg = if ran_at_least_one_migration {
+        "Done!"
+    } else {
+        "You are already up to date, no migrations run!"
+    };
+
+    println!("{}", finish_msg);
+
+    Ok(())
+}
+
+/// Runs every migration against the database without asking first, and
+/// returns how many ran. `smoke-test` uses this on the database it just
+/// made.
+pub async fn apply(
+    config: &db::Config,
+    database_name: &str,
+    print_progress: bool,
+) -> Result<usize, RunError> {
+    let migrations: Vec<Migration> = get_migrations().map_err(RunError::GetMigrations)?;
+    let migrations_len = migrations.len();
+
     let (client, connection) = {
-        let connect_string = format!(
-            "host={} user={} password={} dbname={}",
-            config.host, config.user, config.password, database_name
-        );
+        let connect_string = config.key_value_string(database_name);
 
         tokio_postgres::connect(connect_string.as_str(), NoTls)
             .await
-            .map_err(RunError::ConnectingToDb)?
+            .map_err(|err| {
+                if db::is_missing_database_pg(&err) {
+                    RunError::MissingDatabase(config.missing_database_message(database_name))
+                } else {
+                    RunError::ConnectingToDb(err)
+                }
+            })?
     };
 
     tokio::spawn(async move {
@@ -140,8 +183,11 @@ async fn run_for_database(database_name: &str) -> Result<(), RunError> {
         }
     });
 
-    // Useful for the print statements below
-    let mut ran_at_least_one_migration = false;
+    client
+        .batch_execute(CREATE_SCHEMA_MIGRATION_TABLE)
+        .await
+        .map_err(RunError::RecordingMigration)?;
+
     for (index, migration) in migrations.into_iter().enumerate() {
         let human_migration_name = {
             let without_number = migration
@@ -157,12 +203,14 @@ async fn run_for_database(database_name: &str) -> Result<(), RunError> {
             without_number[..name_len - 4].to_string()
         };
 
-        println!(
-            "Running {}/{}, {}",
-            index + 1,
-            migrations_len,
-            human_migration_name
-        );
+        if print_progress {
+            println!(
+                "Running {}/{}, {}",
+                index + 1,
+                migrations_len,
+                human_migration_name
+            );
+        }
 
         let migration_file_path = format!("./db/migrations/{}", migration.name);
 
@@ -174,18 +222,24 @@ async fn run_for_database(database_name: &str) -> Result<(), RunError> {
             .await
             .map_err(RunError::ExecutingMigration)?;
 
-        ran_at_least_one_migration = true;
+        client
+            .execute(
+                "INSERT INTO schema_migration (name) VALUES ($1) ON CONFLICT (name) DO NOTHING",
+                &[&migration.name],
+            )
+            .await
+            .map_err(RunError::RecordingMigration)?;
     }
 
-    let finish_msg = if ran_at_least_one_migration {
-        "Done!"
-    } else {
-        "You are already up to date, no migrations run!"
-    };
-
-    println!("{}", finish_msg);
+    Ok(migrations_len)
+}
 
-    Ok(())
+/// The file names of every migration in `db/migrations`, oldest first.
+pub fn migration_names() -> Result<Vec<String>, GetMigrationsError> {
+    Ok(get_migrations()?
+        .into_iter()
+        .map(|migration| migration.name)
+        .collect())
 }
 
 fn get_migrations() -> Result<Vec<Migration>, GetMigrationsError> {
@@ -1,5 +1,8 @@
+use serde_json::{json, Value};
 use std::fmt::Display;
 
+const INDENT: &str = "  ";
+
 pub struct NiceError {
     content: String,
 }
@@ -18,3 +21,99 @@ pub trait NiceDisplay {
         }
     }
 }
+
+/// A stable name for what kind of error this is, for api clients and webhook
+/// receivers to branch on instead of parsing the message. Once a code has
+/// been sent out, leave it as it is.
+pub trait ErrorCode {
+    fn code(&self) -> &'static str;
+}
+
+/// How errors are written in api responses and webhook payloads.
+pub fn to_json<E: NiceDisplay + ErrorCode>(error: &E) -> Value {
+    json!({
+        "code": error.code(),
+        "message": error.message(),
+    })
+}
+
+/// Puts what was being done on its own line, and what went wrong indented
+/// under it. Causes that have context of their own nest one level further,
+/// so a whole chain reads top down:
+///
+/// ```text
+/// I failed to run job 0190…
+///   Error processing message job
+///     Failed to get message
+///       connection refused
+/// ```
+pub fn with_context(context: impl Display, cause: impl Display) -> String {
+    let cause = cause.to_string();
+    let mut message = context.to_string();
+
+    for line in cause.lines() {
+        message.push('\n');
+        if !line.is_empty() {
+            message.push_str(INDENT);
+            message.push_str(line);
+        }
+    }
+
+    message
+}
+
+/// `with_context` for a cause that is itself a `NiceDisplay` error.
+pub fn nest(context: impl Display, cause: &impl NiceDisplay) -> String {
+    with_context(context, cause.message())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Inner;
+
+    impl NiceDisplay for Inner {
+        fn message(&self) -> String {
+            with_context("Failed to get message", "connection refused")
+        }
+    }
+
+    #[test]
+    fn test_nested_causes_indent_one_level_per_context() {
+        let message = with_context(
+            "I failed to run job 1",
+            nest("Error processing job", &Inner),
+        );
+
+        assert_eq!(
+            message,
+            "I failed to run job 1\n  Error processing job\n    Failed to get message\n      connection refused"
+        );
+    }
+
+    impl ErrorCode for Inner {
+        fn code(&self) -> &'static str {
+            "get message"
+        }
+    }
+
+    #[test]
+    fn test_to_json_has_the_code_and_the_whole_message() {
+        assert_eq!(
+            to_json(&Inner),
+            json!({
+                "code": "get message",
+                "message": "Failed to get message\n  connection refused",
+            })
+        );
+    }
+
+    #[test]
+    fn test_with_context_keeps_blank_lines_unindented() {
+        assert_eq!(
+            with_context("Bad response", "line one\n\nline three"),
+            "Bad response\n  line one\n\n  line three"
+        );
+    }
+}
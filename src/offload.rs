@@ -0,0 +1,151 @@
+//! Decoding big responses and assembling long timelines can hold a thread
+//! for many milliseconds. Run on the async runtime, that stalls every other
+//! task sharing the thread, like the admin ui's polls and the api's requests,
+//! so those steps go to tokio's blocking pool instead.
+
+/// Below this, handing text to another thread costs more than decoding it
+/// in place. An embedding response is about twice this.
+pub const INLINE_TEXT_BYTES: usize = 16 * 1024;
+
+/// Below this many rows, a list is assembled in place.
+pub const INLINE_ITEMS: usize = 64;
+
+pub fn is_heavy_text(text: &str) -> bool {
+    text.len() >= INLINE_TEXT_BYTES
+}
+
+pub fn is_heavy_list(len: usize) -> bool {
+    len >= INLINE_ITEMS
+}
+
+/// Runs `work` on the blocking pool and waits for it without holding up the
+/// runtime thread.
+pub async fn run<T, F>(work: F) -> Result<T, String>
+where
+    F: FnOnce() -> T + Send + 'static,
+    T: Send + 'static,
+{
+    tokio::task::spawn_blocking(work)
+        .await
+        .map_err(|err| format!("Error running work off the async runtime: {}", err))
+}
+
+/// Like `run`, but does light work in place.
+pub async fn run_if<T, F>(heavy: bool, work: F) -> Result<T, String>
+where
+    F: FnOnce() -> T + Send + 'static,
+    T: Send + 'static,
+{
+    if heavy {
+        run(work).await
+    } else {
+        Ok(work())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::scene_timeline::{TimelineItem, TimelinePage, TimelineSpeaker};
+    use chrono::{Duration as ChronoDuration, TimeZone, Utc};
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::{Arc, Mutex};
+    use std::time::{Duration, Instant};
+    use uuid::Uuid;
+
+    #[tokio::test]
+    async fn test_run_returns_the_work_and_reports_panics() {
+        assert_eq!(run(|| 2 + 2).await, Ok(4));
+        assert_eq!(run_if(false, || "inline").await, Ok("inline"));
+        assert!(run(|| -> i32 { panic!("boom") }).await.is_err());
+        assert!(is_heavy_text(&"x".repeat(INLINE_TEXT_BYTES)));
+        assert!(!is_heavy_list(INLINE_ITEMS - 1));
+    }
+
+    /// The json of a scene timeline with `count` messages.
+    fn large_scene_json(count: usize) -> String {
+        let start = Utc.with_ymd_and_hms(2026, 1, 1, 0, 0, 0).unwrap();
+        let items = (0..count)
+            .map(|index| TimelineItem::Message {
+                message_uuid: Uuid::from_u128(index as u128),
+                at: start + ChronoDuration::seconds(index as i64),
+                sender: TimelineSpeaker {
+                    person_uuid: Some(Uuid::from_u128(1)),
+                    name: "Hank".to_string(),
+                },
+                content: "Did anybody else hear that noise out back? ".repeat(4),
+                audience: "everyone".to_string(),
+            })
+            .collect();
+
+        let page = TimelinePage {
+            schema_version: 1,
+            scene_uuid: Uuid::from_u128(9),
+            items,
+            next_before: None,
+        };
+
+        serde_json::to_string(&page).unwrap()
+    }
+
+    /// The longest a task that wakes every millisecond waited while a large
+    /// scene was decoded, on a runtime with one thread like a busy ui has.
+    fn longest_stall(heavy: bool, json: String) -> Duration {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_time()
+            .build()
+            .unwrap();
+
+        runtime.block_on(async move {
+            let done = Arc::new(AtomicBool::new(false));
+            let longest = Arc::new(Mutex::new(Duration::ZERO));
+
+            let ticker = {
+                let done = done.clone();
+                let longest = longest.clone();
+                tokio::spawn(async move {
+                    let mut last = Instant::now();
+                    while !done.load(Ordering::Relaxed) {
+                        tokio::time::sleep(Duration::from_millis(1)).await;
+                        let gap = last.elapsed();
+                        last = Instant::now();
+                        let mut longest = longest.lock().unwrap();
+                        *longest = (*longest).max(gap);
+                    }
+                })
+            };
+
+            // Let the ticker start before the work does
+            tokio::time::sleep(Duration::from_millis(5)).await;
+
+            let page = run_if(heavy, move || serde_json::from_str::<TimelinePage>(&json))
+                .await
+                .unwrap()
+                .unwrap();
+            assert_eq!(page.items.len(), 20_000);
+
+            done.store(true, Ordering::Relaxed);
+            ticker.await.unwrap();
+
+            let longest = *longest.lock().unwrap();
+            longest
+        })
+    }
+
+    /// A benchmark rather than a check, since it depends on the machine. Run
+    /// it with `cargo test --release offload -- --ignored --nocapture`.
+    #[test]
+    #[ignore]
+    fn test_offloading_a_large_scene_keeps_other_tasks_responsive() {
+        let json = large_scene_json(20_000);
+
+        let inline = longest_stall(false, json.clone());
+        let offloaded = longest_stall(true, json);
+
+        println!(
+            "Longest stall decoding a {} item scene: {:?} in place, {:?} offloaded",
+            20_000, inline, offloaded
+        );
+        assert!(offloaded < inline);
+    }
+}
@@ -1,8 +1,14 @@
+pub mod batch;
+pub mod client;
 pub mod completion;
 pub mod embedding;
+pub mod fine_tune;
 pub mod history;
+pub mod llm_call;
 pub mod message;
 pub mod model;
+pub mod moderation;
+pub mod provider;
 pub mod role;
 pub mod tool;
 pub mod tool_call;
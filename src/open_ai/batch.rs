@@ -0,0 +1,439 @@
+use crate::nice_display::{with_context, NiceDisplay};
+use crate::open_ai::client::OpenAiClient;
+use crate::open_ai::completion::Response;
+use crate::open_ai::fine_tune::multipart_body;
+use crate::open_ai_key::OpenAiKey;
+use uuid::Uuid;
+
+const FILES_URL: &str = "https://api.openai.com/v1/files";
+const BATCHES_URL: &str = "https://api.openai.com/v1/batches";
+/// The endpoint every request in a batch is sent to, relative to the api root.
+const COMPLETIONS_ENDPOINT: &str = "/v1/chat/completions";
+/// The only window OpenAI offers. Most batches finish well before it.
+const COMPLETION_WINDOW: &str = "24h";
+
+#[derive(Debug, Clone)]
+pub enum BatchError {
+    Request(String),
+    Response(String),
+    ResponseJsonDecode(String),
+}
+
+impl NiceDisplay for BatchError {
+    fn message(&self) -> String {
+        match self {
+            BatchError::Request(err) => {
+                with_context("I had trouble making a batch request to open ai", err)
+            }
+            BatchError::Response(err) => {
+                with_context("I had trouble with the batch response from open ai", err)
+            }
+            BatchError::ResponseJsonDecode(err) => with_context(
+                "I had trouble decoding the batch response from open ai",
+                err,
+            ),
+        }
+    }
+}
+
+/// One completion in a batch. OpenAI answers it with the same `custom_id`,
+/// which is all there is to match results back to what asked for them.
+#[derive(Debug, Clone)]
+pub struct BatchRequest {
+    pub custom_id: String,
+    pub body: serde_json::Value,
+}
+
+impl BatchRequest {
+    fn to_json(&self) -> serde_json::Value {
+        serde_json::json!({
+            "custom_id": self.custom_id,
+            "method": "POST",
+            "url": COMPLETIONS_ENDPOINT,
+            "body": self.body,
+        })
+    }
+}
+
+pub fn to_jsonl(requests: &[BatchRequest]) -> String {
+    requests
+        .iter()
+        .map(|request| format!("{}\n", request.to_json()))
+        .collect()
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BatchStatus {
+    Validating,
+    Failed,
+    InProgress,
+    Finalizing,
+    Completed,
+    Expired,
+    Cancelling,
+    Cancelled,
+}
+
+impl BatchStatus {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "validating" => Some(BatchStatus::Validating),
+            "failed" => Some(BatchStatus::Failed),
+            "in_progress" => Some(BatchStatus::InProgress),
+            "finalizing" => Some(BatchStatus::Finalizing),
+            "completed" => Some(BatchStatus::Completed),
+            "expired" => Some(BatchStatus::Expired),
+            "cancelling" => Some(BatchStatus::Cancelling),
+            "cancelled" => Some(BatchStatus::Cancelled),
+            _ => None,
+        }
+    }
+
+    pub fn to_name(&self) -> &'static str {
+        match self {
+            BatchStatus::Validating => "validating",
+            BatchStatus::Failed => "failed",
+            BatchStatus::InProgress => "in_progress",
+            BatchStatus::Finalizing => "finalizing",
+            BatchStatus::Completed => "completed",
+            BatchStatus::Expired => "expired",
+            BatchStatus::Cancelling => "cancelling",
+            BatchStatus::Cancelled => "cancelled",
+        }
+    }
+
+    pub fn is_terminal(&self) -> bool {
+        match self {
+            BatchStatus::Failed
+            | BatchStatus::Completed
+            | BatchStatus::Expired
+            | BatchStatus::Cancelled => true,
+            BatchStatus::Validating
+            | BatchStatus::InProgress
+            | BatchStatus::Finalizing
+            | BatchStatus::Cancelling => false,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Batch {
+    pub id: String,
+    pub status: BatchStatus,
+    /// Results for the requests that succeeded. Expired batches can have
+    /// one too, holding whatever finished in time.
+    pub output_file_id: Option<String>,
+    /// Results for the requests that failed.
+    pub error_file_id: Option<String>,
+}
+
+impl Batch {
+    fn from_json(json: &serde_json::Value) -> Result<Self, BatchError> {
+        let id = json
+            .get("id")
+            .and_then(|value| value.as_str())
+            .ok_or_else(|| BatchError::ResponseJsonDecode(format!("Missing batch id in {}", json)))?
+            .to_string();
+
+        let status_name = json
+            .get("status")
+            .and_then(|value| value.as_str())
+            .ok_or_else(|| {
+                BatchError::ResponseJsonDecode(format!("Missing batch status in {}", json))
+            })?;
+
+        let status = BatchStatus::from_name(status_name).ok_or_else(|| {
+            BatchError::ResponseJsonDecode(format!("Unknown batch status: {}", status_name))
+        })?;
+
+        let file_id = |field: &str| {
+            json.get(field)
+                .and_then(|value| value.as_str())
+                .map(|value| value.to_string())
+        };
+
+        Ok(Batch {
+            id,
+            status,
+            output_file_id: file_id("output_file_id"),
+            error_file_id: file_id("error_file_id"),
+        })
+    }
+}
+
+/// The answer to one request in a batch.
+pub struct BatchResult {
+    pub custom_id: String,
+    pub response: Result<Response, String>,
+}
+
+/// Reads a batch output or error file.
+pub fn parse_results(jsonl: &str) -> Result<Vec<BatchResult>, BatchError> {
+    jsonl
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            let json: serde_json::Value = serde_json::from_str(line)
+                .map_err(|err| BatchError::ResponseJsonDecode(err.to_string()))?;
+            parse_result(&json)
+        })
+        .collect()
+}
+
+fn parse_result(json: &serde_json::Value) -> Result<BatchResult, BatchError> {
+    let custom_id = json
+        .get("custom_id")
+        .and_then(|value| value.as_str())
+        .ok_or_else(|| BatchError::ResponseJsonDecode(format!("Missing custom_id in {}", json)))?
+        .to_string();
+
+    if let Some(error) = json.get("error").filter(|error| !error.is_null()) {
+        return Ok(BatchResult {
+            custom_id,
+            response: Err(error.to_string()),
+        });
+    }
+
+    let response = json
+        .get("response")
+        .ok_or_else(|| BatchError::ResponseJsonDecode(format!("Missing response in {}", json)))?;
+    let status_code = response
+        .get("status_code")
+        .and_then(|value| value.as_u64())
+        .unwrap_or(0);
+    let body = response
+        .get("body")
+        .cloned()
+        .unwrap_or(serde_json::Value::Null);
+
+    let response = if status_code == 200 {
+        Ok(Response::new(body))
+    } else {
+        Err(format!("open ai returned HTTP {}: {}", status_code, body))
+    };
+
+    Ok(BatchResult {
+        custom_id,
+        response,
+    })
+}
+
+/// Uploads the JSONL input of a batch and returns the OpenAI file id.
+pub async fn upload_batch_file(
+    open_ai_key: &OpenAiKey,
+    client: OpenAiClient,
+    jsonl: &str,
+) -> Result<String, BatchError> {
+    let boundary = format!("arizona2-{}", Uuid::now_v7().simple());
+    let body = multipart_body(boundary.as_str(), "batch", "batch.jsonl", jsonl);
+
+    let _permit = client
+        .acquire()
+        .await
+        .map_err(|err| BatchError::Request(err.message()))?;
+
+    let response = client
+        .http()
+        .post(FILES_URL)
+        .header(
+            "Content-Type",
+            format!("multipart/form-data; boundary={}", boundary),
+        )
+        .header("Authorization", open_ai_key.to_header())
+        .body(body)
+        .send()
+        .await
+        .map_err(|err| BatchError::Request(err.to_string()))?;
+
+    let json = response_json(response).await?;
+
+    json.get("id")
+        .and_then(|value| value.as_str())
+        .map(|value| value.to_string())
+        .ok_or_else(|| BatchError::ResponseJsonDecode(format!("Missing file id in {}", json)))
+}
+
+pub async fn create_batch(
+    open_ai_key: &OpenAiKey,
+    client: OpenAiClient,
+    input_file_id: &str,
+) -> Result<Batch, BatchError> {
+    let body = serde_json::json!({
+        "input_file_id": input_file_id,
+        "endpoint": COMPLETIONS_ENDPOINT,
+        "completion_window": COMPLETION_WINDOW,
+    });
+
+    let _permit = client
+        .acquire()
+        .await
+        .map_err(|err| BatchError::Request(err.message()))?;
+
+    let response = client
+        .http()
+        .post(BATCHES_URL)
+        .header("Content-Type", "application/json")
+        .header("Authorization", open_ai_key.to_header())
+        .json(&body)
+        .send()
+        .await
+        .map_err(|err| BatchError::Request(err.to_string()))?;
+
+    let json = response_json(response).await?;
+
+    Batch::from_json(&json)
+}
+
+pub async fn get_batch(
+    open_ai_key: &OpenAiKey,
+    client: OpenAiClient,
+    batch_id: &str,
+) -> Result<Batch, BatchError> {
+    let _permit = client
+        .acquire()
+        .await
+        .map_err(|err| BatchError::Request(err.message()))?;
+
+    let response = client
+        .http()
+        .get(format!("{}/{}", BATCHES_URL, batch_id))
+        .header("Authorization", open_ai_key.to_header())
+        .send()
+        .await
+        .map_err(|err| BatchError::Request(err.to_string()))?;
+
+    let json = response_json(response).await?;
+
+    Batch::from_json(&json)
+}
+
+/// The raw contents of a batch output or error file.
+pub async fn download_file(
+    open_ai_key: &OpenAiKey,
+    client: OpenAiClient,
+    file_id: &str,
+) -> Result<String, BatchError> {
+    let _permit = client
+        .acquire()
+        .await
+        .map_err(|err| BatchError::Request(err.message()))?;
+
+    let response = client
+        .http()
+        .get(format!("{}/{}/content", FILES_URL, file_id))
+        .header("Authorization", open_ai_key.to_header())
+        .send()
+        .await
+        .map_err(|err| BatchError::Request(err.to_string()))?;
+
+    response_text(response).await
+}
+
+async fn response_json(response: reqwest::Response) -> Result<serde_json::Value, BatchError> {
+    let res = response_text(response).await?;
+
+    serde_json::from_str(&res).map_err(|err| BatchError::ResponseJsonDecode(err.to_string()))
+}
+
+async fn response_text(response: reqwest::Response) -> Result<String, BatchError> {
+    let status = response.status();
+    let res = response
+        .text()
+        .await
+        .map_err(|err| BatchError::Response(err.to_string()))?;
+
+    if !status.is_success() {
+        return Err(BatchError::Response(format!(
+            "open ai returned HTTP {}: {}",
+            status, res
+        )));
+    }
+
+    Ok(res)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_jsonl_writes_one_completion_request_per_line() {
+        let requests = vec![
+            BatchRequest {
+                custom_id: "a".to_string(),
+                body: serde_json::json!({ "model": "gpt-5-mini" }),
+            },
+            BatchRequest {
+                custom_id: "b".to_string(),
+                body: serde_json::json!({ "model": "gpt-5-mini" }),
+            },
+        ];
+
+        let jsonl = to_jsonl(&requests);
+        let lines: Vec<serde_json::Value> = jsonl
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[1]["custom_id"], "b");
+        assert_eq!(lines[1]["url"], COMPLETIONS_ENDPOINT);
+        assert_eq!(lines[1]["body"]["model"], "gpt-5-mini");
+    }
+
+    #[test]
+    fn test_batch_from_json_reads_output_file_when_completed() {
+        let json = serde_json::json!({
+            "id": "batch_abc",
+            "status": "completed",
+            "output_file_id": "file-out",
+            "error_file_id": null,
+        });
+
+        let batch = Batch::from_json(&json).unwrap();
+
+        assert_eq!(batch.status, BatchStatus::Completed);
+        assert!(batch.status.is_terminal());
+        assert_eq!(batch.output_file_id, Some("file-out".to_string()));
+        assert_eq!(batch.error_file_id, None);
+    }
+
+    #[test]
+    fn test_parse_results_separates_answers_from_failures() {
+        let jsonl = [
+            serde_json::json!({
+                "custom_id": "ok",
+                "response": {
+                    "status_code": 200,
+                    "body": { "choices": [{ "message": { "content": "Hello" } }] },
+                },
+                "error": null,
+            }),
+            serde_json::json!({
+                "custom_id": "http",
+                "response": { "status_code": 400, "body": { "error": "bad" } },
+                "error": null,
+            }),
+            serde_json::json!({
+                "custom_id": "expired",
+                "response": null,
+                "error": { "code": "batch_expired" },
+            }),
+        ]
+        .iter()
+        .map(|line| line.to_string())
+        .collect::<Vec<String>>()
+        .join("\n");
+
+        let results = parse_results(&jsonl).unwrap();
+
+        assert_eq!(results.len(), 3);
+        match &results[0].response {
+            Ok(response) => assert_eq!(response.as_message().unwrap(), "Hello"),
+            Err(err) => panic!("expected an answer, got {}", err),
+        }
+        assert!(results[1].response.is_err());
+        assert_eq!(results[2].custom_id, "expired");
+        assert!(results[2].response.is_err());
+    }
+}
@@ -0,0 +1,575 @@
+use crate::nice_display::{with_context, NiceDisplay};
+use crate::open_ai::llm_call::{self, LlmCall};
+use crate::open_ai::provider::Provider;
+use crate::open_ai_key::{MissingOpenAiKey, OpenAiKey};
+use sqlx::{Pool, Postgres};
+use std::cell::Cell;
+use std::collections::VecDeque;
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{Mutex, OwnedSemaphorePermit, Semaphore};
+use tokio::time::Instant;
+
+const DEFAULT_CONNECT_TIMEOUT_SECS: u64 = 10;
+const DEFAULT_REQUEST_TIMEOUT_SECS: u64 = 180;
+const DEFAULT_MAX_CONCURRENT_REQUESTS: u64 = 8;
+const DEFAULT_REQUESTS_PER_MINUTE: u64 = 300;
+const DEFAULT_FAILOVER_AFTER_FAILURES: u64 = 2;
+const DEFAULT_FAILOVER_NAME: &str = "secondary";
+const DEFAULT_LOCAL_NAME: &str = "local";
+const LOCAL_BASE_URL_VAR: &str = "LLM_LOCAL_BASE_URL";
+const OPEN_AI_BASE_URL: &str = "https://api.openai.com/v1";
+const RATE_WINDOW: Duration = Duration::from_secs(60);
+
+tokio::task_local! {
+    static OPEN_AI_TIME: Cell<Duration>;
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClientConfig {
+    pub connect_timeout: Duration,
+    pub request_timeout: Duration,
+    pub max_concurrent_requests: usize,
+    pub requests_per_minute: usize,
+    pub failover: Option<FailoverConfig>,
+    pub local: Option<LocalConfig>,
+}
+
+/// An OpenAI compatible server on this machine, such as llama.cpp's
+/// `llama-server` or vLLM, that takes OpenAI's place as the primary
+/// provider so the simulation can run without a network connection.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LocalConfig {
+    /// Recorded in the `llm_call` log for every call this server serves.
+    pub name: String,
+    /// Such as `http://localhost:8080/v1`. `/chat/completions` and
+    /// `/embeddings` are added to the end.
+    pub base_url: String,
+    /// Local servers rarely check a key, so this is only sent when set.
+    pub key: Option<OpenAiKey>,
+    /// Replaces the model of every request. llama.cpp serves whichever
+    /// model it was started with, but vLLM wants the name it was given.
+    pub model: Option<String>,
+}
+
+/// A second OpenAI compatible provider that completions are sent to once
+/// the primary one has failed `after_failures` times in a row for the same
+/// request.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FailoverConfig {
+    /// Recorded in the `llm_call` log for every call this provider serves.
+    pub name: String,
+    /// Such as `https://api.openai.com/v1`. `/chat/completions` is added to
+    /// the end.
+    pub base_url: String,
+    pub key: OpenAiKey,
+    /// Replaces the model of every request, for providers that name their
+    /// models differently.
+    pub model: Option<String>,
+    pub after_failures: usize,
+}
+
+#[derive(Debug)]
+pub enum ClientConfigError {
+    InvalidTimeout { var_name: String, value: String },
+    InvalidLimit { var_name: String, value: String },
+    MissingFailoverKey,
+    Build(reqwest::Error),
+}
+
+/// The http client every OpenAI request goes through. Clones share one
+/// limiter, so jobs and admin ui calls made through the same worker count
+/// against the same caps.
+#[derive(Debug, Clone)]
+pub struct OpenAiClient {
+    http: reqwest::Client,
+    limiter: Arc<RateLimiter>,
+    failover: Option<Arc<FailoverConfig>>,
+    local: Option<Arc<LocalConfig>>,
+    call_log: Option<Pool<Postgres>>,
+    /// Where OpenAI's own completions, embeddings and moderations go.
+    api_base: String,
+}
+
+#[derive(Debug)]
+struct RateLimiter {
+    concurrency: Arc<Semaphore>,
+    requests_per_minute: usize,
+    recent_starts: Mutex<VecDeque<Instant>>,
+}
+
+/// Holds a concurrency slot until it is dropped.
+pub struct RequestPermit {
+    _permit: OwnedSemaphorePermit,
+    /// When the request started waiting for its slot.
+    started: Instant,
+}
+
+/// The concurrency semaphore was closed, so there are no more slots to
+/// send requests in.
+#[derive(Debug, Clone)]
+pub struct LimiterClosed;
+
+impl Drop for RequestPermit {
+    fn drop(&mut self) {
+        let elapsed = self.started.elapsed();
+        // Outside of `measure_open_ai_time` there is nothing to add to
+        let _ = OPEN_AI_TIME.try_with(|total| total.set(total.get().saturating_add(elapsed)));
+    }
+}
+
+/// Runs `future`, and also returns how long it spent on OpenAI requests,
+/// waiting for a free slot included.
+pub async fn measure_open_ai_time<F: Future>(future: F) -> (F::Output, Duration) {
+    OPEN_AI_TIME
+        .scope(Cell::new(Duration::ZERO), async {
+            let output = future.await;
+            let open_ai_time = OPEN_AI_TIME.with(|total| total.get());
+            (output, open_ai_time)
+        })
+        .await
+}
+
+impl NiceDisplay for LimiterClosed {
+    fn message(&self) -> String {
+        "The OpenAI request limiter is closed, so the request was not sent".to_string()
+    }
+}
+
+impl NiceDisplay for ClientConfigError {
+    fn message(&self) -> String {
+        match self {
+            ClientConfigError::InvalidTimeout { var_name, value } => {
+                format!(
+                    "{} must be a whole number of seconds greater than zero, but it was \"{}\"",
+                    var_name, value
+                )
+            }
+            ClientConfigError::InvalidLimit { var_name, value } => {
+                format!(
+                    "{} must be a whole number greater than zero, but it was \"{}\"",
+                    var_name, value
+                )
+            }
+            ClientConfigError::MissingFailoverKey => {
+                "LLM_FAILOVER_BASE_URL is set, but LLM_FAILOVER_API_KEY is not".to_string()
+            }
+            ClientConfigError::Build(err) => with_context("Error building the http client", err),
+        }
+    }
+}
+
+impl ClientConfig {
+    /// Reads OPENAI_CONNECT_TIMEOUT_SECS, OPENAI_REQUEST_TIMEOUT_SECS,
+    /// OPENAI_MAX_CONCURRENT_REQUESTS and OPENAI_REQUESTS_PER_MINUTE, falling
+    /// back to defaults when they are not set, and the LLM_FAILOVER_ and
+    /// LLM_LOCAL_ ones when a failover provider or local server is
+    /// configured.
+    pub fn load() -> Result<Self, ClientConfigError> {
+        let connect_timeout =
+            timeout_from_env("OPENAI_CONNECT_TIMEOUT_SECS", DEFAULT_CONNECT_TIMEOUT_SECS)?;
+        let request_timeout =
+            timeout_from_env("OPENAI_REQUEST_TIMEOUT_SECS", DEFAULT_REQUEST_TIMEOUT_SECS)?;
+        let max_concurrent_requests = limit_from_env(
+            "OPENAI_MAX_CONCURRENT_REQUESTS",
+            DEFAULT_MAX_CONCURRENT_REQUESTS,
+        )?;
+        let requests_per_minute =
+            limit_from_env("OPENAI_REQUESTS_PER_MINUTE", DEFAULT_REQUESTS_PER_MINUTE)?;
+
+        Ok(ClientConfig {
+            connect_timeout,
+            request_timeout,
+            max_concurrent_requests,
+            requests_per_minute,
+            failover: FailoverConfig::load()?,
+            local: LocalConfig::load(),
+        })
+    }
+
+    pub fn build_client(&self) -> Result<OpenAiClient, ClientConfigError> {
+        let http = reqwest::Client::builder()
+            .connect_timeout(self.connect_timeout)
+            .timeout(self.request_timeout)
+            .build()
+            .map_err(ClientConfigError::Build)?;
+
+        Ok(OpenAiClient {
+            http,
+            limiter: Arc::new(RateLimiter {
+                concurrency: Arc::new(Semaphore::new(self.max_concurrent_requests)),
+                requests_per_minute: self.requests_per_minute,
+                recent_starts: Mutex::new(VecDeque::new()),
+            }),
+            failover: self.failover.clone().map(Arc::new),
+            local: self.local.clone().map(Arc::new),
+            call_log: None,
+            api_base: OPEN_AI_BASE_URL.to_string(),
+        })
+    }
+}
+
+impl FailoverConfig {
+    /// Failover is off unless LLM_FAILOVER_BASE_URL is set. Then
+    /// LLM_FAILOVER_API_KEY is required, and LLM_FAILOVER_NAME,
+    /// LLM_FAILOVER_MODEL and LLM_FAILOVER_AFTER_FAILURES (default 2) are
+    /// optional.
+    fn load() -> Result<Option<Self>, ClientConfigError> {
+        let base_url = match dotenv::var("LLM_FAILOVER_BASE_URL") {
+            Ok(base_url) if !base_url.trim().is_empty() => base_url.trim().to_string(),
+            _ => return Ok(None),
+        };
+
+        let key = dotenv::var("LLM_FAILOVER_API_KEY")
+            .map(OpenAiKey::from_string)
+            .map_err(|_| ClientConfigError::MissingFailoverKey)?;
+
+        let name = match dotenv::var("LLM_FAILOVER_NAME") {
+            Ok(name) if !name.trim().is_empty() => name.trim().to_string(),
+            _ => DEFAULT_FAILOVER_NAME.to_string(),
+        };
+
+        let model = match dotenv::var("LLM_FAILOVER_MODEL") {
+            Ok(model) if !model.trim().is_empty() => Some(model.trim().to_string()),
+            _ => None,
+        };
+
+        let after_failures = limit_from_env(
+            "LLM_FAILOVER_AFTER_FAILURES",
+            DEFAULT_FAILOVER_AFTER_FAILURES,
+        )?;
+
+        Ok(Some(FailoverConfig {
+            name,
+            base_url,
+            key,
+            model,
+            after_failures,
+        }))
+    }
+}
+
+impl LocalConfig {
+    /// The local server is off unless LLM_LOCAL_BASE_URL is set. Then
+    /// LLM_LOCAL_NAME, LLM_LOCAL_MODEL and LLM_LOCAL_API_KEY are optional.
+    fn load() -> Option<Self> {
+        let base_url = match dotenv::var(LOCAL_BASE_URL_VAR) {
+            Ok(base_url) if !base_url.trim().is_empty() => base_url.trim().to_string(),
+            _ => return None,
+        };
+
+        let name = match dotenv::var("LLM_LOCAL_NAME") {
+            Ok(name) if !name.trim().is_empty() => name.trim().to_string(),
+            _ => DEFAULT_LOCAL_NAME.to_string(),
+        };
+
+        let model = match dotenv::var("LLM_LOCAL_MODEL") {
+            Ok(model) if !model.trim().is_empty() => Some(model.trim().to_string()),
+            _ => None,
+        };
+
+        let key = match dotenv::var("LLM_LOCAL_API_KEY") {
+            Ok(key) if !key.trim().is_empty() => Some(OpenAiKey::from_string(key)),
+            _ => None,
+        };
+
+        Some(LocalConfig {
+            name,
+            base_url,
+            key,
+            model,
+        })
+    }
+
+    /// Without an OpenAI key the worker can still start, as long as a local
+    /// server answers the completions and embeddings.
+    pub fn is_configured() -> bool {
+        match dotenv::var(LOCAL_BASE_URL_VAR) {
+            Ok(base_url) => !base_url.trim().is_empty(),
+            Err(_) => false,
+        }
+    }
+}
+
+impl OpenAiClient {
+    /// Waits for a free concurrency slot and for room under the requests per
+    /// minute cap. Keep the permit alive until the response has been read.
+    pub async fn acquire(&self) -> Result<RequestPermit, LimiterClosed> {
+        let started = Instant::now();
+
+        let permit = self
+            .limiter
+            .concurrency
+            .clone()
+            .acquire_owned()
+            .await
+            .map_err(|_| LimiterClosed)?;
+
+        loop {
+            let wait = {
+                let mut recent_starts = self.limiter.recent_starts.lock().await;
+                let now = Instant::now();
+                let wait =
+                    wait_for_rate_slot(&mut recent_starts, now, self.limiter.requests_per_minute);
+
+                if wait.is_none() {
+                    recent_starts.push_back(now);
+                }
+
+                wait
+            };
+
+            match wait {
+                None => break,
+                Some(wait) => tokio::time::sleep(wait).await,
+            }
+        }
+
+        Ok(RequestPermit {
+            _permit: permit,
+            started,
+        })
+    }
+
+    pub fn http(&self) -> &reqwest::Client {
+        &self.http
+    }
+
+    /// Has every completion attempt written to the `llm_call` table.
+    pub fn with_call_log(self, pool: Pool<Postgres>) -> Self {
+        OpenAiClient {
+            call_log: Some(pool),
+            ..self
+        }
+    }
+
+    /// Sends what would go to OpenAI to another server with the same api,
+    /// like the stub `smoke-test` runs.
+    pub fn with_api_base(self, api_base: String) -> Self {
+        OpenAiClient { api_base, ..self }
+    }
+
+    /// Like `https://api.openai.com/v1/embeddings` for `/embeddings`.
+    pub fn api_url(&self, path: &str) -> String {
+        format!("{}{}", self.api_base.trim_end_matches('/'), path)
+    }
+
+    /// Where completions and embeddings go first: the local server when
+    /// one is configured, and otherwise OpenAI, which needs the key.
+    pub fn primary<'a>(
+        &'a self,
+        open_ai_key: Option<&'a OpenAiKey>,
+    ) -> Result<Provider<'a>, MissingOpenAiKey> {
+        match (self.local.as_deref(), open_ai_key) {
+            (Some(local), _) => Ok(Provider::Local(local)),
+            (None, Some(key)) => Ok(Provider::OpenAi {
+                base_url: self.api_base.as_str(),
+                key,
+            }),
+            (None, None) => Err(MissingOpenAiKey),
+        }
+    }
+
+    /// Where a completion goes once the primary provider has failed it
+    /// `primary_attempts` times.
+    pub fn secondary(&self) -> Option<Provider<'_>> {
+        self.failover.as_deref().map(Provider::Failover)
+    }
+
+    /// How many times to try the primary provider before giving up on it.
+    /// Without a failover provider there is nothing to gain from trying
+    /// again here.
+    pub fn primary_attempts(&self) -> usize {
+        match self.failover.as_deref() {
+            Some(failover) => failover.after_failures,
+            None => 1,
+        }
+    }
+
+    pub async fn record_call(&self, call: &LlmCall) -> Result<(), String> {
+        match &self.call_log {
+            Some(pool) => llm_call::insert(pool, call).await,
+            None => Ok(()),
+        }
+    }
+}
+
+impl Default for ClientConfig {
+    fn default() -> Self {
+        ClientConfig {
+            connect_timeout: Duration::from_secs(DEFAULT_CONNECT_TIMEOUT_SECS),
+            request_timeout: Duration::from_secs(DEFAULT_REQUEST_TIMEOUT_SECS),
+            max_concurrent_requests: DEFAULT_MAX_CONCURRENT_REQUESTS as usize,
+            requests_per_minute: DEFAULT_REQUESTS_PER_MINUTE as usize,
+            failover: None,
+            local: None,
+        }
+    }
+}
+
+fn timeout_from_env(var_name: &str, default_secs: u64) -> Result<Duration, ClientConfigError> {
+    match dotenv::var(var_name) {
+        Ok(value) => parse_timeout_secs(var_name, value.as_str()),
+        Err(_) => Ok(Duration::from_secs(default_secs)),
+    }
+}
+
+fn parse_timeout_secs(var_name: &str, value: &str) -> Result<Duration, ClientConfigError> {
+    match value.trim().parse::<u64>() {
+        Ok(secs) if secs > 0 => Ok(Duration::from_secs(secs)),
+        _ => Err(ClientConfigError::InvalidTimeout {
+            var_name: var_name.to_string(),
+            value: value.to_string(),
+        }),
+    }
+}
+
+fn limit_from_env(var_name: &str, default: u64) -> Result<usize, ClientConfigError> {
+    let value = match dotenv::var(var_name) {
+        Ok(value) => value,
+        Err(_) => return Ok(default as usize),
+    };
+
+    match value.trim().parse::<usize>() {
+        Ok(limit) if limit > 0 => Ok(limit),
+        _ => Err(ClientConfigError::InvalidLimit {
+            var_name: var_name.to_string(),
+            value,
+        }),
+    }
+}
+
+/// Forgets request starts older than a minute, then says how long to wait
+/// before another request fits under the cap, if at all.
+fn wait_for_rate_slot(
+    recent_starts: &mut VecDeque<Instant>,
+    now: Instant,
+    requests_per_minute: usize,
+) -> Option<Duration> {
+    while let Some(oldest) = recent_starts.front() {
+        if now.duration_since(*oldest) >= RATE_WINDOW {
+            recent_starts.pop_front();
+        } else {
+            break;
+        }
+    }
+
+    if recent_starts.len() < requests_per_minute {
+        return None;
+    }
+
+    recent_starts
+        .front()
+        .map(|oldest| RATE_WINDOW.saturating_sub(now.duration_since(*oldest)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_timeout_secs_accepts_positive_whole_seconds() {
+        let timeout = parse_timeout_secs("OPENAI_REQUEST_TIMEOUT_SECS", " 30 ").unwrap();
+
+        assert_eq!(timeout, Duration::from_secs(30));
+    }
+
+    #[test]
+    fn test_parse_timeout_secs_rejects_zero_and_garbage() {
+        assert!(parse_timeout_secs("OPENAI_REQUEST_TIMEOUT_SECS", "0").is_err());
+        assert!(parse_timeout_secs("OPENAI_REQUEST_TIMEOUT_SECS", "soon").is_err());
+    }
+
+    #[test]
+    fn test_wait_for_rate_slot_waits_for_the_oldest_request_to_age_out() {
+        let start = Instant::now();
+        let mut recent_starts = VecDeque::from(vec![start, start + Duration::from_secs(10)]);
+
+        assert_eq!(
+            wait_for_rate_slot(&mut recent_starts, start + Duration::from_secs(20), 2),
+            Some(Duration::from_secs(40))
+        );
+        assert_eq!(
+            wait_for_rate_slot(&mut recent_starts, start + Duration::from_secs(60), 2),
+            None
+        );
+        assert_eq!(recent_starts.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_measure_open_ai_time_adds_up_dropped_permits() {
+        let slots = Arc::new(Semaphore::new(3));
+        let (_, open_ai_time) = measure_open_ai_time(async {
+            let started = Instant::now() - Duration::from_millis(50);
+            let first = RequestPermit {
+                _permit: slots.clone().acquire_owned().await.unwrap(),
+                started,
+            };
+            let second = RequestPermit {
+                _permit: slots.clone().acquire_owned().await.unwrap(),
+                started,
+            };
+            drop(first);
+            drop(second);
+        })
+        .await;
+
+        assert!(open_ai_time >= Duration::from_millis(100));
+
+        // Permits dropped outside of a measurement are fine too
+        drop(RequestPermit {
+            _permit: slots.clone().acquire_owned().await.unwrap(),
+            started: Instant::now(),
+        });
+    }
+
+    #[tokio::test]
+    async fn test_acquire_fails_once_the_limiter_is_closed() {
+        let client = ClientConfig::default().build_client().unwrap();
+        assert!(client.acquire().await.is_ok());
+
+        client.limiter.concurrency.close();
+
+        match client.acquire().await {
+            Err(err) => assert!(err.message().contains("limiter is closed")),
+            Ok(_) => panic!("expected no permit from a closed limiter"),
+        }
+    }
+
+    #[test]
+    fn test_primary_is_the_local_server_when_there_is_one() {
+        let key = OpenAiKey::from_string("sk-test".to_string());
+        let open_ai = ClientConfig::default().build_client().unwrap();
+
+        let primary = open_ai.primary(Some(&key)).unwrap();
+        assert_eq!(primary.name(), llm_call::PRIMARY_PROVIDER);
+        assert_eq!(
+            primary.url("/embeddings"),
+            "https://api.openai.com/v1/embeddings"
+        );
+        assert_eq!(open_ai.primary(None).err(), Some(MissingOpenAiKey));
+
+        let local = ClientConfig {
+            local: Some(LocalConfig {
+                name: "local".to_string(),
+                base_url: "http://localhost:8080/v1/".to_string(),
+                key: None,
+                model: Some("qwen".to_string()),
+            }),
+            ..ClientConfig::default()
+        }
+        .build_client()
+        .unwrap();
+
+        let primary = local.primary(None).unwrap();
+        assert!(primary.is_local());
+        assert_eq!(primary.authorization(), None);
+        assert_eq!(
+            primary.url("/embeddings"),
+            "http://localhost:8080/v1/embeddings"
+        );
+        assert_eq!(primary.completion_model("gpt-4o"), "qwen");
+        assert!(local.secondary().is_none());
+    }
+}
@@ -1,17 +1,29 @@
-use crate::nice_display::NiceDisplay;
+use crate::nice_display::{nest, with_context, NiceDisplay};
+use crate::offload;
+use crate::open_ai::batch::BatchRequest;
+use crate::open_ai::client::OpenAiClient;
 use crate::open_ai::history::History;
+use crate::open_ai::llm_call::LlmCall;
 use crate::open_ai::model::Model;
+use crate::open_ai::provider::Provider;
 use crate::open_ai::role::Role;
 use crate::open_ai::tool::Tool;
 use crate::open_ai::tool_call;
 use crate::open_ai::tool_call::ToolCall;
-use crate::open_ai_key::OpenAiKey;
+use crate::open_ai_key::{MissingOpenAiKey, OpenAiKey};
 use crate::person_actions::PersonActionError;
+use crate::world_language::{world_language, WorldLanguage};
 use reqwest::header::CONTENT_TYPE;
+use reqwest::StatusCode;
+use std::time::Instant;
 
 pub struct Completion {
     history: History,
     tool_call: Vec<Tool>,
+    model: Model,
+    temperature: Option<f32>,
+    max_tokens: Option<u32>,
+    json_mode: bool,
 }
 
 pub struct Response {
@@ -67,7 +79,7 @@ impl NiceDisplay for MessageError {
 }
 
 impl Response {
-    fn new(json: serde_json::Value) -> Self {
+    pub(super) fn new(json: serde_json::Value) -> Self {
         Self { json }
     }
 
@@ -160,19 +172,20 @@ pub enum CompletionError {
     Message(MessageError),
     ToolCallDecode(tool_call::ToolCallDecodeError),
     PersonAction(PersonActionError),
+    MissingOpenAiKey(MissingOpenAiKey),
 }
 
 impl NiceDisplay for CompletionError {
     fn message(&self) -> String {
         match self {
             CompletionError::Request(err) => {
-                format!("I had trouble making a request to open ai: {}", err)
+                with_context("I had trouble making a request to open ai", err)
             }
             CompletionError::Response(err) => {
-                format!("I had trouble with the response from open ai: {}", err)
+                with_context("I had trouble with the response from open ai", err)
             }
             CompletionError::ResponseJsonDecode(err) => {
-                format!("I had trouble decoding the response from open ai: {}", err)
+                with_context("I had trouble decoding the response from open ai", err)
             }
             CompletionError::Message(err) => {
                 format!(
@@ -180,14 +193,15 @@ impl NiceDisplay for CompletionError {
                     err.message()
                 )
             }
-            CompletionError::ToolCallDecode(err) => {
-                format!(
-                    "I had trouble decoding the tool calls from the response:\n{}",
-                    err.message()
-                )
-            }
+            CompletionError::ToolCallDecode(err) => nest(
+                "I had trouble decoding the tool calls from the response",
+                err,
+            ),
             CompletionError::PersonAction(err) => {
-                format!("I had trouble interpreting the action: {}", err.message())
+                nest("I had trouble interpreting the action", err)
+            }
+            CompletionError::MissingOpenAiKey(err) => {
+                nest("I had no provider to send the completion to", err)
             }
         }
     }
@@ -205,9 +219,36 @@ impl Completion {
         Self {
             history: History::new(),
             tool_call: vec![],
+            model: Model::DEFAULT,
+            temperature: None,
+            max_tokens: None,
+            json_mode: false,
         }
     }
 
+    pub fn set_model(&mut self, model: Model) -> &mut Self {
+        self.model = model;
+        self
+    }
+
+    /// Leave unset to use the model's own default.
+    pub fn set_temperature(&mut self, temperature: f32) -> &mut Self {
+        self.temperature = Some(temperature);
+        self
+    }
+
+    pub fn set_max_tokens(&mut self, max_tokens: u32) -> &mut Self {
+        self.max_tokens = Some(max_tokens);
+        self
+    }
+
+    /// Makes the model answer with a JSON object. Open AI requires the word
+    /// "JSON" to appear somewhere in the messages when this is on.
+    pub fn set_json_mode(&mut self, json_mode: bool) -> &mut Self {
+        self.json_mode = json_mode;
+        self
+    }
+
     pub fn add_message(&mut self, role: Role, content: &str) -> &mut Self {
         self.history.add_message(role, content);
         self
@@ -220,17 +261,76 @@ impl Completion {
 
     pub async fn send_request(
         &self,
-        open_ai_key: &OpenAiKey,
-        client: reqwest::Client,
+        open_ai_key: Option<&OpenAiKey>,
+        client: OpenAiClient,
     ) -> Result<Response, CompletionError> {
-        let mut body = serde_json::json!({
-            "model": Model::DEFAULT.to_string(),
-            "messages": self.history.get_messages().iter().map(|msg| {
+        let body = self.to_body();
+        let model = self.model.to_string();
+
+        let primary = client
+            .primary(open_ai_key)
+            .map_err(CompletionError::MissingOpenAiKey)?;
+
+        let primary_attempts = client.primary_attempts();
+        let mut attempt = 1;
+        let last_error = loop {
+            match try_target(&client, primary, &body, &model, attempt).await {
+                Ok(response) => return Ok(response),
+                Err(failure) if !failure.retryable => return Err(failure.error),
+                Err(_) if attempt < primary_attempts => attempt += 1,
+                Err(failure) => break failure.error,
+            }
+        };
+
+        let secondary = match client.secondary() {
+            Some(secondary) => secondary,
+            None => return Err(last_error),
+        };
+
+        try_target(&client, secondary, &body, &model, attempt + 1)
+            .await
+            .map_err(|failure| failure.error)
+    }
+
+    /// One line of a Batch API input file, which OpenAI answers with the
+    /// same `custom_id`.
+    pub fn to_batch_request(&self, custom_id: String) -> BatchRequest {
+        BatchRequest {
+            custom_id,
+            body: self.to_body(),
+        }
+    }
+
+    fn to_body(&self) -> serde_json::Value {
+        self.to_body_in(&world_language())
+    }
+
+    fn to_body_in(&self, language: &WorldLanguage) -> serde_json::Value {
+        let mut messages = self
+            .history
+            .get_messages()
+            .iter()
+            .map(|msg| {
                 serde_json::json!({
                     "role": msg.role().to_str(),
                     "content": msg.content(),
                 })
-            }).collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+
+        if let Some(instruction) = language.to_instruction() {
+            messages.insert(
+                0,
+                serde_json::json!({
+                    "role": Role::System.to_str(),
+                    "content": instruction,
+                }),
+            );
+        }
+
+        let mut body = serde_json::json!({
+            "model": self.model.to_string(),
+            "messages": messages,
         });
 
         if !self.tool_call.is_empty() {
@@ -242,62 +342,191 @@ impl Completion {
             body["parallel_tool_calls"] = serde_json::json!(false);
         }
 
-        let response = client
-            .post("https://api.openai.com/v1/chat/completions")
-            .header("Content-Type", "application/json")
-            .header("Authorization", open_ai_key.to_header())
-            .json(&body)
-            .send()
-            .await
-            .map_err(|err| CompletionError::Request(err.to_string()))?;
-
-        let status = response.status();
-        let content_type = response
-            .headers()
-            .get(CONTENT_TYPE)
-            .and_then(|value| value.to_str().ok())
-            .map(|value| value.to_string());
-        let res = response
-            .text()
-            .await
-            .map_err(|err| CompletionError::Response(err.to_string()))?;
-
-        if !status.is_success() {
-            let maybe_res_json: Result<serde_json::Value, serde_json::Error> =
-                serde_json::from_str(&res);
-
-            return match maybe_res_json {
-                Ok(res_json) => Err(CompletionError::Response(format!(
-                    "open ai returned HTTP {}: {}",
-                    status,
-                    extract_open_ai_error_message(&res_json)
-                ))),
-                Err(err) => Err(CompletionError::Response(format!(
-                    "open ai returned HTTP {} with a non-JSON body: {}",
-                    status,
-                    describe_json_decode_failure(
-                        content_type.as_deref(),
-                        res.as_str(),
-                        err.to_string().as_str()
-                    )
-                ))),
-            };
+        if let Some(temperature) = self.temperature {
+            body["temperature"] = serde_json::json!(temperature);
+        }
+
+        if let Some(max_tokens) = self.max_tokens {
+            body["max_completion_tokens"] = serde_json::json!(max_tokens);
         }
 
-        let res_json: serde_json::Value = serde_json::from_str(&res).map_err(|err| {
-            CompletionError::ResponseJsonDecode(describe_json_decode_failure(
+        if self.json_mode {
+            body["response_format"] = serde_json::json!({ "type": "json_object" });
+        }
+
+        body
+    }
+}
+
+/// Why an attempt failed, and whether another attempt could go better.
+struct AttemptFailure {
+    error: CompletionError,
+    retryable: bool,
+}
+
+/// Sends one attempt and records it in the `llm_call` log.
+async fn try_target(
+    client: &OpenAiClient,
+    provider: Provider<'_>,
+    body: &serde_json::Value,
+    requested_model: &str,
+    attempt: usize,
+) -> Result<Response, AttemptFailure> {
+    // Local servers get the request in a shape they understand
+    let mut body = if provider.is_local() {
+        to_local_body(body)
+    } else {
+        body.clone()
+    };
+    let model = provider.completion_model(requested_model);
+    body["model"] = serde_json::json!(model);
+
+    let started = Instant::now();
+    let result = send_to_target(client, provider, &body).await;
+
+    let (prompt_tokens, completion_tokens) = match &result {
+        Ok(response) => LlmCall::usage_from_json(&response.json),
+        Err(_) => (None, None),
+    };
+
+    let call = LlmCall {
+        provider: provider.name().to_string(),
+        model,
+        attempt: i32::try_from(attempt).unwrap_or(i32::MAX),
+        error: match &result {
+            Ok(_) => None,
+            Err(failure) => Some(failure.error.message()),
+        },
+        duration_ms: i64::try_from(started.elapsed().as_millis()).unwrap_or(i64::MAX),
+        prompt_tokens,
+        completion_tokens,
+    };
+
+    // A failure to write the log should not fail the completion it describes
+    let _ = client.record_call(&call).await;
+
+    result
+}
+
+async fn send_to_target(
+    client: &OpenAiClient,
+    provider: Provider<'_>,
+    body: &serde_json::Value,
+) -> Result<Response, AttemptFailure> {
+    // A closed limiter stays closed, so there is no point in retrying
+    let _permit = client.acquire().await.map_err(|err| AttemptFailure {
+        error: CompletionError::Request(err.message()),
+        retryable: false,
+    })?;
+
+    let mut request = client
+        .http()
+        .post(provider.url("/chat/completions"))
+        .header("Content-Type", "application/json");
+
+    if let Some(authorization) = provider.authorization() {
+        request = request.header("Authorization", authorization.as_str());
+    }
+
+    let response = request
+        .json(body)
+        .send()
+        .await
+        .map_err(|err| AttemptFailure {
+            error: CompletionError::Request(err.to_string()),
+            retryable: true,
+        })?;
+
+    let status = response.status();
+    let content_type = response
+        .headers()
+        .get(CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .map(|value| value.to_string());
+    let res = response.text().await.map_err(|err| AttemptFailure {
+        error: CompletionError::Response(err.to_string()),
+        retryable: true,
+    })?;
+
+    if !status.is_success() {
+        let maybe_res_json: Result<serde_json::Value, serde_json::Error> =
+            serde_json::from_str(&res);
+
+        let error = match maybe_res_json {
+            Ok(res_json) => CompletionError::Response(format!(
+                "{} returned HTTP {}: {}",
+                provider.label(),
+                status,
+                extract_open_ai_error_message(&res_json)
+            )),
+            Err(err) => CompletionError::Response(format!(
+                "{} returned HTTP {} with a non-JSON body: {}",
+                provider.label(),
+                status,
+                describe_json_decode_failure(
+                    content_type.as_deref(),
+                    res.as_str(),
+                    err.to_string().as_str()
+                )
+            )),
+        };
+
+        return Err(AttemptFailure {
+            error,
+            retryable: is_retryable_status(status),
+        });
+    }
+
+    let res_json = offload::run_if(offload::is_heavy_text(&res), move || {
+        serde_json::from_str::<serde_json::Value>(&res).map_err(|err| {
+            describe_json_decode_failure(
                 content_type.as_deref(),
                 res.as_str(),
                 err.to_string().as_str(),
-            ))
-        })?;
+            )
+        })
+    })
+    .await
+    .and_then(|decoded| decoded)
+    .map_err(|err| AttemptFailure {
+        error: CompletionError::ResponseJsonDecode(err),
+        retryable: false,
+    })?;
+
+    if let Some(api_error) = maybe_open_ai_error_message(&res_json) {
+        return Err(AttemptFailure {
+            error: CompletionError::Response(api_error),
+            retryable: false,
+        });
+    }
 
-        if let Some(api_error) = maybe_open_ai_error_message(&res_json) {
-            return Err(CompletionError::Response(api_error));
-        }
+    Ok(Response::new(res_json))
+}
+
+/// llama.cpp and vLLM follow the older OpenAI request format. They read
+/// `max_tokens` rather than `max_completion_tokens`, and some builds reject
+/// `parallel_tool_calls`, which they would not honor anyway since they only
+/// ever return one tool call.
+fn to_local_body(body: &serde_json::Value) -> serde_json::Value {
+    let mut body = body.clone();
+
+    if let Some(fields) = body.as_object_mut() {
+        fields.remove("parallel_tool_calls");
 
-        Ok(Response::new(res_json))
+        if let Some(max_tokens) = fields.remove("max_completion_tokens") {
+            fields.insert("max_tokens".to_string(), max_tokens);
+        }
     }
+
+    body
+}
+
+/// Overloaded, rate limited or broken servers might do better on the next
+/// try or at another provider. A bad request would fail the same way again.
+fn is_retryable_status(status: StatusCode) -> bool {
+    status.is_server_error()
+        || status == StatusCode::TOO_MANY_REQUESTS
+        || status == StatusCode::REQUEST_TIMEOUT
 }
 
 fn maybe_open_ai_error_message(json: &serde_json::Value) -> Option<String> {
@@ -354,6 +583,53 @@ fn preview_response_body(response_body: &str) -> String {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::open_ai::tool::ToolFunction;
+
+    #[test]
+    fn test_only_server_side_statuses_are_retryable() {
+        assert!(is_retryable_status(StatusCode::INTERNAL_SERVER_ERROR));
+        assert!(is_retryable_status(StatusCode::SERVICE_UNAVAILABLE));
+        assert!(is_retryable_status(StatusCode::TOO_MANY_REQUESTS));
+        assert!(!is_retryable_status(StatusCode::BAD_REQUEST));
+        assert!(!is_retryable_status(StatusCode::UNAUTHORIZED));
+    }
+
+    #[test]
+    fn test_local_body_uses_the_older_request_format() {
+        let mut completion = Completion::new();
+        completion
+            .add_message(Role::System, "You are Hank.")
+            .set_max_tokens(200)
+            .add_tool_call(Tool::FunctionCall(ToolFunction::new(
+                "wait".to_string(),
+                "Wait".to_string(),
+                vec![],
+            )));
+
+        let body = to_local_body(&completion.to_body_in(&WorldLanguage::english()));
+
+        assert_eq!(body["max_tokens"], 200);
+        assert!(body.get("max_completion_tokens").is_none());
+        assert!(body.get("parallel_tool_calls").is_none());
+        assert!(body.get("tools").is_some());
+    }
+
+    #[test]
+    fn test_the_world_language_leads_the_messages() {
+        let mut completion = Completion::new();
+        completion.add_message(Role::System, "You are Hank.");
+
+        let spanish = completion.to_body_in(&WorldLanguage::parse("es").unwrap());
+        assert_eq!(spanish["messages"].as_array().unwrap().len(), 2);
+        assert!(spanish["messages"][0]["content"]
+            .as_str()
+            .unwrap()
+            .contains("Spanish"));
+        assert_eq!(spanish["messages"][1]["content"], "You are Hank.");
+
+        let english = completion.to_body_in(&WorldLanguage::english());
+        assert_eq!(english["messages"].as_array().unwrap().len(), 1);
+    }
 
     #[test]
     fn test_preview_response_body_reports_empty_body() {
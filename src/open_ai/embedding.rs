@@ -1,28 +1,74 @@
-use crate::nice_display::NiceDisplay;
-use crate::open_ai_key::OpenAiKey;
+use crate::nice_display::{nest, with_context, NiceDisplay};
+use crate::offload;
+use crate::open_ai::client::OpenAiClient;
+use crate::open_ai_key::{MissingOpenAiKey, OpenAiKey};
 use reqwest::header::CONTENT_TYPE;
+use std::fmt::Display;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EmbeddingModel {
+    TextEmbedding3Small,
+}
+
+impl EmbeddingModel {
+    /// The model new memories and memory searches are embedded with. Vectors
+    /// from different models cannot be compared, so changing this means
+    /// re-embedding stored memories.
+    pub const CURRENT: EmbeddingModel = EmbeddingModel::TextEmbedding3Small;
+
+    pub fn dimension(&self) -> i32 {
+        match self {
+            EmbeddingModel::TextEmbedding3Small => 1536,
+        }
+    }
+}
+
+impl Display for EmbeddingModel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                EmbeddingModel::TextEmbedding3Small => "text-embedding-3-small",
+            }
+        )
+    }
+}
+
+/// How many of a person's memories were embedded with one model and size.
+#[derive(Debug, Clone)]
+pub struct StoredEmbeddingCount {
+    pub model: String,
+    pub dimension: i32,
+    pub count: i64,
+}
 
 pub struct EmbeddingRequest {
     content: String,
+    model: EmbeddingModel,
 }
 
 pub enum EmbeddingError {
     Request(String),
     Response(String),
     ResponseJsonDecode(String),
+    MissingOpenAiKey(MissingOpenAiKey),
 }
 
 impl NiceDisplay for EmbeddingError {
     fn message(&self) -> String {
         match self {
             EmbeddingError::Request(err) => {
-                format!("I had trouble making a request to open ai\n{}", err)
+                with_context("I had trouble making a request to open ai", err)
             }
             EmbeddingError::Response(err) => {
-                format!("I had trouble with the response from open ai\n{}", err)
+                with_context("I had trouble with the response from open ai", err)
             }
             EmbeddingError::ResponseJsonDecode(err) => {
-                format!("I had trouble decoding the response from open ai\n{}", err)
+                with_context("I had trouble decoding the response from open ai", err)
+            }
+            EmbeddingError::MissingOpenAiKey(err) => {
+                nest("I had no provider to send the embedding to", err)
             }
         }
     }
@@ -30,23 +76,48 @@ impl NiceDisplay for EmbeddingError {
 
 impl EmbeddingRequest {
     pub fn new(content: String) -> Self {
-        Self { content }
+        Self {
+            content,
+            model: EmbeddingModel::CURRENT,
+        }
+    }
+
+    pub fn model(&self) -> EmbeddingModel {
+        self.model
     }
 
+    /// Embeds with the same provider completions go to first. A local server
+    /// is asked for the model by its OpenAI name, so it has to serve vectors
+    /// the same size, under that name if it checks.
     pub async fn create(
         &self,
-        open_ai_key: OpenAiKey,
-        client: reqwest::Client,
+        open_ai_key: Option<&OpenAiKey>,
+        client: OpenAiClient,
     ) -> Result<Vec<f32>, EmbeddingError> {
+        let provider = client
+            .primary(open_ai_key)
+            .map_err(EmbeddingError::MissingOpenAiKey)?;
+
         let json_body = serde_json::json!({
             "input": self.content,
-            "model": "text-embedding-3-small"
+            "model": self.model.to_string()
         });
 
-        let response = client
-            .post("https://api.openai.com/v1/embeddings")
-            .header("Content-Type", "application/json")
-            .header("Authorization", open_ai_key.to_header())
+        let _permit = client
+            .acquire()
+            .await
+            .map_err(|err| EmbeddingError::Request(err.message()))?;
+
+        let mut request = client
+            .http()
+            .post(provider.url("/embeddings"))
+            .header("Content-Type", "application/json");
+
+        if let Some(authorization) = provider.authorization() {
+            request = request.header("Authorization", authorization);
+        }
+
+        let response = request
             .json(&json_body)
             .send()
             .await
@@ -70,11 +141,14 @@ impl EmbeddingRequest {
 
             return match maybe_res_json {
                 Ok(res_json) => Err(EmbeddingError::Response(format!(
-                    "open ai returned HTTP {}: {}",
-                    status, res_json
+                    "{} returned HTTP {}: {}",
+                    provider.label(),
+                    status,
+                    res_json
                 ))),
                 Err(err) => Err(EmbeddingError::Response(format!(
-                    "open ai returned HTTP {} with a non-JSON body: {}",
+                    "{} returned HTTP {} with a non-JSON body: {}",
+                    provider.label(),
                     status,
                     describe_json_decode_failure(
                         content_type.as_deref(),
@@ -85,41 +159,105 @@ impl EmbeddingRequest {
             };
         }
 
-        let res_json: serde_json::Value = serde_json::from_str(&res).map_err(|err| {
-            EmbeddingError::ResponseJsonDecode(describe_json_decode_failure(
-                content_type.as_deref(),
-                res.as_str(),
-                err.to_string().as_str(),
-            ))
-        })?;
-
-        let vector = res_json
-            .get("data")
-            .ok_or_else(|| EmbeddingError::ResponseJsonDecode("Missing data field".to_string()))?
-            .get(0)
-            .ok_or_else(|| {
-                EmbeddingError::ResponseJsonDecode("Missing first data element".to_string())
-            })?
-            .get("embedding")
-            .ok_or_else(|| {
-                EmbeddingError::ResponseJsonDecode("Missing embedding field".to_string())
-            })?
-            .as_array()
-            .ok_or_else(|| EmbeddingError::ResponseJsonDecode("Embedding not array".to_string()))?
+        let model = self.model;
+        offload::run_if(offload::is_heavy_text(&res), move || {
+            parse_embedding(&res, content_type.as_deref(), model)
+        })
+        .await
+        .map_err(EmbeddingError::ResponseJsonDecode)?
+    }
+}
+
+fn parse_embedding(
+    res: &str,
+    content_type: Option<&str>,
+    model: EmbeddingModel,
+) -> Result<Vec<f32>, EmbeddingError> {
+    let res_json: serde_json::Value = serde_json::from_str(res).map_err(|err| {
+        EmbeddingError::ResponseJsonDecode(describe_json_decode_failure(
+            content_type,
+            res,
+            err.to_string().as_str(),
+        ))
+    })?;
+
+    let vector = res_json
+        .get("data")
+        .ok_or_else(|| EmbeddingError::ResponseJsonDecode("Missing data field".to_string()))?
+        .get(0)
+        .ok_or_else(|| {
+            EmbeddingError::ResponseJsonDecode("Missing first data element".to_string())
+        })?
+        .get("embedding")
+        .ok_or_else(|| EmbeddingError::ResponseJsonDecode("Missing embedding field".to_string()))?
+        .as_array()
+        .ok_or_else(|| EmbeddingError::ResponseJsonDecode("Embedding not array".to_string()))?
+        .iter()
+        .map(|v| {
+            v.as_f64()
+                .ok_or_else(|| {
+                    EmbeddingError::ResponseJsonDecode("Embedding value not a number".to_string())
+                })
+                .map(|f| f as f32)
+        })
+        .collect::<Result<Vec<f32>, EmbeddingError>>()?;
+
+    if i32::try_from(vector.len()).ok() != Some(model.dimension()) {
+        return Err(EmbeddingError::ResponseJsonDecode(format!(
+            "Expected a {} dimension embedding from {}, but got {}",
+            model.dimension(),
+            model,
+            vector.len()
+        )));
+    }
+
+    Ok(vector)
+}
+
+/// Says how many stored embeddings a search has to skip because they came
+/// from a different model or size, or explains why none of them can be
+/// searched at all.
+pub fn check_stored_embeddings(
+    model: EmbeddingModel,
+    stored: &[StoredEmbeddingCount],
+) -> Result<i64, String> {
+    let mut compatible = 0;
+    let mut incompatible = Vec::new();
+
+    for count in stored {
+        let is_compatible =
+            count.model == model.to_string() && count.dimension == model.dimension();
+
+        if is_compatible {
+            compatible += count.count;
+        } else {
+            incompatible.push(count);
+        }
+    }
+
+    let skipped = incompatible.iter().map(|count| count.count).sum::<i64>();
+
+    if compatible == 0 && skipped > 0 {
+        let models = incompatible
             .iter()
-            .map(|v| {
-                v.as_f64()
-                    .ok_or_else(|| {
-                        EmbeddingError::ResponseJsonDecode(
-                            "Embedding value not a number".to_string(),
-                        )
-                    })
-                    .map(|f| f as f32)
+            .map(|count| {
+                format!(
+                    "{} memories from {} ({} dimensions)",
+                    count.count, count.model, count.dimension
+                )
             })
-            .collect::<Result<Vec<f32>, EmbeddingError>>()?;
+            .collect::<Vec<String>>()
+            .join(", ");
 
-        Ok(vector)
+        return Err(format!(
+            "None of these memories can be searched with {} ({} dimensions): {}. Re-embed them with the current model first.",
+            model,
+            model.dimension(),
+            models
+        ));
     }
+
+    Ok(skipped)
 }
 
 fn describe_json_decode_failure(
@@ -162,3 +300,39 @@ fn preview_response_body(response_body: &str) -> String {
 
     preview
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stored(model: &str, dimension: i32, count: i64) -> StoredEmbeddingCount {
+        StoredEmbeddingCount {
+            model: model.to_string(),
+            dimension,
+            count,
+        }
+    }
+
+    #[test]
+    fn test_check_stored_embeddings_skips_other_models_when_some_match() {
+        let counts = vec![
+            stored("text-embedding-3-small", 1536, 12),
+            stored("text-embedding-3-large", 3072, 3),
+        ];
+
+        let skipped = check_stored_embeddings(EmbeddingModel::TextEmbedding3Small, &counts);
+
+        assert_eq!(skipped, Ok(3));
+    }
+
+    #[test]
+    fn test_check_stored_embeddings_errors_when_nothing_matches() {
+        let counts = vec![stored("text-embedding-3-large", 3072, 3)];
+
+        assert!(check_stored_embeddings(EmbeddingModel::TextEmbedding3Small, &counts).is_err());
+        assert_eq!(
+            check_stored_embeddings(EmbeddingModel::TextEmbedding3Small, &[]),
+            Ok(0)
+        );
+    }
+}
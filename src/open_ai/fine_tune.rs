@@ -0,0 +1,293 @@
+use crate::nice_display::{with_context, NiceDisplay};
+use crate::open_ai::client::OpenAiClient;
+use crate::open_ai::model::Model;
+use crate::open_ai_key::OpenAiKey;
+use uuid::Uuid;
+
+const FILES_URL: &str = "https://api.openai.com/v1/files";
+const FINE_TUNING_JOBS_URL: &str = "https://api.openai.com/v1/fine_tuning/jobs";
+
+#[derive(Debug, Clone)]
+pub enum FineTuneError {
+    Request(String),
+    Response(String),
+    ResponseJsonDecode(String),
+}
+
+impl NiceDisplay for FineTuneError {
+    fn message(&self) -> String {
+        match self {
+            FineTuneError::Request(err) => {
+                with_context("I had trouble making a fine-tune request to open ai", err)
+            }
+            FineTuneError::Response(err) => with_context(
+                "I had trouble with the fine-tune response from open ai",
+                err,
+            ),
+            FineTuneError::ResponseJsonDecode(err) => with_context(
+                "I had trouble decoding the fine-tune response from open ai",
+                err,
+            ),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FineTuneStatus {
+    ValidatingFiles,
+    Queued,
+    Running,
+    Succeeded,
+    Failed,
+    Cancelled,
+}
+
+impl FineTuneStatus {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "validating_files" => Some(FineTuneStatus::ValidatingFiles),
+            "queued" => Some(FineTuneStatus::Queued),
+            "running" => Some(FineTuneStatus::Running),
+            "succeeded" => Some(FineTuneStatus::Succeeded),
+            "failed" => Some(FineTuneStatus::Failed),
+            "cancelled" => Some(FineTuneStatus::Cancelled),
+            _ => None,
+        }
+    }
+
+    pub fn to_name(&self) -> &'static str {
+        match self {
+            FineTuneStatus::ValidatingFiles => "validating_files",
+            FineTuneStatus::Queued => "queued",
+            FineTuneStatus::Running => "running",
+            FineTuneStatus::Succeeded => "succeeded",
+            FineTuneStatus::Failed => "failed",
+            FineTuneStatus::Cancelled => "cancelled",
+        }
+    }
+
+    pub fn is_terminal(&self) -> bool {
+        match self {
+            FineTuneStatus::Succeeded | FineTuneStatus::Failed | FineTuneStatus::Cancelled => true,
+            FineTuneStatus::ValidatingFiles | FineTuneStatus::Queued | FineTuneStatus::Running => {
+                false
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct FineTuneJob {
+    pub id: String,
+    pub status: FineTuneStatus,
+    pub fine_tuned_model: Option<String>,
+}
+
+impl FineTuneJob {
+    fn from_json(json: &serde_json::Value) -> Result<Self, FineTuneError> {
+        let id = json
+            .get("id")
+            .and_then(|value| value.as_str())
+            .ok_or_else(|| {
+                FineTuneError::ResponseJsonDecode(format!("Missing job id in {}", json))
+            })?
+            .to_string();
+
+        let status_name = json
+            .get("status")
+            .and_then(|value| value.as_str())
+            .ok_or_else(|| {
+                FineTuneError::ResponseJsonDecode(format!("Missing job status in {}", json))
+            })?;
+
+        let status = FineTuneStatus::from_name(status_name).ok_or_else(|| {
+            FineTuneError::ResponseJsonDecode(format!("Unknown job status: {}", status_name))
+        })?;
+
+        let fine_tuned_model = json
+            .get("fine_tuned_model")
+            .and_then(|value| value.as_str())
+            .map(|value| value.to_string());
+
+        Ok(FineTuneJob {
+            id,
+            status,
+            fine_tuned_model,
+        })
+    }
+
+    pub fn to_model(&self) -> Option<Model> {
+        self.fine_tuned_model
+            .as_ref()
+            .map(|model_id| Model::FineTuned(model_id.clone()))
+    }
+}
+
+/// Uploads JSONL training data and returns the OpenAI file id.
+pub async fn upload_training_file(
+    open_ai_key: &OpenAiKey,
+    client: OpenAiClient,
+    file_name: &str,
+    jsonl: &str,
+) -> Result<String, FineTuneError> {
+    let boundary = format!("arizona2-{}", Uuid::now_v7().simple());
+    let body = multipart_body(boundary.as_str(), "fine-tune", file_name, jsonl);
+
+    let _permit = client
+        .acquire()
+        .await
+        .map_err(|err| FineTuneError::Request(err.message()))?;
+
+    let response = client
+        .http()
+        .post(FILES_URL)
+        .header(
+            "Content-Type",
+            format!("multipart/form-data; boundary={}", boundary),
+        )
+        .header("Authorization", open_ai_key.to_header())
+        .body(body)
+        .send()
+        .await
+        .map_err(|err| FineTuneError::Request(err.to_string()))?;
+
+    let json = response_json(response).await?;
+
+    json.get("id")
+        .and_then(|value| value.as_str())
+        .map(|value| value.to_string())
+        .ok_or_else(|| FineTuneError::ResponseJsonDecode(format!("Missing file id in {}", json)))
+}
+
+pub async fn create_job(
+    open_ai_key: &OpenAiKey,
+    client: OpenAiClient,
+    training_file_id: &str,
+    base_model: &Model,
+) -> Result<FineTuneJob, FineTuneError> {
+    let body = serde_json::json!({
+        "training_file": training_file_id,
+        "model": base_model.to_string(),
+    });
+
+    let _permit = client
+        .acquire()
+        .await
+        .map_err(|err| FineTuneError::Request(err.message()))?;
+
+    let response = client
+        .http()
+        .post(FINE_TUNING_JOBS_URL)
+        .header("Content-Type", "application/json")
+        .header("Authorization", open_ai_key.to_header())
+        .json(&body)
+        .send()
+        .await
+        .map_err(|err| FineTuneError::Request(err.to_string()))?;
+
+    let json = response_json(response).await?;
+
+    FineTuneJob::from_json(&json)
+}
+
+pub async fn get_job(
+    open_ai_key: &OpenAiKey,
+    client: OpenAiClient,
+    job_id: &str,
+) -> Result<FineTuneJob, FineTuneError> {
+    let _permit = client
+        .acquire()
+        .await
+        .map_err(|err| FineTuneError::Request(err.message()))?;
+
+    let response = client
+        .http()
+        .get(format!("{}/{}", FINE_TUNING_JOBS_URL, job_id))
+        .header("Authorization", open_ai_key.to_header())
+        .send()
+        .await
+        .map_err(|err| FineTuneError::Request(err.to_string()))?;
+
+    let json = response_json(response).await?;
+
+    FineTuneJob::from_json(&json)
+}
+
+async fn response_json(response: reqwest::Response) -> Result<serde_json::Value, FineTuneError> {
+    let status = response.status();
+    let res = response
+        .text()
+        .await
+        .map_err(|err| FineTuneError::Response(err.to_string()))?;
+
+    if !status.is_success() {
+        return Err(FineTuneError::Response(format!(
+            "open ai returned HTTP {}: {}",
+            status, res
+        )));
+    }
+
+    serde_json::from_str(&res).map_err(|err| FineTuneError::ResponseJsonDecode(err.to_string()))
+}
+
+/// The body of a file upload. `purpose` is what OpenAI will use the file for,
+/// such as "fine-tune" or "batch".
+pub(super) fn multipart_body(
+    boundary: &str,
+    purpose: &str,
+    file_name: &str,
+    jsonl: &str,
+) -> String {
+    format!(
+        "--{boundary}\r\n\
+         Content-Disposition: form-data; name=\"purpose\"\r\n\r\n\
+         {purpose}\r\n\
+         --{boundary}\r\n\
+         Content-Disposition: form-data; name=\"file\"; filename=\"{file_name}\"\r\n\
+         Content-Type: application/jsonl\r\n\r\n\
+         {jsonl}\r\n\
+         --{boundary}--\r\n"
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fine_tune_job_from_json_reads_model_when_succeeded() {
+        let json = serde_json::json!({
+            "id": "ftjob-abc",
+            "status": "succeeded",
+            "fine_tuned_model": "ft:gpt-4o-2024-08-06:org::xyz",
+        });
+
+        let job = FineTuneJob::from_json(&json).unwrap();
+
+        assert_eq!(job.status, FineTuneStatus::Succeeded);
+        assert_eq!(
+            job.to_model().map(|model| model.to_string()),
+            Some("ft:gpt-4o-2024-08-06:org::xyz".to_string())
+        );
+    }
+
+    #[test]
+    fn test_fine_tune_job_from_json_rejects_unknown_status() {
+        let json = serde_json::json!({
+            "id": "ftjob-abc",
+            "status": "exploded",
+            "fine_tuned_model": null,
+        });
+
+        assert!(FineTuneJob::from_json(&json).is_err());
+    }
+
+    #[test]
+    fn test_multipart_body_includes_purpose_and_file() {
+        let body = multipart_body("b", "fine-tune", "data.jsonl", "{}\n");
+
+        assert!(body.contains("name=\"purpose\"\r\n\r\nfine-tune\r\n"));
+        assert!(body.contains("filename=\"data.jsonl\""));
+        assert!(body.ends_with("--b--\r\n"));
+    }
+}
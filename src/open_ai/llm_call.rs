@@ -0,0 +1,57 @@
+use sqlx::{Pool, Postgres};
+use uuid::Uuid;
+
+/// The provider name recorded for calls to OpenAI itself.
+pub const PRIMARY_PROVIDER: &str = "openai";
+
+/// One attempt at a completion, as written to the `llm_call` table.
+#[derive(Debug, Clone)]
+pub struct LlmCall {
+    pub provider: String,
+    pub model: String,
+    /// Counts up across every provider tried for the same completion.
+    pub attempt: i32,
+    pub error: Option<String>,
+    pub duration_ms: i64,
+    pub prompt_tokens: Option<i64>,
+    pub completion_tokens: Option<i64>,
+}
+
+impl LlmCall {
+    /// Token counts from the `usage` field of a response, when it has one.
+    pub fn usage_from_json(json: &serde_json::Value) -> (Option<i64>, Option<i64>) {
+        let usage = json.get("usage");
+        let count = |field: &str| {
+            usage
+                .and_then(|usage| usage.get(field))
+                .and_then(|value| value.as_i64())
+        };
+
+        (count("prompt_tokens"), count("completion_tokens"))
+    }
+}
+
+pub async fn insert(pool: &Pool<Postgres>, call: &LlmCall) -> Result<(), String> {
+    sqlx::query(
+        r#"
+            INSERT INTO llm_call
+                (uuid, provider, model, attempt, succeeded, error, duration_ms, prompt_tokens, completion_tokens)
+            VALUES
+                ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+        "#,
+    )
+    .bind(Uuid::now_v7())
+    .bind(&call.provider)
+    .bind(&call.model)
+    .bind(call.attempt)
+    .bind(call.error.is_none())
+    .bind(&call.error)
+    .bind(call.duration_ms)
+    .bind(call.prompt_tokens)
+    .bind(call.completion_tokens)
+    .execute(pool)
+    .await
+    .map_err(|err| format!("Error recording llm call: {}", err))?;
+
+    Ok(())
+}
@@ -1,14 +1,27 @@
 use std::fmt::Display;
 
 #[allow(dead_code)]
+#[derive(Debug, Clone)]
 pub enum Model {
     Gpt4o,
     Gpt5Mini,
     Gpt5p5,
+    FineTuned(String),
 }
 
 impl Model {
     pub const DEFAULT: Model = Model::Gpt5p5;
+
+    /// The inverse of `to_string`. Ids that are not one of the known models
+    /// are taken to be fine tuned ones.
+    pub fn from_id(id: &str) -> Model {
+        match id.trim() {
+            "gpt-4o-2024-08-06" => Model::Gpt4o,
+            "gpt-5-mini" => Model::Gpt5Mini,
+            "gpt-5.5" => Model::Gpt5p5,
+            other => Model::FineTuned(other.to_string()),
+        }
+    }
 }
 
 impl Display for Model {
@@ -20,6 +33,7 @@ impl Display for Model {
                 Model::Gpt4o => "gpt-4o-2024-08-06".to_string(),
                 Model::Gpt5Mini => "gpt-5-mini".to_string(),
                 Model::Gpt5p5 => "gpt-5.5".to_string(),
+                Model::FineTuned(model_id) => model_id.clone(),
             }
         )
     }
@@ -0,0 +1,151 @@
+use crate::nice_display::{with_context, NiceDisplay};
+use crate::open_ai::client::OpenAiClient;
+use crate::open_ai_key::OpenAiKey;
+
+const MODERATION_MODEL: &str = "omni-moderation-latest";
+
+pub struct ModerationRequest {
+    content: String,
+}
+
+#[derive(Debug, Clone)]
+pub enum ModerationError {
+    Request(String),
+    Response(String),
+    ResponseJsonDecode(String),
+}
+
+impl NiceDisplay for ModerationError {
+    fn message(&self) -> String {
+        match self {
+            ModerationError::Request(err) => {
+                with_context("I had trouble making a moderation request to open ai", err)
+            }
+            ModerationError::Response(err) => with_context(
+                "I had trouble with the moderation response from open ai",
+                err,
+            ),
+            ModerationError::ResponseJsonDecode(err) => with_context(
+                "I had trouble decoding the moderation response from open ai",
+                err,
+            ),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ModerationResult {
+    pub category_scores: Vec<(String, f64)>,
+}
+
+impl ModerationResult {
+    fn from_json(json: &serde_json::Value) -> Result<Self, ModerationError> {
+        let result = json
+            .get("results")
+            .and_then(|results| results.get(0))
+            .ok_or_else(|| {
+                ModerationError::ResponseJsonDecode(format!("Missing results in {}", json))
+            })?;
+
+        let scores_json = result
+            .get("category_scores")
+            .and_then(|value| value.as_object())
+            .ok_or_else(|| {
+                ModerationError::ResponseJsonDecode(format!(
+                    "Missing category_scores in {}",
+                    result
+                ))
+            })?;
+
+        let mut category_scores = Vec::with_capacity(scores_json.len());
+        for (category, score) in scores_json {
+            let score = score.as_f64().ok_or_else(|| {
+                ModerationError::ResponseJsonDecode(format!(
+                    "Score for category {} is not a number",
+                    category
+                ))
+            })?;
+            category_scores.push((category.clone(), score));
+        }
+
+        Ok(ModerationResult { category_scores })
+    }
+}
+
+impl ModerationRequest {
+    pub fn new(content: String) -> Self {
+        Self { content }
+    }
+
+    pub async fn send(
+        &self,
+        open_ai_key: &OpenAiKey,
+        client: OpenAiClient,
+    ) -> Result<ModerationResult, ModerationError> {
+        let body = serde_json::json!({
+            "model": MODERATION_MODEL,
+            "input": self.content,
+        });
+
+        let _permit = client
+            .acquire()
+            .await
+            .map_err(|err| ModerationError::Request(err.message()))?;
+
+        let response = client
+            .http()
+            .post(client.api_url("/moderations"))
+            .header("Content-Type", "application/json")
+            .header("Authorization", open_ai_key.to_header())
+            .json(&body)
+            .send()
+            .await
+            .map_err(|err| ModerationError::Request(err.to_string()))?;
+
+        let status = response.status();
+        let res = response
+            .text()
+            .await
+            .map_err(|err| ModerationError::Response(err.to_string()))?;
+
+        if !status.is_success() {
+            return Err(ModerationError::Response(format!(
+                "open ai returned HTTP {}: {}",
+                status, res
+            )));
+        }
+
+        let res_json: serde_json::Value = serde_json::from_str(&res)
+            .map_err(|err| ModerationError::ResponseJsonDecode(err.to_string()))?;
+
+        ModerationResult::from_json(&res_json)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_moderation_result_from_json_reads_scores() {
+        let json = serde_json::json!({
+            "id": "modr-1",
+            "results": [{
+                "flagged": true,
+                "categories": { "harassment": true, "violence": false },
+                "category_scores": { "harassment": 0.91, "violence": 0.02 },
+            }]
+        });
+
+        let result = ModerationResult::from_json(&json).unwrap();
+
+        assert_eq!(result.category_scores.len(), 2);
+    }
+
+    #[test]
+    fn test_moderation_result_from_json_requires_results() {
+        let json = serde_json::json!({ "id": "modr-1" });
+
+        assert!(ModerationResult::from_json(&json).is_err());
+    }
+}
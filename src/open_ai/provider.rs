@@ -0,0 +1,77 @@
+use crate::open_ai::client::{FailoverConfig, LocalConfig};
+use crate::open_ai::llm_call::PRIMARY_PROVIDER;
+use crate::open_ai_key::OpenAiKey;
+
+/// Where a completion or embedding is sent. Every provider serves the same
+/// OpenAI api under its own base url, so they only differ in where that is,
+/// how to authorize, and which model to ask for.
+#[derive(Debug, Clone, Copy)]
+pub enum Provider<'a> {
+    OpenAi {
+        base_url: &'a str,
+        key: &'a OpenAiKey,
+    },
+    Local(&'a LocalConfig),
+    Failover(&'a FailoverConfig),
+}
+
+impl<'a> Provider<'a> {
+    /// Recorded in the `llm_call` log for every call this provider serves.
+    pub fn name(&self) -> &'a str {
+        match self {
+            Provider::OpenAi { .. } => PRIMARY_PROVIDER,
+            Provider::Local(local) => local.name.as_str(),
+            Provider::Failover(failover) => failover.name.as_str(),
+        }
+    }
+
+    /// How the provider is called in error messages.
+    pub fn label(&self) -> &'a str {
+        match self {
+            Provider::OpenAi { .. } => "open ai",
+            Provider::Local(_) | Provider::Failover(_) => self.name(),
+        }
+    }
+
+    /// Like `http://localhost:8080/v1/embeddings` for `/embeddings`.
+    pub fn url(&self, path: &str) -> String {
+        let base_url = match self {
+            Provider::OpenAi { base_url, .. } => base_url,
+            Provider::Local(local) => local.base_url.as_str(),
+            Provider::Failover(failover) => failover.base_url.as_str(),
+        };
+
+        format!("{}{}", base_url.trim_end_matches('/'), path)
+    }
+
+    pub fn authorization(&self) -> Option<String> {
+        match self {
+            Provider::OpenAi { key, .. } => Some(key.to_header()),
+            Provider::Local(local) => local.key.as_ref().map(|key| key.to_header()),
+            Provider::Failover(failover) => Some(failover.key.to_header()),
+        }
+    }
+
+    /// The chat model to ask for, which local and failover providers can
+    /// replace with what they call theirs.
+    pub fn completion_model(&self, requested: &str) -> String {
+        let replacement = match self {
+            Provider::OpenAi { .. } => None,
+            Provider::Local(local) => local.model.as_ref(),
+            Provider::Failover(failover) => failover.model.as_ref(),
+        };
+
+        match replacement {
+            Some(model) => model.clone(),
+            None => requested.to_string(),
+        }
+    }
+
+    /// Local servers follow an older version of the completions api.
+    pub fn is_local(&self) -> bool {
+        match self {
+            Provider::Local(_) => true,
+            Provider::OpenAi { .. } | Provider::Failover(_) => false,
+        }
+    }
+}
@@ -1,4 +1,5 @@
 #[allow(dead_code)]
+#[derive(Debug, Clone)]
 pub enum Role {
     System,
     User,
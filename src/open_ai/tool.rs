@@ -40,6 +40,20 @@ pub enum ToolFunctionParameter {
         description: String,
         required: bool,
     },
+    ObjectArray {
+        name: String,
+        description: String,
+        required: bool,
+        fields: Vec<ToolFunctionParameter>,
+    },
+    /// An object described by a json schema given as is, for parameters
+    /// whose shape isn't known until runtime.
+    Object {
+        name: String,
+        description: String,
+        required: bool,
+        schema: serde_json::Value,
+    },
 }
 
 impl ToolFunctionParameter {
@@ -49,6 +63,8 @@ impl ToolFunctionParameter {
             ToolFunctionParameter::StringEnum { required, .. } => required,
             ToolFunctionParameter::Integer { required, .. } => required,
             ToolFunctionParameter::StringArray { required, .. } => required,
+            ToolFunctionParameter::ObjectArray { required, .. } => required,
+            ToolFunctionParameter::Object { required, .. } => required,
         }
     }
     pub fn name(&self) -> &str {
@@ -57,6 +73,57 @@ impl ToolFunctionParameter {
             ToolFunctionParameter::StringEnum { name, .. } => name,
             ToolFunctionParameter::Integer { name, .. } => name,
             ToolFunctionParameter::StringArray { name, .. } => name,
+            ToolFunctionParameter::ObjectArray { name, .. } => name,
+            ToolFunctionParameter::Object { name, .. } => name,
+        }
+    }
+
+    fn to_json(&self) -> serde_json::Value {
+        match self {
+            ToolFunctionParameter::String { description, .. } => serde_json::json!({
+                "type": "string",
+                "description": description,
+            }),
+            ToolFunctionParameter::StringEnum {
+                description,
+                values,
+                ..
+            } => serde_json::json!({
+                "type": "string",
+                "description": description,
+                "enum": values,
+            }),
+            ToolFunctionParameter::Integer { description, .. } => serde_json::json!({
+                "type": "integer",
+                "description": description,
+            }),
+            ToolFunctionParameter::StringArray { description, .. } => serde_json::json!({
+                "type": "array",
+                "description": description,
+                "items": {
+                    "type": "string",
+                },
+            }),
+            ToolFunctionParameter::ObjectArray {
+                description,
+                fields,
+                ..
+            } => serde_json::json!({
+                "type": "array",
+                "description": description,
+                "items": object_schema(fields),
+            }),
+            ToolFunctionParameter::Object {
+                description,
+                schema,
+                ..
+            } => {
+                let mut json = schema.clone();
+                if let Some(object) = json.as_object_mut() {
+                    object.insert("description".to_string(), serde_json::json!(description));
+                }
+                json
+            }
         }
     }
 }
@@ -71,77 +138,71 @@ impl Tool {
     pub fn to_json(&self) -> serde_json::Value {
         match self {
             Tool::FunctionCall(func) => {
-                let mut properties = serde_json::json!({});
-
-                for param in &func.parameters {
-                    match param {
-                        ToolFunctionParameter::String {
-                            name,
-                            description,
-                            required: _,
-                        } => {
-                            properties[name] = serde_json::json!({
-                                "type": "string",
-                                "description": description,
-                            });
-                        }
-                        ToolFunctionParameter::StringEnum {
-                            name,
-                            description,
-                            values,
-                            required: _,
-                        } => {
-                            properties[name] = serde_json::json!({
-                                "type": "string",
-                                "description": description,
-                                "enum": values,
-                            });
-                        }
-                        ToolFunctionParameter::Integer {
-                            name,
-                            description,
-                            required: _,
-                        } => {
-                            properties[name] = serde_json::json!({
-                                "type": "integer",
-                                "description": description,
-                            });
-                        }
-                        ToolFunctionParameter::StringArray {
-                            name,
-                            description,
-                            required: _,
-                        } => {
-                            properties[name] = serde_json::json!({
-                                "type": "array",
-                                "description": description,
-                                "items": {
-                                    "type": "string",
-                                },
-                            });
-                        }
-                    }
-                }
-
                 serde_json::json!({
                     "type": "function",
                     "function": {
                         "name": func.name,
                         "description": func.description,
-                        "parameters": serde_json::json!({
-                            "type": "object",
-                            "properties": properties,
-                            "required": func.parameters.iter().filter_map(|param| {
-                                if param.required() {
-                                    Some(param.name())
-                                } else {
-                                    None
-                                }
-                            }).collect::<Vec<_>>(),
-                        }),
+                        "parameters": object_schema(&func.parameters),
                     },
                 })
             }
         }
     }
 }
+
+fn object_schema(parameters: &[ToolFunctionParameter]) -> serde_json::Value {
+    let mut properties = serde_json::json!({});
+
+    for param in parameters {
+        properties[param.name()] = param.to_json();
+    }
+
+    serde_json::json!({
+        "type": "object",
+        "properties": properties,
+        "required": parameters.iter().filter_map(|param| {
+            if param.required() {
+                Some(param.name())
+            } else {
+                None
+            }
+        }).collect::<Vec<_>>(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_object_array_parameter_nests_item_schema() {
+        let tool: Tool = ToolFunction::new(
+            "create_things".to_string(),
+            "Create things.".to_string(),
+            vec![ToolFunctionParameter::ObjectArray {
+                name: "things".to_string(),
+                description: "The things.".to_string(),
+                required: true,
+                fields: vec![ToolFunctionParameter::String {
+                    name: "label".to_string(),
+                    description: "The label.".to_string(),
+                    required: true,
+                }],
+            }],
+        )
+        .into();
+
+        let parameters = &tool.to_json()["function"]["parameters"];
+
+        assert_eq!(parameters["required"], serde_json::json!(["things"]));
+        assert_eq!(
+            parameters["properties"]["things"]["items"]["properties"]["label"]["type"],
+            "string"
+        );
+        assert_eq!(
+            parameters["properties"]["things"]["items"]["required"],
+            serde_json::json!(["label"])
+        );
+    }
+}
@@ -1,4 +1,4 @@
-use crate::nice_display::NiceDisplay;
+use crate::nice_display::{with_context, NiceDisplay};
 
 use super::completion::CompletionError;
 
@@ -40,29 +40,23 @@ impl NiceDisplay for ToolCallDecodeError {
     fn message(&self) -> String {
         match self {
             ToolCallDecodeError::MissingField { field, json } => {
-                format!("Missing field: {}\nHere is the json:\n {}", field, json)
-            }
-            ToolCallDecodeError::FieldWasNotArray { field, json } => {
-                format!(
-                    "Field was not an array: {}\nHere is the json:\n{}",
-                    field, json
-                )
+                with_context(format!("Missing field: {}\nHere is the json", field), json)
             }
+            ToolCallDecodeError::FieldWasNotArray { field, json } => with_context(
+                format!("Field was not an array: {}\nHere is the json", field),
+                json,
+            ),
             ToolCallDecodeError::ArrayWasEmpty { which } => format!("Array was empty: {}", which),
-            ToolCallDecodeError::FieldWasNotString { field, json } => {
-                format!(
-                    "Field was not a string: {}\nHere is the json:\n{}",
-                    field, json
-                )
-            }
-            ToolCallDecodeError::FieldWasNotObject { field, json } => {
-                format!(
-                    "Field was not an object: {}\nHere is the json:\n{}",
-                    field, json
-                )
-            }
+            ToolCallDecodeError::FieldWasNotString { field, json } => with_context(
+                format!("Field was not a string: {}\nHere is the json", field),
+                json,
+            ),
+            ToolCallDecodeError::FieldWasNotObject { field, json } => with_context(
+                format!("Field was not an object: {}\nHere is the json", field),
+                json,
+            ),
             ToolCallDecodeError::CouldParseString(err) => {
-                format!("Could not parse string with serde:\n{}", err)
+                with_context("Could not parse string with serde", err)
             }
         }
     }
@@ -126,21 +120,15 @@ impl ToolCall {
                     })?
                     .to_string();
 
-                let arguments_string = function_call_json
-                    .get("arguments")
-                    .ok_or_else(|| ToolCallDecodeError::MissingField {
-                        field: "arguments".to_string(),
-                        json: function_call_json.clone(),
-                    })?
-                    .as_str()
-                    .ok_or_else(|| ToolCallDecodeError::FieldWasNotString {
+                let arguments_json = function_call_json.get("arguments").ok_or_else(|| {
+                    ToolCallDecodeError::MissingField {
                         field: "arguments".to_string(),
                         json: function_call_json.clone(),
-                    })?;
+                    }
+                })?;
 
                 let arguments: Vec<(String, serde_json::Value)> =
-                    serde_json::from_str::<serde_json::Value>(arguments_string)
-                        .map_err(|err| ToolCallDecodeError::CouldParseString(err.to_string()))?
+                    decode_arguments(arguments_json, function_call_json)?
                         .as_object()
                         .ok_or_else(|| ToolCallDecodeError::FieldWasNotObject {
                             field: "arguments".to_string(),
@@ -155,3 +143,59 @@ impl ToolCall {
             .collect::<Result<Vec<ToolCall>, ToolCallDecodeError>>()
     }
 }
+
+/// OpenAI sends the arguments as a string of JSON. Some local servers send
+/// the object itself, or an empty string when a tool takes no arguments.
+fn decode_arguments(
+    arguments_json: &serde_json::Value,
+    function_call_json: &serde_json::Value,
+) -> Result<serde_json::Value, ToolCallDecodeError> {
+    match arguments_json {
+        serde_json::Value::String(arguments_string) if arguments_string.trim().is_empty() => {
+            Ok(serde_json::json!({}))
+        }
+        serde_json::Value::String(arguments_string) => {
+            serde_json::from_str::<serde_json::Value>(arguments_string)
+                .map_err(|err| ToolCallDecodeError::CouldParseString(err.to_string()))
+        }
+        serde_json::Value::Object(_) => Ok(arguments_json.clone()),
+        _ => Err(ToolCallDecodeError::FieldWasNotString {
+            field: "arguments".to_string(),
+            json: function_call_json.clone(),
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn response_with_arguments(arguments: serde_json::Value) -> serde_json::Value {
+        serde_json::json!({
+            "choices": [{
+                "message": {
+                    "tool_calls": [{
+                        "function": { "name": "say", "arguments": arguments }
+                    }]
+                }
+            }]
+        })
+    }
+
+    #[test]
+    fn test_arguments_can_be_a_string_an_object_or_empty() {
+        let from_string =
+            ToolCall::from_json(&response_with_arguments(serde_json::json!("{\"x\": 1}"))).unwrap();
+        assert_eq!(from_string[0].arguments[0].1, serde_json::json!(1));
+
+        let from_object =
+            ToolCall::from_json(&response_with_arguments(serde_json::json!({ "x": 1 }))).unwrap();
+        assert_eq!(from_object[0].arguments[0].0, "x");
+
+        let from_empty =
+            ToolCall::from_json(&response_with_arguments(serde_json::json!(""))).unwrap();
+        assert!(from_empty[0].arguments.is_empty());
+
+        assert!(ToolCall::from_json(&response_with_arguments(serde_json::json!(3))).is_err());
+    }
+}
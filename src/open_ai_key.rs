@@ -1,10 +1,16 @@
+use crate::nice_display::NiceDisplay;
 use std::env::VarError;
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct OpenAiKey {
     key: String,
 }
 
+/// A worker started against a local server has no key, which is fine until
+/// something has to go to OpenAI itself, like a moderation or a batch.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MissingOpenAiKey;
+
 impl OpenAiKey {
     pub fn from_string(key: String) -> Self {
         OpenAiKey { key }
@@ -18,3 +24,9 @@ impl OpenAiKey {
         format!("Bearer {}", self.key)
     }
 }
+
+impl NiceDisplay for MissingOpenAiKey {
+    fn message(&self) -> String {
+        "This has to be sent to OpenAI, but OPEN_AI_API_KEY is not set".to_string()
+    }
+}
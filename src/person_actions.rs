@@ -1,3 +1,5 @@
+use crate::domain::custom_action::CustomActions;
+use crate::domain::external_tool::ExternalTools;
 use crate::nice_display::NiceDisplay;
 use crate::open_ai::completion::CompletionError;
 use crate::open_ai::tool::{Tool, ToolFunction, ToolFunctionParameter};
@@ -9,9 +11,20 @@ pub enum PersonActionKind {
     Idle,
     GazeInScene,
     SayInScene,
+    Ask,
     MoveToScene,
+    GiveItem,
+    Invite,
+    /// Only offered when the external tools file has tools in it.
+    UseTool,
+    /// Loaded from the custom actions file at startup.
+    Custom(String),
 }
 
+/// How long someone who asks a question waits for an answer when the model
+/// does not say.
+pub const DEFAULT_REPLY_WINDOW_MS: u64 = 2 * 60 * 1000;
+
 #[derive(Debug, Clone)]
 pub enum ReflectionDecision {
     Reflection,
@@ -55,23 +68,87 @@ impl PersonActionKind {
             PersonActionKind::Idle => "idle".to_string(),
             PersonActionKind::GazeInScene => "gaze in scene".to_string(),
             PersonActionKind::SayInScene => "say in scene".to_string(),
+            PersonActionKind::Ask => "ask".to_string(),
             PersonActionKind::MoveToScene => "move to scene".to_string(),
+            PersonActionKind::GiveItem => "give item".to_string(),
+            PersonActionKind::Invite => "invite".to_string(),
+            PersonActionKind::UseTool => "use tool".to_string(),
+            PersonActionKind::Custom(name) => name.clone(),
         }
     }
 
-    pub fn all_action_names() -> Vec<String> {
+    pub fn builtin_action_names() -> Vec<String> {
         vec![
             PersonActionKind::Wait.to_name(),
             PersonActionKind::Hibernate.to_name(),
             PersonActionKind::Idle.to_name(),
             PersonActionKind::GazeInScene.to_name(),
             PersonActionKind::SayInScene.to_name(),
+            PersonActionKind::Ask.to_name(),
             PersonActionKind::MoveToScene.to_name(),
+            PersonActionKind::GiveItem.to_name(),
+            PersonActionKind::Invite.to_name(),
+            PersonActionKind::UseTool.to_name(),
         ]
     }
 
+    pub fn all_action_names(
+        custom_actions: &CustomActions,
+        external_tools: &ExternalTools,
+    ) -> Vec<String> {
+        let use_tool = PersonActionKind::UseTool.to_name();
+        let mut names: Vec<String> = PersonActionKind::builtin_action_names()
+            .into_iter()
+            .filter(|name| *name != use_tool || !external_tools.is_empty())
+            .collect();
+        names.extend(
+            custom_actions
+                .all()
+                .iter()
+                .map(|action| PersonActionKind::Custom(action.name.clone()).to_name()),
+        );
+        names
+    }
+
+    /// The actions for prompts, like "`say in scene`, `ask`, and `idle`".
+    /// Custom actions come after the ones that put words in the scene.
+    pub fn available_actions_text() -> String {
+        let mut names = vec![
+            PersonActionKind::SayInScene.to_name(),
+            PersonActionKind::Ask.to_name(),
+            PersonActionKind::MoveToScene.to_name(),
+            PersonActionKind::GiveItem.to_name(),
+            PersonActionKind::Invite.to_name(),
+        ];
+        names.extend(
+            CustomActions::registered()
+                .all()
+                .iter()
+                .map(|action| action.name.clone()),
+        );
+        if !ExternalTools::registered().is_empty() {
+            names.push(PersonActionKind::UseTool.to_name());
+        }
+        names.extend(vec![
+            PersonActionKind::GazeInScene.to_name(),
+            PersonActionKind::Wait.to_name(),
+            PersonActionKind::Hibernate.to_name(),
+        ]);
+
+        let mut text = names
+            .iter()
+            .map(|name| format!("`{}`, ", name))
+            .collect::<String>();
+        text.push_str(&format!("and `{}`", PersonActionKind::Idle.to_name()));
+        text
+    }
+
     pub fn to_choice_tool() -> Tool {
-        let parameters = vec![
+        PersonActionKind::choice_tool(CustomActions::registered(), ExternalTools::registered())
+    }
+
+    fn choice_tool(custom_actions: &CustomActions, external_tools: &ExternalTools) -> Tool {
+        let mut parameters = vec![
             ToolFunctionParameter::StringEnum {
                 name: "reflection".to_string(),
                 description: "Whether the person should reflect after acting.".to_string(),
@@ -82,7 +159,7 @@ impl PersonActionKind {
                 name: "action".to_string(),
                 description: "The single action to take.".to_string(),
                 required: true,
-                values: PersonActionKind::all_action_names(),
+                values: PersonActionKind::all_action_names(custom_actions, external_tools),
             },
             ToolFunctionParameter::String {
                 name: "comment".to_string(),
@@ -97,9 +174,45 @@ impl PersonActionKind {
                         .to_string(),
                 required: false,
             },
+            ToolFunctionParameter::StringArray {
+                name: "addressed_to".to_string(),
+                description: "Optional names of the people in the scene the comment is meant for if action is say in scene. Leave empty to speak to everyone. Others may overhear unless the comment is whispered.".to_string(),
+                required: false,
+            },
+            ToolFunctionParameter::StringEnum {
+                name: "volume".to_string(),
+                description: "Whether to say the comment aloud or whisper it to the people it is addressed to, if action is say in scene.".to_string(),
+                required: false,
+                values: vec!["aloud".to_string(), "whisper".to_string()],
+            },
+            ToolFunctionParameter::String {
+                name: "recipient_name".to_string(),
+                description: "Who the question is for if action is ask, or who to hand the item to if action is give item; they must be in the current scene. Or who to invite if action is invite; they may be anywhere.".to_string(),
+                required: false,
+            },
+            ToolFunctionParameter::String {
+                name: "item_name".to_string(),
+                description: "Which of the items you are carrying to hand over if action is give item.".to_string(),
+                required: false,
+            },
+            ToolFunctionParameter::String {
+                name: "question".to_string(),
+                description: "What to ask if action is ask, spoken aloud and addressed to the recipient.".to_string(),
+                required: false,
+            },
+            ToolFunctionParameter::String {
+                name: "quote".to_string(),
+                description: "Optional exact words from earlier in the scene that the comment or question is about, if action is ask or say in scene with addressed_to. Copy them as they were said.".to_string(),
+                required: false,
+            },
+            ToolFunctionParameter::Integer {
+                name: "reply_window".to_string(),
+                description: "How long to wait for an answer in milliseconds if action is ask.".to_string(),
+                required: false,
+            },
             ToolFunctionParameter::String {
                 name: "scene_name".to_string(),
-                description: "Scene name to move to if action is move to scene.".to_string(),
+                description: "Scene name to move to if action is move to scene, or to invite the recipient to if action is invite. Leave it out to invite them to the current scene.".to_string(),
                 required: false,
             },
             ToolFunctionParameter::Integer {
@@ -111,10 +224,49 @@ impl PersonActionKind {
             },
         ];
 
+        let mut description = "Choose a single action for the person. Only one action is allowed. Use idle when the person decides to do nothing. Use hibernate for long, uninterrupted sleep. If action is say in scene, the comment should resemble natural speech rather than a document or list. You may also provide destination_scene_name to leave right after speaking. Use addressed_to to speak to particular people, and whisper when others should not overhear. Use ask instead of say in scene when putting a question to one specific person and expecting them to answer. Use give item to hand something you are carrying to someone in the scene. Use invite to ask someone who is elsewhere to come to a scene; they decide whether to come."
+            .to_string();
+
+        for action in custom_actions.all() {
+            parameters.push(ToolFunctionParameter::Object {
+                name: action.arguments_parameter_name(),
+                description: format!("Arguments if action is {}.", action.name),
+                required: false,
+                schema: action.parameters.clone(),
+            });
+            description.push_str(&format!(" Use {}: {}", action.name, action.description));
+        }
+
+        if !external_tools.is_empty() {
+            parameters.push(ToolFunctionParameter::StringEnum {
+                name: "tool_name".to_string(),
+                description: "Which tool to use if action is use tool.".to_string(),
+                required: false,
+                values: external_tools
+                    .all()
+                    .iter()
+                    .map(|tool| tool.name.clone())
+                    .collect(),
+            });
+            description.push_str(" Use use tool to look something up or work something out with a tool, and you will see what it returns. The tools are:");
+
+            for tool in external_tools.all() {
+                parameters.push(ToolFunctionParameter::Object {
+                    name: tool.arguments_parameter_name(),
+                    description: format!(
+                        "Arguments if action is use tool and tool_name is {}.",
+                        tool.name
+                    ),
+                    required: false,
+                    schema: tool.parameters.clone(),
+                });
+                description.push_str(&format!(" {}: {}", tool.name, tool.description));
+            }
+        }
+
         Tool::FunctionCall(ToolFunction::new(
             "choose_action".to_string(),
-            "Choose a single action for the person. Only one action is allowed. Use idle when the person decides to do nothing. Use hibernate for long, uninterrupted sleep. If action is say in scene, the comment should resemble natural speech rather than a document or list. You may also provide destination_scene_name to leave right after speaking."
-                .to_string(),
+            description,
             parameters,
         ))
     }
@@ -133,10 +285,43 @@ pub enum PersonAction {
     SayInScene {
         comment: String,
         destination_scene_name: Option<String>,
+        /// Empty when the comment is for everyone in the scene.
+        addressed_to: Vec<String>,
+        whisper: bool,
+        /// Words from an earlier scene message the comment is about. Only
+        /// attached when the comment is addressed to someone.
+        quote: Option<String>,
+    },
+    Ask {
+        recipient_name: String,
+        question: String,
+        reply_window_ms: u64,
+        quote: Option<String>,
     },
     MoveToScene {
         scene_name: String,
     },
+    GiveItem {
+        item_name: String,
+        recipient_name: String,
+    },
+    UseTool {
+        tool_name: String,
+        /// What the model filled into the tool's json schema. An empty
+        /// object when it left them out.
+        arguments: serde_json::Value,
+    },
+    Invite {
+        recipient_name: String,
+        /// None for the scene the person is in.
+        scene_name: Option<String>,
+    },
+    Custom {
+        name: String,
+        /// What the model filled into the action's json schema. An empty
+        /// object when it left them out.
+        arguments: serde_json::Value,
+    },
 }
 
 impl PersonAction {
@@ -153,15 +338,48 @@ impl PersonAction {
             PersonAction::SayInScene {
                 comment,
                 destination_scene_name,
-            } => match destination_scene_name {
-                Some(scene_name) => {
-                    format!("Spoke in scene then left for {}: {}", scene_name, comment)
+                addressed_to,
+                whisper,
+                ..
+            } => {
+                let verb = match (addressed_to.is_empty(), whisper) {
+                    (true, _) => "Spoke in scene".to_string(),
+                    (false, true) => format!("Whispered to {}", addressed_to.join(", ")),
+                    (false, false) => format!("Spoke to {}", addressed_to.join(", ")),
+                };
+                match destination_scene_name {
+                    Some(scene_name) => {
+                        format!("{} then left for {}: {}", verb, scene_name, comment)
+                    }
+                    None => format!("{}: {}", verb, comment),
                 }
-                None => format!("Spoke in scene: {}", comment),
-            },
+            }
+            PersonAction::Ask {
+                recipient_name,
+                question,
+                ..
+            } => format!("Asked {}: {}", recipient_name, question),
             PersonAction::MoveToScene { scene_name } => {
                 format!("Moved to scene: {}", scene_name)
             }
+            PersonAction::GiveItem {
+                item_name,
+                recipient_name,
+            } => format!("Gave {} to {}", item_name, recipient_name),
+            PersonAction::Invite {
+                recipient_name,
+                scene_name,
+            } => match scene_name {
+                Some(scene_name) => format!("Invited {} to {}", recipient_name, scene_name),
+                None => format!("Invited {} over", recipient_name),
+            },
+            PersonAction::UseTool {
+                tool_name,
+                arguments,
+            } => format!("Used the tool {}: {}", tool_name, arguments),
+            PersonAction::Custom { name, arguments } => {
+                format!("Took the action {}: {}", name, arguments)
+            }
         }
     }
 }
@@ -240,6 +458,18 @@ impl NiceDisplay for PersonActionError {
 
 impl PersonReaction {
     pub fn from_open_ai_tool_call(tool_call: ToolCall) -> Result<Self, PersonActionError> {
+        PersonReaction::from_tool_call(
+            tool_call,
+            CustomActions::registered(),
+            ExternalTools::registered(),
+        )
+    }
+
+    fn from_tool_call(
+        tool_call: ToolCall,
+        custom_actions: &CustomActions,
+        external_tools: &ExternalTools,
+    ) -> Result<Self, PersonActionError> {
         let tool_call_name = tool_call.name;
         if tool_call_name.as_str() != "choose_action" {
             return Err(PersonActionError::UnrecognizedAction {
@@ -256,6 +486,16 @@ impl PersonReaction {
         let mut maybe_destination_scene_name: Option<String> = None;
         let mut maybe_scene_name: Option<String> = None;
         let mut maybe_duration: Option<u64> = None;
+        let mut maybe_recipient_name: Option<String> = None;
+        let mut maybe_question: Option<String> = None;
+        let mut maybe_reply_window: Option<u64> = None;
+        let mut maybe_quote: Option<String> = None;
+        let mut maybe_item_name: Option<String> = None;
+        let mut addressed_to: Vec<String> = Vec::new();
+        let mut whisper = false;
+        let mut maybe_tool_name: Option<String> = None;
+        let mut custom_arguments: Vec<(String, serde_json::Value)> = Vec::new();
+        let mut tool_arguments: Vec<(String, serde_json::Value)> = Vec::new();
 
         for (key, value) in arguments {
             match key.as_str() {
@@ -271,9 +511,58 @@ impl PersonReaction {
                 "destination_scene_name" => {
                     maybe_destination_scene_name = normalized_non_empty_string(&value);
                 }
+                "addressed_to" => {
+                    let names =
+                        value
+                            .as_array()
+                            .ok_or_else(|| PersonActionError::UnexpectedType {
+                                action_name: tool_call_name.clone(),
+                                parameter_name: "addressed_to".to_string(),
+                                wanted_type: "array of strings".to_string(),
+                            })?;
+                    addressed_to = names
+                        .iter()
+                        .filter_map(normalized_non_empty_string)
+                        .collect();
+                }
+                "volume" => match value.as_str().map(str::trim) {
+                    Some("aloud") | None => whisper = false,
+                    Some("whisper") => whisper = true,
+                    Some(_) => Err(PersonActionError::UnexpectedType {
+                        action_name: tool_call_name.clone(),
+                        parameter_name: "volume".to_string(),
+                        wanted_type: "\"aloud\" or \"whisper\"".to_string(),
+                    })?,
+                },
                 "scene_name" => {
                     maybe_scene_name = normalized_non_empty_string(&value);
                 }
+                "recipient_name" => {
+                    maybe_recipient_name = normalized_non_empty_string(&value);
+                }
+                "question" => {
+                    maybe_question = normalized_non_empty_string(&value);
+                }
+                "quote" => {
+                    maybe_quote = normalized_non_empty_string(&value);
+                }
+                "item_name" => {
+                    maybe_item_name = normalized_non_empty_string(&value);
+                }
+                "tool_name" => {
+                    maybe_tool_name = normalized_non_empty_string(&value);
+                }
+                "reply_window" => {
+                    if let Some(window) = value.as_u64() {
+                        maybe_reply_window = Some(window);
+                    } else {
+                        Err(PersonActionError::UnexpectedType {
+                            action_name: tool_call_name.clone(),
+                            parameter_name: "reply_window".to_string(),
+                            wanted_type: "u64".to_string(),
+                        })?
+                    }
+                }
                 "duration" => {
                     if let Some(dur) = value.as_u64() {
                         maybe_duration = Some(dur);
@@ -285,6 +574,12 @@ impl PersonReaction {
                         })?
                     }
                 }
+                _ if custom_actions.find_by_arguments_parameter(&key).is_some() => {
+                    custom_arguments.push((key, value));
+                }
+                _ if external_tools.find_by_arguments_parameter(&key).is_some() => {
+                    tool_arguments.push((key, value));
+                }
                 _ => {
                     Err(PersonActionError::UnrecognizedParameter {
                         action_name: tool_call_name.clone(),
@@ -312,6 +607,29 @@ impl PersonReaction {
                 PersonAction::SayInScene {
                     comment,
                     destination_scene_name: maybe_destination_scene_name,
+                    addressed_to,
+                    whisper,
+                    quote: maybe_quote,
+                }
+            }
+            "ask" => {
+                let recipient_name =
+                    maybe_recipient_name.ok_or_else(|| PersonActionError::ParameterMissing {
+                        action_name: tool_call_name.clone(),
+                        parameter_name: "recipient_name".to_string(),
+                        arguments: arguments_json.clone(),
+                    })?;
+                let question =
+                    maybe_question.ok_or_else(|| PersonActionError::ParameterMissing {
+                        action_name: tool_call_name.clone(),
+                        parameter_name: "question".to_string(),
+                        arguments: arguments_json.clone(),
+                    })?;
+                PersonAction::Ask {
+                    recipient_name,
+                    question,
+                    reply_window_ms: maybe_reply_window.unwrap_or(DEFAULT_REPLY_WINDOW_MS),
+                    quote: maybe_quote,
                 }
             }
             "wait" => {
@@ -343,9 +661,97 @@ impl PersonReaction {
                     })?;
                 PersonAction::MoveToScene { scene_name }
             }
-            _ => Err(PersonActionError::UnrecognizedAction {
-                action_name: action,
-            })?,
+            "give item" => {
+                let item_name =
+                    maybe_item_name.ok_or_else(|| PersonActionError::ParameterMissing {
+                        action_name: tool_call_name.clone(),
+                        parameter_name: "item_name".to_string(),
+                        arguments: arguments_json.clone(),
+                    })?;
+                let recipient_name =
+                    maybe_recipient_name.ok_or_else(|| PersonActionError::ParameterMissing {
+                        action_name: tool_call_name.clone(),
+                        parameter_name: "recipient_name".to_string(),
+                        arguments: arguments_json.clone(),
+                    })?;
+                PersonAction::GiveItem {
+                    item_name,
+                    recipient_name,
+                }
+            }
+            "invite" => {
+                let recipient_name =
+                    maybe_recipient_name.ok_or_else(|| PersonActionError::ParameterMissing {
+                        action_name: tool_call_name.clone(),
+                        parameter_name: "recipient_name".to_string(),
+                        arguments: arguments_json.clone(),
+                    })?;
+                PersonAction::Invite {
+                    recipient_name,
+                    scene_name: maybe_scene_name,
+                }
+            }
+            "use tool" if !external_tools.is_empty() => {
+                let tool_name =
+                    maybe_tool_name.ok_or_else(|| PersonActionError::ParameterMissing {
+                        action_name: tool_call_name.clone(),
+                        parameter_name: "tool_name".to_string(),
+                        arguments: arguments_json.clone(),
+                    })?;
+                let tool = external_tools.find(&tool_name).ok_or_else(|| {
+                    PersonActionError::UnexpectedType {
+                        action_name: tool_call_name.clone(),
+                        parameter_name: "tool_name".to_string(),
+                        wanted_type: "one of the listed tools".to_string(),
+                    }
+                })?;
+
+                let parameter_name = tool.arguments_parameter_name();
+                let arguments = tool_arguments
+                    .into_iter()
+                    .find(|(key, _)| *key == parameter_name)
+                    .map(|(_, value)| value)
+                    .unwrap_or_else(|| serde_json::json!({}));
+
+                if !arguments.is_object() {
+                    Err(PersonActionError::UnexpectedType {
+                        action_name: tool_call_name.clone(),
+                        parameter_name,
+                        wanted_type: "object".to_string(),
+                    })?
+                }
+
+                PersonAction::UseTool {
+                    tool_name: tool.name.clone(),
+                    arguments,
+                }
+            }
+            _ => match custom_actions.find(&action) {
+                Some(custom_action) => {
+                    let parameter_name = custom_action.arguments_parameter_name();
+                    let arguments = custom_arguments
+                        .into_iter()
+                        .find(|(key, _)| *key == parameter_name)
+                        .map(|(_, value)| value)
+                        .unwrap_or_else(|| serde_json::json!({}));
+
+                    if !arguments.is_object() {
+                        Err(PersonActionError::UnexpectedType {
+                            action_name: tool_call_name.clone(),
+                            parameter_name,
+                            wanted_type: "object".to_string(),
+                        })?
+                    }
+
+                    PersonAction::Custom {
+                        name: custom_action.name.clone(),
+                        arguments,
+                    }
+                }
+                None => Err(PersonActionError::UnrecognizedAction {
+                    action_name: action,
+                })?,
+            },
         };
 
         Ok(PersonReaction { action, reflection })
@@ -481,4 +887,208 @@ mod tests {
             other => panic!("unexpected action: {:?}", other),
         }
     }
+
+    #[test]
+    fn test_ask_action_defaults_reply_window() {
+        let reaction = PersonReaction::from_open_ai_tool_call(choose_action_call(vec![
+            ("action".to_string(), json!("ask")),
+            ("recipient_name".to_string(), json!(" Bob ")),
+            ("question".to_string(), json!("Bob, did you lock the door?")),
+        ]))
+        .unwrap();
+
+        match reaction.action {
+            PersonAction::Ask {
+                recipient_name,
+                reply_window_ms,
+                ..
+            } => {
+                assert_eq!(recipient_name, "Bob");
+                assert_eq!(reply_window_ms, DEFAULT_REPLY_WINDOW_MS);
+            }
+            other => panic!("unexpected action: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_say_in_scene_reads_addressed_names_and_whisper() {
+        let reaction = PersonReaction::from_open_ai_tool_call(choose_action_call(vec![
+            ("action".to_string(), json!("say in scene")),
+            ("comment".to_string(), json!("Meet me out back.")),
+            ("addressed_to".to_string(), json!([" Bob ", ""])),
+            ("volume".to_string(), json!("whisper")),
+        ]))
+        .unwrap();
+
+        match reaction.action {
+            PersonAction::SayInScene {
+                addressed_to,
+                whisper,
+                ..
+            } => {
+                assert_eq!(addressed_to, vec!["Bob".to_string()]);
+                assert!(whisper);
+            }
+            other => panic!("unexpected action: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_give_item_needs_an_item_and_a_recipient() {
+        let reaction = PersonReaction::from_open_ai_tool_call(choose_action_call(vec![
+            ("action".to_string(), json!("give item")),
+            ("item_name".to_string(), json!(" the letter ")),
+            ("recipient_name".to_string(), json!("Bob")),
+        ]))
+        .unwrap();
+
+        match reaction.action {
+            PersonAction::GiveItem {
+                item_name,
+                recipient_name,
+            } => {
+                assert_eq!(item_name, "the letter");
+                assert_eq!(recipient_name, "Bob");
+            }
+            other => panic!("unexpected action: {:?}", other),
+        }
+
+        let err = PersonReaction::from_open_ai_tool_call(choose_action_call(vec![
+            ("action".to_string(), json!("give item")),
+            ("recipient_name".to_string(), json!("Bob")),
+        ]))
+        .unwrap_err();
+
+        match err {
+            PersonActionError::ParameterMissing { parameter_name, .. } => {
+                assert_eq!(parameter_name, "item_name");
+            }
+            other => panic!("unexpected error: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_invite_defaults_to_the_current_scene() {
+        let reaction = PersonReaction::from_open_ai_tool_call(choose_action_call(vec![
+            ("action".to_string(), json!("invite")),
+            ("recipient_name".to_string(), json!(" Bob ")),
+            ("scene_name".to_string(), json!("")),
+        ]))
+        .unwrap();
+
+        match reaction.action {
+            PersonAction::Invite {
+                recipient_name,
+                scene_name,
+            } => {
+                assert_eq!(recipient_name, "Bob");
+                assert_eq!(scene_name, None);
+            }
+            other => panic!("unexpected action: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_custom_action_reads_its_arguments() {
+        let custom_actions = CustomActions::parse(
+            r#"[{
+                "name": "pick lock",
+                "description": "Try to pick the lock on something.",
+                "parameters": { "type": "object", "properties": { "target": { "type": "string" } } },
+                "handler": { "type": "webhook", "url": "http://localhost:9000/actions" }
+            }]"#,
+            &PersonActionKind::builtin_action_names(),
+        )
+        .unwrap();
+
+        let reaction = PersonReaction::from_tool_call(
+            choose_action_call(vec![
+                ("action".to_string(), json!("pick lock")),
+                (
+                    "pick_lock_arguments".to_string(),
+                    json!({ "target": "door" }),
+                ),
+            ]),
+            &custom_actions,
+            &ExternalTools::default(),
+        )
+        .unwrap();
+
+        match reaction.action {
+            PersonAction::Custom { name, arguments } => {
+                assert_eq!(name, "pick lock");
+                assert_eq!(arguments, json!({ "target": "door" }));
+            }
+            other => panic!("unexpected action: {:?}", other),
+        }
+
+        let err = PersonReaction::from_tool_call(
+            choose_action_call(vec![("action".to_string(), json!("pick lock"))]),
+            &CustomActions::default(),
+            &ExternalTools::default(),
+        )
+        .unwrap_err();
+
+        match err {
+            PersonActionError::UnrecognizedAction { action_name } => {
+                assert_eq!(action_name, "pick lock");
+            }
+            other => panic!("unexpected error: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_use_tool_reads_the_named_tools_arguments() {
+        let external_tools = ExternalTools::parse(
+            r#"[{
+                "name": "weather",
+                "description": "Look up the current weather in a city.",
+                "parameters": { "type": "object", "properties": { "city": { "type": "string" } } },
+                "url": "http://localhost:9000/weather"
+            }]"#,
+        )
+        .unwrap();
+
+        let reaction = PersonReaction::from_tool_call(
+            choose_action_call(vec![
+                ("action".to_string(), json!("use tool")),
+                ("tool_name".to_string(), json!(" weather ")),
+                (
+                    "weather_tool_arguments".to_string(),
+                    json!({ "city": "Tucson" }),
+                ),
+            ]),
+            &CustomActions::default(),
+            &external_tools,
+        )
+        .unwrap();
+
+        match reaction.action {
+            PersonAction::UseTool {
+                tool_name,
+                arguments,
+            } => {
+                assert_eq!(tool_name, "weather");
+                assert_eq!(arguments, json!({ "city": "Tucson" }));
+            }
+            other => panic!("unexpected action: {:?}", other),
+        }
+
+        let err = PersonReaction::from_tool_call(
+            choose_action_call(vec![
+                ("action".to_string(), json!("use tool")),
+                ("tool_name".to_string(), json!("weather")),
+            ]),
+            &CustomActions::default(),
+            &ExternalTools::default(),
+        )
+        .unwrap_err();
+
+        match err {
+            PersonActionError::UnrecognizedAction { action_name } => {
+                assert_eq!(action_name, "use tool");
+            }
+            other => panic!("unexpected error: {:?}", other),
+        }
+    }
 }
@@ -1,3 +1,33 @@
+pub mod backfill_embeddings;
+
+pub mod doctor;
+
+pub mod export_autobiography;
+
+pub mod export_training_data;
+
+pub mod fine_tune_persona;
+
+pub mod generate_cast;
+
+pub mod import_transcript;
+
+pub mod items;
+
+pub mod kickoff_scene;
+
+pub mod persons;
+
+pub mod run_report;
+
+pub mod smoke_test;
+
+pub mod start_run;
+
 pub mod summarize_memories_v2;
 
 pub mod summarize_person_identities;
+
+pub mod tail;
+
+pub mod tenant;
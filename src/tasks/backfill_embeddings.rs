@@ -0,0 +1,111 @@
+use crate::capability::embedding_backfill::EmbeddingBackfillCapability;
+use crate::domain::embedding_backfill::BackfillProgress;
+use crate::domain::logger::{Level, Logger};
+use crate::nice_display::{nest, with_context, NiceDisplay};
+use crate::worker;
+use crate::worker::{ProcessRole, Worker};
+
+pub const DEFAULT_BATCH_SIZE: i64 = 50;
+
+pub enum Error {
+    WorkerInit(worker::InitError),
+    CountMemories(String),
+    GetMemories(String),
+    BatchSize(i64),
+}
+
+impl NiceDisplay for Error {
+    fn message(&self) -> String {
+        match self {
+            Error::WorkerInit(err) => nest("Worker initialization failed", err),
+            Error::CountMemories(err) => {
+                with_context("Failed to count the memories missing embeddings", err)
+            }
+            Error::GetMemories(err) => {
+                with_context("Failed to fetch the memories missing embeddings", err)
+            }
+            Error::BatchSize(batch_size) => {
+                format!("The batch size must be at least 1, got {}", batch_size)
+            }
+        }
+    }
+}
+
+pub async fn run(batch_size: i64, dry_run: bool) -> Result<(), Error> {
+    if batch_size < 1 {
+        return Err(Error::BatchSize(batch_size));
+    }
+
+    let logger = Logger::init(Level::Warning);
+    let worker = Worker::new(logger, ProcessRole::Task)
+        .await
+        .map_err(Error::WorkerInit)?;
+
+    let total = worker
+        .count_memories_missing_embeddings()
+        .await
+        .map_err(Error::CountMemories)?;
+
+    if total == 0 {
+        println!("Every memory has an embedding");
+        return Ok(());
+    }
+
+    if dry_run {
+        println!(
+            "{} memories are missing embeddings, or have all zero or wrongly sized ones",
+            total
+        );
+        return Ok(());
+    }
+
+    let progress = backfill(&worker, total, batch_size).await?;
+
+    println!("Done. {}", progress.to_line());
+    Ok(())
+}
+
+/// Re-embeds a batch at a time, printing progress after each. A memory
+/// that fails is reported and skipped, so one bad row does not stop the
+/// rest, and a later run picks it up again.
+pub async fn backfill<W: EmbeddingBackfillCapability>(
+    worker: &W,
+    total: i64,
+    batch_size: i64,
+) -> Result<BackfillProgress, Error> {
+    let mut progress = BackfillProgress {
+        total,
+        ..BackfillProgress::default()
+    };
+    let mut after = None;
+
+    loop {
+        let memories = worker
+            .get_memories_missing_embeddings(after.as_ref(), batch_size)
+            .await
+            .map_err(Error::GetMemories)?;
+
+        if memories.is_empty() {
+            break;
+        }
+
+        for memory in memories.iter() {
+            match worker.re_embed_memory(memory).await {
+                Ok(()) => progress.embedded += 1,
+                Err(err) => {
+                    progress.failed += 1;
+                    println!(
+                        "Could not embed memory {}: {}",
+                        memory.memory_uuid.to_uuid(),
+                        err
+                    );
+                }
+            }
+        }
+
+        after = memories.last().map(|memory| memory.memory_uuid.clone());
+        println!("{}", progress.to_line());
+    }
+
+    Ok(progress)
+}
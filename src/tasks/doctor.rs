@@ -0,0 +1,293 @@
+use crate::capability_metrics::chaos::FaultInjector;
+use crate::db;
+use crate::domain::doctor::{self, Check, CheckStatus};
+use crate::domain::fan_out::FanOutPolicy;
+use crate::domain::pause_policy::PausePolicy;
+use crate::migrations;
+use crate::nice_display::NiceDisplay;
+use crate::open_ai::client::ClientConfig;
+use crate::open_ai_key::OpenAiKey;
+use crate::worker::{ConnectRetry, PoolSizes};
+use sqlx::postgres::PgPoolOptions;
+use sqlx::{PgPool, Row};
+use std::time::Duration;
+
+const OPEN_AI_MODELS_URL: &str = "https://api.openai.com/v1/models";
+
+/// Doctor should answer quickly, even when something is down.
+const CHECK_TIMEOUT: Duration = Duration::from_secs(10);
+
+pub enum Error {
+    ChecksFailed(usize),
+}
+
+impl NiceDisplay for Error {
+    fn message(&self) -> String {
+        match self {
+            Error::ChecksFailed(failed) => {
+                format!(
+                    "Doctor found {} failing checks, see the report above",
+                    failed
+                )
+            }
+        }
+    }
+}
+
+/// Checks the environment a long run depends on and prints a report. Fails
+/// when any check fails, so it can gate scripts.
+pub async fn run() -> Result<(), Error> {
+    let mut checks = vec![check_required_vars(), check_settings()];
+
+    if let Some(chaos) = check_chaos() {
+        checks.push(chaos);
+    }
+
+    match connect().await {
+        Ok((pool, details)) => {
+            checks.push(Check::pass("database", details));
+            checks.push(check_pgvector(&pool).await);
+            checks.push(check_migrations(&pool).await);
+            checks.push(check_pool_sizes(&pool).await);
+        }
+        Err(err) => {
+            checks.push(Check::fail("database", err));
+            checks.push(Check::skipped("pgvector", "needs the database"));
+            checks.push(Check::skipped("migrations", "needs the database"));
+            checks.push(Check::skipped("pool sizes", "needs the database"));
+        }
+    }
+
+    checks.push(check_open_ai_key().await);
+
+    println!("{}", doctor::render_report(&checks));
+
+    match doctor::count(&checks, CheckStatus::Fail) {
+        0 => Ok(()),
+        failed => Err(Error::ChecksFailed(failed)),
+    }
+}
+
+fn check_required_vars() -> Check {
+    let missing: Vec<&str> = doctor::REQUIRED_VARS
+        .iter()
+        .filter(|var_name| {
+            dotenv::var(var_name)
+                .map(|value| value.trim().is_empty())
+                .unwrap_or(true)
+        })
+        .copied()
+        .collect();
+
+    if missing.is_empty() {
+        Check::pass("environment", "all required variables are set")
+    } else {
+        Check::fail("environment", format!("missing {}", missing.join(", ")))
+    }
+}
+
+/// The optional settings only complain once something reads them, which
+/// could be hours into a run.
+fn check_settings() -> Check {
+    let mut problems = Vec::new();
+
+    if let Err(err) = ClientConfig::load() {
+        problems.push(err.message());
+    }
+    if let Err(err) = ConnectRetry::load() {
+        problems.push(err.message());
+    }
+    if let Err(err) = PoolSizes::load() {
+        problems.push(err.message());
+    }
+    if let Err(err) = PausePolicy::load() {
+        problems.push(err);
+    }
+    if let Err(err) = FanOutPolicy::load() {
+        problems.push(err);
+    }
+    if let Err(err) = FaultInjector::from_env() {
+        problems.push(err);
+    }
+
+    if problems.is_empty() {
+        Check::pass("settings", "optional settings are valid")
+    } else {
+        Check::fail("settings", problems.join("; "))
+    }
+}
+
+fn check_chaos() -> Option<Check> {
+    match FaultInjector::from_env() {
+        Ok(Some(chaos)) => Some(Check::warn(
+            "chaos",
+            format!(
+                "chaos mode fails {:.1}% of capability calls on purpose",
+                chaos.rate() * 100.0
+            ),
+        )),
+        _ => None,
+    }
+}
+
+async fn connect() -> Result<(PgPool, String), String> {
+    let config = db::Config::load().await.map_err(|err| err.message())?;
+    let database_name = config.database_name();
+    let url = config.url(database_name.as_str());
+
+    let pool = PgPoolOptions::new()
+        .max_connections(1)
+        .acquire_timeout(CHECK_TIMEOUT)
+        .connect(&url)
+        .await
+        .map_err(|err| {
+            if db::is_missing_database(&err) {
+                config.missing_database_message(database_name.as_str())
+            } else {
+                format!(
+                    "could not connect to {} at {}: {}",
+                    database_name, config.host, err
+                )
+            }
+        })?;
+
+    let row = sqlx::query("SHOW server_version")
+        .fetch_one(&pool)
+        .await
+        .map_err(|err| format!("connected, but could not query: {}", err))?;
+    let version = row
+        .try_get::<String, _>("server_version")
+        .map_err(|err| format!("Error reading server_version: {}", err))?;
+
+    let details = format!(
+        "connected to {} at {} (Postgres {})",
+        database_name, config.host, version
+    );
+
+    Ok((pool, details))
+}
+
+async fn check_pgvector(pool: &PgPool) -> Check {
+    let result = sqlx::query("SELECT extversion FROM pg_extension WHERE extname = 'vector'")
+        .fetch_optional(pool)
+        .await;
+
+    match result {
+        Ok(Some(row)) => match row.try_get::<String, _>("extversion") {
+            Ok(version) => Check::pass("pgvector", format!("version {}", version)),
+            Err(err) => Check::fail("pgvector", format!("Error reading extversion: {}", err)),
+        },
+        Ok(None) => Check::fail(
+            "pgvector",
+            "the vector extension is not installed, memories cannot be searched",
+        ),
+        Err(err) => Check::fail("pgvector", err.to_string()),
+    }
+}
+
+async fn check_migrations(pool: &PgPool) -> Check {
+    let migration_names = match migrations::migration_names() {
+        Ok(names) => names,
+        Err(err) => return Check::fail("migrations", err.message()),
+    };
+
+    let is_recorded = sqlx::query("SELECT to_regclass('schema_migration') IS NOT NULL AS exists")
+        .fetch_one(pool)
+        .await
+        .and_then(|row| row.try_get::<bool, _>("exists"));
+
+    match is_recorded {
+        Ok(true) => {}
+        Ok(false) => {
+            return Check::warn(
+                "migrations",
+                "none recorded yet, run run-migrations once to start recording them",
+            )
+        }
+        Err(err) => return Check::fail("migrations", err.to_string()),
+    }
+
+    let applied = sqlx::query("SELECT name FROM schema_migration")
+        .fetch_all(pool)
+        .await
+        .and_then(|rows| {
+            rows.iter()
+                .map(|row| row.try_get::<String, _>("name"))
+                .collect::<Result<Vec<String>, _>>()
+        });
+
+    let applied = match applied {
+        Ok(applied) => applied,
+        Err(err) => return Check::fail("migrations", err.to_string()),
+    };
+
+    let pending = doctor::pending_migrations(&migration_names, &applied);
+    match pending.first() {
+        None => Check::pass(
+            "migrations",
+            format!(
+                "up to date at {}",
+                migration_names.last().map(String::as_str).unwrap_or("-")
+            ),
+        ),
+        Some(first) => Check::fail(
+            "migrations",
+            format!(
+                "{} pending, starting with {}. Run run-migrations",
+                pending.len(),
+                first
+            ),
+        ),
+    }
+}
+
+async fn check_pool_sizes(pool: &PgPool) -> Check {
+    // An invalid setting is already reported by the settings check
+    let pool_sizes = PoolSizes::load().unwrap_or_default();
+
+    match db::available_connections(pool).await {
+        Ok(available) => match pool_sizes.over_limit_warning(available) {
+            None => Check::pass(
+                "pool sizes",
+                format!(
+                    "{} of {} connections",
+                    pool_sizes.admin_ui + pool_sizes.server + pool_sizes.runner + pool_sizes.task,
+                    available
+                ),
+            ),
+            Some(warning) => Check::warn("pool sizes", warning),
+        },
+        Err(err) => Check::fail("pool sizes", err.to_string()),
+    }
+}
+
+/// Listing models is free, so it is a cheap way to see the key works.
+async fn check_open_ai_key() -> Check {
+    let key = match OpenAiKey::from_env() {
+        Ok(key) => key,
+        Err(_) => return Check::skipped("openai", "needs OPEN_AI_API_KEY"),
+    };
+
+    let client = match reqwest::Client::builder().timeout(CHECK_TIMEOUT).build() {
+        Ok(client) => client,
+        Err(err) => return Check::fail("openai", err.to_string()),
+    };
+
+    let response = client
+        .get(OPEN_AI_MODELS_URL)
+        .header("Authorization", key.to_header())
+        .send()
+        .await;
+
+    match response {
+        Ok(response) if response.status().is_success() => Check::pass("openai", "the key works"),
+        Ok(response) if response.status() == reqwest::StatusCode::UNAUTHORIZED => {
+            Check::fail("openai", "the key was rejected")
+        }
+        Ok(response) => Check::fail(
+            "openai",
+            format!("listing models returned HTTP {}", response.status()),
+        ),
+        Err(err) => Check::fail("openai", format!("could not reach OpenAI: {}", err)),
+    }
+}
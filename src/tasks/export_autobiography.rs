@@ -0,0 +1,73 @@
+use crate::capability::person::PersonCapability;
+use crate::domain::autobiography;
+use crate::domain::logger::{Level, Logger};
+use crate::domain::person_name::PersonName;
+use crate::domain::person_uuid::PersonUuid;
+use crate::nice_display::{nest, with_context, NiceDisplay};
+use crate::worker;
+use crate::worker::{ProcessRole, Worker};
+
+pub enum Error {
+    WorkerInit(worker::InitError),
+    GetPerson(String),
+    Write(autobiography::Error),
+    FileWrite(std::io::Error),
+}
+
+impl NiceDisplay for Error {
+    fn message(&self) -> String {
+        match self {
+            Error::WorkerInit(err) => nest("Worker initialization failed", err),
+            Error::GetPerson(err) => with_context("Failed to find the person", err),
+            Error::Write(err) => nest("Failed to write the autobiography", err),
+            Error::FileWrite(err) => with_context("Failed to write the autobiography file", err),
+        }
+    }
+}
+
+/// Where an autobiography goes when no path is given.
+pub fn default_output_path(person_name: &str) -> String {
+    format!(
+        "{}-autobiography.md",
+        person_name.trim().to_lowercase().replace(' ', "-")
+    )
+}
+
+pub async fn run(person_name: String, output_path: Option<String>) -> Result<(), Error> {
+    let logger = Logger::init(Level::Info);
+    let worker = Worker::new(logger, ProcessRole::Task)
+        .await
+        .map_err(Error::WorkerInit)?;
+
+    let person_uuid = worker
+        .get_person_uuid_by_name(PersonName::from_string(person_name.clone()))
+        .await
+        .map_err(Error::GetPerson)?;
+
+    let output_path = output_path.unwrap_or_else(|| default_output_path(person_name.as_str()));
+
+    let chapter_count = export(&worker, &person_uuid, output_path.as_str()).await?;
+
+    println!(
+        "Wrote {}'s autobiography in {} chapters to {}",
+        person_name, chapter_count, output_path
+    );
+
+    Ok(())
+}
+
+/// Writes the person's autobiography to `output_path` as markdown, and
+/// returns how many chapters it has.
+pub async fn export(
+    worker: &Worker,
+    person_uuid: &PersonUuid,
+    output_path: &str,
+) -> Result<usize, Error> {
+    let autobiography = autobiography::write(worker, person_uuid)
+        .await
+        .map_err(Error::Write)?;
+
+    std::fs::write(output_path, autobiography.to_markdown()).map_err(Error::FileWrite)?;
+
+    Ok(autobiography.chapters.len())
+}
@@ -0,0 +1,62 @@
+use crate::capability::reaction_context::ReactionContextCapability;
+use crate::domain::fine_tune_example::FineTuneExample;
+use crate::domain::logger::{Level, Logger};
+use crate::nice_display::{nest, with_context, NiceDisplay};
+use crate::worker;
+use crate::worker::{ProcessRole, Worker};
+use std::fs::File;
+use std::io::Write;
+
+pub enum Error {
+    WorkerInit(worker::InitError),
+    FetchReactionContexts(String),
+    Serialize(serde_json::Error),
+    FileCreation(std::io::Error),
+    FileWrite(std::io::Error),
+}
+
+impl NiceDisplay for Error {
+    fn message(&self) -> String {
+        match self {
+            Error::WorkerInit(err) => nest("Worker initialization failed", err),
+            Error::FetchReactionContexts(err) => {
+                with_context("Failed to fetch good reaction contexts", err)
+            }
+            Error::Serialize(err) => with_context("Failed to serialize training data", err),
+            Error::FileCreation(err) => with_context("Failed to create training data file", err),
+            Error::FileWrite(err) => with_context("Failed to write training data file", err),
+        }
+    }
+}
+
+pub async fn run(output_path: String) -> Result<(), Error> {
+    let logger = Logger::init(Level::Info);
+    let worker = Worker::new(logger, ProcessRole::Task)
+        .await
+        .map_err(Error::WorkerInit)?;
+
+    let count = export(&worker, output_path.as_str()).await?;
+
+    println!("Exported {} training examples to {}", count, output_path);
+
+    Ok(())
+}
+
+pub async fn export(worker: &Worker, output_path: &str) -> Result<usize, Error> {
+    let reaction_contexts = worker
+        .get_good_reaction_contexts()
+        .await
+        .map_err(Error::FetchReactionContexts)?;
+
+    let examples = reaction_contexts
+        .iter()
+        .map(FineTuneExample::from_reaction_context)
+        .collect::<Vec<FineTuneExample>>();
+
+    let jsonl = FineTuneExample::many_to_jsonl(&examples).map_err(Error::Serialize)?;
+
+    let mut file = File::create(output_path).map_err(Error::FileCreation)?;
+    file.write_all(jsonl.as_bytes()).map_err(Error::FileWrite)?;
+
+    Ok(examples.len())
+}
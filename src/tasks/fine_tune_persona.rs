@@ -0,0 +1,118 @@
+use crate::capability::fine_tune::FineTuneCapability;
+use crate::capability::person::PersonCapability;
+use crate::domain::logger::{Level, Logger};
+use crate::domain::person_name::PersonName;
+use crate::domain::person_uuid::PersonUuid;
+use crate::nice_display::{nest, with_context, NiceDisplay};
+use crate::open_ai::fine_tune::{self, FineTuneError};
+use crate::open_ai::model::Model;
+use crate::open_ai_key::MissingOpenAiKey;
+use crate::worker;
+use crate::worker::{ProcessRole, Worker};
+use std::path::Path;
+use std::time::Duration;
+
+const POLL_INTERVAL: Duration = Duration::from_secs(30);
+
+pub enum Error {
+    WorkerInit(worker::InitError),
+    MissingOpenAiKey(MissingOpenAiKey),
+    ReadTrainingFile(std::io::Error),
+    PersonLookup(String),
+    FineTune(FineTuneError),
+    Registry(String),
+    JobDidNotSucceed { job_id: String, status: String },
+}
+
+impl NiceDisplay for Error {
+    fn message(&self) -> String {
+        match self {
+            Error::WorkerInit(err) => nest("Worker initialization failed", err),
+            Error::MissingOpenAiKey(err) => nest("Fine tuning only works at OpenAI", err),
+            Error::ReadTrainingFile(err) => with_context("Failed to read training file", err),
+            Error::PersonLookup(err) => with_context("Failed to find person", err),
+            Error::FineTune(err) => err.message(),
+            Error::Registry(err) => with_context("Failed to register fine-tuned model", err),
+            Error::JobDidNotSucceed { job_id, status } => {
+                format!("Fine-tune job {} finished with status {}", job_id, status)
+            }
+        }
+    }
+}
+
+pub async fn run(training_file_path: String, person_name: Option<String>) -> Result<(), Error> {
+    let logger = Logger::init(Level::Info);
+    let worker = Worker::new(logger, ProcessRole::Task)
+        .await
+        .map_err(Error::WorkerInit)?;
+    let open_ai_key = worker
+        .require_open_ai_key()
+        .map_err(Error::MissingOpenAiKey)?;
+
+    let person_uuid: Option<PersonUuid> = match person_name {
+        Some(name) => Some(
+            worker
+                .get_person_uuid_by_name(PersonName::from_string(name))
+                .await
+                .map_err(Error::PersonLookup)?,
+        ),
+        None => None,
+    };
+
+    let jsonl = std::fs::read_to_string(&training_file_path).map_err(Error::ReadTrainingFile)?;
+    let file_name = Path::new(&training_file_path)
+        .file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or("training_data.jsonl");
+
+    let file_id = fine_tune::upload_training_file(
+        open_ai_key,
+        worker.open_ai_client.clone(),
+        file_name,
+        jsonl.as_str(),
+    )
+    .await
+    .map_err(Error::FineTune)?;
+    println!("Uploaded training file {}", file_id);
+
+    let base_model = Model::Gpt4o;
+    let mut job = fine_tune::create_job(
+        open_ai_key,
+        worker.open_ai_client.clone(),
+        file_id.as_str(),
+        &base_model,
+    )
+    .await
+    .map_err(Error::FineTune)?;
+
+    worker
+        .record_fine_tune_job(&job, &base_model, person_uuid.as_ref())
+        .await
+        .map_err(Error::Registry)?;
+    println!("Created fine-tune job {}", job.id);
+
+    while !job.status.is_terminal() {
+        tokio::time::sleep(POLL_INTERVAL).await;
+
+        job = fine_tune::get_job(open_ai_key, worker.open_ai_client.clone(), job.id.as_str())
+            .await
+            .map_err(Error::FineTune)?;
+
+        worker
+            .update_fine_tune_job(&job)
+            .await
+            .map_err(Error::Registry)?;
+        println!("Fine-tune job {} is {}", job.id, job.status.to_name());
+    }
+
+    match job.to_model() {
+        Some(model) => {
+            println!("Registered fine-tuned model {}", model);
+            Ok(())
+        }
+        None => Err(Error::JobDidNotSucceed {
+            job_id: job.id,
+            status: job.status.to_name().to_string(),
+        }),
+    }
+}
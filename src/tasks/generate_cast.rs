@@ -0,0 +1,52 @@
+use crate::capability::persona::PersonaCapability;
+use crate::domain::cast;
+use crate::domain::logger::{Level, Logger};
+use crate::nice_display::{nest, with_context, NiceDisplay};
+use crate::worker;
+use crate::worker::{ProcessRole, Worker};
+
+pub enum Error {
+    WorkerInit(worker::InitError),
+    Generate(String),
+    Create(cast::Error),
+}
+
+impl NiceDisplay for Error {
+    fn message(&self) -> String {
+        match self {
+            Error::WorkerInit(err) => nest("Worker initialization failed", err),
+            Error::Generate(err) => with_context("Failed to generate cast", err),
+            Error::Create(err) => err.message(),
+        }
+    }
+}
+
+pub async fn run(concept: String, count: usize) -> Result<(), Error> {
+    let logger = Logger::init(Level::Info);
+    let worker = Worker::new(logger, ProcessRole::Task)
+        .await
+        .map_err(Error::WorkerInit)?;
+
+    let generated = worker
+        .generate_cast(concept.as_str(), count)
+        .await
+        .map_err(Error::Generate)?;
+
+    let relationship_count = generated.relationships.len();
+
+    let created = cast::create_cast(&worker, generated)
+        .await
+        .map_err(Error::Create)?;
+
+    for (person_name, person_uuid) in created.iter() {
+        println!("Created {} ({})", person_name, person_uuid);
+    }
+
+    println!(
+        "Created {} people and {} relationships",
+        created.len(),
+        relationship_count
+    );
+
+    Ok(())
+}
@@ -0,0 +1,69 @@
+use crate::domain::logger::{Level, Logger};
+use crate::domain::transcript_import::{self, Import};
+use crate::nice_display::{nest, with_context, NiceDisplay};
+use crate::worker;
+use crate::worker::{ProcessRole, Worker};
+use std::collections::HashMap;
+
+pub enum Error {
+    ReadFile(std::io::Error),
+    WorkerInit(worker::InitError),
+    Import(transcript_import::Error),
+}
+
+impl NiceDisplay for Error {
+    fn message(&self) -> String {
+        match self {
+            Error::ReadFile(err) => with_context("Failed to read the transcript", err),
+            Error::WorkerInit(err) => nest("Worker initialization failed", err),
+            Error::Import(err) => err.message(),
+        }
+    }
+}
+
+pub async fn run(
+    transcript_path: String,
+    scene_name: String,
+    mappings: Vec<String>,
+    no_memories: bool,
+) -> Result<(), Error> {
+    let text = std::fs::read_to_string(&transcript_path).map_err(Error::ReadFile)?;
+
+    let speaker_map = mappings
+        .iter()
+        .map(|mapping| transcript_import::parse_mapping(mapping))
+        .collect::<Result<HashMap<_, _>, _>>()
+        .map_err(Error::Import)?;
+
+    let logger = Logger::init(Level::Info);
+    let worker = Worker::new(logger, ProcessRole::Task)
+        .await
+        .map_err(Error::WorkerInit)?;
+
+    let imported = transcript_import::import_transcript(
+        &worker,
+        Import {
+            scene_name: scene_name.clone(),
+            lines: transcript_import::parse_transcript(text.as_str()),
+            speaker_map,
+            create_memories: !no_memories,
+        },
+    )
+    .await
+    .map_err(Error::Import)?;
+
+    for person_name in imported.created_persons.iter() {
+        println!("Created {}", person_name);
+    }
+
+    for person_name in imported.existing_persons.iter() {
+        println!("Used existing person {}", person_name);
+    }
+
+    println!(
+        "Replayed {} lines into {} ({}) and created {} memories",
+        imported.message_count, scene_name, imported.scene_uuid, imported.memory_count
+    );
+
+    Ok(())
+}
@@ -0,0 +1,126 @@
+use crate::capability::item::{ItemCapability, NewItem};
+use crate::capability::person::PersonCapability;
+use crate::capability::scene::SceneCapability;
+use crate::domain::item::{Item, ItemLocation};
+use crate::domain::logger::{Level, Logger};
+use crate::domain::person_name::PersonName;
+use crate::nice_display::{nest, with_context, NiceDisplay};
+use crate::worker;
+use crate::worker::{ProcessRole, Worker};
+use clap::{Args, Subcommand};
+
+#[derive(Debug, Subcommand, Clone)]
+pub enum Command {
+    /// List what a person is carrying, or what lies in a scene.
+    List {
+        #[clap(flatten)]
+        location: LocationArgs,
+    },
+    /// Put a new item in a person's hands or in a scene.
+    Add {
+        name: String,
+        /// What the item looks like, shown to the persons who carry it
+        #[clap(long, default_value = "")]
+        description: String,
+        #[clap(flatten)]
+        location: LocationArgs,
+    },
+}
+
+#[derive(Debug, Args, Clone)]
+pub struct LocationArgs {
+    /// The person carrying the items
+    #[clap(long, conflicts_with = "scene", required_unless_present = "scene")]
+    person: Option<String>,
+    /// The scene the items lie in
+    #[clap(long)]
+    scene: Option<String>,
+}
+
+pub enum Error {
+    WorkerInit(worker::InitError),
+    GetPerson(String),
+    GetScene(String),
+    SceneNotFound(String),
+    NoLocation,
+    Query(String),
+    Create(String),
+}
+
+impl NiceDisplay for Error {
+    fn message(&self) -> String {
+        match self {
+            Error::WorkerInit(err) => nest("Worker initialization failed", err),
+            Error::GetPerson(err) => with_context("Failed to look up person", err),
+            Error::GetScene(err) => with_context("Failed to look up scene", err),
+            Error::SceneNotFound(scene_name) => format!("No scene named \"{}\"", scene_name),
+            Error::NoLocation => "Pass --person or --scene".to_string(),
+            Error::Query(err) => with_context("Failed to list items", err),
+            Error::Create(err) => with_context("Failed to add item", err),
+        }
+    }
+}
+
+pub async fn run(command: Command) -> Result<(), Error> {
+    let logger = Logger::init(Level::Info);
+    let worker = Worker::new(logger, ProcessRole::Task)
+        .await
+        .map_err(Error::WorkerInit)?;
+
+    match command {
+        Command::List { location } => {
+            let items = match load_location(&worker, &location).await? {
+                ItemLocation::CarriedBy(person_uuid) => {
+                    worker.get_items_carried_by(&person_uuid).await
+                }
+                ItemLocation::InScene(scene_uuid) => worker.get_items_in_scene(&scene_uuid).await,
+            }
+            .map_err(Error::Query)?;
+
+            println!("{}", Item::many_to_list_text(&items));
+        }
+        Command::Add {
+            name,
+            description,
+            location,
+        } => {
+            let location = load_location(&worker, &location).await?;
+
+            let item_uuid = worker
+                .create_item(NewItem {
+                    name: name.clone(),
+                    description,
+                    location,
+                })
+                .await
+                .map_err(Error::Create)?;
+
+            println!("Added \"{}\" ({})", name, item_uuid.to_uuid());
+        }
+    }
+
+    Ok(())
+}
+
+async fn load_location(worker: &Worker, location: &LocationArgs) -> Result<ItemLocation, Error> {
+    match (&location.person, &location.scene) {
+        (Some(person_name), _) => {
+            let person_uuid = worker
+                .get_person_uuid_by_name(PersonName::from_string(person_name.clone()))
+                .await
+                .map_err(Error::GetPerson)?;
+
+            Ok(ItemLocation::CarriedBy(person_uuid))
+        }
+        (None, Some(scene_name)) => {
+            let scene = worker
+                .get_scene_from_name(scene_name.clone())
+                .await
+                .map_err(Error::GetScene)?
+                .ok_or_else(|| Error::SceneNotFound(scene_name.clone()))?;
+
+            Ok(ItemLocation::InScene(scene.uuid))
+        }
+        (None, None) => Err(Error::NoLocation),
+    }
+}
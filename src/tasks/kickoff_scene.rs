@@ -0,0 +1,84 @@
+use crate::capability::job_runner_settings::JobRunnerSettingsCapability;
+use crate::capability::scene::SceneCapability;
+use crate::domain::job::send_message_to_scene::ReactionStagger;
+use crate::domain::logger::{Level, Logger};
+use crate::domain::random_seed::RandomSeed;
+use crate::domain::scene_kickoff::{self, Kickoff};
+use crate::nice_display::{nest, with_context, NiceDisplay};
+use crate::worker;
+use crate::worker::{ProcessRole, Worker};
+
+pub enum Error {
+    WorkerInit(worker::InitError),
+    BlankOpener,
+    GetScene(String),
+    SceneNotFound(String),
+    ActiveClock(String),
+    Kickoff(scene_kickoff::Error),
+}
+
+impl NiceDisplay for Error {
+    fn message(&self) -> String {
+        match self {
+            Error::WorkerInit(err) => nest("Worker initialization failed", err),
+            Error::BlankOpener => "The opener cannot be blank".to_string(),
+            Error::GetScene(err) => with_context("Failed to look up scene", err),
+            Error::SceneNotFound(scene_name) => format!("No scene named \"{}\"", scene_name),
+            Error::ActiveClock(err) => with_context("Failed to read the active clock", err),
+            Error::Kickoff(err) => err.message(),
+        }
+    }
+}
+
+pub async fn run(scene_name: String, opener: String, stagger_secs: u64) -> Result<(), Error> {
+    if opener.trim().is_empty() {
+        return Err(Error::BlankOpener);
+    }
+
+    let logger = Logger::init(Level::Info);
+    let worker = Worker::new(logger, ProcessRole::Task)
+        .await
+        .map_err(Error::WorkerInit)?;
+
+    let scene = worker
+        .get_scene_from_name(scene_name.clone())
+        .await
+        .map_err(Error::GetScene)?
+        .ok_or_else(|| Error::SceneNotFound(scene_name.clone()))?;
+
+    let start_active_ms = worker
+        .get_active_clock_ms()
+        .await
+        .map_err(Error::ActiveClock)?;
+
+    let step_ms = i64::try_from(stagger_secs.saturating_mul(1000)).unwrap_or(i64::MAX);
+
+    let person_names = scene_kickoff::kickoff_scene(
+        &worker,
+        Kickoff {
+            scene_uuid: scene.uuid,
+            opener,
+            stagger: ReactionStagger {
+                start_active_ms,
+                step_ms,
+            },
+            random_seed: RandomSeed::from_u64(rand::random()),
+        },
+    )
+    .await
+    .map_err(Error::Kickoff)?;
+
+    println!(
+        "Kicked off {} with {} participants, {} seconds apart: {}",
+        scene_name,
+        person_names.len(),
+        stagger_secs,
+        person_names
+            .iter()
+            .map(|person_name| person_name.as_str())
+            .collect::<Vec<&str>>()
+            .join(", ")
+    );
+
+    Ok(())
+}
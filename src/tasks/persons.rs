@@ -0,0 +1,252 @@
+use crate::capability::person::PersonCapability;
+use crate::capability::person_query::{PersonQueryCapability, PersonSummary};
+use crate::capability::person_tag::PersonTagCapability;
+use crate::capability::scene::SceneCapability;
+use crate::domain::logger::{Level, Logger};
+use crate::domain::person_filter::{self, PersonFilter, PersonTag};
+use crate::domain::topic::Topic;
+use crate::nice_display::{nest, with_context, NiceDisplay};
+use crate::time_display;
+use crate::worker;
+use crate::worker::{ProcessRole, Worker};
+use clap::{Args, Subcommand};
+
+#[derive(Debug, Subcommand, Clone)]
+pub enum Command {
+    /// List the persons matching the filter.
+    List {
+        #[clap(flatten)]
+        filter: FilterArgs,
+    },
+    /// Hibernate every person matching the filter.
+    Hibernate {
+        #[clap(flatten)]
+        filter: FilterArgs,
+    },
+    /// Wake every person matching the filter from hibernation.
+    Wake {
+        #[clap(flatten)]
+        filter: FilterArgs,
+    },
+    /// Tag every person matching the filter.
+    Tag {
+        tag: String,
+        #[clap(flatten)]
+        filter: FilterArgs,
+    },
+    /// Remove a tag from every person matching the filter.
+    Untag {
+        tag: String,
+        #[clap(flatten)]
+        filter: FilterArgs,
+    },
+}
+
+#[derive(Debug, Args, Clone)]
+pub struct FilterArgs {
+    /// Only persons currently in this scene
+    #[clap(long)]
+    scene: Option<String>,
+    /// Only persons with this tag
+    #[clap(long)]
+    tag: Option<String>,
+    /// Only persons who have not reacted for this long, like 1h
+    #[clap(long)]
+    idle: Option<String>,
+    /// Only persons with scene messages they have not handled
+    #[clap(long)]
+    unread: bool,
+    /// Only persons who were in a scene that talked about this topic
+    #[clap(long)]
+    topic: Option<String>,
+    /// Let a change apply to every person when no filter is given
+    #[clap(long)]
+    all: bool,
+}
+
+pub enum Error {
+    WorkerInit(worker::InitError),
+    InvalidTag(String),
+    InvalidIdle(String),
+    InvalidTopic(String),
+    GetScene(String),
+    SceneNotFound(String),
+    NoFilter,
+    Query(String),
+    Update(String),
+}
+
+impl NiceDisplay for Error {
+    fn message(&self) -> String {
+        match self {
+            Error::WorkerInit(err) => nest("Worker initialization failed", err),
+            Error::InvalidTag(err) => err.clone(),
+            Error::InvalidIdle(err) => err.clone(),
+            Error::InvalidTopic(err) => err.clone(),
+            Error::GetScene(err) => with_context("Failed to look up scene", err),
+            Error::SceneNotFound(scene_name) => format!("No scene named \"{}\"", scene_name),
+            Error::NoFilter => "No filter given, pass --all to change every person".to_string(),
+            Error::Query(err) => with_context("Failed to query persons", err),
+            Error::Update(err) => with_context("Failed to update persons", err),
+        }
+    }
+}
+
+pub async fn run(command: Command) -> Result<(), Error> {
+    let logger = Logger::init(Level::Info);
+    let worker = Worker::new(logger, ProcessRole::Task)
+        .await
+        .map_err(Error::WorkerInit)?;
+
+    match command {
+        Command::List { filter } => {
+            let filter = load_filter(&worker, &filter).await?;
+            let persons = worker.query_persons(&filter).await.map_err(Error::Query)?;
+
+            if persons.is_empty() {
+                println!("No persons match");
+            }
+
+            for person in &persons {
+                println!("{}", summary_line(person));
+            }
+        }
+        Command::Hibernate { filter } => {
+            let persons = matching_persons(&worker, &filter).await?;
+
+            for person in &persons {
+                worker
+                    .set_person_hibernating(&person.person_uuid, true)
+                    .await
+                    .map_err(Error::Update)?;
+            }
+
+            println!("Hibernated {} persons", persons.len());
+        }
+        Command::Wake { filter } => {
+            let persons = matching_persons(&worker, &filter).await?;
+
+            for person in &persons {
+                worker
+                    .set_person_hibernating(&person.person_uuid, false)
+                    .await
+                    .map_err(Error::Update)?;
+            }
+
+            println!("Woke {} persons", persons.len());
+        }
+        Command::Tag { tag, filter } => {
+            let tag = PersonTag::parse(&tag).map_err(Error::InvalidTag)?;
+            let persons = matching_persons(&worker, &filter).await?;
+
+            for person in &persons {
+                worker
+                    .add_person_tag(&person.person_uuid, &tag)
+                    .await
+                    .map_err(Error::Update)?;
+            }
+
+            println!("Tagged {} persons \"{}\"", persons.len(), tag.as_str());
+        }
+        Command::Untag { tag, filter } => {
+            let tag = PersonTag::parse(&tag).map_err(Error::InvalidTag)?;
+            let persons = matching_persons(&worker, &filter).await?;
+
+            for person in &persons {
+                worker
+                    .remove_person_tag(&person.person_uuid, &tag)
+                    .await
+                    .map_err(Error::Update)?;
+            }
+
+            println!(
+                "Removed \"{}\" from {} persons",
+                tag.as_str(),
+                persons.len()
+            );
+        }
+    }
+
+    Ok(())
+}
+
+/// The persons a bulk change applies to. An empty filter needs `--all`, so
+/// a forgotten flag does not change the whole world.
+async fn matching_persons(
+    worker: &Worker,
+    filter_args: &FilterArgs,
+) -> Result<Vec<PersonSummary>, Error> {
+    let filter = load_filter(worker, filter_args).await?;
+
+    if filter.is_empty() && !filter_args.all {
+        return Err(Error::NoFilter);
+    }
+
+    worker.query_persons(&filter).await.map_err(Error::Query)
+}
+
+async fn load_filter(worker: &Worker, filter_args: &FilterArgs) -> Result<PersonFilter, Error> {
+    let scene_uuid = match &filter_args.scene {
+        Some(scene_name) => Some(
+            worker
+                .get_scene_from_name(scene_name.clone())
+                .await
+                .map_err(Error::GetScene)?
+                .ok_or_else(|| Error::SceneNotFound(scene_name.clone()))?
+                .uuid,
+        ),
+        None => None,
+    };
+
+    let tag = match &filter_args.tag {
+        Some(tag) => Some(PersonTag::parse(tag).map_err(Error::InvalidTag)?),
+        None => None,
+    };
+
+    let idle_for = match &filter_args.idle {
+        Some(idle) => Some(person_filter::parse_duration(idle).map_err(Error::InvalidIdle)?),
+        None => None,
+    };
+
+    let topic = match &filter_args.topic {
+        Some(topic) => Some(Topic::parse(topic).map_err(Error::InvalidTopic)?),
+        None => None,
+    };
+
+    Ok(PersonFilter {
+        scene_uuid,
+        tag,
+        idle_for,
+        has_unread_messages: filter_args.unread,
+        topic,
+    })
+}
+
+fn summary_line(person: &PersonSummary) -> String {
+    let mut details = Vec::new();
+
+    if let Some(scene_name) = &person.scene_name {
+        details.push(format!("in {}", scene_name));
+    }
+    if !person.is_enabled {
+        details.push("disabled".to_string());
+    }
+    if person.is_hibernating {
+        details.push("hibernating".to_string());
+    }
+    match person.last_reacted_at {
+        Some(last_reacted_at) => details.push(format!(
+            "last reacted {}",
+            time_display::format_recent(last_reacted_at)
+        )),
+        None => details.push("never reacted".to_string()),
+    }
+    if person.unread_messages > 0 {
+        details.push(format!("{} unread", person.unread_messages));
+    }
+    if !person.tags.is_empty() {
+        details.push(format!("tags: {}", person.tags.join(", ")));
+    }
+
+    format!("{}  {}", person.name.as_str(), details.join(", "))
+}
@@ -0,0 +1,54 @@
+use crate::domain::logger::{Level, Logger};
+use crate::domain::run_report::{ReportFormat, RunReport};
+use crate::nice_display::{nest, with_context, NiceDisplay};
+use crate::worker;
+use crate::worker::{ProcessRole, Worker};
+use chrono::Utc;
+use std::fs::File;
+use std::io::Write;
+
+pub enum Error {
+    WorkerInit(worker::InitError),
+    Load(String),
+    FileCreation(std::io::Error),
+    FileWrite(std::io::Error),
+}
+
+impl NiceDisplay for Error {
+    fn message(&self) -> String {
+        match self {
+            Error::WorkerInit(err) => nest("Worker initialization failed", err),
+            Error::Load(err) => with_context("Failed to gather the run report", err),
+            Error::FileCreation(err) => with_context("Failed to create run report file", err),
+            Error::FileWrite(err) => with_context("Failed to write run report file", err),
+        }
+    }
+}
+
+/// Writes a report of the current run to `output_path`, as HTML if it ends
+/// in `.html` and Markdown otherwise.
+pub async fn run(output_path: String) -> Result<(), Error> {
+    let logger = Logger::init(Level::Info);
+    let worker = Worker::new(logger, ProcessRole::Task)
+        .await
+        .map_err(Error::WorkerInit)?;
+
+    let report = RunReport::load(&worker, Utc::now())
+        .await
+        .map_err(Error::Load)?;
+
+    let document = report.render(ReportFormat::from_path(output_path.as_str()));
+
+    let mut file = File::create(&output_path).map_err(Error::FileCreation)?;
+    file.write_all(document.as_bytes())
+        .map_err(Error::FileWrite)?;
+
+    println!(
+        "Wrote a report of {} scenes and {} persons to {}",
+        report.scenes.len(),
+        report.cast.len(),
+        output_path
+    );
+
+    Ok(())
+}
@@ -0,0 +1,353 @@
+use crate::capability::job::JobCapability;
+use crate::capability::message::MessageCapability;
+use crate::capability::person::{NewPerson, PersonCapability};
+use crate::capability::person_identity::{NewPersonIdentity, PersonIdentityCapability};
+use crate::capability::scene::{NewScene, SceneCapability};
+use crate::capability::state_of_mind::{NewStateOfMind, StateOfMindCapability};
+use crate::db;
+use crate::domain::job::send_message_to_scene::SendMessageToSceneJob;
+use crate::domain::job::JobKind;
+use crate::domain::logger::{Level, Logger};
+use crate::domain::message::MessageSender;
+use crate::domain::person_identity_uuid::PersonIdentityUuid;
+use crate::domain::person_name::PersonName;
+use crate::domain::person_uuid::PersonUuid;
+use crate::domain::scene_uuid::SceneUuid;
+use crate::domain::smoke_test;
+use crate::domain::state_of_mind_uuid::StateOfMindUuid;
+use crate::job_runner::{self, RunNextJobResult};
+use crate::migrations;
+use crate::nice_display::{nest, with_context, NiceDisplay};
+use crate::open_ai::client::{ClientConfig, ClientConfigError};
+use crate::open_ai::embedding::EmbeddingModel;
+use crate::open_ai_key::OpenAiKey;
+use crate::worker;
+use crate::worker::{PoolSizes, ProcessRole, Worker};
+use actix_web::dev::ServerHandle;
+use actix_web::{web, App, HttpResponse, HttpServer};
+use sqlx::postgres::PgPoolOptions;
+
+/// Every cluster has it, so the smoke test database can be made and dropped
+/// from it without the app's own database existing.
+const MAINTENANCE_DATABASE: &str = "postgres";
+/// The send and the reaction are two jobs. Anything past this is a job
+/// queueing more jobs, which the smoke test should not wait out.
+const MAX_JOBS: usize = 20;
+const PERSON_NAME: &str = "Smokey";
+const SCENE_NAME: &str = "Smoke Test";
+
+pub enum Error {
+    DbConfig(db::ConfigError),
+    ConnectMaintenance(sqlx::Error),
+    CreateDatabase {
+        database_name: String,
+        details: sqlx::Error,
+    },
+    DropDatabase {
+        database_name: String,
+        details: sqlx::Error,
+    },
+    Migrations(migrations::RunError),
+    StartStub(std::io::Error),
+    WorkerInit(worker::InitError),
+    HttpClient(ClientConfigError),
+    Setup(String),
+    RunJob(job_runner::Error),
+    NoReply {
+        jobs_run: usize,
+        job_errors: Vec<String>,
+    },
+}
+
+impl NiceDisplay for Error {
+    fn message(&self) -> String {
+        match self {
+            Error::DbConfig(err) => nest("Database configuration error", err),
+            Error::ConnectMaintenance(err) => with_context(
+                format!(
+                    "Could not connect to the {} database to make the smoke test database from",
+                    MAINTENANCE_DATABASE
+                ),
+                err,
+            ),
+            Error::CreateDatabase {
+                database_name,
+                details,
+            } => with_context(format!("Could not create {}", database_name), details),
+            Error::DropDatabase {
+                database_name,
+                details,
+            } => with_context(
+                format!("Could not drop {}, drop it by hand", database_name),
+                details,
+            ),
+            Error::Migrations(err) => nest("Could not migrate the smoke test database", err),
+            Error::StartStub(err) => with_context("Could not start the stub LLM server", err),
+            Error::WorkerInit(err) => nest("Worker initialization failed", err),
+            Error::HttpClient(err) => nest("Could not set up the http client", err),
+            Error::Setup(err) => with_context("Could not set up the smoke test scene", err),
+            Error::RunJob(err) => nest("A job failed", err),
+            Error::NoReply {
+                jobs_run,
+                job_errors,
+            } => {
+                let mut message = format!(
+                    "Ran {} jobs, but the person never replied to the message",
+                    jobs_run
+                );
+                for job_error in job_errors {
+                    message.push_str(format!("\n- {}", job_error).as_str());
+                }
+                message
+            }
+        }
+    }
+}
+
+/// Makes a new database, has a person in a scene reply to a message with
+/// every LLM call answered by a local stub, and drops the database again.
+/// Fails unless the reply shows up in the scene.
+pub async fn run(keep_database: bool) -> Result<(), Error> {
+    let config = db::Config::load().await.map_err(Error::DbConfig)?;
+    let database_name = smoke_test::database_name(config.database_name().as_str(), rand::random());
+
+    let maintenance = PgPoolOptions::new()
+        .max_connections(1)
+        .connect(config.url(MAINTENANCE_DATABASE).as_str())
+        .await
+        .map_err(Error::ConnectMaintenance)?;
+
+    sqlx::query(format!("CREATE DATABASE \"{}\"", database_name).as_str())
+        .execute(&maintenance)
+        .await
+        .map_err(|details| Error::CreateDatabase {
+            database_name: database_name.clone(),
+            details,
+        })?;
+
+    println!("Created {}", database_name);
+
+    let result = run_in_database(&config, database_name.as_str()).await;
+
+    if keep_database {
+        println!("Kept {}", database_name);
+        return result;
+    }
+
+    // The worker's pool may still hold connections, so they are closed too
+    let dropped = sqlx::query(format!("DROP DATABASE \"{}\" WITH (FORCE)", database_name).as_str())
+        .execute(&maintenance)
+        .await
+        .map_err(|details| Error::DropDatabase {
+            database_name: database_name.clone(),
+            details,
+        });
+
+    match (result, dropped) {
+        (Err(err), Err(drop_err)) => {
+            eprintln!("{}", drop_err.message());
+            Err(err)
+        }
+        (result, dropped) => {
+            dropped?;
+            println!("Dropped {}", database_name);
+            result
+        }
+    }
+}
+
+async fn run_in_database(config: &db::Config, database_name: &str) -> Result<(), Error> {
+    let migration_count = migrations::apply(config, database_name, false)
+        .await
+        .map_err(Error::Migrations)?;
+    println!("Ran {} migrations", migration_count);
+
+    let (api_base, stub) = start_stub()?;
+
+    let result = run_pipeline(config, database_name, api_base).await;
+
+    stub.stop(true).await;
+
+    result
+}
+
+/// Answers the OpenAI endpoints the pipeline calls with canned responses,
+/// on a free port.
+fn start_stub() -> Result<(String, ServerHandle), Error> {
+    let server = HttpServer::new(|| {
+        App::new()
+            .route("/v1/chat/completions", web::post().to(chat_completions))
+            .route("/v1/embeddings", web::post().to(embeddings))
+            .route("/v1/moderations", web::post().to(moderations))
+    })
+    .workers(1)
+    .bind(("127.0.0.1", 0))
+    .map_err(Error::StartStub)?;
+
+    let address = server
+        .addrs()
+        .first()
+        .copied()
+        .ok_or_else(|| Error::StartStub(std::io::Error::other("no address to listen on")))?;
+
+    let server = server.run();
+    let handle = server.handle();
+    actix_web::rt::spawn(server);
+
+    Ok((format!("http://{}/v1", address), handle))
+}
+
+async fn chat_completions(request: web::Json<serde_json::Value>) -> HttpResponse {
+    HttpResponse::Ok().json(smoke_test::completion_response(&request))
+}
+
+async fn embeddings() -> HttpResponse {
+    HttpResponse::Ok().json(smoke_test::embedding_response(EmbeddingModel::CURRENT))
+}
+
+async fn moderations() -> HttpResponse {
+    HttpResponse::Ok().json(smoke_test::moderation_response())
+}
+
+async fn run_pipeline(
+    config: &db::Config,
+    database_name: &str,
+    api_base: String,
+) -> Result<(), Error> {
+    let logger = Logger::init(Level::Warning);
+    let pool_size = PoolSizes::load()
+        .map_err(Error::WorkerInit)?
+        .for_role(ProcessRole::Task);
+    let worker = Worker::from_connection_string(
+        logger,
+        config.url(database_name).as_str(),
+        Some(OpenAiKey::from_string("smoke-test".to_string())),
+        pool_size,
+    )
+    .await
+    .map_err(Error::WorkerInit)?;
+
+    // Failover and local servers from the environment are left out, so every
+    // call goes to the stub
+    let open_ai_client = ClientConfig::default()
+        .build_client()
+        .map_err(Error::HttpClient)?
+        .with_api_base(api_base)
+        .with_call_log(worker.sqlx.clone());
+    let worker = Worker {
+        open_ai_client,
+        ..worker
+    };
+
+    let (person_uuid, scene_uuid) = set_up_scene(&worker).await.map_err(Error::Setup)?;
+
+    let random_seed = worker.get_random_seed().map_err(Error::Setup)?;
+    worker
+        .unshift_job(JobKind::SendMessageToScene(SendMessageToSceneJob {
+            sender: MessageSender::RealWorldUser,
+            scene_uuid: scene_uuid.clone(),
+            content: smoke_test::OPENER.to_string(),
+            random_seed,
+        }))
+        .await
+        .map_err(Error::Setup)?;
+    println!("Sent \"{}\" to {}", smoke_test::OPENER, SCENE_NAME);
+
+    let mut jobs_run = 0;
+    while jobs_run < MAX_JOBS {
+        let random_seed = worker.get_random_seed().map_err(Error::Setup)?;
+        let job_kind = match job_runner::run_one_job(worker.clone(), random_seed)
+            .await
+            .map_err(Error::RunJob)?
+        {
+            RunNextJobResult::NoJob => break,
+            RunNextJobResult::RanJob { job_kind, .. } => job_kind,
+            RunNextJobResult::Deferred { job_kind, .. } => format!("{} (deferred)", job_kind),
+        };
+        jobs_run += 1;
+        println!("Ran {}", job_kind);
+
+        if has_replied(&worker, &person_uuid, &scene_uuid).await? {
+            println!("{} replied \"{}\"", PERSON_NAME, smoke_test::REPLY);
+            println!("Smoke test passed");
+            return Ok(());
+        }
+    }
+
+    let job_errors = worker
+        .recent_jobs(MAX_JOBS as i64)
+        .await
+        .map_err(Error::Setup)?
+        .iter()
+        .filter_map(|job| {
+            job.error()
+                .map(|error| format!("{}: {}", job.kind_label(), error))
+        })
+        .collect();
+
+    Err(Error::NoReply {
+        jobs_run,
+        job_errors,
+    })
+}
+
+/// A person with just enough to react: an identity, a state of mind, and a
+/// scene to be in.
+async fn set_up_scene(worker: &Worker) -> Result<(PersonUuid, SceneUuid), String> {
+    let person_name = PersonName::from_string(PERSON_NAME.to_string());
+    let person_uuid = worker
+        .create_person(NewPerson {
+            person_uuid: PersonUuid::new(),
+            person_name: person_name.clone(),
+        })
+        .await?;
+
+    worker
+        .create_person_identity(NewPersonIdentity {
+            person_identity_uuid: PersonIdentityUuid::new(),
+            person_name: PERSON_NAME.to_string(),
+            identity: "A friendly regular who answers whoever talks to them.".to_string(),
+        })
+        .await?;
+
+    worker
+        .create_state_of_mind(NewStateOfMind {
+            uuid: StateOfMindUuid::new(),
+            person_name: person_name.clone(),
+            state_of_mind: "Relaxed and happy to chat.".to_string(),
+        })
+        .await?;
+
+    let scene_uuid = worker
+        .create_scene(NewScene {
+            name: SCENE_NAME.to_string(),
+            description: "An empty room for checking that messages get answered.".to_string(),
+        })
+        .await?;
+
+    worker
+        .add_person_to_scene(scene_uuid.clone(), person_name)
+        .await?;
+
+    println!("Put {} in {}", PERSON_NAME, SCENE_NAME);
+
+    Ok((person_uuid, scene_uuid))
+}
+
+async fn has_replied(
+    worker: &Worker,
+    person_uuid: &PersonUuid,
+    scene_uuid: &SceneUuid,
+) -> Result<bool, Error> {
+    let messages = worker
+        .get_messages_in_scene_page(scene_uuid, 50, None)
+        .await
+        .map_err(Error::Setup)?;
+
+    Ok(messages.iter().any(|message| match &message.sender {
+        MessageSender::AiPerson(sender_uuid) => {
+            sender_uuid == person_uuid && message.content == smoke_test::REPLY
+        }
+        MessageSender::RealWorldUser => false,
+    }))
+}
@@ -0,0 +1,49 @@
+use crate::capability::budget::BudgetCapability;
+use crate::domain::budget;
+use crate::domain::logger::{Level, Logger};
+use crate::nice_display::{nest, with_context, NiceDisplay};
+use crate::worker;
+use crate::worker::{ProcessRole, Worker};
+
+pub enum Error {
+    WorkerInit(worker::InitError),
+    InvalidBudget(String),
+    SetBudget(String),
+}
+
+impl NiceDisplay for Error {
+    fn message(&self) -> String {
+        match self {
+            Error::WorkerInit(err) => nest("Worker initialization failed", err),
+            Error::InvalidBudget(err) => err.clone(),
+            Error::SetBudget(err) => with_context("Failed to set the run budget", err),
+        }
+    }
+}
+
+pub async fn run(budget_usd: Option<String>) -> Result<(), Error> {
+    let budget_usd = match budget_usd {
+        Some(input) => Some(budget::parse_budget_usd(&input).map_err(Error::InvalidBudget)?),
+        None => None,
+    };
+
+    let logger = Logger::init(Level::Info);
+    let worker = Worker::new(logger, ProcessRole::Task)
+        .await
+        .map_err(Error::WorkerInit)?;
+
+    worker
+        .set_run_budget(budget_usd)
+        .await
+        .map_err(Error::SetBudget)?;
+
+    match budget_usd {
+        Some(budget_usd) => println!(
+            "Started a new run with a ${:.2} budget. The job runner pauses once it is spent.",
+            budget_usd
+        ),
+        None => println!("Started a new run without a budget"),
+    }
+
+    Ok(())
+}
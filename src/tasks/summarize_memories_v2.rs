@@ -1,6 +1,6 @@
 use crate::domain::logger::{Level, Logger};
 use crate::domain::memory_uuid::MemoryUuid;
-use crate::nice_display::NiceDisplay;
+use crate::nice_display::{nest, with_context, NiceDisplay};
 use crate::open_ai::completion::Completion;
 use crate::open_ai::embedding::EmbeddingRequest;
 use crate::open_ai::role::Role;
@@ -32,21 +32,21 @@ pub enum Error {
 impl NiceDisplay for Error {
     fn message(&self) -> String {
         match self {
-            Error::WorkerInit(err) => format!("Worker initialization failed: {}", err.message()),
-            Error::FetchPeople(err) => format!("Failed to fetch people: {}", err),
-            Error::FetchPersonMemories(err) => format!("Failed to fetch person memories: {}", err),
-            Error::Completion(err) => format!("Failed to summarize memories with LLM: {}", err),
-            Error::ToolCallDecode(err) => format!("Failed to decode LLM tool call: {}", err),
-            Error::MissingToolArgument(err) => format!("Missing expected tool argument: {}", err),
+            Error::WorkerInit(err) => nest("Worker initialization failed", err),
+            Error::FetchPeople(err) => with_context("Failed to fetch people", err),
+            Error::FetchPersonMemories(err) => with_context("Failed to fetch person memories", err),
+            Error::Completion(err) => with_context("Failed to summarize memories with LLM", err),
+            Error::ToolCallDecode(err) => with_context("Failed to decode LLM tool call", err),
+            Error::MissingToolArgument(err) => with_context("Missing expected tool argument", err),
             Error::InvalidResponseShape {
                 person_name,
                 details,
-            } => format!(
-                "LLM output shape invalid for person {}: {}",
-                person_name, details
+            } => with_context(
+                format!("LLM output shape invalid for person {}", person_name),
+                details,
             ),
-            Error::CreateEmbedding(err) => format!("Failed creating embedding: {}", err),
-            Error::InsertMemory(err) => format!("Failed inserting summarized memory: {}", err),
+            Error::CreateEmbedding(err) => with_context("Failed creating embedding", err),
+            Error::InsertMemory(err) => with_context("Failed inserting summarized memory", err),
         }
     }
 }
@@ -64,7 +64,7 @@ struct V2MemoryCandidate {
 
 pub async fn run() -> Result<(), Error> {
     let logger = Logger::init(Level::Warning);
-    let worker = crate::worker::Worker::new(logger)
+    let worker = crate::worker::Worker::new(logger, crate::worker::ProcessRole::Task)
         .await
         .map_err(Error::WorkerInit)?;
 
@@ -131,18 +131,21 @@ pub async fn run() -> Result<(), Error> {
                 significance_comment.trim()
             );
 
-            let embedding = EmbeddingRequest::new(retrieval_summary.clone())
-                .create(worker.open_ai_key.clone(), worker.reqwest_client.clone())
+            let embedding_request = EmbeddingRequest::new(retrieval_summary.clone());
+            let embedding = embedding_request
+                .create(worker.open_ai_key.as_ref(), worker.open_ai_client.clone())
                 .await
                 .map_err(|err| Error::CreateEmbedding(err.message()))?;
 
-            sqlx::query!(
+            sqlx::query(
                 r#"
                     INSERT INTO memory (
                         uuid,
                         person_uuid,
                         content,
                         embedding,
+                        embedding_model,
+                        embedding_dimension,
                         summary,
                         emotional_score,
                         retrieval_summary,
@@ -155,28 +158,32 @@ pub async fn run() -> Result<(), Error> {
                         $1::UUID,
                         $2::UUID,
                         $3::TEXT,
-                        $4,
+                        $4::vector,
                         $5::TEXT,
                         $6::INT,
                         $7::TEXT,
-                        $8::TEXT,
-                        $9::TEXT[],
-                        $10::UUID[],
-                        $11::TEXT[]
+                        $8::INT,
+                        $9::TEXT,
+                        $10::TEXT,
+                        $11::TEXT[],
+                        $12::UUID[],
+                        $13::TEXT[]
                     );
                 "#,
-                MemoryUuid::new().to_uuid(),
-                person.uuid,
-                third_person_memory.clone(),
-                &embedding[..] as &[f32],
-                third_person_summary,
-                candidate.emotional_score,
-                retrieval_summary,
-                first_person_memory,
-                &normalized_people_names as &[String],
-                &people_uuids as &[Uuid],
-                &normalized_subject_tags as &[String],
             )
+            .bind(MemoryUuid::new().to_uuid())
+            .bind(person.uuid)
+            .bind(third_person_memory.clone())
+            .bind(&embedding[..] as &[f32])
+            .bind(embedding_request.model().to_string())
+            .bind(embedding_request.model().dimension())
+            .bind(third_person_summary)
+            .bind(candidate.emotional_score)
+            .bind(retrieval_summary)
+            .bind(first_person_memory)
+            .bind(&normalized_people_names as &[String])
+            .bind(&people_uuids as &[Uuid])
+            .bind(&normalized_subject_tags as &[String])
             .execute(&worker.sqlx)
             .await
             .map_err(Error::InsertMemory)?;
@@ -267,7 +274,7 @@ async fn summarize_memory_for_section(
         );
 
         let response = completion
-            .send_request(&worker.open_ai_key, worker.reqwest_client.clone())
+            .send_request(worker.open_ai_key.as_ref(), worker.open_ai_client.clone())
             .await
             .map_err(|err| Error::Completion(err.message()))?;
 
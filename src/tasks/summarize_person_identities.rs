@@ -1,7 +1,14 @@
+use crate::capability::job::JobCapability;
+use crate::capability::llm_batch::LlmBatchCapability;
 use crate::capability::person_identity::PersonIdentityCapability;
+use crate::domain::job::poll_llm_batch::PollLlmBatchJob;
+use crate::domain::job::JobKind;
+use crate::domain::llm_batch::BatchHandler;
 use crate::domain::logger::Logger;
-use crate::nice_display::NiceDisplay;
+use crate::domain::person_identity_uuid::PersonIdentityUuid;
+use crate::nice_display::{nest, with_context, NiceDisplay};
 use crate::worker;
+use crate::worker::identity_summary_completion;
 
 pub enum Error {
     WorkerInit(worker::InitError),
@@ -9,25 +16,33 @@ pub enum Error {
     Completion(String),
     UpdateSummary(sqlx::Error),
     DeleteNullSummaries(sqlx::Error),
+    SubmitBatch(String),
+    EnqueueBatchPoll(String),
 }
 
 impl NiceDisplay for Error {
     fn message(&self) -> String {
         match self {
-            Error::WorkerInit(err) => format!("Worker initialization failed: {}", err.message()),
-            Error::FetchPeople(err) => format!("Failed to fetch people/identities: {}", err),
-            Error::Completion(err) => format!("Failed to summarize identity with LLM: {}", err),
-            Error::UpdateSummary(err) => format!("Failed to update identity summary: {}", err),
+            Error::WorkerInit(err) => nest("Worker initialization failed", err),
+            Error::FetchPeople(err) => with_context("Failed to fetch people/identities", err),
+            Error::Completion(err) => with_context("Failed to summarize identity with LLM", err),
+            Error::UpdateSummary(err) => with_context("Failed to update identity summary", err),
             Error::DeleteNullSummaries(err) => {
-                format!("Failed deleting identities with null summary: {}", err)
+                with_context("Failed deleting identities with null summary", err)
+            }
+            Error::SubmitBatch(err) => with_context("Failed to submit the summary batch", err),
+            Error::EnqueueBatchPoll(err) => {
+                with_context("Failed to enqueue the summary batch poll", err)
             }
         }
     }
 }
 
-pub async fn run() -> Result<(), Error> {
+/// With `batch`, the summaries are sent through the Batch API at half the
+/// price, and the job runner writes them back once the batch finishes.
+pub async fn run(batch: bool) -> Result<(), Error> {
     let logger = Logger::init(crate::domain::logger::Level::Info);
-    let worker = crate::worker::Worker::new(logger)
+    let worker = crate::worker::Worker::new(logger, crate::worker::ProcessRole::Task)
         .await
         .map_err(Error::WorkerInit)?;
 
@@ -53,6 +68,8 @@ pub async fn run() -> Result<(), Error> {
     .await
     .map_err(Error::FetchPeople)?;
 
+    let mut batch_requests = Vec::new();
+
     for row in rows {
         let (person_identity_uuid, identity) = match (row.person_identity_uuid, row.identity) {
             (Some(identity_uuid), Some(identity_text)) => (identity_uuid, identity_text),
@@ -65,6 +82,17 @@ pub async fn run() -> Result<(), Error> {
             }
         };
 
+        if batch {
+            let handler = BatchHandler::PersonIdentitySummary {
+                person_identity_uuid: PersonIdentityUuid::from_uuid(person_identity_uuid),
+            };
+            batch_requests.push(
+                identity_summary_completion(&row.person_name, &identity)
+                    .to_batch_request(handler.to_custom_id()),
+            );
+            continue;
+        }
+
         let summary = worker
             .summarize_person_identity(&row.person_name, &identity)
             .await
@@ -89,6 +117,33 @@ pub async fn run() -> Result<(), Error> {
         );
     }
 
+    if batch {
+        if batch_requests.is_empty() {
+            println!("No identities to summarize");
+            return Ok(());
+        }
+
+        let request_count = batch_requests.len();
+        let batch_id = worker
+            .submit_llm_batch(batch_requests)
+            .await
+            .map_err(Error::SubmitBatch)?;
+
+        worker
+            .unshift_job(JobKind::PollLlmBatch(PollLlmBatchJob::now(
+                batch_id.clone(),
+            )))
+            .await
+            .map_err(Error::EnqueueBatchPoll)?;
+
+        println!(
+            "Submitted batch {} with {} identity summaries; the job runner will apply them when it finishes",
+            batch_id, request_count
+        );
+
+        return Ok(());
+    }
+
     let delete_result = sqlx::query!(
         r#"
             DELETE FROM person_identity
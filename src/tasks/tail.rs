@@ -0,0 +1,121 @@
+use crate::capability::scene::SceneCapability;
+use crate::capability::scene_timeline::SceneTimelineCapability;
+use crate::domain::logger::{Level, Logger};
+use crate::domain::scene_timeline::{self, TailCursor, TimelineItem, TimelineQuery};
+use crate::domain::scene_uuid::SceneUuid;
+use crate::nice_display::{nest, with_context, NiceDisplay};
+use crate::time_display;
+use crate::worker;
+use crate::worker::{ProcessRole, Worker};
+use std::time::Duration;
+
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+pub enum Error {
+    WorkerInit(worker::InitError),
+    GetScene(String),
+    SceneNotFound(String),
+    GetTimeline(String),
+    Serialize(serde_json::Error),
+}
+
+impl NiceDisplay for Error {
+    fn message(&self) -> String {
+        match self {
+            Error::WorkerInit(err) => nest("Worker initialization failed", err),
+            Error::GetScene(err) => with_context("Failed to look up scene", err),
+            Error::SceneNotFound(scene_name) => format!("No scene named \"{}\"", scene_name),
+            Error::GetTimeline(err) => with_context("Failed to get the scene timeline", err),
+            Error::Serialize(err) => with_context("Failed to serialize a timeline item", err),
+        }
+    }
+}
+
+/// Prints the last `lines` timeline items of a scene, then each new one as
+/// it happens, until interrupted.
+pub async fn run(scene_name: String, lines: i64, json: bool) -> Result<(), Error> {
+    // Logging to the console would mix into the items, which may be piped
+    let logger = Logger::init(Level::Info).log_to_file();
+    let worker = Worker::new(logger, ProcessRole::Task)
+        .await
+        .map_err(Error::WorkerInit)?;
+
+    let scene = worker
+        .get_scene_from_name(scene_name.clone())
+        .await
+        .map_err(Error::GetScene)?
+        .ok_or_else(|| Error::SceneNotFound(scene_name.clone()))?;
+
+    let mut cursor = TailCursor::new();
+
+    let backlog = newest_items(&worker, &scene.uuid, lines.max(1)).await?;
+    for item in cursor.take_new(backlog) {
+        if lines > 0 {
+            print_item(&item, json)?;
+        }
+    }
+
+    loop {
+        tokio::time::sleep(POLL_INTERVAL).await;
+
+        let items = newest_items(&worker, &scene.uuid, scene_timeline::MAX_PAGE_SIZE).await?;
+        for item in cursor.take_new(items) {
+            print_item(&item, json)?;
+        }
+    }
+}
+
+async fn newest_items(
+    worker: &Worker,
+    scene_uuid: &SceneUuid,
+    limit: i64,
+) -> Result<Vec<TimelineItem>, Error> {
+    worker
+        .get_scene_timeline(scene_uuid, TimelineQuery::new(None, Some(limit)))
+        .await
+        .map(|page| page.items)
+        .map_err(Error::GetTimeline)
+}
+
+fn print_item(item: &TimelineItem, json: bool) -> Result<(), Error> {
+    if json {
+        println!("{}", serde_json::to_string(item).map_err(Error::Serialize)?);
+    } else {
+        println!(
+            "[{}] {}",
+            time_display::format_clock(item.at()),
+            item_line(item)
+        );
+    }
+
+    Ok(())
+}
+
+fn item_line(item: &TimelineItem) -> String {
+    match item {
+        TimelineItem::Message {
+            sender,
+            content,
+            audience,
+            ..
+        } => {
+            if audience == "everyone" {
+                format!("{}: {}", sender.name, content)
+            } else {
+                format!("{} ({}): {}", sender.name, audience, content)
+            }
+        }
+        TimelineItem::Joined { person_name, .. } => format!("→ {} joined", person_name),
+        TimelineItem::Left { person_name, .. } => format!("← {} left", person_name),
+        TimelineItem::ArrivalSummary {
+            person_name,
+            summary,
+            ..
+        } => format!("{} noticed: {}", person_name, summary),
+        TimelineItem::SceneSnapshot { description, .. } => format!("Scene: {}", description),
+        TimelineItem::AmbienceChanged { ambience, .. } => {
+            format!("Ambience: {}", ambience.to_label())
+        }
+        TimelineItem::SceneEvent { description, .. } => format!("Event: {}", description),
+    }
+}
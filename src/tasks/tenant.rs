@@ -0,0 +1,208 @@
+use crate::capability::scene::SceneCapability;
+use crate::capability::tenant::TenantCapability;
+use crate::domain::logger::{Level, Logger};
+use crate::domain::tenant::{self, ApiRole, TenantName};
+use crate::domain::tenant_uuid::TenantUuid;
+use crate::nice_display::{nest, with_context, NiceDisplay};
+use crate::time_display;
+use crate::worker;
+use crate::worker::{ProcessRole, Worker};
+use clap::Subcommand;
+
+#[derive(Debug, Subcommand, Clone)]
+pub enum Command {
+    /// Add a tenant whose scenes can be served by the api.
+    Create {
+        name: String,
+    },
+    List,
+    /// Issue a new api token for the tenant. It is only shown once.
+    IssueToken {
+        tenant: String,
+        /// viewer, director or admin
+        #[clap(long, default_value = "viewer")]
+        role: String,
+    },
+    /// Revoke every api token the tenant has.
+    RevokeTokens {
+        tenant: String,
+    },
+    /// Issue a token for the job queue endpoints. It belongs to no tenant and
+    /// is only shown once.
+    IssueOperatorToken,
+    /// Revoke every operator token.
+    RevokeOperatorTokens,
+    /// Move a scene into the tenant, or out of every tenant with `--none`.
+    AssignScene {
+        scene: String,
+        #[clap(long, required_unless_present = "none", conflicts_with = "none")]
+        tenant: Option<String>,
+        #[clap(long)]
+        none: bool,
+    },
+}
+
+pub enum Error {
+    WorkerInit(worker::InitError),
+    InvalidName(String),
+    InvalidRole(String),
+    Create(String),
+    List(String),
+    GetTenant(String),
+    TenantNotFound(TenantName),
+    AddToken(String),
+    RevokeTokens(String),
+    AddOperatorToken(String),
+    RevokeOperatorTokens(String),
+    GetScene(String),
+    SceneNotFound(String),
+    AssignScene(String),
+}
+
+impl NiceDisplay for Error {
+    fn message(&self) -> String {
+        match self {
+            Error::WorkerInit(err) => nest("Worker initialization failed", err),
+            Error::InvalidName(err) => err.clone(),
+            Error::InvalidRole(err) => err.clone(),
+            Error::Create(err) => with_context("Failed to create the tenant", err),
+            Error::List(err) => with_context("Failed to list tenants", err),
+            Error::GetTenant(err) => with_context("Failed to look up the tenant", err),
+            Error::TenantNotFound(name) => format!("No tenant named \"{}\"", name),
+            Error::AddToken(err) => with_context("Failed to issue the api token", err),
+            Error::RevokeTokens(err) => with_context("Failed to revoke api tokens", err),
+            Error::AddOperatorToken(err) => with_context("Failed to issue the operator token", err),
+            Error::RevokeOperatorTokens(err) => {
+                with_context("Failed to revoke operator tokens", err)
+            }
+            Error::GetScene(err) => with_context("Failed to look up scene", err),
+            Error::SceneNotFound(scene_name) => format!("No scene named \"{}\"", scene_name),
+            Error::AssignScene(err) => with_context("Failed to assign the scene", err),
+        }
+    }
+}
+
+pub async fn run(command: Command) -> Result<(), Error> {
+    let logger = Logger::init(Level::Info);
+    let worker = Worker::new(logger, ProcessRole::Task)
+        .await
+        .map_err(Error::WorkerInit)?;
+
+    match command {
+        Command::Create { name } => {
+            let name = TenantName::parse(&name).map_err(Error::InvalidName)?;
+            worker.create_tenant(&name).await.map_err(Error::Create)?;
+            println!(
+                "Created tenant \"{}\". Issue it a token with `tenant issue-token {}`",
+                name, name
+            );
+        }
+        Command::List => {
+            let tenants = worker.get_tenants().await.map_err(Error::List)?;
+
+            if tenants.is_empty() {
+                println!("No tenants yet");
+            }
+
+            for tenant in tenants {
+                println!(
+                    "{} ({})  {} scenes, {} active tokens, created {}",
+                    tenant.name,
+                    tenant.uuid.to_uuid(),
+                    tenant.scene_count,
+                    tenant.active_token_count,
+                    time_display::format_absolute(tenant.created_at)
+                );
+            }
+        }
+        Command::IssueToken { tenant, role } => {
+            let role = ApiRole::parse(&role).map_err(Error::InvalidRole)?;
+            let (tenant_name, tenant_uuid) = find_tenant(&worker, &tenant).await?;
+            let token = tenant::generate_token();
+
+            worker
+                .add_api_token(&tenant_uuid, &tenant::hash_token(&token), role)
+                .await
+                .map_err(Error::AddToken)?;
+
+            println!(
+                "{} api token for \"{}\", it will not be shown again:",
+                role.to_name(),
+                tenant_name
+            );
+            println!("{}", token);
+        }
+        Command::RevokeTokens { tenant } => {
+            let (tenant_name, tenant_uuid) = find_tenant(&worker, &tenant).await?;
+
+            let revoked = worker
+                .revoke_api_tokens(&tenant_uuid)
+                .await
+                .map_err(Error::RevokeTokens)?;
+
+            println!("Revoked {} tokens for \"{}\"", revoked, tenant_name);
+        }
+        Command::IssueOperatorToken => {
+            let token = tenant::generate_token();
+
+            worker
+                .add_operator_token(&tenant::hash_token(&token))
+                .await
+                .map_err(Error::AddOperatorToken)?;
+
+            println!("Operator token, it will not be shown again:");
+            println!("{}", token);
+        }
+        Command::RevokeOperatorTokens => {
+            let revoked = worker
+                .revoke_operator_tokens()
+                .await
+                .map_err(Error::RevokeOperatorTokens)?;
+
+            println!("Revoked {} operator tokens", revoked);
+        }
+        Command::AssignScene {
+            scene,
+            tenant,
+            none: _,
+        } => {
+            let scene_uuid = worker
+                .get_scene_from_name(scene.clone())
+                .await
+                .map_err(Error::GetScene)?
+                .ok_or_else(|| Error::SceneNotFound(scene.clone()))?
+                .uuid;
+
+            let tenant = match tenant {
+                Some(tenant) => Some(find_tenant(&worker, &tenant).await?),
+                None => None,
+            };
+
+            worker
+                .set_scene_tenant(&scene_uuid, tenant.as_ref().map(|(_, uuid)| uuid))
+                .await
+                .map_err(Error::AssignScene)?;
+
+            match tenant {
+                Some((tenant_name, _)) => {
+                    println!("Scene \"{}\" now belongs to \"{}\"", scene, tenant_name)
+                }
+                None => println!("Scene \"{}\" no longer belongs to a tenant", scene),
+            }
+        }
+    }
+
+    Ok(())
+}
+
+async fn find_tenant(worker: &Worker, name: &str) -> Result<(TenantName, TenantUuid), Error> {
+    let name = TenantName::parse(name).map_err(Error::InvalidName)?;
+
+    let tenant_uuid = worker
+        .get_tenant_uuid_by_name(&name)
+        .await
+        .map_err(Error::GetTenant)?
+        .ok_or_else(|| Error::TenantNotFound(name.clone()))?;
+
+    Ok((name, tenant_uuid))
+}
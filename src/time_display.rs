@@ -0,0 +1,168 @@
+use crate::world_language::world_language;
+use chrono::{DateTime, NaiveTime, TimeZone, Utc};
+use chrono_tz::Tz;
+use std::sync::OnceLock;
+
+/// An IANA timezone name, like `America/Phoenix`, to show times in. Defaults
+/// to UTC.
+pub const TIMEZONE_VAR: &str = "DISPLAY_TIMEZONE";
+
+/// Items newer than this are shown as "3 min ago" rather than a date.
+const RELATIVE_WINDOW_SECS: i64 = 60 * 60;
+
+/// The configured display timezone. Read once, since views format times on
+/// every render. A name that can't be parsed falls back to UTC with a warning
+/// rather than keeping the admin ui from starting.
+pub fn display_timezone() -> Tz {
+    static TIMEZONE: OnceLock<Tz> = OnceLock::new();
+
+    *TIMEZONE.get_or_init(|| match dotenv::var(TIMEZONE_VAR) {
+        Ok(name) => match parse_timezone(name.as_str()) {
+            Ok(timezone) => timezone,
+            Err(err) => {
+                tracing::warn!("{}, showing times in UTC", err);
+                Tz::UTC
+            }
+        },
+        Err(_) => Tz::UTC,
+    })
+}
+
+pub fn parse_timezone(name: &str) -> Result<Tz, String> {
+    name.trim()
+        .parse::<Tz>()
+        .map_err(|_| format!("{} \"{}\" is not a known timezone", TIMEZONE_VAR, name))
+}
+
+/// Like `2026-10-17 14:03:05 MST`, with the date in the world language's
+/// order.
+pub fn format_absolute(at: DateTime<Utc>) -> String {
+    format_absolute_in(display_timezone(), world_language().date_pattern(), at)
+}
+
+/// Like `14:03:05`, for lists where the date is already clear.
+pub fn format_clock(at: DateTime<Utc>) -> String {
+    at.with_timezone(&display_timezone())
+        .format("%H:%M:%S")
+        .to_string()
+}
+
+/// "3 min ago" for anything from the last hour, the absolute time otherwise.
+pub fn format_recent(at: DateTime<Utc>) -> String {
+    format_recent_in(
+        display_timezone(),
+        world_language().date_pattern(),
+        at,
+        Utc::now(),
+    )
+}
+
+/// Midnight of `now`'s day in the display timezone.
+pub fn start_of_day(now: DateTime<Utc>) -> DateTime<Utc> {
+    start_of_day_in(display_timezone(), now)
+}
+
+fn start_of_day_in(timezone: Tz, now: DateTime<Utc>) -> DateTime<Utc> {
+    let midnight = now
+        .with_timezone(&timezone)
+        .date_naive()
+        .and_time(NaiveTime::MIN);
+
+    match timezone.from_local_datetime(&midnight).earliest() {
+        Some(at) => at.with_timezone(&Utc),
+        // Midnight can be skipped by a daylight saving change, so fall back
+        // to the day in UTC
+        None => now.date_naive().and_time(NaiveTime::MIN).and_utc(),
+    }
+}
+
+fn format_absolute_in(timezone: Tz, date_pattern: &str, at: DateTime<Utc>) -> String {
+    at.with_timezone(&timezone)
+        .format(format!("{} %H:%M:%S %Z", date_pattern).as_str())
+        .to_string()
+}
+
+fn format_recent_in(
+    timezone: Tz,
+    date_pattern: &str,
+    at: DateTime<Utc>,
+    now: DateTime<Utc>,
+) -> String {
+    let secs_ago = now.signed_duration_since(at).num_seconds();
+
+    // Times in the future are most likely clock skew between machines
+    if !(0..RELATIVE_WINDOW_SECS).contains(&secs_ago) {
+        return format_absolute_in(timezone, date_pattern, at);
+    }
+
+    if secs_ago < 60 {
+        "just now".to_string()
+    } else {
+        format!("{} min ago", secs_ago / 60)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::world_language::WorldLanguage;
+
+    const ISO: &str = "%Y-%m-%d";
+
+    #[test]
+    fn test_absolute_times_are_shown_in_the_timezone_with_its_abbreviation() {
+        let at = Utc.with_ymd_and_hms(2026, 10, 17, 21, 3, 5).unwrap();
+
+        assert_eq!(
+            format_absolute_in(parse_timezone("America/Phoenix").unwrap(), ISO, at),
+            "2026-10-17 14:03:05 MST"
+        );
+        assert_eq!(
+            format_absolute_in(Tz::UTC, ISO, at),
+            "2026-10-17 21:03:05 UTC"
+        );
+        assert_eq!(
+            format_absolute_in(
+                Tz::UTC,
+                WorldLanguage::parse("es").unwrap().date_pattern(),
+                at
+            ),
+            "17/10/2026 21:03:05 UTC"
+        );
+        assert!(parse_timezone("Mars/Olympus_Mons").is_err());
+    }
+
+    #[test]
+    fn test_start_of_day_is_midnight_in_the_timezone() {
+        let at = Utc.with_ymd_and_hms(2026, 10, 18, 3, 0, 0).unwrap();
+
+        assert_eq!(
+            start_of_day_in(parse_timezone("America/Phoenix").unwrap(), at),
+            Utc.with_ymd_and_hms(2026, 10, 17, 7, 0, 0).unwrap()
+        );
+        assert_eq!(
+            start_of_day_in(Tz::UTC, at),
+            Utc.with_ymd_and_hms(2026, 10, 18, 0, 0, 0).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_recent_times_are_relative_for_an_hour() {
+        let now = Utc.with_ymd_and_hms(2026, 10, 17, 12, 0, 0).unwrap();
+        let ago = |secs: i64| now - chrono::Duration::seconds(secs);
+
+        assert_eq!(format_recent_in(Tz::UTC, ISO, ago(20), now), "just now");
+        assert_eq!(
+            format_recent_in(Tz::UTC, ISO, ago(3 * 60 + 10), now),
+            "3 min ago"
+        );
+        assert_eq!(
+            format_recent_in(Tz::UTC, ISO, ago(2 * 60 * 60), now),
+            "2026-10-17 10:00:00 UTC"
+        );
+        assert_eq!(
+            format_recent_in(Tz::UTC, ISO, ago(-30), now),
+            "2026-10-17 12:00:30 UTC"
+        );
+    }
+}
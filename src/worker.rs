@@ -1,36 +1,108 @@
+mod acknowledgement_capability;
+mod action_budget_capability;
+mod annotation_capability;
+mod arrival_observation_capability;
+mod autobiography_capability;
+mod budget_capability;
+mod chat_latency_capability;
+mod clock_capability;
+mod content_scrub_capability;
+mod conversation_graph_capability;
+mod cron_job_capability;
+mod custom_action_capability;
+mod delivery_capability;
+mod embedding_backfill_capability;
 mod event_capability;
+mod event_stream_capability;
+mod expected_reply_capability;
+mod external_tool_capability;
+mod fine_tune_capability;
+mod guardrail_capability;
+mod idle_person_capability;
+mod item_capability;
 mod job_capability;
 mod job_runner_settings_capability;
+mod llm_batch_capability;
 mod log_event_capability;
 mod logging_capability;
+mod maintenance_capability;
 mod memory_capability;
 mod message_capability;
+mod message_revision_capability;
+mod moderation_capability;
 mod motivation_capability;
+mod outbox_capability;
+mod person_arc_capability;
 mod person_capability;
 mod person_identity_capability;
+mod person_merge_capability;
+mod person_query_capability;
+mod person_tag_capability;
 mod person_task_capability;
+mod persona_capability;
+mod persona_consistency_capability;
 mod reaction_capability;
+mod reaction_context_capability;
 mod reaction_history_capability;
 mod reflection_capability;
+mod relationship_capability;
+mod run_report_capability;
+mod scene_archive_capability;
 mod scene_capability;
+mod scene_drama_capability;
+mod scene_goal_capability;
+mod scene_invitation_capability;
+mod scene_read_cache;
+mod scene_template_capability;
+mod scene_timeline_capability;
+mod scene_tone_capability;
+mod schema_capability;
 mod state_of_mind_capability;
+mod style_guide_capability;
+mod tenant_capability;
+mod topic_capability;
+mod utterance_capability;
+mod voice_exemplar_capability;
+mod world_language_capability;
+mod world_map_capability;
 
-use crate::domain::logger::Logger;
+pub use person_identity_capability::identity_summary_completion;
+
+use crate::capability::world_language::WorldLanguageCapability;
+use crate::db;
+use crate::db::WorldName;
+use crate::domain::clock::Clock;
+use crate::domain::logger::{Level, Logger};
 use crate::domain::random_seed::RandomSeed;
-use crate::{db, nice_display::NiceDisplay, open_ai_key::OpenAiKey};
+use crate::domain::scene_read_cache::SceneReadCache;
+use crate::nice_display::{nest, with_context, NiceDisplay};
+use crate::open_ai::client::{ClientConfig, ClientConfigError, LocalConfig, OpenAiClient};
+use crate::open_ai_key::{MissingOpenAiKey, OpenAiKey};
+use crate::world_language;
 use sqlx::postgres::PgPoolOptions;
 use sqlx::Postgres;
 use std::env::VarError;
 use std::sync::{Arc, Mutex};
 use std::time::Duration;
 
+const DEFAULT_CONNECT_ATTEMPTS: u32 = 5;
+const DEFAULT_CONNECT_RETRY_DELAY_MS: u64 = 500;
+const MAX_CONNECT_RETRY_DELAY_MS: u64 = 8_000;
+/// Pools never keep more than this many idle connections open, so a small
+/// pool is not held full while nothing is happening.
+const MAX_MIN_CONNECTIONS: u32 = 2;
+
 #[derive(Clone, Debug)]
 pub struct Worker {
-    pub open_ai_key: OpenAiKey,
-    pub reqwest_client: reqwest::Client,
+    /// Only missing when a local server answers completions and embeddings.
+    pub open_ai_key: Option<OpenAiKey>,
+    pub open_ai_client: OpenAiClient,
     pub sqlx: sqlx::Pool<Postgres>,
     pub random_seed: Arc<Mutex<RandomSeed>>,
     pub logger: Logger,
+    pub world: Option<WorldName>,
+    pub clock: Clock,
+    pub scene_read_cache: SceneReadCache,
 }
 
 #[derive(Debug)]
@@ -39,49 +111,262 @@ pub enum InitError {
     DbConfig(db::ConfigError),
     PoolConnection(sqlx::Error),
     PoolAcquire(sqlx::Error),
+    HttpClient(ClientConfigError),
+    PositiveNumberConfig { var_name: String, value: String },
+    MissingDatabase(String),
+    WorldLanguage(String),
 }
 
 impl NiceDisplay for InitError {
     fn message(&self) -> String {
         match self {
-            InitError::OpenAiKey(err) => format!("OpenAI API key error: {}", err),
-            InitError::DbConfig(err) => {
-                format!("Database configuration error\n{}", err.message())
-            }
+            InitError::OpenAiKey(err) => with_context("OpenAI API key error", err),
+            InitError::DbConfig(err) => nest("Database configuration error", err),
             InitError::PoolConnection(err) => {
-                format!("Error connecting to the database pool\n{}", err)
+                with_context("Error connecting to the database pool", err)
             }
             InitError::PoolAcquire(err) => {
+                with_context("Error acquiring a database connection from the pool", err)
+            }
+            InitError::HttpClient(err) => nest("Error setting up the OpenAI http client", err),
+            InitError::MissingDatabase(message) => message.clone(),
+            InitError::WorldLanguage(err) => with_context("Error reading the world language", err),
+            InitError::PositiveNumberConfig { var_name, value } => {
                 format!(
-                    "Error acquiring a database connection from the pool\n{}",
-                    err
+                    "{} must be a whole number greater than zero, but it was \"{}\"",
+                    var_name, value
                 )
             }
         }
     }
 }
 
+impl InitError {
+    /// Whether trying again later could help, such as when Postgres is still starting up.
+    fn is_retryable(&self) -> bool {
+        match self {
+            InitError::PoolConnection(_) | InitError::PoolAcquire(_) => true,
+            InitError::OpenAiKey(_)
+            | InitError::DbConfig(_)
+            | InitError::HttpClient(_)
+            | InitError::PositiveNumberConfig { .. }
+            | InitError::MissingDatabase(_)
+            | InitError::WorldLanguage(_) => false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConnectRetry {
+    pub attempts: u32,
+    pub initial_delay: Duration,
+}
+
+impl ConnectRetry {
+    /// Reads DATABASE_CONNECT_ATTEMPTS and DATABASE_CONNECT_RETRY_DELAY_MS,
+    /// falling back to defaults when they are not set.
+    pub fn load() -> Result<Self, InitError> {
+        let attempts = positive_from_env("DATABASE_CONNECT_ATTEMPTS", DEFAULT_CONNECT_ATTEMPTS)?;
+        let initial_delay_ms = positive_from_env(
+            "DATABASE_CONNECT_RETRY_DELAY_MS",
+            DEFAULT_CONNECT_RETRY_DELAY_MS,
+        )?;
+
+        Ok(ConnectRetry {
+            attempts,
+            initial_delay: Duration::from_millis(initial_delay_ms),
+        })
+    }
+
+    /// Doubles the delay after every failed attempt, up to a cap.
+    fn delay_after_attempt(&self, attempt: u32) -> Duration {
+        let multiplier = 2u64.saturating_pow(attempt.saturating_sub(1));
+        let delay_ms = u64::try_from(self.initial_delay.as_millis())
+            .unwrap_or(MAX_CONNECT_RETRY_DELAY_MS)
+            .saturating_mul(multiplier)
+            .min(MAX_CONNECT_RETRY_DELAY_MS);
+
+        Duration::from_millis(delay_ms)
+    }
+}
+
+/// Which kind of process a worker is connecting for. Each gets its own
+/// pool size, so a long running admin ui does not hold connections the job
+/// runner needs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProcessRole {
+    AdminUi,
+    Server,
+    Runner,
+    /// One off cli tasks, like `import-transcript`.
+    Task,
+}
+
+impl ProcessRole {
+    pub fn to_name(&self) -> &'static str {
+        match self {
+            ProcessRole::AdminUi => "admin ui",
+            ProcessRole::Server => "server",
+            ProcessRole::Runner => "job runner",
+            ProcessRole::Task => "task",
+        }
+    }
+}
+
+/// The most connections each role's pool opens.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PoolSizes {
+    pub admin_ui: u32,
+    pub server: u32,
+    pub runner: u32,
+    pub task: u32,
+}
+
+impl Default for PoolSizes {
+    /// The job runner does most of the querying, so it gets the most. The
+    /// admin ui only queries when someone clicks.
+    fn default() -> Self {
+        PoolSizes {
+            admin_ui: 4,
+            server: 8,
+            runner: 12,
+            task: 4,
+        }
+    }
+}
+
+impl PoolSizes {
+    /// Reads DATABASE_POOL_SIZE_UI, DATABASE_POOL_SIZE_SERVER,
+    /// DATABASE_POOL_SIZE_RUNNER and DATABASE_POOL_SIZE_TASK, falling back
+    /// to defaults when they are not set.
+    pub fn load() -> Result<Self, InitError> {
+        let defaults = PoolSizes::default();
+
+        Ok(PoolSizes {
+            admin_ui: positive_from_env("DATABASE_POOL_SIZE_UI", defaults.admin_ui)?,
+            server: positive_from_env("DATABASE_POOL_SIZE_SERVER", defaults.server)?,
+            runner: positive_from_env("DATABASE_POOL_SIZE_RUNNER", defaults.runner)?,
+            task: positive_from_env("DATABASE_POOL_SIZE_TASK", defaults.task)?,
+        })
+    }
+
+    pub fn for_role(&self, role: ProcessRole) -> u32 {
+        match role {
+            ProcessRole::AdminUi => self.admin_ui,
+            ProcessRole::Server => self.server,
+            ProcessRole::Runner => self.runner,
+            ProcessRole::Task => self.task,
+        }
+    }
+
+    /// The admin ui, server and job runner usually run side by side, with a
+    /// task now and then, so together they should fit in what Postgres
+    /// allows. Otherwise whichever connects last waits on connections.
+    pub fn over_limit_warning(&self, available_connections: u32) -> Option<String> {
+        let combined = self.admin_ui + self.server + self.runner + self.task;
+
+        if combined <= available_connections {
+            return None;
+        }
+
+        Some(format!(
+            "The database pools add up to {} connections (admin ui {}, server {}, job runner {}, task {}), but Postgres only allows {}. Lower the DATABASE_POOL_SIZE_ settings or raise max_connections",
+            combined, self.admin_ui, self.server, self.runner, self.task, available_connections
+        ))
+    }
+}
+
+fn positive_from_env<T: std::str::FromStr + PartialOrd + From<u8>>(
+    var_name: &str,
+    default: T,
+) -> Result<T, InitError> {
+    let value = match dotenv::var(var_name) {
+        Ok(value) => value,
+        Err(_) => return Ok(default),
+    };
+
+    match value.trim().parse::<T>() {
+        Ok(parsed) if parsed > T::from(0) => Ok(parsed),
+        _ => Err(InitError::PositiveNumberConfig {
+            var_name: var_name.to_string(),
+            value,
+        }),
+    }
+}
+
 impl Worker {
-    pub async fn new(logger: Logger) -> Result<Self, InitError> {
-        let open_ai_key = OpenAiKey::from_env().map_err(InitError::OpenAiKey)?;
+    pub async fn new(logger: Logger, role: ProcessRole) -> Result<Self, InitError> {
+        let open_ai_key = match OpenAiKey::from_env() {
+            Ok(key) => Some(key),
+            // A local server answers the completions and embeddings
+            Err(_) if LocalConfig::is_configured() => None,
+            Err(err) => return Err(InitError::OpenAiKey(err)),
+        };
         let db_info = db::Config::load().await.map_err(InitError::DbConfig)?;
-        let postgres_conn_url = format!(
-            "postgres://{}:{}@{}/arizona2",
-            db_info.user, db_info.password, db_info.host
-        );
+        let retry = ConnectRetry::load()?;
+        let pool_sizes = PoolSizes::load()?;
+        let database_name = db_info.database_name();
+        let postgres_conn_url = db_info.url(database_name.as_str());
+
+        let mut attempt = 1;
+        loop {
+            match Self::from_connection_string(
+                logger.clone(),
+                &postgres_conn_url,
+                open_ai_key.clone(),
+                pool_sizes.for_role(role),
+            )
+            .await
+            {
+                Ok(worker) => {
+                    worker.warn_if_pools_over_limit(&pool_sizes, role).await;
+
+                    let language = worker
+                        .get_world_language()
+                        .await
+                        .map_err(InitError::WorldLanguage)?;
+                    world_language::set_world_language(language);
 
-        Self::from_connection_string(logger, &postgres_conn_url, open_ai_key).await
+                    return Ok(Worker {
+                        world: db_info.world,
+                        ..worker
+                    });
+                }
+                Err(InitError::PoolConnection(err)) if db::is_missing_database(&err) => {
+                    return Err(InitError::MissingDatabase(
+                        db_info.missing_database_message(database_name.as_str()),
+                    ))
+                }
+                Err(err) if err.is_retryable() && attempt < retry.attempts => {
+                    let delay = retry.delay_after_attempt(attempt);
+                    logger.log(
+                        Level::Warning,
+                        &format!(
+                            "Could not connect to the database (attempt {} of {}), retrying in {}ms\n{}",
+                            attempt,
+                            retry.attempts,
+                            delay.as_millis(),
+                            err.message()
+                        ),
+                    );
+                    tokio::time::sleep(delay).await;
+                    attempt += 1;
+                }
+                Err(err) => return Err(err),
+            }
+        }
     }
 
     pub async fn from_connection_string(
         logger: Logger,
         connection_string: &str,
-        open_ai_key: OpenAiKey,
+        open_ai_key: Option<OpenAiKey>,
+        max_connections: u32,
     ) -> Result<Self, InitError> {
         let sqlx_pool = PgPoolOptions::new()
-            .min_connections(2)
+            .min_connections(max_connections.min(MAX_MIN_CONNECTIONS))
             .idle_timeout(Duration::from_secs(600))
-            .max_connections(19)
+            .max_connections(max_connections)
             .test_before_acquire(true)
             .connect(connection_string)
             .await
@@ -92,15 +377,55 @@ impl Worker {
             .await
             .map_err(InitError::PoolAcquire)?;
 
+        let open_ai_client = ClientConfig::load()
+            .and_then(|config| config.build_client())
+            .map_err(InitError::HttpClient)?
+            .with_call_log(sqlx_pool.clone());
+
+        let scene_read_cache = SceneReadCache::new();
+        scene_read_cache::spawn_listener(sqlx_pool.clone(), scene_read_cache.clone());
+
         Ok(Worker {
             open_ai_key,
-            reqwest_client: reqwest::Client::new(),
+            open_ai_client,
             sqlx: sqlx_pool,
             random_seed: Arc::new(Mutex::new(RandomSeed::new())),
             logger,
+            world: None,
+            clock: Clock::System,
+            scene_read_cache,
         })
     }
 
+    /// Only the long running processes warn, since a task is gone again
+    /// before anyone could act on it.
+    async fn warn_if_pools_over_limit(&self, pool_sizes: &PoolSizes, role: ProcessRole) {
+        if role == ProcessRole::Task {
+            return;
+        }
+
+        match db::available_connections(&self.sqlx).await {
+            Ok(available) => {
+                if let Some(warning) = pool_sizes.over_limit_warning(available) {
+                    self.logger.log(Level::Warning, &warning);
+                }
+            }
+            Err(err) => self.logger.log(
+                Level::Warning,
+                &format!(
+                    "Could not read max_connections to check the {} pool size against: {}",
+                    role.to_name(),
+                    err
+                ),
+            ),
+        }
+    }
+
+    /// For what only OpenAI serves, like moderations, batches and fine tunes.
+    pub fn require_open_ai_key(&self) -> Result<&OpenAiKey, MissingOpenAiKey> {
+        self.open_ai_key.as_ref().ok_or(MissingOpenAiKey)
+    }
+
     pub async fn warm_up_db_connection(&self) -> Result<(), String> {
         sqlx::query("SELECT 1")
             .execute(&self.sqlx)
@@ -119,3 +444,36 @@ impl Worker {
         Ok(seed1)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_connect_retry_delay_doubles_until_capped() {
+        let retry = ConnectRetry {
+            attempts: 10,
+            initial_delay: Duration::from_millis(500),
+        };
+
+        assert_eq!(retry.delay_after_attempt(1), Duration::from_millis(500));
+        assert_eq!(retry.delay_after_attempt(2), Duration::from_millis(1_000));
+        assert_eq!(retry.delay_after_attempt(3), Duration::from_millis(2_000));
+        assert_eq!(
+            retry.delay_after_attempt(9),
+            Duration::from_millis(MAX_CONNECT_RETRY_DELAY_MS)
+        );
+    }
+
+    #[test]
+    fn test_pools_warn_once_they_outgrow_postgres() {
+        let pool_sizes = PoolSizes::default();
+
+        assert_eq!(pool_sizes.for_role(ProcessRole::Runner), 12);
+        assert_eq!(pool_sizes.over_limit_warning(97), None);
+        assert_eq!(pool_sizes.over_limit_warning(28), None);
+
+        let warning = pool_sizes.over_limit_warning(27).unwrap();
+        assert!(warning.starts_with("The database pools add up to 28 connections"));
+    }
+}
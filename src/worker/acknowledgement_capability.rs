@@ -0,0 +1,159 @@
+use crate::capability::acknowledgement::AcknowledgementCapability;
+use crate::domain::acknowledgement;
+use crate::domain::logger::Level;
+use crate::domain::person_uuid::PersonUuid;
+use crate::nice_display::NiceDisplay;
+use crate::open_ai::completion::Completion;
+use crate::open_ai::role::Role;
+use crate::worker::Worker;
+use sqlx::Row;
+use uuid::Uuid;
+
+impl AcknowledgementCapability for Worker {
+    async fn take_acknowledgement(
+        &self,
+        person_uuid: &PersonUuid,
+    ) -> Result<Option<String>, String> {
+        let row = sqlx::query(
+            r#"
+                DELETE FROM acknowledgement
+                WHERE uuid = (
+                    SELECT uuid
+                    FROM acknowledgement
+                    WHERE person_uuid = $1::UUID
+                    ORDER BY created_at ASC
+                    LIMIT 1
+                    FOR UPDATE SKIP LOCKED
+                )
+                RETURNING content;
+            "#,
+        )
+        .bind(person_uuid.to_uuid())
+        .fetch_optional(&self.sqlx)
+        .await
+        .map_err(|err| format!("Error taking acknowledgement: {}", err))?;
+
+        row.map(|row| {
+            row.try_get::<String, _>("content")
+                .map_err(|err| format!("Error reading content from row: {}", err))
+        })
+        .transpose()
+    }
+
+    async fn count_acknowledgements(&self, person_uuid: &PersonUuid) -> Result<usize, String> {
+        let row = sqlx::query(
+            r#"
+                SELECT COUNT(*) AS acknowledgement_count
+                FROM acknowledgement
+                WHERE person_uuid = $1::UUID;
+            "#,
+        )
+        .bind(person_uuid.to_uuid())
+        .fetch_one(&self.sqlx)
+        .await
+        .map_err(|err| format!("Error counting acknowledgements: {}", err))?;
+
+        let count = row
+            .try_get::<i64, _>("acknowledgement_count")
+            .map_err(|err| format!("Error reading acknowledgement_count from row: {}", err))?;
+
+        Ok(usize::try_from(count).unwrap_or(0))
+    }
+
+    async fn add_acknowledgements(
+        &self,
+        person_uuid: &PersonUuid,
+        acknowledgements: &[String],
+    ) -> Result<(), String> {
+        for content in acknowledgements {
+            sqlx::query(
+                r#"
+                    INSERT INTO acknowledgement (uuid, person_uuid, content)
+                    VALUES ($1::UUID, $2::UUID, $3::TEXT);
+                "#,
+            )
+            .bind(Uuid::now_v7())
+            .bind(person_uuid.to_uuid())
+            .bind(content)
+            .execute(&self.sqlx)
+            .await
+            .map_err(|err| format!("Error adding acknowledgement: {}", err))?;
+        }
+
+        Ok(())
+    }
+
+    async fn generate_acknowledgements(
+        &self,
+        person_uuid: &PersonUuid,
+        count: usize,
+    ) -> Result<String, String> {
+        let row = sqlx::query(
+            r#"
+                SELECT person.name,
+                       (
+                           SELECT person_identity.summary
+                           FROM person_identity
+                           WHERE person_identity.person_uuid = person.uuid
+                           ORDER BY person_identity.created_at DESC
+                           LIMIT 1
+                       ) AS identity_summary,
+                       (
+                           SELECT scene.name
+                           FROM scene_participant
+                           JOIN scene ON scene.uuid = scene_participant.scene_uuid
+                           WHERE scene_participant.person_uuid = person.uuid
+                             AND scene_participant.left_at IS NULL
+                           LIMIT 1
+                       ) AS scene_name
+                FROM person
+                WHERE person.uuid = $1::UUID;
+            "#,
+        )
+        .bind(person_uuid.to_uuid())
+        .fetch_one(&self.sqlx)
+        .await
+        .map_err(|err| format!("Error fetching person for acknowledgements: {}", err))?;
+
+        let person_name = row
+            .try_get::<String, _>("name")
+            .map_err(|err| format!("Error reading name from row: {}", err))?;
+        let identity_summary = row
+            .try_get::<Option<String>, _>("identity_summary")
+            .map_err(|err| format!("Error reading identity_summary from row: {}", err))?;
+        let scene_name = row
+            .try_get::<Option<String>, _>("scene_name")
+            .map_err(|err| format!("Error reading scene_name from row: {}", err))?;
+
+        let mut completion = Completion::new();
+        completion.add_message(Role::System, acknowledgement::SYSTEM_PROMPT);
+        completion.add_message(
+            Role::User,
+            acknowledgement::prompt(
+                &person_name,
+                identity_summary.as_deref(),
+                scene_name.as_deref(),
+                count,
+            )
+            .as_str(),
+        );
+
+        let response = completion
+            .send_request(self.open_ai_key.as_ref(), self.open_ai_client.clone())
+            .await
+            .map_err(|err| err.message())?;
+
+        let acknowledgements = response.as_message().map_err(|err| err.message())?;
+
+        self.logger.log(
+            Level::Info,
+            format!(
+                "Generated acknowledgements for {}:\n{}",
+                person_name, acknowledgements
+            )
+            .as_str(),
+        );
+
+        Ok(acknowledgements)
+    }
+}
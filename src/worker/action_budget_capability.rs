@@ -0,0 +1,62 @@
+use crate::capability::action_budget::ActionBudgetCapability;
+use crate::domain::action_budget::{ActionBudget, WORLD_DAY_MS};
+use crate::domain::person_uuid::PersonUuid;
+use crate::worker::Worker;
+use sqlx::Row;
+
+impl ActionBudgetCapability for Worker {
+    async fn get_action_budgets(&self) -> Result<Vec<ActionBudget>, String> {
+        let rows = sqlx::query(
+            r#"
+                SELECT action_name, per_world_day
+                FROM action_budget
+                ORDER BY action_name
+            "#,
+        )
+        .fetch_all(&self.sqlx)
+        .await
+        .map_err(|err| format!("Error fetching action budgets: {}", err))?;
+
+        rows.into_iter()
+            .map(|row| {
+                Ok(ActionBudget {
+                    action_name: row
+                        .try_get::<String, _>("action_name")
+                        .map_err(|err| format!("Error reading action_name: {}", err))?,
+                    per_world_day: row
+                        .try_get::<i64, _>("per_world_day")
+                        .map_err(|err| format!("Error reading per_world_day: {}", err))?,
+                })
+            })
+            .collect()
+    }
+
+    async fn count_reactions_in_world_day(
+        &self,
+        person_uuid: &PersonUuid,
+        reaction_kinds: Vec<String>,
+    ) -> Result<i64, String> {
+        let row = sqlx::query(
+            r#"
+                SELECT COUNT(*) AS reaction_count
+                FROM reaction_history
+                WHERE person_uuid = $1::UUID
+                  AND action_kind = ANY($2::TEXT[])
+                  AND world_day = (
+                      SELECT active_ms / $3::BIGINT
+                      FROM active_clock
+                      WHERE id = TRUE
+                  )
+            "#,
+        )
+        .bind(person_uuid.to_uuid())
+        .bind(&reaction_kinds[..])
+        .bind(WORLD_DAY_MS)
+        .fetch_one(&self.sqlx)
+        .await
+        .map_err(|err| format!("Error counting reactions in world day: {}", err))?;
+
+        row.try_get::<i64, _>("reaction_count")
+            .map_err(|err| format!("Error reading reaction_count: {}", err))
+    }
+}
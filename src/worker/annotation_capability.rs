@@ -0,0 +1,122 @@
+use crate::capability::annotation::AnnotationCapability;
+use crate::domain::annotation::{Annotation, NewAnnotation};
+use crate::domain::annotation_uuid::AnnotationUuid;
+use crate::domain::scene_uuid::SceneUuid;
+use crate::worker::Worker;
+use chrono::{DateTime, Utc};
+use sqlx::Row;
+use uuid::Uuid;
+
+impl AnnotationCapability for Worker {
+    async fn add_annotation(&self, annotation: &NewAnnotation) -> Result<AnnotationUuid, String> {
+        let annotation_uuid = AnnotationUuid::new();
+
+        sqlx::query(
+            r#"
+                INSERT INTO annotation (uuid, scene_uuid, at, item_summary, note, is_bookmark, include_in_export)
+                VALUES ($1::UUID, $2::UUID, $3::TIMESTAMPTZ, $4::TEXT, $5::TEXT, $6::BOOLEAN, $7::BOOLEAN);
+            "#,
+        )
+        .bind(annotation_uuid.to_uuid())
+        .bind(annotation.scene_uuid.to_uuid())
+        .bind(annotation.at)
+        .bind(&annotation.item_summary)
+        .bind(&annotation.note)
+        .bind(annotation.is_bookmark)
+        .bind(annotation.include_in_export)
+        .execute(&self.sqlx)
+        .await
+        .map_err(|err| format!("Error adding annotation: {}", err))?;
+
+        Ok(annotation_uuid)
+    }
+
+    async fn get_scene_annotations(
+        &self,
+        scene_uuid: &SceneUuid,
+    ) -> Result<Vec<Annotation>, String> {
+        let rows = sqlx::query(
+            r#"
+                SELECT uuid, at, item_summary, note, is_bookmark, include_in_export
+                FROM annotation
+                WHERE scene_uuid = $1::UUID
+                ORDER BY at ASC, created_at ASC;
+            "#,
+        )
+        .bind(scene_uuid.to_uuid())
+        .fetch_all(&self.sqlx)
+        .await
+        .map_err(|err| format!("Error getting scene annotations: {}", err))?;
+
+        rows.into_iter()
+            .map(|row| {
+                let uuid: Uuid = row
+                    .try_get("uuid")
+                    .map_err(|err| format!("Error reading uuid from row: {}", err))?;
+                let at: DateTime<Utc> = row
+                    .try_get("at")
+                    .map_err(|err| format!("Error reading at from row: {}", err))?;
+                let item_summary: String = row
+                    .try_get("item_summary")
+                    .map_err(|err| format!("Error reading item_summary from row: {}", err))?;
+                let note: String = row
+                    .try_get("note")
+                    .map_err(|err| format!("Error reading note from row: {}", err))?;
+                let is_bookmark: bool = row
+                    .try_get("is_bookmark")
+                    .map_err(|err| format!("Error reading is_bookmark from row: {}", err))?;
+                let include_in_export: bool = row
+                    .try_get("include_in_export")
+                    .map_err(|err| format!("Error reading include_in_export from row: {}", err))?;
+
+                Ok(Annotation {
+                    uuid: AnnotationUuid::from_uuid(uuid),
+                    at,
+                    item_summary,
+                    note,
+                    is_bookmark,
+                    include_in_export,
+                })
+            })
+            .collect()
+    }
+
+    async fn set_annotation_flags(
+        &self,
+        annotation_uuid: &AnnotationUuid,
+        is_bookmark: bool,
+        include_in_export: bool,
+    ) -> Result<(), String> {
+        sqlx::query(
+            r#"
+                UPDATE annotation
+                SET is_bookmark = $2::BOOLEAN,
+                    include_in_export = $3::BOOLEAN
+                WHERE uuid = $1::UUID;
+            "#,
+        )
+        .bind(annotation_uuid.to_uuid())
+        .bind(is_bookmark)
+        .bind(include_in_export)
+        .execute(&self.sqlx)
+        .await
+        .map_err(|err| format!("Error updating annotation: {}", err))?;
+
+        Ok(())
+    }
+
+    async fn delete_annotation(&self, annotation_uuid: &AnnotationUuid) -> Result<(), String> {
+        sqlx::query(
+            r#"
+                DELETE FROM annotation
+                WHERE uuid = $1::UUID;
+            "#,
+        )
+        .bind(annotation_uuid.to_uuid())
+        .execute(&self.sqlx)
+        .await
+        .map_err(|err| format!("Error deleting annotation: {}", err))?;
+
+        Ok(())
+    }
+}
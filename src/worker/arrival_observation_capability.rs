@@ -0,0 +1,130 @@
+use crate::capability::arrival_observation::ArrivalObservationCapability;
+use crate::domain::logger::Level;
+use crate::domain::message::{Message, MessageSender};
+use crate::domain::message_uuid::MessageUuid;
+use crate::domain::person_uuid::PersonUuid;
+use crate::domain::scene_uuid::SceneUuid;
+use crate::nice_display::NiceDisplay;
+use crate::open_ai::completion::Completion;
+use crate::open_ai::role::Role;
+use crate::worker::Worker;
+use chrono::{DateTime, Utc};
+use sqlx::Row;
+use uuid::Uuid;
+
+impl ArrivalObservationCapability for Worker {
+    async fn get_recent_public_scene_messages(
+        &self,
+        scene_uuid: &SceneUuid,
+        since: DateTime<Utc>,
+    ) -> Result<Vec<Message>, String> {
+        let rows = sqlx::query(
+            r#"
+                SELECT
+                    message.uuid,
+                    message.sender_person_uuid,
+                    message_content.body AS content,
+                    message.sent_at
+                FROM message
+                JOIN message_content ON message_content.hash = message.content_hash
+                WHERE message.scene_uuid = $1::UUID
+                  AND message.sent_at >= $2
+                  AND message.audience = 'everyone'
+                  AND message.superseded_at IS NULL
+                ORDER BY message.sent_at
+            "#,
+        )
+        .bind(scene_uuid.to_uuid())
+        .bind(since)
+        .fetch_all(&self.sqlx)
+        .await
+        .map_err(|err| format!("Error fetching recent scene messages: {}", err))?;
+
+        let mut messages = Vec::with_capacity(rows.len());
+
+        for row in rows {
+            let uuid = row
+                .try_get::<Uuid, _>("uuid")
+                .map_err(|err| format!("Error reading message uuid: {}", err))?;
+            let sender_person_uuid = row
+                .try_get::<Option<Uuid>, _>("sender_person_uuid")
+                .map_err(|err| format!("Error reading sender_person_uuid: {}", err))?;
+            let content = row
+                .try_get::<String, _>("content")
+                .map_err(|err| format!("Error reading content: {}", err))?;
+            let sent_at = row
+                .try_get::<DateTime<Utc>, _>("sent_at")
+                .map_err(|err| format!("Error reading sent_at: {}", err))?;
+
+            messages.push(Message {
+                uuid: MessageUuid::from_uuid(uuid),
+                sender: match sender_person_uuid {
+                    Some(uuid) => MessageSender::AiPerson(PersonUuid::from_uuid(uuid)),
+                    None => MessageSender::RealWorldUser,
+                },
+                scene_uuid: scene_uuid.clone(),
+                content,
+                sent_at,
+            });
+        }
+
+        Ok(messages)
+    }
+
+    async fn summarize_arrival_observation(
+        &self,
+        scene_description: &str,
+        transcript: &str,
+    ) -> Result<String, String> {
+        let mut completion = Completion::new();
+        completion.add_message(
+            Role::System,
+            "You describe what a person notices in the first few moments after walking into a room where a conversation is already going on. Write two to four sentences in the second person. Mention the setting briefly, who is talking, and the gist of what they seem to be discussing. Only include what could be picked up from the transcript; do not quote it at length or invent details.",
+        );
+        completion.add_message(
+            Role::User,
+            format!(
+                "Scene description:\n{}\n\nWhat was said in the last few minutes:\n{}",
+                scene_description, transcript
+            )
+            .as_str(),
+        );
+
+        let response = completion
+            .send_request(self.open_ai_key.as_ref(), self.open_ai_client.clone())
+            .await
+            .map_err(|err| err.message())?;
+
+        let observation = response.as_message().map_err(|err| err.message())?;
+
+        self.logger.log(
+            Level::Info,
+            format!("Summarized arrival observation:\n{}", observation).as_str(),
+        );
+
+        Ok(observation)
+    }
+
+    async fn record_arrival_observation(
+        &self,
+        person_uuid: &PersonUuid,
+        scene_uuid: &SceneUuid,
+        observation: String,
+    ) -> Result<(), String> {
+        sqlx::query(
+            r#"
+                INSERT INTO scene_arrival_observation (uuid, person_uuid, scene_uuid, observation)
+                VALUES ($1::UUID, $2::UUID, $3::UUID, $4::TEXT)
+            "#,
+        )
+        .bind(Uuid::now_v7())
+        .bind(person_uuid.to_uuid())
+        .bind(scene_uuid.to_uuid())
+        .bind(observation)
+        .execute(&self.sqlx)
+        .await
+        .map_err(|err| format!("Error recording arrival observation: {}", err))?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,94 @@
+use crate::capability::autobiography::AutobiographyCapability;
+use crate::domain::autobiography::{self, LifeEvent, LifeEventKind};
+use crate::domain::person_uuid::PersonUuid;
+use crate::nice_display::NiceDisplay;
+use crate::open_ai::completion::Completion;
+use crate::open_ai::role::Role;
+use crate::worker::Worker;
+use chrono::{DateTime, Utc};
+use sqlx::Row;
+
+impl AutobiographyCapability for Worker {
+    async fn get_life_events(&self, person_uuid: &PersonUuid) -> Result<Vec<LifeEvent>, String> {
+        // `person_identity.created_at` has no time zone, and is read as UTC
+        let rows = sqlx::query(
+            r#"
+                SELECT at, kind, text FROM (
+                    SELECT created_at AS at, 'memory'::TEXT AS kind, content AS text
+                    FROM memory
+                    WHERE person_uuid = $1::UUID
+
+                    UNION ALL
+
+                    SELECT created_at AT TIME ZONE 'UTC', 'identity', identity
+                    FROM person_identity
+                    WHERE person_uuid = $1::UUID
+
+                    UNION ALL
+
+                    SELECT scene_participant.joined_at, 'joined scene', scene.name
+                    FROM scene_participant
+                    JOIN scene ON scene.uuid = scene_participant.scene_uuid
+                    WHERE scene_participant.person_uuid = $1::UUID
+
+                    UNION ALL
+
+                    SELECT scene_participant.left_at, 'left scene', scene.name
+                    FROM scene_participant
+                    JOIN scene ON scene.uuid = scene_participant.scene_uuid
+                    WHERE scene_participant.person_uuid = $1::UUID
+                      AND scene_participant.left_at IS NOT NULL
+                ) events
+                ORDER BY at ASC;
+            "#,
+        )
+        .bind(person_uuid.to_uuid())
+        .fetch_all(&self.sqlx)
+        .await
+        .map_err(|err| format!("Error fetching life events: {}", err))?;
+
+        rows.iter()
+            .map(|row| {
+                let at = row
+                    .try_get::<DateTime<Utc>, _>("at")
+                    .map_err(|err| format!("Error reading life event time: {}", err))?;
+                let kind = row
+                    .try_get::<String, _>("kind")
+                    .map_err(|err| format!("Error reading life event kind: {}", err))?;
+                let text = row
+                    .try_get::<String, _>("text")
+                    .map_err(|err| format!("Error reading life event text: {}", err))?;
+
+                let kind = match kind.as_str() {
+                    "memory" => LifeEventKind::Memory,
+                    "identity" => LifeEventKind::Identity,
+                    "joined scene" => LifeEventKind::JoinedScene,
+                    _ => LifeEventKind::LeftScene,
+                };
+
+                Ok(LifeEvent { at, kind, text })
+            })
+            .collect()
+    }
+
+    async fn write_autobiography_chapter(
+        &self,
+        person_name: &str,
+        previous_chapter: Option<&str>,
+        events: &[LifeEvent],
+    ) -> Result<String, String> {
+        let mut completion = Completion::new();
+        completion.add_message(Role::System, autobiography::system_prompt());
+        completion.add_message(
+            Role::User,
+            autobiography::chapter_prompt(person_name, previous_chapter, events).as_str(),
+        );
+
+        let response = completion
+            .send_request(self.open_ai_key.as_ref(), self.open_ai_client.clone())
+            .await
+            .map_err(|err| err.message())?;
+
+        response.as_message().map_err(|err| err.message())
+    }
+}
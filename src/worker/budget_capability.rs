@@ -0,0 +1,104 @@
+use crate::capability::budget::BudgetCapability;
+use crate::domain::budget::{HourlySpend, LlmSpend, RunBudget};
+use crate::worker::Worker;
+use chrono::{DateTime, Utc};
+use sqlx::Row;
+
+impl BudgetCapability for Worker {
+    async fn get_run_budget(&self) -> Result<RunBudget, String> {
+        let row = sqlx::query(
+            r#"
+                SELECT budget_usd, set_at
+                FROM run_budget
+                WHERE id = TRUE;
+            "#,
+        )
+        .fetch_optional(&self.sqlx)
+        .await
+        .map_err(|err| format!("Error fetching run budget: {}", err))?;
+
+        let row = match row {
+            Some(row) => row,
+            None => return Err("Run budget is missing from run_budget".to_string()),
+        };
+
+        let budget_usd = row
+            .try_get::<Option<f64>, _>("budget_usd")
+            .map_err(|err| format!("Error reading budget_usd: {}", err))?;
+        let set_at = row
+            .try_get::<DateTime<Utc>, _>("set_at")
+            .map_err(|err| format!("Error reading set_at: {}", err))?;
+
+        Ok(RunBudget { budget_usd, set_at })
+    }
+
+    async fn set_run_budget(&self, budget_usd: Option<f64>) -> Result<(), String> {
+        sqlx::query(
+            r#"
+                UPDATE run_budget
+                SET budget_usd = $1,
+                    set_at = now()
+                WHERE id = TRUE;
+            "#,
+        )
+        .bind(budget_usd)
+        .execute(&self.sqlx)
+        .await
+        .map_err(|err| format!("Error updating run budget: {}", err))?;
+
+        Ok(())
+    }
+
+    async fn get_llm_spend(&self, since: DateTime<Utc>) -> Result<LlmSpend, String> {
+        // Failed attempts usually report no tokens, so they cost nothing and
+        // are not counted as unpriced either
+        let rows = sqlx::query(
+            r#"
+                SELECT date_trunc('hour', llm_call.created_at) AS hour,
+                       COALESCE(SUM(
+                           (COALESCE(llm_call.prompt_tokens, 0) * llm_price.input_usd_per_million
+                               + COALESCE(llm_call.completion_tokens, 0) * llm_price.output_usd_per_million)
+                               / 1000000.0
+                       ), 0)::DOUBLE PRECISION AS spent_usd,
+                       COUNT(*) FILTER (
+                           WHERE llm_price.model IS NULL
+                             AND (llm_call.prompt_tokens IS NOT NULL
+                               OR llm_call.completion_tokens IS NOT NULL)
+                       ) AS unpriced_calls
+                FROM llm_call
+                LEFT JOIN llm_price ON llm_price.model = llm_call.model
+                WHERE llm_call.created_at >= $1
+                GROUP BY hour
+                ORDER BY hour;
+            "#,
+        )
+        .bind(since)
+        .fetch_all(&self.sqlx)
+        .await
+        .map_err(|err| format!("Error fetching llm spend: {}", err))?;
+
+        let mut spend = LlmSpend {
+            spent_usd: 0.0,
+            unpriced_calls: 0,
+            hourly: Vec::with_capacity(rows.len()),
+        };
+
+        for row in rows {
+            let hour = row
+                .try_get::<DateTime<Utc>, _>("hour")
+                .map_err(|err| format!("Error reading spend hour: {}", err))?;
+            let spent_usd = row
+                .try_get::<f64, _>("spent_usd")
+                .map_err(|err| format!("Error reading spent_usd: {}", err))?;
+            let unpriced_calls = row
+                .try_get::<i64, _>("unpriced_calls")
+                .map_err(|err| format!("Error reading unpriced_calls: {}", err))?;
+
+            spend.spent_usd += spent_usd;
+            spend.unpriced_calls += unpriced_calls;
+            spend.hourly.push(HourlySpend { hour, spent_usd });
+        }
+
+        Ok(spend)
+    }
+}
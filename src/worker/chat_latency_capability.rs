@@ -0,0 +1,27 @@
+use crate::capability::chat_latency::ChatLatencyCapability;
+use crate::worker::Worker;
+use chrono::{DateTime, Utc};
+use sqlx::Row;
+
+impl ChatLatencyCapability for Worker {
+    async fn get_chat_latencies_ms(&self, since: DateTime<Utc>) -> Result<Vec<i64>, String> {
+        let rows = sqlx::query(
+            r#"
+                SELECT latency_ms
+                FROM chat_latency
+                WHERE replied_at >= $1::TIMESTAMPTZ;
+            "#,
+        )
+        .bind(since)
+        .fetch_all(&self.sqlx)
+        .await
+        .map_err(|err| format!("Error fetching chat latencies: {}", err))?;
+
+        rows.iter()
+            .map(|row| {
+                row.try_get::<i64, _>("latency_ms")
+                    .map_err(|err| format!("Error reading latency_ms: {}", err))
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,9 @@
+use crate::capability::clock::ClockCapability;
+use crate::worker::Worker;
+use chrono::{DateTime, Utc};
+
+impl ClockCapability for Worker {
+    fn now(&self) -> DateTime<Utc> {
+        self.clock.now()
+    }
+}
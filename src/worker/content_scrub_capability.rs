@@ -0,0 +1,67 @@
+use crate::capability::content_scrub::ContentScrubCapability;
+use crate::domain::content_scrub::ContentScrubSettings;
+use crate::worker::Worker;
+use sqlx::Row;
+
+impl ContentScrubCapability for Worker {
+    async fn get_content_scrub_settings(&self) -> Result<ContentScrubSettings, String> {
+        let row = sqlx::query(
+            r#"
+                SELECT scrub_personal_data, scrub_profanity
+                FROM content_scrub_setting
+                WHERE id = TRUE;
+            "#,
+        )
+        .fetch_optional(&self.sqlx)
+        .await
+        .map_err(|err| format!("Error fetching content scrub settings: {}", err))?;
+
+        let row = match row {
+            Some(row) => row,
+            None => {
+                return Err(
+                    "Content scrub settings are missing from content_scrub_setting".to_string(),
+                );
+            }
+        };
+
+        let scrub_personal_data = row
+            .try_get::<bool, _>("scrub_personal_data")
+            .map_err(|err| format!("Error reading scrub_personal_data: {}", err))?;
+
+        let scrub_profanity = row
+            .try_get::<bool, _>("scrub_profanity")
+            .map_err(|err| format!("Error reading scrub_profanity: {}", err))?;
+
+        Ok(ContentScrubSettings {
+            scrub_personal_data,
+            scrub_profanity,
+        })
+    }
+
+    async fn set_content_scrub_settings(
+        &self,
+        settings: ContentScrubSettings,
+    ) -> Result<(), String> {
+        sqlx::query(
+            r#"
+                UPDATE content_scrub_setting
+                SET scrub_personal_data = $1,
+                    scrub_profanity = $2
+                WHERE id = TRUE;
+            "#,
+        )
+        .bind(settings.scrub_personal_data)
+        .bind(settings.scrub_profanity)
+        .execute(&self.sqlx)
+        .await
+        .map_err(|err| format!("Error updating content scrub settings: {}", err))?;
+
+        Ok(())
+    }
+
+    async fn scrub_content(&self, content: &str) -> Result<String, String> {
+        let settings = self.get_content_scrub_settings().await?;
+        settings.scrub(content)
+    }
+}
@@ -0,0 +1,108 @@
+use crate::capability::conversation_graph::ConversationGraphCapability;
+use crate::domain::conversation_graph::{
+    ConversationEdge, ConversationGraph, GraphPerson, NEGATIVE_WORDS, POSITIVE_WORDS,
+};
+use crate::domain::utterance::REAL_WORLD_USER_NAME;
+use crate::worker::Worker;
+use chrono::{DateTime, Utc};
+use sqlx::Row;
+
+impl ConversationGraphCapability for Worker {
+    async fn get_conversation_graph(
+        &self,
+        since: Option<DateTime<Utc>>,
+    ) -> Result<ConversationGraph, String> {
+        let person_rows = sqlx::query(
+            r#"
+                SELECT uuid, name
+                FROM person
+                WHERE is_enabled
+                ORDER BY name ASC
+            "#,
+        )
+        .fetch_all(&self.sqlx)
+        .await
+        .map_err(|err| format!("Error fetching people for conversation graph: {}", err))?;
+
+        let mut people = person_rows
+            .into_iter()
+            .map(|row| {
+                Ok(GraphPerson {
+                    person_uuid: Some(
+                        row.try_get::<uuid::Uuid, _>("uuid")
+                            .map_err(|err| format!("Error reading person uuid: {}", err))?,
+                    ),
+                    name: row
+                        .try_get::<String, _>("name")
+                        .map_err(|err| format!("Error reading person name: {}", err))?,
+                })
+            })
+            .collect::<Result<Vec<GraphPerson>, String>>()?;
+
+        // Overheard messages were not said to the listener, so they do not
+        // count as talking to them.
+        let edge_rows = sqlx::query(
+            r#"
+                SELECT
+                    message.sender_person_uuid AS from_uuid,
+                    scene_message_recipient.person_uuid AS to_uuid,
+                    COUNT(*) AS message_count,
+                    COALESCE(SUM(words.positive), 0)::BIGINT AS positive_words,
+                    COALESCE(SUM(words.negative), 0)::BIGINT AS negative_words
+                FROM message
+                JOIN message_content ON message_content.hash = message.content_hash
+                JOIN scene_message_recipient
+                    ON scene_message_recipient.message_uuid = message.uuid
+                LEFT JOIN LATERAL (
+                    SELECT
+                        COUNT(*) FILTER (WHERE word = ANY($2::TEXT[])) AS positive,
+                        COUNT(*) FILTER (WHERE word = ANY($3::TEXT[])) AS negative
+                    FROM regexp_split_to_table(LOWER(message_content.body), '[^a-z'']+') AS word
+                ) AS words ON true
+                WHERE scene_message_recipient.delivery = 'delivered'
+                  AND ($1::TIMESTAMPTZ IS NULL OR message.sent_at >= $1::TIMESTAMPTZ)
+                  AND scene_message_recipient.person_uuid IS DISTINCT FROM message.sender_person_uuid
+                GROUP BY message.sender_person_uuid, scene_message_recipient.person_uuid
+            "#,
+        )
+        .bind(since)
+        .bind(POSITIVE_WORDS)
+        .bind(NEGATIVE_WORDS)
+        .fetch_all(&self.sqlx)
+        .await
+        .map_err(|err| format!("Error fetching conversation graph: {}", err))?;
+
+        let edges = edge_rows
+            .into_iter()
+            .map(|row| {
+                Ok(ConversationEdge {
+                    from: row
+                        .try_get::<Option<uuid::Uuid>, _>("from_uuid")
+                        .map_err(|err| format!("Error reading edge sender: {}", err))?,
+                    to: Some(
+                        row.try_get::<uuid::Uuid, _>("to_uuid")
+                            .map_err(|err| format!("Error reading edge listener: {}", err))?,
+                    ),
+                    message_count: row
+                        .try_get::<i64, _>("message_count")
+                        .map_err(|err| format!("Error reading edge message count: {}", err))?,
+                    positive_words: row
+                        .try_get::<i64, _>("positive_words")
+                        .map_err(|err| format!("Error reading edge positive words: {}", err))?,
+                    negative_words: row
+                        .try_get::<i64, _>("negative_words")
+                        .map_err(|err| format!("Error reading edge negative words: {}", err))?,
+                })
+            })
+            .collect::<Result<Vec<ConversationEdge>, String>>()?;
+
+        if edges.iter().any(|edge| edge.from.is_none()) {
+            people.push(GraphPerson {
+                person_uuid: None,
+                name: REAL_WORLD_USER_NAME.to_string(),
+            });
+        }
+
+        Ok(ConversationGraph { people, edges })
+    }
+}
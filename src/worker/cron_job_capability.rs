@@ -0,0 +1,226 @@
+use crate::capability::clock::ClockCapability;
+use crate::capability::cron_job::CronJobCapability;
+use crate::domain::cron_job::{self, CronJob, NewCronJob};
+use crate::domain::cron_job_uuid::CronJobUuid;
+use crate::worker::Worker;
+use chrono::{DateTime, Utc};
+use sqlx::postgres::PgRow;
+use sqlx::Row;
+use uuid::Uuid;
+
+impl CronJobCapability for Worker {
+    async fn get_cron_jobs(&self) -> Result<Vec<CronJob>, String> {
+        let rows = sqlx::query(
+            r#"
+                SELECT uuid, name, expression, job_name, job_data, enabled, last_run_at, next_run_at
+                FROM cron_job
+                ORDER BY name ASC, created_at ASC;
+            "#,
+        )
+        .fetch_all(&self.sqlx)
+        .await
+        .map_err(|err| format!("Error fetching cron jobs: {}", err))?;
+
+        rows.iter().map(read_cron_job).collect()
+    }
+
+    async fn create_cron_job(&self, cron_job: &NewCronJob) -> Result<CronJobUuid, String> {
+        let cron_job_uuid = CronJobUuid::new();
+        let next_run_at = cron_job::next_run_after(&cron_job.expression, self.now())?;
+
+        sqlx::query(
+            r#"
+                INSERT INTO cron_job (uuid, name, expression, job_name, job_data, next_run_at)
+                VALUES ($1::UUID, $2::TEXT, $3::TEXT, $4::TEXT, $5::JSONB, $6::TIMESTAMPTZ);
+            "#,
+        )
+        .bind(cron_job_uuid.to_uuid())
+        .bind(&cron_job.name)
+        .bind(&cron_job.expression)
+        .bind(&cron_job.job_name)
+        .bind(&cron_job.job_data)
+        .bind(next_run_at)
+        .execute(&self.sqlx)
+        .await
+        .map_err(|err| format!("Error inserting cron job: {}", err))?;
+
+        Ok(cron_job_uuid)
+    }
+
+    async fn update_cron_job(
+        &self,
+        cron_job_uuid: &CronJobUuid,
+        cron_job: &NewCronJob,
+    ) -> Result<(), String> {
+        let next_run_at = cron_job::next_run_after(&cron_job.expression, self.now())?;
+
+        // A disabled cron job stays without a next run
+        sqlx::query(
+            r#"
+                UPDATE cron_job
+                SET name = $2::TEXT,
+                    expression = $3::TEXT,
+                    job_name = $4::TEXT,
+                    job_data = $5::JSONB,
+                    next_run_at = CASE WHEN enabled THEN $6::TIMESTAMPTZ ELSE NULL END
+                WHERE uuid = $1::UUID;
+            "#,
+        )
+        .bind(cron_job_uuid.to_uuid())
+        .bind(&cron_job.name)
+        .bind(&cron_job.expression)
+        .bind(&cron_job.job_name)
+        .bind(&cron_job.job_data)
+        .bind(next_run_at)
+        .execute(&self.sqlx)
+        .await
+        .map_err(|err| format!("Error updating cron job: {}", err))?;
+
+        Ok(())
+    }
+
+    async fn set_cron_job_enabled(
+        &self,
+        cron_job_uuid: &CronJobUuid,
+        enabled: bool,
+    ) -> Result<(), String> {
+        let row = sqlx::query(
+            r#"
+                SELECT expression
+                FROM cron_job
+                WHERE uuid = $1::UUID;
+            "#,
+        )
+        .bind(cron_job_uuid.to_uuid())
+        .fetch_one(&self.sqlx)
+        .await
+        .map_err(|err| format!("Error fetching cron job expression: {}", err))?;
+
+        let expression = row
+            .try_get::<String, _>("expression")
+            .map_err(|err| format!("Error reading expression from row: {}", err))?;
+
+        // Turning a cron job back on does not make up the runs it missed
+        let next_run_at = if enabled {
+            Some(cron_job::next_run_after(&expression, self.now())?)
+        } else {
+            None
+        };
+
+        sqlx::query(
+            r#"
+                UPDATE cron_job
+                SET enabled = $2::BOOLEAN,
+                    next_run_at = $3::TIMESTAMPTZ
+                WHERE uuid = $1::UUID;
+            "#,
+        )
+        .bind(cron_job_uuid.to_uuid())
+        .bind(enabled)
+        .bind(next_run_at)
+        .execute(&self.sqlx)
+        .await
+        .map_err(|err| format!("Error updating cron job enabled: {}", err))?;
+
+        Ok(())
+    }
+
+    async fn delete_cron_job(&self, cron_job_uuid: &CronJobUuid) -> Result<(), String> {
+        sqlx::query(
+            r#"
+                DELETE FROM cron_job
+                WHERE uuid = $1::UUID;
+            "#,
+        )
+        .bind(cron_job_uuid.to_uuid())
+        .execute(&self.sqlx)
+        .await
+        .map_err(|err| format!("Error deleting cron job: {}", err))?;
+
+        Ok(())
+    }
+
+    async fn get_due_cron_jobs(&self, now: DateTime<Utc>) -> Result<Vec<CronJob>, String> {
+        let rows = sqlx::query(
+            r#"
+                SELECT uuid, name, expression, job_name, job_data, enabled, last_run_at, next_run_at
+                FROM cron_job
+                WHERE enabled
+                  AND next_run_at <= $1::TIMESTAMPTZ
+                ORDER BY next_run_at ASC;
+            "#,
+        )
+        .bind(now)
+        .fetch_all(&self.sqlx)
+        .await
+        .map_err(|err| format!("Error fetching due cron jobs: {}", err))?;
+
+        rows.iter().map(read_cron_job).collect()
+    }
+
+    async fn claim_cron_job_run(
+        &self,
+        cron_job_uuid: &CronJobUuid,
+        due_at: DateTime<Utc>,
+        ran_at: DateTime<Utc>,
+        next_run_at: Option<DateTime<Utc>>,
+    ) -> Result<bool, String> {
+        let result = sqlx::query(
+            r#"
+                UPDATE cron_job
+                SET last_run_at = $3::TIMESTAMPTZ,
+                    next_run_at = $4::TIMESTAMPTZ
+                WHERE uuid = $1::UUID
+                  AND enabled
+                  AND next_run_at = $2::TIMESTAMPTZ;
+            "#,
+        )
+        .bind(cron_job_uuid.to_uuid())
+        .bind(due_at)
+        .bind(ran_at)
+        .bind(next_run_at)
+        .execute(&self.sqlx)
+        .await
+        .map_err(|err| format!("Error claiming cron job run: {}", err))?;
+
+        Ok(result.rows_affected() == 1)
+    }
+}
+
+fn read_cron_job(row: &PgRow) -> Result<CronJob, String> {
+    let uuid = row
+        .try_get::<Uuid, _>("uuid")
+        .map_err(|err| format!("Error reading uuid from row: {}", err))?;
+    let name = row
+        .try_get::<String, _>("name")
+        .map_err(|err| format!("Error reading name from row: {}", err))?;
+    let expression = row
+        .try_get::<String, _>("expression")
+        .map_err(|err| format!("Error reading expression from row: {}", err))?;
+    let job_name = row
+        .try_get::<String, _>("job_name")
+        .map_err(|err| format!("Error reading job_name from row: {}", err))?;
+    let job_data = row
+        .try_get::<Option<serde_json::Value>, _>("job_data")
+        .map_err(|err| format!("Error reading job_data from row: {}", err))?;
+    let enabled = row
+        .try_get::<bool, _>("enabled")
+        .map_err(|err| format!("Error reading enabled from row: {}", err))?;
+    let last_run_at = row
+        .try_get::<Option<DateTime<Utc>>, _>("last_run_at")
+        .map_err(|err| format!("Error reading last_run_at from row: {}", err))?;
+    let next_run_at = row
+        .try_get::<Option<DateTime<Utc>>, _>("next_run_at")
+        .map_err(|err| format!("Error reading next_run_at from row: {}", err))?;
+
+    Ok(CronJob {
+        uuid: CronJobUuid::from_uuid(uuid),
+        name,
+        expression,
+        job_name,
+        job_data,
+        enabled,
+        last_run_at,
+        next_run_at,
+    })
+}
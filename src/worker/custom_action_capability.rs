@@ -0,0 +1,33 @@
+use crate::capability::custom_action::CustomActionCapability;
+use crate::worker::Worker;
+use std::time::Duration;
+
+const WEBHOOK_TIMEOUT: Duration = Duration::from_secs(30);
+
+impl CustomActionCapability for Worker {
+    async fn post_custom_action(&self, url: &str, body: &serde_json::Value) -> Result<(), String> {
+        let client = reqwest::Client::builder()
+            .timeout(WEBHOOK_TIMEOUT)
+            .build()
+            .map_err(|err| format!("Error building webhook client: {}", err))?;
+
+        let response = client
+            .post(url)
+            .header("Content-Type", "application/json")
+            .json(body)
+            .send()
+            .await
+            .map_err(|err| format!("Error posting to webhook: {}", err))?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let body = match response.text().await {
+                Ok(body) => body,
+                Err(err) => format!("(could not read the response body: {})", err),
+            };
+            return Err(format!("Webhook responded with {}: {}", status, body));
+        }
+
+        Ok(())
+    }
+}
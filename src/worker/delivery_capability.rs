@@ -0,0 +1,99 @@
+use crate::capability::delivery::DeliveryCapability;
+use crate::domain::delivery::{DeliveryCounts, DeliveryStatus, FailedDelivery};
+use crate::domain::outbox_uuid::OutboxUuid;
+use crate::worker::Worker;
+use chrono::{DateTime, Utc};
+use sqlx::Row;
+
+impl DeliveryCapability for Worker {
+    async fn get_failed_deliveries(&self, limit: i64) -> Result<Vec<FailedDelivery>, String> {
+        let rows = sqlx::query(
+            r#"
+                SELECT
+                    delivery.outbox_uuid,
+                    delivery.integration,
+                    delivery.details,
+                    delivery.created_at,
+                    outbox.event_name,
+                    outbox.payload->>'content' AS content,
+                    outbox.delivered_at IS NOT NULL AS delivered_since
+                FROM delivery
+                JOIN outbox ON outbox.uuid = delivery.outbox_uuid
+                WHERE delivery.status = $1::TEXT
+                ORDER BY delivery.created_at DESC
+                LIMIT $2;
+            "#,
+        )
+        .bind(DeliveryStatus::Failed.to_name())
+        .bind(limit)
+        .fetch_all(&self.sqlx)
+        .await
+        .map_err(|err| format!("Error getting failed deliveries: {}", err))?;
+
+        rows.into_iter()
+            .map(|row| {
+                Ok(FailedDelivery {
+                    outbox_uuid: OutboxUuid::from_uuid(
+                        row.try_get::<uuid::Uuid, _>("outbox_uuid").map_err(|err| {
+                            format!("Error reading delivery outbox uuid: {}", err)
+                        })?,
+                    ),
+                    integration: row
+                        .try_get::<String, _>("integration")
+                        .map_err(|err| format!("Error reading delivery integration: {}", err))?,
+                    event_name: row
+                        .try_get::<String, _>("event_name")
+                        .map_err(|err| format!("Error reading delivery event name: {}", err))?,
+                    content: row
+                        .try_get::<Option<String>, _>("content")
+                        .map_err(|err| format!("Error reading delivery content: {}", err))?,
+                    details: row
+                        .try_get::<Option<String>, _>("details")
+                        .map_err(|err| format!("Error reading delivery details: {}", err))?
+                        .unwrap_or_default(),
+                    delivered_since: row
+                        .try_get::<bool, _>("delivered_since")
+                        .map_err(|err| format!("Error reading delivered flag: {}", err))?,
+                    created_at: row
+                        .try_get::<DateTime<Utc>, _>("created_at")
+                        .map_err(|err| format!("Error reading delivery created_at: {}", err))?,
+                })
+            })
+            .collect()
+    }
+
+    async fn get_delivery_counts_since(
+        &self,
+        since: DateTime<Utc>,
+    ) -> Result<DeliveryCounts, String> {
+        let rows = sqlx::query(
+            r#"
+                SELECT status, COUNT(*) AS count
+                FROM delivery
+                WHERE created_at >= $1::TIMESTAMPTZ
+                GROUP BY status;
+            "#,
+        )
+        .bind(since)
+        .fetch_all(&self.sqlx)
+        .await
+        .map_err(|err| format!("Error counting deliveries: {}", err))?;
+
+        let mut counts = DeliveryCounts::default();
+
+        for row in rows {
+            let name = row
+                .try_get::<String, _>("status")
+                .map_err(|err| format!("Error reading delivery status: {}", err))?;
+            let count = row
+                .try_get::<i64, _>("count")
+                .map_err(|err| format!("Error reading delivery count: {}", err))?;
+            let status = DeliveryStatus::parse(&name)
+                .ok_or_else(|| format!("Unknown delivery status \"{}\"", name))?;
+
+            counts.add(status, count);
+        }
+
+        Ok(counts)
+    }
+}
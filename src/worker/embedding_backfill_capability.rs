@@ -0,0 +1,102 @@
+use crate::capability::embedding_backfill::EmbeddingBackfillCapability;
+use crate::domain::embedding_backfill::{self, MemoryToEmbed};
+use crate::domain::memory_uuid::MemoryUuid;
+use crate::nice_display::NiceDisplay;
+use crate::open_ai::embedding::EmbeddingRequest;
+use crate::worker::Worker;
+use sqlx::Row;
+use uuid::Uuid;
+
+/// `embedding` is NOT NULL today, but rows from before that or from a
+/// hand-run import are looked for all the same.
+const MISSING_EMBEDDING_CONDITION: &str = r#"
+    (
+        embedding IS NULL
+        OR vector_dims(embedding) <> embedding_dimension
+        OR vector_norm(embedding) = 0
+    )
+"#;
+
+impl EmbeddingBackfillCapability for Worker {
+    async fn count_memories_missing_embeddings(&self) -> Result<i64, String> {
+        let row = sqlx::query(&format!(
+            "SELECT COUNT(*) AS count FROM memory WHERE {};",
+            MISSING_EMBEDDING_CONDITION
+        ))
+        .fetch_one(&self.sqlx)
+        .await
+        .map_err(|err| format!("Error counting memories missing embeddings: {}", err))?;
+
+        row.try_get::<i64, _>("count")
+            .map_err(|err| format!("Error reading memory count: {}", err))
+    }
+
+    async fn get_memories_missing_embeddings(
+        &self,
+        after: Option<&MemoryUuid>,
+        limit: i64,
+    ) -> Result<Vec<MemoryToEmbed>, String> {
+        let rows = sqlx::query(&format!(
+            r#"
+                SELECT uuid, content, retrieval_summary
+                FROM memory
+                WHERE {}
+                  AND ($1::UUID IS NULL OR uuid > $1::UUID)
+                ORDER BY uuid ASC
+                LIMIT $2::BIGINT;
+            "#,
+            MISSING_EMBEDDING_CONDITION
+        ))
+        .bind(after.map(MemoryUuid::to_uuid))
+        .bind(limit)
+        .fetch_all(&self.sqlx)
+        .await
+        .map_err(|err| format!("Error fetching memories missing embeddings: {}", err))?;
+
+        rows.iter()
+            .map(|row| {
+                Ok(MemoryToEmbed {
+                    memory_uuid: MemoryUuid::from_uuid(
+                        row.try_get::<Uuid, _>("uuid")
+                            .map_err(|err| format!("Error reading memory uuid: {}", err))?,
+                    ),
+                    content: row
+                        .try_get::<String, _>("content")
+                        .map_err(|err| format!("Error reading memory content: {}", err))?,
+                    retrieval_summary: row.try_get::<String, _>("retrieval_summary").map_err(
+                        |err| format!("Error reading memory retrieval summary: {}", err),
+                    )?,
+                })
+            })
+            .collect()
+    }
+
+    async fn re_embed_memory(&self, memory: &MemoryToEmbed) -> Result<(), String> {
+        let embedding_request = EmbeddingRequest::new(memory.embedding_text().to_string());
+        let embedding = embedding_request
+            .create(self.open_ai_key.as_ref(), self.open_ai_client.clone())
+            .await
+            .map_err(|err| err.message())?;
+
+        embedding_backfill::check_embedding(embedding_request.model(), &embedding)?;
+
+        sqlx::query(
+            r#"
+                UPDATE memory
+                SET embedding = $2::vector,
+                    embedding_model = $3::TEXT,
+                    embedding_dimension = $4::INT
+                WHERE uuid = $1::UUID;
+            "#,
+        )
+        .bind(memory.memory_uuid.to_uuid())
+        .bind(&embedding[..] as &[f32])
+        .bind(embedding_request.model().to_string())
+        .bind(embedding_request.model().dimension())
+        .execute(&self.sqlx)
+        .await
+        .map_err(|err| format!("Error storing memory embedding: {}", err))?;
+
+        Ok(())
+    }
+}
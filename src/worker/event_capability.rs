@@ -5,8 +5,10 @@ use crate::domain::event::{Event, EventType};
 use crate::domain::message_uuid::MessageUuid;
 use crate::domain::person_uuid::PersonUuid;
 use crate::domain::scene_uuid::SceneUuid;
+use crate::domain::utterance::REAL_WORLD_USER_NAME;
 use crate::temporary_event_cutoff::event_history_cutoff;
 use crate::worker::Worker;
+use chrono::{DateTime, Utc};
 
 impl EventCapability for Worker {
     async fn get_events(&self, args: GetArgs) -> Result<Vec<Event>, String> {
@@ -38,45 +40,30 @@ impl EventCapability for Worker {
                 if let Some(record) = participant_record {
                     let joined_at = record.joined_at;
 
-                    // Get scene messages since they joined
-                    let scene_messages = sqlx::query!(
-                        r#"
-                        SELECT uuid, sender_person_uuid, content, sent_at
-                        FROM message
-                        WHERE scene_uuid = $1
-                          AND sent_at >= $2
-                        ORDER BY sent_at
-                        "#,
-                        scene_uuid.to_uuid(),
-                        joined_at
-                    )
-                    .fetch_all(&self.sqlx)
-                    .await
-                    .map_err(|err| format!("Error fetching scene messages: {}", err))?;
-
-                    for msg in scene_messages {
-                        let speaker_name = match msg.sender_person_uuid {
-                            Some(sender_uuid) => {
-                                let sender_person_uuid = PersonUuid::from_uuid(sender_uuid);
-                                let sender_name =
-                                    self.get_persons_name(sender_person_uuid).await.map_err(
-                                        |err| format!("Error fetching sender name: {}", err),
-                                    )?;
-                                sender_name.as_str().to_string()
-                            }
-                            None => "Chadtech".to_string(),
-                        };
-
-                        events.push(Event::new(
-                            msg.sent_at,
-                            EventType::Said {
-                                scene_name: scene_name.clone(),
-                                speaker_name,
-                                comment: msg.content,
-                                message_uuid: MessageUuid::from_uuid(msg.uuid),
-                            },
-                        ));
-                    }
+                    events.extend(
+                        get_arrival_observations(
+                            self,
+                            &person_uuid,
+                            &scene_uuid,
+                            &scene_name,
+                            joined_at,
+                            None,
+                        )
+                        .await?,
+                    );
+
+                    // Get the scene messages they heard since they joined
+                    events.extend(
+                        get_heard_scene_messages(
+                            self,
+                            &person_uuid,
+                            &scene_uuid,
+                            &scene_name,
+                            joined_at,
+                            None,
+                        )
+                        .await?,
+                    );
 
                     // Get scene participant joins/leaves since they joined
                     let participant_events = sqlx::query!(
@@ -154,48 +141,29 @@ impl EventCapability for Worker {
                     let joined_at = participation.joined_at;
                     let left_at = participation.left_at;
 
-                    let scene_messages = sqlx::query!(
-                        r#"
-                        SELECT uuid, sender_person_uuid, content, sent_at
-                        FROM message
-                        WHERE scene_uuid = $1::UUID
-                          AND sent_at >= $2
-                          AND ($3::timestamptz IS NULL OR sent_at <= $3::timestamptz)
-                        ORDER BY sent_at
-                        "#,
-                        scene_uuid.to_uuid(),
-                        joined_at,
-                        left_at
-                    )
-                    .fetch_all(&self.sqlx)
-                    .await
-                    .map_err(|err| {
-                        format!("Error fetching person-scoped scene messages: {}", err)
-                    })?;
-
-                    for msg in scene_messages {
-                        let speaker_name = match msg.sender_person_uuid {
-                            Some(sender_uuid) => {
-                                let sender_person_uuid = PersonUuid::from_uuid(sender_uuid);
-                                let sender_name =
-                                    self.get_persons_name(sender_person_uuid).await.map_err(
-                                        |err| format!("Error fetching sender name: {}", err),
-                                    )?;
-                                sender_name.as_str().to_string()
-                            }
-                            None => "Chadtech".to_string(),
-                        };
-
-                        events.push(Event::new(
-                            msg.sent_at,
-                            EventType::Said {
-                                scene_name: scene_name.clone(),
-                                speaker_name,
-                                comment: msg.content,
-                                message_uuid: MessageUuid::from_uuid(msg.uuid),
-                            },
-                        ));
-                    }
+                    events.extend(
+                        get_arrival_observations(
+                            self,
+                            &person_uuid,
+                            &scene_uuid,
+                            &scene_name,
+                            joined_at,
+                            left_at,
+                        )
+                        .await?,
+                    );
+
+                    events.extend(
+                        get_heard_scene_messages(
+                            self,
+                            &person_uuid,
+                            &scene_uuid,
+                            &scene_name,
+                            joined_at,
+                            left_at,
+                        )
+                        .await?,
+                    );
 
                     let participant_events = sqlx::query!(
                         r#"
@@ -257,10 +225,15 @@ impl EventCapability for Worker {
                 // Get all scene messages
                 let scene_messages = sqlx::query!(
                     r#"
-                    SELECT uuid, sender_person_uuid, content, sent_at
+                    SELECT
+                        message.uuid,
+                        message.sender_person_uuid,
+                        message_content.body AS content,
+                        message.sent_at
                     FROM message
-                    WHERE scene_uuid = $1
-                    ORDER BY sent_at
+                    JOIN message_content ON message_content.hash = message.content_hash
+                    WHERE message.scene_uuid = $1
+                    ORDER BY message.sent_at
                     "#,
                     scene_uuid.to_uuid()
                 )
@@ -278,7 +251,7 @@ impl EventCapability for Worker {
                                 .map_err(|err| format!("Error fetching sender name: {}", err))?;
                             sender_name.as_str().to_string()
                         }
-                        None => "Chadtech".to_string(),
+                        None => REAL_WORLD_USER_NAME.to_string(),
                     };
 
                     events.push(Event::new(
@@ -351,6 +324,122 @@ impl EventCapability for Worker {
     }
 }
 
+/// Scene messages the person either was meant to hear or overheard. Side
+/// conversations and whispers they were not within earshot of are left out.
+async fn get_heard_scene_messages(
+    worker: &Worker,
+    person_uuid: &PersonUuid,
+    scene_uuid: &SceneUuid,
+    scene_name: &str,
+    joined_at: DateTime<Utc>,
+    left_at: Option<DateTime<Utc>>,
+) -> Result<Vec<Event>, String> {
+    let rows = sqlx::query!(
+        r#"
+        SELECT m.uuid, m.sender_person_uuid, mc.body AS content, m.sent_at, smr.delivery AS "delivery?"
+        FROM message m
+        JOIN message_content mc ON mc.hash = m.content_hash
+        LEFT JOIN scene_message_recipient smr
+            ON smr.message_uuid = m.uuid
+            AND smr.person_uuid = $2::UUID
+        WHERE m.scene_uuid = $1::UUID
+          AND m.sent_at >= $3
+          AND m.superseded_at IS NULL
+          AND ($4::timestamptz IS NULL OR m.sent_at <= $4::timestamptz)
+          AND (
+            m.audience = 'everyone'
+            OR m.sender_person_uuid = $2::UUID
+            OR smr.person_uuid IS NOT NULL
+          )
+        ORDER BY m.sent_at
+        "#,
+        scene_uuid.to_uuid(),
+        person_uuid.to_uuid(),
+        joined_at,
+        left_at,
+    )
+    .fetch_all(&worker.sqlx)
+    .await
+    .map_err(|err| format!("Error fetching scene messages: {}", err))?;
+
+    let mut events = Vec::with_capacity(rows.len());
+
+    for row in rows {
+        let speaker_name = match row.sender_person_uuid {
+            Some(sender_uuid) => worker
+                .get_persons_name(PersonUuid::from_uuid(sender_uuid))
+                .await
+                .map_err(|err| format!("Error fetching sender name: {}", err))?
+                .as_str()
+                .to_string(),
+            None => REAL_WORLD_USER_NAME.to_string(),
+        };
+
+        let event_type = match row.delivery.as_deref() {
+            Some("overheard") => EventType::Overheard {
+                scene_name: scene_name.to_string(),
+                speaker_name,
+                comment: row.content,
+                message_uuid: MessageUuid::from_uuid(row.uuid),
+            },
+            _ => EventType::Said {
+                scene_name: scene_name.to_string(),
+                speaker_name,
+                comment: row.content,
+                message_uuid: MessageUuid::from_uuid(row.uuid),
+            },
+        };
+
+        events.push(Event::new(row.sent_at, event_type));
+    }
+
+    Ok(events)
+}
+
+/// Arrival observations are dated to the moment the person joined, so they
+/// come before anything said after they walked in.
+async fn get_arrival_observations(
+    worker: &Worker,
+    person_uuid: &PersonUuid,
+    scene_uuid: &SceneUuid,
+    scene_name: &str,
+    joined_at: DateTime<Utc>,
+    left_at: Option<DateTime<Utc>>,
+) -> Result<Vec<Event>, String> {
+    let rows = sqlx::query!(
+        r#"
+        SELECT observation
+        FROM scene_arrival_observation
+        WHERE person_uuid = $1::UUID
+          AND scene_uuid = $2::UUID
+          AND created_at >= $3
+          AND ($4::timestamptz IS NULL OR created_at <= $4::timestamptz)
+        ORDER BY created_at
+        "#,
+        person_uuid.to_uuid(),
+        scene_uuid.to_uuid(),
+        joined_at,
+        left_at,
+    )
+    .fetch_all(&worker.sqlx)
+    .await
+    .map_err(|err| format!("Error fetching arrival observations: {}", err))?;
+
+    let mut events = Vec::with_capacity(rows.len());
+
+    for row in rows {
+        events.push(Event::new(
+            joined_at,
+            EventType::Observed {
+                scene_name: scene_name.to_string(),
+                observation: row.observation,
+            },
+        ));
+    }
+
+    Ok(events)
+}
+
 async fn get_scene_name(worker: &Worker, scene_uuid: &SceneUuid) -> Result<String, String> {
     match worker.get_scene_name(scene_uuid).await {
         Ok(Some(name)) => Ok(name),
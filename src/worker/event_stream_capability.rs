@@ -0,0 +1,44 @@
+use crate::capability::event_stream::EventStreamCapability;
+use crate::domain::outbox::OutboxEntry;
+use crate::domain::outbox_uuid::OutboxUuid;
+use crate::domain::tenant_uuid::TenantUuid;
+use crate::worker::Worker;
+
+impl EventStreamCapability for Worker {
+    async fn get_events_after(
+        &self,
+        tenant_uuid: &TenantUuid,
+        after: &OutboxUuid,
+        limit: i64,
+    ) -> Result<Vec<OutboxEntry>, String> {
+        // Outbox uuids are v7, so they sort in the order entries were written
+        let rows = sqlx::query(
+            r#"
+                SELECT outbox.uuid, outbox.event_name, outbox.payload, outbox.attempts, outbox.created_at
+                FROM outbox
+                WHERE outbox.uuid > $1::UUID
+                  AND (
+                    outbox.payload->>'scene_uuid' IS NULL
+                    OR EXISTS (
+                        SELECT 1
+                        FROM scene
+                        WHERE scene.uuid::TEXT = outbox.payload->>'scene_uuid'
+                          AND scene.tenant_uuid = $2::UUID
+                    )
+                  )
+                ORDER BY outbox.uuid ASC
+                LIMIT $3;
+            "#,
+        )
+        .bind(after.to_uuid())
+        .bind(tenant_uuid.to_uuid())
+        .bind(limit)
+        .fetch_all(&self.sqlx)
+        .await
+        .map_err(|err| format!("Error getting events for the event stream: {}", err))?;
+
+        rows.iter()
+            .map(super::outbox_capability::read_outbox_entry)
+            .collect()
+    }
+}
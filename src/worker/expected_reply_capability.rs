@@ -0,0 +1,72 @@
+use crate::capability::expected_reply::{
+    ExpectedReplyCapability, ExpectedReplyOutcome, NewExpectedReply,
+};
+use crate::domain::message_uuid::MessageUuid;
+use crate::worker::Worker;
+use sqlx::Row;
+
+impl ExpectedReplyCapability for Worker {
+    async fn expect_reply(&self, new_expected_reply: NewExpectedReply) -> Result<(), String> {
+        sqlx::query(
+            r#"
+                INSERT INTO message_expected_reply (message_uuid, asker_person_uuid, recipient_person_uuid, reply_window_ms)
+                VALUES ($1::UUID, $2::UUID, $3::UUID, $4::BIGINT)
+                ON CONFLICT (message_uuid) DO NOTHING;
+            "#,
+        )
+        .bind(new_expected_reply.message_uuid.to_uuid())
+        .bind(new_expected_reply.asker_person_uuid.to_uuid())
+        .bind(new_expected_reply.recipient_person_uuid.to_uuid())
+        .bind(new_expected_reply.reply_window_ms)
+        .execute(&self.sqlx)
+        .await
+        .map_err(|err| format!("Error recording expected reply: {}", err))?;
+
+        Ok(())
+    }
+
+    async fn has_recipient_replied(&self, message_uuid: &MessageUuid) -> Result<bool, String> {
+        let row = sqlx::query(
+            r#"
+                SELECT EXISTS (
+                    SELECT 1
+                    FROM message_expected_reply
+                    JOIN message AS question ON question.uuid = message_expected_reply.message_uuid
+                    JOIN message AS reply
+                        ON reply.scene_uuid = question.scene_uuid
+                        AND reply.sender_person_uuid = message_expected_reply.recipient_person_uuid
+                        AND reply.sent_at > question.sent_at
+                    WHERE message_expected_reply.message_uuid = $1::UUID
+                ) AS replied;
+            "#,
+        )
+        .bind(message_uuid.to_uuid())
+        .fetch_one(&self.sqlx)
+        .await
+        .map_err(|err| format!("Error checking for a reply: {}", err))?;
+
+        row.try_get::<bool, _>("replied")
+            .map_err(|err| format!("Error reading replied: {}", err))
+    }
+
+    async fn resolve_expected_reply(
+        &self,
+        message_uuid: &MessageUuid,
+        outcome: ExpectedReplyOutcome,
+    ) -> Result<(), String> {
+        sqlx::query(
+            r#"
+                UPDATE message_expected_reply
+                SET outcome = $2::TEXT, resolved_at = NOW()
+                WHERE message_uuid = $1::UUID;
+            "#,
+        )
+        .bind(message_uuid.to_uuid())
+        .bind(outcome.to_name())
+        .execute(&self.sqlx)
+        .await
+        .map_err(|err| format!("Error resolving expected reply: {}", err))?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,95 @@
+use crate::capability::external_tool::{ExternalToolCapability, NewExternalToolCall};
+use crate::domain::external_tool::{ExternalTool, ExternalToolMethod};
+use crate::worker::Worker;
+use std::time::Duration;
+use uuid::Uuid;
+
+const TOOL_TIMEOUT: Duration = Duration::from_secs(30);
+
+impl ExternalToolCapability for Worker {
+    async fn call_external_tool(
+        &self,
+        tool: &ExternalTool,
+        arguments: &serde_json::Value,
+    ) -> Result<String, String> {
+        let client = reqwest::Client::builder()
+            .timeout(TOOL_TIMEOUT)
+            .build()
+            .map_err(|err| format!("Error building tool client: {}", err))?;
+
+        let request = match tool.method {
+            ExternalToolMethod::Get => client
+                .get(&tool.url)
+                .query(&ExternalTool::query_pairs(arguments)),
+            ExternalToolMethod::Post => client
+                .post(&tool.url)
+                .header("Content-Type", "application/json")
+                .json(arguments),
+        };
+
+        let response = request
+            .send()
+            .await
+            .map_err(|err| format!("Error calling tool {}: {}", tool.name, err))?;
+
+        let status = response.status();
+        let body = response
+            .text()
+            .await
+            .map_err(|err| format!("Error reading tool {} response: {}", tool.name, err))?;
+
+        if !status.is_success() {
+            return Err(format!(
+                "Tool {} responded with {}: {}",
+                tool.name, status, body
+            ));
+        }
+
+        Ok(body)
+    }
+
+    async fn record_external_tool_call(&self, call: NewExternalToolCall) -> Result<(), String> {
+        let (result, error) = match call.result {
+            Ok(result) => (Some(result), None),
+            Err(error) => (None, Some(error)),
+        };
+
+        sqlx::query(
+            r#"
+                INSERT INTO external_tool_call (
+                    uuid,
+                    person_uuid,
+                    scene_uuid,
+                    tool_name,
+                    arguments,
+                    result,
+                    error,
+                    duration_ms
+                )
+                VALUES (
+                    $1::UUID,
+                    $2::UUID,
+                    $3::UUID,
+                    $4::TEXT,
+                    $5::JSONB,
+                    $6::TEXT,
+                    $7::TEXT,
+                    $8::BIGINT
+                );
+            "#,
+        )
+        .bind(Uuid::now_v7())
+        .bind(call.person_uuid.to_uuid())
+        .bind(call.scene_uuid.map(|scene_uuid| scene_uuid.to_uuid()))
+        .bind(call.tool_name)
+        .bind(call.arguments)
+        .bind(result)
+        .bind(error)
+        .bind(call.duration_ms)
+        .execute(&self.sqlx)
+        .await
+        .map_err(|err| format!("Error recording external tool call: {}", err))?;
+
+        Ok(())
+    }
+}
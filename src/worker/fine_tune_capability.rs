@@ -0,0 +1,86 @@
+use crate::capability::fine_tune::FineTuneCapability;
+use crate::domain::person_uuid::PersonUuid;
+use crate::open_ai::fine_tune::{FineTuneJob, FineTuneStatus};
+use crate::open_ai::model::Model;
+use crate::worker::Worker;
+use sqlx::Row;
+use uuid::Uuid;
+
+impl FineTuneCapability for Worker {
+    async fn record_fine_tune_job(
+        &self,
+        job: &FineTuneJob,
+        base_model: &Model,
+        person_uuid: Option<&PersonUuid>,
+    ) -> Result<(), String> {
+        sqlx::query(
+            r#"
+                INSERT INTO fine_tuned_model (uuid, job_id, base_model, status, model_id, person_uuid)
+                VALUES ($1::UUID, $2::TEXT, $3::TEXT, $4::TEXT, $5::TEXT, $6::UUID);
+            "#,
+        )
+        .bind(Uuid::now_v7())
+        .bind(job.id.as_str())
+        .bind(base_model.to_string())
+        .bind(job.status.to_name())
+        .bind(job.fine_tuned_model.as_deref())
+        .bind(person_uuid.map(|uuid| uuid.to_uuid()))
+        .execute(&self.sqlx)
+        .await
+        .map_err(|err| format!("Error inserting fine-tuned model: {}", err))?;
+
+        Ok(())
+    }
+
+    async fn update_fine_tune_job(&self, job: &FineTuneJob) -> Result<(), String> {
+        sqlx::query(
+            r#"
+                UPDATE fine_tuned_model
+                SET status = $2::TEXT,
+                    model_id = $3::TEXT,
+                    updated_at = now()
+                WHERE job_id = $1::TEXT;
+            "#,
+        )
+        .bind(job.id.as_str())
+        .bind(job.status.to_name())
+        .bind(job.fine_tuned_model.as_deref())
+        .execute(&self.sqlx)
+        .await
+        .map_err(|err| format!("Error updating fine-tuned model: {}", err))?;
+
+        Ok(())
+    }
+
+    async fn get_person_fine_tuned_model(
+        &self,
+        person_uuid: &PersonUuid,
+    ) -> Result<Option<Model>, String> {
+        let maybe_row = sqlx::query(
+            r#"
+                SELECT model_id
+                FROM fine_tuned_model
+                WHERE person_uuid = $1::UUID
+                  AND status = $2::TEXT
+                  AND model_id IS NOT NULL
+                ORDER BY updated_at DESC
+                LIMIT 1;
+            "#,
+        )
+        .bind(person_uuid.to_uuid())
+        .bind(FineTuneStatus::Succeeded.to_name())
+        .fetch_optional(&self.sqlx)
+        .await
+        .map_err(|err| format!("Error fetching fine-tuned model: {}", err))?;
+
+        match maybe_row {
+            None => Ok(None),
+            Some(row) => {
+                let model_id = row
+                    .try_get::<String, _>("model_id")
+                    .map_err(|err| format!("Error reading model_id from row: {}", err))?;
+                Ok(Some(Model::FineTuned(model_id)))
+            }
+        }
+    }
+}
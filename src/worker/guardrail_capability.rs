@@ -0,0 +1,64 @@
+use crate::capability::guardrail::GuardrailCapability;
+use crate::domain::guardrail::GuardrailSettings;
+use crate::worker::Worker;
+use sqlx::Row;
+
+impl GuardrailCapability for Worker {
+    async fn get_guardrail_settings(&self) -> Result<GuardrailSettings, String> {
+        let row = sqlx::query(
+            r#"
+                SELECT prompt, refused_topics, tone
+                FROM guardrail_setting
+                WHERE id = TRUE;
+            "#,
+        )
+        .fetch_optional(&self.sqlx)
+        .await
+        .map_err(|err| format!("Error fetching guardrail settings: {}", err))?;
+
+        let row = match row {
+            Some(row) => row,
+            None => {
+                return Err("Guardrail settings are missing from guardrail_setting".to_string());
+            }
+        };
+
+        let prompt = row
+            .try_get::<String, _>("prompt")
+            .map_err(|err| format!("Error reading guardrail prompt: {}", err))?;
+
+        let refused_topics = row
+            .try_get::<Vec<String>, _>("refused_topics")
+            .map_err(|err| format!("Error reading refused topics: {}", err))?;
+
+        let tone = row
+            .try_get::<String, _>("tone")
+            .map_err(|err| format!("Error reading guardrail tone: {}", err))?;
+
+        Ok(GuardrailSettings {
+            prompt,
+            refused_topics,
+            tone,
+        })
+    }
+
+    async fn set_guardrail_settings(&self, settings: &GuardrailSettings) -> Result<(), String> {
+        sqlx::query(
+            r#"
+                UPDATE guardrail_setting
+                SET prompt = $1,
+                    refused_topics = $2,
+                    tone = $3
+                WHERE id = TRUE;
+            "#,
+        )
+        .bind(settings.prompt.trim())
+        .bind(&settings.refused_topics)
+        .bind(settings.tone.trim())
+        .execute(&self.sqlx)
+        .await
+        .map_err(|err| format!("Error updating guardrail settings: {}", err))?;
+
+        Ok(())
+    }
+}
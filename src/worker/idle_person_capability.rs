@@ -0,0 +1,172 @@
+use crate::capability::idle_person::{IdlePerson, IdlePersonCapability};
+use crate::domain::person_uuid::PersonUuid;
+use crate::domain::scene_uuid::SceneUuid;
+use crate::domain::talk_share::TalkCount;
+use crate::worker::Worker;
+use chrono::{DateTime, Utc};
+use sqlx::Row;
+use uuid::Uuid;
+
+impl IdlePersonCapability for Worker {
+    async fn get_idle_persons_in_active_scenes(
+        &self,
+        idle_since: DateTime<Utc>,
+        active_since: DateTime<Utc>,
+    ) -> Result<Vec<IdlePerson>, String> {
+        // Unfinished jobs keyed to the person mean a reaction is already on
+        // its way, so there is nothing to wake them up for
+        let rows = sqlx::query(
+            r#"
+                SELECT
+                    person.uuid AS person_uuid,
+                    scene_participant.scene_uuid,
+                    person.chattiness
+                FROM scene_participant
+                JOIN person ON person.uuid = scene_participant.person_uuid
+                WHERE scene_participant.left_at IS NULL
+                  AND scene_participant.joined_at < $1::TIMESTAMPTZ
+                  AND person.is_enabled
+                  AND NOT person.is_hibernating
+                  AND NOT EXISTS (
+                      SELECT 1
+                      FROM reaction_history
+                      WHERE reaction_history.person_uuid = person.uuid
+                        AND reaction_history.created_at >= $1::TIMESTAMPTZ
+                  )
+                  AND EXISTS (
+                      SELECT 1
+                      FROM message
+                      WHERE message.scene_uuid = scene_participant.scene_uuid
+                        AND message.sent_at >= $2::TIMESTAMPTZ
+                        AND message.sender_person_uuid IS DISTINCT FROM person.uuid
+                  )
+                  AND NOT EXISTS (
+                      SELECT 1
+                      FROM job
+                      WHERE job.lock_key = 'person:' || person.uuid::TEXT
+                        AND job.finished_at IS NULL
+                        AND job.error IS NULL
+                        AND job.deleted_at IS NULL
+                  );
+            "#,
+        )
+        .bind(idle_since)
+        .bind(active_since)
+        .fetch_all(&self.sqlx)
+        .await
+        .map_err(|err| format!("Error fetching idle persons: {}", err))?;
+
+        rows.into_iter()
+            .map(|row| {
+                let person_uuid = row
+                    .try_get::<Uuid, _>("person_uuid")
+                    .map_err(|err| format!("Error reading person_uuid from row: {}", err))?;
+                let scene_uuid = row
+                    .try_get::<Uuid, _>("scene_uuid")
+                    .map_err(|err| format!("Error reading scene_uuid from row: {}", err))?;
+                let chattiness = row
+                    .try_get::<f64, _>("chattiness")
+                    .map_err(|err| format!("Error reading chattiness from row: {}", err))?;
+
+                Ok(IdlePerson {
+                    person_uuid: PersonUuid::from_uuid(person_uuid),
+                    scene_uuid: SceneUuid::from_uuid(scene_uuid),
+                    chattiness,
+                })
+            })
+            .collect()
+    }
+
+    async fn get_person_chattiness(&self, person_uuid: &PersonUuid) -> Result<f64, String> {
+        let row = sqlx::query(
+            r#"
+                SELECT chattiness
+                FROM person
+                WHERE uuid = $1::UUID;
+            "#,
+        )
+        .bind(person_uuid.to_uuid())
+        .fetch_one(&self.sqlx)
+        .await
+        .map_err(|err| format!("Error fetching person chattiness: {}", err))?;
+
+        row.try_get::<f64, _>("chattiness")
+            .map_err(|err| format!("Error reading chattiness from row: {}", err))
+    }
+
+    async fn set_person_chattiness(
+        &self,
+        person_uuid: &PersonUuid,
+        chattiness: f64,
+    ) -> Result<(), String> {
+        sqlx::query(
+            r#"
+                UPDATE person
+                SET chattiness = $2::DOUBLE PRECISION
+                WHERE uuid = $1::UUID;
+            "#,
+        )
+        .bind(person_uuid.to_uuid())
+        .bind(chattiness)
+        .execute(&self.sqlx)
+        .await
+        .map_err(|err| format!("Error updating person chattiness: {}", err))?;
+
+        Ok(())
+    }
+
+    async fn get_talk_counts(&self, since: DateTime<Utc>) -> Result<Vec<TalkCount>, String> {
+        let rows = sqlx::query(
+            r#"
+                SELECT
+                    scene_participant.scene_uuid,
+                    person.uuid AS person_uuid,
+                    person.chattiness,
+                    (
+                        SELECT COUNT(*)
+                        FROM message
+                        WHERE message.scene_uuid = scene_participant.scene_uuid
+                          AND message.sender_person_uuid = person.uuid
+                          AND message.sent_at >= $1::TIMESTAMPTZ
+                    ) AS messages
+                FROM scene_participant
+                JOIN person ON person.uuid = scene_participant.person_uuid
+                JOIN scene ON scene.uuid = scene_participant.scene_uuid
+                WHERE scene_participant.left_at IS NULL
+                  AND scene.ended_at IS NULL
+                  AND person.is_enabled
+                  AND NOT person.is_hibernating
+                  AND person.archived_at IS NULL
+                ORDER BY scene_participant.scene_uuid, person.uuid;
+            "#,
+        )
+        .bind(since)
+        .fetch_all(&self.sqlx)
+        .await
+        .map_err(|err| format!("Error fetching talk counts: {}", err))?;
+
+        rows.into_iter()
+            .map(|row| {
+                let scene_uuid = row
+                    .try_get::<Uuid, _>("scene_uuid")
+                    .map_err(|err| format!("Error reading scene_uuid from row: {}", err))?;
+                let person_uuid = row
+                    .try_get::<Uuid, _>("person_uuid")
+                    .map_err(|err| format!("Error reading person_uuid from row: {}", err))?;
+                let chattiness = row
+                    .try_get::<f64, _>("chattiness")
+                    .map_err(|err| format!("Error reading chattiness from row: {}", err))?;
+                let messages = row
+                    .try_get::<i64, _>("messages")
+                    .map_err(|err| format!("Error reading message count from row: {}", err))?;
+
+                Ok(TalkCount {
+                    scene_uuid: SceneUuid::from_uuid(scene_uuid),
+                    person_uuid: PersonUuid::from_uuid(person_uuid),
+                    chattiness,
+                    messages,
+                })
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,112 @@
+use crate::capability::item::{ItemCapability, NewItem};
+use crate::domain::item::{Item, ItemLocation};
+use crate::domain::item_uuid::ItemUuid;
+use crate::domain::person_uuid::PersonUuid;
+use crate::domain::scene_uuid::SceneUuid;
+use crate::worker::Worker;
+use sqlx::postgres::PgRow;
+use sqlx::Row;
+
+impl ItemCapability for Worker {
+    async fn create_item(&self, new_item: NewItem) -> Result<ItemUuid, String> {
+        let item_uuid = ItemUuid::new();
+
+        let (owner_person_uuid, scene_uuid) = match &new_item.location {
+            ItemLocation::CarriedBy(person_uuid) => (Some(person_uuid.to_uuid()), None),
+            ItemLocation::InScene(scene_uuid) => (None, Some(scene_uuid.to_uuid())),
+        };
+
+        sqlx::query(
+            r#"
+                INSERT INTO item (uuid, name, description, owner_person_uuid, scene_uuid)
+                VALUES ($1::UUID, $2::TEXT, $3::TEXT, $4::UUID, $5::UUID);
+            "#,
+        )
+        .bind(item_uuid.to_uuid())
+        .bind(new_item.name.trim())
+        .bind(new_item.description.trim())
+        .bind(owner_person_uuid)
+        .bind(scene_uuid)
+        .execute(&self.sqlx)
+        .await
+        .map_err(|err| format!("Error creating item: {}", err))?;
+
+        Ok(item_uuid)
+    }
+
+    async fn get_items_carried_by(&self, person_uuid: &PersonUuid) -> Result<Vec<Item>, String> {
+        let rows = sqlx::query(
+            r#"
+                SELECT uuid, name, description
+                FROM item
+                WHERE owner_person_uuid = $1::UUID
+                ORDER BY created_at ASC;
+            "#,
+        )
+        .bind(person_uuid.to_uuid())
+        .fetch_all(&self.sqlx)
+        .await
+        .map_err(|err| format!("Error fetching carried items: {}", err))?;
+
+        rows.iter().map(read_item).collect()
+    }
+
+    async fn get_items_in_scene(&self, scene_uuid: &SceneUuid) -> Result<Vec<Item>, String> {
+        let rows = sqlx::query(
+            r#"
+                SELECT uuid, name, description
+                FROM item
+                WHERE scene_uuid = $1::UUID
+                ORDER BY created_at ASC;
+            "#,
+        )
+        .bind(scene_uuid.to_uuid())
+        .fetch_all(&self.sqlx)
+        .await
+        .map_err(|err| format!("Error fetching scene items: {}", err))?;
+
+        rows.iter().map(read_item).collect()
+    }
+
+    async fn give_item(
+        &self,
+        item_uuid: &ItemUuid,
+        giver_person_uuid: &PersonUuid,
+        recipient_person_uuid: &PersonUuid,
+    ) -> Result<(), String> {
+        let result = sqlx::query(
+            r#"
+                UPDATE item
+                SET owner_person_uuid = $3::UUID,
+                    updated_at = now()
+                WHERE uuid = $1::UUID
+                  AND owner_person_uuid = $2::UUID;
+            "#,
+        )
+        .bind(item_uuid.to_uuid())
+        .bind(giver_person_uuid.to_uuid())
+        .bind(recipient_person_uuid.to_uuid())
+        .execute(&self.sqlx)
+        .await
+        .map_err(|err| format!("Error giving item: {}", err))?;
+
+        if result.rows_affected() == 0 {
+            return Err(format!(
+                "Item {} is not carried by the giver",
+                item_uuid.to_uuid()
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+fn read_item(row: &PgRow) -> Result<Item, String> {
+    let read_error = |err: sqlx::Error| format!("Error reading item: {}", err);
+
+    Ok(Item {
+        uuid: ItemUuid::from_uuid(row.try_get("uuid").map_err(read_error)?),
+        name: row.try_get("name").map_err(read_error)?,
+        description: row.try_get("description").map_err(read_error)?,
+    })
+}
@@ -1,62 +1,124 @@
+use crate::capability::clock::ClockCapability;
 use crate::capability::job::JobCapability;
-use crate::domain::job::{Job, JobKind, PoppedJob};
+use crate::domain::fan_out::QueuePressure;
+use crate::domain::job::{person_lock_key, registry, Job, JobKind, PoppedJob};
+use crate::domain::job_event::{JobEvent, JobEventKind, JobTiming, NewJobEvent};
 use crate::domain::job_uuid::JobUuid;
+use crate::domain::person_uuid::PersonUuid;
 use crate::nice_display::NiceDisplay;
 use crate::worker::Worker;
 use chrono::{DateTime, Utc};
 use sqlx::Row;
+use uuid::Uuid;
+
+/// Arbitrary key for the advisory lock that serializes popping jobs.
+pub(super) const POP_JOB_ADVISORY_LOCK: i64 = 4_944_000_001;
 
 impl JobCapability for Worker {
     async fn unshift_job(&self, job: JobKind) -> Result<(), String> {
+        self.enqueue_job(job).await.map(|_| ())
+    }
+
+    async fn enqueue_job(&self, job: JobKind) -> Result<JobUuid, String> {
         let job_uuid = JobUuid::new();
         let job_name = job.to_name();
         let job_data = job.to_data()?;
         let run_at_active_ms = match &job {
             JobKind::PersonWaiting(wait_job) => Some(wait_job.run_at_active_ms()),
             JobKind::PersonHibernating(hibernation_job) => Some(hibernation_job.run_at_active_ms()),
+            JobKind::ProcessMessage(process_message_job) => process_message_job.run_at_active_ms,
+            JobKind::CheckExpectedReply(check_job) => Some(check_job.run_at_active_ms),
+            JobKind::DispatchOutbox(dispatch_job) => dispatch_job.run_at_active_ms,
+            JobKind::PollLlmBatch(poll_job) => poll_job.run_at_active_ms,
+            JobKind::ChangeSceneAmbience(ambience_job) => ambience_job.run_at_active_ms,
             _ => None,
         };
 
         sqlx::query(
             r#"
-				INSERT INTO job (uuid, name, data, run_at_active_ms)
-				VALUES ($1::UUID, $2::TEXT, $3::JSONB, $4::BIGINT);
-			"#,
+                WITH inserted AS (
+                    INSERT INTO job (uuid, name, data, run_at_active_ms, lock_key, priority, created_at)
+                    VALUES ($1::UUID, $2::TEXT, $3::JSONB, $4::BIGINT, $5::TEXT, $6::INT, $9::TIMESTAMPTZ)
+                    RETURNING uuid
+                )
+                INSERT INTO job_event (uuid, job_uuid, kind, created_at)
+                SELECT $7::UUID, inserted.uuid, $8::TEXT, $9::TIMESTAMPTZ
+                FROM inserted;
+            "#,
         )
-        .bind(job_uuid.to_uuid()?)
+        .bind(job_uuid.to_uuid())
         .bind(job_name)
         .bind(job_data)
         .bind(run_at_active_ms)
+        .bind(job.lock_key())
+        .bind(job.priority())
+        .bind(Uuid::now_v7())
+        .bind(JobEventKind::Enqueued.to_name())
+        .bind(self.now())
         .execute(&self.sqlx)
         .await
         .map_err(|err| format!("Error unshifting new job: {}", err))?;
 
-        Ok(())
+        Ok(job_uuid)
     }
 
     async fn pop_next_job(&self, current_active_ms: i64) -> Result<Option<PoppedJob>, String> {
+        let mut transaction = self
+            .sqlx
+            .begin()
+            .await
+            .map_err(|err| format!("Error starting pop job transaction: {}", err))?;
+
+        // Pops take turns, so two runners never both see the same lock key as free
+        sqlx::query("SELECT pg_advisory_xact_lock($1::BIGINT)")
+            .bind(POP_JOB_ADVISORY_LOCK)
+            .execute(&mut *transaction)
+            .await
+            .map_err(|err| format!("Error locking the job queue: {}", err))?;
+
+        // A job that has been running for a long time most likely belongs to a
+        // runner that died, so it stops holding its lock key after a while.
         let maybe_rec = sqlx::query(
             r#"
                 UPDATE job
-                SET started_at = NOW()
+                SET started_at = $2::TIMESTAMPTZ
                 WHERE uuid = (
-                    SELECT uuid
-                    FROM job
-                WHERE started_at IS NULL
-                  AND finished_at IS NULL
-                  AND deleted_at IS NULL
-                  AND (run_at_active_ms IS NULL OR run_at_active_ms <= $1)
-                ORDER BY created_at ASC
-                LIMIT 1
-            )
+                    SELECT candidate.uuid
+                    FROM job candidate
+                    WHERE candidate.started_at IS NULL
+                      AND candidate.finished_at IS NULL
+                      AND candidate.deleted_at IS NULL
+                      AND (candidate.run_at_active_ms IS NULL OR candidate.run_at_active_ms <= $1)
+                      AND (
+                        candidate.lock_key IS NULL
+                        OR NOT EXISTS (
+                            SELECT 1
+                            FROM job running
+                            WHERE running.lock_key = candidate.lock_key
+                              AND running.started_at IS NOT NULL
+                              AND running.finished_at IS NULL
+                              AND running.error IS NULL
+                              AND running.deleted_at IS NULL
+                              AND running.started_at > $2::TIMESTAMPTZ - INTERVAL '15 minutes'
+                        )
+                      )
+                    ORDER BY candidate.priority DESC, candidate.created_at ASC
+                    LIMIT 1
+                )
                 RETURNING uuid;
             "#,
         )
         .bind(current_active_ms)
-        .fetch_optional(&self.sqlx)
+        .bind(self.now())
+        .fetch_optional(&mut *transaction)
         .await
         .map_err(|err| format!("Error setting started_at on job: {}", err))?;
 
+        transaction
+            .commit()
+            .await
+            .map_err(|err| format!("Error committing pop job transaction: {}", err))?;
+
         let rec = match maybe_rec {
             None => return Ok(None),
             Some(r) => r,
@@ -160,7 +222,7 @@ impl JobCapability for Worker {
                 WHERE uuid = $1::UUID
                   AND deleted_at IS NULL
             "#,
-            job_uuid.to_uuid()?
+            job_uuid.to_uuid()
         )
         .fetch_optional(&self.sqlx)
         .await
@@ -189,10 +251,11 @@ impl JobCapability for Worker {
         sqlx::query!(
             r#"
                 UPDATE job
-                SET finished_at = NOW()
+                SET finished_at = $2::TIMESTAMPTZ
                 WHERE uuid = $1::UUID;
             "#,
-            job_uuid.to_uuid()?
+            job_uuid.to_uuid(),
+            self.now()
         )
         .execute(&self.sqlx)
         .await
@@ -208,7 +271,7 @@ impl JobCapability for Worker {
                 SET error = $2::TEXT
                 WHERE uuid = $1::UUID;
             "#,
-            job_uuid.to_uuid()?,
+            job_uuid.to_uuid(),
             details
         )
         .execute(&self.sqlx)
@@ -228,7 +291,7 @@ impl JobCapability for Worker {
                 WHERE uuid = $1::UUID;
             "#,
         )
-        .bind(job_uuid.to_uuid()?)
+        .bind(job_uuid.to_uuid())
         .execute(&self.sqlx)
         .await
         .map_err(|err| format!("Error resetting job: {}", err))?;
@@ -237,20 +300,58 @@ impl JobCapability for Worker {
     }
 
     async fn reset_all_failed_jobs(&self) -> Result<(), String> {
-        sqlx::query(
+        let mut transaction = self
+            .sqlx
+            .begin()
+            .await
+            .map_err(|err| format!("Error starting reset failed jobs transaction: {}", err))?;
+
+        let rows = sqlx::query(
             r#"
                 UPDATE job
                 SET started_at = NULL,
                     finished_at = NULL,
                     error = NULL
                 WHERE error IS NOT NULL
-                  AND deleted_at IS NULL;
+                  AND deleted_at IS NULL
+                RETURNING uuid;
             "#,
         )
-        .execute(&self.sqlx)
+        .fetch_all(&mut *transaction)
         .await
         .map_err(|err| format!("Error resetting failed jobs: {}", err))?;
 
+        let mut job_uuids = Vec::with_capacity(rows.len());
+        for row in rows {
+            let job_uuid = row
+                .try_get::<Uuid, _>("uuid")
+                .map_err(|err| format!("Error reading uuid from row: {}", err))?;
+            job_uuids.push(job_uuid);
+        }
+        let event_uuids = job_uuids
+            .iter()
+            .map(|_| Uuid::now_v7())
+            .collect::<Vec<Uuid>>();
+
+        sqlx::query(
+            r#"
+                INSERT INTO job_event (uuid, job_uuid, kind)
+                SELECT event_uuid, job_uuid, $3::TEXT
+                FROM UNNEST($1::UUID[], $2::UUID[]) AS reset(event_uuid, job_uuid);
+            "#,
+        )
+        .bind(event_uuids)
+        .bind(job_uuids)
+        .bind(JobEventKind::Retried.to_name())
+        .execute(&mut *transaction)
+        .await
+        .map_err(|err| format!("Error recording retried job events: {}", err))?;
+
+        transaction
+            .commit()
+            .await
+            .map_err(|err| format!("Error committing reset failed jobs transaction: {}", err))?;
+
         Ok(())
     }
 
@@ -258,10 +359,11 @@ impl JobCapability for Worker {
         sqlx::query!(
             r#"
                 UPDATE job
-                SET deleted_at = NOW()
+                SET deleted_at = $2::TIMESTAMPTZ
                 WHERE uuid = $1::UUID
             "#,
-            job_uuid.to_uuid()?
+            job_uuid.to_uuid(),
+            self.now()
         )
         .execute(&self.sqlx)
         .await
@@ -269,4 +371,173 @@ impl JobCapability for Worker {
 
         Ok(())
     }
+
+    async fn record_job_event(
+        &self,
+        job_uuid: &JobUuid,
+        event: &NewJobEvent,
+    ) -> Result<(), String> {
+        sqlx::query(
+            r#"
+                INSERT INTO job_event
+                    (uuid, job_uuid, kind, runner, error_class, duration_ms, llm_ms)
+                VALUES
+                    ($1::UUID, $2::UUID, $3::TEXT, $4::TEXT, $5::TEXT, $6::BIGINT, $7::BIGINT);
+            "#,
+        )
+        .bind(Uuid::now_v7())
+        .bind(job_uuid.to_uuid())
+        .bind(event.kind.to_name())
+        .bind(&event.runner)
+        .bind(&event.error_class)
+        .bind(event.timing.as_ref().map(|timing| timing.duration_ms))
+        .bind(event.timing.as_ref().map(|timing| timing.llm_ms))
+        .execute(&self.sqlx)
+        .await
+        .map_err(|err| format!("Error recording job event: {}", err))?;
+
+        Ok(())
+    }
+
+    async fn get_job_events(&self, job_uuid: &JobUuid) -> Result<Vec<JobEvent>, String> {
+        let rows = sqlx::query(
+            r#"
+                SELECT kind, runner, error_class, duration_ms, llm_ms, created_at
+                FROM job_event
+                WHERE job_uuid = $1::UUID
+                ORDER BY created_at ASC, uuid ASC
+            "#,
+        )
+        .bind(job_uuid.to_uuid())
+        .fetch_all(&self.sqlx)
+        .await
+        .map_err(|err| format!("Error fetching job events: {}", err))?;
+
+        let mut events = Vec::with_capacity(rows.len());
+        for row in rows {
+            let kind = row
+                .try_get::<String, _>("kind")
+                .map_err(|err| format!("Error reading kind from row: {}", err))?;
+
+            let runner = row
+                .try_get::<Option<String>, _>("runner")
+                .map_err(|err| format!("Error reading runner from row: {}", err))?;
+
+            let error_class = row
+                .try_get::<Option<String>, _>("error_class")
+                .map_err(|err| format!("Error reading error_class from row: {}", err))?;
+
+            let duration_ms = row
+                .try_get::<Option<i64>, _>("duration_ms")
+                .map_err(|err| format!("Error reading duration_ms from row: {}", err))?;
+
+            let llm_ms = row
+                .try_get::<Option<i64>, _>("llm_ms")
+                .map_err(|err| format!("Error reading llm_ms from row: {}", err))?;
+
+            let created_at = row
+                .try_get::<DateTime<Utc>, _>("created_at")
+                .map_err(|err| format!("Error reading created_at from row: {}", err))?;
+
+            let timing = match (duration_ms, llm_ms) {
+                (Some(duration_ms), Some(llm_ms)) => Some(JobTiming {
+                    duration_ms,
+                    llm_ms,
+                }),
+                _ => None,
+            };
+
+            events.push(JobEvent {
+                kind: JobEventKind::from_name(&kind)?,
+                runner,
+                error_class,
+                timing,
+                created_at,
+            });
+        }
+
+        Ok(events)
+    }
+
+    async fn get_queue_pressure(&self) -> Result<QueuePressure, String> {
+        let row = sqlx::query(
+            r#"
+                SELECT
+                    (
+                        SELECT COUNT(*)
+                        FROM job
+                        WHERE started_at IS NULL
+                          AND finished_at IS NULL
+                          AND deleted_at IS NULL
+                    ) AS pending_jobs,
+                    COALESCE(
+                        (SELECT active_ms FROM active_clock WHERE id = TRUE),
+                        0
+                    ) AS active_ms;
+            "#,
+        )
+        .fetch_one(&self.sqlx)
+        .await
+        .map_err(|err| format!("Error fetching job queue pressure: {}", err))?;
+
+        let pending_jobs = row
+            .try_get::<i64, _>("pending_jobs")
+            .map_err(|err| format!("Error reading pending_jobs from row: {}", err))?;
+        let active_ms = row
+            .try_get::<i64, _>("active_ms")
+            .map_err(|err| format!("Error reading active_ms from row: {}", err))?;
+
+        Ok(QueuePressure {
+            pending_jobs,
+            active_ms,
+        })
+    }
+
+    async fn get_next_delayed_job_active_ms(
+        &self,
+        current_active_ms: i64,
+    ) -> Result<Option<i64>, String> {
+        let row = sqlx::query(
+            r#"
+                SELECT MIN(run_at_active_ms) AS run_at_active_ms
+                FROM job
+                WHERE started_at IS NULL
+                  AND finished_at IS NULL
+                  AND deleted_at IS NULL
+                  AND run_at_active_ms > $1;
+            "#,
+        )
+        .bind(current_active_ms)
+        .fetch_one(&self.sqlx)
+        .await
+        .map_err(|err| format!("Error fetching the next delayed job: {}", err))?;
+
+        row.try_get::<Option<i64>, _>("run_at_active_ms")
+            .map_err(|err| format!("Error reading run_at_active_ms from row: {}", err))
+    }
+
+    async fn get_persons_wait_end_active_ms(
+        &self,
+        person_uuid: &PersonUuid,
+    ) -> Result<Option<i64>, String> {
+        let row = sqlx::query(
+            r#"
+                SELECT MAX(run_at_active_ms) AS run_at_active_ms
+                FROM job
+                WHERE name = $1::TEXT
+                  AND lock_key = $2::TEXT
+                  AND finished_at IS NULL
+                  AND deleted_at IS NULL
+                  AND error IS NULL;
+            "#,
+        )
+        .bind(registry::PERSON_WAITING)
+        .bind(person_lock_key(person_uuid))
+        .fetch_one(&self.sqlx)
+        .await
+        .map_err(|err| format!("Error fetching the person's wait: {}", err))?;
+
+        row.try_get::<Option<i64>, _>("run_at_active_ms")
+            .map_err(|err| format!("Error reading run_at_active_ms from row: {}", err))
+    }
 }
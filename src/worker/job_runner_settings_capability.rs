@@ -99,4 +99,92 @@ impl JobRunnerSettingsCapability for Worker {
 
         Ok(())
     }
+
+    async fn get_active_clock_ms(&self) -> Result<i64, String> {
+        let row = sqlx::query(
+            r#"
+                SELECT active_ms
+                FROM active_clock
+                WHERE id = TRUE
+            "#,
+        )
+        .fetch_optional(&self.sqlx)
+        .await
+        .map_err(|err| format!("Error loading active clock: {}", err))?;
+
+        match row {
+            Some(row) => row
+                .try_get::<i64, _>("active_ms")
+                .map_err(|err| format!("Error reading active_ms: {}", err)),
+            None => Ok(0),
+        }
+    }
+
+    async fn get_time_factor(&self) -> Result<f64, String> {
+        let row = sqlx::query(
+            r#"
+                SELECT time_factor
+                FROM job_runner_setting
+                WHERE id = TRUE;
+            "#,
+        )
+        .fetch_optional(&self.sqlx)
+        .await
+        .map_err(|err| format!("Error fetching time factor: {}", err))?;
+
+        match row {
+            Some(row) => row
+                .try_get::<f64, _>("time_factor")
+                .map_err(|err| format!("Error reading time factor: {}", err)),
+            None => Err("Job runner setting is missing from job_runner_setting".to_string()),
+        }
+    }
+
+    async fn set_time_factor(&self, time_factor: f64) -> Result<(), String> {
+        sqlx::query(
+            r#"
+                UPDATE job_runner_setting
+                SET time_factor = $1
+                WHERE id = TRUE;
+            "#,
+        )
+        .bind(time_factor)
+        .execute(&self.sqlx)
+        .await
+        .map_err(|err| format!("Error updating time factor: {}", err))?;
+
+        Ok(())
+    }
+
+    async fn request_wait_skip(&self) -> Result<(), String> {
+        sqlx::query(
+            r#"
+                UPDATE job_runner_setting
+                SET skip_wait_requested = TRUE
+                WHERE id = TRUE;
+            "#,
+        )
+        .execute(&self.sqlx)
+        .await
+        .map_err(|err| format!("Error requesting a wait skip: {}", err))?;
+
+        Ok(())
+    }
+
+    async fn take_wait_skip_request(&self) -> Result<bool, String> {
+        let row = sqlx::query(
+            r#"
+                UPDATE job_runner_setting
+                SET skip_wait_requested = FALSE
+                WHERE id = TRUE
+                  AND skip_wait_requested
+                RETURNING id;
+            "#,
+        )
+        .fetch_optional(&self.sqlx)
+        .await
+        .map_err(|err| format!("Error taking the wait skip request: {}", err))?;
+
+        Ok(row.is_some())
+    }
 }
@@ -0,0 +1,75 @@
+use crate::capability::llm_batch::LlmBatchCapability;
+use crate::nice_display::NiceDisplay;
+use crate::offload;
+use crate::open_ai::batch::{self, Batch, BatchRequest, BatchResult};
+use crate::worker::Worker;
+
+impl LlmBatchCapability for Worker {
+    async fn submit_llm_batch(&self, requests: Vec<BatchRequest>) -> Result<String, String> {
+        let jsonl = batch::to_jsonl(&requests);
+        let open_ai_key = self.require_open_ai_key().map_err(|err| err.message())?;
+
+        let input_file_id =
+            batch::upload_batch_file(open_ai_key, self.open_ai_client.clone(), &jsonl)
+                .await
+                .map_err(|err| err.message())?;
+
+        let created = batch::create_batch(open_ai_key, self.open_ai_client.clone(), &input_file_id)
+            .await
+            .map_err(|err| err.message())?;
+
+        sqlx::query(
+            r#"
+                INSERT INTO llm_batch (batch_id, status, request_count)
+                VALUES ($1::TEXT, $2::TEXT, $3::INT)
+            "#,
+        )
+        .bind(created.id.as_str())
+        .bind(created.status.to_name())
+        .bind(i32::try_from(requests.len()).unwrap_or(i32::MAX))
+        .execute(&self.sqlx)
+        .await
+        .map_err(|err| format!("Error recording llm batch: {}", err))?;
+
+        Ok(created.id)
+    }
+
+    async fn refresh_llm_batch(&self, batch_id: &str) -> Result<Batch, String> {
+        let open_ai_key = self.require_open_ai_key().map_err(|err| err.message())?;
+
+        let batch = batch::get_batch(open_ai_key, self.open_ai_client.clone(), batch_id)
+            .await
+            .map_err(|err| err.message())?;
+
+        sqlx::query(
+            r#"
+                UPDATE llm_batch
+                SET status = $2::TEXT,
+                    finished_at = CASE
+                        WHEN $3::BOOLEAN THEN COALESCE(finished_at, now())
+                        ELSE NULL
+                    END
+                WHERE batch_id = $1::TEXT
+            "#,
+        )
+        .bind(batch_id)
+        .bind(batch.status.to_name())
+        .bind(batch.status.is_terminal())
+        .execute(&self.sqlx)
+        .await
+        .map_err(|err| format!("Error updating llm batch status: {}", err))?;
+
+        Ok(batch)
+    }
+
+    async fn get_llm_batch_results(&self, file_id: &str) -> Result<Vec<BatchResult>, String> {
+        let open_ai_key = self.require_open_ai_key().map_err(|err| err.message())?;
+
+        let jsonl = batch::download_file(open_ai_key, self.open_ai_client.clone(), file_id)
+            .await
+            .map_err(|err| err.message())?;
+
+        // Output files hold every answer in the batch
+        offload::run(move || batch::parse_results(&jsonl).map_err(|err| err.message())).await?
+    }
+}
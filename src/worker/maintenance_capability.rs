@@ -0,0 +1,358 @@
+use crate::capability::maintenance::MaintenanceCapability;
+use crate::capability::scene::{NewSceneSnapshot, SceneCapability};
+use crate::domain::job::nightly_maintenance::{
+    MaintenanceReport, PrunedRows, RecentMemories, REPORT_EVENT_NAME,
+};
+use crate::domain::job_event::JobEventKind;
+use crate::domain::memory::Memory;
+use crate::domain::person_uuid::PersonUuid;
+use crate::domain::scene_uuid::SceneUuid;
+use crate::nice_display::NiceDisplay;
+use crate::open_ai::completion::Completion;
+use crate::open_ai::role::Role;
+use crate::worker::Worker;
+use chrono::{DateTime, Utc};
+use sqlx::Row;
+use uuid::Uuid;
+
+impl MaintenanceCapability for Worker {
+    async fn reap_stale_jobs(&self, started_before: DateTime<Utc>) -> Result<u64, String> {
+        let mut transaction = self
+            .sqlx
+            .begin()
+            .await
+            .map_err(|err| format!("Error starting reap jobs transaction: {}", err))?;
+
+        let rows = sqlx::query(
+            r#"
+                UPDATE job
+                SET error = $2::TEXT
+                WHERE started_at < $1::TIMESTAMPTZ
+                  AND finished_at IS NULL
+                  AND error IS NULL
+                  AND deleted_at IS NULL
+                RETURNING uuid;
+            "#,
+        )
+        .bind(started_before)
+        .bind("Reaped by nightly maintenance, since it was still running long after its runner would have finished it")
+        .fetch_all(&mut *transaction)
+        .await
+        .map_err(|err| format!("Error reaping stale jobs: {}", err))?;
+
+        for row in rows.iter() {
+            let job_uuid = row
+                .try_get::<Uuid, _>("uuid")
+                .map_err(|err| format!("Error reading reaped job uuid: {}", err))?;
+
+            sqlx::query(
+                r#"
+                    INSERT INTO job_event (uuid, job_uuid, kind, error_class)
+                    VALUES ($1::UUID, $2::UUID, $3::TEXT, 'reaped');
+                "#,
+            )
+            .bind(Uuid::now_v7())
+            .bind(job_uuid)
+            .bind(JobEventKind::Failed.to_name())
+            .execute(&mut *transaction)
+            .await
+            .map_err(|err| format!("Error recording a reaped job: {}", err))?;
+        }
+
+        transaction
+            .commit()
+            .await
+            .map_err(|err| format!("Error committing reaped jobs: {}", err))?;
+
+        Ok(rows.len() as u64)
+    }
+
+    async fn roll_up_llm_usage(&self, before: DateTime<Utc>) -> Result<u64, String> {
+        // Only whole days are rolled up, and only once, since their calls
+        // may be pruned afterwards
+        let result = sqlx::query(
+            r#"
+                INSERT INTO llm_usage_daily (
+                    day,
+                    provider,
+                    model,
+                    calls,
+                    failed_calls,
+                    prompt_tokens,
+                    completion_tokens,
+                    duration_ms
+                )
+                SELECT
+                    (created_at AT TIME ZONE 'UTC')::DATE,
+                    provider,
+                    model,
+                    COUNT(*),
+                    COUNT(*) FILTER (WHERE NOT succeeded),
+                    COALESCE(SUM(prompt_tokens), 0),
+                    COALESCE(SUM(completion_tokens), 0),
+                    SUM(duration_ms)
+                FROM llm_call
+                WHERE (created_at AT TIME ZONE 'UTC')::DATE < ($1::TIMESTAMPTZ AT TIME ZONE 'UTC')::DATE
+                  AND (created_at AT TIME ZONE 'UTC')::DATE > COALESCE(
+                      (SELECT MAX(day) FROM llm_usage_daily),
+                      '-infinity'::DATE
+                  )
+                GROUP BY 1, provider, model
+                ON CONFLICT (day, provider, model) DO NOTHING;
+            "#,
+        )
+        .bind(before)
+        .execute(&self.sqlx)
+        .await
+        .map_err(|err| format!("Error rolling up llm usage: {}", err))?;
+
+        Ok(result.rows_affected())
+    }
+
+    async fn prune_logs(&self, before: DateTime<Utc>) -> Result<PrunedRows, String> {
+        let llm_calls = sqlx::query("DELETE FROM llm_call WHERE created_at < $1::TIMESTAMPTZ;")
+            .bind(before)
+            .execute(&self.sqlx)
+            .await
+            .map_err(|err| format!("Error pruning llm calls: {}", err))?
+            .rows_affected();
+
+        let log_events = sqlx::query(
+            r#"
+                DELETE FROM log_event
+                WHERE created_at < $1::TIMESTAMPTZ
+                  AND event_name <> $2::TEXT;
+            "#,
+        )
+        .bind(before)
+        .bind(REPORT_EVENT_NAME)
+        .execute(&self.sqlx)
+        .await
+        .map_err(|err| format!("Error pruning log events: {}", err))?
+        .rows_affected();
+
+        Ok(PrunedRows {
+            llm_calls,
+            log_events,
+        })
+    }
+
+    async fn remove_duplicate_memories(&self) -> Result<u64, String> {
+        let result = sqlx::query(
+            r#"
+                DELETE FROM memory duplicate
+                USING memory kept
+                WHERE duplicate.person_uuid = kept.person_uuid
+                  AND lower(btrim(duplicate.content)) = lower(btrim(kept.content))
+                  AND (kept.created_at, kept.uuid) < (duplicate.created_at, duplicate.uuid);
+            "#,
+        )
+        .execute(&self.sqlx)
+        .await
+        .map_err(|err| format!("Error removing duplicate memories: {}", err))?;
+
+        Ok(result.rows_affected())
+    }
+
+    async fn get_recent_memories(
+        &self,
+        since: DateTime<Utc>,
+    ) -> Result<Vec<RecentMemories>, String> {
+        let rows = sqlx::query(
+            r#"
+                SELECT memory.person_uuid, memory.content
+                FROM memory
+                JOIN person ON person.uuid = memory.person_uuid
+                WHERE memory.created_at >= $1::TIMESTAMPTZ
+                  AND person.is_enabled
+                  AND NOT person.is_hibernating
+                  AND person.archived_at IS NULL
+                ORDER BY memory.person_uuid, memory.created_at ASC;
+            "#,
+        )
+        .bind(since)
+        .fetch_all(&self.sqlx)
+        .await
+        .map_err(|err| format!("Error fetching recent memories: {}", err))?;
+
+        let mut recent: Vec<RecentMemories> = Vec::new();
+        for row in rows.iter() {
+            let person_uuid = PersonUuid::from_uuid(
+                row.try_get::<Uuid, _>("person_uuid")
+                    .map_err(|err| format!("Error reading memory person uuid: {}", err))?,
+            );
+            let content = row
+                .try_get::<String, _>("content")
+                .map_err(|err| format!("Error reading memory content: {}", err))?;
+
+            match recent.last_mut() {
+                Some(last) if last.person_uuid == person_uuid => {
+                    last.memories.push(Memory { content });
+                }
+                _ => recent.push(RecentMemories {
+                    person_uuid,
+                    memories: vec![Memory { content }],
+                }),
+            }
+        }
+
+        Ok(recent)
+    }
+
+    async fn get_scenes_changed_since_snapshot(&self) -> Result<Vec<SceneUuid>, String> {
+        let rows = sqlx::query(
+            r#"
+                SELECT scene.uuid
+                FROM scene
+                LEFT JOIN LATERAL (
+                    SELECT MAX(created_at) AS at
+                    FROM scene_snapshot
+                    WHERE scene_snapshot.scene_uuid = scene.uuid
+                ) snapshot ON TRUE
+                WHERE scene.ended_at IS NULL
+                  AND scene.archived_at IS NULL
+                  AND (
+                    EXISTS (
+                        SELECT 1
+                        FROM scene_event
+                        WHERE scene_event.scene_uuid = scene.uuid
+                          AND scene_event.created_at > COALESCE(snapshot.at, '-infinity')
+                    )
+                    OR EXISTS (
+                        SELECT 1
+                        FROM scene_ambience_change
+                        WHERE scene_ambience_change.scene_uuid = scene.uuid
+                          AND scene_ambience_change.changed_at > COALESCE(snapshot.at, '-infinity')
+                    )
+                  )
+                ORDER BY scene.name ASC;
+            "#,
+        )
+        .fetch_all(&self.sqlx)
+        .await
+        .map_err(|err| format!("Error fetching scenes with stale snapshots: {}", err))?;
+
+        rows.iter()
+            .map(|row| {
+                row.try_get::<Uuid, _>("uuid")
+                    .map(SceneUuid::from_uuid)
+                    .map_err(|err| format!("Error reading scene uuid: {}", err))
+            })
+            .collect()
+    }
+
+    async fn refresh_scene_snapshot(&self, scene_uuid: &SceneUuid) -> Result<(), String> {
+        let description = self
+            .get_scene_description(scene_uuid)
+            .await?
+            .unwrap_or_default();
+
+        let rows = sqlx::query(
+            r#"
+                WITH snapshot AS (
+                    SELECT COALESCE(MAX(created_at), '-infinity') AS at
+                    FROM scene_snapshot
+                    WHERE scene_uuid = $1::UUID
+                )
+                SELECT change FROM (
+                    SELECT scene_event.created_at AS at, scene_event.description AS change
+                    FROM scene_event, snapshot
+                    WHERE scene_event.scene_uuid = $1::UUID
+                      AND scene_event.created_at > snapshot.at
+
+                    UNION ALL
+
+                    SELECT
+                        scene_ambience_change.changed_at,
+                        'The weather turned ' || scene_ambience_change.weather
+                            || ', the noise became ' || scene_ambience_change.noise
+                            || ' and the lighting ' || scene_ambience_change.lighting || '.'
+                    FROM scene_ambience_change, snapshot
+                    WHERE scene_ambience_change.scene_uuid = $1::UUID
+                      AND scene_ambience_change.changed_at > snapshot.at
+                ) changes
+                ORDER BY at ASC;
+            "#,
+        )
+        .bind(scene_uuid.to_uuid())
+        .fetch_all(&self.sqlx)
+        .await
+        .map_err(|err| format!("Error fetching scene changes: {}", err))?;
+
+        let changes = rows
+            .iter()
+            .map(|row| {
+                row.try_get::<String, _>("change")
+                    .map(|change| format!("- {}", change))
+                    .map_err(|err| format!("Error reading scene change: {}", err))
+            })
+            .collect::<Result<Vec<String>, String>>()?;
+
+        if changes.is_empty() {
+            return Ok(());
+        }
+
+        let mut completion = Completion::new();
+        completion.add_message(
+            Role::System,
+            "You keep scene descriptions for roleplay environments up to date. Rewrite the description so it reflects the changes, keeping everything that still holds. Return exactly two paragraphs. No bullet points or titles. Write in neutral third-person environmental prose only. Do not address the reader (avoid 'you' and imperative phrasing). Do not include specific people, named actors, or what any individual is doing.",
+        );
+        completion.add_message(
+            Role::User,
+            format!(
+                "Current description:\n{}\n\nWhat has changed since, oldest first:\n{}",
+                description,
+                changes.join("\n")
+            )
+            .as_str(),
+        );
+
+        let response = completion
+            .send_request(self.open_ai_key.as_ref(), self.open_ai_client.clone())
+            .await
+            .map_err(|err| format!("Failed to refresh scene description: {}", err.message()))?;
+
+        let refreshed = response.as_message().map_err(|err| {
+            format!(
+                "Failed to read refreshed scene description: {}",
+                err.message()
+            )
+        })?;
+
+        self.create_scene_snapshot(NewSceneSnapshot {
+            scene_uuid: scene_uuid.clone(),
+            description: refreshed,
+        })
+        .await
+    }
+
+    async fn get_latest_maintenance_report(&self) -> Result<Option<MaintenanceReport>, String> {
+        let maybe_row = sqlx::query(
+            r#"
+                SELECT data
+                FROM log_event
+                WHERE event_name = $1::TEXT
+                ORDER BY created_at DESC
+                LIMIT 1;
+            "#,
+        )
+        .bind(REPORT_EVENT_NAME)
+        .fetch_optional(&self.sqlx)
+        .await
+        .map_err(|err| format!("Error fetching the latest maintenance report: {}", err))?;
+
+        let data = match maybe_row {
+            Some(row) => row
+                .try_get::<Option<serde_json::Value>, _>("data")
+                .map_err(|err| format!("Error reading maintenance report: {}", err))?,
+            None => return Ok(None),
+        };
+
+        match data {
+            Some(data) => serde_json::from_value(data)
+                .map(Some)
+                .map_err(|err| format!("Error parsing maintenance report: {}", err)),
+            None => Ok(None),
+        }
+    }
+}
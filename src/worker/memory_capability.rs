@@ -1,4 +1,6 @@
 use super::Worker;
+use crate::capability::clock::ClockCapability;
+use crate::capability::content_scrub::ContentScrubCapability;
 use crate::capability::log_event::LogEventCapability;
 use crate::capability::memory::{
     MemoryCapability, MemoryQueryPrompt, MemorySearchResult, MessageTypeArgs, NewMemory,
@@ -6,16 +8,19 @@ use crate::capability::memory::{
 use crate::capability::person::PersonCapability;
 use crate::capability::scene::SceneCapability;
 use crate::domain::logger::Level;
+use crate::domain::memory::MemoryRetrieval;
 use crate::domain::memory_uuid::MemoryUuid;
 use crate::domain::message::MessageSender;
 use crate::domain::person_name::PersonName;
 use crate::domain::person_uuid::PersonUuid;
+use crate::domain::utterance::REAL_WORLD_USER_NAME;
 use crate::nice_display::NiceDisplay;
 use crate::open_ai::completion::Completion;
-use crate::open_ai::embedding::EmbeddingRequest;
+use crate::open_ai::embedding::{check_stored_embeddings, EmbeddingRequest, StoredEmbeddingCount};
 use crate::open_ai::role::Role;
 use crate::open_ai::tool::{ToolFunction, ToolFunctionParameter};
 use crate::open_ai::tool_call::ToolCall;
+use chrono::{DateTime, Utc};
 use sqlx::Row;
 use uuid::Uuid;
 
@@ -23,7 +28,7 @@ impl MemoryCapability for Worker {
     async fn create_memory(&self, new_memory: NewMemory) -> Result<MemoryUuid, String> {
         let memory_uuid = new_memory.memory_uuid;
         let person_uuid = new_memory.person_uuid;
-        let content = new_memory.content;
+        let content = self.scrub_content(new_memory.content.as_str()).await?;
 
         let person_name = self
             .get_persons_name(person_uuid.clone())
@@ -37,11 +42,18 @@ impl MemoryCapability for Worker {
         let subject_tags = normalize_string_list(metadata.subject_tags);
         let people_uuids = map_people_names_to_uuids(self, people_names.as_slice()).await?;
 
-        let embedding = EmbeddingRequest::new(metadata.retrieval_summary.clone())
-            .create(self.open_ai_key.clone(), self.reqwest_client.clone())
+        let embedding_request = EmbeddingRequest::new(metadata.retrieval_summary.clone());
+        let embedding = embedding_request
+            .create(self.open_ai_key.as_ref(), self.open_ai_client.clone())
             .await
             .map_err(|err| err.message())?;
 
+        let mut transaction = self
+            .sqlx
+            .begin()
+            .await
+            .map_err(|err| format!("Error starting memory transaction: {}", err))?;
+
         sqlx::query!(
             r#"
                 INSERT INTO memory (
@@ -49,6 +61,8 @@ impl MemoryCapability for Worker {
                     person_uuid,
                     content,
                     embedding,
+                    embedding_model,
+                    embedding_dimension,
                     summary,
                     emotional_score,
                     retrieval_summary,
@@ -65,16 +79,20 @@ impl MemoryCapability for Worker {
                     $5::TEXT,
                     $6::INT,
                     $7::TEXT,
-                    $8::TEXT,
-                    $9::TEXT[],
-                    $10::UUID[],
-                    $11::TEXT[]
+                    $8::INT,
+                    $9::TEXT,
+                    $10::TEXT,
+                    $11::TEXT[],
+                    $12::UUID[],
+                    $13::TEXT[]
                 );
             "#,
             memory_uuid.to_uuid(),
             person_uuid.to_uuid(),
             content,
             &embedding[..] as &[f32],
+            embedding_request.model().to_string(),
+            embedding_request.model().dimension(),
             metadata.summary,
             metadata.emotional_score,
             metadata.retrieval_summary,
@@ -83,10 +101,29 @@ impl MemoryCapability for Worker {
             &people_uuids as &[Uuid],
             &subject_tags as &[String],
         )
-        .execute(&self.sqlx)
+        .execute(&mut *transaction)
         .await
         .map_err(|err| format!("Error inserting new memory: {}", err))?;
 
+        sqlx::query!(
+            r#"
+                INSERT INTO memory_person_mention (memory_uuid, person_uuid)
+                SELECT $1::UUID, mentioned.person_uuid
+                FROM UNNEST($2::UUID[]) AS mentioned(person_uuid)
+                ON CONFLICT DO NOTHING;
+            "#,
+            memory_uuid.to_uuid(),
+            &people_uuids as &[Uuid],
+        )
+        .execute(&mut *transaction)
+        .await
+        .map_err(|err| format!("Error inserting memory person mentions: {}", err))?;
+
+        transaction
+            .commit()
+            .await
+            .map_err(|err| format!("Error committing memory transaction: {}", err))?;
+
         Ok(memory_uuid)
     }
 
@@ -133,7 +170,7 @@ impl MemoryCapability for Worker {
         );
         completion.add_tool_call(tool.into());
         let response = completion
-            .send_request(&self.open_ai_key, reqwest::Client::new())
+            .send_request(self.open_ai_key.as_ref(), self.open_ai_client.clone())
             .await
             .map_err(|err| err.message())?;
 
@@ -293,7 +330,7 @@ impl MemoryCapability for Worker {
 
                             rec.name
                         }
-                        MessageSender::RealWorldUser => "Chadtech".to_string(),
+                        MessageSender::RealWorldUser => REAL_WORLD_USER_NAME.to_string(),
                     }
                 };
 
@@ -369,7 +406,7 @@ impl MemoryCapability for Worker {
         completion.add_message(Role::User, prompt.as_str());
 
         let response = completion
-            .send_request(&self.open_ai_key, self.reqwest_client.clone())
+            .send_request(self.open_ai_key.as_ref(), self.open_ai_client.clone())
             .await
             .map_err(|err| {
                 format!(
@@ -394,35 +431,89 @@ impl MemoryCapability for Worker {
         &self,
         person_uuid: PersonUuid,
         query: String,
-        limit: i64,
+        participants: &[PersonUuid],
+        retrieval: &MemoryRetrieval,
     ) -> Result<Vec<MemorySearchResult>, String> {
         // Generate embedding for the query
-        let query_embedding = EmbeddingRequest::new(query)
-            .create(self.open_ai_key.clone(), self.reqwest_client.clone())
+        let embedding_request = EmbeddingRequest::new(query);
+        let embedding_model = embedding_request.model();
+        let query_embedding = embedding_request
+            .create(self.open_ai_key.as_ref(), self.open_ai_client.clone())
             .await
             .map_err(|err| err.message())?;
 
-        // Search for similar memories using vector similarity
+        // Vectors from another model are not comparable, so only search the
+        // memories embedded the same way as the query
+        let stored_counts = get_stored_embedding_counts(self, &person_uuid).await?;
+        let skipped = check_stored_embeddings(embedding_model, &stored_counts)
+            .map_err(|err| format!("Cannot search memories for {}: {}", person_uuid, err))?;
+
+        if skipped > 0 {
+            self.logger.log(
+                Level::Warning,
+                format!(
+                    "Skipping {} memories for {} that were not embedded with {}",
+                    skipped, person_uuid, embedding_model
+                )
+                .as_str(),
+            );
+        }
+
+        let participant_uuids = participants
+            .iter()
+            .map(|participant| participant.to_uuid())
+            .collect::<Vec<Uuid>>();
+
+        // Search for similar memories using vector similarity. Memories that
+        // mention someone in the scene are candidates however far they are,
+        // and get their boost once they are ranked below. Extra nearest
+        // candidates leave room for the recency weight to reorder them.
         let records = sqlx::query(
             r#"
+                WITH nearest AS (
+                    SELECT uuid
+                    FROM memory
+                    WHERE person_uuid = $2::UUID
+                      AND embedding_model = $4::TEXT
+                      AND embedding_dimension = $5::INT
+                    ORDER BY embedding <=> $1::vector
+                    LIMIT $3
+                ),
+                mentioning AS (
+                    SELECT DISTINCT mention.memory_uuid AS uuid
+                    FROM memory_person_mention AS mention
+                    JOIN memory ON memory.uuid = mention.memory_uuid
+                    WHERE memory.person_uuid = $2::UUID
+                      AND memory.embedding_model = $4::TEXT
+                      AND memory.embedding_dimension = $5::INT
+                      AND mention.person_uuid = ANY($6::UUID[])
+                )
                 SELECT
-                    content,
-                    (embedding <=> $1::vector)::FLOAT AS distance
+                    memory.uuid,
+                    memory.content,
+                    (memory.embedding <=> $1::vector)::FLOAT AS distance,
+                    memory.uuid IN (SELECT uuid FROM mentioning) AS mentions_participant,
+                    memory.created_at
                 FROM memory
-                WHERE person_uuid = $2::UUID
-                ORDER BY embedding <=> $1::vector
-                LIMIT $3
+                WHERE memory.uuid IN (SELECT uuid FROM nearest)
+                   OR memory.uuid IN (SELECT uuid FROM mentioning)
             "#,
         )
         .bind(&query_embedding[..] as &[f32])
         .bind(person_uuid.to_uuid())
-        .bind(limit)
+        .bind(retrieval.top_k * CANDIDATES_PER_RESULT)
+        .bind(embedding_model.to_string())
+        .bind(embedding_model.dimension())
+        .bind(&participant_uuids as &[Uuid])
         .fetch_all(&self.sqlx)
         .await
         .map_err(|err| format!("Error searching memories: {}", err))?;
 
         let mut results = Vec::with_capacity(records.len());
         for rec in records {
+            let memory_uuid = rec
+                .try_get::<Uuid, _>("uuid")
+                .map_err(|err| format!("Error reading memory uuid: {}", err))?;
             let content = rec
                 .try_get::<String, _>("content")
                 .map_err(|err| format!("Error reading memory content: {}", err))?;
@@ -430,16 +521,134 @@ impl MemoryCapability for Worker {
                 .try_get::<Option<f64>, _>("distance")
                 .map_err(|err| format!("Error reading memory distance: {}", err))?
                 .unwrap_or(f64::MAX);
-
-            results.push(MemorySearchResult { content, distance });
+            let mentions_participant = rec
+                .try_get::<bool, _>("mentions_participant")
+                .map_err(|err| format!("Error reading memory mentions participant: {}", err))?;
+            let created_at = rec
+                .try_get::<DateTime<Utc>, _>("created_at")
+                .map_err(|err| format!("Error reading memory created at: {}", err))?;
+
+            results.push(MemorySearchResult {
+                memory_uuid: MemoryUuid::from_uuid(memory_uuid),
+                content,
+                distance,
+                mentions_participant,
+                created_at,
+                score: distance,
+            });
         }
 
-        Ok(results)
+        Ok(retrieval.rank(results, self.now()))
+    }
+
+    async fn get_memory_retrieval(&self) -> Result<MemoryRetrieval, String> {
+        let row = sqlx::query(
+            r#"
+                SELECT top_k, max_distance, recency_weight
+                FROM memory_retrieval_setting
+                WHERE id = TRUE;
+            "#,
+        )
+        .fetch_optional(&self.sqlx)
+        .await
+        .map_err(|err| format!("Error fetching memory retrieval settings: {}", err))?;
+
+        let row = match row {
+            Some(row) => row,
+            None => {
+                return Err(
+                    "Memory retrieval settings are missing from memory_retrieval_setting"
+                        .to_string(),
+                );
+            }
+        };
+
+        let top_k = row
+            .try_get::<i32, _>("top_k")
+            .map_err(|err| format!("Error reading top k from row: {}", err))?;
+
+        let max_distance = row
+            .try_get::<f64, _>("max_distance")
+            .map_err(|err| format!("Error reading max distance from row: {}", err))?;
+
+        let recency_weight = row
+            .try_get::<f64, _>("recency_weight")
+            .map_err(|err| format!("Error reading recency weight from row: {}", err))?;
+
+        Ok(MemoryRetrieval {
+            top_k: top_k as i64,
+            max_distance,
+            recency_weight,
+        })
+    }
+
+    async fn set_memory_retrieval(&self, retrieval: &MemoryRetrieval) -> Result<(), String> {
+        sqlx::query(
+            r#"
+                UPDATE memory_retrieval_setting
+                SET top_k = $1::INT,
+                    max_distance = $2::DOUBLE PRECISION,
+                    recency_weight = $3::DOUBLE PRECISION
+                WHERE id = TRUE;
+            "#,
+        )
+        .bind(retrieval.top_k as i32)
+        .bind(retrieval.max_distance)
+        .bind(retrieval.recency_weight)
+        .execute(&self.sqlx)
+        .await
+        .map_err(|err| format!("Error updating memory retrieval settings: {}", err))?;
+
+        Ok(())
+    }
+
+    async fn delete_memory(&self, memory_uuid: &MemoryUuid) -> Result<(), String> {
+        sqlx::query(
+            r#"
+                DELETE FROM memory
+                WHERE uuid = $1::UUID;
+            "#,
+        )
+        .bind(memory_uuid.to_uuid())
+        .execute(&self.sqlx)
+        .await
+        .map_err(|err| format!("Error deleting memory: {}", err))?;
+
+        Ok(())
     }
 }
 
+async fn get_stored_embedding_counts(
+    worker: &Worker,
+    person_uuid: &PersonUuid,
+) -> Result<Vec<StoredEmbeddingCount>, String> {
+    let rows = sqlx::query!(
+        r#"
+            SELECT embedding_model, embedding_dimension, COUNT(*) AS "count!"
+            FROM memory
+            WHERE person_uuid = $1::UUID
+            GROUP BY embedding_model, embedding_dimension
+        "#,
+        person_uuid.to_uuid(),
+    )
+    .fetch_all(&worker.sqlx)
+    .await
+    .map_err(|err| format!("Error counting memory embeddings: {}", err))?;
+
+    Ok(rows
+        .into_iter()
+        .map(|row| StoredEmbeddingCount {
+            model: row.embedding_model,
+            dimension: row.embedding_dimension,
+            count: row.count,
+        })
+        .collect())
+}
+
 const MIN_MEMORY_DISTANCE: f64 = 0.15;
 const MIN_MEMORABLE_SCORE: i64 = 75;
+/// How many nearest memories to rank for each one a search returns.
+const CANDIDATES_PER_RESULT: i64 = 4;
 
 async fn log_memory_decision(
     worker: &Worker,
@@ -479,7 +688,15 @@ async fn is_memory_distinct(
     content: &str,
 ) -> Result<bool, String> {
     let matches = worker
-        .search_memories(person_uuid.clone(), content.to_string(), 1)
+        .search_memories(
+            person_uuid.clone(),
+            content.to_string(),
+            &[],
+            &MemoryRetrieval {
+                top_k: 1,
+                ..MemoryRetrieval::default()
+            },
+        )
         .await?;
 
     if matches.is_empty() {
@@ -602,7 +819,7 @@ async fn summarize_memory_metadata(
     );
 
     let response = completion
-        .send_request(&worker.open_ai_key, worker.reqwest_client.clone())
+        .send_request(worker.open_ai_key.as_ref(), worker.open_ai_client.clone())
         .await
         .map_err(|err| err.message())?;
     let response_json = response.as_pretty_json();
@@ -1,11 +1,43 @@
+use crate::capability::content_scrub::ContentScrubCapability;
 use crate::capability::message::MessageCapability;
 use crate::domain::message::{Message, MessageSender};
+use crate::domain::message_audience::MessageAudience;
+use crate::domain::message_content;
+use crate::domain::message_quote::MessageQuote;
 use crate::domain::message_uuid::MessageUuid;
+use crate::domain::outbox::OutboxEvent;
 use crate::domain::person_uuid::PersonUuid;
 use crate::domain::scene_uuid::SceneUuid;
 use crate::temporary_event_cutoff::event_history_cutoff;
+use crate::worker::outbox_capability::write_outbox_event;
 use crate::worker::Worker;
 use chrono::{DateTime, Utc};
+use sqlx::{PgConnection, Row};
+use std::collections::HashMap;
+
+/// Stores a message body once per distinct text, on the connection of the
+/// transaction sending the message, and gives the hash to reference it by.
+pub(super) async fn store_message_content(
+    connection: &mut PgConnection,
+    body: &str,
+) -> Result<String, String> {
+    let hash = message_content::content_hash(body);
+
+    sqlx::query(
+        r#"
+            INSERT INTO message_content (hash, body)
+            VALUES ($1::TEXT, $2::TEXT)
+            ON CONFLICT (hash) DO NOTHING
+        "#,
+    )
+    .bind(hash.as_str())
+    .bind(body)
+    .execute(connection)
+    .await
+    .map_err(|err| format!("Error storing message content: {}", err))?;
+
+    Ok(hash)
+}
 
 impl MessageCapability for Worker {
     async fn send_scene_message(
@@ -15,26 +47,87 @@ impl MessageCapability for Worker {
         content: String,
     ) -> Result<MessageUuid, String> {
         let message_uuid = MessageUuid::new();
+        let content = self.scrub_content(content.as_str()).await?;
 
-        let sender_uuid = match sender {
-            MessageSender::AiPerson(person_uuid) => Some(person_uuid.to_uuid()),
+        let sender_person_uuid = match sender {
+            MessageSender::AiPerson(person_uuid) => Some(person_uuid),
             MessageSender::RealWorldUser => None,
         };
+        let sender_uuid = sender_person_uuid
+            .as_ref()
+            .map(|person_uuid| person_uuid.to_uuid());
+
+        let mut transaction = self
+            .sqlx
+            .begin()
+            .await
+            .map_err(|err| format!("Error starting scene message transaction: {}", err))?;
+
+        let content_hash = store_message_content(&mut transaction, content.as_str()).await?;
 
         sqlx::query!(
             r#"
-                INSERT INTO message (uuid, sender_person_uuid, scene_uuid, content)
+                INSERT INTO message (uuid, sender_person_uuid, scene_uuid, content_hash)
                 VALUES ($1::UUID, $2::UUID, $3::UUID, $4::TEXT)
             "#,
             message_uuid.to_uuid(),
             sender_uuid,
             scene_uuid.to_uuid(),
-            content
+            content_hash
         )
-        .execute(&self.sqlx)
+        .execute(&mut *transaction)
         .await
         .map_err(|err| format!("Error inserting scene message: {}", err))?;
 
+        // A person speaking answers whatever the real world user said in the
+        // scene since anyone last spoke up
+        if sender_uuid.is_some() {
+            sqlx::query(
+                r#"
+                    INSERT INTO chat_latency (message_uuid, reply_message_uuid, scene_uuid, latency_ms)
+                    SELECT asked.uuid,
+                           reply.uuid,
+                           asked.scene_uuid,
+                           GREATEST(0, (EXTRACT(EPOCH FROM (reply.sent_at - asked.sent_at)) * 1000)::BIGINT)
+                    FROM message AS asked
+                    JOIN message AS reply ON reply.uuid = $1::UUID
+                    WHERE asked.scene_uuid = $2::UUID
+                      AND asked.sender_person_uuid IS NULL
+                      AND asked.sent_at <= reply.sent_at
+                      AND NOT EXISTS (
+                          SELECT 1
+                          FROM message AS earlier_reply
+                          WHERE earlier_reply.scene_uuid = asked.scene_uuid
+                            AND earlier_reply.sender_person_uuid IS NOT NULL
+                            AND earlier_reply.sent_at > asked.sent_at
+                            AND earlier_reply.uuid <> reply.uuid
+                      )
+                    ON CONFLICT (message_uuid) DO NOTHING
+                "#,
+            )
+            .bind(message_uuid.to_uuid())
+            .bind(scene_uuid.to_uuid())
+            .execute(&mut *transaction)
+            .await
+            .map_err(|err| format!("Error recording chat latency: {}", err))?;
+        }
+
+        write_outbox_event(
+            &mut transaction,
+            &OutboxEvent::SceneMessageSent {
+                message_uuid: message_uuid.clone(),
+                scene_uuid,
+                sender_person_uuid,
+                content,
+            },
+        )
+        .await?;
+
+        transaction
+            .commit()
+            .await
+            .map_err(|err| format!("Error committing scene message transaction: {}", err))?;
+
         Ok(message_uuid)
     }
 
@@ -61,6 +154,58 @@ impl MessageCapability for Worker {
         Ok(())
     }
 
+    async fn set_message_audience(
+        &self,
+        message_uuid: &MessageUuid,
+        audience: &MessageAudience,
+    ) -> Result<(), String> {
+        sqlx::query(
+            r#"
+                UPDATE message
+                SET audience = $2::TEXT
+                WHERE uuid = $1::UUID
+            "#,
+        )
+        .bind(message_uuid.to_uuid())
+        .bind(audience.to_name())
+        .execute(&self.sqlx)
+        .await
+        .map_err(|err| format!("Error setting message audience: {}", err))?;
+
+        Ok(())
+    }
+
+    async fn add_scene_message_overhearers(
+        &self,
+        message_uuid: &MessageUuid,
+        overhearers: Vec<PersonUuid>,
+    ) -> Result<(), String> {
+        if overhearers.is_empty() {
+            return Ok(());
+        }
+
+        let person_uuids = overhearers
+            .iter()
+            .map(|person_uuid| person_uuid.to_uuid())
+            .collect::<Vec<_>>();
+
+        sqlx::query(
+            r#"
+                INSERT INTO scene_message_recipient (message_uuid, person_uuid, delivery, handled_at)
+                SELECT $1::UUID, person_uuid, 'overheard', NOW()
+                FROM UNNEST($2::UUID[]) AS person_uuid
+                ON CONFLICT DO NOTHING
+            "#,
+        )
+        .bind(message_uuid.to_uuid())
+        .bind(person_uuids)
+        .execute(&self.sqlx)
+        .await
+        .map_err(|err| format!("Error inserting scene message overhearers: {}", err))?;
+
+        Ok(())
+    }
+
     async fn get_messages_in_scene_page(
         &self,
         scene_uuid: &SceneUuid,
@@ -69,11 +214,17 @@ impl MessageCapability for Worker {
     ) -> Result<Vec<Message>, String> {
         let rows = sqlx::query!(
             r#"
-                SELECT uuid, sender_person_uuid,  scene_uuid, content, sent_at
+                SELECT
+                    message.uuid,
+                    message.sender_person_uuid,
+                    message.scene_uuid,
+                    message_content.body AS content,
+                    message.sent_at
                 FROM message
-                WHERE scene_uuid = $1::UUID
-                  AND ($2::timestamptz IS NULL OR sent_at < $2::timestamptz)
-                ORDER BY sent_at DESC
+                JOIN message_content ON message_content.hash = message.content_hash
+                WHERE message.scene_uuid = $1::UUID
+                  AND ($2::timestamptz IS NULL OR message.sent_at < $2::timestamptz)
+                ORDER BY message.sent_at DESC
                 LIMIT $3
             "#,
             scene_uuid.to_uuid(),
@@ -105,9 +256,15 @@ impl MessageCapability for Worker {
     ) -> Result<Option<Message>, String> {
         let row = sqlx::query!(
             r#"
-                SELECT uuid, sender_person_uuid, scene_uuid, content, sent_at
+                SELECT
+                    message.uuid,
+                    message.sender_person_uuid,
+                    message.scene_uuid,
+                    message_content.body AS content,
+                    message.sent_at
                 FROM message
-                WHERE uuid = $1::UUID
+                JOIN message_content ON message_content.hash = message.content_hash
+                WHERE message.uuid = $1::UUID
             "#,
             message_uuid.to_uuid()
         )
@@ -141,8 +298,9 @@ impl MessageCapability for Worker {
     ) -> Result<Vec<Message>, String> {
         let rows = sqlx::query!(
             r#"
-                SELECT m.uuid, m.sender_person_uuid, m.scene_uuid, m.content, m.sent_at
+                SELECT m.uuid, m.sender_person_uuid, m.scene_uuid, mc.body AS content, m.sent_at
                 FROM message m
+                JOIN message_content mc ON mc.hash = m.content_hash
                 JOIN scene_message_recipient smr ON smr.message_uuid = m.uuid
                 WHERE smr.person_uuid = $1::UUID
                   AND smr.handled_at IS NULL
@@ -205,4 +363,71 @@ impl MessageCapability for Worker {
 
         Ok(())
     }
+
+    async fn set_message_quote(
+        &self,
+        message_uuid: &MessageUuid,
+        quote: &MessageQuote,
+    ) -> Result<(), String> {
+        let quote = serde_json::to_value(quote)
+            .map_err(|err| format!("Error serializing message quote: {}", err))?;
+
+        sqlx::query(
+            r#"
+                UPDATE message
+                SET quote = $2::JSONB
+                WHERE uuid = $1::UUID
+            "#,
+        )
+        .bind(message_uuid.to_uuid())
+        .bind(quote)
+        .execute(&self.sqlx)
+        .await
+        .map_err(|err| format!("Error setting message quote: {}", err))?;
+
+        Ok(())
+    }
+
+    async fn get_message_quotes(
+        &self,
+        message_uuids: Vec<MessageUuid>,
+    ) -> Result<HashMap<MessageUuid, MessageQuote>, String> {
+        if message_uuids.is_empty() {
+            return Ok(HashMap::new());
+        }
+
+        let ids: Vec<uuid::Uuid> = message_uuids
+            .into_iter()
+            .map(|uuid| uuid.to_uuid())
+            .collect();
+
+        let rows = sqlx::query(
+            r#"
+                SELECT uuid, quote
+                FROM message
+                WHERE uuid = ANY($1::UUID[])
+                  AND quote IS NOT NULL
+            "#,
+        )
+        .bind(&ids[..])
+        .fetch_all(&self.sqlx)
+        .await
+        .map_err(|err| format!("Error fetching message quotes: {}", err))?;
+
+        let mut quotes = HashMap::new();
+        for row in rows {
+            let uuid = row
+                .try_get::<uuid::Uuid, _>("uuid")
+                .map_err(|err| format!("Error reading message uuid: {}", err))?;
+            let quote = row
+                .try_get::<serde_json::Value, _>("quote")
+                .map_err(|err| format!("Error reading message quote: {}", err))?;
+            let quote = serde_json::from_value::<MessageQuote>(quote)
+                .map_err(|err| format!("Error parsing message quote: {}", err))?;
+
+            quotes.insert(MessageUuid::from_uuid(uuid), quote);
+        }
+
+        Ok(quotes)
+    }
 }
@@ -0,0 +1,248 @@
+use crate::capability::message_revision::MessageRevisionCapability;
+use crate::domain::job::registry;
+use crate::domain::message::{Message, MessageSender};
+use crate::domain::message_uuid::MessageUuid;
+use crate::domain::scene_uuid::SceneUuid;
+use crate::worker::job_capability::POP_JOB_ADVISORY_LOCK;
+use crate::worker::Worker;
+use chrono::{DateTime, Utc};
+use sqlx::Row;
+use std::collections::HashSet;
+use uuid::Uuid;
+
+impl MessageRevisionCapability for Worker {
+    async fn retract_pending_message(&self, message_uuid: &MessageUuid) -> Result<bool, String> {
+        let mut transaction = self
+            .sqlx
+            .begin()
+            .await
+            .map_err(|err| format!("Error starting retract message transaction: {}", err))?;
+
+        // Holding the pop lock means no runner can start a reaction to the
+        // message between checking it and cancelling its jobs
+        sqlx::query("SELECT pg_advisory_xact_lock($1::BIGINT)")
+            .bind(POP_JOB_ADVISORY_LOCK)
+            .execute(&mut *transaction)
+            .await
+            .map_err(|err| format!("Error locking the job queue: {}", err))?;
+
+        let row = sqlx::query(
+            r#"
+                SELECT message.superseded_at IS NULL
+                    AND message.sender_person_uuid IS NULL
+                    AND NOT EXISTS (
+                        SELECT 1
+                        FROM job
+                        WHERE job.name = $2::TEXT
+                          AND job.data ->> 'message_uuid' = message.uuid::TEXT
+                          AND job.started_at IS NOT NULL
+                          AND job.deleted_at IS NULL
+                    )
+                    AND NOT EXISTS (
+                        SELECT 1
+                        FROM scene_message_recipient
+                        WHERE scene_message_recipient.message_uuid = message.uuid
+                          AND scene_message_recipient.delivery <> 'overheard'
+                          AND scene_message_recipient.handled_at IS NOT NULL
+                    ) AS retractable
+                FROM message
+                WHERE message.uuid = $1::UUID
+                FOR UPDATE OF message
+            "#,
+        )
+        .bind(message_uuid.to_uuid())
+        .bind(registry::PROCESS_MESSAGE)
+        .fetch_optional(&mut *transaction)
+        .await
+        .map_err(|err| {
+            format!(
+                "Error checking whether the message can be retracted: {}",
+                err
+            )
+        })?;
+
+        let retractable = match row {
+            Some(row) => row
+                .try_get::<bool, _>("retractable")
+                .map_err(|err| format!("Error reading retractable: {}", err))?,
+            None => false,
+        };
+
+        if !retractable {
+            return Ok(false);
+        }
+
+        sqlx::query(
+            r#"
+                UPDATE message
+                SET superseded_at = NOW()
+                WHERE uuid = $1::UUID
+            "#,
+        )
+        .bind(message_uuid.to_uuid())
+        .execute(&mut *transaction)
+        .await
+        .map_err(|err| format!("Error marking message superseded: {}", err))?;
+
+        sqlx::query(
+            r#"
+                UPDATE job
+                SET deleted_at = NOW()
+                WHERE name = $2::TEXT
+                  AND data ->> 'message_uuid' = $1::UUID::TEXT
+                  AND started_at IS NULL
+                  AND deleted_at IS NULL
+            "#,
+        )
+        .bind(message_uuid.to_uuid())
+        .bind(registry::PROCESS_MESSAGE)
+        .execute(&mut *transaction)
+        .await
+        .map_err(|err| format!("Error cancelling reactions to message: {}", err))?;
+
+        // Waiting people pick up unhandled messages on their own, so the
+        // message is handled for everyone rather than left for them to find
+        sqlx::query(
+            r#"
+                UPDATE scene_message_recipient
+                SET handled_at = NOW()
+                WHERE message_uuid = $1::UUID
+                  AND handled_at IS NULL
+            "#,
+        )
+        .bind(message_uuid.to_uuid())
+        .execute(&mut *transaction)
+        .await
+        .map_err(|err| format!("Error marking retracted message handled: {}", err))?;
+
+        transaction
+            .commit()
+            .await
+            .map_err(|err| format!("Error committing retract message transaction: {}", err))?;
+
+        Ok(true)
+    }
+
+    async fn set_message_superseded_by(
+        &self,
+        message_uuid: &MessageUuid,
+        superseded_by: &MessageUuid,
+    ) -> Result<(), String> {
+        sqlx::query(
+            r#"
+                UPDATE message
+                SET superseded_by_uuid = $2::UUID
+                WHERE uuid = $1::UUID
+            "#,
+        )
+        .bind(message_uuid.to_uuid())
+        .bind(superseded_by.to_uuid())
+        .execute(&self.sqlx)
+        .await
+        .map_err(|err| format!("Error linking edited message: {}", err))?;
+
+        Ok(())
+    }
+
+    async fn get_pending_real_world_user_messages(
+        &self,
+        scene_uuid: &SceneUuid,
+        limit: i64,
+    ) -> Result<Vec<Message>, String> {
+        let rows = sqlx::query(
+            r#"
+                SELECT message.uuid, message_content.body AS content, message.sent_at
+                FROM message
+                JOIN message_content ON message_content.hash = message.content_hash
+                WHERE message.scene_uuid = $1::UUID
+                  AND message.sender_person_uuid IS NULL
+                  AND message.superseded_at IS NULL
+                  AND NOT EXISTS (
+                      SELECT 1
+                      FROM job
+                      WHERE job.name = $3::TEXT
+                        AND job.data ->> 'message_uuid' = message.uuid::TEXT
+                        AND job.started_at IS NOT NULL
+                        AND job.deleted_at IS NULL
+                  )
+                  AND NOT EXISTS (
+                      SELECT 1
+                      FROM scene_message_recipient
+                      WHERE scene_message_recipient.message_uuid = message.uuid
+                        AND scene_message_recipient.delivery <> 'overheard'
+                        AND scene_message_recipient.handled_at IS NOT NULL
+                  )
+                ORDER BY message.sent_at DESC
+                LIMIT $2
+            "#,
+        )
+        .bind(scene_uuid.to_uuid())
+        .bind(limit)
+        .bind(registry::PROCESS_MESSAGE)
+        .fetch_all(&self.sqlx)
+        .await
+        .map_err(|err| format!("Error fetching pending messages: {}", err))?;
+
+        let mut messages = Vec::with_capacity(rows.len());
+
+        for row in rows {
+            let uuid = row
+                .try_get::<Uuid, _>("uuid")
+                .map_err(|err| format!("Error reading message uuid: {}", err))?;
+            let content = row
+                .try_get::<String, _>("content")
+                .map_err(|err| format!("Error reading content: {}", err))?;
+            let sent_at = row
+                .try_get::<DateTime<Utc>, _>("sent_at")
+                .map_err(|err| format!("Error reading sent_at: {}", err))?;
+
+            messages.push(Message {
+                uuid: MessageUuid::from_uuid(uuid),
+                sender: MessageSender::RealWorldUser,
+                scene_uuid: scene_uuid.clone(),
+                content,
+                sent_at,
+            });
+        }
+
+        Ok(messages)
+    }
+
+    async fn get_superseded_message_uuids(
+        &self,
+        message_uuids: Vec<MessageUuid>,
+    ) -> Result<HashSet<MessageUuid>, String> {
+        if message_uuids.is_empty() {
+            return Ok(HashSet::new());
+        }
+
+        let ids = message_uuids
+            .iter()
+            .map(|message_uuid| message_uuid.to_uuid())
+            .collect::<Vec<_>>();
+
+        let rows = sqlx::query(
+            r#"
+                SELECT uuid
+                FROM message
+                WHERE uuid = ANY($1::UUID[])
+                  AND superseded_at IS NOT NULL
+            "#,
+        )
+        .bind(ids)
+        .fetch_all(&self.sqlx)
+        .await
+        .map_err(|err| format!("Error fetching superseded messages: {}", err))?;
+
+        let mut superseded = HashSet::with_capacity(rows.len());
+
+        for row in rows {
+            let uuid = row
+                .try_get::<Uuid, _>("uuid")
+                .map_err(|err| format!("Error reading message uuid: {}", err))?;
+            superseded.insert(MessageUuid::from_uuid(uuid));
+        }
+
+        Ok(superseded)
+    }
+}
@@ -0,0 +1,182 @@
+use crate::capability::guardrail::GuardrailCapability;
+use crate::capability::moderation::{BlockedContent, ModerationCapability};
+use crate::capability::scene_tone::SceneToneCapability;
+use crate::domain::guardrail;
+use crate::domain::message::MessageSender;
+use crate::domain::moderation::ModerationVerdict;
+use crate::domain::scene_uuid::SceneUuid;
+use crate::domain::utterance::REAL_WORLD_USER_NAME;
+use crate::nice_display::NiceDisplay;
+use crate::open_ai::moderation::ModerationRequest;
+use crate::worker::Worker;
+use chrono::{DateTime, Utc};
+use sqlx::Row;
+use uuid::Uuid;
+
+impl ModerationCapability for Worker {
+    async fn moderate_content(
+        &self,
+        sender: &MessageSender,
+        scene_uuid: &SceneUuid,
+        content: &str,
+    ) -> Result<ModerationVerdict, String> {
+        // Only the simulated persons are held to the world's guardrails
+        let refused_topics = match sender {
+            MessageSender::AiPerson(_) => self
+                .get_guardrail_settings()
+                .await?
+                .refused_topics_in(content)?,
+            MessageSender::RealWorldUser => Vec::new(),
+        };
+
+        let verdict = if refused_topics.is_empty() {
+            let threshold = self.get_moderation_threshold().await?;
+            let tone_profile = self.get_scene_tone_profile(scene_uuid).await?;
+
+            let open_ai_key = self.require_open_ai_key().map_err(|err| err.message())?;
+
+            let result = ModerationRequest::new(content.to_string())
+                .send(open_ai_key, self.open_ai_client.clone())
+                .await
+                .map_err(|err| err.message())?;
+
+            match tone_profile {
+                Some(tone_profile) => {
+                    ModerationVerdict::from_result_by_category(&result, |category| {
+                        tone_profile.moderation_threshold(category, threshold)
+                    })
+                }
+                None => ModerationVerdict::from_result(&result, threshold),
+            }
+        } else {
+            ModerationVerdict::Blocked {
+                categories: refused_topics
+                    .iter()
+                    .map(|topic| guardrail::blocked_category(topic))
+                    .collect(),
+            }
+        };
+
+        if let ModerationVerdict::Blocked { categories } = &verdict {
+            let sender_person_uuid = match sender {
+                MessageSender::AiPerson(person_uuid) => Some(person_uuid.to_uuid()),
+                MessageSender::RealWorldUser => None,
+            };
+
+            sqlx::query(
+                r#"
+                    INSERT INTO blocked_content (uuid, sender_person_uuid, content, categories)
+                    VALUES ($1::UUID, $2::UUID, $3::TEXT, $4::TEXT[]);
+                "#,
+            )
+            .bind(Uuid::now_v7())
+            .bind(sender_person_uuid)
+            .bind(content)
+            .bind(categories)
+            .execute(&self.sqlx)
+            .await
+            .map_err(|err| format!("Error inserting blocked content: {}", err))?;
+        }
+
+        Ok(verdict)
+    }
+
+    async fn get_blocked_content(&self, limit: i64) -> Result<Vec<BlockedContent>, String> {
+        let rows = sqlx::query(
+            r#"
+                SELECT
+                    p.name AS person_name,
+                    bc.content,
+                    bc.categories,
+                    bc.created_at
+                FROM blocked_content bc
+                LEFT JOIN person p ON p.uuid = bc.sender_person_uuid
+                ORDER BY bc.created_at DESC
+                LIMIT $1;
+            "#,
+        )
+        .bind(limit)
+        .fetch_all(&self.sqlx)
+        .await
+        .map_err(|err| format!("Error fetching blocked content: {}", err))?;
+
+        let mut blocked = Vec::with_capacity(rows.len());
+        for row in rows {
+            let person_name = row
+                .try_get::<Option<String>, _>("person_name")
+                .map_err(|err| format!("Error reading person_name from row: {}", err))?;
+
+            let content = row
+                .try_get::<String, _>("content")
+                .map_err(|err| format!("Error reading content from row: {}", err))?;
+
+            let categories = row
+                .try_get::<Vec<String>, _>("categories")
+                .map_err(|err| format!("Error reading categories from row: {}", err))?;
+
+            let created_at = row
+                .try_get::<DateTime<Utc>, _>("created_at")
+                .map_err(|err| format!("Error reading created_at from row: {}", err))?;
+
+            let sender_label = match person_name {
+                Some(name) => name,
+                None => REAL_WORLD_USER_NAME.to_string(),
+            };
+
+            blocked.push(BlockedContent {
+                sender_label,
+                content,
+                categories,
+                created_at,
+            });
+        }
+
+        Ok(blocked)
+    }
+
+    async fn get_moderation_threshold(&self) -> Result<f64, String> {
+        let row = sqlx::query(
+            r#"
+                SELECT threshold
+                FROM moderation_setting
+                WHERE id = TRUE;
+            "#,
+        )
+        .fetch_optional(&self.sqlx)
+        .await
+        .map_err(|err| format!("Error fetching moderation threshold: {}", err))?;
+
+        let row = match row {
+            Some(row) => row,
+            None => {
+                return Err("Moderation threshold is missing from moderation_setting".to_string());
+            }
+        };
+
+        row.try_get::<f64, _>("threshold")
+            .map_err(|err| format!("Error reading moderation threshold: {}", err))
+    }
+
+    async fn set_moderation_threshold(&self, threshold: f64) -> Result<(), String> {
+        if !(0.0..=1.0).contains(&threshold) {
+            return Err(format!(
+                "Moderation threshold must be between 0 and 1, got {}",
+                threshold
+            ));
+        }
+
+        sqlx::query(
+            r#"
+                UPDATE moderation_setting
+                SET threshold = $1
+                WHERE id = TRUE;
+            "#,
+        )
+        .bind(threshold)
+        .execute(&self.sqlx)
+        .await
+        .map_err(|err| format!("Error updating moderation threshold: {}", err))?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,295 @@
+use crate::capability::clock::ClockCapability;
+use crate::capability::outbox::OutboxCapability;
+use crate::domain::delivery::{DeliveryStatus, WEBHOOK_INTEGRATION};
+use crate::domain::job::dispatch_outbox::DispatchOutboxJob;
+use crate::domain::job::JobKind;
+use crate::domain::job_uuid::JobUuid;
+use crate::domain::outbox::{OutboxEntry, OutboxEvent, WEBHOOK_URL_VAR};
+use crate::domain::outbox_uuid::OutboxUuid;
+use crate::worker::Worker;
+use chrono::{DateTime, Utc};
+use sqlx::postgres::PgRow;
+use sqlx::{PgConnection, Row};
+use std::time::Duration;
+
+const WEBHOOK_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Writes an outbox entry on the connection of the transaction making the
+/// change, and makes sure a dispatcher job is waiting to deliver it.
+pub(super) async fn write_outbox_event(
+    connection: &mut PgConnection,
+    event: &OutboxEvent,
+) -> Result<(), String> {
+    sqlx::query(
+        r#"
+            INSERT INTO outbox (uuid, event_name, payload)
+            VALUES ($1::UUID, $2::TEXT, $3::JSONB)
+        "#,
+    )
+    .bind(OutboxUuid::new().to_uuid())
+    .bind(event.to_name())
+    .bind(event.to_payload())
+    .execute(&mut *connection)
+    .await
+    .map_err(|err| format!("Error inserting outbox entry: {}", err))?;
+
+    let dispatch_job = JobKind::DispatchOutbox(DispatchOutboxJob::now());
+
+    sqlx::query(
+        r#"
+            INSERT INTO job (uuid, name, data, lock_key)
+            SELECT $1::UUID, $2::TEXT, $3::JSONB, $4::TEXT
+            WHERE NOT EXISTS (
+                SELECT 1
+                FROM job
+                WHERE name = $2::TEXT
+                  AND started_at IS NULL
+                  AND finished_at IS NULL
+                  AND deleted_at IS NULL
+            )
+        "#,
+    )
+    .bind(JobUuid::new().to_uuid())
+    .bind(dispatch_job.to_name())
+    .bind(dispatch_job.to_data()?)
+    .bind(dispatch_job.lock_key())
+    .execute(&mut *connection)
+    .await
+    .map_err(|err| format!("Error enqueueing outbox dispatch job: {}", err))?;
+
+    Ok(())
+}
+
+/// Every attempt at the webhook gets a delivery row, so failures stay
+/// visible after the entry finally gets through.
+async fn record_delivery(
+    connection: &mut PgConnection,
+    outbox_uuid: &OutboxUuid,
+    status: DeliveryStatus,
+    details: Option<&str>,
+) -> Result<(), String> {
+    sqlx::query(
+        r#"
+            INSERT INTO delivery (uuid, outbox_uuid, integration, status, details)
+            VALUES ($1::UUID, $2::UUID, $3::TEXT, $4::TEXT, $5::TEXT)
+        "#,
+    )
+    .bind(uuid::Uuid::now_v7())
+    .bind(outbox_uuid.to_uuid())
+    .bind(WEBHOOK_INTEGRATION)
+    .bind(status.to_name())
+    .bind(details)
+    .execute(connection)
+    .await
+    .map_err(|err| format!("Error recording delivery: {}", err))?;
+
+    Ok(())
+}
+
+/// Reads a row selecting `uuid, event_name, payload, attempts, created_at`
+/// from the outbox.
+pub(super) fn read_outbox_entry(row: &PgRow) -> Result<OutboxEntry, String> {
+    Ok(OutboxEntry {
+        uuid: OutboxUuid::from_uuid(
+            row.try_get::<uuid::Uuid, _>("uuid")
+                .map_err(|err| format!("Error reading outbox uuid: {}", err))?,
+        ),
+        event_name: row
+            .try_get::<String, _>("event_name")
+            .map_err(|err| format!("Error reading outbox event name: {}", err))?,
+        payload: row
+            .try_get::<serde_json::Value, _>("payload")
+            .map_err(|err| format!("Error reading outbox payload: {}", err))?,
+        attempts: row
+            .try_get::<i32, _>("attempts")
+            .map_err(|err| format!("Error reading outbox attempts: {}", err))?,
+        created_at: row
+            .try_get::<DateTime<Utc>, _>("created_at")
+            .map_err(|err| format!("Error reading outbox created_at: {}", err))?,
+    })
+}
+
+impl OutboxCapability for Worker {
+    fn get_outbox_webhook_url(&self) -> Option<String> {
+        match dotenv::var(WEBHOOK_URL_VAR) {
+            Ok(url) if !url.trim().is_empty() => Some(url.trim().to_string()),
+            _ => None,
+        }
+    }
+
+    async fn add_outbox_event(&self, event: &OutboxEvent) -> Result<(), String> {
+        let mut connection = self
+            .sqlx
+            .acquire()
+            .await
+            .map_err(|err| format!("Error acquiring a connection for the outbox: {}", err))?;
+
+        write_outbox_event(&mut connection, event).await
+    }
+
+    async fn claim_due_outbox_entries(&self, limit: i64) -> Result<Vec<OutboxEntry>, String> {
+        let rows = sqlx::query(
+            r#"
+                UPDATE outbox
+                SET next_attempt_at = $2::TIMESTAMPTZ + INTERVAL '5 minutes'
+                WHERE uuid IN (
+                    SELECT uuid
+                    FROM outbox
+                    WHERE delivered_at IS NULL
+                      AND next_attempt_at <= $2::TIMESTAMPTZ
+                    ORDER BY created_at ASC
+                    LIMIT $1
+                    FOR UPDATE SKIP LOCKED
+                )
+                RETURNING uuid, event_name, payload, attempts, created_at
+            "#,
+        )
+        .bind(limit)
+        .bind(self.now())
+        .fetch_all(&self.sqlx)
+        .await
+        .map_err(|err| format!("Error claiming outbox entries: {}", err))?;
+
+        let mut entries = rows
+            .iter()
+            .map(read_outbox_entry)
+            .collect::<Result<Vec<OutboxEntry>, String>>()?;
+
+        // RETURNING does not keep the order of the subquery
+        entries.sort_by_key(|entry| entry.created_at);
+
+        Ok(entries)
+    }
+
+    async fn post_outbox_entry(
+        &self,
+        webhook_url: &str,
+        entry: &OutboxEntry,
+    ) -> Result<(), String> {
+        let client = reqwest::Client::builder()
+            .timeout(WEBHOOK_TIMEOUT)
+            .build()
+            .map_err(|err| format!("Error building webhook client: {}", err))?;
+
+        let response = client
+            .post(webhook_url)
+            .header("Content-Type", "application/json")
+            .header("Idempotency-Key", entry.uuid.to_uuid().to_string())
+            .json(&entry.to_webhook_body())
+            .send()
+            .await
+            .map_err(|err| format!("Error posting to webhook: {}", err))?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let body = match response.text().await {
+                Ok(body) => body,
+                Err(err) => format!("(could not read the response body: {})", err),
+            };
+            return Err(format!("Webhook responded with {}: {}", status, body));
+        }
+
+        Ok(())
+    }
+
+    async fn mark_outbox_entry_delivered(&self, outbox_uuid: &OutboxUuid) -> Result<(), String> {
+        let mut transaction = self
+            .sqlx
+            .begin()
+            .await
+            .map_err(|err| format!("Error starting outbox delivery transaction: {}", err))?;
+
+        let row = sqlx::query(
+            r#"
+                UPDATE outbox
+                SET delivered_at = $2::TIMESTAMPTZ,
+                    attempts = attempts + 1,
+                    last_error = NULL
+                WHERE uuid = $1::UUID
+                RETURNING attempts
+            "#,
+        )
+        .bind(outbox_uuid.to_uuid())
+        .bind(self.now())
+        .fetch_one(&mut *transaction)
+        .await
+        .map_err(|err| format!("Error marking outbox entry delivered: {}", err))?;
+
+        let attempts = row
+            .try_get::<i32, _>("attempts")
+            .map_err(|err| format!("Error reading outbox attempts: {}", err))?;
+
+        record_delivery(
+            &mut transaction,
+            outbox_uuid,
+            DeliveryStatus::for_success(attempts),
+            None,
+        )
+        .await?;
+
+        transaction
+            .commit()
+            .await
+            .map_err(|err| format!("Error committing outbox delivery: {}", err))
+    }
+
+    async fn mark_outbox_entry_failed(
+        &self,
+        outbox_uuid: &OutboxUuid,
+        details: &str,
+        retry_at: DateTime<Utc>,
+    ) -> Result<(), String> {
+        let mut transaction = self
+            .sqlx
+            .begin()
+            .await
+            .map_err(|err| format!("Error starting outbox failure transaction: {}", err))?;
+
+        sqlx::query(
+            r#"
+                UPDATE outbox
+                SET attempts = attempts + 1,
+                    last_error = $2::TEXT,
+                    next_attempt_at = $3::TIMESTAMPTZ
+                WHERE uuid = $1::UUID
+            "#,
+        )
+        .bind(outbox_uuid.to_uuid())
+        .bind(details)
+        .bind(retry_at)
+        .execute(&mut *transaction)
+        .await
+        .map_err(|err| format!("Error marking outbox entry failed: {}", err))?;
+
+        record_delivery(
+            &mut transaction,
+            outbox_uuid,
+            DeliveryStatus::Failed,
+            Some(details),
+        )
+        .await?;
+
+        transaction
+            .commit()
+            .await
+            .map_err(|err| format!("Error committing outbox failure: {}", err))
+    }
+
+    async fn has_undelivered_outbox_entries(&self) -> Result<bool, String> {
+        let row = sqlx::query(
+            r#"
+                SELECT EXISTS (
+                    SELECT 1
+                    FROM outbox
+                    WHERE delivered_at IS NULL
+                ) AS has_undelivered
+            "#,
+        )
+        .fetch_one(&self.sqlx)
+        .await
+        .map_err(|err| format!("Error checking for undelivered outbox entries: {}", err))?;
+
+        row.try_get::<bool, _>("has_undelivered")
+            .map_err(|err| format!("Error reading undelivered outbox flag: {}", err))
+    }
+}
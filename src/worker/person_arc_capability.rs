@@ -0,0 +1,151 @@
+use crate::capability::person_arc::{NewPersonArc, PersonArcCapability};
+use crate::domain::autobiography::LifeEvent;
+use crate::domain::person_arc::{self, ArcDraft, PersonArc};
+use crate::domain::person_uuid::PersonUuid;
+use crate::nice_display::NiceDisplay;
+use crate::open_ai::completion::Completion;
+use crate::open_ai::role::Role;
+use crate::worker::Worker;
+use chrono::{DateTime, Utc};
+use sqlx::Row;
+use uuid::Uuid;
+
+impl PersonArcCapability for Worker {
+    async fn create_person_arc(&self, new_person_arc: NewPersonArc) -> Result<(), String> {
+        sqlx::query(
+            r#"
+                INSERT INTO person_arc (
+                    uuid,
+                    person_uuid,
+                    storyline,
+                    stakes,
+                    progress_notes,
+                    written_at_active_ms
+                )
+                VALUES ($1::UUID, $2::UUID, $3::TEXT, $4::TEXT, $5::TEXT, $6::BIGINT);
+            "#,
+        )
+        .bind(Uuid::now_v7())
+        .bind(new_person_arc.person_uuid.to_uuid())
+        .bind(new_person_arc.draft.storyline.trim())
+        .bind(new_person_arc.draft.stakes.trim())
+        .bind(new_person_arc.draft.progress_notes.trim())
+        .bind(new_person_arc.written_at_active_ms)
+        .execute(&self.sqlx)
+        .await
+        .map_err(|err| format!("Error creating person arc: {}", err))?;
+
+        Ok(())
+    }
+
+    async fn get_latest_person_arc(
+        &self,
+        person_uuid: &PersonUuid,
+    ) -> Result<Option<PersonArc>, String> {
+        let maybe_row = sqlx::query(
+            r#"
+                SELECT storyline, stakes, progress_notes, created_at
+                FROM person_arc
+                WHERE person_uuid = $1::UUID
+                ORDER BY created_at DESC
+                LIMIT 1;
+            "#,
+        )
+        .bind(person_uuid.to_uuid())
+        .fetch_optional(&self.sqlx)
+        .await
+        .map_err(|err| format!("Error fetching person arc: {}", err))?;
+
+        let row = match maybe_row {
+            Some(row) => row,
+            None => return Ok(None),
+        };
+
+        Ok(Some(PersonArc {
+            storyline: row
+                .try_get::<String, _>("storyline")
+                .map_err(|err| format!("Error reading arc storyline: {}", err))?,
+            stakes: row
+                .try_get::<String, _>("stakes")
+                .map_err(|err| format!("Error reading arc stakes: {}", err))?,
+            progress_notes: row
+                .try_get::<String, _>("progress_notes")
+                .map_err(|err| format!("Error reading arc progress notes: {}", err))?,
+            created_at: row
+                .try_get::<DateTime<Utc>, _>("created_at")
+                .map_err(|err| format!("Error reading arc time: {}", err))?,
+        }))
+    }
+
+    async fn get_persons_due_an_arc(
+        &self,
+        written_before_active_ms: i64,
+        limit: i64,
+    ) -> Result<Vec<PersonUuid>, String> {
+        let rows = sqlx::query(
+            r#"
+                SELECT person.uuid
+                FROM person
+                LEFT JOIN LATERAL (
+                    SELECT written_at_active_ms, created_at
+                    FROM person_arc
+                    WHERE person_arc.person_uuid = person.uuid
+                    ORDER BY created_at DESC
+                    LIMIT 1
+                ) latest_arc ON TRUE
+                WHERE person.is_enabled
+                  AND NOT person.is_hibernating
+                  AND person.archived_at IS NULL
+                  AND (
+                    latest_arc.written_at_active_ms IS NULL
+                    OR latest_arc.written_at_active_ms <= $1::BIGINT
+                  )
+                  AND EXISTS (
+                    SELECT 1
+                    FROM memory
+                    WHERE memory.person_uuid = person.uuid
+                      AND memory.created_at > COALESCE(latest_arc.created_at, '-infinity')
+                  )
+                ORDER BY latest_arc.written_at_active_ms ASC NULLS FIRST, person.uuid
+                LIMIT $2::BIGINT;
+            "#,
+        )
+        .bind(written_before_active_ms)
+        .bind(limit)
+        .fetch_all(&self.sqlx)
+        .await
+        .map_err(|err| format!("Error fetching persons due an arc: {}", err))?;
+
+        rows.iter()
+            .map(|row| {
+                row.try_get::<Uuid, _>("uuid")
+                    .map(PersonUuid::from_uuid)
+                    .map_err(|err| format!("Error reading person uuid: {}", err))
+            })
+            .collect()
+    }
+
+    async fn write_person_arc(
+        &self,
+        person_name: &str,
+        previous_arc: Option<&PersonArc>,
+        events: &[LifeEvent],
+    ) -> Result<ArcDraft, String> {
+        let mut completion = Completion::new();
+        completion.set_json_mode(true);
+        completion.add_message(Role::System, person_arc::system_prompt());
+        completion.add_message(
+            Role::User,
+            person_arc::update_prompt(person_name, previous_arc, events).as_str(),
+        );
+
+        let response = completion
+            .send_request(self.open_ai_key.as_ref(), self.open_ai_client.clone())
+            .await
+            .map_err(|err| err.message())?;
+
+        let json = response.as_message().map_err(|err| err.message())?;
+
+        ArcDraft::parse(json.as_str())
+    }
+}
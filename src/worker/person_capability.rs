@@ -1,3 +1,4 @@
+use crate::capability::clock::ClockCapability;
 use crate::capability::person::{NewPerson, PersonCapability};
 use crate::domain::person_name::PersonName;
 use crate::domain::person_uuid::PersonUuid;
@@ -157,4 +158,30 @@ impl PersonCapability for Worker {
             None => Err(format!("Person {} not found", person_uuid.to_uuid())),
         }
     }
+
+    async fn archive_person(&self, person_uuid: &PersonUuid) -> Result<(), String> {
+        sqlx::query(
+            r#"
+                WITH archived AS (
+                    UPDATE person
+                    SET is_enabled = FALSE,
+                        archived_at = $2::TIMESTAMPTZ
+                    WHERE uuid = $1::UUID
+                      AND archived_at IS NULL
+                    RETURNING uuid
+                )
+                UPDATE scene_participant
+                SET left_at = $2::TIMESTAMPTZ
+                WHERE person_uuid IN (SELECT uuid FROM archived)
+                  AND left_at IS NULL;
+            "#,
+        )
+        .bind(person_uuid.to_uuid())
+        .bind(self.now())
+        .execute(&self.sqlx)
+        .await
+        .map_err(|err| format!("Error archiving person: {}", err))?;
+
+        Ok(())
+    }
 }
@@ -1,12 +1,15 @@
 use async_trait::async_trait;
 
 use crate::capability::person_identity::{NewPersonIdentity, PersonIdentityCapability};
+use crate::domain::person_directory::PersonDirectoryEntry;
 use crate::domain::person_identity_uuid::PersonIdentityUuid;
 use crate::domain::person_uuid::PersonUuid;
 use crate::nice_display::NiceDisplay;
 use crate::open_ai::completion::Completion;
 use crate::open_ai::role::Role;
 use crate::worker::Worker;
+use sqlx::Row;
+use uuid::Uuid;
 
 #[async_trait]
 impl PersonIdentityCapability for Worker {
@@ -15,22 +18,8 @@ impl PersonIdentityCapability for Worker {
         person_name: &str,
         identity: &str,
     ) -> Result<String, String> {
-        let mut completion = Completion::new();
-        completion.add_message(
-            Role::System,
-            "Summarize this description of a person's identity in no more than three sentences.",
-        );
-        completion.add_message(
-            Role::User,
-            format!(
-                "Person name: {}\n\nIdentity text:\n{}",
-                person_name, identity
-            )
-            .as_str(),
-        );
-
-        let response = completion
-            .send_request(&self.open_ai_key, self.reqwest_client.clone())
+        let response = identity_summary_completion(person_name, identity)
+            .send_request(self.open_ai_key.as_ref(), self.open_ai_client.clone())
             .await
             .map_err(|err| err.message())?;
 
@@ -110,4 +99,124 @@ impl PersonIdentityCapability for Worker {
 
         Ok(rec.and_then(|r| r.summary))
     }
+
+    async fn set_person_identity_summary(
+        &self,
+        person_identity_uuid: &PersonIdentityUuid,
+        summary: &str,
+    ) -> Result<(), String> {
+        sqlx::query(
+            r#"
+                UPDATE person_identity
+                SET summary = $2::TEXT
+                WHERE uuid = $1::UUID;
+            "#,
+        )
+        .bind(person_identity_uuid.to_uuid())
+        .bind(summary)
+        .execute(&self.sqlx)
+        .await
+        .map_err(|err| format!("Error updating person identity summary: {}", err))?;
+
+        Ok(())
+    }
+
+    async fn get_person_directory(
+        &self,
+        viewer: &PersonUuid,
+        others: &[PersonUuid],
+    ) -> Result<Vec<PersonDirectoryEntry>, String> {
+        if others.is_empty() {
+            return Ok(vec![]);
+        }
+
+        // Having met before means sharing a scene at the same time, other
+        // than the stint they are both in right now
+        let rows = sqlx::query(
+            r#"
+                SELECT
+                    person.name,
+                    (
+                        SELECT person_identity.summary
+                        FROM person_identity
+                        WHERE person_identity.person_uuid = person.uuid
+                        ORDER BY person_identity.created_at DESC
+                        LIMIT 1
+                    ) AS identity_summary,
+                    (
+                        SELECT person_relationship.description
+                        FROM person_relationship
+                        WHERE person_relationship.person_uuid = $1::UUID
+                          AND person_relationship.other_person_uuid = person.uuid
+                    ) AS relationship,
+                    EXISTS (
+                        SELECT 1
+                        FROM scene_participant AS mine
+                        JOIN scene_participant AS theirs
+                          ON theirs.scene_uuid = mine.scene_uuid
+                        WHERE mine.person_uuid = $1::UUID
+                          AND theirs.person_uuid = person.uuid
+                          AND (mine.left_at IS NOT NULL OR theirs.left_at IS NOT NULL)
+                          AND mine.joined_at < COALESCE(theirs.left_at, NOW())
+                          AND theirs.joined_at < COALESCE(mine.left_at, NOW())
+                    ) AS has_met_before
+                FROM person
+                WHERE person.uuid = ANY($2::UUID[])
+                ORDER BY person.name ASC;
+            "#,
+        )
+        .bind(viewer.to_uuid())
+        .bind(
+            others
+                .iter()
+                .map(|person_uuid| person_uuid.to_uuid())
+                .collect::<Vec<Uuid>>(),
+        )
+        .fetch_all(&self.sqlx)
+        .await
+        .map_err(|err| format!("Error getting person directory: {}", err))?;
+
+        rows.into_iter()
+            .map(|row| {
+                let name: String = row
+                    .try_get("name")
+                    .map_err(|err| format!("Error reading name from row: {}", err))?;
+                let identity_summary: Option<String> = row
+                    .try_get("identity_summary")
+                    .map_err(|err| format!("Error reading identity_summary from row: {}", err))?;
+                let relationship: Option<String> = row
+                    .try_get("relationship")
+                    .map_err(|err| format!("Error reading relationship from row: {}", err))?;
+                let has_met_before: bool = row
+                    .try_get("has_met_before")
+                    .map_err(|err| format!("Error reading has_met_before from row: {}", err))?;
+
+                Ok(PersonDirectoryEntry {
+                    name,
+                    identity_summary,
+                    relationship,
+                    has_met_before,
+                })
+            })
+            .collect()
+    }
+}
+
+/// The completion that summarizes an identity, shared by the direct call and
+/// the Batch API path.
+pub fn identity_summary_completion(person_name: &str, identity: &str) -> Completion {
+    let mut completion = Completion::new();
+    completion.add_message(
+        Role::System,
+        "Summarize this description of a person's identity in no more than three sentences.",
+    );
+    completion.add_message(
+        Role::User,
+        format!(
+            "Person name: {}\n\nIdentity text:\n{}",
+            person_name, identity
+        )
+        .as_str(),
+    );
+    completion
 }
@@ -0,0 +1,273 @@
+use crate::capability::person_merge::PersonMergeCapability;
+use crate::domain::person_identity_uuid::PersonIdentityUuid;
+use crate::domain::person_merge::{self, PersonMergeCounts};
+use crate::domain::person_uuid::PersonUuid;
+use crate::domain::state_of_mind_uuid::StateOfMindUuid;
+use crate::worker::Worker;
+use sqlx::{Postgres, Row, Transaction};
+
+impl PersonMergeCapability for Worker {
+    async fn get_person_merge_counts(
+        &self,
+        keep: &PersonUuid,
+        merge: &PersonUuid,
+    ) -> Result<PersonMergeCounts, String> {
+        person_merge::validate(keep, merge)?;
+
+        let row = sqlx::query(
+            r#"
+                SELECT
+                    (SELECT COUNT(*) FROM message WHERE sender_person_uuid = $2::UUID) AS messages,
+                    (SELECT COUNT(*) FROM memory WHERE person_uuid = $2::UUID) AS memories,
+                    (SELECT COUNT(*) FROM person_identity WHERE person_uuid = $2::UUID) AS identities,
+                    (SELECT COUNT(*) FROM state_of_mind WHERE person_uuid = $2::UUID) AS states_of_mind,
+                    (SELECT COUNT(*) FROM scene_participant WHERE person_uuid = $2::UUID) AS scene_participations,
+                    (
+                        SELECT COUNT(*)
+                        FROM scene_participant AS duplicate
+                        WHERE duplicate.person_uuid = $2::UUID
+                          AND duplicate.left_at IS NULL
+                          AND EXISTS (
+                              SELECT 1
+                              FROM scene_participant AS kept
+                              WHERE kept.person_uuid = $1::UUID
+                                AND kept.scene_uuid = duplicate.scene_uuid
+                                AND kept.left_at IS NULL
+                          )
+                    ) AS shared_scenes;
+            "#,
+        )
+        .bind(keep.to_uuid())
+        .bind(merge.to_uuid())
+        .fetch_one(&self.sqlx)
+        .await
+        .map_err(|err| format!("Error counting what a person merge would move: {}", err))?;
+
+        let count = |column: &str| -> Result<u64, String> {
+            row.try_get::<i64, _>(column)
+                .map(|count| count as u64)
+                .map_err(|err| format!("Error reading {} from row: {}", column, err))
+        };
+
+        Ok(PersonMergeCounts {
+            messages: count("messages")?,
+            memories: count("memories")?,
+            identities: count("identities")?,
+            states_of_mind: count("states_of_mind")?,
+            scene_participations: count("scene_participations")?,
+            shared_scenes: count("shared_scenes")?,
+        })
+    }
+
+    async fn merge_persons(
+        &self,
+        keep: &PersonUuid,
+        merge: &PersonUuid,
+    ) -> Result<PersonMergeCounts, String> {
+        person_merge::validate(keep, merge)?;
+
+        let mut transaction = self
+            .sqlx
+            .begin()
+            .await
+            .map_err(|err| format!("Error starting person merge transaction: {}", err))?;
+
+        let rows = sqlx::query(
+            r#"
+                SELECT uuid
+                FROM person
+                WHERE uuid IN ($1::UUID, $2::UUID)
+                  AND archived_at IS NULL
+                FOR UPDATE;
+            "#,
+        )
+        .bind(keep.to_uuid())
+        .bind(merge.to_uuid())
+        .fetch_all(&mut *transaction)
+        .await
+        .map_err(|err| format!("Error locking persons to merge: {}", err))?;
+
+        if rows.len() != 2 {
+            return Err("Both persons have to exist and not be archived".to_string());
+        }
+
+        // The newest identity and state of mind are the current ones, so a
+        // newer one from the duplicate would replace the kept person's. A
+        // fresh copy of the kept person's keeps them current.
+        sqlx::query(
+            r#"
+                INSERT INTO person_identity (uuid, person_uuid, identity, summary)
+                SELECT $3::UUID, kept.person_uuid, kept.identity, kept.summary
+                FROM (
+                    SELECT person_uuid, identity, summary, created_at
+                    FROM person_identity
+                    WHERE person_uuid = $1::UUID
+                    ORDER BY created_at DESC
+                    LIMIT 1
+                ) AS kept
+                WHERE EXISTS (
+                    SELECT 1
+                    FROM person_identity
+                    WHERE person_uuid = $2::UUID
+                      AND created_at > kept.created_at
+                );
+            "#,
+        )
+        .bind(keep.to_uuid())
+        .bind(merge.to_uuid())
+        .bind(PersonIdentityUuid::new().to_uuid())
+        .execute(&mut *transaction)
+        .await
+        .map_err(|err| format!("Error keeping the current identity: {}", err))?;
+
+        sqlx::query(
+            r#"
+                INSERT INTO state_of_mind (uuid, person_uuid, content)
+                SELECT $3::UUID, kept.person_uuid, kept.content
+                FROM (
+                    SELECT person_uuid, content, created_at
+                    FROM state_of_mind
+                    WHERE person_uuid = $1::UUID
+                    ORDER BY created_at DESC
+                    LIMIT 1
+                ) AS kept
+                WHERE EXISTS (
+                    SELECT 1
+                    FROM state_of_mind
+                    WHERE person_uuid = $2::UUID
+                      AND created_at > kept.created_at
+                );
+            "#,
+        )
+        .bind(keep.to_uuid())
+        .bind(merge.to_uuid())
+        .bind(StateOfMindUuid::new().to_uuid())
+        .execute(&mut *transaction)
+        .await
+        .map_err(|err| format!("Error keeping the current state of mind: {}", err))?;
+
+        let shared_scenes = sqlx::query(
+            r#"
+                UPDATE scene_participant AS duplicate
+                SET left_at = NOW()
+                WHERE duplicate.person_uuid = $2::UUID
+                  AND duplicate.left_at IS NULL
+                  AND EXISTS (
+                      SELECT 1
+                      FROM scene_participant AS kept
+                      WHERE kept.person_uuid = $1::UUID
+                        AND kept.scene_uuid = duplicate.scene_uuid
+                        AND kept.left_at IS NULL
+                  );
+            "#,
+        )
+        .bind(keep.to_uuid())
+        .bind(merge.to_uuid())
+        .execute(&mut *transaction)
+        .await
+        .map_err(|err| format!("Error leaving shared scenes: {}", err))?
+        .rows_affected();
+
+        // Memories about the duplicate are about the kept person now. Not
+        // `reassign`, since a memory can mention both.
+        sqlx::query(
+            r#"
+                WITH moved AS (
+                    DELETE FROM memory_person_mention
+                    WHERE person_uuid = $2::UUID
+                    RETURNING memory_uuid
+                )
+                INSERT INTO memory_person_mention (memory_uuid, person_uuid)
+                SELECT memory_uuid, $1::UUID
+                FROM moved
+                ON CONFLICT DO NOTHING;
+            "#,
+        )
+        .bind(keep.to_uuid())
+        .bind(merge.to_uuid())
+        .execute(&mut *transaction)
+        .await
+        .map_err(|err| format!("Error moving memory mentions: {}", err))?;
+
+        let counts = PersonMergeCounts {
+            messages: reassign(
+                &mut transaction,
+                "message",
+                "sender_person_uuid",
+                keep,
+                merge,
+            )
+            .await?,
+            memories: reassign(&mut transaction, "memory", "person_uuid", keep, merge).await?,
+            identities: reassign(
+                &mut transaction,
+                "person_identity",
+                "person_uuid",
+                keep,
+                merge,
+            )
+            .await?,
+            states_of_mind: reassign(
+                &mut transaction,
+                "state_of_mind",
+                "person_uuid",
+                keep,
+                merge,
+            )
+            .await?,
+            scene_participations: reassign(
+                &mut transaction,
+                "scene_participant",
+                "person_uuid",
+                keep,
+                merge,
+            )
+            .await?,
+            shared_scenes,
+        };
+
+        sqlx::query(
+            r#"
+                UPDATE person
+                SET is_enabled = FALSE,
+                    archived_at = NOW(),
+                    merged_into_uuid = $1::UUID
+                WHERE uuid = $2::UUID;
+            "#,
+        )
+        .bind(keep.to_uuid())
+        .bind(merge.to_uuid())
+        .execute(&mut *transaction)
+        .await
+        .map_err(|err| format!("Error archiving the merged person: {}", err))?;
+
+        transaction
+            .commit()
+            .await
+            .map_err(|err| format!("Error committing person merge transaction: {}", err))?;
+
+        Ok(counts)
+    }
+}
+
+/// `table` and `column` are always literals from `merge_persons`.
+async fn reassign(
+    transaction: &mut Transaction<'_, Postgres>,
+    table: &str,
+    column: &str,
+    keep: &PersonUuid,
+    merge: &PersonUuid,
+) -> Result<u64, String> {
+    let result = sqlx::query(&format!(
+        "UPDATE {table} SET {column} = $1::UUID WHERE {column} = $2::UUID",
+        table = table,
+        column = column
+    ))
+    .bind(keep.to_uuid())
+    .bind(merge.to_uuid())
+    .execute(&mut **transaction)
+    .await
+    .map_err(|err| format!("Error moving {} to the kept person: {}", table, err))?;
+
+    Ok(result.rows_affected())
+}
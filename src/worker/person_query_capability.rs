@@ -0,0 +1,156 @@
+use crate::capability::clock::ClockCapability;
+use crate::capability::person_query::{PersonQueryCapability, PersonSummary};
+use crate::domain::person_filter::PersonFilter;
+use crate::domain::person_name::PersonName;
+use crate::domain::person_uuid::PersonUuid;
+use crate::worker::Worker;
+use chrono::{DateTime, Utc};
+use sqlx::Row;
+use uuid::Uuid;
+
+impl PersonQueryCapability for Worker {
+    async fn query_persons(&self, filter: &PersonFilter) -> Result<Vec<PersonSummary>, String> {
+        // Each condition is skipped when its parameter is null, so one query
+        // covers every combination
+        let rows = sqlx::query(
+            r#"
+                SELECT
+                    person.uuid,
+                    person.name,
+                    person.is_enabled,
+                    person.is_hibernating,
+                    (
+                        SELECT scene.name
+                        FROM scene_participant
+                        JOIN scene ON scene.uuid = scene_participant.scene_uuid
+                        WHERE scene_participant.person_uuid = person.uuid
+                          AND scene_participant.left_at IS NULL
+                        ORDER BY scene_participant.joined_at DESC
+                        LIMIT 1
+                    ) AS scene_name,
+                    COALESCE(
+                        (
+                            SELECT ARRAY_AGG(person_tag.tag ORDER BY person_tag.tag)
+                            FROM person_tag
+                            WHERE person_tag.person_uuid = person.uuid
+                        ),
+                        '{}'
+                    ) AS tags,
+                    (
+                        SELECT MAX(reaction_history.created_at)
+                        FROM reaction_history
+                        WHERE reaction_history.person_uuid = person.uuid
+                    ) AS last_reacted_at,
+                    (
+                        SELECT COUNT(*)
+                        FROM scene_message_recipient
+                        WHERE scene_message_recipient.person_uuid = person.uuid
+                          AND scene_message_recipient.handled_at IS NULL
+                    ) AS unread_messages
+                FROM person
+                WHERE person.archived_at IS NULL
+                  AND (
+                    $1::UUID IS NULL
+                    OR EXISTS (
+                        SELECT 1
+                        FROM scene_participant
+                        WHERE scene_participant.person_uuid = person.uuid
+                          AND scene_participant.scene_uuid = $1::UUID
+                          AND scene_participant.left_at IS NULL
+                    )
+                )
+                  AND (
+                    $2::TEXT IS NULL
+                    OR EXISTS (
+                        SELECT 1
+                        FROM person_tag
+                        WHERE person_tag.person_uuid = person.uuid
+                          AND person_tag.tag = $2::TEXT
+                    )
+                )
+                  AND (
+                    $3::TIMESTAMPTZ IS NULL
+                    OR NOT EXISTS (
+                        SELECT 1
+                        FROM reaction_history
+                        WHERE reaction_history.person_uuid = person.uuid
+                          AND reaction_history.created_at >= $3::TIMESTAMPTZ
+                    )
+                )
+                  AND (
+                    NOT $4::BOOLEAN
+                    OR EXISTS (
+                        SELECT 1
+                        FROM scene_message_recipient
+                        WHERE scene_message_recipient.person_uuid = person.uuid
+                          AND scene_message_recipient.handled_at IS NULL
+                    )
+                )
+                  AND (
+                    $5::TEXT IS NULL
+                    OR EXISTS (
+                        SELECT 1
+                        FROM scene_participant
+                        JOIN topic ON topic.scene_uuid = scene_participant.scene_uuid
+                        WHERE scene_participant.person_uuid = person.uuid
+                          AND topic.topic = $5::TEXT
+                    )
+                )
+                ORDER BY person.name ASC;
+            "#,
+        )
+        .bind(
+            filter
+                .scene_uuid
+                .as_ref()
+                .map(|scene_uuid| scene_uuid.to_uuid()),
+        )
+        .bind(filter.tag.as_ref().map(|tag| tag.as_str()))
+        .bind(filter.idle_since(self.now()))
+        .bind(filter.has_unread_messages)
+        .bind(filter.topic.as_ref().map(|topic| topic.as_str()))
+        .fetch_all(&self.sqlx)
+        .await
+        .map_err(|err| format!("Error querying persons: {}", err))?;
+
+        rows.into_iter()
+            .map(|row| {
+                let person_uuid = row
+                    .try_get::<Uuid, _>("uuid")
+                    .map_err(|err| format!("Error reading uuid from row: {}", err))?;
+                let name = row
+                    .try_get::<String, _>("name")
+                    .map_err(|err| format!("Error reading name from row: {}", err))?;
+                let is_enabled = row
+                    .try_get::<bool, _>("is_enabled")
+                    .map_err(|err| format!("Error reading is_enabled from row: {}", err))?;
+                let is_hibernating = row
+                    .try_get::<bool, _>("is_hibernating")
+                    .map_err(|err| format!("Error reading is_hibernating from row: {}", err))?;
+                let scene_name = row
+                    .try_get::<Option<String>, _>("scene_name")
+                    .map_err(|err| format!("Error reading scene_name from row: {}", err))?;
+                let tags = row
+                    .try_get::<Vec<String>, _>("tags")
+                    .map_err(|err| format!("Error reading tags from row: {}", err))?;
+                let last_reacted_at = row
+                    .try_get::<Option<DateTime<Utc>>, _>("last_reacted_at")
+                    .map_err(|err| format!("Error reading last_reacted_at from row: {}", err))?;
+                let unread_messages = row
+                    .try_get::<i64, _>("unread_messages")
+                    .map_err(|err| format!("Error reading unread_messages from row: {}", err))?;
+
+                Ok(PersonSummary {
+                    person_uuid: PersonUuid::from_uuid(person_uuid),
+                    name: PersonName::from_string(name),
+                    is_enabled,
+                    is_hibernating,
+                    scene_name,
+                    tags,
+                    last_reacted_at,
+                    unread_messages,
+                })
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,48 @@
+use crate::capability::person_tag::PersonTagCapability;
+use crate::domain::person_filter::PersonTag;
+use crate::domain::person_uuid::PersonUuid;
+use crate::worker::Worker;
+
+impl PersonTagCapability for Worker {
+    async fn add_person_tag(
+        &self,
+        person_uuid: &PersonUuid,
+        tag: &PersonTag,
+    ) -> Result<(), String> {
+        sqlx::query(
+            r#"
+                INSERT INTO person_tag (person_uuid, tag)
+                VALUES ($1::UUID, $2::TEXT)
+                ON CONFLICT (person_uuid, tag) DO NOTHING;
+            "#,
+        )
+        .bind(person_uuid.to_uuid())
+        .bind(tag.as_str())
+        .execute(&self.sqlx)
+        .await
+        .map_err(|err| format!("Error adding person tag: {}", err))?;
+
+        Ok(())
+    }
+
+    async fn remove_person_tag(
+        &self,
+        person_uuid: &PersonUuid,
+        tag: &PersonTag,
+    ) -> Result<(), String> {
+        sqlx::query(
+            r#"
+                DELETE FROM person_tag
+                WHERE person_uuid = $1::UUID
+                  AND tag = $2::TEXT;
+            "#,
+        )
+        .bind(person_uuid.to_uuid())
+        .bind(tag.as_str())
+        .execute(&self.sqlx)
+        .await
+        .map_err(|err| format!("Error removing person tag: {}", err))?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,84 @@
+use crate::capability::persona::PersonaCapability;
+use crate::domain::cast::{self, Cast};
+use crate::domain::persona::{self, Persona};
+use crate::nice_display::NiceDisplay;
+use crate::open_ai::completion::Completion;
+use crate::open_ai::role::Role;
+use crate::worker::Worker;
+
+impl PersonaCapability for Worker {
+    async fn generate_persona(&self, concept: &str) -> Result<Persona, String> {
+        let concept = concept.trim();
+
+        if concept.is_empty() {
+            return Err("Persona concept cannot be blank".to_string());
+        }
+
+        let mut completion = Completion::new();
+        completion.add_tool_call(Persona::tool().into());
+        completion.add_message(
+            Role::System,
+            format!(
+                "You invent believable, specific people for a social simulation. Use only the provided tool call and call it exactly once. Give the person exactly {} seed memories. Avoid celebrities and real public figures.",
+                persona::SEED_MEMORY_COUNT
+            )
+            .as_str(),
+        );
+        completion.add_message(Role::User, format!("Concept: {}", concept).as_str());
+
+        let response = completion
+            .send_request(self.open_ai_key.as_ref(), self.open_ai_client.clone())
+            .await
+            .map_err(|err| err.message())?;
+
+        let tool_calls = response.as_tool_calls().map_err(|err| err.message())?;
+
+        let call = tool_calls
+            .iter()
+            .find(|call| call.name == persona::TOOL_NAME)
+            .ok_or_else(|| "The persona generator did not return a persona".to_string())?;
+
+        Persona::from_tool_call(call)
+    }
+
+    async fn generate_cast(&self, concept: &str, count: usize) -> Result<Cast, String> {
+        let concept = concept.trim();
+
+        if concept.is_empty() {
+            return Err("Cast concept cannot be blank".to_string());
+        }
+
+        if count == 0 {
+            return Err("Cast must have at least one person".to_string());
+        }
+
+        let mut completion = Completion::new();
+        completion.add_tool_call(Cast::tool(count).into());
+        completion.add_message(
+            Role::System,
+            format!(
+                "You invent believable, specific groups of people for a social simulation. Everyone in a cast lives in the same scenario, and most of them already know each other: family, coworkers, neighbors, rivals, old friends. Use only the provided tool call and call it exactly once. Give every person a unique name and exactly {} seed memories, and let some memories involve other people in the cast. Avoid celebrities and real public figures.",
+                persona::SEED_MEMORY_COUNT
+            )
+            .as_str(),
+        );
+        completion.add_message(
+            Role::User,
+            format!("Scenario: {}\nNumber of people: {}", concept, count).as_str(),
+        );
+
+        let response = completion
+            .send_request(self.open_ai_key.as_ref(), self.open_ai_client.clone())
+            .await
+            .map_err(|err| err.message())?;
+
+        let tool_calls = response.as_tool_calls().map_err(|err| err.message())?;
+
+        let call = tool_calls
+            .iter()
+            .find(|call| call.name == cast::TOOL_NAME)
+            .ok_or_else(|| "The cast generator did not return a cast".to_string())?;
+
+        Cast::from_tool_call(call)
+    }
+}
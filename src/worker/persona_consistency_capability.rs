@@ -0,0 +1,165 @@
+use crate::capability::persona_consistency::{
+    PersonaConsistencyCapability, RecordedPersonaInconsistency,
+};
+use crate::domain::person_uuid::PersonUuid;
+use crate::domain::persona_consistency::{self, PersonaInconsistency};
+use crate::nice_display::NiceDisplay;
+use crate::open_ai::completion::Completion;
+use crate::open_ai::role::Role;
+use crate::worker::Worker;
+use chrono::{DateTime, Utc};
+use sqlx::Row;
+use uuid::Uuid;
+
+impl PersonaConsistencyCapability for Worker {
+    async fn get_recent_utterances(
+        &self,
+        person_uuid: &PersonUuid,
+        limit: i64,
+    ) -> Result<Vec<String>, String> {
+        let rows = sqlx::query(
+            r#"
+                SELECT message_content.body AS content
+                FROM message
+                JOIN message_content ON message_content.hash = message.content_hash
+                WHERE message.sender_person_uuid = $1::UUID
+                  AND message.superseded_at IS NULL
+                ORDER BY message.sent_at DESC
+                LIMIT $2;
+            "#,
+        )
+        .bind(person_uuid.to_uuid())
+        .bind(limit)
+        .fetch_all(&self.sqlx)
+        .await
+        .map_err(|err| format!("Error fetching recent utterances: {}", err))?;
+
+        rows.into_iter()
+            .map(|row| {
+                row.try_get::<String, _>("content")
+                    .map_err(|err| format!("Error reading content from row: {}", err))
+            })
+            .collect()
+    }
+
+    async fn judge_persona_consistency(
+        &self,
+        identity: &str,
+        utterances: &[String],
+    ) -> Result<Vec<PersonaInconsistency>, String> {
+        if utterances.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let mut completion = Completion::new();
+        completion.add_tool_call(persona_consistency::tool().into());
+        completion.add_message(Role::System, persona_consistency::system_prompt());
+        completion.add_message(
+            Role::User,
+            persona_consistency::judge_prompt(identity, utterances).as_str(),
+        );
+
+        let response = completion
+            .send_request(self.open_ai_key.as_ref(), self.open_ai_client.clone())
+            .await
+            .map_err(|err| err.message())?;
+
+        let tool_calls = response.as_tool_calls().map_err(|err| err.message())?;
+
+        let call = tool_calls
+            .iter()
+            .find(|call| call.name == persona_consistency::TOOL_NAME)
+            .ok_or_else(|| "The consistency judge did not return a report".to_string())?;
+
+        persona_consistency::from_tool_call(call)
+    }
+
+    async fn record_persona_inconsistencies(
+        &self,
+        person_uuid: &PersonUuid,
+        inconsistencies: &[PersonaInconsistency],
+    ) -> Result<(), String> {
+        if inconsistencies.is_empty() {
+            return Ok(());
+        }
+
+        let mut uuids = Vec::with_capacity(inconsistencies.len());
+        let mut utterances = Vec::with_capacity(inconsistencies.len());
+        let mut identity_claims = Vec::with_capacity(inconsistencies.len());
+        let mut explanations = Vec::with_capacity(inconsistencies.len());
+
+        for inconsistency in inconsistencies {
+            uuids.push(Uuid::now_v7());
+            utterances.push(inconsistency.utterance.clone());
+            identity_claims.push(inconsistency.identity_claim.clone());
+            explanations.push(inconsistency.explanation.clone());
+        }
+
+        sqlx::query(
+            r#"
+                INSERT INTO persona_inconsistency
+                    (uuid, person_uuid, utterance, identity_claim, explanation)
+                SELECT entry.uuid, $1::UUID, entry.utterance, entry.identity_claim, entry.explanation
+                FROM UNNEST($2::UUID[], $3::TEXT[], $4::TEXT[], $5::TEXT[])
+                    AS entry (uuid, utterance, identity_claim, explanation);
+            "#,
+        )
+        .bind(person_uuid.to_uuid())
+        .bind(uuids)
+        .bind(utterances)
+        .bind(identity_claims)
+        .bind(explanations)
+        .execute(&self.sqlx)
+        .await
+        .map_err(|err| format!("Error recording persona inconsistencies: {}", err))?;
+
+        Ok(())
+    }
+
+    async fn get_persona_inconsistencies(
+        &self,
+        person_uuid: &PersonUuid,
+        limit: i64,
+    ) -> Result<Vec<RecordedPersonaInconsistency>, String> {
+        let rows = sqlx::query(
+            r#"
+                SELECT utterance, identity_claim, explanation, created_at
+                FROM persona_inconsistency
+                WHERE person_uuid = $1::UUID
+                ORDER BY created_at DESC
+                LIMIT $2;
+            "#,
+        )
+        .bind(person_uuid.to_uuid())
+        .bind(limit)
+        .fetch_all(&self.sqlx)
+        .await
+        .map_err(|err| format!("Error fetching persona inconsistencies: {}", err))?;
+
+        rows.into_iter()
+            .map(|row| {
+                let utterance = row
+                    .try_get::<String, _>("utterance")
+                    .map_err(|err| format!("Error reading utterance from row: {}", err))?;
+                let identity_claim = row
+                    .try_get::<String, _>("identity_claim")
+                    .map_err(|err| format!("Error reading identity_claim from row: {}", err))?;
+                let explanation = row
+                    .try_get::<String, _>("explanation")
+                    .map_err(|err| format!("Error reading explanation from row: {}", err))?;
+                let created_at = row
+                    .try_get::<DateTime<Utc>, _>("created_at")
+                    .map_err(|err| format!("Error reading created_at from row: {}", err))?;
+
+                Ok(RecordedPersonaInconsistency {
+                    inconsistency: PersonaInconsistency {
+                        utterance,
+                        identity_claim,
+                        explanation,
+                    },
+                    created_at,
+                })
+            })
+            .collect()
+    }
+}
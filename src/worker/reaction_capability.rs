@@ -1,14 +1,35 @@
+use crate::capability::fine_tune::FineTuneCapability;
+use crate::capability::guardrail::GuardrailCapability;
+use crate::capability::item::ItemCapability;
 use crate::capability::motivation::MotivationCapability;
 use crate::capability::person::PersonCapability;
+use crate::capability::person_arc::PersonArcCapability;
 use crate::capability::person_identity::PersonIdentityCapability;
 use crate::capability::person_task::{NewPersonTask, PersonTaskCapability};
-use crate::capability::reaction::{ReactionCapability, ReactionPromptPreview};
+use crate::capability::reaction::{
+    ContextFetchTiming, ContextTimings, ReactionCapability, ReactionPromptPreview,
+};
+use crate::capability::reaction_context::{NewReactionContext, ReactionContextCapability};
+use crate::capability::scene::SceneCapability;
+use crate::capability::scene_invitation::SceneInvitationCapability;
+use crate::capability::scene_tone::SceneToneCapability;
 use crate::capability::state_of_mind::StateOfMindCapability;
+use crate::capability::style_guide::StyleGuideCapability;
+use crate::capability::voice_exemplar::VoiceExemplarCapability;
+use crate::domain::action_budget::get_action_budget_usage;
+use crate::domain::guardrail::GuardrailSettings;
+use crate::domain::item::Item;
 use crate::domain::logger::Level;
 use crate::domain::memory::Memory;
 use crate::domain::motivation::Motivation;
+use crate::domain::person_arc::PersonArc;
+use crate::domain::person_name::PersonName;
 use crate::domain::person_task::{PersonTask, PersonTaskOutcomeCheck, PersonTaskTerminalOutcome};
 use crate::domain::person_uuid::PersonUuid;
+use crate::domain::scene_invitation::SceneInvitation;
+use crate::domain::scene_tone::ToneProfile;
+use crate::domain::style_guide::StyleGuide;
+use crate::domain::voice_exemplar;
 use crate::nice_display::NiceDisplay;
 use crate::open_ai::completion::{Completion, CompletionError};
 use crate::open_ai::role::Role;
@@ -19,6 +40,8 @@ use crate::person_actions::{
 };
 use crate::worker::Worker;
 use serde::Deserialize;
+use std::future::Future;
+use std::time::Instant;
 
 const INTERNAL_REACTION_PLACEHOLDER: &str = "<internal reaction text placeholder>";
 const REACTION_VALIDATION_RETRY_LIMIT: usize = 1;
@@ -26,7 +49,7 @@ const REACTION_VALIDATION_RETRY_LIMIT: usize = 1;
 pub enum Error {
     CompletionError(CompletionError),
     FailedToGetMotivations(String),
-    FailedToGetReactionDualLayer(String),
+    FailedToGetReactionContext(ReactionContextError),
     #[allow(dead_code)]
     FailedToInferPersonTaskToAdopt(String),
     FailedToClassifyTaskOutcome(String),
@@ -61,7 +84,7 @@ impl ReactionCapability for Worker {
         );
 
         let response = completion
-            .send_request(&self.open_ai_key, reqwest::Client::new())
+            .send_request(self.open_ai_key.as_ref(), self.open_ai_client.clone())
             .await
             .map_err(|err| err.message())?;
 
@@ -81,45 +104,38 @@ impl ReactionCapability for Worker {
         person_uuid: PersonUuid,
         situation: String,
     ) -> Result<ReactionPromptPreview, String> {
-        let person_name = self
-            .get_persons_name(person_uuid.clone())
-            .await
-            .map_err(|err| format!("Failed to get person's name: {}", err))?;
-
-        let motivations = self
-            .get_motivations_for_person(&person_uuid)
-            .await
-            .map_err(|err| format!("Failed to get motivations: {}", err))?;
-
-        let person_identity = get_person_identity_summary(self, &person_uuid).await?;
-
-        let state_of_mind = if let Some(som) = self
-            .get_latest_state_of_mind(&person_uuid)
-            .await
-            .map_err(|err| format!("Failed to get state of mind: {}", err))?
-        {
-            som
-        } else {
-            Err(format!(
-                "No state of mind found for person_uuid: {}",
-                person_uuid.to_uuid()
-            ))?
-        };
-
-        let current_person_task_text = get_current_person_task_text(self, &person_uuid)
-            .await
-            .map_err(|err| format!("Failed to get current person task: {}", err))?;
+        let started_at = Instant::now();
+
+        // The state of mind is only fetched here; reactions are handed theirs
+        let (mut context, (state_of_mind, state_of_mind_timing)) = tokio::try_join!(
+            async {
+                assemble_reaction_context(self, &person_uuid)
+                    .await
+                    .map_err(|err| err.message())
+            },
+            timed("state of mind", async {
+                match self
+                    .get_latest_state_of_mind(&person_uuid)
+                    .await
+                    .map_err(|err| format!("Failed to get state of mind: {}", err))?
+                {
+                    Some(som) => Ok(som),
+                    None => Err(format!(
+                        "No state of mind found for person_uuid: {}",
+                        person_uuid.to_uuid()
+                    )),
+                }
+            }),
+        )?;
+        context.timings.fetches.push(state_of_mind_timing);
+        context.timings.total = started_at.elapsed();
 
-        let prompts = build_prompts(
-            person_name.as_str(),
+        let mut prompts = build_prompts(&context.prompt_context(
             &memories,
-            &motivations,
-            person_identity.as_str(),
             state_of_mind.content.as_str(),
             situation.as_str(),
-            current_person_task_text.as_str(),
-            INTERNAL_REACTION_PLACEHOLDER,
-        );
+        ));
+        prompts.context_timings = context.timings;
         Ok(prompts)
     }
 
@@ -130,36 +146,28 @@ impl ReactionCapability for Worker {
         state_of_mind: String,
         situation: String,
     ) -> Result<PersonReaction, String> {
-        let person_identity = get_person_identity_summary(self, &person_uuid).await?;
-        get_reaction_helper(
-            self,
-            memories,
-            person_uuid,
-            person_identity,
-            state_of_mind,
-            situation,
-        )
-        .await
-        .map_err(|err| match err {
-            Error::CompletionError(completion_err) => completion_err.message(),
-            Error::FailedToGetMotivations(message) => message,
-            Error::FailedToGetReactionDualLayer(message) => message,
-            Error::FailedToInferPersonTaskToAdopt(message) => message,
-            Error::FailedToClassifyTaskOutcome(message) => message,
-            Error::FailedToInferTaskState(message) => message,
-            Error::InvalidTaskAdoptionToolCall(message) => message,
-            Error::InvalidTaskOutcomeToolCall(message) => message,
-            Error::InvalidTaskStateToolCall(message) => message,
-            Error::NoPersonActionFound => "No person action found".to_string(),
-            Error::MoreThanOnePersonActionFound(actions) => {
-                let actions_str = actions
-                    .into_iter()
-                    .map(|action| format!("{:?}", action))
-                    .collect::<Vec<String>>()
-                    .join(",\n");
-                format!("More than one person action found: \n{}", actions_str)
-            }
-        })
+        get_reaction_helper(self, memories, person_uuid, state_of_mind, situation)
+            .await
+            .map_err(|err| match err {
+                Error::CompletionError(completion_err) => completion_err.message(),
+                Error::FailedToGetMotivations(message) => message,
+                Error::FailedToGetReactionContext(context_err) => context_err.message(),
+                Error::FailedToInferPersonTaskToAdopt(message) => message,
+                Error::FailedToClassifyTaskOutcome(message) => message,
+                Error::FailedToInferTaskState(message) => message,
+                Error::InvalidTaskAdoptionToolCall(message) => message,
+                Error::InvalidTaskOutcomeToolCall(message) => message,
+                Error::InvalidTaskStateToolCall(message) => message,
+                Error::NoPersonActionFound => "No person action found".to_string(),
+                Error::MoreThanOnePersonActionFound(actions) => {
+                    let actions_str = actions
+                        .into_iter()
+                        .map(|action| format!("{:?}", action))
+                        .collect::<Vec<String>>()
+                        .join(",\n");
+                    format!("More than one person action found: \n{}", actions_str)
+                }
+            })
     }
 
     async fn infer_person_task_to_adopt(
@@ -193,7 +201,7 @@ impl ReactionCapability for Worker {
             .map_err(|err| match err {
                 Error::CompletionError(completion_err) => completion_err.message(),
                 Error::FailedToGetMotivations(message) => message,
-                Error::FailedToGetReactionDualLayer(message) => message,
+                Error::FailedToGetReactionContext(context_err) => context_err.message(),
                 Error::FailedToInferPersonTaskToAdopt(message) => message,
                 Error::FailedToClassifyTaskOutcome(message) => message,
                 Error::FailedToInferTaskState(message) => message,
@@ -223,7 +231,7 @@ impl ReactionCapability for Worker {
             .map_err(|err| match err {
                 Error::CompletionError(completion_err) => completion_err.message(),
                 Error::FailedToGetMotivations(message) => message,
-                Error::FailedToGetReactionDualLayer(message) => message,
+                Error::FailedToGetReactionContext(context_err) => context_err.message(),
                 Error::FailedToInferPersonTaskToAdopt(message) => message,
                 Error::FailedToClassifyTaskOutcome(message) => message,
                 Error::FailedToInferTaskState(message) => message,
@@ -248,7 +256,7 @@ fn reaction_capability_error_message(err: Error) -> String {
     match err {
         Error::CompletionError(completion_err) => completion_err.message(),
         Error::FailedToGetMotivations(message) => message,
-        Error::FailedToGetReactionDualLayer(message) => message,
+        Error::FailedToGetReactionContext(context_err) => context_err.message(),
         Error::FailedToInferPersonTaskToAdopt(message) => message,
         Error::FailedToClassifyTaskOutcome(message) => message,
         Error::FailedToInferTaskState(message) => message,
@@ -271,41 +279,31 @@ async fn get_reaction_helper(
     worker: &Worker,
     memories: Vec<Memory>,
     person_uuid: PersonUuid,
-    person_identity: String,
     state_of_mind: String,
     situation: String,
 ) -> Result<PersonReaction, Error> {
-    let person_name = worker
-        .get_persons_name(person_uuid.clone())
-        .await
-        .map_err(|err| {
-            Error::FailedToGetReactionDualLayer(format!("Failed to get person's name: {}", err))
-        })?;
-
-    let motivations = worker
-        .get_motivations_for_person(&person_uuid)
+    let context = assemble_reaction_context(worker, &person_uuid)
         .await
-        .map_err(Error::FailedToGetMotivations)?;
+        .map_err(Error::from)?;
 
-    let current_person_task_text = get_current_person_task_text(worker, &person_uuid)
-        .await
-        .map_err(|err| {
-            Error::FailedToGetReactionDualLayer(format!(
-                "Failed to get current person task: {}",
-                err
-            ))
-        })?;
+    worker.logger.log(
+        Level::Debug,
+        format!(
+            "Assembled reaction context for person {} in {} ms ({} ms one at a time)",
+            person_uuid.to_uuid(),
+            context.timings.total.as_millis(),
+            context.timings.sequential_total().as_millis()
+        )
+        .as_str(),
+    );
 
-    let prompts = build_prompts(
-        person_name.as_str(),
+    let prompts = build_prompts(&context.prompt_context(
         &memories,
-        &motivations,
-        person_identity.as_str(),
         state_of_mind.as_str(),
         situation.as_str(),
-        current_person_task_text.as_str(),
-        INTERNAL_REACTION_PLACEHOLDER,
-    );
+    ));
+    let style_guide = context.style_guide;
+    let used_voice_exemplars = context.used_voice_exemplars;
 
     let first_pass_text = get_first_pass_reaction_text(worker, &prompts, &person_uuid).await?;
     let reformulated_action_prompt =
@@ -321,28 +319,55 @@ async fn get_reaction_helper(
     .await?;
 
     for retry_index in 0..=REACTION_VALIDATION_RETRY_LIMIT {
-        let validation = match validate_reaction_candidate(
-            worker,
-            &prompts,
-            reformulated_action_prompt.as_str(),
-            &candidate,
-            &person_uuid,
-        )
-        .await
+        // Out of retries, a comment that is only too long is cut short
+        // rather than thrown away
+        if retry_index == REACTION_VALIDATION_RETRY_LIMIT
+            && truncate_overlong_comment(&style_guide, &mut candidate.action)
         {
-            Ok(validation) => validation,
-            Err(err) => {
-                worker.logger.log(
-                    Level::Error,
-                    format!(
-                        "Reaction validator failed for person {}: {}. Returning unvalidated action.",
-                        person_uuid.to_uuid(),
-                        err
-                    )
-                    .as_str(),
-                );
-                return Ok(candidate);
-            }
+            worker.logger.log(
+                Level::Info,
+                format!(
+                    "Truncated overlong comment for person {}: {}",
+                    person_uuid.to_uuid(),
+                    describe_action(&candidate.action)
+                )
+                .as_str(),
+            );
+        }
+
+        let feedback = match overlong_comment_feedback(&style_guide, &candidate.action) {
+            Some(feedback) => Some(feedback),
+            None => exhausted_action_budget_feedback(worker, &candidate, &person_uuid).await,
+        };
+
+        let validation = match feedback {
+            Some(feedback) => ReactionValidationResult {
+                is_valid: false,
+                reason: feedback,
+            },
+            None => match validate_reaction_candidate(
+                worker,
+                &prompts,
+                reformulated_action_prompt.as_str(),
+                &candidate,
+                &person_uuid,
+            )
+            .await
+            {
+                Ok(validation) => validation,
+                Err(err) => {
+                    worker.logger.log(
+                        Level::Error,
+                        format!(
+                            "Reaction validator failed for person {}: {}. Returning unvalidated action.",
+                            person_uuid.to_uuid(),
+                            err
+                        )
+                        .as_str(),
+                    );
+                    return Ok(candidate);
+                }
+            },
         };
 
         if validation.is_valid {
@@ -356,6 +381,15 @@ async fn get_reaction_helper(
                 )
                 .as_str(),
             );
+            record_reaction_context(
+                worker,
+                &prompts,
+                reformulated_action_prompt.as_str(),
+                &candidate,
+                &person_uuid,
+                used_voice_exemplars,
+            )
+            .await;
             return Ok(candidate);
         }
 
@@ -458,7 +492,7 @@ async fn infer_person_task_to_adopt_helper(
     );
 
     let response = completion
-        .send_request(&worker.open_ai_key, worker.reqwest_client.clone())
+        .send_request(worker.open_ai_key.as_ref(), worker.open_ai_client.clone())
         .await
         .map_err(|err| {
             Error::FailedToInferPersonTaskToAdopt(format!(
@@ -526,7 +560,7 @@ async fn classify_task_outcome_helper(
     );
 
     let response = completion
-        .send_request(&worker.open_ai_key, worker.reqwest_client.clone())
+        .send_request(worker.open_ai_key.as_ref(), worker.open_ai_client.clone())
         .await
         .map_err(|err| {
             Error::FailedToClassifyTaskOutcome(format!(
@@ -594,7 +628,7 @@ async fn infer_updated_task_state_helper(
     );
 
     let response = completion
-        .send_request(&worker.open_ai_key, worker.reqwest_client.clone())
+        .send_request(worker.open_ai_key.as_ref(), worker.open_ai_client.clone())
         .await
         .map_err(|err| {
             Error::FailedToInferTaskState(format!(
@@ -1070,7 +1104,7 @@ async fn reformulate_action_prompt(
     );
 
     let response = completion
-        .send_request(&worker.open_ai_key, worker.reqwest_client.clone())
+        .send_request(worker.open_ai_key.as_ref(), worker.open_ai_client.clone())
         .await
         .map_err(Error::CompletionError)?;
 
@@ -1110,7 +1144,7 @@ async fn get_first_pass_reaction_text(
     );
 
     let response = completion
-        .send_request(&worker.open_ai_key, reqwest::Client::new())
+        .send_request(worker.open_ai_key.as_ref(), worker.open_ai_client.clone())
         .await
         .map_err(Error::CompletionError)?;
 
@@ -1155,8 +1189,26 @@ async fn choose_reaction(
     action_completion.add_message(Role::User, action_user_prompt.as_str());
     action_completion.add_tool_call(PersonActionKind::to_choice_tool());
 
+    match worker.get_person_fine_tuned_model(person_uuid).await {
+        Ok(Some(model)) => {
+            action_completion.set_model(model);
+        }
+        Ok(None) => {}
+        Err(err) => {
+            worker.logger.log(
+                Level::Error,
+                format!(
+                    "Failed to look up fine-tuned model for person {}, using default: {}",
+                    person_uuid.to_uuid(),
+                    err
+                )
+                .as_str(),
+            );
+        }
+    }
+
     let action_response = action_completion
-        .send_request(&worker.open_ai_key, reqwest::Client::new())
+        .send_request(worker.open_ai_key.as_ref(), worker.open_ai_client.clone())
         .await
         .map_err(Error::CompletionError)?;
     worker.logger.log(
@@ -1223,7 +1275,7 @@ async fn validate_reaction_candidate(
     completion.add_message(Role::User, validator_user_prompt.as_str());
 
     let response = completion
-        .send_request(&worker.open_ai_key, reqwest::Client::new())
+        .send_request(worker.open_ai_key.as_ref(), worker.open_ai_client.clone())
         .await
         .map_err(|err| err.message())?;
 
@@ -1246,6 +1298,452 @@ async fn validate_reaction_candidate(
     })
 }
 
+/// Rejects an action the person has no budget left for today, before asking
+/// the validator model. A budget that cannot be checked lets the action
+/// through, the same as a validator that fails.
+async fn exhausted_action_budget_feedback(
+    worker: &Worker,
+    candidate: &PersonReaction,
+    person_uuid: &PersonUuid,
+) -> Option<String> {
+    match get_action_budget_usage(worker, person_uuid, &candidate.action).await {
+        Ok(Some(usage)) if usage.is_exhausted() => Some(usage.to_feedback()),
+        Ok(_) => None,
+        Err(err) => {
+            worker.logger.log(
+                Level::Error,
+                format!(
+                    "Failed to check action budget for person {}: {}",
+                    person_uuid.to_uuid(),
+                    err
+                )
+                .as_str(),
+            );
+            None
+        }
+    }
+}
+
+/// Everything a reaction's prompts need about the person besides what the
+/// caller hands in.
+struct ReactionContext {
+    person_name: PersonName,
+    motivations: Vec<Motivation>,
+    person_identity: String,
+    current_person_task_text: String,
+    carried_items: Vec<Item>,
+    guardrails: GuardrailSettings,
+    style_guide: StyleGuide,
+    /// Empty when the person has none or this reaction goes without them.
+    voice_exemplars: Vec<String>,
+    used_voice_exemplars: Option<bool>,
+    /// Of the scene the person is in, if it has one.
+    tone_profile: Option<ToneProfile>,
+    /// Answered by whatever this reaction does.
+    pending_invitations: Vec<SceneInvitation>,
+    /// Their long-term storyline, once they have lived long enough for one.
+    arc: Option<PersonArc>,
+    timings: ContextTimings,
+}
+
+/// Everything about a person that goes into their reaction prompts, along
+/// with the memories, state of mind and situation they are reacting in.
+struct PromptContext<'a> {
+    person_name: &'a str,
+    memories: &'a [Memory],
+    motivations: &'a [Motivation],
+    person_identity: &'a str,
+    state_of_mind: &'a str,
+    situation: &'a str,
+    current_person_task_text: &'a str,
+    carried_items: &'a [Item],
+    guardrails: &'a GuardrailSettings,
+    style_guide: &'a StyleGuide,
+    voice_exemplars: &'a [String],
+    tone_profile: Option<ToneProfile>,
+    pending_invitations: &'a [SceneInvitation],
+    arc: Option<&'a PersonArc>,
+}
+
+impl ReactionContext {
+    fn prompt_context<'a>(
+        &'a self,
+        memories: &'a [Memory],
+        state_of_mind: &'a str,
+        situation: &'a str,
+    ) -> PromptContext<'a> {
+        PromptContext {
+            person_name: self.person_name.as_str(),
+            memories,
+            motivations: &self.motivations,
+            person_identity: self.person_identity.as_str(),
+            state_of_mind,
+            situation,
+            current_person_task_text: self.current_person_task_text.as_str(),
+            carried_items: &self.carried_items,
+            guardrails: &self.guardrails,
+            style_guide: &self.style_guide,
+            voice_exemplars: &self.voice_exemplars,
+            tone_profile: self.tone_profile,
+            pending_invitations: &self.pending_invitations,
+            arc: self.arc.as_ref(),
+        }
+    }
+}
+
+/// Which of the fetches that make up a reaction's context failed.
+#[derive(Debug)]
+pub enum ReactionContextError {
+    PersonName(String),
+    Motivations(String),
+    PersonIdentity(String),
+    CurrentTask(String),
+    CarriedItems(String),
+    Guardrails(String),
+    StyleGuide(String),
+    VoiceExemplars(String),
+    SceneTone(String),
+    Invitations(String),
+    Arc(String),
+}
+
+impl NiceDisplay for ReactionContextError {
+    fn message(&self) -> String {
+        match self {
+            ReactionContextError::PersonName(err) => {
+                format!("Failed to get person's name: {}", err)
+            }
+            ReactionContextError::Motivations(err) => format!("Failed to get motivations: {}", err),
+            ReactionContextError::PersonIdentity(err) => err.clone(),
+            ReactionContextError::CurrentTask(err) => {
+                format!("Failed to get current person task: {}", err)
+            }
+            ReactionContextError::CarriedItems(err) => {
+                format!("Failed to get carried items: {}", err)
+            }
+            ReactionContextError::Guardrails(err) => format!("Failed to get guardrails: {}", err),
+            ReactionContextError::StyleGuide(err) => format!("Failed to get style guide: {}", err),
+            ReactionContextError::VoiceExemplars(err) => {
+                format!("Failed to get voice exemplars: {}", err)
+            }
+            ReactionContextError::SceneTone(err) => format!("Failed to get scene tone: {}", err),
+            ReactionContextError::Invitations(err) => {
+                format!("Failed to get pending invitations: {}", err)
+            }
+            ReactionContextError::Arc(err) => format!("Failed to get person arc: {}", err),
+        }
+    }
+}
+
+impl From<ReactionContextError> for Error {
+    fn from(err: ReactionContextError) -> Self {
+        match err {
+            ReactionContextError::Motivations(err) => Error::FailedToGetMotivations(err),
+            other => Error::FailedToGetReactionContext(other),
+        }
+    }
+}
+
+/// Where the fetches for a reaction's context go, one method per fetch.
+trait ReactionContextSource {
+    async fn fetch_person_name(&self, person_uuid: &PersonUuid) -> Result<PersonName, String>;
+    async fn fetch_motivations(&self, person_uuid: &PersonUuid) -> Result<Vec<Motivation>, String>;
+    async fn fetch_person_identity(&self, person_uuid: &PersonUuid) -> Result<String, String>;
+    async fn fetch_current_task_text(&self, person_uuid: &PersonUuid) -> Result<String, String>;
+    async fn fetch_carried_items(&self, person_uuid: &PersonUuid) -> Result<Vec<Item>, String>;
+    async fn fetch_guardrails(&self) -> Result<GuardrailSettings, String>;
+    async fn fetch_style_guide(&self, person_uuid: &PersonUuid) -> Result<StyleGuide, String>;
+    async fn fetch_voice_exemplars(
+        &self,
+        person_uuid: &PersonUuid,
+    ) -> Result<(Vec<String>, Option<bool>), String>;
+    async fn fetch_scene_tone(
+        &self,
+        person_uuid: &PersonUuid,
+    ) -> Result<Option<ToneProfile>, String>;
+    async fn fetch_pending_invitations(
+        &self,
+        person_uuid: &PersonUuid,
+    ) -> Result<Vec<SceneInvitation>, String>;
+    async fn fetch_arc(&self, person_uuid: &PersonUuid) -> Result<Option<PersonArc>, String>;
+}
+
+impl ReactionContextSource for Worker {
+    async fn fetch_person_name(&self, person_uuid: &PersonUuid) -> Result<PersonName, String> {
+        self.get_persons_name(person_uuid.clone()).await
+    }
+
+    async fn fetch_motivations(&self, person_uuid: &PersonUuid) -> Result<Vec<Motivation>, String> {
+        self.get_motivations_for_person(person_uuid).await
+    }
+
+    async fn fetch_person_identity(&self, person_uuid: &PersonUuid) -> Result<String, String> {
+        get_person_identity_summary(self, person_uuid).await
+    }
+
+    async fn fetch_current_task_text(&self, person_uuid: &PersonUuid) -> Result<String, String> {
+        get_current_person_task_text(self, person_uuid).await
+    }
+
+    async fn fetch_carried_items(&self, person_uuid: &PersonUuid) -> Result<Vec<Item>, String> {
+        self.get_items_carried_by(person_uuid).await
+    }
+
+    async fn fetch_guardrails(&self) -> Result<GuardrailSettings, String> {
+        self.get_guardrail_settings().await
+    }
+
+    async fn fetch_style_guide(&self, person_uuid: &PersonUuid) -> Result<StyleGuide, String> {
+        get_style_guide(self, person_uuid).await
+    }
+
+    async fn fetch_voice_exemplars(
+        &self,
+        person_uuid: &PersonUuid,
+    ) -> Result<(Vec<String>, Option<bool>), String> {
+        get_voice_exemplars(self, person_uuid).await
+    }
+
+    async fn fetch_scene_tone(
+        &self,
+        person_uuid: &PersonUuid,
+    ) -> Result<Option<ToneProfile>, String> {
+        get_scene_tone_profile(self, person_uuid).await
+    }
+
+    async fn fetch_pending_invitations(
+        &self,
+        person_uuid: &PersonUuid,
+    ) -> Result<Vec<SceneInvitation>, String> {
+        self.get_pending_scene_invitations(person_uuid).await
+    }
+
+    async fn fetch_arc(&self, person_uuid: &PersonUuid) -> Result<Option<PersonArc>, String> {
+        self.get_latest_person_arc(person_uuid).await
+    }
+}
+
+/// Runs the fetches at the same time, since none of them depend on another.
+async fn assemble_reaction_context(
+    source: &impl ReactionContextSource,
+    person_uuid: &PersonUuid,
+) -> Result<ReactionContext, ReactionContextError> {
+    let started_at = Instant::now();
+
+    let (
+        (person_name, person_name_timing),
+        (motivations, motivations_timing),
+        (person_identity, person_identity_timing),
+        (current_person_task_text, current_task_timing),
+        (carried_items, carried_items_timing),
+        (guardrails, guardrails_timing),
+        (style_guide, style_guide_timing),
+        ((voice_exemplars, used_voice_exemplars), voice_exemplars_timing),
+        (tone_profile, tone_profile_timing),
+        (pending_invitations, pending_invitations_timing),
+        (arc, arc_timing),
+    ) = tokio::try_join!(
+        timed("person name", async {
+            source
+                .fetch_person_name(person_uuid)
+                .await
+                .map_err(ReactionContextError::PersonName)
+        }),
+        timed("motivations", async {
+            source
+                .fetch_motivations(person_uuid)
+                .await
+                .map_err(ReactionContextError::Motivations)
+        }),
+        timed("person identity", async {
+            source
+                .fetch_person_identity(person_uuid)
+                .await
+                .map_err(ReactionContextError::PersonIdentity)
+        }),
+        timed("current task", async {
+            source
+                .fetch_current_task_text(person_uuid)
+                .await
+                .map_err(ReactionContextError::CurrentTask)
+        }),
+        timed("carried items", async {
+            source
+                .fetch_carried_items(person_uuid)
+                .await
+                .map_err(ReactionContextError::CarriedItems)
+        }),
+        timed("guardrails", async {
+            source
+                .fetch_guardrails()
+                .await
+                .map_err(ReactionContextError::Guardrails)
+        }),
+        timed("style guide", async {
+            source
+                .fetch_style_guide(person_uuid)
+                .await
+                .map_err(ReactionContextError::StyleGuide)
+        }),
+        timed("voice exemplars", async {
+            source
+                .fetch_voice_exemplars(person_uuid)
+                .await
+                .map_err(ReactionContextError::VoiceExemplars)
+        }),
+        timed("scene tone", async {
+            source
+                .fetch_scene_tone(person_uuid)
+                .await
+                .map_err(ReactionContextError::SceneTone)
+        }),
+        timed("invitations", async {
+            source
+                .fetch_pending_invitations(person_uuid)
+                .await
+                .map_err(ReactionContextError::Invitations)
+        }),
+        timed("arc", async {
+            source
+                .fetch_arc(person_uuid)
+                .await
+                .map_err(ReactionContextError::Arc)
+        }),
+    )?;
+
+    Ok(ReactionContext {
+        person_name,
+        motivations,
+        person_identity,
+        current_person_task_text,
+        carried_items,
+        guardrails,
+        style_guide,
+        voice_exemplars,
+        used_voice_exemplars,
+        tone_profile,
+        pending_invitations,
+        arc,
+        timings: ContextTimings {
+            fetches: vec![
+                person_name_timing,
+                motivations_timing,
+                person_identity_timing,
+                current_task_timing,
+                carried_items_timing,
+                guardrails_timing,
+                style_guide_timing,
+                voice_exemplars_timing,
+                tone_profile_timing,
+                pending_invitations_timing,
+                arc_timing,
+            ],
+            total: started_at.elapsed(),
+        },
+    })
+}
+
+async fn timed<T, E>(
+    name: &'static str,
+    future: impl Future<Output = Result<T, E>>,
+) -> Result<(T, ContextFetchTiming), E> {
+    let started_at = Instant::now();
+    let value = future.await?;
+
+    Ok((
+        value,
+        ContextFetchTiming {
+            name,
+            elapsed: started_at.elapsed(),
+        },
+    ))
+}
+
+async fn get_style_guide(worker: &Worker, person_uuid: &PersonUuid) -> Result<StyleGuide, String> {
+    let world_style_guide = worker.get_world_style_guide().await?;
+    let person_style_guide = worker.get_person_style_guide(person_uuid).await?;
+
+    Ok(world_style_guide.overridden_by(&person_style_guide))
+}
+
+async fn get_scene_tone_profile(
+    worker: &Worker,
+    person_uuid: &PersonUuid,
+) -> Result<Option<ToneProfile>, String> {
+    match worker.get_persons_current_scene_uuid(person_uuid).await? {
+        Some(scene_uuid) => worker.get_scene_tone_profile(&scene_uuid).await,
+        None => Ok(None),
+    }
+}
+
+/// The person's exemplars if this reaction gets them, and whether it did.
+async fn get_voice_exemplars(
+    worker: &Worker,
+    person_uuid: &PersonUuid,
+) -> Result<(Vec<String>, Option<bool>), String> {
+    let mode = worker.get_voice_exemplar_mode().await?;
+    let exemplars = worker.get_voice_exemplars(person_uuid).await?;
+
+    let used = mode.includes(!exemplars.is_empty(), rand::random());
+
+    if used == Some(true) {
+        Ok((exemplars, used))
+    } else {
+        Ok((Vec::new(), used))
+    }
+}
+
+fn overlong_comment_feedback(style_guide: &StyleGuide, action: &PersonAction) -> Option<String> {
+    match action {
+        PersonAction::SayInScene { comment, .. } => style_guide.overlong_feedback(comment),
+        _ => None,
+    }
+}
+
+/// Whether there was anything to cut.
+fn truncate_overlong_comment(style_guide: &StyleGuide, action: &mut PersonAction) -> bool {
+    match action {
+        PersonAction::SayInScene { comment, .. } => {
+            if style_guide.overlong_feedback(comment).is_none() {
+                return false;
+            }
+
+            *comment = style_guide.truncate(comment);
+            true
+        }
+        _ => false,
+    }
+}
+
+async fn record_reaction_context(
+    worker: &Worker,
+    prompts: &ReactionPromptPreview,
+    reformulated_action_prompt: &str,
+    reaction: &PersonReaction,
+    person_uuid: &PersonUuid,
+    used_voice_exemplars: Option<bool>,
+) {
+    let new_reaction_context = NewReactionContext {
+        person_uuid: person_uuid.clone(),
+        system_prompt: prompts.action_system_prompt.clone(),
+        user_prompt: build_action_user_prompt(reformulated_action_prompt, None),
+        assistant_response: reaction_to_json(reaction),
+        used_voice_exemplars,
+    };
+
+    if let Err(err) = worker.record_reaction_context(new_reaction_context).await {
+        worker.logger.log(
+            Level::Error,
+            format!(
+                "Failed to record reaction context for person {}: {}",
+                person_uuid.to_uuid(),
+                err
+            )
+            .as_str(),
+        );
+    }
+}
+
 fn build_base_action_user_prompt(prompts: &ReactionPromptPreview, first_pass_text: &str) -> String {
     prompts
         .action_user_prompt
@@ -1285,7 +1783,7 @@ fn build_action_user_prompt(
     if let Some(feedback) = validation_feedback {
         action_user_prompt.push_str(
             format!(
-                "\n\nValidator feedback on your previous rejected action:\n{}\n\nChoose a different action that fixes this problem. Remember that Arizona2 can only speak, move scenes, gaze at the current scene, wait, hibernate, or idle. Do not imply that any other action was performed. Choose exactly one tool call and do not output any plain text.",
+                "\n\nValidator feedback on your previous rejected action:\n{}\n\nChoose a different action that fixes this problem. Remember that Arizona2 can only speak, ask someone a question, move scenes, gaze at the current scene, wait, hibernate, or idle. Do not imply that any other action was performed. Choose exactly one tool call and do not output any plain text.",
                 feedback
             )
             .as_str(),
@@ -1322,15 +1820,61 @@ fn action_to_json(action: &PersonAction) -> serde_json::Value {
         PersonAction::SayInScene {
             comment,
             destination_scene_name,
+            addressed_to,
+            whisper,
+            quote,
         } => serde_json::json!({
             "type": "say in scene",
             "comment": comment,
             "destination_scene_name": destination_scene_name,
+            "addressed_to": addressed_to,
+            "volume": if *whisper { "whisper" } else { "aloud" },
+            "quote": quote,
+        }),
+        PersonAction::Ask {
+            recipient_name,
+            question,
+            reply_window_ms,
+            quote,
+        } => serde_json::json!({
+            "type": "ask",
+            "recipient_name": recipient_name,
+            "question": question,
+            "reply_window": reply_window_ms,
+            "quote": quote,
         }),
         PersonAction::MoveToScene { scene_name } => serde_json::json!({
             "type": "move to scene",
             "scene_name": scene_name,
         }),
+        PersonAction::GiveItem {
+            item_name,
+            recipient_name,
+        } => serde_json::json!({
+            "type": "give item",
+            "item_name": item_name,
+            "recipient_name": recipient_name,
+        }),
+        PersonAction::Invite {
+            recipient_name,
+            scene_name,
+        } => serde_json::json!({
+            "type": "invite",
+            "recipient_name": recipient_name,
+            "scene_name": scene_name,
+        }),
+        PersonAction::UseTool {
+            tool_name,
+            arguments,
+        } => serde_json::json!({
+            "type": "use tool",
+            "tool_name": tool_name,
+            "arguments": arguments,
+        }),
+        PersonAction::Custom { name, arguments } => serde_json::json!({
+            "type": name,
+            "arguments": arguments,
+        }),
     }
 }
 
@@ -1347,24 +1891,38 @@ struct ReactionValidationResult {
     reason: String,
 }
 
-fn build_prompts(
-    person_name: &str,
-    memories: &[Memory],
-    motivations: &[Motivation],
-    person_identity: &str,
-    state_of_mind: &str,
-    situation: &str,
-    current_person_task_text: &str,
-    first_pass_text: &str,
-) -> ReactionPromptPreview {
-    let thinking_system_prompt = "You are simulating a real person’s immediate inner reasoning at a single moment in time.
+/// The action prompt holds `INTERNAL_REACTION_PLACEHOLDER` where the first
+/// pass text goes, since that is only known once the thinking prompt has run.
+fn build_prompts(context: &PromptContext) -> ReactionPromptPreview {
+    let PromptContext {
+        person_name,
+        memories,
+        motivations,
+        person_identity,
+        state_of_mind,
+        situation,
+        current_person_task_text,
+        carried_items,
+        guardrails,
+        style_guide,
+        voice_exemplars,
+        tone_profile,
+        pending_invitations,
+        arc,
+    } = *context;
+
+    let situation = match SceneInvitation::many_to_prompt_section(pending_invitations) {
+        Some(section) => format!("{}\n\n{}", situation, section),
+        None => situation.to_string(),
+    };
+    let thinking_system_prompt = format!("You are simulating a real person’s immediate inner reasoning at a single moment in time.
 
 Your job is to infer this person’s current attention, what they believe is happening, what they want to do next, and which single next action they are leaning toward right now.
 
 Rules:
 - Use only the information explicitly present in this prompt.
 - Do not assume abilities beyond the available tool calls.
-- Infer only intentions that this person could actually carry out within Arizona2's available capabilities: `say in scene`, `move to scene`, `gaze in scene`, `wait`, `hibernate`, and `idle`.
+- Infer only intentions that this person could actually carry out within Arizona2's available capabilities: {}.
 - Do not infer intentions that depend on impossible abilities, hidden operations outside those capabilities, or claims that something has already been done when the person could not actually have done it yet.
 - Focus on the newest message events first; use older context only to interpret them.
 - Treat the person's current task as the strongest default signal for what they intend to do, unless the latest situation clearly overrides it.
@@ -1381,19 +1939,23 @@ Your response should make clear:
 - what they believe matters most in this moment,
 - what they want to do next,
 - and what specific action they are leaning toward taking immediately.
-".to_string();
+", PersonActionKind::available_actions_text());
     let memories_list_text = Memory::many_to_list_text(memories);
     let motivations_list_text = Motivation::many_to_list_text(motivations);
+    let carried_items_list_text = Item::many_to_list_text(carried_items);
+    let arc_section = arc.map(PersonArc::to_prompt_section).unwrap_or_default();
 
     let thinking_user_prompt = format!(
-        "Describe this person's immediate intention and current thinking in plain text.\n\nName: \n{}\n\nMemories:\n{}\n\nBackground drives:\n{}\n\nPerson identity:\n{}\n\nState of mind:\n{}\n\nSituation:\n{}{}",
+        "Describe this person's immediate intention and current thinking in plain text.\n\nName: \n{}\n\nMemories:\n{}\n\nBackground drives:\n{}\n\nPerson identity:\n{}\n\nState of mind:\n{}\n\nCarrying:\n{}\n\nSituation:\n{}{}{}",
         person_name,
         memories_list_text,
         motivations_list_text,
         person_identity,
         state_of_mind,
+        carried_items_list_text,
         situation,
-        current_person_task_text
+        current_person_task_text,
+        arc_section
     );
 
     let action_system_prompt = format!(
@@ -1406,7 +1968,7 @@ Stay in character as a person, not a document. Prefer brief, natural behavior ov
 Your job is to choose the single action $name$ would take right now, based on the latest messages, the first-pass internal reaction text, and the available tools.
 
 Rules:
-- Available actions are only: `say in scene`, `move to scene`, `gaze in scene`, `wait`, `hibernate`, and `idle`.
+- Available actions are only: {}.
 - Prioritize the newest message over older context.
 - Use the first-pass internal reaction text as the main guide to intent, unless it conflicts with newer information in this prompt.
 - Choose exactly one tool call.
@@ -1415,20 +1977,54 @@ Rules:
 - Prefer actions that advance $name$’s current task, reduce uncertainty, or enforce an important constraint.
 - If multiple actions are plausible, choose the one that best fits $name$’s highest-priority drives.",
 		person_identity,
+		PersonActionKind::available_actions_text(),
 	).replace("$name$", person_name);
 
     let action_user_prompt = format!(
-        "Memories:\n{}\n\nRecent events and recent messages:\n{}\n\nInternal reaction text:\n{}\n\nNow choose exactly one action tool call. Do not output any plain text.",
+        "Memories:\n{}\n\nCarrying:\n{}\n\nRecent events and recent messages:\n{}\n\nInternal reaction text:\n{}\n\nNow choose exactly one action tool call. Do not output any plain text.",
         memories_list_text,
+        carried_items_list_text,
         situation,
-        first_pass_text
+        INTERNAL_REACTION_PLACEHOLDER
     );
 
+    let action_system_prompt = match style_guide.to_prompt_section() {
+        Some(section) => format!("{}\n\n{}", action_system_prompt, section),
+        None => action_system_prompt,
+    };
+
+    let action_system_prompt = match tone_profile {
+        Some(tone_profile) => format!(
+            "{}\n\n{}",
+            action_system_prompt,
+            tone_profile.to_prompt_section()
+        ),
+        None => action_system_prompt,
+    };
+
+    let action_system_prompt = match voice_exemplar::to_prompt_section(voice_exemplars) {
+        Some(section) => format!(
+            "{}\n\n{}",
+            action_system_prompt,
+            section.replace("$name$", person_name)
+        ),
+        None => action_system_prompt,
+    };
+
+    let (thinking_system_prompt, action_system_prompt) = match guardrails.to_prompt_section() {
+        Some(section) => (
+            format!("{}\n{}", thinking_system_prompt, section),
+            format!("{}\n\n{}", action_system_prompt, section),
+        ),
+        None => (thinking_system_prompt, action_system_prompt),
+    };
+
     ReactionPromptPreview {
         thinking_system_prompt,
         thinking_user_prompt,
         action_system_prompt,
         action_user_prompt,
+        context_timings: ContextTimings::default(),
     }
 }
 
@@ -1441,15 +2037,48 @@ fn describe_action(action: &PersonAction) -> String {
         PersonAction::SayInScene {
             comment,
             destination_scene_name,
-        } => match destination_scene_name {
-            Some(scene_name) => {
-                format!("say in scene then move to {}: {}", scene_name, comment)
+            addressed_to,
+            whisper,
+            ..
+        } => {
+            let verb = match (addressed_to.is_empty(), whisper) {
+                (true, _) => "say in scene".to_string(),
+                (false, true) => format!("whisper to {}", addressed_to.join(", ")),
+                (false, false) => format!("say to {}", addressed_to.join(", ")),
+            };
+            match destination_scene_name {
+                Some(scene_name) => format!("{} then move to {}: {}", verb, scene_name, comment),
+                None => format!("{}: {}", verb, comment),
             }
-            None => format!("say in scene: {}", comment),
-        },
+        }
+        PersonAction::Ask {
+            recipient_name,
+            question,
+            reply_window_ms,
+            ..
+        } => format!(
+            "ask {} (waiting {} ms for a reply): {}",
+            recipient_name, reply_window_ms, question
+        ),
         PersonAction::MoveToScene { scene_name } => {
             format!("move to scene: {}", scene_name)
         }
+        PersonAction::GiveItem {
+            item_name,
+            recipient_name,
+        } => format!("give {} to {}", item_name, recipient_name),
+        PersonAction::Invite {
+            recipient_name,
+            scene_name,
+        } => match scene_name {
+            Some(scene_name) => format!("invite {} to {}", recipient_name, scene_name),
+            None => format!("invite {} to the current scene", recipient_name),
+        },
+        PersonAction::UseTool {
+            tool_name,
+            arguments,
+        } => format!("use tool {}: {}", tool_name, arguments),
+        PersonAction::Custom { name, arguments } => format!("{}: {}", name, arguments),
     }
 }
 
@@ -1463,7 +2092,8 @@ fn describe_reflection(reflection: &ReflectionDecision) -> String {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use chrono::Utc;
+    use crate::domain::clock::Clock;
+    use chrono::{TimeZone, Utc};
 
     fn sample_person_task(state: Option<&str>) -> PersonTask {
         PersonTask {
@@ -1475,7 +2105,7 @@ mod tests {
             abandon_condition: None,
             failure_condition: None,
             priority: 75,
-            created_at: Utc::now(),
+            created_at: Clock::fake(Utc.with_ymd_and_hms(2026, 3, 18, 9, 0, 0).unwrap()).now(),
             completed_at: None,
             abandoned_at: None,
             failed_at: None,
@@ -1491,6 +2121,7 @@ mod tests {
                 "Recent events:\nalpha\n\nInternal reaction text:\n{}\n\nChoose one action.",
                 INTERNAL_REACTION_PLACEHOLDER
             ),
+            context_timings: ContextTimings::default(),
         }
     }
 
@@ -1662,6 +2293,254 @@ mod tests {
         assert!(formatted.contains("\nCurrent Task Priority: 75"));
         assert!(!formatted.contains("Current Task State:"));
     }
+
+    /// Answers every fetch after its own delay, so the fetches finish in a
+    /// different order than they start.
+    struct FakeContextSource {
+        failing_motivations: bool,
+    }
+
+    async fn after(millis: u64) {
+        tokio::time::sleep(std::time::Duration::from_millis(millis)).await;
+    }
+
+    impl ReactionContextSource for FakeContextSource {
+        async fn fetch_person_name(&self, _: &PersonUuid) -> Result<PersonName, String> {
+            after(30).await;
+            Ok(PersonName::from_string("Dolores".to_string()))
+        }
+
+        async fn fetch_motivations(&self, _: &PersonUuid) -> Result<Vec<Motivation>, String> {
+            after(25).await;
+            if self.failing_motivations {
+                return Err("the database went away".to_string());
+            }
+
+            Ok(vec![Motivation {
+                uuid: crate::domain::motivation_uuid::MotivationUuid::new(),
+                content: "Find out who rang the bell.".to_string(),
+                priority: 80,
+                created_at: Clock::fake(Utc.with_ymd_and_hms(2026, 3, 18, 9, 0, 0).unwrap()).now(),
+                ended_at: None,
+            }])
+        }
+
+        async fn fetch_person_identity(&self, _: &PersonUuid) -> Result<String, String> {
+            after(20).await;
+            Ok("A retired pilot.".to_string())
+        }
+
+        async fn fetch_current_task_text(&self, _: &PersonUuid) -> Result<String, String> {
+            after(15).await;
+            Ok(format_current_person_task_text(&sample_person_task(Some(
+                "waiting",
+            ))))
+        }
+
+        async fn fetch_carried_items(&self, _: &PersonUuid) -> Result<Vec<Item>, String> {
+            after(10).await;
+            Ok(vec![Item {
+                uuid: crate::domain::item_uuid::ItemUuid::new(),
+                name: "Brass key".to_string(),
+                description: "It opens the lighthouse.".to_string(),
+            }])
+        }
+
+        async fn fetch_guardrails(&self) -> Result<GuardrailSettings, String> {
+            after(5).await;
+            Ok(GuardrailSettings::default())
+        }
+
+        async fn fetch_style_guide(&self, _: &PersonUuid) -> Result<StyleGuide, String> {
+            Ok(StyleGuide::default())
+        }
+
+        async fn fetch_voice_exemplars(
+            &self,
+            _: &PersonUuid,
+        ) -> Result<(Vec<String>, Option<bool>), String> {
+            after(5).await;
+            Ok((vec!["Well, I never.".to_string()], Some(true)))
+        }
+
+        async fn fetch_scene_tone(&self, _: &PersonUuid) -> Result<Option<ToneProfile>, String> {
+            after(10).await;
+            Ok(Some(ToneProfile::Noir))
+        }
+
+        async fn fetch_pending_invitations(
+            &self,
+            _: &PersonUuid,
+        ) -> Result<Vec<SceneInvitation>, String> {
+            after(15).await;
+            Ok(Vec::new())
+        }
+
+        async fn fetch_arc(&self, _: &PersonUuid) -> Result<Option<PersonArc>, String> {
+            after(20).await;
+            Ok(None)
+        }
+    }
+
+    /// How the context was assembled before the fetches ran at the same time.
+    async fn assemble_reaction_context_one_at_a_time(
+        source: &impl ReactionContextSource,
+        person_uuid: &PersonUuid,
+    ) -> Result<ReactionContext, ReactionContextError> {
+        let person_name = source
+            .fetch_person_name(person_uuid)
+            .await
+            .map_err(ReactionContextError::PersonName)?;
+        let motivations = source
+            .fetch_motivations(person_uuid)
+            .await
+            .map_err(ReactionContextError::Motivations)?;
+        let person_identity = source
+            .fetch_person_identity(person_uuid)
+            .await
+            .map_err(ReactionContextError::PersonIdentity)?;
+        let current_person_task_text = source
+            .fetch_current_task_text(person_uuid)
+            .await
+            .map_err(ReactionContextError::CurrentTask)?;
+        let carried_items = source
+            .fetch_carried_items(person_uuid)
+            .await
+            .map_err(ReactionContextError::CarriedItems)?;
+        let guardrails = source
+            .fetch_guardrails()
+            .await
+            .map_err(ReactionContextError::Guardrails)?;
+        let style_guide = source
+            .fetch_style_guide(person_uuid)
+            .await
+            .map_err(ReactionContextError::StyleGuide)?;
+        let (voice_exemplars, used_voice_exemplars) = source
+            .fetch_voice_exemplars(person_uuid)
+            .await
+            .map_err(ReactionContextError::VoiceExemplars)?;
+        let tone_profile = source
+            .fetch_scene_tone(person_uuid)
+            .await
+            .map_err(ReactionContextError::SceneTone)?;
+        let pending_invitations = source
+            .fetch_pending_invitations(person_uuid)
+            .await
+            .map_err(ReactionContextError::Invitations)?;
+        let arc = source
+            .fetch_arc(person_uuid)
+            .await
+            .map_err(ReactionContextError::Arc)?;
+
+        Ok(ReactionContext {
+            person_name,
+            motivations,
+            person_identity,
+            current_person_task_text,
+            carried_items,
+            guardrails,
+            style_guide,
+            voice_exemplars,
+            used_voice_exemplars,
+            tone_profile,
+            pending_invitations,
+            arc,
+            timings: ContextTimings::default(),
+        })
+    }
+
+    #[tokio::test]
+    async fn test_assembling_at_the_same_time_matches_one_at_a_time() {
+        let source = FakeContextSource {
+            failing_motivations: false,
+        };
+        let person_uuid = PersonUuid::new();
+        let memories = Vec::new();
+
+        let parallel = match assemble_reaction_context(&source, &person_uuid).await {
+            Ok(context) => context,
+            Err(err) => panic!("{}", err.message()),
+        };
+        let sequential = match assemble_reaction_context_one_at_a_time(&source, &person_uuid).await
+        {
+            Ok(context) => context,
+            Err(err) => panic!("{}", err.message()),
+        };
+
+        let parallel_prompts =
+            build_prompts(&parallel.prompt_context(&memories, "Calm.", "A bell rings."));
+        let sequential_prompts =
+            build_prompts(&sequential.prompt_context(&memories, "Calm.", "A bell rings."));
+
+        assert_eq!(
+            parallel_prompts.thinking_system_prompt,
+            sequential_prompts.thinking_system_prompt
+        );
+        assert_eq!(
+            parallel_prompts.thinking_user_prompt,
+            sequential_prompts.thinking_user_prompt
+        );
+        assert_eq!(
+            parallel_prompts.action_system_prompt,
+            sequential_prompts.action_system_prompt
+        );
+        assert_eq!(
+            parallel_prompts.action_user_prompt,
+            sequential_prompts.action_user_prompt
+        );
+        assert_eq!(
+            parallel.used_voice_exemplars,
+            sequential.used_voice_exemplars
+        );
+        assert!(parallel_prompts.thinking_user_prompt.contains("Brass key"));
+
+        let fetch_names = parallel
+            .timings
+            .fetches
+            .iter()
+            .map(|fetch| fetch.name)
+            .collect::<Vec<&str>>();
+        assert_eq!(
+            fetch_names,
+            vec![
+                "person name",
+                "motivations",
+                "person identity",
+                "current task",
+                "carried items",
+                "guardrails",
+                "style guide",
+                "voice exemplars",
+                "scene tone",
+                "invitations",
+                "arc",
+            ]
+        );
+        assert!(parallel.timings.total < parallel.timings.sequential_total());
+    }
+
+    #[tokio::test]
+    async fn test_motivation_failures_are_reported_as_motivation_failures() {
+        let source = FakeContextSource {
+            failing_motivations: true,
+        };
+
+        let err = match assemble_reaction_context(&source, &PersonUuid::new()).await {
+            Err(err) => err,
+            Ok(_) => panic!("expected the motivations fetch to fail"),
+        };
+        assert_eq!(
+            err.message(),
+            "Failed to get motivations: the database went away"
+        );
+
+        match Error::from(err) {
+            Error::FailedToGetMotivations(message) => {
+                assert_eq!(message, "the database went away")
+            }
+            _ => panic!("expected a motivations error"),
+        }
+    }
 }
 
 fn tool_calls_into_reactions(
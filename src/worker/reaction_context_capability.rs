@@ -0,0 +1,157 @@
+use crate::capability::reaction_context::{
+    NewReactionContext, ReactionContext, ReactionContextCapability,
+};
+use crate::domain::person_name::PersonName;
+use crate::domain::reaction_context_uuid::ReactionContextUuid;
+use crate::worker::Worker;
+use chrono::{DateTime, Utc};
+use sqlx::postgres::PgRow;
+use sqlx::Row;
+use uuid::Uuid;
+
+impl ReactionContextCapability for Worker {
+    async fn record_reaction_context(
+        &self,
+        new_reaction_context: NewReactionContext,
+    ) -> Result<ReactionContextUuid, String> {
+        let reaction_context_uuid = Uuid::now_v7();
+
+        sqlx::query(
+            r#"
+                INSERT INTO reaction_context (
+                    uuid,
+                    person_uuid,
+                    system_prompt,
+                    user_prompt,
+                    assistant_response,
+                    used_voice_exemplars
+                )
+                VALUES ($1::UUID, $2::UUID, $3::TEXT, $4::TEXT, $5::TEXT, $6::BOOLEAN);
+            "#,
+        )
+        .bind(reaction_context_uuid)
+        .bind(new_reaction_context.person_uuid.to_uuid())
+        .bind(new_reaction_context.system_prompt)
+        .bind(new_reaction_context.user_prompt)
+        .bind(new_reaction_context.assistant_response)
+        .bind(new_reaction_context.used_voice_exemplars)
+        .execute(&self.sqlx)
+        .await
+        .map_err(|err| format!("Error inserting reaction context: {}", err))?;
+
+        Ok(ReactionContextUuid::from_uuid(reaction_context_uuid))
+    }
+
+    async fn get_recent_reaction_contexts(
+        &self,
+        limit: i64,
+    ) -> Result<Vec<ReactionContext>, String> {
+        let rows = sqlx::query(
+            r#"
+                SELECT
+                    rc.uuid,
+                    p.name AS person_name,
+                    rc.system_prompt,
+                    rc.user_prompt,
+                    rc.assistant_response,
+                    rc.is_good,
+                    rc.created_at
+                FROM reaction_context rc
+                JOIN person p ON p.uuid = rc.person_uuid
+                ORDER BY rc.created_at DESC
+                LIMIT $1;
+            "#,
+        )
+        .bind(limit)
+        .fetch_all(&self.sqlx)
+        .await
+        .map_err(|err| format!("Error fetching recent reaction contexts: {}", err))?;
+
+        rows.iter().map(reaction_context_from_row).collect()
+    }
+
+    async fn get_good_reaction_contexts(&self) -> Result<Vec<ReactionContext>, String> {
+        let rows = sqlx::query(
+            r#"
+                SELECT
+                    rc.uuid,
+                    p.name AS person_name,
+                    rc.system_prompt,
+                    rc.user_prompt,
+                    rc.assistant_response,
+                    rc.is_good,
+                    rc.created_at
+                FROM reaction_context rc
+                JOIN person p ON p.uuid = rc.person_uuid
+                WHERE rc.is_good
+                ORDER BY rc.created_at ASC;
+            "#,
+        )
+        .fetch_all(&self.sqlx)
+        .await
+        .map_err(|err| format!("Error fetching good reaction contexts: {}", err))?;
+
+        rows.iter().map(reaction_context_from_row).collect()
+    }
+
+    async fn set_reaction_context_good(
+        &self,
+        reaction_context_uuid: &ReactionContextUuid,
+        is_good: bool,
+    ) -> Result<(), String> {
+        sqlx::query(
+            r#"
+                UPDATE reaction_context
+                SET is_good = $2::BOOLEAN
+                WHERE uuid = $1::UUID;
+            "#,
+        )
+        .bind(reaction_context_uuid.to_uuid())
+        .bind(is_good)
+        .execute(&self.sqlx)
+        .await
+        .map_err(|err| format!("Error updating reaction context: {}", err))?;
+
+        Ok(())
+    }
+}
+
+fn reaction_context_from_row(row: &PgRow) -> Result<ReactionContext, String> {
+    let uuid = row
+        .try_get::<Uuid, _>("uuid")
+        .map_err(|err| format!("Error reading uuid from row: {}", err))?;
+
+    let person_name = row
+        .try_get::<String, _>("person_name")
+        .map_err(|err| format!("Error reading person_name from row: {}", err))?;
+
+    let system_prompt = row
+        .try_get::<String, _>("system_prompt")
+        .map_err(|err| format!("Error reading system_prompt from row: {}", err))?;
+
+    let user_prompt = row
+        .try_get::<String, _>("user_prompt")
+        .map_err(|err| format!("Error reading user_prompt from row: {}", err))?;
+
+    let assistant_response = row
+        .try_get::<String, _>("assistant_response")
+        .map_err(|err| format!("Error reading assistant_response from row: {}", err))?;
+
+    let is_good = row
+        .try_get::<bool, _>("is_good")
+        .map_err(|err| format!("Error reading is_good from row: {}", err))?;
+
+    let created_at = row
+        .try_get::<DateTime<Utc>, _>("created_at")
+        .map_err(|err| format!("Error reading created_at from row: {}", err))?;
+
+    Ok(ReactionContext {
+        uuid: ReactionContextUuid::from_uuid(uuid),
+        person_name: PersonName::from_string(person_name),
+        system_prompt,
+        user_prompt,
+        assistant_response,
+        is_good,
+        created_at,
+    })
+}
@@ -1,4 +1,5 @@
 use crate::capability::reaction_history::ReactionHistoryCapability;
+use crate::domain::action_budget::WORLD_DAY_MS;
 use crate::domain::person_uuid::PersonUuid;
 use crate::worker::Worker;
 use chrono::{DateTime, Utc};
@@ -14,13 +15,19 @@ impl ReactionHistoryCapability for Worker {
 
         sqlx::query(
             r#"
-                INSERT INTO reaction_history (uuid, person_uuid, action_kind)
-                VALUES ($1::UUID, $2::UUID, $3::TEXT);
+                INSERT INTO reaction_history (uuid, person_uuid, action_kind, world_day)
+                VALUES (
+                    $1::UUID,
+                    $2::UUID,
+                    $3::TEXT,
+                    (SELECT active_ms / $4::BIGINT FROM active_clock WHERE id = TRUE)
+                );
             "#,
         )
         .bind(reaction_uuid)
         .bind(person_uuid.to_uuid())
         .bind(action_kind)
+        .bind(WORLD_DAY_MS)
         .execute(&self.sqlx)
         .await
         .map_err(|err| format!("Error inserting reaction history: {}", err))?;
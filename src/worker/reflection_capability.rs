@@ -153,7 +153,7 @@ impl ReflectionCapability for Worker {
         }
 
         let response = completion
-            .send_request(&self.open_ai_key, self.reqwest_client.clone())
+            .send_request(self.open_ai_key.as_ref(), self.open_ai_client.clone())
             .await
             .map_err(|err| err.message())?;
 
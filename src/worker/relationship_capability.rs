@@ -0,0 +1,45 @@
+use crate::capability::relationship::{NewRelationship, RelationshipCapability};
+use crate::worker::Worker;
+use uuid::Uuid;
+
+impl RelationshipCapability for Worker {
+    async fn create_relationships(
+        &self,
+        relationships: Vec<NewRelationship>,
+    ) -> Result<(), String> {
+        if relationships.is_empty() {
+            return Ok(());
+        }
+
+        let mut uuids = Vec::with_capacity(relationships.len());
+        let mut person_uuids = Vec::with_capacity(relationships.len());
+        let mut other_person_uuids = Vec::with_capacity(relationships.len());
+        let mut descriptions = Vec::with_capacity(relationships.len());
+
+        for relationship in relationships {
+            uuids.push(Uuid::now_v7());
+            person_uuids.push(relationship.person_uuid.to_uuid());
+            other_person_uuids.push(relationship.other_person_uuid.to_uuid());
+            descriptions.push(relationship.description);
+        }
+
+        sqlx::query(
+            r#"
+                INSERT INTO person_relationship (uuid, person_uuid, other_person_uuid, description)
+                SELECT *
+                FROM UNNEST($1::UUID[], $2::UUID[], $3::UUID[], $4::TEXT[])
+                ON CONFLICT (person_uuid, other_person_uuid) DO UPDATE
+                SET description = EXCLUDED.description;
+            "#,
+        )
+        .bind(uuids)
+        .bind(person_uuids)
+        .bind(other_person_uuids)
+        .bind(descriptions)
+        .execute(&self.sqlx)
+        .await
+        .map_err(|err| format!("Error creating relationships: {}", err))?;
+
+        Ok(())
+    }
+}
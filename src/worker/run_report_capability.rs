@@ -0,0 +1,194 @@
+use crate::capability::run_report::RunReportCapability;
+use crate::domain::run_report::{CastMember, ReportBookmark, ReportScene};
+use crate::worker::Worker;
+use chrono::{DateTime, Utc};
+use sqlx::Row;
+
+impl RunReportCapability for Worker {
+    async fn get_run_cast(&self, since: DateTime<Utc>) -> Result<Vec<CastMember>, String> {
+        let rows = sqlx::query(
+            r#"
+                WITH attended AS (
+                    SELECT scene_participant.person_uuid,
+                        COUNT(DISTINCT scene_participant.scene_uuid) AS scene_count
+                    FROM scene_participant
+                    JOIN scene ON scene.uuid = scene_participant.scene_uuid
+                    WHERE (scene_participant.left_at IS NULL
+                        OR scene_participant.left_at >= $1::TIMESTAMPTZ)
+                      AND (scene.ended_at IS NULL
+                        OR scene.ended_at >= $1::TIMESTAMPTZ)
+                    GROUP BY scene_participant.person_uuid
+                ),
+                sent AS (
+                    SELECT message.sender_person_uuid AS person_uuid,
+                        COUNT(*) AS message_count
+                    FROM message
+                    WHERE message.sent_at >= $1::TIMESTAMPTZ
+                      AND message.superseded_at IS NULL
+                    GROUP BY message.sender_person_uuid
+                )
+                SELECT person.name,
+                    attended.scene_count,
+                    COALESCE(sent.message_count, 0) AS message_count
+                FROM attended
+                JOIN person ON person.uuid = attended.person_uuid
+                LEFT JOIN sent ON sent.person_uuid = attended.person_uuid
+                ORDER BY message_count DESC, person.name ASC;
+            "#,
+        )
+        .bind(since)
+        .fetch_all(&self.sqlx)
+        .await
+        .map_err(|err| format!("Error fetching run cast: {}", err))?;
+
+        rows.into_iter()
+            .map(|row| {
+                let name = row
+                    .try_get::<String, _>("name")
+                    .map_err(|err| format!("Error reading name from row: {}", err))?;
+
+                let scene_count = row
+                    .try_get::<i64, _>("scene_count")
+                    .map_err(|err| format!("Error reading scene count from row: {}", err))?;
+
+                let message_count = row
+                    .try_get::<i64, _>("message_count")
+                    .map_err(|err| format!("Error reading message count from row: {}", err))?;
+
+                Ok(CastMember {
+                    name,
+                    scene_count,
+                    message_count,
+                })
+            })
+            .collect()
+    }
+
+    async fn get_run_scenes(&self, since: DateTime<Utc>) -> Result<Vec<ReportScene>, String> {
+        let rows = sqlx::query(
+            r#"
+                SELECT scene.name,
+                    scene.started_at,
+                    scene.ended_at,
+                    (
+                        SELECT COUNT(*)
+                        FROM message
+                        WHERE message.scene_uuid = scene.uuid
+                          AND message.sent_at >= $1::TIMESTAMPTZ
+                          AND message.superseded_at IS NULL
+                    ) AS message_count,
+                    ARRAY(
+                        SELECT DISTINCT person.name
+                        FROM scene_participant
+                        JOIN person ON person.uuid = scene_participant.person_uuid
+                        WHERE scene_participant.scene_uuid = scene.uuid
+                          AND (scene_participant.left_at IS NULL
+                            OR scene_participant.left_at >= $1::TIMESTAMPTZ)
+                        ORDER BY person.name
+                    ) AS participant_names,
+                    scene_snapshot.description AS summary
+                FROM scene
+                LEFT JOIN scene_snapshot
+                    ON scene_snapshot.scene_uuid = scene.uuid
+                    AND scene_snapshot.created_at >= $1::TIMESTAMPTZ
+                WHERE scene.ended_at IS NULL
+                   OR scene.ended_at >= $1::TIMESTAMPTZ
+                ORDER BY scene.started_at ASC;
+            "#,
+        )
+        .bind(since)
+        .fetch_all(&self.sqlx)
+        .await
+        .map_err(|err| format!("Error fetching run scenes: {}", err))?;
+
+        rows.into_iter()
+            .map(|row| {
+                let name = row
+                    .try_get::<String, _>("name")
+                    .map_err(|err| format!("Error reading scene name from row: {}", err))?;
+
+                let started_at = row
+                    .try_get::<DateTime<Utc>, _>("started_at")
+                    .map_err(|err| format!("Error reading started at from row: {}", err))?;
+
+                let ended_at = row
+                    .try_get::<Option<DateTime<Utc>>, _>("ended_at")
+                    .map_err(|err| format!("Error reading ended at from row: {}", err))?;
+
+                let message_count = row
+                    .try_get::<i64, _>("message_count")
+                    .map_err(|err| format!("Error reading message count from row: {}", err))?;
+
+                let participant_names = row
+                    .try_get::<Vec<String>, _>("participant_names")
+                    .map_err(|err| format!("Error reading participant names from row: {}", err))?;
+
+                let summary = row
+                    .try_get::<Option<String>, _>("summary")
+                    .map_err(|err| format!("Error reading summary from row: {}", err))?;
+
+                Ok(ReportScene {
+                    name,
+                    started_at,
+                    ended_at,
+                    message_count,
+                    participant_names,
+                    summary,
+                })
+            })
+            .collect()
+    }
+
+    async fn get_run_bookmarks(
+        &self,
+        since: DateTime<Utc>,
+        limit: i64,
+    ) -> Result<Vec<ReportBookmark>, String> {
+        let rows = sqlx::query(
+            r#"
+                SELECT scene.name AS scene_name,
+                    annotation.at,
+                    annotation.item_summary,
+                    annotation.note
+                FROM annotation
+                JOIN scene ON scene.uuid = annotation.scene_uuid
+                WHERE annotation.is_bookmark
+                  AND annotation.at >= $1::TIMESTAMPTZ
+                ORDER BY (annotation.note <> '') DESC, annotation.at DESC
+                LIMIT $2;
+            "#,
+        )
+        .bind(since)
+        .bind(limit)
+        .fetch_all(&self.sqlx)
+        .await
+        .map_err(|err| format!("Error fetching run bookmarks: {}", err))?;
+
+        rows.into_iter()
+            .map(|row| {
+                let scene_name = row
+                    .try_get::<String, _>("scene_name")
+                    .map_err(|err| format!("Error reading scene name from row: {}", err))?;
+
+                let at = row
+                    .try_get::<DateTime<Utc>, _>("at")
+                    .map_err(|err| format!("Error reading at from row: {}", err))?;
+
+                let item_summary = row
+                    .try_get::<String, _>("item_summary")
+                    .map_err(|err| format!("Error reading item summary from row: {}", err))?;
+
+                let note = row
+                    .try_get::<String, _>("note")
+                    .map_err(|err| format!("Error reading note from row: {}", err))?;
+
+                Ok(ReportBookmark {
+                    scene_name,
+                    at,
+                    item_summary,
+                    note,
+                })
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,81 @@
+use crate::capability::annotation::AnnotationCapability;
+use crate::capability::scene_archive::SceneArchiveCapability;
+use crate::domain::annotation::{self, Annotation};
+use crate::domain::scene_archive;
+use crate::domain::scene_uuid::SceneUuid;
+use crate::worker::Worker;
+use std::fs;
+use std::path::Path;
+
+impl SceneArchiveCapability for Worker {
+    async fn cancel_pending_scene_jobs(&self, scene_uuid: &SceneUuid) -> Result<u64, String> {
+        let result = sqlx::query(
+            r#"
+                UPDATE job
+                SET deleted_at = NOW()
+                WHERE data->>'scene_uuid' = $1::TEXT
+                  AND started_at IS NULL
+                  AND finished_at IS NULL
+                  AND deleted_at IS NULL;
+            "#,
+        )
+        .bind(scene_uuid.to_uuid().to_string())
+        .execute(&self.sqlx)
+        .await
+        .map_err(|err| format!("Error cancelling pending scene jobs: {}", err))?;
+
+        Ok(result.rows_affected())
+    }
+
+    async fn get_exported_annotations(
+        &self,
+        scene_uuid: &SceneUuid,
+    ) -> Result<Vec<Annotation>, String> {
+        let annotations = self.get_scene_annotations(scene_uuid).await?;
+
+        Ok(annotation::exported(&annotations)
+            .into_iter()
+            .cloned()
+            .collect())
+    }
+
+    async fn export_scene_transcript(
+        &self,
+        file_name: &str,
+        transcript: &str,
+    ) -> Result<String, String> {
+        let dir = scene_archive::archive_dir();
+
+        fs::create_dir_all(&dir)
+            .map_err(|err| format!("Error creating scene archive directory: {}", err))?;
+
+        let path = Path::new(&dir).join(file_name);
+
+        fs::write(&path, transcript)
+            .map_err(|err| format!("Error writing scene transcript: {}", err))?;
+
+        Ok(path.display().to_string())
+    }
+
+    async fn mark_scene_archived(
+        &self,
+        scene_uuid: &SceneUuid,
+        transcript_path: &str,
+    ) -> Result<(), String> {
+        sqlx::query(
+            r#"
+                UPDATE scene
+                SET archived_at = NOW(),
+                    transcript_path = $2::TEXT
+                WHERE uuid = $1::UUID;
+            "#,
+        )
+        .bind(scene_uuid.to_uuid())
+        .bind(transcript_path)
+        .execute(&self.sqlx)
+        .await
+        .map_err(|err| format!("Error marking scene archived: {}", err))?;
+
+        Ok(())
+    }
+}
@@ -1,18 +1,27 @@
+use crate::capability::clock::ClockCapability;
+use crate::capability::job_runner_settings::JobRunnerSettingsCapability;
 use crate::capability::scene::{
     CurrentScene, NewScene, NewSceneSnapshot, Scene, SceneCapability, SceneParticipant,
     SceneParticipation,
 };
 use crate::domain::actor_uuid::ActorUuid;
+use crate::domain::knowledge_boundary::Attendance;
+use crate::domain::message_audience::HearingRadius;
 use crate::domain::person_name::PersonName;
 use crate::domain::person_uuid::PersonUuid;
+use crate::domain::scene_ambience::{Ambience, Lighting, NoiseLevel, Weather};
 use crate::domain::scene_participant_uuid::SceneParticipantUuid;
 use crate::domain::scene_uuid::SceneUuid;
+use crate::domain::utterance::REAL_WORLD_USER_NAME;
+use crate::domain::world_time::{TimeOfDay, WorldTime};
 use crate::nice_display::NiceDisplay;
 use crate::open_ai::completion::Completion;
 use crate::open_ai::role::Role;
 use crate::worker::Worker;
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 use sqlx::Row;
+use uuid::Uuid;
 
 fn normalize_scene_name(scene_name: &str) -> Result<String, String> {
     let normalized = scene_name
@@ -86,11 +95,12 @@ impl SceneCapability for Worker {
         sqlx::query!(
             r#"
                 UPDATE scene
-                SET ended_at = NOW()
+                SET ended_at = $2::TIMESTAMPTZ
                 WHERE uuid = $1::UUID
                   AND ended_at IS NULL;
             "#,
             scene_uuid.to_uuid(),
+            self.now(),
         )
         .execute(&self.sqlx)
         .await
@@ -99,11 +109,12 @@ impl SceneCapability for Worker {
         sqlx::query!(
             r#"
                 UPDATE scene_participant
-                SET left_at = NOW()
+                SET left_at = $2::TIMESTAMPTZ
                 WHERE scene_uuid = $1::UUID
                   AND left_at IS NULL;
             "#,
             scene_uuid.to_uuid(),
+            self.now(),
         )
         .execute(&self.sqlx)
         .await
@@ -170,10 +181,12 @@ impl SceneCapability for Worker {
                 .await?;
         }
 
+        let joined_at = self.now();
+
         let rec = sqlx::query!(
             r#"
-                INSERT INTO scene_participant (uuid, scene_uuid, person_uuid)
-                SELECT $1::UUID, $2::UUID, person.uuid
+                INSERT INTO scene_participant (uuid, scene_uuid, person_uuid, joined_at)
+                SELECT $1::UUID, $2::UUID, person.uuid, $4::TIMESTAMPTZ
                 FROM person
                 WHERE person.name = $3::TEXT
                 RETURNING uuid, person_uuid;
@@ -181,6 +194,7 @@ impl SceneCapability for Worker {
             SceneParticipantUuid::new().to_uuid(),
             scene_uuid.to_uuid(),
             person_name.as_str(),
+            joined_at,
         )
         .fetch_one(&self.sqlx)
         .await
@@ -197,15 +211,16 @@ impl SceneCapability for Worker {
                     created_at,
                     updated_at
                 )
-                VALUES ($1::UUID, $2::UUID, NOW(), NOW(), 1, NOW(), NOW())
+                VALUES ($1::UUID, $2::UUID, $3::TIMESTAMPTZ, $3::TIMESTAMPTZ, 1, $3::TIMESTAMPTZ, $3::TIMESTAMPTZ)
                 ON CONFLICT (person_uuid, scene_uuid)
                 DO UPDATE
-                SET last_visited_at = NOW(),
+                SET last_visited_at = $3::TIMESTAMPTZ,
                     visit_count = person_scene_visit.visit_count + 1,
-                    updated_at = NOW();
+                    updated_at = $3::TIMESTAMPTZ;
             "#,
             rec.person_uuid,
             scene_uuid.to_uuid(),
+            joined_at,
         )
         .execute(&self.sqlx)
         .await
@@ -224,7 +239,7 @@ impl SceneCapability for Worker {
         let rec = sqlx::query!(
             r#"
                 UPDATE scene_participant
-                SET left_at = NOW()
+                SET left_at = $3::TIMESTAMPTZ
                 WHERE scene_participant.scene_uuid = $1::UUID
                   AND scene_participant.person_uuid = (SELECT person.uuid FROM person WHERE person.name = $2::TEXT)
                   AND scene_participant.left_at IS NULL
@@ -232,6 +247,7 @@ impl SceneCapability for Worker {
             "#,
             scene_uuid.to_uuid(),
             person_name.as_str(),
+            self.now(),
         )
         .fetch_one(&self.sqlx)
         .await
@@ -302,9 +318,10 @@ impl SceneCapability for Worker {
     ) -> Result<(), String> {
         sqlx::query!(
             r#"
-                INSERT INTO scene_snapshot (scene_uuid, description)
-                VALUES ($1::UUID, $2::TEXT);
+                INSERT INTO scene_snapshot (uuid, scene_uuid, description)
+                VALUES ($1::UUID, $2::UUID, $3::TEXT);
             "#,
+            Uuid::now_v7(),
             new_scene_snapshot.scene_uuid.to_uuid(),
             new_scene_snapshot.description,
         )
@@ -368,6 +385,11 @@ impl SceneCapability for Worker {
         &self,
         scene_uuid: &SceneUuid,
     ) -> Result<Vec<SceneParticipant>, String> {
+        if let Some(participants) = self.scene_read_cache.get_participants(scene_uuid) {
+            return Ok(participants);
+        }
+        let generation = self.scene_read_cache.generation();
+
         let participant_rows = sqlx::query!(
             r#"
                 SELECT
@@ -397,11 +419,14 @@ impl SceneCapability for Worker {
         let is_real_world_user_in_scene = self.is_real_world_user_in_scene(scene_uuid).await?;
         if is_real_world_user_in_scene {
             participants.push(SceneParticipant {
-                person_name: PersonName::from_string("Chadtech".to_string()),
+                person_name: PersonName::from_string(REAL_WORLD_USER_NAME.to_string()),
                 actor_uuid: ActorUuid::RealWorldUser,
             });
         }
 
+        self.scene_read_cache
+            .put_participants(scene_uuid, generation, participants.clone());
+
         Ok(participants)
     }
 
@@ -440,6 +465,53 @@ impl SceneCapability for Worker {
         Ok(participation_history)
     }
 
+    async fn get_persons_attendance(
+        &self,
+        person_uuid: &PersonUuid,
+    ) -> Result<Vec<Attendance>, String> {
+        let rows = sqlx::query(
+            r#"
+                SELECT
+                    scene.uuid,
+                    scene.name,
+                    scene_participant.joined_at,
+                    scene_participant.left_at
+                FROM scene_participant
+                JOIN scene ON scene.uuid = scene_participant.scene_uuid
+                WHERE scene_participant.person_uuid = $1::UUID
+                ORDER BY scene_participant.joined_at DESC;
+            "#,
+        )
+        .bind(person_uuid.to_uuid())
+        .fetch_all(&self.sqlx)
+        .await
+        .map_err(|err| format!("Error fetching person's attendance: {}", err))?;
+
+        rows.into_iter()
+            .map(|row| {
+                let scene_uuid = row
+                    .try_get::<Uuid, _>("uuid")
+                    .map_err(|err| format!("Error reading uuid from row: {}", err))?;
+                let scene_name = row
+                    .try_get::<String, _>("name")
+                    .map_err(|err| format!("Error reading name from row: {}", err))?;
+                let joined_at = row
+                    .try_get::<DateTime<Utc>, _>("joined_at")
+                    .map_err(|err| format!("Error reading joined_at from row: {}", err))?;
+                let left_at = row
+                    .try_get::<Option<DateTime<Utc>>, _>("left_at")
+                    .map_err(|err| format!("Error reading left_at from row: {}", err))?;
+
+                Ok(Attendance {
+                    scene_uuid: SceneUuid::from_uuid(scene_uuid),
+                    scene_name,
+                    joined_at,
+                    left_at,
+                })
+            })
+            .collect()
+    }
+
     async fn set_real_world_user_in_scene(
         &self,
         scene_uuid: &SceneUuid,
@@ -448,11 +520,12 @@ impl SceneCapability for Worker {
         if is_in_scene {
             sqlx::query!(
                 r#"
-                    INSERT INTO real_world_user_scene_presence (scene_uuid)
-                    VALUES ($1::UUID)
+                    INSERT INTO real_world_user_scene_presence (scene_uuid, created_at)
+                    VALUES ($1::UUID, $2::TIMESTAMPTZ)
                     ON CONFLICT (scene_uuid) DO NOTHING;
                 "#,
                 scene_uuid.to_uuid(),
+                self.now(),
             )
             .execute(&self.sqlx)
             .await
@@ -506,10 +579,59 @@ impl SceneCapability for Worker {
         Ok(maybe_rec.map(|rec| rec.name))
     }
 
+    async fn get_scene_hearing_radius(
+        &self,
+        scene_uuid: &SceneUuid,
+    ) -> Result<HearingRadius, String> {
+        let row = sqlx::query(
+            r#"
+                SELECT hearing_radius
+                FROM scene
+                WHERE uuid = $1::UUID;
+            "#,
+        )
+        .bind(scene_uuid.to_uuid())
+        .fetch_one(&self.sqlx)
+        .await
+        .map_err(|err| format!("Error fetching scene hearing radius: {}", err))?;
+
+        let hearing_radius = row
+            .try_get::<String, _>("hearing_radius")
+            .map_err(|err| format!("Error reading hearing_radius: {}", err))?;
+
+        HearingRadius::from_name(hearing_radius.as_str())
+    }
+
+    async fn set_scene_hearing_radius(
+        &self,
+        scene_uuid: &SceneUuid,
+        hearing_radius: HearingRadius,
+    ) -> Result<(), String> {
+        sqlx::query(
+            r#"
+                UPDATE scene
+                SET hearing_radius = $2::TEXT
+                WHERE uuid = $1::UUID;
+            "#,
+        )
+        .bind(scene_uuid.to_uuid())
+        .bind(hearing_radius.to_name())
+        .execute(&self.sqlx)
+        .await
+        .map_err(|err| format!("Error setting scene hearing radius: {}", err))?;
+
+        Ok(())
+    }
+
     async fn get_scene_description(
         &self,
         scene_uuid: &SceneUuid,
     ) -> Result<Option<String>, String> {
+        if let Some(description) = self.scene_read_cache.get_description(scene_uuid) {
+            return Ok(description);
+        }
+        let generation = self.scene_read_cache.generation();
+
         let maybe_rec = sqlx::query!(
             r#"
                 SELECT description
@@ -524,7 +646,147 @@ impl SceneCapability for Worker {
         .await
         .map_err(|err| format!("Error fetching scene description: {}", err))?;
 
-        Ok(maybe_rec.map(|rec| rec.description))
+        let description = maybe_rec.map(|rec| rec.description);
+        self.scene_read_cache
+            .put_description(scene_uuid, generation, description.clone());
+
+        Ok(description)
+    }
+
+    async fn set_scene_time_of_day(
+        &self,
+        scene_uuid: &SceneUuid,
+        time_of_day: TimeOfDay,
+    ) -> Result<(), String> {
+        let active_ms = self.get_active_clock_ms().await?;
+
+        sqlx::query(
+            r#"
+                UPDATE scene
+                SET clock_offset_ms = $2::BIGINT
+                WHERE uuid = $1::UUID;
+            "#,
+        )
+        .bind(scene_uuid.to_uuid())
+        .bind(time_of_day.clock_offset_ms(active_ms))
+        .execute(&self.sqlx)
+        .await
+        .map_err(|err| format!("Error setting scene time of day: {}", err))?;
+
+        Ok(())
+    }
+
+    async fn get_scene_world_time(&self, scene_uuid: &SceneUuid) -> Result<WorldTime, String> {
+        let row = sqlx::query(
+            r#"
+                SELECT
+                    COALESCE(
+                        (SELECT active_ms FROM active_clock WHERE id = TRUE),
+                        0
+                    ) AS active_ms,
+                    scene.clock_offset_ms
+                FROM scene
+                WHERE scene.uuid = $1::UUID;
+            "#,
+        )
+        .bind(scene_uuid.to_uuid())
+        .fetch_optional(&self.sqlx)
+        .await
+        .map_err(|err| format!("Error fetching scene world time: {}", err))?
+        .ok_or_else(|| format!("Scene {} not found", scene_uuid))?;
+
+        let active_ms = row
+            .try_get::<i64, _>("active_ms")
+            .map_err(|err| format!("Error reading active_ms from row: {}", err))?;
+        let clock_offset_ms = row
+            .try_get::<i64, _>("clock_offset_ms")
+            .map_err(|err| format!("Error reading clock_offset_ms from row: {}", err))?;
+
+        Ok(WorldTime::new(active_ms, clock_offset_ms))
+    }
+
+    async fn get_scene_ambience(&self, scene_uuid: &SceneUuid) -> Result<Option<Ambience>, String> {
+        let maybe_row = sqlx::query(
+            r#"
+                SELECT weather, noise, lighting
+                FROM scene_ambience_change
+                WHERE scene_uuid = $1::UUID
+                ORDER BY changed_at DESC
+                LIMIT 1;
+            "#,
+        )
+        .bind(scene_uuid.to_uuid())
+        .fetch_optional(&self.sqlx)
+        .await
+        .map_err(|err| format!("Error fetching scene ambience: {}", err))?;
+
+        let row = match maybe_row {
+            Some(row) => row,
+            None => return Ok(None),
+        };
+
+        let read_error = |err: sqlx::Error| format!("Error reading scene ambience: {}", err);
+
+        Ok(Some(Ambience {
+            weather: Weather::parse(&row.try_get::<String, _>("weather").map_err(read_error)?)?,
+            noise: NoiseLevel::parse(&row.try_get::<String, _>("noise").map_err(read_error)?)?,
+            lighting: Lighting::parse(&row.try_get::<String, _>("lighting").map_err(read_error)?)?,
+        }))
+    }
+
+    async fn set_scene_ambience(
+        &self,
+        scene_uuid: &SceneUuid,
+        ambience: Ambience,
+    ) -> Result<(), String> {
+        sqlx::query(
+            r#"
+                INSERT INTO scene_ambience_change (uuid, scene_uuid, weather, noise, lighting)
+                VALUES ($1::UUID, $2::UUID, $3::TEXT, $4::TEXT, $5::TEXT);
+            "#,
+        )
+        .bind(Uuid::now_v7())
+        .bind(scene_uuid.to_uuid())
+        .bind(ambience.weather.to_name())
+        .bind(ambience.noise.to_name())
+        .bind(ambience.lighting.to_name())
+        .execute(&self.sqlx)
+        .await
+        .map_err(|err| format!("Error setting scene ambience: {}", err))?;
+
+        Ok(())
+    }
+
+    async fn get_scene_participant_chattiness(
+        &self,
+        scene_uuid: &SceneUuid,
+    ) -> Result<Vec<(PersonUuid, f64)>, String> {
+        let rows = sqlx::query(
+            r#"
+                SELECT person.uuid AS person_uuid, person.chattiness
+                FROM scene_participant
+                JOIN person ON person.uuid = scene_participant.person_uuid
+                WHERE scene_participant.scene_uuid = $1::UUID
+                  AND scene_participant.left_at IS NULL;
+            "#,
+        )
+        .bind(scene_uuid.to_uuid())
+        .fetch_all(&self.sqlx)
+        .await
+        .map_err(|err| format!("Error fetching scene participant chattiness: {}", err))?;
+
+        rows.into_iter()
+            .map(|row| {
+                let person_uuid = row
+                    .try_get::<uuid::Uuid, _>("person_uuid")
+                    .map_err(|err| format!("Error reading person_uuid from row: {}", err))?;
+                let chattiness = row
+                    .try_get::<f64, _>("chattiness")
+                    .map_err(|err| format!("Error reading chattiness from row: {}", err))?;
+
+                Ok((PersonUuid::from_uuid(person_uuid), chattiness))
+            })
+            .collect()
     }
 
     async fn create_scene_from_travel(
@@ -567,7 +829,7 @@ impl SceneCapability for Worker {
         );
 
         let response = completion
-            .send_request(&self.open_ai_key, self.reqwest_client.clone())
+            .send_request(self.open_ai_key.as_ref(), self.open_ai_client.clone())
             .await
             .map_err(|err| format!("Failed to generate scene description: {}", err.message()))?;
 
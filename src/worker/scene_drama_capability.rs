@@ -0,0 +1,105 @@
+use crate::capability::scene_drama::{DramaticScene, SceneDramaCapability};
+use crate::domain::scene_uuid::SceneUuid;
+use crate::worker::Worker;
+use sqlx::Row;
+use uuid::Uuid;
+
+impl SceneDramaCapability for Worker {
+    async fn get_dramatic_scenes(&self) -> Result<Vec<DramaticScene>, String> {
+        let rows = sqlx::query(
+            r#"
+                SELECT scene.uuid AS scene_uuid, scene.drama_intensity
+                FROM scene
+                WHERE scene.ended_at IS NULL
+                  AND scene.drama_intensity > 0
+                  AND EXISTS (
+                      SELECT 1
+                      FROM scene_participant
+                      JOIN person ON person.uuid = scene_participant.person_uuid
+                      WHERE scene_participant.scene_uuid = scene.uuid
+                        AND scene_participant.left_at IS NULL
+                        AND person.is_enabled
+                        AND NOT person.is_hibernating
+                  );
+            "#,
+        )
+        .fetch_all(&self.sqlx)
+        .await
+        .map_err(|err| format!("Error fetching dramatic scenes: {}", err))?;
+
+        rows.into_iter()
+            .map(|row| {
+                let scene_uuid = row
+                    .try_get::<Uuid, _>("scene_uuid")
+                    .map_err(|err| format!("Error reading scene_uuid from row: {}", err))?;
+                let intensity = row
+                    .try_get::<f64, _>("drama_intensity")
+                    .map_err(|err| format!("Error reading drama_intensity from row: {}", err))?;
+
+                Ok(DramaticScene {
+                    scene_uuid: SceneUuid::from_uuid(scene_uuid),
+                    intensity,
+                })
+            })
+            .collect()
+    }
+
+    async fn get_scene_drama_intensity(&self, scene_uuid: &SceneUuid) -> Result<f64, String> {
+        let row = sqlx::query(
+            r#"
+                SELECT drama_intensity
+                FROM scene
+                WHERE uuid = $1::UUID;
+            "#,
+        )
+        .bind(scene_uuid.to_uuid())
+        .fetch_one(&self.sqlx)
+        .await
+        .map_err(|err| format!("Error fetching scene drama intensity: {}", err))?;
+
+        row.try_get::<f64, _>("drama_intensity")
+            .map_err(|err| format!("Error reading drama_intensity from row: {}", err))
+    }
+
+    async fn set_scene_drama_intensity(
+        &self,
+        scene_uuid: &SceneUuid,
+        intensity: f64,
+    ) -> Result<(), String> {
+        sqlx::query(
+            r#"
+                UPDATE scene
+                SET drama_intensity = $2::DOUBLE PRECISION
+                WHERE uuid = $1::UUID;
+            "#,
+        )
+        .bind(scene_uuid.to_uuid())
+        .bind(intensity.clamp(0.0, 1.0))
+        .execute(&self.sqlx)
+        .await
+        .map_err(|err| format!("Error updating scene drama intensity: {}", err))?;
+
+        Ok(())
+    }
+
+    async fn add_scene_event(
+        &self,
+        scene_uuid: &SceneUuid,
+        description: &str,
+    ) -> Result<(), String> {
+        sqlx::query(
+            r#"
+                INSERT INTO scene_event (uuid, scene_uuid, description)
+                VALUES ($1::UUID, $2::UUID, $3::TEXT);
+            "#,
+        )
+        .bind(Uuid::now_v7())
+        .bind(scene_uuid.to_uuid())
+        .bind(description)
+        .execute(&self.sqlx)
+        .await
+        .map_err(|err| format!("Error adding scene event: {}", err))?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,264 @@
+use crate::capability::scene_goal::SceneGoalCapability;
+use crate::domain::scene_goal::{self, OpenSceneGoal, SceneGoal, SceneGoalMet, TranscriptLine};
+use crate::domain::scene_uuid::SceneUuid;
+use crate::nice_display::NiceDisplay;
+use crate::open_ai::completion::Completion;
+use crate::open_ai::model::Model;
+use crate::open_ai::role::Role;
+use crate::worker::Worker;
+use chrono::{DateTime, Utc};
+use sqlx::Row;
+use uuid::Uuid;
+
+impl SceneGoalCapability for Worker {
+    async fn set_scene_goal(&self, scene_uuid: &SceneUuid, goal: &SceneGoal) -> Result<(), String> {
+        if goal.is_empty() {
+            sqlx::query(
+                r#"
+                    DELETE FROM scene_goal
+                    WHERE scene_uuid = $1::UUID;
+                "#,
+            )
+            .bind(scene_uuid.to_uuid())
+            .execute(&self.sqlx)
+            .await
+            .map_err(|err| format!("Error removing scene goal: {}", err))?;
+
+            return Ok(());
+        }
+
+        sqlx::query(
+            r#"
+                INSERT INTO scene_goal (scene_uuid, deadline_active_ms, closing_phrase, consensus_on)
+                VALUES ($1::UUID, $2::BIGINT, $3::TEXT, $4::TEXT)
+                ON CONFLICT (scene_uuid) DO UPDATE
+                SET deadline_active_ms = EXCLUDED.deadline_active_ms,
+                    closing_phrase = EXCLUDED.closing_phrase,
+                    consensus_on = EXCLUDED.consensus_on,
+                    set_at = NOW(),
+                    met_at = NULL,
+                    met_reason = NULL;
+            "#,
+        )
+        .bind(scene_uuid.to_uuid())
+        .bind(goal.deadline_active_ms)
+        .bind(goal.closing_phrase.clone())
+        .bind(goal.consensus_on.clone())
+        .execute(&self.sqlx)
+        .await
+        .map_err(|err| format!("Error saving scene goal: {}", err))?;
+
+        Ok(())
+    }
+
+    async fn get_scene_goal(&self, scene_uuid: &SceneUuid) -> Result<Option<SceneGoal>, String> {
+        let maybe_row = sqlx::query(
+            r#"
+                SELECT deadline_active_ms, closing_phrase, consensus_on
+                FROM scene_goal
+                WHERE scene_uuid = $1::UUID;
+            "#,
+        )
+        .bind(scene_uuid.to_uuid())
+        .fetch_optional(&self.sqlx)
+        .await
+        .map_err(|err| format!("Error fetching scene goal: {}", err))?;
+
+        match maybe_row {
+            Some(row) => Ok(Some(read_goal(&row)?)),
+            None => Ok(None),
+        }
+    }
+
+    async fn get_open_scene_goals(&self) -> Result<Vec<OpenSceneGoal>, String> {
+        let rows = sqlx::query(
+            r#"
+                SELECT
+                    scene_goal.scene_uuid,
+                    scene_goal.deadline_active_ms,
+                    scene_goal.closing_phrase,
+                    scene_goal.consensus_on,
+                    scene_goal.set_at
+                FROM scene_goal
+                JOIN scene ON scene.uuid = scene_goal.scene_uuid
+                WHERE scene_goal.met_at IS NULL
+                  AND scene.ended_at IS NULL;
+            "#,
+        )
+        .fetch_all(&self.sqlx)
+        .await
+        .map_err(|err| format!("Error fetching open scene goals: {}", err))?;
+
+        rows.into_iter()
+            .map(|row| {
+                let scene_uuid = row
+                    .try_get::<Uuid, _>("scene_uuid")
+                    .map_err(|err| format!("Error reading scene_uuid from row: {}", err))?;
+                let set_at = row
+                    .try_get::<DateTime<Utc>, _>("set_at")
+                    .map_err(|err| format!("Error reading set_at from row: {}", err))?;
+
+                Ok(OpenSceneGoal {
+                    scene_uuid: SceneUuid::from_uuid(scene_uuid),
+                    goal: read_goal(&row)?,
+                    set_at,
+                })
+            })
+            .collect()
+    }
+
+    async fn get_scene_transcript_since(
+        &self,
+        scene_uuid: &SceneUuid,
+        since: DateTime<Utc>,
+    ) -> Result<Vec<TranscriptLine>, String> {
+        let rows = sqlx::query(
+            r#"
+                SELECT
+                    COALESCE(person.name, 'Chadtech') AS speaker_name,
+                    message_content.body AS content,
+                    message.sent_at
+                FROM message
+                JOIN message_content ON message_content.hash = message.content_hash
+                LEFT JOIN person ON person.uuid = message.sender_person_uuid
+                WHERE message.scene_uuid = $1::UUID
+                  AND message.sent_at >= $2
+                  AND message.audience = 'everyone'
+                  AND message.superseded_at IS NULL
+                ORDER BY message.sent_at;
+            "#,
+        )
+        .bind(scene_uuid.to_uuid())
+        .bind(since)
+        .fetch_all(&self.sqlx)
+        .await
+        .map_err(|err| format!("Error fetching scene transcript: {}", err))?;
+
+        rows.into_iter()
+            .map(|row| {
+                let speaker_name = row
+                    .try_get::<String, _>("speaker_name")
+                    .map_err(|err| format!("Error reading speaker_name from row: {}", err))?;
+                let content = row
+                    .try_get::<String, _>("content")
+                    .map_err(|err| format!("Error reading content from row: {}", err))?;
+                let sent_at = row
+                    .try_get::<DateTime<Utc>, _>("sent_at")
+                    .map_err(|err| format!("Error reading sent_at from row: {}", err))?;
+
+                Ok(TranscriptLine {
+                    speaker_name,
+                    content,
+                    sent_at,
+                })
+            })
+            .collect()
+    }
+
+    async fn judge_scene_consensus(
+        &self,
+        consensus_on: &str,
+        transcript: &str,
+    ) -> Result<bool, String> {
+        let mut completion = Completion::new();
+        completion.set_model(Model::Gpt5Mini);
+        completion.add_tool_call(scene_goal::consensus_tool().into());
+        completion.add_message(
+            Role::System,
+            "You read conversations from a social simulation and decide whether the people in them have agreed on something. Suggestions, maybes and agreement from only some of them do not count. Use only the provided tool call and call it exactly once.",
+        );
+        completion.add_message(
+            Role::User,
+            format!(
+                "Have they agreed on {}?\n\nConversation:\n{}",
+                consensus_on, transcript
+            )
+            .as_str(),
+        );
+
+        let response = completion
+            .send_request(self.open_ai_key.as_ref(), self.open_ai_client.clone())
+            .await
+            .map_err(|err| err.message())?;
+
+        let tool_calls = response.as_tool_calls().map_err(|err| err.message())?;
+
+        let call = tool_calls
+            .iter()
+            .find(|call| call.name == scene_goal::CONSENSUS_TOOL_NAME)
+            .ok_or_else(|| "The consensus judge did not report a result".to_string())?;
+
+        scene_goal::consensus_from_tool_call(call)
+    }
+
+    async fn summarize_scene_ending(
+        &self,
+        scene_description: &str,
+        transcript: &str,
+        met: &SceneGoalMet,
+    ) -> Result<String, String> {
+        let mut completion = Completion::new();
+        completion.add_message(
+            Role::System,
+            "You write the closing description of a scene in a social simulation, after it has come to an end. Write two to four sentences in the third person and present tense describing the place as it is left and how things stand between the people who were there. Only include what follows from the scene description and transcript; do not invent events.",
+        );
+        completion.add_message(
+            Role::User,
+            format!(
+                "Scene description:\n{}\n\nWhy the scene ended:\n{}\n\nWhat was said:\n{}",
+                scene_description,
+                met.to_text(),
+                transcript
+            )
+            .as_str(),
+        );
+
+        let response = completion
+            .send_request(self.open_ai_key.as_ref(), self.open_ai_client.clone())
+            .await
+            .map_err(|err| err.message())?;
+
+        response.as_message().map_err(|err| err.message())
+    }
+
+    async fn mark_scene_goal_met(
+        &self,
+        scene_uuid: &SceneUuid,
+        met: &SceneGoalMet,
+    ) -> Result<bool, String> {
+        let result = sqlx::query(
+            r#"
+                UPDATE scene_goal
+                SET met_at = NOW(),
+                    met_reason = $2::TEXT
+                WHERE scene_uuid = $1::UUID
+                  AND met_at IS NULL;
+            "#,
+        )
+        .bind(scene_uuid.to_uuid())
+        .bind(met.to_text())
+        .execute(&self.sqlx)
+        .await
+        .map_err(|err| format!("Error marking scene goal met: {}", err))?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
+
+fn read_goal(row: &sqlx::postgres::PgRow) -> Result<SceneGoal, String> {
+    let deadline_active_ms = row
+        .try_get::<Option<i64>, _>("deadline_active_ms")
+        .map_err(|err| format!("Error reading deadline_active_ms from row: {}", err))?;
+    let closing_phrase = row
+        .try_get::<Option<String>, _>("closing_phrase")
+        .map_err(|err| format!("Error reading closing_phrase from row: {}", err))?;
+    let consensus_on = row
+        .try_get::<Option<String>, _>("consensus_on")
+        .map_err(|err| format!("Error reading consensus_on from row: {}", err))?;
+
+    Ok(SceneGoal {
+        deadline_active_ms,
+        closing_phrase,
+        consensus_on,
+    })
+}
@@ -0,0 +1,110 @@
+use crate::capability::scene_invitation::{NewSceneInvitation, SceneInvitationCapability};
+use crate::domain::person_uuid::PersonUuid;
+use crate::domain::scene_invitation::SceneInvitation;
+use crate::domain::scene_invitation_uuid::SceneInvitationUuid;
+use crate::domain::scene_uuid::SceneUuid;
+use crate::worker::Worker;
+use sqlx::Row;
+use uuid::Uuid;
+
+impl SceneInvitationCapability for Worker {
+    async fn create_scene_invitation(
+        &self,
+        new_invitation: NewSceneInvitation,
+    ) -> Result<SceneInvitationUuid, String> {
+        let invitation_uuid = SceneInvitationUuid::new();
+
+        sqlx::query(
+            r#"
+                INSERT INTO scene_invitation (
+                    uuid,
+                    scene_uuid,
+                    inviter_person_uuid,
+                    invitee_person_uuid
+                )
+                VALUES ($1::UUID, $2::UUID, $3::UUID, $4::UUID);
+            "#,
+        )
+        .bind(invitation_uuid.to_uuid())
+        .bind(new_invitation.scene_uuid.to_uuid())
+        .bind(new_invitation.inviter_person_uuid.to_uuid())
+        .bind(new_invitation.invitee_person_uuid.to_uuid())
+        .execute(&self.sqlx)
+        .await
+        .map_err(|err| format!("Error inserting scene invitation: {}", err))?;
+
+        Ok(invitation_uuid)
+    }
+
+    async fn get_pending_scene_invitations(
+        &self,
+        person_uuid: &PersonUuid,
+    ) -> Result<Vec<SceneInvitation>, String> {
+        let rows = sqlx::query(
+            r#"
+                SELECT
+                    scene_invitation.uuid,
+                    scene_invitation.scene_uuid,
+                    scene.name AS scene_name,
+                    inviter.name AS inviter_name
+                FROM scene_invitation
+                JOIN scene ON scene.uuid = scene_invitation.scene_uuid
+                JOIN person inviter ON inviter.uuid = scene_invitation.inviter_person_uuid
+                WHERE scene_invitation.invitee_person_uuid = $1::UUID
+                  AND scene_invitation.accepted_at IS NULL
+                  AND scene_invitation.declined_at IS NULL
+                  AND scene.ended_at IS NULL
+                ORDER BY scene_invitation.created_at ASC;
+            "#,
+        )
+        .bind(person_uuid.to_uuid())
+        .fetch_all(&self.sqlx)
+        .await
+        .map_err(|err| format!("Error fetching pending scene invitations: {}", err))?;
+
+        rows.iter()
+            .map(|row| {
+                Ok(SceneInvitation {
+                    uuid: SceneInvitationUuid::from_uuid(
+                        row.try_get::<Uuid, _>("uuid")
+                            .map_err(|err| format!("Error reading invitation uuid: {}", err))?,
+                    ),
+                    scene_uuid: SceneUuid::from_uuid(
+                        row.try_get::<Uuid, _>("scene_uuid")
+                            .map_err(|err| format!("Error reading invitation scene: {}", err))?,
+                    ),
+                    scene_name: row
+                        .try_get::<String, _>("scene_name")
+                        .map_err(|err| format!("Error reading invitation scene name: {}", err))?,
+                    inviter_name: row
+                        .try_get::<String, _>("inviter_name")
+                        .map_err(|err| format!("Error reading inviter name: {}", err))?,
+                })
+            })
+            .collect()
+    }
+
+    async fn respond_to_scene_invitations(
+        &self,
+        person_uuid: &PersonUuid,
+        current_scene_uuid: Option<&SceneUuid>,
+    ) -> Result<(), String> {
+        sqlx::query(
+            r#"
+                UPDATE scene_invitation
+                SET accepted_at = CASE WHEN scene_uuid = $2::UUID THEN now() END,
+                    declined_at = CASE WHEN scene_uuid = $2::UUID THEN NULL ELSE now() END
+                WHERE invitee_person_uuid = $1::UUID
+                  AND accepted_at IS NULL
+                  AND declined_at IS NULL;
+            "#,
+        )
+        .bind(person_uuid.to_uuid())
+        .bind(current_scene_uuid.map(|scene_uuid| scene_uuid.to_uuid()))
+        .execute(&self.sqlx)
+        .await
+        .map_err(|err| format!("Error answering scene invitations: {}", err))?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,54 @@
+use crate::domain::scene_read_cache::SceneReadCache;
+use crate::domain::scene_uuid::SceneUuid;
+use sqlx::postgres::PgListener;
+use sqlx::Postgres;
+use std::time::Duration;
+use uuid::Uuid;
+
+const CHANNEL: &str = "scene_changed";
+const RECONNECT_DELAY: Duration = Duration::from_secs(5);
+
+/// Keeps the cache in step with the database for as long as the worker
+/// runs. While the connection is down the cache is turned off, since
+/// changes made then would go unnoticed.
+pub fn spawn_listener(pool: sqlx::Pool<Postgres>, cache: SceneReadCache) {
+    tokio::spawn(async move {
+        loop {
+            if let Err(err) = listen(&pool, &cache).await {
+                tracing::warn!("Scene read cache stopped listening: {}", err);
+            }
+            cache.set_live(false);
+            tokio::time::sleep(RECONNECT_DELAY).await;
+        }
+    });
+}
+
+async fn listen(pool: &sqlx::Pool<Postgres>, cache: &SceneReadCache) -> Result<(), String> {
+    let mut listener = PgListener::connect_with(pool)
+        .await
+        .map_err(|err| format!("Error connecting the scene change listener: {}", err))?;
+
+    listener
+        .listen(CHANNEL)
+        .await
+        .map_err(|err| format!("Error listening for scene changes: {}", err))?;
+
+    cache.set_live(true);
+
+    loop {
+        let notification = listener
+            .try_recv()
+            .await
+            .map_err(|err| format!("Error receiving a scene change: {}", err))?;
+
+        match notification {
+            // The listener reconnected on its own, so anything could have
+            // changed in between
+            None => cache.clear(),
+            Some(notification) => match Uuid::parse_str(notification.payload()) {
+                Ok(uuid) => cache.invalidate(&SceneUuid::from_uuid(uuid)),
+                Err(_) => cache.clear(),
+            },
+        }
+    }
+}
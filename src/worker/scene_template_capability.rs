@@ -0,0 +1,132 @@
+use crate::capability::scene_template::SceneTemplateCapability;
+use crate::domain::person_name::PersonName;
+use crate::domain::scene_template::SceneTemplate;
+use crate::domain::world_time::TimeOfDay;
+use crate::worker::Worker;
+use sqlx::postgres::PgRow;
+use sqlx::Row;
+use uuid::Uuid;
+
+impl SceneTemplateCapability for Worker {
+    async fn save_scene_template(&self, template: SceneTemplate) -> Result<(), String> {
+        let template_name = template.name.trim().to_string();
+
+        if template_name.is_empty() {
+            return Err("Scene template name cannot be blank".to_string());
+        }
+
+        let starting_cast = template
+            .starting_cast
+            .iter()
+            .map(|person_name| person_name.as_str().to_string())
+            .collect::<Vec<String>>();
+
+        sqlx::query(
+            r#"
+                INSERT INTO scene_template (
+                    uuid,
+                    name,
+                    name_pattern,
+                    description,
+                    starting_cast,
+                    opening_message,
+                    time_of_day_minutes
+                )
+                VALUES ($1::UUID, $2::TEXT, $3::TEXT, $4::TEXT, $5::TEXT[], $6::TEXT, $7::INTEGER)
+                ON CONFLICT (name) DO UPDATE
+                SET name_pattern = EXCLUDED.name_pattern,
+                    description = EXCLUDED.description,
+                    starting_cast = EXCLUDED.starting_cast,
+                    opening_message = EXCLUDED.opening_message,
+                    time_of_day_minutes = EXCLUDED.time_of_day_minutes;
+            "#,
+        )
+        .bind(Uuid::now_v7())
+        .bind(template_name)
+        .bind(template.name_pattern)
+        .bind(template.description)
+        .bind(starting_cast)
+        .bind(template.opening_message)
+        .bind(
+            template
+                .time_of_day
+                .map(|time_of_day| time_of_day.to_minutes() as i32),
+        )
+        .execute(&self.sqlx)
+        .await
+        .map_err(|err| format!("Error saving scene template: {}", err))?;
+
+        Ok(())
+    }
+
+    async fn get_scene_templates(&self) -> Result<Vec<SceneTemplate>, String> {
+        let rows = sqlx::query(
+            r#"
+                SELECT
+                    name,
+                    name_pattern,
+                    description,
+                    starting_cast,
+                    opening_message,
+                    time_of_day_minutes
+                FROM scene_template
+                ORDER BY name ASC;
+            "#,
+        )
+        .fetch_all(&self.sqlx)
+        .await
+        .map_err(|err| format!("Error fetching scene templates: {}", err))?;
+
+        rows.iter().map(scene_template_from_row).collect()
+    }
+
+    async fn delete_scene_template(&self, template_name: &str) -> Result<(), String> {
+        sqlx::query(
+            r#"
+                DELETE FROM scene_template
+                WHERE name = $1::TEXT;
+            "#,
+        )
+        .bind(template_name)
+        .execute(&self.sqlx)
+        .await
+        .map_err(|err| format!("Error deleting scene template: {}", err))?;
+
+        Ok(())
+    }
+}
+
+fn scene_template_from_row(row: &PgRow) -> Result<SceneTemplate, String> {
+    let name = row
+        .try_get::<String, _>("name")
+        .map_err(|err| format!("Error reading scene template name: {}", err))?;
+    let name_pattern = row
+        .try_get::<String, _>("name_pattern")
+        .map_err(|err| format!("Error reading scene template name_pattern: {}", err))?;
+    let description = row
+        .try_get::<String, _>("description")
+        .map_err(|err| format!("Error reading scene template description: {}", err))?;
+    let starting_cast = row
+        .try_get::<Vec<String>, _>("starting_cast")
+        .map_err(|err| format!("Error reading scene template starting_cast: {}", err))?;
+    let opening_message = row
+        .try_get::<String, _>("opening_message")
+        .map_err(|err| format!("Error reading scene template opening_message: {}", err))?;
+    let time_of_day = row
+        .try_get::<Option<i32>, _>("time_of_day_minutes")
+        .map_err(|err| format!("Error reading scene template time_of_day_minutes: {}", err))?
+        .map(|minutes| TimeOfDay::from_minutes(i64::from(minutes)))
+        .transpose()?;
+
+    Ok(SceneTemplate {
+        name,
+        name_pattern,
+        description,
+        starting_cast: starting_cast
+            .into_iter()
+            .map(PersonName::from_string)
+            .collect(),
+        opening_message,
+        time_of_day,
+    })
+}
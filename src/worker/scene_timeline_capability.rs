@@ -0,0 +1,248 @@
+use crate::capability::scene_timeline::SceneTimelineCapability;
+use crate::domain::scene_ambience::Ambience;
+use crate::domain::scene_timeline::{TimelineItem, TimelinePage, TimelineQuery, TimelineSpeaker};
+use crate::domain::scene_uuid::SceneUuid;
+use crate::domain::utterance::REAL_WORLD_USER_NAME;
+use crate::offload;
+use crate::worker::Worker;
+use chrono::{DateTime, Utc};
+use sqlx::postgres::PgRow;
+use sqlx::Row;
+
+impl SceneTimelineCapability for Worker {
+    async fn get_scene_timeline(
+        &self,
+        scene_uuid: &SceneUuid,
+        query: TimelineQuery,
+    ) -> Result<TimelinePage, String> {
+        // Only the newest page is cached, since that is the one a refresh asks for
+        let newest = query.before.is_none();
+        if newest {
+            if let Some(page) = self.scene_read_cache.get_timeline(scene_uuid, query.limit) {
+                return Ok(page);
+            }
+        }
+        let generation = self.scene_read_cache.generation();
+
+        // One extra row tells us whether there is another page
+        let rows = sqlx::query(
+            r#"
+                SELECT kind, at, message_uuid, person_uuid, person_name, body, audience
+                FROM (
+                    SELECT
+                        'message' AS kind,
+                        message.sent_at AS at,
+                        message.uuid AS message_uuid,
+                        message.sender_person_uuid AS person_uuid,
+                        person.name AS person_name,
+                        message_content.body AS body,
+                        message.audience AS audience
+                    FROM message
+                    JOIN message_content ON message_content.hash = message.content_hash
+                    LEFT JOIN person ON person.uuid = message.sender_person_uuid
+                    WHERE message.scene_uuid = $1::UUID
+                      AND message.superseded_at IS NULL
+
+                    UNION ALL
+
+                    SELECT
+                        'joined',
+                        scene_participant.joined_at,
+                        NULL,
+                        scene_participant.person_uuid,
+                        person.name,
+                        NULL,
+                        NULL
+                    FROM scene_participant
+                    JOIN person ON person.uuid = scene_participant.person_uuid
+                    WHERE scene_participant.scene_uuid = $1::UUID
+
+                    UNION ALL
+
+                    SELECT
+                        'left',
+                        scene_participant.left_at,
+                        NULL,
+                        scene_participant.person_uuid,
+                        person.name,
+                        NULL,
+                        NULL
+                    FROM scene_participant
+                    JOIN person ON person.uuid = scene_participant.person_uuid
+                    WHERE scene_participant.scene_uuid = $1::UUID
+                      AND scene_participant.left_at IS NOT NULL
+
+                    UNION ALL
+
+                    SELECT
+                        'arrival_summary',
+                        scene_arrival_observation.created_at,
+                        NULL,
+                        scene_arrival_observation.person_uuid,
+                        person.name,
+                        scene_arrival_observation.observation,
+                        NULL
+                    FROM scene_arrival_observation
+                    JOIN person ON person.uuid = scene_arrival_observation.person_uuid
+                    WHERE scene_arrival_observation.scene_uuid = $1::UUID
+
+                    UNION ALL
+
+                    SELECT
+                        'scene_snapshot',
+                        scene_snapshot.created_at,
+                        NULL,
+                        NULL,
+                        NULL,
+                        scene_snapshot.description,
+                        NULL
+                    FROM scene_snapshot
+                    WHERE scene_snapshot.scene_uuid = $1::UUID
+
+                    UNION ALL
+
+                    SELECT
+                        'ambience_changed',
+                        scene_ambience_change.changed_at,
+                        NULL,
+                        NULL,
+                        NULL,
+                        json_build_object(
+                            'weather', scene_ambience_change.weather,
+                            'noise', scene_ambience_change.noise,
+                            'lighting', scene_ambience_change.lighting
+                        )::TEXT,
+                        NULL
+                    FROM scene_ambience_change
+                    WHERE scene_ambience_change.scene_uuid = $1::UUID
+
+                    UNION ALL
+
+                    SELECT
+                        'scene_event',
+                        scene_event.created_at,
+                        NULL,
+                        NULL,
+                        NULL,
+                        scene_event.description,
+                        NULL
+                    FROM scene_event
+                    WHERE scene_event.scene_uuid = $1::UUID
+                ) timeline
+                WHERE $2::TIMESTAMPTZ IS NULL OR at < $2::TIMESTAMPTZ
+                ORDER BY at DESC
+                LIMIT $3
+            "#,
+        )
+        .bind(scene_uuid.to_uuid())
+        .bind(query.before)
+        .bind(query.limit.saturating_add(1))
+        .fetch_all(&self.sqlx)
+        .await
+        .map_err(|err| format!("Error fetching scene timeline: {}", err))?;
+
+        let uuid = scene_uuid.to_uuid();
+        let page = offload::run_if(offload::is_heavy_list(rows.len()), move || {
+            let items = rows
+                .iter()
+                .map(row_to_item)
+                .collect::<Result<Vec<TimelineItem>, String>>()?;
+
+            Ok::<_, String>(TimelinePage::from_newest_first(uuid, items, query.limit))
+        })
+        .await??;
+
+        if newest {
+            self.scene_read_cache
+                .put_timeline(scene_uuid, generation, query.limit, page.clone());
+        }
+
+        Ok(page)
+    }
+}
+
+fn row_to_item(row: &PgRow) -> Result<TimelineItem, String> {
+    let kind = row
+        .try_get::<String, _>("kind")
+        .map_err(|err| format!("Error reading timeline kind: {}", err))?;
+    let at = row
+        .try_get::<DateTime<Utc>, _>("at")
+        .map_err(|err| format!("Error reading timeline time: {}", err))?;
+    let person_uuid = row
+        .try_get::<Option<uuid::Uuid>, _>("person_uuid")
+        .map_err(|err| format!("Error reading timeline person uuid: {}", err))?;
+    let person_name = row
+        .try_get::<Option<String>, _>("person_name")
+        .map_err(|err| format!("Error reading timeline person name: {}", err))?;
+    let body = row
+        .try_get::<Option<String>, _>("body")
+        .map_err(|err| format!("Error reading timeline body: {}", err))?;
+
+    let required = |value: Option<String>, field: &str| {
+        value.ok_or_else(|| format!("Timeline {} item is missing its {}", kind, field))
+    };
+    let required_person =
+        || person_uuid.ok_or_else(|| format!("Timeline {} item is missing its person", kind));
+
+    match kind.as_str() {
+        "message" => {
+            let message_uuid = row
+                .try_get::<Option<uuid::Uuid>, _>("message_uuid")
+                .map_err(|err| format!("Error reading timeline message uuid: {}", err))?
+                .ok_or_else(|| "Timeline message item is missing its uuid".to_string())?;
+            let audience = row
+                .try_get::<Option<String>, _>("audience")
+                .map_err(|err| format!("Error reading timeline audience: {}", err))?;
+
+            let sender = match person_uuid {
+                Some(person_uuid) => TimelineSpeaker {
+                    person_uuid: Some(person_uuid),
+                    name: person_name.unwrap_or_else(|| person_uuid.to_string()),
+                },
+                None => TimelineSpeaker {
+                    person_uuid: None,
+                    name: REAL_WORLD_USER_NAME.to_string(),
+                },
+            };
+
+            Ok(TimelineItem::Message {
+                message_uuid,
+                at,
+                sender,
+                content: required(body, "content")?,
+                audience: audience.unwrap_or_else(|| "everyone".to_string()),
+            })
+        }
+        "joined" => Ok(TimelineItem::Joined {
+            at,
+            person_uuid: required_person()?,
+            person_name: required(person_name, "person name")?,
+        }),
+        "left" => Ok(TimelineItem::Left {
+            at,
+            person_uuid: required_person()?,
+            person_name: required(person_name, "person name")?,
+        }),
+        "arrival_summary" => Ok(TimelineItem::ArrivalSummary {
+            at,
+            person_uuid: required_person()?,
+            person_name: required(person_name, "person name")?,
+            summary: required(body, "summary")?,
+        }),
+        "scene_snapshot" => Ok(TimelineItem::SceneSnapshot {
+            at,
+            description: required(body, "description")?,
+        }),
+        "ambience_changed" => {
+            let ambience = serde_json::from_str::<Ambience>(&required(body, "ambience")?)
+                .map_err(|err| format!("Error reading timeline ambience: {}", err))?;
+
+            Ok(TimelineItem::AmbienceChanged { at, ambience })
+        }
+        "scene_event" => Ok(TimelineItem::SceneEvent {
+            at,
+            description: required(body, "description")?,
+        }),
+        _ => Err(format!("Unknown timeline item kind: {}", kind)),
+    }
+}
@@ -0,0 +1,53 @@
+use crate::capability::scene_tone::SceneToneCapability;
+use crate::domain::scene_tone::ToneProfile;
+use crate::domain::scene_uuid::SceneUuid;
+use crate::worker::Worker;
+use sqlx::Row;
+
+impl SceneToneCapability for Worker {
+    async fn get_scene_tone_profile(
+        &self,
+        scene_uuid: &SceneUuid,
+    ) -> Result<Option<ToneProfile>, String> {
+        let row = sqlx::query(
+            r#"
+                SELECT tone_profile
+                FROM scene
+                WHERE uuid = $1::UUID;
+            "#,
+        )
+        .bind(scene_uuid.to_uuid())
+        .fetch_one(&self.sqlx)
+        .await
+        .map_err(|err| format!("Error fetching scene tone profile: {}", err))?;
+
+        let tone_profile = row
+            .try_get::<Option<String>, _>("tone_profile")
+            .map_err(|err| format!("Error reading tone_profile from row: {}", err))?;
+
+        tone_profile
+            .map(|name| ToneProfile::from_name(name.as_str()))
+            .transpose()
+    }
+
+    async fn set_scene_tone_profile(
+        &self,
+        scene_uuid: &SceneUuid,
+        tone_profile: Option<ToneProfile>,
+    ) -> Result<(), String> {
+        sqlx::query(
+            r#"
+                UPDATE scene
+                SET tone_profile = $2::TEXT
+                WHERE uuid = $1::UUID;
+            "#,
+        )
+        .bind(scene_uuid.to_uuid())
+        .bind(tone_profile.map(|tone_profile| tone_profile.to_name()))
+        .execute(&self.sqlx)
+        .await
+        .map_err(|err| format!("Error updating scene tone profile: {}", err))?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,143 @@
+use crate::capability::schema::SchemaCapability;
+use crate::domain::schema_overview::{AppliedMigration, IndexStats, TableStats};
+use crate::worker::Worker;
+use chrono::{DateTime, Utc};
+use sqlx::Row;
+
+impl SchemaCapability for Worker {
+    async fn get_applied_migrations(&self) -> Result<Vec<AppliedMigration>, String> {
+        let is_recorded =
+            sqlx::query("SELECT to_regclass('schema_migration') IS NOT NULL AS exists")
+                .fetch_one(&self.sqlx)
+                .await
+                .map_err(|err| format!("Error checking for schema_migration: {}", err))?
+                .try_get::<bool, _>("exists")
+                .map_err(|err| format!("Error reading exists from row: {}", err))?;
+
+        if !is_recorded {
+            return Ok(vec![]);
+        }
+
+        let rows = sqlx::query(
+            r#"
+                SELECT name, applied_at
+                FROM schema_migration
+                ORDER BY name ASC;
+            "#,
+        )
+        .fetch_all(&self.sqlx)
+        .await
+        .map_err(|err| format!("Error fetching applied migrations: {}", err))?;
+
+        rows.into_iter()
+            .map(|row| {
+                let name = row
+                    .try_get::<String, _>("name")
+                    .map_err(|err| format!("Error reading name from row: {}", err))?;
+                let applied_at = row
+                    .try_get::<DateTime<Utc>, _>("applied_at")
+                    .map_err(|err| format!("Error reading applied_at from row: {}", err))?;
+
+                Ok(AppliedMigration { name, applied_at })
+            })
+            .collect()
+    }
+
+    async fn get_table_stats(&self) -> Result<Vec<TableStats>, String> {
+        let rows = sqlx::query(
+            r#"
+                SELECT
+                    relname AS name,
+                    n_live_tup AS estimated_rows,
+                    pg_total_relation_size(relid) AS total_bytes,
+                    COALESCE(seq_scan, 0) AS seq_scans,
+                    COALESCE(idx_scan, 0) AS index_scans
+                FROM pg_stat_user_tables
+                ORDER BY pg_total_relation_size(relid) DESC, relname ASC;
+            "#,
+        )
+        .fetch_all(&self.sqlx)
+        .await
+        .map_err(|err| format!("Error fetching table stats: {}", err))?;
+
+        rows.into_iter()
+            .map(|row| {
+                let name = row
+                    .try_get::<String, _>("name")
+                    .map_err(|err| format!("Error reading name from row: {}", err))?;
+                let estimated_rows = row
+                    .try_get::<i64, _>("estimated_rows")
+                    .map_err(|err| format!("Error reading estimated_rows from row: {}", err))?;
+                let total_bytes = row
+                    .try_get::<i64, _>("total_bytes")
+                    .map_err(|err| format!("Error reading total_bytes from row: {}", err))?;
+                let seq_scans = row
+                    .try_get::<i64, _>("seq_scans")
+                    .map_err(|err| format!("Error reading seq_scans from row: {}", err))?;
+                let index_scans = row
+                    .try_get::<i64, _>("index_scans")
+                    .map_err(|err| format!("Error reading index_scans from row: {}", err))?;
+
+                Ok(TableStats {
+                    name,
+                    estimated_rows,
+                    total_bytes,
+                    seq_scans,
+                    index_scans,
+                })
+            })
+            .collect()
+    }
+
+    async fn get_index_stats(&self) -> Result<Vec<IndexStats>, String> {
+        let rows = sqlx::query(
+            r#"
+                SELECT
+                    stats.relname AS table_name,
+                    stats.indexrelname AS name,
+                    stats.idx_scan AS scans,
+                    pg_relation_size(stats.indexrelid) AS bytes,
+                    pg_index.indisvalid AS is_valid,
+                    pg_index.indisunique AS is_unique
+                FROM pg_stat_user_indexes stats
+                JOIN pg_index ON pg_index.indexrelid = stats.indexrelid
+                ORDER BY stats.relname ASC, stats.indexrelname ASC;
+            "#,
+        )
+        .fetch_all(&self.sqlx)
+        .await
+        .map_err(|err| format!("Error fetching index stats: {}", err))?;
+
+        rows.into_iter()
+            .map(|row| {
+                let table_name = row
+                    .try_get::<String, _>("table_name")
+                    .map_err(|err| format!("Error reading table_name from row: {}", err))?;
+                let name = row
+                    .try_get::<String, _>("name")
+                    .map_err(|err| format!("Error reading name from row: {}", err))?;
+                let scans = row
+                    .try_get::<i64, _>("scans")
+                    .map_err(|err| format!("Error reading scans from row: {}", err))?;
+                let bytes = row
+                    .try_get::<i64, _>("bytes")
+                    .map_err(|err| format!("Error reading bytes from row: {}", err))?;
+                let is_valid = row
+                    .try_get::<bool, _>("is_valid")
+                    .map_err(|err| format!("Error reading is_valid from row: {}", err))?;
+                let is_unique = row
+                    .try_get::<bool, _>("is_unique")
+                    .map_err(|err| format!("Error reading is_unique from row: {}", err))?;
+
+                Ok(IndexStats {
+                    table_name,
+                    name,
+                    scans,
+                    bytes,
+                    is_valid,
+                    is_unique,
+                })
+            })
+            .collect()
+    }
+}
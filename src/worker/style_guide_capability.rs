@@ -0,0 +1,111 @@
+use crate::capability::style_guide::StyleGuideCapability;
+use crate::domain::person_uuid::PersonUuid;
+use crate::domain::style_guide::{Formality, StyleGuide};
+use crate::worker::Worker;
+use sqlx::postgres::PgRow;
+use sqlx::Row;
+
+impl StyleGuideCapability for Worker {
+    async fn get_world_style_guide(&self) -> Result<StyleGuide, String> {
+        let row = sqlx::query(
+            r#"
+                SELECT max_sentences, dialect, formality
+                FROM style_guide_setting
+                WHERE id = TRUE;
+            "#,
+        )
+        .fetch_optional(&self.sqlx)
+        .await
+        .map_err(|err| format!("Error fetching world style guide: {}", err))?;
+
+        match row {
+            Some(row) => style_guide_from_row(&row),
+            None => Err("World style guide is missing from style_guide_setting".to_string()),
+        }
+    }
+
+    async fn set_world_style_guide(&self, style_guide: &StyleGuide) -> Result<(), String> {
+        sqlx::query(
+            r#"
+                UPDATE style_guide_setting
+                SET max_sentences = $1,
+                    dialect = $2,
+                    formality = $3
+                WHERE id = TRUE;
+            "#,
+        )
+        .bind(style_guide.max_sentences)
+        .bind(style_guide.dialect.trim())
+        .bind(style_guide.formality.map(|formality| formality.to_name()))
+        .execute(&self.sqlx)
+        .await
+        .map_err(|err| format!("Error updating world style guide: {}", err))?;
+
+        Ok(())
+    }
+
+    async fn get_person_style_guide(&self, person_uuid: &PersonUuid) -> Result<StyleGuide, String> {
+        let row = sqlx::query(
+            r#"
+                SELECT style_max_sentences AS max_sentences,
+                    style_dialect AS dialect,
+                    style_formality AS formality
+                FROM person
+                WHERE uuid = $1::UUID;
+            "#,
+        )
+        .bind(person_uuid.to_uuid())
+        .fetch_one(&self.sqlx)
+        .await
+        .map_err(|err| format!("Error fetching person style guide: {}", err))?;
+
+        style_guide_from_row(&row)
+    }
+
+    async fn set_person_style_guide(
+        &self,
+        person_uuid: &PersonUuid,
+        style_guide: &StyleGuide,
+    ) -> Result<(), String> {
+        sqlx::query(
+            r#"
+                UPDATE person
+                SET style_max_sentences = $2,
+                    style_dialect = $3,
+                    style_formality = $4
+                WHERE uuid = $1::UUID;
+            "#,
+        )
+        .bind(person_uuid.to_uuid())
+        .bind(style_guide.max_sentences)
+        .bind(style_guide.dialect.trim())
+        .bind(style_guide.formality.map(|formality| formality.to_name()))
+        .execute(&self.sqlx)
+        .await
+        .map_err(|err| format!("Error updating person style guide: {}", err))?;
+
+        Ok(())
+    }
+}
+
+fn style_guide_from_row(row: &PgRow) -> Result<StyleGuide, String> {
+    let max_sentences = row
+        .try_get::<Option<i32>, _>("max_sentences")
+        .map_err(|err| format!("Error reading max sentences from row: {}", err))?;
+
+    let dialect = row
+        .try_get::<String, _>("dialect")
+        .map_err(|err| format!("Error reading dialect from row: {}", err))?;
+
+    let formality = row
+        .try_get::<Option<String>, _>("formality")
+        .map_err(|err| format!("Error reading formality from row: {}", err))?
+        .map(|name| Formality::from_name(name.as_str()))
+        .transpose()?;
+
+    Ok(StyleGuide {
+        max_sentences,
+        dialect,
+        formality,
+    })
+}
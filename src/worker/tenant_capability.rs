@@ -0,0 +1,266 @@
+use crate::capability::tenant::TenantCapability;
+use crate::domain::scene_uuid::SceneUuid;
+use crate::domain::tenant::{ApiRole, ApiTokenGrant, Tenant, TenantName, OPERATOR_ROLE_NAME};
+use crate::domain::tenant_uuid::TenantUuid;
+use crate::worker::Worker;
+use chrono::{DateTime, Utc};
+use sqlx::Row;
+use uuid::Uuid;
+
+impl TenantCapability for Worker {
+    async fn create_tenant(&self, name: &TenantName) -> Result<TenantUuid, String> {
+        let tenant_uuid = TenantUuid::new();
+
+        sqlx::query(
+            r#"
+                INSERT INTO tenant (uuid, name)
+                VALUES ($1::UUID, $2::TEXT);
+            "#,
+        )
+        .bind(tenant_uuid.to_uuid())
+        .bind(name.as_str())
+        .execute(&self.sqlx)
+        .await
+        .map_err(|err| format!("Error creating tenant: {}", err))?;
+
+        Ok(tenant_uuid)
+    }
+
+    async fn get_tenants(&self) -> Result<Vec<Tenant>, String> {
+        let rows = sqlx::query(
+            r#"
+                SELECT
+                    tenant.uuid,
+                    tenant.name,
+                    tenant.created_at,
+                    (
+                        SELECT COUNT(*)
+                        FROM scene
+                        WHERE scene.tenant_uuid = tenant.uuid
+                    ) AS scene_count,
+                    (
+                        SELECT COUNT(*)
+                        FROM api_token
+                        WHERE api_token.tenant_uuid = tenant.uuid
+                          AND api_token.revoked_at IS NULL
+                    ) AS active_token_count
+                FROM tenant
+                ORDER BY tenant.name;
+            "#,
+        )
+        .fetch_all(&self.sqlx)
+        .await
+        .map_err(|err| format!("Error fetching tenants: {}", err))?;
+
+        rows.into_iter()
+            .map(|row| {
+                let uuid = row
+                    .try_get::<Uuid, _>("uuid")
+                    .map_err(|err| format!("Error reading uuid from row: {}", err))?;
+                let name = row
+                    .try_get::<String, _>("name")
+                    .map_err(|err| format!("Error reading name from row: {}", err))?;
+                let created_at = row
+                    .try_get::<DateTime<Utc>, _>("created_at")
+                    .map_err(|err| format!("Error reading created_at from row: {}", err))?;
+                let scene_count = row
+                    .try_get::<i64, _>("scene_count")
+                    .map_err(|err| format!("Error reading scene_count from row: {}", err))?;
+                let active_token_count = row
+                    .try_get::<i64, _>("active_token_count")
+                    .map_err(|err| format!("Error reading active_token_count from row: {}", err))?;
+
+                Ok(Tenant {
+                    uuid: TenantUuid::from_uuid(uuid),
+                    name: TenantName::parse(&name)?,
+                    created_at,
+                    scene_count,
+                    active_token_count,
+                })
+            })
+            .collect()
+    }
+
+    async fn get_tenant_uuid_by_name(
+        &self,
+        name: &TenantName,
+    ) -> Result<Option<TenantUuid>, String> {
+        let maybe_row = sqlx::query(
+            r#"
+                SELECT uuid
+                FROM tenant
+                WHERE name = $1::TEXT;
+            "#,
+        )
+        .bind(name.as_str())
+        .fetch_optional(&self.sqlx)
+        .await
+        .map_err(|err| format!("Error fetching tenant: {}", err))?;
+
+        match maybe_row {
+            Some(row) => {
+                let uuid = row
+                    .try_get::<Uuid, _>("uuid")
+                    .map_err(|err| format!("Error reading uuid from row: {}", err))?;
+                Ok(Some(TenantUuid::from_uuid(uuid)))
+            }
+            None => Ok(None),
+        }
+    }
+
+    async fn add_api_token(
+        &self,
+        tenant_uuid: &TenantUuid,
+        token_hash: &str,
+        role: ApiRole,
+    ) -> Result<(), String> {
+        sqlx::query(
+            r#"
+                INSERT INTO api_token (uuid, tenant_uuid, token_hash, role)
+                VALUES ($1::UUID, $2::UUID, $3::TEXT, $4::TEXT);
+            "#,
+        )
+        .bind(Uuid::now_v7())
+        .bind(tenant_uuid.to_uuid())
+        .bind(token_hash)
+        .bind(role.to_name())
+        .execute(&self.sqlx)
+        .await
+        .map_err(|err| format!("Error adding api token: {}", err))?;
+
+        Ok(())
+    }
+
+    async fn revoke_api_tokens(&self, tenant_uuid: &TenantUuid) -> Result<u64, String> {
+        let result = sqlx::query(
+            r#"
+                UPDATE api_token
+                SET revoked_at = NOW()
+                WHERE tenant_uuid = $1::UUID
+                  AND revoked_at IS NULL;
+            "#,
+        )
+        .bind(tenant_uuid.to_uuid())
+        .execute(&self.sqlx)
+        .await
+        .map_err(|err| format!("Error revoking api tokens: {}", err))?;
+
+        Ok(result.rows_affected())
+    }
+
+    async fn add_operator_token(&self, token_hash: &str) -> Result<(), String> {
+        sqlx::query(
+            r#"
+                INSERT INTO api_token (uuid, tenant_uuid, token_hash, role)
+                VALUES ($1::UUID, NULL, $2::TEXT, $3::TEXT);
+            "#,
+        )
+        .bind(Uuid::now_v7())
+        .bind(token_hash)
+        .bind(OPERATOR_ROLE_NAME)
+        .execute(&self.sqlx)
+        .await
+        .map_err(|err| format!("Error adding operator token: {}", err))?;
+
+        Ok(())
+    }
+
+    async fn revoke_operator_tokens(&self) -> Result<u64, String> {
+        let result = sqlx::query(
+            r#"
+                UPDATE api_token
+                SET revoked_at = NOW()
+                WHERE role = $1::TEXT
+                  AND revoked_at IS NULL;
+            "#,
+        )
+        .bind(OPERATOR_ROLE_NAME)
+        .execute(&self.sqlx)
+        .await
+        .map_err(|err| format!("Error revoking operator tokens: {}", err))?;
+
+        Ok(result.rows_affected())
+    }
+
+    async fn get_api_token_grant(&self, token_hash: &str) -> Result<Option<ApiTokenGrant>, String> {
+        let maybe_row = sqlx::query(
+            r#"
+                SELECT tenant_uuid, role
+                FROM api_token
+                WHERE token_hash = $1::TEXT
+                  AND revoked_at IS NULL;
+            "#,
+        )
+        .bind(token_hash)
+        .fetch_optional(&self.sqlx)
+        .await
+        .map_err(|err| format!("Error fetching api token: {}", err))?;
+
+        match maybe_row {
+            Some(row) => {
+                let tenant_uuid = row
+                    .try_get::<Option<Uuid>, _>("tenant_uuid")
+                    .map_err(|err| format!("Error reading tenant_uuid from row: {}", err))?;
+                let role = row
+                    .try_get::<String, _>("role")
+                    .map_err(|err| format!("Error reading role from row: {}", err))?;
+
+                if role == OPERATOR_ROLE_NAME {
+                    return Ok(Some(ApiTokenGrant::Operator));
+                }
+
+                let tenant_uuid =
+                    tenant_uuid.ok_or_else(|| format!("The {} api token has no tenant", role))?;
+
+                Ok(Some(ApiTokenGrant::Tenant {
+                    tenant_uuid: TenantUuid::from_uuid(tenant_uuid),
+                    role: ApiRole::parse(&role)?,
+                }))
+            }
+            None => Ok(None),
+        }
+    }
+
+    async fn set_scene_tenant(
+        &self,
+        scene_uuid: &SceneUuid,
+        tenant_uuid: Option<&TenantUuid>,
+    ) -> Result<(), String> {
+        sqlx::query(
+            r#"
+                UPDATE scene
+                SET tenant_uuid = $2::UUID
+                WHERE uuid = $1::UUID;
+            "#,
+        )
+        .bind(scene_uuid.to_uuid())
+        .bind(tenant_uuid.map(|tenant_uuid| tenant_uuid.to_uuid()))
+        .execute(&self.sqlx)
+        .await
+        .map_err(|err| format!("Error setting scene tenant: {}", err))?;
+
+        Ok(())
+    }
+
+    async fn is_scene_in_tenant(
+        &self,
+        scene_uuid: &SceneUuid,
+        tenant_uuid: &TenantUuid,
+    ) -> Result<bool, String> {
+        let maybe_row = sqlx::query(
+            r#"
+                SELECT 1
+                FROM scene
+                WHERE uuid = $1::UUID
+                  AND tenant_uuid = $2::UUID;
+            "#,
+        )
+        .bind(scene_uuid.to_uuid())
+        .bind(tenant_uuid.to_uuid())
+        .fetch_optional(&self.sqlx)
+        .await
+        .map_err(|err| format!("Error checking scene tenant: {}", err))?;
+
+        Ok(maybe_row.is_some())
+    }
+}
@@ -0,0 +1,191 @@
+use crate::capability::topic::TopicCapability;
+use crate::domain::scene_uuid::SceneUuid;
+use crate::domain::topic::{Topic, TopicCount, TopicScene, TrendingTopic, UntaggedScene};
+use crate::worker::Worker;
+use chrono::{DateTime, Utc};
+use sqlx::Row;
+use uuid::Uuid;
+
+impl TopicCapability for Worker {
+    async fn get_untagged_scenes(&self) -> Result<Vec<UntaggedScene>, String> {
+        let rows = sqlx::query(
+            r#"
+                SELECT
+                    scene.uuid,
+                    scene.topics_tagged_through,
+                    MAX(message.sent_at) AS latest_message_at
+                FROM scene
+                JOIN message ON message.scene_uuid = scene.uuid
+                WHERE scene.topics_tagged_through IS NULL
+                   OR message.sent_at > scene.topics_tagged_through
+                GROUP BY scene.uuid, scene.topics_tagged_through;
+            "#,
+        )
+        .fetch_all(&self.sqlx)
+        .await
+        .map_err(|err| format!("Error fetching untagged scenes: {}", err))?;
+
+        rows.into_iter()
+            .map(|row| {
+                let scene_uuid = row
+                    .try_get::<Uuid, _>("uuid")
+                    .map_err(|err| format!("Error reading uuid from row: {}", err))?;
+                let tagged_through = row
+                    .try_get::<Option<DateTime<Utc>>, _>("topics_tagged_through")
+                    .map_err(|err| {
+                        format!("Error reading topics_tagged_through from row: {}", err)
+                    })?;
+                let latest_message_at = row
+                    .try_get::<DateTime<Utc>, _>("latest_message_at")
+                    .map_err(|err| format!("Error reading latest_message_at from row: {}", err))?;
+
+                Ok(UntaggedScene {
+                    scene_uuid: SceneUuid::from_uuid(scene_uuid),
+                    tagged_through,
+                    latest_message_at,
+                })
+            })
+            .collect()
+    }
+
+    async fn record_scene_topics(
+        &self,
+        scene_uuid: &SceneUuid,
+        topics: &[TopicCount],
+        tagged_through: DateTime<Utc>,
+    ) -> Result<(), String> {
+        let mut tx = self
+            .sqlx
+            .begin()
+            .await
+            .map_err(|err| format!("Error starting transaction: {}", err))?;
+
+        for topic in topics {
+            sqlx::query(
+                r#"
+                    INSERT INTO topic (scene_uuid, topic, mentions, last_mentioned_at)
+                    VALUES ($1::UUID, $2::TEXT, $3::INT, $4::TIMESTAMPTZ)
+                    ON CONFLICT (scene_uuid, topic) DO UPDATE
+                    SET mentions = topic.mentions + EXCLUDED.mentions,
+                        last_mentioned_at = EXCLUDED.last_mentioned_at;
+                "#,
+            )
+            .bind(scene_uuid.to_uuid())
+            .bind(topic.topic.as_str())
+            .bind(topic.mentions)
+            .bind(tagged_through)
+            .execute(&mut *tx)
+            .await
+            .map_err(|err| format!("Error saving topic: {}", err))?;
+        }
+
+        sqlx::query(
+            r#"
+                UPDATE scene
+                SET topics_tagged_through = $2::TIMESTAMPTZ
+                WHERE uuid = $1::UUID;
+            "#,
+        )
+        .bind(scene_uuid.to_uuid())
+        .bind(tagged_through)
+        .execute(&mut *tx)
+        .await
+        .map_err(|err| format!("Error marking scene topics tagged: {}", err))?;
+
+        tx.commit()
+            .await
+            .map_err(|err| format!("Error committing transaction: {}", err))?;
+
+        Ok(())
+    }
+
+    async fn get_trending_topics(
+        &self,
+        since: Option<DateTime<Utc>>,
+        limit: i64,
+    ) -> Result<Vec<TrendingTopic>, String> {
+        let rows = sqlx::query(
+            r#"
+                SELECT
+                    topic,
+                    SUM(mentions)::BIGINT AS mentions,
+                    COUNT(*) AS scene_count,
+                    MAX(last_mentioned_at) AS last_mentioned_at
+                FROM topic
+                WHERE $1::TIMESTAMPTZ IS NULL
+                   OR last_mentioned_at >= $1::TIMESTAMPTZ
+                GROUP BY topic
+                ORDER BY mentions DESC, topic ASC
+                LIMIT $2::BIGINT;
+            "#,
+        )
+        .bind(since)
+        .bind(limit)
+        .fetch_all(&self.sqlx)
+        .await
+        .map_err(|err| format!("Error fetching trending topics: {}", err))?;
+
+        rows.into_iter()
+            .map(|row| {
+                let topic = row
+                    .try_get::<String, _>("topic")
+                    .map_err(|err| format!("Error reading topic from row: {}", err))?;
+                let mentions = row
+                    .try_get::<i64, _>("mentions")
+                    .map_err(|err| format!("Error reading mentions from row: {}", err))?;
+                let scene_count = row
+                    .try_get::<i64, _>("scene_count")
+                    .map_err(|err| format!("Error reading scene_count from row: {}", err))?;
+                let last_mentioned_at = row
+                    .try_get::<DateTime<Utc>, _>("last_mentioned_at")
+                    .map_err(|err| format!("Error reading last_mentioned_at from row: {}", err))?;
+
+                Ok(TrendingTopic {
+                    topic: Topic::from_string(topic),
+                    mentions,
+                    scene_count,
+                    last_mentioned_at,
+                })
+            })
+            .collect()
+    }
+
+    async fn get_topic_scenes(&self, topic: &Topic) -> Result<Vec<TopicScene>, String> {
+        let rows = sqlx::query(
+            r#"
+                SELECT
+                    scene.name,
+                    topic.mentions,
+                    topic.last_mentioned_at
+                FROM topic
+                JOIN scene ON scene.uuid = topic.scene_uuid
+                WHERE topic.topic = $1::TEXT
+                ORDER BY topic.last_mentioned_at DESC;
+            "#,
+        )
+        .bind(topic.as_str())
+        .fetch_all(&self.sqlx)
+        .await
+        .map_err(|err| format!("Error fetching scenes for topic: {}", err))?;
+
+        rows.into_iter()
+            .map(|row| {
+                let scene_name = row
+                    .try_get::<String, _>("name")
+                    .map_err(|err| format!("Error reading name from row: {}", err))?;
+                let mentions = row
+                    .try_get::<i32, _>("mentions")
+                    .map_err(|err| format!("Error reading mentions from row: {}", err))?;
+                let last_mentioned_at = row
+                    .try_get::<DateTime<Utc>, _>("last_mentioned_at")
+                    .map_err(|err| format!("Error reading last_mentioned_at from row: {}", err))?;
+
+                Ok(TopicScene {
+                    scene_name,
+                    mentions,
+                    last_mentioned_at,
+                })
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,80 @@
+use crate::capability::utterance::UtteranceCapability;
+use crate::domain::message_uuid::MessageUuid;
+use crate::domain::person_uuid::PersonUuid;
+use crate::domain::scene_uuid::SceneUuid;
+use crate::domain::utterance::{self, Utterance};
+use crate::worker::Worker;
+use chrono::{DateTime, Utc};
+use sqlx::Row;
+use uuid::Uuid;
+
+impl UtteranceCapability for Worker {
+    async fn get_scene_utterances(
+        &self,
+        scene_uuid: &SceneUuid,
+        limit: i64,
+        before: Option<DateTime<Utc>>,
+    ) -> Result<Vec<Utterance>, String> {
+        let rows = sqlx::query(
+            r#"
+                SELECT
+                    message.uuid,
+                    message.sender_person_uuid,
+                    person.name AS sender_name,
+                    message_content.body AS content,
+                    message.sent_at,
+                    (
+                        SELECT COUNT(*)
+                        FROM scene_message_recipient
+                        WHERE scene_message_recipient.message_uuid = message.uuid
+                    ) AS deliveries
+                FROM message
+                JOIN message_content ON message_content.hash = message.content_hash
+                LEFT JOIN person ON person.uuid = message.sender_person_uuid
+                WHERE message.scene_uuid = $1::UUID
+                  AND message.superseded_at IS NULL
+                  AND ($2::TIMESTAMPTZ IS NULL OR message.sent_at < $2::TIMESTAMPTZ)
+                ORDER BY message.sent_at DESC
+                LIMIT $3;
+            "#,
+        )
+        .bind(scene_uuid.to_uuid())
+        .bind(before)
+        .bind(limit)
+        .fetch_all(&self.sqlx)
+        .await
+        .map_err(|err| format!("Error fetching scene utterances: {}", err))?;
+
+        rows.into_iter()
+            .map(|row| {
+                let uuid: Uuid = row
+                    .try_get("uuid")
+                    .map_err(|err| format!("Error reading uuid from row: {}", err))?;
+                let sender_person_uuid: Option<Uuid> = row
+                    .try_get("sender_person_uuid")
+                    .map_err(|err| format!("Error reading sender_person_uuid from row: {}", err))?;
+                let sender_name: Option<String> = row
+                    .try_get("sender_name")
+                    .map_err(|err| format!("Error reading sender_name from row: {}", err))?;
+                let content: String = row
+                    .try_get("content")
+                    .map_err(|err| format!("Error reading content from row: {}", err))?;
+                let sent_at: DateTime<Utc> = row
+                    .try_get("sent_at")
+                    .map_err(|err| format!("Error reading sent_at from row: {}", err))?;
+                let deliveries: i64 = row
+                    .try_get("deliveries")
+                    .map_err(|err| format!("Error reading deliveries from row: {}", err))?;
+
+                Ok(Utterance {
+                    message_uuid: MessageUuid::from_uuid(uuid),
+                    speaker_person_uuid: sender_person_uuid.map(PersonUuid::from_uuid),
+                    speaker_name: utterance::speaker_name(sender_name.as_deref()),
+                    content,
+                    sent_at,
+                    deliveries,
+                })
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,126 @@
+use crate::capability::voice_exemplar::VoiceExemplarCapability;
+use crate::domain::person_uuid::PersonUuid;
+use crate::domain::voice_exemplar::{MarkedReactions, VoiceExemplarComparison, VoiceExemplarMode};
+use crate::worker::Worker;
+use sqlx::Row;
+
+impl VoiceExemplarCapability for Worker {
+    async fn get_voice_exemplars(&self, person_uuid: &PersonUuid) -> Result<Vec<String>, String> {
+        let row = sqlx::query(
+            r#"
+                SELECT voice_exemplars
+                FROM person
+                WHERE uuid = $1::UUID;
+            "#,
+        )
+        .bind(person_uuid.to_uuid())
+        .fetch_one(&self.sqlx)
+        .await
+        .map_err(|err| format!("Error fetching voice exemplars: {}", err))?;
+
+        row.try_get::<Vec<String>, _>("voice_exemplars")
+            .map_err(|err| format!("Error reading voice_exemplars from row: {}", err))
+    }
+
+    async fn set_voice_exemplars(
+        &self,
+        person_uuid: &PersonUuid,
+        exemplars: &[String],
+    ) -> Result<(), String> {
+        sqlx::query(
+            r#"
+                UPDATE person
+                SET voice_exemplars = $2::TEXT[]
+                WHERE uuid = $1::UUID;
+            "#,
+        )
+        .bind(person_uuid.to_uuid())
+        .bind(exemplars)
+        .execute(&self.sqlx)
+        .await
+        .map_err(|err| format!("Error updating voice exemplars: {}", err))?;
+
+        Ok(())
+    }
+
+    async fn get_voice_exemplar_mode(&self) -> Result<VoiceExemplarMode, String> {
+        let row = sqlx::query(
+            r#"
+                SELECT mode
+                FROM voice_exemplar_setting
+                WHERE id = TRUE;
+            "#,
+        )
+        .fetch_optional(&self.sqlx)
+        .await
+        .map_err(|err| format!("Error fetching voice exemplar mode: {}", err))?;
+
+        match row {
+            Some(row) => {
+                let mode = row
+                    .try_get::<String, _>("mode")
+                    .map_err(|err| format!("Error reading mode from row: {}", err))?;
+
+                VoiceExemplarMode::from_name(&mode)
+            }
+            None => Ok(VoiceExemplarMode::On),
+        }
+    }
+
+    async fn set_voice_exemplar_mode(&self, mode: VoiceExemplarMode) -> Result<(), String> {
+        sqlx::query(
+            r#"
+                INSERT INTO voice_exemplar_setting (id, mode)
+                VALUES (TRUE, $1::TEXT)
+                ON CONFLICT (id) DO UPDATE
+                SET mode = EXCLUDED.mode;
+            "#,
+        )
+        .bind(mode.to_name())
+        .execute(&self.sqlx)
+        .await
+        .map_err(|err| format!("Error updating voice exemplar mode: {}", err))?;
+
+        Ok(())
+    }
+
+    async fn get_voice_exemplar_comparison(&self) -> Result<VoiceExemplarComparison, String> {
+        let rows = sqlx::query(
+            r#"
+                SELECT used_voice_exemplars,
+                       COUNT(*) AS total,
+                       COUNT(*) FILTER (WHERE is_good) AS good
+                FROM reaction_context
+                WHERE used_voice_exemplars IS NOT NULL
+                GROUP BY used_voice_exemplars;
+            "#,
+        )
+        .fetch_all(&self.sqlx)
+        .await
+        .map_err(|err| format!("Error fetching voice exemplar comparison: {}", err))?;
+
+        let mut comparison = VoiceExemplarComparison::default();
+
+        for row in rows {
+            let used = row
+                .try_get::<bool, _>("used_voice_exemplars")
+                .map_err(|err| format!("Error reading used_voice_exemplars from row: {}", err))?;
+            let marked = MarkedReactions {
+                total: row
+                    .try_get::<i64, _>("total")
+                    .map_err(|err| format!("Error reading total from row: {}", err))?,
+                good: row
+                    .try_get::<i64, _>("good")
+                    .map_err(|err| format!("Error reading good from row: {}", err))?,
+            };
+
+            if used {
+                comparison.with_exemplars = marked;
+            } else {
+                comparison.without_exemplars = marked;
+            }
+        }
+
+        Ok(comparison)
+    }
+}
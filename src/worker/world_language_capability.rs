@@ -0,0 +1,52 @@
+use crate::capability::world_language::WorldLanguageCapability;
+use crate::nice_display::NiceDisplay;
+use crate::worker::Worker;
+use crate::world_language::{self, WorldLanguage};
+use sqlx::Row;
+
+impl WorldLanguageCapability for Worker {
+    async fn get_world_language(&self) -> Result<WorldLanguage, String> {
+        let row = sqlx::query(
+            r#"
+                SELECT language
+                FROM world_language_setting
+                WHERE id = TRUE;
+            "#,
+        )
+        .fetch_optional(&self.sqlx)
+        .await
+        .map_err(|err| format!("Error fetching the world language: {}", err))?;
+
+        let row = match row {
+            Some(row) => row,
+            None => {
+                return Err("The world language is missing from world_language_setting".to_string());
+            }
+        };
+
+        let code = row
+            .try_get::<String, _>("language")
+            .map_err(|err| format!("Error reading the world language: {}", err))?;
+
+        WorldLanguage::parse(code.as_str()).map_err(|err| err.message())
+    }
+
+    async fn set_world_language(&self, language: WorldLanguage) -> Result<(), String> {
+        sqlx::query(
+            r#"
+                UPDATE world_language_setting
+                SET language = $1::TEXT
+                WHERE id = TRUE;
+            "#,
+        )
+        .bind(language.code())
+        .execute(&self.sqlx)
+        .await
+        .map_err(|err| format!("Error updating the world language: {}", err))?;
+
+        // This process speaks it right away, other ones once they read it
+        world_language::set_world_language(language);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,117 @@
+use crate::capability::scene::SceneCapability;
+use crate::capability::world_map::WorldMapCapability;
+use crate::domain::person_name::PersonName;
+use crate::domain::world_map::{self, MapScene, SceneVisit, WorldMap};
+use crate::worker::Worker;
+use chrono::{DateTime, Utc};
+use sqlx::Row;
+use std::collections::{HashMap, HashSet};
+
+impl WorldMapCapability for Worker {
+    async fn get_world_map(&self) -> Result<WorldMap, String> {
+        let scenes = self.get_scenes().await?;
+
+        let participant_rows = sqlx::query(
+            r#"
+                SELECT scene_participant.scene_uuid, person.name
+                FROM scene_participant
+                JOIN person ON person.uuid = scene_participant.person_uuid
+                WHERE scene_participant.left_at IS NULL
+                ORDER BY person.name ASC
+            "#,
+        )
+        .fetch_all(&self.sqlx)
+        .await
+        .map_err(|err| format!("Error fetching people in scenes: {}", err))?;
+
+        let mut people_by_scene: HashMap<uuid::Uuid, Vec<PersonName>> = HashMap::new();
+        for row in participant_rows {
+            let scene_uuid = row
+                .try_get::<uuid::Uuid, _>("scene_uuid")
+                .map_err(|err| format!("Error reading participant scene uuid: {}", err))?;
+            let name = row
+                .try_get::<String, _>("name")
+                .map_err(|err| format!("Error reading participant name: {}", err))?;
+
+            people_by_scene
+                .entry(scene_uuid)
+                .or_default()
+                .push(PersonName::from_string(name));
+        }
+
+        let presence_rows = sqlx::query(
+            r#"
+                SELECT scene_uuid
+                FROM real_world_user_scene_presence
+            "#,
+        )
+        .fetch_all(&self.sqlx)
+        .await
+        .map_err(|err| format!("Error fetching real world user presence: {}", err))?;
+
+        let real_world_user_scenes = presence_rows
+            .into_iter()
+            .map(|row| {
+                row.try_get::<uuid::Uuid, _>("scene_uuid")
+                    .map_err(|err| format!("Error reading presence scene uuid: {}", err))
+            })
+            .collect::<Result<HashSet<uuid::Uuid>, String>>()?;
+
+        // Visits to ended scenes are kept so a detour through one does not
+        // look like a direct route between its neighbours.
+        let visit_rows = sqlx::query(
+            r#"
+                SELECT person_uuid, scene_uuid, joined_at
+                FROM scene_participant
+                WHERE scene_uuid IS NOT NULL
+            "#,
+        )
+        .fetch_all(&self.sqlx)
+        .await
+        .map_err(|err| format!("Error fetching scene visits: {}", err))?;
+
+        let visits = visit_rows
+            .into_iter()
+            .map(|row| {
+                Ok(SceneVisit {
+                    person_uuid: row
+                        .try_get::<uuid::Uuid, _>("person_uuid")
+                        .map_err(|err| format!("Error reading visit person uuid: {}", err))?,
+                    scene_uuid: row
+                        .try_get::<uuid::Uuid, _>("scene_uuid")
+                        .map_err(|err| format!("Error reading visit scene uuid: {}", err))?,
+                    joined_at: row
+                        .try_get::<DateTime<Utc>, _>("joined_at")
+                        .map_err(|err| format!("Error reading visit joined_at: {}", err))?,
+                })
+            })
+            .collect::<Result<Vec<SceneVisit>, String>>()?;
+
+        let active_scenes = scenes
+            .iter()
+            .map(|scene| scene.uuid.to_uuid())
+            .collect::<HashSet<uuid::Uuid>>();
+
+        let edges = world_map::travel_edges(visits)
+            .into_iter()
+            .filter(|edge| {
+                active_scenes.contains(&edge.scene_a) && active_scenes.contains(&edge.scene_b)
+            })
+            .collect();
+
+        let scenes = scenes
+            .into_iter()
+            .map(|scene| {
+                let uuid = scene.uuid.to_uuid();
+                MapScene {
+                    people: people_by_scene.remove(&uuid).unwrap_or_default(),
+                    real_world_user_present: real_world_user_scenes.contains(&uuid),
+                    scene_uuid: scene.uuid,
+                    name: scene.name,
+                }
+            })
+            .collect();
+
+        Ok(WorldMap { scenes, edges })
+    }
+}
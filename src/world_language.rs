@@ -0,0 +1,150 @@
+use crate::nice_display::NiceDisplay;
+use std::sync::RwLock;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WorldLanguage {
+    /// Stored in `world_language_setting`, like `es`.
+    code: &'static str,
+    /// In English, like `Spanish`, since it goes into English instructions.
+    name: &'static str,
+    /// A chrono pattern for dates, like `%d/%m/%Y`.
+    date_pattern: &'static str,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownLanguage {
+    value: String,
+}
+
+impl WorldLanguage {
+    /// Every language a world can be set to.
+    pub const ALL: [WorldLanguage; 8] = [
+        WorldLanguage::new("en", "English", "%Y-%m-%d"),
+        WorldLanguage::new("es", "Spanish", "%d/%m/%Y"),
+        WorldLanguage::new("fr", "French", "%d/%m/%Y"),
+        WorldLanguage::new("de", "German", "%d.%m.%Y"),
+        WorldLanguage::new("it", "Italian", "%d/%m/%Y"),
+        WorldLanguage::new("pt", "Portuguese", "%d/%m/%Y"),
+        WorldLanguage::new("nl", "Dutch", "%d-%m-%Y"),
+        WorldLanguage::new("ja", "Japanese", "%Y/%m/%d"),
+    ];
+
+    const fn new(code: &'static str, name: &'static str, date_pattern: &'static str) -> Self {
+        WorldLanguage {
+            code,
+            name,
+            date_pattern,
+        }
+    }
+
+    pub const fn english() -> Self {
+        WorldLanguage::ALL[0]
+    }
+
+    /// Takes a code or a name, like `es` or `Spanish`.
+    pub fn parse(value: &str) -> Result<Self, UnknownLanguage> {
+        let value = value.trim();
+
+        WorldLanguage::ALL
+            .into_iter()
+            .find(|language| {
+                language.code.eq_ignore_ascii_case(value)
+                    || language.name.eq_ignore_ascii_case(value)
+            })
+            .ok_or_else(|| UnknownLanguage {
+                value: value.to_string(),
+            })
+    }
+
+    pub fn code(&self) -> &'static str {
+        self.code
+    }
+
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    pub fn date_pattern(&self) -> &'static str {
+        self.date_pattern
+    }
+
+    pub fn is_english(&self) -> bool {
+        *self == WorldLanguage::english()
+    }
+
+    /// Added to every completion, so prompts written in English still get
+    /// answers in the world's language. `None` for English.
+    pub fn to_instruction(self) -> Option<String> {
+        if self.is_english() {
+            return None;
+        }
+
+        Some(format!(
+            "This world speaks {language}. Write everything you produce in {language}: dialogue, thoughts, memories, summaries, descriptions and any other prose, even though these instructions are in English. Keep JSON keys, tool and parameter names, and any exact labels the instructions ask for as they are given.",
+            language = self.name()
+        ))
+    }
+}
+
+impl NiceDisplay for UnknownLanguage {
+    fn message(&self) -> String {
+        let known = WorldLanguage::ALL
+            .iter()
+            .map(|language| format!("{} ({})", language.code, language.name))
+            .collect::<Vec<String>>()
+            .join(", ");
+
+        format!(
+            "\"{}\" is not a language the world can speak. It can be one of {}",
+            self.value, known
+        )
+    }
+}
+
+/// The language of the world this process serves. Every completion and
+/// every rendered date looks at it, so it is kept here rather than read from
+/// `world_language_setting` each time. The worker sets it when it starts and
+/// when the setting is saved, and the job runner reads it again between jobs.
+static CURRENT: RwLock<WorldLanguage> = RwLock::new(WorldLanguage::english());
+
+pub fn world_language() -> WorldLanguage {
+    match CURRENT.read() {
+        Ok(language) => *language,
+        Err(poisoned) => *poisoned.into_inner(),
+    }
+}
+
+pub fn set_world_language(language: WorldLanguage) {
+    match CURRENT.write() {
+        Ok(mut current) => *current = language,
+        Err(poisoned) => *poisoned.into_inner() = language,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_languages_parse_by_code_or_name() {
+        let spanish = WorldLanguage::parse(" ES ").unwrap();
+        assert_eq!(Ok(spanish), WorldLanguage::parse("spanish"));
+        assert_eq!(spanish.code(), "es");
+        assert_eq!(spanish.name(), "Spanish");
+        assert_eq!(spanish.date_pattern(), "%d/%m/%Y");
+        assert!(spanish.to_instruction().unwrap().contains("in Spanish"));
+
+        assert_eq!(WorldLanguage::english().to_instruction(), None);
+    }
+
+    #[test]
+    fn test_unknown_languages_are_rejected() {
+        let welsh = WorldLanguage::parse("Welsh");
+
+        match welsh {
+            Err(err) => assert!(err.message().starts_with("\"Welsh\" is not a language")),
+            Ok(_) => panic!("expected Welsh to be rejected"),
+        }
+        assert!(WorldLanguage::parse("").is_err());
+    }
+}
@@ -1,20 +1,31 @@
 use arizona2::capability::job::JobCapability;
 use arizona2::capability::message::MessageCapability;
+use arizona2::capability::message_revision::MessageRevisionCapability;
 use arizona2::capability::person::{NewPerson, PersonCapability};
 use arizona2::capability::scene::{NewScene, SceneCapability};
 use arizona2::db;
+use arizona2::domain::job::process_message::ProcessMessageJob;
 use arizona2::domain::job::{JobKind, JobStatus};
+use arizona2::domain::job_uuid::JobUuid;
 use arizona2::domain::logger::{Level, Logger};
 use arizona2::domain::message::MessageSender;
+use arizona2::domain::message_urgency::MessageUrgency;
+use arizona2::domain::message_uuid::MessageUuid;
 use arizona2::domain::person_name::PersonName;
 use arizona2::domain::person_uuid::PersonUuid;
+use arizona2::domain::scene_uuid::SceneUuid;
 use arizona2::job_runner::{run_one_job, RunNextJobResult};
 use arizona2::nice_display::NiceDisplay;
 use arizona2::open_ai_key::OpenAiKey;
-use arizona2::worker::Worker;
+use arizona2::worker::{PoolSizes, Worker};
 use serial_test::serial;
 use sqlx::Row;
 
+/// Like the `_setting` tables, these hold rows the migrations seed, so they
+/// are not emptied between tests. Sending a message fails without its
+/// content scrub setting, for one.
+const SEEDED_TABLES: [&str; 3] = ["run_budget", "llm_price", "action_budget"];
+
 struct TestContext {
     worker: Worker,
 }
@@ -30,20 +41,19 @@ impl TestContext {
                 err.message()
             )
         });
-        let database_url = format!(
-            "postgres://{}:{}@{}/arizona2_test",
-            config.user, config.password, config.host
-        );
+        let database_name = config.test_database_name();
 
         let worker = Worker::from_connection_string(
             logger,
-            &database_url,
-            OpenAiKey::from_string("test-key".to_string()),
+            config.url(database_name.as_str()).as_str(),
+            Some(OpenAiKey::from_string("test-key".to_string())),
+            PoolSizes::default().task,
         )
         .await
         .unwrap_or_else(|err| {
             panic!(
-                "failed to connect to integration test database 'arizona2_test': {}",
+                "failed to connect to integration test database '{}': {}",
+                database_name,
                 err.message()
             )
         });
@@ -64,9 +74,12 @@ async fn reset_database(worker: &Worker) {
             FROM pg_tables
             WHERE schemaname = 'public'
               AND tablename <> '_sqlx_migrations'
+              AND tablename NOT LIKE '%\_setting'
+              AND tablename <> ALL($1::TEXT[])
             ORDER BY tablename ASC
         "#,
     )
+    .bind(SEEDED_TABLES)
     .fetch_all(&worker.sqlx)
     .await
     .expect("failed to list public tables");
@@ -115,6 +128,55 @@ fn test_person(name: &str) -> NewPerson {
     }
 }
 
+/// A real world user's message to a scene with one person in it, and the
+/// queued job for that person to react to it.
+async fn send_pending_user_message(
+    worker: &Worker,
+) -> (SceneUuid, PersonUuid, MessageUuid, JobUuid) {
+    let recipient = test_person("Harper");
+
+    worker
+        .create_person(NewPerson {
+            person_uuid: recipient.person_uuid.clone(),
+            person_name: recipient.person_name.clone(),
+        })
+        .await
+        .expect("failed to create recipient");
+
+    let scene_uuid = worker
+        .create_scene(NewScene {
+            name: "Porch".to_string(),
+            description: "A creaky porch with two rocking chairs.".to_string(),
+        })
+        .await
+        .expect("failed to create porch scene");
+
+    let message_uuid = worker
+        .send_scene_message(
+            MessageSender::RealWorldUser,
+            scene_uuid.clone(),
+            "is anyone out here".to_string(),
+        )
+        .await
+        .expect("failed to send message");
+    worker
+        .add_scene_message_recipients(&message_uuid, vec![recipient.person_uuid.clone()])
+        .await
+        .expect("failed to add recipient");
+
+    let job_uuid = worker
+        .enqueue_job(JobKind::ProcessMessage(ProcessMessageJob {
+            message_uuid: message_uuid.clone(),
+            recipient_person_uuid: recipient.person_uuid.clone(),
+            run_at_active_ms: None,
+            urgency: MessageUrgency::Background,
+        }))
+        .await
+        .expect("failed to enqueue reaction");
+
+    (scene_uuid, recipient.person_uuid, message_uuid, job_uuid)
+}
+
 #[tokio::test]
 #[serial]
 #[ignore = "requires a configured postgres integration database"]
@@ -542,3 +604,92 @@ async fn deleted_jobs_are_hidden_from_active_queries() {
         .expect("failed to fetch recent jobs after delete");
     assert!(recent_jobs.is_empty());
 }
+
+#[tokio::test]
+#[serial]
+#[ignore = "requires a configured postgres integration database"]
+async fn retracting_a_pending_message_cancels_its_reactions() {
+    let ctx = TestContext::new().await;
+    let worker = ctx.worker();
+    let (scene_uuid, recipient_uuid, message_uuid, job_uuid) =
+        send_pending_user_message(worker).await;
+
+    assert!(worker
+        .retract_pending_message(&message_uuid)
+        .await
+        .expect("failed to retract message"));
+
+    let superseded = worker
+        .get_superseded_message_uuids(vec![message_uuid.clone()])
+        .await
+        .expect("failed to fetch superseded messages");
+    assert!(superseded.contains(&message_uuid));
+
+    let cancelled_job = worker
+        .get_job_by_uuid(&job_uuid)
+        .await
+        .expect("failed to fetch cancelled job");
+    assert!(cancelled_job.is_none());
+
+    let unhandled = worker
+        .get_unhandled_scene_messages_for_person(&recipient_uuid, &scene_uuid)
+        .await
+        .expect("failed to fetch unhandled messages");
+    assert!(unhandled.is_empty());
+
+    let pending = worker
+        .get_pending_real_world_user_messages(&scene_uuid, 10)
+        .await
+        .expect("failed to fetch pending messages");
+    assert!(pending.is_empty());
+
+    assert!(!worker
+        .retract_pending_message(&message_uuid)
+        .await
+        .expect("failed to retract message a second time"));
+}
+
+#[tokio::test]
+#[serial]
+#[ignore = "requires a configured postgres integration database"]
+async fn retracting_is_refused_once_a_reaction_has_been_popped() {
+    let ctx = TestContext::new().await;
+    let worker = ctx.worker();
+    let (scene_uuid, recipient_uuid, message_uuid, job_uuid) =
+        send_pending_user_message(worker).await;
+
+    // Sending the message also queued an outbox dispatch, which can come first
+    let mut popped_reaction = false;
+    while let Some(popped_job) = worker.pop_next_job(0).await.expect("failed to pop job") {
+        if popped_job.uuid.to_uuid() == job_uuid.to_uuid() {
+            popped_reaction = true;
+            break;
+        }
+    }
+    assert!(popped_reaction);
+
+    assert!(!worker
+        .retract_pending_message(&message_uuid)
+        .await
+        .expect("failed to retract message"));
+
+    let superseded = worker
+        .get_superseded_message_uuids(vec![message_uuid.clone()])
+        .await
+        .expect("failed to fetch superseded messages");
+    assert!(superseded.is_empty());
+
+    let running_job = worker
+        .get_job_by_uuid(&job_uuid)
+        .await
+        .expect("failed to fetch running job")
+        .expect("expected the running job to be kept");
+    assert_eq!(running_job.status(), JobStatus::InProgress);
+
+    let unhandled = worker
+        .get_unhandled_scene_messages_for_person(&recipient_uuid, &scene_uuid)
+        .await
+        .expect("failed to fetch unhandled messages");
+    assert_eq!(unhandled.len(), 1);
+    assert_eq!(unhandled[0].uuid.to_uuid(), message_uuid.to_uuid());
+}